@@ -0,0 +1,326 @@
+use bitcoin::absolute::LockTime;
+use bitcoin::blockdata::opcodes::all::{OP_CHECKSIG, OP_ENDIF, OP_IF};
+use bitcoin::blockdata::opcodes::OP_FALSE;
+use bitcoin::blockdata::script;
+use bitcoin::hashes::Hash;
+use bitcoin::key::UntweakedKeyPair;
+use bitcoin::script::PushBytesBuf;
+use bitcoin::secp256k1::{self, rand, Secp256k1, XOnlyPublicKey};
+use bitcoin::sighash::{Prevouts, SighashCache};
+use bitcoin::taproot::{ControlBlock, LeafVersion, TapLeafHash, TaprootBuilder, TaprootSpendInfo};
+use bitcoin::{
+    Address, Amount, Network, OutPoint, ScriptBuf, Sequence, TapSighashType, Transaction, TxIn,
+    TxOut, Txid, Witness,
+};
+
+use super::{BODY_TAG, ENVELOPE_TAG, NONCE_TAG, PUBLICKEY_TAG, ROLLUP_NAME_TAG, SIGNATURE_TAG};
+use crate::spec::utxo::UTXO;
+
+/// The largest data push allowed by the standardness rules.
+const MAX_PUSH_SIZE: usize = 520;
+/// The value of the reveal output, set to the dust limit.
+pub const REVEAL_OUTPUT_AMOUNT: u64 = 546;
+/// The virtual size of the witness of a P2WPKH input, rounded up.
+const P2WPKH_WITNESS_VSIZE: u64 = 27;
+/// Reveal transactions are ground for at most this many nonces before giving up.
+const MAX_NONCE: u64 = 1 << 24;
+
+/// The two transactions that publish a blob.
+#[derive(Debug, Clone)]
+pub struct InscriptionTxs {
+    /// Funds the taproot output committing to the envelope. Unsigned: it spends wallet outputs.
+    pub commit_tx: Transaction,
+    /// Spends the commit output through the script path, revealing the envelope. Fully signed.
+    pub reveal_tx: Transaction,
+}
+
+/// Builds the tapscript of a reveal transaction. The layout is described in [`crate::helpers`].
+pub fn build_reveal_script(
+    spending_key: &XOnlyPublicKey,
+    rollup_name: &str,
+    body: &[u8],
+    signature: &[u8],
+    signer_public_key: &[u8],
+    nonce: u64,
+) -> anyhow::Result<ScriptBuf> {
+    let mut builder = script::Builder::new()
+        .push_x_only_key(spending_key)
+        .push_opcode(OP_CHECKSIG)
+        .push_opcode(OP_FALSE)
+        .push_opcode(OP_IF)
+        .push_slice(push_bytes(ENVELOPE_TAG)?)
+        .push_slice(push_bytes(ROLLUP_NAME_TAG)?)
+        .push_slice(push_bytes(rollup_name.as_bytes())?)
+        .push_slice(push_bytes(SIGNATURE_TAG)?)
+        .push_slice(push_bytes(signature)?)
+        .push_slice(push_bytes(PUBLICKEY_TAG)?)
+        .push_slice(push_bytes(signer_public_key)?)
+        .push_slice(push_bytes(NONCE_TAG)?)
+        .push_slice(push_bytes(&nonce.to_le_bytes())?)
+        .push_slice(push_bytes(BODY_TAG)?);
+    for chunk in body.chunks(MAX_PUSH_SIZE) {
+        builder = builder.push_slice(push_bytes(chunk)?);
+    }
+
+    Ok(builder.push_opcode(OP_ENDIF).into_script())
+}
+
+/// Builds the commit and reveal transactions publishing `body`, grinding the envelope nonce
+/// until the wtxid of the reveal transaction starts with `reveal_wtxid_prefix`.
+///
+/// The commit transaction is funded from `utxos` and stays unsigned. Since its txid is known
+/// before signing, the utxos must be segwit outputs.
+#[allow(clippy::too_many_arguments)]
+pub fn create_inscription_transactions(
+    rollup_name: &str,
+    body: &[u8],
+    signature: &[u8],
+    signer_public_key: &[u8],
+    utxos: Vec<UTXO>,
+    change_address: Address,
+    fee_rate: f64,
+    network: Network,
+    reveal_wtxid_prefix: &[u8],
+) -> anyhow::Result<InscriptionTxs> {
+    let secp = Secp256k1::new();
+    let key_pair = UntweakedKeyPair::new(&secp, &mut rand::thread_rng());
+    let (spending_key, _) = XOnlyPublicKey::from_keypair(&key_pair);
+
+    // The nonce has a fixed size, so every candidate reveal transaction has the same fee
+    let reveal_fee = {
+        let reveal_script = build_reveal_script(
+            &spending_key,
+            rollup_name,
+            body,
+            signature,
+            signer_public_key,
+            0,
+        )?;
+        let (_, control_block) = build_taproot_tree(&secp, spending_key, &reveal_script)?;
+        let reveal_tx = build_reveal_transaction(
+            &reveal_script,
+            &control_block,
+            Txid::all_zeros(),
+            &change_address,
+        );
+        (reveal_tx.vsize() as f64 * fee_rate).ceil() as u64
+    };
+    let commit_value = REVEAL_OUTPUT_AMOUNT + reveal_fee;
+    let (inputs, change) = select_inputs(utxos, commit_value, fee_rate)?;
+
+    for nonce in 0..MAX_NONCE {
+        let reveal_script = build_reveal_script(
+            &spending_key,
+            rollup_name,
+            body,
+            signature,
+            signer_public_key,
+            nonce,
+        )?;
+        let (spend_info, control_block) = build_taproot_tree(&secp, spending_key, &reveal_script)?;
+        let commit_address = Address::p2tr(&secp, spending_key, spend_info.merkle_root(), network);
+
+        let mut output = vec![TxOut {
+            value: commit_value,
+            script_pubkey: commit_address.script_pubkey(),
+        }];
+        if change > REVEAL_OUTPUT_AMOUNT {
+            output.push(TxOut {
+                value: change,
+                script_pubkey: change_address.script_pubkey(),
+            });
+        }
+        let commit_tx = Transaction {
+            version: 2,
+            lock_time: LockTime::ZERO,
+            input: inputs.clone(),
+            output,
+        };
+
+        let mut reveal_tx = build_reveal_transaction(
+            &reveal_script,
+            &control_block,
+            commit_tx.txid(),
+            &change_address,
+        );
+        let leaf_hash = TapLeafHash::from_script(&reveal_script, LeafVersion::TapScript);
+
+        let sighash = SighashCache::new(&reveal_tx).taproot_script_spend_signature_hash(
+            0,
+            &Prevouts::All(&[commit_tx.output[0].clone()]),
+            leaf_hash,
+            TapSighashType::Default,
+        )?;
+        let message = secp256k1::Message::from_slice(sighash.as_byte_array())?;
+        let signature = bitcoin::taproot::Signature {
+            sig: secp.sign_schnorr(&message, &key_pair),
+            hash_ty: TapSighashType::Default,
+        };
+        let mut witness = Witness::new();
+        witness.push(signature.to_vec());
+        witness.push(reveal_script.as_bytes());
+        witness.push(control_block.serialize());
+        reveal_tx.input[0].witness = witness;
+
+        if reveal_tx
+            .wtxid()
+            .to_byte_array()
+            .starts_with(reveal_wtxid_prefix)
+        {
+            return Ok(InscriptionTxs {
+                commit_tx,
+                reveal_tx,
+            });
+        }
+    }
+
+    anyhow::bail!("Could not find a nonce matching the reveal wtxid prefix")
+}
+
+/// Builds a child transaction bumping the fee of a stuck inscription (child pays for parent).
+///
+/// The child spends the reveal output, which pays to the wallet, along with wallet `utxos`, so
+/// that the package of the commit, reveal and child transactions pays `fee_rate` sat/vB. The fee
+/// already paid by the commit transaction isn't known here and is ignored, so the package
+/// overpays slightly. The child signals RBF: a later bump replaces it with a higher paying one.
+/// It's unsigned, since all its inputs are wallet outputs.
+pub fn build_cpfp_transaction(
+    commit_tx: &Transaction,
+    reveal_tx: &Transaction,
+    utxos: Vec<UTXO>,
+    change_address: &Address,
+    fee_rate: f64,
+) -> anyhow::Result<Transaction> {
+    // The commit transaction is unsigned, so the witnesses of its inputs are missing
+    let parents_vsize = commit_tx.vsize() as u64
+        + commit_tx.input.len() as u64 * P2WPKH_WITNESS_VSIZE
+        + reveal_tx.vsize() as u64;
+    let reveal_fee = commit_tx.output[0].value - REVEAL_OUTPUT_AMOUNT;
+    let parents_fee = ((parents_vsize as f64 * fee_rate).ceil() as u64).saturating_sub(reveal_fee);
+    let reveal_input_fee = ((41 + P2WPKH_WITNESS_VSIZE) as f64 * fee_rate).ceil() as u64;
+
+    // The reveal output is added to the change, which keeps it above the dust limit
+    let (mut inputs, change) = select_inputs(utxos, parents_fee + reveal_input_fee, fee_rate)?;
+    inputs.insert(
+        0,
+        TxIn {
+            previous_output: OutPoint {
+                txid: reveal_tx.txid(),
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        },
+    );
+
+    Ok(Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: inputs,
+        output: vec![TxOut {
+            value: change + REVEAL_OUTPUT_AMOUNT,
+            script_pubkey: change_address.script_pubkey(),
+        }],
+    })
+}
+
+/// Builds the taproot tree holding only `reveal_script`, and the control block spending it.
+fn build_taproot_tree(
+    secp: &Secp256k1<secp256k1::All>,
+    spending_key: XOnlyPublicKey,
+    reveal_script: &ScriptBuf,
+) -> anyhow::Result<(TaprootSpendInfo, ControlBlock)> {
+    let spend_info = TaprootBuilder::new()
+        .add_leaf(0, reveal_script.clone())
+        .map_err(|e| anyhow::anyhow!("Could not add the reveal script leaf: {:?}", e))?
+        .finalize(secp, spending_key)
+        .map_err(|_| anyhow::anyhow!("Could not finalize the taproot tree"))?;
+    let control_block = spend_info
+        .control_block(&(reveal_script.clone(), LeafVersion::TapScript))
+        .ok_or_else(|| anyhow::anyhow!("Could not create the control block"))?;
+
+    Ok((spend_info, control_block))
+}
+
+/// Builds a reveal transaction spending the first output of the commit transaction to
+/// `recipient`. The witness holds a dummy signature, so the transaction already has its final size.
+fn build_reveal_transaction(
+    reveal_script: &ScriptBuf,
+    control_block: &ControlBlock,
+    commit_txid: Txid,
+    recipient: &Address,
+) -> Transaction {
+    let mut witness = Witness::new();
+    // A schnorr signature with the default sighash type is 64 bytes
+    witness.push([0u8; 64]);
+    witness.push(reveal_script.as_bytes());
+    witness.push(control_block.serialize());
+
+    Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint {
+                txid: commit_txid,
+                vout: 0,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness,
+        }],
+        output: vec![TxOut {
+            value: REVEAL_OUTPUT_AMOUNT,
+            script_pubkey: recipient.script_pubkey(),
+        }],
+    }
+}
+
+/// Picks the largest utxos until they cover `amount` plus the commit transaction fee.
+/// Returns the inputs and the change left after paying the fee.
+fn select_inputs(
+    mut utxos: Vec<UTXO>,
+    amount: u64,
+    fee_rate: f64,
+) -> anyhow::Result<(Vec<TxIn>, u64)> {
+    utxos.retain(|utxo| utxo.spendable && utxo.solvable);
+    utxos.sort_by(|a, b| b.amount.total_cmp(&a.amount));
+
+    let mut inputs = Vec::new();
+    let mut total = 0u64;
+    for utxo in utxos {
+        inputs.push(TxIn {
+            previous_output: OutPoint {
+                txid: utxo.txid.parse()?,
+                vout: utxo.vout,
+            },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        });
+        total += Amount::from_btc(utxo.amount)?.to_sat();
+
+        let fee = estimate_commit_fee(inputs.len(), fee_rate);
+        if total >= amount + fee {
+            return Ok((inputs, total - amount - fee));
+        }
+    }
+
+    anyhow::bail!(
+        "Not enough funds to publish the blob: {} sats available, at least {} needed",
+        total,
+        amount
+    )
+}
+
+/// Estimates the fee of a commit transaction with `num_inputs` P2WPKH inputs, a taproot output
+/// and a change output.
+fn estimate_commit_fee(num_inputs: usize, fee_rate: f64) -> u64 {
+    // 10 vbytes of overhead, 41 for each input without witness, 43 and 31 for the outputs
+    let vsize = 10 + 84 + num_inputs as u64 * (41 + P2WPKH_WITNESS_VSIZE);
+    (vsize as f64 * fee_rate).ceil() as u64
+}
+
+fn push_bytes(data: &[u8]) -> anyhow::Result<PushBytesBuf> {
+    PushBytesBuf::try_from(data.to_vec()).map_err(|_| anyhow::anyhow!("Data push is too large"))
+}
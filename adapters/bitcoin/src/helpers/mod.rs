@@ -0,0 +1,36 @@
+//! Encoding and decoding of rollup blobs as taproot inscriptions.
+//!
+//! A blob is revealed in the tapscript of the first input of a reveal transaction:
+//!
+//! ```text
+//! <x-only public key> OP_CHECKSIG
+//! OP_FALSE
+//! OP_IF
+//!   "sov"
+//!   1 <rollup name>
+//!   2 <ECDSA signature over sha256(body)>
+//!   3 <signer public key>
+//!   4 <nonce>
+//!   0 <body chunk> <body chunk> ...
+//! OP_ENDIF
+//! ```
+//!
+//! The envelope is never executed, so it costs only witness space. The nonce is ground
+//! until the wtxid of the reveal transaction starts with the rollup's reveal prefix.
+
+#[cfg(feature = "native")]
+pub mod builders;
+pub mod parsers;
+
+/// Marks the start of a rollup envelope.
+pub const ENVELOPE_TAG: &[u8] = b"sov";
+/// Precedes the name of the rollup the blob belongs to.
+pub const ROLLUP_NAME_TAG: &[u8] = &[1];
+/// Precedes the signature of the blob.
+pub const SIGNATURE_TAG: &[u8] = &[2];
+/// Precedes the public key that signed the blob.
+pub const PUBLICKEY_TAG: &[u8] = &[3];
+/// Precedes the nonce used to grind the reveal wtxid.
+pub const NONCE_TAG: &[u8] = &[4];
+/// Precedes the blob body, which runs until `OP_ENDIF`.
+pub const BODY_TAG: &[u8] = &[];
@@ -0,0 +1,214 @@
+use bitcoin::blockdata::opcodes::all::{OP_CHECKSIG, OP_ENDIF, OP_IF};
+use bitcoin::blockdata::script::Instruction;
+use bitcoin::secp256k1::{ecdsa, Message, PublicKey, Secp256k1};
+use bitcoin::{Script, Transaction};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{BODY_TAG, ENVELOPE_TAG, NONCE_TAG, PUBLICKEY_TAG, ROLLUP_NAME_TAG, SIGNATURE_TAG};
+
+/// A rollup blob read back from a reveal transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedInscription {
+    /// The name of the rollup the blob was sent to.
+    pub rollup_name: String,
+    /// The blob data.
+    pub body: Vec<u8>,
+    /// The compact ECDSA signature over `sha256(body)`.
+    pub signature: Vec<u8>,
+    /// The compressed public key which produced the signature.
+    pub public_key: Vec<u8>,
+}
+
+impl ParsedInscription {
+    /// Checks that the inscription was signed by the public key it carries.
+    pub fn verify_signature(&self) -> bool {
+        let hash: [u8; 32] = Sha256::digest(&self.body).into();
+        let (Ok(message), Ok(signature), Ok(public_key)) = (
+            Message::from_slice(&hash),
+            ecdsa::Signature::from_compact(&self.signature),
+            PublicKey::from_slice(&self.public_key),
+        ) else {
+            return false;
+        };
+
+        Secp256k1::verification_only()
+            .verify_ecdsa(&message, &signature, &public_key)
+            .is_ok()
+    }
+}
+
+/// Reasons a transaction does not carry a valid rollup envelope.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParserError {
+    #[error("the transaction does not spend a taproot script path")]
+    NotTaprootScriptSpend,
+    #[error("the script does not contain a rollup envelope")]
+    InvalidEnvelope,
+    #[error("the envelope belongs to another rollup")]
+    WrongRollupName,
+    #[error("the envelope is missing the {0} field")]
+    MissingField(&'static str),
+    #[error("the envelope contains an unknown tag")]
+    UnknownTag,
+    #[error("the script is malformed: {0}")]
+    MalformedScript(bitcoin::blockdata::script::Error),
+}
+
+impl From<bitcoin::blockdata::script::Error> for ParserError {
+    fn from(value: bitcoin::blockdata::script::Error) -> Self {
+        ParserError::MalformedScript(value)
+    }
+}
+
+/// Parses the rollup envelope revealed by the first input of `tx`.
+pub fn parse_transaction(
+    tx: &Transaction,
+    rollup_name: &str,
+) -> Result<ParsedInscription, ParserError> {
+    let script = tx
+        .input
+        .first()
+        .and_then(|input| input.witness.tapscript())
+        .ok_or(ParserError::NotTaprootScriptSpend)?;
+    parse_relevant_inscription(script, rollup_name)
+}
+
+/// Parses a rollup envelope out of a reveal tapscript. Fails if the envelope is malformed
+/// or addressed to a rollup other than `rollup_name`.
+pub fn parse_relevant_inscription(
+    script: &Script,
+    rollup_name: &str,
+) -> Result<ParsedInscription, ParserError> {
+    let mut instructions = script.instructions();
+
+    // The key spending the script path, then the never executed envelope
+    match instructions.next().transpose()? {
+        Some(Instruction::PushBytes(key)) if key.len() == 32 => {}
+        _ => return Err(ParserError::InvalidEnvelope),
+    }
+    expect_op(instructions.next().transpose()?, OP_CHECKSIG)?;
+    expect_push(instructions.next().transpose()?, &[])?;
+    expect_op(instructions.next().transpose()?, OP_IF)?;
+    expect_push(instructions.next().transpose()?, ENVELOPE_TAG)?;
+
+    let mut name = None;
+    let mut signature = None;
+    let mut public_key = None;
+    let mut body = None;
+
+    while body.is_none() {
+        let tag = match instructions.next().transpose()? {
+            Some(Instruction::PushBytes(tag)) => tag.as_bytes(),
+            _ => return Err(ParserError::MissingField("body")),
+        };
+
+        if tag == BODY_TAG {
+            let mut data = Vec::new();
+            loop {
+                match instructions.next().transpose()? {
+                    Some(Instruction::PushBytes(chunk)) => data.extend_from_slice(chunk.as_bytes()),
+                    Some(Instruction::Op(op)) if op == OP_ENDIF => break,
+                    _ => return Err(ParserError::InvalidEnvelope),
+                }
+            }
+            body = Some(data);
+            continue;
+        }
+
+        let value = match instructions.next().transpose()? {
+            Some(Instruction::PushBytes(value)) => value.as_bytes().to_vec(),
+            _ => return Err(ParserError::InvalidEnvelope),
+        };
+        match tag {
+            t if t == ROLLUP_NAME_TAG => name = Some(value),
+            t if t == SIGNATURE_TAG => signature = Some(value),
+            t if t == PUBLICKEY_TAG => public_key = Some(value),
+            // The nonce only matters for the wtxid
+            t if t == NONCE_TAG => {}
+            _ => return Err(ParserError::UnknownTag),
+        }
+    }
+
+    let name = name.ok_or(ParserError::MissingField("rollup name"))?;
+    if name != rollup_name.as_bytes() {
+        return Err(ParserError::WrongRollupName);
+    }
+
+    Ok(ParsedInscription {
+        rollup_name: rollup_name.to_string(),
+        body: body.unwrap_or_default(),
+        signature: signature.ok_or(ParserError::MissingField("signature"))?,
+        public_key: public_key.ok_or(ParserError::MissingField("public key"))?,
+    })
+}
+
+fn expect_op(
+    instruction: Option<Instruction<'_>>,
+    expected: bitcoin::blockdata::opcodes::Opcode,
+) -> Result<(), ParserError> {
+    match instruction {
+        Some(Instruction::Op(op)) if op == expected => Ok(()),
+        _ => Err(ParserError::InvalidEnvelope),
+    }
+}
+
+fn expect_push(instruction: Option<Instruction<'_>>, expected: &[u8]) -> Result<(), ParserError> {
+    match instruction {
+        Some(Instruction::PushBytes(bytes)) if bytes.as_bytes() == expected => Ok(()),
+        _ => Err(ParserError::InvalidEnvelope),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::secp256k1::SecretKey;
+
+    use super::*;
+    use crate::helpers::builders::build_reveal_script;
+
+    #[test]
+    fn reveal_script_roundtrip() {
+        let secp = Secp256k1::new();
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let (spending_key, _) = key.x_only_public_key(&secp);
+        let body = vec![42u8; 1500];
+        let hash: [u8; 32] = Sha256::digest(&body).into();
+        let signature = secp
+            .sign_ecdsa(&Message::from_slice(&hash).unwrap(), &key)
+            .serialize_compact();
+        let public_key = PublicKey::from_secret_key(&secp, &key).serialize();
+
+        let script =
+            build_reveal_script(&spending_key, "sov-btc", &body, &signature, &public_key, 17)
+                .unwrap();
+
+        let inscription = parse_relevant_inscription(&script, "sov-btc").unwrap();
+        assert_eq!(inscription.body, body);
+        assert_eq!(inscription.public_key, public_key.to_vec());
+        assert!(inscription.verify_signature());
+
+        assert_eq!(
+            parse_relevant_inscription(&script, "other-rollup"),
+            Err(ParserError::WrongRollupName)
+        );
+    }
+
+    #[test]
+    fn tampered_body_fails_signature_check() {
+        let secp = Secp256k1::new();
+        let key = SecretKey::from_slice(&[7; 32]).unwrap();
+        let hash: [u8; 32] = Sha256::digest([1, 2, 3]).into();
+        let signature = secp
+            .sign_ecdsa(&Message::from_slice(&hash).unwrap(), &key)
+            .serialize_compact();
+
+        let inscription = ParsedInscription {
+            rollup_name: "sov-btc".to_string(),
+            body: vec![1, 2, 4],
+            signature: signature.to_vec(),
+            public_key: PublicKey::from_secret_key(&secp, &key).serialize().to_vec(),
+        };
+        assert!(!inscription.verify_signature());
+    }
+}
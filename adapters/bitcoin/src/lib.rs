@@ -0,0 +1,14 @@
+#![doc = include_str!("../README.md")]
+
+pub mod helpers;
+#[cfg(feature = "native")]
+pub mod rpc;
+#[cfg(feature = "native")]
+mod service;
+pub mod spec;
+pub mod verifier;
+
+#[cfg(feature = "native")]
+pub use service::{BitcoinHeaderStream, BitcoinService, BitcoinServiceConfig};
+pub use spec::{BitcoinSpec, RollupParams};
+pub use verifier::BitcoinVerifier;
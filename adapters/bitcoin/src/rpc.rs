@@ -0,0 +1,158 @@
+use bitcoin::consensus::encode;
+use bitcoin::{Block, BlockHash, Transaction, Txid};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::spec::utxo::UTXO;
+
+/// A minimal JSON-RPC client for `bitcoind`, covering what the DA service needs.
+#[derive(Debug, Clone)]
+pub struct BitcoinNode {
+    url: String,
+    username: String,
+    password: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct Response<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize, Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct SignedTransaction {
+    hex: String,
+    complete: bool,
+}
+
+#[derive(Deserialize)]
+struct WalletTransaction {
+    /// Negative if the transaction conflicts with one in the most-work chain
+    confirmations: i64,
+}
+
+#[derive(Deserialize)]
+struct SmartFee {
+    /// The estimated fee rate in BTC/kvB
+    feerate: Option<f64>,
+}
+
+impl BitcoinNode {
+    /// Creates a client for the node at `url`, authenticating with the given credentials.
+    pub fn new(url: String, username: String, password: String) -> Self {
+        Self {
+            url,
+            username,
+            password,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Vec<Value>,
+    ) -> anyhow::Result<T> {
+        let response: Response<T> = self
+            .client
+            .post(&self.url)
+            .basic_auth(&self.username, Some(&self.password))
+            .json(&json!({
+                "jsonrpc": "1.0",
+                "id": method,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        if let Some(error) = response.error {
+            anyhow::bail!(
+                "bitcoind returned error {} for {}: {}",
+                error.code,
+                method,
+                error.message
+            );
+        }
+        response
+            .result
+            .ok_or_else(|| anyhow::anyhow!("bitcoind returned no result for {}", method))
+    }
+
+    /// Returns the height of the most-work chain.
+    pub async fn get_block_count(&self) -> anyhow::Result<u64> {
+        self.call("getblockcount", vec![]).await
+    }
+
+    /// Returns the hash of the block at `height` on the most-work chain.
+    pub async fn get_block_hash(&self, height: u64) -> anyhow::Result<BlockHash> {
+        let hash: String = self.call("getblockhash", vec![json!(height)]).await?;
+        Ok(hash.parse()?)
+    }
+
+    /// Returns the full block with the given hash.
+    pub async fn get_block(&self, hash: &BlockHash) -> anyhow::Result<Block> {
+        let raw: String = self
+            .call("getblock", vec![json!(hash.to_string()), json!(0)])
+            .await?;
+        Ok(encode::deserialize(&hex::decode(raw)?)?)
+    }
+
+    /// Returns the confirmed outputs of the wallet.
+    pub async fn get_utxos(&self) -> anyhow::Result<Vec<UTXO>> {
+        self.call("listunspent", vec![json!(1)]).await
+    }
+
+    /// Estimates the fee rate in sat/vB for a confirmation within `target_blocks` blocks.
+    /// Returns `None` if the node doesn't have enough data, which is always the case on regtest.
+    pub async fn estimate_smart_fee(&self, target_blocks: u16) -> anyhow::Result<Option<f64>> {
+        let fee: SmartFee = self
+            .call("estimatesmartfee", vec![json!(target_blocks)])
+            .await?;
+        // 1 BTC/kvB = 100_000 sat/vB
+        Ok(fee.feerate.map(|rate| rate * 100_000.0))
+    }
+
+    /// Signs the inputs of `tx` spending wallet outputs.
+    pub async fn sign_raw_transaction_with_wallet(
+        &self,
+        tx: &Transaction,
+    ) -> anyhow::Result<Transaction> {
+        let signed: SignedTransaction = self
+            .call(
+                "signrawtransactionwithwallet",
+                vec![json!(encode::serialize_hex(tx))],
+            )
+            .await?;
+        if !signed.complete {
+            anyhow::bail!("The wallet could not sign all inputs of {}", tx.txid());
+        }
+        Ok(encode::deserialize(&hex::decode(signed.hex)?)?)
+    }
+
+    /// Returns the number of confirmations of a transaction paying to or from the wallet.
+    pub async fn get_transaction_confirmations(&self, txid: &Txid) -> anyhow::Result<i64> {
+        let tx: WalletTransaction = self
+            .call("gettransaction", vec![json!(txid.to_string())])
+            .await?;
+        Ok(tx.confirmations)
+    }
+
+    /// Broadcasts a signed transaction.
+    pub async fn send_raw_transaction(&self, tx: &Transaction) -> anyhow::Result<Txid> {
+        let txid: String = self
+            .call("sendrawtransaction", vec![json!(encode::serialize_hex(tx))])
+            .await?;
+        Ok(txid.parse()?)
+    }
+}
@@ -0,0 +1,467 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bitcoin::hashes::Hash;
+use bitcoin::secp256k1::{Message, PublicKey, Secp256k1, SecretKey};
+use bitcoin::{Address, Network, Txid};
+use pin_project::pin_project;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::services::da::{DaService, FeeEstimate};
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::helpers::builders::{
+    build_cpfp_transaction, create_inscription_transactions, InscriptionTxs,
+};
+use crate::helpers::parsers::parse_transaction;
+use crate::rpc::BitcoinNode;
+use crate::spec::blob::BlobWithSender;
+use crate::spec::block::BitcoinBlock;
+use crate::spec::header::HeaderWrapper;
+use crate::spec::proof::InclusionMultiProof;
+use crate::spec::{BitcoinSpec, RollupParams};
+use crate::verifier::BitcoinVerifier;
+
+/// Blocks with this many confirmations are considered final.
+const FINALITY_DEPTH: u64 = 6;
+/// Used when the node can't estimate the fee rate, in sat/vB.
+const DEFAULT_FEE_RATE: f64 = 2.0;
+/// The approximate size of the commit transaction and of the reveal transaction without its blob, in vB.
+const INSCRIPTION_OVERHEAD_VBYTES: u64 = 400;
+/// The interval at which the node is polled for new blocks.
+const POLLING_INTERVAL: Duration = Duration::from_secs(1);
+/// The default largest blob of a reveal transaction, which keeps it below the
+/// 400 000 weight units of a standard transaction.
+const DEFAULT_MAX_BLOB_SIZE: usize = 390_000;
+
+/// Runtime configuration for the [`DaService`] implementation.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct BitcoinServiceConfig {
+    /// The URL of the `bitcoind` JSON-RPC server
+    pub node_url: String,
+    /// The user used to authenticate with `bitcoind`
+    pub node_username: String,
+    /// The password used to authenticate with `bitcoind`
+    pub node_password: String,
+    /// The bitcoin network: "bitcoin", "testnet", "signet" or "regtest"
+    pub network: String,
+    /// The wallet address receiving the change of the submitted transactions
+    pub address: String,
+    /// The hex encoded secp256k1 key signing the blobs. Its public key is the sender's DA address.
+    pub da_private_key: Option<String>,
+    /// The largest blob inscribed in a single reveal transaction, in bytes.
+    /// Larger batches are split across several transactions. Defaults to 390 000 bytes.
+    #[serde(default)]
+    pub max_blob_size: Option<usize>,
+}
+
+/// A [`DaService`] writing blobs to Bitcoin as taproot inscriptions, through a `bitcoind` wallet.
+#[derive(Debug, Clone)]
+pub struct BitcoinService {
+    client: BitcoinNode,
+    network: Network,
+    address: Address,
+    rollup_name: String,
+    reveal_wtxid_prefix: Vec<u8>,
+    sequencer_da_private_key: Option<SecretKey>,
+    finalized_header_sender: broadcast::Sender<HeaderWrapper>,
+    max_blob_size: usize,
+    /// The blobs sent but not seen in a block yet, by reveal txid, whose fee can be bumped.
+    pending_inscriptions: Arc<Mutex<HashMap<Txid, InscriptionTxs>>>,
+}
+
+impl BitcoinService {
+    /// Creates the service and spawns a task publishing finalized headers.
+    pub async fn new(config: BitcoinServiceConfig, chain_params: RollupParams) -> Self {
+        let network = Network::from_str(&config.network).expect("Invalid bitcoin network name");
+        let address = Address::from_str(&config.address)
+            .expect("Invalid bitcoin address")
+            .require_network(network)
+            .expect("The address does not belong to the configured network");
+        let sequencer_da_private_key = config
+            .da_private_key
+            .map(|key| SecretKey::from_str(&key).expect("Invalid secp256k1 private key"));
+        let client = BitcoinNode::new(config.node_url, config.node_username, config.node_password);
+
+        let (finalized_header_sender, _) = broadcast::channel(16);
+        tokio::spawn(publish_finalized_headers(
+            client.clone(),
+            finalized_header_sender.clone(),
+        ));
+
+        Self {
+            client,
+            network,
+            address,
+            rollup_name: chain_params.rollup_name,
+            reveal_wtxid_prefix: chain_params.reveal_wtxid_prefix,
+            sequencer_da_private_key,
+            finalized_header_sender,
+            max_blob_size: config.max_blob_size.unwrap_or(DEFAULT_MAX_BLOB_SIZE),
+            pending_inscriptions: Default::default(),
+        }
+    }
+
+    /// Returns the DA address of blobs submitted by this service, if it has a signing key.
+    pub fn get_sequencer_address(&self) -> Option<Vec<u8>> {
+        self.sequencer_da_private_key.map(|key| {
+            PublicKey::from_secret_key(&Secp256k1::new(), &key)
+                .serialize()
+                .to_vec()
+        })
+    }
+
+    async fn get_block_by_height(&self, height: u64) -> anyhow::Result<BitcoinBlock> {
+        let hash = self.client.get_block_hash(height).await?;
+        let block = self.client.get_block(&hash).await?;
+
+        Ok(BitcoinBlock {
+            header: HeaderWrapper::new(block.header, height, block.txdata.len() as u32),
+            txdata: block.txdata,
+        })
+    }
+
+    /// The fee rate for a confirmation in the next block, in sat/vB.
+    async fn fee_rate(&self) -> anyhow::Result<f64> {
+        Ok(self
+            .client
+            .estimate_smart_fee(1)
+            .await?
+            .unwrap_or(DEFAULT_FEE_RATE))
+    }
+
+    /// Signs the blob and writes it to Bitcoin under `rollup_name` at `fee_rate` sat/vB,
+    /// returning the sent transactions.
+    async fn send_inscription(
+        &self,
+        rollup_name: &str,
+        blob: &[u8],
+        fee_rate: f64,
+    ) -> anyhow::Result<InscriptionTxs> {
+        let key = self
+            .sequencer_da_private_key
+            .ok_or_else(|| anyhow::anyhow!("No DA private key is configured"))?;
+        let secp = Secp256k1::new();
+        let hash: [u8; 32] = Sha256::digest(blob).into();
+        let signature = secp
+            .sign_ecdsa(&Message::from_slice(&hash)?, &key)
+            .serialize_compact();
+        let public_key = PublicKey::from_secret_key(&secp, &key).serialize();
+
+        let utxos = self.client.get_utxos().await?;
+
+        let txs = create_inscription_transactions(
+            rollup_name,
+            blob,
+            &signature,
+            &public_key,
+            utxos,
+            self.address.clone(),
+            fee_rate,
+            self.network,
+            &self.reveal_wtxid_prefix,
+        )?;
+
+        let commit_tx = self
+            .client
+            .sign_raw_transaction_with_wallet(&txs.commit_tx)
+            .await?;
+        if commit_tx.txid() != txs.commit_tx.txid() {
+            anyhow::bail!("Signing changed the commit txid, the wallet outputs must be segwit");
+        }
+        let commit_txid = self.client.send_raw_transaction(&commit_tx).await?;
+        let reveal_txid = self.client.send_raw_transaction(&txs.reveal_tx).await?;
+        info!(
+            "Blob of {} bytes sent to Bitcoin. commit_txid={} reveal_txid={}",
+            blob.len(),
+            commit_txid,
+            reveal_txid
+        );
+
+        Ok(InscriptionTxs {
+            commit_tx,
+            reveal_tx: txs.reveal_tx,
+        })
+    }
+
+    /// Sends a batch blob, keeping track of its transactions until it's included,
+    /// so that its fee can be bumped.
+    async fn send_batch(&self, blob: &[u8], fee_rate: f64) -> anyhow::Result<Txid> {
+        let txs = self
+            .send_inscription(&self.rollup_name, blob, fee_rate)
+            .await?;
+        let reveal_txid = txs.reveal_tx.txid();
+        self.pending_inscriptions
+            .lock()
+            .unwrap()
+            .insert(reveal_txid, txs);
+        Ok(reveal_txid)
+    }
+
+    fn proof_rollup_name(&self) -> String {
+        format!("{}-proof", self.rollup_name)
+    }
+
+    fn relevant_reveal_txs<'a>(
+        &'a self,
+        block: &'a BitcoinBlock,
+    ) -> impl Iterator<Item = &'a bitcoin::Transaction> + 'a {
+        block.txdata.iter().skip(1).filter(|tx| {
+            tx.wtxid()
+                .to_byte_array()
+                .starts_with(&self.reveal_wtxid_prefix)
+        })
+    }
+}
+
+async fn publish_finalized_headers(client: BitcoinNode, sender: broadcast::Sender<HeaderWrapper>) {
+    let mut last_published = None;
+    loop {
+        tokio::time::sleep(POLLING_INTERVAL).await;
+
+        let finalized_height = match client.get_block_count().await {
+            Ok(head) => match head.checked_sub(FINALITY_DEPTH) {
+                Some(height) => height,
+                None => continue,
+            },
+            Err(e) => {
+                warn!("Failed to fetch the bitcoin block count: {}", e);
+                continue;
+            }
+        };
+
+        let start = last_published.map_or(finalized_height, |h: u64| h + 1);
+        for height in start..=finalized_height {
+            let header = match client.get_block_hash(height).await {
+                Ok(hash) => client.get_block(&hash).await,
+                Err(e) => Err(e),
+            };
+            match header {
+                Ok(block) => {
+                    let header =
+                        HeaderWrapper::new(block.header, height, block.txdata.len() as u32);
+                    debug!("Finalized bitcoin header: {}", header);
+                    // Sending only fails if there are no subscribers
+                    let _ = sender.send(header);
+                    last_published = Some(height);
+                }
+                Err(e) => {
+                    warn!("Failed to fetch bitcoin block {}: {}", height, e);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// A stream of finalized [`HeaderWrapper`]s.
+#[pin_project]
+pub struct BitcoinHeaderStream {
+    #[pin]
+    inner: tokio_stream::wrappers::BroadcastStream<HeaderWrapper>,
+}
+
+impl futures::Stream for BitcoinHeaderStream {
+    type Item = Result<HeaderWrapper, anyhow::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project()
+            .inner
+            .poll_next(cx)
+            .map(|opt| opt.map(|res| res.map_err(Into::into)))
+    }
+}
+
+#[async_trait]
+impl DaService for BitcoinService {
+    type Spec = BitcoinSpec;
+
+    type Verifier = BitcoinVerifier;
+
+    type FilteredBlock = BitcoinBlock;
+    type HeaderStream = BitcoinHeaderStream;
+    type TransactionId = Txid;
+    type Error = anyhow::Error;
+
+    async fn get_block_at(&self, height: u64) -> Result<Self::FilteredBlock, Self::Error> {
+        // Waits for the block to be mined
+        while self.client.get_block_count().await? < height {
+            tokio::time::sleep(POLLING_INTERVAL).await;
+        }
+        self.get_block_by_height(height).await
+    }
+
+    async fn get_last_finalized_block_header(
+        &self,
+    ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+        let head = self.client.get_block_count().await?;
+        let height = head
+            .checked_sub(FINALITY_DEPTH)
+            .ok_or_else(|| anyhow::anyhow!("No bitcoin block is final yet"))?;
+        Ok(self.get_block_by_height(height).await?.header)
+    }
+
+    async fn subscribe_finalized_header(&self) -> Result<Self::HeaderStream, Self::Error> {
+        Ok(BitcoinHeaderStream {
+            inner: tokio_stream::wrappers::BroadcastStream::new(
+                self.finalized_header_sender.subscribe(),
+            ),
+        })
+    }
+
+    async fn get_head_block_header(
+        &self,
+    ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error> {
+        let head = self.client.get_block_count().await?;
+        Ok(self.get_block_by_height(head).await?.header)
+    }
+
+    fn extract_relevant_blobs(
+        &self,
+        block: &Self::FilteredBlock,
+    ) -> Vec<<Self::Spec as DaSpec>::BlobTransaction> {
+        self.relevant_reveal_txs(block)
+            .filter_map(|tx| {
+                let inscription = parse_transaction(tx, &self.rollup_name).ok()?;
+                if !inscription.verify_signature() {
+                    warn!("Ignoring blob with an invalid signature in {}", tx.txid());
+                    return None;
+                }
+                Some(BlobWithSender::new(
+                    inscription.body,
+                    inscription.public_key,
+                    tx.txid().to_byte_array(),
+                ))
+            })
+            .collect()
+    }
+
+    async fn get_extraction_proof(
+        &self,
+        block: &Self::FilteredBlock,
+        _blobs: &[<Self::Spec as DaSpec>::BlobTransaction],
+    ) -> (
+        <Self::Spec as DaSpec>::InclusionMultiProof,
+        <Self::Spec as DaSpec>::CompletenessProof,
+    ) {
+        let inclusion_proof = InclusionMultiProof {
+            txids: block
+                .txdata
+                .iter()
+                .map(|tx| tx.txid().to_byte_array())
+                .collect(),
+            wtxids: block
+                .txdata
+                .iter()
+                .map(|tx| tx.wtxid().to_byte_array())
+                .collect(),
+            coinbase_tx: block.txdata[0].clone(),
+        };
+        let completeness_proof = self.relevant_reveal_txs(block).cloned().collect();
+
+        (inclusion_proof, completeness_proof)
+    }
+
+    async fn send_transaction(&self, blob: &[u8]) -> Result<Self::TransactionId, Self::Error> {
+        let fee_rate = self.fee_rate().await?;
+        self.send_batch(blob, fee_rate).await
+    }
+
+    async fn is_transaction_included(&self, tx_id: &Txid) -> Result<bool, Self::Error> {
+        let included = self.client.get_transaction_confirmations(tx_id).await? > 0;
+        if included {
+            self.pending_inscriptions.lock().unwrap().remove(tx_id);
+        }
+        Ok(included)
+    }
+
+    /// Bumps the fee of the inscription with a child transaction spending its reveal output.
+    /// The blob isn't sent again, so the reveal txid stays the same.
+    async fn bump_fee(&self, tx_id: Txid, fee_rate: f64) -> Result<Txid, Self::Error> {
+        let txs = self
+            .pending_inscriptions
+            .lock()
+            .unwrap()
+            .get(&tx_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No pending inscription with reveal txid {}", tx_id))?;
+        let utxos = self.client.get_utxos().await?;
+        let child_tx = build_cpfp_transaction(
+            &txs.commit_tx,
+            &txs.reveal_tx,
+            utxos,
+            &self.address,
+            fee_rate,
+        )?;
+        let child_tx = self
+            .client
+            .sign_raw_transaction_with_wallet(&child_tx)
+            .await?;
+        let child_txid = self.client.send_raw_transaction(&child_tx).await?;
+        info!(
+            "Fee of reveal_txid={} bumped to {} sat/vB. child_txid={}",
+            tx_id, fee_rate, child_txid
+        );
+
+        Ok(tx_id)
+    }
+
+    fn max_blob_size(&self) -> Option<usize> {
+        Some(self.max_blob_size)
+    }
+
+    fn blob_location(&self) -> Option<String> {
+        Some(format!(
+            "inscriptions of the reveal script tagged with rollup name {}",
+            self.rollup_name
+        ))
+    }
+
+    async fn estimate_fee(&self, blob_size: usize) -> Result<FeeEstimate, Self::Error> {
+        let fee_rate = self.fee_rate().await?;
+        // The blob is in the witness of the reveal transaction, which is discounted 4 times
+        let vsize = INSCRIPTION_OVERHEAD_VBYTES + (blob_size as u64).div_ceil(4);
+        Ok(FeeEstimate {
+            fee_rate,
+            fee: (vsize as f64 * fee_rate).ceil() as u64,
+        })
+    }
+
+    async fn send_transaction_with_fee_rate(
+        &self,
+        blob: &[u8],
+        fee_rate: f64,
+    ) -> Result<Self::TransactionId, Self::Error> {
+        self.send_batch(blob, fee_rate).await
+    }
+
+    async fn send_aggregated_zk_proof(
+        &self,
+        aggregated_proof_data: &[u8],
+    ) -> Result<u64, Self::Error> {
+        // The proof can't be included before the next block
+        let height = self.client.get_block_count().await? + 1;
+        let fee_rate = self.fee_rate().await?;
+        self.send_inscription(&self.proof_rollup_name(), aggregated_proof_data, fee_rate)
+            .await?;
+        Ok(height)
+    }
+
+    async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error> {
+        let block = self.get_block_at(height).await?;
+        let proof_rollup_name = self.proof_rollup_name();
+        Ok(self
+            .relevant_reveal_txs(&block)
+            .filter_map(|tx| parse_transaction(tx, &proof_rollup_name).ok())
+            .filter(|inscription| inscription.verify_signature())
+            .map(|inscription| inscription.body)
+            .collect())
+    }
+}
@@ -0,0 +1,62 @@
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::BasicAddress;
+
+/// The DA address of a blob sender: the compressed secp256k1 public key which signed the blob.
+#[derive(
+    Debug, PartialEq, Clone, Eq, Hash, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
+pub struct BitcoinAddress(#[serde(with = "hex::serde")] pub Vec<u8>);
+
+impl BitcoinAddress {
+    /// Creates a new address from the serialized public key.
+    pub fn new(public_key: Vec<u8>) -> Self {
+        Self(public_key)
+    }
+}
+
+impl AsRef<[u8]> for BitcoinAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for BitcoinAddress {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        Ok(Self(value.to_vec()))
+    }
+}
+
+impl FromStr for BitcoinAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let public_key = hex::decode(s.trim_start_matches("0x"))?;
+        Ok(Self(public_key))
+    }
+}
+
+impl Display for BitcoinAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", hex::encode(&self.0))
+    }
+}
+
+impl BasicAddress for BitcoinAddress {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitcoin_address_string() {
+        let addr = BitcoinAddress::new(vec![2u8; 33]);
+        let recovered_addr = addr.to_string().parse::<BitcoinAddress>().unwrap();
+        assert_eq!(addr, recovered_addr);
+    }
+}
@@ -0,0 +1,54 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlobReaderTrait, CountedBufReader};
+use sov_rollup_interface::Bytes;
+
+use super::address::BitcoinAddress;
+
+/// A rollup blob extracted from a reveal transaction, together with the key which signed it.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize, BorshDeserialize, BorshSerialize)]
+pub struct BlobWithSender {
+    /// The txid of the reveal transaction carrying the blob.
+    pub hash: [u8; 32],
+    /// The public key that signed the blob.
+    pub sender: BitcoinAddress,
+    /// The blob data.
+    pub blob: CountedBufReader<Bytes>,
+}
+
+impl BlobWithSender {
+    /// Creates a new blob.
+    pub fn new(blob: Vec<u8>, sender: Vec<u8>, hash: [u8; 32]) -> Self {
+        Self {
+            blob: CountedBufReader::new(Bytes::from(blob)),
+            sender: BitcoinAddress::new(sender),
+            hash,
+        }
+    }
+}
+
+impl BlobReaderTrait for BlobWithSender {
+    type Address = BitcoinAddress;
+
+    fn sender(&self) -> Self::Address {
+        self.sender.clone()
+    }
+
+    fn hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    fn verified_data(&self) -> &[u8] {
+        self.blob.accumulator()
+    }
+
+    fn total_len(&self) -> usize {
+        self.blob.total_len()
+    }
+
+    #[cfg(feature = "native")]
+    fn advance(&mut self, num_bytes: usize) -> &[u8] {
+        self.blob.advance(num_bytes);
+        self.verified_data()
+    }
+}
@@ -0,0 +1,36 @@
+use bitcoin::Transaction;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::BlockHeaderTrait;
+use sov_rollup_interface::services::da::SlotData;
+
+use super::header::HeaderWrapper;
+use crate::verifier::ChainValidityCondition;
+
+/// A full Bitcoin block, as fetched from the node.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct BitcoinBlock {
+    /// The block header.
+    pub header: HeaderWrapper,
+    /// All transactions of the block, starting with the coinbase.
+    pub txdata: Vec<Transaction>,
+}
+
+impl SlotData for BitcoinBlock {
+    type BlockHeader = HeaderWrapper;
+    type Cond = ChainValidityCondition;
+
+    fn hash(&self) -> [u8; 32] {
+        self.header.hash().0
+    }
+
+    fn header(&self) -> &Self::BlockHeader {
+        &self.header
+    }
+
+    fn validity_condition(&self) -> Self::Cond {
+        ChainValidityCondition {
+            prev_hash: self.header.prev_hash().0,
+            block_hash: self.header.hash().0,
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use bitcoin::block::Header;
+use bitcoin::blockdata::script::Instruction;
+use bitcoin::consensus::encode;
+use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::{OutPoint, Transaction, Witness};
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_rollup_interface::da::DaDeposit;
+
+use super::header::BitcoinHash;
+use crate::verifier::calculate_merkle_root_from_branch;
+
+/// Proves that a transaction paying to a rollup bridge is included in a Bitcoin block.
+#[derive(Clone, Debug, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct DepositProof {
+    /// The consensus encoded header of the block including the transaction.
+    pub header: Vec<u8>,
+    /// The consensus encoded deposit transaction.
+    pub tx: Vec<u8>,
+    /// The position of the transaction in the block.
+    pub index: u32,
+    /// The siblings of the txid in the merkle tree of the block, from the leaves up.
+    pub merkle_branch: Vec<[u8; 32]>,
+}
+
+/// Verifies a [`DepositProof`] against the merkle root of the block header it carries.
+///
+/// The deposit is the first output of the transaction paying to `bridge_script`, and its
+/// recipient is the data pushed by the first `OP_RETURN` output.
+pub(crate) fn verify_deposit(
+    bridge_script: &[u8],
+    proof: &[u8],
+) -> anyhow::Result<DaDeposit<BitcoinHash>> {
+    let proof = DepositProof::try_from_slice(proof)?;
+    let header: Header = encode::deserialize(&proof.header)?;
+    let tx: Transaction = encode::deserialize(&proof.tx)?;
+
+    // A 64 byte transaction can be passed off as an inner node of the merkle tree
+    let mut stripped = tx.clone();
+    for input in &mut stripped.input {
+        input.witness = Witness::default();
+    }
+    anyhow::ensure!(
+        encode::serialize(&stripped).len() != 64,
+        "64 byte transactions can't be proven with a merkle branch"
+    );
+    anyhow::ensure!(
+        proof.merkle_branch.len() < 32 && proof.index >> proof.merkle_branch.len() == 0,
+        "The transaction index doesn't fit the merkle branch"
+    );
+    let txid = tx.txid();
+    let merkle_root =
+        calculate_merkle_root_from_branch(txid.to_byte_array(), proof.index, &proof.merkle_branch);
+    anyhow::ensure!(
+        merkle_root == header.merkle_root.to_byte_array(),
+        "The transaction is not included in the block"
+    );
+
+    let (vout, output) = tx
+        .output
+        .iter()
+        .enumerate()
+        .find(|(_, output)| output.script_pubkey.as_bytes() == bridge_script)
+        .ok_or_else(|| anyhow::anyhow!("The transaction doesn't pay to the bridge"))?;
+    let recipient = tx
+        .output
+        .iter()
+        .find(|output| output.script_pubkey.is_op_return())
+        .and_then(|output| match output.script_pubkey.instructions().nth(1) {
+            Some(Ok(Instruction::PushBytes(recipient))) => Some(recipient.as_bytes().to_vec()),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow::anyhow!("The transaction doesn't commit to a recipient"))?;
+
+    let outpoint = OutPoint::new(txid, vout as u32);
+    Ok(DaDeposit {
+        block_hash: BitcoinHash(header.block_hash().to_byte_array()),
+        id: sha256d::Hash::hash(&encode::serialize(&outpoint)).to_byte_array(),
+        amount: output.value,
+        recipient,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::blockdata::opcodes::all::OP_RETURN;
+    use bitcoin::script::{Builder, PushBytesBuf};
+    use bitcoin::{Network, ScriptBuf, TxOut};
+
+    use super::*;
+    use crate::verifier::calculate_merkle_root;
+
+    const BRIDGE_SCRIPT: [u8; 3] = [0x51, 0x01, 0x02];
+
+    fn deposit_block() -> (Header, Transaction, Transaction) {
+        let genesis = bitcoin::blockdata::constants::genesis_block(Network::Regtest);
+        let coinbase = genesis.txdata[0].clone();
+        let mut deposit = coinbase.clone();
+        deposit.output = vec![
+            TxOut {
+                value: 1_000,
+                script_pubkey: ScriptBuf::from_bytes(BRIDGE_SCRIPT.to_vec()),
+            },
+            TxOut {
+                value: 0,
+                script_pubkey: Builder::new()
+                    .push_opcode(OP_RETURN)
+                    .push_slice(PushBytesBuf::try_from(vec![7; 32]).unwrap())
+                    .into_script(),
+            },
+        ];
+
+        let mut header = genesis.header;
+        header.merkle_root = bitcoin::TxMerkleNode::from_byte_array(
+            calculate_merkle_root(vec![
+                coinbase.txid().to_byte_array(),
+                deposit.txid().to_byte_array(),
+            ])
+            .unwrap(),
+        );
+        (header, coinbase, deposit)
+    }
+
+    #[test]
+    fn deposit_is_verified_against_the_header() {
+        let (header, coinbase, deposit) = deposit_block();
+        let proof = DepositProof {
+            header: encode::serialize(&header),
+            tx: encode::serialize(&deposit),
+            index: 1,
+            merkle_branch: vec![coinbase.txid().to_byte_array()],
+        };
+
+        let verified = verify_deposit(&BRIDGE_SCRIPT, &borsh::to_vec(&proof).unwrap()).unwrap();
+        assert_eq!(verified.block_hash.0, header.block_hash().to_byte_array());
+        assert_eq!(verified.amount, 1_000);
+        assert_eq!(verified.recipient, vec![7; 32]);
+
+        // Paying to another script is not a deposit to the bridge
+        assert!(verify_deposit(&[0x51], &borsh::to_vec(&proof).unwrap()).is_err());
+
+        // The transaction is not at the proven position
+        let wrong_index = DepositProof { index: 0, ..proof };
+        assert!(verify_deposit(&BRIDGE_SCRIPT, &borsh::to_vec(&wrong_index).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,108 @@
+use core::fmt::{Debug, Display, Formatter};
+
+use bitcoin::block::Header;
+use bitcoin::hashes::Hash;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlockHashTrait, BlockHeaderTrait, Time};
+
+/// The hash of a Bitcoin block, in internal (little-endian) byte order.
+#[derive(
+    Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, BorshDeserialize, BorshSerialize,
+)]
+pub struct BitcoinHash(pub [u8; 32]);
+
+impl Debug for BitcoinHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Hashes are displayed reversed, the same way `bitcoind` and block explorers show them.
+impl Display for BitcoinHash {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut reversed = self.0;
+        reversed.reverse();
+        write!(f, "{}", hex::encode(reversed))
+    }
+}
+
+impl AsRef<[u8]> for BitcoinHash {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for BitcoinHash {
+    fn from(value: [u8; 32]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<BitcoinHash> for [u8; 32] {
+    fn from(value: BitcoinHash) -> Self {
+        value.0
+    }
+}
+
+impl BlockHashTrait for BitcoinHash {}
+
+/// A Bitcoin block header together with the data the rollup needs to place it in the chain.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct HeaderWrapper {
+    /// The raw 80 byte block header.
+    pub header: Header,
+    /// The height of the block.
+    pub height: u64,
+    /// The number of transactions in the block, as reported by the node. The block hash
+    /// doesn't commit to it, so it's never trusted by the verifier.
+    pub tx_count: u32,
+}
+
+impl HeaderWrapper {
+    /// Creates a new header wrapper.
+    pub fn new(header: Header, height: u64, tx_count: u32) -> Self {
+        Self {
+            header,
+            height,
+            tx_count,
+        }
+    }
+
+    /// The root of the merkle tree built from the txids of the block.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        self.header.merkle_root.to_byte_array()
+    }
+}
+
+impl Display for HeaderWrapper {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "BitcoinHeader {{ height: {}, prev_hash: {}, hash: {} }}",
+            self.height,
+            self.prev_hash(),
+            self.hash()
+        )
+    }
+}
+
+impl BlockHeaderTrait for HeaderWrapper {
+    type Hash = BitcoinHash;
+
+    fn prev_hash(&self) -> Self::Hash {
+        BitcoinHash(self.header.prev_blockhash.to_byte_array())
+    }
+
+    fn hash(&self) -> Self::Hash {
+        BitcoinHash(self.header.block_hash().to_byte_array())
+    }
+
+    fn height(&self) -> u64 {
+        self.height
+    }
+
+    fn time(&self) -> Time {
+        Time::from_secs(self.header.time as i64)
+    }
+}
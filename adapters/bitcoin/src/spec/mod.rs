@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{DaDeposit, DaSpec};
+
+pub mod address;
+pub mod blob;
+pub mod block;
+pub mod deposit;
+pub mod header;
+pub mod proof;
+pub mod utxo;
+
+use self::address::BitcoinAddress;
+use self::blob::BlobWithSender;
+use self::header::{BitcoinHash, HeaderWrapper};
+use self::proof::{CompletenessProof, InclusionMultiProof};
+use crate::verifier::ChainValidityCondition;
+
+/// The [`DaSpec`] of the Bitcoin DA layer.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitcoinSpec;
+
+/// The parameters identifying the rollup's data on Bitcoin.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupParams {
+    /// The name written in every inscription envelope. Blobs with a different name are ignored.
+    pub rollup_name: String,
+    /// The prefix every reveal transaction's wtxid is ground to.
+    pub reveal_wtxid_prefix: Vec<u8>,
+}
+
+impl DaSpec for BitcoinSpec {
+    type SlotHash = BitcoinHash;
+
+    type BlockHeader = HeaderWrapper;
+
+    type BlobTransaction = BlobWithSender;
+
+    type Address = BitcoinAddress;
+
+    type ValidityCondition = ChainValidityCondition;
+
+    type InclusionMultiProof = InclusionMultiProof;
+
+    type CompletenessProof = CompletenessProof;
+
+    type ChainParams = RollupParams;
+
+    fn verify_deposit(
+        bridge_script: &[u8],
+        proof: &[u8],
+    ) -> Result<DaDeposit<Self::SlotHash>, anyhow::Error> {
+        deposit::verify_deposit(bridge_script, proof)
+    }
+}
@@ -0,0 +1,22 @@
+use bitcoin::Transaction;
+use serde::{Deserialize, Serialize};
+
+/// Proves that the transactions used by the verifier are part of the block.
+///
+/// The txids commit to the header's merkle root, while the wtxids commit to the witness
+/// commitment found in the coinbase transaction. Rollup blobs live in the witness, so both are needed.
+#[derive(Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct InclusionMultiProof {
+    /// The txids of all transactions in the block, in block order.
+    pub txids: Vec<[u8; 32]>,
+    /// The wtxids of all transactions in the block, in block order.
+    pub wtxids: Vec<[u8; 32]>,
+    /// The coinbase transaction, which holds the witness commitment.
+    pub coinbase_tx: Transaction,
+}
+
+/// Every transaction of the block whose wtxid starts with the rollup's reveal prefix, in block order.
+///
+/// Reveal transactions are ground until their wtxid has the prefix, so the verifier can check
+/// that no blob was left out just by looking at the wtxids of the [`InclusionMultiProof`].
+pub type CompletenessProof = Vec<Transaction>;
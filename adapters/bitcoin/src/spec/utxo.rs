@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// An unspent output of the node's wallet, as returned by `listunspent`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UTXO {
+    /// The id of the transaction that created the output.
+    pub txid: String,
+    /// The index of the output in that transaction.
+    pub vout: u32,
+    /// The address the output pays to.
+    pub address: String,
+    /// The hex encoded locking script of the output.
+    #[serde(rename = "scriptPubKey")]
+    pub script_pubkey: String,
+    /// The value of the output, in BTC.
+    pub amount: f64,
+    /// The number of confirmations of the output.
+    pub confirmations: u64,
+    /// Whether the wallet can spend the output.
+    pub spendable: bool,
+    /// Whether the wallet knows how to spend the output, ignoring the lack of keys.
+    pub solvable: bool,
+}
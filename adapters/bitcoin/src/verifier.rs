@@ -0,0 +1,349 @@
+use bitcoin::hashes::{sha256d, Hash};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec, DaVerifier};
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::zk::ValidityCondition;
+use thiserror::Error;
+
+use crate::helpers::parsers::parse_transaction;
+use crate::spec::proof::InclusionMultiProof;
+use crate::spec::{BitcoinSpec, RollupParams};
+
+/// The script prefix of the coinbase output holding the witness commitment (BIP141).
+const WITNESS_COMMITMENT_PREFIX: &[u8] = &[0x6a, 0x24, 0xaa, 0x21, 0xa9, 0xed];
+
+/// Verifies that the blobs extracted from a Bitcoin block are exactly the ones sent to the rollup.
+pub struct BitcoinVerifier {
+    rollup_name: String,
+    reveal_wtxid_prefix: Vec<u8>,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    Hash,
+    BorshDeserialize,
+    BorshSerialize,
+)]
+/// A validity condition expressing that a chain of DA layer blocks is contiguous and canonical
+pub struct ChainValidityCondition {
+    /// The hash of the parent block.
+    pub prev_hash: [u8; 32],
+    /// The hash of the block.
+    pub block_hash: [u8; 32],
+}
+
+/// Reasons why validity conditions can't be combined.
+#[derive(Error, Debug)]
+pub enum ValidityConditionError {
+    /// The blocks do not build on each other.
+    #[error("conditions for validity can only be combined if the blocks are consecutive")]
+    BlocksNotConsecutive,
+}
+
+impl ValidityCondition for ChainValidityCondition {
+    type Error = ValidityConditionError;
+    fn combine<H: Digest>(&self, rhs: Self) -> Result<Self, Self::Error> {
+        if self.block_hash != rhs.prev_hash {
+            return Err(ValidityConditionError::BlocksNotConsecutive);
+        }
+        Ok(rhs)
+    }
+}
+
+/// Reasons why the claimed blobs of a block are rejected.
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// The txids don't match the merkle root of the header.
+    #[error("the txids do not match the merkle root of the block header")]
+    InvalidMerkleRoot,
+    /// The proof doesn't have a wtxid for every txid.
+    #[error("the proof contains {txids} txids, but {wtxids} wtxids")]
+    WrongWtxidCount {
+        /// The number of txids in the proof.
+        txids: usize,
+        /// The number of wtxids in the proof.
+        wtxids: usize,
+    },
+    /// The coinbase transaction is not the first transaction of the block.
+    #[error("the coinbase transaction does not match the first txid of the block")]
+    InvalidCoinbase,
+    /// The wtxids don't match the witness commitment of the coinbase transaction.
+    #[error("the wtxids do not match the witness commitment of the coinbase transaction")]
+    InvalidWitnessCommitment,
+    /// A transaction of the completeness proof doesn't appear in the block at the expected position.
+    #[error("a transaction of the completeness proof is not part of the block")]
+    InvalidCompletenessProof,
+    /// A blob doesn't match the inscription revealed in the block.
+    #[error("blob {0} does not match the inscription found in the block")]
+    InvalidBlob(usize),
+    /// Blobs are missing from the claimed list.
+    #[error("a blob sent to the rollup is missing")]
+    MissingBlob,
+    /// The claimed list contains blobs which are not in the block.
+    #[error("the claimed blobs contain a blob which is not in the block")]
+    ExtraBlob,
+}
+
+impl DaVerifier for BitcoinVerifier {
+    type Spec = BitcoinSpec;
+
+    type Error = ValidationError;
+
+    fn new(params: <Self::Spec as DaSpec>::ChainParams) -> Self {
+        let RollupParams {
+            rollup_name,
+            reveal_wtxid_prefix,
+        } = params;
+        Self {
+            rollup_name,
+            reveal_wtxid_prefix,
+        }
+    }
+
+    fn verify_relevant_tx_list(
+        &self,
+        block_header: &<Self::Spec as DaSpec>::BlockHeader,
+        txs: &[<Self::Spec as DaSpec>::BlobTransaction],
+        inclusion_proof: <Self::Spec as DaSpec>::InclusionMultiProof,
+        completeness_proof: <Self::Spec as DaSpec>::CompletenessProof,
+    ) -> Result<<Self::Spec as DaSpec>::ValidityCondition, Self::Error> {
+        let validity_condition = ChainValidityCondition {
+            prev_hash: block_header.prev_hash().0,
+            block_hash: block_header.hash().0,
+        };
+
+        verify_inclusion_proof(block_header.merkle_root(), &inclusion_proof)?;
+
+        // Every wtxid starting with the prefix must be matched by the next transaction of the
+        // completeness proof. As the wtxids are committed to by the block, nothing can be left out.
+        let mut relevant_txs = completeness_proof.iter();
+        let mut blobs = txs.iter().enumerate();
+        for wtxid in inclusion_proof
+            .wtxids
+            .iter()
+            .skip(1)
+            .filter(|wtxid| wtxid.starts_with(&self.reveal_wtxid_prefix))
+        {
+            let tx = relevant_txs
+                .next()
+                .ok_or(ValidationError::InvalidCompletenessProof)?;
+            if tx.wtxid().to_byte_array() != *wtxid {
+                return Err(ValidationError::InvalidCompletenessProof);
+            }
+
+            // Transactions which happen to match the prefix, inscriptions for other rollups
+            // and unsigned blobs are not part of the rollup's data
+            let Ok(inscription) = parse_transaction(tx, &self.rollup_name) else {
+                continue;
+            };
+            if !inscription.verify_signature() {
+                continue;
+            }
+
+            let (index, blob) = blobs.next().ok_or(ValidationError::MissingBlob)?;
+            if blob.hash() != tx.txid().to_byte_array()
+                || blob.sender().as_ref() != inscription.public_key.as_slice()
+                || !inscription.body.starts_with(blob.verified_data())
+            {
+                return Err(ValidationError::InvalidBlob(index));
+            }
+        }
+
+        if relevant_txs.next().is_some() {
+            return Err(ValidationError::InvalidCompletenessProof);
+        }
+        if blobs.next().is_some() {
+            return Err(ValidationError::ExtraBlob);
+        }
+
+        Ok(validity_condition)
+    }
+}
+
+/// Checks the txids and the wtxids of the proof against the block header.
+///
+/// The transaction count of the header isn't committed to by the block hash, so the txids are
+/// only checked against the merkle root, which rejects the lists padded with duplicates.
+fn verify_inclusion_proof(
+    merkle_root: [u8; 32],
+    proof: &InclusionMultiProof,
+) -> Result<(), ValidationError> {
+    if proof.wtxids.len() != proof.txids.len() {
+        return Err(ValidationError::WrongWtxidCount {
+            txids: proof.txids.len(),
+            wtxids: proof.wtxids.len(),
+        });
+    }
+    if calculate_merkle_root(proof.txids.clone()) != Some(merkle_root) {
+        return Err(ValidationError::InvalidMerkleRoot);
+    }
+    if proof.txids.first() != Some(&proof.coinbase_tx.txid().to_byte_array()) {
+        return Err(ValidationError::InvalidCoinbase);
+    }
+
+    // Blocks without segwit transactions don't need a witness commitment
+    let Some(commitment) = proof.coinbase_tx.output.iter().rev().find_map(|output| {
+        let script = output.script_pubkey.as_bytes();
+        (script.len() >= 38 && script.starts_with(WITNESS_COMMITMENT_PREFIX))
+            .then(|| &script[6..38])
+    }) else {
+        return if proof.wtxids.iter().skip(1).eq(proof.txids.iter().skip(1)) {
+            Ok(())
+        } else {
+            Err(ValidationError::InvalidWitnessCommitment)
+        };
+    };
+
+    // The wtxid of the coinbase transaction is replaced with zeroes
+    let mut wtxids = proof.wtxids.clone();
+    wtxids[0] = [0; 32];
+    let witness_root =
+        calculate_merkle_root(wtxids).ok_or(ValidationError::InvalidWitnessCommitment)?;
+    let reserved_value = proof
+        .coinbase_tx
+        .input
+        .first()
+        .and_then(|input| input.witness.nth(0))
+        .ok_or(ValidationError::InvalidWitnessCommitment)?;
+
+    let mut preimage = witness_root.to_vec();
+    preimage.extend_from_slice(reserved_value);
+    if sha256d::Hash::hash(&preimage).to_byte_array() != commitment {
+        return Err(ValidationError::InvalidWitnessCommitment);
+    }
+
+    Ok(())
+}
+
+/// Computes the root of a Bitcoin merkle tree, duplicating the last node of odd levels.
+///
+/// Returns [`None`] for an empty list, and for a list with two identical siblings: duplicating
+/// the last nodes of a level gives the root of the level without them (CVE-2012-2459), so such
+/// a list could pass off as the transactions of a block which doesn't include the duplicates.
+pub fn calculate_merkle_root(mut hashes: Vec<[u8; 32]>) -> Option<[u8; 32]> {
+    if hashes.is_empty() {
+        return None;
+    }
+    while hashes.len() > 1 {
+        if hashes.chunks_exact(2).any(|pair| pair[0] == pair[1]) {
+            return None;
+        }
+        hashes = hashes
+            .chunks(2)
+            .map(|pair| hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+            .collect();
+    }
+    Some(hashes[0])
+}
+
+/// Computes the root of a Bitcoin merkle tree from a leaf at position `index`
+/// and its siblings, from the leaves up.
+pub fn calculate_merkle_root_from_branch(
+    leaf: [u8; 32],
+    mut index: u32,
+    branch: &[[u8; 32]],
+) -> [u8; 32] {
+    branch.iter().fold(leaf, |node, sibling| {
+        let parent = if index & 1 == 0 {
+            hash_pair(&node, sibling)
+        } else {
+            hash_pair(sibling, &node)
+        };
+        index >>= 1;
+        parent
+    })
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(left);
+    preimage[32..].copy_from_slice(right);
+    sha256d::Hash::hash(&preimage).to_byte_array()
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::Hash;
+    use bitcoin::{merkle_tree, Network, Txid};
+
+    use super::*;
+    use crate::spec::blob::BlobWithSender;
+    use crate::spec::header::HeaderWrapper;
+
+    #[test]
+    fn merkle_root_matches_bitcoin() {
+        for count in 1..10u8 {
+            let txids: Vec<[u8; 32]> = (0..count).map(|i| [i; 32]).collect();
+            let expected =
+                merkle_tree::calculate_root(txids.iter().map(|txid| Txid::from_byte_array(*txid)))
+                    .map(|root| root.to_byte_array());
+            assert_eq!(calculate_merkle_root(txids), expected);
+        }
+    }
+
+    #[test]
+    fn merkle_root_rejects_duplicated_nodes() {
+        // [a, b, c] and [a, b, c, c] have the same root
+        let txids: Vec<[u8; 32]> = (0..3u8).map(|i| [i; 32]).collect();
+        assert!(calculate_merkle_root(txids.clone()).is_some());
+        let mut padded = txids.clone();
+        padded.push(txids[2]);
+        assert_eq!(calculate_merkle_root(padded), None);
+
+        // So do [a, b, c, d, e, f] and [a, b, c, d, e, f, e, f], duplicated one level up
+        let txids: Vec<[u8; 32]> = (0..6u8).map(|i| [i; 32]).collect();
+        let mut padded = txids.clone();
+        padded.extend_from_slice(&txids[4..]);
+        assert_eq!(calculate_merkle_root(padded), None);
+    }
+
+    #[test]
+    fn block_without_inscriptions_verifies() {
+        let verifier = BitcoinVerifier::new(RollupParams {
+            rollup_name: "sov-btc".to_string(),
+            reveal_wtxid_prefix: vec![0, 0],
+        });
+        let block = bitcoin::blockdata::constants::genesis_block(Network::Regtest);
+        let header = HeaderWrapper::new(block.header, 0, block.txdata.len() as u32);
+        let inclusion_proof = InclusionMultiProof {
+            txids: vec![block.txdata[0].txid().to_byte_array()],
+            wtxids: vec![block.txdata[0].wtxid().to_byte_array()],
+            coinbase_tx: block.txdata[0].clone(),
+        };
+
+        let validity_condition = verifier
+            .verify_relevant_tx_list(&header, &[], inclusion_proof.clone(), vec![])
+            .unwrap();
+        assert_eq!(validity_condition.block_hash, header.hash().0);
+
+        // The transaction count of the header isn't trusted
+        let wrong_count = HeaderWrapper::new(block.header, 0, 2);
+        assert!(verifier
+            .verify_relevant_tx_list(&wrong_count, &[], inclusion_proof.clone(), vec![])
+            .is_ok());
+
+        let blob = BlobWithSender::new(vec![1, 2, 3], vec![2; 33], [0; 32]);
+        assert_eq!(
+            verifier.verify_relevant_tx_list(&header, &[blob], inclusion_proof.clone(), vec![]),
+            Err(ValidationError::ExtraBlob)
+        );
+
+        // Lists with duplicated transactions are rejected
+        let padded = InclusionMultiProof {
+            txids: vec![inclusion_proof.txids[0]; 2],
+            wtxids: vec![inclusion_proof.wtxids[0]; 2],
+            ..inclusion_proof
+        };
+        assert_eq!(
+            verifier.verify_relevant_tx_list(&header, &[], padded, vec![]),
+            Err(ValidationError::InvalidMerkleRoot)
+        );
+    }
+}
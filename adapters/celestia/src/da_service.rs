@@ -13,9 +13,11 @@ use jsonrpsee::core::client::Subscription;
 use jsonrpsee::http_client::{HeaderMap, HttpClient};
 use pin_project::pin_project;
 use sov_rollup_interface::da::CountedBufReader;
-use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::services::da::{DaService, FeeEstimate};
+use sov_rollup_interface::Buf;
 use tracing::{debug, info, instrument, trace};
 
+use crate::envelope::{self, RollupId};
 use crate::shares::Blob;
 use crate::types::FilteredCelestiaBlock;
 use crate::utils::BoxError;
@@ -32,6 +34,8 @@ pub struct CelestiaService {
     client: HttpClient,
     rollup_batch_namespace: Namespace,
     rollup_proof_namespace: Namespace,
+    rollup_id: Option<RollupId>,
+    max_blob_size: usize,
 }
 
 impl CelestiaService {
@@ -44,8 +48,17 @@ impl CelestiaService {
             client,
             rollup_batch_namespace,
             rollup_proof_namespace,
+            rollup_id: None,
+            max_blob_size: default_max_blob_size(),
         }
     }
+
+    /// Shares the namespaces with other rollups, prefixing the blobs with the id of the rollup,
+    /// see [`crate::envelope`].
+    pub fn with_rollup_id(mut self, rollup_id: RollupId) -> Self {
+        self.rollup_id = Some(rollup_id);
+        self
+    }
 }
 
 /// Runtime configuration for the [`DaService`] implementation.
@@ -62,6 +75,10 @@ pub struct CelestiaConfig {
     /// The timeout for a Celestia RPC request, in seconds
     #[serde(default = "default_request_timeout_seconds")]
     pub celestia_rpc_timeout_seconds: u64,
+    /// The largest blob submitted in a single Celestia transaction, in bytes.
+    /// Larger batches are split across several transactions.
+    #[serde(default = "default_max_blob_size")]
+    pub max_blob_size: usize,
 }
 
 fn default_rpc_addr() -> String {
@@ -76,6 +93,11 @@ const fn default_request_timeout_seconds() -> u64 {
     60
 }
 
+const fn default_max_blob_size() -> usize {
+    // Leaves room for the rest of the transaction in a 2 MB Celestia block
+    1_900_000
+}
+
 impl CelestiaService {
     pub async fn new(config: CelestiaConfig, chain_params: RollupParams) -> Self {
         let client = {
@@ -97,11 +119,15 @@ impl CelestiaService {
         }
         .expect("Client initialization is valid");
 
-        Self::with_client(
-            client,
-            chain_params.rollup_batch_namespace,
-            chain_params.rollup_proof_namespace,
-        )
+        Self {
+            max_blob_size: config.max_blob_size,
+            rollup_id: chain_params.rollup_id,
+            ..Self::with_client(
+                client,
+                chain_params.rollup_batch_namespace,
+                chain_params.rollup_proof_namespace,
+            )
+        }
     }
 }
 
@@ -201,8 +227,12 @@ impl DaService for CelestiaService {
         &self,
         block: &Self::FilteredBlock,
     ) -> Vec<<Self::Spec as sov_rollup_interface::da::DaSpec>::BlobTransaction> {
+        let rollup_id = self.rollup_id.as_ref();
         let mut output = Vec::new();
         for blob_ref in block.rollup_data.blobs() {
+            if !envelope::is_relevant(rollup_id, blob_ref.data()) {
+                continue;
+            }
             let commitment = Commitment::from_shares(self.rollup_batch_namespace, blob_ref.0)
                 .expect("blob must be valid");
             info!("Blob: {:?}", commitment);
@@ -215,9 +245,12 @@ impl DaService for CelestiaService {
                 .clone();
 
             let blob: Blob = blob_ref.into();
+            let mut blob = blob.into_iter();
+            // The rollup id isn't part of the batch
+            blob.advance(envelope::envelope_len(rollup_id));
 
             let blob_tx = BlobWithSender {
-                blob: CountedBufReader::new(blob.into_iter()),
+                blob: CountedBufReader::new(blob),
                 sender: sender.parse().expect("Incorrect sender address"),
                 hash: commitment.0,
             };
@@ -241,14 +274,50 @@ impl DaService for CelestiaService {
         (etx_proofs.0, rollup_row_proofs.0)
     }
 
-    #[instrument(skip_all, err)]
     async fn send_transaction(&self, blob: &[u8]) -> Result<(), Self::Error> {
-        debug!("Sending {} bytes of raw data to Celestia.", blob.len());
+        self.send_transaction_with_fee_rate(blob, GAS_PRICE as f64)
+            .await
+    }
+
+    fn max_blob_size(&self) -> Option<usize> {
+        // Leaves room for the envelope
+        Some(self.max_blob_size - envelope::envelope_len(self.rollup_id.as_ref()))
+    }
+
+    fn blob_location(&self) -> Option<String> {
+        Some(format!(
+            "namespace 0x{}",
+            hex::encode(self.rollup_batch_namespace.as_bytes())
+        ))
+    }
+
+    async fn estimate_fee(&self, blob_size: usize) -> Result<FeeEstimate, Self::Error> {
+        // The gas price isn't exposed by the node, so the minimum one is used
+        let blob_size = blob_size + envelope::envelope_len(self.rollup_id.as_ref());
+        let gas_limit = get_gas_limit_for_bytes(blob_size) as u64;
+        Ok(FeeEstimate {
+            fee_rate: GAS_PRICE as f64,
+            fee: gas_limit * GAS_PRICE as u64,
+        })
+    }
+
+    #[instrument(skip_all, err)]
+    async fn send_transaction_with_fee_rate(
+        &self,
+        blob: &[u8],
+        fee_rate: f64,
+    ) -> Result<(), Self::Error> {
+        debug!(
+            "Sending {} bytes of raw data to Celestia, at {} utia/gas.",
+            blob.len(),
+            fee_rate
+        );
 
+        let blob = envelope::wrap_blob(self.rollup_id.as_ref(), blob);
         let gas_limit = get_gas_limit_for_bytes(blob.len()) as u64;
-        let fee = gas_limit * GAS_PRICE as u64;
+        let fee = (gas_limit as f64 * fee_rate).ceil() as u64;
 
-        let blob = JsonBlob::new(self.rollup_batch_namespace, blob.to_vec())?;
+        let blob = JsonBlob::new(self.rollup_batch_namespace, blob)?;
         info!("Submitting: {:?}", blob.commitment);
 
         let height = self
@@ -269,9 +338,10 @@ impl DaService for CelestiaService {
     }
 
     async fn send_aggregated_zk_proof(&self, aggregated_proof: &[u8]) -> Result<u64, Self::Error> {
+        let aggregated_proof = envelope::wrap_blob(self.rollup_id.as_ref(), aggregated_proof);
         let gas_limit = get_gas_limit_for_bytes(aggregated_proof.len()) as u64;
         let fee = gas_limit * GAS_PRICE as u64;
-        let blob = JsonBlob::new(self.rollup_proof_namespace, aggregated_proof.to_vec())?;
+        let blob = JsonBlob::new(self.rollup_proof_namespace, aggregated_proof)?;
 
         let height = self
             .client
@@ -293,7 +363,12 @@ impl DaService for CelestiaService {
             .blob_get_all(height, &[self.rollup_proof_namespace])
             .await?;
 
-        Ok(blobs.into_iter().map(|blob| blob.data).collect())
+        Ok(blobs
+            .into_iter()
+            .filter_map(|blob| {
+                envelope::unwrap_blob(self.rollup_id.as_ref(), &blob.data).map(<[u8]>::to_vec)
+            })
+            .collect())
     }
 }
 
@@ -315,13 +390,14 @@ mod tests {
     use celestia_types::nmt::Namespace;
     use celestia_types::{Blob as JsonBlob, NamespacedShares};
     use serde_json::json;
-    use sov_rollup_interface::da::{BlockHeaderTrait, DaVerifier};
+    use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaVerifier};
     use sov_rollup_interface::services::da::DaService;
     use wiremock::matchers::{bearer_token, body_json, method, path};
     use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
     use super::default_request_timeout_seconds;
     use crate::da_service::{get_gas_limit_for_bytes, CelestiaConfig, CelestiaService, GAS_PRICE};
+    use crate::envelope::{RollupId, ROLLUP_ID_LEN};
     use crate::parse_pfb_namespace;
     use crate::shares::NamespaceGroup;
     use crate::types::tests::{with_rollup_data, without_rollup_data};
@@ -372,12 +448,14 @@ mod tests {
             celestia_rpc_address: mock_server.uri(),
             max_celestia_response_body_size: 120_000,
             celestia_rpc_timeout_seconds: timeout_sec,
+            max_blob_size: default_max_blob_size(),
         };
         let rollup_batch_namespace = Namespace::new_v0(b"sov-test").unwrap();
         let rollup_proof_namespace = Namespace::new_v0(b"sov-proof").unwrap();
         let params = RollupParams {
             rollup_batch_namespace,
             rollup_proof_namespace,
+            rollup_id: None,
         };
 
         let da_service = CelestiaService::new(config.clone(), params.clone()).await;
@@ -644,6 +722,57 @@ mod tests {
         assert!(error.to_string().contains("more proofs than blobs"));
     }
 
+    #[tokio::test]
+    async fn verification_filters_blobs_by_rollup_id() {
+        let block = with_rollup_data::filtered_block();
+        // The rollup whose id is the beginning of the blob in the namespace
+        let blob_prefix: Vec<u8> = block
+            .rollup_data
+            .blobs()
+            .next()
+            .unwrap()
+            .data()
+            .take(ROLLUP_ID_LEN)
+            .collect();
+        let rollup_id = RollupId::new(blob_prefix.try_into().unwrap());
+        let other_rollup_id = RollupId::new(*b"rollup-b");
+
+        for (rollup_id, expected_blob_lens) in [
+            (rollup_id, vec![252 - ROLLUP_ID_LEN]),
+            (other_rollup_id, vec![]),
+        ] {
+            let (_, _, da_service, rollup_params) = setup_service(None).await;
+            let da_service = da_service.with_rollup_id(rollup_id);
+
+            let txs = da_service.extract_relevant_blobs(&block);
+            let blob_lens: Vec<_> = txs.iter().map(|tx| tx.total_len()).collect();
+            assert_eq!(expected_blob_lens, blob_lens);
+            let (correctness_proof, completeness_proof) =
+                da_service.get_extraction_proof(&block, &txs).await;
+
+            let verifier = CelestiaVerifier::new(RollupParams {
+                rollup_id: Some(rollup_id),
+                ..rollup_params
+            });
+            verifier
+                .verify_relevant_tx_list(&block.header, &txs, correctness_proof, completeness_proof)
+                .unwrap();
+        }
+
+        // The blobs of other rollups can't be passed as relevant
+        let (_, _, da_service, rollup_params) = setup_service(None).await;
+        let txs = da_service.extract_relevant_blobs(&block);
+        let (correctness_proof, completeness_proof) =
+            da_service.get_extraction_proof(&block, &txs).await;
+        let verifier = CelestiaVerifier::new(RollupParams {
+            rollup_id: Some(other_rollup_id),
+            ..rollup_params
+        });
+        assert!(verifier
+            .verify_relevant_tx_list(&block.header, &txs, correctness_proof, completeness_proof)
+            .is_err());
+    }
+
     #[tokio::test]
     #[should_panic]
     async fn verification_fails_for_incorrect_namespace() {
@@ -658,6 +787,7 @@ mod tests {
         let verifier = CelestiaVerifier::new(RollupParams {
             rollup_batch_namespace: Namespace::new_v0(b"abc").unwrap(),
             rollup_proof_namespace: Namespace::new_v0(b"xyz").unwrap(),
+            rollup_id: None,
         });
 
         let _panics = verifier.verify_relevant_tx_list(
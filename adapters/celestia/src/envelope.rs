@@ -0,0 +1,89 @@
+//! The envelope of the blobs of rollups sharing a Celestia namespace.
+//!
+//! When several rollups post to the same namespace, each of them prefixes its blobs with its
+//! [`RollupId`]. The blobs of the namespace are still all proven complete, and each rollup only
+//! keeps the ones starting with its own id, which only depends on the data of the blobs.
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::Buf;
+
+/// The length of a [`RollupId`], in bytes.
+pub const ROLLUP_ID_LEN: usize = 8;
+
+/// The identifier of a rollup sharing its namespace with other rollups,
+/// prefixed to the data of each of its blobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RollupId(pub [u8; ROLLUP_ID_LEN]);
+
+impl RollupId {
+    /// Creates the id of a rollup from its bytes.
+    pub const fn new(id: [u8; ROLLUP_ID_LEN]) -> Self {
+        Self(id)
+    }
+
+    /// Returns whether the blob data starts with this id.
+    pub fn is_prefix_of(&self, mut data: impl Buf) -> bool {
+        if data.remaining() < ROLLUP_ID_LEN {
+            return false;
+        }
+        let mut prefix = [0; ROLLUP_ID_LEN];
+        data.copy_to_slice(&mut prefix);
+        prefix == self.0
+    }
+}
+
+/// Returns the length of the envelope of the blobs of a rollup: the length of its id if it
+/// shares its namespace, otherwise 0.
+pub fn envelope_len(rollup_id: Option<&RollupId>) -> usize {
+    match rollup_id {
+        Some(_) => ROLLUP_ID_LEN,
+        None => 0,
+    }
+}
+
+/// Wraps the data of a blob in the envelope of the rollup.
+pub fn wrap_blob(rollup_id: Option<&RollupId>, data: &[u8]) -> Vec<u8> {
+    match rollup_id {
+        Some(rollup_id) => [&rollup_id.0[..], data].concat(),
+        None => data.to_vec(),
+    }
+}
+
+/// Returns the data of the blob without its envelope, or `None` if the blob belongs to another
+/// rollup of the namespace.
+pub fn unwrap_blob<'a>(rollup_id: Option<&RollupId>, data: &'a [u8]) -> Option<&'a [u8]> {
+    match rollup_id {
+        Some(rollup_id) => data.strip_prefix(&rollup_id.0[..]),
+        None => Some(data),
+    }
+}
+
+/// Returns whether the blob data belongs to the rollup.
+pub fn is_relevant(rollup_id: Option<&RollupId>, data: impl Buf) -> bool {
+    match rollup_id {
+        Some(rollup_id) => rollup_id.is_prefix_of(data),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROLLUP_ID: RollupId = RollupId::new(*b"rollup-a");
+    const OTHER_ROLLUP_ID: RollupId = RollupId::new(*b"rollup-b");
+
+    #[test]
+    fn blobs_are_filtered_by_rollup_id() {
+        let blob = wrap_blob(Some(&ROLLUP_ID), b"batch");
+        assert_eq!(b"rollup-abatch", blob.as_slice());
+
+        assert!(is_relevant(Some(&ROLLUP_ID), blob.as_slice()));
+        assert!(!is_relevant(Some(&OTHER_ROLLUP_ID), blob.as_slice()));
+        assert!(!is_relevant(Some(&ROLLUP_ID), &b"rollup"[..]));
+        assert!(is_relevant(None, blob.as_slice()));
+
+        assert_eq!(Some(&b"batch"[..]), unwrap_blob(Some(&ROLLUP_ID), &blob));
+        assert_eq!(None, unwrap_blob(Some(&OTHER_ROLLUP_ID), &blob));
+        assert_eq!(Some(blob.as_slice()), unwrap_blob(None, &blob));
+    }
+}
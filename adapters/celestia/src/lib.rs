@@ -1,6 +1,7 @@
 pub mod celestia;
 #[cfg(feature = "native")]
 mod da_service;
+pub mod envelope;
 pub mod shares;
 pub mod types;
 mod utils;
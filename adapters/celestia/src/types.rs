@@ -134,6 +134,9 @@ pub enum ValidationError {
     #[error("Transaction missing")]
     MissingTx,
 
+    #[error("Transaction not found in the rollup namespace")]
+    UnexpectedTx,
+
     #[error("Invalid row proof")]
     InvalidRowProof,
 
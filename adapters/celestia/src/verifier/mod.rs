@@ -18,6 +18,7 @@ use sov_zk_cycle_macros::cycle_tracker;
 
 use self::address::CelestiaAddress;
 use self::proofs::*;
+use crate::envelope::{self, RollupId};
 use crate::shares::{NamespaceGroup, Share};
 use crate::types::ValidationError;
 use crate::utils::read_varint;
@@ -25,6 +26,9 @@ use crate::{pfb_from_iter, BlobWithSender, CelestiaHeader};
 
 pub struct CelestiaVerifier {
     pub rollup_namespace: Namespace,
+    /// The id of the rollup if it shares its namespace with other rollups.
+    /// Only the blobs prefixed with it are relevant, see [`crate::envelope`].
+    pub rollup_id: Option<RollupId>,
 }
 
 pub const PFB_NAMESPACE: Namespace = Namespace::const_v0([0, 0, 0, 0, 0, 0, 0, 0, 0, 4]);
@@ -127,6 +131,8 @@ impl DaSpec for CelestiaSpec {
 pub struct RollupParams {
     pub rollup_batch_namespace: Namespace,
     pub rollup_proof_namespace: Namespace,
+    /// The id prefixed to the blobs of the rollup, if its namespaces are shared with other rollups.
+    pub rollup_id: Option<RollupId>,
 }
 
 #[derive(
@@ -171,6 +177,7 @@ impl da::DaVerifier for CelestiaVerifier {
     fn new(params: <Self::Spec as DaSpec>::ChainParams) -> Self {
         Self {
             rollup_namespace: params.rollup_batch_namespace,
+            rollup_id: params.rollup_id,
         }
     }
 
@@ -204,7 +211,14 @@ impl da::DaVerifier for CelestiaVerifier {
         let mut tx_iter = txs.iter();
         let mut tx_proofs = inclusion_proof.into_iter();
         let square_size = block_header.dah.row_roots.len();
+        let rollup_id = self.rollup_id.as_ref();
         for blob in verified_shares.blobs() {
+            // The blobs of the other rollups sharing the namespace are skipped. Since all the
+            // blobs of the namespace are proven complete, none of the rollup's can be skipped.
+            if !envelope::is_relevant(rollup_id, blob.data()) {
+                continue;
+            }
+
             // Get the etx proof for this blob
             let Some(tx_proof) = tx_proofs.next() else {
                 return Err(ValidationError::InvalidEtxProof("not all blobs proven"));
@@ -275,6 +289,7 @@ impl da::DaVerifier for CelestiaVerifier {
                 let blob_ref = blob.clone();
 
                 let mut blob_iter = blob_ref.data();
+                blob_iter.advance(envelope::envelope_len(rollup_id));
                 let mut blob_data = vec![0; blob_iter.remaining()];
                 blob_iter.copy_to_slice(blob_data.as_mut_slice());
 
@@ -301,6 +316,9 @@ impl da::DaVerifier for CelestiaVerifier {
         if tx_proofs.next().is_some() {
             return Err(ValidationError::InvalidEtxProof("more proofs than blobs"));
         }
+        if tx_iter.next().is_some() {
+            return Err(ValidationError::UnexpectedTx);
+        }
 
         Ok(validity_condition)
     }
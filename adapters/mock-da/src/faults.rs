@@ -0,0 +1,87 @@
+//! Faults which the [`MockDaService`](crate::MockDaService) can inject into the blocks it produces,
+//! to test how a rollup handles a misbehaving DA layer.
+use serde::{Deserialize, Serialize};
+
+/// A fault applied to a blob submitted to the [`MockDaService`](crate::MockDaService).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fault {
+    /// The blob is included after the `blocks` next submitted blobs.
+    Delay {
+        /// The number of submitted blobs included before the blob.
+        blocks: u64,
+    },
+    /// The blob is swapped with the next submitted blob, like a delay of one block.
+    Reorder,
+    /// The blob is included `copies + 1` times, in consecutive blocks.
+    Duplicate {
+        /// The number of additional copies of the blob.
+        copies: u64,
+    },
+    /// The last `depth` blocks are replaced with blocks containing `blobs`, one blob per block,
+    /// before the blob is included.
+    Reorg {
+        /// The number of blocks reverted.
+        depth: u64,
+        /// The blobs of the blocks replacing the reverted ones.
+        #[serde(default)]
+        blobs: Vec<Vec<u8>>,
+        /// Whether the reorganization can revert finalized blocks.
+        #[serde(default)]
+        reverts_finalized: bool,
+    },
+    /// The blob is padded with zeros to `size` bytes and included despite the maximum blob size,
+    /// like a blob posted by a faulty sequencer.
+    Oversize {
+        /// The size of the included blob, in bytes.
+        size: usize,
+    },
+    /// The submission fails, and the blob isn't included.
+    Reject,
+}
+
+/// A [`Fault`] scheduled at a height of the mock DA layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledFault {
+    /// The fault is applied to the first blob submitted once the head of the DA layer is at
+    /// `at_height - 1`, or higher: the one which would be included at `at_height`.
+    /// A height of 0 applies it to the next submitted blob.
+    pub at_height: u64,
+    /// The fault.
+    pub fault: Fault,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fault_serialization() {
+        let faults = vec![
+            ScheduledFault {
+                at_height: 3,
+                fault: Fault::Delay { blocks: 2 },
+            },
+            ScheduledFault {
+                at_height: 5,
+                fault: Fault::Reorder,
+            },
+            ScheduledFault {
+                at_height: 8,
+                fault: Fault::Reorg {
+                    depth: 2,
+                    blobs: vec![],
+                    reverts_finalized: false,
+                },
+            },
+        ];
+        let json = r#"[
+            {"at_height": 3, "fault": {"delay": {"blocks": 2}}},
+            {"at_height": 5, "fault": "reorder"},
+            {"at_height": 8, "fault": {"reorg": {"depth": 2}}}
+        ]"#;
+
+        let parsed: Vec<ScheduledFault> = serde_json::from_str(json).unwrap();
+        assert_eq!(faults, parsed);
+    }
+}
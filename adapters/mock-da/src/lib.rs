@@ -1,6 +1,7 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+mod faults;
 #[cfg(feature = "native")]
 mod service;
 mod types;
@@ -8,6 +9,7 @@ mod validity_condition;
 /// Contains DaSpec and DaVerifier
 pub mod verifier;
 
+pub use faults::*;
 #[cfg(feature = "native")]
 pub use service::*;
 pub use types::*;
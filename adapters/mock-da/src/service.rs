@@ -9,13 +9,13 @@ use pin_project::pin_project;
 use sha2::Digest;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, Time};
 use sov_rollup_interface::maybestd::sync::Arc;
-use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::services::da::{DaService, FeeEstimate, SlotData};
 use tokio::sync::{broadcast, RwLock, RwLockWriteGuard};
 use tokio::time;
 
 use crate::types::{MockAddress, MockBlob, MockBlock, MockDaVerifier};
 use crate::verifier::MockDaSpec;
-use crate::{MockBlockHeader, MockHash};
+use crate::{Fault, MockBlockHeader, MockHash, ScheduledFault};
 
 const GENESIS_HEADER: MockBlockHeader = MockBlockHeader {
     prev_hash: MockHash([0; 32]),
@@ -30,6 +30,7 @@ pub struct PlannedFork {
     trigger_at_height: u64,
     fork_height: u64,
     blobs: Vec<Vec<u8>>,
+    reverts_finalized: bool,
 }
 
 impl PlannedFork {
@@ -52,8 +53,16 @@ impl PlannedFork {
             trigger_at_height,
             fork_height,
             blobs,
+            reverts_finalized: false,
         }
     }
+
+    /// Allows the fork to revert finalized blocks, like a reorganization
+    /// deeper than the finality of the DA layer would.
+    pub fn reverting_finalized(mut self) -> Self {
+        self.reverts_finalized = true;
+        self
+    }
 }
 
 #[derive(Clone)]
@@ -70,6 +79,14 @@ pub struct MockDaService {
     finalized_header_sender: broadcast::Sender<MockBlockHeader>,
     wait_attempts: usize,
     planned_fork: Arc<Mutex<Option<PlannedFork>>>,
+    /// The fee rate returned by [`DaService::estimate_fee`], per byte of blob.
+    fee_rate: Arc<Mutex<f64>>,
+    /// The maximum size of the blobs accepted by [`DaService::send_transaction`].
+    max_blob_size: Option<usize>,
+    /// The faults not applied yet, see [`MockDaService::schedule_fault`].
+    faults: Arc<Mutex<Vec<ScheduledFault>>>,
+    /// The delayed blobs, with the number of submitted blobs to include before them.
+    delayed_blobs: Arc<Mutex<Vec<(u64, Vec<u8>)>>>,
 }
 
 impl MockDaService {
@@ -95,7 +112,39 @@ impl MockDaService {
             finalized_header_sender: tx,
             wait_attempts: 100_0000,
             planned_fork: Arc::new(Mutex::new(None)),
+            fee_rate: Arc::new(Mutex::new(1.0)),
+            max_blob_size: None,
+            faults: Arc::new(Mutex::new(Vec::new())),
+            delayed_blobs: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Schedules the given faults, like [`MockDaService::schedule_fault`].
+    pub fn with_faults(self, faults: Vec<ScheduledFault>) -> Self {
+        for ScheduledFault { at_height, fault } in faults {
+            self.schedule_fault(at_height, fault);
         }
+        self
+    }
+
+    /// Applies the fault to the first blob submitted once the head of the DA layer is at
+    /// `at_height - 1` or higher, the one which would be included at `at_height`.
+    /// Several faults can apply to the same blob.
+    pub fn schedule_fault(&self, at_height: u64, fault: Fault) {
+        let mut faults = self.faults.lock().unwrap();
+        faults.push(ScheduledFault { at_height, fault });
+    }
+
+    /// Applies the fault to the next submitted blob.
+    pub fn inject_fault(&self, fault: Fault) {
+        self.schedule_fault(0, fault);
+    }
+
+    /// Makes the service reject the blobs larger than `max_blob_size`, like a DA layer
+    /// with a limit on the size of its transactions.
+    pub fn with_max_blob_size(mut self, max_blob_size: usize) -> Self {
+        self.max_blob_size = Some(max_blob_size);
+        self
     }
 
     /// Get sequencer address
@@ -103,6 +152,12 @@ impl MockDaService {
         self.sequencer_da_address
     }
 
+    /// Sets the fee rate returned by [`DaService::estimate_fee`], per byte of blob.
+    /// Blobs are accepted whatever the fee they pay.
+    pub fn set_fee_rate(&self, fee_rate: f64) {
+        *self.fee_rate.lock().unwrap() = fee_rate;
+    }
+
     /// Change number of wait attempts before giving up on waiting for block
     pub fn set_wait_attempts(&mut self, wait_attempts: usize) {
         self.wait_attempts = wait_attempts;
@@ -134,9 +189,36 @@ impl MockDaService {
     /// New blobs will be added **after** specified height,
     /// meaning that first blob will be in the block of height + 1.
     pub async fn fork_at(&self, height: u64, blobs: Vec<Vec<u8>>) -> anyhow::Result<()> {
+        self.rewrite_blocks_after(height, blobs, false).await
+    }
+
+    /// Rewrites existing blocks with given blocks like [`MockDaService::fork_at`],
+    /// including the finalized ones.
+    /// It simulates a reorganization deeper than the finality of the DA layer.
+    pub async fn fork_finalized_at(&self, height: u64, blobs: Vec<Vec<u8>>) -> anyhow::Result<()> {
+        self.rewrite_blocks_after(height, blobs, true).await
+    }
+
+    async fn rewrite_blocks_after(
+        &self,
+        height: u64,
+        blobs: Vec<Vec<u8>>,
+        reverts_finalized: bool,
+    ) -> anyhow::Result<()> {
         let mut blocks = self.blocks.write().await;
-        let last_finalized_height = self.get_last_finalized_height(&blocks).await;
-        if last_finalized_height > height {
+        self.rewrite_blocks(height, blobs, reverts_finalized, &mut blocks)
+            .await
+    }
+
+    async fn rewrite_blocks(
+        &self,
+        height: u64,
+        blobs: Vec<Vec<u8>>,
+        reverts_finalized: bool,
+        blocks: &mut RwLockWriteGuard<'_, VecDeque<MockBlock>>,
+    ) -> anyhow::Result<()> {
+        let last_finalized_height = self.get_last_finalized_height(blocks).await;
+        if last_finalized_height > height && !reverts_finalized {
             anyhow::bail!(
                 "Cannot fork at height {}, last finalized height is {}",
                 height,
@@ -145,6 +227,91 @@ impl MockDaService {
         }
         blocks.retain(|b| b.header().height <= height);
         for blob in blobs {
+            let _ = self.add_blob(&blob, Default::default(), blocks).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Submits the blob, applying the faults due at the height it would be included at.
+    async fn submit_blob(&self, blob: &[u8]) -> anyhow::Result<()> {
+        let mut blocks = self.blocks.write().await;
+        let next_height = blocks
+            .back()
+            .map_or(GENESIS_HEADER.height(), |b| b.header().height())
+            + 1;
+        let faults = {
+            let mut faults = self.faults.lock().unwrap();
+            let (due, pending): (Vec<_>, Vec<_>) = faults
+                .drain(..)
+                .partition(|fault| fault.at_height <= next_height);
+            *faults = pending;
+            due
+        };
+
+        let mut blob = blob.to_vec();
+        let mut oversized = false;
+        let mut copies = 1;
+        let mut delay = 0;
+        for ScheduledFault { fault, .. } in faults {
+            tracing::debug!(?fault, height = next_height, "Injecting fault");
+            match fault {
+                Fault::Delay { blocks } => delay = blocks,
+                Fault::Reorder => delay = 1,
+                Fault::Duplicate { copies: extra } => copies += extra,
+                Fault::Reorg {
+                    depth,
+                    blobs,
+                    reverts_finalized,
+                } => {
+                    let head_height = next_height - 1;
+                    self.rewrite_blocks(
+                        head_height.saturating_sub(depth),
+                        blobs,
+                        reverts_finalized,
+                        &mut blocks,
+                    )
+                    .await?;
+                }
+                Fault::Oversize { size } => {
+                    blob.resize(size.max(blob.len()), 0);
+                    oversized = true;
+                }
+                Fault::Reject => anyhow::bail!("The submission was rejected by an injected fault"),
+            }
+        }
+        if let (Some(max_blob_size), false) = (self.max_blob_size, oversized) {
+            anyhow::ensure!(
+                blob.len() <= max_blob_size,
+                "Blob of {} bytes exceeds the maximum blob size of {} bytes",
+                blob.len(),
+                max_blob_size
+            );
+        }
+
+        // The blobs delayed by earlier submissions are included after this one
+        let released = {
+            let mut delayed_blobs = self.delayed_blobs.lock().unwrap();
+            for (remaining, _) in delayed_blobs.iter_mut() {
+                *remaining -= 1;
+            }
+            let (released, delayed): (Vec<_>, Vec<_>) = delayed_blobs
+                .drain(..)
+                .partition(|(remaining, _)| *remaining == 0);
+            *delayed_blobs = delayed;
+            if delay > 0 {
+                delayed_blobs.extend((0..copies).map(|_| (delay, blob.clone())));
+            }
+            released
+        };
+        if delay == 0 {
+            for _ in 0..copies {
+                let _ = self
+                    .add_blob(&blob, Default::default(), &mut blocks)
+                    .await?;
+            }
+        }
+        for (_, blob) in released {
             let _ = self
                 .add_blob(&blob, Default::default(), &mut blocks)
                 .await?;
@@ -159,7 +326,8 @@ impl MockDaService {
             let blocks = self.blocks.write().await;
             self.get_last_finalized_height(&blocks).await
         };
-        if last_finalized_height > planned_fork.trigger_at_height {
+        if last_finalized_height > planned_fork.trigger_at_height && !planned_fork.reverts_finalized
+        {
             anyhow::bail!(
                 "Cannot fork at height {}, last finalized height is {}",
                 planned_fork.trigger_at_height,
@@ -244,8 +412,12 @@ impl MockDaService {
             }
         };
         if let Some(planned_fork_now) = planned_fork_now {
-            self.fork_at(planned_fork_now.fork_height, planned_fork_now.blobs)
-                .await?;
+            self.rewrite_blocks_after(
+                planned_fork_now.fork_height,
+                planned_fork_now.blobs,
+                planned_fork_now.reverts_finalized,
+            )
+            .await?;
         }
         Ok(())
     }
@@ -361,9 +533,35 @@ impl DaService for MockDaService {
     }
 
     async fn send_transaction(&self, blob: &[u8]) -> Result<(), Self::Error> {
+        self.submit_blob(blob).await
+    }
+
+    async fn is_transaction_included(&self, _tx_id: &()) -> Result<bool, Self::Error> {
+        Ok(self.delayed_blobs.lock().unwrap().is_empty())
+    }
+
+    /// Includes the delayed blobs right away, as if the bumped fee got them picked up.
+    async fn bump_fee(&self, tx_id: (), _fee_rate: f64) -> Result<(), Self::Error> {
         let mut blocks = self.blocks.write().await;
-        let _ = self.add_blob(blob, Default::default(), &mut blocks).await?;
-        Ok(())
+        let released = std::mem::take(&mut *self.delayed_blobs.lock().unwrap());
+        for (_, blob) in released {
+            let _ = self
+                .add_blob(&blob, Default::default(), &mut blocks)
+                .await?;
+        }
+        Ok(tx_id)
+    }
+
+    fn max_blob_size(&self) -> Option<usize> {
+        self.max_blob_size
+    }
+
+    async fn estimate_fee(&self, blob_size: usize) -> Result<FeeEstimate, Self::Error> {
+        let fee_rate = *self.fee_rate.lock().unwrap();
+        Ok(FeeEstimate {
+            fee_rate,
+            fee: (blob_size as f64 * fee_rate).ceil() as u64,
+        })
     }
 
     async fn send_aggregated_zk_proof(&self, proof: &[u8]) -> Result<u64, Self::Error> {
@@ -721,6 +919,39 @@ mod tests {
             assert_ne!(block_3_after, block_3_after_reorg);
         }
 
+        #[tokio::test]
+        async fn test_reorg_finalized() {
+            let da = MockDaService::with_finality(MockAddress::new([1; 32]), 2);
+
+            // 1 -> 2 -> 3 -> 4
+            for blob in [[1, 2, 3, 4], [4, 5, 6, 7], [8, 9, 0, 1], [2, 3, 4, 5]] {
+                da.send_transaction(&blob).await.unwrap();
+            }
+            let block_1_before = da.get_block_at(1).await.unwrap();
+            let block_2_before = da.get_block_at(2).await.unwrap();
+
+            // 1 -> 2' -> 3' -> 4', reverting the finalized block 2
+            da.fork_finalized_at(
+                1,
+                vec![vec![3, 3, 3, 3], vec![4, 4, 4, 4], vec![5, 5, 5, 5]],
+            )
+            .await
+            .unwrap();
+
+            let block_1_after = da.get_block_at(1).await.unwrap();
+            let block_2_after = da.get_block_at(2).await.unwrap();
+            assert_eq!(block_1_before, block_1_after);
+            assert_ne!(block_2_before, block_2_after);
+            assert_eq!(
+                block_1_after.header().hash(),
+                block_2_after.header().prev_hash()
+            );
+            assert_eq!(
+                block_2_after.header(),
+                &da.get_last_finalized_block_header().await.unwrap()
+            );
+        }
+
         #[tokio::test]
         async fn test_planned_reorg() {
             let mut da = MockDaService::with_finality(MockAddress::new([1; 32]), 4);
@@ -794,6 +1025,118 @@ mod tests {
         }
     }
 
+    mod fault_injection {
+        use super::*;
+
+        async fn blobs_up_to(da: &MockDaService, height: u64) -> Vec<Vec<u8>> {
+            let mut blobs = Vec::new();
+            for height in 1..=height {
+                let mut block = da.get_block_at(height).await.unwrap();
+                blobs.push(block.blobs[0].full_data().to_vec());
+            }
+            blobs
+        }
+
+        #[tokio::test]
+        async fn test_delayed_reordered_and_duplicated_blobs() {
+            let mut da = MockDaService::new(MockAddress::new([1; 32])).with_faults(vec![
+                ScheduledFault {
+                    at_height: 1,
+                    fault: Fault::Delay { blocks: 2 },
+                },
+                ScheduledFault {
+                    at_height: 4,
+                    fault: Fault::Reorder,
+                },
+            ]);
+            da.wait_attempts = 2;
+
+            for blob in [[1], [2], [3], [4], [5]] {
+                da.send_transaction(&blob).await.unwrap();
+            }
+            assert_eq!(
+                vec![vec![2], vec![3], vec![1], vec![5], vec![4]],
+                blobs_up_to(&da, 5).await
+            );
+
+            da.inject_fault(Fault::Duplicate { copies: 2 });
+            da.send_transaction(&[6]).await.unwrap();
+            da.send_transaction(&[7]).await.unwrap();
+            assert_eq!(
+                vec![vec![6], vec![6], vec![6], vec![7]],
+                blobs_up_to(&da, 9).await[5..]
+            );
+            for height in 2..=9 {
+                assert_consecutive_blocks(
+                    &da.get_block_at(height - 1).await.unwrap(),
+                    &da.get_block_at(height).await.unwrap(),
+                );
+            }
+        }
+
+        #[tokio::test]
+        async fn test_injected_reorg() {
+            let mut da = MockDaService::with_finality(MockAddress::new([1; 32]), 2);
+            da.wait_attempts = 2;
+            for blob in [[1], [2], [3], [4]] {
+                da.send_transaction(&blob).await.unwrap();
+            }
+            let block_2 = da.get_block_at(2).await.unwrap();
+            let block_3_before = da.get_block_at(3).await.unwrap();
+
+            // Blocks 3 and 4 are reverted, and replaced with a single block
+            da.inject_fault(Fault::Reorg {
+                depth: 2,
+                blobs: vec![vec![13]],
+                reverts_finalized: false,
+            });
+            da.send_transaction(&[5]).await.unwrap();
+            assert_eq!(
+                vec![vec![1], vec![2], vec![13], vec![5]],
+                blobs_up_to(&da, 4).await
+            );
+            let block_3_after = da.get_block_at(3).await.unwrap();
+            assert_ne!(block_3_before, block_3_after);
+            assert_consecutive_blocks(&block_2, &block_3_after);
+
+            // Finalized blocks are only reverted if the fault allows it
+            da.inject_fault(Fault::Reorg {
+                depth: 3,
+                blobs: vec![],
+                reverts_finalized: false,
+            });
+            assert!(da.send_transaction(&[6]).await.is_err());
+            da.inject_fault(Fault::Reorg {
+                depth: 3,
+                blobs: vec![],
+                reverts_finalized: true,
+            });
+            da.send_transaction(&[6]).await.unwrap();
+            assert_eq!(vec![vec![1], vec![6]], blobs_up_to(&da, 2).await);
+            assert_eq!(2, da.get_head_block_header().await.unwrap().height());
+        }
+
+        #[tokio::test]
+        async fn test_oversized_and_rejected_blobs() {
+            let da = MockDaService::new(MockAddress::new([1; 32])).with_max_blob_size(4);
+
+            da.inject_fault(Fault::Reject);
+            assert_eq!(
+                "The submission was rejected by an injected fault",
+                da.send_transaction(&[1]).await.unwrap_err().to_string()
+            );
+            assert_eq!(0, da.get_head_block_header().await.unwrap().height());
+
+            da.inject_fault(Fault::Oversize { size: 10 });
+            da.send_transaction(&[1]).await.unwrap();
+            assert!(da.send_transaction(&[1; 10]).await.is_err());
+            let mut block = da.get_block_at(1).await.unwrap();
+            let mut expected = vec![0; 10];
+            expected[0] = 1;
+            assert_eq!(expected, block.blobs[0].full_data().to_vec());
+        }
+    }
+
     fn assert_consecutive_blocks(block1: &MockBlock, block2: &MockBlock) {
         assert_eq!(block2.header().prev_hash(), block1.header().hash())
     }
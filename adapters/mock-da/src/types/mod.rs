@@ -11,6 +11,7 @@ use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::Bytes;
 
 use crate::validity_condition::MockValidityCond;
+use crate::ScheduledFault;
 
 /// A mock hash digest.
 #[derive(
@@ -135,6 +136,9 @@ impl BlockHeaderTrait for MockBlockHeader {
 pub struct MockDaConfig {
     /// The address to use to "submit" blobs on the mock da layer
     pub sender_address: MockAddress,
+    /// The faults injected into the blocks of the mock da layer, see [`Fault`](crate::Fault).
+    #[serde(default)]
+    pub faults: Vec<ScheduledFault>,
 }
 
 #[derive(Clone, Default)]
@@ -187,6 +191,22 @@ impl MockBlob {
     }
 }
 
+/// A mock deposit to a rollup bridge. Mock DA doesn't have native transactions, so the deposit
+/// is its own proof: it is accepted as long as it is made to the expected bridge.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct MockDeposit {
+    /// The hash of the block claimed to include the deposit.
+    pub block_hash: MockHash,
+    /// The bridge receiving the deposit.
+    pub bridge_script: Vec<u8>,
+    /// The unique identifier of the deposit.
+    pub id: [u8; 32],
+    /// The deposited amount.
+    pub amount: u64,
+    /// The raw rollup address credited with the deposit.
+    pub recipient: Vec<u8>,
+}
+
 /// A mock block type used for testing.
 #[derive(Serialize, Deserialize, Default, PartialEq, Debug, Clone)]
 pub struct MockBlock {
@@ -1,6 +1,9 @@
-use sov_rollup_interface::da::{BlobReaderTrait, DaSpec, DaVerifier};
+use borsh::BorshDeserialize;
+use sov_rollup_interface::da::{BlobReaderTrait, DaDeposit, DaSpec, DaVerifier};
 
-use crate::{MockAddress, MockBlob, MockBlockHeader, MockDaVerifier, MockHash, MockValidityCond};
+use crate::{
+    MockAddress, MockBlob, MockBlockHeader, MockDaVerifier, MockDeposit, MockHash, MockValidityCond,
+};
 
 impl BlobReaderTrait for MockBlob {
     type Address = MockAddress;
@@ -41,6 +44,23 @@ impl DaSpec for MockDaSpec {
     type InclusionMultiProof = [u8; 32];
     type CompletenessProof = ();
     type ChainParams = ();
+
+    fn verify_deposit(
+        bridge_script: &[u8],
+        proof: &[u8],
+    ) -> Result<DaDeposit<Self::SlotHash>, anyhow::Error> {
+        let deposit = MockDeposit::try_from_slice(proof)?;
+        anyhow::ensure!(
+            deposit.bridge_script == bridge_script,
+            "The deposit is made to another bridge"
+        );
+        Ok(DaDeposit {
+            block_hash: deposit.block_hash,
+            id: deposit.id,
+            amount: deposit.amount,
+            recipient: deposit.recipient,
+        })
+    }
 }
 
 impl DaVerifier for MockDaVerifier {
@@ -145,6 +145,20 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::Zkvm for MockZkv
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(bincode::deserialize(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: serde::Serialize + serde::de::DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<
+        sov_rollup_interface::zk::AggregatedStateTransition<Da, Root, Self::CodeCommitment>,
+        Self::Error,
+    > {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
 }
 
 impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
@@ -188,6 +202,7 @@ impl<ValidityCond: ValidityCondition> sov_rollup_interface::zk::ZkvmHost
                     initial_state_root: st.initial_state_root,
                     final_state_root: st.final_state_root,
                     slot_hash: st.da_block_header.hash(),
+                    prev_slot_hash: st.da_block_header.prev_hash(),
                     validity_condition: data.validity_condition,
                 })
             }
@@ -222,6 +237,19 @@ impl sov_rollup_interface::zk::Zkvm for MockZkGuest {
     ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
         unimplemented!()
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + serde::de::DeserializeOwned,
+    >(
+        _serialized_proof: &[u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<
+        sov_rollup_interface::zk::AggregatedStateTransition<Da, Root, Self::CodeCommitment>,
+        Self::Error,
+    > {
+        unimplemented!()
+    }
 }
 
 impl sov_rollup_interface::zk::ZkvmGuest for MockZkGuest {
@@ -54,7 +54,12 @@ impl<'a> Risc0Host<'a> {
             .build()
             .unwrap();
         let mut executor = ExecutorImpl::from_elf(env, self.elf)?;
-        Ok(executor.run()?)
+        let session = executor.run()?;
+        #[cfg(feature = "bench")]
+        if let Ok(path) = std::env::var(crate::metrics::PROFILE_REPORT_PATH_ENV) {
+            crate::metrics::write_profile_report(path)?;
+        }
+        Ok(session)
     }
     /// Run a computation in the zkvm and generate a receipt.
     pub fn run(&mut self) -> anyhow::Result<Receipt> {
@@ -135,6 +140,20 @@ impl<'host> Zkvm for Risc0Host<'host> {
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(risc0_zkvm::serde::from_slice(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<
+        sov_rollup_interface::zk::AggregatedStateTransition<Da, Root, Self::CodeCommitment>,
+        Self::Error,
+    > {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
 }
 
 /// A verifier for Risc0 proofs.
@@ -162,6 +181,20 @@ impl Zkvm for Risc0Verifier {
         let output = Self::verify(serialized_proof, code_commitment)?;
         Ok(risc0_zkvm::serde::from_slice(output)?)
     }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<
+        sov_rollup_interface::zk::AggregatedStateTransition<Da, Root, Self::CodeCommitment>,
+        Self::Error,
+    > {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(risc0_zkvm::serde::from_slice(output)?)
+    }
 }
 
 fn verify_from_slice<'a>(
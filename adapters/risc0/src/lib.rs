@@ -19,6 +19,13 @@ pub mod metrics;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Risc0MethodId([u32; 8]);
 
+impl Risc0MethodId {
+    /// Create a [`Risc0MethodId`] from the image ID generated by `risc0_build`.
+    pub fn new(id: [u32; 8]) -> Self {
+        Self(id)
+    }
+}
+
 impl Matches<Self> for Risc0MethodId {
     fn matches(&self, other: &Self) -> bool {
         self.0 == other.0
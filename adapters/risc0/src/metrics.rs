@@ -1,5 +1,7 @@
 //! Defines utilities for collecting runtime metrics from inside a Risc0 VM
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
 
 use anyhow::Context;
 use once_cell::sync::Lazy;
@@ -47,3 +49,50 @@ pub fn metrics_callback(input: Bytes) -> Result<Bytes, anyhow::Error> {
     add_value(met_tuple.0, met_tuple.1);
     Ok(Bytes::new())
 }
+
+/// The environment variable holding the path the profile report is written to after every
+/// execution of the guest, see [`write_profile_report`].
+pub const PROFILE_REPORT_PATH_ENV: &str = "SOV_CYCLE_PROFILE";
+
+/// Renders the metrics collected so far as a profile report, with one line per metric sorted
+/// by total cycles, so the most expensive hooks, module calls and state accesses come first.
+pub fn profile_report() -> String {
+    let mut metrics: Vec<(String, (u64, u64))> = GLOBAL_HASHMAP
+        .lock()
+        .iter()
+        .map(|(metric, values)| (metric.clone(), *values))
+        .collect();
+    metrics.sort_by(|(name_a, (total_a, _)), (name_b, (total_b, _))| {
+        total_b.cmp(total_a).then_with(|| name_a.cmp(name_b))
+    });
+
+    let name_width = metrics
+        .iter()
+        .map(|(metric, _)| metric.len())
+        .chain(["Metric".len()])
+        .max()
+        .unwrap_or_default();
+    let mut report = format!(
+        "{:<name_width$}  {:>16}  {:>10}  {:>16}\n",
+        "Metric", "Total cycles", "Calls", "Average cycles"
+    );
+    for (metric, (total, count)) in metrics {
+        writeln!(
+            report,
+            "{:<name_width$}  {:>16}  {:>10}  {:>16}",
+            metric,
+            total,
+            count,
+            total / count.max(1)
+        )
+        .expect("Writing to a string can't fail");
+    }
+    report
+}
+
+/// Writes the [`profile_report`] to the given path.
+pub fn write_profile_report(path: impl AsRef<Path>) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    std::fs::write(path, profile_report())
+        .with_context(|| format!("Failed to write the cycle profile to {}", path.display()))
+}
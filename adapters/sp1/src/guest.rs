@@ -0,0 +1,134 @@
+//! This module implements the `ZkvmGuest` trait for the SP1 VM.
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::zk::{Zkvm, ZkvmGuest};
+
+use crate::Sp1MethodId;
+
+#[cfg(target_os = "zkvm")]
+impl ZkvmGuest for Sp1Guest {
+    fn read_from_host<T: serde::de::DeserializeOwned>(&self) -> T {
+        sp1_zkvm::io::read()
+    }
+
+    fn commit<T: serde::Serialize>(&self, item: &T) {
+        sp1_zkvm::io::commit(item);
+    }
+}
+
+#[cfg(not(target_os = "zkvm"))]
+#[derive(Default)]
+struct Hints {
+    values: Vec<Vec<u8>>,
+    position: usize,
+}
+
+#[cfg(not(target_os = "zkvm"))]
+impl Hints {
+    pub fn with_hints(hints: Vec<Vec<u8>>) -> Self {
+        Hints {
+            values: hints,
+            position: 0,
+        }
+    }
+
+    fn next(&mut self) -> Option<&[u8]> {
+        let value = self.values.get(self.position)?;
+        self.position += 1;
+        Some(value)
+    }
+}
+
+/// A guest for the SP1 VM. When running in the SP1 environment, this struct
+/// implements the `ZkvmGuest` trait in terms of SP1's io::read and io::commit functions.
+/// When running in any other environment, the struct uses interior mutability to emulate
+/// the same functionality.
+#[derive(Default)]
+pub struct Sp1Guest {
+    #[cfg(not(target_os = "zkvm"))]
+    hints: std::sync::Mutex<Hints>,
+    #[cfg(not(target_os = "zkvm"))]
+    commits: std::sync::Mutex<Vec<u8>>,
+}
+
+impl Sp1Guest {
+    /// Constructs a new SP1 Guest
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new SP1 Guest with the provided hints. Every hint is
+    /// expected to be serialized with `bincode`, the same way `sp1_zkvm::io::read` expects it.
+    ///
+    /// This function is only available outside of SP1's environment.
+    #[cfg(not(target_os = "zkvm"))]
+    pub fn with_hints(hints: Vec<Vec<u8>>) -> Self {
+        Self {
+            hints: std::sync::Mutex::new(Hints::with_hints(hints)),
+            commits: Default::default(),
+        }
+    }
+
+    /// Returns all the values committed so far, serialized the same way
+    /// SP1 serializes the public values of a proof.
+    ///
+    /// This function is only available outside of SP1's environment.
+    #[cfg(not(target_os = "zkvm"))]
+    pub fn committed(&self) -> Vec<u8> {
+        self.commits.lock().unwrap().clone()
+    }
+}
+
+#[cfg(not(target_os = "zkvm"))]
+impl ZkvmGuest for Sp1Guest {
+    fn read_from_host<T: serde::de::DeserializeOwned>(&self) -> T {
+        let mut hints = self.hints.lock().unwrap();
+        let hint = hints
+            .next()
+            .expect("No more hints were provided by the host");
+        bincode::deserialize(hint).expect("Failed to deserialize the hint")
+    }
+
+    fn commit<T: serde::Serialize>(&self, item: &T) {
+        bincode::serialize_into(&mut *self.commits.lock().unwrap(), item)
+            .expect("Serialization to vec is infallible");
+    }
+}
+
+impl Zkvm for Sp1Guest {
+    type CodeCommitment = Sp1MethodId;
+
+    type Error = anyhow::Error;
+
+    fn verify<'a>(
+        _serialized_proof: &'a [u8],
+        _code_commitment: &Self::CodeCommitment,
+    ) -> Result<&'a [u8], Self::Error> {
+        anyhow::bail!("SP1 proofs can't be verified inside the SP1 VM by the adapter yet")
+    }
+
+    fn verify_and_extract_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<sov_rollup_interface::zk::StateTransition<Da, Root>, Self::Error> {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+
+    fn verify_and_extract_aggregated_output<
+        Da: sov_rollup_interface::da::DaSpec,
+        Root: Serialize + DeserializeOwned,
+    >(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<
+        sov_rollup_interface::zk::AggregatedStateTransition<Da, Root, Self::CodeCommitment>,
+        Self::Error,
+    > {
+        let output = Self::verify(serialized_proof, code_commitment)?;
+        Ok(bincode::deserialize(output)?)
+    }
+}
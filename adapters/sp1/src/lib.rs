@@ -0,0 +1,34 @@
+#![deny(missing_docs)]
+//! # SP1 Adapter
+//!
+//! This crate contains an adapter allowing Succinct's SP1 to be used as a proof system for
+//! Sovereign SDK rollups.
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::zk::Matches;
+
+pub mod guest;
+#[cfg(feature = "native")]
+pub mod host;
+
+/// Uniquely identifies an SP1 binary. Contains the serialized verifying key
+/// of the ELF file, which is needed to verify its proofs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sp1MethodId(Vec<u8>);
+
+impl Sp1MethodId {
+    /// Creates a method id from a serialized SP1 verifying key.
+    pub fn new(verifying_key: Vec<u8>) -> Self {
+        Self(verifying_key)
+    }
+
+    /// The serialized SP1 verifying key.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Matches<Self> for Sp1MethodId {
+    fn matches(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::zk::{Zkvm, ZkvmGuest, ZkvmHost};
+use sov_sp1_adapter::guest::Sp1Guest;
+use sov_sp1_adapter::host::Sp1Host;
+use sov_sp1_adapter::Sp1MethodId;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct TestStruct {
+    ints: Vec<i32>,
+    string: String,
+}
+
+#[test]
+fn test_hints_roundtrip() {
+    let mut host = Sp1Host::new(&[]);
+
+    let hint_a = TestStruct {
+        ints: vec![1, 2, 3, 4, 5],
+        string: "hello".to_string(),
+    };
+    let hint_b = TestStruct {
+        ints: vec![6, 7],
+        string: "world".to_string(),
+    };
+
+    host.add_hint(&hint_a);
+    host.add_hint(&hint_b);
+
+    let guest = host.simulate_with_hints();
+
+    let mut received: TestStruct;
+    received = guest.read_from_host();
+    assert_eq!(hint_a, received);
+    received = guest.read_from_host();
+    assert_eq!(hint_b, received);
+}
+
+#[test]
+fn test_commits_are_concatenated() {
+    let guest = Sp1Host::new(&[]).simulate_with_hints();
+    guest.commit(&1u32);
+    guest.commit(&"hello".to_string());
+
+    let mut expected = bincode::serialize(&1u32).unwrap();
+    expected.extend(bincode::serialize(&"hello".to_string()).unwrap());
+    assert_eq!(expected, guest.committed());
+}
+
+#[test]
+fn test_guest_rejects_proofs() {
+    let code_commitment = Sp1MethodId::new(vec![]);
+    assert!(Sp1Guest::verify(&[], &code_commitment).is_err());
+}
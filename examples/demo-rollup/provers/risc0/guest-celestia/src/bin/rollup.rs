@@ -27,6 +27,7 @@ pub fn main() {
         stf,
         CelestiaVerifier {
             rollup_namespace: ROLLUP_NAMESPACE,
+            rollup_id: None,
         },
     );
     stf_verifier
@@ -0,0 +1,19 @@
+#![no_main]
+use sov_mock_da::MockDaSpec;
+use sov_risc0_adapter::guest::Risc0Guest;
+use sov_state::{DefaultStorageSpec, MerkleProofSpec, Storage, ZkStorage};
+use sov_stf_runner::verifier::ProofAggregator;
+
+type Hasher = <DefaultStorageSpec as MerkleProofSpec>::Hasher;
+type Root = <ZkStorage<DefaultStorageSpec> as Storage>::Root;
+
+risc0_zkvm::guest::entry!(main);
+
+pub fn main() {
+    let guest = Risc0Guest::new();
+    let aggregator: ProofAggregator<Risc0Guest, MockDaSpec, Root> = ProofAggregator::new();
+
+    aggregator
+        .run_aggregation::<Hasher, _>(guest)
+        .expect("Prover must be honest");
+}
@@ -3,6 +3,7 @@ use demo_stf::genesis_config::StorageConfig;
 use demo_stf::runtime::Runtime;
 use sov_celestia_adapter::verifier::{CelestiaSpec, CelestiaVerifier, RollupParams};
 use sov_celestia_adapter::{CelestiaConfig, CelestiaService};
+use sov_db::pruning::PruningHandle;
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
 use sov_modules_api::{Address, Spec};
 use sov_modules_rollup_blueprint::{RollupBlueprint, WalletBlueprint};
@@ -12,7 +13,7 @@ use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
 use sov_rollup_interface::zk::ZkvmHost;
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
-use sov_stf_runner::{ParallelProverService, RollupConfig, RollupProverConfig};
+use sov_stf_runner::{ConfigUpdates, ParallelProverService, RollupConfig, RollupProverConfig};
 
 use crate::{ROLLUP_BATCH_NAMESPACE, ROLLUP_PROOF_NAMESPACE};
 
@@ -56,21 +57,48 @@ impl RollupBlueprint for CelestiaDemoRollup {
         storage: &<Self::NativeContext as sov_modules_api::Spec>::Storage,
         ledger_db: &sov_db::ledger_db::LedgerDB,
         da_service: &Self::DaService,
+        rollup_config: &RollupConfig<Self::DaConfig>,
+        config_updates: &ConfigUpdates,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error> {
         // TODO set the sequencer address
         let sequencer = Address::new([0; 32]);
 
-        #[allow(unused_mut)]
         let mut rpc_methods = sov_modules_rollup_blueprint::register_rpc::<
             Self::NativeRuntime,
             Self::NativeContext,
             Self::DaService,
-        >(storage, ledger_db, da_service, sequencer)?;
+        >(
+            storage,
+            ledger_db,
+            da_service,
+            sequencer,
+            rollup_config.da_compression,
+            &rollup_config.batch_builder,
+            &rollup_config.da_submission,
+            config_updates,
+        )?;
+
+        rpc_methods.merge(sov_bridge::get_withdrawal_proof_rpc::<
+            Self::NativeContext,
+            Self::DaSpec,
+        >(storage.clone())?)?;
+
+        rpc_methods.merge(sov_chain_state::get_chain_state_rpc::<
+            Self::NativeContext,
+            Self::DaSpec,
+        >(storage.clone())?)?;
+
+        rpc_methods.merge(sov_light_client::server::get_state_proof_rpc(
+            storage.clone(),
+        )?)?;
 
         #[cfg(feature = "experimental")]
         crate::eth::register_ethereum::<Self::DaService>(
             da_service.clone(),
             storage.clone(),
+            ledger_db,
+            rollup_config.da_compression,
+            config_updates,
             &mut rpc_methods,
         )?;
 
@@ -86,6 +114,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
             RollupParams {
                 rollup_batch_namespace: ROLLUP_BATCH_NAMESPACE,
                 rollup_proof_namespace: ROLLUP_PROOF_NAMESPACE,
+                rollup_id: None,
             },
         )
         .await
@@ -103,6 +132,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
 
         let da_verifier = CelestiaVerifier {
             rollup_namespace: ROLLUP_BATCH_NAMESPACE,
+            rollup_id: None,
         };
 
         ParallelProverService::new_with_default_workers(
@@ -111,7 +141,7 @@ impl RollupBlueprint for CelestiaDemoRollup {
             da_verifier,
             prover_config,
             zk_storage,
-            rollup_config.prover_service,
+            rollup_config.prover_service.clone(),
         )
     }
 
@@ -122,7 +152,11 @@ impl RollupBlueprint for CelestiaDemoRollup {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
         };
-        ProverStorageManager::new(storage_config)
+        Ok(ProverStorageManager::new(storage_config)?.with_pruning(rollup_config.storage.pruning))
+    }
+
+    fn pruning_handle(&self, storage_manager: &Self::StorageManager) -> Option<PruningHandle> {
+        Some(storage_manager.pruning_handle())
     }
 }
 
@@ -0,0 +1,111 @@
+use anyhow::Context as _;
+use serde::de::DeserializeOwned;
+use sov_db::maintenance::{estimate_pruning, NodeDatabase, TableSize};
+use sov_db::pruning::PruningMode;
+use sov_stf_runner::{from_toml_path, RollupConfig};
+
+/// Maintains the databases of the rollup. They're locked by the running rollup, so it has to be
+/// stopped first.
+#[derive(clap::Subcommand, Debug)]
+pub(crate) enum DbCommand {
+    /// Reports the size of each table of the databases.
+    Stats,
+    /// Compacts the databases, which reclaims the space of the records deleted by pruning.
+    Compact,
+    /// Decodes every record of the databases, and fails if any of them is corrupted.
+    Verify,
+    /// Estimates the space which pruning would reclaim, without deleting anything.
+    EstimatePruning {
+        /// Estimates the pruning of the versions older than the given number of most recent
+        /// versions, instead of the pruning mode of the rollup config.
+        #[arg(long)]
+        keep_last: Option<u64>,
+    },
+}
+
+impl DbCommand {
+    /// Runs the command on the databases of the rollup with the given config.
+    pub(crate) fn run<DaConfig: DeserializeOwned>(
+        &self,
+        rollup_config_path: &str,
+    ) -> Result<(), anyhow::Error> {
+        let rollup_config: RollupConfig<DaConfig> =
+            from_toml_path(rollup_config_path).context("Failed to read rollup configuration")?;
+        let storage_path = &rollup_config.storage.path;
+
+        match self {
+            DbCommand::Stats => {
+                for database in NodeDatabase::ALL {
+                    let db = database.open(storage_path)?;
+                    println!("{} database:", database.name());
+                    println!(
+                        "  {:<28} {:>14} {:>16} {:>16} {:>16}",
+                        "table", "records (est.)", "live bytes", "sst bytes", "memtable bytes"
+                    );
+                    for size in database.table_sizes(&db)? {
+                        println!(
+                            "  {:<28} {:>14} {:>16} {:>16} {:>16}",
+                            size.table,
+                            size.estimated_records,
+                            size.live_data_bytes,
+                            size.sst_files_bytes,
+                            size.memtable_bytes
+                        );
+                    }
+                }
+            }
+            DbCommand::Compact => {
+                for database in NodeDatabase::ALL {
+                    let db = database.open(storage_path)?;
+                    let size_before = total_sst_files_bytes(&database.table_sizes(&db)?);
+                    database.compact(&db)?;
+                    let size_after = total_sst_files_bytes(&database.table_sizes(&db)?);
+                    println!(
+                        "Compacted the {} database from {} to {} bytes",
+                        database.name(),
+                        size_before,
+                        size_after
+                    );
+                }
+            }
+            DbCommand::Verify => {
+                let mut corrupted_records = 0;
+                for database in NodeDatabase::ALL {
+                    let db = database.open(storage_path)?;
+                    for table in database.verify(&db)? {
+                        println!(
+                            "{}/{}: {} records, {} corrupted",
+                            database.name(),
+                            table.table,
+                            table.integrity.records,
+                            table.integrity.corrupted_keys.len()
+                        );
+                        for key in &table.integrity.corrupted_keys {
+                            let key: String =
+                                key.iter().map(|byte| format!("{:02x}", byte)).collect();
+                            println!("  corrupted key: 0x{}", key);
+                        }
+                        corrupted_records += table.integrity.corrupted_keys.len();
+                    }
+                }
+                if corrupted_records > 0 {
+                    anyhow::bail!("Found {} corrupted records", corrupted_records);
+                }
+            }
+            DbCommand::EstimatePruning { keep_last } => {
+                let mode = keep_last
+                    .map(PruningMode::KeepLast)
+                    .unwrap_or(rollup_config.storage.pruning);
+                let ledger_db = NodeDatabase::Ledger.open(storage_path)?;
+                let state_db = NodeDatabase::State.open(storage_path)?;
+                let estimate = estimate_pruning(&ledger_db, &state_db, mode)?;
+                println!("{}", serde_json::to_string_pretty(&estimate)?);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn total_sst_files_bytes(sizes: &[TableSize]) -> u64 {
+    sizes.iter().map(|size| size.sst_files_bytes).sum()
+}
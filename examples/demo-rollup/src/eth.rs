@@ -1,14 +1,18 @@
 use std::str::FromStr;
+use std::sync::Arc;
 
 use anyhow::Context as _;
 use sov_cli::wallet_state::PrivateKeyAndAddress;
+use sov_db::ledger_db::LedgerDB;
 use sov_ethereum::experimental::EthRpcConfig;
-use sov_ethereum::GasPriceOracleConfig;
+use sov_ethereum::{FiltersConfig, GasPriceOracleConfig, LogsConfig};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
 use sov_prover_storage_manager::SnapshotManager;
+use sov_rollup_interface::rpc::LedgerRpcProvider;
 use sov_rollup_interface::services::da::DaService;
 use sov_state::ProverStorage;
+use sov_stf_runner::{ConfigUpdates, DaCompressionConfig, GasOracleConfig};
 
 const TX_SIGNER_PRIV_KEY_PATH: &str = "../test-data/keys/tx_signer_private_key.json";
 
@@ -27,25 +31,57 @@ fn read_sov_tx_signer_priv_key() -> Result<DefaultPrivateKey, anyhow::Error> {
 pub(crate) fn register_ethereum<Da: DaService>(
     da_service: Da,
     storage: ProverStorage<sov_state::DefaultStorageSpec, SnapshotManager>,
+    ledger_db: &LedgerDB,
+    da_compression: Option<DaCompressionConfig>,
+    config_updates: &ConfigUpdates,
     methods: &mut jsonrpsee::RpcModule<()>,
 ) -> Result<(), anyhow::Error> {
     let eth_rpc_config = {
         let eth_signer = eth_dev_signer();
+        let oracle_config = gas_price_oracle_config(&config_updates.borrow().gas_price_oracle);
+        let config_updates = config_updates.clone();
         EthRpcConfig::<DefaultContext> {
             min_blob_size: Some(1),
+            da_compression_level: da_compression.map(|compression| compression.level),
             sov_tx_signer_priv_key: read_sov_tx_signer_priv_key()?,
             eth_signer,
-            gas_price_oracle_config: GasPriceOracleConfig::default(),
+            gas_price_oracle_config: oracle_config,
+            gas_price_oracle_config_source: Some(Arc::new(move || {
+                gas_price_oracle_config(&config_updates.borrow().gas_price_oracle)
+            })),
+            logs_config: LogsConfig::default(),
+            filters_config: FiltersConfig::default(),
         }
     };
 
-    let ethereum_rpc =
-        sov_ethereum::get_ethereum_rpc::<DefaultContext, Da>(da_service, eth_rpc_config, storage);
+    // `eth_subscribe` notifications are published once the slots are committed to the ledger
+    let slot_subscription = ledger_db.subscribe_slots()?;
+
+    let ethereum_rpc = sov_ethereum::get_ethereum_rpc::<DefaultContext, Da>(
+        da_service,
+        eth_rpc_config,
+        storage,
+        slot_subscription,
+    );
+    // The Ethereum RPC serves these methods itself, to support the `pending` block tag
+    for method in sov_ethereum::PENDING_TAG_METHODS {
+        methods.remove_method(method);
+    }
     methods
         .merge(ethereum_rpc)
         .context("Failed to merge Ethereum RPC modules")
 }
 
+/// The config of the gas price oracle, with its defaults for the parameters missing in the rollup config.
+fn gas_price_oracle_config(config: &GasOracleConfig) -> GasPriceOracleConfig {
+    GasPriceOracleConfig::new(
+        config.blocks,
+        config.ignore_price,
+        config.max_price,
+        config.percentile,
+    )
+}
+
 // TODO: #840
 fn eth_dev_signer() -> sov_ethereum::DevSigner {
     sov_ethereum::DevSigner::new(vec![secp256k1::SecretKey::from_str(
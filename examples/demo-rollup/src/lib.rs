@@ -6,11 +6,12 @@ use std::str::FromStr;
 
 use const_rollup_config::{ROLLUP_BATCH_NAMESPACE_RAW, ROLLUP_PROOF_NAMESPACE_RAW};
 use sov_celestia_adapter::types::Namespace;
+use sov_stf_runner::{ConfigUpdates, ReloadableConfig};
 mod mock_rollup;
 pub use mock_rollup::*;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
 
 mod celestia_rollup;
 pub use celestia_rollup::*;
@@ -24,16 +25,61 @@ pub const ROLLUP_BATCH_NAMESPACE: Namespace = Namespace::const_v0(ROLLUP_BATCH_N
 /// The rollup stores the zk proofs in the namespace b"sov-test-p" on Celestia.
 pub const ROLLUP_PROOF_NAMESPACE: Namespace = Namespace::const_v0(ROLLUP_PROOF_NAMESPACE_RAW);
 
-/// Default initialization of logging
-pub fn initialize_logging() {
+/// Changes the log filter installed by [`initialize_logging`].
+#[derive(Clone)]
+pub struct LogFilterHandle {
+    handle: reload::Handle<EnvFilter, Registry>,
+    /// The filter directives the logging was initialized with.
+    initial_directives: String,
+}
+
+impl LogFilterHandle {
+    /// Replaces the log filter with the given directives, in the `RUST_LOG` syntax,
+    /// or with the initial filter if they are missing.
+    pub fn set_directives(&self, directives: Option<&str>) -> anyhow::Result<()> {
+        let filter = EnvFilter::from_str(directives.unwrap_or(&self.initial_directives))?;
+        self.handle.reload(filter)?;
+        Ok(())
+    }
+
+    /// Applies the `log_level` of every snapshot of the reloadable settings, in a background task.
+    /// The filter of the current snapshot is applied right away.
+    pub fn follow(self, mut config_updates: ConfigUpdates) {
+        tokio::spawn(async move {
+            loop {
+                let log_level = config_updates.borrow_and_update().log_level.clone();
+                if let Err(e) = self.set_directives(log_level.as_deref()) {
+                    tracing::warn!("Failed to apply the log level {:?}: {}", log_level, e);
+                }
+                if config_updates.changed().await.is_err() {
+                    return;
+                }
+            }
+        });
+    }
+}
+
+/// Checks that the `log_level` of the reloadable settings is a valid filter.
+pub fn validate_log_level(config: &ReloadableConfig) -> anyhow::Result<()> {
+    if let Some(log_level) = &config.log_level {
+        EnvFilter::from_str(log_level)
+            .map_err(|e| anyhow::anyhow!("Invalid log level {:?}: {}", log_level, e))?;
+    }
+    Ok(())
+}
+
+/// Default initialization of logging.
+/// The returned handle changes the log filter, which is initialized from `RUST_LOG`.
+pub fn initialize_logging() -> LogFilterHandle {
+    let initial_directives =
+        env::var("RUST_LOG").unwrap_or_else(|_| "debug,hyper=info,risc0_zkvm=info".to_string());
+    let (filter, handle) = reload::Layer::new(EnvFilter::from_str(&initial_directives).unwrap());
     tracing_subscriber::registry()
+        .with(filter)
         .with(fmt::layer())
-        .with(
-            EnvFilter::from_str(
-                &env::var("RUST_LOG")
-                    .unwrap_or_else(|_| "debug,hyper=info,risc0_zkvm=info".to_string()),
-            )
-            .unwrap(),
-        )
         .init();
+    LogFilterHandle {
+        handle,
+        initial_directives,
+    }
 }
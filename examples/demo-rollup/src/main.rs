@@ -1,8 +1,16 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::Context as _;
 use clap::Parser;
+use db_command::DbCommand;
 use demo_stf::genesis_config::GenesisPaths;
-use sov_demo_rollup::{initialize_logging, CelestiaDemoRollup, MockDemoRollup};
-use sov_mock_da::MockDaConfig;
+use serde::de::DeserializeOwned;
+use sov_db::ledger_db::migrations::MigrationReport;
+use sov_db::ledger_db::LedgerDB;
+use sov_demo_rollup::{
+    initialize_logging, validate_log_level, CelestiaDemoRollup, LogFilterHandle, MockDemoRollup,
+};
+use sov_mock_da::{MockDaConfig, ScheduledFault};
 use sov_modules_rollup_blueprint::{Rollup, RollupBlueprint};
 use sov_modules_stf_blueprint::kernels::basic::{
     BasicKernelGenesisConfig, BasicKernelGenesisPaths,
@@ -10,6 +18,7 @@ use sov_modules_stf_blueprint::kernels::basic::{
 use sov_stf_runner::{from_toml_path, RollupConfig, RollupProverConfig};
 use tracing::log::debug;
 
+mod db_command;
 #[cfg(test)]
 mod test_rpc;
 
@@ -27,6 +36,31 @@ struct Args {
     /// The path to the rollup config.
     #[arg(long, default_value = "mock_rollup_config.toml")]
     rollup_config_path: String,
+
+    /// The directory containing the genesis files of the runtime modules and the kernel.
+    /// Defaults to the demo genesis files of the selected data layer.
+    #[arg(long)]
+    genesis_paths: Option<PathBuf>,
+
+    /// Reports the migrations the ledger database needs, without applying them or starting the rollup.
+    #[arg(long)]
+    dry_run_migrations: bool,
+
+    /// The path to a JSON file listing the faults injected by the mock DA layer, in addition to the
+    /// ones of the rollup config. See `sov_mock_da::ScheduledFault` for the format.
+    #[arg(long)]
+    mock_da_faults: Option<PathBuf>,
+
+    /// Runs a maintenance command instead of starting the rollup.
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Maintains the databases of the rollup, which has to be stopped first.
+    #[command(subcommand)]
+    Db(DbCommand),
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -35,41 +69,110 @@ enum SupportedDaLayer {
     Mock,
 }
 
+impl SupportedDaLayer {
+    fn default_genesis_dir(&self) -> &'static str {
+        match self {
+            SupportedDaLayer::Celestia => "../test-data/genesis/demo-tests/celestia",
+            SupportedDaLayer::Mock => "../test-data/genesis/demo-tests/mock",
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
-    initialize_logging();
+    let log_filter = initialize_logging();
 
     let args = Args::parse();
     let rollup_config_path = args.rollup_config_path.as_str();
+    let genesis_dir = args
+        .genesis_paths
+        .unwrap_or_else(|| args.da_layer.default_genesis_dir().into());
+    let rt_genesis_paths = GenesisPaths::from_dir(&genesis_dir);
+    let kernel_genesis_paths = BasicKernelGenesisPaths {
+        chain_state: genesis_dir.join("chain_state.json"),
+    };
+
+    if let Some(Command::Db(command)) = &args.command {
+        return match args.da_layer {
+            SupportedDaLayer::Mock => command.run::<MockDaConfig>(rollup_config_path),
+            SupportedDaLayer::Celestia => {
+                command.run::<sov_celestia_adapter::CelestiaConfig>(rollup_config_path)
+            }
+        };
+    }
+
+    if args.dry_run_migrations {
+        let report = match args.da_layer {
+            SupportedDaLayer::Mock => dry_run_migrations::<MockDemoRollup, MockDaConfig>(
+                &MockDemoRollup {},
+                rollup_config_path,
+            ),
+            SupportedDaLayer::Celestia => dry_run_migrations::<
+                CelestiaDemoRollup,
+                sov_celestia_adapter::CelestiaConfig,
+            >(&CelestiaDemoRollup {}, rollup_config_path),
+        }?;
+        print!("{}", report);
+        return Ok(());
+    }
 
     match args.da_layer {
         SupportedDaLayer::Mock => {
             let rollup = new_rollup_with_mock_da(
-                &GenesisPaths::from_dir("../test-data/genesis/demo-tests/mock"),
-                &BasicKernelGenesisPaths {
-                    chain_state: "../test-data/genesis/demo-tests/mock/chain_state.json".into(),
-                },
+                &rt_genesis_paths,
+                &kernel_genesis_paths,
                 rollup_config_path,
+                args.mock_da_faults.as_deref(),
                 RollupProverConfig::Execute,
             )
             .await?;
-            rollup.run().await
+            run_rollup(rollup, rollup_config_path, log_filter).await
         }
         SupportedDaLayer::Celestia => {
             let rollup = new_rollup_with_celestia_da(
-                &GenesisPaths::from_dir("../test-data/genesis/demo-tests/celestia"),
-                &BasicKernelGenesisPaths {
-                    chain_state: "../test-data/genesis/demo-tests/celestia/chain_state.json".into(),
-                },
+                &rt_genesis_paths,
+                &kernel_genesis_paths,
                 rollup_config_path,
                 RollupProverConfig::Execute,
             )
             .await?;
-            rollup.run().await
+            run_rollup(rollup, rollup_config_path, log_filter).await
         }
     }
 }
 
+/// Runs the rollup, reloading its settings which don't need a restart on SIGHUP, or when the
+/// rollup config file is modified.
+async fn run_rollup<B: RollupBlueprint>(
+    mut rollup: Rollup<B>,
+    rollup_config_path: &str,
+    log_filter: LogFilterHandle,
+) -> Result<(), anyhow::Error> {
+    rollup.config_watcher = rollup
+        .config_watcher
+        .watch_file(rollup_config_path)?
+        .with_validation(validate_log_level);
+    log_filter.follow(rollup.config_watcher.subscribe());
+    rollup.run().await
+}
+
+fn dry_run_migrations<B, DaConfig>(
+    rollup: &B,
+    rollup_config_path: &str,
+) -> Result<MigrationReport, anyhow::Error>
+where
+    B: RollupBlueprint<DaConfig = DaConfig>,
+    DaConfig: DeserializeOwned,
+{
+    let rollup_config: RollupConfig<DaConfig> =
+        from_toml_path(rollup_config_path).context("Failed to read rollup configuration")?;
+    LedgerDB::migrate(
+        &rollup_config.storage.path,
+        &rollup.ledger_migrations(),
+        true,
+    )
+}
+
 async fn new_rollup_with_celestia_da(
     rt_genesis_paths: &GenesisPaths,
     kernel_genesis_paths: &BasicKernelGenesisPaths,
@@ -106,12 +209,19 @@ async fn new_rollup_with_mock_da(
     rt_genesis_paths: &GenesisPaths,
     kernel_genesis_paths: &BasicKernelGenesisPaths,
     rollup_config_path: &str,
+    faults_path: Option<&Path>,
     prover_config: RollupProverConfig,
 ) -> Result<Rollup<MockDemoRollup>, anyhow::Error> {
     debug!("Starting mock rollup with config {}", rollup_config_path);
 
-    let rollup_config: RollupConfig<MockDaConfig> =
+    let mut rollup_config: RollupConfig<MockDaConfig> =
         from_toml_path(rollup_config_path).context("Failed to read rollup configuration")?;
+    if let Some(faults_path) = faults_path {
+        let faults: Vec<ScheduledFault> = serde_json::from_str(
+            &std::fs::read_to_string(faults_path).context("Failed to read the mock DA faults")?,
+        )?;
+        rollup_config.da.faults.extend(faults);
+    }
 
     let kernel_genesis = BasicKernelGenesisConfig {
         chain_state: serde_json::from_str(
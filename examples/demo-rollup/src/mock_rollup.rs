@@ -2,6 +2,7 @@ use async_trait::async_trait;
 use demo_stf::genesis_config::StorageConfig;
 use demo_stf::runtime::Runtime;
 use sov_db::ledger_db::LedgerDB;
+use sov_db::pruning::PruningHandle;
 use sov_mock_da::{MockDaConfig, MockDaService, MockDaSpec};
 use sov_modules_api::default_context::{DefaultContext, ZkDefaultContext};
 use sov_modules_api::{Address, Spec};
@@ -10,9 +11,10 @@ use sov_modules_stf_blueprint::kernels::basic::BasicKernel;
 use sov_modules_stf_blueprint::StfBlueprint;
 use sov_prover_storage_manager::ProverStorageManager;
 use sov_risc0_adapter::host::Risc0Host;
+use sov_risc0_adapter::Risc0MethodId;
 use sov_rollup_interface::zk::ZkvmHost;
 use sov_state::{DefaultStorageSpec, Storage, ZkStorage};
-use sov_stf_runner::{ParallelProverService, RollupConfig, RollupProverConfig};
+use sov_stf_runner::{ConfigUpdates, ParallelProverService, RollupConfig, RollupProverConfig};
 
 /// Rollup with MockDa
 pub struct MockDemoRollup {}
@@ -54,21 +56,48 @@ impl RollupBlueprint for MockDemoRollup {
         storage: &<Self::NativeContext as Spec>::Storage,
         ledger_db: &LedgerDB,
         da_service: &Self::DaService,
+        rollup_config: &RollupConfig<Self::DaConfig>,
+        config_updates: &ConfigUpdates,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error> {
         // TODO set the sequencer address
         let sequencer = Address::new([0; 32]);
 
-        #[allow(unused_mut)]
         let mut rpc_methods = sov_modules_rollup_blueprint::register_rpc::<
             Self::NativeRuntime,
             Self::NativeContext,
             Self::DaService,
-        >(storage, ledger_db, da_service, sequencer)?;
+        >(
+            storage,
+            ledger_db,
+            da_service,
+            sequencer,
+            rollup_config.da_compression,
+            &rollup_config.batch_builder,
+            &rollup_config.da_submission,
+            config_updates,
+        )?;
+
+        rpc_methods.merge(sov_bridge::get_withdrawal_proof_rpc::<
+            Self::NativeContext,
+            Self::DaSpec,
+        >(storage.clone())?)?;
+
+        rpc_methods.merge(sov_chain_state::get_chain_state_rpc::<
+            Self::NativeContext,
+            Self::DaSpec,
+        >(storage.clone())?)?;
+
+        rpc_methods.merge(sov_light_client::server::get_state_proof_rpc(
+            storage.clone(),
+        )?)?;
 
         #[cfg(feature = "experimental")]
         crate::eth::register_ethereum::<Self::DaService>(
             da_service.clone(),
             storage.clone(),
+            ledger_db,
+            rollup_config.da_compression,
+            config_updates,
             &mut rpc_methods,
         )?;
 
@@ -80,6 +109,7 @@ impl RollupBlueprint for MockDemoRollup {
         rollup_config: &RollupConfig<Self::DaConfig>,
     ) -> Self::DaService {
         MockDaService::new(rollup_config.da.sender_address)
+            .with_faults(rollup_config.da.faults.clone())
     }
 
     async fn create_prover_service(
@@ -99,7 +129,11 @@ impl RollupBlueprint for MockDemoRollup {
             da_verifier,
             prover_config,
             zk_storage,
-            rollup_config.prover_service,
+            rollup_config.prover_service.clone(),
+        )
+        .with_aggregation(
+            Risc0Host::new(risc0::AGGREGATE_MOCK_DA_ELF),
+            Risc0MethodId::new(risc0::MOCK_DA_ID),
         )
     }
 
@@ -110,6 +144,10 @@ impl RollupBlueprint for MockDemoRollup {
         let storage_config = StorageConfig {
             path: rollup_config.storage.path.clone(),
         };
-        ProverStorageManager::new(storage_config)
+        Ok(ProverStorageManager::new(storage_config)?.with_pruning(rollup_config.storage.pruning))
+    }
+
+    fn pruning_handle(&self, storage_manager: &Self::StorageManager) -> Option<PruningHandle> {
+        Some(storage_manager.pruning_handle())
     }
 }
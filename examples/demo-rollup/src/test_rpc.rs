@@ -75,6 +75,8 @@ fn test_helper(test_queries: Vec<TestExpect>, slots: Vec<SlotCommit<MockBlock, u
         let rpc_config = RpcConfig {
             bind_host: "127.0.0.1".to_string(),
             bind_port: addr.port(),
+            limits: Default::default(),
+            admin: None,
         };
 
         queries_test_runner(test_queries, rpc_config).await;
@@ -86,6 +88,7 @@ fn batch2_tx_receipts() -> Vec<TransactionReceipt<u32>> {
         .map(|i| TransactionReceipt::<u32> {
             tx_hash: ::sha2::Sha256::digest(i.to_string()),
             body_to_save: Some(b"tx body".to_vec()),
+            sender: None,
             events: vec![],
             receipt: 0,
             gas_used: vec![0, 0],
@@ -112,6 +115,7 @@ fn regular_test_helper(payload: serde_json::Value, expected: &serde_json::Value)
                 TransactionReceipt::<u32> {
                     tx_hash: ::sha2::Sha256::digest(b"tx1"),
                     body_to_save: Some(b"tx1 body".to_vec()),
+                    sender: None,
                     events: vec![],
                     receipt: 0,
                     gas_used: vec![0, 0],
@@ -119,6 +123,7 @@ fn regular_test_helper(payload: serde_json::Value, expected: &serde_json::Value)
                 TransactionReceipt::<u32> {
                     tx_hash: ::sha2::Sha256::digest(b"tx2"),
                     body_to_save: Some(b"tx2 body".to_vec()),
+                    sender: None,
                     events: vec![
                         Event::new("event1_key", "event1_value"),
                         Event::new("event2_key", "event2_value"),
@@ -269,6 +274,30 @@ fn test_get_events() {
     regular_test_helper(payload, &expected);
 }
 
+#[test]
+fn test_get_filtered_events() {
+    let payload = jsonrpc_req!(
+        "ledger_getEvents",
+        [{"from_slot": 0, "to_slot": 1, "key": "event2_key"}]
+    );
+    let expected = jsonrpc_result!([{
+        "slot_number": 1,
+        "batch_number": 1,
+        "tx_number": 2,
+        "event_number": 2,
+        "key":[101,118,101,110,116,50,95,107,101,121],
+        "value":[101,118,101,110,116,50,95,118,97,108,117,101]
+    }]);
+    regular_test_helper(payload, &expected);
+
+    let payload = jsonrpc_req!(
+        "ledger_getEvents",
+        [{"from_slot": 0, "to_slot": 1, "module": "Bank"}]
+    );
+    let expected = jsonrpc_result!([]);
+    regular_test_helper(payload, &expected);
+}
+
 fn batch_receipt_without_hasher() -> impl Strategy<Value = BatchReceipt<u32, u32>> {
     let mut args = BatchReceiptStrategyArgs {
         hasher: None,
@@ -548,10 +577,13 @@ proptest!(
                     if random_event_num_usize < *end_event_range {
                         let event_index = random_event_num_usize - *start_event_range;
                         let event: &Event = tx.events.get(event_index).unwrap();
-                        let event_json = json!({
+                        let mut event_json = json!({
                             "key": event.key().inner(),
                             "value": event.value().inner(),
                         });
+                        if let Some(module) = event.module() {
+                            event_json["module"] = json!(module);
+                        }
 
                         test_helper(vec![TestExpect{
                             payload:
@@ -5,20 +5,27 @@
 use std::convert::AsRef;
 use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Context as _};
+use anyhow::Context as _;
 use sov_accounts::AccountConfig;
 use sov_bank::BankConfig;
+use sov_bridge::BridgeConfig;
+use sov_circuit_breaker::CircuitBreakerConfig;
 #[cfg(feature = "experimental")]
 use sov_evm::EvmConfig;
+#[cfg(feature = "devnet")]
+use sov_faucet::FaucetConfig;
+use sov_governance::GovernanceConfig;
 pub use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::Context;
 use sov_modules_stf_blueprint::Runtime as RuntimeTrait;
 use sov_nft_module::NonFungibleTokenConfig;
+use sov_price_oracle::PriceOracleConfig;
+use sov_randomness::RandomnessConfig;
 use sov_rollup_interface::da::DaSpec;
+use sov_scheduler::SchedulerConfig;
 use sov_sequencer_registry::SequencerConfig;
 pub use sov_state::config::Config as StorageConfig;
-use sov_stf_runner::read_json_file;
-use sov_value_setter::ValueSetterConfig;
+use sov_stf_runner::GenesisErrors;
 
 /// Creates config for a rollup with some default settings, the config is used in demos and tests.
 use crate::runtime::GenesisConfig;
@@ -30,12 +37,25 @@ pub struct GenesisPaths {
     pub bank_genesis_path: PathBuf,
     /// Sequencer Registry genesis path.
     pub sequencer_genesis_path: PathBuf,
-    /// Value Setter genesis path.
-    pub value_setter_genesis_path: PathBuf,
+    /// Governance genesis path.
+    pub governance_genesis_path: PathBuf,
     /// Accounts genesis path.
     pub accounts_genesis_path: PathBuf,
     /// NFT genesis path.
     pub nft_path: PathBuf,
+    /// Bridge genesis path.
+    pub bridge_genesis_path: PathBuf,
+    /// Price Oracle genesis path.
+    pub price_oracle_genesis_path: PathBuf,
+    /// Circuit Breaker genesis path.
+    pub circuit_breaker_genesis_path: PathBuf,
+    /// Randomness genesis path.
+    pub randomness_genesis_path: PathBuf,
+    /// Scheduler genesis path.
+    pub scheduler_genesis_path: PathBuf,
+    #[cfg(feature = "devnet")]
+    /// Faucet genesis path.
+    pub faucet_genesis_path: PathBuf,
     #[cfg(feature = "experimental")]
     /// EVM genesis path.
     pub evm_genesis_path: PathBuf,
@@ -51,9 +71,16 @@ impl GenesisPaths {
         Self {
             bank_genesis_path: dir.as_ref().join("bank.json"),
             sequencer_genesis_path: dir.as_ref().join("sequencer_registry.json"),
-            value_setter_genesis_path: dir.as_ref().join("value_setter.json"),
+            governance_genesis_path: dir.as_ref().join("governance.json"),
             accounts_genesis_path: dir.as_ref().join("accounts.json"),
             nft_path: dir.as_ref().join("nft.json"),
+            bridge_genesis_path: dir.as_ref().join("bridge.json"),
+            price_oracle_genesis_path: dir.as_ref().join("price_oracle.json"),
+            circuit_breaker_genesis_path: dir.as_ref().join("circuit_breaker.json"),
+            randomness_genesis_path: dir.as_ref().join("randomness.json"),
+            scheduler_genesis_path: dir.as_ref().join("scheduler.json"),
+            #[cfg(feature = "devnet")]
+            faucet_genesis_path: dir.as_ref().join("faucet.json"),
             #[cfg(feature = "experimental")]
             evm_genesis_path: dir.as_ref().join("evm.json"),
         }
@@ -61,63 +88,150 @@ impl GenesisPaths {
 }
 
 /// Creates genesis configuration.
+///
+/// All the genesis files are read and validated before any state is written,
+/// and every error found is reported together with its file and field.
 pub fn get_genesis_config<C: Context, Da: DaSpec>(
     genesis_paths: &GenesisPaths,
 ) -> Result<<Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig, anyhow::Error> {
     let genesis_config =
         create_genesis_config(genesis_paths).context("Unable to read genesis configuration")?;
-    validate_config(genesis_config)
+    validate_config(genesis_paths, genesis_config).context("Invalid genesis configuration")
 }
 
+/// Checks the configuration of each module, and the consistency of the modules configurations.
 pub(crate) fn validate_config<C: Context, Da: DaSpec>(
+    genesis_paths: &GenesisPaths,
     genesis_config: <Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig,
 ) -> Result<<Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig, anyhow::Error> {
-    let token_address = &sov_bank::get_genesis_token_address::<C>(
-        &genesis_config.bank.tokens[0].token_name,
-        genesis_config.bank.tokens[0].salt,
+    let mut errors = GenesisErrors::default();
+    errors.extend(
+        &genesis_paths.bank_genesis_path,
+        genesis_config.bank.validate(),
+    );
+    errors.extend(
+        &genesis_paths.sequencer_genesis_path,
+        genesis_config.sequencer_registry.validate(),
+    );
+    #[cfg(feature = "experimental")]
+    errors.extend(
+        &genesis_paths.evm_genesis_path,
+        genesis_config.evm.validate(),
     );
 
-    let coins_token_addr = &genesis_config
-        .sequencer_registry
-        .coins_to_lock
-        .token_address;
-
-    if coins_token_addr != token_address {
-        bail!(
-            "Wrong token address in `sequencer_registry_config` expected {} but found {}",
-            token_address,
-            coins_token_addr
-        )
+    match genesis_config.bank.tokens.first() {
+        Some(token) => {
+            let token_address =
+                sov_bank::get_genesis_token_address::<C>(&token.token_name, token.salt);
+            let coins_token_addr = &genesis_config
+                .sequencer_registry
+                .coins_to_lock
+                .token_address;
+            if coins_token_addr != &token_address {
+                errors.push(
+                    &genesis_paths.sequencer_genesis_path,
+                    format!(
+                        "coins_to_lock.token_address: expected the first token of the bank {} but found {}",
+                        token_address, coins_token_addr
+                    ),
+                );
+            }
+        }
+        None => errors.push(
+            &genesis_paths.bank_genesis_path,
+            "tokens: the bank needs a token for the sequencers to lock",
+        ),
     }
 
+    #[cfg(feature = "devnet")]
+    {
+        let faucet_token_addr = &genesis_config.faucet.token_address;
+        let is_genesis_token = genesis_config.bank.tokens.iter().any(|token| {
+            &sov_bank::get_genesis_token_address::<C>(&token.token_name, token.salt)
+                == faucet_token_addr
+        });
+        if !is_genesis_token {
+            errors.push(
+                &genesis_paths.faucet_genesis_path,
+                format!(
+                    "token_address: {} isn't a token created by the bank at genesis",
+                    faucet_token_addr
+                ),
+            );
+        }
+    }
+
+    if genesis_config.bridge.deposit_address == genesis_config.sequencer_registry.seq_da_address {
+        errors.push(
+            &genesis_paths.bridge_genesis_path,
+            "deposit_address: the deposit address can't be the sequencer DA address",
+        );
+    }
+
+    errors.into_result()?;
     Ok(genesis_config)
 }
 
+/// Reads every genesis file, so all the malformed ones are reported at once.
 fn create_genesis_config<C: Context, Da: DaSpec>(
     genesis_paths: &GenesisPaths,
 ) -> anyhow::Result<<Runtime<C, Da> as RuntimeTrait<C, Da>>::GenesisConfig> {
-    let bank_config: BankConfig<C> = read_json_file(&genesis_paths.bank_genesis_path)?;
+    let mut errors = GenesisErrors::default();
+
+    let bank_config: Option<BankConfig<C>> =
+        errors.read_json_file(&genesis_paths.bank_genesis_path);
+
+    let sequencer_registry_config: Option<SequencerConfig<C, Da>> =
+        errors.read_json_file(&genesis_paths.sequencer_genesis_path);
+
+    let governance_config: Option<GovernanceConfig<C>> =
+        errors.read_json_file(&genesis_paths.governance_genesis_path);
+
+    let accounts_config: Option<AccountConfig<C>> =
+        errors.read_json_file(&genesis_paths.accounts_genesis_path);
+
+    let nft_config: Option<NonFungibleTokenConfig<C>> =
+        errors.read_json_file(&genesis_paths.nft_path);
+
+    let bridge_config: Option<BridgeConfig<Da>> =
+        errors.read_json_file(&genesis_paths.bridge_genesis_path);
+
+    let price_oracle_config: Option<PriceOracleConfig<C>> =
+        errors.read_json_file(&genesis_paths.price_oracle_genesis_path);
 
-    let sequencer_registry_config: SequencerConfig<C, Da> =
-        read_json_file(&genesis_paths.sequencer_genesis_path)?;
+    let circuit_breaker_config: Option<CircuitBreakerConfig<C>> =
+        errors.read_json_file(&genesis_paths.circuit_breaker_genesis_path);
 
-    let value_setter_config: ValueSetterConfig<C> =
-        read_json_file(&genesis_paths.value_setter_genesis_path)?;
+    let randomness_config: Option<RandomnessConfig> =
+        errors.read_json_file(&genesis_paths.randomness_genesis_path);
 
-    let accounts_config: AccountConfig<C> = read_json_file(&genesis_paths.accounts_genesis_path)?;
+    let scheduler_config: Option<SchedulerConfig<C>> =
+        errors.read_json_file(&genesis_paths.scheduler_genesis_path);
 
-    let nft_config: NonFungibleTokenConfig = read_json_file(&genesis_paths.nft_path)?;
+    #[cfg(feature = "devnet")]
+    let faucet_config: Option<FaucetConfig<C>> =
+        errors.read_json_file(&genesis_paths.faucet_genesis_path);
 
     #[cfg(feature = "experimental")]
-    let evm_config: EvmConfig = read_json_file(&genesis_paths.evm_genesis_path)?;
+    let evm_config: Option<EvmConfig> = errors.read_json_file(&genesis_paths.evm_genesis_path);
 
+    errors.into_result()?;
+    // Every file was read, since the reading errors are recorded
+    const READ: &str = "The genesis file was read";
     Ok(GenesisConfig::new(
-        bank_config,
-        sequencer_registry_config,
-        value_setter_config,
-        accounts_config,
-        nft_config,
+        bank_config.expect(READ),
+        sequencer_registry_config.expect(READ),
+        governance_config.expect(READ),
+        accounts_config.expect(READ),
+        nft_config.expect(READ),
+        bridge_config.expect(READ),
+        price_oracle_config.expect(READ),
+        circuit_breaker_config.expect(READ),
+        randomness_config.expect(READ),
+        scheduler_config.expect(READ),
+        #[cfg(feature = "devnet")]
+        faucet_config.expect(READ),
         #[cfg(feature = "experimental")]
-        evm_config,
+        evm_config.expect(READ),
     ))
 }
@@ -1,12 +1,12 @@
-use sov_accounts::AccountsTxHook;
+use sov_accounts::{AccountsTxHook, AccountsTxHookArg};
 use sov_bank::BankTxHook;
+use sov_chain_state::ChainState;
 use sov_modules_api::hooks::{ApplyBlobHooks, FinalizeHook, SlotHooks, TxHooks};
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{AccessoryWorkingSet, Context, Spec, WorkingSet};
-use sov_modules_stf_blueprint::{RuntimeTxHook, SequencerOutcome};
-#[cfg(feature = "experimental")]
-use sov_rollup_interface::da::BlockHeaderTrait;
-use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
+use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, RuntimeTxHook, SequencerOutcome};
+use sov_price_oracle::PriceOracleTxHook;
+use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec};
 use sov_sequencer_registry::SequencerRegistry;
 use sov_state::Storage;
 use tracing::info;
@@ -25,9 +25,20 @@ impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
         arg: &RuntimeTxHook<C>,
     ) -> anyhow::Result<C> {
         let RuntimeTxHook { height, sequencer } = arg;
+        let accounts_hook = AccountsTxHookArg {
+            sequencer: sequencer.clone(),
+            height: *height,
+        };
         let AccountsTxHook { sender, sequencer } =
             self.accounts
-                .pre_dispatch_tx_hook(tx, working_set, sequencer)?;
+                .pre_dispatch_tx_hook(tx, working_set, &accounts_hook)?;
+
+        // Swaps the fee token of the sender for the gas tokens taken by the bank
+        let oracle_hook = PriceOracleTxHook {
+            sender: sender.clone(),
+        };
+        self.price_oracle
+            .pre_dispatch_tx_hook(tx, working_set, &oracle_hook)?;
 
         let hook = BankTxHook { sender, sequencer };
         self.bank.pre_dispatch_tx_hook(tx, working_set, &hook)?;
@@ -43,6 +54,8 @@ impl<C: Context, Da: DaSpec> TxHooks for Runtime<C, Da> {
     ) -> anyhow::Result<()> {
         self.accounts.post_dispatch_tx_hook(tx, ctx, working_set)?;
         self.bank.post_dispatch_tx_hook(tx, ctx, working_set)?;
+        self.price_oracle
+            .post_dispatch_tx_hook(tx, ctx, working_set)?;
         Ok(())
     }
 }
@@ -58,7 +71,8 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
         // Before executing each batch, check that the sender is registered as a sequencer
-        self.sequencer_registry.begin_blob_hook(blob, working_set)
+        self.sequencer_registry.begin_blob_hook(blob, working_set)?;
+        Ok(())
     }
 
     fn end_blob_hook(
@@ -68,7 +82,7 @@ impl<C: Context, Da: DaSpec> ApplyBlobHooks<Da::BlobTransaction> for Runtime<C,
     ) -> anyhow::Result<()> {
         match result {
             SequencerOutcome::Rewarded(_reward) => {
-                // TODO: Process reward here or above.
+                // The registry rewards the fees collected by the bank to the sequencer
                 <SequencerRegistry<C, Da> as ApplyBlobHooks<Da::BlobTransaction>>::end_blob_hook(
                     &self.sequencer_registry,
                     sov_sequencer_registry::SequencerOutcome::Completed,
@@ -98,15 +112,35 @@ impl<C: Context, Da: DaSpec> SlotHooks<Da> for Runtime<C, Da> {
 
     fn begin_slot_hook(
         &self,
-        #[allow(unused_variables)] slot_header: &Da::BlockHeader,
+        slot_header: &Da::BlockHeader,
         #[allow(unused_variables)] validity_condition: &Da::ValidityCondition,
         #[allow(unused_variables)]
         pre_state_root: &<<Self::Context as Spec>::Storage as Storage>::Root,
-        #[allow(unused_variables)] working_set: &mut sov_modules_api::WorkingSet<C>,
+        working_set: &mut sov_modules_api::WorkingSet<C>,
     ) {
+        // The randomness of the slot is derived first, so that the other hooks can read it
+        self.randomness
+            .begin_slot_hook(slot_header.hash().into(), working_set);
+
+        #[cfg(feature = "devnet")]
+        self.faucet.begin_slot_hook(working_set);
+
         #[cfg(feature = "experimental")]
-        self.evm
-            .begin_slot_hook(slot_header.hash().into(), pre_state_root, working_set);
+        self.evm.begin_slot_hook(
+            self.randomness.get_randomness(working_set),
+            Some(slot_header.time().secs() as u64),
+            pre_state_root,
+            working_set,
+        );
+
+        // The scheduled calls are executed last, so they see the slot as the batches of the slot do
+        let gas_price = ChainState::<C, Da>::default().get_gas_price(working_set);
+        self.scheduler.begin_slot_hook(
+            self,
+            &|message, working_set| self.check_dispatch(message, working_set),
+            &gas_price,
+            working_set,
+        );
     }
 
     fn end_slot_hook(
@@ -29,6 +29,9 @@
 //! `#[derive(MessageCodec)` adds deserialization capabilities to the `Runtime` (implements `decode_call` method).
 //! `Runtime::decode_call` accepts serialized call message and returns a type that implements the `DispatchCall` trait.
 //!  The `DispatchCall` implementation (derived by a macro) forwards the message to the appropriate module and executes its `call` method.
+//!
+//! `#[derive(ModuleClients)]` generates the `RuntimeModuleClients` trait, giving the clients of the runtime, like the ones of `sov-client`,
+//! access to the clients of its modules: `client.bank().transfer(to, coins)`.
 
 #![allow(unused_doc_comments)]
 #[cfg(feature = "native")]
@@ -36,29 +39,42 @@ use sov_accounts::{AccountsRpcImpl, AccountsRpcServer};
 #[cfg(feature = "native")]
 use sov_bank::{BankRpcImpl, BankRpcServer};
 #[cfg(feature = "native")]
+use sov_bridge::{BridgeRpcImpl, BridgeRpcServer};
+#[cfg(feature = "native")]
+use sov_circuit_breaker::{CircuitBreakerRpcImpl, CircuitBreakerRpcServer};
+#[cfg(feature = "native")]
 #[cfg(feature = "experimental")]
 use sov_evm::{EvmRpcImpl, EvmRpcServer};
 #[cfg(feature = "native")]
+#[cfg(feature = "devnet")]
+use sov_faucet::{FaucetRpcImpl, FaucetRpcServer};
+#[cfg(feature = "native")]
+use sov_governance::{GovernanceRpcImpl, GovernanceRpcServer};
+#[cfg(feature = "native")]
 pub use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::macros::DefaultRuntime;
 #[cfg(feature = "native")]
-use sov_modules_api::macros::{expose_rpc, CliWallet};
+use sov_modules_api::macros::{expose_rpc, CliWallet, ModuleClients};
 #[cfg(feature = "native")]
 use sov_modules_api::Spec;
-use sov_modules_api::{Context, DispatchCall, Genesis, MessageCodec};
+use sov_modules_api::{Context, DispatchCall, Genesis, MessageCodec, ModuleError, WorkingSet};
 #[cfg(feature = "native")]
 use sov_nft_module::{NonFungibleTokenRpcImpl, NonFungibleTokenRpcServer};
+#[cfg(feature = "native")]
+use sov_price_oracle::{PriceOracleRpcImpl, PriceOracleRpcServer};
+#[cfg(feature = "native")]
+use sov_randomness::{RandomnessRpcImpl, RandomnessRpcServer};
 use sov_rollup_interface::da::DaSpec;
 #[cfg(feature = "native")]
-use sov_sequencer_registry::{SequencerRegistryRpcImpl, SequencerRegistryRpcServer};
+use sov_scheduler::{SchedulerRpcImpl, SchedulerRpcServer};
 #[cfg(feature = "native")]
-use sov_value_setter::{ValueSetterRpcImpl, ValueSetterRpcServer};
+use sov_sequencer_registry::{SequencerRegistryRpcImpl, SequencerRegistryRpcServer};
 
 #[cfg(feature = "native")]
 use crate::genesis_config::GenesisPaths;
 
 /// The `demo-stf runtime`.
-#[cfg_attr(feature = "native", derive(CliWallet), expose_rpc)]
+#[cfg_attr(feature = "native", derive(CliWallet, ModuleClients), expose_rpc)]
 #[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
 #[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
 #[cfg_attr(feature = "serde", serialization(serde::Serialize, serde::Deserialize))]
@@ -67,12 +83,25 @@ pub struct Runtime<C: Context, Da: DaSpec> {
     pub bank: sov_bank::Bank<C>,
     /// The Sequencer Registry module.
     pub sequencer_registry: sov_sequencer_registry::SequencerRegistry<C, Da>,
-    /// The Value Setter module.
-    pub value_setter: sov_value_setter::ValueSetter<C>,
+    /// The Governance module.
+    pub governance: sov_governance::Governance<C>,
     /// The Accounts module.
     pub accounts: sov_accounts::Accounts<C>,
     /// The NFT module.
     pub nft: sov_nft_module::NonFungibleToken<C>,
+    /// The Bridge module.
+    pub bridge: sov_bridge::Bridge<C, Da>,
+    /// The Price Oracle module.
+    pub price_oracle: sov_price_oracle::PriceOracle<C>,
+    /// The Circuit Breaker module.
+    pub circuit_breaker: sov_circuit_breaker::CircuitBreaker<C>,
+    /// The Randomness module.
+    pub randomness: sov_randomness::Randomness<C>,
+    /// The Scheduler module.
+    pub scheduler: sov_scheduler::Scheduler<C>,
+    #[cfg(feature = "devnet")]
+    /// The Faucet module, only part of the devnets.
+    pub faucet: sov_faucet::Faucet<C>,
     #[cfg(feature = "experimental")]
     #[cfg_attr(feature = "native", cli_skip)]
     /// The EVM module.
@@ -100,4 +129,13 @@ where
     ) -> Result<Self::GenesisConfig, anyhow::Error> {
         crate::genesis_config::get_genesis_config(genesis_paths)
     }
+
+    fn check_dispatch(
+        &self,
+        message: &<Self as DispatchCall>::Decodable,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), ModuleError> {
+        self.circuit_breaker
+            .check_dispatch(self.module_name(message), working_set)
+    }
 }
@@ -4,7 +4,7 @@ use sov_data_generators::bank_data::{
     BadNonceBankCallMessages, BadSerializationBankCallMessages, BadSignatureBankCallMessages,
     BankMessageGenerator,
 };
-use sov_data_generators::value_setter_data::{ValueSetterMessage, ValueSetterMessages};
+use sov_data_generators::governance_data::GovernanceMessages;
 use sov_data_generators::MessageGenerator;
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
@@ -16,17 +16,23 @@ use crate::runtime::Runtime;
 type C = DefaultContext;
 type Da = MockDaSpec;
 
-pub fn simulate_da(value_setter_admin: DefaultPrivateKey) -> Vec<RawTx> {
+/// The amount of governance tokens voting for the proposal of [`simulate_da`].
+pub const SIMULATED_VOTE_AMOUNT: u64 = 10000;
+
+pub fn simulate_da(governance_token_holder: DefaultPrivateKey) -> Vec<RawTx> {
     let mut messages = Vec::default();
 
     let bank_generator = BankMessageGenerator::<C>::default();
     let bank_txs = bank_generator.create_raw_txs::<Runtime<C, Da>>();
 
-    let value_setter = ValueSetterMessages::new(vec![ValueSetterMessage {
-        admin: Rc::new(value_setter_admin),
-        messages: vec![99, 33],
-    }]);
-    messages.extend(value_setter.create_raw_txs::<Runtime<C, Da>>());
+    let governance = GovernanceMessages {
+        proposer: Rc::new(governance_token_holder),
+        proposal_id: 0,
+        parameter: "sequencer_bond".to_owned(),
+        value: 100,
+        vote_amount: SIMULATED_VOTE_AMOUNT,
+    };
+    messages.extend(governance.create_raw_txs::<Runtime<C, Da>>());
     messages.extend(bank_txs);
     messages
 }
@@ -0,0 +1,134 @@
+use std::str::FromStr;
+
+use borsh::BorshSerialize;
+use reth_primitives::{Bytes, Transaction, TransactionKind, TransactionSigned, TxEip1559, U256};
+use sov_data_generators::new_test_blob_from_batch;
+use sov_evm::{DevSigner, RlpEvmTransaction};
+use sov_mock_da::{MockBlock, MOCK_SEQUENCER_DA_ADDRESS};
+use sov_modules_api::digest::Digest;
+use sov_modules_api::transaction::Transaction as SovTransaction;
+use sov_modules_api::{EncodeCall, PrivateKey, Spec, WorkingSet};
+use sov_modules_stf_blueprint::{Batch, RawTx, StfBlueprint};
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::soft_confirmation::{
+    SignedSoftConfirmationBatch, UnsignedSoftConfirmationBatch,
+};
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+
+use crate::tests::stf_tests::read_private_key;
+use crate::tests::{
+    create_storage_manager_for_tests, get_genesis_config_for_tests, RuntimeTest, StfBlueprintTest,
+    C,
+};
+
+// The account funded in the EVM genesis of the integration tests
+const EVM_PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+/// Signs a transaction of the account funded in the EVM genesis, creating a contract with
+/// `init_code`.
+pub(crate) fn sign_evm_create(init_code: &[u8]) -> TransactionSigned {
+    let secret_key = secp256k1::SecretKey::from_str(EVM_PRIVATE_KEY).unwrap();
+    let signer = DevSigner::new(vec![secret_key]);
+    let tx = Transaction::Eip1559(TxEip1559 {
+        to: TransactionKind::Create,
+        input: Bytes::from(init_code.to_vec()),
+        nonce: 0,
+        chain_id: 1,
+        gas_limit: 1_000_000,
+        max_fee_per_gas: 1_000,
+        ..Default::default()
+    });
+    signer.sign_transaction(tx, signer.signers()[0]).unwrap()
+}
+
+/// Encodes the call message of the EVM module executing `tx`.
+pub(crate) fn encode_evm_call(tx: &TransactionSigned) -> Vec<u8> {
+    <RuntimeTest as EncodeCall<sov_evm::Evm<C>>>::encode_call(sov_evm::CallMessage {
+        tx: RlpEvmTransaction {
+            rlp: tx.envelope_encoded().to_vec(),
+        },
+    })
+}
+
+#[test]
+fn soft_confirmed_and_da_evm_transactions_pay_the_same_l1_fee() {
+    let evm_tx = sign_evm_create(&[0x00]);
+    let sequencer_key = read_private_key::<C>().private_key;
+    let tx =
+        SovTransaction::<C>::new_signed_tx(&sequencer_key, encode_evm_call(&evm_tx), 0, 0, 0, 0);
+    let raw_tx = tx.try_to_vec().unwrap();
+
+    // Executes the transaction from a fresh genesis, either in a DA batch or in a soft
+    // confirmation batch, and returns the L1 fee it paid
+    let execute = |soft_confirmed: bool| {
+        let tempdir = tempfile::tempdir().unwrap();
+        let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+        let mut config = get_genesis_config_for_tests();
+        config.runtime.evm.l1_gas_price = 3;
+        config.runtime.evm.l1_fee_overhead = 2100;
+
+        let genesis_block = MockBlock::default();
+        let block_1 = genesis_block.next_mock();
+        let stf: StfBlueprintTest = StfBlueprint::new();
+        let storage = storage_manager
+            .create_storage_on(genesis_block.header())
+            .unwrap();
+        let (genesis_root, storage) = stf.init_chain(storage, config);
+        storage_manager
+            .save_change_set(genesis_block.header(), storage)
+            .unwrap();
+
+        let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+        let result = if soft_confirmed {
+            let batch = UnsignedSoftConfirmationBatch {
+                da_slot_height: 0,
+                da_slot_hash: [0; 32],
+                txs: vec![raw_tx.clone()],
+            };
+            let message = batch.try_to_vec().unwrap();
+            let hash: [u8; 32] = <C as Spec>::Hasher::digest(&message).into();
+            let mut soft_batch = SignedSoftConfirmationBatch::new(
+                batch,
+                hash,
+                sequencer_key.sign(&message).try_to_vec().unwrap(),
+                sequencer_key.pub_key().try_to_vec().unwrap(),
+            );
+            stf.apply_soft_batch(
+                &genesis_root,
+                storage,
+                Default::default(),
+                &block_1.header,
+                &block_1.validity_cond,
+                &mut soft_batch,
+            )
+        } else {
+            let txs = vec![RawTx {
+                data: raw_tx.clone(),
+            }];
+            let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
+            stf.apply_slot(
+                &genesis_root,
+                storage,
+                Default::default(),
+                &block_1.header,
+                &block_1.validity_cond,
+                &mut [blob],
+            )
+        };
+        assert_eq!(1, result.batch_receipts[0].tx_receipts.len());
+
+        let mut working_set = WorkingSet::new(result.change_set);
+        RuntimeTest::default()
+            .evm
+            .get_transaction_receipt(evm_tx.hash(), &mut working_set)
+            .unwrap()
+            .expect("The EVM transaction should have been executed")
+            .l1_fee
+    };
+
+    let l1_fee = execute(false);
+    let tx_gas = evm_tx.envelope_encoded().len() as u64 * 16 + 2100;
+    assert_eq!(U256::from(tx_gas * 3), l1_fee);
+    assert_eq!(l1_fee, execute(true));
+}
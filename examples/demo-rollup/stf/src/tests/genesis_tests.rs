@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+
+use crate::genesis_config::{get_genesis_config, GenesisPaths};
+
+/// Copies the integration tests genesis files to `dir`, replacing the given files.
+fn write_genesis_dir(dir: &Path, replaced: &[(&str, &str)]) -> GenesisPaths {
+    let integ_test_conf_dir: &Path = "../../test-data/genesis/integration-tests".as_ref();
+    for entry in std::fs::read_dir(integ_test_conf_dir).unwrap() {
+        let path = entry.unwrap().path();
+        std::fs::copy(&path, dir.join(path.file_name().unwrap())).unwrap();
+    }
+    for (file, content) in replaced {
+        std::fs::write(dir.join(file), content).unwrap();
+    }
+    GenesisPaths::from_dir(dir)
+}
+
+fn genesis_error(genesis_paths: &GenesisPaths) -> String {
+    let error = get_genesis_config::<DefaultContext, MockDaSpec>(genesis_paths).unwrap_err();
+    format!("{:#}", error)
+}
+
+#[test]
+fn reports_every_malformed_genesis_file() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let genesis_paths = write_genesis_dir(
+        tmpdir.path(),
+        &[
+            ("nft.json", "{"),
+            ("governance.json", r#"{"voting_period": "ten"}"#),
+        ],
+    );
+
+    let error = genesis_error(&genesis_paths);
+    assert!(error.contains("Found 2 errors in the genesis files"));
+    assert!(error.contains("nft.json: "));
+    assert!(error.contains("governance.json: "));
+}
+
+#[test]
+fn reports_every_invalid_module_config() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let address = "sov1l6n2cku82yfqld30lanm2nfw43n2auc8clw7r5u5m6s7p8jrm4zqrr8r94";
+    let bank = format!(
+        r#"{{"tokens": [{{
+            "token_name": "sov-demo-token",
+            "address_and_balances": [["{address}", 100], ["{address}", 200]],
+            "authorized_minters": [],
+            "salt": 0
+        }}]}}"#
+    );
+    let sequencer_registry = format!(
+        r#"{{
+            "seq_rollup_address": "{address}",
+            "seq_da_address": "0000000000000000000000000000000000000000000000000000000000000000",
+            "coins_to_lock": {{
+                "amount": 0,
+                "token_address": "sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp"
+            }},
+            "unbonding_period": 100,
+            "is_preferred_sequencer": true
+        }}"#
+    );
+    let genesis_paths = write_genesis_dir(
+        tmpdir.path(),
+        &[
+            ("bank.json", &bank),
+            ("sequencer_registry.json", &sequencer_registry),
+        ],
+    );
+
+    let error = genesis_error(&genesis_paths);
+    assert!(error.starts_with("Invalid genesis configuration"));
+    assert!(error.contains(&format!(
+        "bank.json: tokens[0].address_and_balances[1]: address {address} is listed twice"
+    )));
+    assert!(error.contains(
+        "sequencer_registry.json: coins_to_lock.amount: the sequencers must lock a positive bond"
+    ));
+}
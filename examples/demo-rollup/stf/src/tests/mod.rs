@@ -13,6 +13,11 @@ use crate::genesis_config::{get_genesis_config, GenesisPaths};
 use crate::runtime::{GenesisConfig, Runtime};
 
 mod da_simulation;
+#[cfg(feature = "experimental")]
+mod evm_tests;
+mod genesis_tests;
+#[cfg(feature = "experimental")]
+mod scheduler_tests;
 mod stf_tests;
 mod tx_revert_tests;
 pub(crate) type C = DefaultContext;
@@ -0,0 +1,79 @@
+use reth_primitives::U256;
+use sov_mock_da::MockBlock;
+use sov_modules_api::hooks::SlotHooks;
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_modules_stf_blueprint::StfBlueprint;
+use sov_rollup_interface::da::Time;
+use sov_rollup_interface::services::da::SlotData;
+use sov_rollup_interface::stf::StateTransitionFunction;
+use sov_rollup_interface::storage::HierarchicalStorageManager;
+
+use crate::tests::evm_tests::{encode_evm_call, sign_evm_create};
+use crate::tests::stf_tests::read_private_key;
+use crate::tests::{
+    create_storage_manager_for_tests, get_genesis_config_for_tests, RuntimeTest, StfBlueprintTest,
+    C,
+};
+
+// Stores the number of the block in slot 0 and its timestamp in slot 1:
+// NUMBER PUSH1 0 SSTORE TIMESTAMP PUSH1 1 SSTORE STOP
+const BLOCK_ENV_INIT_CODE: [u8; 9] = [0x43, 0x60, 0x00, 0x55, 0x42, 0x60, 0x01, 0x55, 0x00];
+
+#[test]
+fn scheduled_evm_calls_see_the_block_of_their_slot() {
+    let tempdir = tempfile::tempdir().unwrap();
+    let mut storage_manager = create_storage_manager_for_tests(tempdir.path());
+    let config = get_genesis_config_for_tests();
+
+    let genesis_block = MockBlock::default();
+    let mut block_1 = genesis_block.next_mock();
+    block_1.header.time = Time::from_secs(1000);
+
+    let stf: StfBlueprintTest = StfBlueprint::new();
+    let storage = storage_manager
+        .create_storage_on(genesis_block.header())
+        .unwrap();
+    let (genesis_root, storage) = stf.init_chain(storage, config);
+    storage_manager
+        .save_change_set(genesis_block.header(), storage)
+        .unwrap();
+
+    let storage = storage_manager.create_storage_on(block_1.header()).unwrap();
+    let working_set = &mut WorkingSet::new(storage);
+    let runtime = RuntimeTest::default();
+
+    let signed = sign_evm_create(&BLOCK_ENV_INIT_CODE);
+    let message = encode_evm_call(&signed);
+
+    let sender = read_private_key::<C>().address;
+    runtime
+        .scheduler
+        .call(
+            sov_scheduler::CallMessage::Schedule { slot: 1, message },
+            &C::new(sender, sender, 1),
+            working_set,
+        )
+        .unwrap();
+
+    runtime.begin_slot_hook(
+        &block_1.header,
+        &block_1.validity_cond,
+        &genesis_root,
+        working_set,
+    );
+
+    let contract = reth_primitives::contract::create_address(signed.recover_signer().unwrap(), 0);
+    let read_slot = |index: u64, working_set: &mut WorkingSet<C>| {
+        runtime
+            .evm
+            .get_storage_at(
+                contract,
+                U256::from(index),
+                Some("pending".to_owned()),
+                working_set,
+            )
+            .unwrap()
+    };
+    assert_eq!(U256::from(1), read_slot(0, working_set));
+    assert_eq!(U256::from(1000), read_slot(1, working_set));
+}
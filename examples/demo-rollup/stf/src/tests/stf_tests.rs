@@ -4,14 +4,15 @@ use sov_data_generators::{has_tx_events, new_test_blob_from_batch};
 use sov_mock_da::{MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::{Context, PrivateKey, WorkingSet};
+use sov_modules_api::digest::Digest;
+use sov_modules_api::{Context, PrivateKey, Spec, WorkingSet};
 use sov_modules_stf_blueprint::{Batch, SequencerOutcome, StfBlueprint};
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 
 use crate::runtime::Runtime;
-use crate::tests::da_simulation::simulate_da;
+use crate::tests::da_simulation::{simulate_da, SIMULATED_VOTE_AMOUNT};
 use crate::tests::{
     create_storage_manager_for_tests, get_genesis_config_for_tests, StfBlueprintTest, C,
 };
@@ -53,7 +54,7 @@ fn test_demo_values_in_db() {
             &mut blobs,
         );
         assert_eq!(1, result.batch_receipts.len());
-        // 2 transactions from value setter
+        // 2 transactions from governance
         // 2 transactions from bank
         assert_eq!(4, result.batch_receipts[0].tx_receipts.len());
 
@@ -85,9 +86,12 @@ fn test_demo_values_in_db() {
             .unwrap();
         assert_eq!(resp, sov_bank::TotalSupplyResponse { amount: Some(1000) });
 
-        let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
-
-        assert_eq!(resp, sov_value_setter::Response { value: Some(33) });
+        let proposal = runtime
+            .governance
+            .get_proposal(0, &mut working_set)
+            .unwrap();
+        assert_eq!(proposal.value, 100);
+        assert_eq!(proposal.votes_for, SIMULATED_VOTE_AMOUNT);
     }
 }
 
@@ -147,9 +151,12 @@ fn test_demo_values_in_cache() {
         .unwrap();
     assert_eq!(resp, sov_bank::TotalSupplyResponse { amount: Some(1000) });
 
-    let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
-
-    assert_eq!(resp, sov_value_setter::Response { value: Some(33) });
+    let proposal = runtime
+        .governance
+        .get_proposal(0, &mut working_set)
+        .unwrap();
+    assert_eq!(proposal.value, 100);
+    assert_eq!(proposal.votes_for, SIMULATED_VOTE_AMOUNT);
 }
 
 #[test]
@@ -159,7 +166,7 @@ fn test_demo_values_not_in_db() {
     let path = tempdir.path();
     let mut storage_manager = create_storage_manager_for_tests(path);
 
-    let value_setter_admin_private_key = DefaultPrivateKey::generate();
+    let proposer_private_key = DefaultPrivateKey::generate();
     let genesis_block = MockBlock::default();
     let block_1 = genesis_block.next_mock();
     let block_2 = block_1.next_mock();
@@ -176,7 +183,7 @@ fn test_demo_values_not_in_db() {
             .save_change_set(genesis_block.header(), storage)
             .unwrap();
 
-        let txs = simulate_da(value_setter_admin_private_key);
+        let txs = simulate_da(proposer_private_key);
         let blob = new_test_blob_from_batch(Batch { txs }, &MOCK_SEQUENCER_DA_ADDRESS, [0; 32]);
         let mut blobs = [blob];
 
@@ -214,9 +221,10 @@ fn test_demo_values_not_in_db() {
             .unwrap();
         assert_eq!(resp, sov_bank::TotalSupplyResponse { amount: Some(1000) });
 
-        let resp = runtime.value_setter.query_value(&mut working_set).unwrap();
-
-        assert_eq!(resp, sov_value_setter::Response { value: None });
+        assert!(runtime
+            .governance
+            .get_proposal(0, &mut working_set)
+            .is_none());
     }
 }
 
@@ -247,6 +255,7 @@ fn test_sequencer_unknown_sequencer() {
 
     let private_key = read_private_key::<DefaultContext>().private_key;
     let txs = simulate_da(private_key);
+    let forced_txs = txs.clone();
     let blob = new_test_blob_from_batch(Batch { txs }, &some_sequencer, [0; 32]);
     let mut blobs = [blob];
 
@@ -261,20 +270,24 @@ fn test_sequencer_unknown_sequencer() {
         &mut blobs,
     );
 
-    assert_eq!(1, apply_block_result.batch_receipts.len());
-    let apply_blob_outcome = apply_block_result.batch_receipts[0].clone();
-
-    assert_eq!(
-        SequencerOutcome::Ignored,
-        apply_blob_outcome.inner,
-        "Batch should have been skipped due to unknown sequencer"
+    assert!(
+        apply_block_result.batch_receipts.is_empty(),
+        "Blob from unknown sequencer should not have been applied"
     );
 
-    // Assert that there are no events
-    assert!(!has_tx_events(&apply_blob_outcome));
+    // The transactions of the blob are waiting to be force included by a registered sequencer
+    let runtime = &mut Runtime::<DefaultContext, MockDaSpec>::default();
+    let mut working_set = WorkingSet::new(apply_block_result.change_set);
+    for tx in &forced_txs {
+        let tx_hash: [u8; 32] = <C as Spec>::Hasher::digest(&tx.data).into();
+        assert!(runtime
+            .sequencer_registry
+            .get_forced_transaction_deadline(&tx_hash, &mut working_set)
+            .is_some());
+    }
 }
 
-fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
+pub(crate) fn read_private_key<C: Context>() -> PrivateKeyAndAddress<C> {
     let token_deployer_data =
         std::fs::read_to_string("../../test-data/keys/token_deployer_private_key.json")
             .expect("Unable to read file to string");
@@ -3,7 +3,7 @@ use sov_data_generators::bank_data::{get_default_private_key, get_default_token_
 use sov_data_generators::{has_tx_events, new_test_blob_from_batch};
 use sov_mock_da::{MockAddress, MockBlock, MockDaSpec, MOCK_SEQUENCER_DA_ADDRESS};
 use sov_modules_api::default_context::DefaultContext;
-use sov_modules_api::{PrivateKey, WorkingSet};
+use sov_modules_api::{ModuleErrorCode, PrivateKey, WorkingSet};
 use sov_modules_stf_blueprint::{Batch, SequencerOutcome, SlashingReason, StfBlueprint, TxEffect};
 use sov_rollup_interface::da::BlobReaderTrait;
 use sov_rollup_interface::services::da::SlotData;
@@ -79,7 +79,13 @@ fn test_tx_revert() {
         // transfer 5000 tokens // this should be reverted
         assert_eq!(txn_receipts[0].receipt, TxEffect::Successful);
         assert_eq!(txn_receipts[1].receipt, TxEffect::Successful);
-        assert_eq!(txn_receipts[2].receipt, TxEffect::Reverted);
+        match &txn_receipts[2].receipt {
+            TxEffect::Reverted { reason, code } => {
+                assert!(reason.contains("Insufficient funds"));
+                assert_eq!(*code, ModuleErrorCode::InsufficientFunds.code());
+            }
+            receipt => panic!("Unexpected receipt: {:?}", receipt),
+        }
 
         apply_block_result.change_set
     };
@@ -112,7 +118,7 @@ fn test_tx_revert() {
 
         let nonce = match runtime
             .accounts
-            .get_account(get_default_private_key().pub_key(), &mut working_set)
+            .get_account(get_default_private_key().pub_key(), None, &mut working_set)
             .unwrap()
         {
             Response::AccountExists { nonce, .. } => nonce,
@@ -188,7 +194,7 @@ fn test_tx_bad_signature() {
         let mut working_set = WorkingSet::new(storage);
         let nonce = match runtime
             .accounts
-            .get_account(get_default_private_key().pub_key(), &mut working_set)
+            .get_account(get_default_private_key().pub_key(), None, &mut working_set)
             .unwrap()
         {
             Response::AccountExists { nonce, .. } => nonce,
@@ -236,7 +242,10 @@ fn test_tx_bad_nonce() {
         assert_eq!(1, apply_block_result.batch_receipts.len());
         let tx_receipts = apply_block_result.batch_receipts[0].tx_receipts.clone();
         // Bad nonce means that the transaction has to be reverted
-        assert_eq!(tx_receipts[0].receipt, TxEffect::Reverted);
+        match &tx_receipts[0].receipt {
+            TxEffect::Reverted { reason, .. } => assert!(reason.starts_with("Tx bad nonce")),
+            receipt => panic!("Unexpected receipt: {:?}", receipt),
+        }
 
         // We don't expect the sequencer to be slashed for a bad nonce
         // The reason for this is that in cases such as based sequencing, the sequencer can
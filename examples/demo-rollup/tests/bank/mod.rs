@@ -3,7 +3,7 @@ use std::net::SocketAddr;
 use borsh::BorshSerialize;
 use demo_stf::genesis_config::GenesisPaths;
 use demo_stf::runtime::RuntimeCall;
-use jsonrpsee::core::client::{Subscription, SubscriptionClientT};
+use jsonrpsee::core::client::{ClientT, Subscription, SubscriptionClientT};
 use jsonrpsee::rpc_params;
 use sov_bank::Coins;
 use sov_mock_da::MockDaSpec;
@@ -11,7 +11,9 @@ use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
 use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{Address, PrivateKey, Spec};
+use sov_modules_rollup_blueprint::SimulateCallResponse;
 use sov_modules_stf_blueprint::kernels::basic::BasicKernelGenesisPaths;
+use sov_modules_stf_blueprint::TxEffect;
 use sov_sequencer::utils::SimpleClient;
 use sov_stf_runner::RollupProverConfig;
 
@@ -56,6 +58,8 @@ async fn build_create_token_tx(key: &DefaultPrivateKey, nonce: u64) -> Transacti
         initial_balance: 1000,
         minter_address: user_address,
         authorized_minters: vec![],
+        authority: None,
+        max_supply: None,
     });
     let chain_id = 0;
     let gas_tip = 0;
@@ -99,6 +103,67 @@ async fn build_transfer_token_tx(
     )
 }
 
+fn transfer_call(token_address: &Address, recipient: &Address, amount: u64) -> String {
+    let msg = RuntimeCall::<DefaultContext, MockDaSpec>::bank(sov_bank::CallMessage::<
+        DefaultContext,
+    >::Transfer {
+        to: recipient.clone(),
+        coins: Coins {
+            amount,
+            token_address: token_address.clone(),
+        },
+    });
+    hex::encode(msg.try_to_vec().unwrap())
+}
+
+async fn simulate_test_bank_calls(
+    client: &SimpleClient,
+    user_address: &Address,
+    token_address: &Address,
+    recipient_address: &Address,
+) -> Result<(), anyhow::Error> {
+    let response: SimulateCallResponse = client
+        .http()
+        .request(
+            "rollup_simulateCall",
+            rpc_params![
+                user_address,
+                transfer_call(token_address, recipient_address, 100),
+                None::<u64>
+            ],
+        )
+        .await?;
+    assert_eq!(TxEffect::Successful, response.effect);
+    // The balances of the sender and of the recipient
+    assert_eq!(2, response.state_diff.len());
+    assert_eq!(2, response.gas_used.len());
+
+    let response: SimulateCallResponse = client
+        .http()
+        .request(
+            "rollup_simulateCall",
+            rpc_params![
+                user_address,
+                transfer_call(token_address, recipient_address, 5000),
+                None::<u64>
+            ],
+        )
+        .await?;
+    assert!(matches!(response.effect, TxEffect::Reverted { .. }));
+    assert!(response.state_diff.is_empty());
+
+    // The simulated transfer wasn't applied
+    let balance_response = sov_bank::BankRpcClient::<DefaultContext>::balance_of(
+        client.http(),
+        None,
+        recipient_address.clone(),
+        token_address.clone(),
+    )
+    .await?;
+    assert_eq!(balance_response.amount, None);
+    Ok(())
+}
+
 async fn send_test_bank_txs(rpc_address: SocketAddr) -> Result<(), anyhow::Error> {
     let key = DefaultPrivateKey::generate();
     let user_address: <DefaultContext as Spec>::Address = key.to_address();
@@ -140,6 +205,8 @@ async fn send_test_bank_txs(rpc_address: SocketAddr) -> Result<(), anyhow::Error
     let recipient_key = DefaultPrivateKey::generate();
     let recipient_address: <DefaultContext as Spec>::Address = recipient_key.to_address();
 
+    simulate_test_bank_calls(&client, &user_address, &token_address, &recipient_address).await?;
+
     let tx = build_transfer_token_tx(
         &key,
         token_address.clone(),
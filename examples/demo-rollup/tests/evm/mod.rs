@@ -111,6 +111,9 @@ async fn execute(client: &TestClient) -> Result<(), Box<dyn std::error::Error>>
     assert_eq!(first_block.number.unwrap().as_u64(), 1);
     assert_eq!(first_block.transactions.len(), 1);
 
+    let mut new_heads = client.eth_subscribe_new_heads().await;
+    let block_filter = client.eth_new_block_filter().await;
+
     let set_arg = 923;
     let tx_hash = {
         let set_value_req = client
@@ -130,6 +133,20 @@ async fn execute(client: &TestClient) -> Result<(), Box<dyn std::error::Error>>
         .await;
     assert_eq!(storage_value, ethereum_types::U256::from(set_arg));
 
+    // The proof of the storage slot is made against the latest state root
+    let proof = client
+        .eth_get_proof(contract_address, vec![ethereum_types::H256::zero()])
+        .await;
+    let latest_block = client.eth_get_block_by_number(None).await;
+    assert_eq!(proof.address, contract_address);
+    assert_eq!(proof.storage_hash, latest_block.state_root);
+    assert!(!proof.account_proof.is_empty());
+    assert_eq!(proof.storage_proof.len(), 1);
+    assert_eq!(
+        proof.storage_proof[0].value,
+        ethereum_types::U256::from(set_arg)
+    );
+
     // Check that the second block has published
     // None should return the latest block
     // It should have a single transaction, setting the value
@@ -138,6 +155,34 @@ async fn execute(client: &TestClient) -> Result<(), Box<dyn std::error::Error>>
     assert_eq!(latest_block.transactions.len(), 1);
     assert_eq!(latest_block.transactions[0].hash, tx_hash);
 
+    // The subscription should be notified about the new head
+    let new_head = new_heads.next().await.unwrap().unwrap();
+    assert_eq!(
+        new_head["hash"],
+        format!("{:?}", latest_block.hash.unwrap())
+    );
+    new_heads.unsubscribe().await.unwrap();
+
+    // The block filter reports the new block once
+    let new_blocks = client.eth_get_filter_changes(block_filter).await.unwrap();
+    assert_eq!(new_blocks, vec![latest_block.hash.unwrap()]);
+    let new_blocks = client.eth_get_filter_changes(block_filter).await.unwrap();
+    assert!(new_blocks.is_empty());
+    assert!(client.eth_uninstall_filter(block_filter).await);
+    assert!(client.eth_get_filter_changes(block_filter).await.is_err());
+
+    // Trace the set value transaction with the call tracer
+    let call_trace = client
+        .debug_trace_transaction(tx_hash, Some(serde_json::json!({ "tracer": "callTracer" })))
+        .await;
+    assert_eq!(call_trace["type"], "CALL");
+    assert_eq!(call_trace["to"], format!("{:?}", contract_address));
+
+    // The default struct logger should report a successful execution
+    let struct_logs = client.debug_trace_transaction(tx_hash, None).await;
+    assert_eq!(struct_logs["failed"], false);
+    assert!(!struct_logs["structLogs"].as_array().unwrap().is_empty());
+
     // This should just pass without error
     client
         .set_value_call(contract_address, set_arg)
@@ -158,10 +203,35 @@ async fn execute(client: &TestClient) -> Result<(), Box<dyn std::error::Error>>
     client.send_publish_batch_request().await;
     client.send_publish_batch_request().await;
 
+    let mut receipts = Vec::default();
     for req in requests {
-        req.await.unwrap();
+        receipts.push(req.await.unwrap().unwrap());
+    }
+
+    // All three transactions of the block are traced in order
+    let block_number = receipts[0].block_number.unwrap();
+    let trace_opts = serde_json::json!({
+        "tracer": "callTracer",
+        "tracerConfig": { "onlyTopCall": true, "withLog": true },
+        "timeout": "10s",
+    });
+    let block_traces = client
+        .debug_trace_block_by_number(
+            Some(format!("0x{:x}", block_number.as_u64())),
+            Some(trace_opts.clone()),
+        )
+        .await;
+    assert_eq!(block_traces.len(), 3);
+    for trace in &block_traces {
+        assert_eq!(trace["result"]["type"], "CALL");
+        assert_eq!(trace["result"]["to"], format!("{:?}", contract_address));
     }
 
+    let block_traces_by_hash = client
+        .debug_trace_block_by_hash(receipts[0].block_hash.unwrap(), Some(trace_opts))
+        .await;
+    assert_eq!(block_traces, block_traces_by_hash);
+
     {
         let get_arg = client.query_contract(contract_address).await?.as_u32();
         // should be one of three values sent in a single block. 150, 151, or 152
@@ -208,6 +278,32 @@ async fn execute(client: &TestClient) -> Result<(), Box<dyn std::error::Error>>
         // assert gas price is higher
         // TODO: emulate gas price oracle here to have exact value
         assert!(latest_gas_price > initial_gas_price);
+
+        // the suggested priority fee is the tip part of the gas price
+        let latest_priority_fee = client.eth_max_priority_fee_per_gas().await;
+        assert!(latest_priority_fee <= latest_gas_price);
+    }
+
+    {
+        let latest_block = client.eth_get_block_by_number(None).await;
+        let latest_number = latest_block.number.unwrap().as_u64();
+
+        let fee_history = client.eth_fee_history(4, "latest", vec![25.0, 75.0]).await;
+        assert_eq!(fee_history.oldest_block.as_u64(), latest_number - 3);
+        // base fees include the one of the next block
+        assert_eq!(fee_history.base_fee_per_gas.len(), 5);
+        assert_eq!(fee_history.gas_used_ratio.len(), 4);
+        assert_eq!(fee_history.reward.len(), 4);
+        assert_eq!(
+            fee_history.base_fee_per_gas[3],
+            latest_block.base_fee_per_gas.unwrap()
+        );
+        // populated blocks pay tips at every percentile
+        assert!(fee_history
+            .reward
+            .iter()
+            .flatten()
+            .any(|tip| !tip.is_zero()));
     }
 
     let first_block = client.eth_get_block_by_number(Some("0".to_owned())).await;
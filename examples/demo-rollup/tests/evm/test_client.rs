@@ -3,14 +3,16 @@ use ethers_core::abi::Address;
 use ethers_core::k256::ecdsa::SigningKey;
 use ethers_core::types::transaction::eip2718::TypedTransaction;
 use ethers_core::types::{
-    Block, Eip1559TransactionRequest, Transaction, TransactionRequest, TxHash,
+    Block, EIP1186ProofResponse, Eip1559TransactionRequest, FeeHistory, Transaction,
+    TransactionRequest, TxHash,
 };
 use ethers_middleware::SignerMiddleware;
 use ethers_providers::{Http, Middleware, PendingTransaction, Provider};
 use ethers_signers::Wallet;
-use jsonrpsee::core::client::ClientT;
+use jsonrpsee::core::client::{ClientT, Subscription, SubscriptionClientT};
 use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::rpc_params;
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
 use reth_primitives::Bytes;
 use sov_evm::SimpleStorageContract;
 
@@ -23,6 +25,7 @@ pub(crate) struct TestClient {
     contract: SimpleStorageContract,
     client: SignerMiddleware<Provider<Http>, Wallet<SigningKey>>,
     http_client: HttpClient,
+    ws_client: WsClient,
 }
 
 impl TestClient {
@@ -43,12 +46,18 @@ impl TestClient {
 
         let http_client = HttpClientBuilder::default().build(host).unwrap();
 
+        let ws_client = WsClientBuilder::default()
+            .build(format!("ws://localhost:{}", rpc_addr.port()))
+            .await
+            .unwrap();
+
         Self {
             chain_id,
             from_addr,
             contract,
             client,
             http_client,
+            ws_client,
         }
     }
 
@@ -271,6 +280,17 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn eth_get_proof(
+        &self,
+        address: Address,
+        keys: Vec<ethereum_types::H256>,
+    ) -> EIP1186ProofResponse {
+        self.http_client
+            .request("eth_getProof", rpc_params![address, keys, "latest"])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_get_code(&self, address: Address) -> Bytes {
         self.http_client
             .request("eth_getCode", rpc_params![address, "latest"])
@@ -295,6 +315,13 @@ impl TestClient {
             .unwrap()
     }
 
+    pub(crate) async fn eth_max_priority_fee_per_gas(&self) -> ethereum_types::U256 {
+        self.http_client
+            .request("eth_maxPriorityFeePerGas", rpc_params![])
+            .await
+            .unwrap()
+    }
+
     pub(crate) async fn eth_get_block_by_number(
         &self,
         block_number: Option<String>,
@@ -339,4 +366,87 @@ impl TestClient {
 
         gas.as_u64()
     }
+
+    pub(crate) async fn eth_fee_history(
+        &self,
+        block_count: u64,
+        newest_block: &str,
+        reward_percentiles: Vec<f64>,
+    ) -> FeeHistory {
+        self.http_client
+            .request(
+                "eth_feeHistory",
+                rpc_params![
+                    ethereum_types::U64::from(block_count),
+                    newest_block,
+                    reward_percentiles
+                ],
+            )
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn eth_subscribe_new_heads(&self) -> Subscription<serde_json::Value> {
+        self.ws_client
+            .subscribe("eth_subscribe", rpc_params!["newHeads"], "eth_unsubscribe")
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn eth_new_block_filter(&self) -> ethereum_types::U128 {
+        self.http_client
+            .request("eth_newBlockFilter", rpc_params![])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn eth_get_filter_changes(
+        &self,
+        filter_id: ethereum_types::U128,
+    ) -> Result<Vec<ethereum_types::H256>, jsonrpsee::core::Error> {
+        self.http_client
+            .request("eth_getFilterChanges", rpc_params![filter_id])
+            .await
+    }
+
+    pub(crate) async fn eth_uninstall_filter(&self, filter_id: ethereum_types::U128) -> bool {
+        self.http_client
+            .request("eth_uninstallFilter", rpc_params![filter_id])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn debug_trace_transaction(
+        &self,
+        tx_hash: TxHash,
+        opts: Option<serde_json::Value>,
+    ) -> serde_json::Value {
+        self.http_client
+            .request("debug_traceTransaction", rpc_params![tx_hash, opts])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn debug_trace_block_by_number(
+        &self,
+        block_number: Option<String>,
+        opts: Option<serde_json::Value>,
+    ) -> Vec<serde_json::Value> {
+        let block_number = block_number.unwrap_or_else(|| "latest".to_owned());
+        self.http_client
+            .request("debug_traceBlockByNumber", rpc_params![block_number, opts])
+            .await
+            .unwrap()
+    }
+
+    pub(crate) async fn debug_trace_block_by_hash(
+        &self,
+        block_hash: ethereum_types::H256,
+        opts: Option<serde_json::Value>,
+    ) -> Vec<serde_json::Value> {
+        self.http_client
+            .request("debug_traceBlockByHash", rpc_params![block_hash, opts])
+            .await
+            .unwrap()
+    }
 }
@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 
 use demo_stf::genesis_config::GenesisPaths;
+use sov_db::pruning::PruningMode;
 use sov_demo_rollup::MockDemoRollup;
 use sov_mock_da::{MockAddress, MockDaConfig};
 use sov_modules_rollup_blueprint::RollupBlueprint;
@@ -8,7 +9,8 @@ use sov_modules_stf_blueprint::kernels::basic::{
     BasicKernelGenesisConfig, BasicKernelGenesisPaths,
 };
 use sov_stf_runner::{
-    ProverServiceConfig, RollupConfig, RollupProverConfig, RpcConfig, RunnerConfig, StorageConfig,
+    DaCompressionConfig, ProverServiceConfig, RollupConfig, RollupProverConfig, RpcConfig,
+    RunnerConfig, StorageConfig,
 };
 use tokio::sync::oneshot;
 
@@ -24,20 +26,39 @@ pub async fn start_rollup(
     let rollup_config = RollupConfig {
         storage: StorageConfig {
             path: temp_path.to_path_buf(),
+            pruning: PruningMode::Archive,
+            index_txs_by_sender: false,
         },
         runner: RunnerConfig {
             start_height: 1,
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".into(),
                 bind_port: 0,
+                limits: Default::default(),
+                admin: None,
             },
+            soft_confirmations: None,
+            metrics: None,
+            rest: None,
+            max_reorg_depth: 100,
+            confirmation_depth: 0,
+            state_diffs: None,
+            parallel_execution: false,
         },
         da: MockDaConfig {
             sender_address: MockAddress::from([0; 32]),
+            faults: vec![],
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            max_retries: 0,
+            queue_path: None,
         },
+        da_compression: Some(DaCompressionConfig { level: 3 }),
+        batch_builder: Default::default(),
+        da_submission: Default::default(),
+        gas_price_oracle: Default::default(),
+        log_level: None,
     };
 
     let mock_demo_rollup = MockDemoRollup {};
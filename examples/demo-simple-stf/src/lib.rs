@@ -4,6 +4,7 @@ use std::marker::PhantomData;
 
 use sha2::Digest;
 use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{BatchReceipt, SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::{ValidityCondition, Zkvm};
 
@@ -103,6 +104,36 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
             change_set: (),
             batch_receipts: receipts,
             witness: (),
+            state_diff: vec![],
         }
     }
+
+    // Soft confirmations are not supported in this tutorial, so they don't produce any receipts.
+    fn apply_soft_batch(
+        &self,
+        _pre_state_root: &[u8; 0],
+        _base_state: Self::PreState,
+        _witness: Self::Witness,
+        _slot_header: &Da::BlockHeader,
+        _validity_condition: &Da::ValidityCondition,
+        _soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    > {
+        SlotResult {
+            state_root: [],
+            change_set: (),
+            batch_receipts: vec![],
+            witness: (),
+            state_diff: vec![],
+        }
+    }
+
+    fn verify_soft_batch(_soft_batch: &SignedSoftConfirmationBatch) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
 }
@@ -0,0 +1,353 @@
+//! Versioning of the encoding of the ledger tables, and migration of databases written by older releases.
+//!
+//! Every table of the ledger has a schema version which is stored in the [`SchemaVersions`] table.
+//! Tables of databases created before schema versions were introduced are at [`BASE_SCHEMA_VERSION`].
+//! Whenever the encoding of the rows of a table changes, a [`LedgerMigration`] from the previous
+//! version is registered in the [`LedgerMigrations`] of the rollup, and databases are migrated
+//! when the [`super::LedgerDB`] is opened.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::Context;
+use sov_schema_db::{Schema, SchemaBatch, DB};
+use tracing::info;
+
+use crate::schema::tables::{
+    BatchByHash, BatchByNumber, EventByKey, EventByLocation, EventByNumber, LastCommittedSlot,
+    SchemaVersions, SenderByTx, SlotByHash, SlotByNumber, TxByAddress, TxByHash, TxByNumber,
+    LEDGER_TABLES,
+};
+
+/// The schema version of the tables which were never migrated.
+pub const BASE_SCHEMA_VERSION: u32 = 1;
+
+/// Rewrites the rows of a ledger table from one schema version to the next.
+#[derive(Clone, Debug)]
+pub struct LedgerMigration {
+    /// The name of the migrated table, as listed in [`LEDGER_TABLES`], e.g. `"TxByNumber"`.
+    pub table: &'static str,
+    /// The schema version the migration applies to. It produces version `from_version + 1`.
+    pub from_version: u32,
+    /// A human-readable summary of the change, shown in the migration reports.
+    pub description: &'static str,
+    /// Converts an encoded value of the old version into its encoding in the new version,
+    /// or returns [`None`] if the value is unchanged. Keys are never rewritten.
+    pub migrate_value: fn(&[u8]) -> anyhow::Result<Option<Vec<u8>>>,
+}
+
+/// The registry of the [`LedgerMigration`]s known to a rollup.
+#[derive(Clone, Debug, Default)]
+pub struct LedgerMigrations {
+    migrations: BTreeMap<(&'static str, u32), LedgerMigration>,
+}
+
+impl LedgerMigrations {
+    /// Adds a migration to the registry. Fails if the table isn't a ledger table, or if
+    /// a migration from the same version of the table was already registered.
+    pub fn register(&mut self, migration: LedgerMigration) -> anyhow::Result<()> {
+        if !LEDGER_TABLES.contains(&migration.table) {
+            anyhow::bail!("{} isn't a ledger table", migration.table);
+        }
+        let key = (migration.table, migration.from_version);
+        if self.migrations.contains_key(&key) {
+            anyhow::bail!(
+                "A migration of table {} from version {} is already registered",
+                migration.table,
+                migration.from_version
+            );
+        }
+        self.migrations.insert(key, migration);
+        Ok(())
+    }
+
+    /// The schema version of the given table expected by this release, which is the version
+    /// produced by its latest migration.
+    pub fn target_version(&self, table: &str) -> u32 {
+        self.migrations
+            .values()
+            .filter(|migration| migration.table == table)
+            .map(|migration| migration.from_version + 1)
+            .max()
+            .unwrap_or(BASE_SCHEMA_VERSION)
+    }
+}
+
+/// A migration which was applied, or which would be applied in a dry run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AppliedMigration {
+    /// The name of the migrated table.
+    pub table: &'static str,
+    /// The schema version of the table before the migration.
+    pub from_version: u32,
+    /// The schema version of the table after the migration.
+    pub to_version: u32,
+    /// The description of the migration.
+    pub description: &'static str,
+    /// The number of rewritten rows.
+    pub rows: usize,
+}
+
+/// The outcome of migrating a ledger database.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// Whether the migrations were only simulated, without writing anything to the database.
+    pub dry_run: bool,
+    /// The migrations, in the order they were applied.
+    pub applied: Vec<AppliedMigration>,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.dry_run {
+            "Would apply"
+        } else {
+            "Applied"
+        };
+        if self.applied.is_empty() {
+            return writeln!(f, "The ledger schema is up to date");
+        }
+        for migration in &self.applied {
+            writeln!(
+                f,
+                "{} migration of {} from version {} to {} ({} rows): {}",
+                verb,
+                migration.table,
+                migration.from_version,
+                migration.to_version,
+                migration.rows,
+                migration.description
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Brings every ledger table of the database to the version expected by `migrations`.
+///
+/// All the rewritten rows and the new schema versions are written in a single batch, so a
+/// failed migration leaves the database untouched. A dry run returns the same report without
+/// writing anything.
+pub(crate) fn run(
+    db: &DB,
+    migrations: &LedgerMigrations,
+    dry_run: bool,
+) -> anyhow::Result<MigrationReport> {
+    let mut batch = SchemaBatch::new();
+    let mut report = MigrationReport {
+        dry_run,
+        applied: Vec::new(),
+    };
+
+    for table in LEDGER_TABLES.iter().copied() {
+        let target_version = migrations.target_version(table);
+        let mut version = db
+            .get::<SchemaVersions>(&table.to_string())?
+            .unwrap_or(BASE_SCHEMA_VERSION);
+        if version > target_version {
+            anyhow::bail!(
+                "Table {} is at schema version {}, which is newer than the version {} of this release",
+                table,
+                version,
+                target_version
+            );
+        }
+
+        let mut steps = Vec::new();
+        while version < target_version {
+            let migration = migrations
+                .migrations
+                .get(&(table, version))
+                .with_context(|| {
+                    format!(
+                        "No migration of table {} from schema version {} is registered",
+                        table, version
+                    )
+                })?;
+            steps.push(migration);
+            version += 1;
+        }
+        if !steps.is_empty() {
+            let rows = migrate_table(db, table, &steps, &mut batch)
+                .with_context(|| format!("Failed to migrate table {}", table))?;
+            report
+                .applied
+                .extend(
+                    steps
+                        .iter()
+                        .zip(rows)
+                        .map(|(migration, rows)| AppliedMigration {
+                            table,
+                            from_version: migration.from_version,
+                            to_version: migration.from_version + 1,
+                            description: migration.description,
+                            rows,
+                        }),
+                );
+        }
+        batch.put::<SchemaVersions>(&table.to_string(), &version)?;
+    }
+
+    if !dry_run {
+        db.write_schemas(batch)?;
+        for migration in &report.applied {
+            info!(
+                "Migrated ledger table {} from schema version {} to {}",
+                migration.table, migration.from_version, migration.to_version
+            );
+        }
+    }
+    Ok(report)
+}
+
+/// Adds the rows of `table` rewritten by the successive `steps` to the batch, and returns
+/// the number of rows changed by each step.
+fn migrate_table(
+    db: &DB,
+    table: &str,
+    steps: &[&LedgerMigration],
+    batch: &mut SchemaBatch,
+) -> anyhow::Result<Vec<usize>> {
+    macro_rules! migrate_rows_of {
+        ($($schema:ty),+) => {
+            $(
+                if table == <$schema>::table_name() {
+                    return migrate_rows::<$schema>(db, steps, batch);
+                }
+            )+
+        };
+    }
+    migrate_rows_of!(
+        SlotByNumber,
+        SlotByHash,
+        BatchByHash,
+        BatchByNumber,
+        TxByHash,
+        TxByNumber,
+        TxByAddress,
+        SenderByTx,
+        EventByKey,
+        EventByNumber,
+        EventByLocation,
+        LastCommittedSlot
+    );
+    anyhow::bail!("Table {} can't be migrated", table)
+}
+
+fn migrate_rows<S: Schema>(
+    db: &DB,
+    steps: &[&LedgerMigration],
+    batch: &mut SchemaBatch,
+) -> anyhow::Result<Vec<usize>> {
+    let mut rows = vec![0; steps.len()];
+    for (key, value) in db.raw_iter::<S>()? {
+        let mut migrated = None;
+        for (step, rows) in steps.iter().zip(rows.iter_mut()) {
+            let current = migrated.as_deref().unwrap_or(&value);
+            if let Some(value) = (step.migrate_value)(current)? {
+                migrated = Some(value);
+                *rows += 1;
+            }
+        }
+        if let Some(migrated) = migrated {
+            batch.put_raw::<S>(key, migrated);
+        }
+    }
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_mock_da::{MockBlob, MockBlock};
+    use sov_rollup_interface::services::da::SlotData;
+
+    use super::{LedgerMigration, LedgerMigrations, BASE_SCHEMA_VERSION};
+    use crate::ledger_db::{LedgerDB, SlotCommit};
+    use crate::schema::tables::{SchemaVersions, SlotByNumber};
+    use crate::schema::types::SlotNumber;
+
+    /// Increments the first byte of the hash of every slot.
+    fn increment_slot_hash(value: &[u8]) -> anyhow::Result<Option<Vec<u8>>> {
+        let mut value = value.to_vec();
+        value[0] = value[0].wrapping_add(1);
+        Ok(Some(value))
+    }
+
+    fn slot_migration(from_version: u32) -> LedgerMigration {
+        LedgerMigration {
+            table: SlotByNumber::table_name(),
+            from_version,
+            description: "Increment the slot hashes",
+            migrate_value: increment_slot_hash,
+        }
+    }
+
+    fn create_db(path: &std::path::Path) -> [u8; 32] {
+        let db = LedgerDB::with_path(path).unwrap();
+        let block = MockBlock::default();
+        let hash = block.hash();
+        db.commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(block))
+            .unwrap();
+        hash
+    }
+
+    fn slot_hash(db: &LedgerDB) -> [u8; 32] {
+        db.db
+            .get::<SlotByNumber>(&SlotNumber(1))
+            .unwrap()
+            .unwrap()
+            .hash
+    }
+
+    #[test]
+    fn test_fresh_db_is_stamped() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        let version = db
+            .db
+            .get::<SchemaVersions>(&SlotByNumber::table_name().to_string())
+            .unwrap();
+        assert_eq!(version, Some(BASE_SCHEMA_VERSION));
+    }
+
+    #[test]
+    fn test_migrations_are_applied_on_open() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut hash = create_db(temp_dir.path());
+
+        let mut migrations = LedgerMigrations::default();
+        migrations.register(slot_migration(1)).unwrap();
+        migrations.register(slot_migration(2)).unwrap();
+        migrations.register(slot_migration(3)).unwrap();
+        assert!(migrations.register(slot_migration(3)).is_err());
+
+        let report = LedgerDB::migrate(temp_dir.path(), &migrations, true).unwrap();
+        assert_eq!(report.applied.len(), 3);
+        assert!(report.applied.iter().all(|migration| migration.rows == 1));
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        assert_eq!(slot_hash(&db), hash, "A dry run must not change the data");
+        drop(db);
+
+        let db = LedgerDB::with_path_and_migrations(temp_dir.path(), &migrations).unwrap();
+        hash[0] = hash[0].wrapping_add(3);
+        assert_eq!(slot_hash(&db), hash);
+        drop(db);
+
+        // The migrations are only applied once
+        let report = LedgerDB::migrate(temp_dir.path(), &migrations, false).unwrap();
+        assert!(report.applied.is_empty());
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_rejected() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        create_db(temp_dir.path());
+
+        let mut migrations = LedgerMigrations::default();
+        migrations.register(slot_migration(1)).unwrap();
+        LedgerDB::with_path_and_migrations(temp_dir.path(), &migrations).unwrap();
+
+        assert!(LedgerDB::with_path(temp_dir.path()).is_err());
+    }
+}
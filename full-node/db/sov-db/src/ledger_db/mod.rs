@@ -1,21 +1,27 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use serde::Serialize;
 use sov_rollup_interface::services::da::SlotData;
 use sov_rollup_interface::stf::{BatchReceipt, Event};
+use sov_rollup_interface::zk::Proof;
 use sov_schema_db::{Schema, SchemaBatch, SeekKeyEncoder, DB};
 
+use self::migrations::{LedgerMigrations, MigrationReport};
+use crate::pruning::PruningMode;
 use crate::rocks_db_config::gen_rocksdb_options;
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByKey, EventByNumber, SlotByHash, SlotByNumber, TxByHash,
-    TxByNumber, LEDGER_TABLES,
+    BatchByHash, BatchByNumber, EventByKey, EventByLocation, EventByNumber, LastCommittedSlot,
+    ProofBySlotNumber, SenderByTx, SlotByHash, SlotByNumber, TxByAddress, TxByHash, TxByNumber,
+    LEDGER_TABLES,
 };
 use crate::schema::types::{
-    split_tx_for_storage, BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot,
-    StoredTransaction, TxNumber,
+    split_tx_for_storage, BatchNumber, CommittedSlotMarker, DbHash, EventNumber, SlotNumber,
+    StoredBatch, StoredProof, StoredSlot, StoredTransaction, TxNumber,
 };
 
+pub mod migrations;
 mod rpc;
 
 const LEDGER_DB_PATH_SUFFIX: &str = "ledger";
@@ -30,6 +36,8 @@ pub struct LedgerDB {
     db: Arc<DB>,
     next_item_numbers: Arc<Mutex<ItemNumbers>>,
     slot_subscriptions: tokio::sync::broadcast::Sender<u64>,
+    pruning: PruningMode,
+    index_txs_by_sender: bool,
 }
 
 /// A SlotNumber, BatchNumber, TxNumber, and EventNumber which are grouped together, typically representing
@@ -47,6 +55,24 @@ pub struct ItemNumbers {
     pub event_number: u64,
 }
 
+/// The ledger data of a single slot, as stored in a [`crate::state_snapshot`].
+#[derive(Debug, PartialEq, BorshSerialize, BorshDeserialize)]
+pub(crate) struct LedgerSnapshot {
+    slot_number: SlotNumber,
+    slot: StoredSlot,
+    batches: Vec<(BatchNumber, StoredBatch)>,
+    txs: Vec<(TxNumber, StoredTransaction)>,
+    events: Vec<(EventNumber, Event)>,
+    event_locations: Vec<(SlotNumber, BatchNumber, TxNumber, EventNumber)>,
+}
+
+impl LedgerSnapshot {
+    /// The number of the exported slot.
+    pub(crate) fn slot_number(&self) -> SlotNumber {
+        self.slot_number
+    }
+}
+
 /// All of the data to be committed to the ledger db for a single slot.
 #[derive(Debug)]
 pub struct SlotCommit<S: SlotData, B, T> {
@@ -54,6 +80,7 @@ pub struct SlotCommit<S: SlotData, B, T> {
     batch_receipts: Vec<BatchReceipt<B, T>>,
     num_txs: usize,
     num_events: usize,
+    state_version: Option<u64>,
 }
 
 impl<S: SlotData, B, T> SlotCommit<S, B, T> {
@@ -74,8 +101,16 @@ impl<S: SlotData, B, T> SlotCommit<S, B, T> {
             batch_receipts: vec![],
             num_txs: 0,
             num_events: 0,
+            state_version: None,
         }
     }
+
+    /// Records the version of the finalized state the slot is committed with,
+    /// in the [`CommittedSlotMarker`] written along with the slot.
+    pub fn set_state_version(&mut self, state_version: u64) {
+        self.state_version = Some(state_version);
+    }
+
     /// Add a `batch` (of transactions) to the commit
     pub fn add_batch(&mut self, batch: BatchReceipt<B, T>) {
         self.num_txs += batch.tx_receipts.len();
@@ -89,30 +124,77 @@ impl LedgerDB {
     /// Open a [`LedgerDB`] (backed by RocksDB) at the specified path.
     /// The returned instance will be at the path `{path}/ledger-db`.
     pub fn with_path(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
-        let path = path.as_ref().join(LEDGER_DB_PATH_SUFFIX);
-        let inner = DB::open(
-            path,
-            "ledger-db",
-            LEDGER_TABLES.iter().copied(),
-            &gen_rocksdb_options(&Default::default(), false),
-        )?;
+        Self::with_path_and_migrations(path, &LedgerMigrations::default())
+    }
 
-        let next_item_numbers = ItemNumbers {
-            slot_number: Self::last_version_written(&inner, SlotByNumber)?.unwrap_or_default() + 1,
-            batch_number: Self::last_version_written(&inner, BatchByNumber)?.unwrap_or_default()
-                + 1,
-            tx_number: Self::last_version_written(&inner, TxByNumber)?.unwrap_or_default() + 1,
-            event_number: Self::last_version_written(&inner, EventByNumber)?.unwrap_or_default()
-                + 1,
-        };
+    /// Open a [`LedgerDB`] like [`LedgerDB::with_path`], after bringing its tables
+    /// to the schema versions of the given [`LedgerMigrations`].
+    pub fn with_path_and_migrations(
+        path: impl AsRef<Path>,
+        migrations: &LedgerMigrations,
+    ) -> Result<Self, anyhow::Error> {
+        let inner = Self::open_db(path)?;
+        migrations::run(&inner, migrations, false)?;
+
+        let next_item_numbers = Self::next_item_numbers_from(&inner)?;
 
         Ok(Self {
             db: Arc::new(inner),
             next_item_numbers: Arc::new(Mutex::new(next_item_numbers)),
             slot_subscriptions: tokio::sync::broadcast::channel(10).0,
+            pruning: PruningMode::Archive,
+            index_txs_by_sender: false,
         })
     }
 
+    /// Runs the [`LedgerMigrations`] on the database at the specified path and reports
+    /// the migrations needed by its tables. Nothing is written if `dry_run` is set.
+    pub fn migrate(
+        path: impl AsRef<Path>,
+        migrations: &LedgerMigrations,
+        dry_run: bool,
+    ) -> anyhow::Result<MigrationReport> {
+        let inner = Self::open_db(path)?;
+        migrations::run(&inner, migrations, dry_run)
+    }
+
+    pub(crate) fn open_db(path: impl AsRef<Path>) -> anyhow::Result<DB> {
+        let path = path.as_ref().join(LEDGER_DB_PATH_SUFFIX);
+        DB::open(
+            path,
+            "ledger-db",
+            LEDGER_TABLES.iter().copied(),
+            &gen_rocksdb_options(&Default::default(), false),
+        )
+    }
+
+    /// Sets how many of the most recent slots are kept by [`LedgerDB::prune`].
+    /// Defaults to [`PruningMode::Archive`].
+    pub fn with_pruning(mut self, mode: PruningMode) -> Self {
+        self.pruning = mode;
+        self
+    }
+
+    /// Sets whether the transactions committed from now on are indexed by the address of their sender,
+    /// so they can be queried with [`get_transactions_by_address`](sov_rollup_interface::rpc::LedgerRpcProvider::get_transactions_by_address).
+    /// Defaults to `false`.
+    /// Transactions imported from a [`crate::state_snapshot`] are not indexed.
+    pub fn with_sender_index(mut self, enabled: bool) -> Self {
+        self.index_txs_by_sender = enabled;
+        self
+    }
+
+    /// Deletes the slots which are older than what the [`PruningMode`] of the database retains.
+    pub fn prune(&self) -> anyhow::Result<()> {
+        let Some((head_slot, _)) = self.get_head_slot()? else {
+            return Ok(());
+        };
+        match self.pruning.oldest_retained_version(head_slot.0) {
+            Some(first_retained_slot) => self.prune_slots_before(SlotNumber(first_retained_slot)),
+            None => Ok(()),
+        }
+    }
+
     /// Get the next slot, block, transaction, and event numbers
     pub fn get_next_items_numbers(&self) -> ItemNumbers {
         self.next_item_numbers.lock().unwrap().clone()
@@ -202,15 +284,28 @@ impl LedgerDB {
         schema_batch.put::<TxByHash>(&tx.hash, tx_number)
     }
 
+    fn put_sender(
+        &self,
+        sender: Vec<u8>,
+        tx_number: &TxNumber,
+        schema_batch: &mut SchemaBatch,
+    ) -> Result<(), anyhow::Error> {
+        schema_batch.put::<TxByAddress>(&(sender.clone(), *tx_number), &())?;
+        schema_batch.put::<SenderByTx>(tx_number, &sender)
+    }
+
     fn put_event(
         &self,
         event: &Event,
         event_number: &EventNumber,
-        tx_number: TxNumber,
+        location: (SlotNumber, BatchNumber, TxNumber),
         schema_batch: &mut SchemaBatch,
     ) -> Result<(), anyhow::Error> {
+        let (slot_number, batch_number, tx_number) = location;
         schema_batch.put::<EventByNumber>(event_number, event)?;
-        schema_batch.put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())
+        schema_batch.put::<EventByKey>(&(event.key().clone(), tx_number, *event_number), &())?;
+        schema_batch
+            .put::<EventByLocation>(&(slot_number, batch_number, tx_number, *event_number), &())
     }
 
     /// Commits a slot to the database by inserting its events, transactions, and batches before
@@ -240,14 +335,19 @@ impl LedgerDB {
             let first_tx_number = current_item_numbers.tx_number;
             let last_tx_number = first_tx_number + batch_receipt.tx_receipts.len() as u64;
             // Insert transactions and events from each batch before inserting the batch
-            for tx in batch_receipt.tx_receipts.into_iter() {
+            for mut tx in batch_receipt.tx_receipts.into_iter() {
+                let sender = tx.sender.take();
                 let (tx_to_store, events) =
                     split_tx_for_storage(tx, current_item_numbers.event_number);
                 for event in events.into_iter() {
                     self.put_event(
                         &event,
                         &EventNumber(current_item_numbers.event_number),
-                        TxNumber(current_item_numbers.tx_number),
+                        (
+                            SlotNumber(current_item_numbers.slot_number),
+                            BatchNumber(current_item_numbers.batch_number),
+                            TxNumber(current_item_numbers.tx_number),
+                        ),
                         &mut schema_batch,
                     )?;
                     current_item_numbers.event_number += 1;
@@ -257,6 +357,15 @@ impl LedgerDB {
                     &TxNumber(current_item_numbers.tx_number),
                     &mut schema_batch,
                 )?;
+                if self.index_txs_by_sender {
+                    if let Some(sender) = sender {
+                        self.put_sender(
+                            sender,
+                            &TxNumber(current_item_numbers.tx_number),
+                            &mut schema_batch,
+                        )?;
+                    }
+                }
                 current_item_numbers.tx_number += 1;
             }
 
@@ -289,6 +398,16 @@ impl LedgerDB {
             &mut schema_batch,
         )?;
 
+        if let Some(state_version) = data_to_commit.state_version {
+            schema_batch.put::<LastCommittedSlot>(
+                &(),
+                &CommittedSlotMarker {
+                    slot_number: SlotNumber(current_item_numbers.slot_number),
+                    state_version,
+                },
+            )?;
+        }
+
         self.db.write_schemas(schema_batch)?;
 
         // Notify subscribers. This call returns an error IFF there are no subscribers, so we don't need to check the result
@@ -299,6 +418,207 @@ impl LedgerDB {
         Ok(())
     }
 
+    /// Deletes the slots numbered below `first_retained_slot`, together with their batches,
+    /// transactions and events. The item numbers of the remaining data don't change.
+    pub fn prune_slots_before(&self, first_retained_slot: SlotNumber) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+
+        let mut slots = self.db.iter::<SlotByNumber>()?;
+        slots.seek_to_first();
+        for slot in slots {
+            let (slot_number, slot) = slot?.into_tuple();
+            if slot_number >= first_retained_slot {
+                break;
+            }
+            self.delete_slot(slot_number, &slot, &mut schema_batch)?;
+        }
+
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Reverts the ledger to the slot of the given marker, after a reorganization of the DA layer
+    /// reverted the following slots. They are deleted, together with their batches, transactions
+    /// and events, and the items committed afterwards are numbered from the retained slot.
+    pub fn rollback_to_slot(&self, marker: CommittedSlotMarker) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+
+        let mut slots = self.db.iter::<SlotByNumber>()?;
+        slots.seek(&SlotNumber(marker.slot_number.0 + 1))?;
+        for slot in slots {
+            let (slot_number, slot) = slot?.into_tuple();
+            self.delete_slot(slot_number, &slot, &mut schema_batch)?;
+        }
+        // The proofs of the reverted slots are invalid on the new fork of the DA layer
+        let mut proofs = self.db.iter::<ProofBySlotNumber>()?;
+        proofs.seek(&SlotNumber(marker.slot_number.0 + 1))?;
+        for proof in proofs {
+            schema_batch.delete::<ProofBySlotNumber>(&proof?.key)?;
+        }
+        schema_batch.put::<LastCommittedSlot>(&(), &marker)?;
+
+        self.db.write_schemas(schema_batch)?;
+        *self.next_item_numbers.lock().unwrap() = Self::next_item_numbers_from(&self.db)?;
+        Ok(())
+    }
+
+    /// Deletes the slot along with its batches, transactions and events.
+    fn delete_slot(
+        &self,
+        slot_number: SlotNumber,
+        slot: &StoredSlot,
+        schema_batch: &mut SchemaBatch,
+    ) -> anyhow::Result<()> {
+        for batch_number in slot.batches.start.0..slot.batches.end.0 {
+            let batch_number = BatchNumber(batch_number);
+            let Some(batch) = self.db.get::<BatchByNumber>(&batch_number)? else {
+                continue;
+            };
+            for tx_number in batch.txs.start.0..batch.txs.end.0 {
+                let tx_number = TxNumber(tx_number);
+                let Some(tx) = self.db.get::<TxByNumber>(&tx_number)? else {
+                    continue;
+                };
+                for event_number in tx.events.start.0..tx.events.end.0 {
+                    let event_number = EventNumber(event_number);
+                    if let Some(event) = self.db.get::<EventByNumber>(&event_number)? {
+                        schema_batch.delete::<EventByKey>(&(
+                            event.key().clone(),
+                            tx_number,
+                            event_number,
+                        ))?;
+                    }
+                    schema_batch.delete::<EventByNumber>(&event_number)?;
+                    schema_batch.delete::<EventByLocation>(&(
+                        slot_number,
+                        batch_number,
+                        tx_number,
+                        event_number,
+                    ))?;
+                }
+                if let Some(sender) = self.db.get::<SenderByTx>(&tx_number)? {
+                    schema_batch.delete::<TxByAddress>(&(sender, tx_number))?;
+                    schema_batch.delete::<SenderByTx>(&tx_number)?;
+                }
+                schema_batch.delete::<TxByHash>(&tx.hash)?;
+                schema_batch.delete::<TxByNumber>(&tx_number)?;
+            }
+            schema_batch.delete::<BatchByHash>(&batch.hash)?;
+            schema_batch.delete::<BatchByNumber>(&batch_number)?;
+        }
+        schema_batch.delete::<SlotByHash>(&slot.hash)?;
+        schema_batch.delete::<SlotByNumber>(&slot_number)
+    }
+
+    /// Returns the ledger data of the slot `slot_number`, which is exported together with the state
+    /// of the rollup at that slot when taking a [`crate::state_snapshot`].
+    pub(crate) fn export_slot(&self, slot_number: SlotNumber) -> anyhow::Result<LedgerSnapshot> {
+        let slot = self
+            .db
+            .get::<SlotByNumber>(&slot_number)?
+            .ok_or_else(|| anyhow::anyhow!("Slot {} is not in the ledger", slot_number.0))?;
+
+        // The last batch, transaction and event committed by the time of the slot are always exported,
+        // even if they belong to earlier slots, so that a node importing the snapshot numbers
+        // the items of the following slots like the rest of the network does.
+        let mut batches = Vec::new();
+        for batch_number in with_last_item(slot.batches.start.0..slot.batches.end.0) {
+            let batch_number = BatchNumber(batch_number);
+            if let Some(batch) = self.db.get::<BatchByNumber>(&batch_number)? {
+                batches.push((batch_number, batch));
+            }
+        }
+
+        let mut txs = Vec::new();
+        if let (Some((_, first)), Some((_, last))) = (batches.first(), batches.last()) {
+            for tx_number in with_last_item(first.txs.start.0..last.txs.end.0) {
+                let tx_number = TxNumber(tx_number);
+                if let Some(tx) = self.db.get::<TxByNumber>(&tx_number)? {
+                    txs.push((tx_number, tx));
+                }
+            }
+        }
+
+        let mut events = Vec::new();
+        if let (Some((_, first)), Some((_, last))) = (txs.first(), txs.last()) {
+            for event_number in with_last_item(first.events.start.0..last.events.end.0) {
+                let event_number = EventNumber(event_number);
+                if let Some(event) = self.db.get::<EventByNumber>(&event_number)? {
+                    events.push((event_number, event));
+                }
+            }
+        }
+
+        let mut event_locations = Vec::new();
+        let mut iter = self.db.iter::<EventByLocation>()?;
+        iter.seek(&(slot_number, BatchNumber(0), TxNumber(0), EventNumber(0)))?;
+        for item in iter {
+            let location = item?.key;
+            if location.0 != slot_number {
+                break;
+            }
+            event_locations.push(location);
+        }
+
+        Ok(LedgerSnapshot {
+            slot_number,
+            slot,
+            batches,
+            txs,
+            events,
+            event_locations,
+        })
+    }
+
+    /// Writes the ledger data of a [`crate::state_snapshot`] to an empty ledger.
+    pub(crate) fn import_slot(&self, snapshot: LedgerSnapshot) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.get_head_slot()?.is_none(),
+            "A snapshot can only be imported into an empty ledger"
+        );
+        let mut schema_batch = SchemaBatch::new();
+
+        for (event_number, event) in &snapshot.events {
+            schema_batch.put::<EventByNumber>(event_number, event)?;
+        }
+        // The events which were emitted before the slot are only kept to preserve the numbering,
+        // so they are not indexed
+        for (slot_number, batch_number, tx_number, event_number) in snapshot.event_locations {
+            let Some((_, event)) = snapshot
+                .events
+                .iter()
+                .find(|(number, _)| *number == event_number)
+            else {
+                anyhow::bail!("Event {} is missing from the snapshot", event_number.0);
+            };
+            self.put_event(
+                event,
+                &event_number,
+                (slot_number, batch_number, tx_number),
+                &mut schema_batch,
+            )?;
+        }
+        for (tx_number, tx) in &snapshot.txs {
+            self.put_transaction(tx, tx_number, &mut schema_batch)?;
+        }
+        for (batch_number, batch) in &snapshot.batches {
+            self.put_batch(batch, batch_number, &mut schema_batch)?;
+        }
+        self.put_slot(&snapshot.slot, &snapshot.slot_number, &mut schema_batch)?;
+
+        self.db.write_schemas(schema_batch)?;
+        *self.next_item_numbers.lock().unwrap() = Self::next_item_numbers_from(&self.db)?;
+        Ok(())
+    }
+
+    fn next_item_numbers_from(db: &DB) -> anyhow::Result<ItemNumbers> {
+        Ok(ItemNumbers {
+            slot_number: Self::last_version_written(db, SlotByNumber)?.unwrap_or_default() + 1,
+            batch_number: Self::last_version_written(db, BatchByNumber)?.unwrap_or_default() + 1,
+            tx_number: Self::last_version_written(db, TxByNumber)?.unwrap_or_default() + 1,
+            event_number: Self::last_version_written(db, EventByNumber)?.unwrap_or_default() + 1,
+        })
+    }
+
     fn last_version_written<T: Schema<Key = U>, U: Into<u64>>(
         db: &DB,
         _schema: T,
@@ -313,6 +633,90 @@ impl LedgerDB {
         }
     }
 
+    /// Returns the marker of the last slot committed with the version of its finalized state, if any.
+    /// See [`SlotCommit::set_state_version`].
+    pub fn get_last_committed_slot(&self) -> anyhow::Result<Option<CommittedSlotMarker>> {
+        self.db.get::<LastCommittedSlot>(&())
+    }
+
+    /// Returns the number of the committed slot of the DA block with the given hash, if any.
+    pub fn get_slot_number_by_hash(&self, hash: &DbHash) -> anyhow::Result<Option<SlotNumber>> {
+        self.db.get::<SlotByHash>(hash)
+    }
+
+    /// Returns the committed batch with the given number, if it wasn't pruned.
+    pub fn get_batch(&self, batch_number: BatchNumber) -> anyhow::Result<Option<StoredBatch>> {
+        self.db.get::<BatchByNumber>(&batch_number)
+    }
+
+    /// Returns the committed slot containing the batch with the given number, with the number
+    /// of the slot, if it wasn't pruned.
+    pub fn get_slot_of_batch(
+        &self,
+        batch_number: BatchNumber,
+    ) -> anyhow::Result<Option<(SlotNumber, StoredSlot)>> {
+        let mut iter = self.db.iter::<SlotByNumber>()?;
+        iter.seek_to_first();
+        let (mut low, mut high) = match (iter.next().transpose()?, self.get_head_slot()?) {
+            (Some(first), Some((last, _))) => (first.key.0, last.0),
+            _ => return Ok(None),
+        };
+
+        // The batches of the slots are numbered in order, so the slot is found by bisection
+        while low <= high {
+            let middle = SlotNumber(low + (high - low) / 2);
+            let Some(slot) = self.db.get::<SlotByNumber>(&middle)? else {
+                return Ok(None);
+            };
+            if batch_number < slot.batches.start {
+                high = middle.0 - 1;
+            } else if batch_number >= slot.batches.end {
+                low = middle.0 + 1;
+            } else {
+                return Ok(Some((middle, slot)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Stores the proof of the slots from `first_slot` to `last_slot`, inclusive.
+    /// A proof previously stored for the same last slot is replaced.
+    /// The proofs are kept when the slots they prove are pruned.
+    pub fn put_proof(
+        &self,
+        first_slot: SlotNumber,
+        last_slot: SlotNumber,
+        proof: Proof,
+    ) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            first_slot <= last_slot,
+            "The proof of slots {:?} to {:?} proves an empty range",
+            first_slot,
+            last_slot
+        );
+        self.db
+            .put::<ProofBySlotNumber>(&last_slot, &StoredProof { first_slot, proof })
+    }
+
+    /// Returns the stored proof covering the given slot, with the last slot it proves, if any.
+    /// If several proofs cover the slot, the one ending first is returned.
+    pub fn get_proof_for_slot(
+        &self,
+        slot_number: SlotNumber,
+    ) -> anyhow::Result<Option<(SlotNumber, StoredProof)>> {
+        let mut iter = self.db.iter::<ProofBySlotNumber>()?;
+        iter.seek(&slot_number)?;
+
+        match iter.next() {
+            Some(Ok(item)) => {
+                let (last_slot, proof) = item.into_tuple();
+                Ok((proof.first_slot <= slot_number).then_some((last_slot, proof)))
+            }
+            Some(Err(e)) => Err(e),
+            _ => Ok(None),
+        }
+    }
+
     /// Get the most recent committed slot, if any
     pub fn get_head_slot(&self) -> anyhow::Result<Option<(SlotNumber, StoredSlot)>> {
         let mut iter = self.db.iter::<SlotByNumber>()?;
@@ -325,3 +729,13 @@ impl LedgerDB {
         }
     }
 }
+
+/// Extends an empty range of item numbers to the last item before it, if there is one.
+/// Item numbers start at 1.
+fn with_last_item(range: std::ops::Range<u64>) -> std::ops::Range<u64> {
+    if range.is_empty() && range.end > 1 {
+        range.end - 1..range.end
+    } else {
+        range
+    }
+}
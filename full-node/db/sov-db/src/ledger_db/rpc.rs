@@ -1,14 +1,16 @@
 use serde::de::DeserializeOwned;
 use sov_rollup_interface::rpc::{
-    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventIdentifier, ItemOrHash,
-    LedgerRpcProvider, QueryMode, SlotIdAndOffset, SlotIdentifier, SlotResponse, TxIdAndOffset,
-    TxIdentifier, TxResponse,
+    BatchIdAndOffset, BatchIdentifier, BatchResponse, EventFilter, EventIdentifier, EventResponse,
+    ItemOrHash, LedgerRpcProvider, Order, Page, PageRequest, QueryMode, SlotIdAndOffset,
+    SlotIdentifier, SlotResponse, TxIdAndOffset, TxIdentifier, TxResponse,
 };
 use sov_rollup_interface::stf::Event;
+use sov_schema_db::{Schema, SeekKeyEncoder};
 use tokio::sync::broadcast::Receiver;
 
 use crate::schema::tables::{
-    BatchByHash, BatchByNumber, EventByNumber, SlotByHash, SlotByNumber, TxByHash, TxByNumber,
+    BatchByHash, BatchByNumber, EventByLocation, EventByNumber, SlotByHash, SlotByNumber,
+    TxByAddress, TxByHash, TxByNumber,
 };
 use crate::schema::types::{
     BatchNumber, EventNumber, SlotNumber, StoredBatch, StoredSlot, TxNumber,
@@ -22,6 +24,8 @@ const MAX_BATCHES_PER_REQUEST: u64 = 20;
 const MAX_TRANSACTIONS_PER_REQUEST: u64 = 100;
 /// The maximum number of events that can be requested in a single RPC range query
 const MAX_EVENTS_PER_REQUEST: u64 = 500;
+/// The maximum number of slots that can be searched in a single filtered events query
+const MAX_SLOTS_PER_EVENT_FILTER: u64 = 100;
 
 use super::LedgerDB;
 
@@ -139,6 +143,62 @@ impl LedgerRpcProvider for LedgerDB {
         Ok(out)
     }
 
+    fn get_filtered_events(
+        &self,
+        filter: &EventFilter,
+    ) -> Result<Vec<EventResponse>, anyhow::Error> {
+        anyhow::ensure!(
+            filter.from_slot <= filter.to_slot,
+            "from_slot must be <= to_slot"
+        );
+        anyhow::ensure!(
+            filter.to_slot - filter.from_slot <= MAX_SLOTS_PER_EVENT_FILTER,
+            "requested slot range too large. Max: {}",
+            MAX_SLOTS_PER_EVENT_FILTER
+        );
+
+        let mut iter = self.db.iter::<EventByLocation>()?;
+        iter.seek(&(
+            SlotNumber(filter.from_slot),
+            BatchNumber(0),
+            TxNumber(0),
+            EventNumber(0),
+        ))?;
+
+        // At most `MAX_EVENTS_PER_REQUEST` events are returned, the rest can be fetched
+        // by narrowing down the slot range
+        let mut out = Vec::new();
+        for item in iter {
+            let (slot_number, batch_number, tx_number, event_number) = item?.key;
+            if slot_number.0 > filter.to_slot || out.len() >= MAX_EVENTS_PER_REQUEST as usize {
+                break;
+            }
+
+            let Some(event) = self.db.get::<EventByNumber>(&event_number)? else {
+                continue;
+            };
+            if let Some(module) = &filter.module {
+                if event.module() != Some(module.as_str()) {
+                    continue;
+                }
+            }
+            if let Some(key) = &filter.key {
+                if event.key().inner().as_slice() != key.as_bytes() {
+                    continue;
+                }
+            }
+
+            out.push(EventResponse {
+                slot_number: slot_number.into(),
+                batch_number: batch_number.into(),
+                tx_number: tx_number.into(),
+                event_number: event_number.into(),
+                event,
+            });
+        }
+        Ok(out)
+    }
+
     fn get_head<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         query_mode: QueryMode,
@@ -270,6 +330,60 @@ impl LedgerRpcProvider for LedgerDB {
         self.get_transactions(&ids, query_mode)
     }
 
+    fn get_slots_page<B: DeserializeOwned, T: DeserializeOwned>(
+        &self,
+        request: &PageRequest,
+    ) -> Result<Page<SlotResponse<B, T>>, anyhow::Error> {
+        self.get_page::<SlotByNumber, _>(request, MAX_SLOTS_PER_REQUEST, SlotNumber, |num, slot| {
+            self.populate_slot_response(num, slot, request.query_mode)
+        })
+    }
+
+    fn get_batches_page<B: DeserializeOwned, T: DeserializeOwned>(
+        &self,
+        request: &PageRequest,
+    ) -> Result<Page<BatchResponse<B, T>>, anyhow::Error> {
+        self.get_page::<BatchByNumber, _>(
+            request,
+            MAX_BATCHES_PER_REQUEST,
+            BatchNumber,
+            |_, batch| self.populate_batch_response(batch, request.query_mode),
+        )
+    }
+
+    fn get_transactions_page<T: DeserializeOwned>(
+        &self,
+        request: &PageRequest,
+    ) -> Result<Page<TxResponse<T>>, anyhow::Error> {
+        self.get_page::<TxByNumber, _>(request, MAX_TRANSACTIONS_PER_REQUEST, TxNumber, |_, tx| {
+            Ok(tx.try_into()?)
+        })
+    }
+
+    fn get_transactions_by_address<T: DeserializeOwned>(
+        &self,
+        address: &[u8],
+        request: &PageRequest,
+    ) -> Result<Page<TxResponse<T>>, anyhow::Error> {
+        anyhow::ensure!(
+            self.index_txs_by_sender,
+            "transactions are not indexed by sender"
+        );
+        self.get_page_with::<TxByAddress, _>(
+            request,
+            MAX_TRANSACTIONS_PER_REQUEST,
+            |num| (address.to_vec(), TxNumber(num)),
+            // The transactions of other senders are past the end of the page
+            |(sender, num)| (sender == address).then_some(num.into()),
+            |num, _| {
+                self.db
+                    .get::<TxByNumber>(&TxNumber(num))?
+                    .ok_or_else(|| anyhow::anyhow!("Transaction {} is not in the ledger", num))?
+                    .try_into()
+            },
+        )
+    }
+
     fn subscribe_slots(&self) -> Result<Receiver<u64>, anyhow::Error> {
         Ok(self.slot_subscriptions.subscribe())
     }
@@ -346,6 +460,84 @@ impl LedgerDB {
         }
     }
 
+    /// Reads a page of the items of the table `T`, which has to be keyed by the item numbers.
+    /// At most `max_items` items are returned, regardless of the requested limit.
+    fn get_page<T, R>(
+        &self,
+        request: &PageRequest,
+        max_items: u64,
+        to_key: fn(u64) -> T::Key,
+        populate: impl FnMut(u64, T::Value) -> Result<R, anyhow::Error>,
+    ) -> Result<Page<R>, anyhow::Error>
+    where
+        T: Schema,
+        T::Key: Into<u64> + SeekKeyEncoder<T>,
+    {
+        self.get_page_with::<T, R>(request, max_items, to_key, |key| Some(key.into()), populate)
+    }
+
+    /// Reads a page of the items of the table `T`, whose keys are mapped to and from the item
+    /// numbers by `to_key` and `to_number`. The page ends at the first key which `to_number`
+    /// doesn't map to an item number.
+    fn get_page_with<T, R>(
+        &self,
+        request: &PageRequest,
+        max_items: u64,
+        to_key: impl Fn(u64) -> T::Key,
+        to_number: impl Fn(T::Key) -> Option<u64>,
+        mut populate: impl FnMut(u64, T::Value) -> Result<R, anyhow::Error>,
+    ) -> Result<Page<R>, anyhow::Error>
+    where
+        T: Schema,
+        T::Key: SeekKeyEncoder<T>,
+    {
+        let limit = request.limit.unwrap_or(max_items).min(max_items);
+        anyhow::ensure!(limit > 0, "limit must be greater than 0");
+
+        let mut iter = self.db.iter::<T>()?;
+        let (lowest, highest) = match request.order {
+            Order::Ascending => {
+                let (from, to) = (request.from.unwrap_or(0), request.to.unwrap_or(u64::MAX));
+                anyhow::ensure!(from <= to, "from must be <= to");
+                iter.seek(&to_key(from))?;
+                (from, to)
+            }
+            Order::Descending => {
+                let (from, to) = (request.from.unwrap_or(u64::MAX), request.to.unwrap_or(0));
+                anyhow::ensure!(
+                    from >= to,
+                    "from must be >= to when the order is descending"
+                );
+                iter.seek_for_prev(&to_key(from))?;
+                iter = iter.rev();
+                (to, from)
+            }
+        };
+
+        let mut items = Vec::new();
+        for item in iter {
+            let item = item?;
+            let Some(num) = to_number(item.key) else {
+                break;
+            };
+            if num < lowest || num > highest {
+                break;
+            }
+            // The first item which doesn't fit is where the next page starts
+            if items.len() as u64 == limit {
+                return Ok(Page {
+                    items,
+                    next_cursor: Some(num),
+                });
+            }
+            items.push(populate(num, item.value)?);
+        }
+        Ok(Page {
+            items,
+            next_cursor: None,
+        })
+    }
+
     fn populate_slot_response<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
         number: u64,
@@ -428,10 +620,14 @@ impl LedgerDB {
 
 #[cfg(test)]
 mod tests {
-    use sov_mock_da::{MockBlob, MockBlock};
-    use sov_rollup_interface::rpc::LedgerRpcProvider;
+    use sov_mock_da::{MockBlob, MockBlock, MockBlockHeader};
+    use sov_rollup_interface::rpc::{LedgerRpcProvider, Order, PageRequest, QueryMode};
+    use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
+    use sov_rollup_interface::zk::Proof;
 
     use crate::ledger_db::{LedgerDB, SlotCommit};
+    use crate::pruning::PruningMode;
+    use crate::schema::types::{BatchNumber, CommittedSlotMarker, SlotNumber};
     #[test]
     fn test_slot_subscription() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -444,4 +640,251 @@ mod tests {
 
         assert_eq!(rx.blocking_recv().unwrap(), 1);
     }
+
+    #[test]
+    fn test_prune_slots() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path())
+            .unwrap()
+            .with_pruning(PruningMode::KeepLast(2));
+
+        for height in 1..=3 {
+            let block = MockBlock {
+                header: MockBlockHeader::from_height(height),
+                ..Default::default()
+            };
+            db.commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(block))
+                .unwrap();
+        }
+        db.prune().unwrap();
+
+        let first_slot = db
+            .get_slot_by_number::<MockBlob, Vec<u8>>(1, QueryMode::Compact)
+            .unwrap();
+        assert!(first_slot.is_none());
+        let first_slot_hash = MockBlockHeader::from_height(1).hash.0;
+        assert!(db
+            .get_slot_by_hash::<MockBlob, Vec<u8>>(&first_slot_hash, QueryMode::Compact)
+            .unwrap()
+            .is_none());
+        for number in 2..=3 {
+            assert!(db
+                .get_slot_by_number::<MockBlob, Vec<u8>>(number, QueryMode::Compact)
+                .unwrap()
+                .is_some());
+        }
+        assert_eq!(4, db.get_next_items_numbers().slot_number);
+    }
+
+    #[test]
+    fn test_rollback_to_slot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+
+        for height in 1..=3 {
+            let block = MockBlock {
+                header: MockBlockHeader::from_height(height),
+                ..Default::default()
+            };
+            let mut commit = SlotCommit::<_, MockBlob, Vec<u8>>::new(block);
+            commit.set_state_version(height + 1);
+            db.commit_slot(commit).unwrap();
+        }
+        let marker = CommittedSlotMarker {
+            slot_number: SlotNumber(1),
+            state_version: 2,
+        };
+        db.rollback_to_slot(marker).unwrap();
+
+        assert_eq!(Some(marker), db.get_last_committed_slot().unwrap());
+        assert_eq!(SlotNumber(1), db.get_head_slot().unwrap().unwrap().0);
+        let reverted_slot_hash = MockBlockHeader::from_height(2).hash.0;
+        assert!(db
+            .get_slot_by_hash::<MockBlob, Vec<u8>>(&reverted_slot_hash, QueryMode::Compact)
+            .unwrap()
+            .is_none());
+        assert_eq!(2, db.get_next_items_numbers().slot_number);
+    }
+
+    #[test]
+    fn test_proofs_by_slot() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        for height in 1..=4 {
+            let block = MockBlock {
+                header: MockBlockHeader::from_height(height),
+                ..Default::default()
+            };
+            let mut commit = SlotCommit::<_, MockBlob, Vec<u8>>::new(block);
+            commit.set_state_version(height + 1);
+            db.commit_slot(commit).unwrap();
+        }
+        let slot_proof = Proof::Full(vec![1]);
+        let range_proof = Proof::Full(vec![2, 3]);
+        db.put_proof(SlotNumber(1), SlotNumber(1), slot_proof.clone())
+            .unwrap();
+        db.put_proof(SlotNumber(2), SlotNumber(4), range_proof.clone())
+            .unwrap();
+        assert!(db
+            .put_proof(SlotNumber(3), SlotNumber(2), slot_proof.clone())
+            .is_err());
+
+        let proof_of = |slot| {
+            db.get_proof_for_slot(SlotNumber(slot))
+                .unwrap()
+                .map(|(last_slot, stored)| (stored.first_slot, last_slot, stored.proof))
+        };
+        assert_eq!(
+            Some((SlotNumber(1), SlotNumber(1), slot_proof)),
+            proof_of(1)
+        );
+        for slot in 2..=4 {
+            assert_eq!(
+                Some((SlotNumber(2), SlotNumber(4), range_proof.clone())),
+                proof_of(slot)
+            );
+        }
+        assert_eq!(None, proof_of(5));
+        assert_eq!(
+            Some(SlotNumber(3)),
+            db.get_slot_number_by_hash(&MockBlockHeader::from_height(3).hash.0)
+                .unwrap()
+        );
+
+        // The proofs of reverted slots are deleted
+        db.rollback_to_slot(CommittedSlotMarker {
+            slot_number: SlotNumber(3),
+            state_version: 4,
+        })
+        .unwrap();
+        assert_eq!(None, proof_of(2));
+        assert!(proof_of(1).is_some());
+    }
+
+    #[test]
+    fn test_slot_of_batch() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        for (height, num_batches) in [(1, 1), (2, 0), (3, 2)] {
+            let block = MockBlock {
+                header: MockBlockHeader::from_height(height),
+                ..Default::default()
+            };
+            let mut commit = SlotCommit::<_, Vec<u8>, Vec<u8>>::new(block);
+            for i in 0..num_batches {
+                commit.add_batch(BatchReceipt {
+                    batch_hash: [height as u8 * 10 + i; 32],
+                    tx_receipts: vec![],
+                    inner: vec![],
+                });
+            }
+            db.commit_slot(commit).unwrap();
+        }
+
+        let slot_of = |batch| {
+            db.get_slot_of_batch(BatchNumber(batch))
+                .unwrap()
+                .map(|(slot_number, _)| slot_number)
+        };
+        assert_eq!(Some(SlotNumber(1)), slot_of(1));
+        assert_eq!(Some(SlotNumber(3)), slot_of(2));
+        assert_eq!(Some(SlotNumber(3)), slot_of(3));
+        assert_eq!(None, slot_of(4));
+        assert_eq!(None, slot_of(0));
+        assert_eq!(
+            [31; 32],
+            db.get_batch(BatchNumber(3)).unwrap().unwrap().hash
+        );
+        assert!(db.get_batch(BatchNumber(4)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_slots_page() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        for height in 1..=3 {
+            let block = MockBlock {
+                header: MockBlockHeader::from_height(height),
+                ..Default::default()
+            };
+            db.commit_slot(SlotCommit::<_, MockBlob, Vec<u8>>::new(block))
+                .unwrap();
+        }
+        let slot_numbers = |request: &PageRequest| {
+            let page = db.get_slots_page::<MockBlob, Vec<u8>>(request).unwrap();
+            let numbers: Vec<u64> = page.items.iter().map(|slot| slot.number).collect();
+            (numbers, page.next_cursor)
+        };
+
+        let mut request = PageRequest {
+            limit: Some(2),
+            query_mode: QueryMode::Compact,
+            ..Default::default()
+        };
+        assert_eq!((vec![1, 2], Some(3)), slot_numbers(&request));
+        request.from = Some(3);
+        assert_eq!((vec![3], None), slot_numbers(&request));
+
+        request.from = None;
+        request.order = Order::Descending;
+        assert_eq!((vec![3, 2], Some(1)), slot_numbers(&request));
+        request.to = Some(2);
+        request.limit = None;
+        assert_eq!((vec![3, 2], None), slot_numbers(&request));
+
+        request.from = Some(1);
+        assert!(db.get_slots_page::<MockBlob, Vec<u8>>(&request).is_err());
+    }
+
+    #[test]
+    fn test_transactions_by_address() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let db = LedgerDB::with_path(temp_dir.path())
+            .unwrap()
+            .with_sender_index(true);
+        let senders = [b"alice".to_vec(), b"bob".to_vec(), b"alice".to_vec()];
+
+        let mut commit = SlotCommit::<_, Vec<u8>, Vec<u8>>::new(MockBlock::default());
+        commit.add_batch(BatchReceipt {
+            batch_hash: [0; 32],
+            tx_receipts: senders
+                .iter()
+                .enumerate()
+                .map(|(i, sender)| TransactionReceipt {
+                    tx_hash: [i as u8; 32],
+                    body_to_save: None,
+                    sender: Some(sender.clone()),
+                    events: vec![],
+                    receipt: vec![],
+                    gas_used: vec![],
+                })
+                .collect(),
+            inner: vec![],
+        });
+        db.commit_slot(commit).unwrap();
+
+        let tx_hashes = |address: &[u8], request: &PageRequest| {
+            let page = db
+                .get_transactions_by_address::<Vec<u8>>(address, request)
+                .unwrap();
+            let hashes: Vec<u8> = page.items.iter().map(|tx| tx.hash[0]).collect();
+            (hashes, page.next_cursor)
+        };
+
+        let mut request = PageRequest::default();
+        assert_eq!((vec![0, 2], None), tx_hashes(b"alice", &request));
+        assert_eq!((vec![1], None), tx_hashes(b"bob", &request));
+        assert_eq!((vec![], None), tx_hashes(b"carol", &request));
+
+        request.limit = Some(1);
+        assert_eq!((vec![0], Some(3)), tx_hashes(b"alice", &request));
+        request.order = Order::Descending;
+        assert_eq!((vec![2], Some(1)), tx_hashes(b"alice", &request));
+
+        let unindexed_dir = tempfile::tempdir().unwrap();
+        let unindexed_db = LedgerDB::with_path(unindexed_dir.path()).unwrap();
+        assert!(unindexed_db
+            .get_transactions_by_address::<Vec<u8>>(b"alice", &request)
+            .is_err());
+    }
 }
@@ -3,6 +3,12 @@
 //! - Types and traits for storing and retrieving ledger data can be found in the [`ledger_db`] module
 //! - DB "Table" definitions can be found in the [`schema`] module
 //! - Types and traits for storing state data can be found in the [`state_db`] module
+//! - The pruning of historical state can be configured with the [`pruning`] module
+//! - Interrupted state writes can be rolled back with the [`recovery`] module
+//! - The databases of an offline node can be compacted, measured and verified with the [`maintenance`] module
+//! - Snapshots for bootstrapping new nodes can be exported and imported with the [`state_snapshot`] module
+//! - The transactions of the sequencer mempool are persisted in the [`mempool_db`] module
+//! - The blobs of the sequencer waiting for DA are persisted in the [`submission_queue_db`] module
 //! - The default db configuration is generated in the [`rocks_db_config`] module
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
@@ -11,6 +17,16 @@
 /// This wrapper implements helper traits for writing blocks to the ledger, and for
 /// serving historical data via RPC
 pub mod ledger_db;
+/// Implements the maintenance of the databases of a full node: compaction, size reports,
+/// integrity checks and estimates of the space reclaimable by pruning.
+pub mod maintenance;
+/// Implements a wrapper around RocksDB meant for persisting the sequencer mempool across restarts.
+pub mod mempool_db;
+/// Implements the garbage collection of historical state which is not needed anymore.
+pub mod pruning;
+/// Implements the rollback of the state written by a finalization which was interrupted,
+/// so that the state database matches the ledger again after a crash.
+pub mod recovery;
 /// Implements helpers for configuring RocksDB.
 pub mod rocks_db_config;
 /// Defines the tables used by the Sovereign SDK.
@@ -18,6 +34,12 @@ pub mod schema;
 /// Implements a wrapper around [RocksDB](https://rocksdb.org/) meant for storing rollup state.
 /// This is primarily used as the backing store for the [JMT(JellyfishMerkleTree)](https://docs.rs/jmt/latest/jmt/).
 pub mod state_db;
+/// Implements the export and import of state snapshots, which allow new full nodes
+/// to start from a recent slot instead of replaying the rollup from genesis.
+pub mod state_snapshot;
+/// Implements a wrapper around RocksDB meant for persisting the blobs of the sequencer
+/// which weren't submitted to DA yet, so they're resubmitted after a restart.
+pub mod submission_queue_db;
 
 /// Implements a wrapper around RocksDB meant for storing state only accessible
 /// outside of the zkVM execution environment, as this data is not included in
@@ -0,0 +1,314 @@
+use std::path::Path;
+
+use serde::Serialize;
+use sov_schema_db::snapshot::NoopQueryManager;
+use sov_schema_db::{Schema, SchemaIntegrity, DB};
+
+use crate::ledger_db::LedgerDB;
+use crate::native_db::NativeDB;
+use crate::pruning::{estimate_state_pruning, latest_version, PruningMode, ReclaimableSpace};
+use crate::schema::tables::{
+    BatchByHash, BatchByNumber, EventByKey, EventByLocation, EventByNumber, JmtNodes, JmtValues,
+    KeyHashToKey, LastCommittedSlot, ModuleAccessoryState, OldestRetainedVersion,
+    ProofBySlotNumber, SchemaVersions, SenderByTx, SlotByHash, SlotByNumber, StaleNodes, StateKeys,
+    TxByAddress, TxByHash, TxByNumber, LEDGER_TABLES, NATIVE_TABLES, STATE_TABLES,
+};
+use crate::schema::types::{SlotNumber, TxNumber};
+use crate::state_db::StateDB;
+
+/// A database of a full node, stored under the path of its storage config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeDatabase {
+    /// The [`LedgerDB`], storing the slots, batches, transactions and events.
+    Ledger,
+    /// The [`StateDB`], storing the JMT of the rollup state.
+    State,
+    /// The [`NativeDB`], storing the accessory state.
+    Native,
+}
+
+impl NodeDatabase {
+    /// All the databases of a full node.
+    pub const ALL: [NodeDatabase; 3] = [
+        NodeDatabase::Ledger,
+        NodeDatabase::State,
+        NodeDatabase::Native,
+    ];
+
+    /// The name of the database.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeDatabase::Ledger => "ledger",
+            NodeDatabase::State => "state",
+            NodeDatabase::Native => "native",
+        }
+    }
+
+    /// The names of the tables of the database, which are its column families.
+    pub fn tables(&self) -> &'static [&'static str] {
+        match self {
+            NodeDatabase::Ledger => LEDGER_TABLES,
+            NodeDatabase::State => STATE_TABLES,
+            NodeDatabase::Native => NATIVE_TABLES,
+        }
+    }
+
+    /// Opens the database of the node with the given storage path.
+    /// RocksDB locks its databases, so it fails while the node is running.
+    pub fn open(&self, storage_path: impl AsRef<Path>) -> anyhow::Result<DB> {
+        match self {
+            NodeDatabase::Ledger => LedgerDB::open_db(storage_path),
+            NodeDatabase::State => StateDB::<NoopQueryManager>::setup_schema_db(storage_path),
+            NodeDatabase::Native => NativeDB::<NoopQueryManager>::setup_schema_db(storage_path),
+        }
+    }
+
+    /// Returns the sizes of the tables of the database.
+    pub fn table_sizes(&self, db: &DB) -> anyhow::Result<Vec<TableSize>> {
+        self.tables()
+            .iter()
+            .map(|table| TableSize::of(db, table))
+            .collect()
+    }
+
+    /// Compacts all the tables of the database, which reclaims the space of the records deleted
+    /// by pruning and rollbacks.
+    pub fn compact(&self, db: &DB) -> anyhow::Result<()> {
+        for table in self.tables() {
+            tracing::info!(database = self.name(), table, "Compacting");
+            db.compact_cf(table)?;
+        }
+        Ok(())
+    }
+
+    /// Decodes all the records of the database, to find the ones which are corrupted.
+    pub fn verify(&self, db: &DB) -> anyhow::Result<Vec<TableIntegrity>> {
+        match self {
+            NodeDatabase::Ledger => Ok(vec![
+                TableIntegrity::of::<SlotByNumber>(db)?,
+                TableIntegrity::of::<SlotByHash>(db)?,
+                TableIntegrity::of::<BatchByHash>(db)?,
+                TableIntegrity::of::<BatchByNumber>(db)?,
+                TableIntegrity::of::<TxByHash>(db)?,
+                TableIntegrity::of::<TxByNumber>(db)?,
+                TableIntegrity::of::<TxByAddress>(db)?,
+                TableIntegrity::of::<SenderByTx>(db)?,
+                TableIntegrity::of::<EventByKey>(db)?,
+                TableIntegrity::of::<EventByNumber>(db)?,
+                TableIntegrity::of::<EventByLocation>(db)?,
+                TableIntegrity::of::<LastCommittedSlot>(db)?,
+                TableIntegrity::of::<ProofBySlotNumber>(db)?,
+                TableIntegrity::of::<SchemaVersions>(db)?,
+            ]),
+            NodeDatabase::State => Ok(vec![
+                TableIntegrity::of::<KeyHashToKey>(db)?,
+                TableIntegrity::of::<JmtValues>(db)?,
+                TableIntegrity::of::<StateKeys>(db)?,
+                TableIntegrity::of::<JmtNodes>(db)?,
+                TableIntegrity::of::<StaleNodes>(db)?,
+                TableIntegrity::of::<OldestRetainedVersion>(db)?,
+            ]),
+            NodeDatabase::Native => Ok(vec![TableIntegrity::of::<ModuleAccessoryState>(db)?]),
+        }
+    }
+}
+
+/// The size of a table, as estimated by RocksDB.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TableSize {
+    /// The name of the table.
+    pub table: &'static str,
+    /// The estimated number of records.
+    pub estimated_records: u64,
+    /// The estimated size of the live records on disk, in bytes.
+    pub live_data_bytes: u64,
+    /// The size of the files of the table on disk, including the deleted and overwritten records
+    /// which weren't compacted yet, in bytes.
+    pub sst_files_bytes: u64,
+    /// The size of the records which are still in memory, in bytes.
+    pub memtable_bytes: u64,
+}
+
+impl TableSize {
+    fn of(db: &DB, table: &'static str) -> anyhow::Result<Self> {
+        Ok(Self {
+            table,
+            estimated_records: db.get_property(table, "rocksdb.estimate-num-keys")?,
+            live_data_bytes: db.get_property(table, "rocksdb.estimate-live-data-size")?,
+            sst_files_bytes: db.get_property(table, "rocksdb.total-sst-files-size")?,
+            memtable_bytes: db.get_property(table, "rocksdb.cur-size-all-mem-tables")?,
+        })
+    }
+}
+
+/// The result of the verification of a table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableIntegrity {
+    /// The name of the table.
+    pub table: &'static str,
+    /// The records of the table and the corrupted ones.
+    pub integrity: SchemaIntegrity,
+}
+
+impl TableIntegrity {
+    fn of<S: Schema>(db: &DB) -> anyhow::Result<Self> {
+        Ok(Self {
+            table: S::COLUMN_FAMILY_NAME,
+            integrity: db.verify_schema::<S>()?,
+        })
+    }
+}
+
+/// The space which pruning the node with a [`PruningMode`] would reclaim.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PruningEstimate {
+    /// The oldest state version which would be retained, `None` if nothing would be pruned.
+    pub oldest_retained_version: Option<u64>,
+    /// The stale JMT nodes and the overwritten values of the state.
+    pub state: ReclaimableSpace,
+    /// The first slot which would be retained, `None` if no slot would be pruned.
+    pub first_retained_slot: Option<u64>,
+    /// The slots, batches, transactions and events of the ledger. Their indexes, which are
+    /// deleted with them, aren't counted.
+    pub ledger: ReclaimableSpace,
+}
+
+/// Estimates the space which would be reclaimed by pruning the state and ledger databases
+/// with the given mode, without deleting anything.
+pub fn estimate_pruning(
+    ledger_db: &DB,
+    state_db: &DB,
+    mode: PruningMode,
+) -> anyhow::Result<PruningEstimate> {
+    let oldest_retained_version =
+        latest_version(state_db)?.and_then(|version| mode.oldest_retained_version(version));
+    let state = match oldest_retained_version {
+        Some(version) => estimate_state_pruning(state_db, version)?,
+        None => ReclaimableSpace::default(),
+    };
+
+    let mut slots = ledger_db.iter::<SlotByNumber>()?;
+    slots.seek_to_last();
+    let first_retained_slot = match slots.next().transpose()? {
+        Some(head) => mode.oldest_retained_version(head.key.0),
+        None => None,
+    };
+    let ledger = match first_retained_slot {
+        Some(slot) => estimate_ledger_pruning(ledger_db, slot)?,
+        None => ReclaimableSpace::default(),
+    };
+
+    Ok(PruningEstimate {
+        oldest_retained_version,
+        state,
+        first_retained_slot,
+        ledger,
+    })
+}
+
+/// Returns the space taken by the slots numbered below `first_retained_slot`, which are deleted
+/// by [`LedgerDB::prune_slots_before`], together with their batches, transactions and events.
+fn estimate_ledger_pruning(db: &DB, first_retained_slot: u64) -> anyhow::Result<ReclaimableSpace> {
+    let mut space = ReclaimableSpace::default();
+    // The items are numbered in order, so the ones of the pruned slots precede the first
+    // item of the first retained slot.
+    let Some(slot) = db.get::<SlotByNumber>(&SlotNumber(first_retained_slot))? else {
+        return Ok(space);
+    };
+    add_records_before::<SlotByNumber>(db, |number| number.0 < first_retained_slot, &mut space)?;
+    add_records_before::<BatchByNumber>(db, |number| number.0 < slot.batches.start.0, &mut space)?;
+
+    let first_tx = match db.get::<BatchByNumber>(&slot.batches.start)? {
+        Some(batch) => batch.txs.start.0,
+        None => return Ok(space),
+    };
+    add_records_before::<TxByNumber>(db, |number| number.0 < first_tx, &mut space)?;
+
+    let first_event = match db.get::<TxByNumber>(&TxNumber(first_tx))? {
+        Some(tx) => tx.events.start.0,
+        None => return Ok(space),
+    };
+    add_records_before::<EventByNumber>(db, |number| number.0 < first_event, &mut space)?;
+
+    Ok(space)
+}
+
+/// Adds the records of the table to `space`, from the first one while `is_pruned` holds.
+fn add_records_before<S: Schema>(
+    db: &DB,
+    is_pruned: impl Fn(&S::Key) -> bool,
+    space: &mut ReclaimableSpace,
+) -> anyhow::Result<()> {
+    let mut iter = db.iter::<S>()?;
+    iter.seek_to_first();
+    for item in iter {
+        let item = item?;
+        if !is_pruned(&item.key) {
+            break;
+        }
+        space.add(std::mem::size_of::<u64>(), item.value_size_bytes);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use jmt::storage::{Node, NodeKey};
+    use sov_schema_db::SchemaBatch;
+
+    use super::*;
+    use crate::pruning::prune_state;
+
+    #[test]
+    fn test_estimate_and_prune_state() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let ledger_db = NodeDatabase::Ledger.open(tmpdir.path()).unwrap();
+        let state_db = NodeDatabase::State.open(tmpdir.path()).unwrap();
+
+        // Each version replaces the root of the previous one
+        for version in 0..5 {
+            let mut batch = SchemaBatch::new();
+            batch
+                .put::<JmtNodes>(&NodeKey::new_empty_path(version), &Node::Null)
+                .unwrap();
+            if version > 0 {
+                batch
+                    .put::<StaleNodes>(&(version, NodeKey::new_empty_path(version - 1)), &())
+                    .unwrap();
+            }
+            state_db.write_schemas(batch).unwrap();
+        }
+
+        let estimate = estimate_pruning(&ledger_db, &state_db, PruningMode::KeepLast(2)).unwrap();
+        assert_eq!(Some(3), estimate.oldest_retained_version);
+        // The roots of versions 0 to 2 and their stale index
+        assert_eq!(6, estimate.state.records);
+        assert!(estimate.state.bytes > 0);
+        assert_eq!(None, estimate.first_retained_slot);
+        assert_eq!(ReclaimableSpace::default(), estimate.ledger);
+
+        let estimate = estimate_pruning(&ledger_db, &state_db, PruningMode::Archive).unwrap();
+        assert_eq!(None, estimate.oldest_retained_version);
+        assert_eq!(ReclaimableSpace::default(), estimate.state);
+
+        prune_state(&state_db, 3).unwrap();
+        let estimate = estimate_pruning(&ledger_db, &state_db, PruningMode::KeepLast(2)).unwrap();
+        assert_eq!(ReclaimableSpace::default(), estimate.state);
+
+        NodeDatabase::State.compact(&state_db).unwrap();
+        let integrity = NodeDatabase::State.verify(&state_db).unwrap();
+        assert_eq!(STATE_TABLES.len(), integrity.len());
+        let jmt_nodes = integrity
+            .iter()
+            .find(|table| table.table == JmtNodes::table_name())
+            .unwrap();
+        assert_eq!(2, jmt_nodes.integrity.records);
+        assert!(integrity
+            .iter()
+            .all(|table| table.integrity.corrupted_keys.is_empty()));
+        assert_eq!(
+            STATE_TABLES.len(),
+            NodeDatabase::State.table_sizes(&state_db).unwrap().len()
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use sov_schema_db::{SchemaBatch, DB};
+
+use crate::rocks_db_config::gen_rocksdb_options;
+use crate::schema::tables::{PooledTxs, MEMPOOL_TABLES};
+use crate::schema::types::StoredPooledTx;
+
+const MEMPOOL_DB_PATH_SUFFIX: &str = "mempool";
+
+/// A database persisting the transactions of the sequencer mempool, keyed by the order they
+/// arrived in, so the pending transactions aren't lost when the sequencer restarts.
+#[derive(Clone, Debug)]
+pub struct MempoolDB {
+    db: Arc<DB>,
+}
+
+impl MempoolDB {
+    /// Opens the [`MempoolDB`] in the `mempool` directory of `path`, creating it if it's missing.
+    pub fn with_path(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().join(MEMPOOL_DB_PATH_SUFFIX);
+        let db = DB::open(
+            path,
+            "mempool-db",
+            MEMPOOL_TABLES.iter().copied(),
+            &gen_rocksdb_options(&Default::default(), false),
+        )?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Writes the new transactions and deletes the removed ones in a single atomic batch.
+    pub fn write(&self, inserted: &[(u64, StoredPooledTx)], removed: &[u64]) -> anyhow::Result<()> {
+        let mut schema_batch = SchemaBatch::new();
+        for (arrival_number, tx) in inserted {
+            schema_batch.put::<PooledTxs>(arrival_number, tx)?;
+        }
+        for arrival_number in removed {
+            schema_batch.delete::<PooledTxs>(arrival_number)?;
+        }
+        self.db.write_schemas(schema_batch)
+    }
+
+    /// Returns all the persisted transactions, in the order they arrived in.
+    pub fn load(&self) -> anyhow::Result<Vec<(u64, StoredPooledTx)>> {
+        let mut iter = self.db.iter::<PooledTxs>()?;
+        iter.seek_to_first();
+        iter.map(|item| Ok(item?.into_tuple())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(raw: u8) -> StoredPooledTx {
+        StoredPooledTx {
+            raw: vec![raw],
+            received_at: 1000,
+        }
+    }
+
+    #[test]
+    fn test_persisted_txs_survive_reopening() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        {
+            let db = MempoolDB::with_path(tmpdir.path()).unwrap();
+            db.write(&[(2, tx(2)), (1, tx(1)), (300, tx(3))], &[])
+                .unwrap();
+            db.write(&[(4, tx(4))], &[1]).unwrap();
+        }
+
+        let db = MempoolDB::with_path(tmpdir.path()).unwrap();
+        assert_eq!(
+            vec![(2, tx(2)), (4, tx(4)), (300, tx(3))],
+            db.load().unwrap()
+        );
+    }
+}
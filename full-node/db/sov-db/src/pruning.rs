@@ -0,0 +1,312 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{mpsc, Arc, Mutex, MutexGuard};
+
+use jmt::storage::{Node, NodeKey};
+use jmt::Version;
+use serde::{Deserialize, Serialize};
+use sov_schema_db::schema::{KeyEncoder, ValueCodec};
+use sov_schema_db::{SchemaBatch, DB};
+
+use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, OldestRetainedVersion, StaleNodes};
+use crate::schema::types::StateKey;
+
+/// How much history of the rollup is kept by a full node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PruningMode {
+    /// Every historical version is kept forever.
+    #[default]
+    Archive,
+    /// Only the given number of most recent finalized versions are kept.
+    KeepLast(u64),
+    /// Only the latest finalized version is kept.
+    Minimal,
+}
+
+impl PruningMode {
+    /// Returns the oldest version which has to be kept once `latest_version` is finalized,
+    /// or [`None`] if nothing can be pruned.
+    pub fn oldest_retained_version(&self, latest_version: Version) -> Option<Version> {
+        let versions_to_keep = match self {
+            PruningMode::Archive => return None,
+            PruningMode::KeepLast(versions) => (*versions).max(1),
+            PruningMode::Minimal => 1,
+        };
+        latest_version
+            .checked_sub(versions_to_keep - 1)
+            .filter(|version| *version > 0)
+    }
+}
+
+/// Allows the users of the storage, like the prover, to prevent versions from being pruned
+/// while they still need them.
+///
+/// Every user retains versions under its own name, and a version is only pruned once
+/// none of them retains it anymore.
+#[derive(Debug, Clone, Default)]
+pub struct PruningHandle {
+    retained_from: Arc<Mutex<BTreeMap<&'static str, Version>>>,
+}
+
+impl PruningHandle {
+    /// Prevents the versions starting from `version` from being pruned on behalf of `holder`,
+    /// until [`PruningHandle::release`] is called for it. Replaces the version previously
+    /// retained by the same holder.
+    pub fn retain_from(&self, holder: &'static str, version: Version) {
+        self.holders().insert(holder, version);
+    }
+
+    /// Allows the versions retained by `holder` to be pruned again, according to the
+    /// [`PruningMode`] and the versions retained by the other holders.
+    pub fn release(&self, holder: &'static str) {
+        self.holders().remove(holder);
+    }
+
+    /// The oldest version which is still needed by any holder, if any.
+    pub fn retained_from_version(&self) -> Option<Version> {
+        self.holders().values().min().copied()
+    }
+
+    fn holders(&self) -> MutexGuard<'_, BTreeMap<&'static str, Version>> {
+        self.retained_from
+            .lock()
+            .expect("Pruning handle lock is poisoned")
+    }
+}
+
+/// Garbage-collects the JMT nodes and values of the state database which are not needed
+/// by any of the retained versions.
+///
+/// Pruning is done on a background thread, which is notified with [`StatePruner::notify_finalized`]
+/// every time new data is written to the database.
+pub struct StatePruner {
+    mode: PruningMode,
+    handle: PruningHandle,
+    sender: Option<mpsc::Sender<()>>,
+}
+
+impl StatePruner {
+    /// Creates a pruner for the given state database, starting the background pruning thread
+    /// unless the node is running in [`PruningMode::Archive`].
+    pub fn new(db: Arc<DB>, mode: PruningMode) -> Self {
+        let handle = PruningHandle::default();
+        let sender = (mode != PruningMode::Archive).then(|| {
+            let (sender, receiver) = mpsc::channel();
+            let handle = handle.clone();
+            std::thread::spawn(move || run_pruning(&db, mode, &handle, receiver));
+            sender
+        });
+
+        Self {
+            mode,
+            handle,
+            sender,
+        }
+    }
+
+    /// The [`PruningMode`] of this pruner.
+    pub fn mode(&self) -> PruningMode {
+        self.mode
+    }
+
+    /// Returns a handle allowing to retain the versions which are still needed.
+    pub fn handle(&self) -> PruningHandle {
+        self.handle.clone()
+    }
+
+    /// Notifies the pruner that a new version has been finalized and written to the database.
+    pub fn notify_finalized(&self) {
+        if let Some(sender) = &self.sender {
+            // The pruning thread only stops if it failed, which has already been logged
+            let _ = sender.send(());
+        }
+    }
+}
+
+fn run_pruning(db: &DB, mode: PruningMode, handle: &PruningHandle, receiver: mpsc::Receiver<()>) {
+    let mut pruned_up_to = 0;
+    // The loop stops when the [`StatePruner`] is dropped
+    while receiver.recv().is_ok() {
+        // Multiple versions might have been finalized while the previous pruning was running
+        while receiver.try_recv().is_ok() {}
+
+        let latest_version = match latest_version(db) {
+            Ok(Some(version)) => version,
+            Ok(None) => continue,
+            Err(e) => {
+                tracing::error!("Failed to read the latest state version: {:?}", e);
+                return;
+            }
+        };
+        let Some(mut oldest_retained_version) = mode.oldest_retained_version(latest_version) else {
+            continue;
+        };
+        if let Some(retained_from) = handle.retained_from_version() {
+            oldest_retained_version = oldest_retained_version.min(retained_from);
+        }
+        if oldest_retained_version <= pruned_up_to {
+            continue;
+        }
+
+        match prune_state(db, oldest_retained_version) {
+            Ok(()) => {
+                tracing::debug!(
+                    "Pruned state versions older than {}",
+                    oldest_retained_version
+                );
+                pruned_up_to = oldest_retained_version;
+            }
+            Err(e) => {
+                tracing::error!("Failed to prune the state: {:?}", e);
+                return;
+            }
+        }
+    }
+}
+
+pub(crate) fn latest_version(db: &DB) -> anyhow::Result<Option<Version>> {
+    let mut iter = db.iter::<JmtNodes>()?;
+    iter.seek_to_last();
+    iter.next()
+        .transpose()
+        .map(|item| item.map(|item| item.key.version()))
+}
+
+/// The records which are deleted by pruning, and their size.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ReclaimableSpace {
+    /// The number of records.
+    pub records: u64,
+    /// The size of the keys and values of the records, in bytes. The space freed on disk
+    /// differs because of compression, and only once the database is compacted.
+    pub bytes: u64,
+}
+
+impl ReclaimableSpace {
+    pub(crate) fn add(&mut self, key_bytes: usize, value_bytes: usize) {
+        self.records += 1;
+        self.bytes += (key_bytes + value_bytes) as u64;
+    }
+}
+
+/// Removes all the JMT nodes and values which are not needed to read the state
+/// at `oldest_retained_version` and the versions after it.
+pub fn prune_state(db: &DB, oldest_retained_version: Version) -> anyhow::Result<()> {
+    let mut batch = SchemaBatch::new();
+    collect_stale_state(db, oldest_retained_version, &mut batch)?;
+    batch.put::<OldestRetainedVersion>(&(), &oldest_retained_version)?;
+
+    db.write_schemas(batch)
+}
+
+/// Returns the space which [`prune_state`] would reclaim, without deleting anything.
+pub fn estimate_state_pruning(
+    db: &DB,
+    oldest_retained_version: Version,
+) -> anyhow::Result<ReclaimableSpace> {
+    collect_stale_state(db, oldest_retained_version, &mut SchemaBatch::new())
+}
+
+/// Adds the deletion of the records pruned by [`prune_state`] to the batch.
+fn collect_stale_state(
+    db: &DB,
+    oldest_retained_version: Version,
+    batch: &mut SchemaBatch,
+) -> anyhow::Result<ReclaimableSpace> {
+    let mut space = ReclaimableSpace::default();
+    let mut stale_key_hashes = BTreeSet::new();
+
+    let mut iter = db.iter::<StaleNodes>()?;
+    iter.seek_to_first();
+    for item in iter {
+        let (stale_since_version, node_key) = item?.key;
+        // A node which became stale at version `v` is not reachable from the roots of `v` and later versions
+        if stale_since_version > oldest_retained_version {
+            break;
+        }
+        let key_bytes = <NodeKey as KeyEncoder<JmtNodes>>::encode_key(&node_key)?.len();
+        if let Some(node) = db.get::<JmtNodes>(&node_key)? {
+            let value_bytes = <Node as ValueCodec<JmtNodes>>::encode_value(&node)?.len();
+            space.add(key_bytes, value_bytes);
+            if let Node::Leaf(leaf) = node {
+                stale_key_hashes.insert(leaf.key_hash().0);
+            }
+        }
+        // The stale index only holds the version and the key of the node
+        space.add(key_bytes + 8, 0);
+        batch.delete::<JmtNodes>(&node_key)?;
+        batch.delete::<StaleNodes>(&(stale_since_version, node_key))?;
+    }
+
+    for key_hash in stale_key_hashes {
+        if let Some(key) = db.get::<KeyHashToKey>(&key_hash)? {
+            prune_values(db, &key, oldest_retained_version, batch, &mut space)?;
+        }
+    }
+
+    Ok(space)
+}
+
+/// Removes the values of `key` which were overwritten at or before `oldest_retained_version`.
+/// The latest of them is kept, since it's the value at `oldest_retained_version`.
+fn prune_values(
+    db: &DB,
+    key: &StateKey,
+    oldest_retained_version: Version,
+    batch: &mut SchemaBatch,
+    space: &mut ReclaimableSpace,
+) -> anyhow::Result<()> {
+    let mut iter = db.iter::<JmtValues>()?;
+    iter.seek(&(key, 0))?;
+
+    let mut previous = None;
+    for item in iter {
+        let item = item?;
+        let (found_key, version) = &item.key;
+        if found_key != key || *version > oldest_retained_version {
+            break;
+        }
+        if let Some((previous_version, value_bytes)) =
+            previous.replace((*version, item.value_size_bytes))
+        {
+            space.add(key.len() + 8, value_bytes);
+            batch.delete::<JmtValues>(&(key.clone(), previous_version))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PruningHandle, PruningMode};
+
+    #[test]
+    fn test_oldest_retained_version() {
+        assert_eq!(None, PruningMode::Archive.oldest_retained_version(100));
+        assert_eq!(Some(100), PruningMode::Minimal.oldest_retained_version(100));
+        assert_eq!(
+            Some(91),
+            PruningMode::KeepLast(10).oldest_retained_version(100)
+        );
+        assert_eq!(None, PruningMode::KeepLast(10).oldest_retained_version(5));
+    }
+
+    #[test]
+    fn test_pruning_handle_tracks_every_holder() {
+        let handle = PruningHandle::default();
+        assert_eq!(None, handle.retained_from_version());
+
+        handle.retain_from("prover", 10);
+        handle.retain_from("reorgs", 20);
+        assert_eq!(Some(10), handle.retained_from_version());
+
+        // A holder moving forward doesn't release the versions retained by the others
+        handle.retain_from("prover", 30);
+        assert_eq!(Some(20), handle.retained_from_version());
+
+        handle.release("reorgs");
+        assert_eq!(Some(30), handle.retained_from_version());
+        handle.release("prover");
+        assert_eq!(None, handle.retained_from_version());
+    }
+}
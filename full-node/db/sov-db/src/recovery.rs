@@ -0,0 +1,109 @@
+use jmt::storage::NodeKey;
+use jmt::Version;
+use sov_schema_db::{SchemaBatch, DB};
+
+use crate::pruning::latest_version;
+use crate::schema::tables::{JmtNodes, JmtValues, ModuleAccessoryState, StaleNodes};
+
+/// Returns the latest version written to the given state database, or 0 if it's empty.
+pub fn latest_state_version(state_db: &DB) -> anyhow::Result<Version> {
+    Ok(latest_version(state_db)?.unwrap_or_default())
+}
+
+/// Removes the JMT nodes and values written to the state database after `version`,
+/// so that `version` is the latest version of the state again.
+pub fn rollback_state(state_db: &DB, version: Version) -> anyhow::Result<()> {
+    let first_removed_version = version + 1;
+    let mut batch = SchemaBatch::new();
+
+    // Nodes are sorted by their version first
+    let mut iter = state_db.iter::<JmtNodes>()?;
+    iter.seek(&NodeKey::new_empty_path(first_removed_version))?;
+    for item in iter {
+        batch.delete::<JmtNodes>(&item?.key)?;
+    }
+
+    // The nodes which became stale after `version` are still part of the tree at `version`,
+    // only their stale index is removed.
+    let mut iter = state_db.iter::<StaleNodes>()?;
+    iter.seek(&(first_removed_version, NodeKey::new_empty_path(0)))?;
+    for item in iter {
+        batch.delete::<StaleNodes>(&item?.key)?;
+    }
+
+    // Values are sorted by their key first, so all of them have to be checked
+    let mut iter = state_db.iter::<JmtValues>()?;
+    iter.seek_to_first();
+    for item in iter {
+        let key = item?.key;
+        if key.1 > version {
+            batch.delete::<JmtValues>(&key)?;
+        }
+    }
+
+    state_db.write_schemas(batch)
+}
+
+/// Removes the accessory state written after the state `version`, see [`rollback_state`].
+pub fn rollback_accessory_state(native_db: &DB, version: Version) -> anyhow::Result<()> {
+    let mut batch = SchemaBatch::new();
+    let mut iter = native_db.iter::<ModuleAccessoryState>()?;
+    iter.seek_to_first();
+    for item in iter {
+        let key = item?.key;
+        // The accessory writes of a state version are stored with the version preceding it
+        if key.1 >= version {
+            batch.delete::<ModuleAccessoryState>(&key)?;
+        }
+    }
+    native_db.write_schemas(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use jmt::storage::Node;
+
+    use super::*;
+    use crate::rocks_db_config::gen_rocksdb_options;
+    use crate::schema::tables::STATE_TABLES;
+
+    fn put_version(db: &DB, version: Version) {
+        let mut batch = SchemaBatch::new();
+        batch
+            .put::<JmtNodes>(&NodeKey::new_empty_path(version), &Node::Null)
+            .unwrap();
+        batch
+            .put::<JmtValues>(&(b"key".to_vec(), version), &Some(vec![version as u8]))
+            .unwrap();
+        db.write_schemas(batch).unwrap();
+    }
+
+    #[test]
+    fn test_rollback_state() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = DB::open(
+            tmpdir.path(),
+            "state-db",
+            STATE_TABLES.iter().copied(),
+            &gen_rocksdb_options(&Default::default(), false),
+        )
+        .unwrap();
+        assert_eq!(0, latest_state_version(&db).unwrap());
+
+        for version in 0..5 {
+            put_version(&db, version);
+        }
+        assert_eq!(4, latest_state_version(&db).unwrap());
+
+        rollback_state(&db, 2).unwrap();
+        assert_eq!(2, latest_state_version(&db).unwrap());
+        assert!(db
+            .get::<JmtValues>(&(b"key".to_vec(), 2))
+            .unwrap()
+            .is_some());
+        assert!(db
+            .get::<JmtValues>(&(b"key".to_vec(), 3))
+            .unwrap()
+            .is_none());
+    }
+}
@@ -12,18 +12,33 @@
 //! Tx Tables:
 //! - `TxNumber -> (TxHash,Tx)`
 //! - `TxHash -> TxNumber`
+//! - `(SenderAddress, TxNumber) -> ()`
+//! - `TxNumber -> SenderAddress`
 //!
 //! Event Tables:
 //! - `(EventKey, TxNumber) -> EventNumber`
 //! - `EventNumber -> (EventKey, EventValue)`
+//! - `(SlotNumber, BatchNumber, TxNumber, EventNumber) -> ()`
 //!
 //! JMT Tables:
 //! - `KeyHash -> Key`
 //! - `(Key, Version) -> JmtValue`
+//! - `Key -> ()`, the keys ordered by their bytes
 //! - `NodeKey -> Node`
+//! - `(Version, NodeKey) -> ()`, the nodes which became stale at a given version
+//! - `() -> Version`, the oldest version which wasn't pruned
 //!
 //! Module Accessory State Table:
 //! - `(ModuleAddress, Key) -> Value`
+//!
+//! Schema Version Table:
+//! - `TableName -> SchemaVersion`
+//!
+//! Mempool Table:
+//! - `ArrivalNumber -> StoredPooledTx`
+//!
+//! Submission Queue Table:
+//! - `BlobNumber -> StoredQueuedBlob`
 
 use borsh::{maybestd, BorshDeserialize, BorshSerialize};
 use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
@@ -34,8 +49,9 @@ use sov_schema_db::schema::{KeyDecoder, KeyEncoder, ValueCodec};
 use sov_schema_db::{CodecError, SeekKeyEncoder};
 
 use super::types::{
-    AccessoryKey, AccessoryStateValue, BatchNumber, DbHash, EventNumber, JmtValue, SlotNumber,
-    StateKey, StoredBatch, StoredSlot, StoredTransaction, TxNumber,
+    AccessoryKey, AccessoryStateValue, BatchNumber, CommittedSlotMarker, DbHash, EventNumber,
+    JmtValue, SlotNumber, StateKey, StoredBatch, StoredPooledTx, StoredProof, StoredQueuedBlob,
+    StoredSlot, StoredTransaction, TxNumber,
 };
 
 /// A list of all tables used by the StateDB. These tables store rollup state - meaning
@@ -43,7 +59,10 @@ use super::types::{
 pub const STATE_TABLES: &[&str] = &[
     KeyHashToKey::table_name(),
     JmtValues::table_name(),
+    StateKeys::table_name(),
     JmtNodes::table_name(),
+    StaleNodes::table_name(),
+    OldestRetainedVersion::table_name(),
 ];
 
 /// A list of all tables used by the LedgerDB. These tables store rollup "history" - meaning
@@ -55,8 +74,14 @@ pub const LEDGER_TABLES: &[&str] = &[
     BatchByNumber::table_name(),
     TxByHash::table_name(),
     TxByNumber::table_name(),
+    TxByAddress::table_name(),
+    SenderByTx::table_name(),
     EventByKey::table_name(),
     EventByNumber::table_name(),
+    EventByLocation::table_name(),
+    LastCommittedSlot::table_name(),
+    ProofBySlotNumber::table_name(),
+    SchemaVersions::table_name(),
 ];
 
 /// A list of all tables used by the NativeDB. These tables store
@@ -64,6 +89,14 @@ pub const LEDGER_TABLES: &[&str] = &[
 /// used for JSON-RPC and other tooling.
 pub const NATIVE_TABLES: &[&str] = &[ModuleAccessoryState::table_name()];
 
+/// A list of all tables used by the MempoolDB. These tables store the transactions
+/// of the sequencer mempool, so they survive restarts.
+pub const MEMPOOL_TABLES: &[&str] = &[PooledTxs::table_name()];
+
+/// A list of all tables used by the SubmissionQueueDB. These tables store the blobs
+/// of the sequencer which weren't submitted to DA yet, so they survive restarts.
+pub const SUBMISSION_QUEUE_TABLES: &[&str] = &[QueuedBlobs::table_name()];
+
 /// Macro to define a table that implements [`sov_schema_db::Schema`].
 /// KeyCodec<Schema> and ValueCodec<Schema> must be implemented separately.
 ///
@@ -238,6 +271,18 @@ define_table_with_default_codec!(
     (TxByHash) DbHash => TxNumber
 );
 
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for transaction data by the address of their sender, which is only
+    /// written if the ledger indexes transactions by sender. Keys are ordered, so the transactions
+    /// of a sender can be iterated over.
+    (TxByAddress) (Vec<u8>, TxNumber) => ()
+);
+
+define_table_with_default_codec!(
+    /// The sender of the transactions in [`TxByAddress`], used to remove them from the index.
+    (SenderByTx) TxNumber => Vec<u8>
+);
+
 define_table_with_seek_key_codec!(
     /// The primary store for event data
     (EventByNumber) EventNumber => Event
@@ -248,6 +293,45 @@ define_table_with_default_codec!(
     (EventByKey) (EventKey, TxNumber, EventNumber) => ()
 );
 
+define_table_with_seek_key_codec!(
+    /// A "secondary index" for event data by the slot, batch and transaction which emitted it.
+    /// Keys are ordered, so the events of a range of slots can be iterated over.
+    (EventByLocation) (SlotNumber, BatchNumber, TxNumber, EventNumber) => ()
+);
+
+define_table_with_default_codec!(
+    /// A single entry table, holding the marker of the last slot which was fully committed.
+    (LastCommittedSlot) () => CommittedSlotMarker
+);
+
+define_table_with_seek_key_codec!(
+    /// The proofs of the committed slots, by the last slot they prove.
+    /// Keys are ordered, so the proof covering a slot is the first one keyed at or after it.
+    (ProofBySlotNumber) SlotNumber => StoredProof
+);
+
+define_table_with_default_codec!(
+    /// The version of the encoding of the rows of each ledger table, which is used to migrate
+    /// databases written by older releases. See [`crate::ledger_db::migrations`].
+    (SchemaVersions) String => u32
+);
+
+define_table_with_seek_key_codec!(
+    /// The transactions of the sequencer mempool, by the order they arrived in.
+    (PooledTxs) u64 => StoredPooledTx
+);
+
+define_table_with_seek_key_codec!(
+    /// The blobs of the sequencer waiting to be submitted to DA, in the order they're submitted in.
+    (QueuedBlobs) u64 => StoredQueuedBlob
+);
+
+define_table_with_default_codec!(
+    /// A single entry table, holding the oldest state version which wasn't pruned.
+    /// It's absent if the state has never been pruned.
+    (OldestRetainedVersion) () => Version
+);
+
 define_table_without_codec!(
     /// The source of truth for JMT nodes
     (JmtNodes) NodeKey => Node
@@ -264,6 +348,12 @@ impl KeyEncoder<JmtNodes> for NodeKey {
         Ok(output)
     }
 }
+impl SeekKeyEncoder<JmtNodes> for NodeKey {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        <NodeKey as KeyEncoder<JmtNodes>>::encode_key(self)
+    }
+}
+
 impl KeyDecoder<JmtNodes> for NodeKey {
     fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
         if data.len() < 8 {
@@ -290,6 +380,44 @@ impl ValueCodec<JmtNodes> for Node {
     }
 }
 
+define_table_without_codec!(
+    /// An index of the JMT nodes which are not part of the tree anymore, starting from a given version.
+    /// It is used to garbage-collect the nodes which aren't needed by any retained version.
+    (StaleNodes) (Version, NodeKey) => ()
+);
+
+impl KeyEncoder<StaleNodes> for (Version, NodeKey) {
+    fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        // The stale since version goes first and in big-endian order, so that the nodes are sorted by it
+        let mut output = self.0.to_be_bytes().to_vec();
+        output.extend(<NodeKey as KeyEncoder<JmtNodes>>::encode_key(&self.1)?);
+        Ok(output)
+    }
+}
+
+impl SeekKeyEncoder<StaleNodes> for (Version, NodeKey) {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        <(Version, NodeKey) as KeyEncoder<StaleNodes>>::encode_key(self)
+    }
+}
+
+impl KeyDecoder<StaleNodes> for (Version, NodeKey) {
+    fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        if data.len() < 8 {
+            return Err(CodecError::InvalidKeyLength {
+                expected: 16,
+                got: data.len(),
+            });
+        }
+        let mut version = [0u8; 8];
+        version.copy_from_slice(&data[..8]);
+        let node_key = <NodeKey as KeyDecoder<JmtNodes>>::decode_key(&data[8..])?;
+        Ok((u64::from_be_bytes(version), node_key))
+    }
+}
+
+impl_borsh_value_codec!(StaleNodes, ());
+
 define_table_without_codec!(
     /// The source of truth for JMT values by version
     (JmtValues) (StateKey, Version) => JmtValue
@@ -343,6 +471,33 @@ define_table_with_default_codec!(
     (KeyHashToKey) [u8;32] => StateKey
 );
 
+define_table_without_codec!(
+    /// An index of all the keys which have ever been written to the state. Unlike [`JmtValues`],
+    /// the keys are stored as raw bytes, so that all the keys starting with a given prefix are
+    /// adjacent in the table.
+    (StateKeys) StateKey => ()
+);
+
+impl KeyEncoder<StateKeys> for StateKey {
+    fn encode_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        Ok(self.clone())
+    }
+}
+
+impl SeekKeyEncoder<StateKeys> for StateKey {
+    fn encode_seek_key(&self) -> sov_schema_db::schema::Result<Vec<u8>> {
+        <StateKey as KeyEncoder<StateKeys>>::encode_key(self)
+    }
+}
+
+impl KeyDecoder<StateKeys> for StateKey {
+    fn decode_key(data: &[u8]) -> sov_schema_db::schema::Result<Self> {
+        Ok(data.to_vec())
+    }
+}
+
+impl_borsh_value_codec!(StateKeys, ());
+
 define_table_without_codec!(
     /// Non-JMT state stored by a module for JSON-RPC use.
     (ModuleAccessoryState) (AccessoryKey, Version) => AccessoryStateValue
@@ -5,6 +5,7 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sov_rollup_interface::rpc::{BatchResponse, TxIdentifier, TxResponse};
 use sov_rollup_interface::stf::{Event, EventKey, TransactionReceipt};
+use sov_rollup_interface::zk::Proof;
 
 /// A cheaply cloneable bytes abstraction for use within the trust boundary of the node
 /// (i.e. when interfacing with the database). Serializes and deserializes more efficiently,
@@ -67,6 +68,50 @@ pub struct StoredSlot {
     pub batches: std::ops::Range<BatchNumber>,
 }
 
+/// The last slot committed to the ledger, with the version of the finalized state it was committed with.
+/// It's written atomically with the slot, so the state written by a finalization whose slot never
+/// made it to the ledger, for example because of a crash, can be detected and rolled back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct CommittedSlotMarker {
+    /// The number of the slot.
+    pub slot_number: SlotNumber,
+    /// The version of the finalized state once the slot was committed.
+    pub state_version: u64,
+}
+
+/// A zk proof stored in the ledger. It's keyed by the last slot it proves,
+/// so the proofs of single slots and of slot ranges are stored the same way.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct StoredProof {
+    /// The first slot proven.
+    pub first_slot: SlotNumber,
+    /// The proof, as generated by the prover.
+    pub proof: Proof,
+}
+
+/// A transaction of the sequencer mempool, persisted until it's included in a batch.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct StoredPooledTx {
+    /// The raw transaction, as submitted to the sequencer.
+    pub raw: Vec<u8>,
+    /// When the transaction was accepted, in seconds since the Unix epoch.
+    pub received_at: u64,
+}
+
+/// A blob of the sequencer waiting to be submitted to DA, persisted until its submission succeeds.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct StoredQueuedBlob {
+    /// The blob, as sent to DA: a serialized batch, or one of its chunks.
+    pub blob: Vec<u8>,
+    /// The number of transactions of the batch, if the blob is its last one. Otherwise 0.
+    pub num_txs: u64,
+    /// Whether the blob is the last one of its batch.
+    pub ends_batch: bool,
+    /// The height of the DA head before the blob was sent for the first time, if it was.
+    /// The blob may have landed on DA after this height.
+    pub first_attempt_height: Option<u64>,
+}
+
 /// The on-disk format for a batch. Stores the hash and identifies the range of transactions
 /// included in the batch.
 #[derive(Debug, PartialEq, BorshDeserialize, BorshSerialize)]
@@ -1,13 +1,15 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use jmt::storage::{HasPreimage, TreeReader, TreeWriter};
+use jmt::storage::{HasPreimage, StaleNodeIndex, TreeReader, TreeWriter};
 use jmt::{KeyHash, Version};
 use sov_schema_db::snapshot::{DbSnapshot, QueryManager, ReadOnlyDbSnapshot};
 use sov_schema_db::SchemaBatch;
 
 use crate::rocks_db_config::gen_rocksdb_options;
-use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, STATE_TABLES};
+use crate::schema::tables::{
+    JmtNodes, JmtValues, KeyHashToKey, OldestRetainedVersion, StaleNodes, StateKeys, STATE_TABLES,
+};
 use crate::schema::types::StateKey;
 
 /// A typed wrapper around the db for storing rollup state. Internally,
@@ -42,12 +44,33 @@ impl<Q> StateDB<Q> {
     /// Initialize [`sov_schema_db::DB`] that should be used by snapshots.
     pub fn setup_schema_db(path: impl AsRef<Path>) -> anyhow::Result<sov_schema_db::DB> {
         let state_db_path = path.as_ref().join(Self::DB_PATH_SUFFIX);
-        sov_schema_db::DB::open(
+        let db = sov_schema_db::DB::open(
             state_db_path,
             Self::DB_NAME,
             STATE_TABLES.iter().copied(),
             &gen_rocksdb_options(&Default::default(), false),
-        )
+        )?;
+        Self::index_state_keys(&db)?;
+        Ok(db)
+    }
+
+    /// Fills the [`StateKeys`] index from the preimages of the JMT keys, for the databases
+    /// which were created before the index existed.
+    fn index_state_keys(db: &sov_schema_db::DB) -> anyhow::Result<()> {
+        let mut indexed_keys = db.iter::<StateKeys>()?;
+        indexed_keys.seek_to_first();
+        if indexed_keys.next().is_some() {
+            return Ok(());
+        }
+
+        let mut preimages = db.iter::<KeyHashToKey>()?;
+        preimages.seek_to_first();
+        let mut batch = SchemaBatch::new();
+        for preimage in preimages {
+            let (_, key) = preimage?.into_tuple();
+            batch.put::<StateKeys>(&key, &())?;
+        }
+        db.write_schemas(batch)
     }
 
     /// Convert it to [`ReadOnlyDbSnapshot`] which cannot be edited anymore
@@ -78,6 +101,21 @@ impl<Q: QueryManager> StateDB<Q> {
         let mut batch = SchemaBatch::new();
         for (key_hash, key) in items.into_iter() {
             batch.put::<KeyHashToKey>(&key_hash.0, key)?;
+            batch.put::<StateKeys>(key, &())?;
+        }
+        self.db.write_many(batch)?;
+        Ok(())
+    }
+
+    /// Records the JMT nodes which became stale, so they can be garbage-collected
+    /// once the versions using them are pruned. See [`crate::pruning`].
+    pub fn put_stale_node_indices<'a>(
+        &self,
+        items: impl IntoIterator<Item = &'a StaleNodeIndex>,
+    ) -> Result<(), anyhow::Error> {
+        let mut batch = SchemaBatch::new();
+        for index in items.into_iter() {
+            batch.put::<StaleNodes>(&(index.stale_since_version, index.node_key.clone()), &())?;
         }
         self.db.write_many(batch)?;
         Ok(())
@@ -103,6 +141,65 @@ impl<Q: QueryManager> StateDB<Q> {
         }
     }
 
+    /// Get the keys starting with `prefix` together with their values at the given version,
+    /// in ascending order of their bytes. Keys without a value at this version are skipped.
+    ///
+    /// Only the keys in `start..end` are returned, up to `limit` of them.
+    pub fn get_values_by_prefix(
+        &self,
+        version: Version,
+        prefix: &[u8],
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<(StateKey, jmt::OwnedValue)>> {
+        let lower_bound = match start {
+            Some(start) if start > prefix => start,
+            _ => prefix,
+        };
+        // The smallest key that is larger than all the keys starting with the prefix
+        let prefix_end = prefix_successor(prefix);
+        let upper_bound = match (end, prefix_end.as_deref()) {
+            (Some(end), Some(prefix_end)) => Some(end.min(prefix_end)),
+            (end, prefix_end) => end.or(prefix_end),
+        };
+        let seek_key = match upper_bound {
+            Some(upper_bound) => upper_bound.to_vec(),
+            None => match self.db.get_largest::<StateKeys>()? {
+                Some((largest_key, _)) => largest_key,
+                None => return Ok(Vec::new()),
+            },
+        };
+
+        // The snapshots can only be iterated backwards, so the keys are collected before resolving their values
+        let keys = self
+            .db
+            .collect_prev_while::<StateKeys>(&seek_key, |key| key.as_slice() >= lower_bound)?;
+
+        let mut values = Vec::new();
+        for (key, ()) in keys.into_iter().rev() {
+            if values.len() == limit {
+                break;
+            }
+            if Some(key.as_slice()) == upper_bound {
+                continue;
+            }
+            if let Some(value) = self.get_value_option_by_key(version, &key)? {
+                values.push((key, value));
+            }
+        }
+        Ok(values)
+    }
+
+    /// Returns the oldest [`Version`] of the state which can still be read.
+    /// Older versions have been removed by [`crate::pruning`].
+    pub fn get_oldest_retained_version(&self) -> anyhow::Result<Version> {
+        Ok(self
+            .db
+            .read::<OldestRetainedVersion>(&())?
+            .unwrap_or_default())
+    }
+
     /// Increment the `next_version` counter by 1.
     pub fn inc_next_version(&self) {
         let mut version = self.next_version.lock().unwrap();
@@ -121,6 +218,17 @@ impl<Q: QueryManager> StateDB<Q> {
         *version = u64::MAX - 1;
     }
 
+    /// Returns a [`StateDB`] on the same snapshot, whose queries without a version are pinned
+    /// to the latest version written to it, so they don't see the versions written afterwards.
+    /// It's meant to be read only: its `next_version` counter isn't shared with this one.
+    pub fn pin_latest_version(&self) -> anyhow::Result<Self> {
+        let next_version = Self::next_version_from(&self.db)?;
+        Ok(Self {
+            db: self.db.clone(),
+            next_version: Arc::new(Mutex::new(next_version)),
+        })
+    }
+
     fn next_version_from(db_snapshot: &DbSnapshot<Q>) -> anyhow::Result<Version> {
         let last_key_value = db_snapshot.get_largest::<JmtNodes>()?;
         let largest_version = last_key_value.map(|(k, _)| k.version());
@@ -132,6 +240,19 @@ impl<Q: QueryManager> StateDB<Q> {
     }
 }
 
+/// Returns the smallest byte string which is larger than all the byte strings starting with `prefix`,
+/// or `None` if there is no such string.
+fn prefix_successor(prefix: &[u8]) -> Option<Vec<u8>> {
+    let mut successor = prefix.to_vec();
+    while let Some(last) = successor.pop() {
+        if last < u8::MAX {
+            successor.push(last + 1);
+            return Some(successor);
+        }
+    }
+    None
+}
+
 impl<Q: QueryManager> TreeReader for StateDB<Q> {
     fn get_node_option(
         &self,
@@ -216,4 +337,64 @@ mod state_db_tests {
         let found = db.get_value_option_by_key(0, &key).unwrap().unwrap();
         assert_eq!(found, value);
     }
+
+    #[test]
+    fn test_values_by_prefix() {
+        let manager = ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+        let db_snapshot = DbSnapshot::<NoopQueryManager>::new(0, manager);
+        let db = StateDB::with_db_snapshot(db_snapshot).unwrap();
+        let keys: Vec<Vec<u8>> = vec![
+            b"a".to_vec(),
+            b"ab".to_vec(),
+            b"ac".to_vec(),
+            b"ad".to_vec(),
+            vec![b'a', u8::MAX],
+            b"b".to_vec(),
+        ];
+        let key_hashes: Vec<_> = (0..keys.len() as u8).map(|i| KeyHash([i; 32])).collect();
+        db.put_preimages(key_hashes.iter().copied().zip(keys.iter()))
+            .unwrap();
+
+        let mut batch = NodeBatch::default();
+        batch.extend(
+            vec![],
+            key_hashes
+                .iter()
+                .map(|key_hash| ((0, *key_hash), Some(key_hash.0.to_vec()))),
+        );
+        // "ac" is deleted at version 1
+        batch.extend(vec![], vec![((1, key_hashes[2]), None)]);
+        db.write_node_batch(&batch).unwrap();
+
+        let found_keys = |version, start: Option<&[u8]>, end: Option<&[u8]>, limit| {
+            db.get_values_by_prefix(version, b"a", start, end, limit)
+                .unwrap()
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(keys[..5].to_vec(), found_keys(0, None, None, 10));
+        assert_eq!(
+            vec![
+                keys[0].clone(),
+                keys[1].clone(),
+                keys[3].clone(),
+                keys[4].clone()
+            ],
+            found_keys(1, None, None, 10)
+        );
+        assert_eq!(keys[..2].to_vec(), found_keys(0, None, None, 2));
+        assert_eq!(
+            keys[1..3].to_vec(),
+            found_keys(0, Some(b"ab"), Some(b"ad"), 10)
+        );
+
+        let (key, value) = db
+            .get_values_by_prefix(0, &[], Some(b"b"), None, 10)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(keys[5], key);
+        assert_eq!(key_hashes[5].0.to_vec(), value);
+    }
 }
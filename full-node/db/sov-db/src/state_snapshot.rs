@@ -0,0 +1,374 @@
+use std::collections::HashSet;
+use std::io::{Read, Write};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use jmt::storage::{Node, NodeKey};
+use jmt::Version;
+use sov_schema_db::schema::{KeyDecoder, KeyEncoder};
+use sov_schema_db::{SchemaBatch, DB};
+
+use crate::ledger_db::{LedgerDB, LedgerSnapshot};
+use crate::schema::tables::{JmtNodes, JmtValues, KeyHashToKey, StaleNodes, StateKeys};
+use crate::schema::types::{JmtValue, SlotNumber, StateKey};
+
+/// The bytes every snapshot file starts with.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"SOVSNAP\0";
+/// The version of the snapshot format, bumped on every incompatible change.
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+/// The number of entries written to the database at once when importing a snapshot.
+const IMPORT_BATCH_SIZE: usize = 10_000;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct SnapshotHeader {
+    format_version: u32,
+    ledger: LedgerSnapshot,
+}
+
+/// A single entry of the state contained in a snapshot. The entries follow the header
+/// and are terminated by [`SnapshotEntry::End`].
+#[derive(BorshSerialize, BorshDeserialize)]
+enum SnapshotEntry {
+    /// A JMT node, with its key encoded as in [`JmtNodes`].
+    Node {
+        key: Vec<u8>,
+        node: Node,
+    },
+    /// The latest value of a key at the version of the snapshot.
+    Value {
+        key: StateKey,
+        version: Version,
+        value: JmtValue,
+    },
+    /// The preimage of a key hash.
+    Preimage {
+        key_hash: [u8; 32],
+        key: StateKey,
+    },
+    End,
+}
+
+/// A summary of an exported or imported snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotInfo {
+    /// The slot at which the snapshot was taken. It is also the version of the state.
+    pub slot_number: u64,
+    /// The number of JMT nodes in the snapshot.
+    pub nodes: u64,
+    /// The number of state values in the snapshot.
+    pub values: u64,
+}
+
+/// Writes a snapshot of the rollup at `slot_number` to `writer`: the JMT of the finalized state
+/// at that version, with the values and key preimages it references, and the ledger data
+/// of the slot.
+///
+/// Only the nodes reachable from the root at `slot_number` are exported, so the snapshot
+/// is as large as the state itself, regardless of the history kept by the node.
+pub fn export_snapshot(
+    state_db: &DB,
+    ledger_db: &LedgerDB,
+    slot_number: u64,
+    writer: impl Write,
+) -> anyhow::Result<SnapshotInfo> {
+    let version: Version = slot_number;
+    anyhow::ensure!(
+        state_db
+            .get::<JmtNodes>(&NodeKey::new_empty_path(version))?
+            .is_some(),
+        "The state at version {} is not available. Has it been pruned?",
+        version
+    );
+
+    // Nodes which became stale at or before the version are not part of its tree
+    let mut stale_nodes = HashSet::new();
+    let mut iter = state_db.iter::<StaleNodes>()?;
+    iter.seek_to_first();
+    for item in iter {
+        let (stale_since_version, node_key) = item?.key;
+        if stale_since_version > version {
+            break;
+        }
+        stale_nodes.insert(<NodeKey as KeyEncoder<JmtNodes>>::encode_key(&node_key)?);
+    }
+
+    let mut writer = std::io::BufWriter::new(writer);
+    writer.write_all(&SNAPSHOT_MAGIC)?;
+    SnapshotHeader {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        ledger: ledger_db.export_slot(SlotNumber(slot_number))?,
+    }
+    .serialize(&mut writer)?;
+
+    let mut info = SnapshotInfo {
+        slot_number,
+        nodes: 0,
+        values: 0,
+    };
+    let mut iter = state_db.iter::<JmtNodes>()?;
+    iter.seek_to_first();
+    for item in iter {
+        let (node_key, node) = item?.into_tuple();
+        // Nodes are sorted by version, so none of the remaining ones exist at the version
+        if node_key.version() > version {
+            break;
+        }
+        let key = <NodeKey as KeyEncoder<JmtNodes>>::encode_key(&node_key)?;
+        if stale_nodes.contains(&key) {
+            continue;
+        }
+
+        if let Node::Leaf(leaf) = &node {
+            let key_hash = leaf.key_hash().0;
+            let preimage = state_db.get::<KeyHashToKey>(&key_hash)?.ok_or_else(|| {
+                anyhow::anyhow!("The preimage of key hash {:?} is missing", key_hash)
+            })?;
+            let mut values = state_db.iter::<JmtValues>()?;
+            values.seek_for_prev(&(&preimage, version))?;
+            let Some(((found_key, found_version), value)) =
+                values.next().transpose()?.map(|item| item.into_tuple())
+            else {
+                anyhow::bail!("The value of key hash {:?} is missing", key_hash);
+            };
+            anyhow::ensure!(
+                found_key == preimage,
+                "The value of key hash {:?} is missing",
+                key_hash
+            );
+
+            SnapshotEntry::Preimage {
+                key_hash,
+                key: preimage,
+            }
+            .serialize(&mut writer)?;
+            SnapshotEntry::Value {
+                key: found_key,
+                version: found_version,
+                value,
+            }
+            .serialize(&mut writer)?;
+            info.values += 1;
+        }
+
+        SnapshotEntry::Node { key, node }.serialize(&mut writer)?;
+        info.nodes += 1;
+    }
+
+    SnapshotEntry::End.serialize(&mut writer)?;
+    writer.flush()?;
+    Ok(info)
+}
+
+/// Reads a snapshot written by [`export_snapshot`] into the databases of a fresh node.
+///
+/// Once imported, the node continues executing the rollup from the slot following the snapshot.
+pub fn import_snapshot(
+    state_db: &DB,
+    ledger_db: &LedgerDB,
+    reader: impl Read,
+) -> anyhow::Result<SnapshotInfo> {
+    let mut iter = state_db.iter::<JmtNodes>()?;
+    iter.seek_to_first();
+    anyhow::ensure!(
+        iter.next().is_none(),
+        "A snapshot can only be imported into an empty state database"
+    );
+
+    let mut reader = std::io::BufReader::new(reader);
+    let mut magic = [0u8; SNAPSHOT_MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    anyhow::ensure!(magic == SNAPSHOT_MAGIC, "The file is not a rollup snapshot");
+    let header = SnapshotHeader::deserialize_reader(&mut reader)?;
+    anyhow::ensure!(
+        header.format_version == SNAPSHOT_FORMAT_VERSION,
+        "Unsupported snapshot format version {}, expected {}",
+        header.format_version,
+        SNAPSHOT_FORMAT_VERSION
+    );
+
+    let mut info = SnapshotInfo {
+        slot_number: header.ledger.slot_number().0,
+        nodes: 0,
+        values: 0,
+    };
+    let mut batch = SchemaBatch::new();
+    let mut batch_size = 0;
+    loop {
+        match SnapshotEntry::deserialize_reader(&mut reader)? {
+            SnapshotEntry::Node { key, node } => {
+                let node_key = <NodeKey as KeyDecoder<JmtNodes>>::decode_key(&key)?;
+                batch.put::<JmtNodes>(&node_key, &node)?;
+                info.nodes += 1;
+            }
+            SnapshotEntry::Value {
+                key,
+                version,
+                value,
+            } => {
+                batch.put::<JmtValues>(&(key, version), &value)?;
+                info.values += 1;
+            }
+            SnapshotEntry::Preimage { key_hash, key } => {
+                batch.put::<KeyHashToKey>(&key_hash, &key)?;
+                batch.put::<StateKeys>(&key, &())?;
+            }
+            SnapshotEntry::End => break,
+        }
+
+        batch_size += 1;
+        if batch_size == IMPORT_BATCH_SIZE {
+            state_db.write_schemas(std::mem::replace(&mut batch, SchemaBatch::new()))?;
+            batch_size = 0;
+        }
+    }
+    state_db.write_schemas(batch)?;
+    anyhow::ensure!(
+        state_db
+            .get::<JmtNodes>(&NodeKey::new_empty_path(info.slot_number))?
+            .is_some(),
+        "The snapshot doesn't contain the root of the state"
+    );
+
+    // The ledger is written last, so a node that was interrupted while importing
+    // doesn't start from an incomplete state
+    ledger_db.import_slot(header.ledger)?;
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, RwLock};
+
+    use jmt::storage::TreeWriter;
+    use jmt::{JellyfishMerkleTree, KeyHash};
+    use sov_mock_da::{MockBlob, MockBlock, MockBlockHeader};
+    use sov_rollup_interface::stf::{BatchReceipt, Event, TransactionReceipt};
+    use sov_schema_db::snapshot::{DbSnapshot, NoopQueryManager, ReadOnlyLock};
+
+    use super::*;
+    use crate::ledger_db::SlotCommit;
+    use crate::state_db::StateDB;
+
+    fn write_state(state_db: &DB, versions: &[Vec<(&str, &str)>]) {
+        let manager = ReadOnlyLock::new(Arc::new(RwLock::new(Default::default())));
+        let db =
+            StateDB::with_db_snapshot(DbSnapshot::<NoopQueryManager>::new(0, manager)).unwrap();
+        for (version, writes) in versions.iter().enumerate() {
+            let key_hashes: Vec<_> = writes
+                .iter()
+                .map(|(key, _)| KeyHash::with::<sha2::Sha256>(key.as_bytes()))
+                .collect();
+            let preimages: Vec<Vec<u8>> = writes
+                .iter()
+                .map(|(key, _)| key.as_bytes().to_vec())
+                .collect();
+            db.put_preimages(key_hashes.iter().copied().zip(preimages.iter()))
+                .unwrap();
+            let value_set = key_hashes.into_iter().zip(
+                writes
+                    .iter()
+                    .map(|(_, value)| Some(value.as_bytes().to_vec())),
+            );
+            let (_, tree_update) = JellyfishMerkleTree::<_, sha2::Sha256>::new(&db)
+                .put_value_set(value_set, version as Version + 1)
+                .unwrap();
+            db.write_node_batch(&tree_update.node_batch).unwrap();
+            db.put_stale_node_indices(&tree_update.stale_node_index_batch)
+                .unwrap();
+        }
+        state_db.write_schemas(db.freeze().unwrap().into()).unwrap();
+    }
+
+    fn commit_slot(ledger_db: &LedgerDB, height: u64, num_txs: usize) {
+        let block = MockBlock {
+            header: MockBlockHeader::from_height(height),
+            ..Default::default()
+        };
+        let mut commit = SlotCommit::<_, MockBlob, Vec<u8>>::new(block);
+        commit.add_batch(BatchReceipt {
+            batch_hash: [height as u8; 32],
+            tx_receipts: (0..num_txs)
+                .map(|i| TransactionReceipt {
+                    tx_hash: [(height * 16 + i as u64) as u8; 32],
+                    body_to_save: None,
+                    sender: None,
+                    events: vec![Event::new("key", "value")],
+                    receipt: vec![],
+                    gas_used: vec![],
+                })
+                .collect(),
+            inner: vec![],
+        });
+        ledger_db.commit_slot(commit).unwrap();
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let source_state = StateDB::<NoopQueryManager>::setup_schema_db(source_dir.path()).unwrap();
+        let source_ledger = LedgerDB::with_path(source_dir.path()).unwrap();
+        write_state(
+            &source_state,
+            &[
+                vec![("a", "1"), ("b", "2")],
+                vec![("a", "3"), ("c", "4")],
+                vec![("b", "5")],
+            ],
+        );
+        commit_slot(&source_ledger, 1, 2);
+        commit_slot(&source_ledger, 2, 1);
+        commit_slot(&source_ledger, 3, 0);
+
+        let mut snapshot = Vec::new();
+        let exported = export_snapshot(&source_state, &source_ledger, 2, &mut snapshot).unwrap();
+        assert_eq!(3, exported.values);
+
+        let target_dir = tempfile::tempdir().unwrap();
+        let target_state = StateDB::<NoopQueryManager>::setup_schema_db(target_dir.path()).unwrap();
+        let target_ledger = LedgerDB::with_path(target_dir.path()).unwrap();
+        let imported = import_snapshot(&target_state, &target_ledger, snapshot.as_slice()).unwrap();
+        assert_eq!(exported, imported);
+
+        let root_key = NodeKey::new_empty_path(2);
+        assert_eq!(
+            source_state.get::<JmtNodes>(&root_key).unwrap(),
+            target_state.get::<JmtNodes>(&root_key).unwrap()
+        );
+        // Nothing written after the snapshot was exported
+        assert!(target_state
+            .get::<JmtNodes>(&NodeKey::new_empty_path(3))
+            .unwrap()
+            .is_none());
+        let value_at = |key: &[u8]| {
+            let mut iter = target_state.iter::<JmtValues>().unwrap();
+            iter.seek_for_prev(&(key, 2)).unwrap();
+            iter.next().unwrap().unwrap().value
+        };
+        assert_eq!(Some(b"3".to_vec()), value_at(b"a"));
+        assert_eq!(Some(b"2".to_vec()), value_at(b"b"));
+        assert_eq!(Some(b"4".to_vec()), value_at(b"c"));
+
+        let head_slot = target_ledger.get_head_slot().unwrap().unwrap();
+        assert_eq!(
+            source_ledger.export_slot(SlotNumber(2)).unwrap(),
+            target_ledger.export_slot(head_slot.0).unwrap()
+        );
+        let next_items = target_ledger.get_next_items_numbers();
+        assert_eq!(3, next_items.slot_number);
+        assert_eq!(3, next_items.batch_number);
+        assert_eq!(4, next_items.tx_number);
+        assert_eq!(4, next_items.event_number);
+    }
+
+    #[test]
+    fn test_import_requires_empty_databases() {
+        let source_dir = tempfile::tempdir().unwrap();
+        let state = StateDB::<NoopQueryManager>::setup_schema_db(source_dir.path()).unwrap();
+        let ledger = LedgerDB::with_path(source_dir.path()).unwrap();
+        write_state(&state, &[vec![("a", "1")]]);
+        commit_slot(&ledger, 1, 1);
+
+        let mut snapshot = Vec::new();
+        export_snapshot(&state, &ledger, 1, &mut snapshot).unwrap();
+        assert!(import_snapshot(&state, &ledger, snapshot.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use sov_schema_db::DB;
+
+use crate::rocks_db_config::gen_rocksdb_options;
+use crate::schema::tables::{QueuedBlobs, SUBMISSION_QUEUE_TABLES};
+use crate::schema::types::StoredQueuedBlob;
+
+const SUBMISSION_QUEUE_DB_PATH_SUFFIX: &str = "submission_queue";
+
+/// A database persisting the blobs of the sequencer waiting to be submitted to DA, keyed by the
+/// order they're submitted in, so they're resubmitted when the sequencer restarts.
+#[derive(Clone, Debug)]
+pub struct SubmissionQueueDB {
+    db: Arc<DB>,
+}
+
+impl SubmissionQueueDB {
+    /// Opens the [`SubmissionQueueDB`] in the `submission_queue` directory of `path`,
+    /// creating it if it's missing.
+    pub fn with_path(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().join(SUBMISSION_QUEUE_DB_PATH_SUFFIX);
+        let db = DB::open(
+            path,
+            "submission-queue-db",
+            SUBMISSION_QUEUE_TABLES.iter().copied(),
+            &gen_rocksdb_options(&Default::default(), false),
+        )?;
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Writes the blob with the given number, replacing the previous version of it if any.
+    pub fn put(&self, number: u64, blob: &StoredQueuedBlob) -> anyhow::Result<()> {
+        self.db.put::<QueuedBlobs>(&number, blob)
+    }
+
+    /// Deletes the blob with the given number, once it's submitted.
+    pub fn delete(&self, number: u64) -> anyhow::Result<()> {
+        self.db.delete::<QueuedBlobs>(&number)
+    }
+
+    /// Returns all the persisted blobs, in the order they're submitted in.
+    pub fn load(&self) -> anyhow::Result<Vec<(u64, StoredQueuedBlob)>> {
+        let mut iter = self.db.iter::<QueuedBlobs>()?;
+        iter.seek_to_first();
+        iter.map(|item| Ok(item?.into_tuple())).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blob(data: u8) -> StoredQueuedBlob {
+        StoredQueuedBlob {
+            blob: vec![data],
+            num_txs: 1,
+            ends_batch: true,
+            first_attempt_height: None,
+        }
+    }
+
+    #[test]
+    fn test_queued_blobs_survive_reopening() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        {
+            let db = SubmissionQueueDB::with_path(tmpdir.path()).unwrap();
+            db.put(300, &blob(3)).unwrap();
+            db.put(1, &blob(1)).unwrap();
+            db.put(2, &blob(2)).unwrap();
+            db.delete(1).unwrap();
+            db.put(
+                2,
+                &StoredQueuedBlob {
+                    first_attempt_height: Some(7),
+                    ..blob(2)
+                },
+            )
+            .unwrap();
+        }
+
+        let db = SubmissionQueueDB::with_path(tmpdir.path()).unwrap();
+        assert_eq!(
+            vec![
+                (
+                    2,
+                    StoredQueuedBlob {
+                        first_attempt_height: Some(7),
+                        ..blob(2)
+                    }
+                ),
+                (300, blob(3))
+            ],
+            db.load().unwrap()
+        );
+    }
+}
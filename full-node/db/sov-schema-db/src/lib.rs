@@ -38,7 +38,7 @@ use thiserror::Error;
 use tracing::info;
 
 pub use crate::schema::Schema;
-use crate::schema::{ColumnFamilyName, KeyCodec, ValueCodec};
+use crate::schema::{ColumnFamilyName, KeyCodec, KeyDecoder, ValueCodec};
 pub use crate::schema_batch::{SchemaBatch, SchemaBatchIterator};
 
 /// This DB is a schematized RocksDB wrapper where all data passed in and out are typed according to
@@ -282,6 +282,36 @@ impl DB {
             })
     }
 
+    /// Compacts the whole key range of the column family, which reclaims the space of its deleted
+    /// and overwritten records.
+    pub fn compact_cf(&self, cf_name: &str) -> anyhow::Result<()> {
+        self.inner
+            .compact_range_cf::<&[u8], &[u8]>(self.get_cf_handle(cf_name)?, None, None);
+        Ok(())
+    }
+
+    /// Decodes every record of the schema, to find the ones which are corrupted.
+    pub fn verify_schema<S: Schema>(&self) -> anyhow::Result<SchemaIntegrity> {
+        let cf_handle = self.get_cf_handle(S::COLUMN_FAMILY_NAME)?;
+        let mut db_iter = self.inner.raw_iterator_cf(cf_handle);
+        db_iter.seek_to_first();
+
+        let mut integrity = SchemaIntegrity::default();
+        while db_iter.valid() {
+            let (raw_key, raw_value) = db_iter.item().expect("db_iter.item() failed.");
+            let is_valid = <S::Key as KeyDecoder<S>>::decode_key(raw_key).is_ok()
+                && <S::Value as ValueCodec<S>>::decode_value(raw_value).is_ok();
+            if !is_valid {
+                integrity.corrupted_keys.push(raw_key.to_vec());
+            }
+            integrity.records += 1;
+            db_iter.next();
+        }
+        db_iter.status()?;
+
+        Ok(integrity)
+    }
+
     /// Creates new physical DB checkpoint in directory specified by `path`.
     pub fn create_checkpoint<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
         rocksdb::checkpoint::Checkpoint::new(&self.inner)?.create_checkpoint(path)?;
@@ -294,6 +324,15 @@ pub type SchemaKey = Vec<u8>;
 /// Readability alias for a value in the DB.
 pub type SchemaValue = Vec<u8>;
 
+/// The records of a schema checked by [`DB::verify_schema`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SchemaIntegrity {
+    /// The number of records of the schema.
+    pub records: u64,
+    /// The raw keys of the records whose key or value can't be decoded.
+    pub corrupted_keys: Vec<SchemaKey>,
+}
+
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 /// Represents operation written to the database
@@ -3,7 +3,7 @@ use std::iter::Rev;
 
 use crate::metrics::SCHEMADB_BATCH_PUT_LATENCY_SECONDS;
 use crate::schema::{ColumnFamilyName, KeyCodec, ValueCodec};
-use crate::{Operation, Schema, SchemaKey};
+use crate::{Operation, Schema, SchemaKey, SchemaValue};
 
 // [`SchemaBatch`] holds a collection of updates that can be applied to a DB
 /// ([`Schema`]) atomically. The updates will be applied in the order in which
@@ -37,6 +37,14 @@ impl SchemaBatch {
         Ok(())
     }
 
+    /// Adds an insert/update operation with an already encoded key and value to the batch.
+    ///
+    /// This bypasses the codecs of the schema, so it's only meant for rewriting raw rows,
+    /// e.g. when migrating them from an older encoding.
+    pub fn put_raw<S: Schema>(&mut self, key: SchemaKey, value: SchemaValue) {
+        self.insert_operation::<S>(key, Operation::Put { value });
+    }
+
     /// Adds a delete operation to the batch.
     pub fn delete<S: Schema>(&mut self, key: &impl KeyCodec<S>) -> anyhow::Result<()> {
         let key = key.encode_key()?;
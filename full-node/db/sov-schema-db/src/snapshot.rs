@@ -200,6 +200,43 @@ impl<Q: QueryManager> DbSnapshot<Q> {
         }
         Ok(None)
     }
+
+    /// Collects values in [`Schema`] in descending key order, starting from the largest key that is smaller or equal than `seek_key`.
+    /// Stops at the first key for which `predicate` returns false.
+    pub fn collect_prev_while<S: Schema>(
+        &self,
+        seek_key: &impl SeekKeyEncoder<S>,
+        mut predicate: impl FnMut(&S::Key) -> bool,
+    ) -> anyhow::Result<Vec<(S::Key, S::Value)>> {
+        let seek_key = seek_key.encode_seek_key()?;
+        let local_cache = self
+            .cache
+            .lock()
+            .expect("Local cache lock must not be poisoned");
+        let local_cache_iter = local_cache.iter_range::<S>(seek_key.clone());
+
+        let parent = self
+            .parents_manager
+            .read()
+            .expect("Parent snapshots lock must not be poisoned");
+        let parent_iter = parent.iter_range::<S>(self.id, seek_key)?;
+
+        let combined_iter: SnapshotIter<'_, S, _, _> = SnapshotIter {
+            local_cache_iter: local_cache_iter.peekable(),
+            parent_iter: parent_iter.peekable(),
+        };
+
+        let mut values = Vec::new();
+        for (key, value) in combined_iter {
+            let key = S::Key::decode_key(&key)?;
+            if !predicate(&key) {
+                break;
+            }
+            let value = S::Value::decode_value(&value)?;
+            values.push((key, value));
+        }
+        Ok(values)
+    }
 }
 
 struct SnapshotIter<'a, S, LocalIter, ParentIter>
@@ -4,7 +4,7 @@
 use std::path::Path;
 
 use rocksdb::DEFAULT_COLUMN_FAMILY_NAME;
-use sov_schema_db::schema::{ColumnFamilyName, Result};
+use sov_schema_db::schema::{ColumnFamilyName, Result, ValueCodec};
 use sov_schema_db::test::TestField;
 use sov_schema_db::{define_schema, Schema, SchemaBatch, DB};
 use tempfile::TempDir;
@@ -15,6 +15,8 @@ use tempfile::TempDir;
 // everywhere.
 define_schema!(TestSchema1, TestField, TestField, "TestCF1");
 define_schema!(TestSchema2, TestField, TestField, "TestCF2");
+// Writes to the column family of `TestSchema1` records which it can't decode.
+define_schema!(CorruptedTestSchema1, TestField, ShortValue, "TestCF1");
 
 fn get_column_families() -> Vec<ColumnFamilyName> {
     vec![
@@ -345,3 +347,50 @@ fn test_checkpoint() {
         assert_eq!(db.get::<TestSchema1>(&TestField(1)).unwrap(), None);
     }
 }
+
+/// A value too short to be decoded as a [`TestField`].
+#[derive(Debug, PartialEq, Eq)]
+struct ShortValue;
+
+impl ValueCodec<CorruptedTestSchema1> for ShortValue {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(vec![0])
+    }
+
+    fn decode_value(_data: &[u8]) -> Result<Self> {
+        Ok(ShortValue)
+    }
+}
+
+#[test]
+fn test_verify_and_compact() {
+    let db = TestDB::new();
+    for i in 0..100 {
+        db.put::<TestSchema1>(&TestField(i), &TestField(i)).unwrap();
+    }
+    let integrity = db.verify_schema::<TestSchema1>().unwrap();
+    assert_eq!(100, integrity.records);
+    assert!(integrity.corrupted_keys.is_empty());
+
+    db.put::<CorruptedTestSchema1>(&TestField(7), &ShortValue)
+        .unwrap();
+    let integrity = db.verify_schema::<TestSchema1>().unwrap();
+    assert_eq!(100, integrity.records);
+    assert_eq!(vec![7u32.to_be_bytes().to_vec()], integrity.corrupted_keys);
+
+    db.flush_cf("TestCF1").unwrap();
+    let size = db
+        .get_property("TestCF1", "rocksdb.total-sst-files-size")
+        .unwrap();
+    for i in 0..100 {
+        db.delete::<TestSchema1>(&TestField(i)).unwrap();
+    }
+    db.flush_cf("TestCF1").unwrap();
+    db.compact_cf("TestCF1").unwrap();
+    assert!(
+        db.get_property("TestCF1", "rocksdb.total-sst-files-size")
+            .unwrap()
+            < size
+    );
+    assert_eq!(0, db.verify_schema::<TestSchema1>().unwrap().records);
+}
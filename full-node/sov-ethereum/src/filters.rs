@@ -0,0 +1,309 @@
+//! Polling filters for `eth_newFilter` and the related methods, for the clients which can't use
+//! `eth_subscribe`.
+//!
+//! Filters are not bound to the connection which installed them, because HTTP clients poll
+//! over a new connection every time. Instead, a filter lives until it's uninstalled, or until it
+//! isn't polled for [`FiltersConfig::timeout`]. Filter ids are unpredictable, so that clients
+//! can't poll or uninstall the filters of others.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use reth_primitives::{keccak256, BlockNumberOrTag, H256, U128};
+use reth_rpc_types::{Filter, FilterBlockOption, Log};
+use serde::Serialize;
+use sov_evm::Evm;
+use sov_modules_api::WorkingSet;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::TryRecvError;
+
+use crate::subscription::latest_block_number;
+use crate::LogsConfig;
+
+/// Limits of the polling filters, configured by node operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FiltersConfig {
+    /// How long a filter lives without being polled.
+    pub timeout: Duration,
+    /// The maximum number of filters installed at the same time.
+    pub max_filters: usize,
+}
+
+impl Default for FiltersConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(300),
+            max_filters: 1024,
+        }
+    }
+}
+
+/// What a filter reports when it's polled.
+pub(crate) enum FilterKind {
+    /// The logs matching the filter, in the blocks sealed after `last_block`.
+    Logs {
+        filter: Box<Filter>,
+        last_block: u64,
+    },
+    /// The hashes of the blocks sealed after `last_block`.
+    Blocks { last_block: u64 },
+    /// The hashes of the transactions submitted through this node since the last poll.
+    PendingTransactions(broadcast::Receiver<H256>),
+}
+
+/// The result of `eth_getFilterChanges`, depending on the kind of the filter.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub(crate) enum FilterChanges {
+    Logs(Vec<Log>),
+    Hashes(Vec<H256>),
+}
+
+/// The reasons filter operations fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FilterError {
+    /// The filter doesn't exist, or expired.
+    NotFound,
+    /// The maximum number of installed filters is reached.
+    TooManyFilters(usize),
+}
+
+impl std::fmt::Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterError::NotFound => write!(f, "filter not found"),
+            FilterError::TooManyFilters(max) => {
+                write!(f, "too many installed filters, the maximum is {}", max)
+            }
+        }
+    }
+}
+
+struct InstalledFilter {
+    kind: FilterKind,
+    last_poll: Instant,
+}
+
+/// Keeps track of the installed filters, and evicts the expired ones.
+pub(crate) struct FilterManager {
+    config: FiltersConfig,
+    filters: Mutex<HashMap<U128, InstalledFilter>>,
+    /// Mixed into the filter ids, so that they can't be guessed from the number of filters installed.
+    id_salt: [u8; 16],
+    next_id: Mutex<u64>,
+}
+
+impl FilterManager {
+    pub(crate) fn new(config: FiltersConfig) -> Self {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        Self {
+            config,
+            filters: Mutex::new(HashMap::new()),
+            id_salt: nanos.to_be_bytes(),
+            next_id: Mutex::new(0),
+        }
+    }
+
+    /// Installs a filter and returns its id.
+    pub(crate) fn install(&self, kind: FilterKind) -> Result<U128, FilterError> {
+        let mut filters = self.filters.lock().unwrap();
+        self.evict_expired(&mut filters);
+        if filters.len() >= self.config.max_filters {
+            return Err(FilterError::TooManyFilters(self.config.max_filters));
+        }
+
+        let id = self.next_id();
+        filters.insert(
+            id,
+            InstalledFilter {
+                kind,
+                last_poll: Instant::now(),
+            },
+        );
+        Ok(id)
+    }
+
+    /// Runs `f` on the filter with the given id, which postpones its expiry.
+    pub(crate) fn poll<R>(
+        &self,
+        id: U128,
+        f: impl FnOnce(&mut FilterKind) -> R,
+    ) -> Result<R, FilterError> {
+        let mut filters = self.filters.lock().unwrap();
+        self.evict_expired(&mut filters);
+        let filter = filters.get_mut(&id).ok_or(FilterError::NotFound)?;
+        filter.last_poll = Instant::now();
+        Ok(f(&mut filter.kind))
+    }
+
+    /// Removes the filter with the given id. Returns whether it was installed.
+    pub(crate) fn uninstall(&self, id: U128) -> bool {
+        let mut filters = self.filters.lock().unwrap();
+        self.evict_expired(&mut filters);
+        filters.remove(&id).is_some()
+    }
+
+    fn evict_expired(&self, filters: &mut HashMap<U128, InstalledFilter>) {
+        filters.retain(|_, filter| filter.last_poll.elapsed() < self.config.timeout);
+    }
+
+    fn next_id(&self) -> U128 {
+        let mut next_id = self.next_id.lock().unwrap();
+        let mut preimage = self.id_salt.to_vec();
+        preimage.extend_from_slice(&next_id.to_be_bytes());
+        *next_id += 1;
+
+        let hash = keccak256(preimage);
+        let bytes: [u8; 16] = hash[..16].try_into().expect("Hash is 32 bytes long");
+        U128::from_be_bytes(bytes)
+    }
+}
+
+/// Returns what the filter reports since it was last polled, and marks it as polled up to `head`.
+pub(crate) fn filter_changes<C: sov_modules_api::Context>(
+    kind: &mut FilterKind,
+    evm: &Evm<C>,
+    logs_config: &LogsConfig,
+    working_set: &mut WorkingSet<C>,
+) -> jsonrpsee::core::RpcResult<FilterChanges> {
+    let head = latest_block_number(evm, working_set);
+    match kind {
+        FilterKind::Logs { filter, last_block } => {
+            let (from_block, to_block) = block_range(filter, head);
+            let from_block = from_block.max(*last_block + 1);
+            let to_block = to_block.min(head);
+            if from_block > to_block {
+                *last_block = (*last_block).max(head);
+                return Ok(FilterChanges::Logs(Vec::new()));
+            }
+
+            let range = (**filter).clone().from_block(from_block).to_block(to_block);
+            let logs = evm.get_logs(
+                range,
+                logs_config.max_block_range,
+                logs_config.max_results,
+                working_set,
+            )?;
+            *last_block = head;
+            Ok(FilterChanges::Logs(logs))
+        }
+        FilterKind::Blocks { last_block } => {
+            let mut hashes = Vec::new();
+            for number in (*last_block + 1)..=head {
+                let block = evm.get_block_by_number(
+                    Some(format!("0x{:x}", number)),
+                    Some(false),
+                    working_set,
+                )?;
+                hashes.extend(block.and_then(|block| block.header.hash));
+            }
+            *last_block = (*last_block).max(head);
+            Ok(FilterChanges::Hashes(hashes))
+        }
+        FilterKind::PendingTransactions(rx) => Ok(FilterChanges::Hashes(drain(rx))),
+    }
+}
+
+/// Returns the hashes received since the last call.
+fn drain(rx: &mut broadcast::Receiver<H256>) -> Vec<H256> {
+    let mut hashes = Vec::new();
+    loop {
+        match rx.try_recv() {
+            Ok(hash) => hashes.push(hash),
+            // The oldest hashes were dropped, the filter reports the remaining ones
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+        }
+    }
+    hashes
+}
+
+/// Resolves the block range of a log filter in the same way as `eth_getLogs`.
+fn block_range(filter: &Filter, head: u64) -> (u64, u64) {
+    let resolve = |block: &Option<BlockNumberOrTag>| match block {
+        Some(BlockNumberOrTag::Number(number)) => *number,
+        Some(BlockNumberOrTag::Earliest) => 0,
+        _ => head,
+    };
+    match &filter.block_option {
+        FilterBlockOption::Range {
+            from_block,
+            to_block,
+        } => (resolve(from_block), resolve(to_block)),
+        // Block hash filters are rejected when they're installed
+        FilterBlockOption::AtBlockHash(_) => (head, head),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks_filter() -> FilterKind {
+        FilterKind::Blocks { last_block: 0 }
+    }
+
+    #[test]
+    fn filters_are_uninstalled() {
+        let manager = FilterManager::new(FiltersConfig::default());
+        let first = manager.install(blocks_filter()).unwrap();
+        let second = manager.install(blocks_filter()).unwrap();
+        assert_ne!(first, second);
+
+        assert!(manager.uninstall(first));
+        assert!(!manager.uninstall(first));
+        assert_eq!(Err(FilterError::NotFound), manager.poll(first, |_| ()));
+        assert_eq!(Ok(()), manager.poll(second, |_| ()));
+    }
+
+    #[test]
+    fn filters_expire_without_polls() {
+        let manager = FilterManager::new(FiltersConfig {
+            timeout: Duration::from_millis(50),
+            max_filters: 1,
+        });
+        let id = manager.install(blocks_filter()).unwrap();
+        assert_eq!(
+            Err(FilterError::TooManyFilters(1)),
+            manager.install(blocks_filter()).map(|_| ())
+        );
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert_eq!(Ok(()), manager.poll(id, |_| ()));
+        std::thread::sleep(Duration::from_millis(30));
+        // The poll kept the filter alive
+        assert_eq!(Ok(()), manager.poll(id, |_| ()));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(Err(FilterError::NotFound), manager.poll(id, |_| ()));
+        // The expired filter doesn't count towards the limit anymore
+        assert!(manager.install(blocks_filter()).is_ok());
+    }
+
+    #[test]
+    fn pending_transaction_filters_drain_their_channel() {
+        let (tx, rx) = broadcast::channel(2);
+        let manager = FilterManager::new(FiltersConfig::default());
+        let id = manager
+            .install(FilterKind::PendingTransactions(rx))
+            .unwrap();
+
+        for byte in 1..=3 {
+            tx.send(H256::repeat_byte(byte)).unwrap();
+        }
+        let drain = |kind: &mut FilterKind| match kind {
+            FilterKind::PendingTransactions(rx) => drain(rx),
+            _ => unreachable!(),
+        };
+        // The channel keeps the latest two hashes
+        assert_eq!(
+            Ok(vec![H256::repeat_byte(2), H256::repeat_byte(3)]),
+            manager.poll(id, drain)
+        );
+        assert_eq!(Ok(vec![]), manager.poll(id, drain));
+    }
+}
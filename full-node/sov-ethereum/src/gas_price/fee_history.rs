@@ -0,0 +1,89 @@
+//! Helpers for building `eth_feeHistory` responses from sealed blocks and their receipts.
+
+// Adopted from: https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/api/fees.rs
+
+use reth_primitives::U256;
+
+/// Gas used and effective tip of a single transaction in a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TxGasAndReward {
+    /// Gas used by the transaction, taken from its receipt
+    pub(crate) gas_used: u64,
+    /// Effective tip paid per unit of gas
+    pub(crate) reward: U256,
+}
+
+/// Returns `true` if the percentiles are monotonically increasing values between 0 and 100.
+pub(crate) fn validate_reward_percentiles(percentiles: &[f64]) -> bool {
+    percentiles.iter().all(|p| (0.0..=100.0).contains(p))
+        && percentiles.windows(2).all(|pair| pair[0] <= pair[1])
+}
+
+/// Calculates the effective tips at the requested percentiles of a block.
+///
+/// Every transaction is weighted by the gas it used, so the reward at a percentile is the tip
+/// of the transaction that brings the cumulative gas used over that share of `block_gas_used`.
+pub(crate) fn calculate_reward_percentiles(
+    percentiles: &[f64],
+    block_gas_used: u64,
+    mut transactions: Vec<TxGasAndReward>,
+) -> Vec<U256> {
+    if transactions.is_empty() {
+        return vec![U256::ZERO; percentiles.len()];
+    }
+
+    transactions.sort_unstable_by_key(|tx| tx.reward);
+
+    let mut rewards = Vec::with_capacity(percentiles.len());
+    let mut tx_index = 0;
+    let mut cumulative_gas_used = transactions[0].gas_used;
+
+    for percentile in percentiles {
+        let threshold = (block_gas_used as f64 * percentile / 100.0) as u64;
+        while cumulative_gas_used < threshold && tx_index < transactions.len() - 1 {
+            tx_index += 1;
+            cumulative_gas_used += transactions[tx_index].gas_used;
+        }
+        rewards.push(transactions[tx_index].reward);
+    }
+
+    rewards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(gas_used: u64, reward: u64) -> TxGasAndReward {
+        TxGasAndReward {
+            gas_used,
+            reward: U256::from(reward),
+        }
+    }
+
+    #[test]
+    fn empty_block_has_zero_rewards() {
+        let rewards = calculate_reward_percentiles(&[10.0, 50.0, 90.0], 0, vec![]);
+        assert_eq!(rewards, vec![U256::ZERO; 3]);
+    }
+
+    #[test]
+    fn rewards_are_weighted_by_gas_used() {
+        let transactions = vec![tx(79_000, 3), tx(21_000, 1)];
+        let rewards =
+            calculate_reward_percentiles(&[0.0, 10.0, 50.0, 100.0], 100_000, transactions);
+        assert_eq!(
+            rewards,
+            vec![U256::from(1), U256::from(1), U256::from(3), U256::from(3)]
+        );
+    }
+
+    #[test]
+    fn validates_percentiles() {
+        assert!(validate_reward_percentiles(&[]));
+        assert!(validate_reward_percentiles(&[0.0, 25.0, 25.0, 100.0]));
+        assert!(!validate_reward_percentiles(&[50.0, 25.0]));
+        assert!(!validate_reward_percentiles(&[-1.0]));
+        assert!(!validate_reward_percentiles(&[100.5]));
+    }
+}
@@ -1,11 +1,14 @@
-//! An implementation of the eth gas price oracle, used for providing gas price estimates based on
-//! previous blocks.
+//! An implementation of the eth gas price oracle, used for providing gas price and priority fee
+//! estimates based on previous blocks.
 
 // Adopted from: https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/gas_oracle.rs
 
+use std::sync::{Arc, Mutex as SyncMutex};
+
 use reth_primitives::constants::GWEI_TO_WEI;
-use reth_primitives::{H256, U256, U64};
-use reth_rpc_types::BlockTransactions;
+use reth_primitives::{BlockNumberOrTag, H256, U256, U64};
+use reth_rpc_types::{BlockTransactions, FeeHistory};
+use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
 use sov_evm::{EthApiError, EthResult, Evm, RpcInvalidTransactionError};
 use sov_modules_api::WorkingSet;
@@ -13,8 +16,11 @@ use tokio::sync::Mutex;
 use tracing::warn;
 
 use super::cache::BlockCache;
+use super::fee_history::{
+    calculate_reward_percentiles, validate_reward_percentiles, TxGasAndReward,
+};
 
-/// The number of transactions sampled in a block
+/// The default number of transactions sampled in a block
 pub const SAMPLE_NUMBER: u32 = 3;
 
 /// The default maximum gas price to use for the estimate
@@ -25,7 +31,7 @@ pub const DEFAULT_IGNORE_PRICE: U256 = U256::from_limbs([2u64, 0, 0, 0]);
 
 /// Settings for the gas price oracle configured by node operators
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", default)]
 pub struct GasPriceOracleConfig {
     /// The number of populated blocks to produce the gas price estimate
     pub blocks: u32,
@@ -33,13 +39,16 @@ pub struct GasPriceOracleConfig {
     /// The percentile of gas prices to use for the estimate
     pub percentile: u32,
 
+    /// The number of the lowest tips sampled in every block
+    pub sample_number: u32,
+
     /// The maximum number of headers to keep in the cache
     pub max_header_history: u32,
 
     /// The maximum number of blocks for estimating gas price
     pub max_block_history: u64,
 
-    /// The default gas price to use if there are no blocks to use
+    /// The default priority fee to use if there are no blocks to use
     pub default: Option<U256>,
 
     /// The maximum gas price to use for the estimate
@@ -54,6 +63,7 @@ impl Default for GasPriceOracleConfig {
         GasPriceOracleConfig {
             blocks: 20,
             percentile: 60,
+            sample_number: SAMPLE_NUMBER,
             max_header_history: 1024,
             max_block_history: 1024,
             default: None,
@@ -74,6 +84,7 @@ impl GasPriceOracleConfig {
         Self {
             blocks: blocks.unwrap_or(20),
             percentile: percentile.unwrap_or(60),
+            sample_number: SAMPLE_NUMBER,
             max_header_history: 1024,
             max_block_history: 1024,
             default: None,
@@ -83,38 +94,74 @@ impl GasPriceOracleConfig {
     }
 }
 
+/// Gives the current config of a [`GasPriceOracle`], for configs which change while the node runs.
+pub type GasPriceOracleConfigSource = Arc<dyn Fn() -> GasPriceOracleConfig + Send + Sync>;
+
+/// The parent hash of a block and the tips sampled from its transactions.
+type BlockValues = (H256, Vec<U256>);
+
 /// Calculates a gas price depending on recent blocks.
 pub struct GasPriceOracle<C: sov_modules_api::Context> {
     /// The type used to get block and tx info
     provider: Evm<C>,
-    /// The config for the oracle
-    oracle_config: GasPriceOracleConfig,
+    /// The config for the oracle, as it was last used
+    oracle_config: SyncMutex<GasPriceOracleConfig>,
+    /// Where the config is read from before every estimate, if it can change
+    config_source: Option<GasPriceOracleConfigSource>,
     /// The latest calculated price and its block hash
     last_price: Mutex<GasPriceOracleResult>,
     /// Cache
     cache: BlockCache<C>,
+    /// The tips sampled from every recent block, so they aren't recomputed for every new head
+    block_values: SyncMutex<LruMap<H256, BlockValues, ByLength>>,
 }
 
 impl<C: sov_modules_api::Context> GasPriceOracle<C> {
     /// Creates and returns the [GasPriceOracle].
-    pub fn new(provider: Evm<C>, mut oracle_config: GasPriceOracleConfig) -> Self {
-        // sanitize the percentile to be less than 100
-        if oracle_config.percentile > 100 {
-            warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
-            oracle_config.percentile = 100;
-        }
-
+    pub fn new(provider: Evm<C>, oracle_config: GasPriceOracleConfig) -> Self {
+        let oracle_config = sanitize_config(oracle_config);
         let max_header_history = oracle_config.max_header_history;
+        let last_price = GasPriceOracleResult {
+            price: oracle_config.default.unwrap_or(U256::from(GWEI_TO_WEI)),
+            ..Default::default()
+        };
 
         Self {
             provider: provider.clone(),
-            oracle_config,
-            last_price: Default::default(),
+            oracle_config: SyncMutex::new(oracle_config),
+            config_source: None,
+            last_price: Mutex::new(last_price),
             cache: BlockCache::<C>::new(max_header_history, provider),
+            block_values: SyncMutex::new(LruMap::new(ByLength::new(max_header_history))),
+        }
+    }
+
+    /// Makes the oracle read its config from `config_source` before every estimate.
+    /// The sizes of the caches are the ones of the initial config.
+    pub fn with_config_source(mut self, config_source: GasPriceOracleConfigSource) -> Self {
+        self.config_source = Some(config_source);
+        self
+    }
+
+    /// Returns the current config, and whether it changed since it was last used.
+    /// The sampled tips are dropped when it changes, since they depend on the config.
+    fn refresh_config(&self) -> (GasPriceOracleConfig, bool) {
+        let mut oracle_config = self.oracle_config.lock().unwrap();
+        let Some(config_source) = &self.config_source else {
+            return (oracle_config.clone(), false);
+        };
+        let new_config = sanitize_config(config_source());
+        if new_config == *oracle_config {
+            return (new_config, false);
         }
+        *oracle_config = new_config.clone();
+        self.block_values.lock().unwrap().clear();
+        (new_config, true)
     }
 
-    /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
+    /// Suggests a priority fee based on recent blocks: the configured percentile of the lowest
+    /// tips paid in the last populated blocks, ignoring the tips under the configured floor.
+    /// The suggestion is computed once per head block.
     pub async fn suggest_tip_cap(&self, working_set: &mut WorkingSet<C>) -> EthResult<U256> {
         let header = &self
             .provider
@@ -124,9 +171,10 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             .header;
 
         let mut last_price = self.last_price.lock().await;
+        let (oracle_config, config_changed) = self.refresh_config();
 
         // if we have stored a last price, then we check whether or not it was for the same head
-        if last_price.block_hash == header.hash.unwrap() {
+        if !config_changed && last_price.block_hash == header.hash.unwrap() {
             return Ok(last_price.price);
         }
 
@@ -142,15 +190,19 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
         let header_number = convert_u256_to_u64(header.number.unwrap());
 
         // we only check a maximum of 2 * max_block_history, or the number of blocks in the chain
-        let max_blocks = if self.oracle_config.max_block_history * 2 > header_number {
+        let max_blocks = if oracle_config.max_block_history * 2 > header_number {
             header_number
         } else {
-            self.oracle_config.max_block_history * 2
+            oracle_config.max_block_history * 2
         };
 
         for _ in 0..max_blocks {
             let (parent_hash, block_values) = self
-                .get_block_values(current_hash, SAMPLE_NUMBER as usize, working_set)
+                .get_block_values(
+                    current_hash,
+                    oracle_config.sample_number as usize,
+                    working_set,
+                )
                 .await?
                 .ok_or(EthApiError::UnknownBlockNumber)?;
 
@@ -162,7 +214,7 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             }
 
             // break when we have enough populated blocks
-            if populated_blocks >= self.oracle_config.blocks {
+            if populated_blocks >= oracle_config.blocks {
                 break;
             }
 
@@ -170,16 +222,11 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
         }
 
         // sort results then take the configured percentile result
-        let mut price = last_price.price;
-        if !results.is_empty() {
-            results.sort_unstable();
-            price = *results
-                .get((results.len() - 1) * self.oracle_config.percentile as usize / 100)
-                .expect("gas price index is a percent of nonzero array length, so a value always exists; qed");
-        }
+        let mut price =
+            percentile_of(&mut results, oracle_config.percentile).unwrap_or(last_price.price);
 
         // constrain to the max price
-        if let Some(max_price) = self.oracle_config.max_price {
+        if let Some(max_price) = oracle_config.max_price {
             if price > max_price {
                 price = max_price;
             }
@@ -193,6 +240,146 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
         Ok(price)
     }
 
+    /// Reports the fee history of up to `block_count` blocks ending with `newest_block`.
+    ///
+    /// Returns the base fees (including the one of the block following `newest_block`), the gas
+    /// used ratios and, if requested, the effective tips at `reward_percentiles` of every block,
+    /// ordered from the oldest block to the newest one.
+    pub async fn fee_history(
+        &self,
+        block_count: u64,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<FeeHistory> {
+        if let Some(percentiles) = &reward_percentiles {
+            if !validate_reward_percentiles(percentiles) {
+                return Err(EthApiError::InvalidRewardPercentiles);
+            }
+        }
+
+        let head_number = convert_u256_to_u64(
+            self.provider
+                .get_block_by_number(None, None, working_set)
+                .unwrap()
+                .unwrap()
+                .header
+                .number
+                .unwrap(),
+        );
+
+        let newest_number = match newest_block {
+            BlockNumberOrTag::Number(number) => number,
+            BlockNumberOrTag::Earliest => 0,
+            // safe, finalized and pending blocks are not tracked, so the head is used instead
+            _ => head_number,
+        };
+
+        if newest_number > head_number {
+            return Err(EthApiError::UnknownBlockNumber);
+        }
+
+        if block_count == 0 {
+            return Ok(FeeHistory {
+                base_fee_per_gas: Vec::new(),
+                gas_used_ratio: Vec::new(),
+                oldest_block: U256::from(newest_number),
+                reward: reward_percentiles.map(|_| Vec::new()),
+            });
+        }
+
+        // we can't go past genesis or the configured history
+        let block_count = block_count
+            .min(self.refresh_config().0.max_block_history)
+            .min(newest_number + 1);
+        let oldest_block = newest_number + 1 - block_count;
+
+        let newest = self
+            .provider
+            .get_block_by_number(Some(format!("0x{:x}", newest_number)), None, working_set)
+            .unwrap_or(None)
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let mut base_fee_per_gas = Vec::with_capacity(block_count as usize + 1);
+        let mut gas_used_ratio = Vec::with_capacity(block_count as usize);
+        let mut reward = reward_percentiles
+            .as_ref()
+            .map(|_| Vec::with_capacity(block_count as usize));
+
+        // walk back from the newest block, so the results are collected in reverse order
+        let mut current_hash = newest.header.hash.unwrap();
+        for _ in 0..block_count {
+            let block = self
+                .cache
+                .get_block(current_hash, working_set)?
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+
+            let gas_used = convert_u256_to_u64(block.header.gas_used);
+            let gas_limit = convert_u256_to_u64(block.header.gas_limit);
+
+            base_fee_per_gas.push(block.header.base_fee_per_gas.unwrap_or_default());
+            gas_used_ratio.push(if gas_limit == 0 {
+                0.0
+            } else {
+                gas_used as f64 / gas_limit as f64
+            });
+
+            if let (Some(percentiles), Some(reward)) = (&reward_percentiles, &mut reward) {
+                let txs = match &block.transactions {
+                    BlockTransactions::Full(txs) => txs.as_slice(),
+                    _ => &[],
+                };
+
+                let mut transactions = Vec::with_capacity(txs.len());
+                for tx in txs {
+                    let receipt = self
+                        .provider
+                        .get_transaction_receipt(tx.hash, working_set)
+                        .unwrap_or(None)
+                        .ok_or(EthApiError::TransactionNotFound)?;
+
+                    transactions.push(TxGasAndReward {
+                        gas_used: convert_u256_to_u64(receipt.receipt.gas_used.unwrap_or_default()),
+                        reward: effective_gas_tip(tx, block.header.base_fee_per_gas)
+                            .unwrap_or_default(),
+                    });
+                }
+
+                reward.push(calculate_reward_percentiles(
+                    percentiles,
+                    gas_used,
+                    transactions,
+                ));
+            }
+
+            current_hash = block.header.parent_hash;
+        }
+
+        base_fee_per_gas.reverse();
+        gas_used_ratio.reverse();
+        if let Some(reward) = &mut reward {
+            reward.reverse();
+        }
+
+        // the base fee of the next block is known in advance
+        let next_base_fee = self
+            .provider
+            .get_chain_config(working_set)
+            .next_block_base_fee(
+                convert_u256_to_u64(newest.header.gas_used),
+                convert_u256_to_u64(newest.header.gas_limit),
+                convert_u256_to_u64(newest.header.base_fee_per_gas.unwrap_or_default()),
+            );
+        base_fee_per_gas.push(U256::from(next_base_fee));
+
+        Ok(FeeHistory {
+            base_fee_per_gas,
+            gas_used_ratio,
+            oldest_block: U256::from(oldest_block),
+            reward,
+        })
+    }
+
     /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
     /// configured `ignore_price` threshold, then tip values under that threshold will be ignored
     /// before returning a result.
@@ -205,7 +392,27 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
         block_hash: H256,
         limit: usize,
         working_set: &mut WorkingSet<C>,
-    ) -> EthResult<Option<(H256, Vec<U256>)>> {
+    ) -> EthResult<Option<BlockValues>> {
+        if let Some(values) = self.block_values.lock().unwrap().get(&block_hash) {
+            return Ok(Some(values.clone()));
+        }
+
+        let values = self.sample_block_values(block_hash, limit, working_set)?;
+        if let Some(values) = &values {
+            self.block_values
+                .lock()
+                .unwrap()
+                .insert(block_hash, values.clone());
+        }
+        Ok(values)
+    }
+
+    fn sample_block_values(
+        &self,
+        block_hash: H256,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<Option<BlockValues>> {
         // check the cache (this will hit the disk if the block is not cached)
         let block = match self.cache.get_block(block_hash, working_set)? {
             Some(block) => block,
@@ -221,10 +428,11 @@ impl<C: sov_modules_api::Context> GasPriceOracle<C> {
             _ => return Ok(None),
         };
 
+        let ignore_price = self.oracle_config.lock().unwrap().ignore_price;
         let mut txs = txs
             .iter()
             .filter(|tx| {
-                if let Some(ignore_under) = self.oracle_config.ignore_price {
+                if let Some(ignore_under) = ignore_price {
                     let effective_gas_tip = effective_gas_tip(tx, block.header.base_fee_per_gas);
                     if effective_gas_tip < Some(ignore_under) {
                         return false;
@@ -274,6 +482,24 @@ impl Default for GasPriceOracleResult {
     }
 }
 
+/// Caps the percentile of the config to 100.
+fn sanitize_config(mut oracle_config: GasPriceOracleConfig) -> GasPriceOracleConfig {
+    if oracle_config.percentile > 100 {
+        warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
+        oracle_config.percentile = 100;
+    }
+    oracle_config
+}
+
+/// Sorts the samples and returns the one at the given percentile, or `None` if there are no samples.
+fn percentile_of(samples: &mut [U256], percentile: u32) -> Option<U256> {
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_unstable();
+    Some(samples[(samples.len() - 1) * percentile as usize / 100])
+}
+
 // Adopted from: https://github.com/paradigmxyz/reth/blob/main/crates/primitives/src/transaction/mod.rs#L297
 fn effective_gas_tip(
     transaction: &reth_rpc_types::Transaction,
@@ -329,6 +555,54 @@ mod tests {
         assert_eq!(DEFAULT_IGNORE_PRICE, U256::from(2u64));
     }
 
+    #[test]
+    fn percentile_of_samples() {
+        assert_eq!(percentile_of(&mut [], 60), None);
+
+        let mut samples: Vec<U256> = [5u64, 1, 4, 2, 3].into_iter().map(U256::from).collect();
+        assert_eq!(percentile_of(&mut samples, 0), Some(U256::from(1u64)));
+        assert_eq!(percentile_of(&mut samples, 60), Some(U256::from(3u64)));
+        assert_eq!(percentile_of(&mut samples, 100), Some(U256::from(5u64)));
+    }
+
+    #[test]
+    fn partial_config_uses_defaults() {
+        let config: GasPriceOracleConfig =
+            serde_json::from_str(r#"{"percentile": 40, "ignorePrice": "0x64"}"#).unwrap();
+        assert_eq!(
+            config,
+            GasPriceOracleConfig {
+                percentile: 40,
+                ignore_price: Some(U256::from(100u64)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn config_source_changes_are_applied() {
+        type C = sov_modules_api::default_context::ZkDefaultContext;
+
+        let percentile = Arc::new(SyncMutex::new(60));
+        let source_percentile = percentile.clone();
+        let oracle = GasPriceOracle::new(Evm::<C>::default(), GasPriceOracleConfig::default())
+            .with_config_source(Arc::new(move || GasPriceOracleConfig {
+                percentile: *source_percentile.lock().unwrap(),
+                ..Default::default()
+            }));
+        assert_eq!(
+            oracle.refresh_config(),
+            (GasPriceOracleConfig::default(), false)
+        );
+
+        // Invalid percentiles are capped, as in the initial config
+        *percentile.lock().unwrap() = 120;
+        let (config, changed) = oracle.refresh_config();
+        assert!(changed);
+        assert_eq!(config.percentile, 100);
+        assert!(!oracle.refresh_config().1);
+    }
+
     proptest! {
 
         #[test]
@@ -1,2 +1,3 @@
 pub(crate) mod cache;
+pub(crate) mod fee_history;
 pub(crate) mod gas_oracle;
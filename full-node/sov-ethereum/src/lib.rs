@@ -1,11 +1,19 @@
 #[cfg(feature = "experimental")]
 mod batch_builder;
 #[cfg(feature = "experimental")]
+mod filters;
+#[cfg(feature = "experimental")]
 mod gas_price;
 #[cfg(feature = "experimental")]
-pub use experimental::{get_ethereum_rpc, Ethereum};
+mod pending;
+#[cfg(feature = "experimental")]
+mod subscription;
+#[cfg(feature = "experimental")]
+pub use experimental::{get_ethereum_rpc, Ethereum, LogsConfig, PENDING_TAG_METHODS};
 #[cfg(feature = "experimental")]
-pub use gas_price::gas_oracle::GasPriceOracleConfig;
+pub use filters::FiltersConfig;
+#[cfg(feature = "experimental")]
+pub use gas_price::gas_oracle::{GasPriceOracleConfig, GasPriceOracleConfigSource};
 #[cfg(feature = "experimental")]
 pub use sov_evm::DevSigner;
 
@@ -19,42 +27,110 @@ pub mod experimental {
     use ethers::types::{Bytes, H256};
     use jsonrpsee::types::ErrorObjectOwned;
     use jsonrpsee::RpcModule;
-    use reth_primitives::{TransactionSignedNoHash as RethTransactionSignedNoHash, U128, U256};
-    use reth_rpc_types::{CallRequest, TransactionRequest, TypedTransactionRequest};
-    use sov_evm::{CallMessage, Evm, RlpEvmTransaction};
+    use reth_primitives::{
+        BlockNumberOrTag, TransactionSignedNoHash as RethTransactionSignedNoHash, U128, U256, U64,
+    };
+    use reth_rpc_types::pubsub::{Params, SubscriptionKind};
+    use reth_rpc_types::state::StateOverride;
+    use reth_rpc_types::{
+        BlockOverrides, CallRequest, FeeHistory, Filter, FilterBlockOption, FilteredParams,
+        JsonStorageKey, TransactionRequest, TypedTransactionRequest,
+    };
+    use sov_evm::{CallMessage, EthApiError, Evm, LogsCursor, RlpEvmTransaction};
     use sov_modules_api::utils::to_jsonrpsee_error_object;
     use sov_modules_api::{EncodeCall, PrivateKey, WorkingSet};
+    use sov_rollup_interface::chunking::split_blob;
+    use sov_rollup_interface::compression::compress_blob;
     use sov_rollup_interface::services::da::DaService;
+    use sov_state::storage::NativeStorage;
+    use tokio::sync::broadcast;
 
     use super::batch_builder::EthBatchBuilder;
+    use super::filters::{filter_changes, FilterKind, FilterManager};
     #[cfg(feature = "local")]
     use super::DevSigner;
     use crate::gas_price::gas_oracle::GasPriceOracle;
-    use crate::GasPriceOracleConfig;
+    use crate::pending::PendingTransactions;
+    use crate::subscription::{
+        latest_block_number, pipe_from_stream, pipe_logs_pages, SubscriptionManager,
+    };
+    use crate::{FiltersConfig, GasPriceOracleConfig, GasPriceOracleConfigSource};
 
     const ETH_RPC_ERROR: &str = "ETH_RPC_ERROR";
 
+    /// The methods of the EVM module which are served by the Ethereum RPC instead, because the
+    /// `pending` block tag needs the transactions submitted through it. They have to be removed
+    /// from the runtime RPC before the Ethereum RPC is merged into it.
+    pub const PENDING_TAG_METHODS: [&str; 3] = [
+        "eth_getBlockByNumber",
+        "eth_call",
+        "eth_getTransactionCount",
+    ];
+
     #[derive(Clone)]
     pub struct EthRpcConfig<C: sov_modules_api::Context> {
         pub min_blob_size: Option<usize>,
+        /// The zstd compression level of the batches submitted to DA, if they are compressed.
+        pub da_compression_level: Option<i32>,
         pub sov_tx_signer_priv_key: C::PrivateKey,
         pub gas_price_oracle_config: GasPriceOracleConfig,
+        /// Where the gas price oracle reads its config before every estimate, if it can change
+        /// while the node runs. `gas_price_oracle_config` is used otherwise.
+        pub gas_price_oracle_config_source: Option<GasPriceOracleConfigSource>,
+        pub logs_config: LogsConfig,
+        pub filters_config: FiltersConfig,
         #[cfg(feature = "local")]
         pub eth_signer: DevSigner,
     }
 
+    /// Limits of the `eth_getLogs` queries, configured by node operators.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct LogsConfig {
+        /// The maximum number of blocks a single query can span.
+        pub max_block_range: u64,
+        /// The maximum number of logs a single query can return.
+        pub max_results: usize,
+    }
+
+    impl Default for LogsConfig {
+        fn default() -> Self {
+            Self {
+                max_block_range: 100_000,
+                max_results: 20_000,
+            }
+        }
+    }
+
+    impl LogsConfig {
+        /// The number of logs of the `sov_getLogsPage` pages: the requested size,
+        /// capped at `max_results`, or `max_results` if the request doesn't set it.
+        pub fn page_size(&self, requested: Option<usize>) -> usize {
+            requested.map_or(self.max_results, |size| size.min(self.max_results))
+        }
+    }
+
+    /// Creates the Ethereum RPC module. The `eth_subscribe` notifications are published every time
+    /// a slot number is received from `slot_subscription`.
     pub fn get_ethereum_rpc<C: sov_modules_api::Context, Da: DaService>(
         da_service: Da,
         eth_rpc_config: EthRpcConfig<C>,
         storage: C::Storage,
-    ) -> RpcModule<Ethereum<C, Da>> {
+        slot_subscription: broadcast::Receiver<u64>,
+    ) -> RpcModule<Ethereum<C, Da>>
+    where
+        C::Storage: NativeStorage,
+    {
         // Unpack config
         let EthRpcConfig {
             min_blob_size,
+            da_compression_level,
             sov_tx_signer_priv_key,
             #[cfg(feature = "local")]
             eth_signer,
             gas_price_oracle_config,
+            gas_price_oracle_config_source,
+            logs_config,
+            filters_config,
         } = eth_rpc_config;
 
         // Fetch nonce from storage
@@ -62,6 +138,7 @@ pub mod experimental {
         let sov_tx_signer_account = accounts
             .get_account(
                 sov_tx_signer_priv_key.pub_key(),
+                None,
                 &mut WorkingSet::<C>::new(storage.clone()),
             )
             .unwrap();
@@ -77,10 +154,15 @@ pub mod experimental {
                 sov_tx_signer_nonce,
                 min_blob_size,
             ))),
+            da_compression_level,
             gas_price_oracle_config,
+            gas_price_oracle_config_source,
+            logs_config,
+            filters_config,
             #[cfg(feature = "local")]
             eth_signer,
             storage,
+            slot_subscription,
         ));
 
         register_rpc_methods(&mut rpc).expect("Failed to register sequencer RPC methods");
@@ -90,39 +172,59 @@ pub mod experimental {
     pub struct Ethereum<C: sov_modules_api::Context, Da: DaService> {
         da_service: Da,
         batch_builder: Arc<Mutex<EthBatchBuilder<C>>>,
+        da_compression_level: Option<i32>,
         gas_price_oracle: GasPriceOracle<C>,
+        logs_config: LogsConfig,
         #[cfg(feature = "local")]
         eth_signer: DevSigner,
         storage: C::Storage,
+        subscriptions: SubscriptionManager,
+        filters: FilterManager,
+        pending_transactions: PendingTransactions,
     }
 
     impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
+        #[allow(clippy::too_many_arguments)]
         fn new(
             da_service: Da,
             batch_builder: Arc<Mutex<EthBatchBuilder<C>>>,
+            da_compression_level: Option<i32>,
             gas_price_oracle_config: GasPriceOracleConfig,
+            gas_price_oracle_config_source: Option<GasPriceOracleConfigSource>,
+            logs_config: LogsConfig,
+            filters_config: FiltersConfig,
             #[cfg(feature = "local")] eth_signer: DevSigner,
             storage: C::Storage,
+            slot_subscription: broadcast::Receiver<u64>,
         ) -> Self {
             let evm = Evm::<C>::default();
-            let gas_price_oracle = GasPriceOracle::new(evm, gas_price_oracle_config);
+            let mut gas_price_oracle = GasPriceOracle::new(evm, gas_price_oracle_config);
+            if let Some(config_source) = gas_price_oracle_config_source {
+                gas_price_oracle = gas_price_oracle.with_config_source(config_source);
+            }
+            let subscriptions = SubscriptionManager::new::<C>(storage.clone(), slot_subscription);
             Self {
                 da_service,
                 batch_builder,
+                da_compression_level,
                 gas_price_oracle,
+                logs_config,
                 #[cfg(feature = "local")]
                 eth_signer,
                 storage,
+                subscriptions,
+                filters: FilterManager::new(filters_config),
+                pending_transactions: PendingTransactions::default(),
             }
         }
     }
 
     impl<C: sov_modules_api::Context, Da: DaService> Ethereum<C, Da> {
-        fn make_raw_tx(
-            &self,
-            raw_tx: RlpEvmTransaction,
-        ) -> Result<(H256, Vec<u8>), jsonrpsee::core::Error> {
+        /// Decodes a raw transaction into a call message of the runtime, rejecting the
+        /// transactions the EVM doesn't accept before they reach the mempool.
+        fn make_raw_tx(&self, raw_tx: RlpEvmTransaction) -> Result<(H256, Vec<u8>), EthApiError> {
             let signed_transaction: RethTransactionSignedNoHash = raw_tx.clone().try_into()?;
+            sov_evm::ensure_supported_tx_type(signed_transaction.transaction.tx_type())?;
 
             let tx_hash = signed_transaction.hash();
 
@@ -151,14 +253,26 @@ pub mod experimental {
                 return Ok(());
             }
 
-            let blob = batch
+            let mut blob = batch
                 .try_to_vec()
                 .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            if let Some(level) = self.da_compression_level {
+                blob = compress_blob(&blob, level)
+                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            }
 
-            self.da_service
-                .send_transaction(&blob)
-                .await
-                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            // Batches larger than a DA transaction are posted in chunks
+            let chunks = match self.da_service.max_blob_size() {
+                Some(max_blob_size) => split_blob(&blob, max_blob_size)
+                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?,
+                None => vec![blob],
+            };
+            for chunk in &chunks {
+                self.da_service
+                    .send_transaction(chunk)
+                    .await
+                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            }
 
             Ok(())
         }
@@ -180,36 +294,278 @@ pub mod experimental {
         fn add_messages(&self, messages: Vec<Vec<u8>>) {
             self.batch_builder.lock().unwrap().add_messages(messages);
         }
+
+        /// Returns a working set for the queries at `block_number`. For the `pending` block,
+        /// the transactions submitted through this node which are not sealed yet are applied to it.
+        fn working_set_at(&self, evm: &Evm<C>, block_number: Option<&str>) -> WorkingSet<C> {
+            let mut working_set = WorkingSet::<C>::new_pinned(self.storage.clone());
+            if block_number == Some("pending") {
+                let txs = self.pending_transactions.get(evm, &mut working_set);
+                evm.apply_pending_transactions(&txs, &mut working_set);
+            }
+            working_set
+        }
     }
 
     fn register_rpc_methods<C: sov_modules_api::Context, Da: DaService>(
         rpc: &mut RpcModule<Ethereum<C, Da>>,
-    ) -> Result<(), jsonrpsee::core::Error> {
+    ) -> Result<(), jsonrpsee::core::Error>
+    where
+        C::Storage: NativeStorage,
+    {
         rpc.register_async_method("eth_gasPrice", |_, ethereum| async move {
             let price = {
-                let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+                let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
 
                 let suggested_tip = ethereum
                     .gas_price_oracle
                     .suggest_tip_cap(&mut working_set)
-                    .await
-                    .unwrap();
+                    .await?;
 
                 let evm = Evm::<C>::default();
-                let base_fee = evm
+                let head = evm
                     .get_block_by_number(None, None, &mut working_set)
                     .unwrap()
                     .unwrap()
-                    .header
-                    .base_fee_per_gas
-                    .unwrap_or_default();
+                    .header;
 
-                suggested_tip + base_fee
+                // The base fee of the next block is known in advance
+                let base_fee = evm.get_chain_config(&mut working_set).next_block_base_fee(
+                    convert_u256_to_u64(head.gas_used).unwrap(),
+                    convert_u256_to_u64(head.gas_limit).unwrap(),
+                    convert_u256_to_u64(head.base_fee_per_gas.unwrap_or_default()).unwrap(),
+                );
+
+                suggested_tip + U256::from(base_fee)
             };
 
             Ok::<U256, ErrorObjectOwned>(price)
         })?;
 
+        rpc.register_async_method("eth_maxPriorityFeePerGas", |_, ethereum| async move {
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+            let suggested_tip = ethereum
+                .gas_price_oracle
+                .suggest_tip_cap(&mut working_set)
+                .await?;
+
+            Ok::<U256, ErrorObjectOwned>(suggested_tip)
+        })?;
+
+        rpc.register_async_method("eth_feeHistory", |params, ethereum| async move {
+            let mut params = params.sequence();
+            let block_count: U64 = params.next()?;
+            let newest_block: BlockNumberOrTag = params.next()?;
+            let reward_percentiles: Option<Vec<f64>> = params.optional_next()?;
+
+            let fee_history = {
+                let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+                ethereum
+                    .gas_price_oracle
+                    .fee_history(
+                        block_count.as_u64(),
+                        newest_block,
+                        reward_percentiles,
+                        &mut working_set,
+                    )
+                    .await?
+            };
+
+            Ok::<FeeHistory, ErrorObjectOwned>(fee_history)
+        })?;
+
+        rpc.register_method("eth_getProof", |params, ethereum| {
+            let mut params = params.sequence();
+            let address: reth_primitives::Address = params.next()?;
+            let keys: Vec<JsonStorageKey> = params.next()?;
+            let block_number: Option<String> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            evm.get_proof(address, keys, block_number, &mut working_set)
+        })?;
+
+        rpc.register_method("sov_debugEvmAccount", |params, ethereum| {
+            let address: reth_primitives::Address = params.one()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            evm.debug_evm_account(address, &mut working_set)
+        })?;
+
+        rpc.register_method("sov_debugEvmStorageRange", |params, ethereum| {
+            let mut params = params.sequence();
+            let address: reth_primitives::Address = params.next()?;
+            let start: reth_primitives::U256 = params.next()?;
+            let limit: usize = params.next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            evm.debug_evm_storage_range(address, start, limit, &mut working_set)
+        })?;
+
+        rpc.register_method("eth_getBlockByNumber", |params, ethereum| {
+            let mut params = params.sequence();
+            let block_number: Option<String> = params.optional_next()?;
+            let details: Option<bool> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = ethereum.working_set_at(&evm, block_number.as_deref());
+
+            evm.get_block_by_number(block_number, details, &mut working_set)
+        })?;
+
+        rpc.register_method("eth_call", |params, ethereum| {
+            let mut params = params.sequence();
+            let request: CallRequest = params.next()?;
+            let block_number: Option<String> = params.optional_next()?;
+            let state_overrides: Option<StateOverride> = params.optional_next()?;
+            let block_overrides: Option<Box<BlockOverrides>> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = ethereum.working_set_at(&evm, block_number.as_deref());
+
+            evm.get_call(
+                request,
+                block_number,
+                state_overrides,
+                block_overrides,
+                &mut working_set,
+            )
+        })?;
+
+        rpc.register_method("eth_getTransactionCount", |params, ethereum| {
+            let mut params = params.sequence();
+            let address: reth_primitives::Address = params.next()?;
+            let block_number: Option<String> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = ethereum.working_set_at(&evm, block_number.as_deref());
+
+            evm.get_transaction_count(address, block_number, &mut working_set)
+        })?;
+
+        rpc.register_method("eth_getLogs", |params, ethereum| {
+            let filter: Filter = params.one()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            evm.get_logs(
+                filter,
+                ethereum.logs_config.max_block_range,
+                ethereum.logs_config.max_results,
+                &mut working_set,
+            )
+        })?;
+
+        rpc.register_method("sov_getLogsPage", |params, ethereum| {
+            let mut params = params.sequence();
+            let filter: Filter = params.next()?;
+            let cursor: Option<LogsCursor> = params.optional_next()?;
+            let page_size: Option<usize> = params.optional_next()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            evm.get_logs_page(
+                filter,
+                cursor,
+                ethereum.logs_config.page_size(page_size),
+                ethereum.logs_config.max_block_range,
+                &mut working_set,
+            )
+        })?;
+
+        rpc.register_method("eth_newFilter", |params, ethereum| {
+            let filter: Filter = params.one()?;
+            if let FilterBlockOption::AtBlockHash(_) = filter.block_option {
+                return Err(to_jsonrpsee_error_object(
+                    "Block hash filters can't be polled, use eth_getLogs instead",
+                    ETH_RPC_ERROR,
+                ));
+            }
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+            let last_block = latest_block_number(&evm, &mut working_set);
+
+            ethereum
+                .filters
+                .install(FilterKind::Logs {
+                    filter: Box::new(filter),
+                    last_block,
+                })
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+        })?;
+
+        rpc.register_method("eth_newBlockFilter", |_, ethereum| {
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+            let last_block = latest_block_number(&evm, &mut working_set);
+
+            ethereum
+                .filters
+                .install(FilterKind::Blocks { last_block })
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+        })?;
+
+        rpc.register_method("eth_newPendingTransactionFilter", |_, ethereum| {
+            let rx = ethereum.subscriptions.subscribe_pending_transactions();
+            ethereum
+                .filters
+                .install(FilterKind::PendingTransactions(rx))
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))
+        })?;
+
+        rpc.register_method("eth_getFilterChanges", |params, ethereum| {
+            let id: U128 = params.one()?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            ethereum
+                .filters
+                .poll(id, |kind| {
+                    filter_changes(kind, &evm, &ethereum.logs_config, &mut working_set)
+                })
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?
+        })?;
+
+        rpc.register_method("eth_getFilterLogs", |params, ethereum| {
+            let id: U128 = params.one()?;
+
+            let filter = ethereum
+                .filters
+                .poll(id, |kind| match kind {
+                    FilterKind::Logs { filter, .. } => Some((**filter).clone()),
+                    _ => None,
+                })
+                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?
+                .ok_or_else(|| {
+                    to_jsonrpsee_error_object("Filter is not a log filter", ETH_RPC_ERROR)
+                })?;
+
+            let evm = Evm::<C>::default();
+            let mut working_set = WorkingSet::<C>::new_pinned(ethereum.storage.clone());
+
+            evm.get_logs(
+                filter,
+                ethereum.logs_config.max_block_range,
+                ethereum.logs_config.max_results,
+                &mut working_set,
+            )
+        })?;
+
+        rpc.register_method("eth_uninstallFilter", |params, ethereum| {
+            let id: U128 = params.one()?;
+            Ok::<_, ErrorObjectOwned>(ethereum.filters.uninstall(id))
+        })?;
+
         rpc.register_async_method("eth_publishBatch", |params, ethereum| async move {
             let mut params_iter = params.sequence();
 
@@ -233,11 +589,13 @@ pub mod experimental {
 
                 let raw_evm_tx = RlpEvmTransaction { rlp: data.to_vec() };
 
-                let (tx_hash, raw_message) = ethereum
-                    .make_raw_tx(raw_evm_tx)
-                    .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+                let (tx_hash, raw_message) = ethereum.make_raw_tx(raw_evm_tx.clone())?;
 
                 ethereum.add_messages(vec![raw_message]);
+                ethereum.pending_transactions.add(raw_evm_tx);
+                ethereum
+                    .subscriptions
+                    .notify_pending_transaction(tx_hash.0.into());
 
                 Ok::<_, ErrorObjectOwned>(tx_hash)
             },
@@ -268,7 +626,8 @@ pub mod experimental {
             }
 
             let raw_evm_tx = {
-                let mut working_set = WorkingSet::<C>::new(ethereum.storage.clone());
+                // The nonce has to follow the transactions of the sender which are still pending
+                let mut working_set = ethereum.working_set_at(&evm, Some("pending"));
 
                 // set nonce if none
                 if transaction_request.nonce.is_none() {
@@ -342,15 +701,109 @@ pub mod experimental {
                     rlp: signed_tx.envelope_encoded().to_vec(),
                 }
             };
-            let (tx_hash, raw_message) = ethereum
-                .make_raw_tx(raw_evm_tx)
-                .map_err(|e| to_jsonrpsee_error_object(e, ETH_RPC_ERROR))?;
+            let (tx_hash, raw_message) = ethereum.make_raw_tx(raw_evm_tx.clone())?;
 
             ethereum.add_messages(vec![raw_message]);
+            ethereum.pending_transactions.add(raw_evm_tx);
+            ethereum
+                .subscriptions
+                .notify_pending_transaction(tx_hash.0.into());
 
             Ok::<_, ErrorObjectOwned>(tx_hash)
         })?;
 
+        rpc.register_subscription(
+            "eth_subscribe",
+            "eth_subscription",
+            "eth_unsubscribe",
+            |params, pending_subscription, ethereum| async move {
+                let parsed_params = {
+                    let mut params = params.sequence();
+                    params
+                        .next::<SubscriptionKind>()
+                        .and_then(|kind| Ok((kind, params.optional_next::<Params>()?)))
+                };
+                let (kind, kind_params) = match parsed_params {
+                    Ok(parsed_params) => parsed_params,
+                    Err(e) => {
+                        pending_subscription.reject(e).await;
+                        return Ok(());
+                    }
+                };
+
+                match (kind, kind_params) {
+                    (SubscriptionKind::NewHeads, None | Some(Params::None)) => {
+                        let rx = ethereum.subscriptions.subscribe_new_heads();
+                        pipe_from_stream(pending_subscription, rx, |_| true).await
+                    }
+                    (SubscriptionKind::Logs, None | Some(Params::None)) => {
+                        let rx = ethereum.subscriptions.subscribe_logs();
+                        pipe_from_stream(pending_subscription, rx, |_| true).await
+                    }
+                    (SubscriptionKind::Logs, Some(Params::Logs(filter))) => {
+                        let filter = FilteredParams::new(Some(*filter));
+                        let rx = ethereum.subscriptions.subscribe_logs();
+                        pipe_from_stream(pending_subscription, rx, move |log| {
+                            filter.filter_address(log) && filter.filter_topics(log)
+                        })
+                        .await
+                    }
+                    (SubscriptionKind::NewPendingTransactions, None | Some(Params::None)) => {
+                        let rx = ethereum.subscriptions.subscribe_pending_transactions();
+                        pipe_from_stream(pending_subscription, rx, |_| true).await
+                    }
+                    (SubscriptionKind::Syncing, _) => {
+                        pending_subscription
+                            .reject(to_jsonrpsee_error_object(
+                                "Syncing subscription is not supported",
+                                ETH_RPC_ERROR,
+                            ))
+                            .await;
+                        Ok(())
+                    }
+                    _ => {
+                        pending_subscription
+                            .reject(to_jsonrpsee_error_object(
+                                "Invalid subscription params",
+                                ETH_RPC_ERROR,
+                            ))
+                            .await;
+                        Ok(())
+                    }
+                }
+            },
+        )?;
+
+        rpc.register_subscription(
+            "sov_subscribeLogsPages",
+            "sov_logsPage",
+            "sov_unsubscribeLogsPages",
+            |params, pending_subscription, ethereum| async move {
+                let parsed_params = {
+                    let mut params = params.sequence();
+                    params
+                        .next::<Filter>()
+                        .and_then(|filter| Ok((filter, params.optional_next::<usize>()?)))
+                };
+                let (filter, page_size) = match parsed_params {
+                    Ok(parsed_params) => parsed_params,
+                    Err(e) => {
+                        pending_subscription.reject(e).await;
+                        return Ok(());
+                    }
+                };
+
+                pipe_logs_pages::<C>(
+                    pending_subscription,
+                    ethereum.storage.clone(),
+                    filter,
+                    ethereum.logs_config.page_size(page_size),
+                    ethereum.logs_config.max_block_range,
+                )
+                .await
+            },
+        )?;
+
         Ok(())
     }
 
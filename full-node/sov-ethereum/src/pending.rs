@@ -0,0 +1,61 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use reth_primitives::{Address, TransactionSignedEcRecovered, U64};
+use sov_evm::{Evm, RlpEvmTransaction};
+use sov_modules_api::WorkingSet;
+
+/// The maximum number of transactions tracked by [`PendingTransactions`].
+/// Once it is reached, the oldest transactions are dropped first.
+const MAX_PENDING_TRANSACTIONS: usize = 4096;
+
+struct PendingTransaction {
+    sender: Address,
+    nonce: U64,
+    tx: RlpEvmTransaction,
+}
+
+/// Transactions sent through this node which are not part of a sealed block yet.
+/// They make up the `pending` block.
+#[derive(Default)]
+pub(crate) struct PendingTransactions {
+    txs: Mutex<VecDeque<PendingTransaction>>,
+}
+
+impl PendingTransactions {
+    /// Starts tracking a transaction submitted to the sequencer.
+    pub(crate) fn add(&self, tx: RlpEvmTransaction) {
+        let Ok(recovered) = TransactionSignedEcRecovered::try_from(tx.clone()) else {
+            return;
+        };
+
+        let mut txs = self.txs.lock().unwrap();
+        if txs.len() == MAX_PENDING_TRANSACTIONS {
+            txs.pop_front();
+        }
+        txs.push_back(PendingTransaction {
+            sender: recovered.signer(),
+            nonce: U64::from(recovered.nonce()),
+            tx,
+        });
+    }
+
+    /// Returns the pending transactions, in the order they were received.
+    ///
+    /// Transactions whose nonce was already used by their sender, because they were included in
+    /// a block or replaced by another transaction, are not pending anymore and are dropped.
+    pub(crate) fn get<C: sov_modules_api::Context>(
+        &self,
+        evm: &Evm<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<RlpEvmTransaction> {
+        let mut txs = self.txs.lock().unwrap();
+        txs.retain(|pending| {
+            let account_nonce = evm
+                .get_transaction_count(pending.sender, None, working_set)
+                .unwrap_or_default();
+            pending.nonce >= account_nonce
+        });
+        txs.iter().map(|pending| pending.tx.clone()).collect()
+    }
+}
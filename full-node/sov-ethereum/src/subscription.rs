@@ -0,0 +1,201 @@
+//! Notifications for `eth_subscribe`, published whenever the rollup processes a slot.
+
+use jsonrpsee::core::SubscriptionResult;
+use jsonrpsee::{PendingSubscriptionSink, SubscriptionMessage};
+use reth_primitives::H256;
+use reth_rpc_types::{BlockTransactions, Filter, Header, Log};
+use serde::Serialize;
+use sov_evm::{Evm, LogsPage};
+use sov_modules_api::WorkingSet;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+/// The number of notifications buffered for every kind of subscription.
+/// Subscribers lagging further behind miss the oldest notifications.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Broadcasts new EVM heads, their logs and incoming transactions to the subscribers.
+pub(crate) struct SubscriptionManager {
+    new_heads: broadcast::Sender<Header>,
+    logs: broadcast::Sender<Log>,
+    pending_transactions: broadcast::Sender<H256>,
+}
+
+impl SubscriptionManager {
+    /// Creates the manager and spawns a task that publishes every EVM block produced since the
+    /// last notification, each time a slot is received from `slot_subscription`.
+    pub(crate) fn new<C: sov_modules_api::Context>(
+        storage: C::Storage,
+        mut slot_subscription: broadcast::Receiver<u64>,
+    ) -> Self {
+        let (new_heads, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (logs, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let (pending_transactions, _) = broadcast::channel(CHANNEL_CAPACITY);
+
+        let new_heads_tx = new_heads.clone();
+        let logs_tx = logs.clone();
+        tokio::spawn(async move {
+            let evm = Evm::<C>::default();
+            let mut last_published =
+                latest_block_number(&evm, &mut WorkingSet::<C>::new(storage.clone()));
+
+            loop {
+                match slot_subscription.recv().await {
+                    // Blocks are looked up by number, so skipped slots don't matter
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                    Err(RecvError::Closed) => break,
+                }
+
+                let mut working_set = WorkingSet::<C>::new_pinned(storage.clone());
+                let head = latest_block_number(&evm, &mut working_set);
+
+                for number in (last_published + 1)..=head {
+                    let block = match evm.get_block_by_number(
+                        Some(format!("0x{:x}", number)),
+                        Some(false),
+                        &mut working_set,
+                    ) {
+                        Ok(Some(block)) => block,
+                        _ => {
+                            warn!("EVM block {} is missing, skipping notifications", number);
+                            continue;
+                        }
+                    };
+
+                    // Sending only fails if there are no subscribers, which is fine
+                    let _ = new_heads_tx.send(block.header.clone());
+
+                    let tx_hashes = match &block.transactions {
+                        BlockTransactions::Hashes(hashes) => hashes.clone(),
+                        _ => Vec::new(),
+                    };
+                    for tx_hash in tx_hashes {
+                        let receipt = evm
+                            .get_transaction_receipt(tx_hash, &mut working_set)
+                            .unwrap_or(None);
+                        for log in receipt.into_iter().flat_map(|receipt| receipt.receipt.logs) {
+                            let _ = logs_tx.send(log);
+                        }
+                    }
+                }
+
+                last_published = last_published.max(head);
+            }
+        });
+
+        Self {
+            new_heads,
+            logs,
+            pending_transactions,
+        }
+    }
+
+    pub(crate) fn subscribe_new_heads(&self) -> broadcast::Receiver<Header> {
+        self.new_heads.subscribe()
+    }
+
+    pub(crate) fn subscribe_logs(&self) -> broadcast::Receiver<Log> {
+        self.logs.subscribe()
+    }
+
+    pub(crate) fn subscribe_pending_transactions(&self) -> broadcast::Receiver<H256> {
+        self.pending_transactions.subscribe()
+    }
+
+    /// Notifies the subscribers about a transaction accepted by the batch builder.
+    pub(crate) fn notify_pending_transaction(&self, tx_hash: H256) {
+        let _ = self.pending_transactions.send(tx_hash);
+    }
+}
+
+/// Accepts the subscription and forwards to it all the items of `rx` matching `filter`,
+/// until either the subscriber or the channel goes away.
+pub(crate) async fn pipe_from_stream<T: Serialize + Clone>(
+    pending_subscription: PendingSubscriptionSink,
+    mut rx: broadcast::Receiver<T>,
+    filter: impl Fn(&T) -> bool,
+) -> SubscriptionResult {
+    let subscription = pending_subscription.accept().await?;
+
+    loop {
+        tokio::select! {
+            _ = subscription.closed() => break Ok(()),
+            item = rx.recv() => match item {
+                Ok(item) => {
+                    if !filter(&item) {
+                        continue;
+                    }
+
+                    let msg = SubscriptionMessage::from_json(&item)?;
+                    // Sending only fails if the subscriber has canceled, so we can stop sending messages
+                    if subscription.send(msg).await.is_err() {
+                        break Ok(());
+                    }
+                }
+                // The subscriber misses the oldest notifications, but stays subscribed
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Subscriber lagged behind, skipped {} notifications", skipped);
+                }
+                Err(RecvError::Closed) => break Ok(()),
+            },
+        }
+    }
+}
+
+/// Sends the logs matching the filter one page per notification, for `sov_subscribeLogsPages`.
+/// The subscription ends after the last page, or is rejected if the first page can't be queried.
+pub(crate) async fn pipe_logs_pages<C: sov_modules_api::Context>(
+    pending_subscription: PendingSubscriptionSink,
+    storage: C::Storage,
+    filter: Filter,
+    page_size: usize,
+    max_block_range: u64,
+) -> SubscriptionResult {
+    let evm = Evm::<C>::default();
+    let logs_page = |cursor| {
+        evm.get_logs_page(
+            filter.clone(),
+            cursor,
+            page_size,
+            max_block_range,
+            &mut WorkingSet::<C>::new(storage.clone()),
+        )
+    };
+
+    let mut page: LogsPage = match logs_page(None) {
+        Ok(page) => page,
+        Err(e) => {
+            pending_subscription.reject(e).await;
+            return Ok(());
+        }
+    };
+    let subscription = pending_subscription.accept().await?;
+
+    loop {
+        let cursor = page.cursor;
+        let msg = SubscriptionMessage::from_json(&page)?;
+        if subscription.send(msg).await.is_err() {
+            break Ok(());
+        }
+        let Some(cursor) = cursor else {
+            break Ok(());
+        };
+        page = match logs_page(Some(cursor)) {
+            Ok(page) => page,
+            Err(e) => {
+                warn!("Failed to query the next page of logs: {}", e.message());
+                break Ok(());
+            }
+        };
+    }
+}
+
+pub(crate) fn latest_block_number<C: sov_modules_api::Context>(
+    evm: &Evm<C>,
+    working_set: &mut WorkingSet<C>,
+) -> u64 {
+    evm.block_number(working_set)
+        .map(|number| number.to::<u64>())
+        .unwrap_or_default()
+}
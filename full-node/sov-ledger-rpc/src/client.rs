@@ -5,11 +5,11 @@
 
 use jsonrpsee::proc_macros::rpc;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventIdentifier, Page, PageRequest, QueryMode, SlotIdentifier, TxIdentifier,
 };
 use sov_rollup_interface::stf::Event;
 
-use crate::HexHash;
+use crate::{HexBytes, HexHash};
 
 /// A [`jsonrpsee`] trait for interacting with the ledger JSON-RPC API.
 ///
@@ -53,6 +53,26 @@ where
         query_mode: QueryMode,
     ) -> RpcResult<Vec<Option<Tx>>>;
 
+    /// Gets a page of slots, selected by their numbers.
+    #[method(name = "getSlots")]
+    async fn get_slots_page(&self, request: PageRequest) -> RpcResult<Page<Slot>>;
+
+    /// Gets a page of batches, selected by their numbers.
+    #[method(name = "getBatches")]
+    async fn get_batches_page(&self, request: PageRequest) -> RpcResult<Page<Batch>>;
+
+    /// Gets a page of transactions, selected by their numbers.
+    #[method(name = "getTransactions")]
+    async fn get_transactions_page(&self, request: PageRequest) -> RpcResult<Page<Tx>>;
+
+    /// Gets a page of the transactions sent by an address, selected by their numbers.
+    #[method(name = "getTransactionsByAddress")]
+    async fn get_transactions_by_address(
+        &self,
+        address: HexBytes,
+        request: PageRequest,
+    ) -> RpcResult<Page<Tx>>;
+
     /// Gets a list of events by ID. The IDs need not be ordered.
     #[method(name = "getEvents")]
     async fn get_events(&self, event_ids: Vec<EventIdentifier>) -> RpcResult<Vec<Option<Event>>>;
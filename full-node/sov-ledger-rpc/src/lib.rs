@@ -10,3 +10,8 @@ pub mod client;
 /// `0x`. See [`sov_rollup_interface::rpc::utils::rpc_hex`].
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
 pub struct HexHash(#[serde(with = "sov_rollup_interface::rpc::utils::rpc_hex")] pub [u8; 32]);
+
+/// Bytes of any length, like an address, [`serde`]-encoded as a hex string optionally prefixed
+/// with `0x`. See [`sov_rollup_interface::rpc::utils::rpc_hex`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct HexBytes(#[serde(with = "sov_rollup_interface::rpc::utils::rpc_hex")] pub Vec<u8>);
@@ -6,10 +6,12 @@ use jsonrpsee::{RpcModule, SubscriptionMessage};
 use serde::de::DeserializeOwned;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
 use sov_rollup_interface::rpc::{
-    BatchIdentifier, EventIdentifier, LedgerRpcProvider, QueryMode, SlotIdentifier, TxIdentifier,
+    BatchIdentifier, EventFilter, EventIdentifier, EventResponse, LedgerRpcProvider, Page,
+    PageRequest, QueryMode, SlotIdentifier, TxIdentifier,
 };
+use sov_rollup_interface::stf::Event;
 
-use crate::HexHash;
+use crate::{HexBytes, HexHash};
 
 const LEDGER_RPC_ERROR: &str = "LEDGER_RPC_ERROR";
 
@@ -52,27 +54,57 @@ where
     })?;
 
     // Primary getters.
+    // Slots, batches and transactions can be either requested by id, or paginated using a [`PageRequest`].
     rpc.register_method("ledger_getSlots", move |params, ledger| {
-        let args: QueryArgs<Vec<SlotIdentifier>> = extract_query_args(params)?;
-        ledger
-            .get_slots::<B, Tx>(&args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+        match extract_query_args::<Vec<SlotIdentifier>>(params.clone()) {
+            Ok(args) => ledger
+                .get_slots::<B, Tx>(&args.0, args.1)
+                .map(ItemsResponse::ById),
+            Err(e) => ledger
+                .get_slots_page::<B, Tx>(&extract_page_request(params, e)?)
+                .map(ItemsResponse::Page),
+        }
+        .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
     rpc.register_method("ledger_getBatches", move |params, ledger| {
-        let args: QueryArgs<Vec<BatchIdentifier>> = extract_query_args(params)?;
-        ledger
-            .get_batches::<B, Tx>(&args.0, args.1)
-            .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+        match extract_query_args::<Vec<BatchIdentifier>>(params.clone()) {
+            Ok(args) => ledger
+                .get_batches::<B, Tx>(&args.0, args.1)
+                .map(ItemsResponse::ById),
+            Err(e) => ledger
+                .get_batches_page::<B, Tx>(&extract_page_request(params, e)?)
+                .map(ItemsResponse::Page),
+        }
+        .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
     rpc.register_method("ledger_getTransactions", move |params, ledger| {
-        let args: QueryArgs<Vec<TxIdentifier>> = extract_query_args(params)?;
+        match extract_query_args::<Vec<TxIdentifier>>(params.clone()) {
+            Ok(args) => ledger
+                .get_transactions::<Tx>(&args.0, args.1)
+                .map(ItemsResponse::ById),
+            Err(e) => ledger
+                .get_transactions_page::<Tx>(&extract_page_request(params, e)?)
+                .map(ItemsResponse::Page),
+        }
+        .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
+    })?;
+    rpc.register_method("ledger_getTransactionsByAddress", move |params, ledger| {
+        let args: AddressArgs = params.parse()?;
         ledger
-            .get_transactions::<Tx>(&args.0, args.1)
+            .get_transactions_by_address::<Tx>(&args.0 .0, &args.1)
             .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
     rpc.register_method("ledger_getEvents", move |params, db| {
+        // Events can be either requested by id, or searched for using an [`EventFilter`]
+        if let Ok(filter) = params.one::<EventFilter>() {
+            return db
+                .get_filtered_events(&filter)
+                .map(EventsResponse::Filtered)
+                .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR));
+        }
         let ids: Vec<EventIdentifier> = params.parse().or_else(|_| params.one())?;
         db.get_events(&ids)
+            .map(EventsResponse::ById)
             .map_err(|e| to_jsonrpsee_error_object(e, LEDGER_RPC_ERROR))
     })?;
 
@@ -181,9 +213,30 @@ where
     Ok(rpc)
 }
 
+/// The response to `ledger_getEvents`, which depends on how the events were requested.
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum EventsResponse {
+    ById(Vec<Option<Event>>),
+    Filtered(Vec<EventResponse>),
+}
+
+/// The response to `ledger_getSlots`, `ledger_getBatches` and `ledger_getTransactions`,
+/// which depends on how the items were requested.
+#[derive(serde::Serialize)]
+#[serde(untagged)]
+enum ItemsResponse<T> {
+    ById(Vec<Option<T>>),
+    Page(Page<T>),
+}
+
 #[derive(serde::Deserialize)]
 struct RangeArgs(u64, u64, #[serde(default)] QueryMode);
 
+/// The hex encoded address of a sender, and the page of its transactions to return.
+#[derive(serde::Deserialize)]
+struct AddressArgs(HexBytes, #[serde(default)] PageRequest);
+
 /// A structure containing serialized query arguments for RPC queries.
 #[derive(serde::Deserialize)]
 struct QueryArgs<T>(T, #[serde(default)] QueryMode);
@@ -199,3 +252,12 @@ fn extract_query_args<T: DeserializeOwned>(
     let ids: T = params.parse()?;
     Ok(QueryArgs(ids, Default::default()))
 }
+
+/// Extracts a [`PageRequest`] from an RPC query which doesn't contain a list of ids.
+/// If that fails too, the error of parsing the ids is returned, since it's the most common way to query.
+fn extract_page_request(
+    params: jsonrpsee::types::Params,
+    ids_error: ErrorObjectOwned,
+) -> Result<PageRequest, ErrorObjectOwned> {
+    params.one().map_err(|_| ids_error)
+}
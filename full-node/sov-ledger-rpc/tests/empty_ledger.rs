@@ -9,8 +9,8 @@ use sov_ledger_rpc::server::rpc_module;
 use sov_ledger_rpc::HexHash;
 use sov_modules_api::Event;
 use sov_rollup_interface::rpc::{
-    BatchResponse, EventIdentifier, QueryMode, SlotResponse, TxIdAndOffset, TxIdentifier,
-    TxResponse,
+    BatchResponse, EventIdentifier, PageRequest, QueryMode, SlotResponse, TxIdAndOffset,
+    TxIdentifier, TxResponse,
 };
 use tempfile::tempdir;
 
@@ -103,6 +103,21 @@ async fn getters_succeed() {
         .get_txs_range(0, 1, QueryMode::Compact)
         .await
         .unwrap();
+
+    let page = rpc_client
+        .get_slots_page(PageRequest::default())
+        .await
+        .unwrap();
+    assert!(page.items.is_empty());
+    assert_eq!(None, page.next_cursor);
+    rpc_client
+        .get_batches_page(PageRequest::default())
+        .await
+        .unwrap();
+    rpc_client
+        .get_transactions_page(PageRequest::default())
+        .await
+        .unwrap();
 }
 
 #[tokio::test]
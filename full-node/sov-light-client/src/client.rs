@@ -0,0 +1,146 @@
+//! The sources a [`LightClient`] syncs from: a full node, over its JSON-RPC API,
+//! or the DA layer directly.
+//!
+//! See [`FullNodeClient`] and [`sync_from_da`].
+
+use std::ops::Range;
+
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::rpc_params;
+use serde::Deserialize;
+use sov_modules_core::{Storage, StorageKey, StorageValue};
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::Zkvm;
+use tracing::{debug, warn};
+
+use crate::light_client::decode_hex;
+use crate::{LightClient, StateProofResponse};
+
+/// A proof returned by `prover_getProof`, see `sov_stf_runner::ProofResponse`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofResponse {
+    first_slot: u64,
+    last_slot: u64,
+    proof: String,
+}
+
+/// A client syncing a [`LightClient`] from a full node, and querying its state.
+///
+/// The proofs are fetched with `prover_getProof`, and the state with `lightClient_getStateProof`,
+/// which the full node has to serve. Nothing returned by the node is trusted: the proofs are
+/// verified by the light client.
+pub struct FullNodeClient {
+    client: HttpClient,
+    next_slot: u64,
+}
+
+impl FullNodeClient {
+    /// Creates a client of the full node with the given JSON-RPC url, which
+    /// syncs from the first slot of the rollup.
+    pub fn new(url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: HttpClientBuilder::default().build(url)?,
+            next_slot: 1,
+        })
+    }
+
+    /// Syncs from the given slot instead, for light clients created from a trusted
+    /// state root after the genesis.
+    pub fn starting_at(mut self, slot_number: u64) -> Self {
+        self.next_slot = slot_number;
+        self
+    }
+
+    /// Returns the number of the first slot which wasn't synced yet.
+    pub fn next_slot(&self) -> u64 {
+        self.next_slot
+    }
+
+    /// Fetches the proofs of the slots which weren't synced yet, in order, and applies them
+    /// to the light client, until the node has no proof of the next slot.
+    /// Returns the number of proofs applied.
+    pub async fn sync<Vm: Zkvm, Da: DaSpec, S: Storage>(
+        &mut self,
+        light_client: &mut LightClient<Vm, Da, S>,
+    ) -> anyhow::Result<usize> {
+        let mut applied = 0;
+        loop {
+            let response: Option<ProofResponse> = self
+                .client
+                .request("prover_getProof", rpc_params![self.next_slot])
+                .await?;
+            let Some(response) = response else {
+                return Ok(applied);
+            };
+            anyhow::ensure!(
+                response.first_slot == self.next_slot,
+                "The proof of slots {} to {} doesn't start at the next slot {}",
+                response.first_slot,
+                response.last_slot,
+                self.next_slot
+            );
+
+            let proof = decode_hex(&response.proof)?;
+            if response.first_slot == response.last_slot {
+                light_client.apply_slot_proof(&proof)?;
+            } else {
+                light_client.apply_aggregated_proof(&proof)?;
+            }
+            debug!(
+                "Synced the proof of slots {} to {}",
+                response.first_slot, response.last_slot
+            );
+            self.next_slot = response.last_slot + 1;
+            applied += 1;
+        }
+    }
+
+    /// Queries the value of the key in the latest state proven to the light client,
+    /// and checks its merkle proof.
+    pub async fn get_state<Vm: Zkvm, Da: DaSpec, S: Storage>(
+        &self,
+        light_client: &LightClient<Vm, Da, S>,
+        key: &StorageKey,
+    ) -> anyhow::Result<Option<StorageValue>> {
+        let state_root = hex::encode(light_client.latest_state_root().as_ref());
+        let response: StateProofResponse = self
+            .client
+            .request(
+                "lightClient_getStateProof",
+                rpc_params![hex::encode(key.as_ref()), state_root],
+            )
+            .await?;
+        light_client.verify_state_proof_response(key, &response)
+    }
+}
+
+/// Syncs the light client from the aggregated proofs submitted to the DA layer in the given
+/// range of heights, without going through a full node. Returns the number of proofs applied.
+///
+/// Anyone can submit proofs to the DA layer, so the ones which are invalid or don't extend
+/// the proven state of the light client are skipped.
+pub async fn sync_from_da<Vm: Zkvm, Da: DaService, S: Storage>(
+    da_service: &Da,
+    light_client: &mut LightClient<Vm, Da::Spec, S>,
+    heights: Range<u64>,
+) -> anyhow::Result<usize> {
+    let mut applied = 0;
+    for height in heights {
+        let proofs = da_service
+            .get_aggregated_proofs_at(height)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to fetch the proofs at height {}: {}", height, e)
+            })?;
+        for proof in proofs.into_iter().filter(|proof| !proof.is_empty()) {
+            match light_client.apply_aggregated_proof(&proof) {
+                Ok(_) => applied += 1,
+                Err(e) => warn!("Skipping an aggregated proof at height {}: {}", height, e),
+            }
+        }
+    }
+    Ok(applied)
+}
@@ -0,0 +1,33 @@
+#![deny(missing_docs)]
+#![forbid(unsafe_code)]
+#![doc = include_str!("../README.md")]
+
+mod light_client;
+
+#[cfg(feature = "client")]
+pub mod client;
+
+#[cfg(feature = "server")]
+pub mod server;
+
+pub use light_client::{LightClient, ProvenHeader};
+use serde::{Deserialize, Serialize};
+
+/// A merkle proof of a storage key, as returned by `lightClient_getStateProof`.
+///
+/// It proves that the storage `key` holds `value`, or is absent if `value` is `None`,
+/// in the state whose root is `state_root`. All the byte fields are hex encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateProofResponse {
+    /// The state version the proof is generated against.
+    pub version: u64,
+    /// The state root the proof is generated against.
+    pub state_root: String,
+    /// The proven storage key.
+    pub key: String,
+    /// The value of the key, if any, encoded as it is stored.
+    pub value: Option<String>,
+    /// The borsh encoded merkle proof of the key and value against the state root.
+    pub proof: String,
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use borsh::BorshDeserialize;
+use serde::{Deserialize, Serialize};
+use sov_modules_core::{Storage, StorageKey, StorageProof, StorageValue};
+use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::zk::Zkvm;
+use sov_stf_runner::verifier::{verify_aggregated_proof, verify_slot_proof};
+
+use crate::StateProofResponse;
+
+/// The header of a range of slots whose state transition was proven.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProvenHeader<Da: DaSpec, Root> {
+    /// The hash of the last DA slot of the range.
+    pub slot_hash: Da::SlotHash,
+    /// The state root of the rollup after the last slot of the range.
+    pub state_root: Root,
+    /// The validity condition of the slots of the range, which isn't checked by the proof.
+    /// It's up to the user of the light client to check it against the DA layer.
+    pub validity_condition: Da::ValidityCondition,
+}
+
+/// A light client following the proven state of a rollup.
+///
+/// It starts from a trusted genesis state root and only stores the headers of the proven
+/// state transitions, each of which has to start from the state the previous one ended in.
+/// The state of the rollup is never stored nor re-executed: the values of the state are
+/// queried along with merkle proofs, which are checked against the proven state roots.
+pub struct LightClient<Vm: Zkvm, Da: DaSpec, S: Storage> {
+    code_commitment: Vm::CodeCommitment,
+    aggregation_code_commitment: Option<Vm::CodeCommitment>,
+    genesis_state_root: S::Root,
+    headers: Vec<ProvenHeader<Da, S::Root>>,
+    verified_roots: HashSet<[u8; 32]>,
+}
+
+impl<Vm: Zkvm, Da: DaSpec, S: Storage> LightClient<Vm, Da, S> {
+    /// Creates a light client accepting the slot proofs of the rollup program with the given
+    /// code commitment, starting from the trusted genesis state root.
+    pub fn new(code_commitment: Vm::CodeCommitment, genesis_state_root: S::Root) -> Self {
+        let verified_roots = HashSet::from([genesis_state_root.clone().into()]);
+        Self {
+            code_commitment,
+            aggregation_code_commitment: None,
+            genesis_state_root,
+            headers: Vec::new(),
+            verified_roots,
+        }
+    }
+
+    /// Also accepts the aggregated proofs of the aggregation program with the given code
+    /// commitment, like the ones submitted to the DA layer, as long as they aggregate the slot
+    /// proofs of the rollup program.
+    pub fn with_aggregation_code_commitment(
+        mut self,
+        aggregation_code_commitment: Vm::CodeCommitment,
+    ) -> Self {
+        self.aggregation_code_commitment = Some(aggregation_code_commitment);
+        self
+    }
+
+    /// Returns the headers proven so far, in order.
+    pub fn headers(&self) -> &[ProvenHeader<Da, S::Root>] {
+        &self.headers
+    }
+
+    /// Returns the header of the last proven state transition, if any.
+    pub fn latest_header(&self) -> Option<&ProvenHeader<Da, S::Root>> {
+        self.headers.last()
+    }
+
+    /// Returns the latest proven state root, or the genesis state root if nothing was proven yet.
+    pub fn latest_state_root(&self) -> &S::Root {
+        self.latest_header()
+            .map(|header| &header.state_root)
+            .unwrap_or(&self.genesis_state_root)
+    }
+
+    /// Returns whether the state root is the genesis state root or a proven one.
+    pub fn is_verified_root(&self, state_root: &S::Root) -> bool {
+        self.verified_roots.contains(&state_root.clone().into())
+    }
+
+    /// Verifies the proof of a slot, as returned by `prover_getProof`, and appends its header.
+    pub fn apply_slot_proof(&mut self, proof: &[u8]) -> anyhow::Result<&ProvenHeader<Da, S::Root>> {
+        let transition = verify_slot_proof::<Vm, Da, S::Root>(proof, &self.code_commitment)?;
+        self.append_header(
+            transition.initial_state_root,
+            ProvenHeader {
+                slot_hash: transition.slot_hash,
+                state_root: transition.final_state_root,
+                validity_condition: transition.validity_condition,
+            },
+        )
+    }
+
+    /// Verifies an aggregated proof, as submitted to the DA layer, and appends its header.
+    pub fn apply_aggregated_proof(
+        &mut self,
+        proof: &[u8],
+    ) -> anyhow::Result<&ProvenHeader<Da, S::Root>> {
+        let code_commitment = self.aggregation_code_commitment.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("The light client doesn't know the aggregation program")
+        })?;
+        let transition = verify_aggregated_proof::<Vm, Da, S::Root>(
+            proof,
+            code_commitment,
+            &self.code_commitment,
+        )?;
+        self.append_header(
+            transition.initial_state_root,
+            ProvenHeader {
+                slot_hash: transition.final_slot_hash,
+                state_root: transition.final_state_root,
+                validity_condition: transition.validity_condition,
+            },
+        )
+    }
+
+    fn append_header(
+        &mut self,
+        initial_state_root: S::Root,
+        header: ProvenHeader<Da, S::Root>,
+    ) -> anyhow::Result<&ProvenHeader<Da, S::Root>> {
+        anyhow::ensure!(
+            &initial_state_root == self.latest_state_root(),
+            "The proven state transition of slot {:?} doesn't start from the latest proven state",
+            header.slot_hash
+        );
+        self.verified_roots.insert(header.state_root.clone().into());
+        self.headers.push(header);
+        Ok(self.headers.last().expect("The header was just appended"))
+    }
+
+    /// Checks the merkle proof of a storage key against a proven state root,
+    /// and returns the proven value of the key.
+    pub fn verify_state(
+        &self,
+        state_root: S::Root,
+        state_proof: StorageProof<S::Proof>,
+    ) -> anyhow::Result<Option<StorageValue>> {
+        anyhow::ensure!(
+            self.is_verified_root(&state_root),
+            "The state root {} wasn't proven",
+            hex::encode(state_root.as_ref())
+        );
+        let (_, value) = S::open_proof(state_root, state_proof)?;
+        Ok(value)
+    }
+
+    /// Checks a [`StateProofResponse`] of the given key, as returned by
+    /// `lightClient_getStateProof`, and returns the proven value of the key.
+    pub fn verify_state_proof_response(
+        &self,
+        key: &StorageKey,
+        response: &StateProofResponse,
+    ) -> anyhow::Result<Option<StorageValue>> {
+        anyhow::ensure!(
+            decode_hex(&response.key)? == key.as_ref(),
+            "The state proof isn't a proof of the requested key"
+        );
+        let state_root = S::Root::try_from_slice(&decode_hex(&response.state_root)?)?;
+        let value = response
+            .value
+            .as_deref()
+            .map(decode_hex)
+            .transpose()?
+            .map(StorageValue::from);
+        let proof = S::Proof::try_from_slice(&decode_hex(&response.proof)?)?;
+        self.verify_state(
+            state_root,
+            StorageProof {
+                key: key.clone(),
+                value,
+                proof,
+            },
+        )
+    }
+}
+
+pub(crate) fn decode_hex(data: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(hex::decode(data.trim_start_matches("0x"))?)
+}
@@ -0,0 +1,79 @@
+//! The JSON-RPC method served by the full nodes to the light clients.
+//!
+//! See [`get_state_proof_rpc`].
+
+use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE};
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use sov_modules_core::{NativeStorage, Storage, StorageKey, Version};
+
+use crate::light_client::decode_hex;
+use crate::StateProofResponse;
+
+/// Creates the `lightClient_getStateProof` RPC method, which returns the [`StateProofResponse`]
+/// of a hex encoded storage key.
+///
+/// The proof is generated against the state with the hex encoded root given as the optional
+/// second parameter, so that light clients can query the last state they have a proof of, or
+/// against the latest state if it's missing.
+pub fn get_state_proof_rpc<S>(storage: S) -> Result<RpcModule<S>, jsonrpsee::core::Error>
+where
+    S: NativeStorage + Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(storage);
+    rpc.register_method("lightClient_getStateProof", |params, storage| {
+        let storage = &storage.pin_latest_version();
+        let mut params = params.sequence();
+        let key: String = params.next()?;
+        let state_root: Option<String> = params.optional_next()?;
+        let invalid_params = |e: anyhow::Error| {
+            ErrorObjectOwned::owned(INVALID_PARAMS_CODE, e.to_string(), None::<()>)
+        };
+
+        let key = StorageKey::from(decode_hex(&key).map_err(invalid_params)?);
+        let version = match state_root {
+            Some(state_root) => {
+                let state_root = decode_hex(&state_root).map_err(invalid_params)?;
+                find_version(storage, &state_root).map_err(invalid_params)?
+            }
+            None => storage.latest_version(),
+        };
+        get_state_proof(storage, key, version)
+            .map_err(|e| ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>))
+    })?;
+    Ok(rpc)
+}
+
+/// Returns the merkle proof of the key against the state at the given version.
+pub fn get_state_proof<S: NativeStorage>(
+    storage: &S,
+    key: StorageKey,
+    version: Version,
+) -> anyhow::Result<StateProofResponse> {
+    let state_root = storage.get_root_hash(version)?;
+    let storage_proof = storage.get_with_proof_at(key, version)?;
+    Ok(StateProofResponse {
+        version,
+        state_root: hex::encode(state_root.as_ref()),
+        key: hex::encode(storage_proof.key.as_ref()),
+        value: storage_proof.value.map(|value| hex::encode(value.value())),
+        proof: hex::encode(borsh::to_vec(&storage_proof.proof)?),
+    })
+}
+
+/// Finds the latest retained version of the state with the given root.
+fn find_version<S: NativeStorage>(storage: &S, state_root: &[u8]) -> anyhow::Result<Version> {
+    (storage.oldest_retained_version()..=storage.latest_version())
+        .rev()
+        .find(|version| {
+            storage
+                .get_root_hash(*version)
+                .is_ok_and(|root| root.as_ref() == state_root)
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "The state with root {} isn't retained by the node",
+                hex::encode(state_root)
+            )
+        })
+}
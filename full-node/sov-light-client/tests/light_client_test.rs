@@ -0,0 +1,290 @@
+use std::net::SocketAddr;
+
+use jsonrpsee::{rpc_params, RpcModule};
+use sov_light_client::client::{sync_from_da, FullNodeClient};
+use sov_light_client::server::get_state_proof_rpc;
+use sov_light_client::{LightClient, StateProofResponse};
+use sov_mock_da::{MockAddress, MockDaService, MockDaSpec, MockHash, MockValidityCond};
+use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkvm};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{Spec, StateReaderAndWriter, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::zk::{AggregatedStateTransition, Proof, StateTransition};
+use sov_state::storage::{NativeStorage, Storage, StorageKey, StorageValue};
+
+type S = <DefaultContext as Spec>::Storage;
+type Root = <S as Storage>::Root;
+type Client = LightClient<MockZkvm<MockValidityCond>, MockDaSpec, S>;
+
+const CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([1; 32]);
+const AGGREGATION_CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([2; 32]);
+
+fn commit(storage: &S, values: &[(&str, &str)]) -> Root {
+    let mut working_set = WorkingSet::<DefaultContext>::new(storage.clone());
+    for (key, value) in values {
+        working_set.set(&StorageKey::from(*key), StorageValue::from(*value));
+    }
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    storage
+        .validate_and_commit(reads_writes, &witness)
+        .expect("Should be able to commit")
+}
+
+fn make_proof(code_commitment: MockCodeCommitment, output: &impl serde::Serialize) -> Vec<u8> {
+    let log = bincode::serialize(output).unwrap();
+    let proof = MockProof {
+        program_id: code_commitment,
+        is_valid: true,
+        log: &log,
+    };
+    borsh::to_vec(&Proof::Full(proof.encode_to_vec())).unwrap()
+}
+
+fn slot_proof(initial_state_root: Root, final_state_root: Root, slot: u8) -> Vec<u8> {
+    make_proof(
+        CODE_COMMITMENT,
+        &StateTransition::<MockDaSpec, Root> {
+            initial_state_root,
+            final_state_root,
+            slot_hash: MockHash::from([slot; 32]),
+            prev_slot_hash: MockHash::from([slot - 1; 32]),
+            validity_condition: MockValidityCond { is_valid: true },
+        },
+    )
+}
+
+async fn rpc_server(
+    storage: S,
+    slot_proof: Vec<u8>,
+) -> (jsonrpsee::server::ServerHandle, SocketAddr) {
+    let mut rpc_module = get_state_proof_rpc(storage).unwrap();
+    let mut prover_module = RpcModule::new(());
+    prover_module
+        .register_method("prover_getProof", move |params, _| {
+            let slot_number: u64 = params.one()?;
+            Ok::<_, jsonrpsee::types::ErrorObjectOwned>((slot_number == 1).then(|| {
+                serde_json::json!({
+                    "firstSlot": 1,
+                    "lastSlot": 1,
+                    "proof": format!("0x{}", hex::encode(&slot_proof)),
+                })
+            }))
+        })
+        .unwrap();
+    rpc_module.merge(prover_module).unwrap();
+
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+    (server.start(rpc_module), addr)
+}
+
+async fn get_state_proof(
+    rpc_module: &RpcModule<S>,
+    key: &StorageKey,
+    state_root: Option<Root>,
+) -> StateProofResponse {
+    let state_root = state_root.map(|root| hex::encode(root.as_ref()));
+    rpc_module
+        .call(
+            "lightClient_getStateProof",
+            rpc_params![hex::encode(key.as_ref()), state_root],
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn sync_from_full_node() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let genesis_root = commit(&storage, &[("key", "genesis")]);
+    let root = commit(&storage, &[("key", "slot 1"), ("other", "value")]);
+
+    let (_server, addr) = rpc_server(storage.clone(), slot_proof(genesis_root, root, 1)).await;
+    let mut client = FullNodeClient::new(&format!("http://{}", addr)).unwrap();
+    let mut light_client = Client::new(CODE_COMMITMENT, genesis_root);
+
+    assert_eq!(1, client.sync(&mut light_client).await.unwrap());
+    assert_eq!(0, client.sync(&mut light_client).await.unwrap());
+    assert_eq!(2, client.next_slot());
+    assert_eq!(&root, light_client.latest_state_root());
+    assert_eq!(
+        MockHash::from([1; 32]),
+        light_client.latest_header().unwrap().slot_hash
+    );
+
+    let value = client
+        .get_state(&light_client, &StorageKey::from("key"))
+        .await
+        .unwrap();
+    assert_eq!(Some(StorageValue::from("slot 1")), value);
+    let value = client
+        .get_state(&light_client, &StorageKey::from("missing"))
+        .await
+        .unwrap();
+    assert_eq!(None, value);
+}
+
+#[tokio::test]
+async fn verify_state_proofs() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let genesis_root = commit(&storage, &[("key", "genesis")]);
+    let root = commit(&storage, &[("key", "slot 1")]);
+    let rpc_module = get_state_proof_rpc(storage.clone()).unwrap();
+    let key = StorageKey::from("key");
+
+    // The proofs of the previous states are still served
+    let light_client = Client::new(CODE_COMMITMENT, genesis_root);
+    let response = get_state_proof(&rpc_module, &key, Some(genesis_root)).await;
+    assert_eq!(storage.latest_version() - 1, response.version);
+    assert_eq!(
+        Some(StorageValue::from("genesis")),
+        light_client
+            .verify_state_proof_response(&key, &response)
+            .unwrap()
+    );
+
+    // The latest state of the node isn't proven to the light client yet
+    let response = get_state_proof(&rpc_module, &key, None).await;
+    assert_eq!(storage.latest_version(), response.version);
+    let err = light_client
+        .verify_state_proof_response(&key, &response)
+        .unwrap_err();
+    assert!(err.to_string().contains("wasn't proven"));
+
+    let mut light_client = light_client;
+    light_client
+        .apply_slot_proof(&slot_proof(genesis_root, root, 1))
+        .unwrap();
+    assert_eq!(
+        Some(StorageValue::from("slot 1")),
+        light_client
+            .verify_state_proof_response(&key, &response)
+            .unwrap()
+    );
+
+    // A tampered value doesn't match the proof
+    let tampered = StateProofResponse {
+        value: Some(hex::encode("tampered")),
+        ..response.clone()
+    };
+    assert!(light_client
+        .verify_state_proof_response(&key, &tampered)
+        .is_err());
+    assert!(light_client
+        .verify_state_proof_response(&StorageKey::from("other"), &response)
+        .is_err());
+}
+
+#[test]
+fn reject_disconnected_proofs() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let genesis_root = commit(&storage, &[("key", "genesis")]);
+    let root = commit(&storage, &[("key", "slot 1")]);
+
+    let mut light_client = Client::new(CODE_COMMITMENT, genesis_root);
+    let err = light_client
+        .apply_slot_proof(&slot_proof(root, genesis_root, 1))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("doesn't start from the latest proven state"));
+
+    // Proofs of another program are rejected
+    let proof = make_proof(
+        AGGREGATION_CODE_COMMITMENT,
+        &StateTransition::<MockDaSpec, Root> {
+            initial_state_root: genesis_root,
+            final_state_root: root,
+            slot_hash: MockHash::from([1; 32]),
+            prev_slot_hash: MockHash::from([0; 32]),
+            validity_condition: MockValidityCond { is_valid: true },
+        },
+    );
+    assert!(light_client.apply_slot_proof(&proof).is_err());
+    assert!(light_client.headers().is_empty());
+}
+
+#[tokio::test]
+async fn sync_aggregated_proofs_from_da() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let genesis_root = commit(&storage, &[("key", "genesis")]);
+    let first_root = commit(&storage, &[("key", "slot 1")]);
+    let root = commit(&storage, &[("key", "slot 2")]);
+
+    let aggregated_proof =
+        |initial_state_root, final_state_root, code_commitment, slot_code_commitment| {
+            make_proof(
+                code_commitment,
+                &AggregatedStateTransition::<MockDaSpec, Root, MockCodeCommitment> {
+                    slot_code_commitment,
+                    initial_state_root,
+                    final_state_root,
+                    initial_slot_hash: MockHash::from([1; 32]),
+                    final_slot_hash: MockHash::from([2; 32]),
+                    validity_condition: MockValidityCond { is_valid: true },
+                },
+            )
+        };
+    let da_service = MockDaService::new(MockAddress::new([7; 32]));
+    // An invalid proof, a valid one aggregating the proofs of another program,
+    // and a valid one which doesn't extend the genesis state
+    for proof in [
+        aggregated_proof(genesis_root, root, CODE_COMMITMENT, CODE_COMMITMENT),
+        aggregated_proof(
+            genesis_root,
+            root,
+            AGGREGATION_CODE_COMMITMENT,
+            AGGREGATION_CODE_COMMITMENT,
+        ),
+        aggregated_proof(
+            first_root,
+            root,
+            AGGREGATION_CODE_COMMITMENT,
+            CODE_COMMITMENT,
+        ),
+        aggregated_proof(
+            genesis_root,
+            root,
+            AGGREGATION_CODE_COMMITMENT,
+            CODE_COMMITMENT,
+        ),
+    ] {
+        da_service.send_aggregated_zk_proof(&proof).await.unwrap();
+    }
+
+    let mut light_client = Client::new(CODE_COMMITMENT, genesis_root);
+    let err = light_client
+        .apply_aggregated_proof(&aggregated_proof(
+            genesis_root,
+            root,
+            AGGREGATION_CODE_COMMITMENT,
+            CODE_COMMITMENT,
+        ))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("doesn't know the aggregation program"));
+
+    let mut light_client =
+        light_client.with_aggregation_code_commitment(AGGREGATION_CODE_COMMITMENT);
+    assert_eq!(
+        1,
+        sync_from_da(&da_service, &mut light_client, 1..5)
+            .await
+            .unwrap()
+    );
+    assert_eq!(&root, light_client.latest_state_root());
+    assert_eq!(
+        MockHash::from([2; 32]),
+        light_client.latest_header().unwrap().slot_hash
+    );
+    assert!(!light_client.is_verified_root(&first_root));
+}
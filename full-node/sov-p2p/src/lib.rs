@@ -0,0 +1,298 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::Context as _;
+use borsh::{BorshDeserialize, BorshSerialize};
+use futures::StreamExt;
+use libp2p::gossipsub::{self, IdentTopic, MessageAcceptance, MessageAuthenticity};
+use libp2p::swarm::SwarmEvent;
+use libp2p::{Multiaddr, Swarm};
+use serde::Deserialize;
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use tokio::sync::mpsc;
+
+/// The configuration of the gossip of soft batches.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct P2pConfig {
+    /// The multiaddress the node listens on for its peers, like `/ip4/0.0.0.0/tcp/9100`.
+    pub listen_address: String,
+    /// The multiaddresses of the peers the node connects to when it starts.
+    #[serde(default)]
+    pub bootstrap_peers: Vec<String>,
+    /// The gossipsub topic of the soft batches. Rollups sharing peers need different topics.
+    #[serde(default = "default_topic")]
+    pub topic: String,
+    /// The maximum size of a gossiped soft batch, in bytes. Larger ones are dropped.
+    #[serde(default = "default_max_message_size")]
+    pub max_message_size: usize,
+}
+
+fn default_topic() -> String {
+    "sov-soft-batches".to_string()
+}
+
+fn default_max_message_size() -> usize {
+    4 * 1024 * 1024
+}
+
+/// A soft batch, with the number the sequencer gave it, as it's gossiped between peers.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct GossipedSoftBatch {
+    /// The number of the soft batch.
+    pub number: u64,
+    /// The batch signed by the sequencer.
+    pub batch: SignedSoftConfirmationBatch,
+}
+
+/// Decides whether a received soft batch is valid. Valid batches are delivered to the node and
+/// relayed to its peers, while the peers sending invalid ones are penalized.
+pub type SoftBatchValidator = Box<dyn Fn(&GossipedSoftBatch) -> bool + Send + Sync>;
+
+/// The handle of the gossip of soft batches, running on a background task until it's dropped.
+pub struct SoftBatchGossip {
+    published: mpsc::UnboundedSender<GossipedSoftBatch>,
+    listen_addresses: Arc<RwLock<Vec<Multiaddr>>>,
+}
+
+impl SoftBatchGossip {
+    /// Starts listening for peers and connects to the bootstrap peers of the config.
+    /// Returns the handle of the gossip, with the valid soft batches received from the peers.
+    ///
+    /// It has to be called from a Tokio runtime, on which the gossip runs.
+    pub fn start(
+        config: &P2pConfig,
+        validator: SoftBatchValidator,
+    ) -> anyhow::Result<(Self, mpsc::UnboundedReceiver<GossipedSoftBatch>)> {
+        let max_message_size = config.max_message_size;
+        let mut swarm = libp2p::SwarmBuilder::with_new_identity()
+            .with_tokio()
+            .with_tcp(
+                libp2p::tcp::Config::default(),
+                libp2p::noise::Config::new,
+                libp2p::yamux::Config::default,
+            )?
+            .with_behaviour(
+                |key| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+                    let gossipsub_config = gossipsub::ConfigBuilder::default()
+                        .max_transmit_size(max_message_size)
+                        .validate_messages()
+                        .build()?;
+                    Ok(gossipsub::Behaviour::new(
+                        MessageAuthenticity::Signed(key.clone()),
+                        gossipsub_config,
+                    )?)
+                },
+            )?
+            .with_swarm_config(|swarm_config| {
+                swarm_config.with_idle_connection_timeout(Duration::from_secs(60))
+            })
+            .build();
+
+        let topic = IdentTopic::new(&config.topic);
+        swarm.behaviour_mut().subscribe(&topic)?;
+        let listen_address: Multiaddr = config
+            .listen_address
+            .parse()
+            .with_context(|| format!("Invalid p2p listen address {}", config.listen_address))?;
+        swarm.listen_on(listen_address)?;
+        for peer in &config.bootstrap_peers {
+            let address: Multiaddr = peer
+                .parse()
+                .with_context(|| format!("Invalid p2p bootstrap peer {}", peer))?;
+            swarm.dial(address)?;
+        }
+
+        let (published, to_publish) = mpsc::unbounded_channel();
+        let (received, receiver) = mpsc::unbounded_channel();
+        let listen_addresses = Arc::new(RwLock::new(Vec::new()));
+        tokio::spawn(run_gossip(
+            swarm,
+            topic,
+            validator,
+            to_publish,
+            received,
+            listen_addresses.clone(),
+        ));
+
+        Ok((
+            Self {
+                published,
+                listen_addresses,
+            },
+            receiver,
+        ))
+    }
+
+    /// Gossips the soft batch to the peers.
+    pub fn publish(&self, batch: GossipedSoftBatch) -> anyhow::Result<()> {
+        self.published
+            .send(batch)
+            .map_err(|_| anyhow::anyhow!("The soft batch gossip has stopped"))
+    }
+
+    /// The addresses the node is listening on for its peers, once they are bound.
+    pub fn listen_addresses(&self) -> Vec<Multiaddr> {
+        self.listen_addresses
+            .read()
+            .expect("Listen addresses lock is poisoned")
+            .clone()
+    }
+}
+
+async fn run_gossip(
+    mut swarm: Swarm<gossipsub::Behaviour>,
+    topic: IdentTopic,
+    validator: SoftBatchValidator,
+    mut to_publish: mpsc::UnboundedReceiver<GossipedSoftBatch>,
+    received: mpsc::UnboundedSender<GossipedSoftBatch>,
+    listen_addresses: Arc<RwLock<Vec<Multiaddr>>>,
+) {
+    loop {
+        tokio::select! {
+            batch = to_publish.recv() => {
+                // The handle was dropped
+                let Some(batch) = batch else {
+                    return;
+                };
+                let published = borsh::to_vec(&batch)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|message| {
+                        swarm.behaviour_mut().publish(topic.clone(), message)?;
+                        Ok(())
+                    });
+                if let Err(e) = published {
+                    tracing::warn!("Failed to gossip soft batch #{}: {}", batch.number, e);
+                }
+            }
+            event = swarm.select_next_some() => match event {
+                SwarmEvent::Behaviour(gossipsub::Event::Message {
+                    propagation_source,
+                    message_id,
+                    message,
+                }) => {
+                    let acceptance = match GossipedSoftBatch::try_from_slice(&message.data) {
+                        Ok(batch) if validator(&batch) => {
+                            tracing::debug!(
+                                "Received soft batch #{} from peer {}",
+                                batch.number,
+                                propagation_source
+                            );
+                            // The receiver is only dropped when the node stops
+                            let _ = received.send(batch);
+                            MessageAcceptance::Accept
+                        }
+                        _ => {
+                            tracing::warn!("Peer {} gossiped an invalid soft batch", propagation_source);
+                            MessageAcceptance::Reject
+                        }
+                    };
+                    // It only fails if the message isn't in the cache anymore
+                    let _ = swarm.behaviour_mut().report_message_validation_result(
+                        &message_id,
+                        &propagation_source,
+                        acceptance,
+                    );
+                }
+                SwarmEvent::NewListenAddr { address, .. } => {
+                    tracing::info!("Listening for soft batch gossip on {}", address);
+                    listen_addresses
+                        .write()
+                        .expect("Listen addresses lock is poisoned")
+                        .push(address);
+                }
+                SwarmEvent::ConnectionEstablished { peer_id, .. } => {
+                    tracing::debug!("Connected to peer {}", peer_id);
+                }
+                SwarmEvent::OutgoingConnectionError { peer_id, error, .. } => {
+                    tracing::warn!("Failed to connect to peer {:?}: {}", peer_id, error);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_rollup_interface::soft_confirmation::UnsignedSoftConfirmationBatch;
+
+    use super::*;
+
+    fn config(bootstrap_peers: Vec<String>) -> P2pConfig {
+        P2pConfig {
+            listen_address: "/ip4/127.0.0.1/tcp/0".to_string(),
+            bootstrap_peers,
+            topic: default_topic(),
+            max_message_size: default_max_message_size(),
+        }
+    }
+
+    async fn listen_address(gossip: &SoftBatchGossip) -> String {
+        loop {
+            if let Some(address) = gossip.listen_addresses().first() {
+                return address.to_string();
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    fn soft_batch(number: u64, pub_key: Vec<u8>) -> GossipedSoftBatch {
+        GossipedSoftBatch {
+            number,
+            batch: SignedSoftConfirmationBatch::new(
+                UnsignedSoftConfirmationBatch {
+                    da_slot_height: 1,
+                    da_slot_hash: [1; 32],
+                    txs: vec![vec![number as u8]],
+                },
+                [number as u8; 32],
+                vec![],
+                pub_key,
+            ),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_soft_batches_are_relayed() {
+        let sequencer_key = vec![7; 32];
+        let is_from_sequencer = |key: Vec<u8>| -> SoftBatchValidator {
+            Box::new(move |batch| batch.batch.pub_key == key)
+        };
+
+        let (sequencer, _) = SoftBatchGossip::start(&config(vec![]), Box::new(|_| false)).unwrap();
+        let sequencer_address = listen_address(&sequencer).await;
+        // The relay is the only peer of the full node
+        let (relay, mut relay_received) = SoftBatchGossip::start(
+            &config(vec![sequencer_address]),
+            is_from_sequencer(sequencer_key.clone()),
+        )
+        .unwrap();
+        let relay_address = listen_address(&relay).await;
+        let (_full_node, mut received) = SoftBatchGossip::start(
+            &config(vec![relay_address]),
+            is_from_sequencer(sequencer_key.clone()),
+        )
+        .unwrap();
+
+        // The batches published before the mesh is formed are lost, like the forged one
+        let expected = soft_batch(1, sequencer_key);
+        let relayed = tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                sequencer.publish(soft_batch(0, vec![0; 32])).unwrap();
+                sequencer.publish(expected.clone()).unwrap();
+                tokio::select! {
+                    batch = received.recv() => return batch,
+                    _ = tokio::time::sleep(Duration::from_millis(500)) => {}
+                }
+            }
+        })
+        .await
+        .expect("The soft batch wasn't relayed in time");
+
+        assert_eq!(Some(expected.clone()), relayed);
+        assert_eq!(Some(expected), relay_received.recv().await);
+    }
+}
@@ -4,6 +4,8 @@ use std::marker::PhantomData;
 use std::sync::{Arc, RwLock};
 
 use sov_db::native_db::NativeDB;
+use sov_db::pruning::{PruningHandle, PruningMode, StatePruner};
+use sov_db::recovery::{latest_state_version, rollback_accessory_state, rollback_state};
 use sov_db::state_db::StateDB;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
@@ -14,6 +16,9 @@ pub use crate::snapshot_manager::SnapshotManager;
 
 mod snapshot_manager;
 
+/// The snapshot id of the read-only storages, which is never given to the snapshots of the blocks.
+const READ_ONLY_SNAPSHOT_ID: SnapshotId = 0;
+
 /// Implementation of [`HierarchicalStorageManager`] that handles relation between snapshots
 /// And reorgs on Data Availability layer.
 pub struct ProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
@@ -36,6 +41,9 @@ pub struct ProverStorageManager<Da: DaSpec, S: MerkleProofSpec> {
     state_snapshot_manager: Arc<RwLock<SnapshotManager>>,
     accessory_snapshot_manager: Arc<RwLock<SnapshotManager>>,
 
+    // Garbage-collects the finalized state which is not needed anymore
+    state_pruner: StatePruner,
+
     phantom_mp_spec: PhantomData<S>,
 }
 
@@ -49,6 +57,7 @@ where
         let state_snapshot_manager = SnapshotManager::new(state_db, snapshot_id_to_parent.clone());
         let accessory_snapshot_manager =
             SnapshotManager::new(native_db, snapshot_id_to_parent.clone());
+        let state_pruner = StatePruner::new(state_snapshot_manager.db(), PruningMode::Archive);
 
         Self {
             chain_forks: Default::default(),
@@ -59,10 +68,24 @@ where
             snapshot_id_to_parent,
             state_snapshot_manager: Arc::new(RwLock::new(state_snapshot_manager)),
             accessory_snapshot_manager: Arc::new(RwLock::new(accessory_snapshot_manager)),
+            state_pruner,
             phantom_mp_spec: Default::default(),
         }
     }
 
+    /// Sets how much of the finalized state history is kept. Defaults to [`PruningMode::Archive`].
+    pub fn with_pruning(mut self, mode: PruningMode) -> Self {
+        let db = self.state_snapshot_manager.read().unwrap().db();
+        self.state_pruner = StatePruner::new(db, mode);
+        self
+    }
+
+    /// Returns a handle which prevents the state versions still needed,
+    /// for example by a prover lagging behind, from being pruned.
+    pub fn pruning_handle(&self) -> PruningHandle {
+        self.state_pruner.handle()
+    }
+
     /// Create new [`ProverStorageManager`] from state config
     pub fn new(config: sov_state::config::Config) -> anyhow::Result<Self> {
         let path = config.path;
@@ -138,6 +161,7 @@ where
         // Return error here, as underlying database can return error
         state_manager.commit_snapshot(snapshot_id)?;
         native_manager.commit_snapshot(snapshot_id)?;
+        self.state_pruner.notify_finalized();
 
         // All siblings of current snapshot
         let mut to_discard: Vec<_> = self
@@ -259,6 +283,25 @@ where
         Ok(ProverStorage::with_db_handles(state_db, native_db))
     }
 
+    fn create_read_only_storage(&self) -> anyhow::Result<Self::NativeStorage> {
+        let state_snapshot_manager = self.state_snapshot_manager.read().unwrap().read_only();
+        let state_db_snapshot = DbSnapshot::new(
+            READ_ONLY_SNAPSHOT_ID,
+            ReadOnlyLock::new(Arc::new(RwLock::new(state_snapshot_manager))),
+        );
+        let state_db = StateDB::with_db_snapshot(state_db_snapshot)?;
+        state_db.max_out_next_version();
+
+        let accessory_snapshot_manager =
+            self.accessory_snapshot_manager.read().unwrap().read_only();
+        let native_db_snapshot = DbSnapshot::new(
+            READ_ONLY_SNAPSHOT_ID,
+            ReadOnlyLock::new(Arc::new(RwLock::new(accessory_snapshot_manager))),
+        );
+        let native_db = NativeDB::with_db_snapshot(native_db_snapshot)?;
+        Ok(ProverStorage::with_db_handles(state_db, native_db))
+    }
+
     fn save_change_set(
         &mut self,
         block_header: &Da::BlockHeader,
@@ -324,6 +367,41 @@ where
         let prev_block_hash = block_header.prev_hash();
         self.finalize_by_hash_pair(prev_block_hash, current_block_hash)
     }
+
+    fn finalized_version(&self) -> anyhow::Result<u64> {
+        let state_db = self.state_snapshot_manager.read().unwrap().db();
+        latest_state_version(&state_db)
+    }
+
+    fn rollback_finalized(&mut self, version: u64) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.block_hash_to_snapshot_id.is_empty(),
+            "Cannot roll back the finalized storage while there are non-finalized snapshots"
+        );
+        tracing::info!("Rolling back the finalized storage to version {}", version);
+        let state_db = self.state_snapshot_manager.read().unwrap().db();
+        let native_db = self.accessory_snapshot_manager.read().unwrap().db();
+        rollback_state(&state_db, version)?;
+        rollback_accessory_state(&native_db, version)
+    }
+
+    fn discard_non_finalized(&mut self) -> anyhow::Result<()> {
+        tracing::info!(
+            "Discarding {} non-finalized snapshots",
+            self.block_hash_to_snapshot_id.len()
+        );
+        let mut state_manager = self.state_snapshot_manager.write().unwrap();
+        let mut native_manager = self.accessory_snapshot_manager.write().unwrap();
+        let mut snapshot_id_to_parent = self.snapshot_id_to_parent.write().unwrap();
+        for (_, snapshot_id) in self.block_hash_to_snapshot_id.drain() {
+            snapshot_id_to_parent.remove(&snapshot_id);
+            state_manager.discard_snapshot(&snapshot_id);
+            native_manager.discard_snapshot(&snapshot_id);
+        }
+        self.chain_forks.clear();
+        self.blocks_to_parent.clear();
+        Ok(())
+    }
 }
 
 /// Creates orphan [`ProverStorage`] which just points directly to the underlying database for previous data
@@ -347,7 +425,7 @@ pub fn new_orphan_storage<S: MerkleProofSpec>(
 mod tests {
     use sov_mock_da::{MockBlockHeader, MockHash};
     use sov_rollup_interface::da::Time;
-    use sov_state::storage::{CacheKey, CacheValue};
+    use sov_state::storage::{CacheKey, CacheValue, NativeStorage, StorageKey, StorageValue};
     use sov_state::{ArrayWitness, OrderedReadsAndWrites, Storage};
 
     use super::*;
@@ -740,6 +818,191 @@ mod tests {
         assert!(storage_manager.is_empty());
     }
 
+    #[test]
+    fn prune_finalized_state() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+
+        let block_from_i = |i: u8| MockBlockHeader {
+            prev_hash: MockHash::from([i; 32]),
+            hash: MockHash::from([i + 1; 32]),
+            height: i as u64 + 1,
+            time: Time::now(),
+        };
+
+        let witness = ArrayWitness::default();
+        for i in 0u8..4 {
+            let block = block_from_i(i);
+            let storage = storage_manager.create_storage_on(&block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(1, i as u64));
+            state_operations
+                .ordered_writes
+                .push(write_op(10 + i as u64, i as u64));
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &OrderedReadsAndWrites::default());
+            storage_manager.save_change_set(&block, storage).unwrap();
+            storage_manager.finalize(&block).unwrap();
+        }
+
+        assert_eq!(
+            0,
+            storage_manager
+                .create_finalized_storage()
+                .unwrap()
+                .oldest_retained_version()
+        );
+        let db = storage_manager.state_snapshot_manager.read().unwrap().db();
+        sov_db::pruning::prune_state(&db, 4).unwrap();
+
+        let storage = storage_manager.create_finalized_storage().unwrap();
+        assert_eq!(4, storage.oldest_retained_version());
+        assert!(storage.check_archival_version(3).is_err());
+        assert!(storage.check_archival_version(4).is_ok());
+        assert!(storage.get_root_hash(3).is_err());
+        assert!(storage.get_root_hash(4).is_ok());
+
+        // Only the values of the retained version are left
+        let key = StorageKey::from(key_from(1));
+        assert_eq!(
+            Some(StorageValue::from(value_from(3))),
+            storage.get(&key, None, &witness)
+        );
+        assert_eq!(None, storage.get(&key, Some(3), &witness));
+        // Values which are not overwritten are kept
+        assert_eq!(
+            Some(StorageValue::from(value_from(0))),
+            storage.get(&StorageKey::from(key_from(10)), None, &witness)
+        );
+    }
+
+    #[test]
+    fn pruning_respects_retained_versions() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db)
+                .with_pruning(PruningMode::Minimal);
+        let pruning_handle = storage_manager.pruning_handle();
+        pruning_handle.retain_from("prover", 2);
+        pruning_handle.retain_from("reorgs", 3);
+
+        let block_from_i = |i: u8| MockBlockHeader {
+            prev_hash: MockHash::from([i; 32]),
+            hash: MockHash::from([i + 1; 32]),
+            height: i as u64 + 1,
+            time: Time::now(),
+        };
+
+        let witness = ArrayWitness::default();
+        let mut finalize_block = |i: u8| {
+            let block = block_from_i(i);
+            let storage = storage_manager.create_storage_on(&block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(1, i as u64));
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &OrderedReadsAndWrites::default());
+            storage_manager.save_change_set(&block, storage).unwrap();
+            storage_manager.finalize(&block).unwrap();
+            storage_manager.create_finalized_storage().unwrap()
+        };
+        // Pruning runs in the background
+        let wait_for_pruning = |storage: &ProverStorage<S, SnapshotManager>, version| {
+            for _ in 0..500 {
+                if storage.oldest_retained_version() == version {
+                    return;
+                }
+                std::thread::sleep(std::time::Duration::from_millis(10));
+            }
+            panic!("The state wasn't pruned up to version {}", version);
+        };
+
+        for i in 0u8..4 {
+            finalize_block(i);
+        }
+        // The oldest version retained by any holder is kept
+        let storage = finalize_block(4);
+        wait_for_pruning(&storage, 2);
+        assert!(storage.get_root_hash(1).is_err());
+        assert!(storage.get_root_hash(2).is_ok());
+
+        // Releasing one holder doesn't release the versions retained by the others
+        pruning_handle.release("prover");
+        let storage = finalize_block(5);
+        wait_for_pruning(&storage, 3);
+        assert!(storage.get_root_hash(2).is_err());
+        assert!(storage.get_root_hash(3).is_ok());
+
+        pruning_handle.release("reorgs");
+        let storage = finalize_block(6);
+        wait_for_pruning(&storage, 7);
+    }
+
+    #[test]
+    fn read_only_storage_pins_versions() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        let read_only_storage = storage_manager.create_read_only_storage().unwrap();
+
+        let block_from_i = |i: u8| MockBlockHeader {
+            prev_hash: MockHash::from([i; 32]),
+            hash: MockHash::from([i + 1; 32]),
+            height: i as u64 + 1,
+            time: Time::now(),
+        };
+
+        let witness = ArrayWitness::default();
+        let key = StorageKey::from(key_from(1));
+        let mut pinned_storages = Vec::new();
+        for i in 0u8..3 {
+            let block = block_from_i(i);
+            let storage = storage_manager.create_storage_on(&block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(1, i as u64));
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &OrderedReadsAndWrites::default());
+            storage_manager.save_change_set(&block, storage).unwrap();
+
+            // The snapshots which aren't finalized yet aren't seen
+            assert_eq!(
+                pinned_storages.len() as u64,
+                read_only_storage.pin_latest_version().latest_version()
+            );
+            storage_manager.finalize(&block).unwrap();
+            pinned_storages.push(read_only_storage.pin_latest_version());
+        }
+
+        // The read-only storage follows the finalized state...
+        assert_eq!(
+            Some(StorageValue::from(value_from(2))),
+            read_only_storage.get(&key, None, &witness)
+        );
+        // ...while the pinned storages keep reading the version they were pinned to
+        for (i, storage) in pinned_storages.iter().enumerate() {
+            assert_eq!(i as u64 + 1, storage.latest_version());
+            assert_eq!(
+                Some(StorageValue::from(value_from(i as u64))),
+                storage.get(&key, None, &witness)
+            );
+        }
+    }
+
     #[test]
     fn parallel_forks() {
         let tmpdir = tempfile::tempdir().unwrap();
@@ -866,6 +1129,95 @@ mod tests {
         assert!(storage_manager.is_empty());
     }
 
+    #[test]
+    fn rollback_finalized_block() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+        assert_eq!(0, storage_manager.finalized_version().unwrap());
+
+        // Blocks A -> B, both finalized
+        let block_a = MockBlockHeader::from_height(1);
+        let block_b = MockBlockHeader::from_height(2);
+        let witness = ArrayWitness::default();
+        for (block, (key, value), (native_key, native_value)) in
+            [(&block_a, (1, 2), (30, 40)), (&block_b, (3, 4), (50, 60))]
+        {
+            let storage = storage_manager.create_storage_on(block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(key, value));
+            let mut native_operations = OrderedReadsAndWrites::default();
+            native_operations
+                .ordered_writes
+                .push(write_op(native_key, native_value));
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &native_operations);
+            storage_manager.save_change_set(block, storage).unwrap();
+            storage_manager.finalize(block).unwrap();
+        }
+        assert_eq!(2, storage_manager.finalized_version().unwrap());
+
+        // Roll back B
+        storage_manager.rollback_finalized(1).unwrap();
+        assert_eq!(1, storage_manager.finalized_version().unwrap());
+
+        let storage = storage_manager.create_finalized_storage().unwrap();
+        assert_eq!(
+            Some(value_from(2).into()),
+            storage.get(&key_from(1).into(), None, &witness)
+        );
+        assert_eq!(None, storage.get(&key_from(3).into(), None, &witness));
+        assert_eq!(
+            Some(value_from(40).into()),
+            storage.get_accessory(&key_from(30).into(), None)
+        );
+        assert_eq!(None, storage.get_accessory(&key_from(50).into(), None));
+    }
+
+    #[test]
+    fn discard_non_finalized_blocks() {
+        let tmpdir = tempfile::tempdir().unwrap();
+
+        let (state_db, native_db) = build_dbs(tmpdir.path());
+
+        let mut storage_manager =
+            ProverStorageManager::<Da, S>::with_db_handles(state_db, native_db);
+
+        // Blocks A -> B, only A is finalized
+        let block_a = MockBlockHeader::from_height(1);
+        let block_b = MockBlockHeader::from_height(2);
+        let witness = ArrayWitness::default();
+        for (block, (key, value)) in [(&block_a, (1, 2)), (&block_b, (3, 4))] {
+            let storage = storage_manager.create_storage_on(block).unwrap();
+            let mut state_operations = OrderedReadsAndWrites::default();
+            state_operations.ordered_writes.push(write_op(key, value));
+            let (_, state_update) = storage
+                .compute_state_update(state_operations, &witness)
+                .unwrap();
+            storage.commit(&state_update, &OrderedReadsAndWrites::default());
+            storage_manager.save_change_set(block, storage).unwrap();
+        }
+        storage_manager.finalize(&block_a).unwrap();
+
+        storage_manager.discard_non_finalized().unwrap();
+        assert!(storage_manager.is_empty());
+        // The finalized data can be rolled back now
+        storage_manager.rollback_finalized(1).unwrap();
+
+        // B is built again, on top of the finalized A
+        let storage = storage_manager.create_storage_on(&block_b).unwrap();
+        assert_eq!(
+            Some(value_from(2).into()),
+            storage.get(&key_from(1).into(), None, &witness)
+        );
+        assert_eq!(None, storage.get(&key_from(3).into(), None, &witness));
+    }
+
     #[test]
     fn lifecycle_simulation() {
         let tmpdir = tempfile::tempdir().unwrap();
@@ -15,7 +15,7 @@ use crate::snapshot_manager::DataLocation::Snapshot;
 /// down to DB level
 /// Managed externally by [`crate::ProverStorageManager`]
 pub struct SnapshotManager {
-    db: sov_schema_db::DB,
+    db: Arc<sov_schema_db::DB>,
     snapshots: HashMap<SnapshotId, ReadOnlyDbSnapshot>,
     /// Hierarchical
     to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
@@ -27,7 +27,7 @@ impl SnapshotManager {
         to_parent: Arc<RwLock<HashMap<SnapshotId, SnapshotId>>>,
     ) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
             snapshots: HashMap::new(),
             to_parent,
         }
@@ -37,7 +37,18 @@ impl SnapshotManager {
     /// So it only reads from database.
     pub fn orphan(db: sov_schema_db::DB) -> Self {
         Self {
-            db,
+            db: Arc::new(db),
+            snapshots: HashMap::new(),
+            to_parent: Arc::new(RwLock::new(Default::default())),
+        }
+    }
+
+    /// Creates a snapshot manager which only reads the committed snapshots from the database
+    /// of this one. It doesn't share its locks, so its readers don't wait for the snapshots
+    /// added or committed to this one.
+    pub(crate) fn read_only(&self) -> Self {
+        Self {
+            db: self.db.clone(),
             snapshots: HashMap::new(),
             to_parent: Arc::new(RwLock::new(Default::default())),
         }
@@ -50,6 +61,11 @@ impl SnapshotManager {
         }
     }
 
+    /// The underlying database, which only contains the committed snapshots.
+    pub(crate) fn db(&self) -> Arc<sov_schema_db::DB> {
+        self.db.clone()
+    }
+
     pub(crate) fn discard_snapshot(&mut self, snapshot_id: &SnapshotId) {
         self.snapshots.remove(snapshot_id);
     }
@@ -239,8 +255,18 @@ where
 }
 
 impl QueryManager for SnapshotManager {
-    type Iter<'a, S> = SnapshotManagerIter<'a, S, Rev<btree_map::Iter<'a, SchemaKey, Operation>>> where S: Sized, S: Schema, Self: 'a;
-    type RangeIter<'a, S: Schema> = SnapshotManagerIter<'a, S, Rev<btree_map::Range<'a, SchemaKey, Operation>>> where S: Sized, S: Schema, Self: 'a;
+    type Iter<'a, S>
+        = SnapshotManagerIter<'a, S, Rev<btree_map::Iter<'a, SchemaKey, Operation>>>
+    where
+        S: Sized,
+        S: Schema,
+        Self: 'a;
+    type RangeIter<'a, S: Schema>
+        = SnapshotManagerIter<'a, S, Rev<btree_map::Range<'a, SchemaKey, Operation>>>
+    where
+        S: Sized,
+        S: Schema,
+        Self: 'a;
 
     fn get<S: Schema>(
         &self,
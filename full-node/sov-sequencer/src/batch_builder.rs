@@ -1,14 +1,25 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::{bail, Context as ErrorContext};
 use borsh::BorshDeserialize;
+use sov_db::mempool_db::MempoolDB;
+use sov_db::schema::types::StoredPooledTx;
 use sov_modules_api::digest::Digest;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, DispatchCall, PublicKey, Spec, WorkingSet};
+use sov_modules_api::{Context, DispatchCall, ModuleError, PublicKey, Spec, WorkingSet};
 use sov_rollup_interface::services::batch_builder::BatchBuilder;
 use tracing::{info, warn};
 
+use crate::mempool::{Mempool, MempoolEntry, PooledTxInfo, TxPool, TxPoolContent};
+use crate::ordering::{FifoOrdering, TxOrdering};
+
+/// Size of the encoding of a batch as a DA blob, on top of the size of its transactions:
+/// the borsh encoding of the batch prefixes it with its length, and every transaction with its length.
+const BATCH_ENCODING_OVERHEAD: usize = 4;
+const TX_ENCODING_OVERHEAD: usize = 4;
+
 /// Transaction stored in the mempool.
 pub struct PooledTransaction<C: Context, R: DispatchCall<Context = C>> {
     /// Raw transaction bytes.
@@ -17,8 +28,29 @@ pub struct PooledTransaction<C: Context, R: DispatchCall<Context = C>> {
     tx: Transaction<C>,
     /// The decoded runtime message, cached during initial verification.
     msg: Option<R::Decodable>,
+    /// The order the transaction arrived in, which keys it in the [`MempoolDB`].
+    arrival_number: u64,
+    /// When the transaction was accepted, in seconds since the Unix epoch.
+    received_at: u64,
 }
 
+/// Checks a transaction against the rollup state, like the transaction hooks of the runtime do
+/// before dispatching it. The checks of consecutive transactions share the working set.
+pub type TxValidator<C> =
+    Box<dyn Fn(&Transaction<C>, &mut WorkingSet<C>) -> anyhow::Result<()> + Send + Sync>;
+
+/// Checks a decoded call message against the rollup state before it's dispatched, like the runtime
+/// checks the call messages of the batches, e.g. with its circuit breaker.
+pub type DispatchChecker<C, R> = Box<
+    dyn Fn(&<R as DispatchCall>::Decodable, &mut WorkingSet<C>) -> Result<(), ModuleError>
+        + Send
+        + Sync,
+>;
+
+/// Reads the nonce of the account with the given address from the rollup state, if it exists.
+pub type NonceReader<C> =
+    Box<dyn Fn(&<C as Spec>::Address, &mut WorkingSet<C>) -> Option<u64> + Send + Sync>;
+
 impl<C, R> std::fmt::Debug for PooledTransaction<C, R>
 where
     C: Context,
@@ -42,15 +74,33 @@ where
     }
 }
 
-/// BatchBuilder that creates batches of transactions in the order they were submitted
+/// BatchBuilder that creates batches of transactions in the order given by its [`TxOrdering`],
+/// which is the order they were submitted by default.
 /// Only transactions that were successfully dispatched are included.
-pub struct FiFoStrictBatchBuilder<C: Context, R: DispatchCall<Context = C>> {
-    mempool: VecDeque<PooledTransaction<C, R>>,
-    mempool_max_txs_count: usize,
+/// Transactions of every sender are included in the order of their nonces,
+/// transactions after a nonce gap wait in the mempool until it is filled.
+///
+/// The batch is full once the next transaction doesn't fit in the maximum size, which includes
+/// the encoding of the batch as a DA blob, or in the maximum total gas limit of the batch.
+pub struct FiFoStrictBatchBuilder<
+    C: Context,
+    R: DispatchCall<Context = C>,
+    O: TxOrdering = FifoOrdering,
+> {
+    mempool: Mempool<PooledTransaction<C, R>>,
     runtime: R,
     max_batch_size_bytes: usize,
+    max_batch_gas: Option<u64>,
+    max_txs_per_sender: Option<usize>,
+    ordering: O,
     current_storage: C::Storage,
     sequencer: C::Address,
+    mempool_db: Option<MempoolDB>,
+    mempool_max_age: Option<Duration>,
+    tx_validator: Option<TxValidator<C>>,
+    dispatch_checker: Option<DispatchChecker<C, R>>,
+    nonce_reader: Option<NonceReader<C>>,
+    next_arrival_number: u64,
 }
 
 impl<C, R> FiFoStrictBatchBuilder<C, R>
@@ -67,35 +117,201 @@ where
         sequencer: C::Address,
     ) -> Self {
         Self {
-            mempool: VecDeque::new(),
-            mempool_max_txs_count,
+            mempool: Mempool::new(mempool_max_txs_count),
             max_batch_size_bytes,
+            max_batch_gas: None,
+            max_txs_per_sender: None,
+            ordering: FifoOrdering,
             runtime,
             current_storage,
             sequencer,
+            mempool_db: None,
+            mempool_max_age: None,
+            tx_validator: None,
+            dispatch_checker: None,
+            nonce_reader: None,
+            next_arrival_number: 0,
         }
     }
 }
 
-impl<C, R> BatchBuilder for FiFoStrictBatchBuilder<C, R>
+impl<C, R, O> FiFoStrictBatchBuilder<C, R, O>
 where
     C: Context,
     R: DispatchCall<Context = C>,
+    O: TxOrdering,
 {
-    /// Attempt to add transaction to the mempool.
-    ///
-    /// The transaction is discarded if:
-    /// - mempool is full
-    /// - transaction is invalid (deserialization, verification or decoding of the runtime message failed)
-    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<()> {
-        if self.mempool.len() >= self.mempool_max_txs_count {
-            bail!("Mempool is full")
+    /// Makes the batch builder consider the pending transactions in the order given by `ordering`.
+    pub fn with_ordering<O2: TxOrdering>(self, ordering: O2) -> FiFoStrictBatchBuilder<C, R, O2> {
+        FiFoStrictBatchBuilder {
+            mempool: self.mempool,
+            runtime: self.runtime,
+            max_batch_size_bytes: self.max_batch_size_bytes,
+            max_batch_gas: self.max_batch_gas,
+            max_txs_per_sender: self.max_txs_per_sender,
+            ordering,
+            current_storage: self.current_storage,
+            sequencer: self.sequencer,
+            mempool_db: self.mempool_db,
+            mempool_max_age: self.mempool_max_age,
+            tx_validator: self.tx_validator,
+            dispatch_checker: self.dispatch_checker,
+            nonce_reader: self.nonce_reader,
+            next_arrival_number: self.next_arrival_number,
+        }
+    }
+
+    /// Persists the mempool in the given database, so the pending transactions survive restarts.
+    /// Every accepted transaction is written before it's acknowledged, and the transactions
+    /// leaving the mempool are deleted in a single write per batch.
+    /// The persisted transactions are reloaded with [`FiFoStrictBatchBuilder::restore_mempool`].
+    pub fn with_mempool_db(mut self, mempool_db: MempoolDB) -> Self {
+        self.mempool_db = Some(mempool_db);
+        self
+    }
+
+    /// Drops the transactions which waited in the mempool for longer than `max_age`.
+    pub fn with_mempool_max_age(mut self, max_age: Duration) -> Self {
+        self.mempool_max_age = Some(max_age);
+        self
+    }
+
+    /// Revalidates the transactions reloaded by [`FiFoStrictBatchBuilder::restore_mempool`]
+    /// with `tx_validator`, since the rollup state changed while the sequencer was stopped.
+    pub fn with_tx_validator(mut self, tx_validator: TxValidator<C>) -> Self {
+        self.tx_validator = Some(tx_validator);
+        self
+    }
+
+    /// Checks the call message of every transaction with `dispatch_checker` before it's dispatched
+    /// while building a batch, so the transactions the runtime would reject aren't included.
+    pub fn with_dispatch_checker(mut self, dispatch_checker: DispatchChecker<C, R>) -> Self {
+        self.dispatch_checker = Some(dispatch_checker);
+        self
+    }
+
+    /// Starts the transactions of new senders at the nonce of their account read by `nonce_reader`,
+    /// instead of the nonce of their first transaction. It also lets the mempool forget the senders
+    /// whose transactions were all executed.
+    pub fn with_nonce_reader(mut self, nonce_reader: NonceReader<C>) -> Self {
+        self.nonce_reader = Some(nonce_reader);
+        self
+    }
+
+    /// Reloads the transactions persisted in the [`MempoolDB`], and returns how many were
+    /// restored. The expired transactions, and the ones which are no longer valid against the
+    /// current state, are dropped. The transactions of every sender are revalidated in the order
+    /// of their nonces, and restored in the order they arrived in.
+    pub fn restore_mempool(&mut self) -> anyhow::Result<usize> {
+        let Some(mempool_db) = self.mempool_db.clone() else {
+            return Ok(0);
+        };
+        let oldest_received_at = self.oldest_received_at();
+
+        let mut dropped = Vec::new();
+        let mut candidates = Vec::new();
+        for (arrival_number, stored) in mempool_db.load()? {
+            self.next_arrival_number = self.next_arrival_number.max(arrival_number + 1);
+            if stored.received_at < oldest_received_at {
+                dropped.push(arrival_number);
+                continue;
+            }
+            match self.pooled_transaction(stored.raw, arrival_number, stored.received_at) {
+                Ok(candidate) => candidates.push(candidate),
+                Err(error) => {
+                    warn!(%error, "Dropping a persisted transaction which is no longer valid");
+                    dropped.push(arrival_number);
+                }
+            }
+        }
+
+        if let Some(tx_validator) = &self.tx_validator {
+            let mut working_set = WorkingSet::new(self.current_storage.clone());
+            let mut by_nonce: Vec<usize> = (0..candidates.len()).collect();
+            by_nonce.sort_by_key(|index| candidates[*index].0.nonce);
+            let mut is_valid = vec![false; candidates.len()];
+            for index in by_nonce {
+                match tx_validator(&candidates[index].1.tx, &mut working_set) {
+                    Ok(()) => is_valid[index] = true,
+                    Err(error) => warn!(
+                        %error,
+                        hash = candidates[index].0.hash,
+                        "Dropping a persisted transaction which is no longer valid"
+                    ),
+                }
+            }
+            let mut is_valid = is_valid.into_iter();
+            candidates.retain(|(_, pooled)| {
+                let valid = is_valid.next().expect("Every candidate was validated");
+                if !valid {
+                    dropped.push(pooled.arrival_number);
+                }
+                valid
+            });
+        }
+
+        let mut restored = 0;
+        let mut working_set = WorkingSet::new(self.current_storage.clone());
+        for (info, pooled) in candidates {
+            let arrival_number = pooled.arrival_number;
+            let account_nonce = account_nonce(&self.nonce_reader, &info.sender, &mut working_set);
+            match self.mempool.insert(info, pooled, account_nonce) {
+                Ok(replaced) => {
+                    restored += 1;
+                    dropped.extend(replaced.map(|entry| entry.tx.arrival_number));
+                }
+                Err(error) => {
+                    warn!(%error, "Dropping a persisted transaction which doesn't fit in the mempool");
+                    dropped.push(arrival_number);
+                }
+            }
         }
 
-        if raw.len() > self.max_batch_size_bytes {
+        mempool_db.write(&[], &dropped)?;
+        info!(
+            restored,
+            dropped = dropped.len(),
+            "Restored the persisted mempool"
+        );
+        Ok(restored)
+    }
+
+    /// Limits the total gas limit of the transactions of a batch.
+    pub fn with_max_batch_gas(mut self, max_batch_gas: u64) -> Self {
+        self.max_batch_gas = Some(max_batch_gas);
+        self
+    }
+
+    /// Limits the number of transactions of a single sender in a batch.
+    /// The following transactions of the sender wait for the next batch.
+    pub fn with_max_txs_per_sender(mut self, max_txs_per_sender: usize) -> Self {
+        self.max_txs_per_sender = Some(max_txs_per_sender);
+        self
+    }
+
+    /// The maximum size of a transaction, so it fits in a batch on its own.
+    fn max_tx_size_bytes(&self) -> usize {
+        self.max_batch_size_bytes
+            .saturating_sub(BATCH_ENCODING_OVERHEAD + TX_ENCODING_OVERHEAD)
+    }
+
+    /// The transactions received before this time, in seconds since the Unix epoch, are expired.
+    fn oldest_received_at(&self) -> u64 {
+        self.mempool_max_age
+            .map_or(0, |max_age| unix_time().saturating_sub(max_age.as_secs()))
+    }
+
+    /// Checks the raw transaction, and returns it with its metadata, ready to be pooled.
+    fn pooled_transaction(
+        &self,
+        raw: Vec<u8>,
+        arrival_number: u64,
+        received_at: u64,
+    ) -> anyhow::Result<(PooledTxInfo, PooledTransaction<C, R>)> {
+        if raw.len() > self.max_tx_size_bytes() {
             bail!(
                 "Transaction too big. Max allowed size: {}",
-                self.max_batch_size_bytes
+                self.max_tx_size_bytes()
             )
         }
 
@@ -104,6 +320,15 @@ where
         let tx = Transaction::<C>::deserialize_reader(&mut data)
             .context("Failed to deserialize transaction")?;
 
+        if let Some(max_batch_gas) = self.max_batch_gas {
+            if tx.gas_limit() > max_batch_gas {
+                bail!(
+                    "Transaction gas limit too high. Max allowed gas limit: {}",
+                    max_batch_gas
+                )
+            }
+        }
+
         // Verify
         tx.verify().context("Failed to verify transaction")?;
 
@@ -112,22 +337,155 @@ where
             .map_err(anyhow::Error::new)
             .context("Failed to decode message in transaction")?;
 
-        self.mempool.push_back(PooledTransaction {
+        let sender_address: C::Address = tx.pub_key().to_address();
+        let pooled = PooledTransaction {
             raw,
             tx,
             msg: Some(msg),
+            arrival_number,
+            received_at,
+        };
+        let info = PooledTxInfo {
+            hash: hex::encode(pooled.calculate_hash()),
+            sender: sender_address.to_string(),
+            nonce: pooled.tx.nonce(),
+            gas_tip: pooled.tx.gas_tip(),
+            gas_limit: pooled.tx.gas_limit(),
+            size: pooled.raw.len(),
+        };
+        Ok((info, pooled))
+    }
+
+    /// Deletes the transactions which left the mempool from the [`MempoolDB`].
+    fn persist_removals(&self, removed: &[u64]) {
+        if let Some(mempool_db) = &self.mempool_db {
+            if let Err(error) = mempool_db.write(&[], removed) {
+                warn!(%error, "Failed to delete transactions from the persisted mempool");
+            }
+        }
+    }
+
+    /// Drops the expired transactions, and returns their arrival numbers.
+    fn expire_txs(&mut self) -> Vec<u64> {
+        if self.mempool_max_age.is_none() {
+            return Vec::new();
+        }
+        let oldest_received_at = self.oldest_received_at();
+        self.mempool
+            .remove_where(|entry| entry.tx.received_at < oldest_received_at)
+            .into_iter()
+            .map(|entry: MempoolEntry<PooledTransaction<C, R>>| {
+                info!(
+                    hash = entry.info.hash,
+                    "Transaction expired from the mempool"
+                );
+                entry.tx.arrival_number
+            })
+            .collect()
+    }
+}
+
+/// Reads the nonce of the account of `sender` with `nonce_reader`, if it's set.
+fn account_nonce<C: Context>(
+    nonce_reader: &Option<NonceReader<C>>,
+    sender: &str,
+    working_set: &mut WorkingSet<C>,
+) -> Option<u64> {
+    let nonce_reader = nonce_reader.as_ref()?;
+    let address = sender.parse::<C::Address>().ok()?;
+    nonce_reader(&address, working_set)
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|time| time.as_secs())
+        .unwrap_or_default()
+}
+
+impl<C, R, O> BatchBuilder for FiFoStrictBatchBuilder<C, R, O>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+    O: TxOrdering,
+{
+    /// Attempt to add transaction to the mempool.
+    ///
+    /// The transaction is discarded if:
+    /// - mempool is full
+    /// - transaction can't fit in a batch, because of its size or its gas limit
+    /// - transaction is invalid (deserialization, verification or decoding of the runtime message failed)
+    /// - its nonce was already included in a batch, or is lower than the nonce of the account of its sender
+    /// - it replaces a pooled transaction with the same nonce without a sufficient gas tip increase
+    ///
+    /// If the mempool is persisted, the transaction is written to the [`MempoolDB`] before it's acknowledged,
+    /// and it's rejected, leaving the mempool unchanged, if it can't be written.
+    fn accept_tx(&mut self, raw: Vec<u8>) -> anyhow::Result<()> {
+        let arrival_number = self.next_arrival_number;
+        let received_at = unix_time();
+        let (info, pooled) = self.pooled_transaction(raw, arrival_number, received_at)?;
+        self.next_arrival_number += 1;
+
+        let stored = self.mempool_db.as_ref().map(|_| StoredPooledTx {
+            raw: pooled.raw.clone(),
+            received_at,
         });
+        let mut working_set = WorkingSet::new(self.current_storage.clone());
+        let account_nonce = account_nonce(&self.nonce_reader, &info.sender, &mut working_set);
+        let replaced = self.mempool.insert(info, pooled, account_nonce)?;
+        if let (Some(mempool_db), Some(stored)) = (&self.mempool_db, stored) {
+            let removed: Vec<u64> = replaced
+                .iter()
+                .map(|entry| entry.tx.arrival_number)
+                .collect();
+            if let Err(error) = mempool_db.write(&[(arrival_number, stored)], &removed) {
+                // The mempool stays as persisted: the transaction is dropped and the one it
+                // replaced is put back
+                self.mempool
+                    .remove_where(|entry| entry.tx.arrival_number == arrival_number);
+                if let Some(replaced) = replaced {
+                    self.mempool.reinsert(replaced);
+                }
+                return Err(error.context("Failed to persist the transaction"));
+            }
+        }
         Ok(())
     }
 
-    /// Builds a new batch of valid transactions in order they were added to mempool
+    /// Builds a new batch of valid transactions in the order given by the [`TxOrdering`]
     /// Only transactions, which are dispatched successfully are included in the batch
     fn get_next_blob(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
         let mut working_set = WorkingSet::new(self.current_storage.clone());
         let mut txs = Vec::new();
-        let mut current_batch_size = 0;
+        let mut current_batch_size = BATCH_ENCODING_OVERHEAD;
+        let mut current_batch_gas = 0u64;
+        let mut is_batch_full = false;
+        // Senders whose transaction was discarded, so their next transactions have a nonce gap
+        let mut blocked_senders = HashSet::new();
+        let mut included_per_sender: HashMap<String, usize> = HashMap::new();
+        // Transactions which left the mempool, to delete from the persisted mempool
+        let mut removed = self.expire_txs();
+
+        let pending = self.ordering.order(self.mempool.take_pending());
+        for mut entry in pending {
+            if is_batch_full || blocked_senders.contains(&entry.info.sender) {
+                self.mempool.reinsert(entry);
+                continue;
+            }
+
+            let included = included_per_sender
+                .get(&entry.info.sender)
+                .copied()
+                .unwrap_or_default();
+            if self
+                .max_txs_per_sender
+                .is_some_and(|max_txs_per_sender| included >= max_txs_per_sender)
+            {
+                self.mempool.reinsert(entry);
+                continue;
+            }
 
-        while let Some(mut pooled) = self.mempool.pop_front() {
+            let pooled = &mut entry.tx;
             // Take the decoded runtime message cached upon accepting transaction
             // into the pool or attempt to decode the message again if
             // the transaction was previously executed,
@@ -144,29 +502,59 @@ where
                 // FIXME! This should use the correct height
                 let ctx = C::new(sender_address, self.sequencer.clone(), 0);
 
-                if let Err(error) = self.runtime.dispatch_call(msg, &mut working_set, &ctx) {
+                let result = match &self.dispatch_checker {
+                    Some(dispatch_checker) => self.runtime.dispatch_call_and_deferred_checked(
+                        msg,
+                        &mut working_set,
+                        &ctx,
+                        dispatch_checker.as_ref(),
+                    ),
+                    None => self
+                        .runtime
+                        .dispatch_call_and_deferred(msg, &mut working_set, &ctx),
+                };
+                if let Err(error) = result {
                     warn!(%error, tx = hex::encode(&pooled.raw), "Error during transaction dispatch");
+                    blocked_senders.insert(entry.info.sender);
+                    removed.push(pooled.arrival_number);
                     continue;
                 }
             }
 
             // In order to fill batch as big as possible, we only check if valid tx can fit in the batch.
-            let tx_len = pooled.raw.len();
-            if current_batch_size + tx_len > self.max_batch_size_bytes {
-                self.mempool.push_front(pooled);
-                break;
+            let tx_len = pooled.raw.len() + TX_ENCODING_OVERHEAD;
+            let tx_gas = pooled.tx.gas_limit();
+            let exceeds_gas = self.max_batch_gas.is_some_and(|max_batch_gas| {
+                current_batch_gas.saturating_add(tx_gas) > max_batch_gas
+            });
+            if current_batch_size + tx_len > self.max_batch_size_bytes || exceeds_gas {
+                is_batch_full = true;
+                self.mempool.reinsert(entry);
+                continue;
             }
 
-            // Update size of current batch
+            // Update size and gas of current batch
             current_batch_size += tx_len;
+            current_batch_gas += tx_gas;
+            *included_per_sender
+                .entry(entry.info.sender.clone())
+                .or_default() += 1;
 
-            let tx_hash: [u8; 32] = pooled.calculate_hash();
             info!(
-                hash = hex::encode(tx_hash),
+                hash = entry.info.hash,
                 "Transaction has been included in the batch",
             );
-            txs.push(pooled.raw);
+            self.mempool
+                .mark_included(&entry.info.sender, entry.info.nonce);
+            removed.push(entry.tx.arrival_number);
+            txs.push(entry.tx.raw);
         }
+        self.persist_removals(&removed);
+
+        let mut working_set = WorkingSet::new(self.current_storage.clone());
+        let nonce_reader = &self.nonce_reader;
+        self.mempool
+            .prune_senders(|sender| account_nonce(nonce_reader, sender, &mut working_set));
 
         if txs.is_empty() {
             bail!("No valid transactions are available");
@@ -176,6 +564,33 @@ where
     }
 }
 
+impl<C, R, O> TxPool for FiFoStrictBatchBuilder<C, R, O>
+where
+    C: Context,
+    R: DispatchCall<Context = C>,
+    O: TxOrdering,
+{
+    fn txpool_content(&self) -> TxPoolContent {
+        self.mempool.content()
+    }
+
+    fn set_mempool_max_txs_count(&mut self, max_txs_count: usize) {
+        self.mempool.set_max_txs_count(max_txs_count);
+    }
+
+    fn evict_tx(&mut self, hash: &str) -> bool {
+        let hash = hash.trim_start_matches("0x");
+        let removed: Vec<u64> = self
+            .mempool
+            .remove_where(|entry| entry.info.hash == hash)
+            .into_iter()
+            .map(|entry| entry.tx.arrival_number)
+            .collect();
+        self.persist_removals(&removed);
+        !removed.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use borsh::BorshSerialize;
@@ -186,7 +601,8 @@ mod tests {
     use sov_modules_api::macros::DefaultRuntime;
     use sov_modules_api::transaction::Transaction;
     use sov_modules_api::{
-        Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, PrivateKey,
+        Address, Context, DispatchCall, EncodeCall, Genesis, MessageCodec, ModuleErrorCode,
+        PrivateKey,
     };
     use sov_prover_storage_manager::{new_orphan_storage, SnapshotManager};
     use sov_rollup_interface::services::batch_builder::BatchBuilder;
@@ -195,6 +611,7 @@ mod tests {
     use tempfile::TempDir;
 
     use super::*;
+    use crate::ordering::BatchOrdering;
 
     const MAX_TX_POOL_SIZE: usize = 20;
     type C = DefaultContext;
@@ -209,16 +626,32 @@ mod tests {
         let private_key = DefaultPrivateKey::generate();
         let mut rng = rand::thread_rng();
         let value: u32 = rng.gen();
-        generate_valid_tx(&private_key, value)
+        generate_valid_tx(&private_key, value, 1)
+    }
+
+    fn generate_valid_tx(private_key: &DefaultPrivateKey, value: u32, nonce: u64) -> Vec<u8> {
+        generate_valid_tx_with_tip(private_key, value, nonce, 0)
+    }
+
+    fn generate_valid_tx_with_tip(
+        private_key: &DefaultPrivateKey,
+        value: u32,
+        nonce: u64,
+        gas_tip: u64,
+    ) -> Vec<u8> {
+        generate_valid_tx_with_gas(private_key, value, nonce, gas_tip, 0)
     }
 
-    fn generate_valid_tx(private_key: &DefaultPrivateKey, value: u32) -> Vec<u8> {
+    fn generate_valid_tx_with_gas(
+        private_key: &DefaultPrivateKey,
+        value: u32,
+        nonce: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+    ) -> Vec<u8> {
         let msg = CallMessage::SetValue(value);
         let msg = <TestRuntime<C> as EncodeCall<ValueSetter<DefaultContext>>>::encode_call(msg);
         let chain_id = 0;
-        let gas_tip = 0;
-        let gas_limit = 0;
-        let nonce = 1;
 
         Transaction::<DefaultContext>::new_signed_tx(
             private_key,
@@ -259,6 +692,15 @@ mod tests {
         .unwrap()
     }
 
+    /// Size of a batch with transactions of the given sizes, once encoded as a DA blob.
+    fn batch_size_of(tx_sizes: &[usize]) -> usize {
+        BATCH_ENCODING_OVERHEAD
+            + tx_sizes
+                .iter()
+                .map(|size| size + TX_ENCODING_OVERHEAD)
+                .sum::<usize>()
+    }
+
     fn create_batch_builder(
         batch_size_bytes: usize,
         tmpdir: &TempDir,
@@ -307,7 +749,7 @@ mod tests {
             let tx = generate_random_valid_tx();
 
             let tmpdir = tempfile::tempdir().unwrap();
-            let (mut batch_builder, _) = create_batch_builder(tx.len(), &tmpdir);
+            let (mut batch_builder, _) = create_batch_builder(batch_size_of(&[tx.len()]), &tmpdir);
 
             batch_builder.accept_tx(tx).unwrap();
         }
@@ -315,7 +757,9 @@ mod tests {
         #[test]
         fn reject_tx_too_big() {
             let tx = generate_random_valid_tx();
-            let batch_size = tx.len().saturating_sub(1);
+            let max_tx_size = tx.len() - 1;
+            // The encoding of the batch on DA doesn't leave enough space for the transaction
+            let batch_size = batch_size_of(&[max_tx_size]);
 
             let tmpdir = tempfile::tempdir().unwrap();
             let (mut batch_builder, _) = create_batch_builder(batch_size, &tmpdir);
@@ -323,7 +767,7 @@ mod tests {
             let accept_result = batch_builder.accept_tx(tx);
             assert!(accept_result.is_err());
             assert_eq!(
-                format!("Transaction too big. Max allowed size: {batch_size}"),
+                format!("Transaction too big. Max allowed size: {max_tx_size}"),
                 accept_result.unwrap_err().to_string()
             );
         }
@@ -350,7 +794,7 @@ mod tests {
             let tx = generate_random_bytes();
 
             let tmpdir = tempfile::tempdir().unwrap();
-            let (mut batch_builder, _) = create_batch_builder(tx.len(), &tmpdir);
+            let (mut batch_builder, _) = create_batch_builder(batch_size_of(&[tx.len()]), &tmpdir);
 
             let accept_result = batch_builder.accept_tx(tx);
             assert!(accept_result.is_err());
@@ -366,7 +810,7 @@ mod tests {
             let tx = generate_signed_tx_with_invalid_payload(&private_key);
 
             let tmpdir = tempfile::tempdir().unwrap();
-            let (mut batch_builder, _) = create_batch_builder(tx.len(), &tmpdir);
+            let (mut batch_builder, _) = create_batch_builder(batch_size_of(&[tx.len()]), &tmpdir);
 
             let accept_result = batch_builder.accept_tx(tx);
             assert!(accept_result.is_err());
@@ -376,13 +820,87 @@ mod tests {
                 .starts_with("Failed to decode message"))
         }
 
+        #[test]
+        fn reject_tx_below_account_nonce() {
+            let private_key = DefaultPrivateKey::generate();
+            let sender: Address = private_key.pub_key().to_address();
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (batch_builder, _) = create_batch_builder(usize::MAX, &tmpdir);
+            let mut batch_builder = batch_builder.with_nonce_reader(Box::new(move |address, _| {
+                (*address == sender).then_some(1)
+            }));
+
+            let accept_result = batch_builder.accept_tx(generate_valid_tx(&private_key, 1, 0));
+            assert_eq!(
+                "Nonce too low, expected at least: 1, but found: 0",
+                accept_result.unwrap_err().to_string()
+            );
+            assert!(batch_builder.mempool.content().pending.is_empty());
+
+            batch_builder
+                .accept_tx(generate_valid_tx(&private_key, 1, 1))
+                .unwrap();
+            assert_eq!(1, batch_builder.mempool.content().pending.len());
+        }
+
+        #[test]
+        fn replace_tx_with_higher_gas_tip() {
+            let private_key = DefaultPrivateKey::generate();
+            let tx = generate_valid_tx_with_tip(&private_key, 1, 0, 100);
+            let underpriced = generate_valid_tx_with_tip(&private_key, 2, 0, 105);
+            let replacement = generate_valid_tx_with_tip(&private_key, 3, 0, 110);
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, _) = create_batch_builder(usize::MAX, &tmpdir);
+
+            batch_builder.accept_tx(tx).unwrap();
+
+            let accept_result = batch_builder.accept_tx(underpriced);
+            assert!(accept_result.is_err());
+            assert_eq!(
+                "Replacement transaction underpriced, gas tip must be at least: 110",
+                accept_result.unwrap_err().to_string()
+            );
+
+            batch_builder.accept_tx(replacement).unwrap();
+            assert_eq!(1, batch_builder.mempool.len());
+
+            let content = batch_builder.txpool_content();
+            let sender = private_key.pub_key().to_address::<Address>().to_string();
+            assert_eq!(110, content.pending[&sender][&0].gas_tip);
+        }
+
+        #[test]
+        fn evicted_tx_queues_the_next_ones() {
+            let private_key = DefaultPrivateKey::generate();
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, _) = create_batch_builder(usize::MAX, &tmpdir);
+            for nonce in 0..2 {
+                batch_builder
+                    .accept_tx(generate_valid_tx(&private_key, nonce as u32, nonce))
+                    .unwrap();
+            }
+
+            let sender = private_key.pub_key().to_address::<Address>().to_string();
+            let hash = batch_builder.txpool_content().pending[&sender][&0]
+                .hash
+                .clone();
+            assert!(batch_builder.evict_tx(&format!("0x{}", hash)));
+            assert!(!batch_builder.evict_tx(&hash));
+
+            let status = batch_builder.txpool_status();
+            assert_eq!(0, status.pending);
+            assert_eq!(1, status.queued);
+        }
+
         #[test]
         fn zero_sized_mempool_cant_accept_tx() {
             let tx = generate_random_valid_tx();
 
             let tmpdir = tempfile::tempdir().unwrap();
-            let (mut batch_builder, _) = create_batch_builder(tx.len(), &tmpdir);
-            batch_builder.mempool_max_txs_count = 0;
+            let (mut batch_builder, _) = create_batch_builder(batch_size_of(&[tx.len()]), &tmpdir);
+            batch_builder.mempool.set_max_txs_count(0);
 
             let accept_result = batch_builder.accept_tx(tx);
             assert!(accept_result.is_err());
@@ -412,8 +930,8 @@ mod tests {
             let value_setter_admin = DefaultPrivateKey::generate();
             let txs = [
                 // Should be included: 113 bytes
-                generate_valid_tx(&value_setter_admin, 1),
-                generate_valid_tx(&value_setter_admin, 2),
+                generate_valid_tx(&value_setter_admin, 1, 0),
+                generate_valid_tx(&value_setter_admin, 2, 1),
             ];
 
             let tmpdir = tempfile::tempdir().unwrap();
@@ -440,17 +958,17 @@ mod tests {
             let value_setter_admin = DefaultPrivateKey::generate();
             let txs = [
                 // Should be included: 113 bytes
-                generate_valid_tx(&value_setter_admin, 1),
+                generate_valid_tx(&value_setter_admin, 1, 0),
                 // Should be rejected, not admin
                 generate_random_valid_tx(),
                 // Should be included: 113 bytes
-                generate_valid_tx(&value_setter_admin, 2),
+                generate_valid_tx(&value_setter_admin, 2, 1),
                 // Should be skipped, more than batch size
-                generate_valid_tx(&value_setter_admin, 3),
+                generate_valid_tx(&value_setter_admin, 3, 2),
             ];
 
             let tmpdir = tempfile::tempdir().unwrap();
-            let batch_size = txs[0].len() + txs[2].len() + 1;
+            let batch_size = batch_size_of(&[txs[0].len(), txs[2].len()]) + 1;
             let (mut batch_builder, storage) = create_batch_builder(batch_size, &tmpdir);
             setup_runtime(storage, Some(value_setter_admin.pub_key()));
 
@@ -469,5 +987,274 @@ mod tests {
             assert!(!blob.contains(&txs[3]));
             assert_eq!(1, batch_builder.mempool.len());
         }
+
+        #[test]
+        fn builds_batch_waiting_for_nonce_gap() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx(&value_setter_admin, 1, 0),
+                // Queued until nonce 1 arrives
+                generate_valid_tx(&value_setter_admin, 3, 2),
+                generate_valid_tx(&value_setter_admin, 2, 1),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (mut batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            batch_builder.accept_tx(txs[0].clone()).unwrap();
+            batch_builder.accept_tx(txs[1].clone()).unwrap();
+
+            let status = batch_builder.txpool_status();
+            assert_eq!(1, status.pending);
+            assert_eq!(1, status.queued);
+
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(vec![txs[0].clone()], blob);
+            assert_eq!(1, batch_builder.mempool.len());
+
+            batch_builder.accept_tx(txs[2].clone()).unwrap();
+
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(vec![txs[2].clone(), txs[1].clone()], blob);
+            assert!(batch_builder.mempool.is_empty());
+        }
+
+        #[test]
+        fn builds_batch_within_gas_limit() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx_with_gas(&value_setter_admin, 1, 0, 0, 10),
+                generate_valid_tx_with_gas(&value_setter_admin, 2, 1, 0, 10),
+                generate_valid_tx_with_gas(&value_setter_admin, 3, 2, 0, 10),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            let mut batch_builder = batch_builder.with_max_batch_gas(25);
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            let too_much_gas = generate_valid_tx_with_gas(&value_setter_admin, 4, 3, 0, 30);
+            assert_eq!(
+                "Transaction gas limit too high. Max allowed gas limit: 25",
+                batch_builder
+                    .accept_tx(too_much_gas)
+                    .unwrap_err()
+                    .to_string()
+            );
+
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(txs[..2].to_vec(), blob);
+            assert_eq!(1, batch_builder.mempool.len());
+
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(txs[2..].to_vec(), blob);
+        }
+
+        #[test]
+        fn builds_batch_within_sender_limit() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx_with_tip(&value_setter_admin, 1, 0, 1),
+                generate_valid_tx_with_tip(&value_setter_admin, 2, 1, 5),
+                generate_valid_tx_with_tip(&value_setter_admin, 3, 2, 3),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            let mut batch_builder = batch_builder
+                .with_ordering(BatchOrdering::GasPrice)
+                .with_max_txs_per_sender(2);
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+
+            // The gas tips don't change the order of the transactions of a sender
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(txs[..2].to_vec(), blob);
+            assert_eq!(1, batch_builder.mempool.len());
+
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(txs[2..].to_vec(), blob);
+            assert!(batch_builder.mempool.is_empty());
+        }
+
+        #[test]
+        fn builds_batch_skipping_txs_rejected_by_the_dispatch_checker() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx(&value_setter_admin, 1, 0),
+                generate_valid_tx(&value_setter_admin, 2, 1),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let (batch_builder, storage) = create_batch_builder(usize::MAX, &tmpdir);
+            let mut batch_builder =
+                batch_builder.with_dispatch_checker(Box::new(|message, _| match message {
+                    TestRuntimeCall::value_setter(CallMessage::SetValue(2)) => {
+                        Err(ModuleErrorCode::Unauthorized.error("Paused").into())
+                    }
+                    _ => Ok(()),
+                }));
+            setup_runtime(storage, Some(value_setter_admin.pub_key()));
+
+            for tx in &txs {
+                batch_builder.accept_tx(tx.clone()).unwrap();
+            }
+
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(txs[..1].to_vec(), blob);
+            assert!(batch_builder.mempool.is_empty());
+        }
+    }
+
+    mod restore_mempool {
+        use super::*;
+
+        fn persisted_batch_builder(
+            storage: &ProverStorage<DefaultStorageSpec, SnapshotManager>,
+            mempool_path: &TempDir,
+        ) -> FiFoStrictBatchBuilder<C, TestRuntime<C>> {
+            FiFoStrictBatchBuilder::new(
+                usize::MAX,
+                MAX_TX_POOL_SIZE,
+                TestRuntime::<C>::default(),
+                storage.clone(),
+                Address::from([0; 32]),
+            )
+            .with_mempool_db(MempoolDB::with_path(mempool_path.path()).unwrap())
+        }
+
+        #[test]
+        fn restores_pending_txs_after_restart() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx(&value_setter_admin, 1, 0),
+                generate_valid_tx(&value_setter_admin, 2, 1),
+                generate_valid_tx(&value_setter_admin, 3, 2),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let mempool_path = tempfile::tempdir().unwrap();
+            let storage = new_orphan_storage(tmpdir.path()).unwrap();
+            setup_runtime(storage.clone(), Some(value_setter_admin.pub_key()));
+
+            {
+                let mut batch_builder =
+                    persisted_batch_builder(&storage, &mempool_path).with_max_txs_per_sender(1);
+                for tx in &txs {
+                    batch_builder.accept_tx(tx.clone()).unwrap();
+                }
+                let blob = batch_builder.get_next_blob().unwrap();
+                assert_eq!(vec![txs[0].clone()], blob);
+            }
+
+            // The included transaction isn't restored
+            let mut batch_builder = persisted_batch_builder(&storage, &mempool_path);
+            assert_eq!(2, batch_builder.restore_mempool().unwrap());
+            assert_eq!(2, batch_builder.mempool.len());
+
+            // New transactions arrive after the restored ones
+            let tx = generate_valid_tx(&value_setter_admin, 4, 3);
+            batch_builder.accept_tx(tx.clone()).unwrap();
+            let blob = batch_builder.get_next_blob().unwrap();
+            assert_eq!(vec![txs[1].clone(), txs[2].clone(), tx], blob);
+            drop(batch_builder);
+
+            let mut batch_builder = persisted_batch_builder(&storage, &mempool_path);
+            assert_eq!(0, batch_builder.restore_mempool().unwrap());
+        }
+
+        #[test]
+        fn drops_expired_txs() {
+            let tmpdir = tempfile::tempdir().unwrap();
+            let mempool_path = tempfile::tempdir().unwrap();
+            let storage = new_orphan_storage(tmpdir.path()).unwrap();
+            let mempool_db = MempoolDB::with_path(mempool_path.path()).unwrap();
+            let fresh_tx = generate_random_valid_tx();
+            mempool_db
+                .write(
+                    &[
+                        (
+                            0,
+                            StoredPooledTx {
+                                raw: generate_random_valid_tx(),
+                                received_at: unix_time() - 120,
+                            },
+                        ),
+                        (
+                            1,
+                            StoredPooledTx {
+                                raw: fresh_tx.clone(),
+                                received_at: unix_time(),
+                            },
+                        ),
+                    ],
+                    &[],
+                )
+                .unwrap();
+
+            let mut batch_builder = FiFoStrictBatchBuilder::new(
+                usize::MAX,
+                MAX_TX_POOL_SIZE,
+                TestRuntime::<C>::default(),
+                storage,
+                Address::from([0; 32]),
+            )
+            .with_mempool_db(mempool_db.clone())
+            .with_mempool_max_age(Duration::from_secs(60));
+            assert_eq!(1, batch_builder.restore_mempool().unwrap());
+            assert_eq!(1, mempool_db.load().unwrap().len());
+
+            // Restored transactions continue the arrival numbers of the persisted ones
+            batch_builder.accept_tx(generate_random_valid_tx()).unwrap();
+            let arrival_numbers: Vec<u64> = mempool_db
+                .load()
+                .unwrap()
+                .into_iter()
+                .map(|(arrival_number, _)| arrival_number)
+                .collect();
+            assert_eq!(vec![1, 2], arrival_numbers);
+        }
+
+        #[test]
+        fn drops_txs_rejected_by_the_validator() {
+            let value_setter_admin = DefaultPrivateKey::generate();
+            let txs = [
+                generate_valid_tx(&value_setter_admin, 1, 0),
+                generate_valid_tx(&value_setter_admin, 2, 1),
+            ];
+
+            let tmpdir = tempfile::tempdir().unwrap();
+            let mempool_path = tempfile::tempdir().unwrap();
+            let storage = new_orphan_storage(tmpdir.path()).unwrap();
+            {
+                let mut batch_builder = persisted_batch_builder(&storage, &mempool_path);
+                for tx in &txs {
+                    batch_builder.accept_tx(tx.clone()).unwrap();
+                }
+            }
+
+            // The first transaction was included by another sequencer while this one was stopped
+            let mut batch_builder = persisted_batch_builder(&storage, &mempool_path)
+                .with_tx_validator(Box::new(|tx, _| {
+                    anyhow::ensure!(tx.nonce() > 0, "Nonce too low");
+                    Ok(())
+                }));
+            assert_eq!(1, batch_builder.restore_mempool().unwrap());
+            assert_eq!(1, batch_builder.mempool.len());
+            drop(batch_builder);
+
+            let mempool_db = MempoolDB::with_path(mempool_path.path()).unwrap();
+            let persisted = mempool_db.load().unwrap();
+            assert_eq!(1, persisted.len());
+            assert_eq!(txs[1], persisted[0].1.raw);
+        }
     }
 }
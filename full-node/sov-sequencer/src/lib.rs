@@ -1,56 +1,435 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
 /// Concrete implementations of `[BatchBuilder]`
 pub mod batch_builder;
+/// Mempool with per-sender nonce ordering, used by the batch builders.
+pub mod mempool;
+mod metrics;
+/// Orderings of the pending transactions considered by the batch builders.
+pub mod ordering;
+/// Soft confirmations given out by the sequencer before batches land on DA.
+pub mod soft_confirmation;
+/// Strategies deciding when and at which fee rate batches are submitted to DA.
+pub mod submission;
 /// Utilities for the sequencer rpc
 pub mod utils;
 
 use anyhow::anyhow;
 use jsonrpsee::types::ErrorObjectOwned;
 use jsonrpsee::RpcModule;
+use mempool::{TxPool, TxPoolContent, TxPoolStatus};
+use metrics::{DA_SUBMISSION_LATENCY_SECONDS, MEMPOOL_SIZE};
+use soft_confirmation::{SoftConfirmationSigner, SoftConfirmations};
+use sov_db::submission_queue_db::SubmissionQueueDB;
 use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_p2p::SoftBatchGossip;
+use sov_rollup_interface::chunking::split_blob;
+use sov_rollup_interface::compression::compress_blob;
+use sov_rollup_interface::da::BlobReaderTrait;
+use sov_rollup_interface::da::BlockHeaderTrait;
 use sov_rollup_interface::services::batch_builder::BatchBuilder;
 use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use submission::{QueuedBlob, SubmissionQueue, SubmissionStrategy};
 
 const SEQUENCER_RPC_ERROR: &str = "SEQUENCER_RPC_ERROR";
 
+/// The settings of the sequencer which can be changed while it's running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SequencerSettings {
+    /// The maximum number of transactions in the mempool.
+    pub mempool_max_txs_count: usize,
+    /// The DA fee rate ceiling of the [`SubmissionStrategy`].
+    pub max_fee_rate: Option<f64>,
+}
+
+type SettingsSource = Box<dyn Fn() -> SequencerSettings + Send + Sync>;
+
 /// Single data structure that manages mempool and batch producing.
 pub struct Sequencer<B: BatchBuilder, T: DaService> {
     batch_builder: Mutex<B>,
     da_service: T,
+    soft_confirmations: Option<SoftConfirmations>,
+    compression_level: Option<i32>,
+    submission_strategy: SubmissionStrategy,
+    settings: Option<SettingsSource>,
+    /// Locked for the whole submission of the queued blobs, so they're sent one at a time, in order.
+    submission_queue: tokio::sync::Mutex<SubmissionQueue>,
+    /// Whether batch production was paused with `admin_pauseBatchProduction`.
+    batch_production_paused: AtomicBool,
+    /// The DA fee rate ceiling set with `admin_setDaFeeCeiling`, which overrides the settings.
+    max_fee_rate_override: Mutex<Option<Option<f64>>>,
+}
+
+/// The outcome of a request to publish a batch.
+enum BatchSubmission {
+    /// The queued batches were submitted to DA.
+    Submitted { num_txs: usize },
+    /// The fee rate of DA is too high, so the queued batches wait for a next request.
+    Delayed { num_txs: usize, fee_rate: f64 },
+    /// The last submission failed, so the queued batches wait before they're retried
+    /// by a next request.
+    Queued { num_txs: usize, retry_in: Duration },
 }
 
-impl<B: BatchBuilder + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T> {
+/// The outcome of the submission of a queued blob.
+enum BlobSubmission {
+    /// The blob was submitted to DA, or had landed on DA before a restart.
+    Submitted,
+    /// The fee rate of DA is too high.
+    Delayed { fee_rate: f64 },
+    /// The blob waits before it's retried after a failed submission.
+    Waiting { retry_in: Duration },
+}
+
+impl<B: BatchBuilder + TxPool + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T> {
     /// Creates new Sequencer from BatchBuilder and DaService
     pub fn new(batch_builder: B, da_service: T) -> Self {
         Self {
             batch_builder: Mutex::new(batch_builder),
             da_service,
+            soft_confirmations: None,
+            compression_level: None,
+            submission_strategy: SubmissionStrategy::default(),
+            settings: None,
+            submission_queue: tokio::sync::Mutex::new(SubmissionQueue::default()),
+            batch_production_paused: AtomicBool::new(false),
+            max_fee_rate_override: Mutex::new(None),
+        }
+    }
+
+    /// Makes the sequencer submit its batches according to the given [`SubmissionStrategy`].
+    pub fn with_submission_strategy(mut self, strategy: SubmissionStrategy) -> Self {
+        self.submission_strategy = strategy;
+        self
+    }
+
+    /// Makes the sequencer read its [`SequencerSettings`] from `settings` before every
+    /// transaction and batch, so that they can be changed while it's running.
+    /// They override the mempool size of the batch builder and the DA fee rate ceiling
+    /// of the [`SubmissionStrategy`].
+    pub fn with_settings(
+        mut self,
+        settings: impl Fn() -> SequencerSettings + Send + Sync + 'static,
+    ) -> Self {
+        self.settings = Some(Box::new(settings));
+        self
+    }
+
+    /// Makes the sequencer persist the blobs waiting to be submitted to DA in the database, until
+    /// their submission succeeds. The blobs persisted before are resubmitted first, skipping the
+    /// ones which landed on DA before the restart.
+    pub fn with_submission_queue_db(mut self, db: SubmissionQueueDB) -> anyhow::Result<Self> {
+        self.submission_queue = tokio::sync::Mutex::new(SubmissionQueue::with_db(db)?);
+        Ok(self)
+    }
+
+    /// Makes the sequencer compress every batch with the given zstd compression level before submitting it to DA.
+    pub fn with_compression(mut self, level: i32) -> Self {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Makes the sequencer give out a signed soft confirmation for every batch it submits to DA.
+    pub fn with_soft_confirmations(
+        mut self,
+        signer: impl SoftConfirmationSigner + 'static,
+    ) -> Self {
+        self.soft_confirmations = Some(SoftConfirmations::new(Box::new(signer)));
+        self
+    }
+
+    /// Makes the sequencer gossip its soft confirmations to the full nodes, in addition to
+    /// serving them with `sequencer_getSoftBatch`. It has no effect unless soft confirmations
+    /// are enabled with [`Sequencer::with_soft_confirmations`].
+    pub fn with_soft_batch_gossip(mut self, gossip: SoftBatchGossip) -> Self {
+        if let Some(soft_confirmations) = &mut self.soft_confirmations {
+            soft_confirmations.gossip = Some(gossip);
+        }
+        self
+    }
+
+    async fn submit_batch(&self) -> anyhow::Result<BatchSubmission> {
+        tracing::info!("Submit batch request has been received!");
+        if self.batch_production_paused.load(Ordering::SeqCst) {
+            return Err(anyhow!("batch production is paused"));
+        }
+        let strategy = self.submission_strategy();
+        let mut queue = self.submission_queue.lock().await;
+
+        if queue.num_batches() >= strategy.max_queued_batches {
+            tracing::warn!(
+                "The submission queue is full with {} batches, the transactions stay in the mempool",
+                queue.num_batches()
+            );
+        } else {
+            match self.build_blob().await {
+                Ok((blob, num_txs)) => queue.push_batch(self.split_chunks(blob)?, num_txs)?,
+                // The queued batches are submitted even if there is no new one
+                Err(e) if !queue.is_empty() => {
+                    tracing::debug!("No new batch to queue: {:?}", e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.submit_queued_blobs(&mut queue, &strategy).await
+    }
+
+    /// Submits the queued blobs in order, until the queue is empty or the next blob has to wait.
+    async fn submit_queued_blobs(
+        &self,
+        queue: &mut SubmissionQueue,
+        strategy: &SubmissionStrategy,
+    ) -> anyhow::Result<BatchSubmission> {
+        let mut num_txs = 0;
+        while let Some(mut blob) = queue.pop_front() {
+            match self.submit_queued_blob(&mut blob, queue, strategy).await {
+                Ok(BlobSubmission::Submitted) => {
+                    num_txs += blob.stored.num_txs as usize;
+                    queue.confirm(&blob)?;
+                }
+                Ok(BlobSubmission::Delayed { fee_rate }) => {
+                    queue.push_front(blob);
+                    tracing::info!(
+                        "Delaying {} transactions, DA fee rate {} is above the ceiling",
+                        queue.num_txs(),
+                        fee_rate
+                    );
+                    return Ok(BatchSubmission::Delayed {
+                        num_txs: queue.num_txs(),
+                        fee_rate,
+                    });
+                }
+                Ok(BlobSubmission::Waiting { retry_in }) => {
+                    queue.push_front(blob);
+                    return Ok(BatchSubmission::Queued {
+                        num_txs: queue.num_txs(),
+                        retry_in,
+                    });
+                }
+                Err(e) => {
+                    let retry_in = blob.schedule_retry(strategy);
+                    queue.push_front(blob);
+                    tracing::warn!(
+                        "Failed to submit a blob, retrying in {:?}: {:?}",
+                        retry_in,
+                        e
+                    );
+                    return Err(e.context(format!(
+                        "{} transactions are queued, the submission is retried in {:?}",
+                        queue.num_txs(),
+                        retry_in
+                    )));
+                }
+            }
+        }
+        Ok(BatchSubmission::Submitted { num_txs })
+    }
+
+    /// Submits a blob taken from the queue, unless it has to wait.
+    async fn submit_queued_blob(
+        &self,
+        blob: &mut QueuedBlob,
+        queue: &SubmissionQueue,
+        strategy: &SubmissionStrategy,
+    ) -> anyhow::Result<BlobSubmission> {
+        if let Some(retry_at) = blob.retry_at {
+            let now = Instant::now();
+            if retry_at > now {
+                return Ok(BlobSubmission::Waiting {
+                    retry_in: retry_at - now,
+                });
+            }
+        }
+        // A recovered blob is only sent again if it didn't land, so that it isn't posted twice
+        if blob.recovered {
+            if let Some(first_attempt_height) = blob.stored.first_attempt_height {
+                if self
+                    .landed_on_da(&blob.stored.blob, first_attempt_height)
+                    .await?
+                {
+                    tracing::info!("A recovered blob already landed on DA, it isn't sent again");
+                    return Ok(BlobSubmission::Submitted);
+                }
+            }
+            blob.recovered = false;
         }
+
+        let estimate = self
+            .da_service
+            .estimate_fee(blob.stored.blob.len())
+            .await
+            .map_err(|e| anyhow!("failed to estimate DA fee: {:?}", e))?;
+        if strategy.should_delay(&estimate, blob.delayed_since) {
+            blob.delayed_since.get_or_insert_with(Instant::now);
+            return Ok(BlobSubmission::Delayed {
+                fee_rate: estimate.fee_rate,
+            });
+        }
+
+        if blob.stored.first_attempt_height.is_none() {
+            let head = self
+                .da_service
+                .get_head_block_header()
+                .await
+                .map_err(|e| anyhow!("failed to get DA head: {:?}", e))?;
+            blob.stored.first_attempt_height = Some(head.height());
+            queue.persist(blob)?;
+        }
+
+        let submission_started_at = Instant::now();
+        let result = self.send_blob(&blob.stored.blob, estimate.fee_rate).await;
+        DA_SUBMISSION_LATENCY_SECONDS.observe(submission_started_at.elapsed().as_secs_f64());
+        result.map(|_| BlobSubmission::Submitted)
     }
 
-    async fn submit_batch(&self) -> anyhow::Result<usize> {
+    /// Returns whether the blob is in one of the DA blocks from `from_height` to the head.
+    async fn landed_on_da(&self, blob: &[u8], from_height: u64) -> anyhow::Result<bool> {
+        let head = self
+            .da_service
+            .get_head_block_header()
+            .await
+            .map_err(|e| anyhow!("failed to get DA head: {:?}", e))?;
+        // The blob was sent after `from_height` was the head
+        for height in from_height + 1..=head.height() {
+            let block = self
+                .da_service
+                .get_block_at(height)
+                .await
+                .map_err(|e| anyhow!("failed to get DA block {}: {:?}", height, e))?;
+            for mut landed in self.da_service.extract_relevant_blobs(&block) {
+                if landed.full_data() == blob {
+                    return Ok(true);
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// The [`SubmissionStrategy`] with the DA fee rate ceiling of the current settings,
+    /// or the one set with `admin_setDaFeeCeiling`.
+    fn submission_strategy(&self) -> SubmissionStrategy {
+        let mut strategy = self.submission_strategy.clone();
+        if let Some(settings) = &self.settings {
+            strategy.max_fee_rate = settings().max_fee_rate;
+        }
+        if let Some(max_fee_rate) = *self.max_fee_rate_override.lock().unwrap() {
+            strategy.max_fee_rate = max_fee_rate;
+        }
+        strategy
+    }
+
+    /// Builds the next batch, returning it serialized along with its number of transactions.
+    async fn build_blob(&self) -> anyhow::Result<(Vec<u8>, usize)> {
         // Need to release lock before await, so the Future is `Send`.
         // But potentially it can create blobs that are sent out of order.
         // It can be improved with atomics,
         // so a new batch is only created after previous was submitted.
-        tracing::info!("Submit batch request has been received!");
         let blob = {
             let mut batch_builder = self
                 .batch_builder
                 .lock()
                 .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
-            batch_builder.get_next_blob()?
+            let blob = batch_builder.get_next_blob()?;
+            update_mempool_size_metric(&*batch_builder);
+            blob
         };
         let num_txs = blob.len();
-        let blob: Vec<u8> = borsh::to_vec(&blob)?;
 
-        match self.da_service.send_transaction(&blob).await {
-            Ok(_) => Ok(num_txs),
-            Err(e) => Err(anyhow!("failed to submit batch: {:?}", e)),
+        if let Some(soft_confirmations) = &self.soft_confirmations {
+            let head = self
+                .da_service
+                .get_head_block_header()
+                .await
+                .map_err(|e| anyhow!("failed to get DA head: {:?}", e))?;
+            soft_confirmations.produce(head.height(), head.hash().into(), blob.clone())?;
+        }
+
+        let mut blob: Vec<u8> = borsh::to_vec(&blob)?;
+        if let Some(level) = self.compression_level {
+            let uncompressed_len = blob.len();
+            blob = compress_blob(&blob, level)?;
+            tracing::debug!(
+                "Compressed batch from {} to {} bytes",
+                uncompressed_len,
+                blob.len()
+            );
         }
+
+        Ok((blob, num_txs))
+    }
+
+    /// Splits the blob into chunks posted one after the other,
+    /// if it's larger than the maximum blob size of the DA layer.
+    fn split_chunks(&self, blob: Vec<u8>) -> anyhow::Result<Vec<Vec<u8>>> {
+        let Some(max_blob_size) = self.da_service.max_blob_size() else {
+            return Ok(vec![blob]);
+        };
+        let chunks = split_blob(&blob, max_blob_size)?;
+        if chunks.len() > 1 {
+            tracing::info!(
+                "Splitting batch of {} bytes into a manifest and {} chunks",
+                blob.len(),
+                chunks.len() - 1
+            );
+        }
+        Ok(chunks)
+    }
+
+    /// Sends the blob to DA, bumping the fee of its transaction every time it's stuck.
+    ///
+    /// The blob is sent only once: a stuck transaction is tracked by its id, and its fee is bumped
+    /// with [`DaService::bump_fee`], so that the blob can't land twice. For the same reason, the
+    /// submission succeeds once the blob is sent, even if it's still stuck after the last bump.
+    async fn send_blob(&self, blob: &[u8], mut fee_rate: f64) -> anyhow::Result<()> {
+        let mut tx_id = self
+            .da_service
+            .send_transaction_with_fee_rate(blob, fee_rate)
+            .await
+            .map_err(|e| anyhow!("failed to submit batch: {:?}", e))?;
+
+        let strategy = &self.submission_strategy;
+        let Some(timeout) = strategy.stuck_timeout else {
+            return Ok(());
+        };
+        for fee_bumps in 0..=strategy.max_fee_bumps {
+            tokio::time::sleep(timeout).await;
+            match self.da_service.is_transaction_included(&tx_id).await {
+                Ok(true) => return Ok(()),
+                Ok(false) => {}
+                Err(e) => {
+                    tracing::warn!("Could not check the inclusion of a batch: {:?}", e);
+                    return Ok(());
+                }
+            }
+            if fee_bumps == strategy.max_fee_bumps {
+                break;
+            }
+
+            fee_rate = strategy.bumped_fee_rate(fee_rate);
+            tracing::warn!(
+                "Batch submission is stuck after {:?}, bumping its fee rate to {}",
+                timeout,
+                fee_rate
+            );
+            tx_id = match self.da_service.bump_fee(tx_id, fee_rate).await {
+                Ok(tx_id) => tx_id,
+                Err(e) => {
+                    tracing::warn!("Could not bump the fee of a stuck batch: {:?}", e);
+                    return Ok(());
+                }
+            };
+        }
+        tracing::warn!(
+            "Batch submission is still stuck after {} fee bumps, leaving it to the DA layer",
+            strategy.max_fee_bumps
+        );
+        Ok(())
     }
 
     fn accept_tx(&self, tx: Vec<u8>) -> anyhow::Result<()> {
@@ -59,16 +438,94 @@ impl<B: BatchBuilder + Send + Sync, T: DaService + Send + Sync> Sequencer<B, T>
             .batch_builder
             .lock()
             .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        if let Some(settings) = &self.settings {
+            batch_builder.set_mempool_max_txs_count(settings().mempool_max_txs_count);
+        }
         batch_builder.accept_tx(tx)?;
+        update_mempool_size_metric(&*batch_builder);
+        Ok(())
+    }
+
+    fn get_soft_batch(&self, number: u64) -> anyhow::Result<Option<SignedSoftConfirmationBatch>> {
+        match &self.soft_confirmations {
+            Some(soft_confirmations) => soft_confirmations.get(number),
+            None => Err(anyhow!("soft confirmations are disabled")),
+        }
+    }
+
+    fn txpool_content(&self) -> anyhow::Result<TxPoolContent> {
+        let batch_builder = self
+            .batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        Ok(batch_builder.txpool_content())
+    }
+
+    fn txpool_status(&self) -> anyhow::Result<TxPoolStatus> {
+        let batch_builder = self
+            .batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        Ok(batch_builder.txpool_status())
+    }
+
+    fn set_batch_production_paused(&self, paused: bool) {
+        tracing::info!(
+            "Batch production is {}",
+            if paused { "paused" } else { "resumed" }
+        );
+        self.batch_production_paused.store(paused, Ordering::SeqCst);
+    }
+
+    fn set_max_fee_rate(&self, max_fee_rate: Option<f64>) -> anyhow::Result<()> {
+        if let Some(max_fee_rate) = max_fee_rate {
+            if !max_fee_rate.is_finite() || max_fee_rate <= 0.0 {
+                return Err(anyhow!(
+                    "the DA fee rate ceiling must be a positive number, got {}",
+                    max_fee_rate
+                ));
+            }
+        }
+        tracing::info!("DA fee rate ceiling set to {:?}", max_fee_rate);
+        *self
+            .max_fee_rate_override
+            .lock()
+            .map_err(|e| anyhow!("failed to lock DA fee rate ceiling: {}", e.to_string()))? =
+            Some(max_fee_rate);
         Ok(())
     }
+
+    fn evict_tx(&self, hash: &str) -> anyhow::Result<bool> {
+        let mut batch_builder = self
+            .batch_builder
+            .lock()
+            .map_err(|e| anyhow!("failed to lock mempool: {}", e.to_string()))?;
+        let evicted = batch_builder.evict_tx(hash);
+        if evicted {
+            tracing::info!("Evicted tx {} from the mempool", hash);
+            update_mempool_size_metric(&*batch_builder);
+        }
+        Ok(evicted)
+    }
+
+    fn rotate_soft_confirmation_key(&self, private_key: &[u8]) -> anyhow::Result<Vec<u8>> {
+        match &self.soft_confirmations {
+            Some(soft_confirmations) => soft_confirmations.rotate_key(private_key),
+            None => Err(anyhow!("soft confirmations are disabled")),
+        }
+    }
+}
+
+fn update_mempool_size_metric(pool: &impl TxPool) {
+    let status = pool.txpool_status();
+    MEMPOOL_SIZE.set((status.pending + status.queued) as i64);
 }
 
 fn register_txs_rpc_methods<B, D>(
     rpc: &mut RpcModule<Sequencer<B, D>>,
 ) -> Result<(), jsonrpsee::core::Error>
 where
-    B: BatchBuilder + Send + Sync + 'static,
+    B: BatchBuilder + TxPool + Send + Sync + 'static,
     D: DaService,
 {
     rpc.register_async_method(
@@ -80,12 +537,25 @@ where
                     .accept_tx(tx)
                     .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))?;
             }
-            let num_txs = batch_builder
+            let submission = batch_builder
                 .submit_batch()
                 .await
                 .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))?;
 
-            Ok::<String, ErrorObjectOwned>(format!("Submitted {} transactions", num_txs))
+            Ok::<String, ErrorObjectOwned>(match submission {
+                BatchSubmission::Submitted { num_txs } => {
+                    format!("Submitted {} transactions", num_txs)
+                }
+                BatchSubmission::Delayed { num_txs, fee_rate } => format!(
+                    "Delayed {} transactions, the DA fee rate {} is too high",
+                    num_txs, fee_rate
+                ),
+                BatchSubmission::Queued { num_txs, retry_in } => format!(
+                    "Queued {} transactions, the submission is retried in {} seconds",
+                    num_txs,
+                    retry_in.as_secs()
+                ),
+            })
         },
     )?;
     rpc.register_method("sequencer_acceptTx", move |params, sequencer| {
@@ -96,6 +566,63 @@ where
         };
         Ok::<_, ErrorObjectOwned>(response)
     })?;
+    rpc.register_method("sequencer_getSoftBatch", move |params, sequencer| {
+        let number: u64 = params.one()?;
+        sequencer
+            .get_soft_batch(number)
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+    rpc.register_method("txpool_content", move |_, sequencer| {
+        sequencer
+            .txpool_content()
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+    rpc.register_method("txpool_status", move |_, sequencer| {
+        sequencer
+            .txpool_status()
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+
+    Ok(())
+}
+
+/// Registers the `admin_` methods operating the sequencer. The node only serves them on its
+/// authenticated admin RPC server, if it's enabled.
+fn register_admin_rpc_methods<B, D>(
+    rpc: &mut RpcModule<Sequencer<B, D>>,
+) -> Result<(), jsonrpsee::core::Error>
+where
+    B: BatchBuilder + TxPool + Send + Sync + 'static,
+    D: DaService,
+{
+    rpc.register_method("admin_pauseBatchProduction", |_, sequencer| {
+        sequencer.set_batch_production_paused(true);
+        Ok::<_, ErrorObjectOwned>(())
+    })?;
+    rpc.register_method("admin_resumeBatchProduction", |_, sequencer| {
+        sequencer.set_batch_production_paused(false);
+        Ok::<_, ErrorObjectOwned>(())
+    })?;
+    rpc.register_method("admin_setDaFeeCeiling", |params, sequencer| {
+        let max_fee_rate: Option<f64> = params.one()?;
+        sequencer
+            .set_max_fee_rate(max_fee_rate)
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+    rpc.register_method("admin_evictMempoolTx", |params, sequencer| {
+        let hash: String = params.one()?;
+        sequencer
+            .evict_tx(&hash)
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
+    rpc.register_method("admin_rotateSequencerKey", |params, sequencer| {
+        let private_key: String = params.one()?;
+        hex::decode(private_key.trim_start_matches("0x"))
+            .map_err(anyhow::Error::from)
+            .and_then(|private_key| sequencer.rotate_soft_confirmation_key(&private_key))
+            .map(|public_key| format!("0x{}", hex::encode(public_key)))
+            .map_err(|e| to_jsonrpsee_error_object(e, SEQUENCER_RPC_ERROR))
+    })?;
 
     Ok(())
 }
@@ -103,12 +630,36 @@ where
 /// Creates an RPC module with the sequencer's methods
 pub fn get_sequencer_rpc<B, D>(batch_builder: B, da_service: D) -> RpcModule<Sequencer<B, D>>
 where
-    B: BatchBuilder + Send + Sync + 'static,
+    B: BatchBuilder + TxPool + Send + Sync + 'static,
+    D: DaService,
+{
+    create_sequencer_rpc(Sequencer::new(batch_builder, da_service))
+}
+
+/// Creates an RPC module with the sequencer's methods, where every submitted batch is also given out
+/// as a soft confirmation signed by `signer`.
+pub fn get_sequencer_rpc_with_soft_confirmations<B, D>(
+    batch_builder: B,
+    da_service: D,
+    signer: impl SoftConfirmationSigner + 'static,
+) -> RpcModule<Sequencer<B, D>>
+where
+    B: BatchBuilder + TxPool + Send + Sync + 'static,
+    D: DaService,
+{
+    create_sequencer_rpc(Sequencer::new(batch_builder, da_service).with_soft_confirmations(signer))
+}
+
+/// Creates an RPC module with the methods of an already configured sequencer.
+pub fn create_sequencer_rpc<B, D>(sequencer: Sequencer<B, D>) -> RpcModule<Sequencer<B, D>>
+where
+    B: BatchBuilder + TxPool + Send + Sync + 'static,
     D: DaService,
 {
-    let sequencer = Sequencer::new(batch_builder, da_service);
     let mut rpc = RpcModule::new(sequencer);
     register_txs_rpc_methods::<B, D>(&mut rpc).expect("Failed to register sequencer RPC methods");
+    register_admin_rpc_methods::<B, D>(&mut rpc)
+        .expect("Failed to register sequencer admin RPC methods");
     rpc
 }
 
@@ -137,7 +688,12 @@ pub enum SubmitTransactionResponse {
 #[cfg(test)]
 mod tests {
 
+    use crate::soft_confirmation::PrivateKeySigner;
     use sov_mock_da::{MockAddress, MockDaService};
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::PrivateKey;
+    use sov_rollup_interface::chunking::{decode_chunk, PendingBatch};
     use sov_rollup_interface::da::BlobReaderTrait;
 
     use super::*;
@@ -174,6 +730,27 @@ mod tests {
         }
     }
 
+    impl TxPool for MockBatchBuilder {
+        // Mock transactions have no sender, so they're reported as queued under the empty one
+        fn txpool_content(&self) -> TxPoolContent {
+            let mut content = TxPoolContent::default();
+            for (index, tx) in self.mempool.iter().enumerate() {
+                content.queued.entry(String::new()).or_default().insert(
+                    index as u64,
+                    mempool::PooledTxInfo {
+                        hash: hex::encode(tx),
+                        sender: String::new(),
+                        nonce: index as u64,
+                        gas_tip: 0,
+                        gas_limit: 0,
+                        size: tx.len(),
+                    },
+                );
+            }
+            content
+        }
+    }
+
     #[tokio::test]
     async fn test_submit_on_empty_mempool() {
         let batch_builder = MockBatchBuilder { mempool: vec![] };
@@ -214,6 +791,311 @@ mod tests {
         assert_eq!(expected, block_data);
     }
 
+    #[tokio::test]
+    async fn test_submit_compressed_batch() {
+        let tx = vec![7; 100];
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![tx.clone(), tx.clone()],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone()).with_compression(3),
+        );
+
+        let arg: &[u8] = &[];
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+
+        let mut submitted_block = da_service.get_block_at(1).await.unwrap();
+        let block_data = submitted_block.blobs[0].full_data();
+        assert!(sov_rollup_interface::compression::is_compressed(block_data));
+
+        let blob: Vec<Vec<u8>> = vec![vec![tx[0]], vec![tx[0]]];
+        let expected: Vec<u8> = borsh::to_vec(&blob).unwrap();
+        assert_eq!(
+            expected,
+            sov_rollup_interface::compression::decompress_blob(block_data)
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_chunked_batch() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![7]; 100],
+        };
+        let da_service = MockDaService::new(MockAddress::default()).with_max_blob_size(200);
+        let rpc = create_sequencer_rpc(Sequencer::new(batch_builder, da_service.clone()));
+
+        let arg: &[u8] = &[];
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+
+        // The manifest and the 4 chunks are posted in separate transactions
+        let mut pending: Option<PendingBatch> = None;
+        for height in 1..=5 {
+            let mut block = da_service.get_block_at(height).await.unwrap();
+            let data = block.blobs[0].full_data();
+            assert!(data.len() <= 200);
+            let envelope = decode_chunk(data).unwrap();
+            pending
+                .get_or_insert_with(|| PendingBatch::new(envelope.batch_hash()))
+                .insert(envelope)
+                .unwrap();
+        }
+
+        let blob: Vec<Vec<u8>> = vec![vec![7]; 100];
+        assert_eq!(
+            borsh::to_vec(&blob).unwrap(),
+            pending.unwrap().assemble().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submit_delayed_until_fee_drops() {
+        let tx = vec![1, 2, 3];
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![tx.clone()],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone()).with_submission_strategy(
+                SubmissionStrategy {
+                    max_fee_rate: Some(10.0),
+                    ..Default::default()
+                },
+            ),
+        );
+
+        da_service.set_fee_rate(20.0);
+        let arg: &[u8] = &[];
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!(
+            "Delayed 1 transactions, the DA fee rate 20 is too high",
+            result
+        );
+        assert_eq!(
+            0,
+            da_service.get_head_block_header().await.unwrap().height()
+        );
+
+        // The delayed batch is submitted once the fee rate drops, even with an empty mempool
+        da_service.set_fee_rate(5.0);
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 1 transactions", result);
+
+        let mut submitted_block = da_service.get_block_at(1).await.unwrap();
+        let expected: Vec<u8> = borsh::to_vec(&vec![vec![tx[0]]]).unwrap();
+        assert_eq!(expected, submitted_block.blobs[0].full_data());
+    }
+
+    #[tokio::test]
+    async fn test_failed_submission_is_retried_with_backoff() {
+        let tx = vec![1, 2, 3];
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![tx.clone()],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone()).with_submission_strategy(
+                SubmissionStrategy {
+                    retry_backoff: Duration::from_millis(200),
+                    ..Default::default()
+                },
+            ),
+        );
+
+        da_service.inject_fault(sov_mock_da::Fault::Reject);
+        let arg: &[u8] = &[];
+        let result: Result<String, jsonrpsee::core::Error> =
+            rpc.call("sequencer_publishBatch", arg).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("1 transactions are queued"));
+
+        // The batch isn't lost, it waits for the backoff before it's retried
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!(
+            "Queued 1 transactions, the submission is retried in 0 seconds",
+            result
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 1 transactions", result);
+
+        let mut submitted_block = da_service.get_block_at(1).await.unwrap();
+        let expected: Vec<u8> = borsh::to_vec(&vec![vec![tx[0]]]).unwrap();
+        assert_eq!(expected, submitted_block.blobs[0].full_data());
+    }
+
+    #[tokio::test]
+    async fn test_stuck_submission_is_fee_bumped_not_sent_again() {
+        let tx = vec![1, 2, 3];
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![tx.clone()],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone()).with_submission_strategy(
+                SubmissionStrategy {
+                    stuck_timeout: Some(Duration::from_millis(50)),
+                    ..Default::default()
+                },
+            ),
+        );
+
+        da_service.inject_fault(sov_mock_da::Fault::Delay { blocks: 10 });
+        let arg: &[u8] = &[];
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 1 transactions", result);
+
+        // The bumped transaction lands once, instead of a second copy of the blob
+        assert_eq!(
+            1,
+            da_service.get_head_block_header().await.unwrap().height()
+        );
+        let mut submitted_block = da_service.get_block_at(1).await.unwrap();
+        let expected: Vec<u8> = borsh::to_vec(&vec![vec![tx[0]]]).unwrap();
+        assert_eq!(expected, submitted_block.blobs[0].full_data());
+    }
+
+    #[tokio::test]
+    async fn test_full_submission_queue_keeps_txs_in_mempool() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![1, 2, 3]],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone()).with_submission_strategy(
+                SubmissionStrategy {
+                    max_fee_rate: Some(10.0),
+                    max_queued_batches: 1,
+                    ..Default::default()
+                },
+            ),
+        );
+
+        da_service.set_fee_rate(20.0);
+        let arg: &[u8] = &[];
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!(
+            "Delayed 1 transactions, the DA fee rate 20 is too high",
+            result
+        );
+
+        let result: String = rpc
+            .call("sequencer_publishBatch", [vec![4u8, 5, 6]])
+            .await
+            .unwrap();
+        assert_eq!(
+            "Delayed 1 transactions, the DA fee rate 20 is too high",
+            result
+        );
+        let status: TxPoolStatus = rpc.call("txpool_status", arg).await.unwrap();
+        assert_eq!(1, status.queued);
+
+        // Once the queued batch is submitted, the next one is built from the mempool
+        da_service.set_fee_rate(5.0);
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 1 transactions", result);
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 1 transactions", result);
+        let mut submitted_block = da_service.get_block_at(2).await.unwrap();
+        assert_eq!(
+            borsh::to_vec(&vec![vec![4u8]]).unwrap(),
+            submitted_block.blobs[0].full_data()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queued_blobs_are_resubmitted_after_restart() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let da_service = MockDaService::new(MockAddress::default());
+        let arg: &[u8] = &[];
+        let first_tx = vec![1, 2, 3];
+        {
+            let batch_builder = MockBatchBuilder {
+                mempool: vec![first_tx.clone()],
+            };
+            let db = SubmissionQueueDB::with_path(tmpdir.path()).unwrap();
+            let rpc = create_sequencer_rpc(
+                Sequencer::new(batch_builder, da_service.clone())
+                    .with_submission_queue_db(db)
+                    .unwrap(),
+            );
+            da_service.inject_fault(sov_mock_da::Fault::Reject);
+            let result: Result<String, jsonrpsee::core::Error> =
+                rpc.call("sequencer_publishBatch", arg).await;
+            assert!(result.is_err());
+        }
+
+        // A blob which landed on DA before the restart, but wasn't removed from the queue
+        let landed_blob = borsh::to_vec(&vec![vec![7u8]]).unwrap();
+        da_service.send_transaction(&landed_blob).await.unwrap();
+        let db = SubmissionQueueDB::with_path(tmpdir.path()).unwrap();
+        db.put(
+            100,
+            &sov_db::schema::types::StoredQueuedBlob {
+                blob: landed_blob,
+                num_txs: 1,
+                ends_batch: true,
+                first_attempt_height: Some(0),
+            },
+        )
+        .unwrap();
+
+        let batch_builder = MockBatchBuilder { mempool: vec![] };
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone())
+                .with_submission_queue_db(db.clone())
+                .unwrap(),
+        );
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 2 transactions", result);
+
+        // The failed blob is resubmitted, but the landed one isn't posted again
+        assert_eq!(
+            2,
+            da_service.get_head_block_header().await.unwrap().height()
+        );
+        let mut submitted_block = da_service.get_block_at(2).await.unwrap();
+        assert_eq!(
+            borsh::to_vec(&vec![vec![first_tx[0]]]).unwrap(),
+            submitted_block.blobs[0].full_data()
+        );
+        assert!(db.load().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fee_ceiling_is_read_from_settings() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![1, 2, 3]],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let max_fee_rate = std::sync::Arc::new(Mutex::new(Some(10.0)));
+        let settings = max_fee_rate.clone();
+        let rpc = create_sequencer_rpc(
+            Sequencer::new(batch_builder, da_service.clone()).with_settings(move || {
+                SequencerSettings {
+                    mempool_max_txs_count: usize::MAX,
+                    max_fee_rate: *settings.lock().unwrap(),
+                }
+            }),
+        );
+
+        da_service.set_fee_rate(20.0);
+        let arg: &[u8] = &[];
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!(
+            "Delayed 1 transactions, the DA fee rate 20 is too high",
+            result
+        );
+
+        *max_fee_rate.lock().unwrap() = Some(30.0);
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 1 transactions", result);
+    }
+
     #[tokio::test]
     async fn test_accept_tx() {
         let batch_builder = MockBatchBuilder { mempool: vec![] };
@@ -239,6 +1121,129 @@ mod tests {
         assert_eq!(expected, block_data);
     }
 
+    #[tokio::test]
+    async fn test_txpool_status() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![1, 2, 3], vec![3, 4, 5]],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = get_sequencer_rpc(batch_builder, da_service);
+
+        let arg: &[u8] = &[];
+        let status: TxPoolStatus = rpc.call("txpool_status", arg).await.unwrap();
+        assert_eq!(
+            TxPoolStatus {
+                pending: 0,
+                queued: 2
+            },
+            status
+        );
+
+        let content: TxPoolContent = rpc.call("txpool_content", arg).await.unwrap();
+        assert_eq!(3, content.queued[""][&1].size);
+    }
+
+    #[tokio::test]
+    async fn test_soft_confirmations() {
+        let tx = vec![1, 2, 3];
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![tx.clone()],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = get_sequencer_rpc_with_soft_confirmations(
+            batch_builder,
+            da_service.clone(),
+            PrivateKeySigner::<DefaultContext>::new(DefaultPrivateKey::generate()),
+        );
+
+        let arg: &[u8] = &[];
+        let _: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+
+        let soft_batch: Option<SignedSoftConfirmationBatch> =
+            rpc.call("sequencer_getSoftBatch", [0u64]).await.unwrap();
+        let soft_batch = soft_batch.unwrap();
+        assert_eq!(vec![vec![tx[0]]], soft_batch.txs);
+        assert_eq!(0, soft_batch.da_slot_height);
+
+        // The soft batch and the blob sent to DA have the same transactions
+        let mut submitted_block = da_service.get_block_at(1).await.unwrap();
+        let block_data = submitted_block.blobs[0].full_data();
+        assert_eq!(borsh::to_vec(&soft_batch.txs).unwrap(), block_data);
+
+        let missing: Option<SignedSoftConfirmationBatch> =
+            rpc.call("sequencer_getSoftBatch", [1u64]).await.unwrap();
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_admin_methods() {
+        let batch_builder = MockBatchBuilder {
+            mempool: vec![vec![1, 2, 3], vec![4, 5, 6]],
+        };
+        let da_service = MockDaService::new(MockAddress::default());
+        let rpc = get_sequencer_rpc_with_soft_confirmations(
+            batch_builder,
+            da_service.clone(),
+            PrivateKeySigner::<DefaultContext>::new(DefaultPrivateKey::generate()),
+        );
+        let arg: &[u8] = &[];
+
+        let _: () = rpc.call("admin_pauseBatchProduction", arg).await.unwrap();
+        let result: Result<String, jsonrpsee::core::Error> =
+            rpc.call("sequencer_publishBatch", arg).await;
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("batch production is paused"));
+        let _: () = rpc.call("admin_resumeBatchProduction", arg).await.unwrap();
+
+        // The ceiling set by the admin overrides the submission strategy
+        da_service.set_fee_rate(20.0);
+        let _: () = rpc.call("admin_setDaFeeCeiling", [10.0]).await.unwrap();
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!(
+            "Delayed 2 transactions, the DA fee rate 20 is too high",
+            result
+        );
+        let result: Result<(), jsonrpsee::core::Error> =
+            rpc.call("admin_setDaFeeCeiling", [-1.0]).await;
+        assert!(result.is_err());
+        let _: () = rpc
+            .call("admin_setDaFeeCeiling", [None::<f64>])
+            .await
+            .unwrap();
+        let result: String = rpc.call("sequencer_publishBatch", arg).await.unwrap();
+        assert_eq!("Submitted 2 transactions", result);
+
+        // The next soft batches are signed with the rotated key
+        let private_key = DefaultPrivateKey::generate();
+        let public_key: String = rpc
+            .call("admin_rotateSequencerKey", [private_key.as_hex()])
+            .await
+            .unwrap();
+        assert_eq!(
+            format!(
+                "0x{}",
+                hex::encode(borsh::to_vec(&private_key.pub_key()).unwrap())
+            ),
+            public_key
+        );
+        let _: String = rpc
+            .call("sequencer_publishBatch", [vec![7u8]])
+            .await
+            .unwrap();
+        let soft_batch: Option<SignedSoftConfirmationBatch> =
+            rpc.call("sequencer_getSoftBatch", [1u64]).await.unwrap();
+        assert_eq!(
+            borsh::to_vec(&private_key.pub_key()).unwrap(),
+            soft_batch.unwrap().pub_key
+        );
+
+        // The mock batch builder can't evict transactions
+        let evicted: bool = rpc.call("admin_evictMempoolTx", ["0x01"]).await.unwrap();
+        assert!(!evicted);
+    }
+
     #[tokio::test]
     #[ignore = "TBD"]
     async fn test_full_flow() {}
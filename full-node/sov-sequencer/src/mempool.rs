@@ -0,0 +1,512 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+
+/// Minimal increase of the gas tip, in percents, required to replace a pooled transaction.
+pub const DEFAULT_PRICE_BUMP: u64 = 10;
+
+/// Metadata of a transaction stored in the [`Mempool`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PooledTxInfo {
+    /// Hex encoded hash of the raw transaction.
+    pub hash: String,
+    /// The sender of the transaction.
+    pub sender: String,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The gas tip offered to the sequencer.
+    pub gas_tip: u64,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// Size of the raw transaction in bytes.
+    pub size: usize,
+}
+
+/// Transactions grouped by sender and nonce, as returned by `txpool_content`.
+pub type TxsBySender = BTreeMap<String, BTreeMap<u64, PooledTxInfo>>;
+
+/// Response of the `txpool_content` RPC method.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxPoolContent {
+    /// Transactions that can be included in the next batch.
+    pub pending: TxsBySender,
+    /// Transactions waiting for a missing nonce of their sender.
+    pub queued: TxsBySender,
+}
+
+/// Response of the `txpool_status` RPC method.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxPoolStatus {
+    /// Number of transactions that can be included in the next batch.
+    pub pending: usize,
+    /// Number of transactions waiting for a missing nonce of their sender.
+    pub queued: usize,
+}
+
+impl From<&TxPoolContent> for TxPoolStatus {
+    fn from(content: &TxPoolContent) -> Self {
+        let count = |txs: &TxsBySender| txs.values().map(BTreeMap::len).sum();
+        Self {
+            pending: count(&content.pending),
+            queued: count(&content.queued),
+        }
+    }
+}
+
+/// Batch builders exposing their mempool through the `txpool_*` RPC methods.
+pub trait TxPool {
+    /// Returns all the transactions in the mempool.
+    fn txpool_content(&self) -> TxPoolContent;
+
+    /// Returns the number of pending and queued transactions in the mempool.
+    fn txpool_status(&self) -> TxPoolStatus {
+        TxPoolStatus::from(&self.txpool_content())
+    }
+
+    /// Changes the maximum number of transactions in the mempool, if it's bounded.
+    /// Already pooled transactions are kept.
+    fn set_mempool_max_txs_count(&mut self, _max_txs_count: usize) {}
+
+    /// Removes the pooled transaction with the given hex encoded hash, if any, and returns
+    /// whether it was pooled. The following transactions of its sender are queued until
+    /// the nonce gap is filled.
+    fn evict_tx(&mut self, _hash: &str) -> bool {
+        false
+    }
+}
+
+/// A transaction taken out of the [`Mempool`].
+#[derive(Debug)]
+pub struct MempoolEntry<T> {
+    /// Metadata of the transaction.
+    pub info: PooledTxInfo,
+    /// The transaction itself.
+    pub tx: T,
+    /// Arrival order, used to keep batches FIFO across senders.
+    sequence: u64,
+}
+
+struct SenderQueue<T> {
+    /// The nonce of the next transaction of the sender that can be included in a batch.
+    next_nonce: u64,
+    /// Whether `next_nonce` was advanced by a transaction included in a batch, which the rollup
+    /// state may not reflect yet.
+    is_ahead_of_state: bool,
+    txs: BTreeMap<u64, MempoolEntry<T>>,
+}
+
+impl<T> SenderQueue<T> {
+    /// Number of transactions with consecutive nonces, starting from `next_nonce`.
+    fn pending_count(&self) -> usize {
+        self.txs
+            .keys()
+            .zip(self.next_nonce..)
+            .take_while(|(nonce, expected)| **nonce == *expected)
+            .count()
+    }
+}
+
+/// Mempool queueing transactions per sender, ordered by nonce.
+///
+/// A transaction is pending once all the transactions of its sender with lower nonces have been
+/// included in a batch or are pending themselves; otherwise it is queued until the gap is filled.
+/// The next nonce of a new sender is the nonce of its account in the rollup state, given on
+/// insertion, or the nonce of its first transaction if the account is unknown. Senders without
+/// transactions are forgotten by [`Mempool::prune_senders`] once the rollup state caught up with
+/// their included transactions.
+pub struct Mempool<T> {
+    senders: HashMap<String, SenderQueue<T>>,
+    max_txs_count: usize,
+    price_bump: u64,
+    len: usize,
+    next_sequence: u64,
+}
+
+impl<T> Mempool<T> {
+    /// Creates an empty mempool holding at most `max_txs_count` transactions.
+    pub fn new(max_txs_count: usize) -> Self {
+        Self {
+            senders: HashMap::new(),
+            max_txs_count,
+            price_bump: DEFAULT_PRICE_BUMP,
+            len: 0,
+            next_sequence: 0,
+        }
+    }
+
+    /// Sets the minimal gas tip increase, in percents, required to replace a transaction.
+    pub fn with_price_bump(mut self, price_bump: u64) -> Self {
+        self.price_bump = price_bump;
+        self
+    }
+
+    /// Number of transactions in the mempool.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the mempool holds no transactions.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Changes the maximum number of transactions. Already pooled transactions are kept.
+    pub fn set_max_txs_count(&mut self, max_txs_count: usize) {
+        self.max_txs_count = max_txs_count;
+    }
+
+    /// Adds a transaction to the mempool. `account_nonce` is the nonce of the account of the
+    /// sender in the rollup state, if it's known, which is only read for new senders.
+    ///
+    /// A transaction with the same sender and nonce as a pooled one replaces it, if it offers a
+    /// gas tip higher by at least the configured price bump. The replaced transaction is returned.
+    pub fn insert(
+        &mut self,
+        info: PooledTxInfo,
+        tx: T,
+        account_nonce: Option<u64>,
+    ) -> anyhow::Result<Option<MempoolEntry<T>>> {
+        let queue = self.senders.get(&info.sender);
+        let next_nonce = queue.map_or(account_nonce.unwrap_or(info.nonce), |queue| {
+            queue.next_nonce
+        });
+
+        if info.nonce < next_nonce {
+            bail!(
+                "Nonce too low, expected at least: {}, but found: {}",
+                next_nonce,
+                info.nonce
+            );
+        }
+
+        match queue.and_then(|queue| queue.txs.get(&info.nonce)) {
+            Some(existing) => {
+                let min_gas_tip = existing.info.gas_tip.saturating_mul(100 + self.price_bump) / 100;
+                if info.gas_tip <= existing.info.gas_tip || info.gas_tip < min_gas_tip {
+                    bail!(
+                        "Replacement transaction underpriced, gas tip must be at least: {}",
+                        min_gas_tip.max(existing.info.gas_tip + 1)
+                    );
+                }
+            }
+            None => {
+                if self.len >= self.max_txs_count {
+                    bail!("Mempool is full");
+                }
+                self.len += 1;
+            }
+        }
+
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+        let queue = self
+            .senders
+            .entry(info.sender.clone())
+            .or_insert_with(|| SenderQueue {
+                next_nonce,
+                is_ahead_of_state: false,
+                txs: BTreeMap::new(),
+            });
+        Ok(queue
+            .txs
+            .insert(info.nonce, MempoolEntry { info, sequence, tx }))
+    }
+
+    /// Removes and returns the pooled transactions matching `predicate`. The following
+    /// transactions of their senders are queued until the nonce gap is filled.
+    pub fn remove_where(
+        &mut self,
+        mut predicate: impl FnMut(&MempoolEntry<T>) -> bool,
+    ) -> Vec<MempoolEntry<T>> {
+        let mut removed = Vec::new();
+        for queue in self.senders.values_mut() {
+            let nonces: Vec<u64> = queue
+                .txs
+                .iter()
+                .filter(|(_, entry)| predicate(entry))
+                .map(|(nonce, _)| *nonce)
+                .collect();
+            removed.extend(nonces.iter().filter_map(|nonce| queue.txs.remove(nonce)));
+        }
+        self.len -= removed.len();
+        removed
+    }
+
+    /// Removes all the pending transactions from the mempool and returns them in arrival order,
+    /// keeping the transactions of every sender ordered by nonce.
+    ///
+    /// Transactions that don't end up in a batch should be returned with [`Mempool::reinsert`].
+    pub fn take_pending(&mut self) -> Vec<MempoolEntry<T>> {
+        let mut runs: Vec<VecDeque<MempoolEntry<T>>> = Vec::new();
+        for queue in self.senders.values_mut() {
+            let pending: Vec<u64> = queue
+                .txs
+                .keys()
+                .copied()
+                .take(queue.pending_count())
+                .collect();
+            let run: VecDeque<_> = pending
+                .into_iter()
+                .filter_map(|nonce| queue.txs.remove(&nonce))
+                .collect();
+            if !run.is_empty() {
+                runs.push(run);
+            }
+        }
+
+        let mut result = Vec::with_capacity(runs.iter().map(VecDeque::len).sum());
+        while let Some(run) = runs
+            .iter_mut()
+            .filter(|run| !run.is_empty())
+            .min_by_key(|run| run[0].sequence)
+        {
+            result.push(run.pop_front().expect("Run is not empty"));
+        }
+
+        self.len -= result.len();
+        result
+    }
+
+    /// Puts back a transaction previously taken out of the mempool, bypassing the size limit.
+    /// The transaction keeps its original place in the arrival order.
+    pub fn reinsert(&mut self, entry: MempoolEntry<T>) {
+        let queue = self
+            .senders
+            .entry(entry.info.sender.clone())
+            .or_insert_with(|| SenderQueue {
+                next_nonce: entry.info.nonce,
+                is_ahead_of_state: false,
+                txs: BTreeMap::new(),
+            });
+        if queue.txs.insert(entry.info.nonce, entry).is_none() {
+            self.len += 1;
+        }
+    }
+
+    /// Records that the transaction of `sender` with `nonce` was included in a batch,
+    /// so the next one becomes pending.
+    pub fn mark_included(&mut self, sender: &str, nonce: u64) {
+        match self.senders.get_mut(sender) {
+            Some(queue) => {
+                queue.next_nonce = queue.next_nonce.max(nonce + 1);
+                queue.is_ahead_of_state = true;
+            }
+            None => {
+                self.senders.insert(
+                    sender.to_string(),
+                    SenderQueue {
+                        next_nonce: nonce + 1,
+                        is_ahead_of_state: true,
+                        txs: BTreeMap::new(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Forgets the senders without pooled transactions, unless the rollup state doesn't include
+    /// their transactions included in a batch yet. `account_nonce` returns the nonce of the
+    /// account of a sender in the rollup state, if it's known.
+    pub fn prune_senders(&mut self, mut account_nonce: impl FnMut(&str) -> Option<u64>) {
+        self.senders.retain(|sender, queue| {
+            !queue.txs.is_empty()
+                || (queue.is_ahead_of_state
+                    && account_nonce(sender).map_or(true, |nonce| nonce < queue.next_nonce))
+        });
+    }
+
+    /// Returns the metadata of all the pooled transactions.
+    pub fn content(&self) -> TxPoolContent {
+        let mut content = TxPoolContent::default();
+        for (sender, queue) in &self.senders {
+            let pending_count = queue.pending_count();
+            for (index, (nonce, entry)) in queue.txs.iter().enumerate() {
+                let group = if index < pending_count {
+                    &mut content.pending
+                } else {
+                    &mut content.queued
+                };
+                group
+                    .entry(sender.clone())
+                    .or_default()
+                    .insert(*nonce, entry.info.clone());
+            }
+        }
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(sender: &str, nonce: u64, gas_tip: u64) -> PooledTxInfo {
+        PooledTxInfo {
+            hash: format!("{sender}-{nonce}-{gas_tip}"),
+            sender: sender.to_string(),
+            nonce,
+            gas_tip,
+            gas_limit: 0,
+            size: 1,
+        }
+    }
+
+    fn insert(mempool: &mut Mempool<u64>, sender: &str, nonce: u64, gas_tip: u64) {
+        mempool
+            .insert(info(sender, nonce, gas_tip), nonce, None)
+            .unwrap();
+    }
+
+    #[test]
+    fn nonce_gap_is_queued_until_filled() {
+        let mut mempool = Mempool::new(10);
+        insert(&mut mempool, "alice", 0, 0);
+        insert(&mut mempool, "alice", 2, 0);
+
+        let status = TxPoolStatus::from(&mempool.content());
+        assert_eq!(
+            TxPoolStatus {
+                pending: 1,
+                queued: 1
+            },
+            status
+        );
+
+        insert(&mut mempool, "alice", 1, 0);
+        let status = TxPoolStatus::from(&mempool.content());
+        assert_eq!(
+            TxPoolStatus {
+                pending: 3,
+                queued: 0
+            },
+            status
+        );
+    }
+
+    #[test]
+    fn take_pending_keeps_arrival_and_nonce_order() {
+        let mut mempool = Mempool::new(10);
+        insert(&mut mempool, "alice", 1, 0);
+        insert(&mut mempool, "bob", 5, 0);
+        insert(&mut mempool, "alice", 3, 0);
+        insert(&mut mempool, "alice", 2, 0);
+
+        let taken: Vec<_> = mempool
+            .take_pending()
+            .into_iter()
+            .map(|entry| (entry.info.sender, entry.info.nonce))
+            .collect();
+        assert_eq!(
+            vec![
+                ("alice".to_string(), 1),
+                ("bob".to_string(), 5),
+                ("alice".to_string(), 2),
+                ("alice".to_string(), 3),
+            ],
+            taken
+        );
+        assert!(mempool.is_empty());
+    }
+
+    #[test]
+    fn included_nonce_unlocks_next_one() {
+        let mut mempool = Mempool::new(10);
+        insert(&mut mempool, "alice", 0, 0);
+        let taken = mempool.take_pending().remove(0);
+        mempool.mark_included(&taken.info.sender, taken.info.nonce);
+
+        insert(&mut mempool, "alice", 1, 0);
+        assert_eq!(1, mempool.take_pending().len());
+
+        let result = mempool.insert(info("alice", 0, 0), 0, None);
+        assert_eq!(
+            "Nonce too low, expected at least: 1, but found: 0",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn replacement_requires_price_bump() {
+        let mut mempool = Mempool::new(1);
+        insert(&mut mempool, "alice", 0, 100);
+
+        let result = mempool.insert(info("alice", 0, 105), 0, None);
+        assert_eq!(
+            "Replacement transaction underpriced, gas tip must be at least: 110",
+            result.unwrap_err().to_string()
+        );
+
+        // Replacing doesn't need free space
+        insert(&mut mempool, "alice", 0, 110);
+        assert_eq!(1, mempool.len());
+        let pending = mempool.content().pending;
+        assert_eq!(110, pending["alice"][&0].gas_tip);
+    }
+
+    #[test]
+    fn removed_tx_queues_the_next_ones() {
+        let mut mempool = Mempool::new(10);
+        insert(&mut mempool, "alice", 0, 0);
+        insert(&mut mempool, "alice", 1, 5);
+        insert(&mut mempool, "alice", 2, 0);
+        insert(&mut mempool, "bob", 0, 5);
+
+        let removed = mempool.remove_where(|entry| entry.info.gas_tip == 5);
+        assert_eq!(2, removed.len());
+        assert_eq!(
+            TxPoolStatus {
+                pending: 1,
+                queued: 1
+            },
+            TxPoolStatus::from(&mempool.content())
+        );
+        assert_eq!(2, mempool.len());
+    }
+
+    #[test]
+    fn reject_new_tx_on_full_mempool() {
+        let mut mempool = Mempool::new(1);
+        insert(&mut mempool, "alice", 0, 0);
+
+        let result = mempool.insert(info("bob", 0, 0), 0, None);
+        assert_eq!("Mempool is full", result.unwrap_err().to_string());
+        assert!(mempool.content().pending.get("bob").is_none());
+    }
+
+    #[test]
+    fn new_sender_starts_at_account_nonce() {
+        let mut mempool = Mempool::new(10);
+        let result = mempool.insert(info("alice", 2, 0), 2, Some(3));
+        assert_eq!(
+            "Nonce too low, expected at least: 3, but found: 2",
+            result.unwrap_err().to_string()
+        );
+        assert!(mempool.senders.is_empty());
+
+        mempool.insert(info("alice", 4, 0), 4, Some(3)).unwrap();
+        assert_eq!(
+            TxPoolStatus {
+                pending: 0,
+                queued: 1
+            },
+            TxPoolStatus::from(&mempool.content())
+        );
+    }
+
+    #[test]
+    fn senders_are_pruned_once_the_state_caught_up() {
+        let mut mempool = Mempool::new(10);
+        insert(&mut mempool, "alice", 0, 0);
+        insert(&mut mempool, "bob", 0, 0);
+        mempool.remove_where(|entry| entry.info.sender == "bob");
+        mempool.take_pending();
+        mempool.mark_included("alice", 0);
+
+        // The nonce included in the batch is remembered until the state has it
+        mempool.prune_senders(|_| Some(0));
+        assert_eq!(vec!["alice"], mempool.senders.keys().collect::<Vec<_>>());
+        mempool.prune_senders(|_| Some(1));
+        assert!(mempool.senders.is_empty());
+    }
+}
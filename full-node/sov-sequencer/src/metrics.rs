@@ -0,0 +1,25 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_int_gauge, Histogram, IntGauge,
+};
+
+pub(crate) static DA_SUBMISSION_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "sequencer_da_submission_latency_seconds",
+        // metric description
+        "Time taken to submit a batch to the DA layer, in seconds",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 16).unwrap(),
+    )
+    .unwrap()
+});
+
+pub(crate) static MEMPOOL_SIZE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        // metric name
+        "sequencer_mempool_size",
+        // metric description
+        "Number of transactions in the sequencer mempool"
+    )
+    .unwrap()
+});
@@ -0,0 +1,139 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::mempool::MempoolEntry;
+
+/// Decides in which order the pending transactions of the mempool are considered for a batch.
+pub trait TxOrdering {
+    /// Orders the pending transactions, which are given in arrival order.
+    /// The transactions of every sender must stay ordered by nonce.
+    fn order<T>(&self, pending: Vec<MempoolEntry<T>>) -> Vec<MempoolEntry<T>>;
+}
+
+/// Considers the transactions in the order they were submitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FifoOrdering;
+
+impl TxOrdering for FifoOrdering {
+    fn order<T>(&self, pending: Vec<MempoolEntry<T>>) -> Vec<MempoolEntry<T>> {
+        pending
+    }
+}
+
+/// Considers the transactions offering the highest gas tip first.
+///
+/// A transaction can't be considered before the preceding transactions of its sender,
+/// so the next transaction of every sender competes on its gas tip.
+/// Transactions with the same gas tip are considered in arrival order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasPriceOrdering;
+
+impl TxOrdering for GasPriceOrdering {
+    fn order<T>(&self, pending: Vec<MempoolEntry<T>>) -> Vec<MempoolEntry<T>> {
+        let len = pending.len();
+        let mut runs: Vec<VecDeque<(usize, MempoolEntry<T>)>> = Vec::new();
+        let mut run_of_sender: HashMap<String, usize> = HashMap::new();
+        for (arrival, entry) in pending.into_iter().enumerate() {
+            let run = *run_of_sender
+                .entry(entry.info.sender.clone())
+                .or_insert_with(|| {
+                    runs.push(VecDeque::new());
+                    runs.len() - 1
+                });
+            runs[run].push_back((arrival, entry));
+        }
+
+        let mut ordered = Vec::with_capacity(len);
+        while let Some(run) = runs
+            .iter_mut()
+            .filter(|run| !run.is_empty())
+            .max_by_key(|run| (run[0].1.info.gas_tip, Reverse(run[0].0)))
+        {
+            ordered.push(run.pop_front().expect("Run is not empty").1);
+        }
+        ordered
+    }
+}
+
+/// The ordering of a batch builder, chosen in the rollup configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOrdering {
+    /// See [`FifoOrdering`].
+    #[default]
+    Fifo,
+    /// See [`GasPriceOrdering`].
+    GasPrice,
+}
+
+impl TxOrdering for BatchOrdering {
+    fn order<T>(&self, pending: Vec<MempoolEntry<T>>) -> Vec<MempoolEntry<T>> {
+        match self {
+            BatchOrdering::Fifo => FifoOrdering.order(pending),
+            BatchOrdering::GasPrice => GasPriceOrdering.order(pending),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mempool::{Mempool, PooledTxInfo};
+
+    fn pending(txs: &[(&str, u64, u64)]) -> Vec<MempoolEntry<()>> {
+        let mut mempool = Mempool::new(txs.len());
+        for (sender, nonce, gas_tip) in txs {
+            let info = PooledTxInfo {
+                hash: format!("{sender}-{nonce}"),
+                sender: sender.to_string(),
+                nonce: *nonce,
+                gas_tip: *gas_tip,
+                gas_limit: 0,
+                size: 1,
+            };
+            mempool.insert(info, (), None).unwrap();
+        }
+        mempool.take_pending()
+    }
+
+    fn order_of(ordering: impl TxOrdering, txs: &[(&str, u64, u64)]) -> Vec<(String, u64)> {
+        ordering
+            .order(pending(txs))
+            .into_iter()
+            .map(|entry| (entry.info.sender, entry.info.nonce))
+            .collect()
+    }
+
+    #[test]
+    fn gas_price_ordering_keeps_nonce_order() {
+        let txs = [
+            ("alice", 0, 1),
+            ("bob", 0, 5),
+            ("alice", 1, 10),
+            ("carol", 0, 5),
+        ];
+
+        assert_eq!(
+            vec![
+                ("alice".to_string(), 0),
+                ("bob".to_string(), 0),
+                ("alice".to_string(), 1),
+                ("carol".to_string(), 0),
+            ],
+            order_of(FifoOrdering, &txs)
+        );
+        // Alice's second transaction pays the most, but waits for her first one,
+        // and Bob arrived before Carol
+        assert_eq!(
+            vec![
+                ("bob".to_string(), 0),
+                ("carol".to_string(), 0),
+                ("alice".to_string(), 0),
+                ("alice".to_string(), 1),
+            ],
+            order_of(BatchOrdering::GasPrice, &txs)
+        );
+    }
+}
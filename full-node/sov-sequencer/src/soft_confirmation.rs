@@ -0,0 +1,149 @@
+use std::sync::RwLock;
+
+use anyhow::anyhow;
+use sov_modules_api::{Context, PrivateKey, Spec};
+use sov_p2p::{GossipedSoftBatch, SoftBatchGossip};
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::soft_confirmation::{
+    SignedSoftConfirmationBatch, UnsignedSoftConfirmationBatch,
+};
+
+/// Signs the soft confirmation batches given out by the sequencer.
+pub trait SoftConfirmationSigner: Send + Sync {
+    /// Signs the batch and returns it together with its hash and the sequencer public key.
+    fn sign(
+        &self,
+        batch: UnsignedSoftConfirmationBatch,
+    ) -> anyhow::Result<SignedSoftConfirmationBatch>;
+
+    /// Replaces the key signing the next batches with the given private key, in its byte
+    /// representation, and returns the borsh serialization of the new public key.
+    fn rotate_key(&mut self, _private_key: &[u8]) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow!(
+            "the soft confirmation signer doesn't support key rotation"
+        ))
+    }
+}
+
+/// A [`SoftConfirmationSigner`] using the private key of a module system [`Context`].
+pub struct PrivateKeySigner<C: Context> {
+    private_key: C::PrivateKey,
+}
+
+impl<C: Context> PrivateKeySigner<C> {
+    /// Creates a signer from the sequencer private key.
+    pub fn new(private_key: C::PrivateKey) -> Self {
+        Self { private_key }
+    }
+}
+
+impl<C: Context> SoftConfirmationSigner for PrivateKeySigner<C> {
+    fn sign(
+        &self,
+        batch: UnsignedSoftConfirmationBatch,
+    ) -> anyhow::Result<SignedSoftConfirmationBatch> {
+        let message = borsh::to_vec(&batch)?;
+        let hash: [u8; 32] = <C as Spec>::Hasher::digest(&message).into();
+        let signature = self.private_key.sign(&message);
+
+        Ok(SignedSoftConfirmationBatch::new(
+            batch,
+            hash,
+            borsh::to_vec(&signature)?,
+            borsh::to_vec(&self.private_key.pub_key())?,
+        ))
+    }
+
+    fn rotate_key(&mut self, private_key: &[u8]) -> anyhow::Result<Vec<u8>> {
+        self.private_key = C::PrivateKey::try_from(private_key)?;
+        Ok(borsh::to_vec(&self.private_key.pub_key())?)
+    }
+}
+
+/// The soft confirmation batches given out by the sequencer, numbered from 0.
+pub(crate) struct SoftConfirmations {
+    signer: RwLock<Box<dyn SoftConfirmationSigner>>,
+    batches: RwLock<Vec<SignedSoftConfirmationBatch>>,
+    pub(crate) gossip: Option<SoftBatchGossip>,
+}
+
+impl SoftConfirmations {
+    pub(crate) fn new(signer: Box<dyn SoftConfirmationSigner>) -> Self {
+        Self {
+            signer: RwLock::new(signer),
+            batches: RwLock::new(Vec::new()),
+            gossip: None,
+        }
+    }
+
+    /// Signs a new batch built on top of the given DA block and stores it under the next number.
+    /// It's also gossiped to the full nodes, if the gossip is enabled.
+    pub(crate) fn produce(
+        &self,
+        da_slot_height: u64,
+        da_slot_hash: [u8; 32],
+        txs: Vec<Vec<u8>>,
+    ) -> anyhow::Result<SignedSoftConfirmationBatch> {
+        let batch = self
+            .signer
+            .read()
+            .map_err(|e| anyhow!("failed to lock soft confirmation signer: {}", e.to_string()))?
+            .sign(UnsignedSoftConfirmationBatch {
+                da_slot_height,
+                da_slot_hash,
+                txs,
+            })?;
+
+        let mut batches = self
+            .batches
+            .write()
+            .map_err(|e| anyhow!("failed to lock soft confirmations: {}", e.to_string()))?;
+        batches.push(batch.clone());
+        let number = batches.len() as u64 - 1;
+        tracing::info!(
+            "Produced soft confirmation batch #{} with hash 0x{}",
+            number,
+            hex::encode(batch.hash)
+        );
+        if let Some(gossip) = &self.gossip {
+            // Full nodes still fetch the batches they miss from the sequencer RPC
+            if let Err(e) = gossip.publish(GossipedSoftBatch {
+                number,
+                batch: batch.clone(),
+            }) {
+                tracing::warn!(
+                    "Failed to gossip soft confirmation batch #{}: {}",
+                    number,
+                    e
+                );
+            }
+        }
+
+        Ok(batch)
+    }
+
+    /// Makes the next batches signed with the given private key, see [`SoftConfirmationSigner::rotate_key`].
+    pub(crate) fn rotate_key(&self, private_key: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let public_key = self
+            .signer
+            .write()
+            .map_err(|e| anyhow!("failed to lock soft confirmation signer: {}", e.to_string()))?
+            .rotate_key(private_key)?;
+        tracing::info!(
+            "Rotated the soft confirmation key to 0x{}",
+            hex::encode(&public_key)
+        );
+        Ok(public_key)
+    }
+
+    pub(crate) fn get(&self, number: u64) -> anyhow::Result<Option<SignedSoftConfirmationBatch>> {
+        let batches = self
+            .batches
+            .read()
+            .map_err(|e| anyhow!("failed to lock soft confirmations: {}", e.to_string()))?;
+        Ok(usize::try_from(number)
+            .ok()
+            .and_then(|number| batches.get(number))
+            .cloned())
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use sov_db::schema::types::StoredQueuedBlob;
+use sov_db::submission_queue_db::SubmissionQueueDB;
+use sov_rollup_interface::services::da::FeeEstimate;
+
+/// Decides when the batches of the sequencer are submitted to DA, and at which fee rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmissionStrategy {
+    /// Batches are delayed while the fee rate of the DA layer is above this ceiling.
+    /// If it isn't set, batches are always submitted right away.
+    pub max_fee_rate: Option<f64>,
+    /// How long a batch can be delayed. It's submitted whatever the fee rate afterwards.
+    pub max_delay: Duration,
+    /// How long a sent blob can stay out of the DA layer before it's considered stuck,
+    /// and the fee of its transaction is bumped. If it isn't set, sent blobs aren't tracked.
+    pub stuck_timeout: Option<Duration>,
+    /// The percentage by which the fee rate of a stuck submission is bumped every time.
+    pub fee_bump_percent: u32,
+    /// How many times the fee of a stuck submission is bumped before leaving it to the DA layer.
+    pub max_fee_bumps: u32,
+    /// How many batches can wait in the submission queue. Once it's full, no batch is built
+    /// and the transactions stay in the mempool, until the queued batches are submitted.
+    pub max_queued_batches: usize,
+    /// How long a failed submission waits before it's retried. The wait doubles after every
+    /// failure of the same blob, up to `max_retry_backoff`.
+    pub retry_backoff: Duration,
+    /// The longest wait before a failed submission is retried.
+    pub max_retry_backoff: Duration,
+}
+
+impl Default for SubmissionStrategy {
+    fn default() -> Self {
+        Self {
+            max_fee_rate: None,
+            max_delay: Duration::from_secs(600),
+            stuck_timeout: None,
+            fee_bump_percent: 25,
+            max_fee_bumps: 3,
+            max_queued_batches: 16,
+            retry_backoff: Duration::from_secs(5),
+            max_retry_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl SubmissionStrategy {
+    /// Returns whether a batch has to wait for a lower fee rate than the estimated one.
+    /// `delayed_since` is the moment the batch was delayed for the first time, if it was.
+    pub fn should_delay(&self, estimate: &FeeEstimate, delayed_since: Option<Instant>) -> bool {
+        let Some(max_fee_rate) = self.max_fee_rate else {
+            return false;
+        };
+        let urgent = delayed_since.is_some_and(|since| since.elapsed() >= self.max_delay);
+        estimate.fee_rate > max_fee_rate && !urgent
+    }
+
+    /// Returns the fee rate a stuck submission sent at `fee_rate` is bumped to.
+    pub fn bumped_fee_rate(&self, fee_rate: f64) -> f64 {
+        fee_rate * f64::from(100 + self.fee_bump_percent) / 100.0
+    }
+
+    /// Returns how long a blob waits before it's sent again after its `failures`-th failure.
+    pub fn retry_backoff(&self, failures: u32) -> Duration {
+        let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+        self.retry_backoff
+            .saturating_mul(factor)
+            .min(self.max_retry_backoff)
+    }
+}
+
+/// A blob waiting in the [`SubmissionQueue`].
+pub(crate) struct QueuedBlob {
+    /// The number of the blob in the queue, which is its key in the [`SubmissionQueueDB`].
+    pub(crate) number: u64,
+    pub(crate) stored: StoredQueuedBlob,
+    /// Whether the blob was loaded from the [`SubmissionQueueDB`] on startup. If it was sent
+    /// before, it may have landed on DA already.
+    pub(crate) recovered: bool,
+    /// The moment the blob was delayed for the first time because of the DA fee rate, if it was.
+    pub(crate) delayed_since: Option<Instant>,
+    /// The number of failed submissions of the blob.
+    pub(crate) failures: u32,
+    /// The moment the blob can be sent again after a failed submission.
+    pub(crate) retry_at: Option<Instant>,
+}
+
+impl QueuedBlob {
+    /// Schedules the next submission of the blob after a failed one,
+    /// and returns how long it waits for it.
+    pub(crate) fn schedule_retry(&mut self, strategy: &SubmissionStrategy) -> Duration {
+        self.failures += 1;
+        let backoff = strategy.retry_backoff(self.failures);
+        self.retry_at = Some(Instant::now() + backoff);
+        backoff
+    }
+}
+
+/// The blobs of the sequencer waiting to be submitted to DA, in the order they're submitted in.
+///
+/// With a [`SubmissionQueueDB`], the blobs are persisted until their submission succeeds,
+/// so that they're resubmitted after a restart.
+#[derive(Default)]
+pub(crate) struct SubmissionQueue {
+    blobs: VecDeque<QueuedBlob>,
+    next_number: u64,
+    db: Option<SubmissionQueueDB>,
+}
+
+impl SubmissionQueue {
+    /// Creates a queue persisted in the database, starting with the blobs persisted in it.
+    pub(crate) fn with_db(db: SubmissionQueueDB) -> anyhow::Result<Self> {
+        let blobs: VecDeque<_> = db
+            .load()?
+            .into_iter()
+            .map(|(number, stored)| QueuedBlob {
+                number,
+                stored,
+                recovered: true,
+                delayed_since: None,
+                failures: 0,
+                retry_at: None,
+            })
+            .collect();
+        if !blobs.is_empty() {
+            tracing::info!(
+                "Recovered {} blobs waiting to be submitted to DA",
+                blobs.len()
+            );
+        }
+        Ok(Self {
+            next_number: blobs.back().map_or(0, |blob| blob.number + 1),
+            blobs,
+            db: Some(db),
+        })
+    }
+
+    /// Queues the blobs of a batch with `num_txs` transactions.
+    pub(crate) fn push_batch(&mut self, blobs: Vec<Vec<u8>>, num_txs: usize) -> anyhow::Result<()> {
+        let last = blobs.len().saturating_sub(1);
+        for (i, blob) in blobs.into_iter().enumerate() {
+            let queued = QueuedBlob {
+                number: self.next_number,
+                stored: StoredQueuedBlob {
+                    blob,
+                    num_txs: if i == last { num_txs as u64 } else { 0 },
+                    ends_batch: i == last,
+                    first_attempt_height: None,
+                },
+                recovered: false,
+                delayed_since: None,
+                failures: 0,
+                retry_at: None,
+            };
+            self.persist(&queued)?;
+            self.next_number += 1;
+            self.blobs.push_back(queued);
+        }
+        Ok(())
+    }
+
+    /// Writes the blob to the database, if the queue is persisted.
+    pub(crate) fn persist(&self, blob: &QueuedBlob) -> anyhow::Result<()> {
+        match &self.db {
+            Some(db) => db.put(blob.number, &blob.stored),
+            None => Ok(()),
+        }
+    }
+
+    /// Takes the next blob to submit. It stays in the database until it's [`Self::confirm`]ed,
+    /// and has to be given back with [`Self::push_front`] if it isn't submitted.
+    pub(crate) fn pop_front(&mut self) -> Option<QueuedBlob> {
+        self.blobs.pop_front()
+    }
+
+    /// Gives back a blob taken with [`Self::pop_front`], which wasn't submitted.
+    pub(crate) fn push_front(&mut self, blob: QueuedBlob) {
+        self.blobs.push_front(blob);
+    }
+
+    /// Removes a blob taken with [`Self::pop_front`] from the database, once it's submitted.
+    pub(crate) fn confirm(&self, blob: &QueuedBlob) -> anyhow::Result<()> {
+        match &self.db {
+            Some(db) => db.delete(blob.number),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.blobs.is_empty()
+    }
+
+    /// The number of batches whose last blob is queued.
+    pub(crate) fn num_batches(&self) -> usize {
+        self.blobs
+            .iter()
+            .filter(|blob| blob.stored.ends_batch)
+            .count()
+    }
+
+    /// The number of transactions of the queued batches.
+    pub(crate) fn num_txs(&self) -> usize {
+        self.blobs
+            .iter()
+            .map(|blob| blob.stored.num_txs as usize)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(fee_rate: f64) -> FeeEstimate {
+        FeeEstimate { fee_rate, fee: 0 }
+    }
+
+    #[test]
+    fn batches_are_delayed_until_urgent() {
+        let strategy = SubmissionStrategy {
+            max_fee_rate: Some(10.0),
+            max_delay: Duration::from_secs(60),
+            ..Default::default()
+        };
+
+        assert!(!strategy.should_delay(&estimate(10.0), None));
+        assert!(strategy.should_delay(&estimate(10.5), None));
+        assert!(strategy.should_delay(&estimate(10.5), Some(Instant::now())));
+        let long_ago = Instant::now() - Duration::from_secs(61);
+        assert!(!strategy.should_delay(&estimate(10.5), Some(long_ago)));
+
+        assert!(!SubmissionStrategy::default().should_delay(&estimate(1_000.0), None));
+    }
+
+    #[test]
+    fn fee_rate_is_bumped_by_percentage() {
+        let strategy = SubmissionStrategy {
+            fee_bump_percent: 50,
+            ..Default::default()
+        };
+        assert_eq!(3.0, strategy.bumped_fee_rate(2.0));
+    }
+
+    #[test]
+    fn retry_backoff_doubles_up_to_the_maximum() {
+        let strategy = SubmissionStrategy {
+            retry_backoff: Duration::from_secs(5),
+            max_retry_backoff: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let backoffs: Vec<_> = (1..=5)
+            .map(|failures| strategy.retry_backoff(failures).as_secs())
+            .collect();
+        assert_eq!(vec![5, 10, 20, 30, 30], backoffs);
+        assert_eq!(Duration::from_secs(30), strategy.retry_backoff(u32::MAX));
+    }
+
+    #[test]
+    fn queued_blobs_are_recovered_from_the_db() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let db = SubmissionQueueDB::with_path(tmpdir.path()).unwrap();
+        let mut queue = SubmissionQueue::with_db(db.clone()).unwrap();
+        queue
+            .push_batch(vec![vec![1], vec![2], vec![3]], 10)
+            .unwrap();
+        queue.push_batch(vec![vec![4]], 1).unwrap();
+        assert_eq!(2, queue.num_batches());
+        assert_eq!(11, queue.num_txs());
+
+        let first = queue.pop_front().unwrap();
+        queue.confirm(&first).unwrap();
+        let mut second = queue.pop_front().unwrap();
+        second.stored.first_attempt_height = Some(3);
+        queue.persist(&second).unwrap();
+        queue.push_front(second);
+        drop(queue);
+
+        let mut queue = SubmissionQueue::with_db(db).unwrap();
+        assert_eq!(2, queue.num_batches());
+        let recovered = queue.pop_front().unwrap();
+        assert!(recovered.recovered);
+        assert_eq!(vec![2], recovered.stored.blob);
+        assert_eq!(Some(3), recovered.stored.first_attempt_height);
+
+        // New blobs are queued after the recovered ones
+        queue.push_batch(vec![vec![5]], 1).unwrap();
+        let numbers: Vec<_> = std::iter::from_fn(|| queue.pop_front())
+            .map(|blob| blob.number)
+            .collect();
+        assert_eq!(vec![2, 3, 4], numbers);
+    }
+}
@@ -0,0 +1,280 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use anyhow::Context as _;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::INTERNAL_ERROR_CODE;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{Methods, RpcModule};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use tracing::{info, warn};
+
+use crate::rpc_limits::RawParams;
+
+/// The prefix of the methods only served by the admin RPC server.
+const ADMIN_PREFIX: &str = "admin_";
+
+/// The JSON-RPC error code of the requests rejected by the admin RPC server,
+/// because they don't carry its token.
+const UNAUTHORIZED_CODE: i32 = -32001;
+
+/// Reads the token of the admin RPC server from its file, ignoring the surrounding whitespace.
+pub(crate) fn read_auth_token(path: &Path) -> anyhow::Result<String> {
+    let token = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read the admin RPC token from {}", path.display()))?;
+    let token = token.trim();
+    anyhow::ensure!(
+        !token.is_empty(),
+        "The admin RPC token in {} is empty",
+        path.display()
+    );
+    Ok(token.to_string())
+}
+
+/// Removes the `admin_` methods from `methods`, and returns them in their own module,
+/// forwarding their calls to the removed methods.
+pub(crate) fn split_admin_methods(methods: &mut RpcModule<()>) -> RpcModule<()> {
+    let inner: Methods = methods.clone().into();
+    let mut admin_methods = RpcModule::new(());
+
+    let admin_method_names: Vec<&'static str> = methods
+        .method_names()
+        .filter(|method| method.starts_with(ADMIN_PREFIX))
+        .collect();
+    for method in admin_method_names {
+        methods.remove_method(method);
+        let inner = inner.clone();
+        let registered = admin_methods.register_async_method(method, move |params, _| {
+            let inner = inner.clone();
+            async move {
+                let params = params
+                    .as_str()
+                    .map(|params| RawValue::from_string(params.to_owned()))
+                    .transpose()
+                    .map_err(|e| {
+                        ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>)
+                    })?;
+                inner
+                    .call::<_, Value>(method, RawParams(params))
+                    .await
+                    .map_err(|e| match e {
+                        RpcError::Call(error) => error,
+                        e => {
+                            ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>)
+                        }
+                    })
+            }
+        });
+        if let Err(e) = registered {
+            warn!("Failed to register the admin method {}: {}", method, e);
+        }
+    }
+    admin_methods
+}
+
+/// Starts the admin RPC server, serving the methods to the clients sending the token.
+/// Returns the address the server is bound to.
+pub(crate) async fn start_admin_server(
+    address: SocketAddr,
+    auth_token: String,
+    methods: RpcModule<()>,
+) -> anyhow::Result<SocketAddr> {
+    let middleware = tower::ServiceBuilder::new().layer(AdminAuthLayer::new(auth_token));
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .set_middleware(middleware)
+        .build([address].as_ref())
+        .await?;
+    let bound_address = server.local_addr()?;
+    info!("Starting admin RPC server at {}", &bound_address);
+    let server_handle = server.start(methods);
+    tokio::spawn(server_handle.stopped());
+    Ok(bound_address)
+}
+
+/// A HTTP middleware rejecting the requests without the `Authorization: Bearer <token>` header,
+/// with a [`UNAUTHORIZED_CODE`] error and the `401 Unauthorized` status.
+/// For WebSocket clients, the header of the connection request is checked.
+#[derive(Clone)]
+pub(crate) struct AdminAuthLayer {
+    auth_token: Arc<str>,
+}
+
+impl AdminAuthLayer {
+    pub(crate) fn new(auth_token: String) -> Self {
+        Self {
+            auth_token: auth_token.into(),
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for AdminAuthLayer {
+    type Service = AdminAuth<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AdminAuth {
+            inner,
+            auth_token: self.auth_token.clone(),
+        }
+    }
+}
+
+/// The service of [`AdminAuthLayer`].
+#[derive(Clone)]
+pub(crate) struct AdminAuth<S> {
+    inner: S,
+    auth_token: Arc<str>,
+}
+
+impl<S> tower::Service<hyper::Request<hyper::Body>> for AdminAuth<S>
+where
+    S: tower::Service<hyper::Request<hyper::Body>, Response = hyper::Response<hyper::Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<hyper::Body>) -> Self::Future {
+        if !is_authorized(request.headers(), &self.auth_token) {
+            let error = ErrorObjectOwned::owned(
+                UNAUTHORIZED_CODE,
+                "The admin RPC token is missing or invalid",
+                None::<()>,
+            );
+            let body = serde_json::json!({ "jsonrpc": "2.0", "error": error, "id": null });
+            let response = hyper::Response::builder()
+                .status(hyper::StatusCode::UNAUTHORIZED)
+                .header(hyper::header::CONTENT_TYPE, "application/json")
+                .body(hyper::Body::from(body.to_string()))
+                .expect("The response is valid");
+            return Box::pin(async move { Ok(response) });
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+/// Returns whether the headers carry the bearer token.
+fn is_authorized(headers: &hyper::HeaderMap, auth_token: &str) -> bool {
+    let Some(token) = headers
+        .get(hyper::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    // Compared in constant time, so that the token can't be guessed from the response times
+    token.len() == auth_token.len()
+        && token
+            .bytes()
+            .zip(auth_token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::core::client::ClientT;
+    use jsonrpsee::http_client::HttpClientBuilder;
+    use jsonrpsee::rpc_params;
+
+    use super::*;
+
+    const TOKEN: &str = "secret";
+
+    fn methods() -> RpcModule<()> {
+        let mut methods = RpcModule::new(());
+        methods
+            .register_method("admin_double", |params, _| {
+                let value: u32 = params.one()?;
+                Ok::<_, ErrorObjectOwned>(value * 2)
+            })
+            .unwrap();
+        methods
+            .register_method("public_method", |_, _| Ok::<_, ErrorObjectOwned>(1))
+            .unwrap();
+        methods
+    }
+
+    #[tokio::test]
+    async fn admin_methods_are_split_from_the_public_ones() {
+        let mut public_methods = methods();
+        let admin_methods = split_admin_methods(&mut public_methods);
+
+        let public_names: Vec<_> = public_methods.method_names().collect();
+        assert_eq!(vec!["public_method"], public_names);
+        let admin_names: Vec<_> = admin_methods.method_names().collect();
+        assert_eq!(vec!["admin_double"], admin_names);
+
+        let result: u32 = admin_methods
+            .call("admin_double", rpc_params![21])
+            .await
+            .unwrap();
+        assert_eq!(42, result);
+        match admin_methods
+            .call::<_, u32>("admin_double", rpc_params!["21"])
+            .await
+        {
+            Err(RpcError::Call(error)) => assert_eq!(-32602, error.code()),
+            result => panic!("Expected an invalid params error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn admin_server_requires_the_token() {
+        let mut public_methods = methods();
+        let admin_methods = split_admin_methods(&mut public_methods);
+        let address = start_admin_server(
+            "127.0.0.1:0".parse().unwrap(),
+            TOKEN.to_string(),
+            admin_methods,
+        )
+        .await
+        .unwrap();
+        let url = format!("http://{}", address);
+
+        let client = |authorization: Option<&str>| {
+            let mut headers = hyper::HeaderMap::new();
+            if let Some(authorization) = authorization {
+                headers.insert(hyper::header::AUTHORIZATION, authorization.parse().unwrap());
+            }
+            HttpClientBuilder::default()
+                .set_headers(headers)
+                .build(&url)
+                .unwrap()
+        };
+
+        let result: u32 = client(Some("Bearer secret"))
+            .request("admin_double", rpc_params![2])
+            .await
+            .unwrap();
+        assert_eq!(4, result);
+
+        for authorization in [None, Some("Bearer wrong!"), Some("secret")] {
+            let result = client(authorization)
+                .request::<u32, _>("admin_double", rpc_params![2])
+                .await;
+            assert!(result.is_err(), "{:?} was authorized", authorization);
+        }
+    }
+
+    #[test]
+    fn auth_token_is_read_from_its_file() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let path = tmpdir.path().join("admin_token");
+        std::fs::write(&path, "  secret\n").unwrap();
+        assert_eq!(TOKEN, read_auth_token(&path).unwrap());
+
+        std::fs::write(&path, "\n").unwrap();
+        assert!(read_auth_token(&path).is_err());
+        assert!(read_auth_token(&tmpdir.path().join("missing")).is_err());
+    }
+}
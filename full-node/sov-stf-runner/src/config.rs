@@ -1,9 +1,12 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use sov_db::pruning::PruningMode;
+use sov_p2p::P2pConfig;
 
 /// Configuration for StateTransitionRunner.
 #[derive(Debug, Clone, PartialEq, Deserialize)]
@@ -12,6 +15,62 @@ pub struct RunnerConfig {
     pub start_height: u64,
     /// RPC configuration.
     pub rpc_config: RpcConfig,
+    /// Soft confirmations configuration. Soft confirmations are disabled if it's missing.
+    pub soft_confirmations: Option<SoftConfirmationConfig>,
+    /// Prometheus metrics configuration. The metrics endpoint is disabled if it's missing.
+    pub metrics: Option<MetricsConfig>,
+    /// REST API gateway configuration. The REST API is disabled if it's missing.
+    pub rest: Option<RestConfig>,
+    /// How many of the last processed DA blocks, finalized or not, are tracked to recover
+    /// from a reorganization of the DA layer. A deeper reorganization stops the rollup.
+    /// The pruning of the state must keep at least as many versions.
+    #[serde(default = "default_max_reorg_depth")]
+    pub max_reorg_depth: usize,
+    /// How many DA blocks must be built on top of a processed block, in addition to its finality
+    /// on the DA layer, before the block is finalized: committed to the ledger and proven.
+    /// It's meant for DA layers with probabilistic finality. Only the finality of the DA layer
+    /// is required if it's 0.
+    #[serde(default)]
+    pub confirmation_depth: u64,
+    /// Where the state diff of every processed slot is written, one JSON line per slot.
+    /// The state diffs are only published to the subscribers of the runner if it's missing.
+    #[serde(default)]
+    pub state_diffs: Option<StateDiffConfig>,
+    /// Whether the transactions of the soft confirmation batches are executed optimistically
+    /// in parallel. The batches read from DA are always executed serially, like in the prover.
+    #[serde(default)]
+    pub parallel_execution: bool,
+}
+
+fn default_max_reorg_depth() -> usize {
+    100
+}
+
+/// State diff file sink configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StateDiffConfig {
+    /// The JSON-lines file the state diffs are appended to. It's created if it doesn't exist.
+    pub file_path: PathBuf,
+}
+
+/// Soft confirmations configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SoftConfirmationConfig {
+    /// The RPC URL of the sequencer giving out soft confirmations.
+    pub sequencer_url: String,
+    /// The hex encoded public key of the sequencer, in its borsh serialization.
+    /// Soft batches signed with other keys are ignored.
+    pub sequencer_public_key: String,
+    /// The number of DA blocks following the one referenced by a soft batch,
+    /// in which the batch must land on DA before it is discarded.
+    pub inclusion_window: u64,
+    /// How often the sequencer is polled for new soft batches, in milliseconds.
+    pub poll_interval_ms: u64,
+    /// The gossip of the soft batches between full nodes, which delivers them without polling
+    /// the sequencer. The sequencer is still polled for the batches missed by gossip.
+    /// Soft batches are only polled if it's missing.
+    #[serde(default)]
+    pub gossip: Option<P2pConfig>,
 }
 
 /// RPC configuration.
@@ -21,6 +80,74 @@ pub struct RpcConfig {
     pub bind_host: String,
     /// RPC port.
     pub bind_port: u16,
+    /// Limits protecting the node from expensive or abusive clients.
+    #[serde(default)]
+    pub limits: RpcLimitsConfig,
+    /// Admin RPC server configuration. The `admin_` methods aren't served if it's missing.
+    #[serde(default)]
+    pub admin: Option<AdminRpcConfig>,
+}
+
+/// Admin RPC server configuration. The server only serves the `admin_` methods, which are
+/// never served by the public RPC server, to the clients sending its token.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AdminRpcConfig {
+    /// Admin RPC host. It should only be reachable by the operators of the node.
+    pub bind_host: String,
+    /// Admin RPC port.
+    pub bind_port: u16,
+    /// The file holding the token of the server, which the clients send in the
+    /// `Authorization: Bearer <token>` header.
+    pub auth_token_path: PathBuf,
+}
+
+/// Limits of the RPC server. The calls exceeding them are rejected with the `-32005` error code.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct RpcLimitsConfig {
+    /// The maximum number of calls in a JSON-RPC batch request. Batches are unlimited if it's missing.
+    pub max_batch_size: Option<u32>,
+    /// How many HTTP requests per second each client can send. Clients are not limited if it's missing.
+    /// Clients are identified by the `X-Forwarded-For` or `X-Real-IP` header, so this must only be set
+    /// behind a reverse proxy setting them.
+    pub requests_per_second_per_ip: Option<u32>,
+    /// How many calls per second the methods with the given names accept, from all the clients.
+    pub method_rate_limits: HashMap<String, u32>,
+    /// The names of the expensive methods. A trailing `*` matches all the methods with the given prefix.
+    pub expensive_methods: Vec<String>,
+    /// How many calls of the expensive methods are served at the same time.
+    /// They are unlimited if it's missing.
+    pub max_concurrent_expensive_calls: Option<usize>,
+}
+
+impl Default for RpcLimitsConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: None,
+            requests_per_second_per_ip: None,
+            method_rate_limits: HashMap::new(),
+            expensive_methods: vec!["eth_getLogs".to_string(), "debug_trace*".to_string()],
+            max_concurrent_expensive_calls: None,
+        }
+    }
+}
+
+/// Prometheus metrics configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct MetricsConfig {
+    /// Metrics server host.
+    pub bind_host: String,
+    /// Metrics server port.
+    pub bind_port: u16,
+}
+
+/// REST API gateway configuration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct RestConfig {
+    /// REST server host.
+    pub bind_host: String,
+    /// REST server port.
+    pub bind_port: u16,
 }
 
 /// Simple storage configuration
@@ -28,13 +155,141 @@ pub struct RpcConfig {
 pub struct StorageConfig {
     /// Path that can be utilized by concrete implementation
     pub path: PathBuf,
+    /// How much of the finalized history is kept. Everything is kept if it's missing.
+    #[serde(default)]
+    pub pruning: PruningMode,
+    /// Whether the ledger indexes transactions by the address of their sender,
+    /// to serve `ledger_getTransactionsByAddress`.
+    #[serde(default)]
+    pub index_txs_by_sender: bool,
 }
 
 /// Prover service configuration.
-#[derive(Debug, Clone, PartialEq, Deserialize, Copy)]
+#[derive(Debug, Clone, PartialEq, Deserialize)]
 pub struct ProverServiceConfig {
     /// The "distance"  measured in the number of blocks between two consecutive aggregated proofs.
+    /// Proofs are not aggregated if it's 0, or if the prover service has no aggregation program.
     pub aggregated_proof_block_jump: u64,
+    /// How many times a failed proving job is retried before it's reported as failed.
+    #[serde(default)]
+    pub max_retries: u32,
+    /// The directory where the proving jobs are persisted until their proof is generated,
+    /// so they are resumed after a restart. The jobs are only kept in memory if it's missing.
+    #[serde(default)]
+    pub queue_path: Option<PathBuf>,
+}
+
+/// Compression of the batches submitted to DA.
+#[derive(Debug, Clone, PartialEq, Deserialize, Copy)]
+pub struct DaCompressionConfig {
+    /// The zstd compression level, from 1 (fastest) to 22 (smallest).
+    pub level: i32,
+}
+
+/// The order in which the sequencer includes the pending transactions in a batch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOrderingConfig {
+    /// In the order the transactions were submitted.
+    #[default]
+    Fifo,
+    /// The transactions offering the highest gas tip first.
+    GasPrice,
+}
+
+/// Batch building policy of the sequencer.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct BatchBuilderConfig {
+    /// The order in which the pending transactions are included.
+    pub ordering: BatchOrderingConfig,
+    /// The maximum size of a batch in bytes, once encoded as a DA blob.
+    pub max_batch_size_bytes: usize,
+    /// The maximum total gas limit of the transactions of a batch. Unlimited if it's missing.
+    pub max_batch_gas: Option<u64>,
+    /// The maximum number of transactions of a single sender in a batch. Unlimited if it's missing.
+    pub max_txs_per_sender: Option<usize>,
+    /// The maximum number of transactions in the mempool.
+    pub mempool_max_txs_count: usize,
+    /// The directory where the mempool is persisted, so the pending transactions are reloaded
+    /// and revalidated after a restart. The mempool is only kept in memory if it's missing.
+    pub mempool_path: Option<PathBuf>,
+    /// How long a transaction can wait in the mempool before it's dropped, in seconds.
+    /// Transactions never expire if it's missing.
+    pub mempool_max_age_secs: Option<u64>,
+}
+
+impl Default for BatchBuilderConfig {
+    fn default() -> Self {
+        Self {
+            ordering: BatchOrderingConfig::Fifo,
+            max_batch_size_bytes: 1024 * 100,
+            max_batch_gas: None,
+            max_txs_per_sender: None,
+            mempool_max_txs_count: u32::MAX as usize,
+            mempool_path: None,
+            mempool_max_age_secs: None,
+        }
+    }
+}
+
+/// DA submission policy of the sequencer.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct DaSubmissionConfig {
+    /// Batches are delayed while the DA fee rate is above this ceiling. Never delayed if it's missing.
+    pub max_fee_rate: Option<f64>,
+    /// How long a batch can be delayed before it's submitted whatever the fee rate, in seconds.
+    pub max_delay_secs: u64,
+    /// How long a sent blob can stay out of the DA layer before its fee is bumped, in seconds.
+    /// Sent blobs aren't tracked if it's missing.
+    pub stuck_timeout_secs: Option<u64>,
+    /// The percentage by which the fee rate of a stuck submission is bumped.
+    pub fee_bump_percent: u32,
+    /// How many times the fee of a stuck submission is bumped.
+    pub max_fee_bumps: u32,
+    /// How many batches can wait for their submission before the sequencer stops building new
+    /// ones, leaving their transactions in the mempool.
+    pub max_queued_batches: usize,
+    /// How long a failed submission waits before it's retried, in seconds.
+    /// Doubled after each consecutive failure.
+    pub retry_backoff_secs: u64,
+    /// The maximum wait before a failed submission is retried, in seconds.
+    pub max_retry_backoff_secs: u64,
+    /// The directory of the database persisting the queued batches, so they're submitted
+    /// after a restart. They're only kept in memory if it's missing.
+    pub queue_path: Option<PathBuf>,
+}
+
+impl Default for DaSubmissionConfig {
+    fn default() -> Self {
+        Self {
+            max_fee_rate: None,
+            max_delay_secs: 600,
+            stuck_timeout_secs: None,
+            fee_bump_percent: 25,
+            max_fee_bumps: 3,
+            max_queued_batches: 16,
+            retry_backoff_secs: 5,
+            max_retry_backoff_secs: 300,
+            queue_path: None,
+        }
+    }
+}
+
+/// Parameters of the gas price oracle of the Ethereum RPC.
+/// The defaults of the oracle are used for the missing ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct GasOracleConfig {
+    /// The number of populated blocks the suggested gas price is computed from.
+    pub blocks: Option<u32>,
+    /// The percentile of the sampled tips which is suggested, between 0 and 100.
+    pub percentile: Option<u32>,
+    /// The maximum suggested gas price, in wei.
+    pub max_price: Option<u64>,
+    /// The tips under this price, in wei, are ignored.
+    pub ignore_price: Option<u64>,
 }
 
 /// Rollup Configuration
@@ -48,6 +303,22 @@ pub struct RollupConfig<DaServiceConfig> {
     pub da: DaServiceConfig,
     /// Prover service configuration.
     pub prover_service: ProverServiceConfig,
+    /// Compression of the batches submitted to DA. Batches are submitted uncompressed if it's missing.
+    #[serde(default)]
+    pub da_compression: Option<DaCompressionConfig>,
+    /// Batch building policy of the sequencer. The default policy is used if it's missing.
+    #[serde(default)]
+    pub batch_builder: BatchBuilderConfig,
+    /// DA submission policy of the sequencer. The default policy is used if it's missing.
+    #[serde(default)]
+    pub da_submission: DaSubmissionConfig,
+    /// Gas price oracle of the Ethereum RPC. The defaults of the oracle are used if it's missing.
+    #[serde(default)]
+    pub gas_price_oracle: GasOracleConfig,
+    /// Filter directives of the logs, in the `RUST_LOG` syntax, e.g. `"info,sov_sequencer=debug"`.
+    /// The filter set when the node started is used if it's missing.
+    #[serde(default)]
+    pub log_level: Option<String>,
 }
 
 /// Reads toml file as a specific type.
@@ -108,7 +379,16 @@ mod tests {
                 rpc_config: RpcConfig {
                     bind_host: "127.0.0.1".to_string(),
                     bind_port: 12345,
+                    limits: Default::default(),
+                    admin: None,
                 },
+                soft_confirmations: None,
+                metrics: None,
+                rest: None,
+                max_reorg_depth: 100,
+                confirmation_depth: 0,
+                state_diffs: None,
+                parallel_execution: false,
             },
 
             da: sov_celestia_adapter::CelestiaConfig {
@@ -116,14 +396,235 @@ mod tests {
                 celestia_rpc_address: "http://localhost:11111/".into(),
                 max_celestia_response_body_size: 980,
                 celestia_rpc_timeout_seconds: 60,
+                max_blob_size: 1_900_000,
             },
             storage: StorageConfig {
                 path: PathBuf::from("/tmp"),
+                pruning: PruningMode::Archive,
+                index_txs_by_sender: false,
             },
             prover_service: ProverServiceConfig {
                 aggregated_proof_block_jump: 22,
+                max_retries: 0,
+                queue_path: None,
             },
+            da_compression: None,
+            batch_builder: BatchBuilderConfig::default(),
+            da_submission: DaSubmissionConfig::default(),
+            gas_price_oracle: GasOracleConfig::default(),
+            log_level: None,
         };
         assert_eq!(config, expected);
     }
+
+    #[test]
+    fn test_soft_confirmations_config() {
+        let config = r#"
+            start_height = 1
+            [rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [soft_confirmations]
+            sequencer_url = "http://127.0.0.1:12346"
+            sequencer_public_key = "aabb"
+            inclusion_window = 10
+            poll_interval_ms = 500
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RunnerConfig = from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            Some(SoftConfirmationConfig {
+                sequencer_url: "http://127.0.0.1:12346".to_string(),
+                sequencer_public_key: "aabb".to_string(),
+                inclusion_window: 10,
+                poll_interval_ms: 500,
+                gossip: None,
+            }),
+            config.soft_confirmations
+        );
+    }
+
+    #[test]
+    fn test_soft_batch_gossip_config() {
+        let config = r#"
+            start_height = 1
+            [rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [soft_confirmations]
+            sequencer_url = "http://127.0.0.1:12346"
+            sequencer_public_key = "aabb"
+            inclusion_window = 10
+            poll_interval_ms = 500
+            [soft_confirmations.gossip]
+            listen_address = "/ip4/0.0.0.0/tcp/9100"
+            bootstrap_peers = ["/ip4/10.0.0.1/tcp/9100"]
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RunnerConfig = from_toml_path(config_file.path()).unwrap();
+        let gossip = config.soft_confirmations.unwrap().gossip.unwrap();
+        assert_eq!("/ip4/0.0.0.0/tcp/9100", gossip.listen_address);
+        assert_eq!(vec!["/ip4/10.0.0.1/tcp/9100"], gossip.bootstrap_peers);
+        assert_eq!("sov-soft-batches", gossip.topic);
+    }
+
+    #[test]
+    fn test_state_diffs_config() {
+        let config = r#"
+            start_height = 1
+            [rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [state_diffs]
+            file_path = "/tmp/state_diffs.jsonl"
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RunnerConfig = from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            Some(StateDiffConfig {
+                file_path: PathBuf::from("/tmp/state_diffs.jsonl"),
+            }),
+            config.state_diffs
+        );
+    }
+
+    #[test]
+    fn test_rpc_limits_config() {
+        let config = r#"
+            start_height = 1
+            [rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [rpc_config.limits]
+            max_batch_size = 50
+            max_concurrent_expensive_calls = 4
+            [rpc_config.limits.method_rate_limits]
+            eth_call = 100
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RunnerConfig = from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            RpcLimitsConfig {
+                max_batch_size: Some(50),
+                method_rate_limits: HashMap::from([("eth_call".to_string(), 100)]),
+                max_concurrent_expensive_calls: Some(4),
+                ..Default::default()
+            },
+            config.rpc_config.limits
+        );
+    }
+
+    #[test]
+    fn test_da_compression_config() {
+        let config = r#"
+            [da]
+            celestia_rpc_auth_token = "SECRET_RPC_TOKEN"
+            celestia_rpc_address = "http://localhost:11111/"
+            max_celestia_response_body_size = 980
+            [storage]
+            path = "/tmp"
+            [runner]
+            start_height = 31337
+            [runner.rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [prover_service]
+            aggregated_proof_block_jump = 22
+            [da_compression]
+            level = 3
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
+            from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            Some(DaCompressionConfig { level: 3 }),
+            config.da_compression
+        );
+    }
+
+    #[test]
+    fn test_batch_builder_config() {
+        let config = r#"
+            [da]
+            celestia_rpc_auth_token = "SECRET_RPC_TOKEN"
+            celestia_rpc_address = "http://localhost:11111/"
+            max_celestia_response_body_size = 980
+            [storage]
+            path = "/tmp"
+            [runner]
+            start_height = 31337
+            [runner.rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [prover_service]
+            aggregated_proof_block_jump = 22
+            [batch_builder]
+            ordering = "gas_price"
+            max_batch_gas = 1000000
+            mempool_path = "/tmp/mempool"
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
+            from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            BatchBuilderConfig {
+                ordering: BatchOrderingConfig::GasPrice,
+                max_batch_gas: Some(1000000),
+                mempool_path: Some(PathBuf::from("/tmp/mempool")),
+                ..Default::default()
+            },
+            config.batch_builder
+        );
+    }
+
+    #[test]
+    fn test_da_submission_config() {
+        let config = r#"
+            [da]
+            celestia_rpc_auth_token = "SECRET_RPC_TOKEN"
+            celestia_rpc_address = "http://localhost:11111/"
+            max_celestia_response_body_size = 980
+            [storage]
+            path = "/tmp"
+            [runner]
+            start_height = 31337
+            [runner.rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = 12345
+            [prover_service]
+            aggregated_proof_block_jump = 22
+            [da_submission]
+            max_fee_rate = 12.5
+            stuck_timeout_secs = 120
+            max_queued_batches = 4
+            queue_path = "/tmp/submission_queue"
+        "#;
+
+        let config_file = create_config_from(config);
+
+        let config: RollupConfig<sov_celestia_adapter::CelestiaConfig> =
+            from_toml_path(config_file.path()).unwrap();
+        assert_eq!(
+            DaSubmissionConfig {
+                max_fee_rate: Some(12.5),
+                stuck_timeout_secs: Some(120),
+                max_queued_batches: 4,
+                queue_path: Some(PathBuf::from("/tmp/submission_queue")),
+                ..Default::default()
+            },
+            config.da_submission
+        );
+    }
 }
@@ -0,0 +1,490 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context as _;
+use tokio::sync::watch;
+use tracing::{debug, info, warn};
+
+use crate::config::{from_toml_path, BatchBuilderConfig, GasOracleConfig, RpcLimitsConfig};
+use crate::RollupConfig;
+
+/// How often the watched configuration file is checked for changes by default.
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The settings of the rollup configuration which can be changed without restarting the node.
+/// None of them has an effect on consensus.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReloadableConfig {
+    /// The limits of the RPC server, except the maximum size of the batch requests.
+    pub rpc_limits: RpcLimitsConfig,
+    /// The maximum number of transactions in the mempool of the sequencer.
+    pub mempool_max_txs_count: usize,
+    /// The DA fee rate ceiling above which the sequencer delays its batches.
+    pub max_da_fee_rate: Option<f64>,
+    /// The parameters of the gas price oracle of the Ethereum RPC.
+    pub gas_price_oracle: GasOracleConfig,
+    /// The filter directives of the logs.
+    pub log_level: Option<String>,
+}
+
+impl Default for ReloadableConfig {
+    fn default() -> Self {
+        Self {
+            rpc_limits: RpcLimitsConfig::default(),
+            mempool_max_txs_count: BatchBuilderConfig::default().mempool_max_txs_count,
+            max_da_fee_rate: None,
+            gas_price_oracle: GasOracleConfig::default(),
+            log_level: None,
+        }
+    }
+}
+
+impl<Da> From<&RollupConfig<Da>> for ReloadableConfig {
+    fn from(config: &RollupConfig<Da>) -> Self {
+        Self {
+            rpc_limits: config.runner.rpc_config.limits.clone(),
+            mempool_max_txs_count: config.batch_builder.mempool_max_txs_count,
+            max_da_fee_rate: config.da_submission.max_fee_rate,
+            gas_price_oracle: config.gas_price_oracle.clone(),
+            log_level: config.log_level.clone(),
+        }
+    }
+}
+
+impl ReloadableConfig {
+    /// Returns an error if one of the settings can't be applied.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.mempool_max_txs_count == 0 {
+            anyhow::bail!("The mempool must hold at least one transaction");
+        }
+        if let Some(max_fee_rate) = self.max_da_fee_rate {
+            if !max_fee_rate.is_finite() || max_fee_rate <= 0.0 {
+                anyhow::bail!(
+                    "The DA fee rate ceiling must be positive, got {}",
+                    max_fee_rate
+                );
+            }
+        }
+        if self.rpc_limits.requests_per_second_per_ip == Some(0) {
+            anyhow::bail!("The rate limit of the RPC clients can't be zero");
+        }
+        if let Some((method, _)) = self
+            .rpc_limits
+            .method_rate_limits
+            .iter()
+            .find(|(_, rate)| **rate == 0)
+        {
+            anyhow::bail!("The rate limit of {} can't be zero", method);
+        }
+        if self.rpc_limits.max_concurrent_expensive_calls == Some(0) {
+            anyhow::bail!("The concurrency limit of the expensive RPC methods can't be zero");
+        }
+        if let Some(percentile) = self.gas_price_oracle.percentile {
+            if percentile > 100 {
+                anyhow::bail!(
+                    "The gas price percentile must be at most 100, got {}",
+                    percentile
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The receiving end of the snapshots of the [`ReloadableConfig`] published by a [`ConfigWatcher`].
+/// The latest snapshot is always available, even once the watcher is dropped.
+pub type ConfigUpdates = watch::Receiver<Arc<ReloadableConfig>>;
+
+/// An additional check of the reloaded settings, such as the syntax of the log filter.
+type Validation = Box<dyn Fn(&ReloadableConfig) -> anyhow::Result<()> + Send + Sync>;
+
+/// The rollup configuration file, as it was last applied.
+/// The DA configuration is only compared, so it's kept as a raw TOML value.
+struct WatchedFile {
+    path: PathBuf,
+    config: RollupConfig<toml::Value>,
+    modified: Option<SystemTime>,
+}
+
+/// Reloads the [`ReloadableConfig`] from the rollup configuration file on SIGHUP, or when the
+/// file is modified, and publishes it to the [`ConfigUpdates`] of the components using it.
+///
+/// A new configuration replaces the previous snapshot as a whole, once it's validated.
+/// It's rejected if it can't be parsed, if one of its settings is invalid, or if it changes any
+/// setting which is only applied on restart, in which case the previous snapshot is kept.
+pub struct ConfigWatcher {
+    sender: watch::Sender<Arc<ReloadableConfig>>,
+    file: Option<WatchedFile>,
+    validation: Option<Validation>,
+    poll_interval: Duration,
+}
+
+impl ConfigWatcher {
+    /// Creates a watcher publishing `config`, which doesn't watch any file yet.
+    pub fn new(config: ReloadableConfig) -> Self {
+        let (sender, _) = watch::channel(Arc::new(config));
+        Self {
+            sender,
+            file: None,
+            validation: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// Makes the watcher reload the settings from the rollup configuration file at `path`.
+    /// The file is read right away, as the reference for the settings needing a restart.
+    pub fn watch_file(mut self, path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let config: RollupConfig<toml::Value> = from_toml_path(&path)
+            .with_context(|| format!("Failed to read rollup configuration {}", path.display()))?;
+        let modified = modified_at(&path);
+        self.file = Some(WatchedFile {
+            path,
+            config,
+            modified,
+        });
+        Ok(self)
+    }
+
+    /// Adds a check the reloaded settings have to pass, on top of [`ReloadableConfig::validate`].
+    pub fn with_validation(
+        mut self,
+        validation: impl Fn(&ReloadableConfig) -> anyhow::Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.validation = Some(Box::new(validation));
+        self
+    }
+
+    /// Sets how often the watched file is checked for changes.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Returns a receiver of the current snapshot and of the following ones.
+    pub fn subscribe(&self) -> ConfigUpdates {
+        self.sender.subscribe()
+    }
+
+    /// Returns the current snapshot.
+    pub fn current(&self) -> Arc<ReloadableConfig> {
+        self.sender.borrow().clone()
+    }
+
+    /// Validates `config` and replaces the current snapshot with it.
+    /// Returns `false` if it's the same as the current one, which is then kept.
+    pub fn publish(&self, config: ReloadableConfig) -> anyhow::Result<bool> {
+        config.validate()?;
+        if let Some(validation) = &self.validation {
+            validation(&config)?;
+        }
+        Ok(self.sender.send_if_modified(|current| {
+            if **current == config {
+                return false;
+            }
+            *current = Arc::new(config);
+            true
+        }))
+    }
+
+    /// Reads the watched file again and publishes its settings.
+    /// Returns `false` if they didn't change, or if no file is watched.
+    pub fn reload(&mut self) -> anyhow::Result<bool> {
+        let Some(file) = &self.file else {
+            return Ok(false);
+        };
+        let config: RollupConfig<toml::Value> = from_toml_path(&file.path).with_context(|| {
+            format!(
+                "Failed to read rollup configuration {}",
+                file.path.display()
+            )
+        })?;
+        check_restart_settings(&file.config, &config)?;
+        let changed = self.publish(ReloadableConfig::from(&config))?;
+        if let Some(file) = &mut self.file {
+            file.config = config;
+        }
+        Ok(changed)
+    }
+
+    /// Reloads the settings on every SIGHUP, and every time the watched file is modified,
+    /// until the task is cancelled. Returns right away if no file is watched.
+    pub async fn run(mut self) {
+        if self.file.is_none() {
+            return;
+        }
+        let mut hangups = Hangups::new();
+        let mut interval = tokio::time::interval(self.poll_interval);
+        loop {
+            let trigger = tokio::select! {
+                _ = hangups.recv() => "SIGHUP",
+                _ = interval.tick() => {
+                    if !self.file_modified() {
+                        continue;
+                    }
+                    "file change"
+                }
+            };
+            match self.reload() {
+                Ok(true) => info!(
+                    "Reloaded the node settings after a {}: {:?}",
+                    trigger,
+                    self.current()
+                ),
+                Ok(false) => debug!("The node settings are unchanged after a {}", trigger),
+                Err(e) => warn!(
+                    "Rejected the node settings reloaded after a {}, the previous ones are kept: {:#}",
+                    trigger, e
+                ),
+            }
+        }
+    }
+
+    /// Returns `true` if the watched file was modified since it was last checked.
+    fn file_modified(&mut self) -> bool {
+        let Some(file) = &mut self.file else {
+            return false;
+        };
+        let modified = modified_at(&file.path);
+        if modified == file.modified {
+            return false;
+        }
+        file.modified = modified;
+        true
+    }
+}
+
+fn modified_at(path: &std::path::Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Returns an error naming the sections of `new` which changed settings that are only applied
+/// on restart.
+fn check_restart_settings(
+    current: &RollupConfig<toml::Value>,
+    new: &RollupConfig<toml::Value>,
+) -> anyhow::Result<()> {
+    // The reloadable settings of `new` are replaced by the current ones, so that any
+    // remaining difference is a setting needing a restart.
+    let mut unchanged = new.clone();
+    unchanged.runner.rpc_config.limits = RpcLimitsConfig {
+        max_batch_size: new.runner.rpc_config.limits.max_batch_size,
+        ..current.runner.rpc_config.limits.clone()
+    };
+    unchanged.batch_builder.mempool_max_txs_count = current.batch_builder.mempool_max_txs_count;
+    unchanged.da_submission.max_fee_rate = current.da_submission.max_fee_rate;
+    unchanged.gas_price_oracle = current.gas_price_oracle.clone();
+    unchanged.log_level = current.log_level.clone();
+
+    let changed: Vec<&str> = [
+        ("storage", unchanged.storage != current.storage),
+        ("runner", unchanged.runner != current.runner),
+        ("da", unchanged.da != current.da),
+        (
+            "prover_service",
+            unchanged.prover_service != current.prover_service,
+        ),
+        (
+            "da_compression",
+            unchanged.da_compression != current.da_compression,
+        ),
+        (
+            "batch_builder",
+            unchanged.batch_builder != current.batch_builder,
+        ),
+        (
+            "da_submission",
+            unchanged.da_submission != current.da_submission,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(section, changed)| changed.then_some(section))
+    .collect();
+    if !changed.is_empty() {
+        anyhow::bail!(
+            "The changes of the {} sections are only applied on restart",
+            changed.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Resolves on every SIGHUP received by the process.
+/// It never resolves on other platforms, or if the signal can't be listened to.
+struct Hangups {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangups {
+    fn new() -> Self {
+        Self {
+            #[cfg(unix)]
+            signal: tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                .map_err(|e| {
+                    warn!(
+                        "Failed to listen to SIGHUP, the rollup config is only reloaded when it's modified: {}",
+                        e
+                    )
+                })
+                .ok(),
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        {
+            if let Some(signal) = &mut self.signal {
+                signal.recv().await;
+                return;
+            }
+        }
+        std::future::pending::<()>().await
+    }
+}
+
+/// A value built from the [`ReloadableConfig`], which is rebuilt the first time it's used after
+/// a new snapshot was published.
+pub(crate) struct Reloaded<T> {
+    state: Mutex<(ConfigUpdates, Arc<T>)>,
+    build: fn(&ReloadableConfig) -> T,
+}
+
+impl<T> Reloaded<T> {
+    pub(crate) fn new(mut updates: ConfigUpdates, build: fn(&ReloadableConfig) -> T) -> Self {
+        let value = Arc::new(build(&updates.borrow_and_update()));
+        Self {
+            state: Mutex::new((updates, value)),
+            build,
+        }
+    }
+
+    /// Returns the value built from the latest snapshot.
+    pub(crate) fn get(&self) -> Arc<T> {
+        let mut state = self.state.lock().unwrap();
+        let (updates, value) = &mut *state;
+        // The latest snapshot stays available once the watcher is dropped
+        if updates.has_changed().unwrap_or(false) {
+            *value = Arc::new((self.build)(&updates.borrow_and_update()));
+        }
+        value.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn config_file(mempool_max_txs_count: usize, bind_port: u16) -> String {
+        format!(
+            r#"
+            log_level = "info"
+            [da]
+            sender_address = "0000000000000000000000000000000000000000000000000000000000000000"
+            [storage]
+            path = "/tmp"
+            [runner]
+            start_height = 1
+            [runner.rpc_config]
+            bind_host = "127.0.0.1"
+            bind_port = {}
+            [prover_service]
+            aggregated_proof_block_jump = 1
+            [batch_builder]
+            mempool_max_txs_count = {}
+        "#,
+            bind_port, mempool_max_txs_count
+        )
+    }
+
+    fn write(file: &mut NamedTempFile, content: &str) {
+        let file = file.as_file_mut();
+        file.set_len(0).unwrap();
+        std::io::Seek::rewind(file).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    fn watch(file: &NamedTempFile) -> ConfigWatcher {
+        let config: RollupConfig<toml::Value> = from_toml_path(file.path()).unwrap();
+        ConfigWatcher::new(ReloadableConfig::from(&config))
+            .watch_file(file.path())
+            .unwrap()
+    }
+
+    #[test]
+    fn reloaded_settings_are_published() {
+        let mut file = NamedTempFile::new().unwrap();
+        write(&mut file, &config_file(100, 12345));
+        let mut watcher = watch(&file);
+        let mut updates = watcher.subscribe();
+        assert_eq!(100, updates.borrow_and_update().mempool_max_txs_count);
+        assert_eq!(Some("info"), watcher.current().log_level.as_deref());
+
+        assert!(!watcher.reload().unwrap());
+        assert!(!updates.has_changed().unwrap());
+
+        write(&mut file, &config_file(200, 12345));
+        assert!(watcher.reload().unwrap());
+        assert!(updates.has_changed().unwrap());
+        assert_eq!(200, updates.borrow_and_update().mempool_max_txs_count);
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let mut file = NamedTempFile::new().unwrap();
+        write(&mut file, &config_file(100, 12345));
+        let mut watcher = watch(&file).with_validation(|config| {
+            if config.log_level.as_deref() == Some("info") {
+                Ok(())
+            } else {
+                anyhow::bail!("Unexpected log level")
+            }
+        });
+
+        // Settings needing a restart
+        write(&mut file, &config_file(200, 12346));
+        let error = watcher.reload().unwrap_err();
+        assert!(error.to_string().contains("runner"), "{}", error);
+        // Invalid settings
+        write(&mut file, &config_file(0, 12345));
+        assert!(watcher.reload().is_err());
+        // Unparsable file
+        write(&mut file, "[runner");
+        assert!(watcher.reload().is_err());
+        // Settings rejected by the additional validation
+        write(
+            &mut file,
+            &config_file(200, 12345).replace("\"info\"", "\"debug\""),
+        );
+        assert!(watcher.reload().is_err());
+
+        assert_eq!(100, watcher.current().mempool_max_txs_count);
+    }
+
+    #[test]
+    fn reloaded_values_are_rebuilt() {
+        let watcher = ConfigWatcher::new(ReloadableConfig::default());
+        let reloaded = Reloaded::new(watcher.subscribe(), |config| config.mempool_max_txs_count);
+        let first = reloaded.get();
+        assert!(Arc::ptr_eq(&first, &reloaded.get()));
+
+        watcher
+            .publish(ReloadableConfig {
+                mempool_max_txs_count: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(10, *reloaded.get());
+
+        // The last snapshot is kept once the watcher is dropped
+        drop(watcher);
+        assert_eq!(10, *reloaded.get());
+    }
+}
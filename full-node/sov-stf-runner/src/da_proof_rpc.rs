@@ -0,0 +1,178 @@
+//! RPC methods serving the DA inclusion proofs of the committed batches, so that external
+//! verifiers and bridges can check that a batch was posted on the DA layer.
+
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::BatchNumber;
+use sov_rollup_interface::da::BlobReaderTrait;
+use sov_rollup_interface::services::da::{DaService, SlotData};
+
+/// The location of a batch on the DA layer, as returned by `rollup_getBatchDaProof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchDaProofResponse {
+    /// The number of the batch.
+    pub batch_number: u64,
+    /// The number of the slot containing the batch.
+    pub slot_number: u64,
+    /// The height of the DA block containing the batch.
+    pub da_height: u64,
+    /// The hex encoded hash of the DA block containing the batch.
+    pub block_hash: String,
+    /// The index of the blob of the batch, among the blobs of the rollup in the DA block.
+    pub blob_index: usize,
+    /// The hex encoded hash identifying the blob transaction of the batch on the DA layer.
+    pub blob_hash: String,
+    /// The DA address which posted the blob.
+    pub sender: String,
+    /// Where the blobs of the rollup are posted on the DA layer, like the namespace on Celestia.
+    /// See [`DaService::blob_location`].
+    pub location: Option<String>,
+    /// The proof that the blobs of the rollup are included in the DA block,
+    /// in the serialization of the DA layer.
+    pub inclusion_proof: serde_json::Value,
+    /// The proof that the blobs of the rollup in the DA block are complete,
+    /// in the serialization of the DA layer.
+    pub completeness_proof: serde_json::Value,
+}
+
+pub(crate) struct DaProofContext<Da> {
+    ledger_db: LedgerDB,
+    da_service: Da,
+    genesis_height: u64,
+}
+
+/// Creates the module of `rollup_getBatchDaProof`. The slots are expected to be numbered
+/// from the DA block at `genesis_height`, without any gaps.
+pub(crate) fn rpc_module<Da: DaService>(
+    ledger_db: LedgerDB,
+    da_service: Da,
+    genesis_height: u64,
+) -> RpcModule<DaProofContext<Da>> {
+    let mut rpc = RpcModule::new(DaProofContext {
+        ledger_db,
+        da_service,
+        genesis_height,
+    });
+    rpc.register_async_method("rollup_getBatchDaProof", |params, context| async move {
+        let batch_number: u64 = params.one()?;
+        get_batch_da_proof(&context, BatchNumber(batch_number)).await
+    })
+    .expect("Failed to register DA proof RPC methods");
+    rpc
+}
+
+async fn get_batch_da_proof<Da: DaService>(
+    context: &DaProofContext<Da>,
+    batch_number: BatchNumber,
+) -> Result<Option<BatchDaProofResponse>, ErrorObjectOwned> {
+    let ledger_db = &context.ledger_db;
+    let Some(batch) = ledger_db.get_batch(batch_number).map_err(internal_error)? else {
+        return Ok(None);
+    };
+    let Some((slot_number, slot)) = ledger_db
+        .get_slot_of_batch(batch_number)
+        .map_err(internal_error)?
+    else {
+        return Ok(None);
+    };
+
+    let da_height = context.genesis_height + slot_number.0 - 1;
+    let block = context
+        .da_service
+        .get_block_at(da_height)
+        .await
+        .map_err(internal_error)?;
+    if block.hash() != slot.hash {
+        return Err(internal_error(format!(
+            "The DA block at height {} isn't the block of slot {} anymore, the DA layer was reorganized",
+            da_height, slot_number.0
+        )));
+    }
+
+    let (blobs, inclusion_proof, completeness_proof) = context
+        .da_service
+        .extract_relevant_blobs_with_proof(&block)
+        .await;
+    let Some((blob_index, blob)) = blobs
+        .iter()
+        .enumerate()
+        .find(|(_, blob)| blob.hash() == batch.hash)
+    else {
+        return Err(internal_error(format!(
+            "The blob of batch {} isn't in the DA block at height {}",
+            batch_number.0, da_height
+        )));
+    };
+
+    Ok(Some(BatchDaProofResponse {
+        batch_number: batch_number.0,
+        slot_number: slot_number.0,
+        da_height,
+        block_hash: format!("0x{}", hex::encode(slot.hash)),
+        blob_index,
+        blob_hash: format!("0x{}", hex::encode(batch.hash)),
+        sender: blob.sender().to_string(),
+        location: context.da_service.blob_location(),
+        inclusion_proof: serde_json::to_value(&inclusion_proof).map_err(internal_error)?,
+        completeness_proof: serde_json::to_value(&completeness_proof).map_err(internal_error)?,
+    }))
+}
+
+fn internal_error(e: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_db::ledger_db::SlotCommit;
+    use sov_mock_da::{MockAddress, MockDaService};
+    use sov_rollup_interface::stf::BatchReceipt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_batch_da_proof() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let ledger_db = LedgerDB::with_path(temp_dir.path()).unwrap();
+        let da_service = MockDaService::new(MockAddress::new([7; 32]));
+
+        for blob in [b"first".to_vec(), b"second".to_vec()] {
+            da_service.send_transaction(&blob).await.unwrap();
+        }
+        for height in 1..=2 {
+            let block = da_service.get_block_at(height).await.unwrap();
+            let blobs = da_service.extract_relevant_blobs(&block);
+            let mut commit = SlotCommit::<_, Vec<u8>, Vec<u8>>::new(block);
+            for blob in blobs {
+                commit.add_batch(BatchReceipt {
+                    batch_hash: blob.hash(),
+                    tx_receipts: vec![],
+                    inner: vec![],
+                });
+            }
+            ledger_db.commit_slot(commit).unwrap();
+        }
+
+        let module = rpc_module(ledger_db, da_service.clone(), 1);
+        let response: Option<BatchDaProofResponse> =
+            module.call("rollup_getBatchDaProof", [2]).await.unwrap();
+        let response = response.unwrap();
+        let block = da_service.get_block_at(2).await.unwrap();
+        assert_eq!(2, response.slot_number);
+        assert_eq!(2, response.da_height);
+        assert_eq!(
+            format!("0x{}", hex::encode(block.hash())),
+            response.block_hash
+        );
+        assert_eq!(0, response.blob_index);
+        assert_eq!(MockAddress::new([7; 32]).to_string(), response.sender);
+
+        let missing: Option<BatchDaProofResponse> =
+            module.call("rollup_getBatchDaProof", [3]).await.unwrap();
+        assert!(missing.is_none());
+    }
+}
@@ -0,0 +1,104 @@
+//! Collects the errors of the genesis files of a rollup, so a malformed genesis is reported
+//! as a whole, with the file and the field of every error, before any state is written.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+use crate::read_json_file;
+
+/// The errors found while reading and validating the genesis files.
+#[derive(Debug, Default)]
+pub struct GenesisErrors {
+    errors: Vec<(PathBuf, String)>,
+}
+
+impl GenesisErrors {
+    /// Reads the genesis file at `path`. If it's missing or malformed,
+    /// the error is recorded and `None` is returned, so the other files are still checked.
+    pub fn read_json_file<T: serde::de::DeserializeOwned>(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Option<T> {
+        match read_json_file(&path) {
+            Ok(config) => Some(config),
+            Err(error) => {
+                // The root cause of the error has the line and the column of a malformed field
+                self.push(path, error.root_cause());
+                None
+            }
+        }
+    }
+
+    /// Records an error of the genesis file at `path`.
+    pub fn push(&mut self, path: impl AsRef<Path>, error: impl Display) {
+        self.errors
+            .push((path.as_ref().to_path_buf(), error.to_string()));
+    }
+
+    /// Records the errors of the genesis file at `path`, as returned by the validation of
+    /// its module configuration. Every error should start with the field it was found in.
+    pub fn extend(&mut self, path: impl AsRef<Path>, errors: impl IntoIterator<Item = String>) {
+        for error in errors {
+            self.push(&path, error);
+        }
+    }
+
+    /// Returns `true` if no error was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Fails with all the recorded errors, one per line, if there are any.
+    pub fn into_result(self) -> anyhow::Result<()> {
+        if self.errors.is_empty() {
+            return Ok(());
+        }
+        let errors = self
+            .errors
+            .iter()
+            .map(|(path, error)| format!("  {}: {}", path.display(), error))
+            .collect::<Vec<_>>()
+            .join("\n");
+        anyhow::bail!(
+            "Found {} errors in the genesis files:\n{}",
+            self.errors.len(),
+            errors
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        #[allow(dead_code)]
+        amount: u64,
+    }
+
+    #[test]
+    fn test_reports_all_errors() {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let valid = tmpdir.path().join("valid.json");
+        let malformed = tmpdir.path().join("malformed.json");
+        std::fs::write(&valid, r#"{"amount": 1}"#).unwrap();
+        std::fs::write(&malformed, r#"{"amount": "one"}"#).unwrap();
+
+        let mut errors = GenesisErrors::default();
+        assert!(errors.read_json_file::<Config>(&valid).is_some());
+        assert!(errors.is_empty());
+        assert!(errors.read_json_file::<Config>(&malformed).is_none());
+        assert!(errors
+            .read_json_file::<Config>(tmpdir.path().join("missing.json"))
+            .is_none());
+        errors.extend(&valid, vec!["amount: can't be 1".to_owned()]);
+
+        let report = errors.into_result().unwrap_err().to_string();
+        assert!(report.starts_with("Found 3 errors in the genesis files:\n"));
+        assert!(report.contains("malformed.json: invalid type: string \"one\", expected u64"));
+        assert!(report.contains("line 1 column"));
+        assert!(report.contains("missing.json: "));
+        assert!(report.contains("valid.json: amount: can't be 1"));
+    }
+}
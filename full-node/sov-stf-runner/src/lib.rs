@@ -1,13 +1,27 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "native")]
+mod admin_rpc;
 #[cfg(feature = "native")]
 mod config;
+#[cfg(feature = "native")]
+mod config_watcher;
+#[cfg(feature = "native")]
+mod da_proof_rpc;
+#[cfg(feature = "native")]
+mod genesis_errors;
+#[cfg(feature = "native")]
+mod metrics;
 #[cfg(feature = "mock")]
 /// Testing utilities.
 #[cfg(feature = "mock")]
 pub mod mock;
 #[cfg(feature = "native")]
+mod node_status;
+#[cfg(feature = "native")]
+mod proof_rpc;
+#[cfg(feature = "native")]
 mod prover_service;
 
 #[cfg(feature = "native")]
@@ -18,13 +32,42 @@ use anyhow::Context;
 #[cfg(feature = "native")]
 pub use config::RpcConfig;
 #[cfg(feature = "native")]
+pub use da_proof_rpc::BatchDaProofResponse;
+#[cfg(feature = "native")]
+pub use proof_rpc::ProofResponse;
+#[cfg(feature = "native")]
 pub use prover_service::*;
 #[cfg(feature = "native")]
+mod rest;
+#[cfg(feature = "native")]
+mod rpc_limits;
+#[cfg(feature = "native")]
+pub use rpc_limits::LIMIT_EXCEEDED_CODE;
+#[cfg(feature = "native")]
 mod runner;
 #[cfg(feature = "native")]
-pub use config::{from_toml_path, ProverServiceConfig, RollupConfig, RunnerConfig, StorageConfig};
+mod soft_confirmation;
+#[cfg(feature = "native")]
+mod state_diff;
+#[cfg(feature = "native")]
+pub use config::{
+    from_toml_path, AdminRpcConfig, BatchBuilderConfig, BatchOrderingConfig, DaCompressionConfig,
+    DaSubmissionConfig, GasOracleConfig, MetricsConfig, ProverServiceConfig, RestConfig,
+    RollupConfig, RpcLimitsConfig, RunnerConfig, SoftConfirmationConfig, StateDiffConfig,
+    StorageConfig,
+};
+#[cfg(feature = "native")]
+pub use config_watcher::{ConfigUpdates, ConfigWatcher, ReloadableConfig};
+#[cfg(feature = "native")]
+pub use genesis_errors::GenesisErrors;
+#[cfg(feature = "native")]
+pub use node_status::{DaConnectionStatus, EthSyncProgress, EthSyncStatus, NodeStatus, SyncPhase};
 #[cfg(feature = "native")]
 pub use runner::*;
+#[cfg(feature = "native")]
+pub use soft_confirmation::{SoftBatchStatus, SoftConfirmationTracker};
+#[cfg(feature = "native")]
+pub use state_diff::{SlotStateDiff, StateChange, StatePrefix};
 
 /// Implements the `StateTransitionVerifier` type for checking the validity of a state transition
 pub mod verifier;
@@ -0,0 +1,223 @@
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use jsonrpsee::helpers::MethodResponseResult;
+use jsonrpsee::server::logger::{HttpRequest, Logger, MethodKind, Params, TransportProtocol};
+use once_cell::sync::Lazy;
+use prometheus::{
+    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, register_int_gauge, Encoder, Histogram, HistogramVec, IntCounter,
+    IntCounterVec, IntGauge, TextEncoder,
+};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{info, warn};
+
+pub(crate) static BLOCKS_PROCESSED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        // metric name
+        "rollup_blocks_processed_total",
+        // metric description
+        "Number of DA blocks processed by the rollup"
+    )
+    .unwrap()
+});
+
+pub(crate) static BATCHES_PROCESSED: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        // metric name
+        "rollup_batches_processed_total",
+        // metric description
+        "Number of batches applied by the rollup"
+    )
+    .unwrap()
+});
+
+pub(crate) static BATCH_APPLY_LATENCY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "rollup_batch_apply_latency_seconds",
+        // metric description
+        "Time taken to apply all the batches of a DA block, in seconds",
+        exponential_buckets(/*start=*/ 1e-4, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+pub(crate) static PROVER_QUEUE_DEPTH: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        // metric name
+        "prover_queue_depth",
+        // metric description
+        "Number of blocks waiting for their proof to be generated"
+    )
+    .unwrap()
+});
+
+static RPC_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "rpc_requests_total",
+        // metric description
+        "Number of RPC calls handled by the rollup",
+        // metric labels (dimensions)
+        &["method", "result"]
+    )
+    .unwrap()
+});
+
+static RPC_REQUEST_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "rpc_request_latency_seconds",
+        // metric description
+        "RPC call latency in seconds",
+        // metric labels (dimensions)
+        &["method"],
+        exponential_buckets(/*start=*/ 1e-5, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Records the rate and latency of the RPC calls.
+#[derive(Clone)]
+pub(crate) struct RpcMetricsLogger;
+
+impl Logger for RpcMetricsLogger {
+    type Instant = Instant;
+
+    fn on_connect(&self, _remote_addr: SocketAddr, _request: &HttpRequest, _t: TransportProtocol) {}
+
+    fn on_request(&self, _transport: TransportProtocol) -> Self::Instant {
+        Instant::now()
+    }
+
+    fn on_call(
+        &self,
+        _method_name: &str,
+        _params: Params,
+        _kind: MethodKind,
+        _transport: TransportProtocol,
+    ) {
+    }
+
+    fn on_result(
+        &self,
+        method_name: &str,
+        success_or_error: MethodResponseResult,
+        started_at: Self::Instant,
+        _transport: TransportProtocol,
+    ) {
+        let result = if success_or_error.is_success() {
+            "success"
+        } else {
+            "error"
+        };
+        RPC_REQUESTS.with_label_values(&[method_name, result]).inc();
+        RPC_REQUEST_LATENCY_SECONDS
+            .with_label_values(&[method_name])
+            .observe(started_at.elapsed().as_secs_f64());
+    }
+
+    fn on_response(&self, _result: &str, _started_at: Self::Instant, _t: TransportProtocol) {}
+
+    fn on_disconnect(&self, _remote_addr: SocketAddr, _transport: TransportProtocol) {}
+}
+
+/// Serves all the metrics registered in the default prometheus registry
+/// at the `/metrics` path, in the prometheus text format.
+pub(crate) async fn serve_metrics(listener: TcpListener) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(async move {
+                    if let Err(e) = handle_connection(stream).await {
+                        warn!("Failed to serve metrics: {}", e);
+                    }
+                });
+            }
+            Err(e) => warn!("Failed to accept metrics connection: {}", e),
+        }
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream) -> anyhow::Result<()> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 1024];
+    // Only the request line is needed, the headers are ignored.
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..read]);
+        anyhow::ensure!(request.len() <= 8 * 1024, "Request is too large");
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .split_whitespace();
+    let response = match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let encoder = TextEncoder::new();
+            let mut body = Vec::new();
+            encoder.encode(&prometheus::gather(), &mut body)?;
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                encoder.format_type(),
+                body.len()
+            )
+            .into_bytes();
+            response.extend(body);
+            response
+        }
+        _ => b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec(),
+    };
+
+    stream.write_all(&response).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Starts the metrics HTTP server on the given address, returning the address it's bound to.
+pub(crate) async fn start_metrics_server(address: SocketAddr) -> anyhow::Result<SocketAddr> {
+    let listener = TcpListener::bind(address).await?;
+    let bound_address = listener.local_addr()?;
+    info!("Starting metrics server at {}", &bound_address);
+    tokio::spawn(serve_metrics(listener));
+    Ok(bound_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        BLOCKS_PROCESSED.inc();
+        let address = start_metrics_server("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+
+        let response = get(address, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("rollup_blocks_processed_total"));
+
+        let response = get(address, "/other").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+    }
+}
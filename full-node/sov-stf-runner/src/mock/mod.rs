@@ -1,6 +1,7 @@
 use std::marker::PhantomData;
 
 use sov_rollup_interface::da::DaSpec;
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{BatchReceipt, SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::{ValidityCondition, Zkvm};
 
@@ -58,6 +59,39 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
                 inner: (),
             }],
             witness: (),
+            state_diff: vec![],
         }
     }
+
+    fn apply_soft_batch(
+        &self,
+        _pre_state_root: &[u8; 0],
+        _base_state: Self::PreState,
+        _witness: Self::Witness,
+        _slot_header: &Da::BlockHeader,
+        _validity_condition: &Da::ValidityCondition,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    > {
+        SlotResult {
+            state_root: [],
+            change_set: (),
+            batch_receipts: vec![BatchReceipt {
+                batch_hash: soft_batch.hash,
+                tx_receipts: vec![],
+                inner: (),
+            }],
+            witness: (),
+            state_diff: vec![],
+        }
+    }
+
+    fn verify_soft_batch(_soft_batch: &SignedSoftConfirmationBatch) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
 }
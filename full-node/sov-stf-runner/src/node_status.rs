@@ -0,0 +1,213 @@
+use std::sync::{Arc, RwLock};
+
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+
+/// How far the node is from the head of the DA layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    /// The node hasn't heard of the head of the DA layer yet.
+    Starting,
+    /// The node is processing DA blocks below the head of the DA layer.
+    CatchingUp,
+    /// The node has processed the head of the DA layer, and is waiting for the next block.
+    Synced,
+}
+
+/// The health of the connection of the node to the DA layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaConnectionStatus {
+    /// Whether the last request to the DA layer succeeded.
+    pub healthy: bool,
+    /// The number of requests to the DA layer which failed since the last successful one.
+    pub consecutive_failures: u32,
+    /// The error of the last failed request to the DA layer, if any.
+    pub last_error: Option<String>,
+}
+
+/// The progress of the node, served by the `rollup_status` RPC method.
+/// All heights are DA block heights.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeStatus {
+    /// The sync phase of the node.
+    pub phase: SyncPhase,
+    /// The height of the first block processed since the node started.
+    pub starting_height: u64,
+    /// The height of the head of the DA layer, if the node has heard of it.
+    pub da_head_height: Option<u64>,
+    /// The height of the last block processed by the node.
+    pub last_processed_height: u64,
+    /// The height of the last block finalized and committed to the ledger, if any.
+    pub last_finalized_height: Option<u64>,
+    /// The height of the last block whose proof was sent to the DA layer, if any.
+    pub last_proven_height: Option<u64>,
+    /// The number of finalized blocks whose proof wasn't sent to the DA layer yet.
+    pub prover_backlog: usize,
+    /// The health of the connection to the DA layer.
+    pub da_connection: DaConnectionStatus,
+}
+
+impl NodeStatus {
+    /// Creates the status of a node which processes DA blocks from `starting_height`.
+    pub fn new(starting_height: u64) -> Self {
+        Self {
+            phase: SyncPhase::Starting,
+            starting_height,
+            da_head_height: None,
+            last_processed_height: starting_height.saturating_sub(1),
+            last_finalized_height: None,
+            last_proven_height: None,
+            prover_backlog: 0,
+            da_connection: DaConnectionStatus::default(),
+        }
+    }
+
+    /// Records the height of the head of the DA layer, which was just fetched.
+    pub fn record_da_head(&mut self, height: u64) {
+        self.da_head_height = Some(height);
+        self.da_connection = DaConnectionStatus {
+            healthy: true,
+            consecutive_failures: 0,
+            last_error: None,
+        };
+        self.refresh_phase();
+    }
+
+    /// Records a failed request to the DA layer.
+    pub fn record_da_error(&mut self, error: &anyhow::Error) {
+        self.da_connection.healthy = false;
+        self.da_connection.consecutive_failures += 1;
+        self.da_connection.last_error = Some(error.to_string());
+    }
+
+    /// Records that the block at `height` was processed. The processed blocks can go back
+    /// when the DA layer reorganizes.
+    pub fn record_processed(&mut self, height: u64) {
+        self.last_processed_height = height;
+        // The node can't be behind a DA head it processed
+        if self.da_head_height.is_some_and(|head| head < height) {
+            self.da_head_height = Some(height);
+        }
+        self.refresh_phase();
+    }
+
+    fn refresh_phase(&mut self) {
+        self.phase = match self.da_head_height {
+            None => SyncPhase::Starting,
+            Some(head) if head > self.last_processed_height => SyncPhase::CatchingUp,
+            Some(_) => SyncPhase::Synced,
+        };
+    }
+
+    /// The response to `eth_syncing`: `false` if the node is synced, an object with
+    /// the DA heights the node started from, is at, and is catching up to otherwise.
+    pub fn eth_syncing(&self) -> EthSyncStatus {
+        match (self.phase, self.da_head_height) {
+            (SyncPhase::Synced, _) => EthSyncStatus::NotSyncing(false),
+            (_, head) => EthSyncStatus::Syncing(EthSyncProgress {
+                starting_block: format!("{:#x}", self.starting_height),
+                current_block: format!("{:#x}", self.last_processed_height),
+                highest_block: format!("{:#x}", head.unwrap_or(self.last_processed_height)),
+            }),
+        }
+    }
+}
+
+/// The response to `eth_syncing`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EthSyncStatus {
+    /// The node is synced. Always `false`.
+    NotSyncing(bool),
+    /// The node is catching up with the DA layer.
+    Syncing(EthSyncProgress),
+}
+
+/// The progress of a node catching up with the DA layer, with hex encoded heights.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EthSyncProgress {
+    /// The height of the first block processed since the node started.
+    pub starting_block: String,
+    /// The height of the last processed block.
+    pub current_block: String,
+    /// The height of the head of the DA layer.
+    pub highest_block: String,
+}
+
+pub(crate) fn rpc_module(status: Arc<RwLock<NodeStatus>>) -> RpcModule<Arc<RwLock<NodeStatus>>> {
+    let mut rpc = RpcModule::new(status);
+    rpc.register_method("eth_syncing", |_, status| {
+        Ok::<_, ErrorObjectOwned>(read_status(status)?.eth_syncing())
+    })
+    .expect("Failed to register node status RPC methods");
+    rpc.register_method("rollup_status", |_, status| {
+        Ok::<_, ErrorObjectOwned>(read_status(status)?.clone())
+    })
+    .expect("Failed to register node status RPC methods");
+    rpc
+}
+
+fn read_status(
+    status: &RwLock<NodeStatus>,
+) -> Result<std::sync::RwLockReadGuard<'_, NodeStatus>, ErrorObjectOwned> {
+    status.read().map_err(|e| {
+        ErrorObjectOwned::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_phases() {
+        let mut status = NodeStatus::new(10);
+        assert_eq!(SyncPhase::Starting, status.phase);
+
+        status.record_da_head(12);
+        assert_eq!(SyncPhase::CatchingUp, status.phase);
+        assert_eq!(
+            serde_json::json!({
+                "startingBlock": "0xa",
+                "currentBlock": "0x9",
+                "highestBlock": "0xc",
+            }),
+            serde_json::to_value(status.eth_syncing()).unwrap()
+        );
+
+        status.record_processed(12);
+        assert_eq!(SyncPhase::Synced, status.phase);
+        assert_eq!(
+            serde_json::json!(false),
+            serde_json::to_value(status.eth_syncing()).unwrap()
+        );
+
+        // Blocks processed past the last known head are the head
+        status.record_processed(13);
+        assert_eq!(Some(13), status.da_head_height);
+        assert_eq!(SyncPhase::Synced, status.phase);
+    }
+
+    #[test]
+    fn test_da_connection_health() {
+        let mut status = NodeStatus::new(1);
+        status.record_da_error(&anyhow::anyhow!("connection refused"));
+        status.record_da_error(&anyhow::anyhow!("connection refused"));
+        assert_eq!(
+            DaConnectionStatus {
+                healthy: false,
+                consecutive_failures: 2,
+                last_error: Some("connection refused".to_string()),
+            },
+            status.da_connection
+        );
+
+        status.record_da_head(1);
+        assert!(status.da_connection.healthy);
+        assert_eq!(0, status.da_connection.consecutive_failures);
+    }
+}
@@ -0,0 +1,46 @@
+//! RPC methods serving the proofs stored in the ledger, so that light clients and bridges
+//! can fetch proofs directly from full nodes.
+
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+use sov_db::ledger_db::LedgerDB;
+use sov_db::schema::types::SlotNumber;
+
+/// A proof stored by the node, as returned by `prover_getProof`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofResponse {
+    /// The number of the first slot proven.
+    pub first_slot: u64,
+    /// The number of the last slot proven.
+    pub last_slot: u64,
+    /// The hex encoded borsh serialization of the proof, as accepted by `prover_verifyProof`.
+    pub proof: String,
+}
+
+pub(crate) fn rpc_module(ledger_db: LedgerDB) -> RpcModule<LedgerDB> {
+    let mut rpc = RpcModule::new(ledger_db);
+    rpc.register_method("prover_getProof", |params, ledger_db| {
+        let slot_number: u64 = params.one()?;
+        let Some((last_slot, stored)) = ledger_db
+            .get_proof_for_slot(SlotNumber(slot_number))
+            .map_err(internal_error)?
+        else {
+            return Ok(None);
+        };
+        let proof = borsh::to_vec(&stored.proof).map_err(internal_error)?;
+        Ok::<_, ErrorObjectOwned>(Some(ProofResponse {
+            first_slot: stored.first_slot.0,
+            last_slot: last_slot.0,
+            proof: format!("0x{}", hex::encode(proof)),
+        }))
+    })
+    .expect("Failed to register proof RPC methods");
+    rpc
+}
+
+fn internal_error(e: impl ToString) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+}
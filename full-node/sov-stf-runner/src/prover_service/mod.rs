@@ -1,10 +1,11 @@
 mod parallel;
 use async_trait::async_trait;
+use jsonrpsee::Methods;
 pub use parallel::ParallelProverService;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
-use sov_rollup_interface::zk::StateTransitionData;
+use sov_rollup_interface::zk::{Proof, StateTransitionData};
 use thiserror::Error;
 
 /// The possible configurations of the prover.
@@ -31,8 +32,8 @@ pub enum WitnessSubmissionStatus {
 /// Represents the status of a DA proof submission.
 #[derive(Debug, Eq, PartialEq)]
 pub enum ProofSubmissionStatus {
-    /// Indicates successful submission of the proof to the DA.
-    Success,
+    /// Indicates successful submission of the proof to the DA, with the submitted proof.
+    Success(Proof),
     /// Indicates that proof generation is currently in progress.
     ProofGenerationInProgress,
 }
@@ -42,8 +43,30 @@ pub enum ProofSubmissionStatus {
 pub enum ProofProcessingStatus {
     /// Indicates that proof generation is currently in progress.
     ProvingInProgress,
-    /// Indicates that the prover is busy and will not initiate a new proving process.
-    Busy,
+    /// Indicates that all the workers are busy. The job is queued and starts once a worker is available.
+    Queued,
+}
+
+/// The status of a proving job, as reported over RPC.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofJobStatus {
+    /// The witness has been submitted, but the proof generation is not triggered yet.
+    WitnessSubmitted,
+    /// The job is waiting for a worker to be available.
+    Queued,
+    /// The proof is being generated.
+    Proving {
+        /// The current attempt at generating the proof, starting at 1.
+        attempt: u32,
+    },
+    /// The proof is generated and waits to be sent to the DA.
+    Proved,
+    /// The proof generation failed, including all the retries.
+    Failed {
+        /// The error of the last attempt.
+        error: String,
+    },
 }
 
 /// An error that occurred during ZKP proving.
@@ -84,6 +107,7 @@ pub trait ProverService {
     ) -> WitnessSubmissionStatus;
 
     /// Creates ZKP prove for a block corresponding to `block_header_hash`.
+    /// The proof is generated in the background, its progress is reported by `send_proof_to_da`.
     async fn prove(
         &self,
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
@@ -95,4 +119,15 @@ pub trait ProverService {
         &self,
         block_header_hash: <<Self::DaService as DaService>::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofSubmissionStatus, anyhow::Error>;
+
+    /// Returns the aggregated proofs generated since the last call, in the order of their slot ranges.
+    /// They are ready to be sent to the DA with `DaService::send_aggregated_zk_proof`.
+    async fn take_aggregated_proofs(&self) -> Vec<Proof> {
+        Vec::new()
+    }
+
+    /// RPC methods reporting the status of the proving jobs, served alongside the rollup RPC methods.
+    fn rpc_methods(&self) -> Option<Methods> {
+        None
+    }
 }
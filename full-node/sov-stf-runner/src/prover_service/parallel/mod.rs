@@ -1,14 +1,17 @@
 mod prover;
+mod queue;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use jsonrpsee::Methods;
 use prover::Prover;
+use queue::PersistentQueue;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_rollup_interface::da::DaSpec;
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{StateTransitionData, ZkvmHost};
+use sov_rollup_interface::zk::{Proof, StateTransitionData, ZkvmHost};
 
 use super::{ProverService, ProverServiceError};
 use crate::config::ProverServiceConfig;
@@ -19,6 +22,13 @@ use crate::{
 };
 
 /// Prover service that generates proofs in parallel.
+///
+/// Proving jobs are queued while all the workers are busy, and the failed ones are retried
+/// up to [`ProverServiceConfig::max_retries`] times. If [`ProverServiceConfig::queue_path`] is set,
+/// the jobs are persisted there until their proof is generated, and resumed after a restart.
+///
+/// With an aggregation program set by [`ParallelProverService::with_aggregation`], every
+/// [`ProverServiceConfig::aggregated_proof_block_jump`] proofs are aggregated into a single proof.
 pub struct ParallelProverService<StateRoot, Witness, Da, Vm, V>
 where
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]>,
@@ -27,11 +37,7 @@ where
     Vm: ZkvmHost,
     V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync,
 {
-    vm: Vm,
-    prover_config: Arc<ProofGenConfig<V, Da, Vm>>,
-
-    zk_storage: V::PreState,
-    prover_state: Prover<StateRoot, Witness, Da>,
+    prover_state: Prover<StateRoot, Witness, Da, Vm, V>,
 }
 
 impl<StateRoot, Witness, Da, Vm, V> ParallelProverService<StateRoot, Witness, Da, Vm, V>
@@ -39,9 +45,9 @@ where
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
     Da: DaService,
-    Vm: ZkvmHost,
-    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync,
-    V::PreState: Clone + Send + Sync,
+    Vm: ZkvmHost + 'static,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
+    V::PreState: Clone + Send + Sync + 'static,
 {
     /// Creates a new prover.
     pub fn new(
@@ -63,19 +69,53 @@ where
             RollupProverConfig::Prove => ProofGenConfig::Prover,
         };
 
-        let prover_config = Arc::new(config);
+        let queue =
+            prover_service_config
+                .queue_path
+                .and_then(|path| match PersistentQueue::open(path) {
+                    Ok(queue) => Some(queue),
+                    Err(e) => {
+                        tracing::warn!(
+                        "Failed to open the prover queue, the proving jobs won't be persisted: {}",
+                        e
+                    );
+                        None
+                    }
+                });
 
         Self {
-            vm,
-            prover_config,
             prover_state: Prover::new(
                 num_threads,
                 prover_service_config.aggregated_proof_block_jump,
+                prover_service_config.max_retries,
+                queue,
+                Arc::new(config),
+                vm,
+                zk_storage,
             ),
-            zk_storage,
         }
     }
 
+    /// Aggregates the proofs of consecutive slots with the given zkVM host,
+    /// which runs the recursive aggregation program. The program verifies the slot proofs
+    /// against `slot_code_commitment`, the code commitment of the rollup program.
+    pub fn with_aggregation(
+        mut self,
+        aggregation_vm: Vm,
+        slot_code_commitment: Vm::CodeCommitment,
+    ) -> Self {
+        self.prover_state
+            .set_aggregation(aggregation_vm, slot_code_commitment);
+        self
+    }
+
+    /// Serves `prover_verifyProof`, which verifies slot proofs natively against the given
+    /// code commitment of the rollup program.
+    pub fn with_proof_verification(mut self, code_commitment: Vm::CodeCommitment) -> Self {
+        self.prover_state.set_code_commitment(code_commitment);
+        self
+    }
+
     /// Creates a new prover.
     pub fn new_with_default_workers(
         vm: Vm,
@@ -109,7 +149,8 @@ where
     Da: DaService,
     Vm: ZkvmHost + 'static,
     V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
-    V::PreState: Clone + Send + Sync,
+    V::PreState: Clone + Send + Sync + 'static,
+    Vm::CodeCommitment: Send + Sync,
 {
     type StateRoot = StateRoot;
 
@@ -132,15 +173,7 @@ where
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofProcessingStatus, ProverServiceError> {
-        let vm = self.vm.clone();
-        let zk_storage = self.zk_storage.clone();
-
-        self.prover_state.start_proving(
-            block_header_hash,
-            self.prover_config.clone(),
-            vm,
-            zk_storage,
-        )
+        self.prover_state.start_proving(block_header_hash)
     }
 
     async fn send_proof_to_da(
@@ -150,4 +183,12 @@ where
         self.prover_state
             .get_proof_submission_status_and_remove_on_success(block_header_hash)
     }
+
+    async fn take_aggregated_proofs(&self) -> Vec<Proof> {
+        self.prover_state.take_aggregated_proofs()
+    }
+
+    fn rpc_methods(&self) -> Option<Methods> {
+        Some(self.prover_state.rpc_methods())
+    }
 }
@@ -1,30 +1,59 @@
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::ops::Deref;
 use std::sync::{Arc, RwLock};
 
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{Methods, RpcModule};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
 use sov_rollup_interface::services::da::DaService;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{Proof, StateTransitionData, ZkvmHost};
+use sov_rollup_interface::zk::{Proof, StateTransition, StateTransitionData, ZkvmHost};
 
+use super::queue::PersistentQueue;
 use super::ProverServiceError;
+use crate::metrics::PROVER_QUEUE_DEPTH;
+use crate::verifier::verify_slot_proof;
 use crate::{
-    ProofGenConfig, ProofProcessingStatus, ProofSubmissionStatus, WitnessSubmissionStatus,
+    ProofGenConfig, ProofJobStatus, ProofProcessingStatus, ProofSubmissionStatus,
+    WitnessSubmissionStatus,
 };
 
+// A block to prove, with the data its proof is generated from.
+type ProofJob<StateRoot, Witness, Da> = (
+    <Da as DaSpec>::SlotHash,
+    StateTransitionData<StateRoot, Witness, Da>,
+);
+
+// The aggregation of a range of slot proofs.
+enum Aggregation {
+    InProgress,
+    // The aggregation failed after all its retries, and is retried with the next slot proof.
+    Failed(Vec<Proof>),
+    Done(Proof),
+}
+
 enum ProverStatus<StateRoot, Witness, Da: DaSpec> {
     WitnessSubmitted(StateTransitionData<StateRoot, Witness, Da>),
-    ProvingInProgress,
+    Queued(StateTransitionData<StateRoot, Witness, Da>),
+    ProvingInProgress { attempt: u32 },
     Proved(Proof),
     Err(anyhow::Error),
 }
 
 struct ProverState<StateRoot, Witness, Da: DaSpec> {
     prover_status: HashMap<Da::SlotHash, ProverStatus<StateRoot, Witness, Da>>,
+    // Blocks waiting for a worker, in the order their proofs were requested.
+    queue: VecDeque<Da::SlotHash>,
     pending_tasks_count: usize,
+    // Proofs sent to the DA which are not aggregated yet, in the order they were sent.
+    slot_proofs: Vec<Proof>,
+    // Aggregations by the order of their slot ranges.
+    aggregated_proofs: BTreeMap<u64, Aggregation>,
+    next_aggregation: u64,
 }
 
 impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
@@ -32,12 +61,23 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         self.prover_status.remove(hash)
     }
 
+    fn set_to_queued(
+        &mut self,
+        hash: Da::SlotHash,
+        state_transition_data: StateTransitionData<StateRoot, Witness, Da>,
+    ) {
+        self.prover_status
+            .insert(hash.clone(), ProverStatus::Queued(state_transition_data));
+        self.queue.push_back(hash);
+    }
+
     fn set_to_proving(
         &mut self,
         hash: Da::SlotHash,
+        attempt: u32,
     ) -> Option<ProverStatus<StateRoot, Witness, Da>> {
         self.prover_status
-            .insert(hash, ProverStatus::ProvingInProgress)
+            .insert(hash, ProverStatus::ProvingInProgress { attempt })
     }
 
     fn set_to_proved(
@@ -58,6 +98,37 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         self.prover_status.get(&hash)
     }
 
+    // Takes the oldest queued job and marks it as being proved.
+    fn next_job(&mut self) -> Option<ProofJob<StateRoot, Witness, Da>> {
+        while let Some(hash) = self.queue.pop_front() {
+            if let Some(ProverStatus::Queued(state_transition_data)) =
+                self.set_to_proving(hash.clone(), 1)
+            {
+                return Some((hash, state_transition_data));
+            }
+        }
+        None
+    }
+
+    fn job_status(&self, hash: [u8; 32]) -> Option<ProofJobStatus> {
+        let (_, status) = self
+            .prover_status
+            .iter()
+            .find(|(slot_hash, _)| Into::<[u8; 32]>::into((*slot_hash).clone()) == hash)?;
+
+        Some(match status {
+            ProverStatus::WitnessSubmitted(_) => ProofJobStatus::WitnessSubmitted,
+            ProverStatus::Queued(_) => ProofJobStatus::Queued,
+            ProverStatus::ProvingInProgress { attempt } => {
+                ProofJobStatus::Proving { attempt: *attempt }
+            }
+            ProverStatus::Proved(_) => ProofJobStatus::Proved,
+            ProverStatus::Err(e) => ProofJobStatus::Failed {
+                error: e.to_string(),
+            },
+        })
+    }
+
     fn inc_task_count_if_not_busy(&mut self, num_threads: usize) -> bool {
         if self.pending_tasks_count >= num_threads {
             return false;
@@ -71,37 +142,256 @@ impl<StateRoot, Witness, Da: DaSpec> ProverState<StateRoot, Witness, Da> {
         assert!(self.pending_tasks_count > 0);
         self.pending_tasks_count -= 1;
     }
+
+    // Removes the aggregated proofs which are ready, up to the first one which isn't.
+    fn take_aggregated_proofs(&mut self) -> Vec<Proof> {
+        let mut proofs = Vec::new();
+        while let Some(entry) = self.aggregated_proofs.first_entry() {
+            if !matches!(entry.get(), Aggregation::Done(_)) {
+                break;
+            }
+            if let Aggregation::Done(proof) = entry.remove() {
+                proofs.push(proof);
+            }
+        }
+        proofs
+    }
+
+    // Blocks whose witness was submitted, but whose proof is not generated yet.
+    fn update_queue_depth_metric(&self) {
+        let queue_depth = self
+            .prover_status
+            .values()
+            .filter(|status| {
+                matches!(
+                    status,
+                    ProverStatus::WitnessSubmitted(_)
+                        | ProverStatus::Queued(_)
+                        | ProverStatus::ProvingInProgress { .. }
+                )
+            })
+            .count();
+        PROVER_QUEUE_DEPTH.set(queue_depth as i64);
+    }
 }
 
-// A prover that generates proofs in parallel using a thread pool. If the pool is saturated,
-// the prover will reject new jobs.
-pub(crate) struct Prover<StateRoot, Witness, Da: DaService> {
+// Everything a worker thread needs to generate proofs.
+struct Worker<StateRoot, Witness, Da, Vm, V>
+where
+    Da: DaService,
+    Vm: ZkvmHost,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec>,
+{
     prover_state: Arc<RwLock<ProverState<StateRoot, Witness, Da::Spec>>>,
+    config: Arc<ProofGenConfig<V, Da, Vm>>,
+    vm: Vm,
+    zk_storage: V::PreState,
+    max_retries: u32,
+    queue: Option<PersistentQueue>,
+}
+
+impl<StateRoot, Witness, Da, Vm, V> Clone for Worker<StateRoot, Witness, Da, Vm, V>
+where
+    Da: DaService,
+    Vm: ZkvmHost,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec>,
+    V::PreState: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            prover_state: self.prover_state.clone(),
+            config: self.config.clone(),
+            vm: self.vm.clone(),
+            zk_storage: self.zk_storage.clone(),
+            max_retries: self.max_retries,
+            queue: self.queue.clone(),
+        }
+    }
+}
+
+impl<StateRoot, Witness, Da, Vm, V> Worker<StateRoot, Witness, Da, Vm, V>
+where
+    Da: DaService,
+    StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
+    Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
+    Vm: ZkvmHost + 'static,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
+    V::PreState: Clone + Send + Sync + 'static,
+{
+    // Proves `job`, then the queued jobs, until the queue is empty.
+    fn run(self, mut job: Option<ProofJob<StateRoot, Witness, Da::Spec>>) {
+        while let Some((block_header_hash, state_transition_data)) = job {
+            let proof = tracing::info_span!("guest_execution")
+                .in_scope(|| self.prove(block_header_hash.clone(), state_transition_data));
+
+            // The job is kept on disk if it failed, so it's retried after a restart.
+            if let (Some(queue), Ok(_)) = (&self.queue, &proof) {
+                if let Err(e) = queue.remove(block_header_hash.clone().into()) {
+                    tracing::warn!(
+                        "Failed to remove the persisted proving job for {:?}: {}",
+                        block_header_hash,
+                        e
+                    );
+                }
+            }
+
+            let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
+            prover_state.set_to_proved(block_header_hash, proof);
+            job = prover_state.next_job();
+            if job.is_none() {
+                prover_state.dec_task_count();
+            }
+            prover_state.update_queue_depth_metric();
+        }
+    }
+
+    fn prove(
+        &self,
+        block_header_hash: <Da::Spec as DaSpec>::SlotHash,
+        state_transition_data: StateTransitionData<StateRoot, Witness, Da::Spec>,
+    ) -> Result<Proof, anyhow::Error> {
+        let mut vm = self.vm.clone();
+        vm.add_hint(state_transition_data);
+
+        let mut attempt = 1;
+        loop {
+            match make_proof(vm.clone(), self.config.clone(), self.zk_storage.clone()) {
+                Ok(proof) => return Ok(proof),
+                Err(e) if attempt <= self.max_retries => {
+                    tracing::warn!(
+                        "Proof generation for {:?} failed at attempt {}, retrying: {}",
+                        block_header_hash,
+                        attempt,
+                        e
+                    );
+                    attempt += 1;
+                    self.prover_state
+                        .write()
+                        .expect("Lock was poisoned")
+                        .set_to_proving(block_header_hash.clone(), attempt);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+// A prover that generates proofs in parallel using a thread pool. If the pool is saturated,
+// the jobs are queued and picked up by the workers as soon as they are done with their current job.
+//
+// If an aggregation program is set, every `aggregated_proof_block_jump` proofs sent to the DA
+// are aggregated into a single proof of their slot range.
+pub(crate) struct Prover<StateRoot, Witness, Da, Vm, V>
+where
+    Da: DaService,
+    Vm: ZkvmHost,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec>,
+{
+    worker: Worker<StateRoot, Witness, Da, Vm, V>,
     num_threads: usize,
     pool: rayon::ThreadPool,
-    _aggregated_proof_block_jump: u64,
+    aggregated_proof_block_jump: u64,
+    // The host of the aggregation program, with the code commitment of the slot proofs it aggregates.
+    aggregation: Option<(Vm, Vm::CodeCommitment)>,
+    code_commitment: Option<Vm::CodeCommitment>,
 }
 
-impl<StateRoot, Witness, Da> Prover<StateRoot, Witness, Da>
+impl<StateRoot, Witness, Da, Vm, V> Prover<StateRoot, Witness, Da, Vm, V>
 where
     Da: DaService,
     StateRoot: Serialize + DeserializeOwned + Clone + AsRef<[u8]> + Send + Sync + 'static,
     Witness: Serialize + DeserializeOwned + Send + Sync + 'static,
+    Vm: ZkvmHost + 'static,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
+    V::PreState: Clone + Send + Sync + 'static,
 {
-    pub(crate) fn new(num_threads: usize, _aggregated_proof_block_jump: u64) -> Self {
-        Self {
+    pub(crate) fn new(
+        num_threads: usize,
+        aggregated_proof_block_jump: u64,
+        max_retries: u32,
+        queue: Option<PersistentQueue>,
+        config: Arc<ProofGenConfig<V, Da, Vm>>,
+        vm: Vm,
+        zk_storage: V::PreState,
+    ) -> Self {
+        let mut prover_state = ProverState {
+            prover_status: Default::default(),
+            queue: Default::default(),
+            pending_tasks_count: Default::default(),
+            slot_proofs: Default::default(),
+            aggregated_proofs: Default::default(),
+            next_aggregation: Default::default(),
+        };
+
+        // Resume the jobs which were not proved before the restart.
+        if let Some(queue) = &queue {
+            match queue.load::<StateRoot, Witness, Da::Spec>() {
+                Ok(jobs) => {
+                    for state_transition_data in jobs {
+                        let header_hash = state_transition_data.da_block_header.hash();
+                        prover_state.set_to_queued(header_hash, state_transition_data);
+                    }
+                }
+                Err(e) => tracing::warn!("Failed to load the persisted proving jobs: {}", e),
+            }
+        }
+
+        let prover = Self {
+            worker: Worker {
+                prover_state: Arc::new(RwLock::new(prover_state)),
+                config,
+                vm,
+                zk_storage,
+                max_retries,
+                queue,
+            },
             num_threads,
             pool: rayon::ThreadPoolBuilder::new()
                 .num_threads(num_threads)
                 .build()
                 .unwrap(),
+            aggregated_proof_block_jump,
+            aggregation: None,
+            code_commitment: None,
+        };
 
-            prover_state: Arc::new(RwLock::new(ProverState {
-                prover_status: Default::default(),
-                pending_tasks_count: Default::default(),
-            })),
-            _aggregated_proof_block_jump,
+        let mut prover_state = prover
+            .worker
+            .prover_state
+            .write()
+            .expect("Lock was poisoned");
+        if !prover_state.queue.is_empty() {
+            tracing::info!(
+                "Resuming {} persisted proving jobs",
+                prover_state.queue.len()
+            );
         }
+        while !prover_state.queue.is_empty() && prover_state.inc_task_count_if_not_busy(num_threads)
+        {
+            let job = prover_state.next_job();
+            prover.spawn_worker(job);
+        }
+        prover_state.update_queue_depth_metric();
+        drop(prover_state);
+
+        prover
+    }
+
+    pub(crate) fn set_aggregation(
+        &mut self,
+        aggregation_vm: Vm,
+        slot_code_commitment: Vm::CodeCommitment,
+    ) {
+        self.aggregation = Some((aggregation_vm, slot_code_commitment));
+    }
+
+    pub(crate) fn set_code_commitment(&mut self, code_commitment: Vm::CodeCommitment) {
+        self.code_commitment = Some(code_commitment);
+    }
+
+    fn spawn_worker(&self, job: Option<ProofJob<StateRoot, Witness, Da::Spec>>) {
+        let worker = self.worker.clone();
+        self.pool.spawn(move || worker.run(job));
     }
 
     pub(crate) fn submit_witness(
@@ -109,34 +399,35 @@ where
         state_transition_data: StateTransitionData<StateRoot, Witness, Da::Spec>,
     ) -> WitnessSubmissionStatus {
         let header_hash = state_transition_data.da_block_header.hash();
-        let data = ProverStatus::WitnessSubmitted(state_transition_data);
 
-        let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
-        let entry = prover_state.prover_status.entry(header_hash);
+        let mut prover_state = self.worker.prover_state.write().expect("Lock was poisoned");
+        let entry = prover_state.prover_status.entry(header_hash.clone());
 
-        match entry {
+        let status = match entry {
             Entry::Occupied(_) => WitnessSubmissionStatus::WitnessExist,
             Entry::Vacant(v) => {
-                v.insert(data);
+                if let Some(queue) = &self.worker.queue {
+                    if let Err(e) = queue.save(&state_transition_data) {
+                        tracing::warn!(
+                            "Failed to persist the proving job for {:?}: {}",
+                            header_hash,
+                            e
+                        );
+                    }
+                }
+                v.insert(ProverStatus::WitnessSubmitted(state_transition_data));
                 WitnessSubmissionStatus::SubmittedForProving
             }
-        }
+        };
+        prover_state.update_queue_depth_metric();
+        status
     }
 
-    pub(crate) fn start_proving<Vm, V>(
+    pub(crate) fn start_proving(
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
-        config: Arc<ProofGenConfig<V, Da, Vm>>,
-        mut vm: Vm,
-        zk_storage: V::PreState,
-    ) -> Result<ProofProcessingStatus, ProverServiceError>
-    where
-        Vm: ZkvmHost + 'static,
-        V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
-        V::PreState: Send + Sync + 'static,
-    {
-        let prover_state_clone = self.prover_state.clone();
-        let mut prover_state = self.prover_state.write().expect("Lock was poisoned");
+    ) -> Result<ProofProcessingStatus, ProverServiceError> {
+        let mut prover_state = self.worker.prover_state.write().expect("Lock was poisoned");
 
         let prover_status = prover_state
             .remove(&block_header_hash)
@@ -144,35 +435,30 @@ where
 
         match prover_status {
             ProverStatus::WitnessSubmitted(state_transition_data) => {
-                let start_prover = prover_state.inc_task_count_if_not_busy(self.num_threads);
-
-                // Initiate a new proving job only if the prover is not busy.
-                if start_prover {
-                    prover_state.set_to_proving(block_header_hash.clone());
-                    vm.add_hint(state_transition_data);
-
-                    self.pool.spawn(move || {
-                        tracing::info_span!("guest_execution").in_scope(|| {
-                            let proof = make_proof(vm, config, zk_storage);
-
-                            let mut prover_state =
-                                prover_state_clone.write().expect("Lock was poisoned");
+                prover_state.set_to_queued(block_header_hash.clone(), state_transition_data);
 
-                            prover_state.set_to_proved(block_header_hash, proof);
-                            prover_state.dec_task_count();
-                        })
-                    });
+                // Start a new worker if the pool isn't saturated, otherwise the job waits in the queue.
+                if prover_state.inc_task_count_if_not_busy(self.num_threads) {
+                    let job = prover_state.next_job();
+                    self.spawn_worker(job);
+                }
+                prover_state.update_queue_depth_metric();
 
-                    Ok(ProofProcessingStatus::ProvingInProgress)
-                } else {
-                    Ok(ProofProcessingStatus::Busy)
+                match prover_state.get_prover_status(block_header_hash) {
+                    Some(ProverStatus::Queued(_)) => Ok(ProofProcessingStatus::Queued),
+                    _ => Ok(ProofProcessingStatus::ProvingInProgress),
                 }
             }
-            ProverStatus::ProvingInProgress => Err(anyhow::anyhow!(
-                "Proof generation for {:?} still in progress",
-                block_header_hash
-            )
-            .into()),
+            status @ (ProverStatus::Queued(_) | ProverStatus::ProvingInProgress { .. }) => {
+                prover_state
+                    .prover_status
+                    .insert(block_header_hash.clone(), status);
+                Err(anyhow::anyhow!(
+                    "Proof generation for {:?} still in progress",
+                    block_header_hash
+                )
+                .into())
+            }
             ProverStatus::Proved(_) => Err(anyhow::anyhow!(
                 "Witness for block_header_hash {:?}, submitted multiple times.",
                 block_header_hash,
@@ -186,16 +472,20 @@ where
         &self,
         block_header_hash: <Da::Spec as DaSpec>::SlotHash,
     ) -> Result<ProofSubmissionStatus, anyhow::Error> {
-        let mut prover_state = self.prover_state.write().unwrap();
+        let mut prover_state = self.worker.prover_state.write().unwrap();
         let status = prover_state.get_prover_status(block_header_hash.clone());
 
         match status {
-            Some(ProverStatus::ProvingInProgress) => {
+            Some(ProverStatus::Queued(_) | ProverStatus::ProvingInProgress { .. }) => {
                 Ok(ProofSubmissionStatus::ProofGenerationInProgress)
             }
             Some(ProverStatus::Proved(_)) => {
-                prover_state.remove(&block_header_hash);
-                Ok(ProofSubmissionStatus::Success)
+                let Some(ProverStatus::Proved(proof)) = prover_state.remove(&block_header_hash)
+                else {
+                    unreachable!("The status of the block was just checked");
+                };
+                self.add_to_aggregation(&mut prover_state, proof.clone());
+                Ok(ProofSubmissionStatus::Success(proof))
             }
             Some(ProverStatus::WitnessSubmitted(_)) => Err(anyhow::anyhow!(
                 "Witness for {:?} was submitted, but the proof generation is not triggered.",
@@ -208,6 +498,145 @@ where
             )),
         }
     }
+
+    fn add_to_aggregation(
+        &self,
+        prover_state: &mut ProverState<StateRoot, Witness, Da::Spec>,
+        proof: Proof,
+    ) {
+        if self.aggregation.is_none() || self.aggregated_proof_block_jump == 0 {
+            return;
+        }
+
+        // The ranges whose aggregation failed are retried before the next one is started
+        let failed: Vec<u64> = prover_state
+            .aggregated_proofs
+            .iter()
+            .filter(|(_, aggregation)| matches!(aggregation, Aggregation::Failed(_)))
+            .map(|(range, _)| *range)
+            .collect();
+        for range in failed {
+            if let Some(Aggregation::Failed(proofs)) = prover_state
+                .aggregated_proofs
+                .insert(range, Aggregation::InProgress)
+            {
+                self.spawn_aggregation(range, proofs);
+            }
+        }
+
+        prover_state.slot_proofs.push(proof);
+        if (prover_state.slot_proofs.len() as u64) < self.aggregated_proof_block_jump {
+            return;
+        }
+
+        let proofs = std::mem::take(&mut prover_state.slot_proofs);
+        let range = prover_state.next_aggregation;
+        prover_state.next_aggregation += 1;
+        prover_state
+            .aggregated_proofs
+            .insert(range, Aggregation::InProgress);
+        self.spawn_aggregation(range, proofs);
+    }
+
+    // Aggregates the proofs of a range in the background, retrying up to `max_retries` times.
+    // If it still fails, the proofs are kept so that the aggregation is retried later.
+    fn spawn_aggregation(&self, range: u64, proofs: Vec<Proof>) {
+        let Some((aggregation_vm, slot_code_commitment)) = &self.aggregation else {
+            return;
+        };
+        // The aggregation program reads the code commitment of the slot proofs before them
+        let mut vm = aggregation_vm.clone();
+        vm.add_hint(slot_code_commitment);
+        let config = self.worker.config.clone();
+        let max_retries = self.worker.max_retries;
+        let prover_state = self.worker.prover_state.clone();
+        self.pool.spawn(move || {
+            tracing::info_span!("proof_aggregation").in_scope(|| {
+                let mut attempt = 1;
+                let aggregation = loop {
+                    match make_aggregated_proof(vm.clone(), config.clone(), proofs.clone()) {
+                        Ok(proof) => break Aggregation::Done(proof),
+                        Err(e) if attempt <= max_retries => {
+                            tracing::warn!(
+                                "Aggregation of range {} failed at attempt {}, retrying: {}",
+                                range,
+                                attempt,
+                                e
+                            );
+                            attempt += 1;
+                        }
+                        Err(e) => {
+                            tracing::error!(
+                                "Failed to aggregate the proofs of range {}, it's retried with the next proof: {:?}",
+                                range,
+                                e
+                            );
+                            break Aggregation::Failed(proofs);
+                        }
+                    }
+                };
+
+                prover_state
+                    .write()
+                    .expect("Lock was poisoned")
+                    .aggregated_proofs
+                    .insert(range, aggregation);
+            })
+        });
+    }
+
+    pub(crate) fn take_aggregated_proofs(&self) -> Vec<Proof> {
+        self.worker
+            .prover_state
+            .write()
+            .expect("Lock was poisoned")
+            .take_aggregated_proofs()
+    }
+
+    pub(crate) fn rpc_methods(&self) -> Methods
+    where
+        Vm::CodeCommitment: Send + Sync,
+    {
+        let mut rpc = RpcModule::new(self.worker.prover_state.clone());
+        rpc.register_method("prover_getJobStatus", |params, prover_state| {
+            let hash: String = params.one()?;
+            let hash: [u8; 32] = hex::decode(hash.trim_start_matches("0x"))
+                .ok()
+                .and_then(|hash| hash.try_into().ok())
+                .ok_or_else(|| {
+                    ErrorObjectOwned::owned(
+                        ErrorCode::InvalidParams.code(),
+                        "expected a hex encoded 32 bytes hash",
+                        None::<()>,
+                    )
+                })?;
+            let prover_state = prover_state.read().map_err(|e| {
+                ErrorObjectOwned::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+            })?;
+            Ok::<_, ErrorObjectOwned>(prover_state.job_status(hash))
+        })
+        .expect("Failed to register prover RPC methods");
+
+        if let Some(code_commitment) = self.code_commitment.clone() {
+            rpc.register_method("prover_verifyProof", move |params, _| {
+                let proof: String = params.one()?;
+                let invalid_params = |message: String| {
+                    ErrorObjectOwned::owned(ErrorCode::InvalidParams.code(), message, None::<()>)
+                };
+                let proof = hex::decode(proof.trim_start_matches("0x")).map_err(|_| {
+                    invalid_params(
+                        "expected a hex encoded proof, as returned by prover_getProof".to_string(),
+                    )
+                })?;
+                let transition: StateTransition<Da::Spec, StateRoot> =
+                    verify_slot_proof::<Vm, Da::Spec, StateRoot>(&proof, &code_commitment)
+                        .map_err(|e| invalid_params(e.to_string()))?;
+                Ok::<_, ErrorObjectOwned>(transition)
+            })
+            .expect("Failed to register prover RPC methods");
+        }
+        rpc.into()
+    }
 }
 
 fn make_proof<V, Vm, Da>(
@@ -231,3 +660,37 @@ where
         ProofGenConfig::Prover => vm.run(true),
     }
 }
+
+// Runs the aggregation program, which verifies the slot proofs recursively.
+fn make_aggregated_proof<V, Vm, Da>(
+    mut vm: Vm,
+    config: Arc<ProofGenConfig<V, Da, Vm>>,
+    proofs: Vec<Proof>,
+) -> Result<Proof, anyhow::Error>
+where
+    Da: DaService,
+    Vm: ZkvmHost + 'static,
+    V: StateTransitionFunction<Vm::Guest, Da::Spec> + Send + Sync + 'static,
+{
+    let proofs: Vec<Vec<u8>> = proofs
+        .into_iter()
+        .map(|proof| match proof {
+            Proof::PublicInput(data) | Proof::Full(data) => data,
+        })
+        .collect();
+
+    match config.deref() {
+        // The slot proofs are not generated by the zkVM, so there is nothing to verify recursively.
+        ProofGenConfig::Skip | ProofGenConfig::Simulate(_) => {
+            Ok(Proof::PublicInput(Vec::default()))
+        }
+        ProofGenConfig::Execute => {
+            vm.add_hint(proofs);
+            vm.run(false)
+        }
+        ProofGenConfig::Prover => {
+            vm.add_hint(proofs);
+            vm.run(true)
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::zk::StateTransitionData;
+
+// Persists the proving jobs whose proof isn't generated yet, so they are resumed after a restart.
+// Every job is stored in its own file, named after the hash of its DA block.
+#[derive(Clone)]
+pub(crate) struct PersistentQueue {
+    dir: PathBuf,
+}
+
+impl PersistentQueue {
+    pub(crate) fn open(dir: PathBuf) -> anyhow::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn job_path(&self, block_header_hash: [u8; 32]) -> PathBuf {
+        self.dir
+            .join(format!("{}.json", hex::encode(block_header_hash)))
+    }
+
+    pub(crate) fn save<StateRoot, Witness, Da>(
+        &self,
+        state_transition_data: &StateTransitionData<StateRoot, Witness, Da>,
+    ) -> anyhow::Result<()>
+    where
+        StateRoot: Serialize + DeserializeOwned,
+        Witness: Serialize + DeserializeOwned,
+        Da: DaSpec,
+    {
+        let path = self.job_path(state_transition_data.da_block_header.hash().into());
+        // The job is written to a temporary file first, so a crash can't leave a truncated job behind.
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(state_transition_data)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    pub(crate) fn remove(&self, block_header_hash: [u8; 32]) -> anyhow::Result<()> {
+        match fs::remove_file(self.job_path(block_header_hash)) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    // Returns the persisted jobs, ordered by the height of their DA block.
+    pub(crate) fn load<StateRoot, Witness, Da>(
+        &self,
+    ) -> anyhow::Result<Vec<StateTransitionData<StateRoot, Witness, Da>>>
+    where
+        StateRoot: Serialize + DeserializeOwned,
+        Witness: Serialize + DeserializeOwned,
+        Da: DaSpec,
+    {
+        let mut jobs = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != "json")
+            {
+                continue;
+            }
+
+            match fs::read(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|data| Ok(serde_json::from_slice(&data)?))
+            {
+                Ok(job) => jobs.push(job),
+                Err(e) => tracing::warn!(
+                    "Skipping the proving job persisted at {}: {}",
+                    path.display(),
+                    e
+                ),
+            }
+        }
+        jobs.sort_by_key(|job: &StateTransitionData<StateRoot, Witness, Da>| {
+            job.da_block_header.height()
+        });
+        Ok(jobs)
+    }
+}
@@ -0,0 +1,370 @@
+use std::net::SocketAddr;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::{INVALID_PARAMS_CODE, METHOD_NOT_FOUND_CODE};
+use jsonrpsee::Methods;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+/// A parameter of a REST resource.
+struct RestParameter {
+    name: &'static str,
+    /// Either `path` or `query`.
+    location: &'static str,
+    /// The JSON schema type of the parameter.
+    schema_type: &'static str,
+    required: bool,
+    description: &'static str,
+}
+
+/// A REST resource, served by calling the JSON-RPC methods of the node.
+struct RestRoute {
+    /// The path of the resource, in the OpenAPI format.
+    path: &'static str,
+    summary: &'static str,
+    /// The JSON-RPC methods the resource is built from.
+    rpc_methods: &'static [&'static str],
+    parameters: &'static [RestParameter],
+}
+
+const MODE_PARAMETER: RestParameter = RestParameter {
+    name: "mode",
+    location: "query",
+    schema_type: "string",
+    required: false,
+    description: "One of `Compact`, `Standard` or `Full`",
+};
+
+/// The resources exposed by the REST API. The OpenAPI document is generated from them.
+const ROUTES: &[RestRoute] = &[
+    RestRoute {
+        path: "/blocks/{number}",
+        summary: "Returns the DA block (slot) processed by the rollup at the given number",
+        rpc_methods: &["ledger_getSlotByNumber"],
+        parameters: &[
+            RestParameter {
+                name: "number",
+                location: "path",
+                schema_type: "integer",
+                required: true,
+                description: "The number of the slot",
+            },
+            MODE_PARAMETER,
+        ],
+    },
+    RestRoute {
+        path: "/transactions/{hash}",
+        summary: "Returns the transaction with the given hash",
+        rpc_methods: &["ledger_getTransactionByHash"],
+        parameters: &[
+            RestParameter {
+                name: "hash",
+                location: "path",
+                schema_type: "string",
+                required: true,
+                description: "The 0x prefixed hex encoded hash of the transaction",
+            },
+            MODE_PARAMETER,
+        ],
+    },
+    RestRoute {
+        path: "/accounts/{address}/balance",
+        summary: "Returns the balance of an account for the given token",
+        rpc_methods: &["bank_balanceOf"],
+        parameters: &[
+            RestParameter {
+                name: "address",
+                location: "path",
+                schema_type: "string",
+                required: true,
+                description: "The address of the account",
+            },
+            RestParameter {
+                name: "token",
+                location: "query",
+                schema_type: "string",
+                required: true,
+                description: "The address of the token",
+            },
+            RestParameter {
+                name: "version",
+                location: "query",
+                schema_type: "integer",
+                required: false,
+                description: "The state version to read the balance at. Defaults to the latest",
+            },
+        ],
+    },
+    RestRoute {
+        path: "/sequencer/status",
+        summary: "Returns the head of the rollup and the status of the sequencer mempool, \
+            if the node runs a sequencer",
+        rpc_methods: &["ledger_getHead", "txpool_status"],
+        parameters: &[],
+    },
+];
+
+#[derive(Deserialize)]
+struct ModeQuery {
+    mode: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct BalanceQuery {
+    token: String,
+    version: Option<u64>,
+}
+
+/// The error responses of the REST API, with a JSON body `{"error": <message>}`.
+struct RestError(StatusCode, String);
+
+impl RestError {
+    fn not_found() -> Self {
+        Self(StatusCode::NOT_FOUND, "Resource not found".to_string())
+    }
+}
+
+impl From<RpcError> for RestError {
+    fn from(error: RpcError) -> Self {
+        match error {
+            RpcError::Call(error) => {
+                let status = match error.code() {
+                    METHOD_NOT_FOUND_CODE => StatusCode::NOT_FOUND,
+                    INVALID_PARAMS_CODE => StatusCode::BAD_REQUEST,
+                    _ => StatusCode::INTERNAL_SERVER_ERROR,
+                };
+                Self(status, error.message().to_string())
+            }
+            error => Self(StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+        }
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        (self.0, Json(json!({ "error": self.1 }))).into_response()
+    }
+}
+
+type RestResult = Result<Json<Value>, RestError>;
+
+const NO_PARAMS: &[Value] = &[];
+
+/// Calls a JSON-RPC method, answering with a 404 if it returned nothing.
+async fn call(methods: &Methods, method: &str, params: Vec<Value>) -> RestResult {
+    match methods.call::<_, Value>(method, params.as_slice()).await? {
+        Value::Null => Err(RestError::not_found()),
+        value => Ok(Json(value)),
+    }
+}
+
+/// Appends the query mode to the params of a ledger query, if it's given.
+fn with_mode(mut params: Vec<Value>, mode: Option<Value>) -> Vec<Value> {
+    params.extend(mode);
+    params
+}
+
+async fn get_block(
+    State(methods): State<Methods>,
+    Path(number): Path<u64>,
+    Query(query): Query<ModeQuery>,
+) -> RestResult {
+    let params = with_mode(vec![json!(number)], query.mode);
+    call(&methods, "ledger_getSlotByNumber", params).await
+}
+
+async fn get_transaction(
+    State(methods): State<Methods>,
+    Path(hash): Path<String>,
+    Query(query): Query<ModeQuery>,
+) -> RestResult {
+    let params = with_mode(vec![json!(hash)], query.mode);
+    call(&methods, "ledger_getTransactionByHash", params).await
+}
+
+async fn get_balance(
+    State(methods): State<Methods>,
+    Path(address): Path<String>,
+    Query(query): Query<BalanceQuery>,
+) -> RestResult {
+    let params = vec![json!(query.version), json!(address), json!(query.token)];
+    call(&methods, "bank_balanceOf", params).await
+}
+
+async fn get_sequencer_status(State(methods): State<Methods>) -> RestResult {
+    let head = methods
+        .call::<_, Value>("ledger_getHead", NO_PARAMS)
+        .await?;
+    // Only sequencer nodes serve the mempool status.
+    let txpool = if methods.method("txpool_status").is_some() {
+        methods.call::<_, Value>("txpool_status", NO_PARAMS).await?
+    } else {
+        Value::Null
+    };
+    Ok(Json(json!({ "head": head, "txpool": txpool })))
+}
+
+async fn get_openapi() -> Json<Value> {
+    Json(openapi_document())
+}
+
+/// Generates the OpenAPI 3 document describing the REST API.
+fn openapi_document() -> Value {
+    let paths: serde_json::Map<String, Value> = ROUTES
+        .iter()
+        .map(|route| {
+            let parameters: Vec<Value> = route
+                .parameters
+                .iter()
+                .map(|parameter| {
+                    json!({
+                        "name": parameter.name,
+                        "in": parameter.location,
+                        "required": parameter.required,
+                        "description": parameter.description,
+                        "schema": { "type": parameter.schema_type },
+                    })
+                })
+                .collect();
+            let operation = json!({
+                "summary": route.summary,
+                "description": format!(
+                    "Served by the `{}` JSON-RPC method(s).",
+                    route.rpc_methods.join("`, `")
+                ),
+                "parameters": parameters,
+                "responses": {
+                    "200": { "description": "The requested resource, as returned by the JSON-RPC API" },
+                    "400": { "description": "The parameters are invalid" },
+                    "404": { "description": "The resource doesn't exist" },
+                    "500": { "description": "The node failed to serve the resource" },
+                },
+            });
+            (route.path.to_string(), json!({ "get": operation }))
+        })
+        .collect();
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Sovereign rollup REST API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+    })
+}
+
+/// Converts an OpenAPI path (`/blocks/{number}`) into an axum one (`/blocks/:number`).
+fn axum_path(path: &str) -> String {
+    path.replace('{', ":").replace('}', "")
+}
+
+fn router(methods: Methods) -> Router {
+    let handlers = [
+        get(get_block),
+        get(get_transaction),
+        get(get_balance),
+        get(get_sequencer_status),
+    ];
+    ROUTES
+        .iter()
+        .zip(handlers)
+        .fold(Router::new(), |router, (route, handler)| {
+            router.route(&axum_path(route.path), handler)
+        })
+        .route("/openapi.json", get(get_openapi))
+        .with_state(methods)
+}
+
+/// Starts the REST gateway on the given address, returning the address it's bound to.
+/// Every resource is served by calling the given JSON-RPC methods.
+pub(crate) async fn start_rest_server(
+    address: SocketAddr,
+    methods: Methods,
+) -> anyhow::Result<SocketAddr> {
+    let listener = std::net::TcpListener::bind(address)?;
+    listener.set_nonblocking(true)?;
+    let bound_address = listener.local_addr()?;
+    let server = axum::Server::from_tcp(listener)?.serve(router(methods).into_make_service());
+    info!("Starting REST server at {}", &bound_address);
+    tokio::spawn(async move {
+        if let Err(e) = server.await {
+            warn!("REST server stopped: {}", e);
+        }
+    });
+    Ok(bound_address)
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::RpcModule;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+
+    use super::*;
+
+    async fn get(address: SocketAddr, path: &str) -> String {
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream
+            .write_all(
+                format!(
+                    "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+                    path
+                )
+                .as_bytes(),
+            )
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    fn test_methods() -> Methods {
+        let mut rpc = RpcModule::new(());
+        rpc.register_method("ledger_getSlotByNumber", |params, _| {
+            let number: u64 = params.sequence().next()?;
+            Ok::<_, jsonrpsee::types::ErrorObjectOwned>(
+                (number == 1).then(|| json!({ "number": number })),
+            )
+        })
+        .unwrap();
+        rpc.register_method("ledger_getHead", |_, _| {
+            Ok::<_, jsonrpsee::types::ErrorObjectOwned>(json!({ "number": 1 }))
+        })
+        .unwrap();
+        rpc.into()
+    }
+
+    #[tokio::test]
+    async fn test_rest_endpoints() {
+        let address = start_rest_server("127.0.0.1:0".parse().unwrap(), test_methods())
+            .await
+            .unwrap();
+
+        let response = get(address, "/blocks/1").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(r#"{"number":1}"#));
+
+        let response = get(address, "/blocks/2").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+        // The bank module isn't registered on this node.
+        let response = get(address, "/accounts/sov1/balance?token=sov2").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found"));
+
+        let response = get(address, "/sequencer/status").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(r#""txpool":null"#));
+
+        let response = get(address, "/openapi.json").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("/accounts/{address}/balance"));
+    }
+}
@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use jsonrpsee::core::server::MethodCallback;
+use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::core::Error as RpcError;
+use jsonrpsee::types::error::INTERNAL_ERROR_CODE;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::{Methods, RpcModule};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use tokio::sync::Semaphore;
+use tracing::warn;
+
+use crate::config::RpcLimitsConfig;
+use crate::config_watcher::Reloaded;
+use crate::ConfigUpdates;
+
+/// The JSON-RPC error code of the calls rejected by a limit, as used by Ethereum nodes.
+pub const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The buckets of a rate limiter are only cleaned up once there are that many of them.
+const MAX_IDLE_BUCKETS: usize = 10_000;
+
+fn limit_exceeded(message: String) -> ErrorObjectOwned {
+    ErrorObjectOwned::owned(LIMIT_EXCEEDED_CODE, message, None::<()>)
+}
+
+/// A token bucket per key, refilled with `rate` tokens per second, and holding at most `rate` tokens.
+struct RateLimiter<K> {
+    rate: u32,
+    buckets: Mutex<HashMap<K, (f64, Instant)>>,
+}
+
+impl<K: Hash + Eq> RateLimiter<K> {
+    fn new(rate: u32) -> Self {
+        Self {
+            rate,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token from the bucket of `key`. Returns false if it's empty.
+    fn try_acquire(&self, key: K) -> bool {
+        let rate = f64::from(self.rate);
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_IDLE_BUCKETS {
+            // Full buckets are the same as missing ones
+            buckets.retain(|_, (tokens, updated)| {
+                *tokens + now.duration_since(*updated).as_secs_f64() * rate < rate
+            });
+        }
+
+        let (tokens, updated) = buckets.entry(key).or_insert((rate, now));
+        *tokens = (*tokens + now.duration_since(*updated).as_secs_f64() * rate).min(rate);
+        *updated = now;
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+}
+
+/// Enforces the per-method rate limits, and the concurrency limit of the expensive methods.
+struct MethodLimiter {
+    rates: HashMap<String, RateLimiter<()>>,
+    expensive_methods: Vec<String>,
+    expensive_calls: Option<(usize, Semaphore)>,
+}
+
+impl MethodLimiter {
+    fn new(config: &RpcLimitsConfig) -> Self {
+        Self {
+            rates: config
+                .method_rate_limits
+                .iter()
+                .map(|(method, rate)| (method.clone(), RateLimiter::new(*rate)))
+                .collect(),
+            expensive_methods: config.expensive_methods.clone(),
+            expensive_calls: config
+                .max_concurrent_expensive_calls
+                .map(|max| (max, Semaphore::new(max))),
+        }
+    }
+
+    fn is_expensive(&self, method: &str) -> bool {
+        self.expensive_methods
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => method.starts_with(prefix),
+                None => method == pattern,
+            })
+    }
+
+    #[cfg(test)]
+    fn is_limited(&self, method: &str) -> bool {
+        self.rates.contains_key(method)
+            || (self.expensive_calls.is_some() && self.is_expensive(method))
+    }
+
+    /// Runs the call of `method` on `methods`, unless one of its limits is exceeded.
+    async fn call(
+        &self,
+        methods: &Methods,
+        method: &'static str,
+        params: Option<Box<RawValue>>,
+    ) -> Result<Value, ErrorObjectOwned> {
+        if let Some(rate) = self.rates.get(method) {
+            if !rate.try_acquire(()) {
+                return Err(limit_exceeded(format!(
+                    "{} is limited to {} calls per second",
+                    method, rate.rate
+                )));
+            }
+        }
+
+        let _permit = match &self.expensive_calls {
+            Some((max, semaphore)) if self.is_expensive(method) => {
+                Some(semaphore.try_acquire().map_err(|_| {
+                    limit_exceeded(format!(
+                        "At most {} expensive calls are served at the same time",
+                        max
+                    ))
+                })?)
+            }
+            _ => None,
+        };
+
+        methods
+            .call::<_, Value>(method, RawParams(params))
+            .await
+            .map_err(|e| match e {
+                RpcError::Call(error) => error,
+                e => ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>),
+            })
+    }
+}
+
+/// The params of a call forwarded as they were received.
+pub(crate) struct RawParams(pub(crate) Option<Box<RawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<RawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+/// Wraps the methods so that the calls of the methods with a per-method rate limit, or which are
+/// expensive, are rejected with [`LIMIT_EXCEEDED_CODE`] once a limit is exceeded.
+/// The limits are the ones of the latest snapshot of `config_updates`, so every method is wrapped,
+/// in case a reload limits it. The subscriptions are served as they are.
+pub(crate) fn with_method_limits(
+    mut methods: RpcModule<()>,
+    config_updates: ConfigUpdates,
+) -> RpcModule<()> {
+    let limiter = Arc::new(Reloaded::new(config_updates, |config| {
+        MethodLimiter::new(&config.rpc_limits)
+    }));
+    let inner: Methods = methods.clone().into();
+
+    let calls: Vec<&'static str> = methods
+        .method_names()
+        .filter(|method| {
+            matches!(
+                methods.method(method),
+                Some(MethodCallback::Sync(_) | MethodCallback::Async(_))
+            )
+        })
+        .collect();
+    for method in calls {
+        methods.remove_method(method);
+        let limiter = limiter.clone();
+        let inner = inner.clone();
+        let registered = methods.register_async_method(method, move |params, _| {
+            let limiter = limiter.clone();
+            let inner = inner.clone();
+            async move {
+                let params = params
+                    .as_str()
+                    .map(|params| RawValue::from_string(params.to_owned()))
+                    .transpose()
+                    .map_err(|e| {
+                        ErrorObjectOwned::owned(INTERNAL_ERROR_CODE, e.to_string(), None::<()>)
+                    })?;
+                limiter.get().call(&inner, method, params).await
+            }
+        });
+        if let Err(e) = registered {
+            warn!("Failed to apply the limits of {}: {}", method, e);
+        }
+    }
+    methods
+}
+
+/// A HTTP middleware rejecting the requests of the clients exceeding their rate limit,
+/// with a [`LIMIT_EXCEEDED_CODE`] error and the `429 Too Many Requests` status.
+///
+/// The server doesn't give the address of the peer to its middlewares, so clients are
+/// identified by the `X-Forwarded-For` or `X-Real-IP` header of a trusted reverse proxy.
+/// For WebSocket clients, only the connection requests are limited.
+///
+/// The limit is the one of the latest snapshot of the [`ConfigUpdates`], clients are not limited
+/// while it isn't set.
+#[derive(Clone)]
+pub(crate) struct IpRateLimitLayer {
+    limiter: Arc<Reloaded<Option<RateLimiter<IpAddr>>>>,
+}
+
+impl IpRateLimitLayer {
+    pub(crate) fn new(config_updates: ConfigUpdates) -> Self {
+        Self {
+            limiter: Arc::new(Reloaded::new(config_updates, |config| {
+                config
+                    .rpc_limits
+                    .requests_per_second_per_ip
+                    .map(RateLimiter::new)
+            })),
+        }
+    }
+}
+
+impl<S> tower::Layer<S> for IpRateLimitLayer {
+    type Service = IpRateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        IpRateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// The service of [`IpRateLimitLayer`].
+#[derive(Clone)]
+pub(crate) struct IpRateLimit<S> {
+    inner: S,
+    limiter: Arc<Reloaded<Option<RateLimiter<IpAddr>>>>,
+}
+
+impl<S> tower::Service<hyper::Request<hyper::Body>> for IpRateLimit<S>
+where
+    S: tower::Service<hyper::Request<hyper::Body>, Response = hyper::Response<hyper::Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<hyper::Body>) -> Self::Future {
+        let limiter = self.limiter.get();
+        if let Some((limiter, ip)) = limiter.as_ref().zip(client_ip(request.headers())) {
+            if !limiter.try_acquire(ip) {
+                let error = limit_exceeded(format!(
+                    "{} is limited to {} requests per second",
+                    ip, limiter.rate
+                ));
+                let body = serde_json::json!({ "jsonrpc": "2.0", "error": error, "id": null });
+                let response = hyper::Response::builder()
+                    .status(hyper::StatusCode::TOO_MANY_REQUESTS)
+                    .header(hyper::header::CONTENT_TYPE, "application/json")
+                    .body(hyper::Body::from(body.to_string()))
+                    .expect("The response is valid");
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+        Box::pin(self.inner.call(request))
+    }
+}
+
+/// Returns the address of the client in the headers set by the reverse proxy, if any.
+fn client_ip(headers: &hyper::HeaderMap) -> Option<IpAddr> {
+    // The proxy appends the address of the client to the header, which may already contain
+    // addresses given by the client itself, so only the last one is trusted.
+    let forwarded_for = headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next());
+    let real_ip = headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok());
+    forwarded_for
+        .or(real_ip)
+        .and_then(|ip| ip.trim().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ConfigWatcher, ReloadableConfig};
+
+    const NO_PARAMS: &[Value] = &[];
+
+    fn config() -> RpcLimitsConfig {
+        RpcLimitsConfig {
+            method_rate_limits: HashMap::from([("cheap_method".to_string(), 2)]),
+            max_concurrent_expensive_calls: Some(1),
+            ..Default::default()
+        }
+    }
+
+    fn watcher(rpc_limits: RpcLimitsConfig) -> ConfigWatcher {
+        ConfigWatcher::new(ReloadableConfig {
+            rpc_limits,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn rate_limiter_refills() {
+        let limiter = RateLimiter::new(2);
+        assert!(limiter.try_acquire("a"));
+        assert!(limiter.try_acquire("a"));
+        assert!(!limiter.try_acquire("a"));
+        // Every key has its own bucket
+        assert!(limiter.try_acquire("b"));
+
+        std::thread::sleep(std::time::Duration::from_millis(600));
+        assert!(limiter.try_acquire("a"));
+        assert!(!limiter.try_acquire("a"));
+    }
+
+    #[test]
+    fn expensive_methods_match_prefixes() {
+        let limiter = MethodLimiter::new(&config());
+        assert!(limiter.is_expensive("eth_getLogs"));
+        assert!(limiter.is_expensive("debug_traceTransaction"));
+        assert!(!limiter.is_expensive("eth_getLogsByHash"));
+        assert!(!limiter.is_expensive("eth_call"));
+
+        assert!(limiter.is_limited("cheap_method"));
+        assert!(!limiter.is_limited("eth_call"));
+        let unlimited = MethodLimiter::new(&RpcLimitsConfig::default());
+        assert!(!unlimited.is_limited("eth_getLogs"));
+    }
+
+    #[tokio::test]
+    async fn calls_over_the_limits_are_rejected() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("cheap_method", |_, _| Ok::<_, ErrorObjectOwned>(1))
+            .unwrap();
+        module
+            .register_async_method("debug_traceSlowly", |_, _| async {
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                Ok::<_, ErrorObjectOwned>(2)
+            })
+            .unwrap();
+        let methods: Methods = with_method_limits(module, watcher(config()).subscribe()).into();
+
+        for _ in 0..2 {
+            let result: u32 = methods.call("cheap_method", NO_PARAMS).await.unwrap();
+            assert_eq!(1, result);
+        }
+        match methods.call::<_, u32>("cheap_method", NO_PARAMS).await {
+            Err(RpcError::Call(error)) => assert_eq!(LIMIT_EXCEEDED_CODE, error.code()),
+            result => panic!("Expected a limit exceeded error, got {:?}", result),
+        }
+
+        let slow_call = methods.call::<_, u32>("debug_traceSlowly", NO_PARAMS);
+        let concurrent_call = async {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            methods.call::<_, u32>("debug_traceSlowly", NO_PARAMS).await
+        };
+        let (slow_result, concurrent_result) = tokio::join!(slow_call, concurrent_call);
+        assert_eq!(2, slow_result.unwrap());
+        match concurrent_result {
+            Err(RpcError::Call(error)) => assert_eq!(LIMIT_EXCEEDED_CODE, error.code()),
+            result => panic!("Expected a limit exceeded error, got {:?}", result),
+        }
+    }
+
+    #[tokio::test]
+    async fn reloaded_limits_are_applied() {
+        let mut module = RpcModule::new(());
+        module
+            .register_method("cheap_method", |_, _| Ok::<_, ErrorObjectOwned>(1))
+            .unwrap();
+        let watcher = watcher(RpcLimitsConfig::default());
+        let methods: Methods = with_method_limits(module, watcher.subscribe()).into();
+        for _ in 0..5 {
+            let result: u32 = methods.call("cheap_method", NO_PARAMS).await.unwrap();
+            assert_eq!(1, result);
+        }
+
+        // The method wasn't limited when the server started
+        watcher
+            .publish(ReloadableConfig {
+                rpc_limits: config(),
+                ..Default::default()
+            })
+            .unwrap();
+        for _ in 0..2 {
+            let result: u32 = methods.call("cheap_method", NO_PARAMS).await.unwrap();
+            assert_eq!(1, result);
+        }
+        match methods.call::<_, u32>("cheap_method", NO_PARAMS).await {
+            Err(RpcError::Call(error)) => assert_eq!(LIMIT_EXCEEDED_CODE, error.code()),
+            result => panic!("Expected a limit exceeded error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn client_ip_is_read_from_the_proxy_headers() {
+        let mut headers = hyper::HeaderMap::new();
+        assert_eq!(None, client_ip(&headers));
+        headers.insert("x-real-ip", "10.0.0.2".parse().unwrap());
+        assert_eq!(Some("10.0.0.2".parse().unwrap()), client_ip(&headers));
+        headers.insert("x-forwarded-for", "1.2.3.4, 10.0.0.1".parse().unwrap());
+        assert_eq!(Some("10.0.0.1".parse().unwrap()), client_ip(&headers));
+    }
+}
@@ -1,21 +1,88 @@
 use std::collections::VecDeque;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
+use jsonrpsee::server::BatchRequestConfig;
 use jsonrpsee::RpcModule;
 use sov_db::ledger_db::{LedgerDB, SlotCommit};
+use sov_db::pruning::PruningHandle;
+use sov_db::schema::types::{CommittedSlotMarker, SlotNumber};
 use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec};
-use sov_rollup_interface::services::da::{DaService, SlotData};
+use sov_rollup_interface::services::da::{DaService, HeaderChain, SlotData};
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::StateTransitionFunction;
 use sov_rollup_interface::storage::HierarchicalStorageManager;
-use sov_rollup_interface::zk::{StateTransitionData, Zkvm, ZkvmHost};
-use tokio::sync::oneshot;
-use tracing::{debug, info};
+use sov_rollup_interface::zk::{Proof, StateTransitionData, Zkvm, ZkvmHost};
+use tokio::sync::{broadcast, oneshot};
+use tracing::{debug, info, warn};
 
+use crate::admin_rpc::{read_auth_token, split_admin_methods, start_admin_server};
+use crate::metrics::{
+    start_metrics_server, RpcMetricsLogger, BATCHES_PROCESSED, BATCH_APPLY_LATENCY_SECONDS,
+    BLOCKS_PROCESSED,
+};
+use crate::node_status::{self, NodeStatus};
+use crate::rest::start_rest_server;
+use crate::rpc_limits::{with_method_limits, IpRateLimitLayer};
+use crate::soft_confirmation::SoftConfirmationSync;
+use crate::state_diff::{SlotStateDiff, StateDiffPublisher};
 use crate::verifier::StateTransitionVerifier;
-use crate::{ProofSubmissionStatus, ProverService, RunnerConfig};
+use crate::{
+    da_proof_rpc, proof_rpc, ConfigUpdates, ConfigWatcher, ProofSubmissionStatus, ProverService,
+    ReloadableConfig, RpcLimitsConfig, RunnerConfig,
+};
 
 type StateRoot<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::StateRoot;
 type GenesisParams<ST, Vm, Da> = <ST as StateTransitionFunction<Vm, Da>>::GenesisParams;
+type SeenReceipts<ST, Vm, Da, B> = VecDeque<
+    SlotCommit<
+        B,
+        <ST as StateTransitionFunction<Vm, Da>>::BatchReceiptContents,
+        <ST as StateTransitionFunction<Vm, Da>>::TxReceiptContents,
+    >,
+>;
+type SeenTransitions<ST, Vm, Da> = VecDeque<
+    StateTransitionData<
+        <ST as StateTransitionFunction<Vm, Da>>::StateRoot,
+        <ST as StateTransitionFunction<Vm, Da>>::Witness,
+        Da,
+    >,
+>;
+
+/// The finalized slots whose proofs are being generated, from the oldest one: the hash and height
+/// of their DA block, and the version of the finalized state they were applied on.
+type ProofsInProgress<Da> = VecDeque<(<Da as DaSpec>::SlotHash, u64, u64)>;
+
+/// The holder of the state versions which a reorganization of the DA layer can roll back to.
+const REORG_PRUNING_HOLDER: &str = "reorgs";
+/// The holder of the state versions which the slots being proven were applied on.
+const PROVER_PRUNING_HOLDER: &str = "prover";
+
+/// How many finalized slots a subscriber can lag behind before it misses some.
+const FINALIZED_SLOTS_CHANNEL_CAPACITY: usize = 100;
+
+/// A processed slot which became finalized, published by the [`StateTransitionRunner`].
+/// Once it's published, the slot is committed to the ledger and its proof is being generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinalizedSlot {
+    /// The height of the DA block of the slot.
+    pub height: u64,
+    /// The hash of the DA block of the slot.
+    pub da_block_hash: [u8; 32],
+    /// The state root after the slot.
+    pub state_root: Vec<u8>,
+}
+
+/// What is restored of a processed DA block when a reorganization of the DA layer
+/// reverts the blocks following it.
+struct SlotCheckpoint<Root> {
+    /// The state root after the block.
+    state_root: Root,
+    /// The position of the slot in the ledger, once the block is finalized and committed.
+    committed: Option<CommittedSlotMarker>,
+}
 
 /// Combines `DaService` with `StateTransitionFunction` and "runs" the rollup.
 pub struct StateTransitionRunner<Stf, Sm, Da, Vm, Ps>
@@ -27,13 +94,33 @@ where
     Ps: ProverService,
 {
     start_height: u64,
+    /// The height of the DA block of the first slot.
+    genesis_height: u64,
     da_service: Da,
     stf: Stf,
     storage_manager: Sm,
     ledger_db: LedgerDB,
     state_root: StateRoot<Stf, Vm, Da::Spec>,
     listen_address: SocketAddr,
+    rpc_limits: RpcLimitsConfig,
+    /// The address of the admin RPC server, with its token.
+    admin_rpc: Option<(SocketAddr, String)>,
+    config_updates: Option<ConfigUpdates>,
+    metrics_address: Option<SocketAddr>,
+    rest_address: Option<SocketAddr>,
     prover_service: Ps,
+    soft_confirmations: Option<SoftConfirmationSync>,
+    /// The state after applying the unfinalized soft confirmation batches, with its root.
+    soft_state: Option<(StateRoot<Stf, Vm, Da::Spec>, Sm::NativeStorage)>,
+    /// The last processed DA blocks, to detect and revert reorganizations of the DA layer.
+    header_chain: HeaderChain<Da::Spec, SlotCheckpoint<StateRoot<Stf, Vm, Da::Spec>>>,
+    status: Arc<RwLock<NodeStatus>>,
+    state_diffs: StateDiffPublisher,
+    /// How many DA blocks must follow a processed block before it's finalized.
+    confirmation_depth: u64,
+    finalized_slots: broadcast::Sender<FinalizedSlot>,
+    /// Retains the state versions which are still needed from the pruning of the storage, if any.
+    pruning_handle: Option<PruningHandle>,
 }
 
 /// Represents the possible modes of execution for a zkVM program
@@ -79,6 +166,8 @@ where
     >,
 
     Ps: ProverService<StateRoot = Stf::StateRoot, Witness = Stf::Witness, DaService = Da>,
+    Sm::NativeChangeSet: Into<Sm::NativeStorage>,
+    Stf::StateRoot: Send + Sync,
 {
     /// Creates a new `StateTransitionRunner`.
     ///
@@ -96,6 +185,27 @@ where
         prover_service: Ps,
     ) -> Result<Self, anyhow::Error> {
         let rpc_config = runner_config.rpc_config;
+        let soft_confirmations = runner_config
+            .soft_confirmations
+            .map(|config| {
+                SoftConfirmationSync::new(
+                    config,
+                    <Stf as StateTransitionFunction<Vm, Da::Spec>>::verify_soft_batch,
+                )
+            })
+            .transpose()?;
+        let state_diffs = StateDiffPublisher::new(
+            runner_config
+                .state_diffs
+                .as_ref()
+                .map(|config| config.file_path.as_path()),
+        )?;
+
+        rollback_uncommitted_state(
+            &ledger_db,
+            &mut storage_manager,
+            matches!(init_variant, InitVariant::Genesis { .. }),
+        )?;
 
         let prev_state_root = match init_variant {
             InitVariant::Initialized(state_root) => {
@@ -123,6 +233,27 @@ where
         };
 
         let listen_address = SocketAddr::new(rpc_config.bind_host.parse()?, rpc_config.bind_port);
+        let admin_rpc = rpc_config
+            .admin
+            .map(|config| {
+                Ok::<_, anyhow::Error>((
+                    SocketAddr::new(config.bind_host.parse()?, config.bind_port),
+                    read_auth_token(&config.auth_token_path)?,
+                ))
+            })
+            .transpose()?;
+        let metrics_address = runner_config
+            .metrics
+            .map(|config| {
+                Ok::<_, anyhow::Error>(SocketAddr::new(config.bind_host.parse()?, config.bind_port))
+            })
+            .transpose()?;
+        let rest_address = runner_config
+            .rest
+            .map(|config| {
+                Ok::<_, anyhow::Error>(SocketAddr::new(config.bind_host.parse()?, config.bind_port))
+            })
+            .transpose()?;
 
         // Start the main rollup loop
         let item_numbers = ledger_db.get_next_items_numbers();
@@ -131,25 +262,132 @@ where
 
         Ok(Self {
             start_height,
+            genesis_height: runner_config.start_height,
             da_service,
             stf,
             storage_manager,
             ledger_db,
             state_root: prev_state_root,
             listen_address,
+            rpc_limits: rpc_config.limits,
+            admin_rpc,
+            config_updates: None,
+            metrics_address,
+            rest_address,
             prover_service,
+            soft_confirmations,
+            soft_state: None,
+            header_chain: HeaderChain::new(runner_config.max_reorg_depth),
+            status: Arc::new(RwLock::new(NodeStatus::new(start_height))),
+            state_diffs,
+            confirmation_depth: runner_config.confirmation_depth,
+            finalized_slots: broadcast::channel(FINALIZED_SLOTS_CHANNEL_CAPACITY).0,
+            pruning_handle: None,
         })
     }
 
-    /// Starts a RPC server with provided rpc methods.
+    /// Returns a receiver of the state diffs of the slots processed from now on, in order.
+    ///
+    /// A slot is published again when it's processed again, after a reorganization of the DA layer
+    /// or a restart of the node: its diff replaces the one previously published for its height.
+    /// Receivers lagging more than 100 slots behind miss the oldest diffs.
+    pub fn subscribe_state_diffs(&self) -> broadcast::Receiver<Arc<SlotStateDiff>> {
+        self.state_diffs.subscribe()
+    }
+
+    /// Returns a receiver of the processed slots finalized from now on, in order of height.
+    ///
+    /// A slot is finalized once its DA block is finalized by the DA layer, and followed by
+    /// the `confirmation_depth` of the [`RunnerConfig`]. Receivers lagging more than 100 slots
+    /// behind miss the oldest ones.
+    pub fn subscribe_finalized_slots(&self) -> broadcast::Receiver<FinalizedSlot> {
+        self.finalized_slots.subscribe()
+    }
+
+    /// Makes the RPC server apply the limits of the snapshots published by a [`ConfigWatcher`],
+    /// instead of the limits of the [`RunnerConfig`]. The maximum size of the batch requests
+    /// is only applied when the server starts.
+    pub fn with_config_updates(mut self, config_updates: ConfigUpdates) -> Self {
+        self.config_updates = Some(config_updates);
+        self
+    }
+
+    /// Prevents the pruning of the storage from deleting the state versions which the runner
+    /// still needs: the ones a reorganization of the DA layer can roll back to, and the ones
+    /// the slots being proven were applied on.
+    pub fn with_pruning_handle(mut self, pruning_handle: PruningHandle) -> Self {
+        self.pruning_handle = Some(pruning_handle);
+        self
+    }
+
+    /// Starts a RPC server with provided rpc methods, serving both HTTP and WebSocket connections.
+    /// If it's enabled in the [`RunnerConfig`], the REST gateway is started alongside it,
+    /// serving its resources from the same methods.
+    ///
+    /// The `admin_` methods are never served by this server: they're only served by the admin
+    /// RPC server, to the clients sending its token, if it's enabled in the [`RpcConfig`](crate::RpcConfig).
     pub async fn start_rpc_server(
         &self,
-        methods: RpcModule<()>,
+        mut methods: RpcModule<()>,
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) {
+        if let Some(soft_confirmations) = &self.soft_confirmations {
+            methods
+                .merge(soft_confirmations.rpc_module())
+                .expect("Failed to merge soft confirmation RPC methods");
+        }
+        methods
+            .merge(node_status::rpc_module(self.status.clone()))
+            .expect("Failed to merge node status RPC methods");
+        if let Some(prover_methods) = self.prover_service.rpc_methods() {
+            methods
+                .merge(prover_methods)
+                .expect("Failed to merge prover RPC methods");
+        }
+        methods
+            .merge(proof_rpc::rpc_module(self.ledger_db.clone()))
+            .expect("Failed to merge proof RPC methods");
+        methods
+            .merge(da_proof_rpc::rpc_module(
+                self.ledger_db.clone(),
+                self.da_service.clone(),
+                self.genesis_height,
+            ))
+            .expect("Failed to merge DA proof RPC methods");
+
+        let admin_methods = split_admin_methods(&mut methods);
+        if let Some((admin_address, auth_token)) = self.admin_rpc.clone() {
+            if let Err(e) = start_admin_server(admin_address, auth_token, admin_methods).await {
+                warn!("Failed to start the admin RPC server: {}", e);
+            }
+        }
+
+        if let Some(rest_address) = self.rest_address {
+            if let Err(e) = start_rest_server(rest_address, methods.clone().into()).await {
+                warn!("Failed to start the REST server: {}", e);
+            }
+        }
+
+        let config_updates = self.config_updates.clone().unwrap_or_else(|| {
+            ConfigWatcher::new(ReloadableConfig {
+                rpc_limits: self.rpc_limits.clone(),
+                ..Default::default()
+            })
+            .subscribe()
+        });
+        let methods = with_method_limits(methods, config_updates.clone());
+        let batch_config = match self.rpc_limits.max_batch_size {
+            Some(max_batch_size) => BatchRequestConfig::Limit(max_batch_size),
+            None => BatchRequestConfig::Unlimited,
+        };
+        let middleware = tower::ServiceBuilder::new().layer(IpRateLimitLayer::new(config_updates));
+
         let listen_address = self.listen_address;
         let _handle = tokio::spawn(async move {
             let server = jsonrpsee::server::ServerBuilder::default()
+                .set_logger(RpcMetricsLogger)
+                .set_batch_request_config(batch_config)
+                .set_middleware(middleware)
                 .build([listen_address].as_ref())
                 .await
                 .unwrap();
@@ -165,33 +403,70 @@ where
         });
     }
 
-    /// Runs the rollup.
+    /// Starts a HTTP server exposing the prometheus metrics of the node at `/metrics`,
+    /// if it's enabled in the [`RunnerConfig`]. Returns the address the server is bound to.
+    pub async fn start_metrics_server(&self) -> Result<Option<SocketAddr>, anyhow::Error> {
+        match self.metrics_address {
+            Some(address) => Ok(Some(start_metrics_server(address).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Runs the rollup until the node is asked to stop, with SIGINT (Ctrl-C) or SIGTERM.
     pub async fn run_in_process(&mut self) -> Result<(), anyhow::Error> {
+        self.run_until(shutdown_signal()).await
+    }
+
+    /// Runs the rollup until `shutdown` resolves.
+    ///
+    /// The shutdown is graceful: the slot being processed when it's requested is completed,
+    /// including its finalization, before the runner stops. The slots which are not finalized yet
+    /// are processed again once the node restarts.
+    pub async fn run_until(
+        &mut self,
+        shutdown: impl Future<Output = ()>,
+    ) -> Result<(), anyhow::Error> {
+        tokio::pin!(shutdown);
         let mut seen_block_headers: VecDeque<<Da::Spec as DaSpec>::BlockHeader> = VecDeque::new();
         let mut seen_receipts: VecDeque<_> = VecDeque::new();
+        let mut seen_transitions: VecDeque<_> = VecDeque::new();
+        let mut proofs_in_progress: ProofsInProgress<Da::Spec> = VecDeque::new();
         let mut height = self.start_height;
         loop {
+            self.refresh_da_head().await;
             debug!("Requesting data for height {}", height);
-            let mut filtered_block = self.da_service.get_block_at(height).await?;
+            let filtered_block = tokio::select! {
+                // Slots are only interrupted while waiting for their DA block
+                biased;
+                _ = &mut shutdown => {
+                    info!("Shutdown requested, stopping the rollup at height {}", height);
+                    return Ok(());
+                }
+                block = self.wait_for_block_at(height) => block?,
+            };
 
             // Checking if reorg happened or not.
-            if let Some(prev_block_header) = seen_block_headers.back() {
-                if prev_block_header.hash() != filtered_block.header().prev_hash() {
-                    tracing::warn!("Block at height={} does not belong in current chain. Chain has forked. Traversing backwards", height);
-                    while let Some(seen_block_header) = seen_block_headers.pop_back() {
-                        seen_receipts.pop_back();
-                        let block = self
-                            .da_service
-                            .get_block_at(seen_block_header.height())
-                            .await?;
-                        if block.header().prev_hash() == seen_block_header.prev_hash() {
-                            height = seen_block_header.height();
-                            filtered_block = block;
-                            break;
-                        }
-                    }
-                    tracing::info!("Resuming execution on height={}", height);
-                }
+            if !self.header_chain.is_extended_by(filtered_block.header()) {
+                warn!(
+                    "Block at height={} does not belong in current chain. Chain has forked. Traversing backwards",
+                    height
+                );
+                let ancestor_height = self
+                    .revert_to_common_ancestor(
+                        &mut seen_block_headers,
+                        &mut seen_receipts,
+                        &mut seen_transitions,
+                        &mut proofs_in_progress,
+                    )
+                    .await?;
+                height = ancestor_height + 1;
+                self.update_status(|status| {
+                    status.record_processed(ancestor_height);
+                    status.prover_backlog = proofs_in_progress.len();
+                });
+                self.retain_needed_state(&proofs_in_progress);
+                info!("Resuming execution on height={}", height);
+                continue;
             }
 
             let mut blobs = self.da_service.extract_relevant_blobs(&filtered_block);
@@ -215,8 +490,8 @@ where
             let pre_state = self
                 .storage_manager
                 .create_storage_on(filtered_block.header())?;
+            let apply_started_at = Instant::now();
             let slot_result = self.stf.apply_slot(
-                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                 &self.state_root,
                 pre_state,
                 Default::default(),
@@ -224,11 +499,44 @@ where
                 &filtered_block.validity_condition(),
                 &mut blobs,
             );
+            BATCH_APPLY_LATENCY_SECONDS.observe(apply_started_at.elapsed().as_secs_f64());
+            BLOCKS_PROCESSED.inc();
+            BATCHES_PROCESSED.inc_by(slot_result.batch_receipts.len() as u64);
+
+            let mut rebase_soft_state = false;
+            if let Some(soft_confirmations) = &self.soft_confirmations {
+                let da_batches: Vec<Vec<[u8; 32]>> = slot_result
+                    .batch_receipts
+                    .iter()
+                    .map(|receipt| {
+                        receipt
+                            .tx_receipts
+                            .iter()
+                            .map(|tx_receipt| tx_receipt.tx_hash)
+                            .collect()
+                    })
+                    .collect();
+                rebase_soft_state = soft_confirmations
+                    .tracker
+                    .write()
+                    .expect("Soft confirmation tracker lock is poisoned")
+                    .reconcile(height, &da_batches);
+            }
 
             for receipt in slot_result.batch_receipts {
                 data_to_commit.add_batch(receipt);
             }
 
+            if self.state_diffs.is_active() {
+                self.state_diffs.publish(SlotStateDiff::new(
+                    height,
+                    filtered_block.header().hash().into(),
+                    self.state_root.as_ref(),
+                    slot_result.state_root.as_ref(),
+                    &slot_result.state_diff,
+                ))?;
+            }
+
             let (inclusion_proof, completeness_proof) = self
                 .da_service
                 .get_extraction_proof(&filtered_block, &blobs)
@@ -236,7 +544,6 @@ where
 
             let transition_data: StateTransitionData<Stf::StateRoot, Stf::Witness, Da::Spec> =
                 StateTransitionData {
-                    // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1247): incorrect pre-state root in case of re-org
                     initial_state_root: self.state_root.clone(),
                     final_state_root: slot_result.state_root.clone(),
                     da_block_header: filtered_block.header().clone(),
@@ -248,80 +555,559 @@ where
 
             self.storage_manager
                 .save_change_set(filtered_block.header(), slot_result.change_set)?;
-
-            // ----------------
-            // Create ZK proof.
-            {
-                let header_hash = transition_data.da_block_header.hash();
-                self.prover_service.submit_witness(transition_data).await;
-                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185):
-                //   This section will be moved and called upon block finalization once we have fork management ready.
-                self.prover_service
-                    .prove(header_hash.clone())
-                    .await
-                    .expect("The proof creation should succeed");
-
-                loop {
-                    let status = self
-                        .prover_service
-                        .send_proof_to_da(header_hash.clone())
-                        .await;
-
-                    match status {
-                        Ok(ProofSubmissionStatus::Success) => {
-                            break;
-                        }
-                        // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add timeout handling.
-                        Ok(ProofSubmissionStatus::ProofGenerationInProgress) => {
-                            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
-                        }
-                        // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add handling for DA submission errors.
-                        Err(e) => panic!("{:?}", e),
-                    }
-                }
-            }
             let next_state_root = slot_result.state_root;
 
+            // The slot is only proven once it's finalized, so the transition is kept until then
             seen_receipts.push_back(data_to_commit);
+            seen_transitions.push_back(transition_data);
 
+            self.header_chain.push(
+                filtered_block.header().clone(),
+                SlotCheckpoint {
+                    state_root: next_state_root.clone(),
+                    committed: None,
+                },
+            )?;
             self.state_root = next_state_root;
             seen_block_headers.push_back(filtered_block.header().clone());
+            self.update_status(|status| status.record_processed(height));
             height += 1;
 
             // ----------------
             // Finalization. Done after seen block for proper handling of instant finality
             // Can be moved to another thread to improve throughput
-            let last_finalized = self.da_service.get_last_finalized_block_header().await?;
-            // For safety we finalize blocks one by one
-            tracing::info!(
-                "Last finalized header height is {}, ",
-                last_finalized.height()
-            );
+            let confirmed_height = self.confirmed_height().await?;
             // Checking all seen blocks, in case if there was delay in getting last finalized header.
+            // For safety we finalize blocks one by one
             while let Some(earliest_seen_header) = seen_block_headers.front() {
                 tracing::debug!(
                     "Checking seen header height={}",
                     earliest_seen_header.height()
                 );
-                if earliest_seen_header.height() <= last_finalized.height() {
-                    tracing::debug!(
-                        "Finalizing seen header height={}",
-                        earliest_seen_header.height()
-                    );
-                    self.storage_manager.finalize(earliest_seen_header)?;
-                    seen_block_headers.pop_front();
-                    let receipts = seen_receipts.pop_front().unwrap();
-                    self.ledger_db.commit_slot(receipts)?;
-                    continue;
+                if earliest_seen_header.height() > confirmed_height {
+                    break;
+                }
+                match self
+                    .da_service
+                    .get_finalized_at(earliest_seen_header.height())
+                    .await?
+                {
+                    None => break,
+                    Some(finalized) if finalized.hash() != earliest_seen_header.hash() => {
+                        // The block is reverted once the runner reaches the fork
+                        warn!(
+                            "The finalized block at height {} isn't the processed one",
+                            finalized.height()
+                        );
+                        break;
+                    }
+                    Some(_) => {
+                        tracing::debug!(
+                            "Finalizing seen header height={}",
+                            earliest_seen_header.height()
+                        );
+                        let pre_state_version = self.storage_manager.finalized_version()?;
+                        self.storage_manager.finalize(earliest_seen_header)?;
+                        if let Some(soft_confirmations) = &self.soft_confirmations {
+                            soft_confirmations
+                                .tracker
+                                .write()
+                                .expect("Soft confirmation tracker lock is poisoned")
+                                .finalize(earliest_seen_header.height());
+                            rebase_soft_state = true;
+                        }
+                        let finalized_header = seen_block_headers.pop_front().unwrap();
+                        let mut receipts = seen_receipts.pop_front().unwrap();
+                        // The slot is only fully committed once it's in the ledger, along with the version
+                        // of the state finalized for it. Until then, the state is rolled back on startup.
+                        let marker = CommittedSlotMarker {
+                            slot_number: SlotNumber(
+                                self.ledger_db.get_next_items_numbers().slot_number,
+                            ),
+                            state_version: self.storage_manager.finalized_version()?,
+                        };
+                        receipts.set_state_version(marker.state_version);
+                        self.ledger_db.commit_slot(receipts)?;
+                        let mut state_root = self.state_root.as_ref().to_vec();
+                        if let Some(checkpoint) =
+                            self.header_chain.get_mut(finalized_header.height())
+                        {
+                            checkpoint.committed = Some(marker);
+                            state_root = checkpoint.state_root.as_ref().to_vec();
+                        }
+                        self.update_status(|status| {
+                            status.last_finalized_height = Some(finalized_header.height());
+                        });
+                        let ledger_db = self.ledger_db.clone();
+                        tokio::task::spawn_blocking(move || {
+                            if let Err(e) = ledger_db.prune() {
+                                tracing::error!("Failed to prune the ledger: {:?}", e);
+                            }
+                        });
+
+                        // ----------------
+                        // Create ZK proof.
+                        let transition_data = seen_transitions.pop_front().unwrap();
+                        let header_hash = transition_data.da_block_header.hash();
+                        self.prover_service.submit_witness(transition_data).await;
+                        self.prover_service
+                            .prove(header_hash.clone())
+                            .await
+                            .expect("The proof creation should succeed");
+                        // The proof is generated in the background, block processing doesn't wait for it.
+                        proofs_in_progress.push_back((
+                            header_hash,
+                            finalized_header.height(),
+                            pre_state_version,
+                        ));
+
+                        // Sending only fails when there is no subscriber
+                        let _ = self.finalized_slots.send(FinalizedSlot {
+                            height: finalized_header.height(),
+                            da_block_hash: finalized_header.hash().into(),
+                            state_root,
+                        });
+                    }
+                }
+            }
+            self.send_generated_proofs(&mut proofs_in_progress).await;
+            self.update_status(|status| status.prover_backlog = proofs_in_progress.len());
+            self.retain_needed_state(&proofs_in_progress);
+
+            // The soft state is built on top of the finalized state, so it's rebuilt whenever that changes.
+            if rebase_soft_state {
+                self.rebase_soft_state().await?;
+            }
+        }
+    }
+
+    /// Returns the height of the last DA block followed by the `confirmation_depth` of the runner,
+    /// which processed blocks must not exceed to be finalized.
+    async fn confirmed_height(&self) -> Result<u64, anyhow::Error> {
+        if self.confirmation_depth == 0 {
+            return Ok(u64::MAX);
+        }
+        let head = self.da_service.get_head_block_header().await?;
+        Ok(head.height().saturating_sub(self.confirmation_depth))
+    }
+
+    /// Reverts the processed blocks which are not on the best fork of the DA layer anymore,
+    /// after it reorganized. The non-finalized blocks are dropped, while the finalized ones
+    /// are rolled back from the ledger and the state database.
+    /// Returns the height of the last processed block which is kept.
+    async fn revert_to_common_ancestor(
+        &mut self,
+        seen_block_headers: &mut VecDeque<<Da::Spec as DaSpec>::BlockHeader>,
+        seen_receipts: &mut SeenReceipts<Stf, Vm, Da::Spec, Da::FilteredBlock>,
+        seen_transitions: &mut SeenTransitions<Stf, Vm, Da::Spec>,
+        proofs_in_progress: &mut ProofsInProgress<Da::Spec>,
+    ) -> Result<u64, anyhow::Error> {
+        let ancestor = self
+            .da_service
+            .find_common_ancestor(&self.header_chain)
+            .await?
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "The DA layer reorganized deeper than the {} tracked blocks",
+                    self.header_chain.len()
+                )
+            })?;
+        let ancestor_height = ancestor.height();
+        let reverted = self.header_chain.revert_after(ancestor_height);
+        let checkpoint = self
+            .header_chain
+            .get(ancestor_height)
+            .expect("The common ancestor is in the chain");
+        self.state_root = checkpoint.state_root.clone();
+        let ancestor_marker = checkpoint.committed;
+
+        while seen_block_headers
+            .back()
+            .is_some_and(|header| header.height() > ancestor_height)
+        {
+            seen_block_headers.pop_back();
+            seen_receipts.pop_back();
+            seen_transitions.pop_back();
+        }
+        // The proofs of the reverted blocks are not sent anymore
+        proofs_in_progress.retain(|(hash, _, _)| {
+            reverted
+                .iter()
+                .all(|(reverted_header, _)| reverted_header.hash() != *hash)
+        });
+
+        let reverted_finalized = reverted
+            .iter()
+            .filter(|(_, checkpoint)| checkpoint.committed.is_some())
+            .count();
+        if reverted_finalized > 0 {
+            // Blocks are finalized in order, so the ancestor of finalized blocks is finalized too
+            let marker = ancestor_marker.ok_or_else(|| {
+                anyhow::anyhow!("The block at height {} isn't finalized", ancestor_height)
+            })?;
+            warn!(
+                "The reorganization reverted {} finalized blocks, rolling back the ledger and the state to height {}",
+                reverted_finalized,
+                ancestor_height
+            );
+            self.storage_manager.discard_non_finalized()?;
+            self.storage_manager
+                .rollback_finalized(marker.state_version)?;
+            self.ledger_db.rollback_to_slot(marker)?;
+            // The soft state is built on top of the finalized state
+            self.rebase_soft_state().await?;
+        }
+
+        Ok(ancestor_height)
+    }
+
+    /// Retains the oldest state versions the runner still needs from the pruning of the storage:
+    /// the state of the oldest finalized block a reorganization can roll back to, and the state
+    /// the oldest slot being proven was applied on.
+    fn retain_needed_state(&self, proofs_in_progress: &ProofsInProgress<Da::Spec>) {
+        let Some(pruning_handle) = &self.pruning_handle else {
+            return;
+        };
+        match self
+            .header_chain
+            .iter()
+            .find_map(|(_, checkpoint)| checkpoint.committed)
+        {
+            Some(marker) => pruning_handle.retain_from(REORG_PRUNING_HOLDER, marker.state_version),
+            None => pruning_handle.release(REORG_PRUNING_HOLDER),
+        }
+        match proofs_in_progress.front() {
+            Some((_, _, pre_state_version)) => {
+                pruning_handle.retain_from(PROVER_PRUNING_HOLDER, *pre_state_version)
+            }
+            None => pruning_handle.release(PROVER_PRUNING_HOLDER),
+        }
+    }
+
+    /// Sends the proofs which are generated to the DA, in the order of their blocks,
+    /// followed by the aggregated proofs which are ready.
+    async fn send_generated_proofs(&self, proofs_in_progress: &mut ProofsInProgress<Da::Spec>) {
+        while let Some((header_hash, height, _)) = proofs_in_progress.front() {
+            let status = self
+                .prover_service
+                .send_proof_to_da(header_hash.clone())
+                .await;
+
+            match status {
+                Ok(ProofSubmissionStatus::Success(proof)) => {
+                    let height = *height;
+                    self.store_proof(header_hash.clone(), proof);
+                    self.update_status(|node_status| node_status.last_proven_height = Some(height));
+                    proofs_in_progress.pop_front();
+                }
+                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add timeout handling.
+                Ok(ProofSubmissionStatus::ProofGenerationInProgress) => break,
+                // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1185): Add handling for DA submission errors.
+                Err(e) => panic!("{:?}", e),
+            }
+        }
+
+        for aggregated_proof in self.prover_service.take_aggregated_proofs().await {
+            let aggregated_proof =
+                borsh::to_vec(&aggregated_proof).expect("Proof serialization is infallible");
+            match self
+                .da_service
+                .send_aggregated_zk_proof(&aggregated_proof)
+                .await
+            {
+                Ok(height) => info!("Aggregated proof sent to the DA at height {}", height),
+                Err(e) => warn!("Failed to send the aggregated proof to the DA: {:?}", e),
+            }
+        }
+    }
+
+    /// Stores the proof of the slot of the given DA block in the ledger, for `prover_getProof`.
+    /// The slot is committed to the ledger when it's finalized, before its proof is generated.
+    fn store_proof(&self, header_hash: <Da::Spec as DaSpec>::SlotHash, proof: Proof) {
+        let hash: [u8; 32] = header_hash.into();
+        let stored = self
+            .ledger_db
+            .get_slot_number_by_hash(&hash)
+            .and_then(|slot_number| {
+                let slot_number = slot_number.ok_or_else(|| {
+                    anyhow::anyhow!("The slot of block 0x{} isn't committed", hex::encode(hash))
+                })?;
+                self.ledger_db.put_proof(slot_number, slot_number, proof)
+            });
+        if let Err(e) = stored {
+            warn!(
+                "Failed to store the proof of block 0x{}: {}",
+                hex::encode(hash),
+                e
+            );
+        }
+    }
+
+    /// Fetches the DA block at `height`. While waiting for it, applies the soft confirmation
+    /// batches given out by the sequencer, if soft confirmations are enabled.
+    async fn wait_for_block_at(&mut self, height: u64) -> Result<Da::FilteredBlock, anyhow::Error> {
+        let poll_interval = match &self.soft_confirmations {
+            Some(soft_confirmations) => soft_confirmations.poll_interval,
+            None => return self.da_service.get_block_at(height).await,
+        };
+
+        let gossip_received = self
+            .soft_confirmations
+            .as_ref()
+            .and_then(|soft_confirmations| soft_confirmations.gossiped.as_ref())
+            .map(|gossiped| gossiped.received.clone());
+
+        let da_service = self.da_service.clone();
+        let block = da_service.get_block_at(height);
+        tokio::pin!(block);
+        loop {
+            let gossiped = async {
+                match &gossip_received {
+                    Some(received) => received.notified().await,
+                    None => std::future::pending().await,
+                }
+            };
+            tokio::select! {
+                block = &mut block => return block,
+                _ = tokio::time::sleep(poll_interval) => self.sync_soft_batches().await?,
+                _ = gossiped => self.sync_soft_batches().await?,
+            }
+        }
+    }
+
+    /// Applies all the soft confirmation batches the sequencer has given out since the last call.
+    /// The batches gossiped by the peers are applied first, and the other ones are fetched from
+    /// the sequencer.
+    async fn sync_soft_batches(&mut self) -> Result<(), anyhow::Error> {
+        let Some(soft_confirmations) = self.soft_confirmations.clone() else {
+            return Ok(());
+        };
+
+        loop {
+            let number = soft_confirmations
+                .tracker
+                .read()
+                .expect("Soft confirmation tracker lock is poisoned")
+                .next_number();
+            let gossiped = soft_confirmations.take_gossiped(number);
+            let is_gossiped = gossiped.is_some();
+            let mut batch = match gossiped {
+                Some(batch) => batch,
+                None => match soft_confirmations.fetch(number).await {
+                    Ok(Some(batch)) => batch,
+                    Ok(None) => return Ok(()),
+                    Err(e) => {
+                        warn!("Failed to fetch soft confirmation batch #{}: {}", number, e);
+                        return Ok(());
+                    }
+                },
+            };
+
+            let tx_hashes = if soft_confirmations.is_from_sequencer(&batch) {
+                self.apply_soft_batch(&mut batch).await?
+            } else {
+                warn!(
+                    "Soft confirmation batch 0x{} is not signed by the sequencer",
+                    hex::encode(batch.hash)
+                );
+                None
+            };
+
+            let rejected = {
+                let mut tracker = soft_confirmations
+                    .tracker
+                    .write()
+                    .expect("Soft confirmation tracker lock is poisoned");
+                match (tx_hashes, &self.soft_state) {
+                    (Some(tx_hashes), Some((state_root, _))) => {
+                        info!(
+                            "Applied soft confirmation batch #{} with hash 0x{}",
+                            number,
+                            hex::encode(batch.hash)
+                        );
+                        tracker.record_applied(batch, tx_hashes, state_root.as_ref().to_vec());
+                        false
+                    }
+                    // A peer may have forged the batch, so it's fetched from the sequencer instead
+                    _ if is_gossiped => {
+                        warn!(
+                            "Gossiped soft confirmation batch #{} was rejected, fetching it from the sequencer",
+                            number
+                        );
+                        true
+                    }
+                    _ => {
+                        tracker.record_rejected(batch.hash);
+                        true
+                    }
                 }
+            };
 
-                break;
+            // The rejected batch may have changed the soft state through the slot hooks
+            if rejected {
+                self.rebase_soft_state().await?;
             }
         }
     }
 
+    /// Applies the soft confirmation batch on top of the soft state.
+    /// Returns the hashes of the batch transactions, or `None` if the batch was rejected.
+    async fn apply_soft_batch(
+        &mut self,
+        batch: &mut SignedSoftConfirmationBatch,
+    ) -> Result<Option<Vec<[u8; 32]>>, anyhow::Error> {
+        // Batches built on top of blocks preceding the rollup start, such as the genesis block,
+        // are applied in the context of the first block processed by the rollup.
+        let slot_height = batch.da_slot_height.max(self.start_height);
+        let slot = self.da_service.get_block_at(slot_height).await?;
+
+        let (pre_state_root, pre_state) = match self.soft_state.take() {
+            Some(soft_state) => soft_state,
+            None => (
+                self.state_root.clone(),
+                self.storage_manager.create_finalized_storage()?,
+            ),
+        };
+
+        let slot_result = self.stf.apply_soft_batch(
+            &pre_state_root,
+            pre_state,
+            Default::default(),
+            slot.header(),
+            &slot.validity_condition(),
+            batch,
+        );
+
+        let tx_hashes = match slot_result.batch_receipts.first() {
+            Some(receipt) if receipt.tx_receipts.len() == batch.txs.len() => Some(
+                receipt
+                    .tx_receipts
+                    .iter()
+                    .map(|tx_receipt| tx_receipt.tx_hash)
+                    .collect(),
+            ),
+            _ => None,
+        };
+
+        self.soft_state = Some((slot_result.state_root, slot_result.change_set.into()));
+        Ok(tx_hashes)
+    }
+
+    /// Rebuilds the soft state by applying all the unfinalized soft confirmation batches
+    /// on top of the finalized state.
+    async fn rebase_soft_state(&mut self) -> Result<(), anyhow::Error> {
+        let Some(soft_confirmations) = self.soft_confirmations.clone() else {
+            return Ok(());
+        };
+
+        self.soft_state = None;
+        let batches: Vec<SignedSoftConfirmationBatch> = soft_confirmations
+            .tracker
+            .read()
+            .expect("Soft confirmation tracker lock is poisoned")
+            .unfinalized_batches()
+            .cloned()
+            .collect();
+        debug!("Re-applying {} soft confirmation batches", batches.len());
+
+        for mut batch in batches {
+            self.apply_soft_batch(&mut batch).await?;
+        }
+
+        let state_root = self
+            .soft_state
+            .as_ref()
+            .map(|(state_root, _)| state_root.as_ref().to_vec());
+        soft_confirmations
+            .tracker
+            .write()
+            .expect("Soft confirmation tracker lock is poisoned")
+            .set_state_root(state_root);
+        Ok(())
+    }
+
     /// Allows to read current state root
     pub fn get_state_root(&self) -> &Stf::StateRoot {
         &self.state_root
     }
+
+    /// Returns the progress of the node, as served by the `rollup_status` RPC method.
+    pub fn node_status(&self) -> NodeStatus {
+        self.status
+            .read()
+            .expect("Node status lock is poisoned")
+            .clone()
+    }
+
+    fn update_status(&self, update: impl FnOnce(&mut NodeStatus)) {
+        update(&mut self.status.write().expect("Node status lock is poisoned"));
+    }
+
+    /// Fetches the head of the DA layer for the node status. Failures are only recorded,
+    /// since they don't prevent the node from processing the blocks it already knows of.
+    async fn refresh_da_head(&self) {
+        match self.da_service.get_head_block_header().await {
+            Ok(header) => self.update_status(|status| status.record_da_head(header.height())),
+            Err(e) => {
+                warn!("Failed to fetch the head of the DA layer: {:?}", e);
+                self.update_status(|status| status.record_da_error(&e));
+            }
+        }
+    }
+}
+
+/// Rolls back the finalized state of a slot which wasn't committed to the ledger,
+/// because the node stopped in between. The ledger is the source of truth: the slot is
+/// processed again, since the runner resumes after the last slot of the ledger.
+fn rollback_uncommitted_state<Da: DaSpec, Sm: HierarchicalStorageManager<Da>>(
+    ledger_db: &LedgerDB,
+    storage_manager: &mut Sm,
+    is_genesis: bool,
+) -> anyhow::Result<()> {
+    let committed_version = match ledger_db.get_last_committed_slot()? {
+        Some(marker) => marker.state_version,
+        // Nothing was committed yet, so any finalized state comes from an interrupted genesis.
+        None if is_genesis => 0,
+        // The ledger was written without markers, there's nothing to check the state against.
+        None => return Ok(()),
+    };
+    let finalized_version = storage_manager.finalized_version()?;
+    if finalized_version > committed_version {
+        warn!(
+            "The finalized state at version {} is ahead of the last committed slot, at version {}. Rolling back the uncommitted state",
+            finalized_version, committed_version
+        );
+        storage_manager.rollback_finalized(committed_version)?;
+    }
+    Ok(())
+}
+
+/// Resolves once the node is asked to stop, with SIGINT (Ctrl-C) or SIGTERM.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            warn!("Failed to listen for Ctrl-C: {}", e);
+            futures::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn!("Failed to listen for SIGTERM: {}", e);
+                futures::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = futures::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
 }
@@ -0,0 +1,454 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use jsonrpsee::types::error::ErrorCode;
+use jsonrpsee::types::ErrorObjectOwned;
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+use sov_p2p::{GossipedSoftBatch, SoftBatchGossip};
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
+use tokio::sync::Notify;
+
+use crate::SoftConfirmationConfig;
+
+/// How many soft batches gossiped ahead of the next one to apply are kept.
+const MAX_GOSSIPED_BATCHES: usize = 1024;
+/// For how many DA blocks the status of a finalized or discarded batch is still reported.
+const SETTLED_STATUS_RETENTION: u64 = 256;
+
+/// The status of a soft confirmation batch, as seen by the full node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoftBatchStatus {
+    /// The batch has been executed, but it hasn't been found on DA yet.
+    Pending,
+    /// The batch has been found on DA, in a block which is not final yet.
+    Included {
+        /// The height of the DA block containing the batch.
+        da_height: u64,
+    },
+    /// The batch has been found on DA, in a final block.
+    Finalized {
+        /// The height of the DA block containing the batch.
+        da_height: u64,
+    },
+    /// The batch was rejected by the state transition function, or it didn't land on DA
+    /// within the inclusion window. Its effects have been rolled back.
+    Discarded,
+}
+
+struct TrackedBatch {
+    batch: SignedSoftConfirmationBatch,
+    tx_hashes: Vec<[u8; 32]>,
+    status: SoftBatchStatus,
+}
+
+/// Keeps track of the soft confirmation batches executed by the full node until they are either
+/// finalized on DA or discarded.
+///
+/// The soft state of the rollup is the finalized state with all the tracked batches applied on top,
+/// in the order they were received. The statuses of the finalized and discarded batches are forgotten
+/// [`SETTLED_STATUS_RETENTION`] DA blocks later.
+pub struct SoftConfirmationTracker {
+    inclusion_window: u64,
+    next_number: u64,
+    state_root: Option<Vec<u8>>,
+    unfinalized: VecDeque<TrackedBatch>,
+    statuses: HashMap<[u8; 32], SoftBatchStatus>,
+    /// The height of the last reconciled DA block.
+    da_height: u64,
+    /// The finalized and discarded batches, with the DA height they were settled at.
+    settled: VecDeque<(u64, [u8; 32])>,
+}
+
+impl SoftConfirmationTracker {
+    /// Creates a tracker discarding the batches which don't land on DA within
+    /// `inclusion_window` blocks after the DA block they reference.
+    pub fn new(inclusion_window: u64) -> Self {
+        Self {
+            inclusion_window,
+            next_number: 0,
+            state_root: None,
+            unfinalized: VecDeque::new(),
+            statuses: HashMap::new(),
+            da_height: 0,
+            settled: VecDeque::new(),
+        }
+    }
+
+    /// The number of the next soft batch to be fetched from the sequencer.
+    pub fn next_number(&self) -> u64 {
+        self.next_number
+    }
+
+    /// Returns the status of the soft batch with the given hash, if it was received.
+    pub fn status(&self, batch_hash: &[u8; 32]) -> Option<SoftBatchStatus> {
+        self.statuses.get(batch_hash).copied()
+    }
+
+    /// The state root after applying all the tracked batches, if there are any.
+    pub fn state_root(&self) -> Option<&[u8]> {
+        self.state_root.as_deref()
+    }
+
+    /// The batches which are not finalized on DA yet, in the order they have to be applied.
+    pub fn unfinalized_batches(&self) -> impl Iterator<Item = &SignedSoftConfirmationBatch> {
+        self.unfinalized.iter().map(|tracked| &tracked.batch)
+    }
+
+    /// Records a batch which has been executed, producing the transactions with `tx_hashes`.
+    pub fn record_applied(
+        &mut self,
+        batch: SignedSoftConfirmationBatch,
+        tx_hashes: Vec<[u8; 32]>,
+        state_root: Vec<u8>,
+    ) {
+        self.next_number += 1;
+        self.statuses.insert(batch.hash, SoftBatchStatus::Pending);
+        self.unfinalized.push_back(TrackedBatch {
+            batch,
+            tx_hashes,
+            status: SoftBatchStatus::Pending,
+        });
+        self.state_root = Some(state_root);
+    }
+
+    /// Records a batch which was rejected, and thus not applied.
+    pub fn record_rejected(&mut self, batch_hash: [u8; 32]) {
+        self.next_number += 1;
+        self.settle(batch_hash, SoftBatchStatus::Discarded);
+    }
+
+    /// Sets the state root after the tracked batches were re-applied on top of a new finalized state.
+    pub fn set_state_root(&mut self, state_root: Option<Vec<u8>>) {
+        self.state_root = state_root;
+    }
+
+    /// Matches the batches found in the DA block at `da_height` against the pending soft batches,
+    /// using the hashes of their transactions.
+    ///
+    /// Returns `true` if some pending batches were discarded, because they didn't land on DA in time.
+    /// In that case the soft state has to be rebuilt.
+    pub fn reconcile(&mut self, da_height: u64, da_batches: &[Vec<[u8; 32]>]) -> bool {
+        self.da_height = da_height;
+        self.prune_settled();
+
+        for da_batch in da_batches {
+            let included = self.unfinalized.iter_mut().find(|tracked| {
+                tracked.status == SoftBatchStatus::Pending && &tracked.tx_hashes == da_batch
+            });
+            if let Some(tracked) = included {
+                tracked.status = SoftBatchStatus::Included { da_height };
+                self.statuses.insert(tracked.batch.hash, tracked.status);
+            }
+        }
+
+        let inclusion_window = self.inclusion_window;
+        let mut discarded = Vec::new();
+        self.unfinalized.retain(|tracked| {
+            let expired = tracked.status == SoftBatchStatus::Pending
+                && tracked
+                    .batch
+                    .da_slot_height
+                    .saturating_add(inclusion_window)
+                    < da_height;
+            if expired {
+                tracing::warn!(
+                    "Soft confirmation batch 0x{} didn't land on DA in time and was discarded",
+                    hex::encode(tracked.batch.hash)
+                );
+                discarded.push(tracked.batch.hash);
+            }
+            !expired
+        });
+
+        let any_discarded = !discarded.is_empty();
+        for batch_hash in discarded {
+            self.settle(batch_hash, SoftBatchStatus::Discarded);
+        }
+        any_discarded
+    }
+
+    /// Marks the batches included in DA blocks up to `da_height` as finalized and stops tracking them.
+    pub fn finalize(&mut self, da_height: u64) {
+        let mut finalized = Vec::new();
+        self.unfinalized.retain(|tracked| match tracked.status {
+            SoftBatchStatus::Included {
+                da_height: included_at,
+            } if included_at <= da_height => {
+                finalized.push((tracked.batch.hash, included_at));
+                false
+            }
+            _ => true,
+        });
+
+        for (batch_hash, included_at) in finalized {
+            self.settle(
+                batch_hash,
+                SoftBatchStatus::Finalized {
+                    da_height: included_at,
+                },
+            );
+        }
+    }
+
+    fn settle(&mut self, batch_hash: [u8; 32], status: SoftBatchStatus) {
+        self.statuses.insert(batch_hash, status);
+        self.settled.push_back((self.da_height, batch_hash));
+    }
+
+    /// Forgets the statuses of the batches settled more than [`SETTLED_STATUS_RETENTION`]
+    /// DA blocks ago.
+    fn prune_settled(&mut self) {
+        while let Some(&(settled_at, batch_hash)) = self.settled.front() {
+            if settled_at.saturating_add(SETTLED_STATUS_RETENTION) >= self.da_height {
+                break;
+            }
+            self.settled.pop_front();
+            // The batch might have been received again since then
+            if matches!(
+                self.statuses.get(&batch_hash),
+                Some(SoftBatchStatus::Finalized { .. } | SoftBatchStatus::Discarded)
+            ) {
+                self.statuses.remove(&batch_hash);
+            }
+        }
+    }
+}
+
+/// Checks that a soft batch matches its hash and is signed by the public key it carries,
+/// see [`sov_rollup_interface::stf::StateTransitionFunction::verify_soft_batch`].
+pub(crate) type VerifySoftBatch = fn(&SignedSoftConfirmationBatch) -> anyhow::Result<()>;
+
+/// The soft batches received from the gossip of the peers, waiting to be applied.
+#[derive(Clone)]
+pub(crate) struct GossipedBatches {
+    _gossip: Arc<SoftBatchGossip>,
+    batches: Arc<Mutex<BTreeMap<u64, SignedSoftConfirmationBatch>>>,
+    pub(crate) received: Arc<Notify>,
+}
+
+impl GossipedBatches {
+    /// Starts the gossip, buffering the batches correctly signed by the sequencer.
+    /// The other batches are rejected, so they are neither relayed nor delivered.
+    fn start(
+        config: &sov_p2p::P2pConfig,
+        sequencer_public_key: Vec<u8>,
+        verify_signature: VerifySoftBatch,
+    ) -> anyhow::Result<Self> {
+        let (gossip, mut receiver) = SoftBatchGossip::start(
+            config,
+            Box::new(move |gossiped: &GossipedSoftBatch| {
+                gossiped.batch.pub_key == sequencer_public_key
+                    && verify_signature(&gossiped.batch).is_ok()
+            }),
+        )?;
+        let gossiped = Self {
+            _gossip: Arc::new(gossip),
+            batches: Arc::new(Mutex::new(BTreeMap::new())),
+            received: Arc::new(Notify::new()),
+        };
+
+        let batches = gossiped.batches.clone();
+        let received = gossiped.received.clone();
+        tokio::spawn(async move {
+            while let Some(gossiped) = receiver.recv().await {
+                let mut batches = batches.lock().expect("Gossiped batches lock is poisoned");
+                if batches.len() < MAX_GOSSIPED_BATCHES {
+                    batches.insert(gossiped.number, gossiped.batch);
+                    received.notify_one();
+                }
+            }
+        });
+        Ok(gossiped)
+    }
+
+    /// Removes the gossiped batch with the given number, dropping the ones preceding it.
+    fn take(&self, number: u64) -> Option<SignedSoftConfirmationBatch> {
+        let mut batches = self
+            .batches
+            .lock()
+            .expect("Gossiped batches lock is poisoned");
+        *batches = batches.split_off(&number);
+        batches.remove(&number)
+    }
+}
+
+/// Fetches the soft confirmation batches from the sequencer and serves their status over RPC.
+#[derive(Clone)]
+pub(crate) struct SoftConfirmationSync {
+    client: HttpClient,
+    sequencer_public_key: Vec<u8>,
+    pub(crate) poll_interval: Duration,
+    pub(crate) tracker: Arc<RwLock<SoftConfirmationTracker>>,
+    pub(crate) gossiped: Option<GossipedBatches>,
+}
+
+impl SoftConfirmationSync {
+    pub(crate) fn new(
+        config: SoftConfirmationConfig,
+        verify_signature: VerifySoftBatch,
+    ) -> anyhow::Result<Self> {
+        let sequencer_public_key =
+            hex::decode(config.sequencer_public_key.trim_start_matches("0x"))?;
+        let gossiped = config
+            .gossip
+            .as_ref()
+            .map(|gossip| {
+                GossipedBatches::start(gossip, sequencer_public_key.clone(), verify_signature)
+            })
+            .transpose()?;
+
+        Ok(Self {
+            client: HttpClientBuilder::default().build(&config.sequencer_url)?,
+            sequencer_public_key,
+            poll_interval: Duration::from_millis(config.poll_interval_ms),
+            tracker: Arc::new(RwLock::new(SoftConfirmationTracker::new(
+                config.inclusion_window,
+            ))),
+            gossiped,
+        })
+    }
+
+    /// Returns the batch with the given number if it was gossiped by the peers.
+    /// It's signed by the sequencer.
+    pub(crate) fn take_gossiped(&self, number: u64) -> Option<SignedSoftConfirmationBatch> {
+        self.gossiped
+            .as_ref()
+            .and_then(|gossiped| gossiped.take(number))
+    }
+
+    /// Returns `true` if the batch is signed by the configured sequencer.
+    /// The signature itself is checked by the state transition function.
+    pub(crate) fn is_from_sequencer(&self, batch: &SignedSoftConfirmationBatch) -> bool {
+        batch.pub_key == self.sequencer_public_key
+    }
+
+    pub(crate) async fn fetch(
+        &self,
+        number: u64,
+    ) -> anyhow::Result<Option<SignedSoftConfirmationBatch>> {
+        Ok(self
+            .client
+            .request("sequencer_getSoftBatch", vec![number])
+            .await?)
+    }
+
+    pub(crate) fn rpc_module(&self) -> RpcModule<Arc<RwLock<SoftConfirmationTracker>>> {
+        let mut rpc = RpcModule::new(self.tracker.clone());
+        rpc.register_method("softConfirmation_getStatus", |params, tracker| {
+            let hash: String = params.one()?;
+            let hash: [u8; 32] = hex::decode(hash.trim_start_matches("0x"))
+                .ok()
+                .and_then(|hash| hash.try_into().ok())
+                .ok_or_else(|| {
+                    ErrorObjectOwned::owned(
+                        ErrorCode::InvalidParams.code(),
+                        "expected a hex encoded 32 bytes hash",
+                        None::<()>,
+                    )
+                })?;
+            Ok::<_, ErrorObjectOwned>(read_tracker(tracker)?.status(&hash))
+        })
+        .expect("Failed to register soft confirmation RPC methods");
+        rpc.register_method("softConfirmation_getStateRoot", |_, tracker| {
+            Ok::<_, ErrorObjectOwned>(
+                read_tracker(tracker)?
+                    .state_root()
+                    .map(|root| format!("0x{}", hex::encode(root))),
+            )
+        })
+        .expect("Failed to register soft confirmation RPC methods");
+        rpc
+    }
+}
+
+fn read_tracker(
+    tracker: &RwLock<SoftConfirmationTracker>,
+) -> Result<std::sync::RwLockReadGuard<'_, SoftConfirmationTracker>, ErrorObjectOwned> {
+    tracker.read().map_err(|e| {
+        ErrorObjectOwned::owned(ErrorCode::InternalError.code(), e.to_string(), None::<()>)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_rollup_interface::soft_confirmation::UnsignedSoftConfirmationBatch;
+
+    use super::*;
+
+    fn soft_batch(hash: u8, da_slot_height: u64) -> SignedSoftConfirmationBatch {
+        SignedSoftConfirmationBatch::new(
+            UnsignedSoftConfirmationBatch {
+                da_slot_height,
+                da_slot_hash: [0; 32],
+                txs: vec![vec![hash]],
+            },
+            [hash; 32],
+            vec![],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_included_batch_is_finalized() {
+        let mut tracker = SoftConfirmationTracker::new(2);
+        tracker.record_applied(soft_batch(1, 5), vec![[10; 32]], vec![1]);
+        tracker.record_applied(soft_batch(2, 5), vec![[20; 32]], vec![2]);
+        assert_eq!(2, tracker.next_number());
+
+        assert!(!tracker.reconcile(6, &[vec![[10; 32]], vec![[30; 32]]]));
+        assert_eq!(
+            Some(SoftBatchStatus::Included { da_height: 6 }),
+            tracker.status(&[1; 32])
+        );
+        assert_eq!(Some(SoftBatchStatus::Pending), tracker.status(&[2; 32]));
+
+        tracker.finalize(6);
+        assert_eq!(
+            Some(SoftBatchStatus::Finalized { da_height: 6 }),
+            tracker.status(&[1; 32])
+        );
+        assert_eq!(1, tracker.unfinalized_batches().count());
+    }
+
+    #[test]
+    fn test_expired_batch_is_discarded() {
+        let mut tracker = SoftConfirmationTracker::new(2);
+        tracker.record_applied(soft_batch(1, 5), vec![[10; 32]], vec![1]);
+        tracker.record_rejected([2; 32]);
+
+        assert!(!tracker.reconcile(7, &[]));
+        assert!(tracker.reconcile(8, &[]));
+        assert_eq!(Some(SoftBatchStatus::Discarded), tracker.status(&[1; 32]));
+        assert_eq!(Some(SoftBatchStatus::Discarded), tracker.status(&[2; 32]));
+        assert_eq!(0, tracker.unfinalized_batches().count());
+        assert_eq!(None, tracker.status(&[3; 32]));
+    }
+
+    #[test]
+    fn test_settled_statuses_are_forgotten() {
+        let mut tracker = SoftConfirmationTracker::new(2);
+        tracker.record_applied(soft_batch(1, 5), vec![[10; 32]], vec![1]);
+        tracker.record_applied(soft_batch(2, 5), vec![[20; 32]], vec![2]);
+
+        assert!(!tracker.reconcile(6, &[vec![[10; 32]]]));
+        assert!(tracker.reconcile(8, &[]));
+        tracker.finalize(6);
+
+        let retained_until = 8 + SETTLED_STATUS_RETENTION;
+        assert!(!tracker.reconcile(retained_until, &[]));
+        assert_eq!(
+            Some(SoftBatchStatus::Finalized { da_height: 6 }),
+            tracker.status(&[1; 32])
+        );
+        assert_eq!(Some(SoftBatchStatus::Discarded), tracker.status(&[2; 32]));
+
+        assert!(!tracker.reconcile(retained_until + 1, &[]));
+        assert_eq!(None, tracker.status(&[1; 32]));
+        assert_eq!(None, tracker.status(&[2; 32]));
+    }
+}
@@ -0,0 +1,243 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use sov_rollup_interface::stf::StateDiff;
+use tokio::sync::broadcast;
+
+/// How many state diffs a subscriber can lag behind before it misses some.
+const STATE_DIFFS_CHANNEL_CAPACITY: usize = 100;
+
+/// The names decoded from the prefix of a state key, like `sov_bank/Bank/tokens/`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatePrefix {
+    /// The path of the Rust module defining the state item, like `sov_bank`.
+    pub module_path: String,
+    /// The name of the module `struct`, like `Bank`.
+    pub module_name: String,
+    /// The name of the state item in the module, like `tokens`.
+    pub item_name: String,
+}
+
+impl StatePrefix {
+    /// Decodes the prefix of a state key. Returns the prefix and its length in bytes,
+    /// or [`None`] if the key doesn't start with the names of a module and one of its state items.
+    pub fn decode(key: &[u8]) -> Option<(Self, usize)> {
+        let mut names = Vec::with_capacity(3);
+        let mut start = 0;
+        while names.len() < 3 {
+            let len = key[start..].iter().position(|byte| *byte == b'/')?;
+            let name = &key[start..start + len];
+            let is_path = names.is_empty();
+            let is_valid = !name.is_empty()
+                && name.iter().all(|byte| {
+                    byte.is_ascii_alphanumeric() || *byte == b'_' || (is_path && *byte == b':')
+                });
+            if !is_valid {
+                return None;
+            }
+            names.push(String::from_utf8(name.to_vec()).ok()?);
+            start += len + 1;
+        }
+
+        let item_name = names.pop()?;
+        let module_name = names.pop()?;
+        let module_path = names.pop()?;
+        Some((
+            Self {
+                module_path,
+                module_name,
+                item_name,
+            },
+            start,
+        ))
+    }
+}
+
+/// A write of a slot to the provable state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateChange {
+    /// The names decoded from the prefix of the key, if it has the prefix of a module.
+    pub prefix: Option<StatePrefix>,
+    /// The hex encoded raw key, including its prefix.
+    pub key: String,
+    /// The hex encoded value the key was set to, or [`None`] if it was deleted.
+    pub value: Option<String>,
+}
+
+/// The ordered writes of a slot to the provable state, published by the [`StateTransitionRunner`](crate::StateTransitionRunner).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SlotStateDiff {
+    /// The height of the DA block of the slot.
+    pub height: u64,
+    /// The hex encoded hash of the DA block of the slot.
+    pub da_block_hash: String,
+    /// The hex encoded state root the slot was applied on.
+    pub prev_state_root: String,
+    /// The hex encoded state root after the slot.
+    pub state_root: String,
+    /// The writes of the slot, in the order they were made.
+    pub changes: Vec<StateChange>,
+}
+
+impl SlotStateDiff {
+    /// Creates the state diff of the slot at `height`, decoding the prefixes of its keys.
+    pub fn new(
+        height: u64,
+        da_block_hash: [u8; 32],
+        prev_state_root: &[u8],
+        state_root: &[u8],
+        state_diff: &StateDiff,
+    ) -> Self {
+        let changes = state_diff
+            .iter()
+            .map(|(key, value)| StateChange {
+                prefix: StatePrefix::decode(key).map(|(prefix, _)| prefix),
+                key: hex::encode(key),
+                value: value.as_ref().map(hex::encode),
+            })
+            .collect();
+        Self {
+            height,
+            da_block_hash: hex::encode(da_block_hash),
+            prev_state_root: hex::encode(prev_state_root),
+            state_root: hex::encode(state_root),
+            changes,
+        }
+    }
+}
+
+/// Publishes the state diff of every processed slot to the subscribers, and to the JSON-lines file
+/// configured in [`StateDiffConfig`](crate::StateDiffConfig), if any.
+pub(crate) struct StateDiffPublisher {
+    sender: broadcast::Sender<Arc<SlotStateDiff>>,
+    file: Option<BufWriter<File>>,
+}
+
+impl StateDiffPublisher {
+    /// Creates a publisher appending the state diffs to the file at `file_path`, if one is given.
+    pub(crate) fn new(file_path: Option<&Path>) -> anyhow::Result<Self> {
+        let file = file_path
+            .map(|path| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .with_context(|| {
+                        format!("Failed to open the state diff file {}", path.display())
+                    })
+            })
+            .transpose()?
+            .map(BufWriter::new);
+        let (sender, _) = broadcast::channel(STATE_DIFFS_CHANNEL_CAPACITY);
+        Ok(Self { sender, file })
+    }
+
+    /// Returns a receiver of the state diffs published from now on.
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<Arc<SlotStateDiff>> {
+        self.sender.subscribe()
+    }
+
+    /// Whether anyone reads the published state diffs, so that they are worth decoding.
+    pub(crate) fn is_active(&self) -> bool {
+        self.file.is_some() || self.sender.receiver_count() > 0
+    }
+
+    /// Publishes the state diff of a slot. The file sink is flushed before this returns,
+    /// so every line of the file is a complete diff.
+    pub(crate) fn publish(&mut self, diff: SlotStateDiff) -> anyhow::Result<()> {
+        if let Some(file) = &mut self.file {
+            serde_json::to_writer(&mut *file, &diff)?;
+            file.write_all(b"\n")?;
+            file.flush()
+                .context("Failed to write to the state diff file")?;
+        }
+        // Sending only fails when there is no subscriber
+        let _ = self.sender.send(Arc::new(diff));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_prefix() {
+        let (prefix, len) = StatePrefix::decode(b"sov_bank/Bank/tokens/\x01\x02").unwrap();
+        assert_eq!(
+            StatePrefix {
+                module_path: "sov_bank".to_string(),
+                module_name: "Bank".to_string(),
+                item_name: "tokens".to_string(),
+            },
+            prefix
+        );
+        assert_eq!(21, len);
+
+        let (prefix, _) = StatePrefix::decode(b"sov_evm::experimental/Evm/accounts/").unwrap();
+        assert_eq!("sov_evm::experimental", prefix.module_path);
+
+        // Module prefixes without a state item, and binary keys, aren't decoded
+        assert_eq!(None, StatePrefix::decode(b"sov_bank/Bank/"));
+        assert_eq!(None, StatePrefix::decode(b"sov_bank/Bank//"));
+        assert_eq!(
+            None,
+            StatePrefix::decode(&[0xff, b'/', b'a', b'/', b'b', b'/'])
+        );
+    }
+
+    #[test]
+    fn test_publish_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state_diffs.jsonl");
+        let mut publisher = StateDiffPublisher::new(Some(&path)).unwrap();
+        let mut receiver = publisher.subscribe();
+
+        let state_diff = vec![
+            (b"sov_bank/Bank/tokens/\x01".to_vec(), Some(vec![2])),
+            (vec![3], None),
+        ];
+        for height in [1, 2] {
+            publisher
+                .publish(SlotStateDiff::new(
+                    height,
+                    [height as u8; 32],
+                    &[0],
+                    &[1],
+                    &state_diff,
+                ))
+                .unwrap();
+        }
+
+        let received = receiver.try_recv().unwrap();
+        assert_eq!(1, received.height);
+        assert_eq!(
+            vec![
+                StateChange {
+                    prefix: StatePrefix::decode(&state_diff[0].0).map(|(prefix, _)| prefix),
+                    key: hex::encode(&state_diff[0].0),
+                    value: Some("02".to_string()),
+                },
+                StateChange {
+                    prefix: None,
+                    key: "03".to_string(),
+                    value: None,
+                },
+            ],
+            received.changes
+        );
+
+        let lines: Vec<SlotStateDiff> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(2, lines.len());
+        assert_eq!(*received, lines[0]);
+        assert_eq!(2, lines[1].height);
+    }
+}
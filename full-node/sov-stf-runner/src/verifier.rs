@@ -1,8 +1,15 @@
 use std::marker::PhantomData;
 
-use sov_rollup_interface::da::{BlockHeaderTrait, DaVerifier};
+use borsh::BorshDeserialize;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_rollup_interface::da::{BlockHeaderTrait, DaSpec, DaVerifier};
+use sov_rollup_interface::digest::Digest;
 use sov_rollup_interface::stf::StateTransitionFunction;
-use sov_rollup_interface::zk::{StateTransition, StateTransitionData, Zkvm, ZkvmGuest};
+use sov_rollup_interface::zk::{
+    AggregatedStateTransition, Matches, Proof, StateTransition, StateTransitionData, Zkvm,
+    ZkvmGuest,
+};
 /// Verifies a state transition
 pub struct StateTransitionVerifier<ST, Da, Zk>
 where
@@ -53,6 +60,7 @@ where
             initial_state_root: data.initial_state_root,
             final_state_root: result.state_root,
             slot_hash: data.da_block_header.hash(),
+            prev_slot_hash: data.da_block_header.prev_hash(),
             validity_condition,
         };
 
@@ -60,3 +68,85 @@ where
         Ok(())
     }
 }
+
+/// Aggregates the proofs of consecutive slots into a single proof of the whole range.
+/// This is the logic of the recursive aggregation program, which runs inside the zkVM.
+///
+/// The code commitment of the program whose slot proofs are aggregated is given by the host,
+/// and committed along with the aggregated state transition, so that verifiers can check it.
+pub struct ProofAggregator<Vm: Zkvm, Da: DaSpec, Root> {
+    phantom: PhantomData<(Vm, Da, Root)>,
+}
+
+impl<Vm, Da, Root> Default for ProofAggregator<Vm, Da, Root>
+where
+    Vm: Zkvm,
+    Da: DaSpec,
+{
+    fn default() -> Self {
+        Self {
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<Vm, Da, Root> ProofAggregator<Vm, Da, Root>
+where
+    Vm: Zkvm,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned + PartialEq,
+{
+    /// Create a [`ProofAggregator`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the slot proofs given by the host, in order, against the code commitment given
+    /// before them, and commit their aggregated state transition.
+    pub fn run_aggregation<H: Digest, Zk: ZkvmGuest>(&self, zkvm: Zk) -> Result<(), anyhow::Error> {
+        let code_commitment: Vm::CodeCommitment = zkvm.read_from_host();
+        let proofs: Vec<Vec<u8>> = zkvm.read_from_host();
+        let transitions = proofs
+            .iter()
+            .map(|proof| {
+                Vm::verify_and_extract_output::<Da, Root>(proof, &code_commitment)
+                    .map_err(|e| anyhow::anyhow!("Invalid slot proof: {:?}", e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let out = AggregatedStateTransition::aggregate::<H>(code_commitment, transitions)?;
+        zkvm.commit(&out);
+        Ok(())
+    }
+}
+
+/// Verifies an aggregated proof, as submitted to the DA layer, against the code commitment
+/// of the aggregation program, and checks that it aggregates the proofs of the program with
+/// the given `slot_code_commitment`. Returns the range of state transitions it proves.
+pub fn verify_aggregated_proof<Vm, Da, Root>(
+    aggregated_proof: &[u8],
+    code_commitment: &Vm::CodeCommitment,
+    slot_code_commitment: &Vm::CodeCommitment,
+) -> Result<AggregatedStateTransition<Da, Root, Vm::CodeCommitment>, anyhow::Error>
+where
+    Vm: Zkvm,
+    Da: DaSpec,
+    Root: Serialize + DeserializeOwned,
+{
+    let transition = match Proof::try_from_slice(aggregated_proof)? {
+        Proof::Full(proof) => {
+            Vm::verify_and_extract_aggregated_output::<Da, Root>(&proof, code_commitment)
+                .map_err(|e| anyhow::anyhow!("Invalid aggregated proof: {:?}", e))?
+        }
+        Proof::PublicInput(_) => anyhow::bail!(
+            "The aggregated proof only contains its public input and can't be verified"
+        ),
+    };
+    anyhow::ensure!(
+        transition
+            .slot_code_commitment
+            .matches(slot_code_commitment),
+        "The aggregated proof doesn't aggregate the proofs of the rollup program"
+    );
+    Ok(transition)
+}
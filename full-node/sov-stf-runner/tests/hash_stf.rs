@@ -5,6 +5,7 @@ use sov_mock_da::{
 use sov_mock_zkvm::MockZkvm;
 use sov_prover_storage_manager::{new_orphan_storage, SnapshotManager};
 use sov_rollup_interface::da::{BlobReaderTrait, BlockHeaderTrait, DaSpec};
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
 use sov_rollup_interface::zk::{ValidityCondition, Zkvm};
 use sov_state::storage::{NativeStorage, StorageKey, StorageValue};
@@ -132,8 +133,49 @@ impl<Vm: Zkvm, Cond: ValidityCondition, Da: DaSpec> StateTransitionFunction<Vm,
             // TODO: Add batch receipts to inspection
             batch_receipts: vec![],
             witness,
+            state_diff: vec![],
         }
     }
+
+    fn apply_soft_batch(
+        &self,
+        _pre_state_root: &Self::StateRoot,
+        storage: Self::PreState,
+        witness: Self::Witness,
+        _slot_header: &Da::BlockHeader,
+        _validity_condition: &Da::ValidityCondition,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    > {
+        let mut hasher = sha2::Sha256::new();
+
+        let hash_key = HashStf::<Cond>::hash_key();
+        let existing_cache = storage.get(&hash_key, None, &witness).unwrap();
+        hasher.update(existing_cache.value());
+
+        for tx in &soft_batch.txs {
+            hasher.update(tx);
+        }
+
+        let (state_root, storage) = HashStf::<Cond>::save_from_hasher(hasher, storage, &witness);
+
+        SlotResult {
+            state_root,
+            change_set: storage,
+            batch_receipts: vec![],
+            witness,
+            state_diff: vec![],
+        }
+    }
+
+    fn verify_soft_batch(_soft_batch: &SignedSoftConfirmationBatch) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
 }
 
 #[test]
@@ -1,13 +1,16 @@
+use jsonrpsee::rpc_params;
 use sov_mock_da::{
     MockBlockHeader, MockDaService, MockDaSpec, MockDaVerifier, MockHash, MockValidityCond,
 };
-use sov_mock_zkvm::MockZkvm;
+use sov_mock_zkvm::{MockCodeCommitment, MockZkvm};
 use sov_rollup_interface::da::Time;
-use sov_rollup_interface::zk::StateTransitionData;
+use sov_rollup_interface::zk::{AggregatedStateTransition, StateTransition, StateTransitionData};
 use sov_stf_runner::mock::MockStf;
+use sov_stf_runner::verifier::verify_aggregated_proof;
 use sov_stf_runner::{
-    ParallelProverService, ProofProcessingStatus, ProofSubmissionStatus, ProverService,
-    ProverServiceConfig, ProverServiceError, RollupProverConfig, WitnessSubmissionStatus,
+    ParallelProverService, ProofJobStatus, ProofProcessingStatus, ProofSubmissionStatus,
+    ProverService, ProverServiceConfig, ProverServiceError, RollupProverConfig,
+    WitnessSubmissionStatus,
 };
 
 #[tokio::test]
@@ -69,31 +72,29 @@ async fn test_prover_status_busy() -> Result<(), anyhow::Error> {
     }
 
     // Attempting to create another proof while the prover is busy.
+    let queued_header_hash = MockHash::from([0; 32]);
     {
-        let header_hash = MockHash::from([0; 32]);
         prover_service
-            .submit_witness(make_transition_data(header_hash))
+            .submit_witness(make_transition_data(queued_header_hash))
             .await;
 
-        let status = prover_service.prove(header_hash).await?;
-        // The prover is busy and won't accept any new jobs.
-        assert_eq!(ProofProcessingStatus::Busy, status);
-
-        let proof_submission_status = prover_service
-            .send_proof_to_da(header_hash)
-            .await
-            .unwrap_err();
+        let status = prover_service.prove(queued_header_hash).await?;
+        // The prover is busy, the new job waits for a worker.
+        assert_eq!(ProofProcessingStatus::Queued, status);
 
-        // The new job wasn't accepted.
+        let proof_submission_status = prover_service.send_proof_to_da(queued_header_hash).await?;
         assert_eq!(
-        proof_submission_status.to_string(),
-        "Missing witness for: 0x0000000000000000000000000000000000000000000000000000000000000000");
+            ProofSubmissionStatus::ProofGenerationInProgress,
+            proof_submission_status
+        );
     }
 
     vm.make_proof();
     for header_hash in header_hashes.clone() {
         wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
     }
+    // The queued job is picked up once a worker is available.
+    wait_for_proof_proof_da_submission(queued_header_hash, &prover_service).await;
 
     // Retry once the prover is available to process new proofs.
     {
@@ -162,6 +163,170 @@ async fn test_generate_multiple_proofs_for_the_same_witness() -> Result<(), anyh
     Ok(())
 }
 
+#[tokio::test]
+async fn test_persisted_jobs_are_resumed() -> Result<(), anyhow::Error> {
+    let queue_dir = tempfile::tempdir()?;
+    let header_hash = MockHash::from([1; 32]);
+    let rpc_hash = format!("0x{}", hex::encode([1; 32]));
+
+    {
+        let TestProver { prover_service, .. } =
+            make_new_prover_with_queue(Some(queue_dir.path().to_path_buf()));
+        prover_service
+            .submit_witness(make_transition_data(header_hash))
+            .await;
+
+        let methods = prover_service.rpc_methods().unwrap();
+        let status: Option<ProofJobStatus> = methods
+            .call("prover_getJobStatus", rpc_params![rpc_hash.clone()])
+            .await?;
+        assert_eq!(Some(ProofJobStatus::WitnessSubmitted), status);
+    }
+    assert_eq!(1, std::fs::read_dir(queue_dir.path())?.count());
+
+    // The job is resumed by the restarted prover, without calling `prove` again.
+    let TestProver {
+        prover_service, vm, ..
+    } = make_new_prover_with_queue(Some(queue_dir.path().to_path_buf()));
+    let methods = prover_service.rpc_methods().unwrap();
+    let status: Option<ProofJobStatus> = methods
+        .call("prover_getJobStatus", rpc_params![rpc_hash.clone()])
+        .await?;
+    assert_eq!(Some(ProofJobStatus::Proving { attempt: 1 }), status);
+
+    vm.make_proof();
+    wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
+
+    // The proof is generated, so the job isn't persisted anymore.
+    assert_eq!(0, std::fs::read_dir(queue_dir.path())?.count());
+    let status: Option<ProofJobStatus> = methods
+        .call("prover_getJobStatus", rpc_params![rpc_hash])
+        .await?;
+    assert_eq!(None, status);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_proofs_are_aggregated() -> Result<(), anyhow::Error> {
+    let TestProver {
+        prover_service, vm, ..
+    } = make_new_prover_with_config(ProverServiceConfig {
+        aggregated_proof_block_jump: 2,
+        max_retries: 0,
+        queue_path: None,
+    });
+    // The aggregation program is run by its own host
+    let aggregation_vm = MockZkvm::new(MockValidityCond::default());
+    let prover_service =
+        prover_service.with_aggregation(aggregation_vm.clone(), MockCodeCommitment([1; 32]));
+
+    let header_hashes = (1..4).map(|hash| MockHash::from([hash as u8; 32]));
+    for header_hash in header_hashes.clone() {
+        prover_service
+            .submit_witness(make_transition_data(header_hash))
+            .await;
+        prover_service.prove(header_hash).await?;
+    }
+    vm.make_proof();
+    for header_hash in header_hashes {
+        wait_for_proof_proof_da_submission(header_hash, &prover_service).await;
+    }
+    assert!(prover_service.take_aggregated_proofs().await.is_empty());
+    aggregation_vm.make_proof();
+
+    // The first two proofs are aggregated, the third one waits for the next one.
+    let mut aggregated_proofs = Vec::new();
+    for _ in 0..10 {
+        aggregated_proofs.extend(prover_service.take_aggregated_proofs().await);
+        if !aggregated_proofs.is_empty() {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
+    }
+    assert_eq!(1, aggregated_proofs.len());
+    assert!(prover_service.take_aggregated_proofs().await.is_empty());
+
+    // Proofs generated by the executor only contain their public input.
+    let err = verify_aggregated_proof::<MockZkvm<MockValidityCond>, MockDaSpec, [u8; 0]>(
+        &borsh::to_vec(&aggregated_proofs[0])?,
+        &MockCodeCommitment([0; 32]),
+        &MockCodeCommitment([1; 32]),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The aggregated proof only contains its public input and can't be verified"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_state_transitions() {
+    let transition =
+        |initial_state_root: u8, final_state_root: u8, slot: u8, is_valid: bool| StateTransition::<
+            MockDaSpec,
+            [u8; 1],
+        > {
+            initial_state_root: [initial_state_root],
+            final_state_root: [final_state_root],
+            slot_hash: MockHash::from([slot; 32]),
+            prev_slot_hash: MockHash::from([slot - 1; 32]),
+            validity_condition: MockValidityCond { is_valid },
+        };
+    let code_commitment = MockCodeCommitment([1; 32]);
+
+    let aggregated = AggregatedStateTransition::aggregate::<sha2::Sha256>(
+        code_commitment.clone(),
+        [
+            transition(0, 1, 1, true),
+            transition(1, 1, 2, true),
+            transition(1, 2, 3, false),
+        ],
+    )
+    .unwrap();
+    assert_eq!(
+        AggregatedStateTransition {
+            slot_code_commitment: code_commitment.clone(),
+            initial_state_root: [0],
+            final_state_root: [2],
+            initial_slot_hash: MockHash::from([1; 32]),
+            final_slot_hash: MockHash::from([3; 32]),
+            validity_condition: MockValidityCond { is_valid: false },
+        },
+        aggregated
+    );
+
+    // The transitions have to follow each other.
+    let err = AggregatedStateTransition::aggregate::<sha2::Sha256>(
+        code_commitment.clone(),
+        [transition(0, 1, 1, true), transition(2, 3, 2, true)],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The state transition of slot 0x0202020202020202020202020202020202020202020202020202020202020202 doesn't start from the state of the previous slot"
+    );
+
+    // So do their slots, even if the states match.
+    let err = AggregatedStateTransition::aggregate::<sha2::Sha256>(
+        code_commitment.clone(),
+        [transition(0, 1, 1, true), transition(1, 2, 3, true)],
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "The slot 0x0303030303030303030303030303030303030303030303030303030303030303 doesn't follow the previous slot 0x0101010101010101010101010101010101010101010101010101010101010101"
+    );
+
+    assert!(AggregatedStateTransition::aggregate::<sha2::Sha256>(
+        code_commitment,
+        Vec::<StateTransition<MockDaSpec, [u8; 1]>>::new()
+    )
+    .is_err());
+}
+
 struct TestProver {
     prover_service: ParallelProverService<
         [u8; 0],
@@ -186,7 +351,7 @@ async fn wait_for_proof_proof_da_submission(
 ) {
     for _ in 0..10 {
         let status = prover_service.send_proof_to_da(header_hash).await;
-        if let Ok(ProofSubmissionStatus::Success) = status {
+        if let Ok(ProofSubmissionStatus::Success(_)) = status {
             return;
         }
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await
@@ -194,6 +359,18 @@ async fn wait_for_proof_proof_da_submission(
 }
 
 fn make_new_prover() -> TestProver {
+    make_new_prover_with_queue(None)
+}
+
+fn make_new_prover_with_queue(queue_path: Option<std::path::PathBuf>) -> TestProver {
+    make_new_prover_with_config(ProverServiceConfig {
+        aggregated_proof_block_jump: 1,
+        max_retries: 0,
+        queue_path,
+    })
+}
+
+fn make_new_prover_with_config(prover_service_config: ProverServiceConfig) -> TestProver {
     let num_threads = num_cpus::get();
     let vm = MockZkvm::new(MockValidityCond::default());
 
@@ -208,9 +385,7 @@ fn make_new_prover() -> TestProver {
             prover_config,
             (),
             num_threads,
-            ProverServiceConfig {
-                aggregated_proof_block_jump: 1,
-            },
+            prover_service_config,
         ),
         vm,
         num_worker_threads: num_threads,
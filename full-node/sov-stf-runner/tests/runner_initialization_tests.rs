@@ -1,4 +1,5 @@
 use sov_db::ledger_db::LedgerDB;
+use sov_db::pruning::PruningMode;
 use sov_mock_da::{
     MockAddress, MockBlockHeader, MockDaConfig, MockDaService, MockDaSpec, MockDaVerifier,
     MockValidityCond,
@@ -45,6 +46,38 @@ async fn init_and_restart() {
     assert_eq!(state_root_after_genesis, state_root_2);
 }
 
+#[tokio::test]
+async fn interrupted_genesis_is_rolled_back() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let genesis_variant = || -> MockInitVariant {
+        InitVariant::Genesis {
+            block_header: MockBlockHeader::from_height(0),
+            genesis_params: vec![1, 2, 3, 4, 5],
+        }
+    };
+    let finalized_version = || {
+        let storage_config = sov_state::config::Config {
+            path: tmpdir.path().to_path_buf(),
+        };
+        StorageManager::new(storage_config)
+            .unwrap()
+            .finalized_version()
+            .unwrap()
+    };
+
+    let state_root_after_genesis = {
+        let runner = initialize_runner(tmpdir.path(), genesis_variant());
+        *runner.get_state_root()
+    };
+    let version_after_genesis = finalized_version();
+
+    // No slot was committed to the ledger, so the genesis is rolled back and applied again
+    let runner = initialize_runner(tmpdir.path(), genesis_variant());
+    assert_eq!(state_root_after_genesis, *runner.get_state_root());
+    drop(runner);
+    assert_eq!(version_after_genesis, finalized_version());
+}
+
 type MockProverService = ParallelProverService<
     [u8; 32],
     ArrayWitness,
@@ -66,19 +99,33 @@ fn initialize_runner(
     let rollup_config = RollupConfig::<MockDaConfig> {
         storage: StorageConfig {
             path: path.to_path_buf(),
+            pruning: PruningMode::Archive,
+            index_txs_by_sender: false,
         },
         runner: RunnerConfig {
             start_height: 1,
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
+                limits: Default::default(),
+                admin: None,
             },
+            soft_confirmations: None,
+            metrics: None,
+            rest: None,
+            max_reorg_depth: 100,
+            confirmation_depth: 0,
+            state_diffs: None,
+            parallel_execution: false,
         },
         da: MockDaConfig {
             sender_address: address,
+            faults: vec![],
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            max_retries: 0,
+            queue_path: None,
         },
     };
 
@@ -4,16 +4,17 @@ use sov_mock_da::{
 };
 use sov_mock_zkvm::MockZkvm;
 use sov_stf_runner::{
-    InitVariant, ParallelProverService, ProverServiceConfig, RollupConfig, RollupProverConfig,
-    RpcConfig, RunnerConfig, StateTransitionRunner, StorageConfig,
+    FinalizedSlot, InitVariant, ParallelProverService, ProverServiceConfig, RollupConfig,
+    RollupProverConfig, RpcConfig, RunnerConfig, StateTransitionRunner, StorageConfig,
 };
 
 mod hash_stf;
 
 use hash_stf::{get_result_from_blocks, HashStf, Q, S};
 use sov_db::ledger_db::LedgerDB;
+use sov_db::pruning::PruningMode;
 use sov_prover_storage_manager::ProverStorageManager;
-use sov_rollup_interface::services::da::DaService;
+use sov_rollup_interface::services::da::{DaService, SlotData};
 use sov_rollup_interface::storage::HierarchicalStorageManager;
 use sov_state::storage::NativeStorage;
 use sov_state::{ProverStorage, Storage};
@@ -80,6 +81,69 @@ async fn test_simple_reorg_case() {
 #[ignore = "TBD"]
 async fn test_several_reorgs() {}
 
+#[tokio::test]
+async fn test_finalized_blocks_reorg() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let sequencer_address = MockAddress::new([11u8; 32]);
+    let genesis_params = vec![1, 2, 3, 4, 5];
+
+    let main_chain_blobs = vec![
+        vec![1, 1, 1, 1],
+        vec![2, 2, 2, 2],
+        vec![3, 3, 3, 3],
+        vec![4, 4, 4, 4],
+    ];
+    let fork_blobs = vec![
+        vec![13, 13, 13, 13],
+        vec![14, 14, 14, 14],
+        vec![15, 15, 15, 15],
+    ];
+    let expected_final_blobs = vec![
+        vec![1, 1, 1, 1],
+        vec![2, 2, 2, 2],
+        vec![13, 13, 13, 13],
+        vec![14, 14, 14, 14],
+        vec![15, 15, 15, 15],
+    ];
+
+    // With instant finality, blocks 3 and 4 are finalized and committed before the fork reverts them
+    let mut da_service = MockDaService::new(sequencer_address);
+    da_service.set_wait_attempts(2);
+
+    let genesis_header = da_service.get_last_finalized_block_header().await.unwrap();
+
+    let planned_fork = PlannedFork::new(5, 2, fork_blobs.clone()).reverting_finalized();
+    da_service.set_planned_fork(planned_fork).await.unwrap();
+
+    for b in &main_chain_blobs {
+        da_service.send_transaction(b).await.unwrap();
+    }
+
+    let (expected_state_root, expected_root_hash) =
+        get_expected_execution_hash_from(&genesis_params, expected_final_blobs);
+
+    let init_variant: MockInitVariant = InitVariant::Genesis {
+        block_header: genesis_header,
+        genesis_params,
+    };
+
+    let (before, after) = runner_execution(tmpdir.path(), init_variant, da_service.clone()).await;
+    assert_ne!(before, after);
+    assert_eq!(expected_state_root, after);
+
+    let saved_root_hash = get_saved_root_hash(tmpdir.path()).unwrap().unwrap();
+    assert_eq!(expected_root_hash.unwrap(), saved_root_hash);
+
+    // The reverted slots are replaced in the ledger by the blocks of the fork
+    let ledger_db = LedgerDB::with_path(tmpdir.path()).unwrap();
+    let (head_slot_number, head_slot) = ledger_db.get_head_slot().unwrap().unwrap();
+    assert_eq!(5, head_slot_number.0);
+    assert_eq!(
+        da_service.get_block_at(5).await.unwrap().hash(),
+        head_slot.hash
+    );
+}
+
 #[tokio::test]
 async fn test_instant_finality_data_stored() {
     let tmpdir = tempfile::tempdir().unwrap();
@@ -114,27 +178,104 @@ async fn test_instant_finality_data_stored() {
     assert_eq!(expected_root_hash.unwrap(), saved_root_hash);
 }
 
+#[tokio::test]
+async fn test_confirmation_depth_delays_finalization() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let sequencer_address = MockAddress::new([11u8; 32]);
+    let genesis_params = vec![1, 2, 3, 4, 5];
+
+    // The DA layer finalizes blocks instantly, but the runner waits for two confirmations
+    let mut da_service = MockDaService::new(sequencer_address);
+    da_service.set_wait_attempts(2);
+
+    let genesis_header = da_service.get_last_finalized_block_header().await.unwrap();
+
+    let blobs = vec![
+        vec![1, 1, 1, 1],
+        vec![2, 2, 2, 2],
+        vec![3, 3, 3, 3],
+        vec![4, 4, 4, 4],
+    ];
+    for b in &blobs {
+        da_service.send_transaction(b).await.unwrap();
+    }
+
+    let (expected_state_root, _) = get_expected_execution_hash_from(&genesis_params, blobs);
+    let (expected_finalized_root, expected_finalized_root_hash) =
+        get_expected_execution_hash_from(&genesis_params, vec![vec![1, 1, 1, 1], vec![2, 2, 2, 2]]);
+
+    let init_variant: MockInitVariant = InitVariant::Genesis {
+        block_header: genesis_header,
+        genesis_params,
+    };
+
+    let (_, after, finalized_slots) =
+        runner_execution_with_depth(tmpdir.path(), init_variant, da_service.clone(), 2).await;
+    assert_eq!(expected_state_root, after);
+
+    // Only the blocks followed by two blocks are committed
+    assert_eq!(
+        vec![1, 2],
+        finalized_slots
+            .iter()
+            .map(|slot| slot.height)
+            .collect::<Vec<_>>()
+    );
+    let last_finalized = finalized_slots.last().unwrap();
+    assert_eq!(expected_finalized_root.to_vec(), last_finalized.state_root);
+    assert_eq!(
+        da_service.get_block_at(2).await.unwrap().hash(),
+        last_finalized.da_block_hash
+    );
+    let saved_root_hash = get_saved_root_hash(tmpdir.path()).unwrap().unwrap();
+    assert_eq!(expected_finalized_root_hash.unwrap(), saved_root_hash);
+}
+
 async fn runner_execution(
     path: &std::path::Path,
     init_variant: MockInitVariant,
     da_service: MockDaService,
 ) -> ([u8; 32], [u8; 32]) {
+    let (before, after, _) = runner_execution_with_depth(path, init_variant, da_service, 0).await;
+    (before, after)
+}
+
+async fn runner_execution_with_depth(
+    path: &std::path::Path,
+    init_variant: MockInitVariant,
+    da_service: MockDaService,
+    confirmation_depth: u64,
+) -> ([u8; 32], [u8; 32], Vec<FinalizedSlot>) {
     let rollup_config = RollupConfig::<MockDaConfig> {
         storage: StorageConfig {
             path: path.to_path_buf(),
+            pruning: PruningMode::Archive,
+            index_txs_by_sender: false,
         },
         runner: RunnerConfig {
             start_height: 1,
             rpc_config: RpcConfig {
                 bind_host: "127.0.0.1".to_string(),
                 bind_port: 0,
+                limits: Default::default(),
+                admin: None,
             },
+            soft_confirmations: None,
+            metrics: None,
+            rest: None,
+            max_reorg_depth: 100,
+            confirmation_depth,
+            state_diffs: None,
+            parallel_execution: false,
         },
         da: MockDaConfig {
             sender_address: da_service.get_sequencer_address(),
+            faults: vec![],
         },
         prover_service: ProverServiceConfig {
             aggregated_proof_block_jump: 1,
+            max_retries: 0,
+            queue_path: None,
         },
     };
 
@@ -173,12 +314,17 @@ async fn runner_execution(
     )
     .unwrap();
 
+    let mut finalized_slots_receiver = runner.subscribe_finalized_slots();
     let before = *runner.get_state_root();
     let end = runner.run_in_process().await;
     assert!(end.is_err());
     let after = *runner.get_state_root();
 
-    (before, after)
+    let mut finalized_slots = Vec::new();
+    while let Ok(slot) = finalized_slots_receiver.try_recv() {
+        finalized_slots.push(slot);
+    }
+    (before, after, finalized_slots)
 }
 
 fn get_saved_root_hash(
@@ -2,15 +2,20 @@ use std::fmt::Debug;
 
 use anyhow::Result;
 #[cfg(feature = "native")]
-use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
 use sov_modules_api::prelude::*;
-use sov_modules_api::{CallResponse, WorkingSet};
+use sov_modules_api::{CallResponse, EventEmitter, WorkingSet};
 use thiserror::Error;
 
 use super::ValueSetter;
 
 /// This enumeration represents the available call messages for interacting with the `sov-value-setter` module.
-#[cfg_attr(feature = "native", derive(CliWalletArg), derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema)
+)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize),
@@ -50,7 +55,7 @@ impl<C: sov_modules_api::Context> ValueSetter<C> {
 
         // This is how we set a new value:
         self.value.set(&new_value, working_set);
-        working_set.add_event("set", &format!("value_set: {new_value:?}"));
+        working_set.emit_event(self, "set", &format!("value_set: {new_value:?}"));
 
         Ok(CallResponse::default())
     }
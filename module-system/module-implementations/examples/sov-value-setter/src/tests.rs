@@ -46,7 +46,10 @@ fn test_value_setter_helper<C: Context>(
     {
         module.call(call_msg, &context, working_set).unwrap();
         let event = &working_set.events()[0];
-        assert_eq!(event, &Event::new("set", "value_set: 99"));
+        assert_eq!(
+            event,
+            &Event::with_module("ValueSetter", "set", "value_set: 99")
+        );
     }
 
     // Test query
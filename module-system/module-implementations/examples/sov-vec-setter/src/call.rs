@@ -2,7 +2,7 @@ use std::fmt::Debug;
 
 use anyhow::Result;
 #[cfg(feature = "native")]
-use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 use thiserror::Error;
@@ -15,6 +15,7 @@ use super::VecSetter;
     derive(serde::Serialize),
     derive(serde::Deserialize),
     derive(CliWalletArg),
+    derive(CallMessageBuilder),
     derive(schemars::JsonSchema)
 )]
 #[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
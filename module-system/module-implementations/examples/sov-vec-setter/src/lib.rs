@@ -8,6 +8,8 @@ mod query;
 
 pub use call::CallMessage;
 #[cfg(feature = "native")]
+pub use call::CallMessageBuilder;
+#[cfg(feature = "native")]
 pub use query::*;
 use serde::{Deserialize, Serialize};
 use sov_modules_api::{Error, ModuleInfo, WorkingSet};
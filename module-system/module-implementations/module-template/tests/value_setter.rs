@@ -47,7 +47,10 @@ fn test_value_setter_helper<C: Context>(
     {
         module.call(call_msg, &context, working_set).unwrap();
         let event = &working_set.events()[0];
-        assert_eq!(event, &Event::new("set", "value_set: 99"));
+        assert_eq!(
+            event,
+            &Event::with_module("ExampleModule", "set", "value_set: 99")
+        );
     }
 
     // Test query
@@ -1,16 +1,31 @@
-use anyhow::{ensure, Result};
-use sov_modules_api::{CallResponse, Context, Signature, StateMapAccessor, WorkingSet};
+use anyhow::Result;
+use sov_modules_api::{
+    CallResponse, Context, ModuleErrorCode, Signature, StateMapAccessor, StateValueAccessor,
+    WorkingSet,
+};
 
-use crate::Accounts;
+use crate::{Accounts, SessionKey};
 
 /// To update the account's public key, the sender must sign this message as proof of possession of the new key.
 pub const UPDATE_ACCOUNT_MSG: [u8; 32] = [1; 32];
 
+/// To register a session key, the key must sign this message followed by the address of the account,
+/// as returned by [`session_key_message`].
+pub const REGISTER_SESSION_KEY_MSG: [u8; 32] = [2; 32];
+
+/// Returns the message a session key signs to be registered for the account at `account`.
+pub fn session_key_message<C: Context>(account: &C::Address) -> Vec<u8> {
+    let mut message = REGISTER_SESSION_KEY_MSG.to_vec();
+    message.extend_from_slice(account.as_ref());
+    message
+}
+
 /// Represents the available call messages for interacting with the sov-accounts module.
 #[cfg_attr(
     feature = "native",
     derive(schemars::JsonSchema),
     derive(sov_modules_api::macros::CliWalletArg),
+    derive(sov_modules_api::macros::CallMessageBuilder),
     schemars(
         bound = "C::PublicKey: ::schemars::JsonSchema, C::Signature: ::schemars::JsonSchema",
         rename = "CallMessage"
@@ -31,6 +46,21 @@ pub enum CallMessage<C: Context> {
         /// A valid signature from the new public key
         C::Signature,
     ),
+    /// Registers a session key, allowed to sign transactions on behalf of the sender.
+    /// The sender must be in possession of the session key.
+    RegisterSessionKey(
+        /// The session key
+        C::PublicKey,
+        /// A valid signature from the session key of the message returned by [`session_key_message`]
+        C::Signature,
+        /// The rollup height from which the session key can't sign transactions anymore, if any
+        Option<u64>,
+    ),
+    /// Revokes a session key of the sender.
+    RevokeSessionKey(
+        /// The session key to revoke
+        C::PublicKey,
+    ),
 }
 
 impl<C: Context> Accounts<C> {
@@ -41,16 +71,22 @@ impl<C: Context> Accounts<C> {
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
+        self.exit_if_session_key_is_active(working_set)?;
         self.exit_if_account_exists(&new_pub_key, working_set)?;
 
-        let pub_key = self.public_keys.get_or_err(context.sender(), working_set)?;
+        let pub_key = self
+            .public_keys
+            .get_or_err(context.sender(), working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))?;
 
-        let account = self.accounts.remove_or_err(&pub_key, working_set)?;
+        let account = self
+            .accounts
+            .remove_or_err(&pub_key, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))?;
         // Sanity check
-        ensure!(
-            context.sender() == &account.addr,
-            "Inconsistent account data"
-        );
+        if context.sender() != &account.addr {
+            return Err(ModuleErrorCode::InvariantViolation.error("Inconsistent account data"));
+        }
 
         // Proof that the sender is in possession of the `new_pub_key`.
         signature.verify(&new_pub_key, &UPDATE_ACCOUNT_MSG)?;
@@ -62,15 +98,82 @@ impl<C: Context> Accounts<C> {
         Ok(CallResponse::default())
     }
 
+    pub(crate) fn register_session_key(
+        &self,
+        session_key: C::PublicKey,
+        signature: C::Signature,
+        expires_at: Option<u64>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key_is_active(working_set)?;
+        self.exit_if_account_exists(&session_key, working_set)?;
+        if let Some(expires_at) = expires_at {
+            if expires_at <= context.slot_height() {
+                return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                    "Session key expiration {} is not in the future",
+                    expires_at
+                )));
+            }
+        }
+
+        // Proof that the sender is in possession of the `session_key`.
+        signature.verify(&session_key, &session_key_message::<C>(context.sender()))?;
+
+        let session_key_data = SessionKey {
+            account: context.sender().clone(),
+            expires_at,
+        };
+        self.session_keys
+            .set(&session_key, &session_key_data, working_set);
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn revoke_session_key(
+        &self,
+        session_key: C::PublicKey,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.exit_if_session_key_is_active(working_set)?;
+
+        let session_key_data = self
+            .session_keys
+            .get_or_err(&session_key, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))?;
+        if context.sender() != &session_key_data.account {
+            return Err(
+                ModuleErrorCode::Unauthorized.error("Session key doesn't belong to the sender")
+            );
+        }
+
+        self.session_keys.delete(&session_key, working_set);
+        Ok(CallResponse::default())
+    }
+
     fn exit_if_account_exists(
         &self,
         new_pub_key: &C::PublicKey,
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
-        anyhow::ensure!(
-            self.accounts.get(new_pub_key, working_set).is_none(),
-            "New PublicKey already exists"
-        );
+        if self.accounts.get(new_pub_key, working_set).is_some() {
+            return Err(ModuleErrorCode::InvariantViolation.error("New PublicKey already exists"));
+        }
+        if self.session_keys.get(new_pub_key, working_set).is_some() {
+            return Err(
+                ModuleErrorCode::InvariantViolation.error("New PublicKey is already a session key")
+            );
+        }
+        Ok(())
+    }
+
+    // The keys of an account are managed with its account key only,
+    // so a session key can't extend its own permissions.
+    fn exit_if_session_key_is_active(&self, working_set: &mut WorkingSet<C>) -> Result<()> {
+        if self.active_session_key.get(working_set).is_some() {
+            return Err(ModuleErrorCode::Unauthorized
+                .error("Session keys can't manage the keys of an account"));
+        }
         Ok(())
     }
 }
@@ -1,9 +1,17 @@
 use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
+use sov_modules_api::{Context, StateMapAccessor, StateValueAccessor, WorkingSet};
 
 use crate::{Account, Accounts};
 
+/// The arguments of the pre-dispatch tx hook.
+pub struct AccountsTxHookArg<C: Context> {
+    /// The sequencer public key
+    pub sequencer: C::PublicKey,
+    /// The current rollup height, used to check the expiration of session keys
+    pub height: u64,
+}
+
 /// The computed addresses of a pre-dispatch tx hook.
 pub struct AccountsTxHook<C: Context> {
     /// The tx sender address
@@ -23,20 +31,58 @@ impl<C: Context> Accounts<C> {
             .map(Ok)
             .unwrap_or_else(|| self.create_default_account(pubkey, working_set))
     }
+
+    /// Returns the key of the account the transactions signed by `signer` are sent from.
+    /// It is the account key of the account which registered `signer` as a session key,
+    /// or `signer` itself otherwise.
+    fn account_key(
+        &self,
+        signer: &C::PublicKey,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<C::PublicKey> {
+        match self.session_keys.get(signer, working_set) {
+            Some(session_key) => self
+                .public_keys
+                .get_or_err(&session_key.account, working_set),
+            None => Ok(signer.clone()),
+        }
+    }
 }
 
 impl<C: Context> TxHooks for Accounts<C> {
     type Context = C;
-    type PreArg = C::PublicKey;
+    type PreArg = AccountsTxHookArg<C>;
     type PreResult = AccountsTxHook<C>;
 
     fn pre_dispatch_tx_hook(
         &self,
         tx: &Transaction<C>,
         working_set: &mut WorkingSet<C>,
-        sequencer: &C::PublicKey,
+        arg: &AccountsTxHookArg<C>,
     ) -> anyhow::Result<AccountsTxHook<C>> {
-        let sender = self.get_or_create_default(tx.pub_key(), working_set)?;
+        let AccountsTxHookArg { sequencer, height } = arg;
+
+        let sender = match self.session_keys.get(tx.pub_key(), working_set) {
+            Some(session_key) => {
+                anyhow::ensure!(
+                    !session_key.is_expired(*height),
+                    "Session key expired at height {}",
+                    session_key.expires_at.unwrap_or_default()
+                );
+                self.active_session_key.set(tx.pub_key(), working_set);
+                let account_key = self
+                    .public_keys
+                    .get_or_err(&session_key.account, working_set)?;
+                self.accounts.get_or_err(&account_key, working_set)?
+            }
+            None => {
+                // The session key of a previous transaction rejected after this hook.
+                if self.active_session_key.get(working_set).is_some() {
+                    self.active_session_key.delete(working_set);
+                }
+                self.get_or_create_default(tx.pub_key(), working_set)?
+            }
+        };
         let sequencer = self.get_or_create_default(sequencer, working_set)?;
         let tx_nonce = tx.nonce();
 
@@ -59,9 +105,14 @@ impl<C: Context> TxHooks for Accounts<C> {
         _ctx: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
-        let mut account = self.accounts.get_or_err(tx.pub_key(), working_set)?;
+        let account_key = self.account_key(tx.pub_key(), working_set)?;
+        let mut account = self.accounts.get_or_err(&account_key, working_set)?;
         account.nonce += 1;
-        self.accounts.set(tx.pub_key(), &account, working_set);
+        self.accounts.set(&account_key, &account, working_set);
+
+        if &account_key != tx.pub_key() {
+            self.active_session_key.delete(working_set);
+        }
         Ok(())
     }
 }
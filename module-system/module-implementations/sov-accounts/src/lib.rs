@@ -13,8 +13,10 @@ pub use query::*;
 #[cfg(test)]
 mod tests;
 
-pub use call::{CallMessage, UPDATE_ACCOUNT_MSG};
-pub use hooks::AccountsTxHook;
+#[cfg(feature = "native")]
+pub use call::CallMessageBuilder;
+pub use call::{session_key_message, CallMessage, REGISTER_SESSION_KEY_MSG, UPDATE_ACCOUNT_MSG};
+pub use hooks::{AccountsTxHook, AccountsTxHookArg};
 use sov_modules_api::{Context, Error, ModuleInfo, WorkingSet};
 
 impl<C: Context> FromIterator<C::PublicKey> for AccountConfig<C> {
@@ -34,6 +36,24 @@ pub struct Account<C: Context> {
     pub nonce: u64,
 }
 
+/// A key allowed to sign transactions on behalf of an account, other than the account key.
+/// Session keys are registered with [`CallMessage::RegisterSessionKey`].
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub struct SessionKey<C: Context> {
+    /// The address of the account the key signs transactions for.
+    pub account: C::Address,
+    /// The rollup height from which the key can't sign transactions anymore, if any.
+    pub expires_at: Option<u64>,
+}
+
+impl<C: Context> SessionKey<C> {
+    /// Returns `true` if the key can't sign transactions anymore at the rollup height `height`.
+    pub fn is_expired(&self, height: u64) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| height >= expires_at)
+    }
+}
+
 /// A module responsible for managing accounts on the rollup.
 #[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
 #[derive(ModuleInfo, Clone)]
@@ -50,6 +70,15 @@ pub struct Accounts<C: Context> {
     /// Mapping from a public key to a corresponding account.
     #[state]
     pub(crate) accounts: sov_modules_api::StateMap<C::PublicKey, Account<C>>,
+
+    /// Mapping from a session key to the account it signs transactions for.
+    #[state]
+    pub(crate) session_keys: sov_modules_api::StateMap<C::PublicKey, SessionKey<C>>,
+
+    /// The session key which signed the transaction being executed, if any.
+    /// It is set by the pre-dispatch hook and removed by the post-dispatch hook.
+    #[state]
+    pub(crate) active_session_key: sov_modules_api::StateValue<C::PublicKey>,
 }
 
 impl<C: Context> sov_modules_api::Module for Accounts<C> {
@@ -75,6 +104,12 @@ impl<C: Context> sov_modules_api::Module for Accounts<C> {
             call::CallMessage::UpdatePublicKey(new_pub_key, sig) => {
                 Ok(self.update_public_key(new_pub_key, sig, context, working_set)?)
             }
+            call::CallMessage::RegisterSessionKey(session_key, sig, expires_at) => {
+                Ok(self.register_session_key(session_key, sig, expires_at, context, working_set)?)
+            }
+            call::CallMessage::RevokeSessionKey(session_key) => {
+                Ok(self.revoke_session_key(session_key, context, working_set)?)
+            }
         }
     }
 }
@@ -1,9 +1,10 @@
 //! Defines rpc queries exposed by the accounts module, along with the relevant types
 use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::utils::set_rpc_archival_version;
 use sov_modules_api::{AddressBech32, StateMapAccessor, WorkingSet};
 
-use crate::{Account, Accounts};
+use crate::{Account, Accounts, SessionKey};
 
 /// This is the response returned from the accounts_getAccount endpoint.
 #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
@@ -23,15 +24,31 @@ pub enum Response {
     AccountEmpty,
 }
 
+/// This is the response returned from the accounts_getSessionKey endpoint.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub enum SessionKeyResponse {
+    /// The public key is a session key of an account.
+    SessionKeyExists {
+        /// The address of the account the session key signs transactions for.
+        addr: AddressBech32,
+        /// The rollup height from which the session key can't sign transactions anymore, if any.
+        expires_at: Option<u64>,
+    },
+    /// The public key is not a session key.
+    SessionKeyEmpty,
+}
+
 #[rpc_gen(client, server, namespace = "accounts")]
 impl<C: sov_modules_api::Context> Accounts<C> {
     #[rpc_method(name = "getAccount")]
-    /// Get the account corresponding to the given public key.
+    /// Get the account corresponding to the given public key, at the state `version` if it's given.
     pub fn get_account(
         &self,
         pub_key: C::PublicKey,
+        version: Option<u64>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<Response> {
+        set_rpc_archival_version(version, working_set)?;
         let response = match self.accounts.get(&pub_key, working_set) {
             Some(Account { addr, nonce }) => Response::AccountExists {
                 addr: addr.into(),
@@ -42,4 +59,36 @@ impl<C: sov_modules_api::Context> Accounts<C> {
 
         Ok(response)
     }
+
+    #[rpc_method(name = "getSessionKey")]
+    /// Get the account the given session key signs transactions for.
+    pub fn get_session_key(
+        &self,
+        pub_key: C::PublicKey,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<SessionKeyResponse> {
+        let response = match self.session_keys.get(&pub_key, working_set) {
+            Some(SessionKey {
+                account,
+                expires_at,
+            }) => SessionKeyResponse::SessionKeyExists {
+                addr: account.into(),
+                expires_at,
+            },
+            None => SessionKeyResponse::SessionKeyEmpty,
+        };
+
+        Ok(response)
+    }
+}
+
+impl<C: sov_modules_api::Context> Accounts<C> {
+    /// Get the nonce of the account with the given address, if it exists.
+    /// The transactions signed by the session keys of the account share this nonce.
+    pub fn get_nonce(&self, address: &C::Address, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        let pub_key = self.public_keys.get(address, working_set)?;
+        self.accounts
+            .get(&pub_key, working_set)
+            .map(|account| account.nonce)
+    }
 }
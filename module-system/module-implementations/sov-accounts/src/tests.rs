@@ -1,12 +1,15 @@
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::transaction::Transaction;
 use sov_modules_api::{
-    AddressBech32, Context, Module, PrivateKey, PublicKey, Spec, StateMapAccessor, WorkingSet,
+    AddressBech32, Context, Error, Module, PrivateKey, PublicKey, Spec, StateMapAccessor,
+    WorkingSet,
 };
 use sov_prover_storage_manager::new_orphan_storage;
 
-use crate::query::{self, Response};
-use crate::{call, AccountConfig, Accounts};
+use crate::query::{self, Response, SessionKeyResponse};
+use crate::{call, session_key_message, AccountConfig, Accounts, AccountsTxHookArg};
 
 type C = DefaultContext;
 
@@ -26,7 +29,9 @@ fn test_config_account() {
 
     accounts.init_module(&account_config, working_set).unwrap();
 
-    let query_response = accounts.get_account(init_pub_key, working_set).unwrap();
+    let query_response = accounts
+        .get_account(init_pub_key, None, working_set)
+        .unwrap();
 
     assert_eq!(
         query_response,
@@ -54,11 +59,14 @@ fn test_update_account() {
 
     // Test new account creation
     {
+        assert_eq!(None, accounts.get_nonce(&sender_addr, working_set));
         accounts
             .create_default_account(&sender, working_set)
             .unwrap();
 
-        let query_response = accounts.get_account(sender.clone(), working_set).unwrap();
+        let query_response = accounts
+            .get_account(sender.clone(), None, working_set)
+            .unwrap();
 
         assert_eq!(
             query_response,
@@ -83,12 +91,16 @@ fn test_update_account() {
             .unwrap();
 
         // Account corresponding to the old public key does not exist
-        let query_response = accounts.get_account(sender, working_set).unwrap();
+        let query_response = accounts.get_account(sender, None, working_set).unwrap();
 
         assert_eq!(query_response, query::Response::AccountEmpty);
+        // The nonce is still found from the address of the account
+        assert_eq!(Some(0), accounts.get_nonce(&sender_addr, working_set));
 
         // New account with the new public key and an old address is created.
-        let query_response = accounts.get_account(new_pub_key, working_set).unwrap();
+        let query_response = accounts
+            .get_account(new_pub_key, None, working_set)
+            .unwrap();
 
         assert_eq!(
             query_response,
@@ -129,7 +141,7 @@ fn test_update_account_fails() {
             &sender_context_1,
             working_set
         )
-        .is_err())
+        .is_err_and(|err| matches!(err, Error::InvariantViolation(_))))
 }
 
 #[test]
@@ -164,6 +176,130 @@ fn test_get_account_after_pub_key_update() {
     assert_eq!(acc.addr, sender_1_addr)
 }
 
+#[test]
+fn test_session_key() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let accounts = &mut Accounts::<C>::default();
+
+    let account_pub_key = DefaultPrivateKey::generate().pub_key();
+    let sequencer = DefaultPrivateKey::generate().pub_key();
+    let account_addr = account_pub_key.to_address::<<C as Spec>::Address>();
+    let sequencer_addr = sequencer.to_address::<<C as Spec>::Address>();
+    let account_context = C::new(account_addr, sequencer_addr, 1);
+
+    accounts
+        .create_default_account(&account_pub_key, working_set)
+        .unwrap();
+
+    let session_priv_key = DefaultPrivateKey::generate();
+    let session_pub_key = session_priv_key.pub_key();
+
+    // The session key must sign the address of the account it is registered for.
+    let wrong_sig = session_priv_key.sign(&session_key_message::<C>(&sequencer_addr));
+    assert!(accounts
+        .call(
+            call::CallMessage::<C>::RegisterSessionKey(
+                session_pub_key.clone(),
+                wrong_sig,
+                Some(10)
+            ),
+            &account_context,
+            working_set
+        )
+        .is_err());
+
+    let sig = session_priv_key.sign(&session_key_message::<C>(&account_addr));
+    accounts
+        .call(
+            call::CallMessage::<C>::RegisterSessionKey(session_pub_key.clone(), sig, Some(10)),
+            &account_context,
+            working_set,
+        )
+        .unwrap();
+    assert_eq!(
+        accounts
+            .get_session_key(session_pub_key.clone(), working_set)
+            .unwrap(),
+        SessionKeyResponse::SessionKeyExists {
+            addr: AddressBech32::from(&account_addr),
+            expires_at: Some(10)
+        }
+    );
+
+    let session_tx =
+        |nonce| Transaction::<C>::new_signed_tx(&session_priv_key, vec![], 0, 0, 0, nonce);
+    let hook_arg = |height| AccountsTxHookArg {
+        sequencer: sequencer.clone(),
+        height,
+    };
+
+    // Transactions signed by the session key are sent from the account.
+    let tx = session_tx(0);
+    let hook = accounts
+        .pre_dispatch_tx_hook(&tx, working_set, &hook_arg(5))
+        .unwrap();
+    assert_eq!(hook.sender, account_addr);
+    let session_context = C::new(hook.sender, hook.sequencer, 5);
+
+    // A session key can't manage the keys of the account.
+    let other_priv_key = DefaultPrivateKey::generate();
+    let other_sig = other_priv_key.sign(&session_key_message::<C>(&account_addr));
+    assert!(accounts
+        .call(
+            call::CallMessage::<C>::RegisterSessionKey(other_priv_key.pub_key(), other_sig, None),
+            &session_context,
+            working_set
+        )
+        .is_err_and(|err| matches!(err, Error::Unauthorized(_))));
+
+    accounts
+        .post_dispatch_tx_hook(&tx, &session_context, working_set)
+        .unwrap();
+    assert_eq!(
+        accounts
+            .get_account(account_pub_key.clone(), None, working_set)
+            .unwrap(),
+        Response::AccountExists {
+            addr: AddressBech32::from(&account_addr),
+            nonce: 1
+        }
+    );
+    assert_eq!(
+        accounts
+            .get_account(session_pub_key.clone(), None, working_set)
+            .unwrap(),
+        Response::AccountEmpty
+    );
+
+    // The session key can't be used once expired.
+    assert!(accounts
+        .pre_dispatch_tx_hook(&session_tx(1), working_set, &hook_arg(10))
+        .is_err());
+
+    // Once revoked, the session key signs for its own account.
+    accounts
+        .call(
+            call::CallMessage::<C>::RevokeSessionKey(session_pub_key.clone()),
+            &account_context,
+            working_set,
+        )
+        .unwrap();
+    assert_eq!(
+        accounts
+            .get_session_key(session_pub_key.clone(), working_set)
+            .unwrap(),
+        SessionKeyResponse::SessionKeyEmpty
+    );
+    let hook = accounts
+        .pre_dispatch_tx_hook(&session_tx(0), working_set, &hook_arg(5))
+        .unwrap();
+    assert_eq!(
+        hook.sender,
+        session_pub_key.to_address::<<C as Spec>::Address>()
+    );
+}
+
 #[test]
 fn test_response_serialization() {
     let addr: Vec<u8> = (1..=32).collect();
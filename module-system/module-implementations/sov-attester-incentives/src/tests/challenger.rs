@@ -65,6 +65,7 @@ fn test_valid_challenge() {
         let transition = StateTransition::<MockDaSpec, _> {
             initial_state_root: initial_transition.state_root,
             slot_hash: [1; 32].into(),
+            prev_slot_hash: [0; 32].into(),
             final_state_root: transition_1.state_root,
             validity_condition: MockValidityCond { is_valid: true },
         };
@@ -193,6 +194,7 @@ fn test_invalid_challenge() {
     let transition: StateTransition<MockDaSpec, _> = StateTransition {
         initial_state_root: initial_transition.state_root,
         slot_hash: [1; 32].into(),
+        prev_slot_hash: [0; 32].into(),
         final_state_root: transition_1.state_root,
         validity_condition: MockValidityCond { is_valid: true },
     };
@@ -253,6 +255,7 @@ fn test_invalid_challenge() {
         let bad_transition = StateTransition::<MockDaSpec, _> {
             initial_state_root: initial_transition.state_root,
             slot_hash: [2; 32].into(),
+            prev_slot_hash: [0; 32].into(),
             final_state_root: transition_1.state_root,
             validity_condition: MockValidityCond { is_valid: true },
         }
@@ -280,6 +283,7 @@ fn test_invalid_challenge() {
         let bad_transition = StateTransition::<MockDaSpec, _> {
             initial_state_root: initial_transition.state_root,
             slot_hash: [1; 32].into(),
+            prev_slot_hash: [0; 32].into(),
             final_state_root: transition_1.state_root,
             validity_condition: MockValidityCond { is_valid: false },
         }
@@ -307,6 +311,7 @@ fn test_invalid_challenge() {
         let bad_transition = StateTransition::<MockDaSpec, _> {
             initial_state_root: transition_1.state_root,
             slot_hash: [1; 32].into(),
+            prev_slot_hash: [0; 32].into(),
             final_state_root: transition_1.state_root,
             validity_condition: MockValidityCond { is_valid: true },
         }
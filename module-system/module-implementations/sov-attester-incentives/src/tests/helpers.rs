@@ -60,6 +60,8 @@ pub(crate) fn create_bank_config_with_token(
         address_and_balances: address_and_balances.clone(),
         authorized_minters: vec![address_and_balances.first().unwrap().0],
         salt,
+        authority: None,
+        max_supply: None,
     };
 
     (
@@ -108,6 +110,7 @@ pub(crate) fn setup(
     let chain_state_config = sov_chain_state::ChainStateConfig {
         initial_slot_height: INIT_HEIGHT,
         current_time: Default::default(),
+        gas_price: vec![0, 0],
     };
 
     let chain_state = sov_chain_state::ChainState::<C, MockDaSpec>::default();
@@ -1,7 +1,7 @@
 use anyhow::{bail, Context, Result};
 #[cfg(feature = "native")]
-use sov_modules_api::macros::CliWalletArg;
-use sov_modules_api::{CallResponse, StateMapAccessor, WorkingSet};
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::{CallResponse, EventEmitter, ModuleErrorCode, StateMapAccessor, WorkingSet};
 
 use crate::{Amount, Bank, Coins, Token};
 
@@ -9,6 +9,7 @@ use crate::{Amount, Bank, Coins, Token};
 #[cfg_attr(
     feature = "native",
     derive(CliWalletArg),
+    derive(CallMessageBuilder),
     derive(schemars::JsonSchema),
     schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
 )]
@@ -31,6 +32,12 @@ pub enum CallMessage<C: sov_modules_api::Context> {
         minter_address: C::Address,
         /// Authorized minter list.
         authorized_minters: Vec<C::Address>,
+        /// The administrator of the new token, allowed to freeze and unfreeze accounts.
+        #[cfg_attr(feature = "serde", serde(default))]
+        authority: Option<C::Address>,
+        /// The maximum total supply of the new token. The supply is unbounded if it's `None`.
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_supply: Option<Amount>,
     },
 
     /// Transfers a specified amount of tokens to the specified address.
@@ -60,6 +67,89 @@ pub enum CallMessage<C: sov_modules_api::Context> {
         /// Address of the token to be frozen
         token_address: C::Address,
     },
+
+    /// Allows the spender to transfer up to the specified amount of tokens on behalf of the sender.
+    /// Replaces the previous allowance of the spender for that token.
+    Approve {
+        /// The address allowed to spend the tokens.
+        spender: C::Address,
+        /// The maximum amount of tokens the spender can transfer.
+        coins: Coins<C>,
+    },
+
+    /// Transfers tokens on behalf of their owner, using the allowance given to the sender.
+    TransferFrom {
+        /// The address from which the tokens will be transferred.
+        owner: C::Address,
+        /// The address to which the tokens will be transferred.
+        to: C::Address,
+        /// The amount of tokens to transfer.
+        coins: Coins<C>,
+    },
+
+    /// Removes the allowance given by the sender to the spender.
+    RevokeApproval {
+        /// The address which isn't allowed to spend the tokens anymore.
+        spender: C::Address,
+        /// Address of the token for which the allowance is removed.
+        token_address: C::Address,
+    },
+
+    /// Freezes an account, so it can't send, receive or burn tokens of that token anymore.
+    /// Only the authority of the token can freeze its accounts.
+    FreezeAccount {
+        /// Address of the token for which the account is frozen.
+        token_address: C::Address,
+        /// The account to freeze.
+        account: C::Address,
+    },
+
+    /// Unfreezes an account previously frozen by the authority of the token.
+    UnfreezeAccount {
+        /// Address of the token for which the account is unfrozen.
+        token_address: C::Address,
+        /// The account to unfreeze.
+        account: C::Address,
+    },
+
+    /// Replaces the authority of a token. Only the current authority can set a new one,
+    /// and setting it to `None` permanently gives up the administration of the token.
+    SetAuthority {
+        /// Address of the token whose authority is replaced.
+        token_address: C::Address,
+        /// The new authority of the token.
+        authority: Option<C::Address>,
+    },
+
+    /// Transfers tokens which stay locked until they vest. Nothing unlocks before `cliff_slots`
+    /// slots have passed, then the tokens unlock linearly until all of them are unlocked after
+    /// `vesting_slots` slots. The recipient gets the unlocked tokens with [`CallMessage::ClaimVested`].
+    TransferWithLock {
+        /// The address to which the tokens will be transferred.
+        to: C::Address,
+        /// The amount of tokens to lock.
+        coins: Coins<C>,
+        /// The number of slots before the first tokens unlock.
+        cliff_slots: u64,
+        /// The number of slots after which all the tokens are unlocked.
+        vesting_slots: u64,
+    },
+
+    /// Transfers the tokens unlocked by the vesting schedules of the sender to the sender.
+    ClaimVested {
+        /// Address of the token to claim.
+        token_address: C::Address,
+    },
+
+    /// Allows the minter to mint up to the specified amount of tokens, without being an authorized
+    /// minter of the token. Replaces the previous mint allowance of the minter for that token.
+    /// Only the authorized minters of the token can delegate mint allowances.
+    SetMintAllowance {
+        /// The address allowed to mint the tokens, like the address of a bridge module.
+        minter: C::Address,
+        /// The maximum amount of tokens the minter can mint.
+        coins: Coins<C>,
+    },
 }
 
 impl<C: sov_modules_api::Context> Bank<C> {
@@ -73,6 +163,8 @@ impl<C: sov_modules_api::Context> Bank<C> {
         initial_balance: Amount,
         minter_address: C::Address,
         authorized_minters: Vec<C::Address>,
+        authority: Option<C::Address>,
+        max_supply: Option<Amount>,
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<C::Address> {
@@ -80,6 +172,8 @@ impl<C: sov_modules_api::Context> Bank<C> {
             &token_name,
             &[(minter_address, initial_balance)],
             &authorized_minters,
+            authority,
+            max_supply,
             context.sender().as_ref(),
             salt,
             self.tokens.prefix(),
@@ -124,6 +218,9 @@ impl<C: sov_modules_api::Context> Bank<C> {
         let mut token = self
             .tokens
             .get_or_err(&coins.token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(context_logger)?;
+        self.ensure_not_frozen(owner, &coins.token_address, working_set)
             .with_context(context_logger)?;
         token
             .burn(owner, coins.amount, working_set)
@@ -160,8 +257,10 @@ impl<C: sov_modules_api::Context> Bank<C> {
         self.mint(coins, mint_to_address, context.sender(), working_set)
     }
 
-    /// Mints the `coins` to the address `mint_to_address` if `authorizer` is an allowed minter.
-    /// Returns an error if the token address doesn't exist or `context.sender()` is not authorized to mint tokens.
+    /// Mints the `coins` to the address `mint_to_address` if `authorizer` is an allowed minter,
+    /// or if it was given a large enough mint allowance, which is decreased by the minted amount.
+    /// Returns an error if the token address doesn't exist, `authorizer` is not authorized to mint tokens,
+    /// or the maximum supply of the token would be exceeded.
     ///
     /// On success, it updates the `self.tokens` set to store the new minted address.
     pub fn mint(
@@ -180,15 +279,102 @@ impl<C: sov_modules_api::Context> Bank<C> {
         let mut token = self
             .tokens
             .get_or_err(&coins.token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(context_logger)?;
+        self.ensure_not_frozen(mint_to_address, &coins.token_address, working_set)
             .with_context(context_logger)?;
+        token.ensure_mintable().with_context(context_logger)?;
+
+        // The authorized minters aren't bounded by an allowance
+        let allowance_key = (authorizer.clone(), coins.token_address.clone());
+        let remaining_allowance = match token.is_authorized_minter(authorizer) {
+            Ok(()) => None,
+            Err(e) => {
+                let allowance = self
+                    .mint_allowances
+                    .get(&allowance_key, working_set)
+                    .ok_or(e)
+                    .with_context(context_logger)?;
+                let remaining_allowance = allowance
+                    .checked_sub(coins.amount)
+                    .ok_or_else(|| {
+                        ModuleErrorCode::Unauthorized.error(format!(
+                            "Minter {} is allowed to mint {} tokens, but {} were requested",
+                            authorizer, allowance, coins.amount
+                        ))
+                    })
+                    .with_context(context_logger)?;
+                Some(remaining_allowance)
+            }
+        };
+
         token
-            .mint(authorizer, mint_to_address, coins.amount, working_set)
+            .mint(mint_to_address, coins.amount, working_set)
             .with_context(context_logger)?;
         self.tokens.set(&coins.token_address, &token, working_set);
 
+        if let Some(remaining_allowance) = remaining_allowance {
+            if remaining_allowance == 0 {
+                self.mint_allowances.delete(&allowance_key, working_set);
+            } else {
+                self.mint_allowances
+                    .set(&allowance_key, &remaining_allowance, working_set);
+            }
+            working_set.emit_event(
+                self,
+                "spend_mint_allowance",
+                &format!(
+                    "{} by {}, {} remaining",
+                    coins, authorizer, remaining_allowance
+                ),
+            );
+        }
+
         Ok(())
     }
 
+    /// Sets the amount of `coins` that `minter` can mint, replacing its previous mint allowance.
+    /// Returns an error if the token address doesn't exist or `context.sender()` is not an authorized minter of the token.
+    pub(crate) fn set_mint_allowance(
+        &self,
+        minter: C::Address,
+        coins: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let context_logger = || {
+            format!(
+                "Failed to set the mint allowance of {} for coins({}) by sender {}",
+                minter,
+                coins,
+                context.sender()
+            )
+        };
+        let token = self
+            .tokens
+            .get_or_err(&coins.token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(context_logger)?;
+        token
+            .is_authorized_minter(context.sender())
+            .with_context(context_logger)?;
+
+        let allowance_key = (minter.clone(), coins.token_address.clone());
+        if coins.amount == 0 {
+            self.mint_allowances.delete(&allowance_key, working_set);
+        } else {
+            self.mint_allowances
+                .set(&allowance_key, &coins.amount, working_set);
+        }
+        working_set.emit_event(
+            self,
+            "set_mint_allowance",
+            &format!("{} for {} by {}", coins, minter, context.sender()),
+        );
+
+        Ok(CallResponse::default())
+    }
+
     /// Tries to freeze the token address `token_address`.
     /// Returns an error if the token address doesn't exist,
     /// otherwise calls the [`Token::freeze`] function, and update the token set upon success.
@@ -208,6 +394,7 @@ impl<C: sov_modules_api::Context> Bank<C> {
         let mut token = self
             .tokens
             .get_or_err(&token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
             .with_context(context_logger)?;
         token
             .freeze(context.sender())
@@ -216,12 +403,163 @@ impl<C: sov_modules_api::Context> Bank<C> {
 
         Ok(CallResponse::default())
     }
+
+    /// Freezes or unfreezes `account` for the token at `token_address`.
+    /// Returns an error if the token address doesn't exist or `context.sender()` is not the authority of the token.
+    pub(crate) fn set_account_frozen(
+        &self,
+        token_address: C::Address,
+        account: C::Address,
+        frozen: bool,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let context_logger = || {
+            format!(
+                "Failed to {} account {} of token_address={} by sender {}",
+                if frozen { "freeze" } else { "unfreeze" },
+                account,
+                token_address,
+                context.sender()
+            )
+        };
+        let token = self
+            .tokens
+            .get_or_err(&token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(context_logger)?;
+        token
+            .is_authority(context.sender())
+            .with_context(context_logger)?;
+
+        let frozen_key = (account, token_address);
+        if frozen {
+            self.frozen_accounts.set(&frozen_key, &true, working_set);
+        } else {
+            self.frozen_accounts.delete(&frozen_key, working_set);
+        }
+
+        Ok(CallResponse::default())
+    }
+
+    /// Replaces the authority of the token at `token_address` by `authority`.
+    /// Returns an error if the token address doesn't exist or `context.sender()` is not the authority of the token.
+    pub(crate) fn set_authority(
+        &self,
+        token_address: C::Address,
+        authority: Option<C::Address>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let context_logger = || {
+            format!(
+                "Failed to set the authority of token_address={} by sender {}",
+                token_address,
+                context.sender()
+            )
+        };
+        let mut token = self
+            .tokens
+            .get_or_err(&token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(context_logger)?;
+        token
+            .is_authority(context.sender())
+            .with_context(context_logger)?;
+        token.authority = authority;
+        self.tokens.set(&token_address, &token, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    /// Sets the amount of `coins` that `spender` can transfer on behalf of `context.sender()`.
+    /// Returns an error if the token address doesn't exist.
+    pub(crate) fn approve(
+        &self,
+        spender: C::Address,
+        coins: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let owner = context.sender();
+        self.tokens
+            .get_or_err(&coins.token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(|| {
+                format!(
+                    "Failed to approve spender {} for coins({}) of owner {}",
+                    spender, coins, owner
+                )
+            })?;
+
+        let allowance_key = (owner.clone(), spender, coins.token_address);
+        if coins.amount == 0 {
+            self.allowances.delete(&allowance_key, working_set);
+        } else {
+            self.allowances
+                .set(&allowance_key, &coins.amount, working_set);
+        }
+
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers the set of `coins` from `owner` to `to`, spending the allowance given by `owner` to `context.sender()`.
+    /// Returns an error if the allowance is not large enough.
+    pub(crate) fn transfer_approved(
+        &self,
+        owner: C::Address,
+        to: C::Address,
+        coins: Coins<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let spender = context.sender();
+        let allowance_key = (owner.clone(), spender.clone(), coins.token_address.clone());
+        let allowance = self
+            .allowances
+            .get(&allowance_key, working_set)
+            .unwrap_or_default();
+
+        let remaining_allowance = allowance.checked_sub(coins.amount).ok_or_else(|| {
+            ModuleErrorCode::Unauthorized.error(format!(
+                "Spender {} is allowed to transfer {} tokens of owner {}, but {} were requested",
+                spender, allowance, owner, coins.amount
+            ))
+        })?;
+
+        self.transfer_from(&owner, &to, coins, working_set)?;
+
+        if remaining_allowance == 0 {
+            self.allowances.delete(&allowance_key, working_set);
+        } else {
+            self.allowances
+                .set(&allowance_key, &remaining_allowance, working_set);
+        }
+
+        Ok(CallResponse::default())
+    }
+
+    /// Removes the allowance given by `context.sender()` to `spender` for the token at `token_address`.
+    pub(crate) fn revoke_approval(
+        &self,
+        spender: C::Address,
+        token_address: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.allowances.delete(
+            &(context.sender().clone(), spender, token_address),
+            working_set,
+        );
+
+        Ok(CallResponse::default())
+    }
 }
 
 impl<C: sov_modules_api::Context> Bank<C> {
     /// Transfers the set of `coins` from the address `from` to the address `to`.
     ///
-    /// Returns an error if the token address doesn't exist.
+    /// Returns an error if the token address doesn't exist, or if one of the accounts is frozen.
     pub fn transfer_from(
         &self,
         from: &C::Address,
@@ -238,6 +576,10 @@ impl<C: sov_modules_api::Context> Bank<C> {
         let token = self
             .tokens
             .get_or_err(&coins.token_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))
+            .with_context(context_logger)?;
+        self.ensure_not_frozen(from, &coins.token_address, working_set)
+            .and_then(|_| self.ensure_not_frozen(to, &coins.token_address, working_set))
             .with_context(context_logger)?;
         token
             .transfer(from, to, coins.amount, working_set)
@@ -245,6 +587,46 @@ impl<C: sov_modules_api::Context> Bank<C> {
         Ok(CallResponse::default())
     }
 
+    /// Returns an error if `account` is frozen for the token at `token_address`.
+    fn ensure_not_frozen(
+        &self,
+        account: &C::Address,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if self.is_account_frozen(account.clone(), token_address.clone(), working_set) {
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
+                "Account {} is frozen for token_address={}",
+                account, token_address
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if `account` was frozen by the authority of the token at `token_address`.
+    pub fn is_account_frozen(
+        &self,
+        account: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        self.frozen_accounts
+            .get(&(account, token_address), working_set)
+            .unwrap_or_default()
+    }
+
+    /// Returns the authority of the token at `token_address`, or `None` if the token doesn't exist
+    /// or has no authority.
+    pub fn get_token_authority(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<C::Address> {
+        self.tokens
+            .get(token_address, working_set)
+            .and_then(|token| token.authority)
+    }
+
     /// Helper function used by the rpc method [`balance_of`](Bank::balance_of) to return the balance of the token stored at `token_address`
     /// for the user having the address `user_address` from the underlying storage. If the token address doesn't exist, or
     /// if the user doesn't have tokens of that type, return `None`. Otherwise, wrap the resulting balance in `Some`.
@@ -269,6 +651,57 @@ impl<C: sov_modules_api::Context> Bank<C> {
         token.map(|token| token.name)
     }
 
+    /// Returns the amount of tokens stored at `token_address` that `spender` can transfer on behalf of `owner`,
+    /// or `None` if `owner` didn't approve `spender`.
+    pub fn get_allowance(
+        &self,
+        owner: C::Address,
+        spender: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        self.allowances
+            .get(&(owner, spender, token_address), working_set)
+    }
+
+    /// Returns the amount of tokens stored at `token_address` that `minter` can still mint,
+    /// or `None` if the token doesn't exist or its supply is unbounded for `minter`.
+    ///
+    /// It's the mint allowance of `minter`, or the remaining supply of the token for its authorized minters,
+    /// capped by the remaining supply. It's zero if the token is frozen.
+    pub fn get_mintable_amount(
+        &self,
+        minter: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        let token = self.tokens.get(&token_address, working_set)?;
+        if token.ensure_mintable().is_err() {
+            return Some(0);
+        }
+        let remaining_supply = token.remaining_supply();
+        if token.is_authorized_minter(&minter).is_ok() {
+            return remaining_supply;
+        }
+        let allowance = self
+            .mint_allowances
+            .get(&(minter, token_address), working_set)
+            .unwrap_or_default();
+        Some(remaining_supply.map_or(allowance, |remaining| remaining.min(allowance)))
+    }
+
+    /// Returns the maximum total supply of the token stored at `token_address`,
+    /// or `None` if the token doesn't exist or its supply is unbounded.
+    pub fn get_max_supply_of(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        self.tokens
+            .get(token_address, working_set)
+            .and_then(|token| token.max_supply)
+    }
+
     /// Total supply of a token stored at the address `token_address`
     pub fn get_total_supply_of(
         &self,
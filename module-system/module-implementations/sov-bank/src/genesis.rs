@@ -27,11 +27,74 @@ pub struct TokenConfig<C: sov_modules_api::Context> {
     pub authorized_minters: Vec<C::Address>,
     /// A salt used to encrypt the token address.
     pub salt: u64,
+    /// The administrator of the token, allowed to freeze and unfreeze accounts.
+    #[serde(default)]
+    pub authority: Option<C::Address>,
+    /// The maximum total supply of the token. The supply is unbounded if it's missing.
+    #[serde(default)]
+    pub max_supply: Option<u64>,
 }
 
 /// The address of the deployment node. For now, set to [0; 32]
 pub(crate) const DEPLOYER: [u8; 32] = [0; 32];
 
+impl<C: sov_modules_api::Context> BankConfig<C> {
+    /// Checks the configuration without writing any state, and returns all the errors found.
+    /// Every error starts with the field it was found in.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        for (i, token) in self.tokens.iter().enumerate() {
+            if self.tokens[..i]
+                .iter()
+                .any(|other| other.token_name == token.token_name && other.salt == token.salt)
+            {
+                errors.push(format!(
+                    "tokens[{}]: token {} with salt {} is listed twice",
+                    i, token.token_name, token.salt
+                ));
+            }
+            for (j, (address, _)) in token.address_and_balances.iter().enumerate() {
+                if token.address_and_balances[..j]
+                    .iter()
+                    .any(|(other, _)| other == address)
+                {
+                    errors.push(format!(
+                        "tokens[{}].address_and_balances[{}]: address {} is listed twice",
+                        i, j, address
+                    ));
+                }
+            }
+            let total_supply = token
+                .address_and_balances
+                .iter()
+                .try_fold(0u64, |total, (_, balance)| total.checked_add(*balance));
+            if total_supply.is_none() {
+                errors.push(format!(
+                    "tokens[{}].address_and_balances: the total supply overflows",
+                    i
+                ));
+            }
+            if let (Some(total_supply), Some(max_supply)) = (total_supply, token.max_supply) {
+                if total_supply > max_supply {
+                    errors.push(format!(
+                        "tokens[{}].max_supply: the initial supply {} exceeds the maximum supply {}",
+                        i, total_supply, max_supply
+                    ));
+                }
+            }
+            for (j, minter) in token.authorized_minters.iter().enumerate() {
+                if token.authorized_minters[..j].contains(minter) {
+                    errors.push(format!(
+                        "tokens[{}].authorized_minters[{}]: address {} is listed twice",
+                        i, j, minter
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
 impl<C: sov_modules_api::Context> Bank<C> {
     /// Init an instance of the bank module from the configuration `config`.
     /// For each token in the `config`, calls the [`Token::create`] function to create
@@ -47,6 +110,8 @@ impl<C: sov_modules_api::Context> Bank<C> {
                 &token_config.token_name,
                 &token_config.address_and_balances,
                 &token_config.authorized_minters,
+                token_config.authority.clone(),
+                token_config.max_supply,
                 &DEPLOYER,
                 token_config.salt,
                 parent_prefix,
@@ -86,6 +151,8 @@ mod tests {
                 address_and_balances: vec![(address, 100000000)],
                 authorized_minters: vec![address],
                 salt: 0,
+                authority: None,
+                max_supply: None,
             }],
         };
 
@@ -105,4 +172,54 @@ mod tests {
 
         assert_eq!(config, parsed_config)
     }
+
+    #[test]
+    fn test_config_validation() {
+        let address = sov_modules_api::utils::generate_address::<DefaultContext>("address");
+        let token = TokenConfig::<DefaultContext> {
+            token_name: "sov-demo-token".to_owned(),
+            address_and_balances: vec![(address, 100), (address, u64::MAX)],
+            authorized_minters: vec![],
+            salt: 0,
+            authority: None,
+            max_supply: None,
+        };
+        let config = BankConfig {
+            tokens: vec![token.clone(), token],
+        };
+
+        let errors = config.validate();
+        assert_eq!(5, errors.len());
+        assert_eq!(
+            format!(
+                "tokens[0].address_and_balances[1]: address {} is listed twice",
+                address
+            ),
+            errors[0]
+        );
+        assert_eq!(
+            "tokens[0].address_and_balances: the total supply overflows",
+            errors[1]
+        );
+        assert_eq!(
+            "tokens[1]: token sov-demo-token with salt 0 is listed twice",
+            errors[2]
+        );
+
+        let capped_token = TokenConfig::<DefaultContext> {
+            token_name: "sov-capped-token".to_owned(),
+            address_and_balances: vec![(address, 100)],
+            authorized_minters: vec![],
+            salt: 0,
+            authority: None,
+            max_supply: Some(99),
+        };
+        let config = BankConfig {
+            tokens: vec![capped_token],
+        };
+        assert_eq!(
+            vec!["tokens[0].max_supply: the initial supply 100 exceeds the maximum supply 99"],
+            config.validate()
+        );
+    }
 }
@@ -3,7 +3,9 @@ use core::str::FromStr;
 use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::macros::config_constant;
 use sov_modules_api::transaction::Transaction;
-use sov_modules_api::{Context, GasUnit, WorkingSet};
+use sov_modules_api::{
+    Context, EventEmitter, GasUnit, ModuleErrorCode, StateValueAccessor, WorkingSet,
+};
 
 use crate::{Bank, Coins};
 
@@ -21,6 +23,88 @@ use crate::{Bank, Coins};
 // https://github.com/Sovereign-Labs/sovereign-sdk/issues/1234
 const GAS_TOKEN_ADDRESS: &'static str;
 
+impl<C: Context> Bank<C> {
+    /// Returns the address of the token which pays for gas.
+    pub fn get_gas_token_address(&self) -> anyhow::Result<C::Address> {
+        C::Address::from_str(GAS_TOKEN_ADDRESS)
+            .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))
+    }
+
+    /// Returns the address of the account holding the gas fees of the transactions until they
+    /// are distributed. Its funds can only be moved by the tx hooks and [`Bank::distribute_fees`].
+    pub fn fee_escrow_address(&self) -> C::Address {
+        sov_modules_api::derived_account_address::<C>(&self.address, b"gas_fees")
+    }
+
+    /// Returns the gas fees collected since they were last distributed.
+    pub fn get_collected_fees(&self, working_set: &mut WorkingSet<C>) -> CollectedFees {
+        self.collected_fees.get(working_set).unwrap_or_default()
+    }
+
+    /// Distributes the collected gas fees: the tips are rewarded to the `sequencer`, and so is the
+    /// base fee, unless `burn_base_fee` is set, in which case it's burned. Returns the reward.
+    pub fn distribute_fees(
+        &self,
+        sequencer: &C::Address,
+        burn_base_fee: bool,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<u64> {
+        let CollectedFees { base_fee, tips } = self.get_collected_fees(working_set);
+        let token_address = self.get_gas_token_address()?;
+        let escrow = self.fee_escrow_address();
+
+        let reward = if burn_base_fee {
+            if base_fee > 0 {
+                let coins = Coins {
+                    amount: base_fee,
+                    token_address: token_address.clone(),
+                };
+                self.burn(coins, &escrow, working_set)?;
+                working_set.emit_event(self, "burn_base_fee", &format!("{}", base_fee));
+            }
+            tips
+        } else {
+            base_fee.saturating_add(tips)
+        };
+        if reward > 0 {
+            let coins = Coins {
+                amount: reward,
+                token_address,
+            };
+            self.transfer_from(&escrow, sequencer, coins, working_set)?;
+            working_set.emit_event(
+                self,
+                "reward_sequencer",
+                &format!("{} to {}", reward, sequencer),
+            );
+        }
+        self.collected_fees
+            .set(&CollectedFees::default(), working_set);
+
+        Ok(reward)
+    }
+}
+
+/// The gas fees collected from the transactions, held by the fee escrow account.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+pub struct CollectedFees {
+    /// The gas used by the transactions, at the gas price of their batches.
+    pub base_fee: u64,
+    /// The priority tips of the transactions.
+    pub tips: u64,
+}
+
 /// The computed addresses of a pre-dispatch tx hook.
 pub struct BankTxHook<C: Context> {
     /// The tx sender address
@@ -46,8 +130,7 @@ impl<C: Context> TxHooks for Bank<C> {
         let gas_fixed_cost = tx.gas_fixed_cost();
         if working_set.charge_gas(&gas_fixed_cost).is_err() {
             let amount = gas_fixed_cost.value(working_set.gas_price());
-            let token_address = C::Address::from_str(GAS_TOKEN_ADDRESS)
-                .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))?;
+            let token_address = self.get_gas_token_address()?;
             let coins = Coins {
                 amount,
                 token_address,
@@ -61,22 +144,21 @@ impl<C: Context> TxHooks for Bank<C> {
             // critical design flaw in the sequencer registry.
             self.burn(coins, sequencer, working_set).expect("Unrecoverable error: the sequencer doesn't have enough funds to pay for the transaction base cost.");
 
-            anyhow::bail!(
-                "Transaction sender doesn't have enough funds to pay for the transaction base cost"
-            );
+            return Err(ModuleErrorCode::InsufficientFunds.error(
+                "Transaction sender doesn't have enough funds to pay for the transaction base cost",
+            ));
         }
 
+        // The gas limit and the tip are escrowed until the transaction is executed
         let amount = tx.gas_limit().saturating_add(tx.gas_tip());
         if amount > 0 {
-            let token_address = C::Address::from_str(GAS_TOKEN_ADDRESS)
-                .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))?;
-            let from = sender;
-            let to = sequencer;
+            let token_address = self.get_gas_token_address()?;
             let coins = Coins {
                 amount,
                 token_address,
             };
-            self.transfer_from(from, to, coins, working_set)?;
+            self.transfer_from(sender, &self.fee_escrow_address(), coins, working_set)?;
+            working_set.emit_event(self, "reserve_gas", &format!("{} from {}", amount, sender));
         }
 
         Ok(())
@@ -84,24 +166,33 @@ impl<C: Context> TxHooks for Bank<C> {
 
     fn post_dispatch_tx_hook(
         &self,
-        _tx: &Transaction<Self::Context>,
+        tx: &Transaction<Self::Context>,
         ctx: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
-        let amount = working_set.gas_remaining_funds();
+        let refund = working_set.gas_remaining_funds();
+        let base_fee = tx.gas_limit().saturating_sub(refund);
 
-        if amount > 0 {
-            let token_address = C::Address::from_str(GAS_TOKEN_ADDRESS)
-                .map_err(|_| anyhow::anyhow!("failed to parse gas token address"))?;
-            let from = ctx.sequencer();
-            let to = ctx.sender();
+        if refund > 0 {
+            let token_address = self.get_gas_token_address()?;
             let coins = Coins {
-                amount,
+                amount: refund,
                 token_address,
             };
-            self.transfer_from(from, to, coins, working_set)?;
+            self.transfer_from(&self.fee_escrow_address(), ctx.sender(), coins, working_set)?;
+            working_set.emit_event(
+                self,
+                "refund_gas",
+                &format!("{} to {}", refund, ctx.sender()),
+            );
         }
 
+        // The used gas and the tip stay in the escrow until they are distributed
+        let mut collected_fees = self.get_collected_fees(working_set);
+        collected_fees.base_fee = collected_fees.base_fee.saturating_add(base_fee);
+        collected_fees.tips = collected_fees.tips.saturating_add(tx.gas_tip());
+        self.collected_fees.set(&collected_fees, working_set);
+
         Ok(())
     }
 }
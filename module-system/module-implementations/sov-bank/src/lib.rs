@@ -10,15 +10,17 @@ pub use query::*;
 mod token;
 /// Util functions for bank
 pub mod utils;
+mod vesting;
 pub use call::*;
 pub use genesis::*;
-pub use hooks::BankTxHook;
+pub use hooks::{BankTxHook, CollectedFees};
 use sov_modules_api::{CallResponse, Error, GasUnit, ModuleInfo, WorkingSet};
 use token::Token;
 /// Specifies an interface to interact with tokens.
 pub use token::{Amount, Coins};
 /// Methods to get a token address.
 pub use utils::{get_genesis_token_address, get_token_address};
+pub use vesting::{VestingSchedule, MAX_VESTING_SCHEDULES};
 
 /// Gas configuration for the bank module
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -37,12 +39,43 @@ pub struct BankGasConfig<GU: GasUnit> {
 
     /// Gas price multiplier for the freeze operation
     pub freeze: GU,
+
+    /// Gas price multiplier for the approve operation
+    pub approve: GU,
+
+    /// Gas price multiplier for the transfer from operation
+    pub transfer_from: GU,
+
+    /// Gas price multiplier for the revoke approval operation
+    pub revoke_approval: GU,
+
+    /// Gas price multiplier for the freeze account operation
+    pub freeze_account: GU,
+
+    /// Gas price multiplier for the unfreeze account operation
+    pub unfreeze_account: GU,
+
+    /// Gas price multiplier for the set authority operation
+    pub set_authority: GU,
+
+    /// Gas price multiplier for the transfer with lock operation
+    pub transfer_with_lock: GU,
+
+    /// Gas price multiplier for the claim vested operation
+    pub claim_vested: GU,
+
+    /// Gas price multiplier for the set mint allowance operation
+    pub set_mint_allowance: GU,
 }
 
 /// The sov-bank module manages user balances. It provides functionality for:
 /// - Token creation.
 /// - Token transfers.
 /// - Token burn.
+/// - Token allowances, letting an address spend tokens on behalf of their owner.
+/// - Token administration, letting the authority of a token freeze its accounts.
+/// - Vesting, locking transferred tokens until they unlock at a cliff or linearly over a number of slots.
+/// - Supply caps, and mint allowances letting the minters of a token delegate bounded minting to other addresses.
 #[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
 #[derive(ModuleInfo, Clone)]
 pub struct Bank<C: sov_modules_api::Context> {
@@ -57,6 +90,30 @@ pub struct Bank<C: sov_modules_api::Context> {
     /// A mapping of addresses to tokens in the sov-bank.
     #[state]
     pub(crate) tokens: sov_modules_api::StateMap<C::Address, Token<C>>,
+
+    /// A mapping of (owner, spender, token address) to the amount of tokens the spender can transfer on behalf of the owner.
+    #[state]
+    pub(crate) allowances: sov_modules_api::StateMap<(C::Address, C::Address, C::Address), Amount>,
+
+    /// A mapping of (account, token address) to whether the account was frozen by the authority of the token.
+    #[state]
+    pub(crate) frozen_accounts: sov_modules_api::StateMap<(C::Address, C::Address), bool>,
+
+    /// A mapping of (minter, token address) to the amount of tokens the minter can mint without being
+    /// an authorized minter of the token.
+    #[state]
+    pub(crate) mint_allowances: sov_modules_api::StateMap<(C::Address, C::Address), Amount>,
+
+    /// A mapping of (recipient, token address) to the unclaimed vesting schedules of the recipient.
+    /// The locked tokens are held by the sov-bank module until they are claimed.
+    #[state]
+    pub(crate) vesting_schedules:
+        sov_modules_api::StateMap<(C::Address, C::Address), Vec<VestingSchedule>>,
+
+    /// The gas fees collected from the transactions since they were last distributed.
+    /// The collected tokens are held by the fee escrow account, see [`Bank::fee_escrow_address`].
+    #[state]
+    pub(crate) collected_fees: sov_modules_api::StateValue<CollectedFees>,
 }
 
 impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
@@ -85,6 +142,8 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                 initial_balance,
                 minter_address,
                 authorized_minters,
+                authority,
+                max_supply,
             } => {
                 self.charge_gas(working_set, &self.gas.create_token)?;
                 self.create_token(
@@ -93,6 +152,8 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                     initial_balance,
                     minter_address,
                     authorized_minters,
+                    authority,
+                    max_supply,
                     context,
                     working_set,
                 )?;
@@ -100,7 +161,7 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
             }
 
             call::CallMessage::Transfer { to, coins } => {
-                self.charge_gas(working_set, &self.gas.create_token)?;
+                self.charge_gas(working_set, &self.gas.transfer)?;
                 Ok(self.transfer(to, coins, context, working_set)?)
             }
 
@@ -122,6 +183,75 @@ impl<C: sov_modules_api::Context> sov_modules_api::Module for Bank<C> {
                 self.charge_gas(working_set, &self.gas.freeze)?;
                 Ok(self.freeze(token_address, context, working_set)?)
             }
+
+            call::CallMessage::Approve { spender, coins } => {
+                self.charge_gas(working_set, &self.gas.approve)?;
+                Ok(self.approve(spender, coins, context, working_set)?)
+            }
+
+            call::CallMessage::TransferFrom { owner, to, coins } => {
+                self.charge_gas(working_set, &self.gas.transfer_from)?;
+                Ok(self.transfer_approved(owner, to, coins, context, working_set)?)
+            }
+
+            call::CallMessage::RevokeApproval {
+                spender,
+                token_address,
+            } => {
+                self.charge_gas(working_set, &self.gas.revoke_approval)?;
+                Ok(self.revoke_approval(spender, token_address, context, working_set)?)
+            }
+
+            call::CallMessage::FreezeAccount {
+                token_address,
+                account,
+            } => {
+                self.charge_gas(working_set, &self.gas.freeze_account)?;
+                Ok(self.set_account_frozen(token_address, account, true, context, working_set)?)
+            }
+
+            call::CallMessage::UnfreezeAccount {
+                token_address,
+                account,
+            } => {
+                self.charge_gas(working_set, &self.gas.unfreeze_account)?;
+                Ok(self.set_account_frozen(token_address, account, false, context, working_set)?)
+            }
+
+            call::CallMessage::SetAuthority {
+                token_address,
+                authority,
+            } => {
+                self.charge_gas(working_set, &self.gas.set_authority)?;
+                Ok(self.set_authority(token_address, authority, context, working_set)?)
+            }
+
+            call::CallMessage::TransferWithLock {
+                to,
+                coins,
+                cliff_slots,
+                vesting_slots,
+            } => {
+                self.charge_gas(working_set, &self.gas.transfer_with_lock)?;
+                Ok(self.transfer_with_lock(
+                    to,
+                    coins,
+                    cliff_slots,
+                    vesting_slots,
+                    context,
+                    working_set,
+                )?)
+            }
+
+            call::CallMessage::ClaimVested { token_address } => {
+                self.charge_gas(working_set, &self.gas.claim_vested)?;
+                Ok(self.claim_vested(token_address, context, working_set)?)
+            }
+
+            call::CallMessage::SetMintAllowance { minter, coins } => {
+                self.charge_gas(working_set, &self.gas.set_mint_allowance)?;
+                Ok(self.set_mint_allowance(minter, coins, context, working_set)?)
+            }
         }
     }
 }
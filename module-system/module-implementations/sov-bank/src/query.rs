@@ -1,9 +1,11 @@
 //! Defines rpc queries exposed by the bank module, along with the relevant types
 use jsonrpsee::core::RpcResult;
 use sov_modules_api::macros::rpc_gen;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::utils::set_rpc_archival_version;
+use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_state::storage::NativeStorage;
 
-use crate::{Amount, Bank};
+use crate::{Amount, Bank, VestingSchedule};
 
 /// Structure returned by the `balance_of` rpc method.
 #[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
@@ -19,6 +21,42 @@ pub struct TotalSupplyResponse {
     pub amount: Option<Amount>,
 }
 
+/// Structure returned by the `allowance` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct AllowanceResponse {
+    /// The amount of tokens the spender can transfer on behalf of the owner. Equivalent to u64.
+    pub amount: Option<Amount>,
+}
+
+/// Structure returned by the `mintableAmount` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct MintableAmountResponse {
+    /// The amount of tokens the minter can still mint, or `None` if it's unbounded. Equivalent to u64.
+    pub amount: Option<Amount>,
+    /// The maximum total supply of the token, or `None` if it's unbounded. Equivalent to u64.
+    pub max_supply: Option<Amount>,
+}
+
+/// Structure returned by the `accountFrozen` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct AccountFrozenResponse {
+    /// Whether the account was frozen by the authority of the token.
+    pub frozen: bool,
+}
+
+/// Structure returned by the `vestingBalance` rpc method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct VestingBalanceResponse {
+    /// The balance the account can spend, without its unclaimed vested tokens. Equivalent to u64.
+    pub liquid: Amount,
+    /// The amount of vested tokens the account can claim. Equivalent to u64.
+    pub claimable: Amount,
+    /// The amount of tokens which are still locked. Equivalent to u64.
+    pub locked: Amount,
+    /// The unclaimed vesting schedules of the account.
+    pub schedules: Vec<VestingSchedule>,
+}
+
 #[rpc_gen(client, server, namespace = "bank")]
 impl<C: sov_modules_api::Context> Bank<C> {
     #[rpc_method(name = "balanceOf")]
@@ -31,9 +69,7 @@ impl<C: sov_modules_api::Context> Bank<C> {
         token_address: C::Address,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<BalanceResponse> {
-        if let Some(v) = version {
-            working_set.set_archival_version(v)
-        }
+        set_rpc_archival_version(version, working_set)?;
         Ok(BalanceResponse {
             amount: self.get_balance_of(user_address, token_address, working_set),
         })
@@ -47,11 +83,116 @@ impl<C: sov_modules_api::Context> Bank<C> {
         token_address: C::Address,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<TotalSupplyResponse> {
-        if let Some(v) = version {
-            working_set.set_archival_version(v)
-        }
+        set_rpc_archival_version(version, working_set)?;
         Ok(TotalSupplyResponse {
             amount: self.get_total_supply_of(&token_address, working_set),
         })
     }
+
+    #[rpc_method(name = "allowance")]
+    /// Rpc method that returns the amount of tokens stored at the address `token_address`
+    /// that the `spender` can transfer on behalf of the `owner`.
+    pub fn allowance(
+        &self,
+        version: Option<u64>,
+        owner: C::Address,
+        spender: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AllowanceResponse> {
+        set_rpc_archival_version(version, working_set)?;
+        Ok(AllowanceResponse {
+            amount: self.get_allowance(owner, spender, token_address, working_set),
+        })
+    }
+
+    #[rpc_method(name = "mintableAmount")]
+    /// Rpc method that returns the amount of tokens stored at the address `token_address`
+    /// that the `minter` can still mint, with its mint allowance or as an authorized minter,
+    /// along with the maximum supply of the token.
+    pub fn mintable_amount(
+        &self,
+        version: Option<u64>,
+        minter: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<MintableAmountResponse> {
+        set_rpc_archival_version(version, working_set)?;
+        Ok(MintableAmountResponse {
+            amount: self.get_mintable_amount(minter, token_address.clone(), working_set),
+            max_supply: self.get_max_supply_of(&token_address, working_set),
+        })
+    }
+
+    #[rpc_method(name = "accountFrozen")]
+    /// Rpc method that returns whether the account at the address `account` is frozen for the token
+    /// stored at the address `token_address`.
+    pub fn account_frozen(
+        &self,
+        version: Option<u64>,
+        account: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AccountFrozenResponse> {
+        set_rpc_archival_version(version, working_set)?;
+        Ok(AccountFrozenResponse {
+            frozen: self.is_account_frozen(account, token_address, working_set),
+        })
+    }
+
+    #[rpc_method(name = "vestingBalance")]
+    /// Rpc method that returns the liquid balance of the account at the address `account` for the
+    /// token stored at the address `token_address`, and how many of its vesting tokens can be claimed
+    /// or are still locked at the slot height `slot_height`.
+    pub fn vesting_balance(
+        &self,
+        version: Option<u64>,
+        account: C::Address,
+        token_address: C::Address,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<VestingBalanceResponse> {
+        set_rpc_archival_version(version, working_set)?;
+        let schedules =
+            self.get_vesting_schedules(account.clone(), token_address.clone(), working_set);
+        Ok(VestingBalanceResponse {
+            liquid: self
+                .get_balance_of(account, token_address, working_set)
+                .unwrap_or_default(),
+            claimable: schedules
+                .iter()
+                .map(|schedule| schedule.claimable_at(slot_height))
+                .sum(),
+            locked: schedules
+                .iter()
+                .map(|schedule| schedule.locked_at(slot_height))
+                .sum(),
+            schedules,
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Bank<C>
+where
+    C::Storage: NativeStorage,
+{
+    /// Returns the holders of the token stored at `token_address` with their balances, ordered by address,
+    /// or `None` if the token doesn't exist. The accounts which spent all their tokens are included.
+    ///
+    /// At most `limit` holders are returned, starting from the address `start` if it's provided,
+    /// so the holders can be listed page by page.
+    pub fn get_token_holders(
+        &self,
+        token_address: C::Address,
+        start: Option<C::Address>,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Vec<(C::Address, Amount)>> {
+        let token = self.tokens.get(&token_address, working_set)?;
+        Some(
+            token
+                .balances
+                .iter_range(start.as_ref(), None, limit, working_set),
+        )
+    }
 }
@@ -7,7 +7,7 @@ use std::num::ParseIntError;
 
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
-use sov_modules_api::{StateMapAccessor, WorkingSet};
+use sov_modules_api::{ModuleErrorCode, StateMapAccessor, WorkingSet};
 use sov_state::Prefix;
 #[cfg(feature = "native")]
 use thiserror::Error;
@@ -120,6 +120,14 @@ pub(crate) struct Token<C: sov_modules_api::Context> {
     /// Freezing a token requires emptying the vector
     /// NOTE: This is explicit so if a creator doesn't add themselves, then they can't mint
     pub(crate) authorized_minters: Vec<C::Address>,
+
+    /// The administrator of the token, allowed to freeze and unfreeze accounts.
+    /// `None` indicates that the token has no administrator, so its accounts can't be frozen.
+    pub(crate) authority: Option<C::Address>,
+
+    /// The maximum total supply of the token, enforced when tokens are minted.
+    /// `None` indicates that the supply is unbounded.
+    pub(crate) max_supply: Option<Amount>,
 }
 
 impl<C: sov_modules_api::Context> Token<C> {
@@ -161,6 +169,21 @@ impl<C: sov_modules_api::Context> Token<C> {
         Ok(())
     }
 
+    /// Checks that the token can still be minted, i.e. that it isn't frozen.
+    pub(crate) fn ensure_mintable(&self) -> Result<()> {
+        if self.authorized_minters.is_empty() {
+            bail!("Attempt to mint frozen token {}", self.name)
+        }
+        Ok(())
+    }
+
+    /// Returns the amount of tokens which can still be minted before the maximum supply is reached,
+    /// or `None` if the supply is unbounded.
+    pub(crate) fn remaining_supply(&self) -> Option<Amount> {
+        self.max_supply
+            .map(|max_supply| max_supply.saturating_sub(self.total_supply))
+    }
+
     /// Freezing a token requires emptying the authorized_minter vector
     /// authorized_minter: Vec<Address> is used to determine if the token is frozen or not
     /// If the vector is empty when the function is called, this means the token is already frozen
@@ -173,22 +196,27 @@ impl<C: sov_modules_api::Context> Token<C> {
         Ok(())
     }
 
-    /// Mints a given `amount` of token sent by `sender` to the specified `mint_to_address`.
-    /// Checks that the `authorized_minters` set is not empty for the token and that the `sender`
-    /// is an `authorized_minter`. If so, update the balances of token for the `mint_to_address` by
+    /// Mints a given `amount` of token to the specified `mint_to_address`. The caller is responsible
+    /// for checking that the minter is allowed to mint that amount.
+    /// Checks that the `authorized_minters` set is not empty for the token and that the maximum supply
+    /// isn't exceeded. If so, update the balances of token for the `mint_to_address` by
     /// adding the minted tokens. Updates the `total_supply` of that token.
     pub(crate) fn mint(
         &mut self,
-        authorizer: &C::Address,
         mint_to_address: &C::Address,
         amount: Amount,
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
-        if self.authorized_minters.is_empty() {
-            bail!("Attempt to mint frozen token {}", self.name)
+        self.ensure_mintable()?;
+        if let Some(remaining_supply) = self.remaining_supply() {
+            if amount > remaining_supply {
+                return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                    "Minting {} tokens of token {} exceeds its maximum supply, only {} can be minted",
+                    amount, self.name, remaining_supply
+                )));
+            }
         }
 
-        self.is_authorized_minter(authorizer)?;
         let to_balance: Amount = self
             .balances
             .get(mint_to_address, working_set)
@@ -208,13 +236,26 @@ impl<C: sov_modules_api::Context> Token<C> {
         Ok(())
     }
 
-    fn is_authorized_minter(&self, sender: &C::Address) -> Result<()> {
+    /// Checks that `sender` is the administrator of the token.
+    pub(crate) fn is_authority(&self, sender: &C::Address) -> Result<()> {
+        match &self.authority {
+            Some(authority) if authority == sender => Ok(()),
+            Some(_) => Err(ModuleErrorCode::Unauthorized.error(format!(
+                "Sender {} is not the authority of token {}",
+                sender, self.name
+            ))),
+            None => Err(ModuleErrorCode::Unauthorized
+                .error(format!("Token {} has no authority", self.name))),
+        }
+    }
+
+    /// Checks that `sender` is one of the authorized minters of the token.
+    pub(crate) fn is_authorized_minter(&self, sender: &C::Address) -> Result<()> {
         if !self.authorized_minters.contains(sender) {
-            bail!(
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
                 "Sender {} is not an authorized minter of token {}",
-                sender,
-                self.name
-            )
+                sender, self.name
+            )));
         }
         Ok(())
     }
@@ -227,10 +268,16 @@ impl<C: sov_modules_api::Context> Token<C> {
         amount: Amount,
         working_set: &mut WorkingSet<C>,
     ) -> Result<Amount> {
-        let balance = self.balances.get_or_err(from, working_set)?;
+        let balance = self
+            .balances
+            .get_or_err(from, working_set)
+            .map_err(|e| ModuleErrorCode::InsufficientFunds.error(e))?;
         let new_balance = match balance.checked_sub(amount) {
             Some(from_balance) => from_balance,
-            None => bail!("Insufficient funds for {}", from),
+            None => {
+                return Err(ModuleErrorCode::InsufficientFunds
+                    .error(format!("Insufficient funds for {}", from)))
+            }
         };
         Ok(new_balance)
     }
@@ -239,11 +286,16 @@ impl<C: sov_modules_api::Context> Token<C> {
     /// The `token_name`, `sender` address (as a `u8` slice), and the `salt` (`u64` number) are used as an input
     /// to an hash function that computes the token address. Then the initial accounts and balances are populated
     /// from the `address_and_balances` slice and the `total_supply` of tokens is updated each time.
+    /// The optional `authority` becomes the administrator of the token, and the initial supply can't
+    /// exceed the optional `max_supply`.
     /// Returns a tuple containing the computed `token_address` and the created `token` object.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn create(
         token_name: &str,
         address_and_balances: &[(C::Address, u64)],
         authorized_minters: &[C::Address],
+        authority: Option<C::Address>,
+        max_supply: Option<Amount>,
         sender: &[u8],
         salt: u64,
         parent_prefix: &Prefix,
@@ -263,6 +315,16 @@ impl<C: sov_modules_api::Context> Token<C> {
             Some(total_supply) => total_supply,
             None => bail!("Total supply overflow"),
         };
+        if let Some(max_supply) = max_supply {
+            if total_supply > max_supply {
+                bail!(
+                    "The initial supply {} of token {} exceeds its maximum supply {}",
+                    total_supply,
+                    token_name,
+                    max_supply
+                );
+            }
+        }
 
         let mut indices = HashSet::new();
         let mut auth_minter_list = Vec::new();
@@ -278,6 +340,8 @@ impl<C: sov_modules_api::Context> Token<C> {
             total_supply,
             balances,
             authorized_minters: auth_minter_list,
+            authority,
+            max_supply,
         };
 
         Ok((token_address, token))
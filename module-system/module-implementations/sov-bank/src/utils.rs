@@ -27,9 +27,9 @@ pub fn get_genesis_token_address<C: sov_modules_api::Context>(
 
 #[cfg(feature = "test-utils")]
 mod tests {
-    use sov_modules_api::Context;
+    use sov_modules_api::{Context, WorkingSet};
 
-    use crate::{Bank, BankGasConfig};
+    use crate::{Bank, BankConfig, BankGasConfig, TokenConfig};
 
     impl<C: Context> Bank<C> {
         /// Returns the underlying gas config
@@ -41,5 +41,43 @@ mod tests {
         pub fn override_gas_config(&mut self, gas: BankGasConfig<C::GasUnit>) {
             self.gas = gas;
         }
+
+        /// Returns the balance of `user_address`, which is zero if it never held the token.
+        pub fn balance_or_zero(
+            &self,
+            user_address: C::Address,
+            token_address: C::Address,
+            working_set: &mut WorkingSet<C>,
+        ) -> u64 {
+            self.get_balance_of(user_address, token_address, working_set)
+                .unwrap_or_default()
+        }
+    }
+
+    impl<C: Context> TokenConfig<C> {
+        /// Creates the config of a token with a salt of 0, which can't be minted after genesis
+        /// and has no authority. Its address is `get_genesis_token_address(token_name, 0)`.
+        pub fn fixed_supply(
+            token_name: &str,
+            address_and_balances: Vec<(C::Address, u64)>,
+        ) -> Self {
+            Self {
+                token_name: token_name.to_owned(),
+                address_and_balances,
+                authorized_minters: vec![],
+                salt: 0,
+                authority: None,
+                max_supply: None,
+            }
+        }
+    }
+
+    impl<C: Context> BankConfig<C> {
+        /// Creates the config of a bank with the single token [`TokenConfig::fixed_supply`].
+        pub fn with_token(token_name: &str, address_and_balances: Vec<(C::Address, u64)>) -> Self {
+            Self {
+                tokens: vec![TokenConfig::fixed_supply(token_name, address_and_balances)],
+            }
+        }
     }
 }
@@ -0,0 +1,202 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{CallResponse, ModuleErrorCode, StateMapAccessor, WorkingSet};
+
+use crate::{Amount, Bank, Coins};
+
+/// The maximum number of unclaimed vesting schedules an account can have for a token,
+/// which bounds the cost of claiming them.
+pub const MAX_VESTING_SCHEDULES: usize = 32;
+
+/// Tokens locked by a [`CallMessage::TransferWithLock`](crate::CallMessage::TransferWithLock),
+/// which the recipient can claim as they unlock.
+///
+/// Nothing unlocks before `cliff_height`. From then on the tokens unlock linearly, from
+/// `start_height` to `end_height`, so the part accrued since `start_height` unlocks at once
+/// at the cliff. A schedule whose cliff is its end unlocks everything at the cliff.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+pub struct VestingSchedule {
+    /// The amount of locked tokens.
+    pub amount: Amount,
+    /// The amount of tokens already claimed by the recipient.
+    pub claimed: Amount,
+    /// The slot height at which the tokens were locked.
+    pub start_height: u64,
+    /// The slot height before which no token unlocks.
+    pub cliff_height: u64,
+    /// The slot height at which all the tokens are unlocked.
+    pub end_height: u64,
+}
+
+impl VestingSchedule {
+    /// The amount of tokens unlocked at `slot_height`, including the claimed ones.
+    pub fn vested_at(&self, slot_height: u64) -> Amount {
+        if slot_height < self.cliff_height {
+            return 0;
+        }
+        if slot_height >= self.end_height {
+            return self.amount;
+        }
+        let elapsed = (slot_height - self.start_height) as u128;
+        let duration = (self.end_height - self.start_height) as u128;
+        (self.amount as u128 * elapsed / duration) as Amount
+    }
+
+    /// The amount of tokens unlocked at `slot_height` which weren't claimed yet.
+    pub fn claimable_at(&self, slot_height: u64) -> Amount {
+        self.vested_at(slot_height) - self.claimed
+    }
+
+    /// The amount of tokens still locked at `slot_height`.
+    pub fn locked_at(&self, slot_height: u64) -> Amount {
+        self.amount - self.vested_at(slot_height)
+    }
+}
+
+fn validate_lock(amount: Amount, cliff_slots: u64, vesting_slots: u64) -> Result<()> {
+    if amount == 0 {
+        bail!("The amount of locked coins can't be zero");
+    }
+    if vesting_slots == 0 {
+        bail!("The vesting period can't be zero");
+    }
+    if cliff_slots > vesting_slots {
+        bail!(
+            "The cliff after {} slots is past the end of the vesting period of {} slots",
+            cliff_slots,
+            vesting_slots
+        );
+    }
+    Ok(())
+}
+
+impl<C: sov_modules_api::Context> Bank<C> {
+    /// Moves the `coins` of `context.sender()` to the bank, to be claimed by `to` following a
+    /// [`VestingSchedule`] starting at the current slot, with a cliff after `cliff_slots` slots
+    /// and fully unlocked after `vesting_slots` slots.
+    pub(crate) fn transfer_with_lock(
+        &self,
+        to: C::Address,
+        coins: Coins<C>,
+        cliff_slots: u64,
+        vesting_slots: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let context_logger = || {
+            format!(
+                "Failed to transfer coins({}) locked for {} slots from {} to {}",
+                coins,
+                vesting_slots,
+                context.sender(),
+                to
+            )
+        };
+        validate_lock(coins.amount, cliff_slots, vesting_slots).with_context(context_logger)?;
+
+        let schedule_key = (to.clone(), coins.token_address.clone());
+        let mut schedules = self
+            .vesting_schedules
+            .get(&schedule_key, working_set)
+            .unwrap_or_default();
+        if schedules.len() >= MAX_VESTING_SCHEDULES {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Account {} already has {} unclaimed vesting schedules for token_address={}",
+                schedule_key.0, MAX_VESTING_SCHEDULES, schedule_key.1
+            )))
+            .with_context(context_logger);
+        }
+
+        let start_height = context.slot_height();
+        let end_height = start_height
+            .checked_add(vesting_slots)
+            .context("The vesting period is too long")
+            .with_context(context_logger)?;
+        self.transfer_from(context.sender(), &self.address, coins.clone(), working_set)
+            .with_context(context_logger)?;
+
+        schedules.push(VestingSchedule {
+            amount: coins.amount,
+            claimed: 0,
+            start_height,
+            cliff_height: start_height + cliff_slots,
+            end_height,
+        });
+        self.vesting_schedules
+            .set(&schedule_key, &schedules, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    /// Transfers all the tokens at `token_address` unlocked by the vesting schedules of
+    /// `context.sender()` to their recipient. Returns an error if there is nothing to claim.
+    pub(crate) fn claim_vested(
+        &self,
+        token_address: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let recipient = context.sender();
+        let slot_height = context.slot_height();
+        let schedule_key = (recipient.clone(), token_address.clone());
+        let mut schedules = self
+            .vesting_schedules
+            .get(&schedule_key, working_set)
+            .unwrap_or_default();
+
+        let mut claimed = 0;
+        for schedule in schedules.iter_mut() {
+            let claimable = schedule.claimable_at(slot_height);
+            schedule.claimed += claimable;
+            claimed += claimable;
+        }
+        if claimed == 0 {
+            return Err(ModuleErrorCode::NotFound.error(format!(
+                "Account {} has no vested tokens of token_address={} to claim at slot {}",
+                recipient, token_address, slot_height
+            )));
+        }
+
+        self.transfer_from(
+            &self.address,
+            recipient,
+            Coins {
+                amount: claimed,
+                token_address,
+            },
+            working_set,
+        )
+        .with_context(|| format!("Failed to claim vested tokens of {}", recipient))?;
+
+        schedules.retain(|schedule| schedule.claimed < schedule.amount);
+        if schedules.is_empty() {
+            self.vesting_schedules.delete(&schedule_key, working_set);
+        } else {
+            self.vesting_schedules
+                .set(&schedule_key, &schedules, working_set);
+        }
+
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the unclaimed vesting schedules of `account` for the token at `token_address`.
+    pub fn get_vesting_schedules(
+        &self,
+        account: C::Address,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<VestingSchedule> {
+        self.vesting_schedules
+            .get(&(account, token_address), working_set)
+            .unwrap_or_default()
+    }
+}
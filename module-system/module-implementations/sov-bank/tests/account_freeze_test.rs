@@ -0,0 +1,246 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, AccountFrozenResponse, Bank, CallMessage, Coins};
+use sov_modules_api::{Address, Context, Error, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn freeze_and_unfreeze_account() {
+    let initial_balance = 100;
+    let mut bank_config = create_bank_config_with_token(4, initial_balance);
+    let authority_address = generate_address("authority");
+    bank_config.tokens[0].authority = Some(authority_address);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let holder_address = bank_config.tokens[0].address_and_balances[0].0;
+    let receiver_address = bank_config.tokens[0].address_and_balances[1].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[3].0;
+
+    let authority_context = C::new(authority_address, sequencer_address, 1);
+    let holder_context = C::new(holder_address, sequencer_address, 1);
+    let receiver_context = C::new(receiver_address, sequencer_address, 1);
+
+    let query_frozen = |account: Address, working_set: &mut WorkingSet<C>| -> bool {
+        let response: AccountFrozenResponse = bank
+            .account_frozen(None, account, token_address, working_set)
+            .unwrap();
+        response.frozen
+    };
+    let transfer_message = |to| CallMessage::Transfer {
+        to,
+        coins: Coins {
+            amount: 10,
+            token_address,
+        },
+    };
+
+    // Only the authority can freeze an account
+    let freeze_message = CallMessage::FreezeAccount {
+        token_address,
+        account: holder_address,
+    };
+    let result = bank.call(freeze_message.clone(), &receiver_context, &mut working_set);
+    let Error::Unauthorized(err) = result.unwrap_err() else {
+        panic!("Expected an unauthorized error");
+    };
+    let mut chain = err.chain();
+    assert_eq!(
+        format!(
+            "Failed to freeze account {} of token_address={} by sender {}",
+            holder_address, token_address, receiver_address
+        ),
+        chain.next().unwrap().to_string()
+    );
+    assert_eq!(
+        format!(
+            "Sender {} is not the authority of token {}",
+            receiver_address, bank_config.tokens[0].token_name
+        ),
+        chain.next().unwrap().to_string()
+    );
+    assert!(chain.next().is_none());
+    assert!(!query_frozen(holder_address, &mut working_set));
+
+    bank.call(freeze_message, &authority_context, &mut working_set)
+        .expect("FreezeAccount call failed");
+    assert!(query_frozen(holder_address, &mut working_set));
+    assert!(!query_frozen(receiver_address, &mut working_set));
+
+    // A frozen account can't send tokens
+    let result = bank.call(
+        transfer_message(receiver_address),
+        &holder_context,
+        &mut working_set,
+    );
+    let Error::Unauthorized(err) = result.unwrap_err() else {
+        panic!("Expected an unauthorized error");
+    };
+    let mut chain = err.chain();
+    assert_eq!(
+        format!(
+            "Failed transfer from={} to={} of coins(token_address={} amount={})",
+            holder_address, receiver_address, token_address, 10
+        ),
+        chain.next().unwrap().to_string()
+    );
+    assert_eq!(
+        format!(
+            "Account {} is frozen for token_address={}",
+            holder_address, token_address
+        ),
+        chain.next().unwrap().to_string()
+    );
+    assert!(chain.next().is_none());
+
+    // A frozen account can't receive tokens
+    assert!(bank
+        .call(
+            transfer_message(holder_address),
+            &receiver_context,
+            &mut working_set
+        )
+        .is_err());
+
+    // A frozen account can't burn tokens
+    let burn_message = CallMessage::Burn {
+        coins: Coins {
+            amount: 10,
+            token_address,
+        },
+    };
+    assert!(bank
+        .call(burn_message.clone(), &holder_context, &mut working_set)
+        .is_err());
+
+    assert_eq!(
+        Some(initial_balance),
+        bank.get_balance_of(holder_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance),
+        bank.get_balance_of(receiver_address, token_address, &mut working_set)
+    );
+
+    // Once unfrozen, the account can use its tokens again
+    let unfreeze_message = CallMessage::UnfreezeAccount {
+        token_address,
+        account: holder_address,
+    };
+    bank.call(unfreeze_message, &authority_context, &mut working_set)
+        .expect("UnfreezeAccount call failed");
+    assert!(!query_frozen(holder_address, &mut working_set));
+
+    bank.call(
+        transfer_message(receiver_address),
+        &holder_context,
+        &mut working_set,
+    )
+    .expect("Transfer call failed");
+    bank.call(burn_message, &holder_context, &mut working_set)
+        .expect("Burn call failed");
+    assert_eq!(
+        Some(initial_balance - 20),
+        bank.get_balance_of(holder_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 10),
+        bank.get_balance_of(receiver_address, token_address, &mut working_set)
+    );
+}
+
+#[test]
+fn set_token_authority() {
+    let mut bank_config = create_bank_config_with_token(2, 100);
+    let authority_address = generate_address("authority");
+    let new_authority_address = generate_address("new_authority");
+    bank_config.tokens[0].authority = Some(authority_address);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let holder_address = bank_config.tokens[0].address_and_balances[0].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[1].0;
+
+    let authority_context = C::new(authority_address, sequencer_address, 1);
+    let new_authority_context = C::new(new_authority_address, sequencer_address, 1);
+
+    let freeze_message = CallMessage::FreezeAccount {
+        token_address,
+        account: holder_address,
+    };
+    let set_authority_message = |authority| CallMessage::SetAuthority {
+        token_address,
+        authority,
+    };
+
+    // Only the current authority can replace itself
+    assert!(bank
+        .call(
+            set_authority_message(Some(new_authority_address)),
+            &new_authority_context,
+            &mut working_set
+        )
+        .is_err());
+
+    bank.call(
+        set_authority_message(Some(new_authority_address)),
+        &authority_context,
+        &mut working_set,
+    )
+    .expect("SetAuthority call failed");
+    assert_eq!(
+        Some(new_authority_address),
+        bank.get_token_authority(&token_address, &mut working_set)
+    );
+
+    // The previous authority can't freeze accounts anymore
+    assert!(bank
+        .call(freeze_message.clone(), &authority_context, &mut working_set)
+        .is_err());
+    bank.call(
+        freeze_message.clone(),
+        &new_authority_context,
+        &mut working_set,
+    )
+    .expect("FreezeAccount call failed");
+    assert!(bank.is_account_frozen(holder_address, token_address, &mut working_set));
+
+    // Once the authority is given up, nobody can administrate the token
+    bank.call(
+        set_authority_message(None),
+        &new_authority_context,
+        &mut working_set,
+    )
+    .expect("SetAuthority call failed");
+    assert_eq!(
+        None,
+        bank.get_token_authority(&token_address, &mut working_set)
+    );
+
+    let result = bank.call(freeze_message, &new_authority_context, &mut working_set);
+    let Error::Unauthorized(err) = result.unwrap_err() else {
+        panic!("Expected an unauthorized error");
+    };
+    let mut chain = err.chain();
+    chain.next().unwrap();
+    assert_eq!(
+        format!(
+            "Token {} has no authority",
+            bank_config.tokens[0].token_name
+        ),
+        chain.next().unwrap().to_string()
+    );
+}
@@ -0,0 +1,139 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, AllowanceResponse, Bank, CallMessage, Coins};
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+#[test]
+fn transfer_from_approved_allowance() {
+    let initial_balance = 100;
+    let bank_config = create_bank_config_with_token(4, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0;
+    let spender_address = bank_config.tokens[0].address_and_balances[1].0;
+    let receiver_address = bank_config.tokens[0].address_and_balances[2].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[3].0;
+
+    let owner_context = C::new(owner_address, sequencer_address, 1);
+    let spender_context = C::new(spender_address, sequencer_address, 1);
+
+    let query_allowance = |working_set: &mut WorkingSet<C>| -> Option<u64> {
+        let allowance: AllowanceResponse = bank
+            .allowance(
+                None,
+                owner_address,
+                spender_address,
+                token_address,
+                working_set,
+            )
+            .unwrap();
+        allowance.amount
+    };
+    let transfer_from_message = |amount| CallMessage::TransferFrom {
+        owner: owner_address,
+        to: receiver_address,
+        coins: Coins {
+            amount,
+            token_address,
+        },
+    };
+
+    // Nothing can be spent before the approval
+    assert_eq!(None, query_allowance(&mut working_set));
+    assert!(bank
+        .call(
+            transfer_from_message(10),
+            &spender_context,
+            &mut working_set
+        )
+        .is_err());
+
+    let approve_message = CallMessage::Approve {
+        spender: spender_address,
+        coins: Coins {
+            amount: 30,
+            token_address,
+        },
+    };
+    bank.call(approve_message, &owner_context, &mut working_set)
+        .expect("Approve call failed");
+    assert_eq!(Some(30), query_allowance(&mut working_set));
+
+    bank.call(
+        transfer_from_message(20),
+        &spender_context,
+        &mut working_set,
+    )
+    .expect("TransferFrom call failed");
+    assert_eq!(Some(10), query_allowance(&mut working_set));
+    assert_eq!(
+        Some(initial_balance - 20),
+        bank.get_balance_of(owner_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance + 20),
+        bank.get_balance_of(receiver_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(initial_balance),
+        bank.get_balance_of(spender_address, token_address, &mut working_set)
+    );
+
+    // The allowance is exceeded
+    let result = bank.call(
+        transfer_from_message(11),
+        &spender_context,
+        &mut working_set,
+    );
+    assert!(result.is_err());
+    assert_eq!(Some(10), query_allowance(&mut working_set));
+
+    // The owner can't spend its own tokens without approving itself
+    assert!(bank
+        .call(transfer_from_message(1), &owner_context, &mut working_set)
+        .is_err());
+
+    let revoke_message = CallMessage::RevokeApproval {
+        spender: spender_address,
+        token_address,
+    };
+    bank.call(revoke_message, &owner_context, &mut working_set)
+        .expect("RevokeApproval call failed");
+    assert_eq!(None, query_allowance(&mut working_set));
+    assert!(bank
+        .call(transfer_from_message(1), &spender_context, &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn approve_unknown_token() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let owner_address = bank_config.tokens[0].address_and_balances[0].0;
+    let spender_address = bank_config.tokens[0].address_and_balances[1].0;
+    let owner_context = C::new(owner_address, generate_address("sequencer"), 1);
+
+    let approve_message = CallMessage::Approve {
+        spender: spender_address,
+        coins: Coins {
+            amount: 30,
+            token_address: generate_address("unknown_token"),
+        },
+    };
+    assert!(bank
+        .call(approve_message, &owner_context, &mut working_set)
+        .is_err());
+}
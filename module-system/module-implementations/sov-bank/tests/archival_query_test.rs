@@ -231,6 +231,35 @@ fn transfer_initial_token() {
     assert_eq!("v2", String::from_utf8(val.value().to_vec()).unwrap());
 }
 
+#[test]
+fn archival_rpc_query_rejects_uncommitted_version() {
+    let bank_config = create_bank_config_with_token(2, 100);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let prover_storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(prover_storage.clone());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+    commit(working_set, prover_storage.clone());
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let user_address = bank_config.tokens[0].address_and_balances[0].0;
+
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage.clone());
+    let response = bank
+        .balance_of(Some(1), user_address, token_address, &mut working_set)
+        .unwrap();
+    assert_eq!(Some(100), response.amount);
+
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(prover_storage);
+    let err = bank
+        .balance_of(Some(2), user_address, token_address, &mut working_set)
+        .unwrap_err();
+    assert!(err.to_string().contains("Archival state is unavailable"));
+}
+
 fn query_sender_receiver_balances(
     bank: &Bank<DefaultContext>,
     token_address: Address,
@@ -41,6 +41,8 @@ fn burn_deployed_tokens() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        authority: None,
+        max_supply: None,
     };
     bank.call(mint_message, &minter_context, &mut working_set)
         .expect("Failed to mint token");
@@ -85,7 +87,9 @@ fn burn_deployed_tokens() {
     // Burn by another user, who doesn't have tokens at all
     let failed_to_burn = bank.call(burn_message, &sender_context, &mut working_set);
     assert!(failed_to_burn.is_err());
-    let Error::ModuleError(err) = failed_to_burn.err().unwrap();
+    let Error::NotFound(err) = failed_to_burn.err().unwrap() else {
+        panic!("Expected a not found error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -134,7 +138,9 @@ fn burn_deployed_tokens() {
 
     let failed_to_burn = bank.call(burn_message, &minter_context, &mut working_set);
     assert!(failed_to_burn.is_err());
-    let Error::ModuleError(err) = failed_to_burn.err().unwrap();
+    let Error::InsufficientFunds(err) = failed_to_burn.err().unwrap() else {
+        panic!("Expected an insufficient funds error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -165,7 +171,9 @@ fn burn_deployed_tokens() {
 
     let failed_to_burn = bank.call(burn_message, &minter_context, &mut working_set);
     assert!(failed_to_burn.is_err());
-    let Error::ModuleError(err) = failed_to_burn.err().unwrap();
+    let Error::NotFound(err) = failed_to_burn.err().unwrap() else {
+        panic!("Expected a not found error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
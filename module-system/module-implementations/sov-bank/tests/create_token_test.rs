@@ -28,6 +28,8 @@ fn initial_and_deployed_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        authority: None,
+        max_supply: None,
     };
 
     bank.call(create_token_message, &sender_context, &mut working_set)
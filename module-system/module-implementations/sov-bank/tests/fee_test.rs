@@ -0,0 +1,141 @@
+use helpers::*;
+use sov_bank::{
+    get_genesis_token_address, Bank, BankConfig, BankTxHook, CollectedFees, TokenConfig,
+};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, GasUnit, Module, PrivateKey, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+mod helpers;
+
+const SENDER_BALANCE: u64 = 1000;
+const GAS_LIMIT: u64 = 100;
+const GAS_TIP: u64 = 5;
+const GAS_PRICE: [u64; 2] = [1, 1];
+
+struct FeeTestCase {
+    bank: Bank<C>,
+    token: <C as Spec>::Address,
+    sender: <C as Spec>::Address,
+    sequencer: <C as Spec>::Address,
+}
+
+impl FeeTestCase {
+    fn init(working_set: &mut WorkingSet<C>) -> Self {
+        let bank = Bank::<C>::default();
+        let sender = generate_address("sender");
+        let bank_config: BankConfig<C> = BankConfig {
+            tokens: vec![TokenConfig {
+                token_name: "sov-gas-token".to_string(),
+                address_and_balances: vec![(sender, SENDER_BALANCE)],
+                authorized_minters: vec![],
+                salt: 0,
+                authority: None,
+                max_supply: None,
+            }],
+        };
+        bank.genesis(&bank_config, working_set).unwrap();
+
+        Self {
+            token: get_genesis_token_address::<C>("sov-gas-token", 0),
+            bank,
+            sender,
+            sequencer: generate_address("sequencer"),
+        }
+    }
+
+    /// Runs the tx hooks around a call using `gas_used` of gas, and returns the base fee paid.
+    fn execute_tx(&self, gas_used: [u64; 2], working_set: &mut WorkingSet<C>) -> u64 {
+        let tx = Transaction::<DefaultContext>::new_signed_tx(
+            &DefaultPrivateKey::generate(),
+            vec![],
+            0,
+            GAS_TIP,
+            GAS_LIMIT,
+            0,
+        );
+        let hook = BankTxHook {
+            sender: self.sender,
+            sequencer: self.sequencer,
+        };
+        let ctx = C::new(self.sender, self.sequencer, 1);
+
+        working_set.set_gas(GAS_LIMIT, GAS_PRICE);
+        self.bank
+            .pre_dispatch_tx_hook(&tx, working_set, &hook)
+            .unwrap();
+        working_set.charge_gas(&gas_used).unwrap();
+        self.bank
+            .post_dispatch_tx_hook(&tx, &ctx, working_set)
+            .unwrap();
+
+        tx.gas_fixed_cost().value(&GAS_PRICE) + gas_used.value(&GAS_PRICE)
+    }
+
+    fn balance(&self, account: <C as Spec>::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.bank
+            .get_balance_of(account, self.token, working_set)
+            .unwrap_or_default()
+    }
+}
+
+#[test]
+fn unused_gas_is_refunded_and_fees_rewarded() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let test_case = FeeTestCase::init(working_set);
+    let escrow = test_case.bank.fee_escrow_address();
+
+    let base_fee = test_case.execute_tx([10, 5], working_set);
+    assert!(base_fee < GAS_LIMIT);
+    assert_eq!(
+        SENDER_BALANCE - base_fee - GAS_TIP,
+        test_case.balance(test_case.sender, working_set)
+    );
+    assert_eq!(base_fee + GAS_TIP, test_case.balance(escrow, working_set));
+    assert_eq!(
+        CollectedFees {
+            base_fee,
+            tips: GAS_TIP
+        },
+        test_case.bank.get_collected_fees(working_set)
+    );
+
+    let reward = test_case
+        .bank
+        .distribute_fees(&test_case.sequencer, false, working_set)
+        .unwrap();
+    assert_eq!(base_fee + GAS_TIP, reward);
+    assert_eq!(reward, test_case.balance(test_case.sequencer, working_set));
+    assert_eq!(0, test_case.balance(escrow, working_set));
+    assert_eq!(
+        CollectedFees::default(),
+        test_case.bank.get_collected_fees(working_set)
+    );
+}
+
+#[test]
+fn base_fee_can_be_burned() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let test_case = FeeTestCase::init(working_set);
+
+    let first_base_fee = test_case.execute_tx([10, 5], working_set);
+    let second_base_fee = test_case.execute_tx([0, 0], working_set);
+
+    let reward = test_case
+        .bank
+        .distribute_fees(&test_case.sequencer, true, working_set)
+        .unwrap();
+    assert_eq!(2 * GAS_TIP, reward);
+    assert_eq!(reward, test_case.balance(test_case.sequencer, working_set));
+    assert_eq!(
+        Some(SENDER_BALANCE - first_base_fee - second_base_fee),
+        test_case
+            .bank
+            .get_total_supply_of(&test_case.token, working_set)
+    );
+}
@@ -35,6 +35,8 @@ fn freeze_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        authority: None,
+        max_supply: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -57,7 +59,9 @@ fn freeze_token() {
 
     let freeze = bank.call(freeze_message, &minter_context, &mut working_set);
     assert!(freeze.is_err());
-    let Error::ModuleError(err) = freeze.err().unwrap();
+    let Error::ModuleError(err) = freeze.err().unwrap() else {
+        panic!("Expected an untyped error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -84,6 +88,8 @@ fn freeze_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        authority: None,
+        max_supply: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -101,7 +107,9 @@ fn freeze_token() {
 
     let freeze = bank.call(freeze_message, &unauthorized_context, &mut working_set);
     assert!(freeze.is_err());
-    let Error::ModuleError(err) = freeze.err().unwrap();
+    let Error::Unauthorized(err) = freeze.err().unwrap() else {
+        panic!("Expected an unauthorized error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -142,7 +150,9 @@ fn freeze_token() {
     let minted = bank.call(mint_message, &minter_context, &mut working_set);
     assert!(minted.is_err());
 
-    let Error::ModuleError(err) = minted.err().unwrap();
+    let Error::ModuleError(err) = minted.err().unwrap() else {
+        panic!("Expected an untyped error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -1,10 +1,10 @@
 use helpers::*;
 use sov_bank::{
-    get_genesis_token_address, Bank, BankConfig, BankGasConfig, CallMessage, TokenConfig,
+    get_genesis_token_address, Bank, BankConfig, BankGasConfig, CallMessage, Coins, TokenConfig,
 };
 use sov_modules_api::macros::config_constant;
 use sov_modules_api::utils::generate_address;
-use sov_modules_api::{Context, GasUnit, Module, WorkingSet};
+use sov_modules_api::{Context, GasUnit, Module, Spec, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
 use tempfile::TempDir;
 
@@ -72,6 +72,37 @@ fn constants_price_is_charged_correctly() {
     );
 }
 
+#[test]
+fn transfer_is_charged_with_its_own_price() {
+    let sender_balance = 100;
+
+    let native_price = 2;
+    let zk_price = 3;
+    let test_case = BankGasTestCase::init(sender_balance)
+        .with_native_price(native_price)
+        .with_zk_price(zk_price);
+    let message = CallMessage::Transfer {
+        to: generate_address::<C>("receiver"),
+        coins: Coins {
+            amount: 10,
+            token_address: test_case.gas_token_address,
+        },
+    };
+    let remaining_funds = test_case.with_message(message).execute().unwrap();
+
+    // compute the expected gas cost, based on the json constants
+    let bank = Bank::<C>::default();
+    let config = bank.gas_config();
+    let gas_price = <C as Context>::GasUnit::from_arbitrary_dimensions(&[native_price, zk_price]);
+    let gas_used = config.transfer.value(&gas_price);
+
+    assert_eq!(
+        remaining_funds,
+        sender_balance - gas_used,
+        "the transfer cost should be charged"
+    );
+}
+
 #[test]
 fn not_enough_gas_wont_panic() {
     let sender_balance = 100;
@@ -107,6 +138,7 @@ pub struct BankGasTestCase {
     bank: Bank<C>,
     ctx: C,
     message: CallMessage<C>,
+    gas_token_address: <C as Spec>::Address,
     tmpdir: TempDir,
     gas_limit: u64,
     native_price: u64,
@@ -137,6 +169,8 @@ impl BankGasTestCase {
                 address_and_balances,
                 authorized_minters,
                 salt,
+                authority: None,
+                max_supply: None,
             }],
         };
 
@@ -165,6 +199,8 @@ impl BankGasTestCase {
             initial_balance,
             minter_address,
             authorized_minters: vec![minter_address],
+            authority: None,
+            max_supply: None,
         };
 
         Self {
@@ -172,6 +208,7 @@ impl BankGasTestCase {
             bank,
             ctx,
             message,
+            gas_token_address: base_token_address,
             tmpdir,
             gas_limit: sender_balance,
             native_price: 0,
@@ -186,10 +223,24 @@ impl BankGasTestCase {
             burn: Default::default(),
             mint: Default::default(),
             freeze: Default::default(),
+            approve: Default::default(),
+            transfer_from: Default::default(),
+            revoke_approval: Default::default(),
+            freeze_account: Default::default(),
+            unfreeze_account: Default::default(),
+            set_authority: Default::default(),
+            transfer_with_lock: Default::default(),
+            claim_vested: Default::default(),
+            set_mint_allowance: Default::default(),
         });
         self
     }
 
+    pub fn with_message(mut self, message: CallMessage<C>) -> Self {
+        self.message = message;
+        self
+    }
+
     pub fn with_native_price(mut self, price: u64) -> Self {
         self.native_price = price;
         self
@@ -206,6 +257,7 @@ impl BankGasTestCase {
             bank,
             ctx,
             message,
+            gas_token_address: _,
             tmpdir,
             gas_limit,
             native_price,
@@ -30,6 +30,8 @@ pub fn create_bank_config_with_token(
         address_and_balances,
         authorized_minters: vec![],
         salt: 5,
+        authority: None,
+        max_supply: None,
     };
 
     BankConfig {
@@ -0,0 +1,193 @@
+use helpers::C;
+use sov_bank::{get_token_address, Bank, BankConfig, CallMessage, Coins, MintableAmountResponse};
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+mod helpers;
+
+#[test]
+fn mint_up_to_max_supply() {
+    let bank = Bank::<C>::default();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    bank.genesis(&empty_bank_config, &mut working_set).unwrap();
+
+    let minter_address = generate_address::<C>("minter");
+    let sequencer_address = generate_address::<C>("sequencer");
+    let minter_context = C::new(minter_address, sequencer_address, 1);
+
+    let salt = 0;
+    let token_name = "Token1".to_owned();
+    let token_address = get_token_address::<C>(&token_name, minter_address.as_ref(), salt);
+
+    // The initial balance can't exceed the maximum supply
+    let create_token_message = |max_supply| CallMessage::CreateToken {
+        salt,
+        token_name: token_name.clone(),
+        initial_balance: 100,
+        minter_address,
+        authorized_minters: vec![minter_address],
+        authority: None,
+        max_supply: Some(max_supply),
+    };
+    let result = bank.call(create_token_message(99), &minter_context, &mut working_set);
+    assert!(result.is_err());
+    bank.call(create_token_message(150), &minter_context, &mut working_set)
+        .expect("Failed to create token");
+    assert_eq!(
+        Some(150),
+        bank.get_max_supply_of(&token_address, &mut working_set)
+    );
+
+    let mint_message = |amount| CallMessage::Mint {
+        coins: Coins {
+            amount,
+            token_address,
+        },
+        minter_address,
+    };
+    bank.call(mint_message(50), &minter_context, &mut working_set)
+        .expect("Failed to mint up to the maximum supply");
+    assert_eq!(
+        Some(150),
+        bank.get_total_supply_of(&token_address, &mut working_set)
+    );
+
+    let result = bank.call(mint_message(1), &minter_context, &mut working_set);
+    assert!(result.is_err());
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("exceeds its maximum supply"));
+    assert_eq!(
+        Some(150),
+        bank.get_total_supply_of(&token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(0),
+        bank.get_mintable_amount(minter_address, token_address, &mut working_set)
+    );
+}
+
+#[test]
+fn mint_with_allowance() {
+    let bank = Bank::<C>::default();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let empty_bank_config = BankConfig::<C> { tokens: vec![] };
+    bank.genesis(&empty_bank_config, &mut working_set).unwrap();
+
+    let minter_address = generate_address::<C>("minter");
+    let bridge_address = generate_address::<C>("bridge");
+    let sequencer_address = generate_address::<C>("sequencer");
+    let minter_context = C::new(minter_address, sequencer_address, 1);
+    let bridge_context = C::new(bridge_address, sequencer_address, 1);
+
+    let salt = 0;
+    let token_name = "Token1".to_owned();
+    let token_address = get_token_address::<C>(&token_name, minter_address.as_ref(), salt);
+    bank.call(
+        CallMessage::CreateToken {
+            salt,
+            token_name,
+            initial_balance: 100,
+            minter_address,
+            authorized_minters: vec![minter_address],
+            authority: None,
+            max_supply: Some(1000),
+        },
+        &minter_context,
+        &mut working_set,
+    )
+    .expect("Failed to create token");
+
+    let query_mintable_amount = |minter, working_set: &mut WorkingSet<C>| {
+        let response: MintableAmountResponse = bank
+            .mintable_amount(None, minter, token_address, working_set)
+            .unwrap();
+        response.amount
+    };
+    assert_eq!(
+        Some(900),
+        query_mintable_amount(minter_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(0),
+        query_mintable_amount(bridge_address, &mut working_set)
+    );
+
+    let set_allowance_message = |amount| CallMessage::SetMintAllowance {
+        minter: bridge_address,
+        coins: Coins {
+            amount,
+            token_address,
+        },
+    };
+    let mint_message = |amount| CallMessage::Mint {
+        coins: Coins {
+            amount,
+            token_address,
+        },
+        minter_address: bridge_address,
+    };
+
+    // Only the authorized minters can delegate mint allowances
+    let result = bank.call(set_allowance_message(30), &bridge_context, &mut working_set);
+    assert!(result.is_err());
+    let result = bank.call(mint_message(10), &bridge_context, &mut working_set);
+    assert!(result.is_err());
+
+    bank.call(set_allowance_message(30), &minter_context, &mut working_set)
+        .expect("Failed to set the mint allowance");
+    assert_eq!(1, working_set.events().len());
+    assert_eq!(
+        Some(30),
+        query_mintable_amount(bridge_address, &mut working_set)
+    );
+
+    bank.call(mint_message(20), &bridge_context, &mut working_set)
+        .expect("Failed to mint with the allowance");
+    assert_eq!(2, working_set.events().len());
+    assert_eq!(
+        Some(20),
+        bank.get_balance_of(bridge_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(10),
+        query_mintable_amount(bridge_address, &mut working_set)
+    );
+
+    // The allowance can't be exceeded
+    let result = bank.call(mint_message(11), &bridge_context, &mut working_set);
+    assert!(result.is_err());
+    bank.call(mint_message(10), &bridge_context, &mut working_set)
+        .expect("Failed to mint the rest of the allowance");
+    assert_eq!(
+        Some(0),
+        query_mintable_amount(bridge_address, &mut working_set)
+    );
+    assert_eq!(
+        Some(130),
+        bank.get_total_supply_of(&token_address, &mut working_set)
+    );
+
+    // The allowance is capped by the remaining supply
+    bank.call(
+        set_allowance_message(5000),
+        &minter_context,
+        &mut working_set,
+    )
+    .expect("Failed to set the mint allowance");
+    assert_eq!(
+        Some(870),
+        query_mintable_amount(bridge_address, &mut working_set)
+    );
+
+    // A revoked allowance can't be used anymore
+    bank.call(set_allowance_message(0), &minter_context, &mut working_set)
+        .expect("Failed to revoke the mint allowance");
+    let result = bank.call(mint_message(1), &bridge_context, &mut working_set);
+    assert!(result.is_err());
+}
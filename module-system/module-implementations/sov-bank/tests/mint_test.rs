@@ -35,6 +35,8 @@ fn mint_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![minter_address],
+        authority: None,
+        max_supply: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -93,7 +95,9 @@ fn mint_token() {
 
     assert!(unauthorized_mint.is_err());
 
-    let Error::ModuleError(err) = unauthorized_mint.err().unwrap();
+    let Error::Unauthorized(err) = unauthorized_mint.err().unwrap() else {
+        panic!("Expected an unauthorized error");
+    };
     let mut chain = err.chain();
 
     let message_1 = chain.next().unwrap().to_string();
@@ -131,6 +135,8 @@ fn mint_token() {
         initial_balance,
         minter_address,
         authorized_minters: vec![authorized_minter_address_1, authorized_minter_address_2],
+        authority: None,
+        max_supply: None,
     };
     let _minted = bank
         .call(mint_message, &minter_context, &mut working_set)
@@ -151,7 +157,9 @@ fn mint_token() {
 
     let minted = bank.call(mint_message, &minter_context, &mut working_set);
     assert!(minted.is_err());
-    let Error::ModuleError(err) = minted.err().unwrap();
+    let Error::Unauthorized(err) = minted.err().unwrap() else {
+        panic!("Expected an unauthorized error");
+    };
     let mut chain = err.chain();
 
     let message_1 = chain.next().unwrap().to_string();
@@ -220,7 +228,9 @@ fn mint_token() {
         &mut working_set,
     );
     assert!(minted.is_err());
-    let Error::ModuleError(err) = minted.err().unwrap();
+    let Error::ModuleError(err) = minted.err().unwrap() else {
+        panic!("Expected an untyped error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -259,7 +269,9 @@ fn mint_token() {
         &mut working_set,
     );
     assert!(minted.is_err());
-    let Error::ModuleError(err) = minted.err().unwrap();
+    let Error::ModuleError(err) = minted.err().unwrap() else {
+        panic!("Expected an untyped error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -0,0 +1,81 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{get_genesis_token_address, Bank, CallMessage, Coins};
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::Storage;
+
+#[test]
+fn list_token_holders() {
+    let initial_balance = 100;
+    let bank_config = create_bank_config_with_token(4, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::<C>::new(storage.clone());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    // The genesis balances are read from the database
+    let (cache_log, witness) = working_set.checkpoint().freeze();
+    storage
+        .validate_and_commit(cache_log, &witness)
+        .expect("JMT update is valid");
+    let mut working_set = WorkingSet::<C>::new(storage);
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let mut expected_holders = bank_config.tokens[0].address_and_balances.clone();
+    expected_holders.sort_by_key(|(address, _)| address.as_ref().to_vec());
+
+    let holders = bank
+        .get_token_holders(token_address, None, 10, &mut working_set)
+        .unwrap();
+    assert_eq!(expected_holders, holders);
+
+    // Holders are listed page by page
+    let first_page = bank
+        .get_token_holders(token_address, None, 3, &mut working_set)
+        .unwrap();
+    assert_eq!(expected_holders[..3], first_page);
+    let second_page = bank
+        .get_token_holders(
+            token_address,
+            Some(expected_holders[3].0),
+            3,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(expected_holders[3..], second_page);
+
+    // Balances which aren't committed yet are listed too
+    let receiver = generate_address("receiver");
+    let sender = expected_holders[0].0;
+    bank.call(
+        CallMessage::Transfer {
+            to: receiver,
+            coins: Coins {
+                amount: initial_balance,
+                token_address,
+            },
+        },
+        &C::new(sender, generate_address("sequencer"), 1),
+        &mut working_set,
+    )
+    .expect("Transfer call failed");
+
+    expected_holders[0].1 = 0;
+    expected_holders.push((receiver, initial_balance));
+    expected_holders.sort_by_key(|(address, _)| address.as_ref().to_vec());
+    let holders = bank
+        .get_token_holders(token_address, None, 10, &mut working_set)
+        .unwrap();
+    assert_eq!(expected_holders, holders);
+
+    assert_eq!(
+        None,
+        bank.get_token_holders(generate_address("missing"), None, 10, &mut working_set)
+    );
+}
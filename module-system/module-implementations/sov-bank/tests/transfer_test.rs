@@ -95,7 +95,9 @@ fn transfer_initial_token() {
 
         let result = bank.call(transfer_message, &sender_context, &mut working_set);
         assert!(result.is_err());
-        let Error::ModuleError(err) = result.err().unwrap();
+        let Error::InsufficientFunds(err) = result.err().unwrap() else {
+            panic!("Expected an insufficient funds error");
+        };
         let mut chain = err.chain();
         let message_1 = chain.next().unwrap().to_string();
         let message_2 = chain.next().unwrap().to_string();
@@ -140,7 +142,9 @@ fn transfer_initial_token() {
 
         let result = bank.call(transfer_message, &sender_context, &mut working_set);
         assert!(result.is_err());
-        let Error::ModuleError(err) = result.err().unwrap();
+        let Error::NotFound(err) = result.err().unwrap() else {
+            panic!("Expected a not found error");
+        };
         let mut chain = err.chain();
         let message_1 = chain.next().unwrap().to_string();
         let message_2 = chain.next().unwrap().to_string();
@@ -177,7 +181,9 @@ fn transfer_initial_token() {
 
         let result = bank.call(transfer_message, &unknown_sender_context, &mut working_set);
         assert!(result.is_err());
-        let Error::ModuleError(err) = result.err().unwrap();
+        let Error::InsufficientFunds(err) = result.err().unwrap() else {
+            panic!("Expected an insufficient funds error");
+        };
         let mut chain = err.chain();
         let message_1 = chain.next().unwrap().to_string();
         let message_2 = chain.next().unwrap().to_string();
@@ -303,6 +309,8 @@ fn transfer_deployed_token() {
         initial_balance,
         minter_address: sender_address,
         authorized_minters: vec![sender_address],
+        authority: None,
+        max_supply: None,
     };
     bank.call(mint_message, &sender_context, &mut working_set)
         .expect("Failed to mint token");
@@ -0,0 +1,184 @@
+mod helpers;
+
+use helpers::*;
+use sov_bank::{
+    get_genesis_token_address, Bank, CallMessage, Coins, VestingBalanceResponse, VestingSchedule,
+};
+use sov_modules_api::{Context, Error, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+const LOCK_HEIGHT: u64 = 10;
+
+#[test]
+fn vested_tokens_unlock_linearly_after_the_cliff() {
+    let initial_balance = 1000;
+    let bank_config = create_bank_config_with_token(3, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let sender_address = bank_config.tokens[0].address_and_balances[0].0;
+    let recipient_address = bank_config.tokens[0].address_and_balances[1].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[2].0;
+
+    let query_vesting = |slot_height, working_set: &mut WorkingSet<C>| {
+        let response: VestingBalanceResponse = bank
+            .vesting_balance(
+                None,
+                recipient_address,
+                token_address,
+                slot_height,
+                working_set,
+            )
+            .unwrap();
+        (response.liquid, response.claimable, response.locked)
+    };
+    let claim = |slot_height, working_set: &mut WorkingSet<C>| {
+        let recipient_context = C::new(recipient_address, sequencer_address, slot_height);
+        bank.call(
+            CallMessage::ClaimVested { token_address },
+            &recipient_context,
+            working_set,
+        )
+    };
+
+    let sender_context = C::new(sender_address, sequencer_address, LOCK_HEIGHT);
+    let lock_message = CallMessage::TransferWithLock {
+        to: recipient_address,
+        coins: Coins {
+            amount: 100,
+            token_address,
+        },
+        cliff_slots: 5,
+        vesting_slots: 20,
+    };
+    bank.call(lock_message, &sender_context, &mut working_set)
+        .expect("TransferWithLock call failed");
+    assert_eq!(
+        Some(initial_balance - 100),
+        bank.get_balance_of(sender_address, token_address, &mut working_set)
+    );
+    assert_eq!(
+        vec![VestingSchedule {
+            amount: 100,
+            claimed: 0,
+            start_height: LOCK_HEIGHT,
+            cliff_height: LOCK_HEIGHT + 5,
+            end_height: LOCK_HEIGHT + 20,
+        }],
+        bank.get_vesting_schedules(recipient_address, token_address, &mut working_set)
+    );
+
+    // Nothing unlocks before the cliff
+    assert_eq!(
+        (initial_balance, 0, 100),
+        query_vesting(LOCK_HEIGHT + 4, &mut working_set)
+    );
+    let Error::NotFound(_) = claim(LOCK_HEIGHT + 4, &mut working_set).unwrap_err() else {
+        panic!("Expected a not found error");
+    };
+
+    // The tokens accrued since the lock unlock at the cliff
+    assert_eq!(
+        (initial_balance, 25, 75),
+        query_vesting(LOCK_HEIGHT + 5, &mut working_set)
+    );
+    claim(LOCK_HEIGHT + 5, &mut working_set).expect("ClaimVested call failed");
+    assert_eq!(
+        (initial_balance + 25, 0, 75),
+        query_vesting(LOCK_HEIGHT + 5, &mut working_set)
+    );
+
+    claim(LOCK_HEIGHT + 10, &mut working_set).expect("ClaimVested call failed");
+    assert_eq!(
+        (initial_balance + 50, 0, 50),
+        query_vesting(LOCK_HEIGHT + 10, &mut working_set)
+    );
+
+    // Everything is unlocked at the end of the vesting period
+    assert_eq!(
+        (initial_balance + 50, 50, 0),
+        query_vesting(LOCK_HEIGHT + 30, &mut working_set)
+    );
+    claim(LOCK_HEIGHT + 30, &mut working_set).expect("ClaimVested call failed");
+    assert_eq!(
+        (initial_balance + 100, 0, 0),
+        query_vesting(LOCK_HEIGHT + 30, &mut working_set)
+    );
+    assert!(bank
+        .get_vesting_schedules(recipient_address, token_address, &mut working_set)
+        .is_empty());
+}
+
+#[test]
+fn cliff_only_schedule_unlocks_at_once() {
+    let initial_balance = 1000;
+    let bank_config = create_bank_config_with_token(3, initial_balance);
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bank = Bank::default();
+    bank.genesis(&bank_config, &mut working_set).unwrap();
+
+    let token_address = get_genesis_token_address::<C>(
+        &bank_config.tokens[0].token_name,
+        bank_config.tokens[0].salt,
+    );
+    let sender_address = bank_config.tokens[0].address_and_balances[0].0;
+    let recipient_address = bank_config.tokens[0].address_and_balances[1].0;
+    let sequencer_address = bank_config.tokens[0].address_and_balances[2].0;
+    let sender_context = C::new(sender_address, sequencer_address, LOCK_HEIGHT);
+
+    let lock_message = |amount, cliff_slots, vesting_slots| CallMessage::TransferWithLock {
+        to: recipient_address,
+        coins: Coins {
+            amount,
+            token_address,
+        },
+        cliff_slots,
+        vesting_slots,
+    };
+
+    // The cliff must be within the vesting period, and something must be locked
+    for invalid_message in [
+        lock_message(100, 11, 10),
+        lock_message(100, 0, 0),
+        lock_message(0, 10, 10),
+    ] {
+        assert!(bank
+            .call(invalid_message, &sender_context, &mut working_set)
+            .is_err());
+    }
+    // The sender can't lock more than its balance
+    assert!(bank
+        .call(
+            lock_message(initial_balance + 1, 10, 10),
+            &sender_context,
+            &mut working_set
+        )
+        .is_err());
+
+    bank.call(lock_message(100, 10, 10), &sender_context, &mut working_set)
+        .expect("TransferWithLock call failed");
+    let schedules = bank.get_vesting_schedules(recipient_address, token_address, &mut working_set);
+    assert_eq!(1, schedules.len());
+    assert_eq!(0, schedules[0].vested_at(LOCK_HEIGHT + 9));
+    assert_eq!(100, schedules[0].vested_at(LOCK_HEIGHT + 10));
+
+    // Locked tokens can't be spent
+    let recipient_context = C::new(recipient_address, sequencer_address, LOCK_HEIGHT + 1);
+    let transfer_message = CallMessage::Transfer {
+        to: sender_address,
+        coins: Coins {
+            amount: initial_balance + 1,
+            token_address,
+        },
+    };
+    assert!(bank
+        .call(transfer_message, &recipient_context, &mut working_set)
+        .is_err());
+}
@@ -9,6 +9,7 @@ use crate::BlobStorage;
 #[cfg_attr(
     feature = "native",
     derive(sov_modules_api::macros::CliWalletArg),
+    derive(sov_modules_api::macros::CallMessageBuilder),
     derive(schemars::JsonSchema)
 )]
 #[cfg_attr(
@@ -31,6 +31,67 @@ impl<C: Context, Da: DaSpec> BlobStorage<C, Da> {
     }
 }
 
+impl<C: Context, Da: DaSpec> BlobStorage<C, Da> {
+    /// Adds the blobs from non-sequencers to the forced inclusion queue and returns the remaining ones.
+    fn take_forced_transactions<'a, I>(
+        &self,
+        current_blobs: I,
+        current_slot: TransitionHeight,
+        working_set: &mut KernelWorkingSet<'_, C>,
+    ) -> Vec<&'a mut Da::BlobTransaction>
+    where
+        I: IntoIterator<Item = &'a mut Da::BlobTransaction>,
+    {
+        current_blobs
+            .into_iter()
+            .filter_map(|blob| {
+                if self
+                    .sequencer_registry
+                    .is_sender_allowed(&blob.sender(), working_set.inner)
+                {
+                    Some(blob)
+                } else {
+                    self.sequencer_registry.enqueue_forced_transactions(
+                        blob,
+                        current_slot,
+                        working_set.inner,
+                    );
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+impl<C: Context, Da: DaSpec> BlobStorage<C, Da> {
+    /// Hands the blobs from the bridge deposit address to the bridge and returns the remaining ones.
+    fn take_deposits<'a, I>(
+        &self,
+        current_blobs: I,
+        current_slot: TransitionHeight,
+        working_set: &mut KernelWorkingSet<'_, C>,
+    ) -> Vec<&'a mut Da::BlobTransaction>
+    where
+        I: IntoIterator<Item = &'a mut Da::BlobTransaction>,
+    {
+        current_blobs
+            .into_iter()
+            .filter_map(|blob| {
+                if self
+                    .bridge
+                    .is_deposit_sender(&blob.sender(), working_set.inner)
+                {
+                    self.bridge
+                        .process_deposits(blob, current_slot, working_set.inner);
+                    None
+                } else {
+                    Some(blob)
+                }
+            })
+            .collect()
+    }
+}
+
 impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
     type Context = C;
 
@@ -38,6 +99,10 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
     // 1. Any blobs sent by the preferred sequencer ("prority blobs")
     // 2. Any non-priority blobs which were sent `DEFERRED_SLOTS_COUNT` slots ago ("expiring deferred blobs")
     // 3. Some additional deferred blobs needed to fill the total requested by the sequencer, if applicable. ("bonus blobs")
+    //
+    // Blobs from senders which are not registered sequencers are never returned. Their transactions are
+    // added to the forced inclusion queue of the sequencer registry instead. Blobs from the deposit address
+    // of the bridge aren't returned either, their deposits are minted by the bridge.
     fn get_blobs_for_this_slot<'a, 'k, I>(
         &self,
         current_blobs: I,
@@ -46,6 +111,22 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
     where
         I: IntoIterator<Item = &'a mut Da::BlobTransaction>,
     {
+        let current_slot: TransitionHeight = self.get_true_slot_height(working_set);
+
+        // Forced transactions which missed their deadline have to be detected before any blob of this slot is applied
+        self.sequencer_registry
+            .enforce_forced_inclusion_deadlines(current_slot, working_set.inner);
+        // The preferred sequencer of this slot has to be known before the blobs are prioritized
+        self.sequencer_registry
+            .rotate_preferred_sequencer(current_slot, working_set.inner);
+        let current_blobs = self.take_deposits(current_blobs, current_slot, working_set);
+        let current_blobs = self.take_forced_transactions(current_blobs, current_slot, working_set);
+        self.sequencer_registry.record_batches(
+            current_blobs.iter().map(|blob| blob.sender()),
+            current_slot,
+            working_set.inner,
+        );
+
         // If `DEFERRED_SLOTS_COUNT` is 0, we don't never to do any deferred blob processing and this
         // function just sorts and filters the current blobs before returning
         if DEFERRED_SLOTS_COUNT == 0 {
@@ -65,7 +146,6 @@ impl<C: Context, Da: DaSpec> BlobSelector<Da> for BlobStorage<C, Da> {
         // Calculate any expiring deferred blobs first, since these have to be processed no matter what (Case 2 above).
         // Note that we have to handle this case even if there is no preferred sequencer, since that sequencer might have
         // exited while there were deferred blobs waiting to be processed
-        let current_slot: TransitionHeight = self.get_true_slot_height(working_set);
         let slot_for_expiring_blobs =
             current_slot.saturating_sub(self.get_deferred_slots_count(working_set.inner));
         let expiring_deferred_blobs: Vec<Da::BlobTransaction> =
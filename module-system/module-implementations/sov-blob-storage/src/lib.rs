@@ -3,6 +3,8 @@
 
 mod call;
 pub use call::CallMessage;
+#[cfg(feature = "native")]
+pub use call::CallMessageBuilder;
 mod capabilities;
 #[cfg(feature = "native")]
 mod query;
@@ -44,6 +46,9 @@ pub struct BlobStorage<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
     #[module]
     pub(crate) sequencer_registry: sov_sequencer_registry::SequencerRegistry<C, Da>,
 
+    #[module]
+    pub(crate) bridge: sov_bridge::Bridge<C, Da>,
+
     #[kernel_module]
     chain_state: sov_chain_state::ChainState<C, Da>,
 }
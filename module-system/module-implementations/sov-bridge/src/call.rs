@@ -0,0 +1,93 @@
+use anyhow::{bail, Context as _};
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_bank::{Amount, Coins};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Context, DaSpec, WorkingSet};
+
+use crate::Bridge;
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-bridge` module.
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(CliWalletArg),
+    derive(CallMessageBuilder)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(Debug, PartialEq, Clone, borsh::BorshSerialize, borsh::BorshDeserialize)]
+pub enum CallMessage {
+    /// Burn bridged tokens of the sender and queue their withdrawal to the DA layer.
+    Withdraw {
+        /// The amount of bridged tokens to withdraw.
+        amount: Amount,
+        /// The raw Da address receiving the withdrawn tokens.
+        da_recipient: Vec<u8>,
+    },
+}
+
+/// A withdrawal queued by the bridge, to be claimed on the DA layer.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct Withdrawal<C: Context> {
+    /// The rollup address whose tokens were burnt.
+    pub sender: C::Address,
+    /// The raw Da address receiving the withdrawn tokens.
+    pub da_recipient: Vec<u8>,
+    /// The amount of bridged tokens withdrawn.
+    pub amount: Amount,
+    /// The slot height at which the withdrawal was queued.
+    pub slot_height: u64,
+}
+
+impl<C: Context, Da: DaSpec> Bridge<C, Da> {
+    pub(crate) fn withdraw(
+        &self,
+        amount: Amount,
+        da_recipient: Vec<u8>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        if amount == 0 {
+            bail!("Withdrawal amount can't be zero");
+        }
+        Da::Address::try_from(da_recipient.as_slice()).context("Invalid withdrawal recipient")?;
+        let token_address = self
+            .token_address
+            .get(working_set)
+            .context("The bridge is disabled")?;
+
+        self.bank.burn(
+            Coins {
+                amount,
+                token_address,
+            },
+            context.sender(),
+            working_set,
+        )?;
+
+        let index = self.withdrawal_count.get(working_set).unwrap_or_default();
+        self.withdrawals.set(
+            &index,
+            &Withdrawal {
+                sender: context.sender().clone(),
+                da_recipient,
+                amount,
+                slot_height: context.slot_height(),
+            },
+            working_set,
+        );
+        self.withdrawal_count.set(&(index + 1), working_set);
+        Ok(CallResponse::default())
+    }
+}
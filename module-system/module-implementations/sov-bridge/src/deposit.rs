@@ -0,0 +1,149 @@
+use anyhow::Context as _;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sov_bank::Coins;
+use sov_modules_api::compression::decompress_blob;
+use sov_modules_api::da::DaDeposit;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{BlobReaderTrait, Context, DaSpec, WorkingSet};
+use tracing::{info, warn};
+
+use crate::{Bridge, DepositRecord};
+
+/// A deposit made on the DA layer, as relayed to the rollup. Deposit blobs hold a borsh
+/// encoded `Vec<Deposit>`, optionally compressed like sequencer batches.
+///
+/// The relayer is not trusted: the amount and the recipient of a deposit are read from the
+/// DA transaction it proves.
+#[cfg_attr(feature = "native", derive(serde::Deserialize, serde::Serialize))]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct Deposit {
+    /// The height of the rollup slot built from the DA block which includes the deposit.
+    pub slot_height: u64,
+    /// The DA specific proof that the deposit transaction is included in that block,
+    /// checked by [`DaSpec::verify_deposit`].
+    pub proof: Vec<u8>,
+}
+
+impl<C: Context, Da: DaSpec> Bridge<C, Da> {
+    /// Returns `true` if the blobs of the given DA address are deposits.
+    pub fn is_deposit_sender(&self, sender: &Da::Address, working_set: &mut WorkingSet<C>) -> bool {
+        self.deposit_address
+            .get(working_set)
+            .is_some_and(|deposit_address| &deposit_address == sender)
+    }
+
+    /// Mints the deposits of a blob posted on DA by the deposit address.
+    ///
+    /// Every deposit is verified against the hash of the DA block it claims to be included in,
+    /// as recorded by the chain state. Invalid deposits and deposits which were already minted
+    /// are skipped.
+    pub fn process_deposits(
+        &self,
+        blob: &mut Da::BlobTransaction,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let deposits = match decompress_blob(data_for_deserialization(blob))
+            .map_err(anyhow::Error::from)
+            .and_then(|data| Ok(Vec::<Deposit>::try_from_slice(&data)?))
+        {
+            Ok(deposits) => deposits,
+            Err(e) => {
+                info!(
+                    "Blob hash=0x{} from sender {} doesn't hold valid deposits and is going to be discarded: {}",
+                    hex::encode(blob.hash()),
+                    blob.sender(),
+                    e
+                );
+                return;
+            }
+        };
+
+        let (Some(token_address), Some(bridge_script)) = (
+            self.token_address.get(working_set),
+            self.bridge_script.get(working_set),
+        ) else {
+            return;
+        };
+        for deposit in deposits {
+            let (da_deposit, recipient) =
+                match self.verify_deposit(&deposit, &bridge_script, working_set) {
+                    Ok(verified) => verified,
+                    Err(e) => {
+                        warn!(
+                            "Invalid deposit in blob 0x{}: {:?}",
+                            hex::encode(blob.hash()),
+                            e
+                        );
+                        continue;
+                    }
+                };
+            if self.deposits.get(&da_deposit.id, working_set).is_some() {
+                info!(
+                    "Deposit 0x{} was already minted, skipping",
+                    hex::encode(da_deposit.id)
+                );
+                continue;
+            }
+
+            let coins = Coins {
+                amount: da_deposit.amount,
+                token_address: token_address.clone(),
+            };
+            if let Err(e) = self
+                .bank
+                .mint(&coins, &recipient, &self.address, working_set)
+            {
+                warn!(
+                    "Failed to mint deposit 0x{}: {:?}",
+                    hex::encode(da_deposit.id),
+                    e
+                );
+                continue;
+            }
+
+            self.deposits.set(
+                &da_deposit.id,
+                &DepositRecord {
+                    recipient,
+                    amount: da_deposit.amount,
+                    blob_hash: blob.hash(),
+                    slot_height,
+                },
+                working_set,
+            );
+        }
+    }
+
+    /// Checks the proof of a deposit and returns the verified deposit with its rollup recipient.
+    fn verify_deposit(
+        &self,
+        deposit: &Deposit,
+        bridge_script: &[u8],
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<(DaDeposit<Da::SlotHash>, C::Address)> {
+        let da_deposit = Da::verify_deposit(bridge_script, &deposit.proof)?;
+        let slot_hash = self
+            .chain_state
+            .get_slot_da_block_hash(deposit.slot_height, working_set)
+            .with_context(|| format!("Slot {} was not processed", deposit.slot_height))?;
+        anyhow::ensure!(
+            slot_hash == da_deposit.block_hash,
+            "The deposit is not included in the DA block of slot {}",
+            deposit.slot_height
+        );
+        let recipient = C::Address::try_from(da_deposit.recipient.as_slice())
+            .context("Invalid deposit recipient")?;
+        Ok((da_deposit, recipient))
+    }
+}
+
+#[cfg(feature = "native")]
+fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
+    blob.full_data()
+}
+
+#[cfg(not(feature = "native"))]
+fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
+    blob.verified_data()
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Bridge;
+
+/// Genesis configuration for the [`Bridge`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "Da::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct BridgeConfig<Da: sov_modules_api::DaSpec> {
+    /// The DA address posting the deposit blobs.
+    ///
+    /// Blobs from this address are never executed as batches, so it must not be
+    /// the address of a sequencer.
+    pub deposit_address: Da::Address,
+    /// The hex encoded DA script the deposits lock their funds in, like the `scriptPubKey`
+    /// of the bridge on Bitcoin.
+    #[serde(with = "hex::serde")]
+    pub bridge_script: Vec<u8>,
+    /// The name of the bridged token.
+    ///
+    /// The token is created at genesis, with the module as its only authorized minter.
+    pub token_name: String,
+    /// The salt used to compute the address of the bridged token.
+    pub salt: u64,
+}
+
+impl<C: Context, Da: sov_modules_api::DaSpec> Bridge<C, Da> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let context = C::new(self.address.clone(), self.address.clone(), 0);
+        let token_address = self.bank.create_token(
+            config.token_name.clone(),
+            config.salt,
+            0,
+            self.address.clone(),
+            vec![self.address.clone()],
+            None,
+            None,
+            &context,
+            working_set,
+        )?;
+
+        self.token_address.set(&token_address, working_set);
+        self.deposit_address
+            .set(&config.deposit_address, working_set);
+        self.bridge_script.set(&config.bridge_script, working_set);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use sov_mock_da::{MockAddress, MockDaSpec};
+
+    use crate::BridgeConfig;
+
+    #[test]
+    fn test_config_serialization() {
+        let config = BridgeConfig::<MockDaSpec> {
+            deposit_address: MockAddress::from_str(
+                "0505050505050505050505050505050505050505050505050505050505050505",
+            )
+            .unwrap(),
+            bridge_script: vec![0x00, 0x14, 0x05],
+            token_name: "sov-bridged-token".to_owned(),
+            salt: 0,
+        };
+
+        let data = r#"
+        {
+            "deposit_address":"0505050505050505050505050505050505050505050505050505050505050505",
+            "bridge_script":"001405",
+            "token_name":"sov-bridged-token",
+            "salt":0
+        }"#;
+
+        let parsed_config: BridgeConfig<MockDaSpec> = serde_json::from_str(data).unwrap();
+        assert_eq!(config, parsed_config)
+    }
+}
@@ -0,0 +1,166 @@
+//! The `sov-bridge` module bridges tokens from the Data Availability layer to the rollup.
+//!
+//! Deposits are native transactions of the DA layer locking funds in the bridge script.
+//! They are relayed to the rollup by the designated deposit address, as blobs holding
+//! a list of [`Deposit`]s, each carrying a DA inclusion proof of its transaction. The module
+//! checks the proof against the DA block recorded by the chain state, mints the locked amount
+//! of the bridged token to the recipient committed to by the transaction, and records the
+//! deposit, so the same deposit can't be minted twice. The relayer can't mint anything
+//! that wasn't deposited on DA.
+//!
+//! Deposit blobs are routed to the module by the blob storage, before the blobs
+//! of the slot are selected for execution.
+//!
+//! Users withdraw bridged tokens with [`CallMessage::Withdraw`], which burns them and
+//! appends a [`Withdrawal`] to the withdrawal queue. The queue is part of the rollup
+//! state, so a withdrawal is claimed on the DA layer with a merkle proof of its
+//! entry against the state root.
+
+#![deny(missing_docs)]
+mod call;
+mod deposit;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use call::*;
+pub use deposit::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+use sov_state::codec::BcsCodec;
+
+/// A deposit which was minted on the rollup.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct DepositRecord<C: sov_modules_api::Context> {
+    /// The rollup address which received the deposit.
+    pub recipient: C::Address,
+    /// The amount of bridged tokens minted to the recipient.
+    pub amount: Amount,
+    /// The hash of the DA blob which relayed the deposit.
+    pub blob_hash: [u8; 32],
+    /// The slot height at which the deposit was minted.
+    pub slot_height: u64,
+}
+
+/// The `sov-bridge` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct Bridge<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> {
+    /// The address of the `sov_bridge` module.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+
+    /// Reference to the chain state module, used to check the DA blocks including the deposits.
+    #[kernel_module]
+    pub(crate) chain_state: sov_chain_state::ChainState<C, Da>,
+
+    /// The DA address posting the deposit blobs.
+    /// If it isn't set, the bridge is disabled.
+    #[state]
+    pub(crate) deposit_address: StateValue<Da::Address, BcsCodec>,
+
+    /// The DA script the deposits lock their funds in.
+    #[state]
+    pub(crate) bridge_script: StateValue<Vec<u8>>,
+
+    /// The address of the bridged token, which only this module can mint.
+    #[state]
+    pub(crate) token_address: StateValue<C::Address>,
+
+    /// Deposit id on the DA layer => the deposit minted for it.
+    #[state]
+    pub(crate) deposits: StateMap<[u8; 32], DepositRecord<C>>,
+
+    /// The withdrawal queue. Withdrawal index => the queued withdrawal.
+    #[state]
+    pub(crate) withdrawals: StateMap<u64, Withdrawal<C>>,
+
+    /// The number of queued withdrawals, which is the index of the next one.
+    #[state]
+    pub(crate) withdrawal_count: StateValue<u64>,
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> sov_modules_api::Module
+    for Bridge<C, Da>
+{
+    type Context = C;
+
+    type Config = BridgeConfig<Da>;
+
+    type CallMessage = CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::Withdraw {
+                amount,
+                da_recipient,
+            } => self.withdraw(amount, da_recipient, context, working_set)?,
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> Bridge<C, Da> {
+    /// Returns the DA address posting the deposit blobs, or [`None`] if the bridge is disabled.
+    pub fn get_deposit_address(&self, working_set: &mut WorkingSet<C>) -> Option<Da::Address> {
+        self.deposit_address.get(working_set)
+    }
+
+    /// Returns the address of the bridged token.
+    pub fn get_token_address(&self, working_set: &mut WorkingSet<C>) -> Option<C::Address> {
+        self.token_address.get(working_set)
+    }
+
+    /// Returns the DA script the deposits lock their funds in.
+    pub fn get_bridge_script(&self, working_set: &mut WorkingSet<C>) -> Option<Vec<u8>> {
+        self.bridge_script.get(working_set)
+    }
+
+    /// Returns the deposit minted for the given DA deposit id, if any.
+    pub fn get_deposit(
+        &self,
+        id: [u8; 32],
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<DepositRecord<C>> {
+        self.deposits.get(&id, working_set)
+    }
+
+    /// Returns the queued withdrawal with the given index, if any.
+    pub fn get_withdrawal(
+        &self,
+        index: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Withdrawal<C>> {
+        self.withdrawals.get(&index, working_set)
+    }
+
+    /// Returns the number of queued withdrawals.
+    pub fn get_withdrawal_count(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.withdrawal_count.get(working_set).unwrap_or_default()
+    }
+}
@@ -0,0 +1,164 @@
+//! Defines rpc queries exposed by the bridge module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, DaSpec, WorkingSet};
+use sov_state::storage::{NativeStorage, StorageKey};
+
+use crate::{Bridge, DepositRecord, Withdrawal};
+
+/// The response type to the `getDeposit` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct DepositResponse<C: Context> {
+    /// The deposit minted for the requested DA deposit id, if any.
+    pub deposit: Option<DepositRecord<C>>,
+}
+
+/// The response type to the `getTokenAddress` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct TokenAddressResponse<C: Context> {
+    /// The address of the bridged token.
+    pub token_address: Option<C::Address>,
+}
+
+/// The response type to the `getWithdrawal` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct WithdrawalResponse<C: Context> {
+    /// The queued withdrawal with the requested index, if any.
+    pub withdrawal: Option<Withdrawal<C>>,
+}
+
+/// The response type to the `bridge_getWithdrawalProof` RPC method.
+///
+/// It proves that the storage `key` of the withdrawal holds `value` in the state
+/// whose root is `state_root`. All the byte fields are hex encoded.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct WithdrawalProofResponse<C: Context> {
+    /// The proven withdrawal.
+    pub withdrawal: Withdrawal<C>,
+    /// The state version the proof is generated against, which is the number of
+    /// the last slot processed by the node.
+    pub version: u64,
+    /// The state root the proof is generated against.
+    pub state_root: String,
+    /// The storage key of the withdrawal.
+    pub key: String,
+    /// The withdrawal, encoded as it is stored.
+    pub value: String,
+    /// The borsh encoded merkle proof of the key and value against the state root.
+    pub proof: String,
+}
+
+#[rpc_gen(client, server, namespace = "bridge")]
+impl<C: Context, Da: DaSpec> Bridge<C, Da> {
+    /// Returns the deposit minted for the given DA deposit id.
+    ///
+    /// The response only contains data if the deposit was minted.
+    #[rpc_method(name = "getDeposit")]
+    pub fn deposit(
+        &self,
+        id: [u8; 32],
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<DepositResponse<C>> {
+        Ok(DepositResponse {
+            deposit: self.get_deposit(id, working_set),
+        })
+    }
+
+    /// Returns the address of the bridged token.
+    #[rpc_method(name = "getTokenAddress")]
+    pub fn token_address(
+        &self,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<TokenAddressResponse<C>> {
+        Ok(TokenAddressResponse {
+            token_address: self.get_token_address(working_set),
+        })
+    }
+
+    /// Returns the queued withdrawal with the given index.
+    #[rpc_method(name = "getWithdrawal")]
+    pub fn withdrawal(
+        &self,
+        index: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<WithdrawalResponse<C>> {
+        Ok(WithdrawalResponse {
+            withdrawal: self.get_withdrawal(index, working_set),
+        })
+    }
+}
+
+impl<C: Context, Da: DaSpec> Bridge<C, Da>
+where
+    C::Storage: NativeStorage,
+{
+    /// Gives the storage key of the withdrawal with the given index.
+    pub fn get_withdrawal_storage_key(&self, index: u64) -> StorageKey {
+        StorageKey::new(self.withdrawals.prefix(), &index, self.withdrawals.codec())
+    }
+
+    /// Returns a merkle proof of the queued withdrawal with the given index against
+    /// the latest state root of the storage, or [`None`] if there is no such withdrawal.
+    pub fn get_withdrawal_proof(
+        &self,
+        index: u64,
+        storage: &C::Storage,
+    ) -> anyhow::Result<Option<WithdrawalProofResponse<C>>> {
+        // The withdrawal and its proof are read from the same version of the state
+        let storage = storage.pin_latest_version();
+        let mut working_set = WorkingSet::<C>::new(storage.clone());
+        let Some(withdrawal) = self.get_withdrawal(index, &mut working_set) else {
+            return Ok(None);
+        };
+
+        let version = storage.latest_version();
+        let state_root = storage.get_root_hash(version)?;
+        let storage_proof = storage.get_with_proof(self.get_withdrawal_storage_key(index));
+        let value = storage_proof
+            .value
+            .ok_or_else(|| anyhow::anyhow!("Withdrawal {} is missing from the proof", index))?;
+
+        Ok(Some(WithdrawalProofResponse {
+            withdrawal,
+            version,
+            state_root: hex::encode(state_root.as_ref()),
+            key: hex::encode(storage_proof.key.as_ref()),
+            value: hex::encode(value.value()),
+            proof: hex::encode(borsh::to_vec(&storage_proof.proof)?),
+        }))
+    }
+}
+
+/// Creates the `bridge_getWithdrawalProof` RPC method, which returns the
+/// [`WithdrawalProofResponse`] of the withdrawal with the given index, or `null`.
+///
+/// Proofs need a [`NativeStorage`], so unlike the other bridge queries the method
+/// isn't exposed by the runtime and has to be registered by the node.
+pub fn get_withdrawal_proof_rpc<C, Da>(
+    storage: C::Storage,
+) -> Result<RpcModule<C::Storage>, jsonrpsee::core::Error>
+where
+    C: Context,
+    Da: DaSpec,
+    C::Storage: NativeStorage + Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(storage);
+    rpc.register_method("bridge_getWithdrawalProof", |params, storage| {
+        let index: u64 = params.one()?;
+        Bridge::<C, Da>::default()
+            .get_withdrawal_proof(index, storage)
+            .map_err(|e| {
+                jsonrpsee::types::ErrorObjectOwned::owned(
+                    jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                    e.to_string(),
+                    None::<()>,
+                )
+            })
+    })?;
+    Ok(rpc)
+}
@@ -0,0 +1,117 @@
+use helpers::*;
+use sov_bank::Bank;
+use sov_bridge::{Bridge, DepositRecord};
+use sov_mock_da::{MockAddress, MockBlob};
+use sov_modules_api::WorkingSet;
+use sov_prover_storage_manager::new_orphan_storage;
+
+mod helpers;
+
+#[test]
+fn deposits_are_minted_once() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bridge = setup(working_set);
+    let bank = Bank::<C>::default();
+
+    let token_address = bridge.get_token_address(working_set).unwrap();
+    let recipient = generate_address("recipient");
+    assert!(bridge.is_deposit_sender(&MockAddress::from(DEPOSIT_DA_ADDRESS), working_set));
+    assert!(!bridge.is_deposit_sender(&MockAddress::from(OTHER_DA_ADDRESS), working_set));
+
+    let deposits = [
+        deposit(0, recipient, 100, DEPOSIT_DA_HASH),
+        deposit(1, recipient, 50, DEPOSIT_DA_HASH),
+    ];
+    let mut blob = deposit_blob(&deposits, DEPOSIT_DA_ADDRESS, [1; 32]);
+    bridge.process_deposits(&mut blob, DEPOSIT_SLOT, working_set);
+
+    assert_eq!(
+        Some(150),
+        bank.get_balance_of(recipient, token_address, working_set)
+    );
+    assert_eq!(
+        Some(DepositRecord {
+            recipient,
+            amount: 50,
+            blob_hash: [1; 32],
+            slot_height: DEPOSIT_SLOT,
+        }),
+        bridge.get_deposit([1; 32], working_set)
+    );
+
+    // Replaying the deposits, even from another blob, doesn't mint them again
+    let mut replayed_blob = deposit_blob(&deposits, DEPOSIT_DA_ADDRESS, [2; 32]);
+    bridge.process_deposits(&mut replayed_blob, DEPOSIT_SLOT + 1, working_set);
+    assert_eq!(
+        Some(150),
+        bank.get_balance_of(recipient, token_address, working_set)
+    );
+    assert_eq!(
+        Some([1; 32]),
+        bridge
+            .get_deposit([0; 32], working_set)
+            .map(|d| d.blob_hash)
+    );
+    assert_eq!(None, bridge.get_deposit([2; 32], working_set));
+}
+
+#[test]
+fn unproven_deposits_are_not_minted() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bridge = setup(working_set);
+    let bank = Bank::<C>::default();
+
+    let token_address = bridge.get_token_address(working_set).unwrap();
+    let recipient = generate_address("recipient");
+
+    // The deposit is proven against a DA block which is not the one of its slot
+    let wrong_block = deposit(0, recipient, 100, [11; 32]);
+    // The deposit claims a slot which was not processed yet
+    let mut future_slot = deposit(1, recipient, 100, DEPOSIT_DA_HASH);
+    future_slot.slot_height = DEPOSIT_SLOT + 1;
+    // The proof is not a deposit at all
+    let mut malformed = deposit(2, recipient, 100, DEPOSIT_DA_HASH);
+    malformed.proof.truncate(10);
+
+    let mut blob = deposit_blob(
+        &[wrong_block, future_slot, malformed],
+        DEPOSIT_DA_ADDRESS,
+        [1; 32],
+    );
+    bridge.process_deposits(&mut blob, DEPOSIT_SLOT, working_set);
+
+    assert_eq!(
+        None,
+        bank.get_balance_of(recipient, token_address, working_set)
+    );
+    for id in 0..3 {
+        assert_eq!(None, bridge.get_deposit([id; 32], working_set));
+    }
+}
+
+#[test]
+fn malformed_deposit_blob_is_discarded() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bridge = setup(working_set);
+
+    let mut blob = MockBlob::new(
+        vec![1, 2, 3],
+        MockAddress::from(DEPOSIT_DA_ADDRESS),
+        [1; 32],
+    );
+    bridge.process_deposits(&mut blob, DEPOSIT_SLOT, working_set);
+    assert_eq!(None, bridge.get_deposit([0; 32], working_set));
+}
+
+#[test]
+fn bridge_without_genesis_is_disabled() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let bridge = Bridge::<C, Da>::default();
+
+    assert!(!bridge.is_deposit_sender(&MockAddress::from(DEPOSIT_DA_ADDRESS), working_set));
+    assert_eq!(None, bridge.get_token_address(working_set));
+}
@@ -0,0 +1,97 @@
+use sov_bridge::{Bridge, BridgeConfig, Deposit};
+use sov_chain_state::{ChainState, ChainStateConfig};
+use sov_mock_da::{
+    MockAddress, MockBlob, MockBlockHeader, MockDaSpec, MockDeposit, MockValidityCond,
+};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::{Address, KernelModule, KernelWorkingSet, Module, Spec, WorkingSet};
+use sov_modules_core::runtime::capabilities::mocks::MockKernel;
+use sov_state::Storage;
+
+pub type C = DefaultContext;
+pub type Da = MockDaSpec;
+
+pub const DEPOSIT_DA_ADDRESS: [u8; 32] = [5; 32];
+#[allow(dead_code)]
+pub const OTHER_DA_ADDRESS: [u8; 32] = [6; 32];
+pub const BRIDGE_SCRIPT: [u8; 3] = [0x00, 0x14, 0x05];
+pub const DEPOSIT_SLOT: u64 = 10;
+/// The hash of the DA block the slot `DEPOSIT_SLOT` is built from.
+pub const DEPOSIT_DA_HASH: [u8; 32] = [10; 32];
+
+pub fn generate_address(key: &str) -> <C as Spec>::Address {
+    let hash: [u8; 32] = <C as Spec>::Hasher::digest(key.as_bytes()).into();
+    Address::from(hash)
+}
+
+pub fn bridge_config() -> BridgeConfig<Da> {
+    BridgeConfig {
+        deposit_address: MockAddress::from(DEPOSIT_DA_ADDRESS),
+        bridge_script: BRIDGE_SCRIPT.to_vec(),
+        token_name: "sov-bridged-token".to_owned(),
+        salt: 0,
+    }
+}
+
+/// Runs the genesis of the bridge, and processes the slot `DEPOSIT_SLOT`, built from
+/// the DA block with hash `DEPOSIT_DA_HASH`.
+pub fn setup(working_set: &mut WorkingSet<C>) -> Bridge<C, Da> {
+    let chain_state = ChainState::<C, Da>::default();
+    chain_state
+        .genesis(
+            &ChainStateConfig {
+                initial_slot_height: DEPOSIT_SLOT - 1,
+                current_time: Default::default(),
+                gas_price: vec![0, 0],
+            },
+            working_set,
+        )
+        .unwrap();
+    let header = MockBlockHeader {
+        prev_hash: [9; 32].into(),
+        hash: DEPOSIT_DA_HASH.into(),
+        height: DEPOSIT_SLOT,
+        time: Default::default(),
+    };
+    let genesis_root: <<C as Spec>::Storage as Storage>::Root = [0; 32].into();
+    let kernel = MockKernel::<C, Da>::new(DEPOSIT_SLOT, DEPOSIT_SLOT);
+    chain_state.begin_slot_hook(
+        &header,
+        &MockValidityCond::default(),
+        &genesis_root,
+        &mut KernelWorkingSet::from_kernel(&kernel, working_set),
+    );
+
+    let bridge = Bridge::<C, Da>::default();
+    bridge.genesis(&bridge_config(), working_set).unwrap();
+    bridge
+}
+
+/// A deposit of `amount` to `recipient`, proven against the DA block with hash `block_hash`.
+pub fn deposit(
+    id: u8,
+    recipient: <C as Spec>::Address,
+    amount: u64,
+    block_hash: [u8; 32],
+) -> Deposit {
+    let proof = MockDeposit {
+        block_hash: block_hash.into(),
+        bridge_script: BRIDGE_SCRIPT.to_vec(),
+        id: [id; 32],
+        amount,
+        recipient: recipient.as_ref().to_vec(),
+    };
+    Deposit {
+        slot_height: DEPOSIT_SLOT,
+        proof: borsh::to_vec(&proof).unwrap(),
+    }
+}
+
+pub fn deposit_blob(deposits: &[Deposit], sender: [u8; 32], hash: [u8; 32]) -> MockBlob {
+    MockBlob::new(
+        borsh::to_vec(&deposits.to_vec()).unwrap(),
+        MockAddress::from(sender),
+        hash,
+    )
+}
@@ -0,0 +1,110 @@
+use borsh::BorshDeserialize;
+use helpers::*;
+use sov_bank::Bank;
+use sov_bridge::{CallMessage, Withdrawal};
+use sov_modules_api::{Context, Module, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::storage::{NativeStorage, Storage};
+
+mod helpers;
+
+const DA_RECIPIENT: [u8; 32] = [7; 32];
+
+#[test]
+fn withdrawals_are_queued_and_proven() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage.clone());
+    let bridge = setup(&mut working_set);
+    let bank = Bank::<C>::default();
+
+    let token_address = bridge.get_token_address(&mut working_set).unwrap();
+    let user = generate_address("user");
+    let mut blob = deposit_blob(
+        &[deposit(0, user, 100, DEPOSIT_DA_HASH)],
+        DEPOSIT_DA_ADDRESS,
+        [1; 32],
+    );
+    bridge.process_deposits(&mut blob, DEPOSIT_SLOT, &mut working_set);
+
+    let context = C::new(user, generate_address("sequencer"), DEPOSIT_SLOT + 1);
+    let withdraw = |amount, da_recipient: Vec<u8>| CallMessage::Withdraw {
+        amount,
+        da_recipient,
+    };
+
+    // Withdrawals must be valid before any token is burnt
+    assert!(bridge
+        .call(
+            withdraw(0, DA_RECIPIENT.to_vec()),
+            &context,
+            &mut working_set
+        )
+        .is_err());
+    assert!(bridge
+        .call(withdraw(10, vec![7; 3]), &context, &mut working_set)
+        .is_err());
+    assert!(bridge
+        .call(
+            withdraw(101, DA_RECIPIENT.to_vec()),
+            &context,
+            &mut working_set
+        )
+        .is_err());
+    assert_eq!(0, bridge.get_withdrawal_count(&mut working_set));
+
+    bridge
+        .call(
+            withdraw(40, DA_RECIPIENT.to_vec()),
+            &context,
+            &mut working_set,
+        )
+        .expect("Withdraw call failed");
+    assert_eq!(
+        Some(60),
+        bank.get_balance_of(user, token_address, &mut working_set)
+    );
+    let expected_withdrawal = Withdrawal::<C> {
+        sender: user,
+        da_recipient: DA_RECIPIENT.to_vec(),
+        amount: 40,
+        slot_height: DEPOSIT_SLOT + 1,
+    };
+    assert_eq!(1, bridge.get_withdrawal_count(&mut working_set));
+    assert_eq!(
+        Some(expected_withdrawal.clone()),
+        bridge.get_withdrawal(0, &mut working_set)
+    );
+
+    // The proof is generated against the committed state
+    let (reads_writes, witness) = working_set.checkpoint().freeze();
+    let state_root = storage
+        .validate_and_commit(reads_writes, &witness)
+        .expect("Should be able to commit");
+
+    let response = bridge
+        .get_withdrawal_proof(0, &storage)
+        .unwrap()
+        .expect("The withdrawal must be proven");
+    assert_eq!(expected_withdrawal, response.withdrawal);
+    assert_eq!(storage.latest_version(), response.version);
+    assert_eq!(hex::encode(state_root.as_ref()), response.state_root);
+
+    let proof = <<C as Spec>::Storage as Storage>::Proof::try_from_slice(
+        &hex::decode(response.proof).unwrap(),
+    )
+    .unwrap();
+    let mut storage_proof = storage.get_with_proof(bridge.get_withdrawal_storage_key(0));
+    storage_proof.proof = proof;
+    let (key, value) = <C as Spec>::Storage::open_proof(state_root, storage_proof).unwrap();
+    assert_eq!(hex::encode(key.as_ref()), response.key);
+    assert_eq!(
+        response.value,
+        hex::encode(value.expect("The withdrawal must be stored").value())
+    );
+    let stored: Withdrawal<C> =
+        Withdrawal::try_from_slice(&hex::decode(&response.value).unwrap()).unwrap();
+    assert_eq!(expected_withdrawal, stored);
+
+    assert!(bridge.get_withdrawal_proof(1, &storage).unwrap().is_none());
+}
@@ -1,9 +1,10 @@
+use sov_modules_api::da::BlockHeaderTrait;
 use sov_modules_api::prelude::*;
 use sov_modules_api::WorkingSet;
 use sov_state::storage::KernelWorkingSet;
 use sov_state::Storage;
 
-use crate::{ChainState, StateTransitionId, TransitionHeight};
+use crate::{ChainState, SlotDaInfo, StateTransitionId, TransitionHeight};
 
 impl<C, Da> ChainState<C, Da>
 where
@@ -27,4 +28,24 @@ where
         self.historical_transitions
             .set(&height, &transition, working_set);
     }
+
+    /// Record the DA block of the current slot, and map its DA height to the slot height
+    pub(crate) fn store_slot_da_info(
+        &self,
+        slot_header: &Da::BlockHeader,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let height = self
+            .true_height
+            .get(working_set)
+            .expect("Block height must be set");
+        let info = SlotDaInfo {
+            da_height: slot_header.height(),
+            da_block_hash: slot_header.hash(),
+            time: slot_header.time(),
+        };
+        self.slot_da_info.set(&height, &info, working_set);
+        self.rollup_heights
+            .set(&slot_header.height(), &height, working_set);
+    }
 }
@@ -12,6 +12,8 @@ pub struct ChainStateConfig {
     pub initial_slot_height: TransitionHeight,
     /// The time at genesis
     pub current_time: Time,
+    /// The gas price, one value per gas dimension, charged for the transactions of every batch
+    pub gas_price: Vec<u64>,
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
@@ -27,6 +29,8 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
             .set(&config.initial_slot_height, working_set);
 
         self.time.set_genesis(&config.current_time, working_set);
+
+        self.gas_price.set(&config.gas_price, working_set);
         Ok(())
     }
 }
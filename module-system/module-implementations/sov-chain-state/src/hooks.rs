@@ -46,6 +46,7 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
 
         self.increment_true_slot_height(working_set);
         self.time.set_current(&slot_header.time(), working_set);
+        self.store_slot_da_info(slot_header, working_set.inner);
 
         self.in_progress_transition.set(
             &TransitionInProgress {
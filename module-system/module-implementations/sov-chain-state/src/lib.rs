@@ -22,7 +22,9 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use sov_modules_api::da::Time;
 use sov_modules_api::prelude::*;
-use sov_modules_api::{DaSpec, Error, KernelModuleInfo, ValidityConditionChecker, WorkingSet};
+use sov_modules_api::{
+    DaSpec, Error, GasUnit, KernelModuleInfo, ValidityConditionChecker, WorkingSet,
+};
 use sov_state::codec::BcsCodec;
 use sov_state::storage::kernel_state::VersionReader;
 use sov_state::storage::KernelWorkingSet;
@@ -107,6 +109,17 @@ impl<Da: DaSpec> TransitionInProgress<Da> {
     }
 }
 
+/// The DA block a rollup slot was built from.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SlotDaInfo<Da: DaSpec> {
+    /// The height of the DA block.
+    pub da_height: u64,
+    /// The hash of the DA block.
+    pub da_block_hash: Da::SlotHash,
+    /// The timestamp of the DA block.
+    pub time: Time,
+}
+
 /// A new module:
 /// - Must derive `ModuleInfo`
 /// - Must contain `[address]` field
@@ -147,6 +160,14 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
         BcsCodec,
     >,
 
+    /// Rollup slot height => the DA block the slot was built from.
+    #[state]
+    slot_da_info: sov_modules_api::StateMap<TransitionHeight, SlotDaInfo<Da>, BcsCodec>,
+
+    /// DA block height => the rollup slot height built from that DA block.
+    #[state]
+    rollup_heights: sov_modules_api::StateMap<u64, TransitionHeight>,
+
     /// The transition that is currently processed
     #[state]
     in_progress_transition: sov_modules_api::KernelStateValue<TransitionInProgress<Da>, BcsCodec>,
@@ -161,6 +182,10 @@ pub struct ChainState<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec>
     // TODO: This should be made read-only
     #[state]
     genesis_height: sov_modules_api::StateValue<TransitionHeight>,
+
+    /// The gas price charged for the transactions of every batch, one value per gas dimension.
+    #[state]
+    gas_price: sov_modules_api::StateValue<Vec<u64>>,
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
@@ -181,6 +206,44 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
             .expect("Time must be set at initialization")
     }
 
+    /// Returns the DA block the slot with the given height was built from, if that slot was processed.
+    pub fn get_slot_da_info(
+        &self,
+        slot_height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<SlotDaInfo<Da>> {
+        self.slot_da_info.get(&slot_height, working_set)
+    }
+
+    /// Returns the timestamp of the DA block the slot with the given height was built from.
+    pub fn get_slot_time(
+        &self,
+        slot_height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Time> {
+        self.get_slot_da_info(slot_height, working_set)
+            .map(|info| info.time)
+    }
+
+    /// Returns the hash of the DA block the slot with the given height was built from.
+    pub fn get_slot_da_block_hash(
+        &self,
+        slot_height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Da::SlotHash> {
+        self.get_slot_da_info(slot_height, working_set)
+            .map(|info| info.da_block_hash)
+    }
+
+    /// Returns the height of the rollup slot built from the DA block with the given height.
+    pub fn get_rollup_height(
+        &self,
+        da_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<TransitionHeight> {
+        self.rollup_heights.get(&da_height, working_set)
+    }
+
     /// Return the genesis hash of the module.
     pub fn get_genesis_hash(
         &self,
@@ -194,6 +257,13 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
         self.genesis_height.get(working_set)
     }
 
+    /// Returns the gas price of the batches applied in the current slot.
+    /// The price is zero if it wasn't set.
+    pub fn get_gas_price(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        let gas_price = self.gas_price.get(working_set).unwrap_or_default();
+        C::GasUnit::from_arbitrary_dimensions(&gas_price)
+    }
+
     /// Returns the transition in progress of the module.
     pub fn get_in_progress_transition(
         &self,
@@ -1,11 +1,12 @@
-// use jsonrpsee::core::RpcResult;
+use jsonrpsee::core::RpcResult;
+use jsonrpsee::RpcModule;
 use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, DaSpec, WorkingSet};
 
-// use sov_modules_api::WorkingSet;
-use crate::ChainState;
+use crate::{ChainState, SlotDaInfo, TransitionHeight};
 
 #[rpc_gen(client, server, namespace = "chainState")]
-impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da> {
+impl<C: Context, Da: DaSpec> ChainState<C, Da> {
     // TODO: Re-enable this RPC method once the `KernelWorkingSet` type is removed
     // /// Get the height of the current slot.
     // /// Panics if the slot height is not set
@@ -16,4 +17,52 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> ChainState<C, Da>
     // ) -> RpcResult<TransitionHeight> {
     //     Ok(self.get_slot_height(working_set))
     // }
+
+    /// Returns the height, hash and timestamp of the DA block the rollup slot
+    /// with the given height was built from, or nothing if that slot wasn't processed.
+    #[rpc_method(name = "getSlotDaInfo")]
+    pub fn get_slot_da_info_rpc(
+        &self,
+        slot_height: TransitionHeight,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<SlotDaInfo<Da>>> {
+        Ok(self.get_slot_da_info(slot_height, working_set))
+    }
+
+    /// Returns the height of the rollup slot built from the DA block with the given height,
+    /// or nothing if that DA block wasn't processed.
+    #[rpc_method(name = "getRollupHeight")]
+    pub fn get_rollup_height_rpc(
+        &self,
+        da_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<TransitionHeight>> {
+        Ok(self.get_rollup_height(da_height, working_set))
+    }
+}
+
+/// Creates the `chainState_getSlotDaInfo` and `chainState_getRollupHeight` RPC methods.
+///
+/// The chain state is a kernel module, so its queries aren't exposed by the runtime
+/// and have to be registered by the node.
+pub fn get_chain_state_rpc<C, Da>(
+    storage: C::Storage,
+) -> Result<RpcModule<C::Storage>, jsonrpsee::core::Error>
+where
+    C: Context,
+    Da: DaSpec,
+    C::Storage: Send + Sync + 'static,
+{
+    let mut rpc = RpcModule::new(storage);
+    rpc.register_method("chainState_getSlotDaInfo", |params, storage| {
+        let slot_height: TransitionHeight = params.one()?;
+        ChainState::<C, Da>::default()
+            .get_slot_da_info_rpc(slot_height, &mut WorkingSet::new_pinned(storage.clone()))
+    })?;
+    rpc.register_method("chainState_getRollupHeight", |params, storage| {
+        let da_height: u64 = params.one()?;
+        ChainState::<C, Da>::default()
+            .get_rollup_height_rpc(da_height, &mut WorkingSet::new_pinned(storage.clone()))
+    })?;
+    Ok(rpc)
 }
@@ -1,6 +1,11 @@
+use sov_mock_da::{MockBlockHeader, MockDaSpec, MockValidityCond};
 use sov_modules_api::da::{NanoSeconds, Time};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{KernelModule, KernelWorkingSet, WorkingSet};
+use sov_modules_core::capabilities::mocks::MockKernel;
+use sov_prover_storage_manager::new_orphan_storage;
 
-use crate::ChainStateConfig;
+use crate::{ChainState, ChainStateConfig, SlotDaInfo};
 
 #[test]
 fn test_config_serialization() {
@@ -8,6 +13,7 @@ fn test_config_serialization() {
     let config = ChainStateConfig {
         initial_slot_height: 1,
         current_time: time,
+        gas_price: vec![2, 3],
     };
 
     let data = r#"
@@ -16,9 +22,76 @@ fn test_config_serialization() {
         "current_time":{
             "secs":2,
             "nanos":3
-        }
+        },
+        "gas_price":[2,3]
     }"#;
 
     let parsed_config: ChainStateConfig = serde_json::from_str(data).unwrap();
     assert_eq!(config, parsed_config)
 }
+
+#[test]
+fn test_gas_price_is_set_at_genesis() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+
+    // The price is zero before genesis
+    assert_eq!([0, 0], chain_state.get_gas_price(&mut working_set));
+
+    let config = ChainStateConfig {
+        initial_slot_height: 0,
+        current_time: Default::default(),
+        gas_price: vec![2, 3],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    assert_eq!([2, 3], chain_state.get_gas_price(&mut working_set));
+}
+
+#[test]
+fn test_slot_da_info_is_recorded() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let chain_state = ChainState::<DefaultContext, MockDaSpec>::default();
+    let config = ChainStateConfig {
+        initial_slot_height: 5,
+        current_time: Default::default(),
+        gas_price: vec![0, 0],
+    };
+    chain_state.genesis(&config, &mut working_set).unwrap();
+
+    let header = MockBlockHeader {
+        prev_hash: [0; 32].into(),
+        hash: [1; 32].into(),
+        height: 20,
+        time: Time::from_secs(1_000),
+    };
+    {
+        let kernel = MockKernel::<DefaultContext, MockDaSpec>::new(5, 5);
+        let mut kernel_working_set = KernelWorkingSet::from_kernel(&kernel, &mut working_set);
+        chain_state.begin_slot_hook(
+            &header,
+            &MockValidityCond::default(),
+            &[10u8; 32].into(),
+            &mut kernel_working_set,
+        );
+    }
+
+    // The slot built from the DA block is the one following genesis
+    assert_eq!(
+        Some(SlotDaInfo {
+            da_height: 20,
+            da_block_hash: [1; 32].into(),
+            time: Time::from_secs(1_000),
+        }),
+        chain_state.get_slot_da_info(6, &mut working_set)
+    );
+    assert_eq!(
+        Some(Time::from_secs(1_000)),
+        chain_state.get_slot_time(6, &mut working_set)
+    );
+    assert_eq!(Some(6), chain_state.get_rollup_height(20, &mut working_set));
+    assert_eq!(None, chain_state.get_slot_da_info(5, &mut working_set));
+    assert_eq!(None, chain_state.get_rollup_height(19, &mut working_set));
+}
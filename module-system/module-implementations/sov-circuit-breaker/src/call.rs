@@ -0,0 +1,132 @@
+use anyhow::Result;
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{
+    CallResponse, Context, EventEmitter, ModuleErrorCode, ModuleInfo, WorkingSet,
+};
+
+use crate::CircuitBreaker;
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-circuit-breaker` module. They can only be sent by the admin of the module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Rejects the call messages of a module, until it's unpaused.
+    PauseModule {
+        /// The name of the module, as returned by
+        /// [`DispatchCall::module_name`](sov_modules_api::DispatchCall::module_name).
+        module_name: String,
+    },
+    /// Accepts the call messages of a paused module again.
+    UnpauseModule {
+        /// The name of the module.
+        module_name: String,
+    },
+    /// Rejects the call messages of every module but the circuit breaker, until it's unpaused.
+    PauseAll,
+    /// Unpauses the runtime. The modules paused individually stay paused.
+    UnpauseAll,
+    /// Transfers the control of the module to a new admin.
+    SetAdmin {
+        /// The new admin.
+        admin: C::Address,
+    },
+}
+
+impl<C: Context> CircuitBreaker<C> {
+    fn ensure_admin(&self, context: &C, working_set: &mut WorkingSet<C>) -> Result<()> {
+        let admin = self
+            .admin
+            .get(working_set)
+            .ok_or_else(|| anyhow::anyhow!("The admin of the circuit breaker isn't set"))?;
+        if &admin != context.sender() {
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
+                "Only the admin {} can pause and unpause the modules, not {}",
+                admin,
+                context.sender()
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn pause_module(
+        &self,
+        module_name: String,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+        if module_name == self.prefix().module_name() {
+            return Err(
+                ModuleErrorCode::InvariantViolation.error("The circuit breaker can't pause itself")
+            );
+        }
+
+        let mut paused_modules = self.get_paused_modules(working_set);
+        if !paused_modules.contains(&module_name) {
+            paused_modules.push(module_name.clone());
+            self.paused_modules.set(&paused_modules, working_set);
+        }
+        working_set.emit_event(self, "pause_module", &module_name);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn unpause_module(
+        &self,
+        module_name: String,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+
+        let mut paused_modules = self.get_paused_modules(working_set);
+        paused_modules.retain(|paused| paused != &module_name);
+        self.paused_modules.set(&paused_modules, working_set);
+        working_set.emit_event(self, "unpause_module", &module_name);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn set_paused(
+        &self,
+        paused: bool,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+
+        self.paused.set(&paused, working_set);
+        let event = if paused { "pause_all" } else { "unpause_all" };
+        working_set.emit_event(self, event, &format!("by {}", context.sender()));
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn set_admin(
+        &self,
+        admin: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        self.ensure_admin(context, working_set)?;
+
+        working_set.emit_event(self, "set_admin", &format!("{}", admin));
+        self.admin.set(&admin, working_set);
+
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,61 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::CircuitBreaker;
+
+/// Genesis configuration for the [`CircuitBreaker`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct CircuitBreakerConfig<C: Context> {
+    /// The account allowed to pause and unpause the modules.
+    pub admin: C::Address,
+    /// The names of the modules paused at genesis.
+    #[serde(default)]
+    pub paused_modules: Vec<String>,
+}
+
+impl<C: Context> CircuitBreaker<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.admin.set(&config.admin, working_set);
+        self.paused.set(&false, working_set);
+        self.paused_modules.set(&config.paused_modules, working_set);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::utils::generate_address;
+
+    use crate::CircuitBreakerConfig;
+
+    #[test]
+    fn test_config_serialization() {
+        let config = CircuitBreakerConfig::<DefaultContext> {
+            admin: generate_address::<DefaultContext>("admin"),
+            paused_modules: vec!["Bank".to_owned()],
+        };
+
+        let data = format!(
+            r#"
+        {{
+            "admin":"{}",
+            "paused_modules":["Bank"]
+        }}"#,
+            config.admin
+        );
+
+        let parsed_config: CircuitBreakerConfig<DefaultContext> =
+            serde_json::from_str(&data).unwrap();
+        assert_eq!(config, parsed_config)
+    }
+}
@@ -0,0 +1,125 @@
+//! The `sov-circuit-breaker` module pauses the modules of the rollup, or the whole runtime,
+//! in an emergency.
+//!
+//! The runtime consults the module before every call message is dispatched, including the
+//! [`DeferredCall`](sov_modules_api::DeferredCall)s of the modules, and rejects the messages of
+//! the paused modules. The module is controlled by an admin, which can be a key, a multisig
+//! account or an account derived by the governance module.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleErrorCode, ModuleInfo, StateValue, WorkingSet};
+
+/// The error code of the call messages rejected while their module is paused.
+pub const PAUSED_ERROR: ModuleErrorCode = ModuleErrorCode::Custom(0);
+
+/// The `sov-circuit-breaker` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct CircuitBreaker<C: sov_modules_api::Context> {
+    /// The address of the `sov_circuit_breaker` module.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The account allowed to pause and unpause the modules.
+    #[state]
+    pub(crate) admin: StateValue<C::Address>,
+
+    /// Whether the whole runtime is paused.
+    #[state]
+    pub(crate) paused: StateValue<bool>,
+
+    /// The names of the paused modules.
+    #[state]
+    pub(crate) paused_modules: StateValue<Vec<String>>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for CircuitBreaker<C> {
+    type Context = C;
+
+    type Config = CircuitBreakerConfig<C>;
+
+    type CallMessage = CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::PauseModule { module_name } => {
+                self.pause_module(module_name, context, working_set)?
+            }
+            CallMessage::UnpauseModule { module_name } => {
+                self.unpause_module(module_name, context, working_set)?
+            }
+            CallMessage::PauseAll => self.set_paused(true, context, working_set)?,
+            CallMessage::UnpauseAll => self.set_paused(false, context, working_set)?,
+            CallMessage::SetAdmin { admin } => self.set_admin(admin, context, working_set)?,
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> CircuitBreaker<C> {
+    /// Checks whether a call message of the module named `module_name` can be dispatched, as
+    /// returned by [`DispatchCall::module_name`](sov_modules_api::DispatchCall::module_name).
+    /// The messages of the circuit breaker itself are always accepted, so that it can be
+    /// unpaused while the whole runtime is paused.
+    pub fn check_dispatch(
+        &self,
+        module_name: &str,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), Error> {
+        if module_name == self.prefix().module_name() {
+            return Ok(());
+        }
+        if self.is_paused(working_set) {
+            return Err(PAUSED_ERROR.error("The runtime is paused").into());
+        }
+        if self.is_module_paused(module_name, working_set) {
+            return Err(PAUSED_ERROR
+                .error(format!("Module {} is paused", module_name))
+                .into());
+        }
+        Ok(())
+    }
+
+    /// Returns the account allowed to pause and unpause the modules.
+    pub fn get_admin(&self, working_set: &mut WorkingSet<C>) -> Option<C::Address> {
+        self.admin.get(working_set)
+    }
+
+    /// Returns whether the whole runtime is paused.
+    pub fn is_paused(&self, working_set: &mut WorkingSet<C>) -> bool {
+        self.paused.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the names of the paused modules.
+    pub fn get_paused_modules(&self, working_set: &mut WorkingSet<C>) -> Vec<String> {
+        self.paused_modules.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns whether the module named `module_name` is paused.
+    pub fn is_module_paused(&self, module_name: &str, working_set: &mut WorkingSet<C>) -> bool {
+        self.get_paused_modules(working_set)
+            .iter()
+            .any(|paused| paused == module_name)
+    }
+}
@@ -0,0 +1,31 @@
+//! Defines rpc queries exposed by the circuit breaker module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::CircuitBreaker;
+
+/// The response type to the `getStatus` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct StatusResponse<C: Context> {
+    /// Whether the whole runtime is paused.
+    pub paused: bool,
+    /// The names of the paused modules.
+    pub paused_modules: Vec<String>,
+    /// The account allowed to pause and unpause the modules.
+    pub admin: Option<C::Address>,
+}
+
+#[rpc_gen(client, server, namespace = "circuitBreaker")]
+impl<C: Context> CircuitBreaker<C> {
+    /// Returns which modules are paused, and the admin of the circuit breaker.
+    #[rpc_method(name = "getStatus")]
+    pub fn status(&self, working_set: &mut WorkingSet<C>) -> RpcResult<StatusResponse<C>> {
+        Ok(StatusResponse {
+            paused: self.is_paused(working_set),
+            paused_modules: self.get_paused_modules(working_set),
+            admin: self.get_admin(working_set),
+        })
+    }
+}
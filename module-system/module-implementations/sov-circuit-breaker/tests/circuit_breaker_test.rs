@@ -0,0 +1,185 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins};
+use sov_circuit_breaker::{CallMessage, CircuitBreaker, CircuitBreakerConfig, PAUSED_ERROR};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    CallResponse, Context, DispatchCall, EncodeCall, Error, Genesis, MessageCodec, Spec, WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    pub bank: Bank<C>,
+    pub circuit_breaker: CircuitBreaker<C>,
+}
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    token: <C as Spec>::Address,
+    admin: <C as Spec>::Address,
+    alice: <C as Spec>::Address,
+    sequencer: <C as Spec>::Address,
+}
+
+impl TestSetup {
+    fn init(working_set: &mut WorkingSet<C>) -> Self {
+        let runtime = TestRuntime::<C>::default();
+        let admin = generate_address::<C>("admin");
+        let alice = generate_address::<C>("alice");
+        let sequencer = generate_address::<C>("sequencer");
+
+        let bank_config = BankConfig::with_token("sov-test-token", vec![(alice, 1000)]);
+        let circuit_breaker_config = CircuitBreakerConfig {
+            admin,
+            paused_modules: vec![],
+        };
+        runtime
+            .genesis(
+                &GenesisConfig::new(bank_config, circuit_breaker_config),
+                working_set,
+            )
+            .unwrap();
+
+        Self {
+            runtime,
+            token: get_genesis_token_address::<C>("sov-test-token", 0),
+            admin,
+            alice,
+            sequencer,
+        }
+    }
+
+    /// Dispatches the message like the STF blueprint, checking it with the circuit breaker.
+    fn dispatch(
+        &self,
+        sender: <C as Spec>::Address,
+        message: Vec<u8>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message = TestRuntime::<C>::decode_call(&message).unwrap();
+        let context = C::new(sender, self.sequencer, 1);
+        self.runtime.dispatch_call_and_deferred_checked(
+            message,
+            working_set,
+            &context,
+            &|message, working_set| {
+                self.runtime
+                    .circuit_breaker
+                    .check_dispatch(self.runtime.module_name(message), working_set)
+            },
+        )
+    }
+
+    fn call(
+        &self,
+        sender: <C as Spec>::Address,
+        message: CallMessage<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message = <TestRuntime<C> as EncodeCall<CircuitBreaker<C>>>::encode_call(message);
+        self.dispatch(sender, message, working_set)
+    }
+
+    fn transfer(
+        &self,
+        amount: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message =
+            <TestRuntime<C> as EncodeCall<Bank<C>>>::encode_call(sov_bank::CallMessage::Transfer {
+                to: self.admin,
+                coins: Coins {
+                    amount,
+                    token_address: self.token,
+                },
+            });
+        self.dispatch(self.alice, message, working_set)
+    }
+}
+
+fn assert_paused(result: Result<CallResponse, Error>) {
+    let error = result.unwrap_err();
+    assert_eq!(
+        Some(PAUSED_ERROR),
+        error.kind(),
+        "Unexpected error {}",
+        error
+    );
+}
+
+#[test]
+fn paused_module_rejects_calls() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    setup.transfer(10, working_set).expect("Transfer failed");
+
+    let pause = CallMessage::PauseModule {
+        module_name: "Bank".to_owned(),
+    };
+    let Error::Unauthorized(_) = setup
+        .call(setup.alice, pause.clone(), working_set)
+        .unwrap_err()
+    else {
+        panic!("Only the admin can pause the modules");
+    };
+    setup
+        .call(setup.admin, pause, working_set)
+        .expect("Pause call failed");
+    assert_eq!(
+        vec!["Bank".to_owned()],
+        setup
+            .runtime
+            .circuit_breaker
+            .get_paused_modules(working_set)
+    );
+    assert_paused(setup.transfer(10, working_set));
+
+    setup
+        .call(
+            setup.admin,
+            CallMessage::UnpauseModule {
+                module_name: "Bank".to_owned(),
+            },
+            working_set,
+        )
+        .expect("Unpause call failed");
+    setup.transfer(10, working_set).expect("Transfer failed");
+}
+
+#[test]
+fn paused_runtime_can_be_unpaused() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    let new_admin = generate_address::<C>("new_admin");
+
+    setup
+        .call(setup.admin, CallMessage::PauseAll, working_set)
+        .expect("Pause call failed");
+    assert!(setup.runtime.circuit_breaker.is_paused(working_set));
+    assert_paused(setup.transfer(10, working_set));
+
+    // The circuit breaker itself is never paused
+    setup
+        .call(
+            setup.admin,
+            CallMessage::SetAdmin { admin: new_admin },
+            working_set,
+        )
+        .expect("Set admin call failed");
+    let Error::Unauthorized(_) = setup
+        .call(setup.admin, CallMessage::UnpauseAll, working_set)
+        .unwrap_err()
+    else {
+        panic!("The previous admin can't unpause the runtime");
+    };
+    setup
+        .call(new_admin, CallMessage::UnpauseAll, working_set)
+        .expect("Unpause call failed");
+    setup.transfer(10, working_set).expect("Transfer failed");
+}
@@ -1,14 +1,15 @@
 use anyhow::Result;
-use reth_primitives::TransactionSignedEcRecovered;
+use reth_primitives::{Address, TransactionSignedEcRecovered, TxType, U256};
 use reth_revm::into_reth_log;
 use revm::primitives::{CfgEnv, EVMError, SpecId};
 use sov_modules_api::prelude::*;
 use sov_modules_api::{CallResponse, WorkingSet};
 
+use crate::error::rpc::{EthResult, RpcInvalidTransactionError};
 use crate::evm::db::EvmDb;
 use crate::evm::executor::{self};
 use crate::evm::primitive_types::{BlockEnv, Receipt, TransactionSignedAndRecovered};
-use crate::evm::{EvmChainConfig, RlpEvmTransaction};
+use crate::evm::{AccountInfo, DbAccount, EvmChainConfig, RlpEvmTransaction};
 use crate::experimental::PendingTransaction;
 use crate::Evm;
 
@@ -33,13 +34,39 @@ impl<C: sov_modules_api::Context> Evm<C> {
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
         let evm_tx_recovered: TransactionSignedEcRecovered = tx.try_into()?;
+        self.apply_transaction(evm_tx_recovered, working_set)?;
+        Ok(CallResponse::default())
+    }
+
+    /// Executes the transaction in the pending block and adds it to the pending transactions.
+    /// Transactions which can't be included in the block, like the ones with an invalid nonce, are skipped.
+    ///
+    /// The transaction pays the L1 (DA) fee of its bytes to the coinbase before it's executed, so it
+    /// can't spend the balance it needs for it. Transactions which can't pay it are skipped too.
+    /// Transactions of an unsupported type are rejected with an error.
+    pub(crate) fn apply_transaction(
+        &self,
+        evm_tx_recovered: TransactionSignedEcRecovered,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        ensure_supported_tx_type(evm_tx_recovered.tx_type())?;
+
         let block_env = self
             .block_env
             .get(working_set)
             .expect("Pending block must be set");
 
-        let cfg = self.cfg.get(working_set).expect("Evm config must be set");
+        let cfg = self
+            .chain_config(working_set)
+            .expect("Evm config must be set");
+        // The fee only depends on the transaction, so it's the same whether its batch is
+        // soft-confirmed or read from the DA layer
+        let l1_fee = cfg.tx_l1_fee(evm_tx_recovered.envelope_encoded().len() as u64);
         let cfg_env = get_cfg_env(&block_env, cfg, None);
+        let signer = evm_tx_recovered.signer();
+        if !self.transfer_l1_fee(signer, block_env.coinbase, l1_fee.fee, working_set) {
+            return Ok(());
+        }
 
         let evm_db: EvmDb<'_, C> = self.get_db(working_set);
         let result = executor::execute_tx(evm_db, &block_env, &evm_tx_recovered, cfg_env);
@@ -70,14 +97,17 @@ impl<C: sov_modules_api::Context> Evm<C> {
                     gas_used,
                     log_index_start,
                     error: None,
+                    l1_fee,
                 }
             }
             // Adopted from https://github.com/paradigmxyz/reth/blob/main/crates/payload/basic/src/lib.rs#L884
             Err(err) => {
+                // The state of a failed execution isn't committed, so the fee can be refunded
+                self.transfer_l1_fee(block_env.coinbase, signer, l1_fee.fee, working_set);
                 return match err {
                     EVMError::Transaction(_) => {
                         // This is a transactional error, so we can skip it without doing anything.
-                        Ok(CallResponse::default())
+                        Ok(())
                     }
                     err => {
                         // This is a fatal error, so we need to return it.
@@ -89,7 +119,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
         let pending_transaction = PendingTransaction {
             transaction: TransactionSignedAndRecovered {
-                signer: evm_tx_recovered.signer(),
+                signer,
                 signed_transaction: evm_tx_recovered.into(),
                 block_number: block_env.number,
             },
@@ -99,7 +129,48 @@ impl<C: sov_modules_api::Context> Evm<C> {
         self.pending_transactions
             .push(&pending_transaction, working_set);
 
-        Ok(CallResponse::default())
+        Ok(())
+    }
+
+    /// Moves an L1 fee of `amount` wei from the balance of `from` to the one of `to`.
+    /// Returns `false`, without moving anything, if `from` can't pay it.
+    fn transfer_l1_fee(
+        &self,
+        from: Address,
+        to: Address,
+        amount: U256,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        if amount == U256::ZERO {
+            return true;
+        }
+
+        let Some(mut payer) = self.accounts.get(&from, working_set) else {
+            return false;
+        };
+        let Some(balance) = payer.info.balance.checked_sub(amount) else {
+            return false;
+        };
+        payer.info.balance = balance;
+        self.accounts.set(&from, &payer, working_set);
+
+        let mut payee = self.accounts.get(&to, working_set).unwrap_or_else(|| {
+            DbAccount::new_with_info(self.accounts.prefix(), to, AccountInfo::default())
+        });
+        payee.info.balance = payee.info.balance.saturating_add(amount);
+        self.accounts.set(&to, &payee, working_set);
+        true
+    }
+}
+
+/// Returns an error for the types of transactions the EVM of the rollup doesn't accept.
+///
+/// The rollup doesn't carry the blob sidecars of EIP-4844 transactions, so it can't make their
+/// blobs available: they are rejected, both by the mempool and when they reach the EVM.
+pub fn ensure_supported_tx_type(tx_type: TxType) -> EthResult<()> {
+    match tx_type {
+        TxType::EIP4844 => Err(RpcInvalidTransactionError::BlobTransactionsNotSupported.into()),
+        _ => Ok(()),
     }
 }
 
@@ -113,14 +184,14 @@ pub(crate) fn get_cfg_env(
 ) -> CfgEnv {
     let mut cfg_env = template_cfg.unwrap_or_default();
     cfg_env.chain_id = cfg.chain_id;
-    cfg_env.spec_id = get_spec_id(cfg.spec, block_env.number);
+    cfg_env.spec_id = cfg.spec_at(block_env.number);
     cfg_env.limit_contract_code_size = cfg.limit_contract_code_size;
     cfg_env
 }
 
 /// Get spec id for a given block number
-/// Returns the first spec id defined for block >= block_number
-pub(crate) fn get_spec_id(spec: Vec<(u64, SpecId)>, block_number: u64) -> SpecId {
+/// Returns the last spec id defined for a block <= block_number
+pub(crate) fn get_spec_id(spec: &[(u64, SpecId)], block_number: u64) -> SpecId {
     match spec.binary_search_by(|&(k, _)| k.cmp(&block_number)) {
         Ok(index) => spec[index].1,
         Err(index) => {
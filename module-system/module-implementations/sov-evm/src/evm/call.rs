@@ -139,9 +139,16 @@ pub(crate) fn prepare_call_env(block_env: &BlockEnv, request: CallRequest) -> Et
         nonce,
         access_list,
         chain_id,
+        blob_versioned_hashes,
+        max_fee_per_blob_gas,
         ..
     } = request;
 
+    // The EVM doesn't execute blob transactions, see `crate::ensure_supported_tx_type`
+    if !blob_versioned_hashes.is_empty() || max_fee_per_blob_gas.is_some() {
+        return Err(RpcInvalidTransactionError::BlobTransactionsNotSupported.into());
+    }
+
     let CallFees {
         max_priority_fee_per_gas,
         gas_price,
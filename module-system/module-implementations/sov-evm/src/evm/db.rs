@@ -1,8 +1,9 @@
+use std::cell::RefCell;
 use std::convert::Infallible;
 
 use reth_primitives::{Address, Bytes, H256};
 use revm::primitives::{AccountInfo as ReVmAccountInfo, Bytecode, B160, B256, U256};
-use revm::Database;
+use revm::{Database, DatabaseRef};
 use sov_modules_api::{StateMapAccessor, WorkingSet};
 use sov_state::codec::BcsCodec;
 
@@ -64,3 +65,32 @@ impl<'a, C: sov_modules_api::Context> Database for EvmDb<'a, C> {
         todo!("block_hash not yet implemented")
     }
 }
+
+/// Read-only view over [`EvmDb`], required by tracers that inspect the state after execution.
+pub(crate) struct EvmDbRef<'a, C: sov_modules_api::Context>(RefCell<EvmDb<'a, C>>);
+
+impl<'a, C: sov_modules_api::Context> EvmDbRef<'a, C> {
+    pub(crate) fn new(db: EvmDb<'a, C>) -> Self {
+        Self(RefCell::new(db))
+    }
+}
+
+impl<'a, C: sov_modules_api::Context> DatabaseRef for EvmDbRef<'a, C> {
+    type Error = Infallible;
+
+    fn basic(&self, address: B160) -> Result<Option<ReVmAccountInfo>, Self::Error> {
+        self.0.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.0.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage(&self, address: B160, index: U256) -> Result<U256, Self::Error> {
+        self.0.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash(&self, number: U256) -> Result<B256, Self::Error> {
+        self.0.borrow_mut().block_hash(number)
+    }
+}
@@ -13,12 +13,16 @@ use reth_revm::tracing::js::JsInspectorError;
 use reth_rpc_types::error::EthRpcErrorCode;
 use reth_rpc_types::{BlockError, CallInputError};
 use revm::primitives::{EVMError, ExecutionResult, Halt, InvalidHeader, OutOfGasError};
+use sov_modules_api::ArchivalVersionError;
 
 use super::pool::{
     Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolTransactionError,
 };
 use super::result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code};
 
+/// Error code for requests exceeding a limit of the node, as defined in EIP-1474.
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
 /// Result alias
 pub type EthResult<T> = Result<T, EthApiError>;
 
@@ -45,6 +49,12 @@ pub enum EthApiError {
     UnknownBlockOrTxIndex,
     #[error("Invalid block range")]
     InvalidBlockRange,
+    /// Thrown when a logs query spans more blocks than allowed
+    #[error("query exceeds max block range {0}")]
+    QueryExceedsMaxBlocks(u64),
+    /// Thrown when a logs query matches more logs than allowed
+    #[error("query exceeds max results {0}")]
+    QueryExceedsMaxResults(usize),
     /// An internal error where prevrandao is not set in the evm's environment
     #[error("Prevrandao not in th EVM's environment after merge")]
     PrevrandaoNotSet,
@@ -102,6 +112,9 @@ pub enum EthApiError {
     InternalJsTracerError(String),
     #[error(transparent)]
     CallInputError(#[from] CallInputError),
+    /// The state of the requested block is not available, for example because it was pruned.
+    #[error(transparent)]
+    ArchivalState(#[from] ArchivalVersionError),
 }
 
 impl From<EthApiError> for ErrorObject<'static> {
@@ -111,6 +124,7 @@ impl From<EthApiError> for ErrorObject<'static> {
             | EthApiError::InvalidTransactionSignature
             | EthApiError::EmptyRawTransactionData
             | EthApiError::InvalidBlockRange
+            | EthApiError::QueryExceedsMaxBlocks(_)
             | EthApiError::ConflictingFeeFieldsInRequest
             | EthApiError::Signing(_)
             | EthApiError::BothStateAndStateDiffInOverride(_)
@@ -122,9 +136,14 @@ impl From<EthApiError> for ErrorObject<'static> {
             | EthApiError::InvalidBlockData(_)
             | EthApiError::Internal(_)
             | EthApiError::TransactionNotFound => internal_rpc_err(error.to_string()),
-            EthApiError::UnknownBlockNumber | EthApiError::UnknownBlockOrTxIndex => {
+            EthApiError::UnknownBlockNumber
+            | EthApiError::UnknownBlockOrTxIndex
+            | EthApiError::ArchivalState(_) => {
                 rpc_error_with_code(EthRpcErrorCode::ResourceNotFound.code(), error.to_string())
             }
+            EthApiError::QueryExceedsMaxResults(_) => {
+                rpc_error_with_code(LIMIT_EXCEEDED_CODE, error.to_string())
+            }
             EthApiError::UnknownSafeOrFinalizedBlock => {
                 rpc_error_with_code(EthRpcErrorCode::UnknownBlock.code(), error.to_string())
             }
@@ -314,6 +333,10 @@ pub enum RpcInvalidTransactionError {
     /// Blob transaction is a create transaction
     #[error("blob transaction is a create transaction")]
     BlobTransactionIsCreate,
+    /// The rollup doesn't carry blob sidecars, so it rejects EIP-4844 transactions and requests
+    /// with blob fields instead of executing them without their blobs.
+    #[error("blob transactions are not supported: the rollup doesn't make blob data available")]
+    BlobTransactionsNotSupported,
 }
 
 impl RpcInvalidTransactionError {
@@ -2,51 +2,139 @@ use std::convert::Infallible;
 
 use reth_primitives::TransactionSignedEcRecovered;
 use reth_revm::tracing::{TracingInspector, TracingInspectorConfig};
-use revm::primitives::{CfgEnv, EVMError, Env, ExecutionResult, ResultAndState, TxEnv};
-use revm::{self, Database, DatabaseCommit};
+use revm::db::CacheDB;
+use revm::inspectors::NoOpInspector;
+use revm::primitives::specification::*;
+use revm::primitives::{CfgEnv, EVMError, EVMResult, Env, ExecutionResult, ResultAndState, TxEnv};
+use revm::{self, Database, DatabaseCommit, DatabaseRef, EVMImpl, Inspector, Transact};
 
 use super::conversions::create_tx_env;
+use super::precompiles::with_custom_precompiles;
 use super::primitive_types::BlockEnv;
 
+/// Runs the transaction of `env` on top of `db`, without committing its results.
+///
+/// The EVM gets the precompiles of the hardfork set in `env` together with the custom precompiles
+/// registered with [`crate::Evm::register_precompile`], so they're executed and charged for
+/// like the Ethereum ones.
+fn transact<DB: Database, const INSPECT: bool>(
+    env: &mut Env,
+    db: &mut DB,
+    inspector: &mut dyn Inspector<DB>,
+) -> EVMResult<DB::Error> {
+    let Some(precompiles) = with_custom_precompiles(revm::to_precompile_id(env.cfg.spec_id)) else {
+        return revm::evm_inner::<DB, INSPECT>(env, db, inspector).transact();
+    };
+
+    // Same as `revm::evm_inner`, with the extended precompile set
+    macro_rules! transact_with_spec {
+        ($spec:ident) => {
+            EVMImpl::<'_, $spec, DB, INSPECT>::new(db, env, inspector, precompiles).transact()
+        };
+    }
+    match env.cfg.spec_id {
+        SpecId::FRONTIER | SpecId::FRONTIER_THAWING => transact_with_spec!(FrontierSpec),
+        SpecId::HOMESTEAD | SpecId::DAO_FORK => transact_with_spec!(HomesteadSpec),
+        SpecId::TANGERINE => transact_with_spec!(TangerineSpec),
+        SpecId::SPURIOUS_DRAGON => transact_with_spec!(SpuriousDragonSpec),
+        SpecId::BYZANTIUM => transact_with_spec!(ByzantiumSpec),
+        SpecId::PETERSBURG | SpecId::CONSTANTINOPLE => transact_with_spec!(PetersburgSpec),
+        SpecId::ISTANBUL | SpecId::MUIR_GLACIER => transact_with_spec!(IstanbulSpec),
+        SpecId::BERLIN => transact_with_spec!(BerlinSpec),
+        SpecId::LONDON | SpecId::ARROW_GLACIER | SpecId::GRAY_GLACIER => {
+            transact_with_spec!(LondonSpec)
+        }
+        SpecId::MERGE => transact_with_spec!(MergeSpec),
+        SpecId::SHANGHAI => transact_with_spec!(ShanghaiSpec),
+        SpecId::CANCUN => transact_with_spec!(CancunSpec),
+        SpecId::LATEST => transact_with_spec!(LatestSpec),
+    }
+}
+
 pub(crate) fn execute_tx<DB: Database<Error = Infallible> + DatabaseCommit>(
-    db: DB,
+    mut db: DB,
     block_env: &BlockEnv,
     tx: &TransactionSignedEcRecovered,
     config_env: CfgEnv,
 ) -> Result<ExecutionResult, EVMError<Infallible>> {
-    let mut evm = revm::new();
-
-    let env = Env {
+    let mut env = Env {
         block: block_env.into(),
         cfg: config_env,
         tx: create_tx_env(tx),
     };
 
-    evm.env = env;
-    evm.database(db);
-    evm.transact_commit()
+    let ResultAndState { result, state } =
+        transact::<_, false>(&mut env, &mut db, &mut NoOpInspector)?;
+    db.commit(state);
+    Ok(result)
 }
 
 pub(crate) fn inspect<DB: Database<Error = Infallible> + DatabaseCommit>(
-    db: DB,
+    mut db: DB,
     block_env: &BlockEnv,
     tx: TxEnv,
     config_env: CfgEnv,
 ) -> Result<ResultAndState, EVMError<Infallible>> {
-    let mut evm = revm::new();
-
-    let env = Env {
+    let mut env = Env {
         cfg: config_env,
         block: block_env.into(),
         tx,
     };
 
-    evm.env = env;
-    evm.database(db);
-
     let config = TracingInspectorConfig::all();
 
     let mut inspector = TracingInspector::new(config);
 
-    evm.inspect(&mut inspector)
+    transact::<_, true>(&mut env, &mut db, &mut inspector)
+}
+
+pub(crate) fn inspect_with<DB: Database<Error = Infallible>, I: Inspector<DB>>(
+    mut db: DB,
+    block_env: &BlockEnv,
+    tx: TxEnv,
+    config_env: CfgEnv,
+    mut inspector: I,
+) -> Result<ResultAndState, EVMError<Infallible>> {
+    let mut env = Env {
+        cfg: config_env,
+        block: block_env.into(),
+        tx,
+    };
+
+    transact::<_, true>(&mut env, &mut db, &mut inspector)
+}
+
+/// Executes the same call multiple times with different gas limits, without committing its results.
+/// The state read by the first execution is cached and reused by the following ones.
+pub(crate) struct CachedExecutor<DB: DatabaseRef> {
+    env: Env,
+    db: CacheDB<DB>,
+}
+
+impl<DB: DatabaseRef> CachedExecutor<DB> {
+    pub(crate) fn new(db: DB, block_env: &BlockEnv, tx: TxEnv, config_env: CfgEnv) -> Self {
+        let env = Env {
+            cfg: config_env,
+            block: block_env.into(),
+            tx,
+        };
+        Self {
+            env,
+            db: CacheDB::new(db),
+        }
+    }
+
+    /// The transaction which is executed.
+    pub(crate) fn tx_env(&self) -> &TxEnv {
+        &self.env.tx
+    }
+
+    /// Executes the transaction with the given gas limit.
+    pub(crate) fn transact_with_gas_limit(
+        &mut self,
+        gas_limit: u64,
+    ) -> Result<ResultAndState, EVMError<DB::Error>> {
+        self.env.tx.gas_limit = gas_limit;
+        transact::<_, false>(&mut self.env, &mut self.db, &mut NoOpInspector)
+    }
 }
@@ -0,0 +1,77 @@
+//! The gas price oracle, a system contract exposing the fee parameters of the rollup to the
+//! other contracts, so that they can quote the fees of their users on-chain.
+//!
+//! The contract is predeployed at genesis, and the STF writes the parameters of the pending block
+//! to its storage at the beginning of every slot. Calling it with a slot number, ABI encoded as a
+//! `uint256`, returns the value of the slot: one of the `*_SLOT` constants of this module.
+
+use reth_primitives::hex_literal::hex;
+use reth_primitives::{keccak256, Address, Bytes, H160, U256};
+use sov_modules_api::StateMapAccessor;
+
+use super::db::EvmDb;
+use super::db_init::InitEvmDb;
+use super::{AccountInfo, EvmChainConfig};
+
+/// The address of the gas price oracle, the one of the `GasPriceOracle` of Optimism.
+pub const GAS_PRICE_ORACLE_ADDRESS: Address =
+    H160(hex!("420000000000000000000000000000000000000f"));
+
+/// The slot of the base fee of the current block, in wei.
+pub const BASE_FEE_SLOT: u64 = 0;
+/// The slot of the price of a unit of L1 (DA) gas, in wei.
+pub const L1_GAS_PRICE_SLOT: u64 = 1;
+/// The slot of the L1 gas spent to post a block besides its transactions.
+pub const L1_FEE_OVERHEAD_SLOT: u64 = 2;
+/// The slot of the scalar of the L1 fee, in millionths.
+pub const L1_FEE_SCALAR_SLOT: u64 = 3;
+
+/// `PUSH1 0 CALLDATALOAD SLOAD PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN`: returns the slot whose
+/// number is the first word of the call data.
+const CODE: [u8; 12] = hex!("6000355460005260206000f3");
+
+/// Deploys the gas price oracle, with the parameters of the genesis block.
+pub(crate) fn deploy<C: sov_modules_api::Context>(
+    mut evm_db: EvmDb<'_, C>,
+    cfg: &EvmChainConfig,
+    base_fee: u64,
+) {
+    let code = Bytes::from(CODE.to_vec());
+    let code_hash = keccak256(&code);
+    evm_db.insert_account_info(
+        GAS_PRICE_ORACLE_ADDRESS,
+        AccountInfo {
+            balance: U256::ZERO,
+            code_hash,
+            nonce: 1,
+        },
+    );
+    evm_db.insert_code(code_hash, code);
+    update(evm_db, cfg, base_fee);
+}
+
+/// Writes the parameters of a block with the given base fee to the storage of the gas price
+/// oracle. Chains started before the oracle existed don't have it, and are left untouched.
+pub(crate) fn update<C: sov_modules_api::Context>(
+    mut evm_db: EvmDb<'_, C>,
+    cfg: &EvmChainConfig,
+    base_fee: u64,
+) {
+    let Some(oracle) = evm_db
+        .accounts
+        .get(&GAS_PRICE_ORACLE_ADDRESS, evm_db.working_set)
+    else {
+        return;
+    };
+
+    for (slot, value) in [
+        (BASE_FEE_SLOT, base_fee),
+        (L1_GAS_PRICE_SLOT, cfg.l1_gas_price),
+        (L1_FEE_OVERHEAD_SLOT, cfg.l1_fee_overhead),
+        (L1_FEE_SCALAR_SLOT, cfg.l1_fee_scalar),
+    ] {
+        oracle
+            .storage
+            .set(&U256::from(slot), &U256::from(value), evm_db.working_set);
+    }
+}
@@ -11,11 +11,19 @@ mod db_commit;
 pub(crate) mod db_init;
 pub(crate) mod error;
 pub(crate) mod executor;
+pub(crate) mod gas_price_oracle;
+pub(crate) mod precompiles;
 pub(crate) mod primitive_types;
 #[cfg(test)]
 mod tests;
 
 pub(crate) use call::prepare_call_env;
+pub use gas_price_oracle::{
+    BASE_FEE_SLOT, GAS_PRICE_ORACLE_ADDRESS, L1_FEE_OVERHEAD_SLOT, L1_FEE_SCALAR_SLOT,
+    L1_GAS_PRICE_SLOT,
+};
+pub use precompiles::{CustomPrecompileFn, PrecompileRegistrationError};
+use primitive_types::L1Fee;
 pub use primitive_types::RlpEvmTransaction;
 use sov_state::codec::BcsCodec;
 
@@ -55,13 +63,34 @@ impl DbAccount {
         }
     }
 
-    fn create_storage_prefix(parent_prefix: &Prefix, address: Address) -> Prefix {
+    /// Creates an account whose storage doesn't share any slot with the storage of the account at `address`.
+    /// It is used to replace the whole state of an account in `eth_call` state overrides.
+    pub(crate) fn new_with_empty_storage(
+        parent_prefix: &Prefix,
+        address: Address,
+        info: AccountInfo,
+    ) -> Self {
+        let mut prefix = Self::create_storage_prefix(parent_prefix, address)
+            .as_aligned_vec()
+            .clone()
+            .into_inner();
+        prefix.extend_from_slice(b"/state_override");
+        Self {
+            info,
+            storage: StateMap::with_codec(Prefix::new(prefix), BcsCodec {}),
+        }
+    }
+
+    pub(crate) fn create_storage_prefix(parent_prefix: &Prefix, address: Address) -> Prefix {
         let mut prefix = parent_prefix.as_aligned_vec().clone().into_inner();
         prefix.extend_from_slice(&address.0);
         Prefix::new(prefix)
     }
 }
 
+/// The L1 fee scalar of `1`, as the scalars are expressed in millionths.
+pub const L1_FEE_SCALAR_PRECISION: u64 = 1_000_000;
+
 /// EVM Chain configuration
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct EvmChainConfig {
@@ -73,7 +102,7 @@ pub struct EvmChainConfig {
     /// By default it is 0x6000 (~25kb).
     pub limit_contract_code_size: Option<usize>,
 
-    /// List of EVM hardforks by block number
+    /// List of EVM hardforks by block number, sorted by block number and starting from block 0
     pub spec: Vec<(u64, SpecId)>,
 
     /// Coinbase where all the fees go
@@ -87,6 +116,18 @@ pub struct EvmChainConfig {
 
     /// Base fee params.
     pub base_fee_params: BaseFeeParams,
+
+    /// The base fee never goes below this value, however empty the blocks are.
+    pub min_base_fee: u64,
+
+    /// Price in wei of a unit of the L1 (DA) gas spent to post the transactions.
+    pub l1_gas_price: u64,
+
+    /// L1 gas spent to post a transaction besides its bytes.
+    pub l1_fee_overhead: u64,
+
+    /// Scalar of the L1 fee of the transactions, in millionths.
+    pub l1_fee_scalar: u64,
 }
 
 impl Default for EvmChainConfig {
@@ -99,6 +140,54 @@ impl Default for EvmChainConfig {
             block_gas_limit: reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT,
             block_timestamp_delta: 1,
             base_fee_params: BaseFeeParams::ethereum(),
+            min_base_fee: 0,
+            l1_gas_price: 0,
+            l1_fee_overhead: 0,
+            l1_fee_scalar: L1_FEE_SCALAR_PRECISION,
+        }
+    }
+}
+
+impl EvmChainConfig {
+    /// Returns the base fee of the block following a block with the given gas usage and base fee,
+    /// as defined by EIP-1559 with the configured base fee params and minimum base fee.
+    pub fn next_block_base_fee(&self, gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
+        reth_primitives::basefee::calculate_next_block_base_fee(
+            gas_used,
+            gas_limit,
+            base_fee,
+            self.base_fee_params,
+        )
+        .max(self.min_base_fee)
+    }
+
+    /// Returns the hardfork active at the given block: the last one of `spec` activated
+    /// at or before it.
+    pub fn spec_at(&self, block_number: u64) -> SpecId {
+        crate::call::get_spec_id(&self.spec, block_number)
+    }
+
+    /// Returns whether blocks have an EIP-1559 base fee at the given block, which is the
+    /// case from London.
+    pub fn has_base_fee_at(&self, block_number: u64) -> bool {
+        SpecId::enabled(self.spec_at(block_number), SpecId::LONDON)
+    }
+
+    /// Returns the L1 fee of posting a transaction of `tx_size` bytes, Optimism-style: 16 L1 gas
+    /// per byte plus the overhead of a transaction, priced at the L1 gas price and scaled by the
+    /// L1 fee scalar.
+    pub(crate) fn tx_l1_fee(&self, tx_size: u64) -> L1Fee {
+        let gas_used = tx_size
+            .saturating_mul(16)
+            .saturating_add(self.l1_fee_overhead);
+        // The product of three `u64`s fits in 192 bits, so it can't overflow
+        let fee =
+            U256::from(gas_used) * U256::from(self.l1_gas_price) * U256::from(self.l1_fee_scalar)
+                / U256::from(L1_FEE_SCALAR_PRECISION);
+        L1Fee {
+            fee,
+            gas_used,
+            scalar: self.l1_fee_scalar,
         }
     }
 }
@@ -0,0 +1,86 @@
+//! Precompiled contracts added by the rollup, on top of the ones of the Ethereum hardforks.
+
+use std::sync::RwLock;
+
+use reth_primitives::Address;
+use revm::precompile::{Precompile, Precompiles, SpecId as PrecompileSpecId};
+use revm::primitives::B160;
+
+/// The implementation of a custom precompiled contract. It gets the call data and the gas limit of the call,
+/// and returns the gas it used together with its output.
+pub type CustomPrecompileFn = revm::precompile::StandardPrecompileFn;
+
+/// The custom precompiles, by address. They are kept in registration order so that building
+/// the precompile set is deterministic.
+static CUSTOM_PRECOMPILES: RwLock<Vec<(B160, CustomPrecompileFn)>> = RwLock::new(Vec::new());
+
+/// The reasons a custom precompile can't be registered.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PrecompileRegistrationError {
+    /// The address is used by a precompile of an Ethereum hardfork.
+    #[error("address {0:?} is reserved for the precompiles of Ethereum")]
+    Reserved(Address),
+    /// Another custom precompile was registered at the address.
+    #[error("a precompile is already registered at address {0:?}")]
+    AlreadyRegistered(Address),
+}
+
+pub(crate) fn register(
+    address: Address,
+    precompile: CustomPrecompileFn,
+) -> Result<(), PrecompileRegistrationError> {
+    let address_b160 = B160::from(address.0);
+    if Precompiles::new(PrecompileSpecId::LATEST)
+        .fun
+        .contains_key(&address_b160)
+    {
+        return Err(PrecompileRegistrationError::Reserved(address));
+    }
+
+    let mut precompiles = CUSTOM_PRECOMPILES
+        .write()
+        .expect("Custom precompiles lock must not be poisoned");
+    if precompiles.iter().any(|(a, _)| *a == address_b160) {
+        return Err(PrecompileRegistrationError::AlreadyRegistered(address));
+    }
+    precompiles.push((address_b160, precompile));
+    Ok(())
+}
+
+pub(crate) fn registered_addresses() -> Vec<Address> {
+    CUSTOM_PRECOMPILES
+        .read()
+        .expect("Custom precompiles lock must not be poisoned")
+        .iter()
+        .map(|(address, _)| Address::from(address.0))
+        .collect()
+}
+
+/// Returns the addresses of the precompiles of the given hardfork and of the custom ones.
+pub(crate) fn addresses(spec: PrecompileSpecId) -> Vec<Address> {
+    Precompiles::new(spec)
+        .fun
+        .keys()
+        .map(|address| Address::from(address.0))
+        .chain(registered_addresses())
+        .collect()
+}
+
+/// Returns the precompiles of the given hardfork together with the custom ones,
+/// or `None` if no custom precompile is registered.
+pub(crate) fn with_custom_precompiles(spec: PrecompileSpecId) -> Option<Precompiles> {
+    let custom_precompiles = CUSTOM_PRECOMPILES
+        .read()
+        .expect("Custom precompiles lock must not be poisoned");
+    if custom_precompiles.is_empty() {
+        return None;
+    }
+
+    let mut precompiles = Precompiles::new(spec).clone();
+    for (address, precompile) in custom_precompiles.iter() {
+        precompiles
+            .fun
+            .insert(*address, Precompile::Standard(*precompile));
+    }
+    Some(precompiles)
+}
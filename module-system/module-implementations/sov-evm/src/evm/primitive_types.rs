@@ -1,6 +1,6 @@
 use std::ops::Range;
 
-use reth_primitives::{Address, Header, SealedHeader, TransactionSigned, H256};
+use reth_primitives::{Address, Header, SealedHeader, TransactionSigned, H256, U256};
 use revm::primitives::EVMError;
 
 #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Clone)]
@@ -117,4 +117,17 @@ pub(crate) struct Receipt {
     pub(crate) gas_used: u64,
     pub(crate) log_index_start: u64,
     pub(crate) error: Option<EVMError<u8>>,
+    /// The L1 (DA) fee paid by the transaction.
+    pub(crate) l1_fee: L1Fee,
+}
+
+/// The L1 (DA) fee of a transaction.
+#[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct L1Fee {
+    /// The fee in wei.
+    pub(crate) fee: U256,
+    /// The L1 gas the fee pays for.
+    pub(crate) gas_used: u64,
+    /// The scalar the fee was computed with, in millionths.
+    pub(crate) scalar: u64,
 }
@@ -1,7 +1,7 @@
 use std::convert::Infallible;
 
-use reth_primitives::TransactionKind;
-use revm::precompile::B160;
+use reth_primitives::{Address, TransactionKind};
+use revm::precompile::{PrecompileError, PrecompileResult, B160};
 use revm::primitives::{CfgEnv, ExecutionResult, Output, SpecId, KECCAK_EMPTY, U256};
 use revm::{Database, DatabaseCommit};
 use sov_modules_api::WorkingSet;
@@ -14,7 +14,7 @@ use crate::evm::primitive_types::BlockEnv;
 use crate::evm::AccountInfo;
 use crate::smart_contracts::SimpleStorageContract;
 use crate::tests::test_signer::TestSigner;
-use crate::Evm;
+use crate::{Evm, PrecompileRegistrationError};
 
 type C = sov_modules_api::default_context::DefaultContext;
 
@@ -123,6 +123,64 @@ fn simple_contract_execution<DB: Database<Error = Infallible> + DatabaseCommit +
     }
 }
 
+/// Returns its input, for a fixed cost.
+fn echo_precompile(input: &[u8], gas_limit: u64) -> PrecompileResult {
+    const COST: u64 = 100;
+    if gas_limit < COST {
+        return Err(PrecompileError::OutOfGas);
+    }
+    Ok((COST, input.to_vec()))
+}
+
+#[test]
+fn custom_precompile_execution() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set: WorkingSet<C> =
+        WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let evm = Evm::<C>::default();
+    let mut evm_db: EvmDb<'_, C> = evm.get_db(&mut working_set);
+
+    let precompile_address = Address::from_low_u64_be(0x0f00);
+    Evm::<C>::register_precompile(precompile_address, echo_precompile).unwrap();
+    assert!(Evm::<C>::custom_precompiles().contains(&precompile_address));
+    assert_eq!(
+        Err(PrecompileRegistrationError::AlreadyRegistered(
+            precompile_address
+        )),
+        Evm::<C>::register_precompile(precompile_address, echo_precompile)
+    );
+    // ecrecover
+    let reserved_address = Address::from_low_u64_be(1);
+    assert_eq!(
+        Err(PrecompileRegistrationError::Reserved(reserved_address)),
+        Evm::<C>::register_precompile(reserved_address, echo_precompile)
+    );
+
+    let dev_signer = TestSigner::new_random();
+    evm_db.insert_account_info(
+        dev_signer.address(),
+        AccountInfo {
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            nonce: 0,
+        },
+    );
+
+    let mut cfg_env = CfgEnv::default();
+    cfg_env.spec_id = SpecId::SHANGHAI;
+
+    let input = vec![1, 2, 3, 4];
+    let tx = dev_signer
+        .sign_default_transaction(TransactionKind::Call(precompile_address), input.clone(), 0)
+        .unwrap();
+    let tx = &tx.try_into().unwrap();
+    let result = executor::execute_tx(&mut evm_db, &BlockEnv::default(), tx, cfg_env).unwrap();
+
+    // The intrinsic cost of the transaction, its four non-zero bytes of calldata and the precompile cost
+    assert_eq!(21_000 + 4 * 16 + 100, result.gas_used());
+    assert_eq!(input, output(result).to_vec());
+}
+
 fn contract_address(result: &ExecutionResult) -> Option<B160> {
     match result {
         ExecutionResult::Success {
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use reth_primitives::constants::{EMPTY_RECEIPTS, EMPTY_TRANSACTIONS};
 use reth_primitives::{Address, Bloom, Bytes, EMPTY_OMMER_ROOT, H256, KECCAK_EMPTY, U256};
 use revm::primitives::SpecId;
@@ -9,7 +9,10 @@ use sov_modules_api::WorkingSet;
 
 use crate::evm::db_init::InitEvmDb;
 use crate::evm::primitive_types::Block;
-use crate::evm::{AccountInfo, EvmChainConfig};
+use crate::evm::{
+    gas_price_oracle, AccountInfo, EvmChainConfig, GAS_PRICE_ORACLE_ADDRESS,
+    L1_FEE_SCALAR_PRECISION,
+};
 use crate::Evm;
 
 /// Evm account.
@@ -62,6 +65,22 @@ pub struct EvmConfig {
     pub block_timestamp_delta: u64,
     /// Base fee params.
     pub base_fee_params: reth_primitives::BaseFeeParams,
+    /// Minimum base fee of the blocks after genesis.
+    #[serde(default)]
+    pub min_base_fee: u64,
+    /// Price in wei of a unit of the L1 (DA) gas spent to post the transactions.
+    #[serde(default)]
+    pub l1_gas_price: u64,
+    /// L1 gas spent to post a transaction besides its bytes.
+    #[serde(default)]
+    pub l1_fee_overhead: u64,
+    /// Scalar of the L1 fee of the transactions, in millionths.
+    #[serde(default = "default_l1_fee_scalar")]
+    pub l1_fee_scalar: u64,
+}
+
+fn default_l1_fee_scalar() -> u64 {
+    L1_FEE_SCALAR_PRECISION
 }
 
 impl Default for EvmConfig {
@@ -77,7 +96,64 @@ impl Default for EvmConfig {
             block_timestamp_delta: reth_primitives::constants::SLOT_DURATION.as_secs(),
             genesis_timestamp: 0,
             base_fee_params: reth_primitives::BaseFeeParams::ethereum(),
+            min_base_fee: 0,
+            l1_gas_price: 0,
+            l1_fee_overhead: 0,
+            l1_fee_scalar: L1_FEE_SCALAR_PRECISION,
+        }
+    }
+}
+
+/// The largest chain id supported by the wallets, see EIP-2294.
+const MAX_CHAIN_ID: u64 = u64::MAX / 2 - 36;
+
+impl EvmConfig {
+    /// Checks the configuration without writing any state, and returns all the errors found.
+    /// Every error starts with the field it was found in.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.chain_id == 0 || self.chain_id > MAX_CHAIN_ID {
+            errors.push(format!(
+                "chain_id: {} isn't between 1 and {}",
+                self.chain_id, MAX_CHAIN_ID
+            ));
+        }
+        for (i, account) in self.data.iter().enumerate() {
+            if self.data[..i]
+                .iter()
+                .any(|other| other.address == account.address)
+            {
+                errors.push(format!(
+                    "data[{}].address: account {} is listed twice",
+                    i, account.address
+                ));
+            }
+            if account.address == GAS_PRICE_ORACLE_ADDRESS {
+                errors.push(format!(
+                    "data[{}].address: {} is reserved for the gas price oracle",
+                    i, account.address
+                ));
+            }
+            if reth_primitives::keccak256(&account.code) != account.code_hash {
+                errors.push(format!(
+                    "data[{}].code_hash: {} isn't the hash of the code of account {}",
+                    i, account.code_hash, account.address
+                ));
+            }
+        }
+        if !self.spec.is_empty() && !self.spec.contains_key(&0) {
+            errors.push("spec: the EVM spec must start from block 0".to_owned());
         }
+        if self.spec.values().any(|spec| *spec == SpecId::CANCUN) {
+            errors.push("spec: Cancun is not supported".to_owned());
+        }
+        if self.starting_base_fee < self.min_base_fee {
+            errors.push(format!(
+                "starting_base_fee: {} is lower than the minimum base fee {}",
+                self.starting_base_fee, self.min_base_fee
+            ));
+        }
+        errors
     }
 }
 
@@ -87,6 +163,25 @@ impl<C: sov_modules_api::Context> Evm<C> {
         config: &<Self as sov_modules_api::Module>::Config,
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
+        if config.starting_base_fee < config.min_base_fee {
+            bail!(
+                "Starting base fee {} is lower than the minimum base fee {}",
+                config.starting_base_fee,
+                config.min_base_fee
+            );
+        }
+
+        if config
+            .data
+            .iter()
+            .any(|acc| acc.address == GAS_PRICE_ORACLE_ADDRESS)
+        {
+            bail!(
+                "Account {} is reserved for the gas price oracle",
+                GAS_PRICE_ORACLE_ADDRESS
+            );
+        }
+
         let mut evm_db = self.get_db(working_set);
 
         for acc in &config.data {
@@ -133,6 +228,10 @@ impl<C: sov_modules_api::Context> Evm<C> {
             block_gas_limit: config.block_gas_limit,
             block_timestamp_delta: config.block_timestamp_delta,
             base_fee_params: config.base_fee_params,
+            min_base_fee: config.min_base_fee,
+            l1_gas_price: config.l1_gas_price,
+            l1_fee_overhead: config.l1_fee_overhead,
+            l1_fee_scalar: config.l1_fee_scalar,
         };
 
         self.cfg.set(&chain_cfg, working_set);
@@ -154,7 +253,9 @@ impl<C: sov_modules_api::Context> Evm<C> {
             timestamp: config.genesis_timestamp,
             mix_hash: H256::default(),
             nonce: 0,
-            base_fee_per_gas: Some(config.starting_base_fee),
+            base_fee_per_gas: chain_cfg
+                .has_base_fee_at(0)
+                .then_some(config.starting_base_fee),
             extra_data: Bytes::default(),
             // EIP-4844 related fields
             // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
@@ -165,6 +266,12 @@ impl<C: sov_modules_api::Context> Evm<C> {
             parent_beacon_block_root: None,
         };
 
+        gas_price_oracle::deploy(
+            self.get_db(working_set),
+            &chain_cfg,
+            header.base_fee_per_gas.unwrap_or_default(),
+        );
+
         let block = Block {
             header,
             transactions: 0u64..0u64,
@@ -234,4 +341,27 @@ mod tests {
         let parsed_config: EvmConfig = serde_json::from_str(data).unwrap();
         assert_eq!(config, parsed_config)
     }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(EvmConfig::default().validate().is_empty());
+
+        let account = AccountData {
+            address: Address::from_str("0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266").unwrap(),
+            balance: AccountData::balance(u64::MAX),
+            code_hash: AccountData::empty_code(),
+            code: Bytes::from(vec![0x60, 0x00]),
+            nonce: 0,
+        };
+        let config = EvmConfig {
+            data: vec![account.clone(), account],
+            chain_id: 0,
+            ..Default::default()
+        };
+        let errors = config.validate();
+        assert_eq!(4, errors.len());
+        assert!(errors[0].starts_with("chain_id: 0 isn't between 1 and"));
+        assert!(errors[1].starts_with("data[0].code_hash: "));
+        assert!(errors[2].starts_with("data[1].address: "));
+    }
 }
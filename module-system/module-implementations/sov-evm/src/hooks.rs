@@ -3,7 +3,8 @@ use sov_modules_api::prelude::*;
 use sov_modules_api::{AccessoryWorkingSet, Spec, WorkingSet};
 use sov_state::Storage;
 
-use crate::evm::primitive_types::{Block, BlockEnv};
+use crate::evm::primitive_types::{Block, BlockEnv, SealedBlock};
+use crate::evm::{gas_price_oracle, EvmChainConfig};
 use crate::experimental::PendingTransaction;
 use crate::Evm;
 
@@ -12,9 +13,18 @@ where
     <C::Storage as Storage>::Root: Into<[u8; 32]>,
 {
     /// Logic executed at the beginning of the slot. Here we set the root hash of the previous head.
+    ///
+    /// The `prevrandao` of the pending block is the given randomness, like the one of a randomness beacon
+    /// module, or the hash of the DA block.
+    ///
+    /// The fee parameters of the pending block are written to the gas price oracle contract.
+    ///
+    /// The pending block is timestamped with `da_timestamp`, the time of the DA block in seconds, if it's given.
+    /// Otherwise its timestamp is the one of the previous head, incremented by the configured `block_timestamp_delta`.
     pub fn begin_slot_hook(
         &self,
-        da_root_hash: [u8; 32],
+        prevrandao: [u8; 32],
+        da_timestamp: Option<u64>,
         pre_state_root: &<<C as Spec>::Storage as Storage>::Root,
         working_set: &mut WorkingSet<C>,
     ) {
@@ -26,26 +36,20 @@ where
         parent_block.header.state_root = H256(pre_state_root.clone().into());
         self.head.set(&parent_block, working_set);
 
-        let cfg = self.cfg.get(working_set).unwrap_or_default();
-        let new_pending_env = BlockEnv {
-            number: parent_block.header.number + 1,
-            coinbase: cfg.coinbase,
-            timestamp: parent_block.header.timestamp + cfg.block_timestamp_delta,
-            prevrandao: da_root_hash.into(),
-            basefee: parent_block
-                .header
-                .next_block_base_fee(cfg.base_fee_params)
-                .unwrap(),
-            gas_limit: cfg.block_gas_limit,
-        };
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let mut new_pending_env = next_block_env(&parent_block.header, &cfg, prevrandao.into());
+        if let Some(da_timestamp) = da_timestamp {
+            // Block timestamps must keep increasing, even if the DA time doesn't
+            new_pending_env.timestamp = da_timestamp.max(parent_block.header.timestamp + 1);
+        }
         self.block_env.set(&new_pending_env, working_set);
+
+        gas_price_oracle::update(self.get_db(working_set), &cfg, new_pending_env.basefee);
     }
 
     /// Logic executed at the end of the slot. Here, we generate an authenticated block and set it as the new head of the chain.
     /// It's important to note that the state root hash is not known at this moment, so we postpone setting this field until the begin_slot_hook of the next slot.
     pub fn end_slot_hook(&self, working_set: &mut WorkingSet<C>) {
-        let cfg = self.cfg.get(working_set).unwrap_or_default();
-
         let block_env = self
             .block_env
             .get(working_set)
@@ -69,65 +73,15 @@ where
 
         self.pending_transactions.clear(working_set);
 
-        let start_tx_index = parent_block.transactions.end;
-
-        let gas_used = pending_transactions
-            .last()
-            .map_or(0u64, |tx| tx.receipt.receipt.cumulative_gas_used);
-
-        let transactions: Vec<&reth_primitives::TransactionSigned> = pending_transactions
-            .iter()
-            .map(|tx| &tx.transaction.signed_transaction)
-            .collect();
-
-        let receipts: Vec<reth_primitives::ReceiptWithBloom> = pending_transactions
-            .iter()
-            .map(|tx| tx.receipt.receipt.clone().with_bloom())
-            .collect();
-
-        let header = reth_primitives::Header {
-            parent_hash: parent_block.header.hash,
-            timestamp: block_env.timestamp,
-            number: block_env.number,
-            ommers_hash: reth_primitives::constants::EMPTY_OMMER_ROOT,
-            beneficiary: parent_block.header.beneficiary,
-            // This will be set in finalize_hook or in the next begin_slot_hook
-            state_root: reth_primitives::constants::KECCAK_EMPTY,
-            transactions_root: reth_primitives::proofs::calculate_transaction_root(
-                transactions.as_slice(),
-            ),
-            receipts_root: reth_primitives::proofs::calculate_receipt_root(receipts.as_slice()),
-            withdrawals_root: None,
-            logs_bloom: receipts
-                .iter()
-                .fold(Bloom::zero(), |bloom, r| bloom | r.bloom),
-            difficulty: U256::ZERO,
-            gas_limit: block_env.gas_limit,
-            gas_used,
-            mix_hash: block_env.prevrandao,
-            nonce: 0,
-            base_fee_per_gas: parent_block.header.next_block_base_fee(cfg.base_fee_params),
-            extra_data: Bytes::default(),
-            // EIP-4844 related fields
-            // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
-            blob_gas_used: None,
-            excess_blob_gas: None,
-            // EIP-4788 related field
-            // unrelated for rollups
-            parent_beacon_block_root: None,
-        };
-
-        let block = Block {
-            header,
-            transactions: start_tx_index..start_tx_index + pending_transactions.len() as u64,
-        };
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let block = build_block(&parent_block, &block_env, &pending_transactions, &cfg);
 
         self.head.set(&block, working_set);
 
         let mut accessory_state = working_set.accessory_state();
         self.pending_head.set(&block, &mut accessory_state);
 
-        let mut tx_index = start_tx_index;
+        let mut tx_index = block.transactions.start;
         for PendingTransaction {
             transaction,
             receipt,
@@ -182,6 +136,8 @@ where
         let sealed_block = block.seal();
 
         self.blocks.push(&sealed_block, accessory_working_set);
+        self.logs_blooms
+            .push(&sealed_block.header.logs_bloom, accessory_working_set);
         self.block_hashes.set(
             &sealed_block.header.hash,
             &sealed_block.header.number,
@@ -190,3 +146,98 @@ where
         self.pending_head.delete(accessory_working_set);
     }
 }
+
+/// Returns the environment of the block following the one with `parent_header`.
+///
+/// Blocks have no base fee before London. The first London block starts from the initial
+/// base fee of EIP-1559, since its parent has none.
+pub(crate) fn next_block_env(
+    parent_header: &reth_primitives::Header,
+    cfg: &EvmChainConfig,
+    prevrandao: H256,
+) -> BlockEnv {
+    let number = parent_header.number + 1;
+    let basefee = if cfg.has_base_fee_at(number) {
+        match parent_header.base_fee_per_gas {
+            Some(base_fee) => {
+                cfg.next_block_base_fee(parent_header.gas_used, parent_header.gas_limit, base_fee)
+            }
+            None => reth_primitives::constants::EIP1559_INITIAL_BASE_FEE.max(cfg.min_base_fee),
+        }
+    } else {
+        0
+    };
+
+    BlockEnv {
+        number,
+        coinbase: cfg.coinbase,
+        timestamp: parent_header.timestamp + cfg.block_timestamp_delta,
+        prevrandao,
+        basefee,
+        gas_limit: cfg.block_gas_limit,
+    }
+}
+
+/// Builds the block containing `pending_transactions` on top of `parent_block`.
+/// The state root of the block is not known yet, so it is left empty.
+pub(crate) fn build_block(
+    parent_block: &SealedBlock,
+    block_env: &BlockEnv,
+    pending_transactions: &[PendingTransaction],
+    cfg: &EvmChainConfig,
+) -> Block {
+    let start_tx_index = parent_block.transactions.end;
+
+    let gas_used = pending_transactions
+        .last()
+        .map_or(0u64, |tx| tx.receipt.receipt.cumulative_gas_used);
+
+    let transactions: Vec<&reth_primitives::TransactionSigned> = pending_transactions
+        .iter()
+        .map(|tx| &tx.transaction.signed_transaction)
+        .collect();
+
+    let receipts: Vec<reth_primitives::ReceiptWithBloom> = pending_transactions
+        .iter()
+        .map(|tx| tx.receipt.receipt.clone().with_bloom())
+        .collect();
+
+    let header = reth_primitives::Header {
+        parent_hash: parent_block.header.hash,
+        timestamp: block_env.timestamp,
+        number: block_env.number,
+        ommers_hash: reth_primitives::constants::EMPTY_OMMER_ROOT,
+        beneficiary: parent_block.header.beneficiary,
+        // This will be set in finalize_hook or in the next begin_slot_hook
+        state_root: reth_primitives::constants::KECCAK_EMPTY,
+        transactions_root: reth_primitives::proofs::calculate_transaction_root(
+            transactions.as_slice(),
+        ),
+        receipts_root: reth_primitives::proofs::calculate_receipt_root(receipts.as_slice()),
+        withdrawals_root: None,
+        logs_bloom: receipts
+            .iter()
+            .fold(Bloom::zero(), |bloom, r| bloom | r.bloom),
+        difficulty: U256::ZERO,
+        gas_limit: block_env.gas_limit,
+        gas_used,
+        mix_hash: block_env.prevrandao,
+        nonce: 0,
+        base_fee_per_gas: cfg
+            .has_base_fee_at(block_env.number)
+            .then_some(block_env.basefee),
+        extra_data: Bytes::default(),
+        // EIP-4844 related fields
+        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
+        blob_gas_used: None,
+        excess_blob_gas: None,
+        // EIP-4788 related field
+        // unrelated for rollups
+        parent_beacon_block_root: None,
+    };
+
+    Block {
+        header,
+        transactions: start_tx_index..start_tx_index + pending_transactions.len() as u64,
+    }
+}
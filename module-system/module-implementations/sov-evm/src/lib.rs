@@ -16,6 +16,9 @@ mod query;
 #[cfg(feature = "native")]
 #[cfg(feature = "experimental")]
 pub use query::*;
+#[cfg(feature = "native")]
+#[cfg(feature = "experimental")]
+mod pending;
 #[cfg(feature = "experimental")]
 mod signer;
 #[cfg(feature = "experimental")]
@@ -40,7 +43,9 @@ mod experimental {
     use sov_state::codec::BcsCodec;
 
     use super::evm::db::EvmDb;
-    use super::evm::{DbAccount, EvmChainConfig};
+    use super::evm::{
+        precompiles, CustomPrecompileFn, DbAccount, EvmChainConfig, PrecompileRegistrationError,
+    };
     use crate::evm::primitive_types::{
         Block, BlockEnv, Receipt, SealedBlock, TransactionSignedAndRecovered,
     };
@@ -65,6 +70,11 @@ mod experimental {
         #[address]
         pub(crate) address: C::Address,
 
+        /// Reference to the Governance module, which can govern the block gas limit and the fee
+        /// constants of the chain configuration.
+        #[module]
+        pub(crate) governance: sov_governance::Governance<C>,
+
         /// Mapping from account address to account state.
         #[state]
         pub(crate) accounts: sov_modules_api::StateMap<Address, DbAccount, BcsCodec>,
@@ -108,6 +118,12 @@ mod experimental {
         pub(crate) block_hashes:
             sov_modules_api::AccessoryStateMap<reth_primitives::H256, u64, BcsCodec>,
 
+        /// Used only by the RPC: The logs bloom of every block, indexed by block number.
+        /// Allows `eth_getLogs` to skip the blocks without matching logs, without loading them.
+        #[state]
+        pub(crate) logs_blooms:
+            sov_modules_api::AccessoryStateVec<reth_primitives::Bloom, BcsCodec>,
+
         /// Used only by the RPC: List of processed transactions.
         #[state]
         pub(crate) transactions:
@@ -154,5 +170,51 @@ mod experimental {
         pub(crate) fn get_db<'a>(&self, working_set: &'a mut WorkingSet<C>) -> EvmDb<'a, C> {
             EvmDb::new(self.accounts.clone(), self.code.clone(), working_set)
         }
+
+        /// Returns the chain configuration set at genesis, with the governed parameters in place
+        /// of the configured ones.
+        pub fn get_chain_config(&self, working_set: &mut WorkingSet<C>) -> EvmChainConfig {
+            self.chain_config(working_set)
+                .expect("EVM config must be set at genesis")
+        }
+
+        /// Returns the chain configuration set at genesis, with the block gas limit and the fee
+        /// constants governed by [`sov_governance`] overriding the configured ones.
+        pub(crate) fn chain_config(
+            &self,
+            working_set: &mut WorkingSet<C>,
+        ) -> Option<EvmChainConfig> {
+            let mut cfg = self.cfg.get(working_set)?;
+            for (name, value) in [
+                (sov_governance::BLOCK_GAS_LIMIT, &mut cfg.block_gas_limit),
+                (sov_governance::MIN_BASE_FEE, &mut cfg.min_base_fee),
+                (sov_governance::L1_GAS_PRICE, &mut cfg.l1_gas_price),
+                (sov_governance::L1_FEE_OVERHEAD, &mut cfg.l1_fee_overhead),
+                (sov_governance::L1_FEE_SCALAR, &mut cfg.l1_fee_scalar),
+            ] {
+                *value = self.governance.get_parameter_or(name, *value, working_set);
+            }
+            Some(cfg)
+        }
+
+        /// Adds a precompiled contract at `address`, on top of the precompiles of the configured hardfork.
+        /// It's called by transactions and `eth_call` like any other contract, and the gas it reports
+        /// is charged to the caller.
+        ///
+        /// Custom precompiles are part of the state transition function: they must be registered before
+        /// any transaction is executed, with the same addresses and implementations in the full node
+        /// and in the zk guest, or their state roots will diverge. The addresses of the Ethereum
+        /// precompiles are rejected, including the ones of hardforks which aren't activated yet.
+        pub fn register_precompile(
+            address: Address,
+            precompile: CustomPrecompileFn,
+        ) -> Result<(), PrecompileRegistrationError> {
+            precompiles::register(address, precompile)
+        }
+
+        /// Returns the addresses of the registered custom precompiles.
+        pub fn custom_precompiles() -> Vec<Address> {
+            precompiles::registered_addresses()
+        }
     }
 }
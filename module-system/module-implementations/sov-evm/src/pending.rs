@@ -0,0 +1,79 @@
+use reth_primitives::TransactionSignedEcRecovered;
+use sov_modules_api::prelude::*;
+use sov_modules_api::WorkingSet;
+
+use crate::evm::primitive_types::BlockEnv;
+use crate::evm::RlpEvmTransaction;
+use crate::experimental::PendingTransaction;
+use crate::hooks::{build_block, next_block_env};
+use crate::query::build_rpc_block;
+use crate::Evm;
+
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Executes transactions which are not part of a sealed block yet, like the ones waiting
+    /// to be included by the sequencer, in the block following the head of the chain.
+    ///
+    /// The pending block is layered over the state of the working set, which becomes a view
+    /// of the pending state: the queries using the `pending` block tag see the effects of the
+    /// transactions. Invalid transactions are skipped, as they would be by the sequencer.
+    /// The working set must not be committed afterwards.
+    pub fn apply_pending_transactions(
+        &self,
+        txs: &[RlpEvmTransaction],
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let block_env = self.pending_block_env(working_set);
+        self.block_env.set(&block_env, working_set);
+
+        for tx in txs {
+            let result = TransactionSignedEcRecovered::try_from(tx.clone())
+                .map_err(anyhow::Error::from)
+                .and_then(|tx| self.apply_transaction(tx, working_set));
+            if let Err(e) = result {
+                tracing::debug!("Pending transaction can't be applied: {}", e);
+            }
+        }
+    }
+
+    /// Returns the pending block, containing the transactions applied with
+    /// [`Evm::apply_pending_transactions`]. Its state root is not known yet, so it is left empty.
+    pub fn get_pending_block(
+        &self,
+        details: Option<bool>,
+        working_set: &mut WorkingSet<C>,
+    ) -> reth_rpc_types::RichBlock {
+        let block_env = self.pending_block_env(working_set);
+        let parent_block = self
+            .blocks
+            .last(&mut working_set.accessory_state())
+            .expect("Head block must be set");
+        let pending_transactions: Vec<PendingTransaction> =
+            self.pending_transactions.iter(working_set).collect();
+
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let block = build_block(&parent_block, &block_env, &pending_transactions, &cfg).seal();
+        let transactions = pending_transactions
+            .into_iter()
+            .map(|tx| tx.transaction)
+            .collect();
+
+        build_rpc_block(&block, transactions, details)
+    }
+
+    /// Returns the environment of the pending block, which follows the head of the chain.
+    pub(crate) fn pending_block_env(&self, working_set: &mut WorkingSet<C>) -> BlockEnv {
+        let head = self
+            .blocks
+            .last(&mut working_set.accessory_state())
+            .expect("Head block must be set");
+        let block_env = self.block_env.get(working_set).unwrap_or_default();
+        // Once the pending transactions are applied, the environment of the pending block is set
+        if block_env.number > head.header.number {
+            return block_env;
+        }
+
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        // The randomness of the pending block is only known with its DA slot, so the one of the head is used
+        next_block_env(&head.header, &cfg, head.header.mix_hash)
+    }
+}
@@ -1,23 +1,47 @@
 use std::array::TryFromSliceError;
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
+use borsh::BorshSerialize;
 use ethereum_types::U64;
 use jsonrpsee::core::RpcResult;
 use reth_primitives::contract::create_address;
 use reth_primitives::TransactionKind::{Call, Create};
-use reth_primitives::{TransactionSignedEcRecovered, U128, U256};
+use reth_primitives::{
+    keccak256, Address, BlockNumberOrTag, TransactionSignedEcRecovered, H256, U128, U256,
+};
+use reth_revm::access_list::AccessListInspector;
+use reth_revm::tracing::{FourByteInspector, TracingInspector, TracingInspectorConfig};
+use reth_rpc_types::state::AccountOverride;
+use reth_rpc_types::trace::geth::{
+    FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
+};
+use reth_rpc_types::{
+    AccessListWithGasUsed, EIP1186AccountProofResponse, Filter, FilterBlockOption, FilteredParams,
+    JsonStorageKey, StorageProof,
+};
 use revm::primitives::{
-    EVMError, ExecutionResult, Halt, InvalidTransaction, TransactTo, KECCAK_EMPTY,
+    CfgEnv, EVMError, ExecutionResult, Halt, InvalidTransaction, ResultAndState, TransactTo, TxEnv,
+    KECCAK_EMPTY,
 };
+use revm::DatabaseCommit;
 use sov_modules_api::macros::rpc_gen;
 use sov_modules_api::prelude::*;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::{AccessoryWorkingSet, WorkingSet};
+use sov_state::storage::{NativeStorage, Storage, StorageKey};
 use tracing::info;
 
 use crate::call::get_cfg_env;
-use crate::error::rpc::{ensure_success, RevertError, RpcInvalidTransactionError};
-use crate::evm::db::EvmDb;
+use crate::error::rpc::{ensure_success, EthResult, RevertError, RpcInvalidTransactionError};
+use crate::evm::conversions::create_tx_env;
+use crate::evm::db::{EvmDb, EvmDbRef};
+use crate::evm::executor::CachedExecutor;
 use crate::evm::primitive_types::{BlockEnv, Receipt, SealedBlock, TransactionSignedAndRecovered};
-use crate::evm::{executor, prepare_call_env};
+use crate::evm::{
+    executor, precompiles, prepare_call_env, AccountInfo, DbAccount, L1_FEE_SCALAR_PRECISION,
+};
 use crate::experimental::{MIN_CREATE_GAS, MIN_TRANSACTION_GAS};
 use crate::{EthApiError, Evm};
 
@@ -30,8 +54,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
 
         // Network ID is the same as chain ID for most networks
         let chain_id = self
-            .cfg
-            .get(working_set)
+            .chain_config(working_set)
             .expect("EVM config must be set at genesis")
             .chain_id;
 
@@ -44,8 +67,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         tracing::debug!("evm module: eth_chainId");
 
         let chain_id = reth_primitives::U64::from(
-            self.cfg
-                .get(working_set)
+            self.chain_config(working_set)
                 .expect("EVM config must be set at genesis")
                 .chain_id,
         );
@@ -83,54 +105,24 @@ impl<C: sov_modules_api::Context> Evm<C> {
     ) -> RpcResult<Option<reth_rpc_types::RichBlock>> {
         info!("evm module: eth_getBlockByNumber");
 
-        let block = self.get_sealed_block_by_number(block_number, working_set);
-
-        // Build rpc header response
-        let header = reth_rpc_types::Header::from_primitive_with_hash(block.header.clone());
-
-        // Collect transactions with ids from db
-        let transactions_with_ids = block.transactions.clone().map(|id| {
-            let tx = self
-                .transactions
-                .get(id as usize, &mut working_set.accessory_state())
-                .expect("Transaction must be set");
-            (id, tx)
-        });
+        if block_number.as_deref() == Some("pending") {
+            return Ok(Some(self.get_pending_block(details, working_set)));
+        }
 
-        // Build rpc transactions response
-        let transactions = match details {
-            Some(true) => reth_rpc_types::BlockTransactions::Full(
-                transactions_with_ids
-                    .map(|(id, tx)| {
-                        reth_rpc_types_compat::from_recovered_with_block_context(
-                            tx.clone().into(),
-                            block.header.hash,
-                            block.header.number,
-                            block.header.base_fee_per_gas,
-                            U256::from(id - block.transactions.start),
-                        )
-                    })
-                    .collect::<Vec<_>>(),
-            ),
-            _ => reth_rpc_types::BlockTransactions::Hashes({
-                transactions_with_ids
-                    .map(|(_, tx)| tx.signed_transaction.hash)
-                    .collect::<Vec<_>>()
-            }),
-        };
+        let block = self.get_sealed_block_by_number(block_number, working_set)?;
 
-        // Build rpc block response
-        let total_difficulty = Some(block.header.difficulty);
-        let block = reth_rpc_types::Block {
-            header,
-            total_difficulty,
-            uncles: Default::default(),
-            transactions,
-            size: Default::default(),
-            withdrawals: Default::default(),
-        };
+        // Collect transactions from db
+        let transactions = block
+            .transactions
+            .clone()
+            .map(|id| {
+                self.transactions
+                    .get(id as usize, &mut working_set.accessory_state())
+                    .expect("Transaction must be set")
+            })
+            .collect();
 
-        Ok(Some(block.into()))
+        Ok(Some(build_rpc_block(&block, transactions, details)))
     }
 
     /// Handler for: `eth_getBalance`
@@ -138,19 +130,19 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_balance(
         &self,
         address: reth_primitives::Address,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U256> {
         info!("evm module: eth_getBalance");
 
-        // TODO: Implement block_number once we have archival state #951
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/951
-
+        let (_, archival_version) = self.call_block_env(block_number, working_set)?;
+        set_state_version(archival_version, working_set)?;
         let balance = self
             .accounts
             .get(&address, working_set)
             .map(|account| account.info.balance)
             .unwrap_or_default();
+        working_set.unset_archival_version();
 
         Ok(balance)
     }
@@ -161,19 +153,19 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         address: reth_primitives::Address,
         index: reth_primitives::U256,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U256> {
         info!("evm module: eth_getStorageAt");
 
-        // TODO: Implement block_number once we have archival state #951
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/951
-
+        let (_, archival_version) = self.call_block_env(block_number, working_set)?;
+        set_state_version(archival_version, working_set)?;
         let storage_slot = self
             .accounts
             .get(&address, working_set)
             .and_then(|account| account.storage.get(&index, working_set))
             .unwrap_or_default();
+        working_set.unset_archival_version();
 
         Ok(storage_slot)
     }
@@ -183,19 +175,19 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_transaction_count(
         &self,
         address: reth_primitives::Address,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U64> {
         info!("evm module: eth_getTransactionCount");
 
-        // TODO: Implement block_number once we have archival state #882
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/882
-
+        let (_, archival_version) = self.call_block_env(block_number, working_set)?;
+        set_state_version(archival_version, working_set)?;
         let nonce = self
             .accounts
             .get(&address, working_set)
             .map(|account| account.info.nonce)
             .unwrap_or_default();
+        working_set.unset_archival_version();
 
         Ok(nonce.into())
     }
@@ -205,39 +197,23 @@ impl<C: sov_modules_api::Context> Evm<C> {
     pub fn get_code(
         &self,
         address: reth_primitives::Address,
-        _block_number: Option<String>,
+        block_number: Option<String>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         info!("evm module: eth_getCode");
 
-        // TODO: Implement block_number once we have archival state #951
-        // https://github.com/Sovereign-Labs/sovereign-sdk/issues/951
-
+        let (_, archival_version) = self.call_block_env(block_number, working_set)?;
+        set_state_version(archival_version, working_set)?;
         let code = self
             .accounts
             .get(&address, working_set)
             .and_then(|account| self.code.get(&account.info.code_hash, working_set))
             .unwrap_or_default();
+        working_set.unset_archival_version();
 
         Ok(code)
     }
 
-    /// Handler for: `eth_feeHistory`
-    // TODO https://github.com/Sovereign-Labs/sovereign-sdk/issues/502
-    #[rpc_method(name = "eth_feeHistory")]
-    pub fn fee_history(
-        &self,
-        _working_set: &mut WorkingSet<C>,
-    ) -> RpcResult<reth_rpc_types::FeeHistory> {
-        info!("evm module: eth_feeHistory");
-        Ok(reth_rpc_types::FeeHistory {
-            base_fee_per_gas: Default::default(),
-            gas_used_ratio: Default::default(),
-            oldest_block: Default::default(),
-            reward: Default::default(),
-        })
-    }
-
     /// Handler for: `eth_getTransactionByHash`
     // TODO https://github.com/Sovereign-Labs/sovereign-sdk/issues/502
     #[rpc_method(name = "eth_getTransactionByHash")]
@@ -291,7 +267,7 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         hash: reth_primitives::H256,
         working_set: &mut WorkingSet<C>,
-    ) -> RpcResult<Option<reth_rpc_types::TransactionReceipt>> {
+    ) -> RpcResult<Option<RollupTransactionReceipt>> {
         info!("evm module: eth_getTransactionReceipt");
 
         let mut accessory_state = working_set.accessory_state();
@@ -313,12 +289,157 @@ impl<C: sov_modules_api::Context> Evm<C> {
                 .get(tx_number.unwrap() as usize, &mut accessory_state)
                 .expect("Receipt for known transaction must be set");
 
-            build_rpc_receipt(block, tx, tx_number.unwrap(), receipt)
+            build_rollup_receipt(&block, tx, tx_number.unwrap(), receipt)
         });
 
         Ok(receipt)
     }
 
+    /// Handler for: `eth_getBlockReceipts`
+    ///
+    /// The block is identified by its hash, its hex number or a tag. The receipts are read
+    /// from the transaction range of the block, without looking up the transactions by hash.
+    #[rpc_method(name = "eth_getBlockReceipts")]
+    pub fn get_block_receipts(
+        &self,
+        block_id: String,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<Vec<RollupTransactionReceipt>>> {
+        info!("evm module: eth_getBlockReceipts({})", block_id);
+
+        // The pending block isn't executed, so it has no receipts.
+        let Some(block) = self.get_sealed_block_by_id(block_id, working_set)? else {
+            return Ok(None);
+        };
+
+        let mut accessory_state = working_set.accessory_state();
+        let receipts = block
+            .transactions
+            .clone()
+            .map(|tx_number| {
+                let tx = self
+                    .transactions
+                    .get(tx_number as usize, &mut accessory_state)
+                    .expect("Transaction of a known block must be set");
+                let receipt = self
+                    .receipts
+                    .get(tx_number as usize, &mut accessory_state)
+                    .expect("Receipt of a known block must be set");
+                build_rollup_receipt(&block, tx, tx_number, receipt)
+            })
+            .collect();
+
+        Ok(Some(receipts))
+    }
+
+    /// Handler for: `eth_getTransactionByBlockHashAndIndex`
+    #[rpc_method(name = "eth_getTransactionByBlockHashAndIndex")]
+    pub fn get_transaction_by_block_hash_and_index(
+        &self,
+        block_hash: reth_primitives::H256,
+        index: reth_primitives::U64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<reth_rpc_types::Transaction>> {
+        info!(
+            "evm module: eth_getTransactionByBlockHashAndIndex({}, {})",
+            block_hash, index
+        );
+
+        let Some(block) = self.get_sealed_block_by_id(format!("{:?}", block_hash), working_set)?
+        else {
+            return Ok(None);
+        };
+        Ok(self.get_transaction_in_block(&block, index, working_set))
+    }
+
+    /// Handler for: `eth_getTransactionByBlockNumberAndIndex`
+    ///
+    /// The block is identified by its hex number or a tag. The transactions of the pending
+    /// block aren't executed yet, so they aren't returned.
+    #[rpc_method(name = "eth_getTransactionByBlockNumberAndIndex")]
+    pub fn get_transaction_by_block_number_and_index(
+        &self,
+        block_number: String,
+        index: reth_primitives::U64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<reth_rpc_types::Transaction>> {
+        info!(
+            "evm module: eth_getTransactionByBlockNumberAndIndex({}, {})",
+            block_number, index
+        );
+
+        let Some(block) = self.get_sealed_block_by_id(block_number, working_set)? else {
+            return Ok(None);
+        };
+        Ok(self.get_transaction_in_block(&block, index, working_set))
+    }
+
+    /// Handler for: `eth_getUncleByBlockHashAndIndex`
+    ///
+    /// Rollup blocks have no uncles, so no uncle is ever returned.
+    #[rpc_method(name = "eth_getUncleByBlockHashAndIndex")]
+    pub fn get_uncle_by_block_hash_and_index(
+        &self,
+        _block_hash: reth_primitives::H256,
+        _index: reth_primitives::U64,
+        _working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<reth_rpc_types::RichBlock>> {
+        info!("evm module: eth_getUncleByBlockHashAndIndex");
+        Ok(None)
+    }
+
+    /// Handler for: `eth_getUncleByBlockNumberAndIndex`
+    ///
+    /// Rollup blocks have no uncles, so no uncle is ever returned.
+    #[rpc_method(name = "eth_getUncleByBlockNumberAndIndex")]
+    pub fn get_uncle_by_block_number_and_index(
+        &self,
+        _block_number: String,
+        _index: reth_primitives::U64,
+        _working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<reth_rpc_types::RichBlock>> {
+        info!("evm module: eth_getUncleByBlockNumberAndIndex");
+        Ok(None)
+    }
+
+    /// Handler for: `eth_getUncleCountByBlockHash`
+    ///
+    /// Returns zero for the known blocks, since rollup blocks have no uncles.
+    #[rpc_method(name = "eth_getUncleCountByBlockHash")]
+    pub fn get_uncle_count_by_block_hash(
+        &self,
+        block_hash: reth_primitives::H256,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<U256>> {
+        info!("evm module: eth_getUncleCountByBlockHash({})", block_hash);
+
+        Ok(self
+            .get_sealed_block_by_id(format!("{:?}", block_hash), working_set)?
+            .map(|_| U256::ZERO))
+    }
+
+    /// Handler for: `eth_getUncleCountByBlockNumber`
+    ///
+    /// Returns zero for the known blocks, since rollup blocks have no uncles.
+    #[rpc_method(name = "eth_getUncleCountByBlockNumber")]
+    pub fn get_uncle_count_by_block_number(
+        &self,
+        block_number: String,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<U256>> {
+        info!(
+            "evm module: eth_getUncleCountByBlockNumber({})",
+            block_number
+        );
+
+        if block_number == "pending" {
+            return Ok(Some(U256::ZERO));
+        }
+        Ok(self
+            .get_sealed_block_by_id(block_number, working_set)?
+            .map(|_| U256::ZERO))
+    }
+
     /// Handler for: `eth_call`
     //https://github.com/paradigmxyz/reth/blob/f577e147807a783438a3f16aad968b4396274483/crates/rpc/rpc/src/eth/api/transactions.rs#L502
     //https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc-types/src/eth/call.rs#L7
@@ -327,34 +448,27 @@ impl<C: sov_modules_api::Context> Evm<C> {
         &self,
         request: reth_rpc_types::CallRequest,
         block_number: Option<String>,
-        _state_overrides: Option<reth_rpc_types::state::StateOverride>,
-        _block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
+        state_overrides: Option<reth_rpc_types::state::StateOverride>,
+        block_overrides: Option<Box<reth_rpc_types::BlockOverrides>>,
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::Bytes> {
         info!("evm module: eth_call");
-        let block_env = match block_number {
-            Some(ref block_number) if block_number == "pending" => {
-                self.block_env.get(working_set).unwrap_or_default().clone()
-            }
-            _ => {
-                let block = self.get_sealed_block_by_number(block_number, working_set);
-                BlockEnv::from(&block)
-            }
-        };
-
-        let tx_env = prepare_call_env(&block_env, request.clone()).unwrap();
-
-        let cfg = self.cfg.get(working_set).unwrap_or_default();
-        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
-
-        let evm_db: EvmDb<'_, C> = self.get_db(working_set);
+        let (mut block_env, archival_version) = self.call_block_env(block_number, working_set)?;
+        if let Some(block_overrides) = block_overrides {
+            apply_block_overrides(&mut block_env, *block_overrides)?;
+        }
 
-        let result = match executor::inspect(evm_db, &block_env, tx_env, cfg_env) {
-            Ok(result) => result.result,
-            Err(err) => return Err(EthApiError::from(err).into()),
-        };
+        set_state_version(archival_version, working_set)?;
+        // The working set of an RPC call is never committed, so the overrides only affect this call.
+        // They are applied on top of the archival state, if any.
+        let result = match state_overrides {
+            Some(state_overrides) => self.apply_state_overrides(state_overrides, working_set),
+            None => Ok(()),
+        }
+        .and_then(|_| self.call_with_env(request, &block_env, working_set));
+        working_set.unset_archival_version();
 
-        Ok(ensure_success(result)?)
+        Ok(result?)
     }
 
     /// Handler for: `eth_blockNumber`
@@ -373,6 +487,44 @@ impl<C: sov_modules_api::Context> Evm<C> {
         Ok(block_number)
     }
 
+    /// Handler for: `eth_blobBaseFee`
+    ///
+    /// The rollup rejects blob transactions, so this is the blob gas price of the next block as
+    /// specified by EIP-4844, for the tooling which reads it before choosing a transaction type.
+    #[rpc_method(name = "eth_blobBaseFee")]
+    pub fn blob_base_fee(&self, working_set: &mut WorkingSet<C>) -> RpcResult<U128> {
+        info!("evm module: eth_blobBaseFee");
+
+        let head = self
+            .blocks
+            .last(&mut working_set.accessory_state())
+            .expect("Head block must be set");
+        let next_excess_blob_gas = calc_next_excess_blob_gas(
+            head.header.excess_blob_gas.unwrap_or_default(),
+            head.header.blob_gas_used.unwrap_or_default(),
+        );
+        Ok(U128::from(calc_blob_gas_price(next_excess_blob_gas)))
+    }
+
+    /// Handler for: `sov_getEvmSpec`
+    ///
+    /// Returns the hardfork active at the given block, which selects the gas rules, opcodes and
+    /// precompiles of its execution. The latest block is used if it's missing.
+    #[rpc_method(name = "sov_getEvmSpec")]
+    pub fn get_evm_spec(
+        &self,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<revm::primitives::SpecId> {
+        info!("evm module: sov_getEvmSpec");
+
+        let (block_env, _) = self.call_block_env(block_number, working_set)?;
+        let cfg = self
+            .chain_config(working_set)
+            .expect("EVM config must be set at genesis");
+        Ok(cfg.spec_at(block_env.number))
+    }
+
     /// Handler for: `eth_estimateGas`
     // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/api/call.rs#L172
     #[rpc_method(name = "eth_estimateGas")]
@@ -383,211 +535,1604 @@ impl<C: sov_modules_api::Context> Evm<C> {
         working_set: &mut WorkingSet<C>,
     ) -> RpcResult<reth_primitives::U64> {
         info!("evm module: eth_estimateGas");
-        let mut block_env = match block_number {
-            Some(ref block_number) if block_number == "pending" => {
-                self.block_env.get(working_set).unwrap_or_default().clone()
-            }
-            _ => {
-                let block = self.get_sealed_block_by_number(block_number, working_set);
-                BlockEnv::from(&block)
-            }
-        };
-
-        let tx_env = prepare_call_env(&block_env, request.clone()).unwrap();
+        let (block_env, archival_version) = self.call_block_env(block_number, working_set)?;
 
-        let cfg = self.cfg.get(working_set).unwrap_or_default();
-        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+        set_state_version(archival_version, working_set)?;
+        let estimate = self.estimate_gas_with_env(request, block_env, working_set);
+        working_set.unset_archival_version();
 
-        let request_gas = request.gas;
-        let request_gas_price = request.gas_price;
-        let env_gas_limit = block_env.gas_limit;
+        Ok(estimate?)
+    }
 
-        // get the highest possible gas limit, either the request's set value or the currently
-        // configured gas limit
-        let mut highest_gas_limit = request.gas.unwrap_or(U256::from(env_gas_limit));
+    /// Handler for: `eth_createAccessList`. Executes the call on top of the state of the given
+    /// block and returns the accounts and storage slots it accessed, along with the gas
+    /// the call needs when it's sent with that access list.
+    // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/eth/api/call.rs
+    #[rpc_method(name = "eth_createAccessList")]
+    pub fn create_access_list(
+        &self,
+        request: reth_rpc_types::CallRequest,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AccessListWithGasUsed> {
+        info!("evm module: eth_createAccessList");
+        let (block_env, archival_version) = self.call_block_env(block_number, working_set)?;
 
-        let account = self
-            .accounts
-            .get(&tx_env.caller, working_set)
-            .map(|account| account.info)
-            .unwrap_or_default();
+        set_state_version(archival_version, working_set)?;
+        let access_list = self.create_access_list_with_env(request, block_env, working_set);
+        working_set.unset_archival_version();
 
-        // if the request is a simple transfer we can optimize
-        if tx_env.data.is_empty() {
-            if let TransactTo::Call(to) = tx_env.transact_to {
-                let to_account = self
-                    .accounts
-                    .get(&to, working_set)
-                    .map(|account| account.info)
-                    .unwrap_or_default();
-                if KECCAK_EMPTY == to_account.code_hash {
-                    // simple transfer, check if caller has sufficient funds
-                    let available_funds = account.balance;
+        Ok(access_list?)
+    }
 
-                    if tx_env.value > available_funds {
-                        return Err(RpcInvalidTransactionError::InsufficientFundsForTransfer.into());
-                    }
-                    return Ok(U64::from(MIN_TRANSACTION_GAS));
-                }
-            }
-        }
+    /// Handler for: `debug_traceTransaction`
+    // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/debug.rs
+    #[rpc_method(name = "debug_traceTransaction")]
+    pub fn debug_trace_transaction(
+        &self,
+        tx_hash: reth_primitives::H256,
+        opts: Option<GethDebugTracingOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<GethTrace> {
+        info!("evm module: debug_traceTransaction({})", tx_hash);
 
-        // check funds of the sender
-        if tx_env.gas_price > U256::ZERO {
-            // allowance is (balance - tx.value) / tx.gas_price
-            let allowance = (account.balance - tx_env.value) / tx_env.gas_price;
+        let mut accessory_state = working_set.accessory_state();
 
-            if highest_gas_limit > allowance {
-                // cap the highest gas limit by max gas caller can afford with given gas price
-                highest_gas_limit = allowance;
-            }
+        let tx_number = self
+            .transaction_hashes
+            .get(&tx_hash, &mut accessory_state)
+            .ok_or(EthApiError::TransactionNotFound)?;
+        let tx = self
+            .transactions
+            .get(tx_number as usize, &mut accessory_state)
+            .expect("Transaction with known hash must be set");
+        let block = self
+            .blocks
+            .get(tx.block_number as usize, &mut accessory_state)
+            .expect("Block number for known transaction must be set");
+
+        // Transactions preceding the traced one in the same block
+        let preceding_txs: Vec<TransactionSignedEcRecovered> = (block.transactions.start
+            ..tx_number)
+            .map(|number| {
+                self.transactions
+                    .get(number as usize, &mut accessory_state)
+                    .expect("Transaction of a known block must be set")
+                    .into()
+            })
+            .collect();
+
+        let block_env = BlockEnv::from(&block);
+        let cfg = self
+            .chain_config(working_set)
+            .expect("EVM config must be set at genesis");
+        let cfg_env = get_cfg_env(&block_env, cfg, None);
+
+        // Slot `n` commits version `n + 1`, so version `n` holds the state block `n` was built on.
+        set_state_version(Some(block.header.number), working_set)?;
+
+        for preceding_tx in &preceding_txs {
+            // Only the state changes matter here, the results were already recorded in the receipts.
+            let _ = executor::execute_tx(
+                self.get_db(working_set),
+                &block_env,
+                preceding_tx,
+                cfg_env.clone(),
+            );
         }
 
-        // if the provided gas limit is less than computed cap, use that
-        let gas_limit = std::cmp::min(U256::from(tx_env.gas_limit), highest_gas_limit);
-        block_env.gas_limit = convert_u256_to_u64(gas_limit).unwrap();
-
-        let evm_db = self.get_db(working_set);
+        let tx_env = create_tx_env(&tx.into());
+        let trace = self.trace_tx_env(
+            opts.unwrap_or_default(),
+            &block_env,
+            tx_env,
+            cfg_env,
+            working_set,
+        );
 
-        // execute the call without writing to db
-        let result = executor::inspect(evm_db, &block_env, tx_env.clone(), cfg_env.clone());
+        working_set.unset_archival_version();
 
-        // Exceptional case: init used too much gas, we need to increase the gas limit and try
-        // again
-        if let Err(EVMError::Transaction(InvalidTransaction::CallerGasLimitMoreThanBlock)) = result
-        {
-            // if price or limit was included in the request then we can execute the request
-            // again with the block's gas limit to check if revert is gas related or not
-            if request_gas.is_some() || request_gas_price.is_some() {
-                let evm_db = self.get_db(working_set);
-                return Err(map_out_of_gas_err(block_env, tx_env, cfg_env, evm_db).into());
-            }
-        }
+        Ok(trace?.0)
+    }
 
-        let result = match result {
-            Ok(result) => match result.result {
-                ExecutionResult::Success { .. } => result.result,
-                ExecutionResult::Halt { reason, gas_used } => {
-                    return Err(RpcInvalidTransactionError::halt(reason, gas_used).into())
-                }
-                ExecutionResult::Revert { output, .. } => {
-                    // if price or limit was included in the request then we can execute the request
-                    // again with the block's gas limit to check if revert is gas related or not
-                    return if request_gas.is_some() || request_gas_price.is_some() {
-                        let evm_db = self.get_db(working_set);
-                        Err(map_out_of_gas_err(block_env, tx_env, cfg_env, evm_db).into())
-                    } else {
-                        // the transaction did revert
-                        Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
-                    };
-                }
-            },
-            Err(err) => return Err(EthApiError::from(err).into()),
+    /// Handler for: `debug_traceBlockByNumber`
+    #[rpc_method(name = "debug_traceBlockByNumber")]
+    pub fn debug_trace_block_by_number(
+        &self,
+        block_number: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        info!("evm module: debug_traceBlockByNumber({:?})", block_number);
+
+        let block_number = match block_number {
+            BlockNumberOrTag::Number(number) => number,
+            BlockNumberOrTag::Earliest => 0,
+            // safe, finalized, and pending are not supported, the latest block is used instead
+            _ => (self.blocks.len(&mut working_set.accessory_state()) as u64).saturating_sub(1),
         };
 
-        // at this point we know the call succeeded but want to find the _best_ (lowest) gas the
-        // transaction succeeds with. we  find this by doing a binary search over the
-        // possible range NOTE: this is the gas the transaction used, which is less than the
-        // transaction requires to succeed
-        let gas_used = result.gas_used();
-        // the lowest value is capped by the gas it takes for a transfer
-        let mut lowest_gas_limit = if tx_env.transact_to.is_create() {
-            MIN_CREATE_GAS
-        } else {
-            MIN_TRANSACTION_GAS
-        };
-        let mut highest_gas_limit: u64 = highest_gas_limit.try_into().unwrap_or(u64::MAX);
-        // pick a point that's close to the estimated gas
-        let mut mid_gas_limit = std::cmp::min(
-            gas_used * 3,
-            ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64,
-        );
-        // binary search
-        while (highest_gas_limit - lowest_gas_limit) > 1 {
-            let mut tx_env = tx_env.clone();
-            tx_env.gas_limit = mid_gas_limit;
+        let mut traces = Vec::new();
+        self.trace_block(
+            block_number,
+            opts.unwrap_or_default(),
+            working_set,
+            |trace| traces.push(trace),
+        )?;
+        Ok(traces)
+    }
 
-            let evm_db = self.get_db(working_set);
-            let result = executor::inspect(evm_db, &block_env, tx_env.clone(), cfg_env.clone());
+    /// Handler for: `debug_traceBlockByHash`
+    #[rpc_method(name = "debug_traceBlockByHash")]
+    pub fn debug_trace_block_by_hash(
+        &self,
+        block_hash: reth_primitives::H256,
+        opts: Option<GethDebugTracingOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        info!("evm module: debug_traceBlockByHash({})", block_hash);
 
-            // Exceptional case: init used too much gas, we need to increase the gas limit and try
-            // again
-            if let Err(EVMError::Transaction(InvalidTransaction::CallerGasLimitMoreThanBlock)) =
-                result
-            {
-                // increase the lowest gas limit
-                lowest_gas_limit = mid_gas_limit;
+        let block_number = self
+            .block_hashes
+            .get(&block_hash, &mut working_set.accessory_state())
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+
+        let mut traces = Vec::new();
+        self.trace_block(
+            block_number,
+            opts.unwrap_or_default(),
+            working_set,
+            |trace| traces.push(trace),
+        )?;
+        Ok(traces)
+    }
 
-                // new midpoint
-                mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
-                continue;
-            }
+    /// Handler for: `debug_traceCall`. Traces the call on top of the state of the given block,
+    /// after applying the state and block overrides of `opts`.
+    // https://github.com/paradigmxyz/reth/blob/main/crates/rpc/rpc/src/debug.rs
+    #[rpc_method(name = "debug_traceCall")]
+    pub fn debug_trace_call(
+        &self,
+        request: reth_rpc_types::CallRequest,
+        block_number: Option<String>,
+        opts: Option<GethDebugTracingCallOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<GethTrace> {
+        info!("evm module: debug_traceCall");
+
+        let mut simulated_call = None;
+        self.simulate_calls(
+            vec![request],
+            block_number,
+            opts.unwrap_or_default(),
+            working_set,
+            |call| simulated_call = Some(call),
+        )?;
+
+        let simulated_call = simulated_call.expect("A single call is always simulated")?;
+        Ok(simulated_call
+            .trace
+            .expect("An executed call is always traced"))
+    }
 
-            match result {
-                Ok(result) => match result.result {
-                    ExecutionResult::Success { .. } => {
-                        // cap the highest gas limit with succeeding gas limit
-                        highest_gas_limit = mid_gas_limit;
-                    }
-                    ExecutionResult::Revert { .. } => {
-                        // increase the lowest gas limit
-                        lowest_gas_limit = mid_gas_limit;
-                    }
-                    ExecutionResult::Halt { reason, .. } => {
-                        match reason {
-                            Halt::OutOfGas(_) => {
-                                // increase the lowest gas limit
-                                lowest_gas_limit = mid_gas_limit;
-                            }
-                            err => {
-                                // these should be unreachable because we know the transaction succeeds,
-                                // but we consider these cases an error
-                                return Err(RpcInvalidTransactionError::EvmHalt(err).into());
-                            }
-                        }
+    /// Handler for: `eth_callMany`. Executes the bundle of calls in order on top of the state of
+    /// the given block, each call seeing the state changes of the previous ones,
+    /// and returns the output, the trace and the state diff of every call.
+    ///
+    /// The calls share the state and block overrides, and the tracer of `opts`.
+    /// A call which can't be executed doesn't change the state and is reported with its error.
+    #[rpc_method(name = "eth_callMany")]
+    pub fn eth_call_many(
+        &self,
+        requests: Vec<reth_rpc_types::CallRequest>,
+        block_number: Option<String>,
+        opts: Option<GethDebugTracingCallOptions>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<SimulatedCall>> {
+        info!("evm module: eth_callMany({} calls)", requests.len());
+
+        let mut simulated_calls = Vec::with_capacity(requests.len());
+        self.simulate_calls(
+            requests,
+            block_number,
+            opts.unwrap_or_default(),
+            working_set,
+            |call| {
+                simulated_calls.push(call.unwrap_or_else(|e| SimulatedCall {
+                    output: Default::default(),
+                    error: Some(e.to_string()),
+                    gas_used: Default::default(),
+                    trace: None,
+                    state_diff: Default::default(),
+                }))
+            },
+        )?;
+        Ok(simulated_calls)
+    }
+
+    /// Applies the `eth_call` state overrides of each account to the working set.
+    fn apply_state_overrides(
+        &self,
+        state_overrides: reth_rpc_types::state::StateOverride,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<()> {
+        for (address, account_override) in state_overrides {
+            let AccountOverride {
+                nonce,
+                code,
+                balance,
+                state,
+                state_diff,
+            } = account_override;
+
+            let existing_account = self.accounts.get(&address, working_set);
+            let mut db_account = match (state, state_diff) {
+                (Some(_), Some(_)) => {
+                    return Err(EthApiError::BothStateAndStateDiffInOverride(address))
+                }
+                // The whole storage is replaced, so none of the existing slots can be visible
+                (Some(state), None) => {
+                    let info = existing_account
+                        .map(|account| account.info)
+                        .unwrap_or_else(empty_account_info);
+                    let db_account =
+                        DbAccount::new_with_empty_storage(self.accounts.prefix(), address, info);
+                    apply_storage_override(&db_account, state, working_set);
+                    db_account
+                }
+                (None, state_diff) => {
+                    let db_account = existing_account.unwrap_or_else(|| {
+                        DbAccount::new_with_info(
+                            self.accounts.prefix(),
+                            address,
+                            empty_account_info(),
+                        )
+                    });
+                    if let Some(state_diff) = state_diff {
+                        apply_storage_override(&db_account, state_diff, working_set);
                     }
-                },
-                Err(err) => return Err(EthApiError::from(err).into()),
+                    db_account
+                }
             };
 
-            // new midpoint
-            mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
+            if let Some(nonce) = nonce {
+                db_account.info.nonce = nonce.to::<u64>();
+            }
+            if let Some(balance) = balance {
+                db_account.info.balance = balance;
+            }
+            if let Some(code) = code {
+                let code_hash = keccak256(&code);
+                self.code.set(&code_hash, &code, working_set);
+                db_account.info.code_hash = code_hash;
+            }
+
+            self.accounts.set(&address, &db_account, working_set);
         }
 
-        Ok(U64::from(highest_gas_limit))
+        Ok(())
     }
 
-    fn get_sealed_block_by_number(
+    /// Executes `tx_env` on top of the current state without committing it,
+    /// and returns the trace produced by the tracer requested in `opts`,
+    /// along with the execution result and the state changes.
+    fn trace_tx_env(
         &self,
-        block_number: Option<String>,
+        opts: GethDebugTracingOptions,
+        block_env: &BlockEnv,
+        tx_env: TxEnv,
+        cfg_env: CfgEnv,
         working_set: &mut WorkingSet<C>,
-    ) -> SealedBlock {
+    ) -> EthResult<(GethTrace, ResultAndState)> {
+        let GethDebugTracingOptions {
+            config,
+            tracer,
+            tracer_config,
+            ..
+        } = opts;
+
+        let tracer = match tracer {
+            Some(tracer) => tracer,
+            None => {
+                // Default struct logger
+                let mut inspector =
+                    TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+                let res = executor::inspect_with(
+                    self.get_db(working_set),
+                    block_env,
+                    tx_env,
+                    cfg_env,
+                    &mut inspector,
+                )?;
+                let gas_used = res.result.gas_used();
+                let return_value = execution_output(&res.result);
+
+                let trace = inspector
+                    .into_geth_builder()
+                    .geth_traces(gas_used, return_value, config)
+                    .into();
+                return Ok((trace, res));
+            }
+        };
+
+        match tracer {
+            GethDebugTracerType::BuiltInTracer(tracer) => match tracer {
+                GethDebugBuiltInTracerType::FourByteTracer => {
+                    let mut inspector = FourByteInspector::default();
+                    let res = executor::inspect_with(
+                        self.get_db(working_set),
+                        block_env,
+                        tx_env,
+                        cfg_env,
+                        &mut inspector,
+                    )?;
+
+                    Ok((FourByteFrame::from(inspector).into(), res))
+                }
+                GethDebugBuiltInTracerType::CallTracer => {
+                    let call_config = tracer_config
+                        .into_call_config()
+                        .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                    let mut inspector = TracingInspector::new(
+                        TracingInspectorConfig::from_geth_config(&config)
+                            .set_record_logs(call_config.with_log.unwrap_or_default()),
+                    );
+                    let res = executor::inspect_with(
+                        self.get_db(working_set),
+                        block_env,
+                        tx_env,
+                        cfg_env,
+                        &mut inspector,
+                    )?;
+
+                    let trace = inspector
+                        .into_geth_builder()
+                        .geth_call_traces(call_config, res.result.gas_used())
+                        .into();
+                    Ok((trace, res))
+                }
+                GethDebugBuiltInTracerType::PreStateTracer => {
+                    let prestate_config = tracer_config
+                        .into_pre_state_config()
+                        .map_err(|_| EthApiError::InvalidTracerConfig)?;
+                    let mut inspector =
+                        TracingInspector::new(TracingInspectorConfig::from_geth_config(&config));
+                    let res = executor::inspect_with(
+                        self.get_db(working_set),
+                        block_env,
+                        tx_env,
+                        cfg_env,
+                        &mut inspector,
+                    )?;
+
+                    let frame = inspector
+                        .into_geth_builder()
+                        .geth_prestate_traces(
+                            &res,
+                            prestate_config,
+                            EvmDbRef::new(self.get_db(working_set)),
+                        )
+                        .unwrap_or_else(|never| match never {});
+
+                    Ok((frame.into(), res))
+                }
+                GethDebugBuiltInTracerType::NoopTracer => {
+                    // The transaction is still executed, since its state changes might be needed
+                    let res = executor::inspect_with(
+                        self.get_db(working_set),
+                        block_env,
+                        tx_env,
+                        cfg_env,
+                        TracingInspector::new(TracingInspectorConfig::none()),
+                    )?;
+
+                    Ok((NoopFrame::default().into(), res))
+                }
+            },
+            GethDebugTracerType::JsTracer(_) => {
+                Err(EthApiError::Unsupported("JS tracer is not supported"))
+            }
+        }
+    }
+
+    /// Returns the sealed block with the given hash, hex number or tag, if it exists.
+    /// The pending block isn't sealed, so it's never returned.
+    fn get_sealed_block_by_id(
+        &self,
+        block_id: String,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<Option<SealedBlock>> {
+        match H256::from_str(&block_id) {
+            Ok(block_hash) => {
+                let mut accessory_state = working_set.accessory_state();
+                Ok(self
+                    .block_hashes
+                    .get(&block_hash, &mut accessory_state)
+                    .map(|block_number| {
+                        self.blocks
+                            .get(block_number as usize, &mut accessory_state)
+                            .expect("Block for known block hash must be set")
+                    }))
+            }
+            Err(_) if block_id == "pending" => Ok(None),
+            Err(_) => match self.get_sealed_block_by_number(Some(block_id), working_set) {
+                Ok(block) => Ok(Some(block)),
+                Err(EthApiError::UnknownBlockNumber) => Ok(None),
+                Err(e) => Err(e),
+            },
+        }
+    }
+
+    /// Returns the transaction at the given index of the block, read from the transaction range
+    /// of the block.
+    fn get_transaction_in_block(
+        &self,
+        block: &SealedBlock,
+        index: reth_primitives::U64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<reth_rpc_types::Transaction> {
+        let tx_number = block.transactions.start.checked_add(index.as_u64())?;
+        if !block.transactions.contains(&tx_number) {
+            return None;
+        }
+        let tx = self
+            .transactions
+            .get(tx_number as usize, &mut working_set.accessory_state())
+            .expect("Transaction of a known block must be set");
+
+        Some(reth_rpc_types_compat::from_recovered_with_block_context(
+            tx.into(),
+            block.header.hash,
+            block.header.number,
+            block.header.base_fee_per_gas,
+            U256::from(index.as_u64()),
+        ))
+    }
+
+    fn get_sealed_block_by_number(
+        &self,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<SealedBlock> {
         // safe, finalized, and pending are not supported
         match block_number {
-            Some(ref block_number) if block_number == "earliest" => self
+            Some(ref block_number) if block_number == "earliest" => Ok(self
                 .blocks
                 .get(0, &mut working_set.accessory_state())
-                .expect("Genesis block must be set"),
-            Some(ref block_number) if block_number == "latest" => self
+                .expect("Genesis block must be set")),
+            Some(ref block_number) if block_number == "latest" => Ok(self
                 .blocks
                 .last(&mut working_set.accessory_state())
-                .expect("Head block must be set"),
+                .expect("Head block must be set")),
             Some(ref block_number) => {
                 // hex representation may have 0x prefix
                 let block_number = usize::from_str_radix(block_number.trim_start_matches("0x"), 16)
-                    .expect("Block number must be a valid hex number, with or without 0x prefix");
+                    .map_err(|_| {
+                        EthApiError::InvalidParams(
+                            "Block number must be a valid hex number, with or without 0x prefix"
+                                .to_string(),
+                        )
+                    })?;
                 self.blocks
                     .get(block_number, &mut working_set.accessory_state())
-                    .expect("Block must be set")
+                    .ok_or(EthApiError::UnknownBlockNumber)
             }
-            None => self
+            None => Ok(self
                 .blocks
                 .last(&mut working_set.accessory_state())
-                .expect("Head block must be set"),
+                .expect("Head block must be set")),
         }
     }
 }
 
+impl<C: sov_modules_api::Context> Evm<C> {
+    /// Returns the environment of the block calls are executed in, along with the archival version
+    /// of the state after that block if it isn't the latest one.
+    fn call_block_env(
+        &self,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<(BlockEnv, Option<u64>)> {
+        match block_number {
+            Some(ref block_number) if block_number == "pending" => {
+                Ok((self.pending_block_env(working_set), None))
+            }
+            _ => {
+                let block = self.get_sealed_block_by_number(block_number, working_set)?;
+                let head_block_number =
+                    (self.blocks.len(&mut working_set.accessory_state()) as u64).saturating_sub(1);
+                // Slot `n` commits version `n + 1`, so version `n + 1` holds the state after block `n`.
+                let archival_version =
+                    (block.header.number < head_block_number).then_some(block.header.number + 1);
+                Ok((BlockEnv::from(&block), archival_version))
+            }
+        }
+    }
+
+    fn call_with_env(
+        &self,
+        request: reth_rpc_types::CallRequest,
+        block_env: &BlockEnv,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<reth_primitives::Bytes> {
+        let tx_env = prepare_call_env(block_env, request).unwrap();
+
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let cfg_env = get_cfg_env(block_env, cfg, Some(get_cfg_env_template()));
+
+        let evm_db: EvmDb<'_, C> = self.get_db(working_set);
+
+        let result = executor::inspect(evm_db, block_env, tx_env, cfg_env)
+            .map_err(EthApiError::from)?
+            .result;
+
+        ensure_success(result)
+    }
+
+    /// Executes `requests` in order on top of the state after the given block, with the state
+    /// and block overrides of `opts`, passing the outcome of every call to `on_call`.
+    ///
+    /// The `timeout` of the tracing options (5 seconds by default) is checked before each call.
+    /// Only the working set of the RPC request is changed, which is never committed.
+    fn simulate_calls(
+        &self,
+        requests: Vec<reth_rpc_types::CallRequest>,
+        block_number: Option<String>,
+        opts: GethDebugTracingCallOptions,
+        working_set: &mut WorkingSet<C>,
+        on_call: impl FnMut(EthResult<SimulatedCall>),
+    ) -> EthResult<()> {
+        let GethDebugTracingCallOptions {
+            tracing_options,
+            state_overrides,
+            block_overrides,
+        } = opts;
+
+        let (mut block_env, archival_version) = self.call_block_env(block_number, working_set)?;
+        if let Some(block_overrides) = block_overrides {
+            apply_block_overrides(&mut block_env, block_overrides)?;
+        }
+
+        set_state_version(archival_version, working_set)?;
+        // The overrides are applied on top of the archival state, if any
+        let result = match state_overrides {
+            Some(state_overrides) => self.apply_state_overrides(state_overrides, working_set),
+            None => Ok(()),
+        }
+        .and_then(|_| {
+            self.simulate_calls_with_env(
+                requests,
+                &block_env,
+                tracing_options,
+                working_set,
+                on_call,
+            )
+        });
+        working_set.unset_archival_version();
+
+        result
+    }
+
+    fn simulate_calls_with_env(
+        &self,
+        requests: Vec<reth_rpc_types::CallRequest>,
+        block_env: &BlockEnv,
+        tracing_options: GethDebugTracingOptions,
+        working_set: &mut WorkingSet<C>,
+        mut on_call: impl FnMut(EthResult<SimulatedCall>),
+    ) -> EthResult<()> {
+        let timeout = match &tracing_options.timeout {
+            Some(timeout) => parse_trace_timeout(timeout)?,
+            None => DEFAULT_TRACE_TIMEOUT,
+        };
+        let started_at = Instant::now();
+
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let cfg_env = get_cfg_env(block_env, cfg, Some(get_cfg_env_template()));
+
+        for request in requests {
+            if started_at.elapsed() > timeout {
+                return Err(EthApiError::ExecutionTimedOut(timeout));
+            }
+
+            let simulated_call = prepare_call_env(block_env, request).and_then(|tx_env| {
+                self.trace_tx_env(
+                    tracing_options.clone(),
+                    block_env,
+                    tx_env,
+                    cfg_env.clone(),
+                    working_set,
+                )
+            });
+
+            match simulated_call {
+                Ok((trace, res)) => {
+                    let state_diff = self.state_diff(&res.state, working_set);
+                    let simulated_call = SimulatedCall {
+                        output: execution_output(&res.result),
+                        error: ensure_success(res.result.clone())
+                            .err()
+                            .map(|e| e.to_string()),
+                        gas_used: reth_primitives::U64::from(res.result.gas_used()),
+                        trace: Some(trace),
+                        state_diff,
+                    };
+                    // The following calls are executed on top of this one
+                    self.get_db(working_set).commit(res.state);
+                    on_call(Ok(simulated_call));
+                }
+                // The options are the same for all the calls
+                Err(e @ (EthApiError::InvalidTracerConfig | EthApiError::Unsupported(_))) => {
+                    return Err(e)
+                }
+                Err(e) => on_call(Err(e)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the changes of `state` to the accounts of the working set.
+    /// Accounts which were only read are left out.
+    fn state_diff(
+        &self,
+        state: &revm::primitives::HashMap<Address, revm::primitives::Account>,
+        working_set: &mut WorkingSet<C>,
+    ) -> BTreeMap<Address, AccountStateDiff> {
+        let mut state_diff = BTreeMap::new();
+        for (address, account) in state {
+            let before = self
+                .accounts
+                .get(address, working_set)
+                .map(|account| account.info)
+                .unwrap_or_else(empty_account_info);
+            let after = &account.info;
+
+            let account_diff = AccountStateDiff {
+                balance: ValueDiff::of(before.balance, after.balance),
+                nonce: ValueDiff::of(
+                    reth_primitives::U64::from(before.nonce),
+                    reth_primitives::U64::from(after.nonce),
+                ),
+                code_hash: ValueDiff::of(before.code_hash, after.code_hash),
+                storage: account
+                    .storage
+                    .iter()
+                    .filter(|(_, slot)| slot.is_changed())
+                    .map(|(key, slot)| {
+                        (
+                            H256::from(key.to_be_bytes()),
+                            ValueDiff {
+                                from: H256::from(slot.original_value().to_be_bytes()),
+                                to: H256::from(slot.present_value().to_be_bytes()),
+                            },
+                        )
+                    })
+                    .collect(),
+            };
+
+            if account_diff != AccountStateDiff::default() {
+                state_diff.insert(*address, account_diff);
+            }
+        }
+        state_diff
+    }
+
+    /// Finds the lowest gas limit the call succeeds with, by executing it on top of the current state
+    /// of the working set with the given block environment.
+    ///
+    /// The call is first executed with the highest possible gas limit, then a binary search is done
+    /// between the lowest gas limit of a transaction and the highest one. All the executions
+    /// share the same cache of the state.
+    pub(crate) fn estimate_gas_with_env(
+        &self,
+        request: reth_rpc_types::CallRequest,
+        block_env: BlockEnv,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<U64> {
+        let tx_env = prepare_call_env(&block_env, request.clone())?;
+
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+
+        let request_gas = request.gas;
+        let request_gas_price = request.gas_price;
+
+        // get the highest possible gas limit, either the request's set value or the currently
+        // configured gas limit
+        let mut highest_gas_limit = request.gas.unwrap_or(U256::from(block_env.gas_limit));
+
+        let account = self
+            .accounts
+            .get(&tx_env.caller, working_set)
+            .map(|account| account.info)
+            .unwrap_or_default();
+
+        if tx_env.value > account.balance {
+            return Err(RpcInvalidTransactionError::InsufficientFundsForTransfer.into());
+        }
+
+        // if the request is a simple transfer we can optimize
+        if tx_env.data.is_empty() {
+            if let TransactTo::Call(to) = tx_env.transact_to {
+                let to_account = self
+                    .accounts
+                    .get(&to, working_set)
+                    .map(|account| account.info)
+                    .unwrap_or_default();
+                if KECCAK_EMPTY == to_account.code_hash {
+                    return Ok(U64::from(MIN_TRANSACTION_GAS));
+                }
+            }
+        }
+
+        // check funds of the sender
+        if tx_env.gas_price > U256::ZERO {
+            // allowance is (balance - tx.value) / tx.gas_price
+            let allowance = (account.balance - tx_env.value) / tx_env.gas_price;
+
+            if highest_gas_limit > allowance {
+                // cap the highest gas limit by max gas caller can afford with given gas price
+                highest_gas_limit = allowance;
+            }
+        }
+
+        // if the provided gas limit is less than computed cap, use that
+        let gas_limit = std::cmp::min(U256::from(tx_env.gas_limit), highest_gas_limit);
+        // the gas limit is at most the one of the transaction, so it fits in a u64
+        let gas_limit = convert_u256_to_u64(gas_limit).unwrap_or(u64::MAX);
+
+        let is_create = tx_env.transact_to.is_create();
+        let mut executor = CachedExecutor::new(
+            EvmDbRef::new(self.get_db(working_set)),
+            &block_env,
+            tx_env,
+            cfg_env,
+        );
+
+        // execute the call without writing to db
+        let result = executor.transact_with_gas_limit(gas_limit);
+
+        // Exceptional case: init used too much gas, we need to increase the gas limit and try
+        // again
+        if let Err(EVMError::Transaction(InvalidTransaction::CallerGasLimitMoreThanBlock)) = result
+        {
+            // if price or limit was included in the request then we can execute the request
+            // again with the block's gas limit to check if revert is gas related or not
+            if request_gas.is_some() || request_gas_price.is_some() {
+                return Err(map_out_of_gas_err(
+                    &mut executor,
+                    gas_limit,
+                    block_env.gas_limit,
+                ));
+            }
+        }
+
+        let result = match result {
+            Ok(result) => match result.result {
+                ExecutionResult::Success { .. } => result.result,
+                ExecutionResult::Halt { reason, gas_used } => {
+                    return Err(RpcInvalidTransactionError::halt(reason, gas_used).into())
+                }
+                ExecutionResult::Revert { output, .. } => {
+                    // if price or limit was included in the request then we can execute the request
+                    // again with the block's gas limit to check if revert is gas related or not
+                    return if request_gas.is_some() || request_gas_price.is_some() {
+                        Err(map_out_of_gas_err(
+                            &mut executor,
+                            gas_limit,
+                            block_env.gas_limit,
+                        ))
+                    } else {
+                        // the transaction did revert
+                        Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
+                    };
+                }
+            },
+            Err(err) => return Err(EthApiError::from(err)),
+        };
+
+        // at this point we know the call succeeded but want to find the _best_ (lowest) gas the
+        // transaction succeeds with. we  find this by doing a binary search over the
+        // possible range NOTE: this is the gas the transaction used, which is less than the
+        // transaction requires to succeed
+        let gas_used = result.gas_used();
+        // the lowest value is capped by the gas it takes for a transfer
+        let mut lowest_gas_limit = if is_create {
+            MIN_CREATE_GAS
+        } else {
+            MIN_TRANSACTION_GAS
+        };
+        let mut highest_gas_limit = gas_limit;
+        // pick a point that's close to the estimated gas
+        let mut mid_gas_limit = std::cmp::min(
+            gas_used * 3,
+            ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64,
+        );
+        // binary search
+        while (highest_gas_limit - lowest_gas_limit) > 1 {
+            match executor.transact_with_gas_limit(mid_gas_limit) {
+                Ok(result) => match result.result {
+                    ExecutionResult::Success { .. } => {
+                        // cap the highest gas limit with succeeding gas limit
+                        highest_gas_limit = mid_gas_limit;
+                    }
+                    ExecutionResult::Revert { .. } => {
+                        // increase the lowest gas limit
+                        lowest_gas_limit = mid_gas_limit;
+                    }
+                    ExecutionResult::Halt { reason, .. } => {
+                        match reason {
+                            Halt::OutOfGas(_) => {
+                                // increase the lowest gas limit
+                                lowest_gas_limit = mid_gas_limit;
+                            }
+                            err => {
+                                // these should be unreachable because we know the transaction succeeds,
+                                // but we consider these cases an error
+                                return Err(RpcInvalidTransactionError::EvmHalt(err).into());
+                            }
+                        }
+                    }
+                },
+                // Exceptional case: init used too much gas, we need to increase the gas limit and try
+                // again
+                Err(EVMError::Transaction(InvalidTransaction::CallerGasLimitMoreThanBlock)) => {
+                    lowest_gas_limit = mid_gas_limit;
+                }
+                Err(err) => return Err(EthApiError::from(err)),
+            };
+
+            // new midpoint
+            mid_gas_limit = ((highest_gas_limit as u128 + lowest_gas_limit as u128) / 2) as u64;
+        }
+
+        Ok(U64::from(highest_gas_limit))
+    }
+
+    /// Executes the call with an inspector recording the accounts and storage slots it accesses,
+    /// on top of the current state of the working set, then estimates its gas with the resulting
+    /// access list.
+    ///
+    /// The sender, the recipient and the precompiles are warm anyway, so they're only part of the
+    /// access list for their storage slots. The entries of the access list of the request are kept.
+    fn create_access_list_with_env(
+        &self,
+        mut request: reth_rpc_types::CallRequest,
+        block_env: BlockEnv,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<AccessListWithGasUsed> {
+        let tx_env = prepare_call_env(&block_env, request.clone())?;
+
+        let cfg = self.chain_config(working_set).unwrap_or_default();
+        let cfg_env = get_cfg_env(&block_env, cfg, Some(get_cfg_env_template()));
+
+        let from = tx_env.caller;
+        let to = match tx_env.transact_to {
+            TransactTo::Call(to) => to,
+            TransactTo::Create(_) => {
+                let nonce = match tx_env.nonce {
+                    Some(nonce) => nonce,
+                    None => self
+                        .accounts
+                        .get(&from, working_set)
+                        .map(|account| account.info.nonce)
+                        .unwrap_or_default(),
+                };
+                create_address(from, nonce)
+            }
+        };
+        let mut inspector = AccessListInspector::new(
+            request.access_list.take().unwrap_or_default(),
+            from,
+            to,
+            precompiles::addresses(revm::to_precompile_id(cfg_env.spec_id)),
+        );
+
+        let result = executor::inspect_with(
+            self.get_db(working_set),
+            &block_env,
+            tx_env,
+            cfg_env,
+            &mut inspector,
+        )
+        .map_err(EthApiError::from)?;
+        ensure_success(result.result)?;
+
+        let access_list = inspector.into_access_list();
+        request.access_list = Some(access_list.clone());
+        let gas_used = self.estimate_gas_with_env(request, block_env, working_set)?;
+
+        Ok(AccessListWithGasUsed {
+            access_list,
+            gas_used: U256::from(gas_used.as_u64()),
+        })
+    }
+
+    /// Re-executes all the transactions of the block with the given number in order,
+    /// on top of the state the block was built on, passing the trace of every transaction
+    /// to `on_trace` as soon as it's produced.
+    ///
+    /// The `timeout` of `opts` (5 seconds by default) is checked before each transaction is traced.
+    pub fn trace_block(
+        &self,
+        block_number: u64,
+        opts: GethDebugTracingOptions,
+        working_set: &mut WorkingSet<C>,
+        mut on_trace: impl FnMut(TraceResult),
+    ) -> Result<(), EthApiError> {
+        let timeout = match &opts.timeout {
+            Some(timeout) => parse_trace_timeout(timeout)?,
+            None => DEFAULT_TRACE_TIMEOUT,
+        };
+        let started_at = Instant::now();
+
+        let mut accessory_state = working_set.accessory_state();
+        let block = self
+            .blocks
+            .get(block_number as usize, &mut accessory_state)
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let txs: Vec<TransactionSignedEcRecovered> = block
+            .transactions
+            .clone()
+            .map(|number| {
+                self.transactions
+                    .get(number as usize, &mut accessory_state)
+                    .expect("Transaction of a known block must be set")
+                    .into()
+            })
+            .collect();
+
+        let block_env = BlockEnv::from(&block);
+        let cfg = self
+            .chain_config(working_set)
+            .expect("EVM config must be set at genesis");
+        let cfg_env = get_cfg_env(&block_env, cfg, None);
+
+        // Slot `n` commits version `n + 1`, so version `n` holds the state block `n` was built on.
+        set_state_version(Some(block.header.number), working_set)?;
+
+        let mut result = Ok(());
+        for tx in &txs {
+            if started_at.elapsed() > timeout {
+                result = Err(EthApiError::ExecutionTimedOut(timeout));
+                break;
+            }
+
+            let trace = match self.trace_tx_env(
+                opts.clone(),
+                &block_env,
+                create_tx_env(tx),
+                cfg_env.clone(),
+                working_set,
+            ) {
+                Ok((trace, res)) => {
+                    // The following transactions are executed on top of this one
+                    self.get_db(working_set).commit(res.state);
+                    TraceResult::Success { result: trace }
+                }
+                // The options are the same for all the transactions
+                Err(e @ (EthApiError::InvalidTracerConfig | EthApiError::Unsupported(_))) => {
+                    result = Err(e);
+                    break;
+                }
+                Err(e) => TraceResult::Error {
+                    error: e.to_string(),
+                },
+            };
+            on_trace(trace);
+        }
+
+        working_set.unset_archival_version();
+
+        result
+    }
+
+    /// Handler for: `eth_getLogs`. Scans at most `max_block_range` blocks
+    /// and returns at most `max_results` logs.
+    ///
+    /// The logs bloom of every block is checked before its receipts are loaded,
+    /// so blocks without matching logs are skipped cheaply.
+    pub fn get_logs(
+        &self,
+        filter: Filter,
+        max_block_range: u64,
+        max_results: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Vec<reth_rpc_types::Log>> {
+        info!("evm module: eth_getLogs");
+
+        Ok(self.logs_for_filter(filter, max_block_range, max_results, working_set)?)
+    }
+
+    fn logs_for_filter(
+        &self,
+        filter: Filter,
+        max_block_range: u64,
+        max_results: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<Vec<reth_rpc_types::Log>> {
+        let mut accessory_state = working_set.accessory_state();
+        let Some((from_block, to_block)) = self.logs_block_range(&filter, &mut accessory_state)?
+        else {
+            return Ok(Vec::new());
+        };
+        if to_block - from_block >= max_block_range {
+            return Err(EthApiError::QueryExceedsMaxBlocks(max_block_range));
+        }
+
+        let start = LogsCursor {
+            block_number: from_block,
+            log_index: 0,
+        };
+        let (logs, next) =
+            self.scan_logs(filter, start, to_block, max_results, &mut accessory_state)?;
+        if next.is_some() {
+            return Err(EthApiError::QueryExceedsMaxResults(max_results));
+        }
+        Ok(logs)
+    }
+
+    /// Handler for: `sov_getLogsPage`. Returns a page of the logs matching the filter, starting
+    /// from `cursor`, or from the start of the filter if it's missing.
+    ///
+    /// Unlike `eth_getLogs`, the queries are not limited: every page scans at most `max_block_range`
+    /// blocks and returns at most `page_size` logs, along with the cursor of the next page
+    /// until all the logs are returned.
+    pub fn get_logs_page(
+        &self,
+        filter: Filter,
+        cursor: Option<LogsCursor>,
+        page_size: usize,
+        max_block_range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<LogsPage> {
+        info!("evm module: sov_getLogsPage");
+
+        Ok(self.logs_page(filter, cursor, page_size, max_block_range, working_set)?)
+    }
+
+    pub(crate) fn logs_page(
+        &self,
+        filter: Filter,
+        cursor: Option<LogsCursor>,
+        page_size: usize,
+        max_block_range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> EthResult<LogsPage> {
+        let mut accessory_state = working_set.accessory_state();
+        let Some((from_block, to_block)) = self.logs_block_range(&filter, &mut accessory_state)?
+        else {
+            return Ok(LogsPage::default());
+        };
+        let start = cursor.unwrap_or(LogsCursor {
+            block_number: from_block,
+            log_index: 0,
+        });
+        if start.block_number < from_block || start.block_number > to_block {
+            return Err(EthApiError::InvalidParams(
+                "The cursor is outside of the block range of the filter".to_string(),
+            ));
+        }
+        if page_size == 0 {
+            return Err(EthApiError::InvalidParams(
+                "The page size must be positive".to_string(),
+            ));
+        }
+
+        let page_end = to_block.min(
+            start
+                .block_number
+                .saturating_add(max_block_range.max(1) - 1),
+        );
+        let (logs, next) =
+            self.scan_logs(filter, start, page_end, page_size, &mut accessory_state)?;
+        let cursor = next.or_else(|| {
+            (page_end < to_block).then_some(LogsCursor {
+                block_number: page_end + 1,
+                log_index: 0,
+            })
+        });
+        Ok(LogsPage { logs, cursor })
+    }
+
+    /// Resolves the blocks spanned by the filter, up to the latest block.
+    /// Returns `None` if the filter starts after the latest block.
+    fn logs_block_range(
+        &self,
+        filter: &Filter,
+        accessory_state: &mut AccessoryWorkingSet<C>,
+    ) -> EthResult<Option<(u64, u64)>> {
+        let latest_block = (self.blocks.len(accessory_state) as u64).saturating_sub(1);
+
+        let (from_block, to_block) = match &filter.block_option {
+            FilterBlockOption::AtBlockHash(block_hash) => {
+                let block_number = self
+                    .block_hashes
+                    .get(block_hash, accessory_state)
+                    .ok_or(EthApiError::UnknownBlockNumber)?;
+                (block_number, block_number)
+            }
+            FilterBlockOption::Range {
+                from_block,
+                to_block,
+            } => {
+                // safe, finalized, and pending are not supported, the latest block is used instead
+                let resolve = |block: &Option<BlockNumberOrTag>| match block {
+                    Some(BlockNumberOrTag::Number(number)) => *number,
+                    Some(BlockNumberOrTag::Earliest) => 0,
+                    _ => latest_block,
+                };
+                let (from_block, to_block) = (resolve(from_block), resolve(to_block));
+                if from_block > to_block {
+                    return Err(EthApiError::InvalidBlockRange);
+                }
+                (from_block, to_block.min(latest_block))
+            }
+        };
+
+        Ok((from_block <= to_block).then_some((from_block, to_block)))
+    }
+
+    /// Collects at most `limit` logs matching the filter, from the `start` position to the end
+    /// of `to_block`. Returns the logs, and the position of the first matching log which didn't fit.
+    fn scan_logs(
+        &self,
+        filter: Filter,
+        start: LogsCursor,
+        to_block: u64,
+        limit: usize,
+        accessory_state: &mut AccessoryWorkingSet<C>,
+    ) -> EthResult<(Vec<reth_rpc_types::Log>, Option<LogsCursor>)> {
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+        let filter = FilteredParams::new(Some(filter));
+
+        let mut logs = Vec::new();
+        for block_number in start.block_number..=to_block {
+            let logs_bloom = match self.logs_blooms.get(block_number as usize, accessory_state) {
+                Some(logs_bloom) => logs_bloom,
+                // Blocks finalized before the blooms were stored separately
+                None => {
+                    self.blocks
+                        .get(block_number as usize, accessory_state)
+                        .ok_or(EthApiError::UnknownBlockNumber)?
+                        .header
+                        .logs_bloom
+                }
+            };
+            if !FilteredParams::matches_address(logs_bloom, &address_filter)
+                || !FilteredParams::matches_topics(logs_bloom, &topics_filter)
+            {
+                continue;
+            }
+
+            let block = self
+                .blocks
+                .get(block_number as usize, accessory_state)
+                .ok_or(EthApiError::UnknownBlockNumber)?;
+            for tx_number in block.transactions.clone() {
+                let receipt = self
+                    .receipts
+                    .get(tx_number as usize, accessory_state)
+                    .expect("Receipt for known transaction must be set");
+                // Avoids loading the transactions without matching logs
+                let receipt_bloom = receipt.receipt.bloom_slow();
+                if !FilteredParams::matches_address(receipt_bloom, &address_filter)
+                    || !FilteredParams::matches_topics(receipt_bloom, &topics_filter)
+                {
+                    continue;
+                }
+                // The logs preceding the cursor were returned by a previous page
+                let log_index_end = receipt.log_index_start + receipt.receipt.logs.len() as u64;
+                if block_number == start.block_number && log_index_end <= start.log_index {
+                    continue;
+                }
+
+                let transaction_hash = self
+                    .transactions
+                    .get(tx_number as usize, accessory_state)
+                    .expect("Transaction must be set")
+                    .signed_transaction
+                    .hash;
+                let transaction_index = tx_number - block.transactions.start;
+
+                for (idx, log) in receipt.receipt.logs.into_iter().enumerate() {
+                    let log_index = receipt.log_index_start + idx as u64;
+                    if block_number == start.block_number && log_index < start.log_index {
+                        continue;
+                    }
+                    let log = reth_rpc_types::Log {
+                        address: log.address,
+                        topics: log.topics,
+                        data: log.data,
+                        block_hash: Some(block.header.hash),
+                        block_number: Some(U256::from(block.header.number)),
+                        transaction_hash: Some(transaction_hash),
+                        transaction_index: Some(U256::from(transaction_index)),
+                        log_index: Some(U256::from(log_index)),
+                        removed: false,
+                    };
+                    if !filter.filter_address(&log) || !filter.filter_topics(&log) {
+                        continue;
+                    }
+                    if logs.len() == limit {
+                        let next = LogsCursor {
+                            block_number,
+                            log_index,
+                        };
+                        return Ok((logs, Some(next)));
+                    }
+                    logs.push(log);
+                }
+            }
+        }
+
+        Ok((logs, None))
+    }
+}
+
+impl<C: sov_modules_api::Context> Evm<C>
+where
+    C::Storage: NativeStorage,
+{
+    /// Handler for: `eth_getProof`
+    ///
+    /// Accounts and their storage are not kept in a Merkle Patricia Trie but in the rollup's
+    /// Jellyfish Merkle Tree, so the returned proofs are serialized JMT proofs against the
+    /// `state_root` of the latest block, which is also returned as `storageHash`.
+    /// Every proof is a single element list.
+    pub fn get_proof(
+        &self,
+        address: reth_primitives::Address,
+        keys: Vec<JsonStorageKey>,
+        block_number: Option<String>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<EIP1186AccountProofResponse> {
+        info!("evm module: eth_getProof");
+
+        // The JMT only produces proofs for the latest version
+        if !matches!(block_number.as_deref(), None | Some("latest")) {
+            return Err(
+                EthApiError::Unsupported("Proofs are only available for the latest block").into(),
+            );
+        }
+
+        let state_root = self
+            .blocks
+            .last(&mut working_set.accessory_state())
+            .expect("Head block must be set")
+            .header
+            .state_root;
+
+        let account_proof = working_set.get_with_proof(StorageKey::new(
+            self.accounts.prefix(),
+            &address,
+            self.accounts.codec(),
+        ));
+        let account = self.accounts.get(&address, working_set);
+
+        let storage_proof = keys
+            .into_iter()
+            .map(|key| {
+                let index = U256::from_be_bytes(key.0 .0);
+                let (value, proof) = match &account {
+                    Some(account) => {
+                        let proof = working_set.get_with_proof(StorageKey::new(
+                            account.storage.prefix(),
+                            &index,
+                            account.storage.codec(),
+                        ));
+                        let value = account.storage.get(&index, working_set).unwrap_or_default();
+                        (value, encode_proof::<C>(proof.proof))
+                    }
+                    // Without an account there is no storage, which the account proof already shows
+                    None => (U256::ZERO, Vec::new()),
+                };
+
+                StorageProof { key, value, proof }
+            })
+            .collect();
+
+        let info = account.map(|account| account.info).unwrap_or_default();
+        let code_hash = if info.code_hash == Default::default() {
+            KECCAK_EMPTY
+        } else {
+            info.code_hash
+        };
+
+        Ok(EIP1186AccountProofResponse {
+            address,
+            balance: info.balance,
+            code_hash,
+            nonce: info.nonce.into(),
+            storage_hash: state_root,
+            account_proof: encode_proof::<C>(account_proof.proof),
+            storage_proof,
+        })
+    }
+
+    /// Handler for: `sov_debugEvmAccount`
+    ///
+    /// Returns the keys of the rollup state backing the account: its entry in the `accounts` map
+    /// of the module, the entry of its code in the `code` map, and the prefix of its storage slots.
+    pub fn debug_evm_account(
+        &self,
+        address: reth_primitives::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<EvmAccountLayout> {
+        info!("evm module: sov_debugEvmAccount");
+
+        let account_key = StorageKey::new(self.accounts.prefix(), &address, self.accounts.codec());
+        let storage_prefix = DbAccount::create_storage_prefix(self.accounts.prefix(), address);
+        let info = self
+            .accounts
+            .get(&address, working_set)
+            .map(|account| account.info);
+        let code_key = info
+            .as_ref()
+            .filter(|info| ![H256::default(), KECCAK_EMPTY].contains(&info.code_hash))
+            .map(|info| {
+                StorageKey::new(self.code.prefix(), &info.code_hash, self.code.codec())
+                    .as_ref()
+                    .clone()
+                    .into()
+            });
+
+        Ok(EvmAccountLayout {
+            address,
+            exists: info.is_some(),
+            account_key: account_key.as_ref().clone().into(),
+            balance: info.as_ref().map(|info| info.balance).unwrap_or_default(),
+            nonce: info
+                .as_ref()
+                .map(|info| info.nonce)
+                .unwrap_or_default()
+                .into(),
+            code_hash: info
+                .map(|info| info.code_hash)
+                .filter(|code_hash| *code_hash != H256::default())
+                .unwrap_or(KECCAK_EMPTY),
+            code_key,
+            storage_prefix: storage_prefix.as_aligned_vec().clone().into_inner().into(),
+        })
+    }
+
+    /// Handler for: `sov_debugEvmStorageRange`
+    ///
+    /// Returns up to `limit` storage slots of the account, starting at the slot `start`, like
+    /// `debug_storageRangeAt`. The slots are ordered by the bytes of their keys in the rollup
+    /// state, which isn't their numeric order, and `nextKey` is the slot the next range starts at.
+    pub fn debug_evm_storage_range(
+        &self,
+        address: reth_primitives::Address,
+        start: U256,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<EvmStorageRange> {
+        info!("evm module: sov_debugEvmStorageRange");
+
+        let Some(account) = self.accounts.get(&address, working_set) else {
+            return Ok(EvmStorageRange::default());
+        };
+        let limit = limit.min(MAX_STORAGE_RANGE_LIMIT);
+        let mut slots =
+            account
+                .storage
+                .iter_range(Some(&start), None, limit.saturating_add(1), working_set);
+        let next_key = if slots.len() > limit {
+            slots.pop().map(|(slot, _)| slot)
+        } else {
+            None
+        };
+
+        let storage = slots
+            .into_iter()
+            .map(|(slot, value)| {
+                let state_key =
+                    StorageKey::new(account.storage.prefix(), &slot, account.storage.codec());
+                (
+                    format!("0x{}", hex::encode(state_key.as_ref())),
+                    StorageRangeEntry { key: slot, value },
+                )
+            })
+            .collect();
+
+        Ok(EvmStorageRange { storage, next_key })
+    }
+}
+
+/// The maximum number of slots returned by `sov_debugEvmStorageRange`.
+const MAX_STORAGE_RANGE_LIMIT: usize = 1024;
+
+/// The keys of the rollup state backing an EVM account, returned by `sov_debugEvmAccount`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmAccountLayout {
+    /// The address of the account.
+    pub address: Address,
+    /// Whether the account is in the state.
+    pub exists: bool,
+    /// The key of the account in the `accounts` map of the module. Its value is the BCS encoded
+    /// balance, code hash and nonce of the account, and the prefix of its storage.
+    pub account_key: reth_primitives::Bytes,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The nonce of the account.
+    pub nonce: reth_primitives::U64,
+    /// The hash of the code of the account.
+    pub code_hash: H256,
+    /// The key of the code in the `code` map of the module, missing for accounts without code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_key: Option<reth_primitives::Bytes>,
+    /// The prefix of the keys of the storage slots of the account. The key of a slot is the
+    /// prefix followed by the BCS encoded slot.
+    pub storage_prefix: reth_primitives::Bytes,
+}
+
+/// A range of the storage of an EVM account, returned by `sov_debugEvmStorageRange`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvmStorageRange {
+    /// The slots of the range, by their hex encoded key in the rollup state.
+    pub storage: BTreeMap<String, StorageRangeEntry>,
+    /// The slot the next range starts at, or `None` if this is the last range.
+    pub next_key: Option<U256>,
+}
+
+/// A storage slot of an EVM account.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct StorageRangeEntry {
+    /// The slot.
+    pub key: U256,
+    /// The value of the slot.
+    pub value: U256,
+}
+
+/// The outcome of a call simulated by `eth_callMany`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    /// The returned data of the call, or the revert data if it reverted.
+    pub output: reth_primitives::Bytes,
+    /// Why the call failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The gas used by the call.
+    pub gas_used: reth_primitives::U64,
+    /// The trace of the call, produced by the requested tracer.
+    /// Missing if the call couldn't be executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<GethTrace>,
+    /// The changes of the call to every account it modified.
+    pub state_diff: BTreeMap<Address, AccountStateDiff>,
+}
+
+/// The changes of a call to an account. Unchanged fields are missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStateDiff {
+    /// The change of the balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<ValueDiff<U256>>,
+    /// The change of the nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<ValueDiff<reth_primitives::U64>>,
+    /// The change of the code hash, when a contract is created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_hash: Option<ValueDiff<H256>>,
+    /// The changed storage slots.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, ValueDiff<H256>>,
+}
+
+/// A value before and after a call.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ValueDiff<T> {
+    /// The value before the call.
+    pub from: T,
+    /// The value after the call.
+    pub to: T,
+}
+
+impl<T: PartialEq> ValueDiff<T> {
+    fn of(from: T, to: T) -> Option<Self> {
+        (from != to).then_some(Self { from, to })
+    }
+}
+
+/// A receipt returned by `eth_getTransactionReceipt` and `eth_getBlockReceipts`, with the
+/// L1 (DA) fee fields of the Optimism receipts.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollupTransactionReceipt {
+    /// The Ethereum fields of the receipt.
+    #[serde(flatten)]
+    pub receipt: reth_rpc_types::TransactionReceipt,
+    /// The fee in wei paid for posting the transaction on the DA layer.
+    pub l1_fee: U256,
+    /// The L1 gas spent to post the transaction.
+    pub l1_gas_used: U256,
+    /// The scalar applied to the L1 fee, as a decimal number.
+    pub l1_fee_scalar: String,
+}
+
+/// Formats an L1 fee scalar given in millionths as a decimal number, like `"0.684"`.
+fn format_l1_fee_scalar(scalar: u64) -> String {
+    let fraction = scalar % L1_FEE_SCALAR_PRECISION;
+    let integer = scalar / L1_FEE_SCALAR_PRECISION;
+    if fraction == 0 {
+        return integer.to_string();
+    }
+    let fraction = format!("{:06}", fraction);
+    format!("{}.{}", integer, fraction.trim_end_matches('0'))
+}
+
+/// The position of the next log returned by `sov_getLogsPage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsCursor {
+    /// The number of the block of the log.
+    pub block_number: u64,
+    /// The index of the log in its block.
+    pub log_index: u64,
+}
+
+/// A page of the logs matching a filter, returned by `sov_getLogsPage`.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LogsPage {
+    /// The logs of the page, in order.
+    pub logs: Vec<reth_rpc_types::Log>,
+    /// Where the next page starts, or `None` if this is the last page.
+    pub cursor: Option<LogsCursor>,
+}
+
+/// The tracing timeout used when the request doesn't specify one, the same as geth's.
+const DEFAULT_TRACE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Parses a tracing timeout given as a Go duration string, like `"500ms"` or `"1m30s"`.
+fn parse_trace_timeout(timeout: &str) -> EthResult<Duration> {
+    let invalid = || EthApiError::InvalidParams(format!("invalid tracing timeout: {}", timeout));
+    let is_number = |c: char| c.is_ascii_digit() || c == '.';
+
+    let mut rest = timeout.trim();
+    if rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut duration = Duration::ZERO;
+    while !rest.is_empty() {
+        let number_len = rest.find(|c: char| !is_number(c)).ok_or_else(invalid)?;
+        let (number, tail) = rest.split_at(number_len);
+        let unit_len = tail.find(is_number).unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        let unit_secs = match unit {
+            "ns" => 1e-9,
+            "us" | "µs" => 1e-6,
+            "ms" => 1e-3,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            _ => return Err(invalid()),
+        };
+        duration += Duration::try_from_secs_f64(number * unit_secs).map_err(|_| invalid())?;
+        rest = tail;
+    }
+
+    Ok(duration)
+}
+
+fn encode_proof<C: sov_modules_api::Context>(
+    proof: <C::Storage as Storage>::Proof,
+) -> Vec<reth_primitives::Bytes> {
+    vec![proof
+        .try_to_vec()
+        .expect("Serialization of a storage proof cannot fail")
+        .into()]
+}
+
 fn get_cfg_env_template() -> revm::primitives::CfgEnv {
     let mut cfg_env = revm::primitives::CfgEnv::default();
     // Reth sets this to true and uses only timeout, but other clients use this as a part of DOS attacks protection, with 100mln gas limit
@@ -603,9 +2148,73 @@ fn get_cfg_env_template() -> revm::primitives::CfgEnv {
     cfg_env
 }
 
+/// Builds the rpc block response of `block`, which contains `transactions` in order.
+pub(crate) fn build_rpc_block(
+    block: &SealedBlock,
+    transactions: Vec<TransactionSignedAndRecovered>,
+    details: Option<bool>,
+) -> reth_rpc_types::RichBlock {
+    // Build rpc header response
+    let header = reth_rpc_types::Header::from_primitive_with_hash(block.header.clone());
+
+    // Build rpc transactions response
+    let transactions = match details {
+        Some(true) => reth_rpc_types::BlockTransactions::Full(
+            transactions
+                .into_iter()
+                .enumerate()
+                .map(|(index, tx)| {
+                    reth_rpc_types_compat::from_recovered_with_block_context(
+                        tx.into(),
+                        block.header.hash,
+                        block.header.number,
+                        block.header.base_fee_per_gas,
+                        U256::from(index),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        ),
+        _ => reth_rpc_types::BlockTransactions::Hashes({
+            transactions
+                .into_iter()
+                .map(|tx| tx.signed_transaction.hash)
+                .collect::<Vec<_>>()
+        }),
+    };
+
+    // Build rpc block response
+    let total_difficulty = Some(block.header.difficulty);
+    let block = reth_rpc_types::Block {
+        header,
+        total_difficulty,
+        uncles: Default::default(),
+        transactions,
+        size: Default::default(),
+        withdrawals: Default::default(),
+    };
+
+    block.into()
+}
+
 // modified from: https://github.com/paradigmxyz/reth/blob/cc576bc8690a3e16e6e5bf1cbbbfdd029e85e3d4/crates/rpc/rpc/src/eth/api/transactions.rs#L849
+/// Builds the receipt of `tx`, with the L1 fee it paid when it was executed.
+pub(crate) fn build_rollup_receipt(
+    block: &SealedBlock,
+    tx: TransactionSignedAndRecovered,
+    tx_number: u64,
+    receipt: Receipt,
+) -> RollupTransactionReceipt {
+    let l1_fee = receipt.l1_fee.clone();
+    RollupTransactionReceipt {
+        receipt: build_rpc_receipt(block, tx, tx_number, receipt),
+        l1_fee: l1_fee.fee,
+        l1_gas_used: U256::from(l1_fee.gas_used),
+        l1_fee_scalar: format_l1_fee_scalar(l1_fee.scalar),
+    }
+}
+
 pub(crate) fn build_rpc_receipt(
-    block: SealedBlock,
+    block: &SealedBlock,
     tx: TransactionSignedAndRecovered,
     tx_number: u64,
     receipt: Receipt,
@@ -630,10 +2239,14 @@ pub(crate) fn build_rpc_receipt(
         },
         cumulative_gas_used: U256::from(receipt.receipt.cumulative_gas_used),
         gas_used: Some(U256::from(receipt.gas_used)),
-        // EIP-4844 related
+        // The EVM doesn't execute blob transactions, so they never use blob gas.
+        // The blob fields are only set for blocks carrying the EIP-4844 header fields.
         // https://github.com/Sovereign-Labs/sovereign-sdk/issues/912
-        blob_gas_used: None,
-        blob_gas_price: None,
+        blob_gas_used: block.header.blob_gas_used.map(|_| U128::ZERO),
+        blob_gas_price: block
+            .header
+            .excess_blob_gas
+            .map(|excess_blob_gas| U128::from(calc_blob_gas_price(excess_blob_gas))),
         contract_address: match transaction_kind {
             Create => Some(create_address(transaction.signer(), transaction.nonce())),
             Call(_) => None,
@@ -669,15 +2282,45 @@ pub(crate) fn build_rpc_receipt(
     }
 }
 
-fn map_out_of_gas_err<C: sov_modules_api::Context>(
-    block_env: BlockEnv,
-    mut tx_env: revm::primitives::TxEnv,
-    cfg_env: revm::primitives::CfgEnv,
-    db: EvmDb<'_, C>,
+/// Computes the excess blob gas of the block following a block, as specified by EIP-4844.
+fn calc_next_excess_blob_gas(excess_blob_gas: u64, blob_gas_used: u64) -> u64 {
+    const TARGET_BLOB_GAS_PER_BLOCK: u64 = 393_216;
+
+    excess_blob_gas
+        .saturating_add(blob_gas_used)
+        .saturating_sub(TARGET_BLOB_GAS_PER_BLOCK)
+}
+
+/// Computes the blob gas price from the excess blob gas of a block, as specified by EIP-4844.
+fn calc_blob_gas_price(excess_blob_gas: u64) -> u128 {
+    const MIN_BLOB_GAS_PRICE: u128 = 1;
+    const BLOB_GAS_PRICE_UPDATE_FRACTION: u128 = 3_338_477;
+
+    // Approximates `factor * e ** (numerator / denominator)` using Taylor expansion.
+    let numerator = excess_blob_gas as u128;
+    let denominator = BLOB_GAS_PRICE_UPDATE_FRACTION;
+    let mut i = 1;
+    let mut output = 0u128;
+    let mut numerator_accum = MIN_BLOB_GAS_PRICE * denominator;
+    while numerator_accum > 0 {
+        output = output.saturating_add(numerator_accum);
+        numerator_accum = numerator_accum.saturating_mul(numerator) / (denominator * i);
+        i += 1;
+    }
+    output / denominator
+}
+
+/// Executes the call again with the gas limit of the block, to tell whether the failure
+/// with the requested gas limit is related to gas or not.
+fn map_out_of_gas_err<DB: revm::DatabaseRef<Error = std::convert::Infallible>>(
+    executor: &mut CachedExecutor<DB>,
+    req_gas_limit: u64,
+    block_gas_limit: u64,
 ) -> EthApiError {
-    let req_gas_limit = tx_env.gas_limit;
-    tx_env.gas_limit = block_env.gas_limit;
-    let res = executor::inspect(db, &block_env, tx_env, cfg_env).unwrap();
+    let res = match executor.transact_with_gas_limit(block_gas_limit) {
+        Ok(res) => res,
+        Err(err) => return EthApiError::from(err),
+    };
     match res.result {
         ExecutionResult::Success { .. } => {
             // transaction succeeded by manually increasing the gas limit to
@@ -692,8 +2335,185 @@ fn map_out_of_gas_err<C: sov_modules_api::Context>(
     }
 }
 
+/// Applies the `eth_call` block overrides to `block_env`.
+fn apply_block_overrides(
+    block_env: &mut BlockEnv,
+    block_overrides: reth_rpc_types::BlockOverrides,
+) -> EthResult<()> {
+    let reth_rpc_types::BlockOverrides {
+        number,
+        // The difficulty isn't used after the merge
+        difficulty: _,
+        time,
+        gas_limit,
+        coinbase,
+        random,
+        base_fee,
+        block_hash,
+    } = block_overrides;
+
+    if block_hash.is_some() {
+        return Err(EthApiError::Unsupported(
+            "Block hash overrides are not supported",
+        ));
+    }
+
+    let to_u64 = |value: U256, field: &str| {
+        u64::try_from(value)
+            .map_err(|_| EthApiError::InvalidParams(format!("block {} overflows u64", field)))
+    };
+
+    if let Some(number) = number {
+        block_env.number = to_u64(number, "number")?;
+    }
+    if let Some(time) = time {
+        block_env.timestamp = time.to::<u64>();
+    }
+    if let Some(gas_limit) = gas_limit {
+        block_env.gas_limit = gas_limit.to::<u64>();
+    }
+    if let Some(coinbase) = coinbase {
+        block_env.coinbase = coinbase;
+    }
+    if let Some(random) = random {
+        block_env.prevrandao = random;
+    }
+    if let Some(base_fee) = base_fee {
+        block_env.basefee = to_u64(base_fee, "base fee")?;
+    }
+
+    Ok(())
+}
+
+/// Returns the returned data of an execution, or its revert data if it reverted.
+/// Sets the archival version of the working set, if any, after checking that the state
+/// at this version hasn't been pruned.
+fn set_state_version<C: sov_modules_api::Context>(
+    version: Option<u64>,
+    working_set: &mut WorkingSet<C>,
+) -> EthResult<()> {
+    if let Some(version) = version {
+        working_set.try_set_archival_version(version)?;
+    }
+    Ok(())
+}
+
+fn execution_output(result: &ExecutionResult) -> reth_primitives::Bytes {
+    match result {
+        ExecutionResult::Success { output, .. } => output.clone().into_data().into(),
+        ExecutionResult::Revert { output, .. } => output.clone().into(),
+        ExecutionResult::Halt { .. } => Default::default(),
+    }
+}
+
+fn empty_account_info() -> AccountInfo {
+    AccountInfo {
+        code_hash: KECCAK_EMPTY,
+        ..Default::default()
+    }
+}
+
+fn apply_storage_override<C: sov_modules_api::Context>(
+    db_account: &DbAccount,
+    slots: HashMap<H256, H256>,
+    working_set: &mut WorkingSet<C>,
+) {
+    for (key, value) in slots {
+        db_account.storage.set(
+            &U256::from_be_bytes(key.0),
+            &U256::from_be_bytes(value.0),
+            working_set,
+        );
+    }
+}
+
 fn convert_u256_to_u64(u256: reth_primitives::U256) -> Result<u64, TryFromSliceError> {
     let bytes: [u8; 32] = u256.to_be_bytes();
     let bytes: [u8; 8] = bytes[24..].try_into()?;
     Ok(u64::from_be_bytes(bytes))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use reth_primitives::U256;
+    use reth_rpc_types::BlockOverrides;
+
+    use super::{
+        apply_block_overrides, calc_blob_gas_price, calc_next_excess_blob_gas,
+        format_l1_fee_scalar, parse_trace_timeout,
+    };
+    use crate::evm::primitive_types::BlockEnv;
+
+    #[test]
+    fn test_format_l1_fee_scalar() {
+        assert_eq!("1", format_l1_fee_scalar(1_000_000));
+        assert_eq!("0", format_l1_fee_scalar(0));
+        assert_eq!("0.684", format_l1_fee_scalar(684_000));
+        assert_eq!("2.000001", format_l1_fee_scalar(2_000_001));
+    }
+
+    #[test]
+    fn test_parse_trace_timeout() {
+        assert_eq!(Duration::from_secs(10), parse_trace_timeout("10s").unwrap());
+        assert_eq!(
+            Duration::from_millis(500),
+            parse_trace_timeout("500ms").unwrap()
+        );
+        assert_eq!(
+            Duration::from_secs(90),
+            parse_trace_timeout("1m30s").unwrap()
+        );
+        assert_eq!(
+            Duration::from_millis(1500),
+            parse_trace_timeout("1.5s").unwrap()
+        );
+
+        assert!(parse_trace_timeout("").is_err());
+        assert!(parse_trace_timeout("10").is_err());
+        assert!(parse_trace_timeout("s").is_err());
+        assert!(parse_trace_timeout("10 days").is_err());
+    }
+
+    #[test]
+    fn test_apply_block_overrides() {
+        let mut block_env = BlockEnv::default();
+        apply_block_overrides(
+            &mut block_env,
+            BlockOverrides {
+                number: Some(U256::from(5)),
+                base_fee: Some(U256::from(10)),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(block_env.number, 5);
+        assert_eq!(block_env.basefee, 10);
+
+        assert!(apply_block_overrides(
+            &mut block_env,
+            BlockOverrides {
+                number: Some(U256::MAX),
+                ..Default::default()
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_calc_blob_gas_price() {
+        assert_eq!(1, calc_blob_gas_price(0));
+        assert_eq!(1, calc_blob_gas_price(2314057));
+        assert_eq!(2, calc_blob_gas_price(2314058));
+        assert_eq!(23, calc_blob_gas_price(10 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_calc_next_excess_blob_gas() {
+        assert_eq!(0, calc_next_excess_blob_gas(0, 0));
+        assert_eq!(0, calc_next_excess_blob_gas(0, 393_216));
+        assert_eq!(131_072, calc_next_excess_blob_gas(131_072, 393_216));
+        assert_eq!(0, calc_next_excess_blob_gas(131_072, 0));
+    }
+}
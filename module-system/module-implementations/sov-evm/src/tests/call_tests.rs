@@ -1,15 +1,31 @@
-use reth_primitives::{Address, Bytes, TransactionKind};
+use std::collections::HashMap;
+
+use reth_primitives::{AccessList, AccessListItem, Address, Bytes, TransactionKind, H256};
+use reth_rpc_types::state::AccountOverride;
+use reth_rpc_types::trace::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingCallOptions,
+    GethDebugTracingOptions, GethTrace,
+};
+use reth_rpc_types::{CallInput, CallRequest};
 use revm::primitives::{SpecId, KECCAK_EMPTY, U256};
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::utils::generate_address;
-use sov_modules_api::{Context, Module, StateMapAccessor, StateValueAccessor, StateVecAccessor};
+use sov_modules_api::{
+    Context, Module, StateMapAccessor, StateValueAccessor, StateVecAccessor, WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::Storage;
 
 use crate::call::CallMessage;
 use crate::evm::primitive_types::Receipt;
+use crate::experimental::MIN_TRANSACTION_GAS;
 use crate::smart_contracts::SimpleStorageContract;
 use crate::tests::genesis_tests::get_evm;
 use crate::tests::test_signer::TestSigner;
-use crate::{AccountData, EvmConfig};
+use crate::{
+    AccountData, Evm, EvmConfig, ValueDiff, BASE_FEE_SLOT, GAS_PRICE_ORACLE_ADDRESS,
+    L1_FEE_OVERHEAD_SLOT, L1_FEE_SCALAR_SLOT, L1_GAS_PRICE_SLOT,
+};
 type C = DefaultContext;
 
 #[test]
@@ -37,7 +53,7 @@ fn call_test() {
             .as_slice(),
     );
 
-    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), &mut working_set);
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
 
     let set_arg = 999;
     {
@@ -76,7 +92,8 @@ fn call_test() {
                 },
                 gas_used: 132943,
                 log_index_start: 0,
-                error: None
+                error: None,
+                l1_fee: Default::default(),
             },
             Receipt {
                 receipt: reth_primitives::Receipt {
@@ -87,7 +104,8 @@ fn call_test() {
                 },
                 gas_used: 43730,
                 log_index_start: 0,
-                error: None
+                error: None,
+                l1_fee: Default::default(),
             }
         ]
     )
@@ -99,7 +117,7 @@ fn failed_transaction_test() {
     let (evm, mut working_set) = get_evm(&EvmConfig::default());
     let working_set = &mut working_set;
 
-    evm.begin_slot_hook([5u8; 32], &[10u8; 32].into(), working_set);
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), working_set);
     {
         let sender_address = generate_address::<C>("sender");
         let sequencer_address = generate_address::<C>("sequencer");
@@ -123,6 +141,792 @@ fn failed_transaction_test() {
     assert_eq!(block.transactions.end, 0);
 }
 
+#[test]
+fn blob_transaction_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        ..Default::default()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    let working_set = &mut working_set;
+    let receiver = Address::from_slice(&[1u8; 20]);
+
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+        let message = CallMessage {
+            tx: dev_signer
+                .sign_blob_transaction(TransactionKind::Call(receiver), 0)
+                .unwrap(),
+        };
+        let err = evm.call(message, &context, working_set).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("blob transactions are not supported"));
+    }
+
+    // The blob transaction isn't executed without its blobs
+    let pending_txs = evm.pending_transactions.iter(working_set);
+    assert_eq!(pending_txs.len(), 0);
+
+    // Calls with blob fields are rejected as well
+    let blob_call = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(receiver),
+        blob_versioned_hashes: vec![H256::random()],
+        ..Default::default()
+    };
+    assert!(evm
+        .get_call(blob_call.clone(), None, None, None, working_set)
+        .is_err());
+    assert!(evm.eth_estimate_gas(blob_call, None, working_set).is_err());
+
+    // No blob is ever used, so the blob base fee stays at its minimum
+    assert_eq!(
+        reth_primitives::U128::from(1),
+        evm.blob_base_fee(working_set).unwrap()
+    );
+}
+
+#[test]
+fn call_with_state_overrides_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let (evm, mut working_set) = get_evm(&config);
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("819c5497b157177315e1204f52e588b393771719")
+            .unwrap()
+            .as_slice(),
+    );
+
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        let messages = vec![
+            create_contract_message(&dev_signer, 0),
+            set_arg_message(contract_addr, &dev_signer, 1, 999),
+        ];
+        for tx in messages {
+            evm.call(tx, &context, &mut working_set).unwrap();
+        }
+    }
+    evm.end_slot_hook(&mut working_set);
+
+    let get_request = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(contract_addr),
+        input: CallInput {
+            input: Some(Bytes::from(
+                SimpleStorageContract::default().get_call_data().to_vec(),
+            )),
+            data: None,
+        },
+        ..Default::default()
+    };
+    let slot_override =
+        |value: u64| HashMap::from([(H256::zero(), H256::from(U256::from(value).to_be_bytes()))]);
+
+    let call_with = |state_overrides, working_set: &mut WorkingSet<C>| {
+        evm.get_call(
+            get_request.clone(),
+            Some("pending".to_owned()),
+            state_overrides,
+            None,
+            working_set,
+        )
+    };
+
+    let output = call_with(None, &mut working_set).unwrap();
+    assert_eq!(
+        U256::from(999).to_be_bytes::<32>().as_slice(),
+        output.as_ref()
+    );
+
+    let state_diff = HashMap::from([(
+        contract_addr,
+        AccountOverride {
+            state_diff: Some(slot_override(5)),
+            ..Default::default()
+        },
+    )]);
+    let output = call_with(Some(state_diff), &mut working_set).unwrap();
+    assert_eq!(
+        U256::from(5).to_be_bytes::<32>().as_slice(),
+        output.as_ref()
+    );
+
+    // The whole storage is replaced, so the slot isn't set anymore
+    let state = HashMap::from([(
+        contract_addr,
+        AccountOverride {
+            state: Some(HashMap::new()),
+            ..Default::default()
+        },
+    )]);
+    let output = call_with(Some(state), &mut working_set).unwrap();
+    assert_eq!(U256::ZERO.to_be_bytes::<32>().as_slice(), output.as_ref());
+
+    let both = HashMap::from([(
+        contract_addr,
+        AccountOverride {
+            state: Some(slot_override(6)),
+            state_diff: Some(slot_override(7)),
+            ..Default::default()
+        },
+    )]);
+    assert!(call_with(Some(both), &mut working_set).is_err());
+}
+
+#[test]
+fn estimate_gas_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let (evm, mut working_set) = get_evm(&config);
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("819c5497b157177315e1204f52e588b393771719")
+            .unwrap()
+            .as_slice(),
+    );
+
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+        evm.call(
+            create_contract_message(&dev_signer, 0),
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_slot_hook(&mut working_set);
+
+    let request_with_input = |input: Bytes| CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(contract_addr),
+        input: CallInput {
+            input: Some(input),
+            data: None,
+        },
+        ..Default::default()
+    };
+    let contract = SimpleStorageContract::default();
+
+    let set_request = request_with_input(Bytes::from(contract.set_call_data(999).to_vec()));
+    let gas = evm
+        .eth_estimate_gas(
+            set_request.clone(),
+            Some("pending".to_owned()),
+            &mut working_set,
+        )
+        .unwrap()
+        .as_u64();
+    assert!(gas > MIN_TRANSACTION_GAS);
+
+    // The estimated gas is enough for the call to succeed, but not any less
+    let call_with_gas = |gas: u64, working_set: &mut WorkingSet<C>| {
+        evm.get_call(
+            CallRequest {
+                gas: Some(U256::from(gas)),
+                ..set_request.clone()
+            },
+            Some("pending".to_owned()),
+            None,
+            None,
+            working_set,
+        )
+    };
+    assert!(call_with_gas(gas, &mut working_set).is_ok());
+    assert!(call_with_gas(gas - 1, &mut working_set).is_err());
+
+    let failing_request =
+        request_with_input(Bytes::from(contract.failing_function_call_data().to_vec()));
+    let err = evm
+        .eth_estimate_gas(
+            failing_request,
+            Some("pending".to_owned()),
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("execution reverted"));
+
+    // Unknown blocks are rejected instead of estimating against the latest state
+    assert!(evm
+        .eth_estimate_gas(set_request, Some("0x100".to_owned()), &mut working_set)
+        .is_err());
+}
+
+#[test]
+fn create_access_list_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let (evm, mut working_set) = get_evm(&config);
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("819c5497b157177315e1204f52e588b393771719")
+            .unwrap()
+            .as_slice(),
+    );
+
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+        evm.call(
+            create_contract_message(&dev_signer, 0),
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_slot_hook(&mut working_set);
+
+    let contract = SimpleStorageContract::default();
+    let set_request = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(contract_addr),
+        input: CallInput {
+            input: Some(Bytes::from(contract.set_call_data(999).to_vec())),
+            data: None,
+        },
+        ..Default::default()
+    };
+
+    let access_list = evm
+        .create_access_list(
+            set_request.clone(),
+            Some("pending".to_owned()),
+            &mut working_set,
+        )
+        .unwrap();
+    // The contract is the recipient, so only its storage slot is listed
+    assert_eq!(
+        access_list.access_list,
+        AccessList(vec![AccessListItem {
+            address: contract_addr,
+            storage_keys: vec![H256::zero()],
+        }])
+    );
+
+    // The gas is estimated for the call sent with the access list
+    let gas_with_access_list = evm
+        .eth_estimate_gas(
+            CallRequest {
+                access_list: Some(access_list.access_list.clone()),
+                ..set_request.clone()
+            },
+            Some("pending".to_owned()),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(
+        access_list.gas_used,
+        U256::from(gas_with_access_list.as_u64())
+    );
+
+    let failing_request = CallRequest {
+        input: CallInput {
+            input: Some(Bytes::from(contract.failing_function_call_data().to_vec())),
+            data: None,
+        },
+        ..set_request
+    };
+    let err = evm
+        .create_access_list(
+            failing_request,
+            Some("pending".to_owned()),
+            &mut working_set,
+        )
+        .unwrap_err();
+    assert!(err.to_string().contains("execution reverted"));
+}
+
+#[test]
+fn call_many_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let (evm, mut working_set) = get_evm(&config);
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("819c5497b157177315e1204f52e588b393771719")
+            .unwrap()
+            .as_slice(),
+    );
+
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+
+        let messages = vec![
+            create_contract_message(&dev_signer, 0),
+            set_arg_message(contract_addr, &dev_signer, 1, 999),
+        ];
+        for tx in messages {
+            evm.call(tx, &context, &mut working_set).unwrap();
+        }
+    }
+    evm.end_slot_hook(&mut working_set);
+
+    let request_with_input = |input: Bytes| CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(contract_addr),
+        input: CallInput {
+            input: Some(input),
+            data: None,
+        },
+        ..Default::default()
+    };
+    let contract = SimpleStorageContract::default();
+    let get_request = request_with_input(Bytes::from(contract.get_call_data().to_vec()));
+    let noop_tracer = GethDebugTracingCallOptions {
+        tracing_options: GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::BuiltInTracer(
+                GethDebugBuiltInTracerType::NoopTracer,
+            )),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let trace = evm
+        .debug_trace_call(
+            get_request.clone(),
+            Some("latest".to_owned()),
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(matches!(trace, GethTrace::Default(_)));
+
+    let calls = evm
+        .eth_call_many(
+            vec![
+                request_with_input(Bytes::from(contract.set_call_data(777).to_vec())),
+                get_request,
+                request_with_input(Bytes::from(contract.failing_function_call_data().to_vec())),
+            ],
+            Some("latest".to_owned()),
+            Some(noop_tracer),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(calls.len(), 3);
+
+    // The set call changes the storage of the contract
+    assert_eq!(calls[0].error, None);
+    assert!(calls[0].trace.is_some());
+    assert_eq!(
+        calls[0].state_diff[&contract_addr].storage[&H256::zero()],
+        ValueDiff {
+            from: H256::from(U256::from(999).to_be_bytes()),
+            to: H256::from(U256::from(777).to_be_bytes()),
+        }
+    );
+
+    // The get call sees the changes of the set call, and doesn't change anything
+    assert_eq!(
+        U256::from(777).to_be_bytes::<32>().as_slice(),
+        calls[1].output.as_ref()
+    );
+    assert_eq!(calls[1].state_diff.get(&contract_addr), None);
+
+    assert!(calls[2]
+        .error
+        .as_ref()
+        .unwrap()
+        .contains("execution reverted"));
+}
+
+#[test]
+fn pending_transactions_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let (evm, mut working_set) = get_evm(&config);
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("819c5497b157177315e1204f52e588b393771719")
+            .unwrap()
+            .as_slice(),
+    );
+
+    let pending_txs = vec![
+        create_contract_message(&dev_signer, 0).tx,
+        set_arg_message(contract_addr, &dev_signer, 1, 999).tx,
+        // The nonce is too high, so the transaction is skipped
+        set_arg_message(contract_addr, &dev_signer, 5, 1000).tx,
+    ];
+    evm.apply_pending_transactions(&pending_txs, &mut working_set);
+
+    let nonce = evm
+        .get_transaction_count(
+            dev_signer.address(),
+            Some("pending".to_owned()),
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(nonce, reth_primitives::U64::from(2));
+
+    let pending_block = evm
+        .get_block_by_number(Some("pending".to_owned()), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(pending_block.header.number, Some(U256::from(1)));
+    match &pending_block.transactions {
+        reth_rpc_types::BlockTransactions::Hashes(hashes) => assert_eq!(hashes.len(), 2),
+        _ => panic!("Expected transaction hashes"),
+    }
+
+    let output = evm
+        .get_call(
+            CallRequest {
+                from: Some(dev_signer.address()),
+                to: Some(contract_addr),
+                input: CallInput {
+                    input: Some(Bytes::from(
+                        SimpleStorageContract::default().get_call_data().to_vec(),
+                    )),
+                    data: None,
+                },
+                ..Default::default()
+            },
+            Some("pending".to_owned()),
+            None,
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(
+        U256::from(999).to_be_bytes::<32>().as_slice(),
+        output.as_ref()
+    );
+
+    // The latest block is unaffected by the pending transactions
+    let latest_block = evm
+        .get_block_by_number(Some("latest".to_owned()), None, &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest_block.header.number, Some(U256::ZERO));
+}
+
+#[test]
+fn historical_state_test() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: dev_signer.address(),
+            balance: U256::from(1000000000),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+        ..Default::default()
+    };
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let evm = Evm::<C>::default();
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    evm.genesis(&config, &mut working_set).unwrap();
+    evm.finalize_hook(&[10u8; 32].into(), &mut working_set.accessory_state());
+    commit(working_set, &storage);
+
+    let contract_addr: Address = Address::from_slice(
+        hex::decode("819c5497b157177315e1204f52e588b393771719")
+            .unwrap()
+            .as_slice(),
+    );
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    {
+        let sender_address = generate_address::<C>("sender");
+        let sequencer_address = generate_address::<C>("sequencer");
+        let context = C::new(sender_address, sequencer_address, 1);
+        evm.call(
+            create_contract_message(&dev_signer, 0),
+            &context,
+            &mut working_set,
+        )
+        .unwrap();
+    }
+    evm.end_slot_hook(&mut working_set);
+    evm.finalize_hook(&[20u8; 32].into(), &mut working_set.accessory_state());
+    commit(working_set, &storage);
+
+    let mut working_set = WorkingSet::new(storage.clone());
+    let nonce_at = |block_number: &str, working_set: &mut WorkingSet<C>| {
+        evm.get_transaction_count(
+            dev_signer.address(),
+            Some(block_number.to_owned()),
+            working_set,
+        )
+    };
+    assert_eq!(
+        reth_primitives::U64::from(1),
+        nonce_at("latest", &mut working_set).unwrap()
+    );
+    assert_eq!(
+        reth_primitives::U64::from(0),
+        nonce_at("0x0", &mut working_set).unwrap()
+    );
+    assert!(evm
+        .get_code(contract_addr, Some("0x0".to_owned()), &mut working_set)
+        .unwrap()
+        .is_empty());
+    assert!(!evm
+        .get_code(contract_addr, Some("0x1".to_owned()), &mut working_set)
+        .unwrap()
+        .is_empty());
+
+    // The contract only exists from block 1
+    let get_request = CallRequest {
+        from: Some(dev_signer.address()),
+        to: Some(contract_addr),
+        input: CallInput {
+            input: Some(Bytes::from(
+                SimpleStorageContract::default().get_call_data().to_vec(),
+            )),
+            data: None,
+        },
+        ..Default::default()
+    };
+    let output = evm
+        .get_call(
+            get_request.clone(),
+            Some("0x0".to_owned()),
+            None,
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(output.is_empty());
+    let output = evm
+        .get_call(
+            get_request,
+            Some("latest".to_owned()),
+            None,
+            None,
+            &mut working_set,
+        )
+        .unwrap();
+    assert_eq!(U256::ZERO.to_be_bytes::<32>().as_slice(), output.as_ref());
+}
+
+#[test]
+fn l1_fee_is_charged_to_the_sender() {
+    let dev_signer: TestSigner = TestSigner::new_random();
+    let message = create_contract_message(&dev_signer, 0);
+    let tx_size = message.tx.rlp.len() as u64;
+
+    let run = |l1_gas_price: u64, balance: u64| {
+        let config = EvmConfig {
+            data: vec![AccountData {
+                address: dev_signer.address(),
+                balance: U256::from(balance),
+                code_hash: KECCAK_EMPTY,
+                code: Bytes::default(),
+                nonce: 0,
+            }],
+            spec: vec![(0, SpecId::SHANGHAI)].into_iter().collect(),
+            l1_gas_price,
+            l1_fee_overhead: 2100,
+            l1_fee_scalar: 1_500_000,
+            ..Default::default()
+        };
+        let (evm, mut working_set) = get_evm(&config);
+        evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+        let context = C::new(
+            generate_address::<C>("sender"),
+            generate_address::<C>("sequencer"),
+            1,
+        );
+        evm.call(message.clone(), &context, &mut working_set)
+            .unwrap();
+        evm.end_slot_hook(&mut working_set);
+
+        let balance = evm
+            .accounts
+            .get(&dev_signer.address(), &mut working_set)
+            .unwrap()
+            .info
+            .balance;
+        let receipts = evm
+            .receipts
+            .iter(&mut working_set.accessory_state())
+            .collect::<Vec<_>>();
+        (balance, receipts)
+    };
+
+    let (balance_without_l1_fee, _) = run(0, 1_000_000_000);
+    let (balance, receipts) = run(3, 1_000_000_000);
+
+    let l1_gas = tx_size * 16 + 2100;
+    let l1_fee = U256::from(l1_gas * 3 * 1_500_000 / 1_000_000);
+    assert_eq!(1, receipts.len());
+    assert_eq!(l1_fee, receipts[0].l1_fee.fee);
+    assert_eq!(l1_gas, receipts[0].l1_fee.gas_used);
+    assert_eq!(1_500_000, receipts[0].l1_fee.scalar);
+    assert_eq!(balance_without_l1_fee - l1_fee, balance);
+
+    // A sender left without the balance for its gas by the L1 fee is skipped, and refunded.
+    let gas_balance = 1_000_000 * 2 * reth_primitives::constants::MIN_PROTOCOL_BASE_FEE;
+    let (balance, receipts) = run(3, gas_balance);
+    assert!(receipts.is_empty());
+    assert_eq!(U256::from(gas_balance), balance);
+
+    // So is a sender which can't pay the L1 fee at all.
+    let (balance, receipts) = run(3, 1);
+    assert!(receipts.is_empty());
+    assert_eq!(U256::from(1), balance);
+}
+
+fn commit(working_set: WorkingSet<C>, storage: &<C as sov_modules_api::Spec>::Storage) {
+    let mut checkpoint = working_set.checkpoint();
+    let (reads_writes, witness) = checkpoint.freeze();
+    let accessory_writes = checkpoint.freeze_non_provable();
+    storage
+        .validate_and_commit_with_accessory_update(reads_writes, &witness, &accessory_writes)
+        .unwrap();
+}
+
+#[test]
+fn gas_price_oracle_exposes_fee_parameters() {
+    let config = EvmConfig {
+        l1_gas_price: 3,
+        l1_fee_overhead: 2100,
+        l1_fee_scalar: 684_000,
+        ..Default::default()
+    };
+    let (evm, mut working_set) = get_evm(&config);
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    let base_fee = evm.block_env.get(&mut working_set).unwrap().basefee;
+
+    for (slot, expected) in [
+        (BASE_FEE_SLOT, base_fee),
+        (L1_GAS_PRICE_SLOT, 3),
+        (L1_FEE_OVERHEAD_SLOT, 2100),
+        (L1_FEE_SCALAR_SLOT, 684_000),
+    ] {
+        let request = CallRequest {
+            to: Some(GAS_PRICE_ORACLE_ADDRESS),
+            input: CallInput {
+                input: Some(Bytes::from(U256::from(slot).to_be_bytes::<32>().to_vec())),
+                data: None,
+            },
+            ..Default::default()
+        };
+        let output = evm
+            .get_call(
+                request,
+                Some("pending".to_owned()),
+                None,
+                None,
+                &mut working_set,
+            )
+            .unwrap();
+        assert_eq!(
+            U256::from(expected).to_be_bytes::<32>().as_slice(),
+            output.as_ref()
+        );
+    }
+}
+
+#[test]
+fn genesis_rejects_gas_price_oracle_account() {
+    let config = EvmConfig {
+        data: vec![AccountData {
+            address: GAS_PRICE_ORACLE_ADDRESS,
+            balance: U256::from(1),
+            code_hash: KECCAK_EMPTY,
+            code: Bytes::default(),
+            nonce: 0,
+        }],
+        ..Default::default()
+    };
+    assert_eq!(1, config.validate().len());
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    assert!(Evm::<C>::default()
+        .genesis(&config, &mut working_set)
+        .is_err());
+}
+
 fn create_contract_message(dev_signer: &TestSigner, nonce: u64) -> CallMessage {
     let contract = SimpleStorageContract::default();
     let signed_tx = dev_signer
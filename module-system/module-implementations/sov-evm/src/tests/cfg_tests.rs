@@ -1,5 +1,7 @@
 use revm::primitives::{CfgEnv, SpecId};
+use sov_modules_api::StateValueAccessor;
 
+use super::genesis_tests::{get_evm, TEST_CONFIG};
 use crate::call::{get_cfg_env, get_spec_id};
 use crate::evm::primitive_types::BlockEnv;
 use crate::evm::EvmChainConfig;
@@ -40,10 +42,72 @@ fn spec_id_lookup() {
         (20, SpecId::LONDON),
     ];
 
-    assert_eq!(get_spec_id(spec.clone(), 0), SpecId::CONSTANTINOPLE);
-    assert_eq!(get_spec_id(spec.clone(), 5), SpecId::CONSTANTINOPLE);
-    assert_eq!(get_spec_id(spec.clone(), 10), SpecId::BERLIN);
-    assert_eq!(get_spec_id(spec.clone(), 15), SpecId::BERLIN);
-    assert_eq!(get_spec_id(spec.clone(), 20), SpecId::LONDON);
-    assert_eq!(get_spec_id(spec, 25), SpecId::LONDON);
+    assert_eq!(get_spec_id(&spec, 0), SpecId::CONSTANTINOPLE);
+    assert_eq!(get_spec_id(&spec, 5), SpecId::CONSTANTINOPLE);
+    assert_eq!(get_spec_id(&spec, 10), SpecId::BERLIN);
+    assert_eq!(get_spec_id(&spec, 15), SpecId::BERLIN);
+    assert_eq!(get_spec_id(&spec, 20), SpecId::LONDON);
+    assert_eq!(get_spec_id(&spec, 25), SpecId::LONDON);
+}
+
+#[test]
+fn spec_is_scheduled_by_block_number() {
+    let mut config = TEST_CONFIG.clone();
+    config.spec = vec![(0, SpecId::BERLIN), (2, SpecId::LONDON)]
+        .into_iter()
+        .collect();
+    let (evm, mut working_set) = get_evm(&config);
+
+    // Blocks have no base fee before London
+    let genesis = evm.head.get(&mut working_set).unwrap();
+    assert_eq!(None, genesis.header.base_fee_per_gas);
+    assert_eq!(
+        SpecId::BERLIN,
+        evm.get_evm_spec(None, &mut working_set).unwrap()
+    );
+
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    assert_eq!(0, evm.block_env.get(&mut working_set).unwrap().basefee);
+    evm.end_slot_hook(&mut working_set);
+    evm.finalize_hook(&[10u8; 32].into(), &mut working_set.accessory_state());
+    assert_eq!(
+        None,
+        evm.head
+            .get(&mut working_set)
+            .unwrap()
+            .header
+            .base_fee_per_gas
+    );
+    assert_eq!(
+        SpecId::LONDON,
+        evm.get_evm_spec(Some("pending".to_owned()), &mut working_set)
+            .unwrap()
+    );
+
+    // The first London block starts from the initial base fee
+    evm.begin_slot_hook([5u8; 32], None, &[10u8; 32].into(), &mut working_set);
+    assert_eq!(
+        reth_primitives::constants::EIP1559_INITIAL_BASE_FEE,
+        evm.block_env.get(&mut working_set).unwrap().basefee
+    );
+    evm.end_slot_hook(&mut working_set);
+    evm.finalize_hook(&[10u8; 32].into(), &mut working_set.accessory_state());
+    assert_eq!(
+        Some(reth_primitives::constants::EIP1559_INITIAL_BASE_FEE),
+        evm.head
+            .get(&mut working_set)
+            .unwrap()
+            .header
+            .base_fee_per_gas
+    );
+
+    assert_eq!(
+        SpecId::LONDON,
+        evm.get_evm_spec(None, &mut working_set).unwrap()
+    );
+    assert_eq!(
+        SpecId::BERLIN,
+        evm.get_evm_spec(Some("0x1".to_owned()), &mut working_set)
+            .unwrap()
+    );
 }
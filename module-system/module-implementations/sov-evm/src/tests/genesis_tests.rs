@@ -36,6 +36,10 @@ lazy_static! {
         limit_contract_code_size: Some(5000),
         starting_base_fee: 70,
         base_fee_params: BaseFeeParams::ethereum(),
+        min_base_fee: 0,
+        l1_gas_price: 0,
+        l1_fee_overhead: 0,
+        l1_fee_scalar: 1_000_000,
     };
 }
 
@@ -78,6 +82,59 @@ fn genesis_data() {
     );
 }
 
+#[test]
+fn debug_account_and_storage_range() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let address = TEST_CONFIG.data[0].address;
+
+    let layout = evm.debug_evm_account(address, &mut working_set).unwrap();
+    assert!(layout.exists);
+    assert_eq!(TEST_CONFIG.data[0].balance, layout.balance);
+    assert_eq!(KECCAK_EMPTY, layout.code_hash);
+    assert_eq!(None, layout.code_key);
+    assert!(layout
+        .account_key
+        .starts_with(evm.accounts.prefix().as_aligned_vec().as_ref()));
+    assert!(layout.storage_prefix.ends_with(&address.0));
+
+    let account = evm.accounts.get(&address, &mut working_set).unwrap();
+    for slot in 0..5u64 {
+        account
+            .storage
+            .set(&U256::from(slot), &U256::from(slot + 100), &mut working_set);
+    }
+
+    // The ranges cover every slot once, whatever their order
+    let mut slots = Vec::new();
+    let mut start = U256::ZERO;
+    let first = evm
+        .debug_evm_storage_range(address, start, 3, &mut working_set)
+        .unwrap();
+    assert_eq!(3, first.storage.len());
+    start = first.next_key.expect("The first range isn't the last");
+    let second = evm
+        .debug_evm_storage_range(address, start, 3, &mut working_set)
+        .unwrap();
+    assert_eq!(None, second.next_key);
+    for (state_key, entry) in first.storage.iter().chain(second.storage.iter()) {
+        assert!(state_key.starts_with(&format!("0x{}", hex::encode(&layout.storage_prefix))));
+        assert_eq!(entry.key + U256::from(100), entry.value);
+        slots.push(entry.key);
+    }
+    slots.sort();
+    assert_eq!((0..5u64).map(U256::from).collect::<Vec<_>>(), slots);
+
+    let missing = evm
+        .debug_evm_account(Address::from([9u8; 20]), &mut working_set)
+        .unwrap();
+    assert!(!missing.exists);
+    assert!(evm
+        .debug_evm_storage_range(Address::from([9u8; 20]), U256::ZERO, 3, &mut working_set)
+        .unwrap()
+        .storage
+        .is_empty());
+}
+
 #[test]
 fn genesis_cfg() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
@@ -93,6 +150,10 @@ fn genesis_cfg() {
             coinbase: Address::from([3u8; 20]),
             limit_contract_code_size: Some(5000),
             base_fee_params: BaseFeeParams::ethereum(),
+            min_base_fee: 0,
+            l1_gas_price: 0,
+            l1_fee_overhead: 0,
+            l1_fee_scalar: 1_000_000,
         }
     );
 }
@@ -116,6 +177,16 @@ fn genesis_empty_spec_defaults_to_shanghai() {
     assert_eq!(cfg.spec, vec![(0, SpecId::SHANGHAI)]);
 }
 
+#[test]
+#[should_panic(expected = "lower than the minimum base fee")]
+fn genesis_base_fee_below_minimum() {
+    get_evm(&EvmConfig {
+        starting_base_fee: 5,
+        min_base_fee: 10,
+        ..Default::default()
+    });
+}
+
 #[test]
 #[should_panic(expected = "Cancun is not supported")]
 fn genesis_cfg_cancun() {
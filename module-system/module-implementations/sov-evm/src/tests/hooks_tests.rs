@@ -20,7 +20,7 @@ lazy_static! {
 #[test]
 fn begin_slot_hook_creates_pending_block() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
-    evm.begin_slot_hook(DA_ROOT_HASH.0, &[10u8; 32].into(), &mut working_set);
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &[10u8; 32].into(), &mut working_set);
     let pending_block = evm.block_env.get(&mut working_set).unwrap();
     assert_eq!(
         pending_block,
@@ -35,10 +35,54 @@ fn begin_slot_hook_creates_pending_block() {
     );
 }
 
+#[test]
+fn begin_slot_hook_follows_da_time() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let da_timestamp = TEST_CONFIG.genesis_timestamp + 100;
+    evm.begin_slot_hook(
+        DA_ROOT_HASH.0,
+        Some(da_timestamp),
+        &[10u8; 32].into(),
+        &mut working_set,
+    );
+    assert_eq!(
+        da_timestamp,
+        evm.block_env.get(&mut working_set).unwrap().timestamp
+    );
+    evm.end_slot_hook(&mut working_set);
+
+    // DA time going backwards still gives an increasing block timestamp
+    evm.begin_slot_hook(
+        DA_ROOT_HASH.0,
+        Some(0),
+        &[10u8; 32].into(),
+        &mut working_set,
+    );
+    assert_eq!(
+        da_timestamp + 1,
+        evm.block_env.get(&mut working_set).unwrap().timestamp
+    );
+}
+
+#[test]
+fn base_fee_does_not_go_below_minimum() {
+    let mut config = TEST_CONFIG.clone();
+    // The base fee would decrease to 62 after the empty genesis block
+    config.min_base_fee = 65;
+    let (evm, mut working_set) = get_evm(&config);
+
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &[10u8; 32].into(), &mut working_set);
+    assert_eq!(evm.block_env.get(&mut working_set).unwrap().basefee, 65);
+
+    evm.end_slot_hook(&mut working_set);
+    let head = evm.head.get(&mut working_set).unwrap();
+    assert_eq!(head.header.base_fee_per_gas, Some(65));
+}
+
 #[test]
 fn end_slot_hook_sets_head() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
-    evm.begin_slot_hook(DA_ROOT_HASH.0, &[10u8; 32].into(), &mut working_set);
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &[10u8; 32].into(), &mut working_set);
 
     evm.pending_transactions.push(
         &create_pending_transaction(H256::from([1u8; 32]), 1),
@@ -97,7 +141,7 @@ fn end_slot_hook_sets_head() {
 #[test]
 fn end_slot_hook_moves_transactions_and_receipts() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
-    evm.begin_slot_hook(DA_ROOT_HASH.0, &[10u8; 32].into(), &mut working_set);
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &[10u8; 32].into(), &mut working_set);
 
     let tx1 = create_pending_transaction(H256::from([1u8; 32]), 1);
     evm.pending_transactions.push(&tx1, &mut working_set);
@@ -172,6 +216,7 @@ fn create_pending_transaction(hash: H256, index: u64) -> PendingTransaction {
             gas_used: 100u64,
             log_index_start: 0,
             error: None,
+            l1_fee: Default::default(),
         },
     }
 }
@@ -180,7 +225,7 @@ fn create_pending_transaction(hash: H256, index: u64) -> PendingTransaction {
 fn finalize_hook_creates_final_block() {
     let (evm, mut working_set) = get_evm(&TEST_CONFIG);
     let p = [10u8; 32].into();
-    evm.begin_slot_hook(DA_ROOT_HASH.0, &p, &mut working_set);
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &p, &mut working_set);
     evm.pending_transactions.push(
         &create_pending_transaction(H256::from([1u8; 32]), 1),
         &mut working_set,
@@ -197,7 +242,7 @@ fn finalize_hook_creates_final_block() {
     evm.finalize_hook(&root_hash, &mut accessory_state);
     assert_eq!(evm.blocks.len(&mut accessory_state), 2);
 
-    evm.begin_slot_hook(DA_ROOT_HASH.0, &root_hash, &mut working_set);
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &root_hash, &mut working_set);
 
     let mut accessory_state = working_set.accessory_state();
 
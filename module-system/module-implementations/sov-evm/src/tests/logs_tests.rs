@@ -0,0 +1,483 @@
+use reth_primitives::constants::{EMPTY_RECEIPTS, EMPTY_TRANSACTIONS};
+use reth_primitives::{Address, Bloom, Bytes, Log, Signature, TransactionSigned, H256};
+use reth_rpc_types::Filter;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::{StateVecAccessor, WorkingSet};
+
+use super::genesis_tests::{get_evm, TEST_CONFIG};
+use super::hooks_tests::DA_ROOT_HASH;
+use crate::evm::primitive_types::{L1Fee, Receipt, TransactionSignedAndRecovered};
+use crate::experimental::PendingTransaction;
+use crate::{EthApiError, Evm, LogsCursor};
+
+type C = DefaultContext;
+
+const EMITTER: Address = Address::repeat_byte(7);
+const OTHER_EMITTER: Address = Address::repeat_byte(8);
+const TOPIC: H256 = H256::repeat_byte(9);
+
+fn log_from(address: Address) -> Log {
+    Log {
+        address,
+        topics: vec![TOPIC],
+        data: Bytes::from([1u8; 4]),
+    }
+}
+
+fn pending_transaction(block_number: u64, hash: H256, logs: Vec<Log>) -> PendingTransaction {
+    PendingTransaction {
+        transaction: TransactionSignedAndRecovered {
+            signer: Address::from([1u8; 20]),
+            signed_transaction: TransactionSigned {
+                hash,
+                signature: Signature::default(),
+                transaction: reth_primitives::Transaction::Eip1559(reth_primitives::TxEip1559 {
+                    chain_id: 1u64,
+                    nonce: 1u64,
+                    gas_limit: 1000u64,
+                    max_fee_per_gas: 2000u64 as u128,
+                    max_priority_fee_per_gas: 3000u64 as u128,
+                    to: reth_primitives::TransactionKind::Call(EMITTER),
+                    value: 0u128,
+                    access_list: reth_primitives::AccessList::default(),
+                    input: Bytes::default(),
+                }),
+            },
+            block_number,
+        },
+        receipt: Receipt {
+            receipt: reth_primitives::Receipt {
+                tx_type: reth_primitives::TxType::EIP1559,
+                success: true,
+                cumulative_gas_used: 100u64,
+                logs,
+            },
+            gas_used: 100u64,
+            log_index_start: 0,
+            error: None,
+            l1_fee: Default::default(),
+        },
+    }
+}
+
+fn produce_block(evm: &Evm<C>, txs: Vec<PendingTransaction>, working_set: &mut WorkingSet<C>) {
+    evm.begin_slot_hook(DA_ROOT_HASH.0, None, &[10u8; 32].into(), working_set);
+    for tx in txs {
+        evm.pending_transactions.push(&tx, working_set);
+    }
+    evm.end_slot_hook(working_set);
+    evm.finalize_hook(&[99u8; 32].into(), &mut working_set.accessory_state());
+}
+
+#[test]
+fn get_logs_uses_blooms_and_limits() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let tx_hash = H256::from([1u8; 32]);
+    produce_block(
+        &evm,
+        vec![pending_transaction(1, tx_hash, vec![log_from(EMITTER)])],
+        &mut working_set,
+    );
+    produce_block(
+        &evm,
+        vec![pending_transaction(2, H256::from([2u8; 32]), vec![])],
+        &mut working_set,
+    );
+    assert_eq!(3, evm.logs_blooms.len(&mut working_set.accessory_state()));
+
+    let filter = Filter::new().from_block(0u64).to_block(2u64);
+
+    let logs = evm
+        .logs_for_filter(filter.clone().address(EMITTER), 100, 100, &mut working_set)
+        .unwrap();
+    assert_eq!(1, logs.len());
+    assert_eq!(EMITTER, logs[0].address);
+    assert_eq!(vec![TOPIC], logs[0].topics);
+    assert_eq!(Some(tx_hash), logs[0].transaction_hash);
+    assert_eq!(Some(reth_primitives::U256::from(1)), logs[0].block_number);
+
+    let logs = evm
+        .logs_for_filter(
+            filter.clone().address(OTHER_EMITTER),
+            100,
+            100,
+            &mut working_set,
+        )
+        .unwrap();
+    assert!(logs.is_empty());
+
+    assert!(matches!(
+        evm.logs_for_filter(filter.clone(), 2, 100, &mut working_set),
+        Err(EthApiError::QueryExceedsMaxBlocks(2))
+    ));
+    assert!(matches!(
+        evm.logs_for_filter(filter.clone(), 100, 0, &mut working_set),
+        Err(EthApiError::QueryExceedsMaxResults(0))
+    ));
+    assert!(matches!(
+        evm.logs_for_filter(
+            Filter::new().from_block(2u64).to_block(1u64),
+            100,
+            100,
+            &mut working_set
+        ),
+        Err(EthApiError::InvalidBlockRange)
+    ));
+}
+
+#[test]
+fn get_logs_pages_with_a_cursor() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    produce_block(
+        &evm,
+        vec![pending_transaction(
+            1,
+            H256::from([1u8; 32]),
+            vec![log_from(EMITTER), log_from(EMITTER)],
+        )],
+        &mut working_set,
+    );
+    produce_block(
+        &evm,
+        vec![pending_transaction(2, H256::from([2u8; 32]), vec![])],
+        &mut working_set,
+    );
+    produce_block(
+        &evm,
+        vec![pending_transaction(
+            3,
+            H256::from([3u8; 32]),
+            vec![log_from(EMITTER)],
+        )],
+        &mut working_set,
+    );
+
+    let filter = Filter::new()
+        .from_block(0u64)
+        .to_block(3u64)
+        .address(EMITTER);
+
+    // Pages are limited by their size
+    let page = evm
+        .logs_page(filter.clone(), None, 1, 100, &mut working_set)
+        .unwrap();
+    assert_eq!(1, page.logs.len());
+    assert_eq!(Some(reth_primitives::U256::from(0)), page.logs[0].log_index);
+    let cursor = LogsCursor {
+        block_number: 1,
+        log_index: 1,
+    };
+    assert_eq!(Some(cursor), page.cursor);
+
+    let page = evm
+        .logs_page(filter.clone(), Some(cursor), 1, 100, &mut working_set)
+        .unwrap();
+    assert_eq!(1, page.logs.len());
+    assert_eq!(Some(reth_primitives::U256::from(1)), page.logs[0].log_index);
+    let cursor = LogsCursor {
+        block_number: 3,
+        log_index: 0,
+    };
+    assert_eq!(Some(cursor), page.cursor);
+
+    let page = evm
+        .logs_page(filter.clone(), Some(cursor), 1, 100, &mut working_set)
+        .unwrap();
+    assert_eq!(
+        Some(reth_primitives::U256::from(3)),
+        page.logs[0].block_number
+    );
+    assert_eq!(None, page.cursor);
+
+    // And by the blocks they scan
+    let page = evm
+        .logs_page(filter.clone(), None, 10, 2, &mut working_set)
+        .unwrap();
+    assert_eq!(2, page.logs.len());
+    let cursor = LogsCursor {
+        block_number: 2,
+        log_index: 0,
+    };
+    assert_eq!(Some(cursor), page.cursor);
+
+    let page = evm
+        .logs_page(filter.clone(), Some(cursor), 10, 2, &mut working_set)
+        .unwrap();
+    assert_eq!(1, page.logs.len());
+    assert_eq!(None, page.cursor);
+
+    let outside = LogsCursor {
+        block_number: 5,
+        log_index: 0,
+    };
+    assert!(matches!(
+        evm.logs_page(filter.clone(), Some(outside), 10, 100, &mut working_set),
+        Err(EthApiError::InvalidParams(_))
+    ));
+    assert!(matches!(
+        evm.logs_page(filter, None, 0, 100, &mut working_set),
+        Err(EthApiError::InvalidParams(_))
+    ));
+}
+
+#[test]
+fn get_block_receipts_of_a_block() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let first_hash = H256::from([1u8; 32]);
+    let second_hash = H256::from([2u8; 32]);
+    produce_block(
+        &evm,
+        vec![pending_transaction(1, first_hash, vec![log_from(EMITTER)])],
+        &mut working_set,
+    );
+    produce_block(
+        &evm,
+        vec![
+            pending_transaction(2, second_hash, vec![]),
+            pending_transaction(2, H256::from([3u8; 32]), vec![log_from(EMITTER)]),
+        ],
+        &mut working_set,
+    );
+
+    let receipts = evm
+        .get_block_receipts("0x2".to_string(), &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(2, receipts.len());
+    assert_eq!(Some(second_hash), receipts[0].receipt.transaction_hash);
+    assert_eq!(
+        reth_primitives::U64::from(1),
+        receipts[1].receipt.transaction_index
+    );
+    assert_eq!(
+        Some(reth_primitives::U256::from(1)),
+        receipts[1].receipt.logs[0].transaction_index
+    );
+
+    let block_hash = receipts[0].receipt.block_hash.unwrap();
+    assert_eq!(
+        Some(receipts),
+        evm.get_block_receipts(format!("{:?}", block_hash), &mut working_set)
+            .unwrap()
+    );
+
+    let latest = evm
+        .get_block_receipts("latest".to_string(), &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(2, latest.len());
+
+    let earliest = evm
+        .get_block_receipts("earliest".to_string(), &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert!(earliest.is_empty());
+
+    assert_eq!(
+        None,
+        evm.get_block_receipts("0x10".to_string(), &mut working_set)
+            .unwrap()
+    );
+    assert_eq!(
+        None,
+        evm.get_block_receipts(format!("{:?}", H256::from([4u8; 32])), &mut working_set)
+            .unwrap()
+    );
+}
+
+#[test]
+fn get_transaction_by_block_and_index() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let first_hash = H256::from([1u8; 32]);
+    let second_hash = H256::from([2u8; 32]);
+    produce_block(
+        &evm,
+        vec![pending_transaction(1, first_hash, vec![])],
+        &mut working_set,
+    );
+    produce_block(
+        &evm,
+        vec![
+            pending_transaction(2, H256::from([3u8; 32]), vec![]),
+            pending_transaction(2, second_hash, vec![]),
+        ],
+        &mut working_set,
+    );
+    let index = |index: u64| reth_primitives::U64::from(index);
+
+    let tx = evm
+        .get_transaction_by_block_number_and_index("0x2".to_string(), index(1), &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(second_hash, tx.hash);
+    assert_eq!(
+        Some(tx.clone()),
+        evm.get_transaction_by_hash(second_hash, &mut working_set)
+            .unwrap()
+    );
+    assert_eq!(
+        Some(tx),
+        evm.get_transaction_by_block_hash_and_index(
+            evm.blocks
+                .get(2, &mut working_set.accessory_state())
+                .unwrap()
+                .header
+                .hash,
+            index(1),
+            &mut working_set
+        )
+        .unwrap()
+    );
+    let latest = evm
+        .get_transaction_by_block_number_and_index("latest".to_string(), index(0), &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(H256::from([3u8; 32]), latest.hash);
+
+    // The index is within the transactions of the block
+    assert_eq!(
+        None,
+        evm.get_transaction_by_block_number_and_index(
+            "0x2".to_string(),
+            index(2),
+            &mut working_set
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        None,
+        evm.get_transaction_by_block_number_and_index(
+            "0x0".to_string(),
+            index(0),
+            &mut working_set
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        None,
+        evm.get_transaction_by_block_hash_and_index(
+            H256::from([4u8; 32]),
+            index(0),
+            &mut working_set
+        )
+        .unwrap()
+    );
+
+    // Rollup blocks have no uncles
+    assert_eq!(
+        Some(reth_primitives::U256::ZERO),
+        evm.get_uncle_count_by_block_number("0x1".to_string(), &mut working_set)
+            .unwrap()
+    );
+    assert_eq!(
+        None,
+        evm.get_uncle_count_by_block_hash(H256::from([4u8; 32]), &mut working_set)
+            .unwrap()
+    );
+    assert!(evm
+        .get_uncle_by_block_number_and_index("0x1".to_string(), index(0), &mut working_set)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn block_headers_commit_to_transactions_and_receipts() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    produce_block(
+        &evm,
+        vec![
+            pending_transaction(1, H256::from([1u8; 32]), vec![log_from(EMITTER)]),
+            pending_transaction(1, H256::from([2u8; 32]), vec![log_from(OTHER_EMITTER)]),
+        ],
+        &mut working_set,
+    );
+    produce_block(&evm, vec![], &mut working_set);
+
+    let mut accessory_state = working_set.accessory_state();
+    let block = evm.blocks.get(1, &mut accessory_state).unwrap();
+    let transactions: Vec<_> = block
+        .transactions
+        .clone()
+        .map(|i| {
+            evm.transactions
+                .get(i as usize, &mut accessory_state)
+                .unwrap()
+                .signed_transaction
+        })
+        .collect();
+    let receipts: Vec<_> = block
+        .transactions
+        .clone()
+        .map(|i| {
+            evm.receipts
+                .get(i as usize, &mut accessory_state)
+                .unwrap()
+                .receipt
+                .with_bloom()
+        })
+        .collect();
+    assert_eq!(2, transactions.len());
+
+    let header = &block.header;
+    assert_eq!(
+        reth_primitives::proofs::calculate_transaction_root(
+            transactions.iter().collect::<Vec<_>>().as_slice()
+        ),
+        header.transactions_root
+    );
+    assert_ne!(EMPTY_TRANSACTIONS, header.transactions_root);
+    assert_eq!(
+        reth_primitives::proofs::calculate_receipt_root(&receipts),
+        header.receipts_root
+    );
+    assert_ne!(EMPTY_RECEIPTS, header.receipts_root);
+    assert_eq!(receipts[0].bloom | receipts[1].bloom, header.logs_bloom);
+    assert_ne!(Bloom::zero(), header.logs_bloom);
+
+    // The roots of blocks without transactions are the ones of the empty tries
+    let empty_block = evm.blocks.get(2, &mut accessory_state).unwrap();
+    assert_eq!(EMPTY_TRANSACTIONS, empty_block.header.transactions_root);
+    assert_eq!(EMPTY_RECEIPTS, empty_block.header.receipts_root);
+    assert_eq!(Bloom::zero(), empty_block.header.logs_bloom);
+}
+
+#[test]
+fn receipts_return_the_l1_fee_paid() {
+    let (evm, mut working_set) = get_evm(&TEST_CONFIG);
+    let hash = H256::from([1u8; 32]);
+    let mut transaction = pending_transaction(1, hash, vec![]);
+    transaction.receipt.l1_fee = L1Fee {
+        fee: reth_primitives::U256::from(4500),
+        gas_used: 1000,
+        scalar: 1_500_000,
+    };
+    produce_block(
+        &evm,
+        vec![
+            transaction,
+            pending_transaction(1, H256::from([2u8; 32]), vec![]),
+        ],
+        &mut working_set,
+    );
+
+    let receipt = evm
+        .get_transaction_receipt(hash, &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(Some(hash), receipt.receipt.transaction_hash);
+    assert_eq!(reth_primitives::U256::from(1000), receipt.l1_gas_used);
+    assert_eq!(reth_primitives::U256::from(4500), receipt.l1_fee);
+    assert_eq!("1.5", receipt.l1_fee_scalar);
+
+    let json = serde_json::to_value(&receipt).unwrap();
+    assert!(json.get("l1Fee").is_some());
+    assert!(json.get("transactionHash").is_some());
+
+    // The receipts of the block carry the same fields
+    let receipts = evm
+        .get_block_receipts("0x1".to_string(), &mut working_set)
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt, receipts[0]);
+    assert_eq!(reth_primitives::U256::ZERO, receipts[1].l1_fee);
+}
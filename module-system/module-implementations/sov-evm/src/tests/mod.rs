@@ -2,5 +2,6 @@ mod call_tests;
 mod cfg_tests;
 mod genesis_tests;
 mod hooks_tests;
+mod logs_tests;
 pub(crate) mod test_signer;
 mod tx_tests;
@@ -2,7 +2,7 @@ use ethers_core::rand::rngs::StdRng;
 use ethers_core::rand::SeedableRng;
 use reth_primitives::{
     Address, Bytes as RethBytes, Transaction as RethTransaction, TransactionKind,
-    TxEip1559 as RethTxEip1559,
+    TxEip1559 as RethTxEip1559, TxEip4844 as RethTxEip4844, H256,
 };
 use secp256k1::{PublicKey, SecretKey};
 
@@ -62,4 +62,29 @@ impl TestSigner {
             rlp: signed.envelope_encoded().to_vec(),
         })
     }
+
+    /// Signs Eip4844 transaction carrying one random blob hash with to and nonce overridden.
+    pub(crate) fn sign_blob_transaction(
+        &self,
+        to: TransactionKind,
+        nonce: u64,
+    ) -> Result<RlpEvmTransaction, SignError> {
+        let reth_tx = RethTxEip4844 {
+            to,
+            nonce,
+            chain_id: 1,
+            gas_limit: 1_000_000u64,
+            max_fee_per_gas: u128::from(reth_primitives::constants::MIN_PROTOCOL_BASE_FEE * 2),
+            blob_versioned_hashes: vec![H256::random()],
+            max_fee_per_blob_gas: 1,
+            ..Default::default()
+        };
+
+        let reth_tx = RethTransaction::Eip4844(reth_tx);
+        let signed = self.signer.sign_transaction(reth_tx, self.address)?;
+
+        Ok(RlpEvmTransaction {
+            rlp: signed.envelope_encoded().to_vec(),
+        })
+    }
 }
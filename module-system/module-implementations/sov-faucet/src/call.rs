@@ -0,0 +1,107 @@
+use anyhow::Result;
+use sov_bank::Coins;
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Context, EventEmitter, ModuleErrorCode, WorkingSet};
+
+use crate::Faucet;
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-faucet` module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Sends the amount per request of the faucet to the recipient, which can be the sender.
+    /// Both the sender and the recipient then wait the request interval before their next
+    /// request.
+    RequestFunds {
+        /// The address receiving the tokens.
+        recipient: C::Address,
+    },
+}
+
+impl<C: Context> Faucet<C> {
+    pub(crate) fn request_funds(
+        &self,
+        recipient: C::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let config = self
+            .get_config(working_set)
+            .ok_or_else(|| anyhow::anyhow!("The limits of the faucet must be set at genesis"))?;
+        let slot = self.get_slot(working_set);
+
+        for address in [context.sender(), &recipient] {
+            let next_request_slot = self.next_request_slot(address, working_set);
+            if next_request_slot > slot {
+                return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                    "Address {} can't request tokens before slot {}",
+                    address, next_request_slot
+                )));
+            }
+        }
+
+        let slot_requests = self.slot_requests.get(working_set).unwrap_or_default();
+        if slot_requests >= config.max_requests_per_slot {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Slot {} already has the maximum of {} requests",
+                slot, config.max_requests_per_slot
+            )));
+        }
+
+        let remaining_budget = self.remaining_daily_budget(working_set);
+        if remaining_budget < config.amount_per_request {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "The faucet has {} tokens left in its daily budget, less than the {} of a request",
+                remaining_budget, config.amount_per_request
+            )));
+        }
+
+        let spent_today = self
+            .spent_today
+            .get(working_set)
+            .unwrap_or_default()
+            .checked_add(config.amount_per_request)
+            .ok_or_else(|| {
+                ModuleErrorCode::InvariantViolation
+                    .error("The amount handed out today overflows a u64")
+            })?;
+
+        let coins = Coins {
+            amount: config.amount_per_request,
+            token_address: config.token_address,
+        };
+        self.bank
+            .transfer_from(&self.address, &recipient, coins, working_set)?;
+
+        self.last_requests.set(context.sender(), &slot, working_set);
+        self.last_requests.set(&recipient, &slot, working_set);
+        self.slot_requests.set(&(slot_requests + 1), working_set);
+        self.spent_today.set(&spent_today, working_set);
+        working_set.emit_event(
+            self,
+            "request_funds",
+            &format!(
+                "{} tokens to {} requested by {}",
+                config.amount_per_request,
+                recipient,
+                context.sender()
+            ),
+        );
+
+        Ok(CallResponse::default())
+    }
+}
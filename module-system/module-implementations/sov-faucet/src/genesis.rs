@@ -0,0 +1,106 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Faucet;
+
+/// Genesis configuration for the [`Faucet`] module, the limits of the faucet.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    Debug,
+    Clone,
+    Serialize,
+    Deserialize,
+    Eq,
+    PartialEq,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct FaucetConfig<C: Context> {
+    /// The address of the token handed out.
+    pub token_address: C::Address,
+    /// The amount of tokens sent by every request.
+    pub amount_per_request: u64,
+    /// How many slots an address waits between two requests.
+    pub request_interval: u64,
+    /// The maximum number of requests of a slot.
+    pub max_requests_per_slot: u32,
+    /// The maximum amount of tokens handed out in a day.
+    pub daily_budget: u64,
+    /// The number of slots of a day.
+    pub slots_per_day: u64,
+}
+
+impl<C: Context> Faucet<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if config.amount_per_request == 0 {
+            anyhow::bail!("The amount per request can't be zero");
+        }
+        if config.max_requests_per_slot == 0 {
+            anyhow::bail!("The maximum number of requests per slot can't be zero");
+        }
+        if config.slots_per_day == 0 {
+            anyhow::bail!("The number of slots per day can't be zero");
+        }
+        if config.daily_budget < config.amount_per_request {
+            anyhow::bail!(
+                "The daily budget {} is lower than the amount per request {}",
+                config.daily_budget,
+                config.amount_per_request
+            );
+        }
+        self.slot.set(&0, working_set);
+        self.config.set(config, working_set);
+        self.slot_requests.set(&0, working_set);
+        self.spent_today.set(&0, working_set);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::{AddressBech32, Spec};
+
+    use crate::FaucetConfig;
+
+    #[test]
+    fn test_config_serialization() {
+        let token_address: <DefaultContext as Spec>::Address = AddressBech32::from_str(
+            "sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp",
+        )
+        .unwrap()
+        .into();
+        let config = FaucetConfig::<DefaultContext> {
+            token_address,
+            amount_per_request: 100,
+            request_interval: 50,
+            max_requests_per_slot: 4,
+            daily_budget: 100000,
+            slots_per_day: 7200,
+        };
+
+        let data = r#"
+        {
+            "token_address":"sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp",
+            "amount_per_request":100,
+            "request_interval":50,
+            "max_requests_per_slot":4,
+            "daily_budget":100000,
+            "slots_per_day":7200
+        }"#;
+
+        let parsed_config: FaucetConfig<DefaultContext> = serde_json::from_str(data).unwrap();
+        assert_eq!(config, parsed_config);
+    }
+}
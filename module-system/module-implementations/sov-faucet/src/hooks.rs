@@ -0,0 +1,21 @@
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Faucet;
+
+impl<C: Context> Faucet<C> {
+    /// Starts a new slot, resetting the number of requests of the slot, and the daily budget
+    /// at the first slot of a day. It has to be called once per slot, before the batches of the
+    /// slot are applied.
+    pub fn begin_slot_hook(&self, working_set: &mut WorkingSet<C>) {
+        let Some(config) = self.get_config(working_set) else {
+            return;
+        };
+        let slot = self.get_slot(working_set) + 1;
+        self.slot.set(&slot, working_set);
+        self.slot_requests.set(&0, working_set);
+        if slot % config.slots_per_day == 0 {
+            self.spent_today.set(&0, working_set);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! The `sov-faucet` module hands out tokens to the users of devnets and testnets, so they can
+//! fund their accounts without a centralized faucet service.
+//!
+//! The module sends the tokens of its own account, funded at genesis or by transfers. Every
+//! request sends the same amount, and is rate limited: an address has to wait a number of slots
+//! between its requests, a slot has a maximum number of requests, and the amount handed out each
+//! day is capped by a budget. A day is a fixed number of slots, counted by
+//! [`Faucet::begin_slot_hook`].
+//!
+//! The module is meant for networks whose tokens have no value: the runtimes only include it
+//! behind a `devnet` feature, never on mainnet.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+mod hooks;
+#[cfg(feature = "native")]
+mod query;
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The `sov-faucet` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct Faucet<C: sov_modules_api::Context> {
+    /// The address of the `sov_faucet` module, which holds the tokens handed out.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The number of the current slot, counted from genesis.
+    #[state]
+    pub(crate) slot: StateValue<u64>,
+
+    /// The limits of the faucet.
+    #[state]
+    pub(crate) config: StateValue<FaucetConfig<C>>,
+
+    /// Address => the last slot the address requested tokens at, or received them at.
+    #[state]
+    pub(crate) last_requests: StateMap<C::Address, u64>,
+
+    /// The number of requests of the current slot.
+    #[state]
+    pub(crate) slot_requests: StateValue<u32>,
+
+    /// The amount handed out since the beginning of the current day.
+    #[state]
+    pub(crate) spent_today: StateValue<u64>,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Faucet<C> {
+    type Context = C;
+
+    type Config = FaucetConfig<C>;
+
+    type CallMessage = CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::RequestFunds { recipient } => {
+                self.request_funds(recipient, context, working_set)?
+            }
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Faucet<C> {
+    /// Returns the number of the current slot, counted from genesis.
+    pub fn get_slot(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.slot.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the limits of the faucet, set at genesis.
+    pub fn get_config(&self, working_set: &mut WorkingSet<C>) -> Option<FaucetConfig<C>> {
+        self.config.get(working_set)
+    }
+
+    /// Returns the first slot the address can request tokens at, either the current slot or
+    /// the slot its last request stops being rate limited at.
+    pub fn next_request_slot(&self, address: &C::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        let slot = self.get_slot(working_set);
+        let request_interval = self
+            .get_config(working_set)
+            .map(|config| config.request_interval)
+            .unwrap_or_default();
+        match self.last_requests.get(address, working_set) {
+            Some(last_request) => slot.max(last_request.saturating_add(request_interval)),
+            None => slot,
+        }
+    }
+
+    /// Returns the amount the faucet can still hand out today.
+    pub fn remaining_daily_budget(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        let daily_budget = self
+            .get_config(working_set)
+            .map(|config| config.daily_budget)
+            .unwrap_or_default();
+        let spent_today = self.spent_today.get(working_set).unwrap_or_default();
+        daily_budget.saturating_sub(spent_today)
+    }
+}
@@ -0,0 +1,62 @@
+//! Defines rpc queries exposed by the faucet module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Faucet;
+
+/// The response type to the `getStatus` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct FaucetStatus {
+    /// The current slot.
+    pub slot: u64,
+    /// The amount of tokens sent by every request.
+    pub amount_per_request: u64,
+    /// The balance of the faucet.
+    pub balance: u64,
+    /// The amount the faucet can still hand out today.
+    pub remaining_daily_budget: u64,
+    /// The number of requests the current slot can still have.
+    pub remaining_slot_requests: u32,
+    /// The first slot the queried address can request tokens at, if an address was given.
+    pub next_request_slot: Option<u64>,
+}
+
+#[rpc_gen(client, server, namespace = "faucet")]
+impl<C: Context> Faucet<C> {
+    /// Returns the limits and the balance of the faucet, and when the given address can request
+    /// tokens, so users can check a `RequestFunds` call goes through before sending it.
+    #[rpc_method(name = "getStatus")]
+    pub fn status(
+        &self,
+        address: Option<C::Address>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<FaucetStatus> {
+        let config = self.get_config(working_set);
+        let balance = config
+            .as_ref()
+            .and_then(|config| {
+                self.bank.get_balance_of(
+                    self.address.clone(),
+                    config.token_address.clone(),
+                    working_set,
+                )
+            })
+            .unwrap_or_default();
+        let max_requests_per_slot = config
+            .as_ref()
+            .map(|config| config.max_requests_per_slot)
+            .unwrap_or_default();
+        let slot_requests = self.slot_requests.get(working_set).unwrap_or_default();
+        Ok(FaucetStatus {
+            slot: self.get_slot(working_set),
+            amount_per_request: config
+                .map(|config| config.amount_per_request)
+                .unwrap_or_default(),
+            balance,
+            remaining_daily_budget: self.remaining_daily_budget(working_set),
+            remaining_slot_requests: max_requests_per_slot.saturating_sub(slot_requests),
+            next_request_slot: address.map(|address| self.next_request_slot(&address, working_set)),
+        })
+    }
+}
@@ -0,0 +1,158 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig};
+use sov_faucet::{CallMessage, Faucet, FaucetConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    CallResponse, Context, DispatchCall, EncodeCall, Error, Genesis, MessageCodec, ModuleInfo,
+    Spec, WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    pub bank: Bank<C>,
+    pub faucet: Faucet<C>,
+}
+
+const FAUCET_BALANCE: u64 = 10000;
+const AMOUNT_PER_REQUEST: u64 = 100;
+const REQUEST_INTERVAL: u64 = 3;
+const SLOTS_PER_DAY: u64 = 10;
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    token: <C as Spec>::Address,
+}
+
+impl TestSetup {
+    fn init(daily_budget: u64, working_set: &mut WorkingSet<C>) -> Self {
+        let runtime = TestRuntime::<C>::default();
+        let token = get_genesis_token_address::<C>("sov-test-token", 0);
+
+        let bank_config = BankConfig::with_token(
+            "sov-test-token",
+            vec![(*runtime.faucet.address(), FAUCET_BALANCE)],
+        );
+        let faucet_config = FaucetConfig {
+            token_address: token,
+            amount_per_request: AMOUNT_PER_REQUEST,
+            request_interval: REQUEST_INTERVAL,
+            max_requests_per_slot: 2,
+            daily_budget,
+            slots_per_day: SLOTS_PER_DAY,
+        };
+        runtime
+            .genesis(&GenesisConfig::new(bank_config, faucet_config), working_set)
+            .unwrap();
+
+        Self { runtime, token }
+    }
+
+    fn request_funds(
+        &self,
+        sender: <C as Spec>::Address,
+        recipient: <C as Spec>::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message =
+            <TestRuntime<C> as EncodeCall<Faucet<C>>>::encode_call(CallMessage::RequestFunds {
+                recipient,
+            });
+        let message = TestRuntime::<C>::decode_call(&message).unwrap();
+        let context = C::new(sender, sender, 1);
+        self.runtime
+            .dispatch_call_and_deferred(message, working_set, &context)
+    }
+
+    fn begin_slots(&self, count: u64, working_set: &mut WorkingSet<C>) {
+        for _ in 0..count {
+            self.runtime.faucet.begin_slot_hook(working_set);
+        }
+    }
+
+    fn balance(&self, account: <C as Spec>::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.runtime
+            .bank
+            .balance_or_zero(account, self.token, working_set)
+    }
+}
+
+#[test]
+fn requests_are_rate_limited_per_address_and_slot() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(FAUCET_BALANCE, working_set);
+    let alice = generate_address::<C>("alice");
+    let bob = generate_address::<C>("bob");
+    let carol = generate_address::<C>("carol");
+    setup.begin_slots(1, working_set);
+
+    setup
+        .request_funds(alice, alice, working_set)
+        .expect("Request failed");
+    assert_eq!(AMOUNT_PER_REQUEST, setup.balance(alice, working_set));
+    // Alice waits the request interval, even to fund another address
+    assert!(setup.request_funds(alice, bob, working_set).is_err());
+    assert_eq!(
+        1 + REQUEST_INTERVAL,
+        setup.runtime.faucet.next_request_slot(&alice, working_set)
+    );
+
+    setup
+        .request_funds(bob, bob, working_set)
+        .expect("Request failed");
+    // The slot already has its 2 requests
+    assert!(setup.request_funds(carol, carol, working_set).is_err());
+
+    setup.begin_slots(1, working_set);
+    setup
+        .request_funds(carol, carol, working_set)
+        .expect("Request failed");
+    assert!(setup.request_funds(alice, alice, working_set).is_err());
+
+    setup.begin_slots(REQUEST_INTERVAL - 1, working_set);
+    setup
+        .request_funds(alice, alice, working_set)
+        .expect("Request failed");
+    assert_eq!(2 * AMOUNT_PER_REQUEST, setup.balance(alice, working_set));
+    assert_eq!(
+        FAUCET_BALANCE - 4 * AMOUNT_PER_REQUEST,
+        setup.balance(*setup.runtime.faucet.address(), working_set)
+    );
+}
+
+#[test]
+fn daily_budget_is_reset_every_day() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(2 * AMOUNT_PER_REQUEST, working_set);
+    let accounts: Vec<_> = (0..3)
+        .map(|i| generate_address::<C>(&format!("account-{}", i)))
+        .collect();
+    setup.begin_slots(1, working_set);
+
+    setup
+        .request_funds(accounts[0], accounts[0], working_set)
+        .expect("Request failed");
+    setup.begin_slots(1, working_set);
+    setup
+        .request_funds(accounts[1], accounts[1], working_set)
+        .expect("Request failed");
+    assert_eq!(0, setup.runtime.faucet.remaining_daily_budget(working_set));
+    setup.begin_slots(1, working_set);
+    assert!(setup
+        .request_funds(accounts[2], accounts[2], working_set)
+        .is_err());
+
+    // The next day starts at slot 10
+    setup.begin_slots(SLOTS_PER_DAY - 3, working_set);
+    assert_eq!(SLOTS_PER_DAY, setup.runtime.faucet.get_slot(working_set));
+    setup
+        .request_funds(accounts[2], accounts[2], working_set)
+        .expect("Request failed");
+    assert_eq!(AMOUNT_PER_REQUEST, setup.balance(accounts[2], working_set));
+}
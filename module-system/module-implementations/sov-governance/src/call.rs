@@ -0,0 +1,268 @@
+use anyhow::{Context as _, Result};
+use sov_bank::{Amount, Coins};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Context, EventEmitter, ModuleErrorCode, WorkingSet};
+
+use crate::{Governance, ParameterValue, Proposal, ProposalStatus, Vote, VotingRules};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-governance` module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema)
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage {
+    /// Proposes to change the value of a governed parameter. The sender must hold at least
+    /// the proposal threshold of governance tokens.
+    Propose {
+        /// The name of the governed parameter.
+        parameter: String,
+        /// Its proposed value.
+        value: ParameterValue,
+    },
+    /// Votes on a proposal with `amount` governance tokens, which are locked in the module
+    /// until the end of the voting period. Every account votes at most once on a proposal.
+    Vote {
+        /// The identifier of the proposal.
+        proposal_id: u64,
+        /// Whether the sender approves the proposal.
+        approve: bool,
+        /// The amount of governance tokens backing the vote.
+        amount: Amount,
+    },
+    /// Applies an approved proposal whose timelock expired. Anyone can send this message.
+    Execute {
+        /// The identifier of the proposal.
+        proposal_id: u64,
+    },
+    /// Unlocks the governance tokens of the vote of the sender on a proposal
+    /// whose voting period ended.
+    WithdrawVote {
+        /// The identifier of the proposal.
+        proposal_id: u64,
+    },
+}
+
+impl<C: Context> Governance<C> {
+    fn voting_rules(&self, working_set: &mut WorkingSet<C>) -> Result<VotingRules<C>> {
+        self.voting_rules
+            .get(working_set)
+            .context("The voting rules aren't set")
+    }
+
+    fn proposal(&self, proposal_id: u64, working_set: &mut WorkingSet<C>) -> Result<Proposal<C>> {
+        self.proposals
+            .get(&proposal_id, working_set)
+            .ok_or_else(|| {
+                ModuleErrorCode::NotFound.error(format!("Proposal {} doesn't exist", proposal_id))
+            })
+    }
+
+    pub(crate) fn propose(
+        &self,
+        parameter: String,
+        value: ParameterValue,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let rules = self.voting_rules(working_set)?;
+        if self.parameters.get(&parameter, working_set).is_none() {
+            return Err(
+                ModuleErrorCode::NotFound.error(format!("Parameter {} isn't governed", parameter))
+            );
+        }
+        let balance = self
+            .bank
+            .get_balance_of(
+                context.sender().clone(),
+                rules.governance_token.clone(),
+                working_set,
+            )
+            .unwrap_or_default();
+        if balance < rules.proposal_threshold {
+            return Err(ModuleErrorCode::InsufficientFunds.error(format!(
+                "Account {} holds {} governance tokens, but proposals require {}",
+                context.sender(),
+                balance,
+                rules.proposal_threshold
+            )));
+        }
+
+        let voting_end_height = context
+            .slot_height()
+            .checked_add(rules.voting_period)
+            .context("The voting period is too long")?;
+        let execution_height = voting_end_height
+            .checked_add(rules.timelock)
+            .context("The timelock is too long")?;
+        let proposal_id = self.next_proposal_id.get(working_set).unwrap_or_default();
+        let proposal = Proposal {
+            proposer: context.sender().clone(),
+            parameter,
+            value,
+            voting_end_height,
+            execution_height,
+            votes_for: 0,
+            votes_against: 0,
+            executed: false,
+        };
+        working_set.emit_event(
+            self,
+            "propose",
+            &format!(
+                "proposal {}: {} = {}",
+                proposal_id, proposal.parameter, proposal.value
+            ),
+        );
+        self.proposals.set(&proposal_id, &proposal, working_set);
+        self.next_proposal_id.set(&(proposal_id + 1), working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn vote(
+        &self,
+        proposal_id: u64,
+        vote: Vote,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let rules = self.voting_rules(working_set)?;
+        let mut proposal = self.proposal(proposal_id, working_set)?;
+        if context.slot_height() >= proposal.voting_end_height {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "The voting period of proposal {} ended at slot {}",
+                proposal_id, proposal.voting_end_height
+            )));
+        }
+        if vote.amount == 0 {
+            anyhow::bail!("A vote must be backed by governance tokens");
+        }
+        let vote_key = (proposal_id, context.sender().clone());
+        if self.votes.get(&vote_key, working_set).is_some() {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Account {} already voted on proposal {}",
+                context.sender(),
+                proposal_id
+            )));
+        }
+
+        let tally = if vote.approve {
+            &mut proposal.votes_for
+        } else {
+            &mut proposal.votes_against
+        };
+        *tally = tally
+            .checked_add(vote.amount)
+            .context("The votes on the proposal overflow")?;
+        self.bank
+            .transfer_from(
+                context.sender(),
+                &self.address,
+                Coins {
+                    amount: vote.amount,
+                    token_address: rules.governance_token,
+                },
+                working_set,
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to lock the governance tokens of {}",
+                    context.sender()
+                )
+            })?;
+
+        self.votes.set(&vote_key, &vote, working_set);
+        self.proposals.set(&proposal_id, &proposal, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn execute(
+        &self,
+        proposal_id: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let rules = self.voting_rules(working_set)?;
+        let mut proposal = self.proposal(proposal_id, working_set)?;
+        match proposal.status_at(context.slot_height(), rules.quorum) {
+            ProposalStatus::Executable => {}
+            status => {
+                return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                    "Proposal {} can't be executed while {:?}",
+                    proposal_id, status
+                )))
+            }
+        }
+
+        self.parameters
+            .set(&proposal.parameter, &proposal.value, working_set);
+        working_set.emit_event(
+            self,
+            "execute",
+            &format!(
+                "proposal {}: {} = {}",
+                proposal_id, proposal.parameter, proposal.value
+            ),
+        );
+        proposal.executed = true;
+        self.proposals.set(&proposal_id, &proposal, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn withdraw_vote(
+        &self,
+        proposal_id: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let rules = self.voting_rules(working_set)?;
+        let proposal = self.proposal(proposal_id, working_set)?;
+        if context.slot_height() < proposal.voting_end_height {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "The tokens voting on proposal {} are locked until slot {}",
+                proposal_id, proposal.voting_end_height
+            )));
+        }
+        let vote_key = (proposal_id, context.sender().clone());
+        let vote = self.votes.get(&vote_key, working_set).ok_or_else(|| {
+            ModuleErrorCode::NotFound.error(format!(
+                "Account {} has no tokens locked on proposal {}",
+                context.sender(),
+                proposal_id
+            ))
+        })?;
+
+        self.bank
+            .transfer_from(
+                &self.address,
+                context.sender(),
+                Coins {
+                    amount: vote.amount,
+                    token_address: rules.governance_token,
+                },
+                working_set,
+            )
+            .with_context(|| {
+                format!(
+                    "Failed to unlock the governance tokens of {}",
+                    context.sender()
+                )
+            })?;
+        self.votes.delete(&vote_key, working_set);
+
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,106 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Governance, ParameterValue, VotingRules};
+
+/// A parameter governed from genesis, with its initial value.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct GovernedParameter {
+    /// The name of the parameter.
+    pub name: String,
+    /// Its initial value.
+    pub value: ParameterValue,
+}
+
+/// Genesis configuration for the [`Governance`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct GovernanceConfig<C: Context> {
+    /// The rules of the votes on proposals.
+    pub voting_rules: VotingRules<C>,
+    /// The parameters which can be changed by proposals.
+    #[serde(default)]
+    pub parameters: Vec<GovernedParameter>,
+}
+
+impl<C: Context> Governance<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        let rules = &config.voting_rules;
+        if self
+            .bank
+            .get_token_name(&rules.governance_token, working_set)
+            .is_none()
+        {
+            bail!(
+                "The governance token {} doesn't exist",
+                rules.governance_token
+            );
+        }
+        if rules.voting_period == 0 {
+            bail!("The voting period can't be zero");
+        }
+        self.voting_rules.set(rules, working_set);
+
+        for GovernedParameter { name, value } in &config.parameters {
+            if self.parameters.get(name, working_set).is_some() {
+                bail!("Parameter {} is governed twice", name);
+            }
+            self.parameters.set(name, value, working_set);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::utils::generate_address;
+
+    use crate::{GovernanceConfig, GovernedParameter, VotingRules};
+
+    #[test]
+    fn test_config_serialization() {
+        let config = GovernanceConfig::<DefaultContext> {
+            voting_rules: VotingRules {
+                governance_token: generate_address::<DefaultContext>("token"),
+                proposal_threshold: 100,
+                quorum: 1000,
+                voting_period: 10,
+                timelock: 5,
+            },
+            parameters: vec![GovernedParameter {
+                name: "sequencer_bond".to_owned(),
+                value: 50,
+            }],
+        };
+
+        let data = format!(
+            r#"
+        {{
+            "voting_rules":{{
+                "governance_token":"{}",
+                "proposal_threshold":100,
+                "quorum":1000,
+                "voting_period":10,
+                "timelock":5
+            }},
+            "parameters":[
+                {{"name":"sequencer_bond","value":50}}
+            ]
+        }}"#,
+            config.voting_rules.governance_token
+        );
+
+        let parsed_config: GovernanceConfig<DefaultContext> = serde_json::from_str(&data).unwrap();
+        assert_eq!(config, parsed_config)
+    }
+}
@@ -0,0 +1,277 @@
+//! The `sov-governance` module changes named runtime parameters, such as gas limits, fee constants
+//! or the bond of sequencers, through proposals voted by the holders of a governance token.
+//!
+//! Voters lock the governance tokens backing their vote in the module until the end of the
+//! voting period, through [`sov_bank`], so the same tokens can't be counted twice. A proposal
+//! approved by a quorum is executed once its timelock expires, which gives the users who disagree
+//! with it time to react before it takes effect.
+//!
+//! Other modules read the governed parameters with [`Governance::get_parameter`]: the sequencer
+//! registry reads the bond of the sequencers, and the EVM reads its block gas limit and its fee
+//! constants.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The value of a governed parameter.
+pub type ParameterValue = u64;
+
+/// The minimum bond of the sequencers, read by `sov-sequencer-registry` instead of the amount of
+/// its `coins_to_lock`.
+pub const SEQUENCER_BOND: &str = "sequencer_bond";
+/// The gas limit of the EVM blocks, read by `sov-evm` instead of its `block_gas_limit`.
+pub const BLOCK_GAS_LIMIT: &str = "block_gas_limit";
+/// The minimum base fee of the EVM blocks, read by `sov-evm` instead of its `min_base_fee`.
+pub const MIN_BASE_FEE: &str = "min_base_fee";
+/// The price of a unit of L1 gas, read by `sov-evm` instead of its `l1_gas_price`.
+pub const L1_GAS_PRICE: &str = "l1_gas_price";
+/// The L1 gas spent to post a transaction besides its bytes, read by `sov-evm` instead of its `l1_fee_overhead`.
+pub const L1_FEE_OVERHEAD: &str = "l1_fee_overhead";
+/// The scalar of the L1 fee, read by `sov-evm` instead of its `l1_fee_scalar`.
+pub const L1_FEE_SCALAR: &str = "l1_fee_scalar";
+
+/// The rules of the votes on proposals, set at genesis.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct VotingRules<C: sov_modules_api::Context> {
+    /// The address of the token weighting the votes.
+    pub governance_token: C::Address,
+    /// The balance of governance tokens an account needs to submit a proposal.
+    pub proposal_threshold: Amount,
+    /// The amount of governance tokens which must vote on a proposal for it to pass.
+    pub quorum: Amount,
+    /// The number of slots during which a proposal can be voted on.
+    pub voting_period: u64,
+    /// The number of slots between the end of the vote on an approved proposal and its execution.
+    pub timelock: u64,
+}
+
+/// A proposal to change the value of a governed parameter.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct Proposal<C: sov_modules_api::Context> {
+    /// The account which submitted the proposal.
+    pub proposer: C::Address,
+    /// The name of the governed parameter.
+    pub parameter: String,
+    /// The proposed value of the parameter.
+    pub value: ParameterValue,
+    /// The slot height from which the proposal can't be voted on anymore.
+    pub voting_end_height: u64,
+    /// The slot height from which the proposal can be executed, if it was approved.
+    pub execution_height: u64,
+    /// The governance tokens voting for the proposal.
+    pub votes_for: Amount,
+    /// The governance tokens voting against the proposal.
+    pub votes_against: Amount,
+    /// Whether the proposal was executed.
+    pub executed: bool,
+}
+
+/// The stage of a [`Proposal`] at a given slot height.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    /// The proposal can be voted on.
+    Voting,
+    /// The proposal didn't reach the quorum, or wasn't approved by a majority.
+    Rejected,
+    /// The proposal was approved, and is waiting for its timelock to expire.
+    Queued,
+    /// The proposal was approved, and can be executed.
+    Executable,
+    /// The proposal was executed.
+    Executed,
+}
+
+impl<C: sov_modules_api::Context> Proposal<C> {
+    /// Whether the votes on the proposal reached `quorum`, with a majority voting for it.
+    pub fn is_approved(&self, quorum: Amount) -> bool {
+        let turnout = self.votes_for as u128 + self.votes_against as u128;
+        turnout >= quorum as u128 && self.votes_for > self.votes_against
+    }
+
+    /// The stage of the proposal at `slot_height`.
+    pub fn status_at(&self, slot_height: u64, quorum: Amount) -> ProposalStatus {
+        if self.executed {
+            ProposalStatus::Executed
+        } else if slot_height < self.voting_end_height {
+            ProposalStatus::Voting
+        } else if !self.is_approved(quorum) {
+            ProposalStatus::Rejected
+        } else if slot_height < self.execution_height {
+            ProposalStatus::Queued
+        } else {
+            ProposalStatus::Executable
+        }
+    }
+}
+
+/// A vote on a proposal, backed by the governance tokens locked by the voter.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+pub struct Vote {
+    /// Whether the voter approves the proposal.
+    pub approve: bool,
+    /// The amount of governance tokens locked by the voter.
+    pub amount: Amount,
+}
+
+/// The `sov-governance` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct Governance<C: sov_modules_api::Context> {
+    /// The address of the `sov_governance` module, which holds the tokens locked by voters.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+
+    /// The rules of the votes on proposals.
+    #[state]
+    pub(crate) voting_rules: StateValue<VotingRules<C>>,
+
+    /// Parameter name => its current value. Only the parameters set at genesis can be governed.
+    #[state]
+    pub(crate) parameters: StateMap<String, ParameterValue>,
+
+    /// The identifier of the next proposal.
+    #[state]
+    pub(crate) next_proposal_id: StateValue<u64>,
+
+    /// Proposal identifier => the proposal.
+    #[state]
+    pub(crate) proposals: StateMap<u64, Proposal<C>>,
+
+    /// (Proposal identifier, voter address) => the vote whose tokens weren't withdrawn yet.
+    #[state]
+    pub(crate) votes: StateMap<(u64, C::Address), Vote>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Governance<C> {
+    type Context = C;
+
+    type Config = GovernanceConfig<C>;
+
+    type CallMessage = CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::Propose { parameter, value } => {
+                self.propose(parameter, value, context, working_set)?
+            }
+            CallMessage::Vote {
+                proposal_id,
+                approve,
+                amount,
+            } => self.vote(proposal_id, Vote { approve, amount }, context, working_set)?,
+            CallMessage::Execute { proposal_id } => {
+                self.execute(proposal_id, context, working_set)?
+            }
+            CallMessage::WithdrawVote { proposal_id } => {
+                self.withdraw_vote(proposal_id, context, working_set)?
+            }
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Governance<C> {
+    /// Returns the current value of the governed parameter `name`, or [`None`] if it isn't governed.
+    /// This is how other modules read the parameters changed by proposals, such as
+    /// [`SEQUENCER_BOND`]. The parameters which aren't governed keep the values of the genesis
+    /// config of the modules reading them.
+    pub fn get_parameter(
+        &self,
+        name: &str,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<ParameterValue> {
+        self.parameters.get(&name.to_owned(), working_set)
+    }
+
+    /// Returns the current value of the governed parameter `name`, or `default` if it isn't governed.
+    pub fn get_parameter_or(
+        &self,
+        name: &str,
+        default: ParameterValue,
+        working_set: &mut WorkingSet<C>,
+    ) -> ParameterValue {
+        self.get_parameter(name, working_set).unwrap_or(default)
+    }
+
+    /// Returns the rules of the votes on proposals.
+    pub fn get_voting_rules(&self, working_set: &mut WorkingSet<C>) -> Option<VotingRules<C>> {
+        self.voting_rules.get(working_set)
+    }
+
+    /// Returns the proposal with the given identifier, if it exists.
+    pub fn get_proposal(
+        &self,
+        proposal_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Proposal<C>> {
+        self.proposals.get(&proposal_id, working_set)
+    }
+
+    /// Returns the vote of `voter` on the given proposal, if its tokens weren't withdrawn yet.
+    pub fn get_vote(
+        &self,
+        proposal_id: u64,
+        voter: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Vote> {
+        self.votes.get(&(proposal_id, voter), working_set)
+    }
+}
@@ -0,0 +1,54 @@
+//! Defines rpc queries exposed by the governance module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Governance, ParameterValue, Proposal, VotingRules};
+
+/// The response type to the `getParameter` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct ParameterResponse {
+    /// The current value of the requested parameter, if it's governed.
+    pub value: Option<ParameterValue>,
+}
+
+/// The response type to the `getProposal` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct ProposalResponse<C: Context> {
+    /// The requested proposal, if it exists.
+    pub proposal: Option<Proposal<C>>,
+}
+
+#[rpc_gen(client, server, namespace = "governance")]
+impl<C: Context> Governance<C> {
+    /// Returns the current value of the given governed parameter.
+    #[rpc_method(name = "getParameter")]
+    pub fn parameter(
+        &self,
+        name: String,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ParameterResponse> {
+        Ok(ParameterResponse {
+            value: self.get_parameter(&name, working_set),
+        })
+    }
+
+    /// Returns the proposal with the given identifier.
+    #[rpc_method(name = "getProposal")]
+    pub fn proposal_by_id(
+        &self,
+        proposal_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ProposalResponse<C>> {
+        Ok(ProposalResponse {
+            proposal: self.get_proposal(proposal_id, working_set),
+        })
+    }
+
+    /// Returns the rules of the votes on proposals.
+    #[rpc_method(name = "getVotingRules")]
+    pub fn rules(&self, working_set: &mut WorkingSet<C>) -> RpcResult<Option<VotingRules<C>>> {
+        Ok(self.get_voting_rules(working_set))
+    }
+}
@@ -0,0 +1,280 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig};
+use sov_governance::{
+    CallMessage, Governance, GovernanceConfig, GovernedParameter, ProposalStatus, Vote, VotingRules,
+};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Error, Module, ModuleInfo, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+const PROPOSAL_HEIGHT: u64 = 10;
+const VOTING_PERIOD: u64 = 10;
+const TIMELOCK: u64 = 5;
+const QUORUM: u64 = 300;
+
+struct TestSetup {
+    governance: Governance<C>,
+    bank: Bank<C>,
+    token: <C as Spec>::Address,
+    alice: <C as Spec>::Address,
+    bob: <C as Spec>::Address,
+    sequencer: <C as Spec>::Address,
+}
+
+impl TestSetup {
+    fn init(working_set: &mut WorkingSet<C>) -> Self {
+        let governance = Governance::<C>::default();
+        let bank = Bank::<C>::default();
+        let alice = generate_address::<C>("alice");
+        let bob = generate_address::<C>("bob");
+        let sequencer = generate_address::<C>("sequencer");
+
+        let bank_config =
+            BankConfig::with_token("sov-governance-token", vec![(alice, 1000), (bob, 50)]);
+        bank.genesis(&bank_config, working_set).unwrap();
+        let token = get_genesis_token_address::<C>("sov-governance-token", 0);
+
+        let governance_config = GovernanceConfig {
+            voting_rules: VotingRules {
+                governance_token: token,
+                proposal_threshold: 100,
+                quorum: QUORUM,
+                voting_period: VOTING_PERIOD,
+                timelock: TIMELOCK,
+            },
+            parameters: vec![GovernedParameter {
+                name: "sequencer_bond".to_owned(),
+                value: 50,
+            }],
+        };
+        governance.genesis(&governance_config, working_set).unwrap();
+
+        Self {
+            governance,
+            bank,
+            token,
+            alice,
+            bob,
+            sequencer,
+        }
+    }
+
+    fn call(
+        &self,
+        sender: <C as Spec>::Address,
+        slot_height: u64,
+        message: CallMessage,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<(), Error> {
+        let context = C::new(sender, self.sequencer, slot_height);
+        self.governance
+            .call(message, &context, working_set)
+            .map(|_| ())
+    }
+
+    fn balance(&self, account: <C as Spec>::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.bank.balance_or_zero(account, self.token, working_set)
+    }
+
+    fn propose_bond(&self, value: u64, working_set: &mut WorkingSet<C>) {
+        self.call(
+            self.alice,
+            PROPOSAL_HEIGHT,
+            CallMessage::Propose {
+                parameter: "sequencer_bond".to_owned(),
+                value,
+            },
+            working_set,
+        )
+        .expect("Propose call failed");
+    }
+}
+
+#[test]
+fn approved_proposal_is_executed_after_its_timelock() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    setup.propose_bond(100, working_set);
+
+    let vote = |approve, amount| CallMessage::Vote {
+        proposal_id: 0,
+        approve,
+        amount,
+    };
+    setup
+        .call(
+            setup.alice,
+            PROPOSAL_HEIGHT + 1,
+            vote(true, 400),
+            working_set,
+        )
+        .expect("Vote call failed");
+    setup
+        .call(setup.bob, PROPOSAL_HEIGHT + 2, vote(false, 50), working_set)
+        .expect("Vote call failed");
+    // The voting tokens are locked in the module
+    assert_eq!(600, setup.balance(setup.alice, working_set));
+    assert_eq!(450, setup.balance(*setup.governance.address(), working_set));
+    assert_eq!(
+        Some(Vote {
+            approve: true,
+            amount: 400
+        }),
+        setup.governance.get_vote(0, setup.alice, working_set)
+    );
+
+    // Every account votes once
+    let Error::InvariantViolation(_) = setup
+        .call(
+            setup.alice,
+            PROPOSAL_HEIGHT + 3,
+            vote(true, 100),
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Expected an invariant violation");
+    };
+
+    let voting_end_height = PROPOSAL_HEIGHT + VOTING_PERIOD;
+    let execution_height = voting_end_height + TIMELOCK;
+    let proposal = setup.governance.get_proposal(0, working_set).unwrap();
+    assert_eq!((400, 50), (proposal.votes_for, proposal.votes_against));
+    assert_eq!(voting_end_height, proposal.voting_end_height);
+    assert_eq!(
+        ProposalStatus::Voting,
+        proposal.status_at(voting_end_height - 1, QUORUM)
+    );
+    assert_eq!(
+        ProposalStatus::Queued,
+        proposal.status_at(voting_end_height, QUORUM)
+    );
+
+    // Votes and executions are only accepted in their own period
+    assert!(setup
+        .call(setup.bob, voting_end_height, vote(true, 10), working_set)
+        .is_err());
+    let execute = CallMessage::Execute { proposal_id: 0 };
+    assert!(setup
+        .call(
+            setup.bob,
+            execution_height - 1,
+            execute.clone(),
+            working_set
+        )
+        .is_err());
+    assert_eq!(
+        Some(50),
+        setup
+            .governance
+            .get_parameter("sequencer_bond", working_set)
+    );
+
+    setup
+        .call(setup.bob, execution_height, execute.clone(), working_set)
+        .expect("Execute call failed");
+    assert_eq!(
+        Some(100),
+        setup
+            .governance
+            .get_parameter("sequencer_bond", working_set)
+    );
+    assert!(setup
+        .call(setup.bob, execution_height, execute, working_set)
+        .is_err());
+
+    // The voters get their tokens back
+    let withdraw = CallMessage::WithdrawVote { proposal_id: 0 };
+    setup
+        .call(setup.alice, execution_height, withdraw.clone(), working_set)
+        .expect("WithdrawVote call failed");
+    assert_eq!(1000, setup.balance(setup.alice, working_set));
+    assert!(setup
+        .call(setup.alice, execution_height, withdraw, working_set)
+        .is_err());
+}
+
+#[test]
+fn rejected_proposals_are_not_executed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+
+    // Only the governed parameters can be proposed, by the holders of enough tokens
+    let Error::NotFound(_) = setup
+        .call(
+            setup.alice,
+            PROPOSAL_HEIGHT,
+            CallMessage::Propose {
+                parameter: "unknown".to_owned(),
+                value: 1,
+            },
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Expected a not found error");
+    };
+    let Error::InsufficientFunds(_) = setup
+        .call(
+            setup.bob,
+            PROPOSAL_HEIGHT,
+            CallMessage::Propose {
+                parameter: "sequencer_bond".to_owned(),
+                value: 1,
+            },
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Expected an insufficient funds error");
+    };
+
+    // A proposal approved below the quorum is rejected
+    setup.propose_bond(100, working_set);
+    setup
+        .call(
+            setup.alice,
+            PROPOSAL_HEIGHT,
+            CallMessage::Vote {
+                proposal_id: 0,
+                approve: true,
+                amount: QUORUM - 1,
+            },
+            working_set,
+        )
+        .expect("Vote call failed");
+    // The tokens stay locked during the vote
+    assert!(setup
+        .call(
+            setup.alice,
+            PROPOSAL_HEIGHT + 1,
+            CallMessage::WithdrawVote { proposal_id: 0 },
+            working_set,
+        )
+        .is_err());
+
+    let execution_height = PROPOSAL_HEIGHT + VOTING_PERIOD + TIMELOCK;
+    let proposal = setup.governance.get_proposal(0, working_set).unwrap();
+    assert_eq!(
+        ProposalStatus::Rejected,
+        proposal.status_at(execution_height, QUORUM)
+    );
+    assert!(setup
+        .call(
+            setup.alice,
+            execution_height,
+            CallMessage::Execute { proposal_id: 0 },
+            working_set,
+        )
+        .is_err());
+    assert_eq!(
+        50,
+        setup
+            .governance
+            .get_parameter_or("sequencer_bond", 0, working_set)
+    );
+}
@@ -0,0 +1,220 @@
+use anyhow::{bail, Context as _, Result};
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{
+    CallResponse, Context, DeferredCall, EventEmitter, ModuleErrorCode, WorkingSet,
+};
+
+use crate::{Multisig, MultisigAccount, MultisigProposal};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-multisig` module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Creates a multisig account. Its address is returned by [`Multisig::account_address`].
+    CreateAccount {
+        /// The owners of the account.
+        owners: Vec<C::Address>,
+        /// The number of owners which must approve a proposal before it's executed.
+        threshold: u32,
+    },
+    /// Proposes a call message of the runtime, to be sent by a multisig account.
+    /// The sender must be an owner of the account, and approves the proposal.
+    Propose {
+        /// The identifier of the multisig account.
+        account_id: u64,
+        /// The encoded call message of the runtime.
+        message: Vec<u8>,
+    },
+    /// Approves a proposal. The sender must be an owner of its account.
+    Approve {
+        /// The identifier of the proposal.
+        proposal_id: u64,
+    },
+    /// Executes a proposal approved by the threshold of owners of its account.
+    /// The sender must be an owner of the account. The call message of the proposal is dispatched
+    /// by the runtime on behalf of the account, and the execution fails if the call fails.
+    Execute {
+        /// The identifier of the proposal.
+        proposal_id: u64,
+    },
+}
+
+impl<C: Context> Multisig<C> {
+    fn account(
+        &self,
+        account_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<MultisigAccount<C>> {
+        self.accounts.get(&account_id, working_set).ok_or_else(|| {
+            ModuleErrorCode::NotFound
+                .error(format!("Multisig account {} doesn't exist", account_id))
+        })
+    }
+
+    fn proposal(
+        &self,
+        proposal_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<MultisigProposal<C>> {
+        self.proposals
+            .get(&proposal_id, working_set)
+            .ok_or_else(|| {
+                ModuleErrorCode::NotFound.error(format!("Proposal {} doesn't exist", proposal_id))
+            })
+    }
+
+    fn ensure_owner(
+        account_id: u64,
+        account: &MultisigAccount<C>,
+        sender: &C::Address,
+    ) -> Result<()> {
+        if !account.owners.contains(sender) {
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
+                "Account {} isn't an owner of multisig account {}",
+                sender, account_id
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn create_account(
+        &self,
+        account: MultisigAccount<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        if account.threshold == 0 || account.threshold as usize > account.owners.len() {
+            bail!(
+                "The threshold of a multisig account must be between 1 and its {} owners, got {}",
+                account.owners.len(),
+                account.threshold
+            );
+        }
+        for (i, owner) in account.owners.iter().enumerate() {
+            if account.owners[..i].contains(owner) {
+                bail!("Owner {} is listed twice", owner);
+            }
+        }
+
+        let account_id = self.next_account_id.get(working_set).unwrap_or_default();
+        working_set.emit_event(
+            self,
+            "create_account",
+            &format!(
+                "multisig account {}: {}",
+                account_id,
+                self.account_address(account_id)
+            ),
+        );
+        self.accounts.set(&account_id, &account, working_set);
+        self.next_account_id.set(&(account_id + 1), working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn propose(
+        &self,
+        account_id: u64,
+        message: Vec<u8>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let account = self.account(account_id, working_set)?;
+        Self::ensure_owner(account_id, &account, context.sender())?;
+
+        let proposal_id = self.next_proposal_id.get(working_set).unwrap_or_default();
+        let proposal = MultisigProposal {
+            account_id,
+            message,
+            approvals: vec![context.sender().clone()],
+            executed: false,
+        };
+        self.proposals.set(&proposal_id, &proposal, working_set);
+        self.next_proposal_id.set(&(proposal_id + 1), working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn approve(
+        &self,
+        proposal_id: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let mut proposal = self.proposal(proposal_id, working_set)?;
+        let account = self.account(proposal.account_id, working_set)?;
+        Self::ensure_owner(proposal.account_id, &account, context.sender())?;
+        if proposal.executed {
+            return Err(ModuleErrorCode::InvariantViolation
+                .error(format!("Proposal {} was already executed", proposal_id)));
+        }
+        if proposal.approvals.contains(context.sender()) {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Account {} already approved proposal {}",
+                context.sender(),
+                proposal_id
+            )));
+        }
+
+        proposal.approvals.push(context.sender().clone());
+        self.proposals.set(&proposal_id, &proposal, working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn execute(
+        &self,
+        proposal_id: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let mut proposal = self.proposal(proposal_id, working_set)?;
+        let account = self.account(proposal.account_id, working_set)?;
+        Self::ensure_owner(proposal.account_id, &account, context.sender())?;
+        if proposal.executed {
+            return Err(ModuleErrorCode::InvariantViolation
+                .error(format!("Proposal {} was already executed", proposal_id)));
+        }
+        let approvals = u32::try_from(proposal.approvals.len())
+            .context("The proposal has too many approvals")?;
+        if approvals < account.threshold {
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
+                "Proposal {} has {} approvals, but {} are required",
+                proposal_id, approvals, account.threshold
+            )));
+        }
+
+        // The proposal is only marked as executed if its call succeeds, since otherwise
+        // the whole transaction is reverted
+        proposal.executed = true;
+        self.proposals.set(&proposal_id, &proposal, working_set);
+        working_set.emit_event(
+            self,
+            "execute",
+            &format!(
+                "proposal {} of multisig account {}",
+                proposal_id, proposal.account_id
+            ),
+        );
+
+        Ok(CallResponse {
+            deferred_calls: vec![DeferredCall {
+                salt: proposal.account_id.to_le_bytes().to_vec(),
+                message: proposal.message,
+            }],
+        })
+    }
+}
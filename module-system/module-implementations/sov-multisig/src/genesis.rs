@@ -0,0 +1,65 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Multisig, MultisigAccount};
+
+/// Genesis configuration for the [`Multisig`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct MultisigConfig<C: Context> {
+    /// The multisig accounts created at genesis, like the treasury of the rollup.
+    /// Their identifiers are their positions in the list.
+    #[serde(default)]
+    pub accounts: Vec<MultisigAccount<C>>,
+}
+
+impl<C: Context> Multisig<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        for account in &config.accounts {
+            self.create_account(account.clone(), working_set)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::utils::generate_address;
+
+    use crate::{MultisigAccount, MultisigConfig};
+
+    #[test]
+    fn test_config_serialization() {
+        let config = MultisigConfig::<DefaultContext> {
+            accounts: vec![MultisigAccount {
+                owners: vec![
+                    generate_address::<DefaultContext>("alice"),
+                    generate_address::<DefaultContext>("bob"),
+                ],
+                threshold: 2,
+            }],
+        };
+
+        let data = format!(
+            r#"
+        {{
+            "accounts":[
+                {{"owners":["{}","{}"],"threshold":2}}
+            ]
+        }}"#,
+            config.accounts[0].owners[0], config.accounts[0].owners[1]
+        );
+
+        let parsed_config: MultisigConfig<DefaultContext> = serde_json::from_str(&data).unwrap();
+        assert_eq!(config, parsed_config)
+    }
+}
@@ -0,0 +1,154 @@
+//! The `sov-multisig` module manages accounts controlled by a threshold of owners, like the
+//! treasuries of the rollup.
+//!
+//! The owners of a multisig account propose call messages of the runtime, and approve them. Once a
+//! proposal gathers the approvals of the threshold of owners, it is executed: the runtime dispatches
+//! its call message as if it was sent by the multisig account, through a
+//! [`DeferredCall`](sov_modules_api::DeferredCall). The address of every multisig account is
+//! derived from the address of the module, so it can't send calls any other way.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The owners of a multisig account, and how many of them must approve its calls.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct MultisigAccount<C: sov_modules_api::Context> {
+    /// The owners of the account.
+    pub owners: Vec<C::Address>,
+    /// The number of owners which must approve a proposal before it's executed.
+    pub threshold: u32,
+}
+
+/// A call message proposed by an owner of a multisig account.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct MultisigProposal<C: sov_modules_api::Context> {
+    /// The identifier of the multisig account sending the call.
+    pub account_id: u64,
+    /// The encoded call message of the runtime.
+    pub message: Vec<u8>,
+    /// The owners who approved the proposal, starting with the one who proposed it.
+    pub approvals: Vec<C::Address>,
+    /// Whether the proposal was executed.
+    pub executed: bool,
+}
+
+/// The `sov-multisig` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct Multisig<C: sov_modules_api::Context> {
+    /// The address of the `sov_multisig` module, which the addresses of the multisig accounts
+    /// are derived from.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The identifier of the next multisig account.
+    #[state]
+    pub(crate) next_account_id: StateValue<u64>,
+
+    /// Account identifier => the multisig account.
+    #[state]
+    pub(crate) accounts: StateMap<u64, MultisigAccount<C>>,
+
+    /// The identifier of the next proposal.
+    #[state]
+    pub(crate) next_proposal_id: StateValue<u64>,
+
+    /// Proposal identifier => the proposal.
+    #[state]
+    pub(crate) proposals: StateMap<u64, MultisigProposal<C>>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Multisig<C> {
+    type Context = C;
+
+    type Config = MultisigConfig<C>;
+
+    type CallMessage = CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::CreateAccount { owners, threshold } => {
+                self.create_account(MultisigAccount { owners, threshold }, working_set)?
+            }
+            CallMessage::Propose {
+                account_id,
+                message,
+            } => self.propose(account_id, message, context, working_set)?,
+            CallMessage::Approve { proposal_id } => {
+                self.approve(proposal_id, context, working_set)?
+            }
+            CallMessage::Execute { proposal_id } => {
+                self.execute(proposal_id, context, working_set)?
+            }
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Multisig<C> {
+    /// Returns the address of the multisig account with the given identifier,
+    /// which is the sender of the calls it executes.
+    pub fn account_address(&self, account_id: u64) -> C::Address {
+        sov_modules_api::derived_account_address::<C>(&self.address, &account_id.to_le_bytes())
+    }
+
+    /// Returns the multisig account with the given identifier, if it exists.
+    pub fn get_account(
+        &self,
+        account_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<MultisigAccount<C>> {
+        self.accounts.get(&account_id, working_set)
+    }
+
+    /// Returns the proposal with the given identifier, if it exists.
+    pub fn get_proposal(
+        &self,
+        proposal_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<MultisigProposal<C>> {
+        self.proposals.get(&proposal_id, working_set)
+    }
+}
@@ -0,0 +1,52 @@
+//! Defines rpc queries exposed by the multisig module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Multisig, MultisigAccount, MultisigProposal};
+
+/// The response type to the `getAccount` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct AccountResponse<C: Context> {
+    /// The address of the requested account, which sends the calls it executes.
+    pub address: C::Address,
+    /// The requested account, if it exists.
+    pub account: Option<MultisigAccount<C>>,
+}
+
+/// The response type to the `getProposal` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct ProposalResponse<C: Context> {
+    /// The requested proposal, if it exists.
+    pub proposal: Option<MultisigProposal<C>>,
+}
+
+#[rpc_gen(client, server, namespace = "multisig")]
+impl<C: Context> Multisig<C> {
+    /// Returns the multisig account with the given identifier, and its address.
+    #[rpc_method(name = "getAccount")]
+    pub fn account_by_id(
+        &self,
+        account_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AccountResponse<C>> {
+        Ok(AccountResponse {
+            address: self.account_address(account_id),
+            account: self.get_account(account_id, working_set),
+        })
+    }
+
+    /// Returns the proposal with the given identifier.
+    #[rpc_method(name = "getProposal")]
+    pub fn proposal_by_id(
+        &self,
+        proposal_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ProposalResponse<C>> {
+        Ok(ProposalResponse {
+            proposal: self.get_proposal(proposal_id, working_set),
+        })
+    }
+}
@@ -0,0 +1,229 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    CallResponse, Context, DispatchCall, EncodeCall, Error, Genesis, MessageCodec, Spec, WorkingSet,
+};
+use sov_multisig::{CallMessage, Multisig, MultisigAccount, MultisigConfig};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    pub bank: Bank<C>,
+    pub multisig: Multisig<C>,
+}
+
+const TREASURY_BALANCE: u64 = 1000;
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    token: <C as Spec>::Address,
+    treasury: <C as Spec>::Address,
+    alice: <C as Spec>::Address,
+    bob: <C as Spec>::Address,
+    carol: <C as Spec>::Address,
+    sequencer: <C as Spec>::Address,
+}
+
+impl TestSetup {
+    fn init(working_set: &mut WorkingSet<C>) -> Self {
+        let runtime = TestRuntime::<C>::default();
+        let alice = generate_address::<C>("alice");
+        let bob = generate_address::<C>("bob");
+        let carol = generate_address::<C>("carol");
+        let sequencer = generate_address::<C>("sequencer");
+        // The treasury is the first multisig account created at genesis
+        let treasury = runtime.multisig.account_address(0);
+
+        let bank_config =
+            BankConfig::with_token("sov-treasury-token", vec![(treasury, TREASURY_BALANCE)]);
+        let multisig_config = MultisigConfig {
+            accounts: vec![MultisigAccount {
+                owners: vec![alice, bob, carol],
+                threshold: 2,
+            }],
+        };
+        runtime
+            .genesis(
+                &GenesisConfig::new(bank_config, multisig_config),
+                working_set,
+            )
+            .unwrap();
+
+        Self {
+            runtime,
+            token: get_genesis_token_address::<C>("sov-treasury-token", 0),
+            treasury,
+            alice,
+            bob,
+            carol,
+            sequencer,
+        }
+    }
+
+    fn call(
+        &self,
+        sender: <C as Spec>::Address,
+        message: CallMessage<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message = <TestRuntime<C> as EncodeCall<Multisig<C>>>::encode_call(message);
+        let message = TestRuntime::<C>::decode_call(&message).unwrap();
+        let context = C::new(sender, self.sequencer, 1);
+        self.runtime
+            .dispatch_call_and_deferred(message, working_set, &context)
+    }
+
+    fn transfer_message(&self, to: <C as Spec>::Address, amount: u64) -> Vec<u8> {
+        <TestRuntime<C> as EncodeCall<Bank<C>>>::encode_call(sov_bank::CallMessage::Transfer {
+            to,
+            coins: Coins {
+                amount,
+                token_address: self.token,
+            },
+        })
+    }
+
+    fn balance(&self, account: <C as Spec>::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.runtime
+            .bank
+            .balance_or_zero(account, self.token, working_set)
+    }
+}
+
+#[test]
+fn approved_proposal_is_sent_by_the_multisig_account() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    let recipient = generate_address::<C>("recipient");
+
+    let propose = CallMessage::Propose {
+        account_id: 0,
+        message: setup.transfer_message(recipient, 100),
+    };
+    setup
+        .call(setup.alice, propose, working_set)
+        .expect("Propose call failed");
+    let execute = CallMessage::Execute { proposal_id: 0 };
+
+    // The proposer approves the proposal, but the threshold is two owners
+    let Error::Unauthorized(_) = setup
+        .call(setup.alice, execute.clone(), working_set)
+        .unwrap_err()
+    else {
+        panic!("Expected an unauthorized error");
+    };
+    let Error::Unauthorized(_) = setup
+        .call(
+            recipient,
+            CallMessage::Approve { proposal_id: 0 },
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Only the owners can approve");
+    };
+    let Error::InvariantViolation(_) = setup
+        .call(
+            setup.alice,
+            CallMessage::Approve { proposal_id: 0 },
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Expected an invariant violation");
+    };
+    setup
+        .call(
+            setup.bob,
+            CallMessage::Approve { proposal_id: 0 },
+            working_set,
+        )
+        .expect("Approve call failed");
+
+    setup
+        .call(setup.carol, execute.clone(), working_set)
+        .expect("Execute call failed");
+    assert_eq!(
+        TREASURY_BALANCE - 100,
+        setup.balance(setup.treasury, working_set)
+    );
+    assert_eq!(100, setup.balance(recipient, working_set));
+    let proposal = setup.runtime.multisig.get_proposal(0, working_set).unwrap();
+    assert!(proposal.executed);
+    assert_eq!(vec![setup.alice, setup.bob], proposal.approvals);
+
+    // Proposals are executed once
+    let Error::InvariantViolation(_) = setup.call(setup.alice, execute, working_set).unwrap_err()
+    else {
+        panic!("Expected an invariant violation");
+    };
+}
+
+#[test]
+fn failed_call_fails_the_execution() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+
+    // Only valid accounts can be created
+    for (owners, threshold) in [
+        (vec![setup.alice, setup.bob], 0),
+        (vec![setup.alice, setup.bob], 3),
+        (vec![setup.alice, setup.alice], 1),
+    ] {
+        assert!(setup
+            .call(
+                setup.alice,
+                CallMessage::CreateAccount { owners, threshold },
+                working_set,
+            )
+            .is_err());
+    }
+    let Error::NotFound(_) = setup
+        .call(
+            setup.alice,
+            CallMessage::Propose {
+                account_id: 1,
+                message: vec![],
+            },
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Expected a not found error");
+    };
+
+    // The treasury can't send more than its balance
+    let propose = CallMessage::Propose {
+        account_id: 0,
+        message: setup.transfer_message(setup.alice, TREASURY_BALANCE + 1),
+    };
+    setup
+        .call(setup.alice, propose, working_set)
+        .expect("Propose call failed");
+    setup
+        .call(
+            setup.bob,
+            CallMessage::Approve { proposal_id: 0 },
+            working_set,
+        )
+        .expect("Approve call failed");
+    let Error::InsufficientFunds(_) = setup
+        .call(
+            setup.bob,
+            CallMessage::Execute { proposal_id: 0 },
+            working_set,
+        )
+        .unwrap_err()
+    else {
+        panic!("Expected an insufficient funds error");
+    };
+    assert_eq!(TREASURY_BALANCE, setup.balance(setup.treasury, working_set));
+    assert_eq!(0, setup.balance(setup.alice, working_set));
+}
@@ -1,13 +1,16 @@
 use anyhow::Result;
-use sov_modules_api::{CallResponse, Context, StateMapAccessor, WorkingSet};
+use sov_modules_api::{CallResponse, Context, ModuleErrorCode, StateMapAccessor, WorkingSet};
 
 use crate::address::UserAddress;
-use crate::offchain::{update_collection, update_nft};
-use crate::{Collection, CollectionAddress, Nft, NftIdentifier, NonFungibleToken, TokenId};
+use crate::offchain::{delete_nft, update_collection, update_nft};
+use crate::{
+    Collection, CollectionAddress, Nft, NftIdentifier, NonFungibleToken, OwnerAddress, TokenId,
+};
 
 #[cfg_attr(
     feature = "native",
     derive(schemars::JsonSchema),
+    derive(sov_modules_api::macros::CallMessageBuilder),
     schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
 )]
 #[cfg_attr(
@@ -72,6 +75,31 @@ pub enum CallMessage<C: Context> {
         /// Target address of the user to transfer the NFT to
         to: UserAddress<C>,
     },
+    /// Burn an NFT, removing it from its collection.
+    /// Can be sent by the owner of the NFT, the address approved for it, or an operator of the owner
+    BurnNft {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the token to be burnt
+        token_id: TokenId,
+    },
+    /// Approve an address to transfer or burn an NFT on behalf of its owner.
+    /// The approval is cleared when the NFT is transferred
+    Approve {
+        /// Collection Address
+        collection_address: CollectionAddress<C>,
+        /// NFT id of the owned token
+        token_id: TokenId,
+        /// Address to approve. `None` revokes the existing approval
+        approved: Option<UserAddress<C>>,
+    },
+    /// Allow or disallow an operator to transfer, burn and approve all the NFTs of the sender
+    SetApprovalForAll {
+        /// Address of the operator
+        operator: UserAddress<C>,
+        /// Whether the operator is allowed or not
+        approved: bool,
+    },
 }
 
 impl<C: Context> NonFungibleToken<C> {
@@ -85,13 +113,13 @@ impl<C: Context> NonFungibleToken<C> {
         let (collection_address, collection) = Collection::new(
             collection_name,
             collection_uri,
+            context.sender(),
             &self.collections,
-            context,
             working_set,
         )?;
         self.collections
-            .set(&collection_address, &collection, working_set);
-        update_collection(&collection);
+            .set(&collection_address, collection.inner(), working_set);
+        update_collection(collection.inner());
         Ok(CallResponse::default())
     }
 
@@ -163,11 +191,9 @@ impl<C: Context> NonFungibleToken<C> {
             &self.nfts,
             working_set,
         )?;
-        self.nfts.set(
-            &NftIdentifier(token_id, collection_address.clone()),
-            &new_nft,
-            working_set,
-        );
+        let nft_identifier = NftIdentifier(token_id, collection_address.clone());
+        self.nfts.set(&nft_identifier, &new_nft, working_set);
+        self.add_owned_nft(new_nft.get_owner(), nft_identifier, working_set);
         collection.increment_supply();
         self.collections
             .set(&collection_address, collection.inner(), working_set);
@@ -186,19 +212,118 @@ impl<C: Context> NonFungibleToken<C> {
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse> {
-        let mut owned_nft =
-            Nft::get_owned_nft(nft_id, collection_address, &self.nfts, context, working_set)?;
+        let mut owned_nft = Nft::get_transferable_nft(
+            nft_id,
+            collection_address,
+            &self.nfts,
+            &self.approvals,
+            &self.operators,
+            context,
+            working_set,
+        )?;
+        let nft_identifier = NftIdentifier(nft_id, collection_address.clone());
         let original_owner = owned_nft.inner().get_owner().clone();
         owned_nft.set_owner(to);
-        self.nfts.set(
-            &NftIdentifier(nft_id, collection_address.clone()),
-            owned_nft.inner(),
-            working_set,
-        );
+        self.nfts
+            .set(&nft_identifier, owned_nft.inner(), working_set);
+        // The approval was given by the previous owner
+        self.approvals.delete(&nft_identifier, working_set);
+        self.remove_owned_nft(&original_owner, &nft_identifier, working_set);
+        self.add_owned_nft(owned_nft.inner().get_owner(), nft_identifier, working_set);
         update_nft(owned_nft.inner(), Some(original_owner.clone()));
         Ok(CallResponse::default())
     }
 
+    pub(crate) fn burn_nft(
+        &self,
+        nft_id: u64,
+        collection_address: &CollectionAddress<C>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let owned_nft = Nft::get_transferable_nft(
+            nft_id,
+            collection_address,
+            &self.nfts,
+            &self.approvals,
+            &self.operators,
+            context,
+            working_set,
+        )?;
+        let nft_identifier = NftIdentifier(nft_id, collection_address.clone());
+        let mut collection = self
+            .collections
+            .get(collection_address, working_set)
+            .ok_or_else(|| {
+                ModuleErrorCode::NotFound
+                    .error(format!("Collection {} does not exist", collection_address))
+            })?;
+
+        self.nfts.delete(&nft_identifier, working_set);
+        self.approvals.delete(&nft_identifier, working_set);
+        self.remove_owned_nft(owned_nft.inner().get_owner(), &nft_identifier, working_set);
+        collection.decrement_supply();
+        self.collections
+            .set(collection_address, &collection, working_set);
+
+        update_collection(&collection);
+        delete_nft(owned_nft.inner());
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn approve(
+        &self,
+        nft_id: u64,
+        collection_address: &CollectionAddress<C>,
+        approved: Option<&UserAddress<C>>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let nft = Nft::get_nft(nft_id, collection_address, &self.nfts, working_set)?;
+        let sender = UserAddress::new(context.sender());
+        let is_operator = self
+            .operators
+            .get(&(nft.get_owner().clone(), sender.clone()), working_set)
+            .unwrap_or(false);
+        if nft.get_owner().get_address() != sender.get_address() && !is_operator {
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
+                "user: {} is neither the owner nor an operator of the owner of nft: {} from collection address: {}",
+                sender,
+                nft_id,
+                collection_address
+            )));
+        }
+
+        let nft_identifier = NftIdentifier(nft_id, collection_address.clone());
+        match approved {
+            Some(approved) => self.approvals.set(&nft_identifier, approved, working_set),
+            None => self.approvals.delete(&nft_identifier, working_set),
+        }
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn set_approval_for_all(
+        &self,
+        operator: &UserAddress<C>,
+        approved: bool,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let owner = OwnerAddress::new(context.sender());
+        if owner.get_address() == operator.get_address() {
+            return Err(ModuleErrorCode::InvariantViolation
+                .error(format!("user: {} cannot be its own operator", owner)));
+        }
+
+        let key = (owner, operator.clone());
+        if approved {
+            self.operators.set(&key, &true, working_set);
+        } else {
+            self.operators.delete(&key, working_set);
+        }
+        Ok(CallResponse::default())
+    }
+
     pub(crate) fn update_nft(
         &self,
         collection_name: &str,
@@ -230,4 +355,30 @@ impl<C: Context> NonFungibleToken<C> {
         update_nft(mutable_nft.inner(), None);
         Ok(CallResponse::default())
     }
+
+    pub(crate) fn add_owned_nft(
+        &self,
+        owner: &OwnerAddress<C>,
+        nft_identifier: NftIdentifier<C>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut owned_nfts = self.owned_nfts.get(owner, working_set).unwrap_or_default();
+        owned_nfts.push(nft_identifier);
+        self.owned_nfts.set(owner, &owned_nfts, working_set);
+    }
+
+    fn remove_owned_nft(
+        &self,
+        owner: &OwnerAddress<C>,
+        nft_identifier: &NftIdentifier<C>,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut owned_nfts = self.owned_nfts.get(owner, working_set).unwrap_or_default();
+        owned_nfts.retain(|owned| owned != nft_identifier);
+        if owned_nfts.is_empty() {
+            self.owned_nfts.delete(owner, working_set);
+        } else {
+            self.owned_nfts.set(owner, &owned_nfts, working_set);
+        }
+    }
 }
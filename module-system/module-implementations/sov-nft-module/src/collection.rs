@@ -1,5 +1,5 @@
-use anyhow::{anyhow, bail, Context as _};
-use sov_modules_api::{Context, StateMap, StateMapAccessor, WorkingSet};
+use anyhow::Context as _;
+use sov_modules_api::{Context, ModuleErrorCode, StateMap, StateMapAccessor, WorkingSet};
 
 use crate::address::CollectionAddress;
 use crate::utils::get_collection_address;
@@ -24,8 +24,8 @@ pub struct Collection<C: Context> {
     /// cannot be minted and the supply is frozen
     frozen: bool,
     /// Supply of the collection. This is dynamic and changes
-    /// with the number of NFTs created. It stops changing
-    /// when frozen is set to true.
+    /// with the number of NFTs created and burnt. It stops
+    /// increasing when frozen is set to true.
     supply: u64,
     /// collection metadata stored at this url
     collection_uri: String,
@@ -39,11 +39,13 @@ pub enum CollectionState<C: Context> {
 impl<C: Context> CollectionState<C> {
     pub fn get_mutable_or_bail(&self) -> anyhow::Result<MutableCollection<C>> {
         match self {
-            CollectionState::Frozen(collection) => bail!(
-                "Collection with name: {} , creator: {} is frozen",
-                collection.get_name(),
-                collection.get_creator()
-            ),
+            CollectionState::Frozen(collection) => {
+                Err(ModuleErrorCode::InvariantViolation.error(format!(
+                    "Collection with name: {} , creator: {} is frozen",
+                    collection.get_name(),
+                    collection.get_creator()
+                )))
+            }
             CollectionState::Mutable(mut_collection) => Ok(mut_collection.clone()),
         }
     }
@@ -53,29 +55,27 @@ impl<C: Context> Collection<C> {
     pub fn new(
         collection_name: &str,
         collection_uri: &str,
+        creator: &C::Address,
         collections: &StateMap<CollectionAddress<C>, Collection<C>>,
-        context: &C,
         working_set: &mut WorkingSet<C>,
-    ) -> anyhow::Result<(CollectionAddress<C>, Collection<C>)> {
-        let creator = context.sender();
+    ) -> anyhow::Result<(CollectionAddress<C>, MutableCollection<C>)> {
         let collection_address = get_collection_address(collection_name, creator.as_ref());
         let collection = collections.get(&collection_address, working_set);
         if collection.is_some() {
-            Err(anyhow!(
+            Err(ModuleErrorCode::InvariantViolation.error(format!(
                 "Collection with name: {} already exists creator {}",
-                collection_name,
-                creator
-            ))
+                collection_name, creator
+            )))
         } else {
             Ok((
                 collection_address,
-                Collection {
+                MutableCollection(Collection {
                     name: collection_name.to_string(),
                     creator: CreatorAddress::new(creator),
                     frozen: false,
                     supply: 0,
                     collection_uri: collection_uri.to_string(),
-                },
+                }),
             ))
         }
     }
@@ -99,7 +99,7 @@ impl<C: Context> Collection<C> {
                 ))
             }
         } else {
-            Err(anyhow!("Collection not found")).with_context(|| {
+            Err(ModuleErrorCode::NotFound.error("Collection not found")).with_context(|| {
                 format!(
                     "Collection with name: {} does not exist for creator {}",
                     collection_name, creator
@@ -132,6 +132,12 @@ impl<C: Context> Collection<C> {
     pub fn get_collection_uri(&self) -> &str {
         &self.collection_uri
     }
+
+    /// NFTs can be burnt even if the collection is frozen, so the supply
+    /// is decremented regardless of the frozen status
+    pub fn decrement_supply(&mut self) {
+        self.supply = self.supply.saturating_sub(1);
+    }
 }
 
 // We use a NewType instead of &mut on the Collection because we don't want all
@@ -143,7 +149,7 @@ pub struct MutableCollection<C: Context>(Collection<C>);
 /// Member Functions to allow controlled mutability for the Collection struct
 /// Can only freeze. Cannot unfreeze
 /// Can modify collection_uri
-/// Can increment supply. Cannot decrement
+/// Can increment supply. Decrementing is only done by burning an NFT
 /// Cannot modify creator address
 /// Cannot modify name
 impl<C: Context> MutableCollection<C> {
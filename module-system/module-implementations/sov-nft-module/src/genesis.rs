@@ -1,24 +1,162 @@
+use anyhow::Result;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
-// no genesis for this module
+use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
+
+use crate::offchain::{update_collection, update_nft};
+use crate::{Collection, Nft, NftIdentifier, NonFungibleToken, TokenId, UserAddress};
 
 /// Config for the NonFungibleToken module.
-/// Sets admin and existing owners.
+/// Sets the collections and NFTs existing at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct NonFungibleTokenConfig<C: Context> {
+    /// Collections created at genesis.
+    #[serde(default)]
+    pub collections: Vec<CollectionConfig<C>>,
+}
+
+/// A collection created at genesis, along with its NFTs.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct CollectionConfig<C: Context> {
+    /// Name of the collection, unique in the scope of the creator.
+    pub name: String,
+    /// Creator of the collection, the only address that can mint new NFTs in it.
+    pub creator: C::Address,
+    /// Collection metadata uri.
+    pub collection_uri: String,
+    /// Whether the collection is frozen once its genesis NFTs are minted.
+    #[serde(default)]
+    pub frozen: bool,
+    /// NFTs of the collection minted at genesis.
+    #[serde(default)]
+    pub nfts: Vec<NftConfig<C>>,
+}
+
+/// An NFT minted at genesis.
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
-pub struct NonFungibleTokenConfig {}
+pub struct NftConfig<C: Context> {
+    /// Token id, unique in the scope of the collection.
+    pub token_id: TokenId,
+    /// URI pointing to the offchain metadata.
+    pub token_uri: String,
+    /// Owner of the NFT.
+    pub owner: C::Address,
+    /// A frozen NFT cannot have its token_uri modified.
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+impl<C: Context> NonFungibleToken<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        for collection_config in config.collections.iter() {
+            let (collection_address, mut collection) = Collection::new(
+                &collection_config.name,
+                &collection_config.collection_uri,
+                &collection_config.creator,
+                &self.collections,
+                working_set,
+            )?;
+
+            for nft_config in collection_config.nfts.iter() {
+                let owner = UserAddress::new(&nft_config.owner);
+                let nft = Nft::new(
+                    nft_config.token_id,
+                    &nft_config.token_uri,
+                    &owner,
+                    nft_config.frozen,
+                    &collection_address,
+                    &self.nfts,
+                    working_set,
+                )?;
+                let nft_identifier = NftIdentifier(nft_config.token_id, collection_address.clone());
+                self.nfts.set(&nft_identifier, &nft, working_set);
+                self.add_owned_nft(nft.get_owner(), nft_identifier, working_set);
+                collection.increment_supply();
+                update_nft(&nft, None);
+            }
+
+            if collection_config.frozen {
+                collection.freeze();
+            }
+            self.collections
+                .set(&collection_address, collection.inner(), working_set);
+            update_collection(collection.inner());
+        }
+        Ok(())
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::NonFungibleTokenConfig;
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::utils::generate_address;
+    use sov_modules_api::Spec;
+
+    use crate::{CollectionConfig, NftConfig, NonFungibleTokenConfig};
+
     #[test]
     fn test_config_serialization() {
-        let config = NonFungibleTokenConfig {};
+        let config = NonFungibleTokenConfig::<DefaultContext> {
+            collections: vec![],
+        };
+
+        let data = r#"
+        {
+
+        }"#;
+
+        let parsed_config: NonFungibleTokenConfig<DefaultContext> =
+            serde_json::from_str(data).unwrap();
+        assert_eq!(config, parsed_config)
+    }
+
+    #[test]
+    fn test_config_with_collections_serialization() {
+        let creator: <DefaultContext as Spec>::Address =
+            generate_address::<DefaultContext>("admin");
+        let owner: <DefaultContext as Spec>::Address = generate_address::<DefaultContext>("owner");
+        let config = NonFungibleTokenConfig::<DefaultContext> {
+            collections: vec![CollectionConfig {
+                name: "Test Collection".to_string(),
+                creator,
+                collection_uri: "http://foo.bar/test_collection".to_string(),
+                frozen: false,
+                nfts: vec![NftConfig {
+                    token_id: 42,
+                    token_uri: "http://foo.bar/test_collection/42".to_string(),
+                    owner,
+                    frozen: true,
+                }],
+            }],
+        };
 
         let data = r#"
         {
-        
+            "collections": [
+                {
+                    "name": "Test Collection",
+                    "creator": "sov1335hded4gyzpt00fpz75mms4m7ck02wgw07yhw9grahj4dzg4yvqk63pml",
+                    "collection_uri": "http://foo.bar/test_collection",
+                    "nfts": [
+                        {
+                            "token_id": 42,
+                            "token_uri": "http://foo.bar/test_collection/42",
+                            "owner": "sov1fsgzj6t7udv8zhf6zj32mkqhcjcpv52yph5qsdcl0qt94jgdckqsczjm2y",
+                            "frozen": true
+                        }
+                    ]
+                }
+            ]
         }"#;
 
-        let parsed_config: NonFungibleTokenConfig = serde_json::from_str(data).unwrap();
+        let parsed_config: NonFungibleTokenConfig<DefaultContext> =
+            serde_json::from_str(data).unwrap();
         assert_eq!(config, parsed_config)
     }
 }
@@ -11,6 +11,7 @@ mod collection;
 use collection::*;
 mod nft;
 use nft::*;
+pub use nft::{NftIdentifier, TokenId};
 #[cfg(feature = "native")]
 mod query;
 #[cfg(feature = "native")]
@@ -38,23 +39,31 @@ pub struct NonFungibleToken<C: Context> {
     #[state]
     /// Mapping of tokens to their owners
     nfts: StateMap<NftIdentifier<C>, Nft<C>>,
+
+    #[state]
+    /// Mapping of owners to the tokens they own, across all the collections
+    owned_nfts: StateMap<OwnerAddress<C>, Vec<NftIdentifier<C>>>,
+
+    #[state]
+    /// Mapping of tokens to the address approved to transfer them on behalf of their owner
+    approvals: StateMap<NftIdentifier<C>, UserAddress<C>>,
+
+    #[state]
+    /// Mapping of (owner, operator) to whether the operator can transfer all the tokens of the owner
+    operators: StateMap<(OwnerAddress<C>, UserAddress<C>), bool>,
 }
 
 impl<C: Context> Module for NonFungibleToken<C> {
     type Context = C;
 
-    type Config = NonFungibleTokenConfig;
+    type Config = NonFungibleTokenConfig<C>;
 
     type CallMessage = CallMessage<C>;
 
     type Event = ();
 
-    fn genesis(
-        &self,
-        _config: &Self::Config,
-        _working_set: &mut WorkingSet<C>,
-    ) -> Result<(), Error> {
-        Ok(())
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
     }
 
     fn call(
@@ -95,6 +104,24 @@ impl<C: Context> Module for NonFungibleToken<C> {
                 token_id,
                 to,
             } => self.transfer_nft(token_id, &collection_address, &to, context, working_set),
+            CallMessage::BurnNft {
+                collection_address,
+                token_id,
+            } => self.burn_nft(token_id, &collection_address, context, working_set),
+            CallMessage::Approve {
+                collection_address,
+                token_id,
+                approved,
+            } => self.approve(
+                token_id,
+                &collection_address,
+                approved.as_ref(),
+                context,
+                working_set,
+            ),
+            CallMessage::SetApprovalForAll { operator, approved } => {
+                self.set_approval_for_all(&operator, approved, context, working_set)
+            }
             CallMessage::UpdateNft {
                 collection_name,
                 token_id,
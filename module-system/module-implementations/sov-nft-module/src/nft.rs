@@ -1,5 +1,5 @@
-use anyhow::{anyhow, bail, Context as _};
-use sov_modules_api::{Context, StateMap, StateMapAccessor, WorkingSet};
+use anyhow::Context as _;
+use sov_modules_api::{Context, ModuleErrorCode, StateMap, StateMapAccessor, WorkingSet};
 
 use crate::collection::Collection;
 use crate::{CollectionAddress, OwnerAddress, UserAddress};
@@ -52,7 +52,7 @@ impl<C: Context> OwnedNft<C> {
         if nft.owner == sender {
             Ok(OwnedNft(nft))
         } else {
-            Err(anyhow!("NFT not owned by sender")).with_context(|| {
+            Err(ModuleErrorCode::Unauthorized.error("NFT not owned by sender")).with_context(|| {
                 format!(
                     "user: {} does not own nft: {} from collection address: {} , owner is: {}",
                     sender, nft.token_id, nft.collection_address, nft.owner
@@ -99,11 +99,10 @@ impl<C: Context> Nft<C> {
             )
             .is_some()
         {
-            bail!(
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
                 "NFT with id {} already exists for collection address {}",
-                token_id,
-                collection_address
-            )
+                token_id, collection_address
+            )));
         }
         Ok(Nft {
             token_id,
@@ -114,24 +113,46 @@ impl<C: Context> Nft<C> {
         })
     }
 
-    pub fn get_owned_nft(
+    pub fn get_nft(
         token_id: TokenId,
         collection_address: &CollectionAddress<C>,
         nfts: &StateMap<NftIdentifier<C>, Nft<C>>,
-        context: &C,
         working_set: &mut WorkingSet<C>,
-    ) -> anyhow::Result<OwnedNft<C>> {
+    ) -> anyhow::Result<Nft<C>> {
         let nft_identifier = NftIdentifier(token_id, collection_address.clone());
-        let nft = nfts
-            .get(&nft_identifier, working_set)
-            .ok_or_else(|| anyhow!("NFT not found"))
+        nfts.get(&nft_identifier, working_set)
+            .ok_or_else(|| ModuleErrorCode::NotFound.error("NFT not found"))
             .with_context(|| {
                 format!(
                     "Nft with token_id: {} in collection_address: {} does not exist",
                     token_id, collection_address
                 )
-            })?;
-        OwnedNft::new(nft, context)
+            })
+    }
+
+    /// Returns the NFT if the context sender can transfer or burn it: the sender has to be
+    /// the owner of the NFT, the address approved for it, or an operator of the owner.
+    pub fn get_transferable_nft(
+        token_id: TokenId,
+        collection_address: &CollectionAddress<C>,
+        nfts: &StateMap<NftIdentifier<C>, Nft<C>>,
+        approvals: &StateMap<NftIdentifier<C>, UserAddress<C>>,
+        operators: &StateMap<(OwnerAddress<C>, UserAddress<C>), bool>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<OwnedNft<C>> {
+        let nft = Self::get_nft(token_id, collection_address, nfts, working_set)?;
+        let sender = UserAddress::new(context.sender());
+        let nft_identifier = NftIdentifier(token_id, collection_address.clone());
+        let is_approved = approvals.get(&nft_identifier, working_set).as_ref() == Some(&sender);
+        let is_operator = operators
+            .get(&(nft.owner.clone(), sender), working_set)
+            .unwrap_or(false);
+        if is_approved || is_operator {
+            Ok(OwnedNft(nft))
+        } else {
+            OwnedNft::new(nft, context)
+        }
     }
 
     pub fn get_mutable_nft(
@@ -150,18 +171,16 @@ impl<C: Context> Nft<C> {
             if !nft.frozen {
                 Ok((collection_address, MutableNft(nft.clone())))
             } else {
-                bail!(
+                Err(ModuleErrorCode::InvariantViolation.error(format!(
                     "NFT with token id {} in collection address {} is frozen",
-                    token_id,
-                    token_identifier.1
-                )
+                    token_id, token_identifier.1
+                )))
             }
         } else {
-            bail!(
+            Err(ModuleErrorCode::NotFound.error(format!(
                 "Nft with token_id: {} in collection_address: {} does not exist",
-                token_id,
-                token_identifier.1
-            )
+                token_id, token_identifier.1
+            )))
         }
     }
 
@@ -5,6 +5,7 @@ use sov_modules_api::{Context, StateMapAccessor, WorkingSet};
 use crate::utils::get_collection_address;
 use crate::{
     CollectionAddress, CreatorAddress, NftIdentifier, NonFungibleToken, OwnerAddress, TokenId,
+    UserAddress,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
@@ -56,6 +57,39 @@ pub struct CollectionAddressResponse<C: Context> {
     pub collection_address: CollectionAddress<C>,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(bound(
+    serialize = "OwnerAddress<C>: serde::Serialize",
+    deserialize = "OwnerAddress<C>: serde::Deserialize<'de>"
+))]
+/// Response for `ownerOf` method
+pub struct OwnerResponse<C: Context> {
+    /// Owner of the NFT, if it exists
+    pub owner: Option<OwnerAddress<C>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(bound(
+    serialize = "NftIdentifier<C>: serde::Serialize",
+    deserialize = "NftIdentifier<C>: serde::Deserialize<'de>"
+))]
+/// Response for `tokensOf` method
+pub struct TokensResponse<C: Context> {
+    /// NFTs owned by the address, across all the collections
+    pub nfts: Vec<NftIdentifier<C>>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(bound(
+    serialize = "UserAddress<C>: serde::Serialize",
+    deserialize = "UserAddress<C>: serde::Deserialize<'de>"
+))]
+/// Response for `getApproved` method
+pub struct ApprovedResponse<C: Context> {
+    /// Address approved to transfer the NFT, if any
+    pub approved: Option<UserAddress<C>>,
+}
+
 #[rpc_gen(client, server, namespace = "nft")]
 impl<C: Context> NonFungibleToken<C> {
     #[rpc_method(name = "getCollection")]
@@ -109,4 +143,58 @@ impl<C: Context> NonFungibleToken<C> {
             collection_address: n.get_collection_address().clone(),
         })
     }
+
+    #[rpc_method(name = "ownerOf")]
+    /// Get the owner of an NFT
+    pub fn owner_of(
+        &self,
+        collection_address: CollectionAddress<C>,
+        token_id: TokenId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<OwnerResponse<C>> {
+        let nft_id = NftIdentifier(token_id, collection_address);
+        Ok(OwnerResponse {
+            owner: self
+                .nfts
+                .get(&nft_id, working_set)
+                .map(|n| n.get_owner().clone()),
+        })
+    }
+    #[rpc_method(name = "tokensOf")]
+    /// Get the NFTs owned by an address
+    pub fn tokens_of(
+        &self,
+        owner: OwnerAddress<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<TokensResponse<C>> {
+        Ok(TokensResponse {
+            nfts: self.owned_nfts.get(&owner, working_set).unwrap_or_default(),
+        })
+    }
+    #[rpc_method(name = "getApproved")]
+    /// Get the address approved to transfer an NFT
+    pub fn get_approved(
+        &self,
+        collection_address: CollectionAddress<C>,
+        token_id: TokenId,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<ApprovedResponse<C>> {
+        let nft_id = NftIdentifier(token_id, collection_address);
+        Ok(ApprovedResponse {
+            approved: self.approvals.get(&nft_id, working_set),
+        })
+    }
+    #[rpc_method(name = "isApprovedForAll")]
+    /// Check whether an operator can transfer all the NFTs of an owner
+    pub fn is_approved_for_all(
+        &self,
+        owner: OwnerAddress<C>,
+        operator: UserAddress<C>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<bool> {
+        Ok(self
+            .operators
+            .get(&(owner, operator), working_set)
+            .unwrap_or(false))
+    }
 }
@@ -28,3 +28,5 @@ pub const INSERT_OR_UPDATE_NFT: &str = "INSERT INTO nfts (\
         DO UPDATE SET metadata_url = EXCLUDED.metadata_url,\
                       owner = EXCLUDED.owner,\
                       frozen = EXCLUDED.frozen";
+
+pub const DELETE_NFT: &str = "DELETE FROM nfts WHERE collection_address = $1 AND nft_id = $2";
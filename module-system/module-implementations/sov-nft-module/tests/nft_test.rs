@@ -1,8 +1,11 @@
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
-use sov_modules_api::{Context, Module, WorkingSet};
+use sov_modules_api::{Context, Error, Module, WorkingSet};
 use sov_nft_module::utils::get_collection_address;
-use sov_nft_module::{CallMessage, NonFungibleToken, OwnerAddress, UserAddress};
+use sov_nft_module::{
+    CallMessage, CollectionConfig, NftConfig, NftIdentifier, NonFungibleToken,
+    NonFungibleTokenConfig, OwnerAddress, UserAddress,
+};
 use sov_prover_storage_manager::new_orphan_storage;
 use sov_state::DefaultStorageSpec;
 
@@ -109,7 +112,7 @@ fn mints_and_transfers() {
     let mint_response = nft.call(mint_nft_message, &creator_context, &mut working_set);
     if let Err(err) = mint_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::NotFound(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "Collection with name: {} does not exist for creator {}",
@@ -117,6 +120,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected a not found error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -161,7 +165,7 @@ fn mints_and_transfers() {
     );
     if let Err(err) = freeze_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::NotFound(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "Collection with name: {} does not exist for creator {}",
@@ -169,6 +173,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected a not found error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -209,7 +214,7 @@ fn mints_and_transfers() {
     );
     if let Err(err) = update_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::InvariantViolation(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "Collection with name: {} , creator: {} is frozen",
@@ -217,6 +222,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected an invariant violation error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -247,7 +253,7 @@ fn mints_and_transfers() {
     let mint_response = nft.call(mint_nft_message, &creator_context, &mut working_set);
     if let Err(err) = mint_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::InvariantViolation(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "Collection with name: {} , creator: {} is frozen",
@@ -255,6 +261,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected an invariant violation error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -279,7 +286,7 @@ fn mints_and_transfers() {
     let transfer_response = nft.call(transfer_nft_message, &creator_context, &mut working_set);
     if let Err(err) = transfer_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::Unauthorized(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "user: {} does not own nft: {} from collection address: {} , owner is: {}",
@@ -287,6 +294,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected an unauthorized error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -304,7 +312,7 @@ fn mints_and_transfers() {
     let transfer_response = nft.call(transfer_nft_message, &owner_context, &mut working_set);
     if let Err(err) = transfer_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::NotFound(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "Nft with token_id: {} in collection_address: {} does not exist",
@@ -312,6 +320,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected a not found error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -405,7 +414,7 @@ fn mints_and_transfers() {
     let update_response = nft.call(update_nft_message, &creator_context, &mut working_set);
     if let Err(err) = update_response {
         match err {
-            sov_modules_api::Error::ModuleError(anyhow_err) => {
+            sov_modules_api::Error::InvariantViolation(anyhow_err) => {
                 let err_message = anyhow_err.to_string();
                 let expected_message = format!(
                     "NFT with token id {} in collection address {} is frozen",
@@ -413,6 +422,7 @@ fn mints_and_transfers() {
                 );
                 assert_eq!(err_message, expected_message);
             }
+            err => panic!("Expected an invariant violation error, got {}", err),
         }
     } else {
         panic!("Expected an error, got Ok");
@@ -455,3 +465,263 @@ fn mints_and_transfers() {
     // ensure supply hasn't changed with a transfer
     assert_eq!(actual_collection.supply, 1);
 }
+
+#[test]
+fn approvals_and_burns() {
+    let creator_pk = DefaultPrivateKey::try_from(&PK1[..]).unwrap();
+    let owner_pk = DefaultPrivateKey::try_from(&PK2[..]).unwrap();
+    let spender_pk = DefaultPrivateKey::try_from(&PK3[..]).unwrap();
+    let sequencer_pk = DefaultPrivateKey::try_from(&PK4[..]).unwrap();
+
+    let creator_address = creator_pk.default_address();
+    let owner_address = owner_pk.default_address();
+    let spender_address = spender_pk.default_address();
+    let sequencer_address = sequencer_pk.default_address();
+    let collection_name = "Test Collection";
+    let collection_address =
+        get_collection_address::<DefaultContext>(collection_name, creator_address.as_ref());
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage::<DefaultStorageSpec>(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let nft = NonFungibleToken::default();
+
+    let creator_context = DefaultContext::new(creator_address, sequencer_address, 1);
+    let owner_context = DefaultContext::new(owner_address, sequencer_address, 1);
+    let spender_context = DefaultContext::new(spender_address, sequencer_address, 1);
+
+    nft.call(
+        CallMessage::CreateCollection {
+            name: collection_name.to_string(),
+            collection_uri: "http://foo.bar/test_collection".to_string(),
+        },
+        &creator_context,
+        &mut working_set,
+    )
+    .expect("Creating Collection failed");
+    for token_id in [1, 2] {
+        nft.call(
+            CallMessage::MintNft {
+                collection_name: collection_name.to_string(),
+                token_uri: format!("http://foo.bar/test_collection/{}", token_id),
+                token_id,
+                owner: UserAddress::new(&owner_address),
+                frozen: false,
+            },
+            &creator_context,
+            &mut working_set,
+        )
+        .expect("Minting NFT failed");
+    }
+
+    let owner = OwnerAddress::new(&owner_address);
+    let spender = UserAddress::new(&spender_address);
+    let tokens = nft.tokens_of(owner.clone(), &mut working_set).unwrap();
+    assert_eq!(
+        tokens.nfts,
+        vec![
+            NftIdentifier(1, collection_address.clone()),
+            NftIdentifier(2, collection_address.clone())
+        ]
+    );
+
+    let transfer_nft_message = |token_id| CallMessage::TransferNft {
+        collection_address: collection_address.clone(),
+        token_id,
+        to: UserAddress::new(&spender_address),
+    };
+
+    // The spender can't transfer the NFT before being approved
+    assert!(nft
+        .call(transfer_nft_message(1), &spender_context, &mut working_set)
+        .is_err_and(|err| matches!(err, Error::Unauthorized(_))));
+    // Only the owner can approve
+    assert!(nft
+        .call(
+            CallMessage::Approve {
+                collection_address: collection_address.clone(),
+                token_id: 1,
+                approved: Some(spender.clone()),
+            },
+            &spender_context,
+            &mut working_set
+        )
+        .is_err_and(|err| matches!(err, Error::Unauthorized(_))));
+
+    nft.call(
+        CallMessage::Approve {
+            collection_address: collection_address.clone(),
+            token_id: 1,
+            approved: Some(spender.clone()),
+        },
+        &owner_context,
+        &mut working_set,
+    )
+    .expect("Approve failed");
+    let approved = nft
+        .get_approved(collection_address.clone(), 1, &mut working_set)
+        .unwrap();
+    assert_eq!(approved.approved, Some(spender.clone()));
+
+    // The approval only covers a single NFT
+    assert!(nft
+        .call(transfer_nft_message(2), &spender_context, &mut working_set)
+        .is_err_and(|err| matches!(err, Error::Unauthorized(_))));
+    nft.call(transfer_nft_message(1), &spender_context, &mut working_set)
+        .expect("Transfer by the approved address failed");
+
+    let new_owner = OwnerAddress::new(&spender_address);
+    let owner_of = nft
+        .owner_of(collection_address.clone(), 1, &mut working_set)
+        .unwrap();
+    assert_eq!(owner_of.owner, Some(new_owner.clone()));
+    // The transfer clears the approval
+    let approved = nft
+        .get_approved(collection_address.clone(), 1, &mut working_set)
+        .unwrap();
+    assert_eq!(approved.approved, None);
+    assert_eq!(
+        nft.tokens_of(owner.clone(), &mut working_set).unwrap().nfts,
+        vec![NftIdentifier(2, collection_address.clone())]
+    );
+    assert_eq!(
+        nft.tokens_of(new_owner.clone(), &mut working_set)
+            .unwrap()
+            .nfts,
+        vec![NftIdentifier(1, collection_address.clone())]
+    );
+
+    // An operator can burn all the NFTs of the owner
+    nft.call(
+        CallMessage::SetApprovalForAll {
+            operator: spender.clone(),
+            approved: true,
+        },
+        &owner_context,
+        &mut working_set,
+    )
+    .expect("SetApprovalForAll failed");
+    assert!(nft
+        .is_approved_for_all(owner.clone(), spender.clone(), &mut working_set)
+        .unwrap());
+
+    nft.call(
+        CallMessage::BurnNft {
+            collection_address: collection_address.clone(),
+            token_id: 2,
+        },
+        &spender_context,
+        &mut working_set,
+    )
+    .expect("Burn by an operator failed");
+    let owner_of = nft
+        .owner_of(collection_address.clone(), 2, &mut working_set)
+        .unwrap();
+    assert_eq!(owner_of.owner, None);
+    assert!(nft
+        .tokens_of(owner.clone(), &mut working_set)
+        .unwrap()
+        .nfts
+        .is_empty());
+    let actual_collection = nft
+        .get_collection(collection_address.clone(), &mut working_set)
+        .unwrap();
+    assert_eq!(actual_collection.supply, 1);
+
+    // Once the operator is revoked, it can't touch the NFTs of the owner anymore
+    nft.call(
+        CallMessage::SetApprovalForAll {
+            operator: spender.clone(),
+            approved: false,
+        },
+        &owner_context,
+        &mut working_set,
+    )
+    .expect("SetApprovalForAll failed");
+    assert!(!nft
+        .is_approved_for_all(owner, spender, &mut working_set)
+        .unwrap());
+
+    // The previous owner can't burn the transferred NFT
+    let burn_nft_message = CallMessage::BurnNft {
+        collection_address: collection_address.clone(),
+        token_id: 1,
+    };
+    assert!(nft
+        .call(burn_nft_message.clone(), &owner_context, &mut working_set)
+        .is_err_and(|err| matches!(err, Error::Unauthorized(_))));
+    nft.call(burn_nft_message, &spender_context, &mut working_set)
+        .expect("Burn by the owner failed");
+    let actual_collection = nft
+        .get_collection(collection_address, &mut working_set)
+        .unwrap();
+    assert_eq!(actual_collection.supply, 0);
+}
+
+#[test]
+fn genesis_collections() {
+    let creator_pk = DefaultPrivateKey::try_from(&PK1[..]).unwrap();
+    let owner_pk = DefaultPrivateKey::try_from(&PK2[..]).unwrap();
+    let sequencer_pk = DefaultPrivateKey::try_from(&PK4[..]).unwrap();
+
+    let creator_address = creator_pk.default_address();
+    let owner_address = owner_pk.default_address();
+    let sequencer_address = sequencer_pk.default_address();
+    let collection_name = "Genesis Collection";
+    let collection_address =
+        get_collection_address::<DefaultContext>(collection_name, creator_address.as_ref());
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage::<DefaultStorageSpec>(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let nft = NonFungibleToken::default();
+
+    let config = NonFungibleTokenConfig {
+        collections: vec![CollectionConfig {
+            name: collection_name.to_string(),
+            creator: creator_address,
+            collection_uri: "http://foo.bar/genesis_collection".to_string(),
+            frozen: true,
+            nfts: vec![NftConfig {
+                token_id: 7,
+                token_uri: "http://foo.bar/genesis_collection/7".to_string(),
+                owner: owner_address,
+                frozen: false,
+            }],
+        }],
+    };
+    nft.genesis(&config, &mut working_set)
+        .expect("Genesis failed");
+
+    let actual_collection = nft
+        .get_collection(collection_address.clone(), &mut working_set)
+        .unwrap();
+    assert_eq!(actual_collection.supply, 1);
+    assert!(actual_collection.frozen);
+    let owner_of = nft
+        .owner_of(collection_address.clone(), 7, &mut working_set)
+        .unwrap();
+    assert_eq!(owner_of.owner, Some(OwnerAddress::new(&owner_address)));
+    assert_eq!(
+        nft.tokens_of(OwnerAddress::new(&owner_address), &mut working_set)
+            .unwrap()
+            .nfts,
+        vec![NftIdentifier(7, collection_address)]
+    );
+
+    // The collection is frozen, so no more NFTs can be minted
+    let creator_context = DefaultContext::new(creator_address, sequencer_address, 1);
+    assert!(nft
+        .call(
+            CallMessage::MintNft {
+                collection_name: collection_name.to_string(),
+                token_uri: "http://foo.bar/genesis_collection/8".to_string(),
+                token_id: 8,
+                owner: UserAddress::new(&owner_address),
+                frozen: false,
+            },
+            &creator_context,
+            &mut working_set,
+        )
+        .is_err_and(|err| matches!(err, Error::InvariantViolation(_))));
+}
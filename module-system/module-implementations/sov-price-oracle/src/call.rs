@@ -0,0 +1,94 @@
+use anyhow::{bail, Context as _};
+use sov_bank::Amount;
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Context, WorkingSet};
+
+use crate::{Price, PriceOracle};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-price-oracle` module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema),
+    schemars(bound = "C::Address: ::schemars::JsonSchema", rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage<C: Context> {
+    /// Accepts a token for fees at the given price, or updates its price.
+    /// Only the price feeder can send this message.
+    SetPrice {
+        /// The address of the fee token.
+        token_address: C::Address,
+        /// The amount of fee tokens exchanged for `gas_token_amount` gas tokens.
+        fee_token_amount: Amount,
+        /// The amount of gas tokens exchanged for `fee_token_amount` fee tokens.
+        gas_token_amount: Amount,
+    },
+    /// Stops accepting a token for fees. Only the price feeder can send this message.
+    RemovePrice {
+        /// The address of the fee token.
+        token_address: C::Address,
+    },
+    /// Selects the token paying for the gas of the transactions of the sender,
+    /// starting from the next one. Without a token, the sender pays with the gas token.
+    SetFeeToken {
+        /// The address of an accepted fee token.
+        #[cfg_attr(feature = "serde", serde(default))]
+        token_address: Option<C::Address>,
+    },
+}
+
+impl<C: Context> PriceOracle<C> {
+    pub(crate) fn set_price(
+        &self,
+        token_address: C::Address,
+        price: Option<Price>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let price_feeder = self
+            .price_feeder
+            .get(working_set)
+            .context("The price feeder isn't set")?;
+        if context.sender() != &price_feeder {
+            bail!("Only the price feeder can update prices");
+        }
+
+        match price {
+            Some(price) => {
+                price.validate()?;
+                self.prices.set(&token_address, &price, working_set);
+            }
+            None => self.prices.delete(&token_address, working_set),
+        }
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn set_fee_token(
+        &self,
+        token_address: Option<C::Address>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        match token_address {
+            Some(token_address) => {
+                if self.prices.get(&token_address, working_set).is_none() {
+                    bail!("Token {} isn't accepted for fees", token_address);
+                }
+                self.fee_tokens
+                    .set(context.sender(), &token_address, working_set);
+            }
+            None => self.fee_tokens.delete(context.sender(), working_set),
+        }
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,88 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Price, PriceOracle};
+
+/// The price of a token accepted for fees at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct TokenPrice<C: Context> {
+    /// The address of the fee token.
+    pub token_address: C::Address,
+    /// Its price against the gas token.
+    pub price: Price,
+}
+
+/// Genesis configuration for the [`PriceOracle`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct PriceOracleConfig<C: Context> {
+    /// The only address allowed to update prices.
+    pub price_feeder: C::Address,
+    /// The tokens accepted for fees at genesis.
+    #[serde(default)]
+    pub prices: Vec<TokenPrice<C>>,
+}
+
+impl<C: Context> PriceOracle<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.price_feeder.set(&config.price_feeder, working_set);
+        for TokenPrice {
+            token_address,
+            price,
+        } in &config.prices
+        {
+            price.validate()?;
+            self.prices.set(token_address, price, working_set);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::utils::generate_address;
+
+    use crate::{Price, PriceOracleConfig, TokenPrice};
+
+    #[test]
+    fn test_config_serialization() {
+        let config = PriceOracleConfig::<DefaultContext> {
+            price_feeder: generate_address::<DefaultContext>("feeder"),
+            prices: vec![TokenPrice {
+                token_address: generate_address::<DefaultContext>("stablecoin"),
+                price: Price {
+                    fee_token_amount: 3,
+                    gas_token_amount: 2,
+                },
+            }],
+        };
+
+        let data = format!(
+            r#"
+        {{
+            "price_feeder":"{}",
+            "prices":[
+                {{
+                    "token_address":"{}",
+                    "price":{{"fee_token_amount":3,"gas_token_amount":2}}
+                }}
+            ]
+        }}"#,
+            config.price_feeder, config.prices[0].token_address
+        );
+
+        let parsed_config: PriceOracleConfig<DefaultContext> = serde_json::from_str(&data).unwrap();
+        assert_eq!(config, parsed_config)
+    }
+}
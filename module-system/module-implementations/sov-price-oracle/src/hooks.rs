@@ -0,0 +1,169 @@
+use anyhow::Context as _;
+use sov_bank::Coins;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::prelude::*;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{FeeSwap, PriceOracle};
+
+/// The computed addresses of a pre-dispatch tx hook.
+pub struct PriceOracleTxHook<C: Context> {
+    /// The tx sender address
+    pub sender: C::Address,
+}
+
+/// Converts the fees of the transactions whose sender selected a fee token.
+///
+/// The runtime must run the pre-dispatch hook before the one of the bank, which takes the gas
+/// tokens of the transaction, and the post-dispatch hook after the one of the bank, which
+/// refunds the unused gas tokens.
+impl<C: Context> TxHooks for PriceOracle<C> {
+    type Context = C;
+    type PreArg = PriceOracleTxHook<C>;
+    type PreResult = ();
+
+    fn pre_dispatch_tx_hook(
+        &self,
+        tx: &Transaction<C>,
+        working_set: &mut WorkingSet<C>,
+        hook: &PriceOracleTxHook<C>,
+    ) -> anyhow::Result<()> {
+        let PriceOracleTxHook { sender } = hook;
+
+        // The swap of a transaction rejected by a following hook is never reversed
+        self.pending_swap.delete(working_set);
+
+        let Some(token_address) = self.fee_tokens.get(sender, working_set) else {
+            return Ok(());
+        };
+        let price = self
+            .prices
+            .get(&token_address, working_set)
+            .with_context(|| format!("Token {} isn't accepted for fees", token_address))?;
+
+        let gas_amount = tx.gas_limit().saturating_add(tx.gas_tip());
+        if gas_amount == 0 {
+            return Ok(());
+        }
+        let fee_amount = price
+            .fee_tokens_for(gas_amount)
+            .context("The fee amount overflows")?;
+
+        // Check that both transfers succeed first: the state changes of the pre-dispatch hooks are
+        // kept even if the transaction is rejected, so the sender must not pay for a failed swap.
+        let gas_token_address = self.bank.get_gas_token_address()?;
+        if self.is_frozen(sender, &token_address, &gas_token_address, working_set) {
+            anyhow::bail!("The fee token or the gas token is frozen for the sender or the reserve");
+        }
+        let reserve = self
+            .bank
+            .get_balance_of(self.address.clone(), gas_token_address.clone(), working_set)
+            .unwrap_or_default();
+        if reserve < gas_amount {
+            anyhow::bail!("The gas token reserve can't cover the fees of the transaction");
+        }
+
+        self.bank.transfer_from(
+            sender,
+            &self.address,
+            Coins {
+                amount: fee_amount,
+                token_address: token_address.clone(),
+            },
+            working_set,
+        )?;
+        self.bank.transfer_from(
+            &self.address,
+            sender,
+            Coins {
+                amount: gas_amount,
+                token_address: gas_token_address,
+            },
+            working_set,
+        )?;
+
+        self.pending_swap.set(
+            &FeeSwap {
+                sender: sender.clone(),
+                token_address,
+                price,
+            },
+            working_set,
+        );
+        Ok(())
+    }
+
+    fn post_dispatch_tx_hook(
+        &self,
+        _tx: &Transaction<Self::Context>,
+        _ctx: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        let Some(FeeSwap {
+            sender,
+            token_address,
+            price,
+        }) = self.pending_swap.get(working_set)
+        else {
+            return Ok(());
+        };
+        self.pending_swap.delete(working_set);
+
+        // The unused gas was refunded to the sender in gas tokens, which are swapped back.
+        // Only the gas tokens the sender still holds are swapped, in case the transaction spent some.
+        let gas_token_address = self.bank.get_gas_token_address()?;
+        if self.is_frozen(&sender, &token_address, &gas_token_address, working_set) {
+            return Ok(());
+        }
+        let balance = self
+            .bank
+            .get_balance_of(sender.clone(), gas_token_address.clone(), working_set)
+            .unwrap_or_default();
+        let gas_amount = working_set.gas_remaining_funds().min(balance);
+        let fee_amount = price.fee_tokens_refunded(gas_amount).unwrap_or_default();
+        if fee_amount == 0 {
+            return Ok(());
+        }
+
+        self.bank.transfer_from(
+            &sender,
+            &self.address,
+            Coins {
+                amount: gas_amount,
+                token_address: gas_token_address,
+            },
+            working_set,
+        )?;
+        self.bank.transfer_from(
+            &self.address,
+            &sender,
+            Coins {
+                amount: fee_amount,
+                token_address,
+            },
+            working_set,
+        )?;
+        Ok(())
+    }
+}
+
+impl<C: Context> PriceOracle<C> {
+    /// Returns `true` if the sender or the reserve can't transfer one of the swapped tokens.
+    fn is_frozen(
+        &self,
+        sender: &C::Address,
+        fee_token_address: &C::Address,
+        gas_token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> bool {
+        [sender, &self.address].into_iter().any(|account| {
+            [fee_token_address, gas_token_address]
+                .into_iter()
+                .any(|token_address| {
+                    self.bank
+                        .is_account_frozen(account.clone(), token_address.clone(), working_set)
+                })
+        })
+    }
+}
@@ -0,0 +1,181 @@
+//! The `sov-price-oracle` module lets users pay transaction fees in tokens other than the gas token.
+//!
+//! The price feeder publishes the price of every accepted fee token against the gas token.
+//! A user who selected a fee token pays for gas by swapping it with the gas token reserve
+//! of the module: the fee tokens are exchanged for the gas tokens of the transaction before
+//! it's executed, and the unused gas tokens are exchanged back after its execution.
+//!
+//! The swaps are done through [`sov_bank`], so the gas reserve of the module is its gas
+//! token balance, funded by plain transfers to the module address.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+mod hooks;
+#[cfg(feature = "native")]
+mod query;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use call::*;
+pub use genesis::*;
+pub use hooks::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The price of a fee token: `fee_token_amount` fee tokens are exchanged for
+/// `gas_token_amount` gas tokens.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+pub struct Price {
+    /// The amount of fee tokens.
+    pub fee_token_amount: Amount,
+    /// The amount of gas tokens they're worth.
+    pub gas_token_amount: Amount,
+}
+
+impl Price {
+    /// Returns an error unless both amounts are positive.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.fee_token_amount == 0 || self.gas_token_amount == 0 {
+            anyhow::bail!("The amounts of a price can't be zero");
+        }
+        Ok(())
+    }
+
+    /// The fee tokens paid for `gas_amount` gas tokens, rounded up so the reserve never loses value.
+    /// Returns [`None`] on overflow, or if the price is invalid.
+    pub fn fee_tokens_for(&self, gas_amount: Amount) -> Option<Amount> {
+        let divisor = self.gas_token_amount as u128;
+        let fee_amount = (gas_amount as u128 * self.fee_token_amount as u128)
+            .checked_add(divisor.checked_sub(1)?)?
+            / divisor;
+        Amount::try_from(fee_amount).ok()
+    }
+
+    /// The fee tokens refunded for `gas_amount` unused gas tokens, rounded down.
+    /// Returns [`None`] on overflow, or if the price is invalid.
+    pub fn fee_tokens_refunded(&self, gas_amount: Amount) -> Option<Amount> {
+        let fee_amount = (gas_amount as u128 * self.fee_token_amount as u128)
+            .checked_div(self.gas_token_amount as u128)?;
+        Amount::try_from(fee_amount).ok()
+    }
+}
+
+/// The swap of fee tokens done for the transaction being executed, which is reversed for its unused gas.
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FeeSwap<C: sov_modules_api::Context> {
+    pub(crate) sender: C::Address,
+    pub(crate) token_address: C::Address,
+    pub(crate) price: Price,
+}
+
+/// The `sov-price-oracle` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct PriceOracle<C: sov_modules_api::Context> {
+    /// The address of the `sov_price_oracle` module, which holds the gas token reserve.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+
+    /// The only address allowed to update prices.
+    #[state]
+    pub(crate) price_feeder: StateValue<C::Address>,
+
+    /// Accepted fee token address => its price.
+    #[state]
+    pub(crate) prices: StateMap<C::Address, Price>,
+
+    /// User address => the token paying for the gas of their transactions.
+    /// Users without an entry pay with the gas token.
+    #[state]
+    pub(crate) fee_tokens: StateMap<C::Address, C::Address>,
+
+    /// The swap done for the transaction being executed, if any.
+    #[state]
+    pub(crate) pending_swap: StateValue<FeeSwap<C>>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for PriceOracle<C> {
+    type Context = C;
+
+    type Config = PriceOracleConfig<C>;
+
+    type CallMessage = CallMessage<C>;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::SetPrice {
+                token_address,
+                fee_token_amount,
+                gas_token_amount,
+            } => self.set_price(
+                token_address,
+                Some(Price {
+                    fee_token_amount,
+                    gas_token_amount,
+                }),
+                context,
+                working_set,
+            )?,
+            CallMessage::RemovePrice { token_address } => {
+                self.set_price(token_address, None, context, working_set)?
+            }
+            CallMessage::SetFeeToken { token_address } => {
+                self.set_fee_token(token_address, context, working_set)?
+            }
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> PriceOracle<C> {
+    /// Returns the address allowed to update prices.
+    pub fn get_price_feeder(&self, working_set: &mut WorkingSet<C>) -> Option<C::Address> {
+        self.price_feeder.get(working_set)
+    }
+
+    /// Returns the price of the given fee token, or [`None`] if it isn't accepted for fees.
+    pub fn get_price(
+        &self,
+        token_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Price> {
+        self.prices.get(token_address, working_set)
+    }
+
+    /// Returns the token paying for the gas of the given user, or [`None`] if they pay with the gas token.
+    pub fn get_fee_token(
+        &self,
+        user_address: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<C::Address> {
+        self.fee_tokens.get(user_address, working_set)
+    }
+}
@@ -0,0 +1,48 @@
+//! Defines rpc queries exposed by the price oracle module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Price, PriceOracle};
+
+/// The response type to the `getPrice` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct PriceResponse {
+    /// The price of the requested token, if it's accepted for fees.
+    pub price: Option<Price>,
+}
+
+/// The response type to the `getFeeToken` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct FeeTokenResponse<C: Context> {
+    /// The token paying for the gas of the requested user, if they don't pay with the gas token.
+    pub token_address: Option<C::Address>,
+}
+
+#[rpc_gen(client, server, namespace = "priceOracle")]
+impl<C: Context> PriceOracle<C> {
+    /// Returns the price of the given fee token against the gas token.
+    #[rpc_method(name = "getPrice")]
+    pub fn price(
+        &self,
+        token_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<PriceResponse> {
+        Ok(PriceResponse {
+            price: self.get_price(&token_address, working_set),
+        })
+    }
+
+    /// Returns the token paying for the gas of the given user.
+    #[rpc_method(name = "getFeeToken")]
+    pub fn fee_token(
+        &self,
+        user_address: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<FeeTokenResponse<C>> {
+        Ok(FeeTokenResponse {
+            token_address: self.get_fee_token(&user_address, working_set),
+        })
+    }
+}
@@ -0,0 +1,270 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, BankTxHook, TokenConfig};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::TxHooks;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, GasUnit, Module, ModuleInfo, PrivateKey, Spec, WorkingSet};
+use sov_price_oracle::{
+    CallMessage, Price, PriceOracle, PriceOracleConfig, PriceOracleTxHook, TokenPrice,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+
+type C = DefaultContext;
+
+const GAS_LIMIT: u64 = 100;
+const GAS_TIP: u64 = 5;
+const INITIAL_BALANCE: u64 = 1000;
+
+struct TestSetup {
+    oracle: PriceOracle<C>,
+    bank: Bank<C>,
+    gas_token: <C as Spec>::Address,
+    stablecoin: <C as Spec>::Address,
+    sender: <C as Spec>::Address,
+    sequencer: <C as Spec>::Address,
+    feeder: <C as Spec>::Address,
+}
+
+impl TestSetup {
+    fn init(reserve: u64, working_set: &mut WorkingSet<C>) -> Self {
+        let oracle = PriceOracle::<C>::default();
+        let bank = Bank::<C>::default();
+        let sender = generate_address::<C>("sender");
+        let sequencer = generate_address::<C>("sequencer");
+        let feeder = generate_address::<C>("feeder");
+
+        let bank_config = BankConfig {
+            tokens: vec![
+                TokenConfig::fixed_supply("sov-gas-token", vec![(*oracle.address(), reserve)]),
+                TokenConfig::fixed_supply("stablecoin", vec![(sender, INITIAL_BALANCE)]),
+            ],
+        };
+        bank.genesis(&bank_config, working_set).unwrap();
+
+        let gas_token = get_genesis_token_address::<C>("sov-gas-token", 0);
+        let stablecoin = get_genesis_token_address::<C>("stablecoin", 0);
+        assert_eq!(gas_token, bank.get_gas_token_address().unwrap());
+
+        let oracle_config = PriceOracleConfig {
+            price_feeder: feeder,
+            prices: vec![TokenPrice {
+                token_address: stablecoin,
+                price: Price {
+                    fee_token_amount: 2,
+                    gas_token_amount: 1,
+                },
+            }],
+        };
+        oracle.genesis(&oracle_config, working_set).unwrap();
+
+        Self {
+            oracle,
+            bank,
+            gas_token,
+            stablecoin,
+            sender,
+            sequencer,
+            feeder,
+        }
+    }
+
+    fn balance(
+        &self,
+        account: <C as Spec>::Address,
+        token: <C as Spec>::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> u64 {
+        self.bank.balance_or_zero(account, token, working_set)
+    }
+
+    fn select_stablecoin(&self, working_set: &mut WorkingSet<C>) {
+        let context = C::new(self.sender, self.sequencer, 1);
+        self.oracle
+            .call(
+                CallMessage::SetFeeToken {
+                    token_address: Some(self.stablecoin),
+                },
+                &context,
+                working_set,
+            )
+            .unwrap();
+    }
+}
+
+fn transaction() -> Transaction<C> {
+    Transaction::new_signed_tx(
+        &DefaultPrivateKey::generate(),
+        vec![],
+        0,
+        GAS_TIP,
+        GAS_LIMIT,
+        0,
+    )
+}
+
+#[test]
+fn fees_are_paid_in_the_selected_token() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(INITIAL_BALANCE, working_set);
+    setup.select_stablecoin(working_set);
+    assert_eq!(
+        Some(setup.stablecoin),
+        setup.oracle.get_fee_token(&setup.sender, working_set)
+    );
+
+    let tx = transaction();
+    let gas_price = <C as Context>::GasUnit::from_arbitrary_dimensions(&[1, 1]);
+    working_set.set_gas(GAS_LIMIT, gas_price);
+
+    let oracle_hook = PriceOracleTxHook {
+        sender: setup.sender,
+    };
+    setup
+        .oracle
+        .pre_dispatch_tx_hook(&tx, working_set, &oracle_hook)
+        .unwrap();
+    let bank_hook = BankTxHook {
+        sender: setup.sender,
+        sequencer: setup.sequencer,
+    };
+    setup
+        .bank
+        .pre_dispatch_tx_hook(&tx, working_set, &bank_hook)
+        .unwrap();
+
+    // The sender paid the gas limit and the tip in stablecoins, at 2 stablecoins per gas token
+    let paid = 2 * (GAS_LIMIT + GAS_TIP);
+    assert_eq!(
+        INITIAL_BALANCE - paid,
+        setup.balance(setup.sender, setup.stablecoin, working_set)
+    );
+    assert_eq!(0, setup.balance(setup.sender, setup.gas_token, working_set));
+    assert_eq!(
+        GAS_LIMIT + GAS_TIP,
+        setup.balance(setup.sequencer, setup.gas_token, working_set)
+    );
+
+    // The transaction uses some gas
+    let gas_used = <C as Context>::GasUnit::from_arbitrary_dimensions(&[30, 10]);
+    working_set.charge_gas(&gas_used).unwrap();
+    let remaining = working_set.gas_remaining_funds();
+    assert!(remaining > 0);
+
+    let context = C::new(setup.sender, setup.sequencer, 1);
+    setup
+        .bank
+        .post_dispatch_tx_hook(&tx, &context, working_set)
+        .unwrap();
+    setup
+        .oracle
+        .post_dispatch_tx_hook(&tx, &context, working_set)
+        .unwrap();
+
+    // The unused gas is refunded in stablecoins
+    assert_eq!(
+        INITIAL_BALANCE - paid + 2 * remaining,
+        setup.balance(setup.sender, setup.stablecoin, working_set)
+    );
+    assert_eq!(0, setup.balance(setup.sender, setup.gas_token, working_set));
+    assert_eq!(
+        GAS_LIMIT + GAS_TIP - remaining,
+        setup.balance(setup.sequencer, setup.gas_token, working_set)
+    );
+    assert_eq!(
+        INITIAL_BALANCE - (GAS_LIMIT + GAS_TIP) + remaining,
+        setup.balance(*setup.oracle.address(), setup.gas_token, working_set)
+    );
+    assert_eq!(
+        paid - 2 * remaining,
+        setup.balance(*setup.oracle.address(), setup.stablecoin, working_set)
+    );
+}
+
+#[test]
+fn empty_reserve_rejects_the_transaction() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(0, working_set);
+    setup.select_stablecoin(working_set);
+
+    let oracle_hook = PriceOracleTxHook {
+        sender: setup.sender,
+    };
+    assert!(setup
+        .oracle
+        .pre_dispatch_tx_hook(&transaction(), working_set, &oracle_hook)
+        .is_err());
+    // The sender didn't pay for the failed swap
+    assert_eq!(
+        INITIAL_BALANCE,
+        setup.balance(setup.sender, setup.stablecoin, working_set)
+    );
+}
+
+#[test]
+fn only_the_feeder_updates_prices() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(INITIAL_BALANCE, working_set);
+    let other_token = generate_address::<C>("other-token");
+    let set_price = CallMessage::SetPrice {
+        token_address: other_token,
+        fee_token_amount: 1,
+        gas_token_amount: 1,
+    };
+
+    let sender_context = C::new(setup.sender, setup.sequencer, 1);
+    assert!(setup
+        .oracle
+        .call(set_price.clone(), &sender_context, working_set)
+        .is_err());
+    // Only the accepted tokens can be selected
+    assert!(setup
+        .oracle
+        .call(
+            CallMessage::SetFeeToken {
+                token_address: Some(other_token),
+            },
+            &sender_context,
+            working_set,
+        )
+        .is_err());
+
+    let feeder_context = C::new(setup.feeder, setup.sequencer, 1);
+    setup
+        .oracle
+        .call(set_price, &feeder_context, working_set)
+        .unwrap();
+    assert_eq!(
+        Some(Price {
+            fee_token_amount: 1,
+            gas_token_amount: 1,
+        }),
+        setup.oracle.get_price(&other_token, working_set)
+    );
+
+    setup
+        .oracle
+        .call(
+            CallMessage::RemovePrice {
+                token_address: other_token,
+            },
+            &feeder_context,
+            working_set,
+        )
+        .unwrap();
+    assert_eq!(None, setup.oracle.get_price(&other_token, working_set));
+
+    // Zero prices are rejected
+    let zero_price = CallMessage::SetPrice {
+        token_address: other_token,
+        fee_token_amount: 0,
+        gas_token_amount: 1,
+    };
+    assert!(setup
+        .oracle
+        .call(zero_price, &feeder_context, working_set)
+        .is_err());
+}
@@ -32,6 +32,8 @@ fn create_bank_config() -> (
         address_and_balances: vec![(prover_address, BOND_AMOUNT * 5)],
         authorized_minters: vec![prover_address],
         salt: 2,
+        authority: None,
+        max_supply: None,
     };
 
     (
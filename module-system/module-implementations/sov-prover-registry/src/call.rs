@@ -0,0 +1,397 @@
+use anyhow::{bail, ensure};
+use sov_bank::{Amount, Coins};
+use sov_chain_state::TransitionHeight;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{AggregatedStateTransition, CallResponse, Context, DaSpec, WorkingSet, Zkvm};
+use sov_state::Storage;
+
+use crate::{ProverRegistry, RangeAssignment, RangeStatus};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-prover-registry` module.
+#[cfg_attr(feature = "native", derive(schemars::JsonSchema))]
+#[derive(Debug, PartialEq, Clone, borsh::BorshSerialize, borsh::BorshDeserialize)]
+pub enum CallMessage {
+    /// Registers the sender as a prover.
+    /// Anyone can register, as long as they bond at least the minimum stake.
+    Register {
+        /// The amount of tokens to bond, which can't be less than the minimum stake.
+        amount: Amount,
+    },
+    /// Removes the sender from the registered provers and returns its stake.
+    /// Provers can't exit while one of their ranges waits for a proof.
+    Exit,
+    /// Adds the given amount of bonding tokens to the reward pool.
+    FundRewards {
+        /// The amount of tokens transferred from the sender to the reward pool.
+        amount: Amount,
+    },
+    /// Submits the aggregated proof of a range, which must be assigned to the sender.
+    SubmitProof {
+        /// The index of the proven range.
+        range: u64,
+        /// The serialized proof.
+        proof: Vec<u8>,
+    },
+    /// Slashes the prover of a range whose deadline passed without a proof. Anyone can report it.
+    ReportMissingProof {
+        /// The index of the range.
+        range: u64,
+    },
+}
+
+impl<C: Context, Vm: Zkvm, Da: DaSpec> ProverRegistry<C, Vm, Da> {
+    pub(crate) fn register(
+        &self,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        self.register_prover(context.sender(), amount, working_set)?;
+        Ok(CallResponse::default())
+    }
+
+    /// Bonds the stake of the prover and adds it to the registered provers.
+    pub(crate) fn register_prover(
+        &self,
+        prover: &C::Address,
+        amount: Amount,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<()> {
+        if self.stakes.get(prover, working_set).is_some() {
+            bail!("prover {} already registered", prover)
+        }
+        let minimum_bond = self.minimum_bond.get_or_err(working_set)?;
+        if amount < minimum_bond.amount {
+            bail!(
+                "stake of {} is below the minimum of {}",
+                amount,
+                minimum_bond.amount
+            )
+        }
+        let coins = Coins {
+            amount,
+            token_address: minimum_bond.token_address,
+        };
+        self.bank
+            .transfer_from(prover, &self.address, coins, working_set)?;
+
+        self.stakes.set(prover, &amount, working_set);
+        let mut provers = self.get_provers(working_set);
+        provers.push(prover.clone());
+        self.provers.set(&provers, working_set);
+
+        working_set.add_event(
+            "registered_prover",
+            &format!("prover: {prover}, stake: {amount}"),
+        );
+        Ok(())
+    }
+
+    pub(crate) fn exit(
+        &self,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let prover = context.sender();
+        let stake = self.stakes.get_or_err(prover, working_set)?;
+        let pending_ranges = self
+            .pending_ranges
+            .get(prover, working_set)
+            .unwrap_or_default();
+        if pending_ranges > 0 {
+            bail!(
+                "prover {} can't exit, {} of its ranges wait for a proof",
+                prover,
+                pending_ranges
+            )
+        }
+
+        self.remove_prover(prover, working_set);
+        let token_address = self.minimum_bond.get_or_err(working_set)?.token_address;
+        self.bank.transfer_from(
+            &self.address,
+            prover,
+            Coins {
+                amount: stake,
+                token_address,
+            },
+            working_set,
+        )?;
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn fund_rewards(
+        &self,
+        amount: Amount,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let token_address = self.minimum_bond.get_or_err(working_set)?.token_address;
+        self.bank.transfer_from(
+            context.sender(),
+            &self.address,
+            Coins {
+                amount,
+                token_address,
+            },
+            working_set,
+        )?;
+        let reward_pool = self.get_reward_pool(working_set);
+        self.reward_pool
+            .set(&reward_pool.saturating_add(amount), working_set);
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn submit_proof(
+        &self,
+        range: u64,
+        proof: &[u8],
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let prover = context.sender();
+        let assignment = self.assign(range, working_set)?;
+        ensure!(
+            assignment.status == RangeStatus::Assigned,
+            "range {} doesn't wait for a proof",
+            range
+        );
+        ensure!(
+            &assignment.prover == prover,
+            "range {} is assigned to prover {}",
+            range,
+            assignment.prover
+        );
+        let deadline = self.deadline(range, working_set)?;
+        ensure!(
+            self.chain_state.true_slot_height(working_set) <= deadline,
+            "the deadline of range {} passed at slot {}",
+            range,
+            deadline
+        );
+
+        let code_commitment = self
+            .commitment_of_allowed_verifier_method
+            .get_or_err(working_set)?;
+        let output = Vm::verify_and_extract_aggregated_output::<Da, <C::Storage as Storage>::Root>(
+            proof,
+            &code_commitment,
+        );
+
+        // Don't return an error for invalid proofs - the prover has to be slashed, which a revert would undo.
+        match output {
+            Ok(output) if self.proves_range(&output, range, working_set)? => {
+                self.close_range(range, assignment, RangeStatus::Proven, working_set);
+                let reward = self.reward(prover, working_set)?;
+                working_set.add_event(
+                    "processed_valid_proof",
+                    &format!("prover: {prover}, range: {range}, reward: {reward}"),
+                );
+            }
+            _ => {
+                self.slash(prover, working_set);
+                // The range is assigned again, to one of the remaining provers
+                self.assignments.delete(&range, working_set);
+                working_set.add_event(
+                    "processed_invalid_proof",
+                    &format!("slashed_prover: {prover}, range: {range}"),
+                );
+            }
+        }
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn report_missing_proof(
+        &self,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let assignment = self.assign(range, working_set)?;
+        ensure!(
+            assignment.status == RangeStatus::Assigned,
+            "range {} doesn't wait for a proof",
+            range
+        );
+        let deadline = self.deadline(range, working_set)?;
+        ensure!(
+            self.chain_state.true_slot_height(working_set) > deadline,
+            "proofs of range {} are accepted until slot {}",
+            range,
+            deadline
+        );
+
+        let prover = assignment.prover.clone();
+        self.slash(&prover, working_set);
+        self.close_range(range, assignment, RangeStatus::Missed, working_set);
+        working_set.add_event(
+            "missed_proof",
+            &format!("slashed_prover: {prover}, range: {range}"),
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    /// Returns the assignment of the range, assigning it first if it wasn't assigned yet.
+    ///
+    /// A range is assigned once all its slots were processed. The prover is picked
+    /// from the registered provers with the hash of the DA block of the first slot.
+    pub(crate) fn assign(
+        &self,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<RangeAssignment<C>> {
+        if let Some(assignment) = self.assignments.get(&range, working_set) {
+            return Ok(assignment);
+        }
+
+        let (first, last) = self.range_slots(range, working_set)?;
+        if self
+            .chain_state
+            .get_historical_transitions(last, working_set)
+            .is_none()
+        {
+            bail!("range {} isn't complete before slot {}", range, last + 1)
+        }
+        let first_transition = self
+            .chain_state
+            .get_historical_transitions(first, working_set)
+            .ok_or_else(|| anyhow::anyhow!("transition of slot {} is missing", first))?;
+        let provers = self.get_provers(working_set);
+        ensure!(!provers.is_empty(), "no prover is registered");
+
+        let seed: [u8; 32] = first_transition.da_block_hash().clone().into();
+        let seed = u64::from_le_bytes(seed[..8].try_into().expect("Seed has 8 bytes"));
+        let prover = provers[(seed % provers.len() as u64) as usize].clone();
+
+        let assignment = RangeAssignment {
+            prover,
+            status: RangeStatus::Assigned,
+        };
+        self.assignments.set(&range, &assignment, working_set);
+        let pending_ranges = self
+            .pending_ranges
+            .get(&assignment.prover, working_set)
+            .unwrap_or_default();
+        self.pending_ranges
+            .set(&assignment.prover, &(pending_ranges + 1), working_set);
+
+        Ok(assignment)
+    }
+
+    /// Returns the last slot height at which the proof of the range is accepted.
+    fn deadline(
+        &self,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<TransitionHeight> {
+        let (_, last) = self.range_slots(range, working_set)?;
+        let proof_window = self.proof_window.get_or_err(working_set)?;
+        Ok(last.saturating_add(proof_window))
+    }
+
+    /// Checks the output of a proof against the state transitions of the range recorded by the chain state.
+    fn proves_range(
+        &self,
+        output: &AggregatedStateTransition<Da, <C::Storage as Storage>::Root, Vm::CodeCommitment>,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<bool> {
+        let (first, last) = self.range_slots(range, working_set)?;
+        let genesis_height = self
+            .chain_state
+            .get_genesis_height(working_set)
+            .unwrap_or_default();
+        // The first slot of the first range starts from the genesis state
+        let initial_state_root = if first - 1 == genesis_height {
+            self.chain_state.get_genesis_hash(working_set)
+        } else {
+            self.chain_state
+                .get_historical_transitions(first - 1, working_set)
+                .map(|transition| transition.post_state_root().clone())
+        }
+        .ok_or_else(|| anyhow::anyhow!("state root before slot {} is missing", first))?;
+        let first_transition = self
+            .chain_state
+            .get_historical_transitions(first, working_set)
+            .ok_or_else(|| anyhow::anyhow!("transition of slot {} is missing", first))?;
+        let last_transition = self
+            .chain_state
+            .get_historical_transitions(last, working_set)
+            .ok_or_else(|| anyhow::anyhow!("transition of slot {} is missing", last))?;
+
+        Ok(output.initial_state_root == initial_state_root
+            && &output.final_state_root == last_transition.post_state_root()
+            && &output.initial_slot_hash == first_transition.da_block_hash()
+            && &output.final_slot_hash == last_transition.da_block_hash())
+    }
+
+    /// Records the final status of the range, which no longer waits for a proof from its prover.
+    fn close_range(
+        &self,
+        range: u64,
+        mut assignment: RangeAssignment<C>,
+        status: RangeStatus,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        if let Some(pending_ranges) = self.pending_ranges.get(&assignment.prover, working_set) {
+            self.pending_ranges.set(
+                &assignment.prover,
+                &pending_ranges.saturating_sub(1),
+                working_set,
+            );
+        }
+        assignment.status = status;
+        self.assignments.set(&range, &assignment, working_set);
+    }
+
+    /// Pays the reward of an accepted proof to the prover, as far as the reward pool allows it.
+    fn reward(
+        &self,
+        prover: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<Amount> {
+        let reward_pool = self.get_reward_pool(working_set);
+        let reward = self
+            .reward_per_proof
+            .get(working_set)
+            .unwrap_or_default()
+            .min(reward_pool);
+        if reward > 0 {
+            let token_address = self.minimum_bond.get_or_err(working_set)?.token_address;
+            self.bank.transfer_from(
+                &self.address,
+                prover,
+                Coins {
+                    amount: reward,
+                    token_address,
+                },
+                working_set,
+            )?;
+            self.reward_pool.set(&(reward_pool - reward), working_set);
+        }
+        Ok(reward)
+    }
+
+    /// Removes the prover from the registered provers. Its stake stays in the module
+    /// and is added to the reward pool.
+    fn slash(&self, prover: &C::Address, working_set: &mut WorkingSet<C>) {
+        if let Some(stake) = self.stakes.get(prover, working_set) {
+            let reward_pool = self.get_reward_pool(working_set);
+            self.reward_pool
+                .set(&reward_pool.saturating_add(stake), working_set);
+            self.remove_prover(prover, working_set);
+        }
+    }
+
+    fn remove_prover(&self, prover: &C::Address, working_set: &mut WorkingSet<C>) {
+        self.stakes.delete(prover, working_set);
+        self.pending_ranges.delete(prover, working_set);
+        let mut provers = self.get_provers(working_set);
+        provers.retain(|registered| registered != prover);
+        self.provers.set(&provers, working_set);
+    }
+}
@@ -0,0 +1,53 @@
+use anyhow::{ensure, Result};
+use serde::{Deserialize, Serialize};
+use sov_bank::Amount;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, DaSpec, WorkingSet, Zkvm};
+
+use crate::ProverRegistry;
+
+/// Genesis configuration for the [`ProverRegistry`] module.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProverRegistryConfig<C: Context, Vm: Zkvm> {
+    /// The minimum stake of a prover, and the token it's denominated in.
+    /// Rewards are paid in the same token.
+    pub minimum_bond: sov_bank::Coins<C>,
+    /// The code commitment of the aggregation program the proofs are verified against.
+    pub commitment_of_allowed_verifier_method: Vm::CodeCommitment,
+    /// The number of consecutive slots in a range. Must not be zero.
+    pub slots_per_range: u64,
+    /// The number of slots after the end of a range within which its proof has to be submitted.
+    pub proof_window: u64,
+    /// The reward paid for every accepted proof.
+    pub reward_per_proof: Amount,
+    /// The initial provers and their stakes.
+    pub initial_provers: Vec<(C::Address, Amount)>,
+}
+
+impl<C: Context, Vm: Zkvm, Da: DaSpec> ProverRegistry<C, Vm, Da> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        ensure!(
+            config.slots_per_range > 0,
+            "A range must contain at least one slot"
+        );
+
+        self.minimum_bond.set(&config.minimum_bond, working_set);
+        self.commitment_of_allowed_verifier_method
+            .set(&config.commitment_of_allowed_verifier_method, working_set);
+        self.slots_per_range
+            .set(&config.slots_per_range, working_set);
+        self.proof_window.set(&config.proof_window, working_set);
+        self.reward_per_proof
+            .set(&config.reward_per_proof, working_set);
+
+        for (prover, amount) in config.initial_provers.iter() {
+            self.register_prover(prover, *amount, working_set)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+//! The `sov-prover-registry` module is responsible for prover registration, rewards and slashing.
+//! Provers bond at least the minimum stake to be registered, in the same way as sequencers
+//! do in the `sov-sequencer-registry` module.
+//!
+//! The slots of the rollup are split into consecutive ranges of `slots_per_range` slots.
+//! Each range is assigned to one of the registered provers, who has to submit an aggregated
+//! proof of the range within `proof_window` slots after the end of the range.
+//! The assignment is derived deterministically from the chain state: the prover is picked from
+//! the registered provers with the hash of the DA block of the first slot of the range.
+//!
+//! A prover submitting a proof which matches the state transitions recorded by the chain state
+//! is rewarded from the module's reward pool. A prover submitting an invalid proof, or missing
+//! the deadline of its range, is slashed, and its stake is added to the reward pool.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+#[cfg(feature = "native")]
+mod query;
+#[cfg(test)]
+mod tests;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Amount;
+use sov_chain_state::TransitionHeight;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{
+    CallResponse, Context, DaSpec, Error, ModuleInfo, StateMap, StateValue, WorkingSet, Zkvm,
+};
+use sov_state::codec::BcsCodec;
+
+/// The progress of a slot range assigned to a prover.
+#[cfg_attr(feature = "native", derive(serde::Deserialize, serde::Serialize))]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeStatus {
+    /// The range waits for a proof from its prover.
+    Assigned,
+    /// The range was proven, and its prover rewarded.
+    Proven,
+    /// The deadline of the range passed without a proof, and its prover was slashed.
+    Missed,
+}
+
+/// The prover a slot range is assigned to.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct RangeAssignment<C: Context> {
+    /// The rollup address of the prover.
+    pub prover: C::Address,
+    /// The progress of the range.
+    pub status: RangeStatus,
+}
+
+/// The `sov-prover-registry` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct ProverRegistry<C: Context, Vm: Zkvm, Da: DaSpec> {
+    /// The address of the `sov_prover_registry` module.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+
+    /// Reference to the chain state module, used to derive the assignments
+    /// and to check the proofs against the recorded state transitions.
+    #[kernel_module]
+    pub(crate) chain_state: sov_chain_state::ChainState<C, Da>,
+
+    /// The minimum stake a prover has to bond, and the token it's denominated in.
+    #[state]
+    pub(crate) minimum_bond: StateValue<sov_bank::Coins<C>>,
+
+    /// The code commitment of the aggregation program the proofs are verified against.
+    #[state]
+    pub(crate) commitment_of_allowed_verifier_method: StateValue<Vm::CodeCommitment, BcsCodec>,
+
+    /// The number of consecutive slots in a range.
+    #[state]
+    pub(crate) slots_per_range: StateValue<u64>,
+
+    /// The number of slots after the end of a range within which its proof has to be submitted.
+    #[state]
+    pub(crate) proof_window: StateValue<u64>,
+
+    /// The reward paid for every accepted proof, as long as the reward pool allows it.
+    #[state]
+    pub(crate) reward_per_proof: StateValue<Amount>,
+
+    /// The tokens held by the module to reward provers, funded by anyone and by the slashed stakes.
+    #[state]
+    pub(crate) reward_pool: StateValue<Amount>,
+
+    /// The registered provers, in registration order.
+    #[state]
+    pub(crate) provers: StateValue<Vec<C::Address>>,
+
+    /// The amount bonded by each registered prover.
+    #[state]
+    pub(crate) stakes: StateMap<C::Address, Amount>,
+
+    /// Range index => the prover the range is assigned to.
+    #[state]
+    pub(crate) assignments: StateMap<u64, RangeAssignment<C>>,
+
+    /// The number of ranges waiting for a proof from each prover. Provers can't exit while it's not zero.
+    #[state]
+    pub(crate) pending_ranges: StateMap<C::Address, u64>,
+}
+
+impl<C: Context, Vm: Zkvm, Da: DaSpec> sov_modules_api::Module for ProverRegistry<C, Vm, Da> {
+    type Context = C;
+
+    type Config = ProverRegistryConfig<C, Vm>;
+
+    type CallMessage = CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::Register { amount } => self.register(amount, context, working_set)?,
+            CallMessage::Exit => self.exit(context, working_set)?,
+            CallMessage::FundRewards { amount } => {
+                self.fund_rewards(amount, context, working_set)?
+            }
+            CallMessage::SubmitProof { range, proof } => {
+                self.submit_proof(range, &proof, context, working_set)?
+            }
+            CallMessage::ReportMissingProof { range } => {
+                self.report_missing_proof(range, working_set)?
+            }
+        })
+    }
+}
+
+impl<C: Context, Vm: Zkvm, Da: DaSpec> ProverRegistry<C, Vm, Da> {
+    /// Returns the amount bonded by the registered prover with the given address.
+    pub fn get_stake(
+        &self,
+        prover: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        self.stakes.get(prover, working_set)
+    }
+
+    /// Returns the registered provers, in registration order.
+    pub fn get_provers(&self, working_set: &mut WorkingSet<C>) -> Vec<C::Address> {
+        self.provers.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the amount of tokens available to reward provers.
+    pub fn get_reward_pool(&self, working_set: &mut WorkingSet<C>) -> Amount {
+        self.reward_pool.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the prover the range with the given index is assigned to,
+    /// if the range was assigned already.
+    pub fn get_assignment(
+        &self,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<RangeAssignment<C>> {
+        self.assignments.get(&range, working_set)
+    }
+
+    /// Returns the first and last slot heights of the range with the given index.
+    pub fn range_slots(
+        &self,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<(TransitionHeight, TransitionHeight)> {
+        let slots_per_range = self.slots_per_range.get_or_err(working_set)?;
+        // The first slot follows the genesis height
+        let genesis_height = self
+            .chain_state
+            .get_genesis_height(working_set)
+            .unwrap_or_default();
+        let first = genesis_height
+            .saturating_add(range.saturating_mul(slots_per_range))
+            .saturating_add(1);
+        Ok((first, first.saturating_add(slots_per_range - 1)))
+    }
+}
@@ -0,0 +1,62 @@
+//! Defines rpc queries exposed by the prover registry module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_bank::Amount;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, DaSpec, WorkingSet, Zkvm};
+
+use crate::{ProverRegistry, RangeAssignment};
+
+/// The response type to the `proverRegistry_getStake` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct StakeResponse {
+    /// The amount bonded by the requested prover, if it's registered.
+    pub stake: Option<Amount>,
+}
+
+/// The response type to the `proverRegistry_getAssignment` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct AssignmentResponse<C: Context> {
+    /// The first slot height of the range.
+    pub first_slot: u64,
+    /// The last slot height of the range.
+    pub last_slot: u64,
+    /// The prover the range is assigned to. Ranges are assigned once all their slots were processed.
+    pub assignment: Option<RangeAssignment<C>>,
+}
+
+#[rpc_gen(client, server, namespace = "proverRegistry")]
+impl<C: Context, Vm: Zkvm, Da: DaSpec> ProverRegistry<C, Vm, Da> {
+    /// Returns the amount bonded by the prover with the given address.
+    #[rpc_method(name = "getStake")]
+    pub fn stake(
+        &self,
+        prover: C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<StakeResponse> {
+        Ok(StakeResponse {
+            stake: self.get_stake(&prover, working_set),
+        })
+    }
+
+    /// Returns the slots of the range with the given index, and the prover it's assigned to.
+    #[rpc_method(name = "getAssignment")]
+    pub fn assignment(
+        &self,
+        range: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<AssignmentResponse<C>> {
+        let (first_slot, last_slot) = self.range_slots(range, working_set).map_err(|e| {
+            jsonrpsee::types::ErrorObjectOwned::owned(
+                jsonrpsee::types::error::INTERNAL_ERROR_CODE,
+                e.to_string(),
+                None::<()>,
+            )
+        })?;
+        Ok(AssignmentResponse {
+            first_slot,
+            last_slot,
+            assignment: self.get_assignment(range, working_set),
+        })
+    }
+}
@@ -0,0 +1,286 @@
+use std::ops::RangeInclusive;
+
+use sov_bank::{Bank, Coins};
+use sov_chain_state::{ChainState, ChainStateConfig};
+use sov_mock_da::{MockBlockHeader, MockDaSpec, MockValidityCond};
+use sov_mock_zkvm::{MockCodeCommitment, MockProof, MockZkvm};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    AggregatedStateTransition, Context, KernelModule, KernelWorkingSet, Module, Spec, WorkingSet,
+};
+use sov_modules_core::runtime::capabilities::mocks::MockKernel;
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_state::Storage;
+
+use crate::{CallMessage, ProverRegistry, ProverRegistryConfig, RangeStatus};
+
+type C = DefaultContext;
+type Root = <<C as Spec>::Storage as Storage>::Root;
+type Registry = ProverRegistry<C, MockZkvm<MockValidityCond>, MockDaSpec>;
+
+const TOKEN_NAME: &str = "PROVER_TOKEN";
+const SALT: u64 = 7;
+const BOND_AMOUNT: u64 = 1000;
+const REWARD: u64 = 100;
+const CODE_COMMITMENT: MockCodeCommitment = MockCodeCommitment([0u8; 32]);
+
+/// The state root after the slot with the given height, the genesis root being the one of slot 0.
+fn root(height: u64) -> Root {
+    [100 + height as u8; 32].into()
+}
+
+fn da_hash(height: u64) -> [u8; 32] {
+    [height as u8; 32]
+}
+
+/// Sets up the bank, the chain state and the registry with two provers,
+/// and funds the reward pool. Returns the registry, the token and the provers.
+fn setup(
+    working_set: &mut WorkingSet<C>,
+) -> (Registry, <C as Spec>::Address, Vec<<C as Spec>::Address>) {
+    let provers = vec![
+        generate_address::<C>("prover_1"),
+        generate_address::<C>("prover_2"),
+    ];
+    let funder = generate_address::<C>("funder");
+    let bank_config = sov_bank::BankConfig {
+        tokens: vec![sov_bank::TokenConfig {
+            token_name: TOKEN_NAME.to_owned(),
+            address_and_balances: vec![
+                (provers[0], BOND_AMOUNT),
+                (provers[1], BOND_AMOUNT),
+                (funder, REWARD * 10),
+            ],
+            authorized_minters: vec![],
+            salt: SALT,
+            authority: None,
+            max_supply: None,
+        }],
+    };
+    Bank::<C>::default()
+        .genesis(&bank_config, working_set)
+        .expect("bank genesis must succeed");
+    let token_address = sov_bank::get_genesis_token_address::<C>(TOKEN_NAME, SALT);
+
+    ChainState::<C, MockDaSpec>::default()
+        .genesis(
+            &ChainStateConfig {
+                initial_slot_height: 0,
+                current_time: Default::default(),
+                gas_price: vec![0, 0],
+            },
+            working_set,
+        )
+        .expect("chain state genesis must succeed");
+
+    let registry = Registry::default();
+    let config = ProverRegistryConfig {
+        minimum_bond: Coins {
+            amount: BOND_AMOUNT,
+            token_address,
+        },
+        commitment_of_allowed_verifier_method: CODE_COMMITMENT,
+        slots_per_range: 2,
+        proof_window: 2,
+        reward_per_proof: REWARD,
+        initial_provers: provers.iter().map(|p| (*p, BOND_AMOUNT)).collect(),
+    };
+    registry
+        .genesis(&config, working_set)
+        .expect("prover registry genesis must succeed");
+
+    registry
+        .call(
+            CallMessage::FundRewards {
+                amount: REWARD * 10,
+            },
+            &C::new(funder, generate_address::<C>("sequencer"), 0),
+            working_set,
+        )
+        .expect("funding the rewards must succeed");
+
+    (registry, token_address, provers)
+}
+
+/// Runs the chain state through the slots with the given heights, so the true slot height becomes the last one.
+fn run_slots(registry: &Registry, heights: RangeInclusive<u64>, working_set: &mut WorkingSet<C>) {
+    for height in heights {
+        let header = MockBlockHeader {
+            prev_hash: da_hash(height - 1).into(),
+            hash: da_hash(height).into(),
+            height,
+            time: Default::default(),
+        };
+        let kernel = MockKernel::<C, MockDaSpec>::new(height, height);
+        registry.chain_state.begin_slot_hook(
+            &header,
+            &MockValidityCond::default(),
+            &root(height - 1),
+            &mut KernelWorkingSet::from_kernel(&kernel, working_set),
+        );
+    }
+}
+
+fn range_proof(first: u64, last: u64, final_state_root: Root) -> Vec<u8> {
+    let output = AggregatedStateTransition::<MockDaSpec, Root, MockCodeCommitment> {
+        slot_code_commitment: MockCodeCommitment([0; 32]),
+        initial_state_root: root(first - 1),
+        final_state_root,
+        initial_slot_hash: da_hash(first).into(),
+        final_slot_hash: da_hash(last).into(),
+        validity_condition: MockValidityCond::default(),
+    };
+    MockProof {
+        program_id: CODE_COMMITMENT,
+        is_valid: true,
+        log: &bincode::serialize(&output).unwrap(),
+    }
+    .encode_to_vec()
+}
+
+fn context(sender: <C as Spec>::Address) -> C {
+    C::new(sender, generate_address::<C>("sequencer"), 0)
+}
+
+/// The prover range 0 gets, picked with the hash of the DA block of slot 1.
+fn expected_prover(provers: &[<C as Spec>::Address]) -> <C as Spec>::Address {
+    let seed = u64::from_le_bytes(da_hash(1)[..8].try_into().unwrap());
+    provers[(seed % provers.len() as u64) as usize]
+}
+
+#[test]
+fn accepted_proof_is_rewarded() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let (registry, token_address, provers) = setup(working_set);
+    let prover = expected_prover(&provers);
+    let other_prover = *provers.iter().find(|p| **p != prover).unwrap();
+    let submit = |sender, proof: Vec<u8>, working_set: &mut WorkingSet<C>| {
+        registry.call(
+            CallMessage::SubmitProof { range: 0, proof },
+            &context(sender),
+            working_set,
+        )
+    };
+
+    // The transition of slot 2 is only recorded once slot 3 begins
+    run_slots(&registry, 1..=2, working_set);
+    assert!(submit(prover, range_proof(1, 2, root(2)), working_set).is_err());
+    assert_eq!(None, registry.get_assignment(0, working_set));
+
+    run_slots(&registry, 3..=3, working_set);
+    assert!(submit(other_prover, range_proof(1, 2, root(2)), working_set).is_err());
+    submit(prover, range_proof(1, 2, root(2)), working_set).expect("proof must be accepted");
+
+    let assignment = registry.get_assignment(0, working_set).unwrap();
+    assert_eq!(prover, assignment.prover);
+    assert_eq!(RangeStatus::Proven, assignment.status);
+    assert_eq!(
+        Some(REWARD),
+        registry
+            .bank
+            .get_balance_of(prover, token_address, working_set)
+    );
+    assert_eq!(REWARD * 9, registry.get_reward_pool(working_set));
+    // A range is only proven once
+    assert!(submit(prover, range_proof(1, 2, root(2)), working_set).is_err());
+
+    // The prover can exit once its range is proven
+    registry
+        .call(CallMessage::Exit, &context(prover), working_set)
+        .expect("exit must succeed");
+    assert_eq!(None, registry.get_stake(&prover, working_set));
+    assert_eq!(
+        Some(REWARD + BOND_AMOUNT),
+        registry
+            .bank
+            .get_balance_of(prover, token_address, working_set)
+    );
+}
+
+#[test]
+fn invalid_proof_is_slashed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let (registry, _, provers) = setup(working_set);
+    let prover = expected_prover(&provers);
+    let other_prover = *provers.iter().find(|p| **p != prover).unwrap();
+    run_slots(&registry, 1..=3, working_set);
+
+    // The proof ends in another state than the one recorded by the chain state
+    registry
+        .call(
+            CallMessage::SubmitProof {
+                range: 0,
+                proof: range_proof(1, 2, root(3)),
+            },
+            &context(prover),
+            working_set,
+        )
+        .expect("invalid proofs don't revert");
+
+    assert_eq!(None, registry.get_stake(&prover, working_set));
+    assert_eq!(vec![other_prover], registry.get_provers(working_set));
+    assert_eq!(
+        REWARD * 10 + BOND_AMOUNT,
+        registry.get_reward_pool(working_set)
+    );
+
+    // The range goes to the remaining prover
+    registry
+        .call(
+            CallMessage::SubmitProof {
+                range: 0,
+                proof: range_proof(1, 2, root(2)),
+            },
+            &context(other_prover),
+            working_set,
+        )
+        .expect("proof must be accepted");
+    assert_eq!(
+        RangeStatus::Proven,
+        registry.get_assignment(0, working_set).unwrap().status
+    );
+}
+
+#[test]
+fn missing_proof_is_slashed() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let (registry, _, provers) = setup(working_set);
+    let prover = expected_prover(&provers);
+    let reporter = generate_address::<C>("reporter");
+    let report = |working_set: &mut WorkingSet<C>| {
+        registry.call(
+            CallMessage::ReportMissingProof { range: 0 },
+            &context(reporter),
+            working_set,
+        )
+    };
+
+    // Assigned provers can't exit
+    run_slots(&registry, 1..=3, working_set);
+    assert_eq!(prover, registry.assign(0, working_set).unwrap().prover);
+    assert!(registry
+        .call(CallMessage::Exit, &context(prover), working_set)
+        .is_err());
+
+    // Proofs of range 0 are accepted until slot 4
+    run_slots(&registry, 4..=4, working_set);
+    assert!(report(working_set).is_err());
+
+    run_slots(&registry, 5..=5, working_set);
+    report(working_set).expect("report must succeed");
+
+    assert_eq!(
+        RangeStatus::Missed,
+        registry.get_assignment(0, working_set).unwrap().status
+    );
+    assert_eq!(None, registry.get_stake(&prover, working_set));
+    assert_eq!(
+        REWARD * 10 + BOND_AMOUNT,
+        registry.get_reward_pool(working_set)
+    );
+    assert!(report(working_set).is_err());
+}
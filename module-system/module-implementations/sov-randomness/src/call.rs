@@ -0,0 +1,108 @@
+use anyhow::Result;
+use sov_modules_api::digest::Digest;
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Context, EventEmitter, ModuleErrorCode, WorkingSet};
+
+use crate::{Commitment, Randomness};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-randomness` module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema),
+    schemars(rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage {
+    /// Commits to a secret, to be revealed in a later slot. The commitment is the hash of the
+    /// address of the sender followed by the secret, see [`commitment_hash`]. It replaces the
+    /// previous commitment of the sender which wasn't revealed.
+    Commit {
+        /// The 32 bytes hash of the commitment.
+        hash: Vec<u8>,
+    },
+    /// Reveals the secret of the commitment of the sender, which is mixed into the randomness
+    /// of the next slot.
+    Reveal {
+        /// The secret.
+        secret: Vec<u8>,
+    },
+}
+
+/// Returns the commitment of `committer` to `secret`.
+pub fn commitment_hash<C: Context>(committer: &C::Address, secret: &[u8]) -> [u8; 32] {
+    let mut hasher = C::Hasher::new();
+    hasher.update(committer.as_ref());
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+impl<C: Context> Randomness<C> {
+    pub(crate) fn commit(
+        &self,
+        hash: Vec<u8>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let hash: [u8; 32] = hash
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("The commitment must be a 32 bytes hash"))?;
+        let commitment = Commitment {
+            hash,
+            slot: self.get_slot(working_set),
+        };
+        self.commitments
+            .set(context.sender(), &commitment, working_set);
+        working_set.emit_event(
+            self,
+            "commit",
+            &format!("{} in slot {}", context.sender(), commitment.slot),
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn reveal(
+        &self,
+        secret: Vec<u8>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let commitment = self
+            .commitments
+            .get(context.sender(), working_set)
+            .ok_or_else(|| {
+                ModuleErrorCode::NotFound
+                    .error(format!("Account {} has no commitment", context.sender()))
+            })?;
+        // Otherwise the secret would be chosen knowing the randomness of the slot
+        if commitment.slot >= self.get_slot(working_set) {
+            return Err(ModuleErrorCode::InvariantViolation
+                .error("A commitment can only be revealed in a later slot"));
+        }
+        if commitment_hash::<C>(context.sender(), &secret) != commitment.hash {
+            return Err(
+                ModuleErrorCode::Unauthorized.error("The secret doesn't match the commitment")
+            );
+        }
+
+        let mut hasher = C::Hasher::new();
+        hasher.update(self.revealed_entropy.get(working_set).unwrap_or_default());
+        hasher.update(&secret);
+        self.revealed_entropy
+            .set(&hasher.finalize().into(), working_set);
+        self.commitments.delete(context.sender(), working_set);
+        working_set.emit_event(self, "reveal", &format!("{}", context.sender()));
+
+        Ok(CallResponse::default())
+    }
+}
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Randomness;
+
+/// Genesis configuration for the [`Randomness`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RandomnessConfig {
+    /// The randomness of the genesis slot, which the randomness of the following slots is
+    /// derived from.
+    #[serde(default)]
+    pub seed: [u8; 32],
+}
+
+impl<C: Context> Randomness<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        self.slot.set(&0, working_set);
+        self.randomness.set(&config.seed, working_set);
+        self.randomness_by_slot.set(&0, &config.seed, working_set);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RandomnessConfig;
+
+    #[test]
+    fn test_config_serialization() {
+        let config = RandomnessConfig { seed: [7; 32] };
+        let data = format!(r#"{{"seed":{:?}}}"#, [7u8; 32]);
+
+        let parsed_config: RandomnessConfig = serde_json::from_str(&data).unwrap();
+        assert_eq!(config, parsed_config);
+        let parsed_config: RandomnessConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(RandomnessConfig::default(), parsed_config);
+    }
+}
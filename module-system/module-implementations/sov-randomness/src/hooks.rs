@@ -0,0 +1,28 @@
+use sov_modules_api::digest::Digest;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{Randomness, RANDOMNESS_HISTORY};
+
+impl<C: Context> Randomness<C> {
+    /// Derives the randomness of the new slot from the randomness of the previous slot, the hash
+    /// of the DA block and the secrets revealed during the previous slot. It must run at the
+    /// beginning of every slot, before the modules reading the randomness.
+    pub fn begin_slot_hook(&self, da_block_hash: [u8; 32], working_set: &mut WorkingSet<C>) {
+        let slot = self.get_slot(working_set) + 1;
+
+        let mut hasher = C::Hasher::new();
+        hasher.update(self.get_randomness(working_set));
+        hasher.update(da_block_hash);
+        hasher.update(self.revealed_entropy.get(working_set).unwrap_or_default());
+        let randomness: [u8; 32] = hasher.finalize().into();
+
+        self.slot.set(&slot, working_set);
+        self.randomness.set(&randomness, working_set);
+        self.randomness_by_slot.set(&slot, &randomness, working_set);
+        if let Some(expired) = slot.checked_sub(RANDOMNESS_HISTORY) {
+            self.randomness_by_slot.delete(&expired, working_set);
+        }
+        self.revealed_entropy.delete(working_set);
+    }
+}
@@ -0,0 +1,133 @@
+//! The `sov-randomness` module is a randomness beacon: it derives a random value for every slot
+//! from the hash of the DA block, and from the secrets revealed by the users who take part in its
+//! commit-reveal scheme.
+//!
+//! The randomness of a slot is the hash of the randomness of the previous slot, of the hash of the
+//! DA block and of the secrets revealed during the previous slot. It's deterministic, so every
+//! node and the zkVM compute the same value, and the modules read it with
+//! [`Randomness::get_randomness`]. It's known as soon as the DA block is, so it must not be used
+//! for decisions the producer of the DA block or the sequencer could profit from biasing.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+mod hooks;
+#[cfg(feature = "native")]
+mod query;
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// The number of past slots the randomness of which is kept in the state, like the block hashes
+/// the EVM `BLOCKHASH` opcode can read.
+pub const RANDOMNESS_HISTORY: u64 = 256;
+
+/// A commitment to a secret, to be revealed in a later slot.
+#[derive(
+    borsh::BorshDeserialize,
+    borsh::BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+pub struct Commitment {
+    /// The hash of the address of the committer followed by the secret.
+    pub hash: [u8; 32],
+    /// The slot in which the commitment was made.
+    pub slot: u64,
+}
+
+/// The `sov-randomness` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct Randomness<C: sov_modules_api::Context> {
+    /// The address of the `sov_randomness` module.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The number of the current slot, counted from genesis.
+    #[state]
+    pub(crate) slot: StateValue<u64>,
+
+    /// The randomness of the current slot.
+    #[state]
+    pub(crate) randomness: StateValue<[u8; 32]>,
+
+    /// Slot number => the randomness of the slot, for the last [`RANDOMNESS_HISTORY`] slots.
+    #[state]
+    pub(crate) randomness_by_slot: StateMap<u64, [u8; 32]>,
+
+    /// The hash of the secrets revealed during the current slot, mixed into the randomness of
+    /// the next slot.
+    #[state]
+    pub(crate) revealed_entropy: StateValue<[u8; 32]>,
+
+    /// Committer => its commitment which wasn't revealed yet.
+    #[state]
+    pub(crate) commitments: StateMap<C::Address, Commitment>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Randomness<C> {
+    type Context = C;
+
+    type Config = RandomnessConfig;
+
+    type CallMessage = CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::Commit { hash } => self.commit(hash, context, working_set)?,
+            CallMessage::Reveal { secret } => self.reveal(secret, context, working_set)?,
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Randomness<C> {
+    /// Returns the number of the current slot, counted from genesis.
+    pub fn get_slot(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.slot.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the randomness of the current slot.
+    pub fn get_randomness(&self, working_set: &mut WorkingSet<C>) -> [u8; 32] {
+        self.randomness.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the randomness of the given slot, if it's one of the last [`RANDOMNESS_HISTORY`]
+    /// slots.
+    pub fn get_randomness_at(
+        &self,
+        slot: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<[u8; 32]> {
+        self.randomness_by_slot.get(&slot, working_set)
+    }
+
+    /// Returns the commitment of `committer` which wasn't revealed yet, if any.
+    pub fn get_commitment(
+        &self,
+        committer: &C::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Commitment> {
+        self.commitments.get(committer, working_set)
+    }
+}
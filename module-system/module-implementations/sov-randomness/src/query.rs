@@ -0,0 +1,34 @@
+//! Defines rpc queries exposed by the randomness module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Randomness;
+
+/// The response type to the `getRandomness` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct RandomnessResponse {
+    /// The requested slot.
+    pub slot: u64,
+    /// The hex encoded randomness of the slot, if it's still kept in the state.
+    pub randomness: Option<String>,
+}
+
+#[rpc_gen(client, server, namespace = "randomness")]
+impl<C: Context> Randomness<C> {
+    /// Returns the randomness of the given slot, or of the current slot if it's missing.
+    #[rpc_method(name = "getRandomness")]
+    pub fn randomness_of_slot(
+        &self,
+        slot: Option<u64>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<RandomnessResponse> {
+        let slot = slot.unwrap_or_else(|| self.get_slot(working_set));
+        Ok(RandomnessResponse {
+            slot,
+            randomness: self
+                .get_randomness_at(slot, working_set)
+                .map(|randomness| format!("0x{}", hex::encode(randomness))),
+        })
+    }
+}
@@ -0,0 +1,149 @@
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{Context, Error, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_randomness::{
+    commitment_hash, CallMessage, Randomness, RandomnessConfig, RANDOMNESS_HISTORY,
+};
+
+type C = DefaultContext;
+
+fn commit_and_reveal(
+    randomness: &Randomness<C>,
+    context: &C,
+    secret: &[u8],
+    working_set: &mut WorkingSet<C>,
+) -> Result<(), Error> {
+    let hash = commitment_hash::<C>(context.sender(), secret).to_vec();
+    randomness.call(CallMessage::Commit { hash }, context, working_set)?;
+    randomness.begin_slot_hook([1; 32], working_set);
+    randomness.call(
+        CallMessage::Reveal {
+            secret: secret.to_vec(),
+        },
+        context,
+        working_set,
+    )?;
+    Ok(())
+}
+
+#[test]
+fn randomness_is_derived_from_the_da_blocks() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let randomness = Randomness::<C>::default();
+    let config = RandomnessConfig { seed: [7; 32] };
+    randomness.genesis(&config, working_set).unwrap();
+    assert_eq!([7; 32], randomness.get_randomness(working_set));
+
+    randomness.begin_slot_hook([1; 32], working_set);
+    let first = randomness.get_randomness(working_set);
+    assert_eq!(1, randomness.get_slot(working_set));
+    assert_ne!([7; 32], first);
+    assert_eq!(Some(first), randomness.get_randomness_at(1, working_set));
+
+    // The same DA blocks always give the same randomness
+    let tmpdir = tempfile::tempdir().unwrap();
+    let other_working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    randomness.genesis(&config, other_working_set).unwrap();
+    randomness.begin_slot_hook([1; 32], other_working_set);
+    assert_eq!(first, randomness.get_randomness(other_working_set));
+
+    // The randomness changes in every slot, even for the same DA block hash
+    randomness.begin_slot_hook([1; 32], working_set);
+    assert_ne!(first, randomness.get_randomness(working_set));
+
+    // Only the last slots are kept
+    for _ in 0..RANDOMNESS_HISTORY {
+        randomness.begin_slot_hook([2; 32], working_set);
+    }
+    assert_eq!(None, randomness.get_randomness_at(1, working_set));
+    assert!(randomness
+        .get_randomness_at(RANDOMNESS_HISTORY + 2, working_set)
+        .is_some());
+}
+
+#[test]
+fn revealed_secrets_are_mixed_into_the_randomness() {
+    let alice = C::new(
+        generate_address::<C>("alice"),
+        generate_address::<C>("seq"),
+        1,
+    );
+    let bob = C::new(
+        generate_address::<C>("bob"),
+        generate_address::<C>("seq"),
+        1,
+    );
+    let randomness = Randomness::<C>::default();
+
+    let mut next_randomness = Vec::new();
+    for secret in [b"first secret", b"other secret"] {
+        let tmpdir = tempfile::tempdir().unwrap();
+        let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+        randomness
+            .genesis(&RandomnessConfig::default(), working_set)
+            .unwrap();
+        commit_and_reveal(&randomness, &alice, secret, working_set).unwrap();
+        assert_eq!(None, randomness.get_commitment(alice.sender(), working_set));
+        randomness.begin_slot_hook([1; 32], working_set);
+        next_randomness.push(randomness.get_randomness(working_set));
+    }
+    assert_ne!(next_randomness[0], next_randomness[1]);
+
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    randomness
+        .genesis(&RandomnessConfig::default(), working_set)
+        .unwrap();
+
+    // Nothing to reveal
+    let Error::NotFound(_) = randomness
+        .call(CallMessage::Reveal { secret: vec![1] }, &alice, working_set)
+        .unwrap_err()
+    else {
+        panic!("Expected a not found error");
+    };
+
+    // A commitment can't be revealed in the same slot
+    let hash = commitment_hash::<C>(alice.sender(), b"secret").to_vec();
+    randomness
+        .call(
+            CallMessage::Commit { hash: hash.clone() },
+            &alice,
+            working_set,
+        )
+        .unwrap();
+    let reveal = CallMessage::Reveal {
+        secret: b"secret".to_vec(),
+    };
+    let Error::InvariantViolation(_) = randomness
+        .call(reveal.clone(), &alice, working_set)
+        .unwrap_err()
+    else {
+        panic!("Expected an invariant violation");
+    };
+    randomness.begin_slot_hook([1; 32], working_set);
+
+    // A copied commitment can't be revealed by another sender
+    randomness
+        .call(CallMessage::Commit { hash }, &bob, working_set)
+        .unwrap();
+    randomness.begin_slot_hook([1; 32], working_set);
+    let Error::Unauthorized(_) = randomness
+        .call(reveal.clone(), &bob, working_set)
+        .unwrap_err()
+    else {
+        panic!("Expected an unauthorized error");
+    };
+    randomness.call(reveal, &alice, working_set).unwrap();
+
+    // Commitments are hashes
+    assert!(randomness
+        .call(
+            CallMessage::Commit { hash: vec![1; 31] },
+            &alice,
+            working_set
+        )
+        .is_err());
+}
@@ -0,0 +1,125 @@
+use anyhow::Result;
+#[cfg(feature = "native")]
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Context, EventEmitter, ModuleErrorCode, WorkingSet};
+
+use crate::{ScheduledCall, Scheduler};
+
+/// This enumeration represents the available call messages for interacting with
+/// the `sov-scheduler` module.
+#[cfg_attr(
+    feature = "native",
+    derive(CliWalletArg),
+    derive(CallMessageBuilder),
+    derive(schemars::JsonSchema),
+    schemars(rename = "CallMessage")
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    derive(serde::Deserialize)
+)]
+#[derive(borsh::BorshDeserialize, borsh::BorshSerialize, Debug, PartialEq, Clone)]
+pub enum CallMessage {
+    /// Schedules a call message of the runtime, to be sent by the sender at the beginning of
+    /// a future slot. The fee of the module is paid upfront.
+    Schedule {
+        /// The slot the call message is executed at.
+        slot: u64,
+        /// The encoded call message of the runtime.
+        message: Vec<u8>,
+    },
+    /// Cancels a call scheduled by the sender, and refunds its fee.
+    Cancel {
+        /// The identifier of the call.
+        call_id: u64,
+    },
+}
+
+impl<C: Context> Scheduler<C> {
+    pub(crate) fn schedule(
+        &self,
+        slot: u64,
+        message: Vec<u8>,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let current_slot = self.get_slot(working_set);
+        let max_delay = self.max_delay.get(working_set).unwrap_or_default();
+        if slot <= current_slot || slot - current_slot > max_delay {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Calls can only be scheduled between slots {} and {}, got {}",
+                current_slot + 1,
+                current_slot.saturating_add(max_delay),
+                slot
+            )));
+        }
+        let mut slot_calls = self.get_calls_at(slot, working_set);
+        let max_calls_per_slot = self.max_calls_per_slot.get(working_set).unwrap_or_default();
+        if slot_calls.len() >= max_calls_per_slot as usize {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Slot {} already has the maximum of {} scheduled calls",
+                slot, max_calls_per_slot
+            )));
+        }
+
+        let fee = self
+            .fee
+            .get(working_set)
+            .ok_or_else(|| anyhow::anyhow!("The fee of the scheduler must be set at genesis"))?;
+        self.bank
+            .transfer_from(context.sender(), &self.address, fee.clone(), working_set)?;
+
+        let call_id = self.next_call_id.get(working_set).unwrap_or_default();
+        let call = ScheduledCall {
+            owner: context.sender().clone(),
+            slot,
+            message,
+            fee,
+        };
+        self.calls.set(&call_id, &call, working_set);
+        slot_calls.push(call_id);
+        self.calls_by_slot.set(&slot, &slot_calls, working_set);
+        self.next_call_id.set(&(call_id + 1), working_set);
+        working_set.emit_event(
+            self,
+            "schedule",
+            &format!("call {} of {} at slot {}", call_id, context.sender(), slot),
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn cancel(
+        &self,
+        call_id: u64,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse> {
+        let call = self.calls.get(&call_id, working_set).ok_or_else(|| {
+            ModuleErrorCode::NotFound.error(format!("Scheduled call {} doesn't exist", call_id))
+        })?;
+        if &call.owner != context.sender() {
+            return Err(ModuleErrorCode::Unauthorized.error(format!(
+                "Account {} didn't schedule call {}",
+                context.sender(),
+                call_id
+            )));
+        }
+
+        self.bank
+            .transfer_from(&self.address, &call.owner, call.fee, working_set)?;
+        self.calls.delete(&call_id, working_set);
+        let mut slot_calls = self.get_calls_at(call.slot, working_set);
+        slot_calls.retain(|id| *id != call_id);
+        if slot_calls.is_empty() {
+            self.calls_by_slot.delete(&call.slot, working_set);
+        } else {
+            self.calls_by_slot.set(&call.slot, &slot_calls, working_set);
+        }
+        working_set.emit_event(self, "cancel", &format!("call {}", call_id));
+
+        Ok(CallResponse::default())
+    }
+}
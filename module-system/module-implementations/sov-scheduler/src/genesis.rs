@@ -0,0 +1,87 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sov_bank::Coins;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::Scheduler;
+
+/// Genesis configuration for the [`Scheduler`] module.
+///
+/// This `struct` must be passed as an argument to
+/// [`Module::genesis`](sov_modules_api::Module::genesis).
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct SchedulerConfig<C: Context> {
+    /// The fee of scheduling a call, paid upfront to the module.
+    pub fee: Coins<C>,
+    /// The maximum number of calls scheduled for the same slot.
+    pub max_calls_per_slot: u32,
+    /// How many slots ahead of the current one a call can be scheduled.
+    pub max_delay: u64,
+    /// The gas funds each scheduled call can spend when it's executed, at the gas price of its
+    /// slot. The fee accounts for them.
+    pub call_gas_limit: u64,
+}
+
+impl<C: Context> Scheduler<C> {
+    pub(crate) fn init_module(
+        &self,
+        config: &<Self as sov_modules_api::Module>::Config,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<()> {
+        if config.max_calls_per_slot == 0 {
+            anyhow::bail!("The maximum number of calls per slot can't be zero");
+        }
+        self.slot.set(&0, working_set);
+        self.fee.set(&config.fee, working_set);
+        self.max_calls_per_slot
+            .set(&config.max_calls_per_slot, working_set);
+        self.max_delay.set(&config.max_delay, working_set);
+        self.call_gas_limit.set(&config.call_gas_limit, working_set);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use sov_bank::Coins;
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::{AddressBech32, Spec};
+
+    use crate::SchedulerConfig;
+
+    #[test]
+    fn test_config_serialization() {
+        let token_address: <DefaultContext as Spec>::Address = AddressBech32::from_str(
+            "sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp",
+        )
+        .unwrap()
+        .into();
+        let config = SchedulerConfig::<DefaultContext> {
+            fee: Coins {
+                amount: 10,
+                token_address,
+            },
+            max_calls_per_slot: 16,
+            max_delay: 1000,
+            call_gas_limit: 100,
+        };
+
+        let data = r#"
+        {
+            "fee":{
+                "amount":10,
+                "token_address":"sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp"
+            },
+            "max_calls_per_slot":16,
+            "max_delay":1000,
+            "call_gas_limit":100
+        }"#;
+
+        let parsed_config: SchedulerConfig<DefaultContext> = serde_json::from_str(data).unwrap();
+        assert_eq!(config, parsed_config);
+    }
+}
@@ -0,0 +1,59 @@
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, DispatchCall, DispatchCheck, Error, EventEmitter, WorkingSet};
+
+use crate::Scheduler;
+
+impl<C: Context> Scheduler<C> {
+    /// Starts a new slot, and executes the calls scheduled for it in the order they were
+    /// scheduled in. It has to be called once per slot, after the other slot hooks of the
+    /// runtime and before the batches of the slot are applied.
+    ///
+    /// The call messages are dispatched by `runtime` on behalf of the accounts which scheduled
+    /// them, along with their deferred calls, if they pass `check`, like the call messages of
+    /// the transactions. Every call can spend gas worth the configured call gas limit at
+    /// `gas_price`. The changes of a failing call are reverted, and the following calls are
+    /// executed anyway. The fees of the executed calls stay in the account of the module.
+    pub fn begin_slot_hook<D: DispatchCall<Context = C>>(
+        &self,
+        runtime: &D,
+        check: &DispatchCheck<'_, D::Decodable, C>,
+        gas_price: &C::GasUnit,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let slot = self.get_slot(working_set) + 1;
+        self.slot.set(&slot, working_set);
+
+        let call_ids = self.get_calls_at(slot, working_set);
+        if call_ids.is_empty() {
+            return;
+        }
+        self.calls_by_slot.delete(&slot, working_set);
+
+        // The gas meter of the hook is restored once the calls are executed
+        let remaining_funds = working_set.gas_remaining_funds();
+        let hook_gas_price = working_set.gas_price().clone();
+        let call_gas_limit = self.get_call_gas_limit(working_set);
+        for call_id in call_ids {
+            let Some(call) = self.calls.get(&call_id, working_set) else {
+                continue;
+            };
+            self.calls.delete(&call_id, working_set);
+
+            let context = C::new(call.owner, self.address.clone(), slot);
+            working_set.set_gas(call_gas_limit, gas_price.clone());
+            let result = working_set.revert_on_error(|working_set| {
+                let message = D::decode_call(&call.message).map_err(|e| {
+                    Error::ModuleError(anyhow::anyhow!("Failed to decode the call message: {}", e))
+                })?;
+                runtime.dispatch_call_and_deferred_checked(message, working_set, &context, check)
+            });
+            let outcome = if result.is_ok() { "executed" } else { "failed" };
+            working_set.emit_event(
+                self,
+                "execute",
+                &format!("call {} {} at slot {}", call_id, outcome, slot),
+            );
+        }
+        working_set.set_gas(remaining_funds, hook_gas_price);
+    }
+}
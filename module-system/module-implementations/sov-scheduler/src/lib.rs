@@ -0,0 +1,152 @@
+//! The `sov-scheduler` module executes call messages of the runtime at future slots, like the
+//! rebalancing of a vault or the maintenance tasks of a protocol.
+//!
+//! An account schedules a call message for a slot, and pays the fee of its execution upfront.
+//! At the beginning of the slot, [`Scheduler::begin_slot_hook`] dispatches the scheduled call
+//! messages through the runtime, as if they were sent by the accounts which scheduled them, in
+//! the order they were scheduled in. A failing call message is reverted without affecting the
+//! others. The number of call messages of a slot is bounded, and so is the gas each of them can
+//! use, so is the work of the hook.
+
+#![deny(missing_docs)]
+mod call;
+mod genesis;
+mod hooks;
+#[cfg(feature = "native")]
+mod query;
+use borsh::{BorshDeserialize, BorshSerialize};
+pub use call::*;
+pub use genesis::*;
+#[cfg(feature = "native")]
+pub use query::*;
+use sov_bank::Coins;
+use sov_modules_api::prelude::*;
+use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+
+/// A call message of the runtime scheduled for a future slot.
+#[derive(
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+)]
+#[serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")]
+pub struct ScheduledCall<C: sov_modules_api::Context> {
+    /// The account which scheduled the call, and sends its call message.
+    pub owner: C::Address,
+    /// The slot the call is executed at.
+    pub slot: u64,
+    /// The encoded call message of the runtime.
+    pub message: Vec<u8>,
+    /// The fee paid upfront, refunded if the call is cancelled.
+    pub fee: Coins<C>,
+}
+
+/// The `sov-scheduler` module `struct`.
+#[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
+#[derive(Clone, ModuleInfo)]
+pub struct Scheduler<C: sov_modules_api::Context> {
+    /// The address of the `sov_scheduler` module, which holds the fees of the scheduled calls.
+    /// Note: this is address is generated by the module framework and the
+    /// corresponding private key is unknown.
+    #[address]
+    pub(crate) address: C::Address,
+
+    /// The number of the current slot, counted from genesis.
+    #[state]
+    pub(crate) slot: StateValue<u64>,
+
+    /// The fee of scheduling a call.
+    #[state]
+    pub(crate) fee: StateValue<Coins<C>>,
+
+    /// The maximum number of calls scheduled for the same slot.
+    #[state]
+    pub(crate) max_calls_per_slot: StateValue<u32>,
+
+    /// How many slots ahead of the current one a call can be scheduled.
+    #[state]
+    pub(crate) max_delay: StateValue<u64>,
+
+    /// The gas funds each scheduled call can spend when it's executed.
+    #[state]
+    pub(crate) call_gas_limit: StateValue<u64>,
+
+    /// The identifier of the next scheduled call.
+    #[state]
+    pub(crate) next_call_id: StateValue<u64>,
+
+    /// Call identifier => the call which wasn't executed yet.
+    #[state]
+    pub(crate) calls: StateMap<u64, ScheduledCall<C>>,
+
+    /// Slot number => the identifiers of the calls scheduled for the slot, in the order they
+    /// were scheduled in.
+    #[state]
+    pub(crate) calls_by_slot: StateMap<u64, Vec<u64>>,
+
+    /// Reference to the Bank module.
+    #[module]
+    pub(crate) bank: sov_bank::Bank<C>,
+}
+
+impl<C: sov_modules_api::Context> sov_modules_api::Module for Scheduler<C> {
+    type Context = C;
+
+    type Config = SchedulerConfig<C>;
+
+    type CallMessage = CallMessage;
+
+    type Event = ();
+
+    fn genesis(&self, config: &Self::Config, working_set: &mut WorkingSet<C>) -> Result<(), Error> {
+        Ok(self.init_module(config, working_set)?)
+    }
+
+    fn call(
+        &self,
+        message: Self::CallMessage,
+        context: &Self::Context,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(match message {
+            CallMessage::Schedule { slot, message } => {
+                self.schedule(slot, message, context, working_set)?
+            }
+            CallMessage::Cancel { call_id } => self.cancel(call_id, context, working_set)?,
+        })
+    }
+}
+
+impl<C: sov_modules_api::Context> Scheduler<C> {
+    /// Returns the number of the current slot, counted from genesis.
+    pub fn get_slot(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.slot.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the call with the given identifier, if it wasn't executed or cancelled yet.
+    pub fn get_call(
+        &self,
+        call_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<ScheduledCall<C>> {
+        self.calls.get(&call_id, working_set)
+    }
+
+    /// Returns the gas funds each scheduled call can spend when it's executed.
+    pub fn get_call_gas_limit(&self, working_set: &mut WorkingSet<C>) -> u64 {
+        self.call_gas_limit.get(working_set).unwrap_or_default()
+    }
+
+    /// Returns the identifiers of the calls scheduled for the given slot, in the order they're
+    /// executed in.
+    pub fn get_calls_at(&self, slot: u64, working_set: &mut WorkingSet<C>) -> Vec<u64> {
+        self.calls_by_slot
+            .get(&slot, working_set)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,42 @@
+//! Defines rpc queries exposed by the scheduler module, along with the relevant types
+use jsonrpsee::core::RpcResult;
+use sov_modules_api::macros::rpc_gen;
+use sov_modules_api::{Context, WorkingSet};
+
+use crate::{ScheduledCall, Scheduler};
+
+/// The response type to the `getCallsAt` RPC method.
+#[derive(Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, Clone)]
+pub struct SlotCallsResponse {
+    /// The requested slot.
+    pub slot: u64,
+    /// The identifiers of the calls scheduled for the slot, in the order they're executed in.
+    pub call_ids: Vec<u64>,
+}
+
+#[rpc_gen(client, server, namespace = "scheduler")]
+impl<C: Context> Scheduler<C> {
+    /// Returns the call with the given identifier, if it wasn't executed or cancelled yet.
+    #[rpc_method(name = "getScheduledCall")]
+    pub fn scheduled_call(
+        &self,
+        call_id: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<Option<ScheduledCall<C>>> {
+        Ok(self.get_call(call_id, working_set))
+    }
+
+    /// Returns the calls scheduled for the given slot, or for the next slot if it's missing.
+    #[rpc_method(name = "getCallsAt")]
+    pub fn calls_at(
+        &self,
+        slot: Option<u64>,
+        working_set: &mut WorkingSet<C>,
+    ) -> RpcResult<SlotCallsResponse> {
+        let slot = slot.unwrap_or_else(|| self.get_slot(working_set) + 1);
+        Ok(SlotCallsResponse {
+            slot,
+            call_ids: self.get_calls_at(slot, working_set),
+        })
+    }
+}
@@ -0,0 +1,270 @@
+use sov_bank::{get_genesis_token_address, Bank, BankConfig, Coins};
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::utils::generate_address;
+use sov_modules_api::{
+    CallResponse, Context, DispatchCall, DispatchCheck, EncodeCall, Error, Genesis, MessageCodec,
+    ModuleErrorCode, ModuleInfo, Spec, WorkingSet,
+};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_scheduler::{CallMessage, Scheduler, SchedulerConfig};
+
+type C = DefaultContext;
+
+#[derive(Genesis, DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct TestRuntime<C: Context> {
+    pub bank: Bank<C>,
+    pub scheduler: Scheduler<C>,
+}
+
+const INITIAL_BALANCE: u64 = 1000;
+const FEE: u64 = 10;
+// The gas of a bank transfer at a gas price of one
+const CALL_GAS_LIMIT: u64 = 10;
+const GAS_PRICE: [u64; 2] = [1, 1];
+
+struct TestSetup {
+    runtime: TestRuntime<C>,
+    token: <C as Spec>::Address,
+    alice: <C as Spec>::Address,
+    bob: <C as Spec>::Address,
+}
+
+impl TestSetup {
+    fn init(working_set: &mut WorkingSet<C>) -> Self {
+        let runtime = TestRuntime::<C>::default();
+        let alice = generate_address::<C>("alice");
+        let bob = generate_address::<C>("bob");
+        let token = get_genesis_token_address::<C>("sov-test-token", 0);
+
+        let bank_config = BankConfig::with_token(
+            "sov-test-token",
+            vec![(alice, INITIAL_BALANCE), (bob, INITIAL_BALANCE)],
+        );
+        let scheduler_config = SchedulerConfig {
+            fee: Coins {
+                amount: FEE,
+                token_address: token,
+            },
+            max_calls_per_slot: 2,
+            max_delay: 10,
+            call_gas_limit: CALL_GAS_LIMIT,
+        };
+        runtime
+            .genesis(
+                &GenesisConfig::new(bank_config, scheduler_config),
+                working_set,
+            )
+            .unwrap();
+
+        Self {
+            runtime,
+            token,
+            alice,
+            bob,
+        }
+    }
+
+    fn call(
+        &self,
+        sender: <C as Spec>::Address,
+        message: CallMessage,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message = <TestRuntime<C> as EncodeCall<Scheduler<C>>>::encode_call(message);
+        let message = TestRuntime::<C>::decode_call(&message).unwrap();
+        let context = C::new(sender, sender, 1);
+        self.runtime
+            .dispatch_call_and_deferred(message, working_set, &context)
+    }
+
+    fn schedule_transfer(
+        &self,
+        sender: <C as Spec>::Address,
+        slot: u64,
+        to: <C as Spec>::Address,
+        amount: u64,
+        working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        let message =
+            <TestRuntime<C> as EncodeCall<Bank<C>>>::encode_call(sov_bank::CallMessage::Transfer {
+                to,
+                coins: Coins {
+                    amount,
+                    token_address: self.token,
+                },
+            });
+        self.call(sender, CallMessage::Schedule { slot, message }, working_set)
+    }
+
+    fn begin_slot(&self, working_set: &mut WorkingSet<C>) {
+        self.begin_slot_with(&|_, _| Ok(()), &GAS_PRICE, working_set);
+    }
+
+    fn begin_slot_with(
+        &self,
+        check: &DispatchCheck<'_, <TestRuntime<C> as DispatchCall>::Decodable, C>,
+        gas_price: &<C as Spec>::GasUnit,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        self.runtime
+            .scheduler
+            .begin_slot_hook(&self.runtime, check, gas_price, working_set);
+    }
+
+    fn execute_events(&self, working_set: &mut WorkingSet<C>) -> Vec<String> {
+        working_set
+            .events()
+            .iter()
+            .filter(|event| event.key().inner() == b"execute")
+            .map(|event| String::from_utf8(event.value().inner().clone()).unwrap())
+            .collect()
+    }
+
+    fn balance(&self, account: <C as Spec>::Address, working_set: &mut WorkingSet<C>) -> u64 {
+        self.runtime
+            .bank
+            .balance_or_zero(account, self.token, working_set)
+    }
+}
+
+#[test]
+fn scheduled_calls_are_executed_at_their_slot() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    let recipient = generate_address::<C>("recipient");
+
+    setup
+        .schedule_transfer(setup.alice, 2, recipient, 100, working_set)
+        .expect("Schedule call failed");
+    // Bob can't send more than his balance, so his call fails without affecting Alice's
+    setup
+        .schedule_transfer(setup.bob, 2, recipient, INITIAL_BALANCE, working_set)
+        .expect("Schedule call failed");
+    assert_eq!(
+        INITIAL_BALANCE - FEE,
+        setup.balance(setup.alice, working_set)
+    );
+    let module_address = setup.runtime.scheduler.address();
+    assert_eq!(2 * FEE, setup.balance(*module_address, working_set));
+    assert_eq!(
+        vec![0, 1],
+        setup.runtime.scheduler.get_calls_at(2, working_set)
+    );
+
+    setup.begin_slot(working_set);
+    assert_eq!(0, setup.balance(recipient, working_set));
+
+    setup.begin_slot(working_set);
+    assert_eq!(100, setup.balance(recipient, working_set));
+    assert_eq!(
+        INITIAL_BALANCE - FEE - 100,
+        setup.balance(setup.alice, working_set)
+    );
+    assert_eq!(INITIAL_BALANCE - FEE, setup.balance(setup.bob, working_set));
+    assert!(setup.runtime.scheduler.get_call(0, working_set).is_none());
+    assert!(setup
+        .runtime
+        .scheduler
+        .get_calls_at(2, working_set)
+        .is_empty());
+    assert_eq!(
+        vec!["call 0 executed at slot 2", "call 1 failed at slot 2"],
+        setup.execute_events(working_set)
+    );
+}
+
+#[test]
+fn scheduled_calls_are_checked_and_metered() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    let recipient = generate_address::<C>("recipient");
+
+    for slot in [1, 2, 3] {
+        setup
+            .schedule_transfer(setup.alice, slot, recipient, 100, working_set)
+            .expect("Schedule call failed");
+    }
+
+    // The call is rejected like a transaction to a paused module
+    setup.begin_slot_with(
+        &|_, _| {
+            Err(ModuleErrorCode::Unauthorized
+                .error("Module is paused")
+                .into())
+        },
+        &GAS_PRICE,
+        working_set,
+    );
+    // The transfer costs twice the gas limit of the call
+    setup.begin_slot_with(&|_, _| Ok(()), &[2, 2], working_set);
+    assert_eq!(0, setup.balance(recipient, working_set));
+
+    setup.begin_slot(working_set);
+    assert_eq!(100, setup.balance(recipient, working_set));
+    assert_eq!(
+        vec![
+            "call 0 failed at slot 1",
+            "call 1 failed at slot 2",
+            "call 2 executed at slot 3"
+        ],
+        setup.execute_events(working_set)
+    );
+}
+
+#[test]
+fn invalid_schedules_and_cancels_fail() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let setup = TestSetup::init(working_set);
+    setup.begin_slot(working_set);
+
+    // Calls are scheduled for the next slots, up to the maximum delay
+    for slot in [0, 1, 12] {
+        let Error::InvariantViolation(_) = setup
+            .schedule_transfer(setup.alice, slot, setup.bob, 1, working_set)
+            .unwrap_err()
+        else {
+            panic!("Expected an invariant violation");
+        };
+    }
+    for _ in 0..2 {
+        setup
+            .schedule_transfer(setup.alice, 11, setup.bob, 1, working_set)
+            .expect("Schedule call failed");
+    }
+    let Error::InvariantViolation(_) = setup
+        .schedule_transfer(setup.alice, 11, setup.bob, 1, working_set)
+        .unwrap_err()
+    else {
+        panic!("The slot is full");
+    };
+
+    // Only the account which scheduled a call can cancel it, and gets its fee back
+    let Error::Unauthorized(_) = setup
+        .call(setup.bob, CallMessage::Cancel { call_id: 0 }, working_set)
+        .unwrap_err()
+    else {
+        panic!("Expected an unauthorized error");
+    };
+    setup
+        .call(setup.alice, CallMessage::Cancel { call_id: 0 }, working_set)
+        .expect("Cancel call failed");
+    assert_eq!(
+        INITIAL_BALANCE - FEE,
+        setup.balance(setup.alice, working_set)
+    );
+    assert_eq!(
+        vec![1],
+        setup.runtime.scheduler.get_calls_at(11, working_set)
+    );
+    let Error::NotFound(_) = setup
+        .call(setup.alice, CallMessage::Cancel { call_id: 0 }, working_set)
+        .unwrap_err()
+    else {
+        panic!("Expected a not found error");
+    };
+}
@@ -1,14 +1,19 @@
-use anyhow::bail;
+use sov_bank::{Amount, Coins};
 #[cfg(feature = "native")]
-use sov_modules_api::macros::CliWalletArg;
+use sov_modules_api::macros::{CallMessageBuilder, CliWalletArg};
 use sov_modules_api::prelude::*;
-use sov_modules_api::{CallResponse, WorkingSet};
+use sov_modules_api::{CallResponse, ModuleErrorCode, WorkingSet};
 
-use crate::SequencerRegistry;
+use crate::{SequencerRegistry, UnbondingInfo};
 
 /// This enumeration represents the available call messages for interacting with
 /// the `sov-sequencer-registry` module.
-#[cfg_attr(feature = "native", derive(schemars::JsonSchema), derive(CliWalletArg))]
+#[cfg_attr(
+    feature = "native",
+    derive(schemars::JsonSchema),
+    derive(CliWalletArg),
+    derive(CallMessageBuilder)
+)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize),
@@ -21,57 +26,123 @@ use crate::SequencerRegistry;
 #[derive(Debug, PartialEq, Clone, borsh::BorshSerialize, borsh::BorshDeserialize)]
 pub enum CallMessage {
     /// Add a new sequencer to the sequencer registry.
+    /// Anyone can register, as long as they bond at least the minimum stake.
     Register {
         /// The raw Da address of the sequencer you're registering.
         da_address: Vec<u8>,
+        /// The amount of tokens to bond, which can't be less than the minimum stake.
+        amount: Amount,
     },
-    /// Remove a sequencer from the sequencer registry.
-    Exit {
+    /// Remove a sequencer from the sequencer registry and start unbonding its stake.
+    /// The stake can be withdrawn once the unbonding period has passed.
+    InitiateExit {
         /// The raw Da address of the sequencer you're removing.
         da_address: Vec<u8>,
     },
+    /// Withdraw the stake of an exited sequencer after the unbonding period.
+    Withdraw {
+        /// The raw Da address of the exited sequencer.
+        da_address: Vec<u8>,
+    },
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
     pub(crate) fn register(
         &self,
         da_address: &Da::Address,
+        amount: Amount,
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<CallResponse> {
         let sequencer = context.sender();
-        self.register_sequencer(da_address, sequencer, working_set)?;
+        self.register_sequencer(da_address, sequencer, amount, working_set)?;
         Ok(CallResponse::default())
     }
 
-    pub(crate) fn exit(
+    pub(crate) fn initiate_exit(
         &self,
         da_address: &Da::Address,
         context: &C,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<CallResponse> {
-        let locker = &self.address;
-        let coins = self.coins_to_lock.get_or_err(working_set)?;
         let sequencer = context.sender();
 
         let belongs_to = self
             .allowed_sequencers
-            .get_or_err(da_address, working_set)?;
+            .get_or_err(da_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))?;
 
         if sequencer != &belongs_to {
-            bail!("Unauthorized exit attempt");
+            return Err(ModuleErrorCode::Unauthorized.error("Unauthorized exit attempt"));
         }
 
+        let amount = self.stakes.get_or_err(da_address, working_set)?;
         self.delete(da_address, working_set);
 
-        self.bank
-            .transfer_from(locker, sequencer, coins, working_set)?;
+        self.unbonding_sequencers.set(
+            da_address,
+            &UnbondingInfo {
+                rollup_address: belongs_to,
+                amount,
+                unbonding_initiated_height: context.slot_height(),
+            },
+            working_set,
+        );
+
+        Ok(CallResponse::default())
+    }
+
+    pub(crate) fn withdraw(
+        &self,
+        da_address: &Da::Address,
+        context: &C,
+        working_set: &mut WorkingSet<C>,
+    ) -> anyhow::Result<CallResponse> {
+        let locker = &self.address;
+        let sequencer = context.sender();
+
+        let unbonding_info = self
+            .unbonding_sequencers
+            .get_or_err(da_address, working_set)
+            .map_err(|e| ModuleErrorCode::NotFound.error(e))?;
+
+        if sequencer != &unbonding_info.rollup_address {
+            return Err(ModuleErrorCode::Unauthorized.error("Unauthorized withdraw attempt"));
+        }
+
+        let unbonding_period = self.unbonding_period.get_or_err(working_set)?;
+        let unbonded_at = unbonding_info
+            .unbonding_initiated_height
+            .saturating_add(unbonding_period);
+        if context.slot_height() < unbonded_at {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "Unbonding period is not over, the stake can be withdrawn at slot {}",
+                unbonded_at
+            )));
+        }
+
+        self.unbonding_sequencers.delete(da_address, working_set);
+
+        let token_address = self.coins_to_lock.get_or_err(working_set)?.token_address;
+        self.bank.transfer_from(
+            locker,
+            sequencer,
+            Coins {
+                amount: unbonding_info.amount,
+                token_address,
+            },
+            working_set,
+        )?;
 
         Ok(CallResponse::default())
     }
 
+    /// Removes the sequencer from the registry. Its stake stays locked in the module,
+    /// so calling this without recording an unbonding entry forfeits the stake.
     pub(crate) fn delete(&self, da_address: &Da::Address, working_set: &mut WorkingSet<C>) {
         self.allowed_sequencers.delete(da_address, working_set);
+        self.stakes.delete(da_address, working_set);
+        self.remove_sequencer_order(da_address, working_set);
 
         if let Some(preferred_sequencer) = self.preferred_sequencer.get(working_set) {
             if da_address == &preferred_sequencer {
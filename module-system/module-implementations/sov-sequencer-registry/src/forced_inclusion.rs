@@ -0,0 +1,183 @@
+use borsh::BorshDeserialize;
+use sov_modules_api::compression::decompress_blob;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::macros::config_constant;
+use sov_modules_api::prelude::*;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{BlobReaderTrait, Context, DaSpec, Spec, WorkingSet};
+use tracing::{info, warn};
+
+use crate::SequencerRegistry;
+
+/// The number of slots after the one in which a forced transaction was posted on DA,
+/// during which a sequencer has to include it in a batch.
+#[config_constant]
+pub const FORCED_INCLUSION_SLOTS_COUNT: u64;
+
+impl<C: Context, Da: DaSpec> SequencerRegistry<C, Da> {
+    /// Adds the transactions of a blob posted on DA by a non-sequencer to the forced inclusion queue.
+    /// The blob is expected to be encoded like a sequencer batch. Entries which are not correctly
+    /// signed transactions are discarded, a sequencer can't be expected to include them.
+    pub fn enqueue_forced_transactions(
+        &self,
+        blob: &mut Da::BlobTransaction,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let txs = match deserialize_batch(blob) {
+            Ok(txs) => txs,
+            Err(e) => {
+                info!(
+                    "Blob hash=0x{} from sender {} is not a valid batch and is going to be discarded: {}",
+                    hex::encode(blob.hash()),
+                    blob.sender(),
+                    e
+                );
+                return;
+            }
+        };
+
+        let deadline = slot_height.saturating_add(FORCED_INCLUSION_SLOTS_COUNT);
+        let mut due = self
+            .forced_transactions_by_deadline
+            .get(&deadline, working_set)
+            .unwrap_or_default();
+        for tx in txs {
+            let tx_hash: [u8; 32] = <C as Spec>::Hasher::digest(&tx).into();
+            if let Err(e) = Transaction::<C>::try_from_slice(&tx)
+                .map_err(anyhow::Error::from)
+                .and_then(|tx| tx.verify())
+            {
+                info!(
+                    "Forced transaction 0x{} is not a valid transaction and is going to be discarded: {}",
+                    hex::encode(tx_hash),
+                    e
+                );
+                continue;
+            }
+            // The transaction is already waiting for an earlier deadline
+            if self
+                .forced_transactions
+                .get(&tx_hash, working_set)
+                .is_some()
+            {
+                continue;
+            }
+            info!(
+                "Forced transaction 0x{} has to be included by slot {}",
+                hex::encode(tx_hash),
+                deadline
+            );
+            self.forced_transactions
+                .set(&tx_hash, &deadline, working_set);
+            due.push(tx_hash);
+        }
+        self.forced_transactions_by_deadline
+            .set(&deadline, &due, working_set);
+    }
+
+    /// Removes the transactions of a sequencer batch from the forced inclusion queue.
+    pub(crate) fn mark_forced_transactions_included(
+        &self,
+        blob: &mut Da::BlobTransaction,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        // Sequencers posting malformed batches are slashed when the batch is applied
+        let Ok(txs) = deserialize_batch(blob) else {
+            return;
+        };
+
+        for tx in txs {
+            let tx_hash: [u8; 32] = <C as Spec>::Hasher::digest(&tx).into();
+            self.forced_transactions.delete(&tx_hash, working_set);
+        }
+    }
+
+    /// Enforces the forced inclusion deadlines. It has to be called once per slot,
+    /// before any blob of the slot is applied.
+    ///
+    /// If any forced transaction had to be included before the slot at `slot_height` and wasn't,
+    /// the preferred sequencer is considered to be censoring it and gets slashed. Only entries
+    /// which were correctly signed transactions are ever queued, invalid ones have no deadline.
+    pub fn enforce_forced_inclusion_deadlines(
+        &self,
+        slot_height: u64,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let Some(deadline) = slot_height.checked_sub(1) else {
+            return;
+        };
+
+        let due = self
+            .forced_transactions_by_deadline
+            .remove(&deadline, working_set)
+            .unwrap_or_default();
+        let mut censored = 0;
+        for tx_hash in due {
+            if self
+                .forced_transactions
+                .remove(&tx_hash, working_set)
+                .is_some()
+            {
+                censored += 1;
+            }
+        }
+
+        if censored == 0 {
+            return;
+        }
+
+        match self.get_preferred_sequencer(working_set) {
+            Some(preferred_sequencer) => {
+                warn!(
+                    "Preferred sequencer {} didn't include {} forced transactions by slot {} and is slashed",
+                    preferred_sequencer, censored, deadline
+                );
+                self.delete(&preferred_sequencer, working_set);
+            }
+            None => {
+                info!(
+                    "{} forced transactions were not included by slot {} and are dropped",
+                    censored, deadline
+                );
+            }
+        }
+    }
+
+    /// Returns the last slot in which the forced transaction with the given hash has to be included,
+    /// or [`None`] if it isn't waiting for inclusion.
+    pub fn get_forced_transaction_deadline(
+        &self,
+        tx_hash: &[u8; 32],
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<u64> {
+        self.forced_transactions.get(tx_hash, working_set)
+    }
+}
+
+/// Deserializes the transactions of a blob encoded like a sequencer batch, which might be compressed.
+fn deserialize_batch(blob: &mut impl BlobReaderTrait) -> anyhow::Result<Vec<Vec<u8>>> {
+    let txs = decompress_blob(data_for_deserialization(blob))
+        .map_err(anyhow::Error::from)
+        .and_then(|data| Ok(Vec::<Vec<u8>>::try_from_slice(&data)?));
+    // If the deserialization fails, we need to make sure it's not because the prover was malicious
+    // and left out some relevant data, like `StfBlueprint` does for the batches it applies.
+    if txs.is_err() {
+        assert_eq!(
+            blob.verified_data().len(),
+            blob.total_len(),
+            "Batch deserialization failed and some data was not provided. The prover might be malicious"
+        );
+    }
+    txs
+}
+
+#[cfg(feature = "native")]
+fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
+    blob.full_data()
+}
+
+#[cfg(not(feature = "native"))]
+fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
+    blob.verified_data()
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use sov_modules_api::prelude::*;
 use sov_modules_api::WorkingSet;
 
-use crate::SequencerRegistry;
+use crate::{RotationConfig, SequencerRegistry};
 
 /// Genesis configuration for the [`SequencerRegistry`] module.
 ///
@@ -27,13 +27,43 @@ pub struct SequencerConfig<C: sov_modules_api::Context, Da: sov_modules_api::DaS
     ///
     /// Only sequencers that are [`SequencerRegistry::is_sender_allowed`] list are
     /// allowed to exit.
+    ///
+    /// The amount is also the minimum stake of the sequencers registering after genesis.
     pub coins_to_lock: sov_bank::Coins<C>,
+    /// The number of slots an exiting sequencer has to wait before it can withdraw its stake.
+    pub unbonding_period: u64,
     /// Determines whether this sequencer is *regular* or *preferred*.
     ///
     /// Batches from the preferred sequencer are always processed first in
     /// block, which means the preferred sequencer can guarantee soft
     /// confirmation time for transactions.
     pub is_preferred_sequencer: bool,
+    /// Makes the preferred sequencer role rotate among the bonded sequencers, if set.
+    ///
+    /// The genesis sequencer is the preferred sequencer of the first epoch,
+    /// regardless of [`SequencerConfig::is_preferred_sequencer`].
+    #[serde(default)]
+    pub rotation: Option<RotationConfig>,
+    /// Burns the base fee of the transactions, instead of rewarding it to the sequencers
+    /// along with the priority tips.
+    #[serde(default)]
+    pub burn_base_fee: bool,
+}
+
+impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerConfig<C, Da> {
+    /// Checks the configuration without writing any state, and returns all the errors found.
+    /// Every error starts with the field it was found in.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.coins_to_lock.amount == 0 {
+            errors
+                .push("coins_to_lock.amount: the sequencers must lock a positive bond".to_owned());
+        }
+        if let Some(Err(error)) = self.rotation.as_ref().map(RotationConfig::validate) {
+            errors.push(format!("rotation: {}", error));
+        }
+        errors
+    }
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
@@ -43,15 +73,24 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
         working_set: &mut WorkingSet<C>,
     ) -> Result<()> {
         self.coins_to_lock.set(&config.coins_to_lock, working_set);
+        self.unbonding_period
+            .set(&config.unbonding_period, working_set);
+        self.burn_base_fee.set(&config.burn_base_fee, working_set);
         self.register_sequencer(
             &config.seq_da_address,
             &config.seq_rollup_address,
+            config.coins_to_lock.amount,
             working_set,
         )?;
-        if config.is_preferred_sequencer {
+        if config.is_preferred_sequencer || config.rotation.is_some() {
             self.preferred_sequencer
                 .set(&config.seq_da_address, working_set);
         }
+        if let Some(rotation) = &config.rotation {
+            rotation.validate()?;
+            self.rotation.set(rotation, working_set);
+            self.preferred_sequencer_last_activity.set(&0, working_set);
+        }
 
         Ok(())
     }
@@ -66,7 +105,7 @@ mod tests {
     use sov_modules_api::default_context::DefaultContext;
     use sov_modules_api::{AddressBech32, Spec};
 
-    use crate::SequencerConfig;
+    use crate::{RotationConfig, SequencerConfig};
 
     #[test]
     fn test_config_serialization() {
@@ -96,7 +135,10 @@ mod tests {
             seq_rollup_address,
             seq_da_address: seq_da_addreess,
             coins_to_lock: coins,
+            unbonding_period: 100,
             is_preferred_sequencer: true,
+            rotation: None,
+            burn_base_fee: false,
         };
 
         let data = r#"
@@ -107,6 +149,7 @@ mod tests {
                 "amount":50,
                 "token_address":"sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp"
             },
+            "unbonding_period":100,
             "is_preferred_sequencer":true
         }"#;
 
@@ -114,4 +157,62 @@ mod tests {
             serde_json::from_str(data).unwrap();
         assert_eq!(config, parsed_config)
     }
+
+    #[test]
+    fn test_rotation_config_serialization() {
+        let data = r#"
+        {
+            "seq_rollup_address":"sov1l6n2cku82yfqld30lanm2nfw43n2auc8clw7r5u5m6s7p8jrm4zqrr8r94",
+            "seq_da_address":"0000000000000000000000000000000000000000000000000000000000000000",
+            "coins_to_lock":{
+                "amount":50,
+                "token_address":"sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp"
+            },
+            "unbonding_period":100,
+            "is_preferred_sequencer":false,
+            "rotation":{
+                "epoch_length":20,
+                "liveness_timeout":3
+            }
+        }"#;
+
+        let parsed_config: SequencerConfig<DefaultContext, MockDaSpec> =
+            serde_json::from_str(data).unwrap();
+        assert_eq!(
+            Some(RotationConfig {
+                epoch_length: 20,
+                liveness_timeout: 3,
+            }),
+            parsed_config.rotation
+        );
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let data = r#"
+        {
+            "seq_rollup_address":"sov1l6n2cku82yfqld30lanm2nfw43n2auc8clw7r5u5m6s7p8jrm4zqrr8r94",
+            "seq_da_address":"0000000000000000000000000000000000000000000000000000000000000000",
+            "coins_to_lock":{
+                "amount":0,
+                "token_address":"sov1zsnx7n2wjvtkr0ttscfgt06pjca3v2e6stxeu49qwynavmk7a8xqlxkkjp"
+            },
+            "unbonding_period":100,
+            "is_preferred_sequencer":false,
+            "rotation":{
+                "epoch_length":0,
+                "liveness_timeout":3
+            }
+        }"#;
+
+        let parsed_config: SequencerConfig<DefaultContext, MockDaSpec> =
+            serde_json::from_str(data).unwrap();
+        assert_eq!(
+            vec![
+                "coins_to_lock.amount: the sequencers must lock a positive bond".to_owned(),
+                "rotation: The epoch length of the sequencer rotation can't be zero".to_owned(),
+            ],
+            parsed_config.validate()
+        );
+    }
 }
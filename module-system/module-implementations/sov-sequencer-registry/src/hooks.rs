@@ -1,4 +1,5 @@
 use sov_modules_api::hooks::ApplyBlobHooks;
+use sov_modules_api::prelude::*;
 use sov_modules_api::{BlobReaderTrait, Context, WorkingSet};
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
@@ -24,6 +25,8 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ApplyBlobHooks<Da::BlobTransaction
         if !self.is_sender_allowed(&blob.sender(), working_set) {
             anyhow::bail!("sender {} is not allowed to submit blobs", blob.sender());
         }
+        self.current_sequencer.set(&blob.sender(), working_set);
+        self.mark_forced_transactions_included(blob, working_set);
         #[cfg(all(target_os = "zkvm", feature = "bench"))]
         print_cycle_count();
         Ok(())
@@ -35,7 +38,8 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ApplyBlobHooks<Da::BlobTransaction
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
         match result {
-            SequencerOutcome::Completed => (),
+            SequencerOutcome::Completed => self.distribute_fees(working_set)?,
+            // The fees collected from a slashed sequencer's blob go to the next sequencer
             SequencerOutcome::Slashed { sequencer } => {
                 self.delete(&sequencer, working_set);
             }
@@ -43,3 +47,22 @@ impl<C: Context, Da: sov_modules_api::DaSpec> ApplyBlobHooks<Da::BlobTransaction
         Ok(())
     }
 }
+
+impl<C: Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
+    /// Rewards the gas fees collected by the bank to the sequencer of the current blob, or burns
+    /// their base fee if the registry is configured to. The fees stay collected if the sequencer
+    /// exited in its own blob.
+    fn distribute_fees(&self, working_set: &mut WorkingSet<C>) -> anyhow::Result<()> {
+        let Some(rollup_address) = self
+            .current_sequencer
+            .get(working_set)
+            .and_then(|sequencer| self.allowed_sequencers.get(&sequencer, working_set))
+        else {
+            return Ok(());
+        };
+        let burn_base_fee = self.burn_base_fee.get(working_set).unwrap_or_default();
+        self.bank
+            .distribute_fees(&rollup_address, burn_base_fee, working_set)?;
+        Ok(())
+    }
+}
@@ -1,24 +1,55 @@
 //! The `sov-sequencer-registry` module is responsible for sequencer
-//! registration, slashing, and rewards. At the moment, only a centralized
-//! sequencer is supported. The sequencer's address and bond are registered
-//! during the rollup deployment.
+//! registration, slashing, and rewards. A sequencer is registered during the
+//! rollup deployment, and anyone can register as a sequencer afterwards by
+//! bonding at least the minimum stake. Exiting sequencers can withdraw their
+//! stake once the unbonding period, measured in slots, has passed.
 //!
 //! The module implements the [`sov_modules_api::hooks::ApplyBlobHooks`] trait.
+//!
+//! Users can also post transactions directly on DA. These forced transactions have to be
+//! included by a sequencer within [`FORCED_INCLUSION_SLOTS_COUNT`] slots, otherwise the
+//! preferred sequencer is slashed for censoring them.
+//!
+//! The preferred sequencer role can rotate among the bonded sequencers, see [`RotationConfig`].
 
 #![deny(missing_docs)]
 mod call;
+mod forced_inclusion;
 mod genesis;
 mod hooks;
 #[cfg(feature = "native")]
 mod query;
+mod rotation;
+use borsh::{BorshDeserialize, BorshSerialize};
 pub use call::*;
+pub use forced_inclusion::FORCED_INCLUSION_SLOTS_COUNT;
 pub use genesis::*;
 #[cfg(feature = "native")]
 pub use query::*;
+pub use rotation::RotationConfig;
+use sov_bank::Amount;
 use sov_modules_api::prelude::*;
-use sov_modules_api::{CallResponse, Error, ModuleInfo, StateMap, StateValue, WorkingSet};
+use sov_modules_api::{
+    CallResponse, Error, ModuleErrorCode, ModuleInfo, StateMap, StateValue, WorkingSet,
+};
 use sov_state::codec::BcsCodec;
 
+/// The stake of a sequencer which has initiated its exit and waits for the unbonding period to pass.
+#[cfg_attr(
+    feature = "native",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "C::Address: serde::Serialize + serde::de::DeserializeOwned")
+)]
+#[derive(BorshDeserialize, BorshSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct UnbondingInfo<C: sov_modules_api::Context> {
+    /// The rollup address which receives the stake once it's withdrawn.
+    pub rollup_address: C::Address,
+    /// The amount of tokens being unbonded.
+    pub amount: Amount,
+    /// The slot height at which the sequencer initiated its exit.
+    pub unbonding_initiated_height: u64,
+}
+
 /// The `sov-sequencer-registry` module `struct`.
 #[cfg_attr(feature = "native", derive(sov_modules_api::ModuleCallJsonSchema))]
 #[derive(Clone, ModuleInfo)]
@@ -33,6 +64,10 @@ pub struct SequencerRegistry<C: sov_modules_api::Context, Da: sov_modules_api::D
     #[module]
     pub(crate) bank: sov_bank::Bank<C>,
 
+    /// Reference to the Governance module, which can govern the minimum stake.
+    #[module]
+    pub(crate) governance: sov_governance::Governance<C>,
+
     /// Only batches from sequencers from this list are going to be processed.
     #[state]
     pub(crate) allowed_sequencers: StateMap<Da::Address, C::Address, BcsCodec>,
@@ -43,16 +78,57 @@ pub struct SequencerRegistry<C: sov_modules_api::Context, Da: sov_modules_api::D
     #[state]
     pub(crate) preferred_sequencer: StateValue<Da::Address, BcsCodec>,
 
-    /// Coin's that will be slashed if the sequencer is malicious.
-    /// The coins will be transferred from
-    /// [`SequencerConfig::seq_rollup_address`] to
-    /// [`SequencerRegistry::address`] and locked forever, until sequencer
-    /// decides to exit (unregister).
+    /// The minimum stake a sequencer has to bond, and the token it's denominated in.
+    /// The governed [`sov_governance::SEQUENCER_BOND`] overrides the amount, if it's set.
+    /// The stake will be transferred from the sequencer's rollup address to
+    /// [`SequencerRegistry::address`] and locked, until the sequencer
+    /// decides to exit (unregister) and the unbonding period passes.
     ///
     /// Only sequencers in the [`SequencerRegistry::allowed_sequencers`] list are
     /// allowed to exit.
     #[state]
     pub(crate) coins_to_lock: StateValue<sov_bank::Coins<C>>,
+
+    /// The amount bonded by each registered sequencer, which is slashed if the sequencer is malicious.
+    #[state]
+    pub(crate) stakes: StateMap<Da::Address, Amount, BcsCodec>,
+
+    /// The number of slots an exiting sequencer has to wait before withdrawing its stake.
+    #[state]
+    pub(crate) unbonding_period: StateValue<u64>,
+
+    /// Sequencers which have initiated their exit, but haven't withdrawn their stake yet.
+    #[state]
+    pub(crate) unbonding_sequencers: StateMap<Da::Address, UnbondingInfo<C>>,
+
+    /// Transactions posted on DA by users, which have to be included by a sequencer.
+    /// Transaction hash => the last slot in which it can be included.
+    #[state]
+    pub(crate) forced_transactions: StateMap<[u8; 32], u64>,
+
+    /// Slot height => hashes of the forced transactions which have to be included by that slot.
+    #[state]
+    pub(crate) forced_transactions_by_deadline: StateMap<u64, Vec<[u8; 32]>>,
+
+    /// The rotation of the preferred sequencer role, if it's enabled.
+    #[state]
+    pub(crate) rotation: StateValue<RotationConfig>,
+
+    /// The bonded sequencers, in the order they take the preferred role.
+    #[state]
+    pub(crate) sequencer_order: StateValue<Vec<Da::Address>, BcsCodec>,
+
+    /// The last slot in which the preferred sequencer posted a batch, or got the preferred role.
+    #[state]
+    pub(crate) preferred_sequencer_last_activity: StateValue<u64>,
+
+    /// The sequencer of the blob being applied, which is rewarded the gas fees once it completes.
+    #[state]
+    pub(crate) current_sequencer: StateValue<Da::Address, BcsCodec>,
+
+    /// Whether the base fee of the transactions is burned instead of rewarded to the sequencers.
+    #[state]
+    pub(crate) burn_base_fee: StateValue<bool>,
 }
 
 /// Result of applying a blob, from sequencer's point of view.
@@ -89,28 +165,63 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> sov_modules_api::
         working_set: &mut WorkingSet<C>,
     ) -> Result<CallResponse, Error> {
         Ok(match message {
-            CallMessage::Register { da_address } => {
+            CallMessage::Register { da_address, amount } => {
                 let da_address = Da::Address::try_from(&da_address)?;
-                self.register(&da_address, context, working_set)?
+                self.register(&da_address, amount, context, working_set)?
             }
-            CallMessage::Exit { da_address } => {
+            CallMessage::InitiateExit { da_address } => {
                 let da_address = Da::Address::try_from(&da_address)?;
-                self.exit(&da_address, context, working_set)?
+                self.initiate_exit(&da_address, context, working_set)?
+            }
+            CallMessage::Withdraw { da_address } => {
+                let da_address = Da::Address::try_from(&da_address)?;
+                self.withdraw(&da_address, context, working_set)?
             }
         })
     }
 }
 
 impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry<C, Da> {
-    /// Returns the configured amount of [`Coins`](sov_bank::Coins) to lock.
+    /// Returns the minimum amount of [`Coins`](sov_bank::Coins) to lock: the governed
+    /// [`sov_governance::SEQUENCER_BOND`] if it's set, the configured amount otherwise.
     pub fn get_coins_to_lock(&self, working_set: &mut WorkingSet<C>) -> Option<sov_bank::Coins<C>> {
-        self.coins_to_lock.get(working_set)
+        let mut coins = self.coins_to_lock.get(working_set)?;
+        coins.amount = self.governance.get_parameter_or(
+            sov_governance::SEQUENCER_BOND,
+            coins.amount,
+            working_set,
+        );
+        Some(coins)
+    }
+
+    /// Returns the amount bonded by the registered sequencer with the given DA address.
+    pub fn get_stake(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<Amount> {
+        self.stakes.get(da_address, working_set)
+    }
+
+    /// Returns the number of slots an exiting sequencer has to wait before withdrawing its stake.
+    pub fn get_unbonding_period(&self, working_set: &mut WorkingSet<C>) -> Option<u64> {
+        self.unbonding_period.get(working_set)
+    }
+
+    /// Returns the unbonding stake of the exiting sequencer with the given DA address.
+    pub fn get_unbonding_info(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) -> Option<UnbondingInfo<C>> {
+        self.unbonding_sequencers.get(da_address, working_set)
     }
 
     pub(crate) fn register_sequencer(
         &self,
         da_address: &Da::Address,
         rollup_address: &C::Address,
+        amount: Amount,
         working_set: &mut WorkingSet<C>,
     ) -> anyhow::Result<()> {
         if self
@@ -118,15 +229,40 @@ impl<C: sov_modules_api::Context, Da: sov_modules_api::DaSpec> SequencerRegistry
             .get(da_address, working_set)
             .is_some()
         {
-            anyhow::bail!("sequencer {} already registered", rollup_address)
+            return Err(ModuleErrorCode::InvariantViolation
+                .error(format!("sequencer {} already registered", rollup_address)));
+        }
+        if self
+            .unbonding_sequencers
+            .get(da_address, working_set)
+            .is_some()
+        {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "sequencer {} has to withdraw its unbonding stake before registering again",
+                da_address
+            )));
+        }
+        let minimum_stake = self
+            .get_coins_to_lock(working_set)
+            .ok_or_else(|| anyhow::anyhow!("the minimum stake isn't set"))?;
+        if amount < minimum_stake.amount {
+            return Err(ModuleErrorCode::InvariantViolation.error(format!(
+                "stake of {} is below the minimum of {}",
+                amount, minimum_stake.amount
+            )));
         }
         let locker = &self.address;
-        let coins = self.coins_to_lock.get_or_err(working_set)?;
+        let coins = sov_bank::Coins {
+            amount,
+            token_address: minimum_stake.token_address,
+        };
         self.bank
             .transfer_from(rollup_address, locker, coins, working_set)?;
 
         self.allowed_sequencers
             .set(da_address, rollup_address, working_set);
+        self.stakes.set(da_address, &amount, working_set);
+        self.push_sequencer_order(da_address, working_set);
 
         Ok(())
     }
@@ -0,0 +1,138 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use sov_modules_api::prelude::*;
+use sov_modules_api::{Context, DaSpec, WorkingSet};
+use tracing::{info, warn};
+
+use crate::SequencerRegistry;
+
+/// The rotation of the preferred sequencer role among the bonded sequencers.
+///
+/// The role is given to the bonded sequencers in turn, in registration order, for
+/// `epoch_length` slots each. If the preferred sequencer doesn't post a batch for
+/// `liveness_timeout` slots, the next sequencer in line takes over until the end of the epoch.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RotationConfig {
+    /// The number of slots during which a sequencer keeps the preferred role.
+    pub epoch_length: u64,
+    /// The number of slots without a batch from the preferred sequencer after which
+    /// the next sequencer in line takes over.
+    pub liveness_timeout: u64,
+}
+
+impl RotationConfig {
+    /// Returns an error unless both the epoch length and the liveness timeout are positive.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.epoch_length == 0 {
+            anyhow::bail!("The epoch length of the sequencer rotation can't be zero");
+        }
+        if self.liveness_timeout == 0 {
+            anyhow::bail!("The liveness timeout of the sequencer rotation can't be zero");
+        }
+        Ok(())
+    }
+}
+
+impl<C: Context, Da: DaSpec> SequencerRegistry<C, Da> {
+    /// Updates the preferred sequencer according to the [`RotationConfig`]. It has to be called
+    /// once per slot, before the blobs of the slot are selected, and does nothing if the rotation
+    /// isn't enabled.
+    ///
+    /// The preferred role is handed to the sequencer of the epoch of `slot_height` at every epoch
+    /// boundary, or whenever there is no preferred sequencer because it exited or was slashed.
+    /// Within an epoch, the next sequencer in line replaces a preferred sequencer which didn't
+    /// post a batch for [`RotationConfig::liveness_timeout`] slots.
+    pub fn rotate_preferred_sequencer(&self, slot_height: u64, working_set: &mut WorkingSet<C>) {
+        let Some(rotation) = self.rotation.get(working_set) else {
+            return;
+        };
+        let order = self.sequencer_order.get(working_set).unwrap_or_default();
+        if order.is_empty() {
+            return;
+        }
+
+        let preferred_sequencer = self.preferred_sequencer.get(working_set);
+        let next = match preferred_sequencer {
+            Some(_) if slot_height % rotation.epoch_length != 0 => {
+                let last_activity = self
+                    .preferred_sequencer_last_activity
+                    .get(working_set)
+                    .unwrap_or_default();
+                if slot_height.saturating_sub(last_activity) <= rotation.liveness_timeout {
+                    return;
+                }
+                let position = order
+                    .iter()
+                    .position(|sequencer| Some(sequencer) == preferred_sequencer.as_ref())
+                    .unwrap_or_default();
+                let next = order[(position + 1) % order.len()].clone();
+                warn!(
+                    "Preferred sequencer didn't post a batch since slot {}, sequencer {} takes over at slot {}",
+                    last_activity, next, slot_height
+                );
+                next
+            }
+            _ => {
+                let epoch = slot_height / rotation.epoch_length;
+                let next = order[(epoch % order.len() as u64) as usize].clone();
+                info!(
+                    "Sequencer {} is the preferred sequencer from slot {}",
+                    next, slot_height
+                );
+                next
+            }
+        };
+
+        self.preferred_sequencer.set(&next, working_set);
+        self.preferred_sequencer_last_activity
+            .set(&slot_height, working_set);
+    }
+
+    /// Records the senders of the batches posted on DA in the slot at `slot_height`, which keeps
+    /// the preferred sequencer from being replaced for liveness.
+    pub fn record_batches<I>(&self, senders: I, slot_height: u64, working_set: &mut WorkingSet<C>)
+    where
+        I: IntoIterator<Item = Da::Address>,
+    {
+        let Some(preferred_sequencer) = self.preferred_sequencer.get(working_set) else {
+            return;
+        };
+        if senders
+            .into_iter()
+            .any(|sender| sender == preferred_sequencer)
+        {
+            self.preferred_sequencer_last_activity
+                .set(&slot_height, working_set);
+        }
+    }
+
+    /// Returns the rotation of the preferred sequencer, or [`None`] if it isn't enabled.
+    pub fn get_rotation_config(&self, working_set: &mut WorkingSet<C>) -> Option<RotationConfig> {
+        self.rotation.get(working_set)
+    }
+
+    /// Returns the DA addresses of the bonded sequencers, in the order they take the preferred role.
+    pub fn get_sequencer_order(&self, working_set: &mut WorkingSet<C>) -> Vec<Da::Address> {
+        self.sequencer_order.get(working_set).unwrap_or_default()
+    }
+
+    pub(crate) fn push_sequencer_order(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut order = self.sequencer_order.get(working_set).unwrap_or_default();
+        order.push(da_address.clone());
+        self.sequencer_order.set(&order, working_set);
+    }
+
+    pub(crate) fn remove_sequencer_order(
+        &self,
+        da_address: &Da::Address,
+        working_set: &mut WorkingSet<C>,
+    ) {
+        let mut order = self.sequencer_order.get(working_set).unwrap_or_default();
+        order.retain(|sequencer| sequencer != da_address);
+        self.sequencer_order.set(&order, working_set);
+    }
+}
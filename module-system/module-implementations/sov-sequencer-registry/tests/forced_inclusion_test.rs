@@ -0,0 +1,209 @@
+use helpers::*;
+use sov_mock_da::{MockAddress, MockBlob};
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::hooks::ApplyBlobHooks;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{BlobReaderTrait, Spec, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_sequencer_registry::FORCED_INCLUSION_SLOTS_COUNT;
+
+mod helpers;
+
+const FORCED_TX_SENDER_KEY: &str = "236e80cb222c4ed0431b093b3ac53e6aa7a2273fe1f4351cd354989a823432a27b758bf2e7670fafaf6bf0015ce0ff5aa802306fc7e3f45762853ffc37180fe6";
+const FORCED_TX_SLOT: u64 = 10;
+
+fn create_preferred_test_sequencer() -> TestSequencer {
+    let mut test_sequencer = create_test_sequencer();
+    test_sequencer.sequencer_config.is_preferred_sequencer = true;
+    test_sequencer
+}
+
+fn forced_tx() -> Vec<u8> {
+    let priv_key = DefaultPrivateKey::from_hex(FORCED_TX_SENDER_KEY).unwrap();
+    let tx = Transaction::<C>::new_signed_tx(&priv_key, vec![1, 2, 3], 0, 0, 0, 0);
+    borsh::to_vec(&tx).unwrap()
+}
+
+fn forced_tx_hash() -> [u8; 32] {
+    <C as Spec>::Hasher::digest(forced_tx()).into()
+}
+
+fn enqueue_forced_txs(
+    txs: Vec<Vec<u8>>,
+    test_sequencer: &TestSequencer,
+    working_set: &mut WorkingSet<C>,
+) {
+    let mut user_blob = MockBlob::new(
+        borsh::to_vec(&txs).unwrap(),
+        MockAddress::from(UNKNOWN_SEQUENCER_DA_ADDRESS),
+        [0_u8; 32],
+    );
+    test_sequencer.registry.enqueue_forced_transactions(
+        &mut user_blob,
+        FORCED_TX_SLOT,
+        working_set,
+    );
+}
+
+fn enqueue_forced_tx(test_sequencer: &TestSequencer, working_set: &mut WorkingSet<C>) {
+    enqueue_forced_txs(vec![forced_tx()], test_sequencer, working_set);
+}
+
+#[test]
+fn forced_transaction_included_in_time() {
+    let mut test_sequencer = create_preferred_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    enqueue_forced_tx(&test_sequencer, working_set);
+    let deadline = FORCED_TX_SLOT + FORCED_INCLUSION_SLOTS_COUNT;
+    assert_eq!(
+        Some(deadline),
+        test_sequencer
+            .registry
+            .get_forced_transaction_deadline(&forced_tx_hash(), working_set)
+    );
+
+    let mut sequencer_blob = MockBlob::new(
+        borsh::to_vec(&vec![vec![4, 5, 6], forced_tx()]).unwrap(),
+        MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS),
+        [1_u8; 32],
+    );
+    test_sequencer
+        .registry
+        .begin_blob_hook(&mut sequencer_blob, working_set)
+        .unwrap();
+    assert_eq!(
+        None,
+        test_sequencer
+            .registry
+            .get_forced_transaction_deadline(&forced_tx_hash(), working_set)
+    );
+
+    test_sequencer
+        .registry
+        .enforce_forced_inclusion_deadlines(deadline + 1, working_set);
+    assert_eq!(
+        Some(MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS)),
+        test_sequencer.registry.get_preferred_sequencer(working_set)
+    );
+}
+
+#[test]
+fn censoring_preferred_sequencer_is_slashed() {
+    let mut test_sequencer = create_preferred_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    enqueue_forced_tx(&test_sequencer, working_set);
+    let deadline = FORCED_TX_SLOT + FORCED_INCLUSION_SLOTS_COUNT;
+
+    // The deadline has not passed yet
+    test_sequencer
+        .registry
+        .enforce_forced_inclusion_deadlines(deadline, working_set);
+    assert!(test_sequencer
+        .registry
+        .get_preferred_sequencer(working_set)
+        .is_some());
+
+    test_sequencer
+        .registry
+        .enforce_forced_inclusion_deadlines(deadline + 1, working_set);
+    assert!(test_sequencer
+        .registry
+        .get_preferred_sequencer(working_set)
+        .is_none());
+    assert!(!test_sequencer.registry.is_sender_allowed(
+        &MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS),
+        working_set
+    ));
+    assert_eq!(
+        None,
+        test_sequencer
+            .registry
+            .get_forced_transaction_deadline(&forced_tx_hash(), working_set)
+    );
+}
+
+#[test]
+fn invalid_forced_transactions_are_not_enqueued() {
+    let mut test_sequencer = create_preferred_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let not_a_tx = vec![1, 2, 3];
+    let mut bad_signature = forced_tx();
+    // Flip a bit of the nonce, the last field of the transaction
+    *bad_signature.last_mut().unwrap() ^= 1;
+    enqueue_forced_txs(
+        vec![not_a_tx.clone(), bad_signature.clone()],
+        &test_sequencer,
+        working_set,
+    );
+
+    for tx in [not_a_tx, bad_signature] {
+        let tx_hash: [u8; 32] = <C as Spec>::Hasher::digest(tx).into();
+        assert_eq!(
+            None,
+            test_sequencer
+                .registry
+                .get_forced_transaction_deadline(&tx_hash, working_set)
+        );
+    }
+
+    // Nothing was due, so the preferred sequencer is not slashed
+    let deadline = FORCED_TX_SLOT + FORCED_INCLUSION_SLOTS_COUNT;
+    test_sequencer
+        .registry
+        .enforce_forced_inclusion_deadlines(deadline + 1, working_set);
+    assert!(test_sequencer
+        .registry
+        .get_preferred_sequencer(working_set)
+        .is_some());
+}
+
+#[test]
+fn partially_read_blobs_are_deserialized_in_full() {
+    let mut test_sequencer = create_preferred_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let mut user_blob = MockBlob::new(
+        borsh::to_vec(&vec![forced_tx()]).unwrap(),
+        MockAddress::from(UNKNOWN_SEQUENCER_DA_ADDRESS),
+        [0_u8; 32],
+    );
+    user_blob.advance(8);
+    test_sequencer.registry.enqueue_forced_transactions(
+        &mut user_blob,
+        FORCED_TX_SLOT,
+        working_set,
+    );
+    assert!(test_sequencer
+        .registry
+        .get_forced_transaction_deadline(&forced_tx_hash(), working_set)
+        .is_some());
+
+    let mut sequencer_blob = MockBlob::new(
+        borsh::to_vec(&vec![forced_tx()]).unwrap(),
+        MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS),
+        [1_u8; 32],
+    );
+    sequencer_blob.advance(8);
+    test_sequencer
+        .registry
+        .begin_blob_hook(&mut sequencer_blob, working_set)
+        .unwrap();
+    assert_eq!(
+        None,
+        test_sequencer
+            .registry
+            .get_forced_transaction_deadline(&forced_tx_hash(), working_set)
+    );
+}
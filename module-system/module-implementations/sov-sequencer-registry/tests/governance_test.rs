@@ -0,0 +1,110 @@
+use helpers::*;
+use sov_governance::{CallMessage as GovernanceCallMessage, GovernanceConfig, GovernedParameter};
+use sov_mock_da::MockAddress;
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_sequencer_registry::CallMessage;
+
+mod helpers;
+
+const GOVERNED_BOND: u64 = 100;
+
+// The minimum stake follows the sequencer bond changed by an executed proposal
+#[test]
+fn executed_proposal_changes_the_minimum_stake() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+
+    // The tokens of the sequencers also weight the votes
+    let governance = sov_governance::Governance::<C>::default();
+    let governance_config = GovernanceConfig {
+        voting_rules: sov_governance::VotingRules {
+            governance_token: test_sequencer.sequencer_config.coins_to_lock.token_address,
+            proposal_threshold: 100,
+            quorum: 200,
+            voting_period: 1,
+            timelock: 0,
+        },
+        parameters: vec![GovernedParameter {
+            name: sov_governance::SEQUENCER_BOND.to_owned(),
+            value: LOCKED_AMOUNT,
+        }],
+    };
+    test_sequencer
+        .bank
+        .genesis(&test_sequencer.bank_config, working_set)
+        .unwrap();
+    governance.genesis(&governance_config, working_set).unwrap();
+    test_sequencer
+        .registry
+        .genesis(&test_sequencer.sequencer_config, working_set)
+        .unwrap();
+
+    let voter = generate_address(UNKNOWN_SEQUENCER_KEY);
+    let sequencer_address = generate_address(ANOTHER_SEQUENCER_KEY);
+    let register_message = CallMessage::Register {
+        da_address: MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS)
+            .as_ref()
+            .to_vec(),
+        amount: GOVERNED_BOND,
+    };
+
+    // The stake is below the bond of the genesis
+    assert!(test_sequencer
+        .registry
+        .call(
+            register_message.clone(),
+            &C::new(sequencer_address, sequencer_address, 1),
+            working_set,
+        )
+        .is_err());
+
+    let voter_context = C::new(voter, voter, 1);
+    for message in [
+        GovernanceCallMessage::Propose {
+            parameter: sov_governance::SEQUENCER_BOND.to_owned(),
+            value: GOVERNED_BOND,
+        },
+        GovernanceCallMessage::Vote {
+            proposal_id: 0,
+            approve: true,
+            amount: 200,
+        },
+    ] {
+        governance
+            .call(message, &voter_context, working_set)
+            .expect("Governance call failed");
+    }
+    governance
+        .call(
+            GovernanceCallMessage::Execute { proposal_id: 0 },
+            &C::new(voter, voter, 2),
+            working_set,
+        )
+        .expect("Execute call failed");
+
+    assert_eq!(
+        GOVERNED_BOND,
+        test_sequencer
+            .registry
+            .get_coins_to_lock(working_set)
+            .unwrap()
+            .amount
+    );
+    test_sequencer
+        .registry
+        .call(
+            register_message,
+            &C::new(sequencer_address, sequencer_address, 2),
+            working_set,
+        )
+        .expect("Sequencer registration has failed");
+    assert_eq!(
+        Some(GOVERNED_BOND),
+        test_sequencer.registry.get_stake(
+            &MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS),
+            working_set
+        )
+    );
+}
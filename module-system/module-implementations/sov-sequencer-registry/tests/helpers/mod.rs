@@ -21,6 +21,7 @@ pub const UNKNOWN_SEQUENCER_DA_ADDRESS: [u8; 32] = [3; 32];
 pub const LOW_FUND_KEY: &str = "zero_funds";
 pub const INITIAL_BALANCE: u64 = 210;
 pub const LOCKED_AMOUNT: u64 = 200;
+pub const UNBONDING_PERIOD: u64 = 10;
 
 pub struct TestSequencer {
     pub bank: sov_bank::Bank<C>,
@@ -80,6 +81,8 @@ pub fn create_bank_config() -> (sov_bank::BankConfig<C>, <C as Spec>::Address) {
         ],
         authorized_minters: vec![],
         salt: 8,
+        authority: None,
+        max_supply: None,
     };
 
     (
@@ -101,7 +104,10 @@ pub fn create_sequencer_config(
             amount: LOCKED_AMOUNT,
             token_address,
         },
+        unbonding_period: UNBONDING_PERIOD,
         is_preferred_sequencer: false,
+        rotation: None,
+        burn_base_fee: false,
     }
 }
 
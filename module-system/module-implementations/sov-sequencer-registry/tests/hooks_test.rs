@@ -1,7 +1,10 @@
 use helpers::*;
+use sov_bank::BankTxHook;
 use sov_mock_da::{MockAddress, MockBlob};
-use sov_modules_api::hooks::ApplyBlobHooks;
-use sov_modules_api::WorkingSet;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::hooks::{ApplyBlobHooks, TxHooks};
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Context, PrivateKey, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
 use sov_sequencer_registry::{SequencerOutcome, SequencerRegistry};
 
@@ -99,6 +102,71 @@ fn end_blob_hook_success() {
     assert!(resp.address.is_some());
 }
 
+#[test]
+fn end_blob_hook_rewards_fees() {
+    let mut test_sequencer = create_test_sequencer();
+    let user = generate_address(LOW_FUND_KEY);
+    test_sequencer
+        .bank_config
+        .tokens
+        .push(sov_bank::TokenConfig {
+            token_name: "sov-gas-token".to_owned(),
+            address_and_balances: vec![(user, INITIAL_BALANCE)],
+            authorized_minters: vec![],
+            salt: 0,
+            authority: None,
+            max_supply: None,
+        });
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+    let gas_token = test_sequencer.bank.get_gas_token_address().unwrap();
+    let sequencer = test_sequencer.sequencer_config.seq_rollup_address;
+
+    let genesis_sequencer_da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let mut test_blob = MockBlob::new(Vec::new(), genesis_sequencer_da_address, [0_u8; 32]);
+    test_sequencer
+        .registry
+        .begin_blob_hook(&mut test_blob, working_set)
+        .unwrap();
+
+    // A transaction with a gas limit of 10 and a tip of 2 uses 3 gas
+    let tx = Transaction::<C>::new_signed_tx(&DefaultPrivateKey::generate(), vec![], 0, 2, 10, 0);
+    working_set.set_gas(10, [1, 1]);
+    let hook = BankTxHook {
+        sender: user,
+        sequencer,
+    };
+    test_sequencer
+        .bank
+        .pre_dispatch_tx_hook(&tx, working_set, &hook)
+        .unwrap();
+    working_set.charge_gas(&[2, 1]).unwrap();
+    test_sequencer
+        .bank
+        .post_dispatch_tx_hook(&tx, &C::new(user, sequencer, 1), working_set)
+        .unwrap();
+
+    <SequencerRegistry<C, Da> as ApplyBlobHooks<MockBlob>>::end_blob_hook(
+        &test_sequencer.registry,
+        SequencerOutcome::Completed,
+        working_set,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(5),
+        test_sequencer
+            .bank
+            .get_balance_of(sequencer, gas_token, working_set)
+    );
+    assert_eq!(
+        Some(INITIAL_BALANCE - 5),
+        test_sequencer
+            .bank
+            .get_balance_of(user, gas_token, working_set)
+    );
+}
+
 #[test]
 fn end_blob_hook_slash() {
     let mut test_sequencer = create_test_sequencer();
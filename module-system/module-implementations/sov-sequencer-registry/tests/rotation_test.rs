@@ -0,0 +1,166 @@
+use helpers::*;
+use sov_mock_da::MockAddress;
+use sov_modules_api::{Context, Module, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
+use sov_sequencer_registry::{CallMessage, RotationConfig};
+
+mod helpers;
+
+const EPOCH_LENGTH: u64 = 10;
+const LIVENESS_TIMEOUT: u64 = 3;
+
+fn create_rotating_test_sequencer(working_set: &mut WorkingSet<C>) -> TestSequencer {
+    let mut test_sequencer = create_test_sequencer();
+    test_sequencer.sequencer_config.rotation = Some(RotationConfig {
+        epoch_length: EPOCH_LENGTH,
+        liveness_timeout: LIVENESS_TIMEOUT,
+    });
+    test_sequencer.genesis(working_set);
+
+    let sender_context = C::new(
+        generate_address(ANOTHER_SEQUENCER_KEY),
+        generate_address(REWARD_SEQUENCER_KEY),
+        1,
+    );
+    test_sequencer
+        .registry
+        .call(
+            CallMessage::Register {
+                da_address: ANOTHER_SEQUENCER_DA_ADDRESS.to_vec(),
+                amount: LOCKED_AMOUNT,
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Sequencer registration has failed");
+    test_sequencer
+}
+
+fn genesis_sequencer() -> MockAddress {
+    MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS)
+}
+
+fn another_sequencer() -> MockAddress {
+    MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS)
+}
+
+#[test]
+fn preferred_sequencer_rotates_per_epoch() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let test_sequencer = create_rotating_test_sequencer(working_set);
+    let registry = &test_sequencer.registry;
+    assert_eq!(
+        vec![genesis_sequencer(), another_sequencer()],
+        registry.get_sequencer_order(working_set)
+    );
+
+    for (slot_height, expected) in [
+        (LIVENESS_TIMEOUT, genesis_sequencer()),
+        (EPOCH_LENGTH, another_sequencer()),
+        (2 * EPOCH_LENGTH, genesis_sequencer()),
+    ] {
+        registry.rotate_preferred_sequencer(slot_height, working_set);
+        registry.record_batches([expected.clone()], slot_height, working_set);
+        assert_eq!(
+            Some(expected),
+            registry.get_preferred_sequencer(working_set)
+        );
+    }
+}
+
+#[test]
+fn next_sequencer_takes_over_an_inactive_one() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let test_sequencer = create_rotating_test_sequencer(working_set);
+    let registry = &test_sequencer.registry;
+
+    // Batches from other sequencers don't count as activity of the preferred sequencer
+    for slot_height in 1..=LIVENESS_TIMEOUT {
+        registry.rotate_preferred_sequencer(slot_height, working_set);
+        registry.record_batches([another_sequencer()], slot_height, working_set);
+        assert_eq!(
+            Some(genesis_sequencer()),
+            registry.get_preferred_sequencer(working_set)
+        );
+    }
+
+    registry.rotate_preferred_sequencer(LIVENESS_TIMEOUT + 1, working_set);
+    assert_eq!(
+        Some(another_sequencer()),
+        registry.get_preferred_sequencer(working_set)
+    );
+}
+
+#[test]
+fn batches_keep_the_preferred_sequencer() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let test_sequencer = create_rotating_test_sequencer(working_set);
+    let registry = &test_sequencer.registry;
+
+    for slot_height in 1..EPOCH_LENGTH {
+        registry.rotate_preferred_sequencer(slot_height, working_set);
+        registry.record_batches([genesis_sequencer()], slot_height, working_set);
+        assert_eq!(
+            Some(genesis_sequencer()),
+            registry.get_preferred_sequencer(working_set)
+        );
+    }
+}
+
+#[test]
+fn exited_sequencer_leaves_the_rotation() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let test_sequencer = create_rotating_test_sequencer(working_set);
+    let registry = &test_sequencer.registry;
+
+    let sender_context = C::new(
+        generate_address(GENESIS_SEQUENCER_KEY),
+        generate_address(REWARD_SEQUENCER_KEY),
+        1,
+    );
+    registry
+        .call(
+            CallMessage::InitiateExit {
+                da_address: GENESIS_SEQUENCER_DA_ADDRESS.to_vec(),
+            },
+            &sender_context,
+            working_set,
+        )
+        .expect("Sequencer exit has failed");
+    assert_eq!(
+        vec![another_sequencer()],
+        registry.get_sequencer_order(working_set)
+    );
+    assert_eq!(None, registry.get_preferred_sequencer(working_set));
+
+    // The remaining sequencer gets the preferred role without waiting for the next epoch
+    registry.rotate_preferred_sequencer(2, working_set);
+    assert_eq!(
+        Some(another_sequencer()),
+        registry.get_preferred_sequencer(working_set)
+    );
+}
+
+#[test]
+fn rotation_is_disabled_by_default() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    test_sequencer
+        .registry
+        .rotate_preferred_sequencer(EPOCH_LENGTH, working_set);
+    assert_eq!(
+        None,
+        test_sequencer.registry.get_rotation_config(working_set)
+    );
+    assert_eq!(
+        None,
+        test_sequencer.registry.get_preferred_sequencer(working_set)
+    );
+}
@@ -2,7 +2,7 @@ use helpers::*;
 use sov_mock_da::MockAddress;
 use sov_modules_api::{Context, Error, Module, ModuleInfo, WorkingSet};
 use sov_prover_storage_manager::new_orphan_storage;
-use sov_sequencer_registry::{CallMessage, SequencerRegistry};
+use sov_sequencer_registry::{CallMessage, SequencerRegistry, UnbondingInfo};
 
 mod helpers;
 
@@ -10,7 +10,7 @@ mod helpers;
 // This test checks:
 //  - genesis sequencer is present after genesis
 //  - registration works, and funds are deducted
-//  - exit works and funds are returned
+//  - exit works and funds are returned after the unbonding period
 #[test]
 fn test_registration_lifecycle() {
     let mut test_sequencer = create_test_sequencer();
@@ -50,6 +50,7 @@ fn test_registration_lifecycle() {
 
     let register_message = CallMessage::Register {
         da_address: da_address.as_ref().to_vec(),
+        amount: LOCKED_AMOUNT,
     };
     test_sequencer
         .registry
@@ -72,7 +73,7 @@ fn test_registration_lifecycle() {
         registry_response_after_registration.address
     );
 
-    let exit_message = CallMessage::Exit {
+    let exit_message = CallMessage::InitiateExit {
         da_address: da_address.as_ref().to_vec(),
     };
     test_sequencer
@@ -80,18 +81,38 @@ fn test_registration_lifecycle() {
         .call(exit_message, &sender_context, working_set)
         .expect("Sequencer exit has failed");
 
+    let registry_response_after_exit = test_sequencer
+        .registry
+        .sequencer_address(da_address, working_set)
+        .unwrap();
+    assert!(registry_response_after_exit.address.is_none());
+    assert_eq!(
+        Some(balance_after_registration),
+        test_sequencer
+            .query_balance(sequencer_address, working_set)
+            .unwrap()
+            .amount
+    );
+
+    let withdraw_message = CallMessage::Withdraw {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    let withdraw_context = C::new(sequencer_address, reward_address, 1 + UNBONDING_PERIOD);
+    test_sequencer
+        .registry
+        .call(withdraw_message, &withdraw_context, working_set)
+        .expect("Sequencer withdraw has failed");
+
     let balance_after_exit = test_sequencer
         .query_balance(sequencer_address, working_set)
         .unwrap()
         .amount
         .unwrap();
     assert_eq!(balance_before, balance_after_exit);
-
-    let registry_response_after_exit = test_sequencer
+    assert!(test_sequencer
         .registry
-        .sequencer_address(da_address, working_set)
-        .unwrap();
-    assert!(registry_response_after_exit.address.is_none());
+        .get_unbonding_info(&da_address, working_set)
+        .is_none());
 }
 
 #[test]
@@ -109,6 +130,7 @@ fn test_registration_not_enough_funds() {
 
     let register_message = CallMessage::Register {
         da_address: da_address.as_ref().to_vec(),
+        amount: LOCKED_AMOUNT,
     };
     let response = test_sequencer
         .registry
@@ -118,7 +140,9 @@ fn test_registration_not_enough_funds() {
         response.is_err(),
         "insufficient funds registration should fail"
     );
-    let Error::ModuleError(err) = response.err().unwrap();
+    let Error::InsufficientFunds(err) = response.err().unwrap() else {
+        panic!("Expected an insufficient funds error");
+    };
     let mut chain = err.chain();
     let message_1 = chain.next().unwrap().to_string();
     let message_2 = chain.next().unwrap().to_string();
@@ -163,14 +187,18 @@ fn test_registration_second_time() {
 
     let register_message = CallMessage::Register {
         da_address: da_address.as_ref().to_vec(),
+        amount: LOCKED_AMOUNT,
     };
     let response = test_sequencer
         .registry
         .call(register_message, &sender_context, working_set);
 
     assert!(response.is_err(), "duplicate registration should fail");
+    let Error::InvariantViolation(err) = response.err().unwrap() else {
+        panic!("Expected an invariant violation error");
+    };
     let expected_error_message = format!("sequencer {} already registered", sequencer_address);
-    let actual_error_message = response.err().unwrap().to_string();
+    let actual_error_message = err.to_string();
 
     assert_eq!(expected_error_message, actual_error_message);
 }
@@ -190,13 +218,14 @@ fn test_exit_different_sender() {
 
     let register_message = CallMessage::Register {
         da_address: ANOTHER_SEQUENCER_DA_ADDRESS.to_vec(),
+        amount: LOCKED_AMOUNT,
     };
     test_sequencer
         .registry
         .call(register_message, &sender_context, working_set)
         .expect("Sequencer registration has failed");
 
-    let exit_message = CallMessage::Exit {
+    let exit_message = CallMessage::InitiateExit {
         da_address: ANOTHER_SEQUENCER_DA_ADDRESS.to_vec(),
     };
     let response = test_sequencer
@@ -207,7 +236,10 @@ fn test_exit_different_sender() {
         response.is_err(),
         "exit by non authorized sender should fail"
     );
-    let actual_error_message = response.err().unwrap().to_string();
+    let Error::Unauthorized(err) = response.err().unwrap() else {
+        panic!("Expected an unauthorized error");
+    };
+    let actual_error_message = err.to_string();
 
     assert_eq!("Unauthorized exit attempt", actual_error_message);
 }
@@ -222,7 +254,7 @@ fn test_allow_exit_last_sequencer() {
     let sequencer_address = generate_address(GENESIS_SEQUENCER_KEY);
     let rewards_address = generate_address(REWARD_SEQUENCER_KEY);
     let sender_context = C::new(sequencer_address, rewards_address, 1);
-    let exit_message = CallMessage::Exit {
+    let exit_message = CallMessage::InitiateExit {
         da_address: GENESIS_SEQUENCER_DA_ADDRESS.to_vec(),
     };
     test_sequencer
@@ -265,7 +297,7 @@ fn test_preferred_sequencer_returned_and_removed() {
     let sequencer_address = generate_address(GENESIS_SEQUENCER_KEY);
     let reward_address = generate_address(REWARD_SEQUENCER_KEY);
     let sender_context = C::new(sequencer_address, reward_address, 1);
-    let exit_message = CallMessage::Exit {
+    let exit_message = CallMessage::InitiateExit {
         da_address: GENESIS_SEQUENCER_DA_ADDRESS.to_vec(),
     };
     test_sequencer
@@ -279,3 +311,158 @@ fn test_preferred_sequencer_returned_and_removed() {
         .get_preferred_sequencer(working_set)
         .is_none());
 }
+
+#[test]
+fn test_registration_with_custom_stake() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let da_address = MockAddress::from(ANOTHER_SEQUENCER_DA_ADDRESS);
+    let sequencer_address = generate_address(ANOTHER_SEQUENCER_KEY);
+    let reward_address = generate_address(REWARD_SEQUENCER_KEY);
+    let sender_context = C::new(sequencer_address, reward_address, 1);
+
+    assert_eq!(
+        Some(LOCKED_AMOUNT),
+        test_sequencer.registry.get_stake(
+            &MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS),
+            working_set
+        )
+    );
+
+    let below_minimum_message = CallMessage::Register {
+        da_address: da_address.as_ref().to_vec(),
+        amount: LOCKED_AMOUNT - 1,
+    };
+    let response =
+        test_sequencer
+            .registry
+            .call(below_minimum_message, &sender_context, working_set);
+    assert_eq!(
+        format!(
+            "stake of {} is below the minimum of {}",
+            LOCKED_AMOUNT - 1,
+            LOCKED_AMOUNT
+        ),
+        response.err().unwrap().to_string()
+    );
+
+    let register_message = CallMessage::Register {
+        da_address: da_address.as_ref().to_vec(),
+        amount: LOCKED_AMOUNT + 5,
+    };
+    test_sequencer
+        .registry
+        .call(register_message, &sender_context, working_set)
+        .expect("Sequencer registration has failed");
+
+    assert_eq!(
+        Some(LOCKED_AMOUNT + 5),
+        test_sequencer.registry.get_stake(&da_address, working_set)
+    );
+    assert_eq!(
+        Some(INITIAL_BALANCE - LOCKED_AMOUNT - 5),
+        test_sequencer
+            .query_balance(sequencer_address, working_set)
+            .unwrap()
+            .amount
+    );
+}
+
+#[test]
+fn test_withdraw_before_unbonding_period() {
+    let mut test_sequencer = create_test_sequencer();
+    let tmpdir = tempfile::tempdir().unwrap();
+    let working_set = &mut WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    test_sequencer.genesis(working_set);
+
+    let da_address = MockAddress::from(GENESIS_SEQUENCER_DA_ADDRESS);
+    let sequencer_address = generate_address(GENESIS_SEQUENCER_KEY);
+    let reward_address = generate_address(REWARD_SEQUENCER_KEY);
+    let exit_height = 5;
+    let context_at = |height| C::new(sequencer_address, reward_address, height);
+    let withdraw_message = || CallMessage::Withdraw {
+        da_address: da_address.as_ref().to_vec(),
+    };
+
+    // Nothing to withdraw before the exit is initiated
+    assert!(test_sequencer
+        .registry
+        .call(withdraw_message(), &context_at(exit_height), working_set)
+        .is_err_and(|err| matches!(err, Error::NotFound(_))));
+
+    let exit_message = CallMessage::InitiateExit {
+        da_address: da_address.as_ref().to_vec(),
+    };
+    test_sequencer
+        .registry
+        .call(exit_message, &context_at(exit_height), working_set)
+        .expect("Sequencer exit has failed");
+    assert_eq!(
+        Some(UnbondingInfo {
+            rollup_address: sequencer_address,
+            amount: LOCKED_AMOUNT,
+            unbonding_initiated_height: exit_height,
+        }),
+        test_sequencer
+            .registry
+            .get_unbonding_info(&da_address, working_set)
+    );
+
+    // The sequencer can't register again while its stake is unbonding
+    let register_message = CallMessage::Register {
+        da_address: da_address.as_ref().to_vec(),
+        amount: LOCKED_AMOUNT,
+    };
+    assert!(test_sequencer
+        .registry
+        .call(register_message, &context_at(exit_height), working_set)
+        .is_err_and(|err| matches!(err, Error::InvariantViolation(_))));
+
+    let response = test_sequencer.registry.call(
+        withdraw_message(),
+        &context_at(exit_height + UNBONDING_PERIOD - 1),
+        working_set,
+    );
+    let Error::InvariantViolation(err) = response.err().unwrap() else {
+        panic!("Expected an invariant violation error");
+    };
+    assert_eq!(
+        format!(
+            "Unbonding period is not over, the stake can be withdrawn at slot {}",
+            exit_height + UNBONDING_PERIOD
+        ),
+        err.to_string()
+    );
+
+    let attacker_context = C::new(
+        generate_address("some_random_key"),
+        reward_address,
+        exit_height + UNBONDING_PERIOD,
+    );
+    let response = test_sequencer
+        .registry
+        .call(withdraw_message(), &attacker_context, working_set);
+    let Error::Unauthorized(err) = response.err().unwrap() else {
+        panic!("Expected an unauthorized error");
+    };
+    assert_eq!("Unauthorized withdraw attempt", err.to_string());
+
+    test_sequencer
+        .registry
+        .call(
+            withdraw_message(),
+            &context_at(exit_height + UNBONDING_PERIOD),
+            working_set,
+        )
+        .expect("Sequencer withdraw has failed");
+    assert_eq!(
+        Some(INITIAL_BALANCE),
+        test_sequencer
+            .query_balance(sequencer_address, working_set)
+            .unwrap()
+            .amount
+    );
+}
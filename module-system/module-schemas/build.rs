@@ -17,6 +17,9 @@ fn main() -> io::Result<()> {
     store_json_schema::<sov_sequencer_registry::SequencerRegistry<C, MockDaSpec>>(
         "sov-sequencer-registry.json",
     )?;
+    store_json_schema::<sov_bridge::Bridge<C, MockDaSpec>>("sov-bridge.json")?;
+    store_json_schema::<sov_price_oracle::PriceOracle<C>>("sov-price-oracle.json")?;
+    store_json_schema::<sov_governance::Governance<C>>("sov-governance.json")?;
     Ok(())
 }
 
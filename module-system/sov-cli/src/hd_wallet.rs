@@ -0,0 +1,220 @@
+//! Hierarchical deterministic keys, derived from a BIP-39 mnemonic stored in an encrypted keystore.
+//!
+//! The keys of the named accounts are derived from the seed of the mnemonic with SLIP-0010,
+//! which only supports hardened derivation for ed25519 keys, at the path
+//! `m/44'/1551'/<account>'/0'/0'`.
+use std::fs;
+use std::path::Path;
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use anyhow::Context;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha512;
+
+pub use bip39::Mnemonic;
+
+/// The BIP-44 purpose of the derivation paths.
+const PURPOSE: u32 = 44;
+/// The coin type of the derivation paths.
+const COIN_TYPE: u32 = 1551;
+/// The flag of the hardened indexes of a derivation path.
+const HARDENED: u32 = 0x8000_0000;
+/// The number of words of the generated mnemonics.
+const MNEMONIC_WORDS: usize = 24;
+/// The length of the scrypt salt, in bytes.
+const SALT_LENGTH: usize = 32;
+/// The length of the AES-GCM nonce, in bytes.
+const NONCE_LENGTH: usize = 12;
+
+/// Generates a random 24 words mnemonic.
+pub fn generate_mnemonic() -> Mnemonic {
+    let mut entropy = [0u8; MNEMONIC_WORDS / 3 * 4];
+    rand::rngs::OsRng.fill_bytes(&mut entropy);
+    Mnemonic::from_entropy(&entropy).expect("The entropy has a valid length")
+}
+
+/// Returns the derivation path of the key of the given account.
+pub fn derivation_path(account: u32) -> String {
+    format!("m/{}'/{}'/{}'/0'/0'", PURPOSE, COIN_TYPE, account)
+}
+
+/// Derives the private key of the given account from the mnemonic, without a BIP-39 passphrase.
+pub fn derive_private_key<C: sov_modules_api::Context>(
+    mnemonic: &Mnemonic,
+    account: u32,
+) -> Result<C::PrivateKey, anyhow::Error> {
+    let seed = mnemonic.to_seed("");
+    let key = slip10_derive(&seed, &[PURPOSE, COIN_TYPE, account, 0, 0]);
+    C::PrivateKey::try_from(&key[..])
+}
+
+/// Derives the ed25519 secret key at the path of hardened `indexes` from the seed, with SLIP-0010.
+fn slip10_derive(seed: &[u8], indexes: &[u32]) -> [u8; 32] {
+    let (mut key, mut chain_code) = hmac_sha512(b"ed25519 seed", &[seed]);
+    for index in indexes {
+        (key, chain_code) = hmac_sha512(
+            &chain_code,
+            &[&[0], &key, &(index | HARDENED).to_be_bytes()],
+        );
+    }
+    key
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    let output = mac.finalize().into_bytes();
+    let mut left = [0u8; 32];
+    let mut right = [0u8; 32];
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    (left, right)
+}
+
+/// The scrypt parameters deriving the encryption key of a keystore from its password.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScryptParams {
+    /// The base 2 logarithm of the CPU/memory cost.
+    pub log_n: u8,
+    /// The block size.
+    pub r: u32,
+    /// The parallelization.
+    pub p: u32,
+}
+
+impl Default for ScryptParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// A mnemonic encrypted on disk with AES-256-GCM, under a key derived from a password with scrypt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedKeystore {
+    /// The parameters of the key derivation.
+    pub scrypt: ScryptParams,
+    /// The hex encoded salt of the key derivation.
+    pub salt: String,
+    /// The hex encoded nonce of the encryption.
+    pub nonce: String,
+    /// The hex encoded encrypted mnemonic, with its authentication tag.
+    pub ciphertext: String,
+}
+
+impl EncryptedKeystore {
+    /// Encrypts the mnemonic with the password.
+    pub fn encrypt(mnemonic: &Mnemonic, password: &str) -> Result<Self, anyhow::Error> {
+        let scrypt = ScryptParams::default();
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+        let cipher = Self::cipher(scrypt, &salt, password)?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), mnemonic.to_string().as_bytes())
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt the mnemonic"))?;
+        Ok(Self {
+            scrypt,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        })
+    }
+
+    /// Decrypts the mnemonic with the password.
+    pub fn decrypt(&self, password: &str) -> Result<Mnemonic, anyhow::Error> {
+        let salt = hex::decode(&self.salt).context("Invalid keystore salt")?;
+        let nonce = hex::decode(&self.nonce).context("Invalid keystore nonce")?;
+        let ciphertext = hex::decode(&self.ciphertext).context("Invalid keystore ciphertext")?;
+        anyhow::ensure!(nonce.len() == NONCE_LENGTH, "Invalid keystore nonce");
+
+        let cipher = Self::cipher(self.scrypt, &salt, password)?;
+        let phrase = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| anyhow::anyhow!("Wrong password, or corrupted keystore"))?;
+        let phrase = String::from_utf8(phrase).context("The keystore isn't a mnemonic")?;
+        Ok(Mnemonic::parse(phrase)?)
+    }
+
+    /// Loads the keystore from the given path on disk.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref();
+        let data = fs::read(path)
+            .with_context(|| format!("Unable to read the keystore {}", path.display()))?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Saves the keystore to the given path on disk.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), anyhow::Error> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn cipher(
+        scrypt: ScryptParams,
+        salt: &[u8],
+        password: &str,
+    ) -> Result<Aes256Gcm, anyhow::Error> {
+        let params = scrypt::Params::new(scrypt.log_n, scrypt.r, scrypt.p, 32)
+            .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+            .map_err(|e| anyhow::anyhow!("Failed to derive the keystore key: {}", e))?;
+        Ok(Aes256Gcm::new_from_slice(&key).expect("The key has a valid length"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::PrivateKey;
+
+    use super::*;
+
+    #[test]
+    fn test_slip10_vector() {
+        // Test vector 1 of SLIP-0010 for ed25519
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        assert_eq!(
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            hex::encode(slip10_derive(&seed, &[]))
+        );
+        assert_eq!(
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            hex::encode(slip10_derive(&seed, &[0]))
+        );
+    }
+
+    #[test]
+    fn test_derived_keys() {
+        let mnemonic = generate_mnemonic();
+        assert_eq!(MNEMONIC_WORDS, mnemonic.word_count());
+
+        let key = derive_private_key::<DefaultContext>(&mnemonic, 0).unwrap();
+        let same_key = derive_private_key::<DefaultContext>(&mnemonic, 0).unwrap();
+        let other_key = derive_private_key::<DefaultContext>(&mnemonic, 1).unwrap();
+        assert_eq!(key.pub_key(), same_key.pub_key());
+        assert_ne!(key.pub_key(), other_key.pub_key());
+        assert_eq!("m/44'/1551'/1'/0'/0'", derivation_path(1));
+    }
+
+    #[test]
+    fn test_keystore_encryption() {
+        let mnemonic = generate_mnemonic();
+        let keystore = EncryptedKeystore::encrypt(&mnemonic, "password").unwrap();
+        let json = serde_json::to_string(&keystore).unwrap();
+        let keystore: EncryptedKeystore = serde_json::from_str(&json).unwrap();
+        assert_eq!(mnemonic, keystore.decrypt("password").unwrap());
+        assert!(keystore.decrypt("wrong password").is_err());
+    }
+}
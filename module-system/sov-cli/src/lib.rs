@@ -1,16 +1,19 @@
 #![deny(missing_docs)]
 #![doc = include_str!("../README.md")]
 use std::env;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use directories::BaseDirs;
 pub use sov_modules_api::clap;
 
+pub mod hd_wallet;
 /// Types and functionality storing and loading the persistent state of the wallet
 pub mod wallet_state;
 pub mod workflows;
 
 const SOV_WALLET_DIR_ENV_VAR: &str = "SOV_WALLET_DIR";
+const SOV_WALLET_PASSWORD_ENV_VAR: &str = "SOV_WALLET_PASSWORD";
+const KEYSTORE_FILE: &str = "keystore.json";
 
 /// The directory where the wallet is stored.
 pub fn wallet_dir() -> Result<impl AsRef<Path>, anyhow::Error> {
@@ -27,3 +30,22 @@ pub fn wallet_dir() -> Result<impl AsRef<Path>, anyhow::Error> {
 
     Ok(dir)
 }
+
+/// The path of the encrypted keystore of the wallet mnemonic, in the wallet directory.
+pub fn keystore_path(app_dir: impl AsRef<Path>) -> PathBuf {
+    app_dir.as_ref().join(KEYSTORE_FILE)
+}
+
+/// Reads the password of the keystore from the `SOV_WALLET_PASSWORD` environment variable,
+/// or prompts the user for it. New passwords are prompted twice.
+pub fn read_password(is_new: bool) -> Result<String, anyhow::Error> {
+    if let Ok(password) = env::var(SOV_WALLET_PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+
+    let password = rpassword::prompt_password("Wallet password: ")?;
+    if is_new && rpassword::prompt_password("Repeat the wallet password: ")? != password {
+        anyhow::bail!("The passwords don't match");
+    }
+    Ok(password)
+}
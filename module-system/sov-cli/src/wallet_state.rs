@@ -179,9 +179,38 @@ impl<Ctx: sov_modules_api::Context> AddressList<Ctx> {
             nickname,
             location,
             pub_key: public_key,
+            account_index: None,
         };
         self.addresses.push(entry);
     }
+
+    /// Add an address derived from the mnemonic of the keystore at `keystore_location`
+    pub fn add_derived(
+        &mut self,
+        address: Ctx::Address,
+        nickname: Option<String>,
+        public_key: Ctx::PublicKey,
+        keystore_location: PathBuf,
+        account_index: u32,
+    ) {
+        let entry = AddressEntry {
+            address,
+            nickname,
+            location: keystore_location,
+            pub_key: public_key,
+            account_index: Some(account_index),
+        };
+        self.addresses.push(entry);
+    }
+
+    /// The account index following the ones of the derived addresses
+    pub fn next_account_index(&self) -> u32 {
+        self.addresses
+            .iter()
+            .filter_map(|entry| entry.account_index)
+            .max()
+            .map_or(0, |index| index + 1)
+    }
 }
 
 /// An entry in the address list
@@ -192,11 +221,15 @@ pub struct AddressEntry<Ctx: sov_modules_api::Context> {
     pub address: Ctx::Address,
     /// A user-provided nickname
     pub nickname: Option<String>,
-    /// The location of the private key on disk
+    /// The location of the private key on disk, or of the keystore it's derived from
     pub location: PathBuf,
     /// The public key associated with the address
     #[serde(with = "pubkey_hex")]
     pub pub_key: Ctx::PublicKey,
+    /// The index of the account the private key is derived from, if it's derived from the
+    /// mnemonic of the keystore
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_index: Option<u32>,
 }
 
 impl<Ctx: sov_modules_api::Context> AddressEntry<Ctx> {
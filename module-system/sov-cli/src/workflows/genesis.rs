@@ -0,0 +1,341 @@
+//! Genesis workflows for the sov CLI wallet
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sov_accounts::AccountConfig;
+use sov_bank::{BankConfig, TokenConfig};
+use sov_modules_api::clap;
+
+/// The hash of empty EVM bytecode, the code hash of the prefunded accounts.
+const EMPTY_CODE_HASH: &str = "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470";
+
+#[derive(clap::Subcommand)]
+/// Generate the genesis files of the rollup modules
+pub enum GenesisWorkflow {
+    /// Generate the genesis JSON file of every module from a TOML spec of the initial accounts,
+    /// sequencer and EVM state
+    Generate {
+        #[clap(short, long)]
+        /// The path of the TOML spec
+        spec: PathBuf,
+        #[clap(short, long)]
+        /// The directory the genesis files are written to
+        output_dir: PathBuf,
+    },
+}
+
+impl GenesisWorkflow {
+    /// Run the genesis workflow
+    pub fn run<C: sov_modules_api::Context>(self) -> Result<(), anyhow::Error> {
+        match self {
+            GenesisWorkflow::Generate { spec, output_dir } => {
+                let spec = GenesisSpec::<C>::from_toml_path(&spec)?;
+                let files = spec.generate()?;
+                write_genesis_files(&files, &output_dir)?;
+                println!(
+                    "Wrote {} genesis files to {}",
+                    files.len(),
+                    output_dir.display()
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The high level spec of the genesis of a rollup, from which the genesis files of all its
+/// modules are generated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct GenesisSpec<C: sov_modules_api::Context> {
+    /// The chain id of the EVM.
+    pub chain_id: u64,
+    /// The administrator of the rollup: it mints the token, pauses the modules with the circuit
+    /// breaker and feeds the price oracle.
+    pub admin: C::Address,
+    /// The token of the rollup, which the sequencers lock and fees are paid with.
+    pub token: TokenSpec,
+    /// The initial balances of the token.
+    #[serde(default)]
+    pub accounts: Vec<AccountSpec<C>>,
+    /// The preferred sequencer.
+    pub sequencer: SequencerSpec<C>,
+    /// The DA address the deposits of the bridge are sent to, in the format of the DA layer.
+    pub bridge_deposit_address: String,
+    /// The hex encoded DA script the deposits of the bridge lock their funds in.
+    pub bridge_script: String,
+    /// The prefunded accounts of the EVM.
+    #[serde(default)]
+    pub evm: EvmSpec,
+}
+
+/// The token created at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenSpec {
+    /// The name of the token.
+    pub name: String,
+    /// The salt of the token address.
+    #[serde(default)]
+    pub salt: u64,
+}
+
+/// An initial balance of the token.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct AccountSpec<C: sov_modules_api::Context> {
+    /// The address of the account.
+    pub address: C::Address,
+    /// The balance of the account.
+    pub balance: u64,
+}
+
+/// The sequencer registered at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(bound = "C::Address: Serialize + DeserializeOwned")]
+pub struct SequencerSpec<C: sov_modules_api::Context> {
+    /// The rollup address of the sequencer, which needs a balance of at least the bond.
+    pub rollup_address: C::Address,
+    /// The DA address of the sequencer, in the format of the DA layer.
+    pub da_address: String,
+    /// The amount of tokens locked by the sequencer.
+    pub bond: u64,
+    /// How many slots the bond of a leaving sequencer stays locked.
+    #[serde(default = "default_unbonding_period")]
+    pub unbonding_period: u64,
+}
+
+fn default_unbonding_period() -> u64 {
+    100
+}
+
+/// The initial state of the EVM.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvmSpec {
+    /// The prefunded accounts.
+    #[serde(default)]
+    pub accounts: Vec<EvmAccountSpec>,
+}
+
+/// A prefunded EVM account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EvmAccountSpec {
+    /// The hex encoded address of the account.
+    pub address: String,
+    /// The hex encoded balance of the account, like `"0xffffffffffffffff"`.
+    pub balance: String,
+}
+
+impl<C: sov_modules_api::Context> GenesisSpec<C> {
+    /// Reads the spec from a TOML file.
+    pub fn from_toml_path(path: &Path) -> Result<Self, anyhow::Error> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Unable to read {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Invalid genesis spec {}", path.display()))
+    }
+
+    /// Returns the address of the token, as computed by the bank at genesis.
+    pub fn token_address(&self) -> C::Address {
+        sov_bank::get_genesis_token_address::<C>(&self.token.name, self.token.salt)
+    }
+
+    /// Generates the genesis file of each module, keyed by its file name.
+    pub fn generate(&self) -> Result<BTreeMap<&'static str, serde_json::Value>, anyhow::Error> {
+        self.validate()?;
+        let token_address = self.token_address();
+        let coins = json!({
+            "amount": self.sequencer.bond,
+            "token_address": token_address,
+        });
+
+        let bank = BankConfig::<C> {
+            tokens: vec![TokenConfig {
+                token_name: self.token.name.clone(),
+                address_and_balances: self
+                    .accounts
+                    .iter()
+                    .map(|account| (account.address.clone(), account.balance))
+                    .collect(),
+                authorized_minters: vec![self.admin.clone()],
+                salt: self.token.salt,
+                authority: None,
+                max_supply: None,
+            }],
+        };
+        let accounts = AccountConfig::<C> { pub_keys: vec![] };
+
+        let evm_accounts: Vec<_> = self
+            .evm
+            .accounts
+            .iter()
+            .map(|account| {
+                json!({
+                    "address": account.address,
+                    "balance": account.balance,
+                    "code_hash": EMPTY_CODE_HASH,
+                    "code": "0x",
+                    "nonce": 0,
+                })
+            })
+            .collect();
+
+        Ok(BTreeMap::from([
+            ("accounts.json", serde_json::to_value(accounts)?),
+            ("bank.json", serde_json::to_value(bank)?),
+            (
+                "bridge.json",
+                json!({
+                    "deposit_address": self.bridge_deposit_address,
+                    "bridge_script": self.bridge_script,
+                    "token_name": "sov-bridged-token",
+                    "salt": 0,
+                }),
+            ),
+            (
+                "chain_state.json",
+                json!({
+                    "initial_slot_height": 0,
+                    "current_time": { "secs": 0, "nanos": 0 },
+                    "gas_price": [0, 0],
+                }),
+            ),
+            (
+                "circuit_breaker.json",
+                json!({ "admin": self.admin, "paused_modules": [] }),
+            ),
+            (
+                "evm.json",
+                json!({
+                    "data": evm_accounts,
+                    "chain_id": self.chain_id,
+                    "limit_contract_code_size": null,
+                    "spec": { "0": "SHANGHAI" },
+                    "coinbase": "0x0000000000000000000000000000000000000000",
+                    "starting_base_fee": 7,
+                    "block_gas_limit": 30000000,
+                    "genesis_timestamp": 0,
+                    "block_timestamp_delta": 1,
+                    "base_fee_params": {
+                        "max_change_denominator": 8,
+                        "elasticity_multiplier": 2,
+                    },
+                    "min_base_fee": 7,
+                }),
+            ),
+            (
+                "faucet.json",
+                json!({
+                    "token_address": token_address,
+                    "amount_per_request": 1000,
+                    "request_interval": 100,
+                    "max_requests_per_slot": 16,
+                    "daily_budget": 1000000,
+                    "slots_per_day": 7200,
+                }),
+            ),
+            (
+                "governance.json",
+                json!({
+                    "voting_rules": {
+                        "governance_token": token_address,
+                        "proposal_threshold": 1000,
+                        "quorum": 10000,
+                        "voting_period": 10,
+                        "timelock": 10,
+                    },
+                    "parameters": [
+                        { "name": "block_gas_limit", "value": 30000000 },
+                        { "name": "min_base_fee", "value": 7 },
+                        { "name": "sequencer_bond", "value": self.sequencer.bond },
+                    ],
+                }),
+            ),
+            ("nft.json", json!({})),
+            (
+                "price_oracle.json",
+                json!({ "price_feeder": self.admin, "prices": [] }),
+            ),
+            ("randomness.json", json!({})),
+            (
+                "scheduler.json",
+                json!({
+                    "fee": { "amount": 10, "token_address": token_address },
+                    "max_calls_per_slot": 16,
+                    "max_delay": 100000,
+                    "call_gas_limit": 100000,
+                }),
+            ),
+            (
+                "sequencer_registry.json",
+                json!({
+                    "seq_rollup_address": self.sequencer.rollup_address,
+                    "seq_da_address": self.sequencer.da_address,
+                    "coins_to_lock": coins,
+                    "unbonding_period": self.sequencer.unbonding_period,
+                    "is_preferred_sequencer": true,
+                }),
+            ),
+        ]))
+    }
+
+    /// Checks that the sequencer can lock its bond, and that the EVM accounts are well formed.
+    fn validate(&self) -> Result<(), anyhow::Error> {
+        let sequencer_balance: u64 = self
+            .accounts
+            .iter()
+            .filter(|account| account.address == self.sequencer.rollup_address)
+            .map(|account| account.balance)
+            .sum();
+        if sequencer_balance < self.sequencer.bond {
+            anyhow::bail!(
+                "The sequencer {} has a balance of {}, lower than its bond {}",
+                self.sequencer.rollup_address,
+                sequencer_balance,
+                self.sequencer.bond
+            );
+        }
+        if self.bridge_deposit_address == self.sequencer.da_address {
+            anyhow::bail!("The bridge deposit address can't be the sequencer DA address");
+        }
+        if hex::decode(&self.bridge_script).map_or(true, |script| script.is_empty()) {
+            anyhow::bail!("The bridge script must be a non-empty hex string");
+        }
+
+        for account in &self.evm.accounts {
+            let address = hex::decode(account.address.trim_start_matches("0x"))
+                .with_context(|| format!("Invalid EVM address {}", account.address))?;
+            if address.len() != 20 {
+                anyhow::bail!("Invalid EVM address {}: expected 20 bytes", account.address);
+            }
+            let balance = account.balance.strip_prefix("0x").with_context(|| {
+                format!("The EVM balance {} isn't hex encoded", account.balance)
+            })?;
+            if balance.is_empty()
+                || balance.len() > 64
+                || !balance.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                anyhow::bail!("Invalid EVM balance {}", account.balance);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the genesis files to the directory, which is created if it doesn't exist.
+pub fn write_genesis_files(
+    files: &BTreeMap<&'static str, serde_json::Value>,
+    output_dir: &Path,
+) -> Result<(), anyhow::Error> {
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Unable to create {}", output_dir.display()))?;
+    for (file_name, contents) in files {
+        let path = output_dir.join(file_name);
+        std::fs::write(&path, serde_json::to_string_pretty(contents)?)
+            .with_context(|| format!("Unable to write to {}", path.display()))?;
+    }
+    Ok(())
+}
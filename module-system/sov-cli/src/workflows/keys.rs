@@ -6,7 +6,9 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_modules_api::{clap, PrivateKey, PublicKey, Spec};
 
-use crate::wallet_state::{KeyIdentifier, PrivateKeyAndAddress, WalletState};
+use crate::hd_wallet::{derivation_path, derive_private_key, EncryptedKeystore};
+use crate::wallet_state::{AddressEntry, KeyIdentifier, PrivateKeyAndAddress, WalletState};
+use crate::{keystore_path, read_password};
 
 #[derive(clap::Subcommand)]
 /// View and manage keys associated with this wallet
@@ -23,6 +25,16 @@ pub enum KeyWorkflow<C: sov_modules_api::Context> {
         /// A nickname for this key pair
         nickname: Option<String>,
     },
+    /// Derive the key pair of a named account from the wallet mnemonic.
+    /// The mnemonic is created with `wallet new` or `wallet import`
+    Derive {
+        #[clap(short, long)]
+        /// A nickname for this key pair
+        nickname: Option<String>,
+        #[clap(short, long)]
+        /// The index of the account (default: the one following the derived accounts)
+        account: Option<u32>,
+    },
     /// Import an existing key pair
     Import {
         #[clap(short, long)]
@@ -65,6 +77,27 @@ impl<C: sov_modules_api::Context> KeyWorkflow<C> {
             KeyWorkflow::Generate { nickname } => {
                 generate_and_save_key(nickname, app_dir, wallet_state)?;
             }
+            KeyWorkflow::Derive { nickname, account } => {
+                let account =
+                    account.unwrap_or_else(|| wallet_state.addresses.next_account_index());
+                let keystore_path = keystore_path(app_dir);
+                let mnemonic =
+                    EncryptedKeystore::load(&keystore_path)?.decrypt(&read_password(false)?)?;
+                let public_key = derive_private_key::<C>(&mnemonic, account)?.pub_key();
+                let address = public_key.to_address::<C::Address>();
+                println!(
+                    "Derived key pair at {}. address: {}",
+                    derivation_path(account),
+                    address
+                );
+                wallet_state.addresses.add_derived(
+                    address,
+                    nickname,
+                    public_key,
+                    keystore_path,
+                    account,
+                );
+            }
             KeyWorkflow::Import {
                 nickname,
                 address_override,
@@ -120,6 +153,21 @@ pub fn load_key<C: sov_modules_api::Context>(
     Ok(key_and_address.private_key)
 }
 
+/// Load the private key of an address of the wallet, from its key file or by deriving it
+/// from the mnemonic of the keystore. Decrypting the keystore requires the wallet password
+pub fn load_account_key<C: sov_modules_api::Context>(
+    account: &AddressEntry<C>,
+) -> Result<C::PrivateKey, anyhow::Error> {
+    match account.account_index {
+        Some(index) => {
+            let mnemonic =
+                EncryptedKeystore::load(&account.location)?.decrypt(&read_password(false)?)?;
+            derive_private_key::<C>(&mnemonic, index)
+        }
+        None => load_key::<C>(&account.location),
+    }
+}
+
 /// Generate a new key pair and save it to the wallet
 pub fn generate_and_save_key<Tx, C: sov_modules_api::Context>(
     nickname: Option<String>,
@@ -1,4 +1,7 @@
 //! Workflows for the CLI wallet
+pub mod genesis;
 pub mod keys;
 pub mod rpc;
+pub mod signing;
 pub mod transactions;
+pub mod wallet;
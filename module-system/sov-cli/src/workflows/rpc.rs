@@ -12,10 +12,10 @@ use serde::Serialize;
 use sov_accounts::AccountsRpcClient;
 use sov_bank::{BalanceResponse, BankRpcClient};
 use sov_modules_api::clap;
-use sov_modules_api::transaction::Transaction;
+use sov_modules_api::transaction::{Transaction, UnsignedTransaction};
 
 use crate::wallet_state::{AddressEntry, KeyIdentifier, WalletState};
-use crate::workflows::keys::load_key;
+use crate::workflows::keys::load_account_key;
 const NO_ACCOUNTS_FOUND: &str =
     "No accounts found. You can generate one with the `keys generate` subcommand";
 const BAD_RPC_URL: &str = "Unable to connect to provided rpc. You can change to a different rpc url with the `rpc set-url` subcommand ";
@@ -68,21 +68,31 @@ impl<C: sov_modules_api::Context> RpcWorkflows<C> {
             RpcWorkflows::GetBalance { account, .. } => account.as_ref(),
             RpcWorkflows::SubmitBatch { account, .. } => account.as_ref(),
         };
-
-        let account = if let Some(id) = account_id {
-            let addr = wallet_state.addresses.get_address(id);
-
-            addr.ok_or_else(|| anyhow::format_err!("No account found matching identifier: {}", id))?
-        } else {
-            wallet_state
-                .addresses
-                .default_address()
-                .ok_or_else(|| anyhow::format_err!(NO_ACCOUNTS_FOUND))?
-        };
-        Ok(account)
+        resolve_account(wallet_state, account_id)
     }
 }
 
+/// Returns the account matching the identifier, or the active account if there's none.
+pub(crate) fn resolve_account<'wallet, Tx, C: sov_modules_api::Context>(
+    wallet_state: &'wallet mut WalletState<Tx, C>,
+    account_id: Option<&KeyIdentifier<C>>,
+) -> Result<&'wallet AddressEntry<C>, anyhow::Error>
+where
+    Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
+{
+    let account = if let Some(id) = account_id {
+        let addr = wallet_state.addresses.get_address(id);
+
+        addr.ok_or_else(|| anyhow::format_err!("No account found matching identifier: {}", id))?
+    } else {
+        wallet_state
+            .addresses
+            .default_address()
+            .ok_or_else(|| anyhow::format_err!(NO_ACCOUNTS_FOUND))?
+    };
+    Ok(account)
+}
+
 impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> RpcWorkflows<C> {
     /// Run the rpc workflow
     pub async fn run<Tx>(
@@ -143,46 +153,70 @@ impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> R
                 );
             }
             RpcWorkflows::SubmitBatch { nonce_override, .. } => {
-                let private_key = load_key::<C>(&account.location)?;
+                let private_key = load_account_key::<C>(account)?;
 
                 let nonce = match nonce_override {
                     Some(nonce) => *nonce,
                     None => get_nonce_for_account(&client, account).await?,
                 };
 
-                let txs = mem::take(&mut wallet_state.unsent_transactions)
-                    .into_iter()
-                    .enumerate()
-                    .map(|(offset, tx)| {
-                        Transaction::<C>::new_signed_tx(
-                            &private_key,
-                            tx.try_to_vec().unwrap(),
-                            tx.chain_id,
-                            tx.gas_tip,
-                            tx.gas_limit,
-                            nonce + offset as u64,
-                        )
-                        .try_to_vec()
-                        .unwrap()
-                    })
-                    .collect::<Vec<_>>();
-
-                let response: String = client
-                    .request("sequencer_publishBatch", txs)
-                    .await
-                    .context("Unable to publish batch")?;
-
-                // Print the result
-                println!(
-                    "Your batch was submitted to the sequencer for publication. Response: {:?}",
-                    response
+                let txs = sign_batch(
+                    &private_key,
+                    mem::take(&mut wallet_state.unsent_transactions),
+                    nonce,
                 );
+                publish_batch(&client, &txs).await?;
             }
         }
         Ok(())
     }
 }
 
+/// Signs the transactions with sequential nonces, starting from `nonce`.
+pub(crate) fn sign_batch<C: sov_modules_api::Context, Tx: BorshSerialize + BorshDeserialize>(
+    private_key: &C::PrivateKey,
+    unsent_transactions: Vec<UnsignedTransaction<Tx>>,
+    nonce: u64,
+) -> Vec<Transaction<C>> {
+    unsent_transactions
+        .into_iter()
+        .enumerate()
+        .map(|(offset, tx)| {
+            Transaction::<C>::new_signed_tx(
+                private_key,
+                tx.try_to_vec().unwrap(),
+                tx.chain_id,
+                tx.gas_tip,
+                tx.gas_limit,
+                nonce + offset as u64,
+            )
+        })
+        .collect()
+}
+
+/// Submits the signed transactions to the sequencer as a single batch.
+pub(crate) async fn publish_batch<C: sov_modules_api::Context>(
+    client: &(impl ClientT + Send + Sync),
+    txs: &[Transaction<C>],
+) -> Result<(), anyhow::Error> {
+    let txs = txs
+        .iter()
+        .map(|tx| tx.try_to_vec().unwrap())
+        .collect::<Vec<_>>();
+
+    let response: String = client
+        .request("sequencer_publishBatch", txs)
+        .await
+        .context("Unable to publish batch")?;
+
+    // Print the result
+    println!(
+        "Your batch was submitted to the sequencer for publication. Response: {:?}",
+        response
+    );
+    Ok(())
+}
+
 async fn get_nonce_for_account<C: sov_modules_api::Context + Send + Sync + Serialize>(
     client: &(impl ClientT + Send + Sync),
     account: &AddressEntry<C>,
@@ -190,6 +224,7 @@ async fn get_nonce_for_account<C: sov_modules_api::Context + Send + Sync + Seria
     Ok(match AccountsRpcClient::<C>::get_account(
         client,
         account.pub_key.clone(),
+        None,
     )
     .await
     .context(
@@ -0,0 +1,125 @@
+//! Sign transactions offline and broadcast them from another machine
+
+use core::mem;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use borsh::{BorshDeserialize, BorshSerialize};
+use jsonrpsee::http_client::HttpClientBuilder;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sov_modules_api::clap;
+use sov_modules_api::transaction::Transaction;
+
+use crate::wallet_state::{KeyIdentifier, WalletState};
+use crate::workflows::keys::load_account_key;
+use crate::workflows::rpc::{publish_batch, resolve_account, sign_batch};
+
+/// Sign transactions without connecting to the rollup, and broadcast signed transactions.
+///
+/// This lets operators keep their keys on a machine which is never online: the batch is signed
+/// there with `tx sign`, and the resulting file is carried to a connected machine to be
+/// submitted with `tx broadcast`.
+#[derive(clap::Subcommand)]
+pub enum SigningWorkflow<C: sov_modules_api::Context> {
+    /// Sign all transactions from the current batch and write them to a file, without connecting to the rollup.
+    /// The current batch is cleared.
+    Sign {
+        /// (Optional) The account to sign transactions for this batch (default: the active account)
+        #[clap(subcommand)]
+        account: Option<KeyIdentifier<C>>,
+        /// The nonce to use for the first transaction in the batch. Any other transactions will be signed
+        /// with sequential nonces starting from this value. It can be queried with `rpc get-nonce` from a connected machine.
+        #[arg(long)]
+        nonce: u64,
+        /// The file to write the signed transactions to
+        #[arg(long, short)]
+        output: PathBuf,
+    },
+    /// Submit the transactions of a file written by `tx sign` to the rollup
+    Broadcast {
+        /// The file containing the signed transactions
+        path: PathBuf,
+        /// (Optional) The url of the rpc server to use (default: the url set with `rpc set-url`)
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
+}
+
+impl<C: sov_modules_api::Context + Serialize + DeserializeOwned + Send + Sync> SigningWorkflow<C> {
+    /// Run the signing workflow
+    pub async fn run<Tx>(
+        self,
+        wallet_state: &mut WalletState<Tx, C>,
+        _app_dir: impl AsRef<Path>,
+    ) -> Result<(), anyhow::Error>
+    where
+        Tx: Serialize + DeserializeOwned + BorshSerialize + BorshDeserialize,
+    {
+        match self {
+            SigningWorkflow::Sign {
+                account,
+                nonce,
+                output,
+            } => {
+                if wallet_state.unsent_transactions.is_empty() {
+                    anyhow::bail!(
+                        "The current batch is empty. You can add transactions to it with the `transactions import` subcommand"
+                    );
+                }
+                let account = resolve_account(wallet_state, account.as_ref())?;
+                let private_key = load_account_key::<C>(account)?;
+
+                let txs = sign_batch(
+                    &private_key,
+                    mem::take(&mut wallet_state.unsent_transactions),
+                    nonce,
+                );
+                std::fs::write(&output, txs.try_to_vec()?)
+                    .with_context(|| format!("Unable to write to {}", output.display()))?;
+
+                println!(
+                    "Signed {} transactions with nonces starting from {}. Saved to {}",
+                    txs.len(),
+                    nonce,
+                    output.display()
+                );
+            }
+            SigningWorkflow::Broadcast { path, rpc_url } => {
+                let txs = load_signed_batch::<C>(&path)?;
+
+                let rpc_url = rpc_url
+                    .or_else(|| wallet_state.rpc_url.clone())
+                    .ok_or(anyhow::format_err!(
+                        "No rpc url set. Use the `rpc set-url` subcommand or the `--rpc-url` flag to set one"
+                    ))?;
+                let client = HttpClientBuilder::default()
+                    .build(rpc_url)
+                    .context("Invalid RPC url: ")?;
+
+                publish_batch(&client, &txs).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Load the transactions written by `tx sign` from the given path, and check their signatures
+pub fn load_signed_batch<C: sov_modules_api::Context>(
+    path: impl AsRef<Path>,
+) -> Result<Vec<Transaction<C>>, anyhow::Error> {
+    let path = path.as_ref();
+    let data = std::fs::read(path).with_context(|| format!("Unable to read {}", path.display()))?;
+    let txs = Vec::<Transaction<C>>::try_from_slice(&data)
+        .with_context(|| format!("{} doesn't contain signed transactions", path.display()))?;
+
+    for tx in &txs {
+        tx.verify().with_context(|| {
+            format!(
+                "The transaction with nonce {} has an invalid signature",
+                tx.nonce()
+            )
+        })?;
+    }
+    Ok(txs)
+}
@@ -0,0 +1,85 @@
+//! Mnemonic management workflows for the sov CLI wallet
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use sov_modules_api::clap;
+
+use crate::hd_wallet::{generate_mnemonic, EncryptedKeystore, Mnemonic};
+use crate::{keystore_path, read_password};
+
+#[derive(clap::Subcommand)]
+/// Create, import and export the mnemonic the named accounts of the wallet are derived from.
+/// The mnemonic is stored in a keystore encrypted with the wallet password
+pub enum WalletWorkflow {
+    /// Generate a new 24 words mnemonic, and print it once
+    New,
+    /// Import an existing BIP-39 mnemonic, read from the standard input
+    Import {
+        #[clap(short, long)]
+        /// (Optional) The path of a file containing the mnemonic, instead of the standard input
+        path: Option<PathBuf>,
+    },
+    /// Export the mnemonic, printed to the standard output
+    Export {
+        #[clap(short, long)]
+        /// (Optional) The path of a file to write the mnemonic to, instead of the standard output
+        path: Option<PathBuf>,
+    },
+}
+
+impl WalletWorkflow {
+    /// Run the wallet workflow to create, import or export the mnemonic
+    pub fn run(self, app_dir: impl AsRef<Path>) -> Result<(), anyhow::Error> {
+        let keystore_path = keystore_path(app_dir);
+        match self {
+            WalletWorkflow::New => {
+                let mnemonic = generate_mnemonic();
+                save_mnemonic(&mnemonic, &keystore_path)?;
+                println!(
+                    "Generated a new mnemonic. Write it down, it's the only backup of the derived keys:\n{}",
+                    mnemonic
+                );
+            }
+            WalletWorkflow::Import { path } => {
+                let phrase = match path {
+                    Some(path) => std::fs::read_to_string(&path)
+                        .with_context(|| format!("Unable to read {}", path.display()))?,
+                    None => {
+                        let mut phrase = String::new();
+                        std::io::stdin().read_to_string(&mut phrase)?;
+                        phrase
+                    }
+                };
+                let mnemonic = Mnemonic::parse(phrase.trim()).context("Invalid mnemonic")?;
+                save_mnemonic(&mnemonic, &keystore_path)?;
+                println!("Imported the mnemonic to {}", keystore_path.display());
+            }
+            WalletWorkflow::Export { path } => {
+                let mnemonic =
+                    EncryptedKeystore::load(&keystore_path)?.decrypt(&read_password(false)?)?;
+                match path {
+                    Some(path) => {
+                        std::fs::write(&path, mnemonic.to_string())
+                            .with_context(|| format!("Unable to write to {}", path.display()))?;
+                        println!("Exported the mnemonic to {}", path.display());
+                    }
+                    None => println!("{}", mnemonic),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encrypts the mnemonic to the keystore. An existing keystore is never overwritten,
+/// since the keys derived from it would be lost.
+fn save_mnemonic(mnemonic: &Mnemonic, keystore_path: &Path) -> Result<(), anyhow::Error> {
+    if keystore_path.exists() {
+        anyhow::bail!(
+            "The wallet already has a mnemonic at {}. Export it with `wallet export` and remove the keystore first",
+            keystore_path.display()
+        );
+    }
+    EncryptedKeystore::encrypt(mnemonic, &read_password(true)?)?.save(keystore_path)
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use demo_stf::genesis_config::{get_genesis_config, GenesisPaths};
+use sov_bank::BankConfig;
+use sov_cli::workflows::genesis::{GenesisSpec, GenesisWorkflow};
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+
+const DEMO_GENESIS_DIR: &str = "../../examples/test-data/genesis/demo-tests";
+
+fn read_json(path: &Path) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+#[test]
+fn test_generated_genesis_matches_the_demo_files() {
+    let demo_dir = Path::new(DEMO_GENESIS_DIR);
+    let output_dir = tempfile::tempdir().unwrap();
+    let workflow = GenesisWorkflow::Generate {
+        spec: demo_dir.join("mock.toml"),
+        output_dir: output_dir.path().to_path_buf(),
+    };
+    workflow.run::<DefaultContext>().unwrap();
+
+    for entry in std::fs::read_dir(demo_dir.join("mock")).unwrap() {
+        let expected_path = entry.unwrap().path();
+        let file_name = expected_path.file_name().unwrap();
+        let generated_path = output_dir.path().join(file_name);
+        if file_name == "bank.json" {
+            // The optional fields of the tokens are written explicitly
+            let expected: BankConfig<DefaultContext> =
+                serde_json::from_value(read_json(&expected_path)).unwrap();
+            let generated: BankConfig<DefaultContext> =
+                serde_json::from_value(read_json(&generated_path)).unwrap();
+            assert_eq!(expected, generated);
+        } else {
+            assert_eq!(
+                read_json(&expected_path),
+                read_json(&generated_path),
+                "{:?} differs",
+                file_name
+            );
+        }
+    }
+
+    get_genesis_config::<DefaultContext, MockDaSpec>(&GenesisPaths::from_dir(output_dir.path()))
+        .unwrap();
+}
+
+#[test]
+fn test_sequencer_needs_a_balance_for_its_bond() {
+    let mut spec = GenesisSpec::<DefaultContext>::from_toml_path(
+        &Path::new(DEMO_GENESIS_DIR).join("mock.toml"),
+    )
+    .unwrap();
+    spec.accounts[0].balance = 10;
+
+    let error = spec.generate().unwrap_err();
+    assert!(error.to_string().contains("lower than its bond 50"));
+}
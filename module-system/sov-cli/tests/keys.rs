@@ -1,6 +1,8 @@
 use demo_stf::runtime::RuntimeCall;
+use sov_cli::hd_wallet::generate_mnemonic;
 use sov_cli::wallet_state::{KeyIdentifier, PrivateKeyAndAddress, WalletState};
-use sov_cli::workflows::keys::KeyWorkflow;
+use sov_cli::workflows::keys::{load_account_key, KeyWorkflow};
+use sov_cli::workflows::wallet::WalletWorkflow;
 use sov_mock_da::MockDaSpec;
 use sov_modules_api::default_context::DefaultContext;
 use sov_modules_api::{PrivateKey, PublicKey, Spec};
@@ -96,3 +98,63 @@ fn test_activate() {
     let current_active_wallet = wallet_state.addresses.default_address().unwrap();
     assert!(current_active_wallet.is_nicknamed("key1"));
 }
+
+#[test]
+fn test_derived_accounts() {
+    std::env::set_var("SOV_WALLET_PASSWORD", "test-password");
+    let app_dir = tempfile::tempdir().unwrap();
+    let mnemonic_path = app_dir.path().join("mnemonic.txt");
+    let mnemonic = generate_mnemonic();
+    std::fs::write(&mnemonic_path, mnemonic.to_string()).unwrap();
+
+    // Import the mnemonic, which is only stored encrypted
+    let workflow = WalletWorkflow::Import {
+        path: Some(mnemonic_path.clone()),
+    };
+    workflow.run(&app_dir).unwrap();
+    let keystore = std::fs::read_to_string(sov_cli::keystore_path(&app_dir)).unwrap();
+    assert!(!keystore.contains(&mnemonic.to_string()));
+    // The keystore isn't overwritten
+    let workflow = WalletWorkflow::New;
+    assert!(workflow.run(&app_dir).is_err());
+
+    // Derive two named accounts
+    let mut wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    for nickname in ["treasury", "operator"] {
+        let workflow = KeyWorkflow::Derive {
+            nickname: Some(nickname.to_string()),
+            account: None,
+        };
+        workflow.run(&mut wallet_state, &app_dir).unwrap();
+    }
+    let treasury = wallet_state
+        .addresses
+        .get_address(&KeyIdentifier::ByNickname {
+            nickname: "treasury".to_string(),
+        })
+        .unwrap();
+    assert_eq!(Some(0), treasury.account_index);
+    let private_key = load_account_key::<DefaultContext>(treasury).unwrap();
+    assert_eq!(treasury.pub_key, private_key.pub_key());
+    let treasury_address = treasury.address;
+    let operator = wallet_state
+        .addresses
+        .get_address(&KeyIdentifier::ByNickname {
+            nickname: "operator".to_string(),
+        })
+        .unwrap();
+    assert_eq!(Some(1), operator.account_index);
+    assert_ne!(treasury_address, operator.address);
+
+    // The exported mnemonic derives the same keys
+    let export_path = app_dir.path().join("export.txt");
+    let workflow = WalletWorkflow::Export {
+        path: Some(export_path.clone()),
+    };
+    workflow.run(&app_dir).unwrap();
+    assert_eq!(
+        mnemonic.to_string(),
+        std::fs::read_to_string(export_path).unwrap()
+    );
+}
@@ -0,0 +1,72 @@
+use demo_stf::runtime::RuntimeCall;
+use sov_cli::wallet_state::WalletState;
+use sov_cli::workflows::keys::KeyWorkflow;
+use sov_cli::workflows::signing::{load_signed_batch, SigningWorkflow};
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::transaction::UnsignedTransaction;
+use sov_modules_api::utils::generate_address;
+
+type Da = MockDaSpec;
+
+fn unsigned_tx() -> UnsignedTransaction<RuntimeCall<DefaultContext, Da>> {
+    let tx = RuntimeCall::bank(sov_bank::CallMessage::Freeze {
+        token_address: generate_address::<DefaultContext>("token"),
+    });
+    UnsignedTransaction::new(tx, 0, 0, 0)
+}
+
+#[tokio::test]
+async fn test_sign_offline() {
+    let app_dir = tempfile::tempdir().unwrap();
+    let mut wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    KeyWorkflow::Generate { nickname: None }
+        .run(&mut wallet_state, &app_dir)
+        .unwrap();
+    wallet_state.unsent_transactions.push(unsigned_tx());
+    wallet_state.unsent_transactions.push(unsigned_tx());
+
+    let output = app_dir.path().join("tx.bin");
+    let workflow = SigningWorkflow::Sign {
+        account: None,
+        nonce: 5,
+        output: output.clone(),
+    };
+    workflow.run(&mut wallet_state, &app_dir).await.unwrap();
+    assert!(wallet_state.unsent_transactions.is_empty());
+
+    let txs = load_signed_batch::<DefaultContext>(&output).unwrap();
+    let nonces = txs.iter().map(|tx| tx.nonce()).collect::<Vec<_>>();
+    assert_eq!(nonces, vec![5, 6]);
+    assert_eq!(
+        txs[0].pub_key(),
+        &wallet_state.addresses.default_address().unwrap().pub_key
+    );
+
+    // Corrupted files are rejected
+    let mut data = std::fs::read(&output).unwrap();
+    let last = data.len() - 1;
+    data[last] ^= 1;
+    std::fs::write(&output, data).unwrap();
+    assert!(load_signed_batch::<DefaultContext>(&output).is_err());
+}
+
+#[tokio::test]
+async fn test_sign_empty_batch() {
+    let app_dir = tempfile::tempdir().unwrap();
+    let mut wallet_state =
+        WalletState::<RuntimeCall<DefaultContext, Da>, DefaultContext>::default();
+    KeyWorkflow::Generate { nickname: None }
+        .run(&mut wallet_state, &app_dir)
+        .unwrap();
+
+    let output = app_dir.path().join("tx.bin");
+    let workflow = SigningWorkflow::Sign {
+        account: None,
+        nonce: 0,
+        output: output.clone(),
+    };
+    assert!(workflow.run(&mut wallet_state, &app_dir).await.is_err());
+    assert!(!output.exists());
+}
@@ -0,0 +1,218 @@
+#![deny(missing_docs)]
+#![doc = include_str!("../README.md")]
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use anyhow::Context as _;
+use borsh::BorshSerialize;
+use jsonrpsee::core::client::ClientT;
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sov_accounts::AccountsRpcClient;
+use sov_modules_api::digest::Digest;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{
+    CallMessageSubmitter, Context, DispatchCall, PrivateKey, PublicKey, RuntimeCallSubmitter, Spec,
+};
+
+/// A transaction submitted to `sequencer_acceptTx`, see `sov_sequencer::SubmitTransaction`.
+#[derive(Serialize)]
+struct SubmitTransaction {
+    body: Vec<u8>,
+}
+
+/// The result of `sequencer_acceptTx`, see `sov_sequencer::SubmitTransactionResponse`.
+#[derive(Deserialize)]
+enum SubmitTransactionResponse {
+    Registered,
+    Failed(String),
+}
+
+/// A client submitting the calls of the runtime `R` to a rollup, signed with its private key.
+///
+/// The nonce of the account is queried with `accounts_getAccount` before the first submission,
+/// and incremented locally after every transaction accepted by the sequencer, so consecutive
+/// transactions can be submitted without waiting for them to be included.
+///
+/// The clients of the modules of a runtime deriving `ModuleClients` are returned by the methods
+/// named after them, like `client.bank()`. They submit the call messages built by the builders
+/// derived with `CallMessageBuilder`, while the modules are queried with their RPC client trait:
+///
+/// ```rust,ignore
+/// use demo_stf::runtime::RuntimeModuleClients;
+/// use sov_bank::{BankRpcClient, CallMessageBuilder};
+///
+/// let mut client = RollupClient::<DefaultContext, Runtime<DefaultContext, MockDaSpec>>::new(url, key, chain_id)?;
+/// let tx_hash = client.bank().transfer(to, coins).await?;
+/// let balance = BankRpcClient::<DefaultContext>::balance_of(client.rpc(), None, to, token_address).await?;
+/// ```
+pub struct RollupClient<C: Context, R: DispatchCall> {
+    rpc: HttpClient,
+    private_key: C::PrivateKey,
+    chain_id: u64,
+    gas_tip: u64,
+    gas_limit: u64,
+    next_nonce: Option<u64>,
+    _runtime: PhantomData<R>,
+}
+
+impl<C, R> RollupClient<C, R>
+where
+    C: Context + Serialize + DeserializeOwned + Send + Sync,
+    R: DispatchCall<Context = C>,
+    R::Decodable: BorshSerialize,
+{
+    /// Creates a client of the rollup with the given JSON-RPC url, signing the transactions
+    /// for the given chain with the private key. Transactions have no gas tip nor limit by default.
+    pub fn new(url: &str, private_key: C::PrivateKey, chain_id: u64) -> anyhow::Result<Self> {
+        Ok(Self {
+            rpc: HttpClientBuilder::default()
+                .build(url)
+                .with_context(|| format!("Invalid RPC url: {}", url))?,
+            private_key,
+            chain_id,
+            gas_tip: 0,
+            gas_limit: 0,
+            next_nonce: None,
+            _runtime: PhantomData,
+        })
+    }
+
+    /// Signs the next transactions with the given gas tip and limit.
+    pub fn with_gas(mut self, gas_tip: u64, gas_limit: u64) -> Self {
+        self.gas_tip = gas_tip;
+        self.gas_limit = gas_limit;
+        self
+    }
+
+    /// Returns the JSON-RPC client of the rollup, to query it with the RPC client traits of
+    /// the modules, like `BankRpcClient`.
+    pub fn rpc(&self) -> &HttpClient {
+        &self.rpc
+    }
+
+    /// Returns the address of the account signing the transactions.
+    pub fn address(&self) -> C::Address {
+        self.private_key.pub_key().to_address()
+    }
+
+    /// Returns the nonce of the next transaction, which is queried from the rollup on first use.
+    pub async fn nonce(&mut self) -> anyhow::Result<u64> {
+        match self.next_nonce {
+            Some(nonce) => Ok(nonce),
+            None => self.sync_nonce().await,
+        }
+    }
+
+    /// Queries the nonce of the next transaction from the rollup again, like after transactions
+    /// submitted by another client of the account, or dropped by the sequencer.
+    pub async fn sync_nonce(&mut self) -> anyhow::Result<u64> {
+        let response =
+            AccountsRpcClient::<C>::get_account(&self.rpc, self.private_key.pub_key(), None)
+                .await
+                .context("Failed to query the nonce of the account")?;
+        let nonce = match response {
+            sov_accounts::Response::AccountExists { nonce, .. } => nonce,
+            sov_accounts::Response::AccountEmpty => 0,
+        };
+        self.next_nonce = Some(nonce);
+        Ok(nonce)
+    }
+
+    /// Signs the call of the runtime with the given nonce.
+    pub fn sign(&self, call: &R::Decodable, nonce: u64) -> anyhow::Result<Transaction<C>> {
+        Ok(Transaction::<C>::new_signed_tx(
+            &self.private_key,
+            borsh::to_vec(call)?,
+            self.chain_id,
+            self.gas_tip,
+            self.gas_limit,
+            nonce,
+        ))
+    }
+
+    /// Signs the call of the runtime with the next nonce and submits it to the mempool of the
+    /// sequencer with `sequencer_acceptTx`. Returns the hash of the transaction.
+    pub async fn submit(&mut self, call: R::Decodable) -> anyhow::Result<[u8; 32]> {
+        let nonce = self.nonce().await?;
+        let body = borsh::to_vec(&self.sign(&call, nonce)?)?;
+        let hash = <C as Spec>::Hasher::digest(&body).into();
+
+        let response: SubmitTransactionResponse = self
+            .rpc
+            .request("sequencer_acceptTx", [SubmitTransaction { body }])
+            .await
+            .context("Failed to submit the transaction")?;
+        match response {
+            SubmitTransactionResponse::Registered => {
+                self.next_nonce = Some(nonce + 1);
+                Ok(hash)
+            }
+            SubmitTransactionResponse::Failed(reason) => {
+                anyhow::bail!("The sequencer rejected the transaction: {}", reason)
+            }
+        }
+    }
+
+    /// Asks the sequencer to publish a batch of the transactions of its mempool,
+    /// with `sequencer_publishBatch`.
+    pub async fn publish_batch(&self) -> anyhow::Result<()> {
+        let no_txs: [Vec<u8>; 0] = [];
+        let _: String = self
+            .rpc
+            .request("sequencer_publishBatch", no_txs)
+            .await
+            .context("Failed to publish a batch")?;
+        Ok(())
+    }
+
+    /// Returns the client of a module, whose call messages are wrapped into the calls of the
+    /// runtime with `wrap`, typically a variant of the call enum of the runtime.
+    pub fn module<M>(&mut self, wrap: fn(M) -> R::Decodable) -> ModuleClient<'_, C, R, M> {
+        ModuleClient { client: self, wrap }
+    }
+}
+
+impl<C, R> RuntimeCallSubmitter<R> for RollupClient<C, R>
+where
+    C: Context + Serialize + DeserializeOwned + Send + Sync,
+    R: DispatchCall<Context = C>,
+    R::Decodable: BorshSerialize,
+{
+    type ModuleSubmitter<'a, M>
+        = ModuleClient<'a, C, R, M>
+    where
+        Self: 'a;
+
+    fn module_submitter<M>(&mut self, wrap: fn(M) -> R::Decodable) -> ModuleClient<'_, C, R, M> {
+        self.module(wrap)
+    }
+}
+
+/// The client of a module of the runtime `R`, returned by [`RollupClient::module`].
+///
+/// It submits the call messages `M` of the module through its [`RollupClient`], and has the
+/// builders derived with `CallMessageBuilder` for them.
+pub struct ModuleClient<'a, C: Context, R: DispatchCall, M> {
+    client: &'a mut RollupClient<C, R>,
+    wrap: fn(M) -> R::Decodable,
+}
+
+impl<'a, C, R, M> CallMessageSubmitter for ModuleClient<'a, C, R, M>
+where
+    C: Context + Serialize + DeserializeOwned + Send + Sync,
+    R: DispatchCall<Context = C>,
+    R::Decodable: BorshSerialize,
+{
+    type CallMessage = M;
+    type Output = Pin<Box<dyn Future<Output = anyhow::Result<[u8; 32]>> + Send + 'a>>;
+
+    /// Submits the call message with [`RollupClient::submit`].
+    fn submit_call(self, call_message: M) -> Self::Output {
+        let ModuleClient { client, wrap } = self;
+        Box::pin(client.submit(wrap(call_message)))
+    }
+}
@@ -0,0 +1,110 @@
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use borsh::BorshDeserialize;
+use demo_stf::runtime::{Runtime, RuntimeCall, RuntimeModuleClients};
+use jsonrpsee::RpcModule;
+use sov_bank::{CallMessageBuilder, Coins};
+use sov_client::RollupClient;
+use sov_mock_da::MockDaSpec;
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+use sov_modules_api::transaction::Transaction;
+use sov_modules_api::{Address, AddressBech32, PrivateKey};
+
+type RT = Runtime<DefaultContext, MockDaSpec>;
+
+const CHAIN_ID: u64 = 7;
+
+/// Serves the nonce of the account, and records the transactions submitted to the sequencer.
+async fn rpc_server(
+    address: Address,
+    nonce: u64,
+) -> (
+    jsonrpsee::server::ServerHandle,
+    SocketAddr,
+    Arc<Mutex<Vec<Vec<u8>>>>,
+) {
+    let submitted = Arc::new(Mutex::new(Vec::new()));
+    let mut rpc_module = RpcModule::new(submitted.clone());
+    rpc_module
+        .register_method("accounts_getAccount", move |_, _| {
+            Ok::<_, jsonrpsee::types::ErrorObjectOwned>(sov_accounts::Response::AccountExists {
+                addr: AddressBech32::from(&address),
+                nonce,
+            })
+        })
+        .unwrap();
+    rpc_module
+        .register_method("sequencer_acceptTx", |params, submitted| {
+            let tx: serde_json::Value = params.one()?;
+            let body: Vec<u8> = serde_json::from_value(tx["body"].clone()).unwrap();
+            submitted.lock().unwrap().push(body);
+            Ok::<_, jsonrpsee::types::ErrorObjectOwned>("Registered")
+        })
+        .unwrap();
+
+    let server = jsonrpsee::server::ServerBuilder::default()
+        .build("127.0.0.1:0")
+        .await
+        .unwrap();
+    let addr = server.local_addr().unwrap();
+    (server.start(rpc_module), addr, submitted)
+}
+
+#[tokio::test]
+async fn submit_module_calls() {
+    let private_key = DefaultPrivateKey::generate();
+    let address: Address = private_key.pub_key().to_address();
+    let (_server, addr, submitted) = rpc_server(address.clone(), 5).await;
+
+    let mut client =
+        RollupClient::<DefaultContext, RT>::new(&format!("http://{}", addr), private_key, CHAIN_ID)
+            .unwrap()
+            .with_gas(1, 100);
+    assert_eq!(address, client.address());
+
+    let to = Address::from([2; 32]);
+    let coins = Coins {
+        amount: 10,
+        token_address: Address::from([3; 32]),
+    };
+    let first_hash = client
+        .bank()
+        .transfer(to.clone(), coins.clone())
+        .await
+        .unwrap();
+    let second_hash = client.bank().burn(coins.clone()).await.unwrap();
+    assert_ne!(first_hash, second_hash);
+    assert_eq!(7, client.nonce().await.unwrap());
+
+    let submitted = submitted.lock().unwrap();
+    assert_eq!(2, submitted.len());
+    let txs: Vec<_> = submitted
+        .iter()
+        .map(|body| Transaction::<DefaultContext>::try_from_slice(body).unwrap())
+        .collect();
+    for (tx, nonce) in txs.iter().zip([5, 6]) {
+        tx.verify().unwrap();
+        assert_eq!(nonce, tx.nonce());
+        assert_eq!(CHAIN_ID, tx.chain_id());
+        assert_eq!(1, tx.gas_tip());
+        assert_eq!(100, tx.gas_limit());
+    }
+
+    let call =
+        RuntimeCall::<DefaultContext, MockDaSpec>::try_from_slice(txs[0].runtime_msg()).unwrap();
+    assert_eq!(
+        RuntimeCall::bank(sov_bank::CallMessage::Transfer {
+            to,
+            coins: coins.clone(),
+        }),
+        call
+    );
+    let call =
+        RuntimeCall::<DefaultContext, MockDaSpec>::try_from_slice(txs[1].runtime_msg()).unwrap();
+    assert_eq!(
+        RuntimeCall::bank(sov_bank::CallMessage::Burn { coins }),
+        call
+    );
+}
@@ -0,0 +1,65 @@
+use sov_bank::Amount;
+use sov_governance::{Governance, ParameterValue};
+
+use super::*;
+use crate::EncodeCall;
+
+const DEFAULT_CHAIN_ID: u64 = 0;
+const DEFAULT_GAS_TIP: u64 = 0;
+const DEFAULT_GAS_LIMIT: u64 = 0;
+
+/// A proposal to change a governed parameter, followed by the vote of its proposer.
+pub struct GovernanceMessages<C: Context> {
+    pub proposer: Rc<C::PrivateKey>,
+    /// The identifier the proposal gets, which is the number of proposals submitted before it.
+    pub proposal_id: u64,
+    pub parameter: String,
+    pub value: ParameterValue,
+    pub vote_amount: Amount,
+}
+
+impl<C: Context> MessageGenerator for GovernanceMessages<C> {
+    type Module = Governance<C>;
+    type Context = C;
+
+    fn create_messages(&self) -> Vec<Message<Self::Context, Self::Module>> {
+        let propose_msg = sov_governance::CallMessage::Propose {
+            parameter: self.parameter.clone(),
+            value: self.value,
+        };
+        let vote_msg = sov_governance::CallMessage::Vote {
+            proposal_id: self.proposal_id,
+            approve: true,
+            amount: self.vote_amount,
+        };
+
+        [propose_msg, vote_msg]
+            .into_iter()
+            .enumerate()
+            .map(|(nonce, msg)| {
+                Message::new(
+                    self.proposer.clone(),
+                    msg,
+                    DEFAULT_CHAIN_ID,
+                    DEFAULT_GAS_TIP,
+                    DEFAULT_GAS_LIMIT,
+                    nonce.try_into().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn create_tx<Encoder: EncodeCall<Self::Module>>(
+        &self,
+        sender: &C::PrivateKey,
+        message: <Self::Module as Module>::CallMessage,
+        chain_id: u64,
+        gas_tip: u64,
+        gas_limit: u64,
+        nonce: u64,
+        _is_last: bool,
+    ) -> Transaction<C> {
+        let message = Encoder::encode_call(message);
+        Transaction::<C>::new_signed_tx(sender, message, chain_id, gas_tip, gas_limit, nonce)
+    }
+}
@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 
 use sov_modules_core::{Context, Prefix, StateCodec, StateKeyCodec, StateValueCodec, WorkingSet};
+#[cfg(feature = "native")]
+use sov_modules_core::{StorageKey, StorageValue};
 use sov_state::codec::BorshCodec;
 
 use super::traits::StateMapAccessor;
@@ -72,6 +74,92 @@ where
     }
 }
 
+/// Iteration over the entries of a [`StateMap`], for RPC queries and other native tooling.
+///
+/// The entries are ordered by the bytes of their encoded keys, which only matches the order of
+/// the keys themselves for some codecs and key types. For instance, [`BorshCodec`] encodes
+/// integers as little-endian. The merkle tree orders the entries by the hashes of their keys,
+/// so iteration can't be proven and is not available inside the zkVM.
+///
+/// The keys are decoded with the [`StateValueCodec`] implementation of the key codec.
+#[cfg(feature = "native")]
+impl<K, V, Codec> StateMap<K, V, Codec>
+where
+    Codec: StateCodec,
+    Codec::KeyCodec: StateKeyCodec<K> + StateValueCodec<K>,
+    Codec::ValueCodec: StateValueCodec<V>,
+{
+    /// Returns the entries with keys in `start..end`, up to `limit` of them.
+    /// A missing bound means that the range is unbounded on this side.
+    pub fn iter_range<C>(
+        &self,
+        start: Option<&K>,
+        end: Option<&K>,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<(K, V)>
+    where
+        C: Context,
+        C::Storage: sov_modules_core::NativeStorage,
+    {
+        let prefix = StorageKey::singleton(&self.prefix);
+        let start =
+            start.map(|key| StorageKey::new::<K, K, _>(&self.prefix, key, self.codec.key_codec()));
+        let end =
+            end.map(|key| StorageKey::new::<K, K, _>(&self.prefix, key, self.codec.key_codec()));
+        self.decode_entries(working_set.get_by_prefix(
+            prefix.as_ref(),
+            start.as_ref().map(|key| key.as_ref().as_slice()),
+            end.as_ref().map(|key| key.as_ref().as_slice()),
+            limit,
+        ))
+    }
+
+    /// Returns the entries with encoded keys starting with the encoding of `key_prefix`, up to
+    /// `limit` of them. The keys before `start` are skipped, which allows to query the entries
+    /// page by page.
+    ///
+    /// With [`BorshCodec`], for instance, the entries of a map keyed by `(owner, token_id)` can be
+    /// listed for a single owner by using the owner as the prefix.
+    pub fn iter_prefix<P, C>(
+        &self,
+        key_prefix: &P,
+        start: Option<&K>,
+        limit: usize,
+        working_set: &mut WorkingSet<C>,
+    ) -> Vec<(K, V)>
+    where
+        Codec::KeyCodec: StateKeyCodec<P>,
+        C: Context,
+        C::Storage: sov_modules_core::NativeStorage,
+    {
+        let prefix = StorageKey::new::<P, P, _>(&self.prefix, key_prefix, self.codec.key_codec());
+        let start =
+            start.map(|key| StorageKey::new::<K, K, _>(&self.prefix, key, self.codec.key_codec()));
+        self.decode_entries(working_set.get_by_prefix(
+            prefix.as_ref(),
+            start.as_ref().map(|key| key.as_ref().as_slice()),
+            None,
+            limit,
+        ))
+    }
+
+    fn decode_entries(&self, entries: Vec<(StorageKey, StorageValue)>) -> Vec<(K, V)> {
+        let prefix_len = self.prefix.len();
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let key = self
+                    .codec
+                    .key_codec()
+                    .decode_value_unwrap(&key.as_ref()[prefix_len..]);
+                let value = self.codec.value_codec().decode_value_unwrap(value.value());
+                (key, value)
+            })
+            .collect()
+    }
+}
+
 #[cfg(feature = "arbitrary")]
 impl<'a, K, V, Codec> StateMap<K, V, Codec>
 where
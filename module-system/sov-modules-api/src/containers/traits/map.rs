@@ -4,6 +4,8 @@ use sov_modules_core::{
 };
 use thiserror::Error;
 
+use crate::profiling::track_cycles;
+
 /// Error type for the get method of state maps.
 #[derive(Debug, Error)]
 pub enum StateMapError {
@@ -35,7 +37,10 @@ where
         Codec::KeyCodec: EncodeKeyLike<Q, K>,
         Q: ?Sized,
     {
-        working_set.set_value(self.prefix(), key, value, self.codec())
+        track_cycles(
+            || format!("state::set {}", self.prefix()),
+            || working_set.set_value(self.prefix(), key, value, self.codec()),
+        )
     }
 
     /// Returns the value corresponding to the key, or [`None`] if the map
@@ -77,7 +82,10 @@ where
         Codec::ValueCodec: StateValueCodec<V>,
         Q: ?Sized,
     {
-        working_set.get_value(self.prefix(), key, self.codec())
+        track_cycles(
+            || format!("state::get {}", self.prefix()),
+            || working_set.get_value(self.prefix(), key, self.codec()),
+        )
     }
 
     /// Returns the value corresponding to the key or [`StateMapError`] if key is absent from
@@ -106,7 +114,10 @@ where
         Codec::ValueCodec: StateValueCodec<V>,
         Q: ?Sized,
     {
-        working_set.remove_value(self.prefix(), key, self.codec())
+        track_cycles(
+            || format!("state::remove {}", self.prefix()),
+            || working_set.remove_value(self.prefix(), key, self.codec()),
+        )
     }
 
     /// Removes a key from the map, returning the corresponding value (or
@@ -138,6 +149,9 @@ where
         Codec::KeyCodec: EncodeKeyLike<Q, K>,
         Q: ?Sized,
     {
-        working_set.delete_value(self.prefix(), key, self.codec());
+        track_cycles(
+            || format!("state::delete {}", self.prefix()),
+            || working_set.delete_value(self.prefix(), key, self.codec()),
+        );
     }
 }
@@ -1,6 +1,8 @@
 use sov_modules_core::{Prefix, StateCodec, StateReaderAndWriter, StateValueCodec};
 use thiserror::Error;
 
+use crate::profiling::track_cycles;
+
 /// Error type for getters from state values method.
 #[derive(Debug, Error)]
 pub enum StateValueError {
@@ -23,12 +25,18 @@ where
 
     /// Sets the value.
     fn set(&self, value: &V, working_set: &mut W) {
-        working_set.set_singleton(self.prefix(), value, self.codec())
+        track_cycles(
+            || format!("state::set {}", self.prefix()),
+            || working_set.set_singleton(self.prefix(), value, self.codec()),
+        )
     }
 
     /// Gets the value from state or returns None if the value is absent.
     fn get(&self, working_set: &mut W) -> Option<V> {
-        working_set.get_singleton(self.prefix(), self.codec())
+        track_cycles(
+            || format!("state::get {}", self.prefix()),
+            || working_set.get_singleton(self.prefix(), self.codec()),
+        )
     }
 
     /// Gets the value from state or Error if the value is absent.
@@ -39,7 +47,10 @@ where
 
     /// Removes the value from state, returning the value (or None if the key is absent).
     fn remove(&self, working_set: &mut W) -> Option<V> {
-        working_set.remove_singleton(self.prefix(), self.codec())
+        track_cycles(
+            || format!("state::remove {}", self.prefix()),
+            || working_set.remove_singleton(self.prefix(), self.codec()),
+        )
     }
 
     /// Removes a value from state, returning the value (or Error if the key is absent).
@@ -50,6 +61,9 @@ where
 
     /// Deletes a value from state.
     fn delete(&self, working_set: &mut W) {
-        working_set.delete_singleton(self.prefix());
+        track_cycles(
+            || format!("state::delete {}", self.prefix()),
+            || working_set.delete_singleton(self.prefix()),
+        );
     }
 }
@@ -1,4 +1,4 @@
-use sov_modules_core::{AccessoryWorkingSet, Context, Spec, Storage, WorkingSet};
+use sov_modules_core::{AccessoryWorkingSet, Context, ModuleError, Spec, Storage, WorkingSet};
 use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 
 use crate::transaction::Transaction;
@@ -21,6 +21,20 @@ pub trait TxHooks {
         arg: &Self::PreArg,
     ) -> anyhow::Result<Self::PreResult>;
 
+    /// Runs when the call of a tx fails, right after the changes it made to the state are
+    /// reverted. The batch goes on with the next transaction, and the changes made by this
+    /// hook are kept.
+    /// IF this hook returns error rollup panics
+    fn tx_reverted_hook(
+        &self,
+        _tx: &Transaction<Self::Context>,
+        _ctx: &Self::Context,
+        _error: &ModuleError,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
     /// Runs after the tx is dispatched to an appropriate module.
     /// IF this hook returns error rollup panics
     fn post_dispatch_tx_hook(
@@ -6,6 +6,8 @@ mod containers;
 pub mod default_context;
 pub mod default_signature;
 pub mod hooks;
+pub mod migration;
+pub mod profiling;
 mod pub_key_hex;
 
 #[cfg(feature = "macros")]
@@ -32,18 +34,21 @@ pub use clap;
 #[cfg(feature = "native")]
 pub use sov_modules_core::PrivateKey;
 pub use sov_modules_core::{
-    archival_state, runtime, AccessoryWorkingSet, Address, AddressBech32, CallResponse, Context,
-    DispatchCall, EncodeCall, GasUnit, Genesis, KernelModule, KernelWorkingSet, Module,
-    ModuleCallJsonSchema, ModuleError, ModuleError as Error, ModuleInfo, ModulePrefix, PublicKey,
-    Signature, Spec, StateCheckpoint, StateReaderAndWriter, VersionedWorkingSet, WorkingSet,
+    archival_state, derived_account_address, runtime, AccessoryWorkingSet, Address, AddressBech32,
+    ArchivalVersionError, CallResponse, CheckpointId, CodedError, Context, DeferredCall,
+    DispatchCall, DispatchCheck, EncodeCall, EventEmitter, GasUnit, Genesis, KernelModule,
+    KernelWorkingSet, Module, ModuleCallJsonSchema, ModuleError, ModuleError as Error,
+    ModuleErrorCode, ModuleInfo, ModulePrefix, OutOfGasError, PublicKey, Signature, Spec,
+    StateCheckpoint, StateReaderAndWriter, StateSnapshot, TrackedChanges, VersionedCall,
+    VersionedWorkingSet, WorkingSet, CALL_MESSAGE_VERSION, MAX_DEFERRED_CALL_DEPTH,
 };
 pub use sov_rollup_interface::da::{BlobReaderTrait, DaSpec};
 pub use sov_rollup_interface::services::da::SlotData;
 pub use sov_rollup_interface::stf::Event;
 pub use sov_rollup_interface::zk::{
-    StateTransition, ValidityCondition, ValidityConditionChecker, Zkvm,
+    AggregatedStateTransition, StateTransition, ValidityCondition, ValidityConditionChecker, Zkvm,
 };
-pub use sov_rollup_interface::{digest, BasicAddress, RollupAddress};
+pub use sov_rollup_interface::{chunking, compression, digest, BasicAddress, RollupAddress};
 
 pub mod prelude {
     pub use super::{StateMapAccessor, StateValueAccessor, StateVecAccessor};
@@ -54,7 +59,7 @@ pub mod optimistic {
 }
 
 pub mod da {
-    pub use sov_rollup_interface::da::{BlockHeaderTrait, NanoSeconds, Time};
+    pub use sov_rollup_interface::da::{BlockHeaderTrait, DaDeposit, NanoSeconds, Time};
 }
 
 pub mod storage {
@@ -178,6 +183,43 @@ where
     Ok(sorted_values)
 }
 
+/// Checks that the state variables of the modules of a runtime have distinct prefixes, none of
+/// which is a prefix of another. Otherwise, the keys of a state variable could be overwritten
+/// through another one.
+///
+/// The modules are given with the name of their field in the runtime, which the error refers to.
+pub fn check_storage_prefixes(
+    modules: &[(&'static str, Vec<ModulePrefix>)],
+) -> Result<(), anyhow::Error> {
+    let mut prefixes: Vec<_> = modules
+        .iter()
+        .flat_map(|(field, prefixes)| {
+            prefixes
+                .iter()
+                .map(move |prefix| (prefix.to_bytes(), *field, prefix))
+        })
+        .collect();
+    // A prefix of a key sorts right before the keys it prefixes
+    prefixes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let collisions: Vec<_> = prefixes
+        .windows(2)
+        .filter(|pair| pair[1].0.starts_with(&pair[0].0))
+        .map(|pair| {
+            format!(
+                "`{}` of the module `{}` and `{}` of the module `{}`",
+                pair[0].2, pair[0].1, pair[1].2, pair[1].1
+            )
+        })
+        .collect();
+    anyhow::ensure!(
+        collisions.is_empty(),
+        "The storage prefixes of the runtime collide: {}. Every module of the runtime needs a distinct type",
+        collisions.join(", ")
+    );
+    Ok(())
+}
+
 /// This trait is implemented by types that can be used as arguments in the sov-cli wallet.
 /// The recommended way to implement this trait is using the provided derive macro (`#[derive(CliWalletArg)]`).
 /// Currently, this trait is a thin wrapper around [`clap::Parser`]
@@ -197,3 +239,31 @@ pub trait CliWallet: sov_modules_core::DispatchCall {
     /// typical end-usage will impl traits only in the case where `CliStringRepr<T>: Into::RuntimeCall`
     type CliStringRepr<T>;
 }
+
+/// Submits the call messages of a module to a rollup, like the module clients of `sov-client`.
+/// The builders derived with `#[derive(CallMessageBuilder)]` are implemented for all the
+/// submitters of their call message.
+#[cfg(feature = "native")]
+pub trait CallMessageSubmitter {
+    /// The call message of the module.
+    type CallMessage;
+    /// The outcome of a submission, typically a future.
+    type Output;
+
+    /// Submits the call message.
+    fn submit_call(self, call_message: Self::CallMessage) -> Self::Output;
+}
+
+/// A trait that needs to be implemented by the clients of a *runtime* to access the clients of its
+/// modules. The accessors derived with `#[derive(ModuleClients)]` are implemented for all of them.
+#[cfg(feature = "native")]
+pub trait RuntimeCallSubmitter<R: sov_modules_core::DispatchCall> {
+    /// The submitter of the call messages `M` of a module of the runtime.
+    type ModuleSubmitter<'a, M>: CallMessageSubmitter<CallMessage = M>
+    where
+        Self: 'a;
+
+    /// Returns the submitter of the call messages of a module, which are wrapped into the calls
+    /// of the runtime with `wrap`, typically a variant of the call enum of the runtime.
+    fn module_submitter<M>(&mut self, wrap: fn(M) -> R::Decodable) -> Self::ModuleSubmitter<'_, M>;
+}
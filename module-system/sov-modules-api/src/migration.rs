@@ -0,0 +1,132 @@
+//! Migrations of the state of modules whose layout changed after launch.
+//!
+//! The version of the state of each migrated module is stored in the rollup state. A runtime
+//! registers a [`Migration`] for every version of a module which has to be reached at a given slot,
+//! and [`run_migrations`] brings the state of the modules to that version at the beginning
+//! of the slot. Since migrations are part of the state transition, they run identically
+//! in native and zkVM execution.
+
+use sov_modules_core::{Context, Module, ModuleInfo, Prefix, Spec, WorkingSet};
+
+use crate::{StateMap, StateMapAccessor};
+
+/// The prefix of the versions of the migrated modules.
+const MODULE_VERSIONS_PREFIX: &[u8] = b"sov_modules_api/migration/module_versions";
+
+/// Implemented by modules which know how to migrate their state from previous versions.
+pub trait MigrateModule: ModuleInfo {
+    /// Migrates the state of the module from `from_version` to `from_version + 1`.
+    ///
+    /// The migration has to be deterministic: it is executed by every node and by the prover.
+    fn migrate(
+        &self,
+        from_version: u32,
+        working_set: &mut WorkingSet<Self::Context>,
+    ) -> anyhow::Result<()>;
+}
+
+/// A migration of a module to `to_version`, which runs at the beginning of the slot `activation_slot`.
+pub struct Migration<'a, C: Context> {
+    module: &'a dyn MigrateModule<Context = C>,
+    to_version: u32,
+    activation_slot: u64,
+}
+
+impl<'a, C: Context> Migration<'a, C> {
+    /// Creates a migration of `module` to `to_version`, which can't be higher than [`Module::VERSION`].
+    pub fn new<M>(module: &'a M, to_version: u32, activation_slot: u64) -> Self
+    where
+        M: Module<Context = C> + MigrateModule<Context = C>,
+    {
+        assert!(
+            to_version <= M::VERSION,
+            "A module can't be migrated to version {} which is higher than its current version {}",
+            to_version,
+            M::VERSION
+        );
+        Self {
+            module,
+            to_version,
+            activation_slot,
+        }
+    }
+
+    /// The version the module is migrated to.
+    pub fn to_version(&self) -> u32 {
+        self.to_version
+    }
+
+    /// The slot at the beginning of which the migration runs.
+    pub fn activation_slot(&self) -> u64 {
+        self.activation_slot
+    }
+}
+
+fn module_versions<C: Context>() -> StateMap<<C as Spec>::Address, u32> {
+    StateMap::new(Prefix::new(MODULE_VERSIONS_PREFIX.to_vec()))
+}
+
+/// Returns the version of the state of the module at `module_address`.
+/// Modules which were never migrated are at version 0.
+pub fn get_module_version<C: Context>(
+    module_address: &<C as Spec>::Address,
+    working_set: &mut WorkingSet<C>,
+) -> u32 {
+    module_versions::<C>()
+        .get(module_address, working_set)
+        .unwrap_or_default()
+}
+
+/// Records that the modules of the migrations are at their [`Migration::to_version`], without migrating them.
+///
+/// It has to be called at genesis, since the state of a new rollup is created with the latest layout.
+pub fn set_genesis_versions<C: Context>(
+    migrations: &[Migration<C>],
+    working_set: &mut WorkingSet<C>,
+) {
+    for migration in migrations {
+        let version =
+            get_module_version(migration.module.address(), working_set).max(migration.to_version);
+        module_versions::<C>().set(migration.module.address(), &version, working_set);
+    }
+}
+
+/// Runs the migrations which are active at `slot_height` and were not applied yet,
+/// in the order in which they are registered.
+///
+/// A module is migrated one version at a time, from the version of its state to the
+/// [`Migration::to_version`].
+pub fn run_migrations<C: Context>(
+    migrations: &[Migration<C>],
+    slot_height: u64,
+    working_set: &mut WorkingSet<C>,
+) -> anyhow::Result<()> {
+    for migration in migrations {
+        if slot_height < migration.activation_slot {
+            continue;
+        }
+
+        let current_version = get_module_version(migration.module.address(), working_set);
+        for from_version in current_version..migration.to_version {
+            migration
+                .module
+                .migrate(from_version, working_set)
+                .map_err(|e| {
+                    anyhow::anyhow!(
+                        "Failed to migrate module {} from version {}: {}",
+                        migration.module.address(),
+                        from_version,
+                        e
+                    )
+                })?;
+        }
+        if current_version < migration.to_version {
+            module_versions::<C>().set(
+                migration.module.address(),
+                &migration.to_version,
+                working_set,
+            );
+        }
+    }
+    Ok(())
+}
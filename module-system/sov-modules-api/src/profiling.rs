@@ -0,0 +1,20 @@
+//! Attribution of zkVM cycles to the hooks, module calls and state accesses of the rollup.
+//!
+//! When the guest is built with the `bench` feature, [`track_cycles`] reports the cycles spent
+//! in each labelled section to the Risc0 host, which aggregates them into a profile report. In
+//! any other build it only runs the section, and the label is never computed.
+
+/// Runs `f`, reporting the cycles it used under the label returned by `label` when running in
+/// the zkVM with the `bench` feature.
+#[inline(always)]
+pub fn track_cycles<T>(label: impl FnOnce() -> String, f: impl FnOnce() -> T) -> T {
+    #[cfg(all(target_os = "zkvm", feature = "bench"))]
+    {
+        sov_zk_cycle_utils::track_cycles(&label(), f)
+    }
+    #[cfg(not(all(target_os = "zkvm", feature = "bench")))]
+    {
+        let _ = label;
+        f()
+    }
+}
@@ -46,7 +46,7 @@ pub use sov_modules_macros::MessageCodec;
 ///        _context: &Self::Context,
 ///        _working_set: &mut WorkingSet<C>,
 ///     ) -> Result<CallResponse, Error> {
-///        Ok(CallResponse {})
+///        Ok(CallResponse::default())
 ///     }
 /// }
 ///
@@ -108,6 +108,39 @@ pub mod macros {
     pub use sov_modules_macros::expose_rpc;
     #[cfg(feature = "native")]
     pub use sov_modules_macros::rpc_gen;
+    /// Derives a `<EnumName>Builder` trait for the annotated call message enum, with one method per variant,
+    /// named after the variant in snake case, which builds the call message from the fields of the variant and
+    /// submits it. The trait is implemented for all the [`CallMessageSubmitter`](crate::CallMessageSubmitter)s of the enum.
+    ///
+    /// ## Example
+    /// ```
+    /// use sov_modules_api::macros::CallMessageBuilder;
+    /// use sov_modules_api::CallMessageSubmitter;
+    ///
+    /// #[derive(CallMessageBuilder, Debug, PartialEq)]
+    /// pub enum CallMessage {
+    ///    /// Sets the value
+    ///    SetValue { value: u32 },
+    ///    /// Clears the value
+    ///    Clear,
+    /// }
+    ///
+    /// struct Submitter;
+    ///
+    /// impl CallMessageSubmitter for Submitter {
+    ///     type CallMessage = CallMessage;
+    ///     type Output = CallMessage;
+    ///
+    ///     fn submit_call(self, call_message: CallMessage) -> CallMessage {
+    ///         call_message
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(CallMessage::SetValue { value: 1 }, Submitter.set_value(1));
+    /// assert_eq!(CallMessage::Clear, Submitter.clear());
+    /// ```
+    #[cfg(feature = "native")]
+    pub use sov_modules_macros::CallMessageBuilder;
     /// Implements the `sov_modules_api::CliWallet` trait for the annotated runtime.
     /// Under the hood, this macro generates an enum called `CliTransactionParser` which derives the [`clap::Parser`] trait.
     /// This enum has one variant for each field of the `Runtime`, and uses the `sov_modules_api::CliWalletArg` trait to parse the
@@ -195,4 +228,12 @@ pub mod macros {
     /// trait for the Runtime because the stdlib implementation of the default trait imposes the generic
     /// arguments to have the Default trait, which is not needed in our case.
     pub use sov_modules_macros::DefaultRuntime;
+    /// Derives a `<RuntimeName>ModuleClients` trait for the annotated runtime, with one method per module, named after
+    /// its field, returning the submitter of the call messages of the module. The trait is implemented for all the
+    /// [`RuntimeCallSubmitter`](crate::RuntimeCallSubmitter)s of the runtime, like the clients of `sov-client`, so that
+    /// `client.bank().transfer(to, coins)` submits a transfer when the builders of the bank are in scope.
+    ///
+    /// The runtime must also derive [`DispatchCall`].
+    #[cfg(feature = "native")]
+    pub use sov_modules_macros::ModuleClients;
 }
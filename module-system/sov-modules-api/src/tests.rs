@@ -1,10 +1,16 @@
+use std::collections::HashSet;
+
 use borsh::{BorshDeserialize, BorshSerialize};
-use sov_modules_core::{Address, PrivateKey, Signature};
+use sov_modules_core::{Address, PrivateKey, Signature, WorkingSet};
+use sov_prover_storage_manager::new_orphan_storage;
 
 use crate::default_context::DefaultContext;
 use crate::default_signature::private_key::DefaultPrivateKey;
 use crate::default_signature::{DefaultPublicKey, DefaultSignature};
-use crate::ModuleInfo;
+use crate::migration::{
+    get_module_version, run_migrations, set_genesis_versions, MigrateModule, Migration,
+};
+use crate::{ModuleInfo, StateValueAccessor};
 
 #[test]
 fn test_account_bech32m_display() {
@@ -63,11 +69,42 @@ impl crate::ModuleInfo for Module {
         crate::ModulePrefix::new_module(module_path!(), "Module")
     }
 
+    fn storage_prefixes(&self) -> Vec<crate::ModulePrefix> {
+        vec![]
+    }
+
     fn dependencies(&self) -> Vec<&<Self::Context as crate::Spec>::Address> {
         self.dependencies.iter().collect()
     }
 }
 
+#[test]
+fn test_storage_prefix_collisions() {
+    let bank = || {
+        vec![
+            crate::ModulePrefix::new_storage("sov_bank", "Bank", "tokens"),
+            crate::ModulePrefix::new_storage("sov_bank", "Bank", "balances"),
+        ]
+    };
+    let accounts = vec![crate::ModulePrefix::new_storage(
+        "sov_accounts",
+        "Accounts",
+        "accounts",
+    )];
+    crate::check_storage_prefixes(&[("bank", bank()), ("accounts", accounts.clone())]).unwrap();
+
+    let error = crate::check_storage_prefixes(&[
+        ("bank", bank()),
+        ("accounts", accounts),
+        ("bank_2", bank()),
+    ])
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("`sov_bank::Bank::balances` of the module `bank`"));
+    assert!(error.to_string().contains("of the module `bank_2`"));
+}
+
 #[test]
 fn test_sorting_modules() {
     let module_a = Module {
@@ -180,3 +217,150 @@ fn test_default_signature_roundtrip() {
     sig.verify(&key.pub_key(), msg)
         .expect("Roundtrip verification failed");
 }
+
+struct VersionedModule {
+    address: Address,
+    migrated_from: crate::StateValue<Vec<u32>>,
+}
+
+impl crate::ModuleInfo for VersionedModule {
+    type Context = DefaultContext;
+
+    fn address(&self) -> &<Self::Context as crate::Spec>::Address {
+        &self.address
+    }
+
+    fn prefix(&self) -> crate::ModulePrefix {
+        crate::ModulePrefix::new_module(module_path!(), "VersionedModule")
+    }
+
+    fn storage_prefixes(&self) -> Vec<crate::ModulePrefix> {
+        vec![]
+    }
+
+    fn dependencies(&self) -> Vec<&<Self::Context as crate::Spec>::Address> {
+        vec![]
+    }
+}
+
+impl crate::Module for VersionedModule {
+    type Context = DefaultContext;
+    type Config = ();
+    type CallMessage = ();
+    type Event = ();
+
+    const VERSION: u32 = 2;
+
+    fn call(
+        &self,
+        _message: Self::CallMessage,
+        _context: &Self::Context,
+        _working_set: &mut WorkingSet<Self::Context>,
+    ) -> Result<crate::CallResponse, crate::ModuleError> {
+        Ok(crate::CallResponse::default())
+    }
+}
+
+impl MigrateModule for VersionedModule {
+    fn migrate(
+        &self,
+        from_version: u32,
+        working_set: &mut WorkingSet<DefaultContext>,
+    ) -> anyhow::Result<()> {
+        let mut migrated_from = self.migrated_from.get(working_set).unwrap_or_default();
+        migrated_from.push(from_version);
+        self.migrated_from.set(&migrated_from, working_set);
+        Ok(())
+    }
+}
+
+fn versioned_module() -> VersionedModule {
+    VersionedModule {
+        address: Address::from([7; 32]),
+        migrated_from: crate::StateValue::new(sov_modules_core::Prefix::new(
+            b"migrated_from".to_vec(),
+        )),
+    }
+}
+
+#[test]
+fn test_run_migrations() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let module = versioned_module();
+    let migrations = vec![
+        Migration::new(&module, 1, 10),
+        Migration::new(&module, 2, 20),
+    ];
+
+    // No migration is active yet
+    run_migrations(&migrations, 9, &mut working_set).unwrap();
+    assert_eq!(0, get_module_version(&module.address, &mut working_set));
+    assert_eq!(None, module.migrated_from.get(&mut working_set));
+
+    run_migrations(&migrations, 10, &mut working_set).unwrap();
+    assert_eq!(1, get_module_version(&module.address, &mut working_set));
+    assert_eq!(Some(vec![0]), module.migrated_from.get(&mut working_set));
+
+    // Migrations only run once
+    run_migrations(&migrations, 11, &mut working_set).unwrap();
+    assert_eq!(Some(vec![0]), module.migrated_from.get(&mut working_set));
+
+    run_migrations(&migrations, 20, &mut working_set).unwrap();
+    assert_eq!(2, get_module_version(&module.address, &mut working_set));
+    assert_eq!(Some(vec![0, 1]), module.migrated_from.get(&mut working_set));
+}
+
+#[test]
+fn test_migrations_are_skipped_after_genesis() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let mut working_set = WorkingSet::new(new_orphan_storage(tmpdir.path()).unwrap());
+    let module = versioned_module();
+    let migrations = vec![Migration::new(&module, 2, 10)];
+
+    set_genesis_versions(&migrations, &mut working_set);
+    run_migrations(&migrations, 10, &mut working_set).unwrap();
+    assert_eq!(2, get_module_version(&module.address, &mut working_set));
+    assert_eq!(None, module.migrated_from.get(&mut working_set));
+}
+
+#[test]
+#[should_panic]
+fn test_migration_to_unknown_version() {
+    let module = versioned_module();
+    let _ = Migration::new(&module, 3, 10);
+}
+
+#[test]
+fn test_tracked_changes() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set: WorkingSet<DefaultContext> = WorkingSet::new(storage.clone());
+    let value = crate::StateValue::<u64>::new(sov_modules_core::Prefix::new(b"value".to_vec()));
+    let other = crate::StateValue::<u64>::new(sov_modules_core::Prefix::new(b"other".to_vec()));
+    value.set(&1, &mut working_set);
+
+    let snapshot = working_set.snapshot();
+    let mut writer = snapshot.to_working_set();
+    assert_eq!(Some(1), value.get(&mut writer));
+    other.set(&2, &mut writer);
+    let writer_changes = writer.into_tracked_changes().unwrap();
+
+    let mut reader = snapshot.to_working_set();
+    assert_eq!(Some(1), value.get(&mut reader));
+    let reader_changes = reader.into_tracked_changes().unwrap();
+
+    // The changes made on top of the snapshot only apply once they're applied
+    assert_eq!(None, other.get(&mut working_set));
+    working_set.apply_tracked_changes(&writer_changes);
+    assert_eq!(Some(2), other.get(&mut working_set));
+
+    let written: HashSet<_> = writer_changes.writes.keys().cloned().collect();
+    assert!(writer_changes.depends_on(&written, &HashSet::new()));
+    assert!(!reader_changes.depends_on(&written, &HashSet::new()));
+    let read: HashSet<_> = reader_changes.reads.iter().cloned().collect();
+    assert!(writer_changes.depends_on(&read, &HashSet::new()));
+
+    let untracked: WorkingSet<DefaultContext> = WorkingSet::new(storage);
+    assert!(untracked.into_tracked_changes().is_none());
+}
@@ -1,5 +1,5 @@
 use jsonrpsee::types::ErrorObjectOwned;
-use sov_modules_core::{Context, Spec};
+use sov_modules_core::{Context, Spec, Version, WorkingSet};
 use sov_rollup_interface::digest::Digest;
 
 pub fn generate_address<C: Context>(key: &str) -> <C as Spec>::Address {
@@ -14,3 +14,17 @@ pub fn to_jsonrpsee_error_object(err: impl ToString, message: &str) -> ErrorObje
         Some(err.to_string()),
     )
 }
+
+/// Sets the archival version of the working set of an RPC query, if one is requested.
+/// A version which isn't available anymore, because it has been pruned, is returned as an RPC error.
+pub fn set_rpc_archival_version<C: Context>(
+    version: Option<Version>,
+    working_set: &mut WorkingSet<C>,
+) -> Result<(), ErrorObjectOwned> {
+    if let Some(version) = version {
+        working_set
+            .try_set_archival_version(version)
+            .map_err(|e| to_jsonrpsee_error_object(e, "Archival state is unavailable"))?;
+    }
+    Ok(())
+}
@@ -124,6 +124,43 @@ fn test_state_map_with_delete() {
     }
 }
 
+#[test]
+fn test_state_map_iteration() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    for (before_delete, after_delete) in create_storage_operations() {
+        let mut working_set = WorkingSet::new(storage.clone());
+        let state_map = StateMap::<(u8, u8), u32>::new(Prefix::new(vec![1]));
+        for key in [(1, 1), (1, 2), (2, 1), (1, 3)] {
+            state_map.set(&key, &(key.0 as u32 * 10 + key.1 as u32), &mut working_set);
+        }
+        // The entries of other maps are not iterated
+        let other_map = StateMap::<u8, u32>::new(Prefix::new(vec![2]));
+        other_map.set(&1, &1, &mut working_set);
+
+        working_set = before_delete.execute(working_set, storage.clone());
+        state_map.delete(&(1, 2), &mut working_set);
+
+        working_set = after_delete.execute(working_set, storage.clone());
+        assert_eq!(
+            vec![((1, 1), 11), ((1, 3), 13)],
+            state_map.iter_prefix(&1u8, None, 10, &mut working_set)
+        );
+        assert_eq!(
+            vec![((1, 3), 13)],
+            state_map.iter_prefix(&1u8, Some(&(1, 2)), 10, &mut working_set)
+        );
+        assert_eq!(
+            vec![((1, 3), 13), ((2, 1), 21)],
+            state_map.iter_range(Some(&(1, 3)), Some(&(2, 2)), 10, &mut working_set)
+        );
+        assert_eq!(
+            vec![((1, 1), 11), ((1, 3), 13)],
+            state_map.iter_range(None, None, 2, &mut working_set)
+        );
+    }
+}
+
 fn create_state_value(value: u32, working_set: &mut WorkingSet<DefaultContext>) -> StateValue<u32> {
     let state_value = StateValue::new(Prefix::new(vec![0]));
     state_value.set(&value, working_set);
@@ -164,6 +201,32 @@ fn test_state_value_with_delete() {
     }
 }
 
+#[test]
+fn test_nested_checkpoints() {
+    let tmpdir = tempfile::tempdir().unwrap();
+    let storage = new_orphan_storage(tmpdir.path()).unwrap();
+    let mut working_set = WorkingSet::new(storage);
+    let state_value = create_state_value(1, &mut working_set);
+
+    let outer = working_set.start_checkpoint();
+    state_value.set(&2, &mut working_set);
+    let inner = working_set.start_checkpoint();
+    state_value.set(&3, &mut working_set);
+    assert_eq!(working_set.checkpoint_depth(), 2);
+
+    working_set.revert_checkpoint(inner);
+    assert_eq!(state_value.get(&mut working_set), Some(2));
+
+    let inner = working_set.start_checkpoint();
+    state_value.set(&4, &mut working_set);
+    working_set.commit_checkpoint(inner);
+    assert_eq!(state_value.get(&mut working_set), Some(4));
+
+    working_set.revert_checkpoint(outer);
+    assert_eq!(working_set.checkpoint_depth(), 0);
+    assert_eq!(state_value.get(&mut working_set), Some(1));
+}
+
 #[test]
 fn test_witness_round_trip() {
     let tempdir = tempfile::tempdir().unwrap();
@@ -150,7 +150,7 @@ impl Prefix {
 const DOMAIN_SEPARATOR: [u8; 1] = [47];
 
 /// A unique identifier for each state variable in a module.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ModulePrefix {
     module_path: &'static str,
     module_name: &'static str,
@@ -180,6 +180,21 @@ impl ModulePrefix {
         }
     }
 
+    /// Returns the name of the module this prefix belongs to.
+    pub const fn module_name(&self) -> &'static str {
+        self.module_name
+    }
+
+    /// Returns the name of the state variable, `None` for the prefix of the module itself.
+    pub const fn storage_name(&self) -> Option<&'static str> {
+        self.storage_name
+    }
+
+    /// Returns the bytes the keys of the state variable are prefixed with.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.combine_prefix()
+    }
+
     fn combine_prefix(&self) -> Vec<u8> {
         let storage_name_len = self
             .storage_name
@@ -213,6 +228,16 @@ impl ModulePrefix {
     }
 }
 
+impl fmt::Display for ModulePrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}::{}", self.module_path, self.module_name)?;
+        if let Some(storage_name) = self.storage_name {
+            write!(f, "::{}", storage_name)?;
+        }
+        Ok(())
+    }
+}
+
 impl From<ModulePrefix> for Prefix {
     fn from(prefix: ModulePrefix) -> Self {
         let combined_prefix = prefix.combine_prefix();
@@ -2,6 +2,8 @@
 
 use alloc::string::String;
 
+use borsh::{BorshDeserialize, BorshSerialize};
+
 use crate::storage::CacheValue;
 
 /// Representation of a signature verification error.
@@ -60,6 +62,48 @@ impl From<bech32::Error> for Bech32ParseError {
     }
 }
 
+/// An error when the state can't be read at an archival version.
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(thiserror::Error))]
+pub enum ArchivalVersionError {
+    /// The state at the version has been pruned.
+    #[cfg_attr(
+        feature = "std",
+        error("State at version {version} has been pruned, the oldest retained version is {oldest_retained_version}")
+    )]
+    Pruned {
+        /// The requested version.
+        version: u64,
+        /// The oldest version which can still be read.
+        oldest_retained_version: u64,
+    },
+    /// The version hasn't been committed yet.
+    #[cfg_attr(
+        feature = "std",
+        error("State at version {version} is not committed yet, the latest version is {latest_version}")
+    )]
+    NotCommitted {
+        /// The requested version.
+        version: u64,
+        /// The latest committed version.
+        latest_version: u64,
+    },
+}
+
+#[cfg(not(feature = "std"))]
+impl core::fmt::Display for ArchivalVersionError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        <ArchivalVersionError as core::fmt::Debug>::fmt(self, f)
+    }
+}
+
+#[cfg(all(not(feature = "std"), feature = "sync"))]
+impl From<ArchivalVersionError> for anyhow::Error {
+    fn from(err: ArchivalVersionError) -> anyhow::Error {
+        anyhow::Error::msg(err)
+    }
+}
+
 /// An error when merging two cache values.
 #[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
@@ -133,13 +177,192 @@ impl From<ReadError> for anyhow::Error {
     }
 }
 
+/// The first numeric code of the [`ModuleErrorCode::Custom`] errors.
+pub const CUSTOM_ERROR_CODE_OFFSET: u32 = 1000;
+
+/// The kind of a typed [`ModuleError`]. Each kind has a stable numeric code, which is part of
+/// the receipts of the reverted transactions.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshDeserialize,
+    BorshSerialize,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+pub enum ModuleErrorCode {
+    /// The sender doesn't have enough funds.
+    InsufficientFunds,
+    /// The sender isn't allowed to perform the call.
+    Unauthorized,
+    /// An item the call refers to doesn't exist.
+    NotFound,
+    /// The call would break an invariant of the module.
+    InvariantViolation,
+    /// An error specific to a module, with a code chosen by the module.
+    Custom(u32),
+}
+
+impl ModuleErrorCode {
+    /// The numeric code of the errors of this kind. The codes of the [`ModuleErrorCode::Custom`]
+    /// errors start at [`CUSTOM_ERROR_CODE_OFFSET`], the untyped errors have code `1`.
+    pub const fn code(&self) -> u32 {
+        match self {
+            ModuleErrorCode::InsufficientFunds => 2,
+            ModuleErrorCode::Unauthorized => 3,
+            ModuleErrorCode::NotFound => 4,
+            ModuleErrorCode::InvariantViolation => 5,
+            ModuleErrorCode::Custom(code) => CUSTOM_ERROR_CODE_OFFSET.saturating_add(*code),
+        }
+    }
+
+    /// Returns the kind of errors with the given numeric code, or `None` for untyped errors.
+    pub const fn from_code(code: u32) -> Option<Self> {
+        match code {
+            2 => Some(ModuleErrorCode::InsufficientFunds),
+            3 => Some(ModuleErrorCode::Unauthorized),
+            4 => Some(ModuleErrorCode::NotFound),
+            5 => Some(ModuleErrorCode::InvariantViolation),
+            code if code >= CUSTOM_ERROR_CODE_OFFSET => {
+                Some(ModuleErrorCode::Custom(code - CUSTOM_ERROR_CODE_OFFSET))
+            }
+            _ => None,
+        }
+    }
+
+    /// Creates an error of this kind, to be returned as the cause of a failed call.
+    /// Context can be added to the returned error, it's converted into a typed
+    /// [`ModuleError`] of this kind anyway.
+    pub fn error(self, message: impl core::fmt::Display) -> anyhow::Error {
+        anyhow::Error::msg(CodedError {
+            code: self,
+            message: alloc::format!("{}", message),
+        })
+    }
+}
+
+/// The cause of a typed [`ModuleError`], created with [`ModuleErrorCode::error`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodedError {
+    /// The kind of the error.
+    pub code: ModuleErrorCode,
+    /// The description of the error.
+    pub message: String,
+}
+
+impl core::fmt::Display for CodedError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 /// General error type in the Module System.
+///
+/// The typed variants let callers, like integration tests, tell the reasons why a call failed
+/// apart. Every variant keeps the [`anyhow::Error`] describing the failure with its context.
+/// The errors are borsh-encoded as their numeric code and their description.
 #[derive(Debug)]
 #[cfg_attr(feature = "std", derive(thiserror::Error))]
 pub enum ModuleError {
+    /// The sender doesn't have enough funds.
+    #[cfg_attr(feature = "std", error(transparent))]
+    InsufficientFunds(anyhow::Error),
+    /// The sender isn't allowed to perform the call.
+    #[cfg_attr(feature = "std", error(transparent))]
+    Unauthorized(anyhow::Error),
+    /// An item the call refers to doesn't exist.
+    #[cfg_attr(feature = "std", error(transparent))]
+    NotFound(anyhow::Error),
+    /// The call would break an invariant of the module.
+    #[cfg_attr(feature = "std", error(transparent))]
+    InvariantViolation(anyhow::Error),
+    /// An error specific to a module, with a code chosen by the module.
+    #[cfg_attr(feature = "std", error("{1}"))]
+    Custom(u32, anyhow::Error),
     /// Custom error thrown by a module.
     #[cfg_attr(feature = "std", error(transparent))]
-    ModuleError(#[cfg_attr(feature = "std", from)] anyhow::Error),
+    ModuleError(anyhow::Error),
+}
+
+impl ModuleError {
+    /// Creates a typed error of the given kind.
+    pub fn new(code: ModuleErrorCode, error: anyhow::Error) -> Self {
+        match code {
+            ModuleErrorCode::InsufficientFunds => ModuleError::InsufficientFunds(error),
+            ModuleErrorCode::Unauthorized => ModuleError::Unauthorized(error),
+            ModuleErrorCode::NotFound => ModuleError::NotFound(error),
+            ModuleErrorCode::InvariantViolation => ModuleError::InvariantViolation(error),
+            ModuleErrorCode::Custom(code) => ModuleError::Custom(code, error),
+        }
+    }
+
+    /// The kind of the error, or `None` if it's untyped.
+    pub fn kind(&self) -> Option<ModuleErrorCode> {
+        match self {
+            ModuleError::InsufficientFunds(_) => Some(ModuleErrorCode::InsufficientFunds),
+            ModuleError::Unauthorized(_) => Some(ModuleErrorCode::Unauthorized),
+            ModuleError::NotFound(_) => Some(ModuleErrorCode::NotFound),
+            ModuleError::InvariantViolation(_) => Some(ModuleErrorCode::InvariantViolation),
+            ModuleError::Custom(code, _) => Some(ModuleErrorCode::Custom(*code)),
+            ModuleError::ModuleError(_) => None,
+        }
+    }
+
+    /// The stable numeric code of the error. See [`ModuleErrorCode::code`].
+    pub fn code(&self) -> u32 {
+        self.kind().map_or(1, |kind| kind.code())
+    }
+
+    /// The error describing the failure, with its context.
+    pub fn error(&self) -> &anyhow::Error {
+        match self {
+            ModuleError::InsufficientFunds(error)
+            | ModuleError::Unauthorized(error)
+            | ModuleError::NotFound(error)
+            | ModuleError::InvariantViolation(error)
+            | ModuleError::Custom(_, error)
+            | ModuleError::ModuleError(error) => error,
+        }
+    }
+
+    /// Consumes the error and returns the error describing the failure, with its context.
+    pub fn into_inner(self) -> anyhow::Error {
+        match self {
+            ModuleError::InsufficientFunds(error)
+            | ModuleError::Unauthorized(error)
+            | ModuleError::NotFound(error)
+            | ModuleError::InvariantViolation(error)
+            | ModuleError::Custom(_, error)
+            | ModuleError::ModuleError(error) => error,
+        }
+    }
+}
+
+impl BorshSerialize for ModuleError {
+    fn serialize<W: sov_rollup_interface::maybestd::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> sov_rollup_interface::maybestd::io::Result<()> {
+        self.code().serialize(writer)?;
+        alloc::format!("{:#}", self.error()).serialize(writer)
+    }
+}
+
+impl BorshDeserialize for ModuleError {
+    fn deserialize_reader<R: sov_rollup_interface::maybestd::io::Read>(
+        reader: &mut R,
+    ) -> sov_rollup_interface::maybestd::io::Result<Self> {
+        let code = u32::deserialize_reader(reader)?;
+        let message = String::deserialize_reader(reader)?;
+        let error = anyhow::Error::msg(message);
+        Ok(match ModuleErrorCode::from_code(code) {
+            Some(kind) => ModuleError::new(kind, error),
+            None => ModuleError::ModuleError(error),
+        })
+    }
 }
 
 #[cfg(not(feature = "std"))]
@@ -156,9 +379,67 @@ impl From<ModuleError> for anyhow::Error {
     }
 }
 
-#[cfg(not(feature = "std"))]
+/// Untyped errors become [`ModuleError::ModuleError`], unless they were caused by a
+/// [`CodedError`].
 impl From<anyhow::Error> for ModuleError {
     fn from(err: anyhow::Error) -> ModuleError {
-        ModuleError::ModuleError(err)
+        match err.downcast_ref::<CodedError>() {
+            Some(cause) => ModuleError::new(cause.code, err),
+            None => ModuleError::ModuleError(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Context;
+
+    use super::*;
+
+    #[test]
+    fn test_typed_errors_keep_their_kind_through_context() {
+        let error = ModuleErrorCode::InsufficientFunds
+            .error("Insufficient funds for alice")
+            .context("Failed transfer");
+        let error = ModuleError::from(error);
+        assert!(matches!(error, ModuleError::InsufficientFunds(_)));
+        assert_eq!(error.code(), 2);
+
+        let messages: Vec<_> = error.error().chain().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            ["Failed transfer", "Insufficient funds for alice"]
+        );
+
+        let error = ModuleError::from(anyhow::anyhow!("Untyped"));
+        assert!(matches!(error, ModuleError::ModuleError(_)));
+        assert_eq!(error.code(), 1);
+    }
+
+    #[test]
+    fn test_error_codes_are_stable() {
+        for kind in [
+            ModuleErrorCode::InsufficientFunds,
+            ModuleErrorCode::Unauthorized,
+            ModuleErrorCode::NotFound,
+            ModuleErrorCode::InvariantViolation,
+            ModuleErrorCode::Custom(0),
+            ModuleErrorCode::Custom(42),
+        ] {
+            assert_eq!(ModuleErrorCode::from_code(kind.code()), Some(kind));
+        }
+        assert_eq!(ModuleErrorCode::Custom(42).code(), 1042);
+        assert_eq!(ModuleErrorCode::from_code(1), None);
+    }
+
+    #[test]
+    fn test_borsh_encoding() {
+        let error = ModuleError::new(
+            ModuleErrorCode::Custom(7),
+            anyhow::anyhow!("Root cause").context("Failed call"),
+        );
+        let decoded = ModuleError::try_from_slice(&error.try_to_vec().unwrap()).unwrap();
+        assert!(matches!(decoded, ModuleError::Custom(7, _)));
+        assert_eq!(decoded.to_string(), "Failed call: Root cause");
     }
 }
@@ -66,6 +66,18 @@ impl<const N: usize> GasUnit for TupleGasUnit<N> {
     }
 }
 
+/// The error returned when the remaining funds of a [`GasMeter`] don't cover the gas charged.
+///
+/// It can be recovered from the errors of a transaction with [`anyhow::Error::downcast_ref`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfGasError;
+
+impl fmt::Display for OutOfGasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Not enough gas")
+    }
+}
+
 /// A gas meter.
 pub struct GasMeter<GU>
 where
@@ -126,7 +138,7 @@ where
         self.remaining_funds = self
             .remaining_funds
             .checked_sub(gas)
-            .ok_or_else(|| anyhow::anyhow!("Not enough gas"))?;
+            .ok_or_else(|| anyhow::Error::msg(OutOfGasError))?;
 
         Ok(())
     }
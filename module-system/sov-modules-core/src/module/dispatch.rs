@@ -1,11 +1,19 @@
 //! Runtime call message definitions.
 
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use borsh::{BorshDeserialize, BorshSerialize};
 use sov_rollup_interface::maybestd::io;
 
-use crate::common::ModuleError;
-use crate::module::{CallResponse, Context, Spec};
+use crate::common::{ModuleError, ModuleErrorCode};
+use crate::module::{derived_account_address, CallResponse, Context, Spec};
 use crate::storage::WorkingSet;
 
+/// How deep [`DeferredCall`](crate::module::DeferredCall)s can nest, when the call of a derived
+/// account defers calls itself.
+pub const MAX_DEFERRED_CALL_DEPTH: usize = 4;
+
 /// A trait that needs to be implemented for any call message.
 pub trait DispatchCall: Send + Sync {
     /// The context of the call
@@ -14,7 +22,8 @@ pub trait DispatchCall: Send + Sync {
     /// The concrete type that will decode into the call message of the module.
     type Decodable: Send + Sync;
 
-    /// Decodes serialized call message
+    /// Decodes serialized call message, either in a [`VersionedCall`] envelope or in the
+    /// legacy encoding of the call enum of the runtime.
     fn decode_call(serialized_message: &[u8]) -> Result<Self::Decodable, io::Error>;
 
     /// Dispatches a call message to the appropriate module.
@@ -27,4 +36,129 @@ pub trait DispatchCall: Send + Sync {
 
     /// Returns an address of the dispatched module.
     fn module_address(&self, message: &Self::Decodable) -> &<Self::Context as Spec>::Address;
+
+    /// Returns the name of the dispatched module.
+    fn module_name(&self, message: &Self::Decodable) -> &'static str;
+
+    /// Dispatches a call message, then the [`DeferredCall`](crate::module::DeferredCall)s
+    /// requested by the module in its response, in order.
+    ///
+    /// The deferred calls share the working set of the call, so the whole call fails
+    /// if any of them fails.
+    fn dispatch_call_and_deferred(
+        &self,
+        message: Self::Decodable,
+        working_set: &mut WorkingSet<Self::Context>,
+        context: &Self::Context,
+    ) -> Result<CallResponse, ModuleError> {
+        dispatch_nested(self, message, working_set, context, &|_, _| Ok(()), 0)
+    }
+
+    /// Same as [`DispatchCall::dispatch_call_and_deferred`], except that `check` runs before
+    /// every call message is dispatched, including the deferred ones. The whole call fails
+    /// if the check of any of them fails.
+    fn dispatch_call_and_deferred_checked(
+        &self,
+        message: Self::Decodable,
+        working_set: &mut WorkingSet<Self::Context>,
+        context: &Self::Context,
+        check: &DispatchCheck<'_, Self::Decodable, Self::Context>,
+    ) -> Result<CallResponse, ModuleError> {
+        dispatch_nested(self, message, working_set, context, check, 0)
+    }
+}
+
+/// A check of a call message before it's dispatched, see
+/// [`DispatchCall::dispatch_call_and_deferred_checked`].
+pub type DispatchCheck<'a, M, C> = dyn Fn(&M, &mut WorkingSet<C>) -> Result<(), ModuleError> + 'a;
+
+/// The version of the [`VersionedCall`] envelope of the call messages.
+///
+/// The call messages are also accepted in the legacy encoding, the borsh encoded call enum of
+/// the runtime, which starts with the index of its variant. The versions start from 128, above
+/// the index of any variant, so that the two encodings are told apart by their first byte.
+pub const CALL_MESSAGE_VERSION: u8 = 128;
+
+/// A call message in an envelope naming its module, so that it keeps decoding when the modules
+/// of the runtime are reordered, or new ones are added.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct VersionedCall {
+    /// The version of the envelope, [`CALL_MESSAGE_VERSION`].
+    pub version: u8,
+    /// The id of the module: the name of its field in the runtime.
+    pub module: String,
+    /// The borsh encoded call message of the module.
+    pub payload: Vec<u8>,
+}
+
+impl VersionedCall {
+    /// Wraps the encoded call message of a module in an envelope of the current version.
+    pub fn new(module: &str, payload: Vec<u8>) -> Self {
+        Self {
+            version: CALL_MESSAGE_VERSION,
+            module: module.into(),
+            payload,
+        }
+    }
+
+    /// Encodes the envelope: the version byte, the id of the module and the payload.
+    pub fn encode(&self) -> Vec<u8> {
+        self.try_to_vec()
+            .expect("Serialization to vec is infallible")
+    }
+
+    /// Decodes the envelope of a serialized call message, or returns `None` if the message is
+    /// in the legacy encoding. Envelopes of an unknown version are rejected.
+    pub fn decode(serialized_message: &[u8]) -> Result<Option<Self>, io::Error> {
+        match serialized_message.first() {
+            Some(version) if *version == CALL_MESSAGE_VERSION => {
+                Self::try_from_slice(serialized_message).map(Some)
+            }
+            Some(version) if *version > CALL_MESSAGE_VERSION => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Unsupported version of the call message",
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+fn dispatch_nested<D: DispatchCall + ?Sized>(
+    dispatcher: &D,
+    message: D::Decodable,
+    working_set: &mut WorkingSet<D::Context>,
+    context: &D::Context,
+    check: &DispatchCheck<'_, D::Decodable, D::Context>,
+    depth: usize,
+) -> Result<CallResponse, ModuleError> {
+    check(&message, working_set)?;
+    let module_address = dispatcher.module_address(&message).clone();
+    let response = dispatcher.dispatch_call(message, working_set, context)?;
+
+    for deferred_call in response.deferred_calls {
+        if depth >= MAX_DEFERRED_CALL_DEPTH {
+            return Err(ModuleErrorCode::InvariantViolation
+                .error(alloc::format!(
+                    "Deferred calls can't be nested deeper than {}",
+                    MAX_DEFERRED_CALL_DEPTH
+                ))
+                .into());
+        }
+        let message = D::decode_call(&deferred_call.message).map_err(|e| {
+            ModuleError::ModuleError(anyhow::anyhow!("Failed to decode a deferred call: {}", e))
+        })?;
+        let sender = derived_account_address::<D::Context>(&module_address, &deferred_call.salt);
+        let deferred_context =
+            D::Context::new(sender, context.sequencer().clone(), context.slot_height());
+        dispatch_nested(
+            dispatcher,
+            message,
+            working_set,
+            &deferred_context,
+            check,
+            depth + 1,
+        )?;
+    }
+
+    Ok(CallResponse::default())
 }
@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 use core::fmt::Debug;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::Digest;
 
 use crate::common::{ModuleError, ModulePrefix};
 use crate::storage::WorkingSet;
@@ -17,7 +18,39 @@ pub use spec::*;
 
 /// Response type for the `Module::call` method.
 #[derive(Default, Debug)]
-pub struct CallResponse {}
+pub struct CallResponse {
+    /// The calls the runtime dispatches once the call of the module succeeded, on behalf of
+    /// the accounts derived from the address of the module. See [`DeferredCall`].
+    pub deferred_calls: Vec<DeferredCall>,
+}
+
+/// A call message which a module asks the runtime to dispatch on behalf of one of its derived
+/// accounts, whose address is [`derived_account_address`] of the module address and of `salt`.
+///
+/// This lets a module act as the owner of accounts, like multisig accounts, without being able to
+/// send calls on behalf of any other account. The deferred calls are dispatched by
+/// [`DispatchCall::dispatch_call_and_deferred`], in the working set of the call requesting them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeferredCall {
+    /// The salt the sender of the call is derived from.
+    pub salt: Vec<u8>,
+    /// The encoded call message of the runtime.
+    pub message: Vec<u8>,
+}
+
+/// Returns the address of the account derived from the address of a module with `salt`.
+/// See [`DeferredCall`].
+pub fn derived_account_address<C: Context>(module_address: &C::Address, salt: &[u8]) -> C::Address {
+    let mut hasher = C::Hasher::new();
+    hasher.update(DERIVED_ACCOUNT_DOMAIN);
+    hasher.update(module_address.as_ref());
+    hasher.update(salt);
+    let hash: [u8; 32] = hasher.finalize().into();
+    C::Address::from(hash)
+}
+
+/// Separates the derived account addresses from the other hash-based addresses.
+const DERIVED_ACCOUNT_DOMAIN: &[u8] = b"sov_derived_account/";
 
 /// The core trait implemented by all modules. This trait defines how a module is initialized at genesis,
 /// and how it handles user transactions (if applicable).
@@ -34,6 +67,10 @@ pub trait Module {
     /// Module defined event resulting from a call method.
     type Event: Debug + BorshSerialize + BorshDeserialize;
 
+    /// The version of the layout of the module's state. It has to be increased every time
+    /// the layout changes after launch, together with a migration of the existing state.
+    const VERSION: u32 = 0;
+
     /// Genesis is called when a rollup is deployed and can be used to set initial state values in the module.
     fn genesis(
         &self,
@@ -87,13 +124,18 @@ pub trait ModuleInfo {
     /// Returns the prefix of the module.
     fn prefix(&self) -> ModulePrefix;
 
+    /// Returns the prefixes of the state variables of the module. The modules it depends on
+    /// own the prefixes of their own state variables, which aren't included.
+    fn storage_prefixes(&self) -> Vec<ModulePrefix>;
+
     /// Returns addresses of all the other modules this module is dependent on
     fn dependencies(&self) -> Vec<&<Self::Context as Spec>::Address>;
 }
 
 /// A trait that specifies how a runtime should encode the data for each module
 pub trait EncodeCall<M: Module> {
-    /// The encoding function
+    /// The encoding function. The call message is wrapped in a
+    /// [`VersionedCall`](crate::module::VersionedCall) envelope.
     fn encode_call(data: M::CallMessage) -> Vec<u8>;
 }
 
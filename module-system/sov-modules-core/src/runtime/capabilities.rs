@@ -33,6 +33,8 @@ pub trait Kernel<C: Context, Da: DaSpec>: BlobSelector<Da, Context = C> + Defaul
     fn true_height(&self, working_set: &mut WorkingSet<C>) -> u64;
     /// Return the height at which transactions currently *appear* to be executing.
     fn visible_height(&self, working_set: &mut WorkingSet<C>) -> u64;
+    /// Return the gas price of the batches applied in the current slot.
+    fn gas_price(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit;
 }
 
 /// Hooks allowing the kernel to get access to the DA layer state
@@ -114,7 +116,7 @@ pub mod mocks {
     use sov_rollup_interface::da::DaSpec;
 
     use super::{BlobRefOrOwned, BlobSelector, Kernel};
-    use crate::{Context, WorkingSet};
+    use crate::{Context, GasUnit, WorkingSet};
 
     /// A mock kernel for use in tests
     #[derive(Debug, Clone)]
@@ -154,6 +156,9 @@ pub mod mocks {
         fn visible_height(&self, _ws: &mut WorkingSet<C>) -> u64 {
             self.visible_height
         }
+        fn gas_price(&self, _ws: &mut WorkingSet<C>) -> C::GasUnit {
+            C::GasUnit::ZEROED
+        }
 
         type GenesisConfig = ();
 
@@ -252,6 +252,15 @@ impl CacheLog {
             .collect()
     }
 
+    /// Returns the key/value pairs written to the cache, without taking them.
+    pub fn writes(&self) -> impl Iterator<Item = (&CacheKey, &Option<CacheValue>)> {
+        self.log.iter().filter_map(|(k, v)| match v {
+            Access::Read(_) => None,
+            Access::ReadThenWrite { modified, .. } => Some((k, modified)),
+            Access::Write(write) => Some((k, write)),
+        })
+    }
+
     /// Returns a value corresponding to the key.
     pub fn get_value(&self, key: &CacheKey) -> ValueExists {
         match self.log.get(key) {
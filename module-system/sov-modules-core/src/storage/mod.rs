@@ -8,7 +8,7 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_rollup_interface::maybestd::RefCount;
 
-use crate::common::{AlignedVec, Prefix, Version, Witness};
+use crate::common::{AlignedVec, ArchivalVersionError, Prefix, Version, Witness};
 
 mod cache;
 mod codec;
@@ -35,6 +35,14 @@ impl From<CacheKey> for StorageKey {
     }
 }
 
+impl From<Vec<u8>> for StorageKey {
+    fn from(key: Vec<u8>) -> Self {
+        Self {
+            key: RefCount::new(key),
+        }
+    }
+}
+
 impl StorageKey {
     /// Returns a new [`RefCount`] reference to the bytes of this key.
     pub fn key(&self) -> RefCount<Vec<u8>> {
@@ -227,6 +235,24 @@ pub trait Storage: Clone {
         None
     }
 
+    /// Checks that the state can be read at the given archival version.
+    ///
+    /// This method is blanket-implemented to accept every version, for the
+    /// storages which don't keep the history of the state.
+    fn check_archival_version(&self, _version: Version) -> Result<(), ArchivalVersionError> {
+        Ok(())
+    }
+
+    /// Returns a read-only handle on the latest version committed to this storage, whose reads
+    /// without a version keep returning the state of that version after new ones are committed.
+    /// The RPC queries read through it, so that their reads are consistent with each other.
+    ///
+    /// This method is blanket-implemented to return a clone of the storage, for the storages
+    /// which don't keep the history of the state.
+    fn pin_latest_version(&self) -> Self {
+        self.clone()
+    }
+
     /// Calculates new state root but does not commit any changes to the database.
     fn compute_state_update(
         &self,
@@ -304,6 +330,37 @@ pub trait NativeStorage: Storage {
     /// get the value.
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof>;
 
+    /// Returns the value of the key at the given version of the tree, with a proof
+    /// against the root of that version. Fails if the version isn't committed or has been pruned.
+    fn get_with_proof_at(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> Result<StorageProof<Self::Proof>, anyhow::Error>;
+
     /// Get the root hash of the tree at the requested version
     fn get_root_hash(&self, version: Version) -> Result<Self::Root, anyhow::Error>;
+
+    /// Returns the latest committed version of the tree, which the proofs of
+    /// [`NativeStorage::get_with_proof`] are generated against.
+    fn latest_version(&self) -> Version;
+
+    /// Returns the oldest version of the tree which can still be read.
+    /// The versions before it have been pruned.
+    fn oldest_retained_version(&self) -> Version;
+
+    /// Returns the keys starting with `prefix` and their values, in ascending order of the key bytes.
+    /// Only the keys in `start..end` are returned, up to `limit` of them.
+    ///
+    /// The values are read at the given version, or at the latest committed version if it's `None`.
+    /// The merkle tree orders the keys by their hashes, so the result comes with no proof and
+    /// can't be verified inside the zkVM.
+    fn get_by_prefix(
+        &self,
+        prefix: &[u8],
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        limit: usize,
+        version: Option<Version>,
+    ) -> Result<Vec<(StorageKey, StorageValue)>, anyhow::Error>;
 }
@@ -1,18 +1,21 @@
 //! Runtime state machine definitions.
 
+use alloc::collections::BTreeMap;
 use alloc::vec::Vec;
 use core::{fmt, mem};
 
 pub use kernel_state::{KernelWorkingSet, VersionedWorkingSet};
-use sov_rollup_interface::maybestd::collections::HashMap;
+use sov_rollup_interface::maybestd::collections::{HashMap, HashSet};
+use sov_rollup_interface::maybestd::RefCount;
 use sov_rollup_interface::stf::Event;
 
 use crate::archival_state::{ArchivalAccessoryWorkingSet, ArchivalJmtWorkingSet};
-use crate::common::{GasMeter, Prefix};
-use crate::module::{Context, Spec};
+use crate::common::{ArchivalVersionError, GasMeter, Prefix};
+use crate::module::{Context, ModuleInfo, Spec};
 use crate::storage::{
     CacheKey, CacheValue, EncodeKeyLike, NativeStorage, OrderedReadsAndWrites, StateCodec,
     StateValueCodec, Storage, StorageInternalCache, StorageKey, StorageProof, StorageValue,
+    ValueExists,
 };
 use crate::Version;
 
@@ -154,6 +157,7 @@ pub struct Delta<S: Storage> {
     inner: S,
     witness: S::Witness,
     cache: StorageInternalCache,
+    accesses: Option<StateAccesses>,
 }
 
 impl<S: Storage> Delta<S> {
@@ -169,6 +173,7 @@ impl<S: Storage> Delta<S> {
                 None => Default::default(),
                 Some(v) => StorageInternalCache::new_with_version(v),
             },
+            accesses: None,
         }
     }
 
@@ -188,14 +193,23 @@ impl<S: Storage> fmt::Debug for Delta<S> {
 
 impl<S: Storage> StateReaderAndWriter for Delta<S> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.read(key.to_cache_key_version(self.cache.version));
+        }
         self.cache.get_or_fetch(key, &self.inner, &self.witness)
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.write(key.to_cache_key_version(self.cache.version));
+        }
         self.cache.set(key, value)
     }
 
     fn delete(&mut self, key: &StorageKey) {
+        if let Some(accesses) = &mut self.accesses {
+            accesses.write(key.to_cache_key_version(self.cache.version));
+        }
         self.cache.delete(key)
     }
 }
@@ -214,6 +228,7 @@ struct AccessoryDelta<S: Storage> {
     #[allow(dead_code)]
     storage: S,
     writes: RevertableWrites,
+    accesses: Option<StateAccesses>,
 }
 
 impl<S: Storage> AccessoryDelta<S> {
@@ -225,7 +240,11 @@ impl<S: Storage> AccessoryDelta<S> {
                 version: Some(v),
             },
         };
-        Self { storage, writes }
+        Self {
+            storage,
+            writes,
+            accesses: None,
+        }
     }
 
     fn freeze(&mut self) -> OrderedReadsAndWrites {
@@ -243,6 +262,9 @@ impl<S: Storage> AccessoryDelta<S> {
 impl<S: Storage> StateReaderAndWriter for AccessoryDelta<S> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
         let cache_key = key.to_cache_key_version(self.writes.version);
+        if let Some(accesses) = &mut self.accesses {
+            accesses.read(cache_key.clone());
+        }
         if let Some(value) = self.writes.cache.get(&cache_key) {
             return value.clone().map(Into::into);
         }
@@ -250,16 +272,21 @@ impl<S: Storage> StateReaderAndWriter for AccessoryDelta<S> {
     }
 
     fn set(&mut self, key: &StorageKey, value: StorageValue) {
-        self.writes.cache.insert(
-            key.to_cache_key_version(self.writes.version),
-            Some(value.into_cache_value()),
-        );
+        let cache_key = key.to_cache_key_version(self.writes.version);
+        if let Some(accesses) = &mut self.accesses {
+            accesses.write(cache_key.clone());
+        }
+        self.writes
+            .cache
+            .insert(cache_key, Some(value.into_cache_value()));
     }
 
     fn delete(&mut self, key: &StorageKey) {
-        self.writes
-            .cache
-            .insert(key.to_cache_key_version(self.writes.version), None);
+        let cache_key = key.to_cache_key_version(self.writes.version);
+        if let Some(accesses) = &mut self.accesses {
+            accesses.write(cache_key.clone());
+        }
+        self.writes.cache.insert(cache_key, None);
     }
 }
 
@@ -304,6 +331,7 @@ impl<C: Context> StateCheckpoint<C> {
             delta: RevertableWriter::new(self.delta, None),
             accessory_delta: RevertableWriter::new(self.accessory_delta, None),
             events: Default::default(),
+            checkpoints: Default::default(),
             gas_meter: self.gas_meter,
             archival_working_set: None,
             archival_accessory_working_set: None,
@@ -335,14 +363,32 @@ impl<C: Context> StateCheckpoint<C> {
     }
 }
 
+/// A checkpoint of the changes of a [`WorkingSet`], started with
+/// [`WorkingSet::start_checkpoint`]. It's closed with [`WorkingSet::commit_checkpoint`] or
+/// [`WorkingSet::revert_checkpoint`].
+#[must_use = "A checkpoint has to be committed or reverted"]
+#[derive(Debug, PartialEq, Eq)]
+pub struct CheckpointId(usize);
+
+/// The changes of a [`WorkingSet`] when one of its checkpoints was started.
+struct SavedChanges {
+    writes: HashMap<CacheKey, Option<CacheValue>>,
+    accessory_writes: HashMap<CacheKey, Option<CacheValue>>,
+    events_len: usize,
+}
+
 /// This structure contains the read-write set and the events collected during the execution of a transaction.
 /// There are two ways to convert it into a StateCheckpoint:
 /// 1. By using the checkpoint() method, where all the changes are added to the underlying StateCheckpoint.
 /// 2. By using the revert method, where the most recent changes are reverted and the previous `StateCheckpoint` is returned.
+///
+/// The changes can also be reverted partially, without leaving the working set, with the nested
+/// checkpoints started by [`WorkingSet::start_checkpoint`].
 pub struct WorkingSet<C: Context> {
     delta: RevertableWriter<Delta<C::Storage>>,
     accessory_delta: RevertableWriter<AccessoryDelta<C::Storage>>,
     events: Vec<Event>,
+    checkpoints: Vec<SavedChanges>,
     gas_meter: GasMeter<C::GasUnit>,
     archival_working_set: Option<ArchivalJmtWorkingSet<C>>,
     archival_accessory_working_set: Option<ArchivalAccessoryWorkingSet<C>>,
@@ -357,6 +403,13 @@ impl<C: Context> WorkingSet<C> {
         StateCheckpoint::new(inner).to_revertable()
     }
 
+    /// Creates a new [`WorkingSet`] reading the latest version committed to the storage,
+    /// unaffected by the versions committed while it's in use, see [`Storage::pin_latest_version`].
+    /// It's meant for the read-only queries, like the RPC methods.
+    pub fn new_pinned(inner: <C as Spec>::Storage) -> Self {
+        Self::new(inner.pin_latest_version())
+    }
+
     /// Returns a handler for the accessory state (non-JMT state).
     ///
     /// You can use this method when calling getters and setters on accessory
@@ -381,6 +434,17 @@ impl<C: Context> WorkingSet<C> {
         self.archival_accessory_working_set = Some(self.archival_accessory_state(version));
     }
 
+    /// Sets archival version for a working set, after checking that the state
+    /// at this version is still available in the storage.
+    pub fn try_set_archival_version(
+        &mut self,
+        version: Version,
+    ) -> Result<(), ArchivalVersionError> {
+        self.delta.inner.inner.check_archival_version(version)?;
+        self.set_archival_version(version);
+        Ok(())
+    }
+
     /// Unset archival version
     pub fn unset_archival_version(&mut self) {
         self.archival_working_set = None;
@@ -439,6 +503,140 @@ impl<C: Context> WorkingSet<C> {
         }
     }
 
+    /// Starts a checkpoint of the changes made to the state and of the events added from now on,
+    /// nested in the checkpoints already started. The gas used isn't part of the checkpoints:
+    /// it stays used when they're reverted.
+    pub fn start_checkpoint(&mut self) -> CheckpointId {
+        self.checkpoints.push(SavedChanges {
+            writes: self.delta.writes.clone(),
+            accessory_writes: self.accessory_delta.writes.clone(),
+            events_len: self.events.len(),
+        });
+        CheckpointId(self.checkpoints.len() - 1)
+    }
+
+    /// Keeps the changes made since the checkpoint was started, along with the ones of the
+    /// checkpoints nested in it. They're still reverted if a checkpoint it's nested in is.
+    ///
+    /// # Panics
+    /// If the checkpoint was already closed, directly or by closing a checkpoint it's nested in.
+    pub fn commit_checkpoint(&mut self, checkpoint: CheckpointId) {
+        assert!(
+            checkpoint.0 < self.checkpoints.len(),
+            "The checkpoint was already closed"
+        );
+        self.checkpoints.truncate(checkpoint.0);
+    }
+
+    /// Reverts the changes made to the state and the events added since the checkpoint was
+    /// started, including the ones of the checkpoints nested in it.
+    ///
+    /// # Panics
+    /// If the checkpoint was already closed, directly or by closing a checkpoint it's nested in.
+    pub fn revert_checkpoint(&mut self, checkpoint: CheckpointId) {
+        assert!(
+            checkpoint.0 < self.checkpoints.len(),
+            "The checkpoint was already closed"
+        );
+        let saved = self
+            .checkpoints
+            .drain(checkpoint.0..)
+            .next()
+            .expect("The checkpoint is open");
+        self.delta.writes = saved.writes;
+        self.accessory_delta.writes = saved.accessory_writes;
+        self.events.truncate(saved.events_len);
+    }
+
+    /// Returns the number of checkpoints started and not closed yet.
+    pub fn checkpoint_depth(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Runs `f` in a checkpoint, which is reverted if it fails. The changes made before `f`
+    /// is run are kept either way.
+    pub fn revert_on_error<T, E>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let checkpoint = self.start_checkpoint();
+        let result = f(self);
+        match result {
+            Ok(_) => self.commit_checkpoint(checkpoint),
+            Err(_) => self.revert_checkpoint(checkpoint),
+        }
+        result
+    }
+
+    /// Returns a snapshot of the state of this working set, including its uncommitted changes.
+    /// Transactions can be executed speculatively on top of it, each in the working set
+    /// returned by [`StateSnapshot::to_working_set`].
+    pub fn snapshot(&self) -> StateSnapshot<C> {
+        let mut writes: HashMap<CacheKey, Option<CacheValue>> = self
+            .delta
+            .inner
+            .cache
+            .tx_cache
+            .writes()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        writes.extend(self.delta.writes.clone());
+        let mut accessory_writes = self.accessory_delta.inner.writes.cache.clone();
+        accessory_writes.extend(self.accessory_delta.writes.clone());
+
+        StateSnapshot {
+            storage: self.delta.inner.inner.clone(),
+            version: self.delta.inner.cache.version,
+            writes: RefCount::new(writes),
+            accessory_writes: RefCount::new(accessory_writes),
+        }
+    }
+
+    /// Commits the changes of this working set, and returns them with the keys it accessed,
+    /// if it was created by [`StateSnapshot::to_working_set`]. Its events are discarded.
+    pub fn into_tracked_changes(self) -> Option<TrackedChanges> {
+        let mut checkpoint = self.checkpoint();
+        let accesses = checkpoint.delta.accesses.take()?;
+        let accessory_accesses = checkpoint.accessory_delta.accesses.take()?;
+
+        let tx_cache = &checkpoint.delta.cache.tx_cache;
+        let writes = accesses
+            .writes
+            .into_iter()
+            .map(|key| match tx_cache.get_value(&key) {
+                ValueExists::Yes(value) => (key, value),
+                ValueExists::No => (key, None),
+            })
+            .collect();
+        let accessory_cache = &checkpoint.accessory_delta.writes.cache;
+        let accessory_writes = accessory_accesses
+            .writes
+            .into_iter()
+            .map(|key| {
+                let value = accessory_cache.get(&key).cloned().flatten();
+                (key, value)
+            })
+            .collect();
+
+        Some(TrackedChanges {
+            reads: accesses.reads,
+            writes,
+            accessory_reads: accessory_accesses.reads,
+            accessory_writes,
+        })
+    }
+
+    /// Applies the changes of a working set created by [`StateSnapshot::to_working_set`] on top
+    /// of the changes of this one. They're only equivalent to executing the transaction on this
+    /// working set if the keys it accessed weren't changed since the snapshot,
+    /// see [`TrackedChanges::depends_on`].
+    pub fn apply_tracked_changes(&mut self, changes: &TrackedChanges) {
+        self.delta.writes.extend(changes.writes.clone());
+        self.accessory_delta
+            .writes
+            .extend(changes.accessory_writes.clone());
+    }
+
     /// Adds an event to the working set.
     pub fn add_event(&mut self, key: &str, value: &str) {
         self.events.push(Event::new(key, value));
@@ -492,6 +690,64 @@ impl<C: Context> WorkingSet<C> {
         // First inner is `RevertableWriter` and second inner is actually a `Storage` instance
         self.delta.inner.inner.get_with_proof(key)
     }
+
+    /// Returns the entries of the state whose keys start with `prefix`, in ascending order of the key bytes.
+    /// Only the keys in `start..end` are returned, up to `limit` of them.
+    ///
+    /// The writes of this working set which aren't committed yet are taken into account. This isn't
+    /// available inside the zkVM: the merkle tree orders keys by their hashes, so the entries can't be
+    /// proven. For the same reason, they are not added to the witness.
+    pub fn get_by_prefix(
+        &mut self,
+        prefix: &[u8],
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        limit: usize,
+    ) -> Vec<(StorageKey, StorageValue)>
+    where
+        C::Storage: NativeStorage,
+    {
+        let delta = match &self.archival_working_set {
+            None => &self.delta,
+            Some(archival_working_set) => &archival_working_set.delta,
+        };
+        let in_range = |key: &[u8]| {
+            key.starts_with(prefix)
+                && start.map_or(true, |start| key >= start)
+                && end.map_or(true, |end| key < end)
+        };
+        let pending_writes: BTreeMap<_, _> = delta
+            .pending_writes()
+            .into_iter()
+            .filter(|(key, _)| in_range(key))
+            .collect();
+
+        // Each pending write hides at most one stored entry
+        let stored = delta
+            .inner
+            .inner
+            .get_by_prefix(
+                prefix,
+                start,
+                end,
+                limit.saturating_add(pending_writes.len()),
+                delta.version,
+            )
+            .unwrap_or_else(|e| panic!("Unable to read values from db: {e}"));
+
+        let mut entries: BTreeMap<Vec<u8>, Option<StorageValue>> = stored
+            .into_iter()
+            .map(|(key, value)| (key.as_ref().clone(), Some(value)))
+            .collect();
+        for (key, value) in pending_writes {
+            entries.insert(key, value.map(Into::into));
+        }
+        entries
+            .into_iter()
+            .filter_map(|(key, value)| Some((StorageKey::from(key), value?)))
+            .take(limit)
+            .collect()
+    }
 }
 
 impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
@@ -517,6 +773,139 @@ impl<C: Context> StateReaderAndWriter for WorkingSet<C> {
     }
 }
 
+/// A read-only snapshot of the state of a [`WorkingSet`], created with [`WorkingSet::snapshot`].
+pub struct StateSnapshot<C: Context> {
+    storage: C::Storage,
+    version: Option<u64>,
+    writes: RefCount<HashMap<CacheKey, Option<CacheValue>>>,
+    accessory_writes: RefCount<HashMap<CacheKey, Option<CacheValue>>>,
+}
+
+impl<C: Context> Clone for StateSnapshot<C> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            version: self.version,
+            writes: self.writes.clone(),
+            accessory_writes: self.accessory_writes.clone(),
+        }
+    }
+}
+
+impl<C: Context> StateSnapshot<C> {
+    /// Creates a snapshot of the state of the storage at the given version, or at its latest
+    /// committed version, without any change on top of it.
+    ///
+    /// The keys of the changes tracked by the working sets of a versioned snapshot are prefixed
+    /// with the version, see [`StorageKey::to_cache_key_version`].
+    pub fn new(storage: C::Storage, version: Option<Version>) -> Self {
+        Self {
+            storage,
+            version,
+            writes: RefCount::new(HashMap::new()),
+            accessory_writes: RefCount::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a working set on top of this snapshot, which tracks the keys of the state
+    /// it accesses. Its changes never affect the snapshot, they're extracted with
+    /// [`WorkingSet::into_tracked_changes`].
+    pub fn to_working_set(&self) -> WorkingSet<C> {
+        let mut delta = Delta::new(self.storage.clone(), self.version);
+        for (key, value) in self.writes.iter() {
+            delta.cache.tx_cache.add_write(key.clone(), value.clone());
+        }
+        delta.accesses = Some(StateAccesses::default());
+
+        let mut accessory_delta = AccessoryDelta::new(self.storage.clone(), self.version);
+        accessory_delta.writes.cache = (*self.accessory_writes).clone();
+        accessory_delta.accesses = Some(StateAccesses::default());
+
+        StateCheckpoint {
+            delta,
+            accessory_delta,
+            gas_meter: GasMeter::default(),
+        }
+        .to_revertable()
+    }
+}
+
+/// The keys of the state read and written through a delta.
+#[derive(Default)]
+struct StateAccesses {
+    reads: HashSet<CacheKey>,
+    writes: HashSet<CacheKey>,
+}
+
+impl StateAccesses {
+    fn read(&mut self, key: CacheKey) {
+        self.reads.insert(key);
+    }
+
+    fn write(&mut self, key: CacheKey) {
+        self.writes.insert(key);
+    }
+}
+
+/// The changes made by a working set created with [`StateSnapshot::to_working_set`],
+/// with the keys of the state it read.
+#[derive(Debug, Clone, Default)]
+pub struct TrackedChanges {
+    /// The keys of the state read by the working set.
+    pub reads: HashSet<CacheKey>,
+    /// The last values written to the state by the working set, `None` for deleted keys.
+    pub writes: HashMap<CacheKey, Option<CacheValue>>,
+    /// The keys of the accessory state read by the working set.
+    pub accessory_reads: HashSet<CacheKey>,
+    /// The last values written to the accessory state by the working set, `None` for deleted keys.
+    pub accessory_writes: HashMap<CacheKey, Option<CacheValue>>,
+}
+
+impl TrackedChanges {
+    /// Returns `true` if these changes read or wrote any of the given keys of the state,
+    /// or of the accessory state. If they didn't, they're the same as if they were made after
+    /// the changes of these keys.
+    pub fn depends_on(
+        &self,
+        written: &HashSet<CacheKey>,
+        accessory_written: &HashSet<CacheKey>,
+    ) -> bool {
+        let touches = |reads: &HashSet<CacheKey>,
+                       writes: &HashMap<CacheKey, Option<CacheValue>>,
+                       keys: &HashSet<CacheKey>| {
+            reads
+                .iter()
+                .chain(writes.keys())
+                .any(|key| keys.contains(key))
+        };
+        touches(&self.reads, &self.writes, written)
+            || touches(
+                &self.accessory_reads,
+                &self.accessory_writes,
+                accessory_written,
+            )
+    }
+}
+
+/// Emits events attributed to the module which produced them.
+///
+/// Events emitted this way are stored in the ledger together with the name of the module,
+/// so indexers can query them by module and key.
+pub trait EventEmitter {
+    /// Emits an event with the given key and value on behalf of `module`.
+    fn emit_event<M: ModuleInfo>(&mut self, module: &M, key: &str, value: &str);
+}
+
+impl<C: Context> EventEmitter for WorkingSet<C> {
+    fn emit_event<M: ModuleInfo>(&mut self, module: &M, key: &str, value: &str) {
+        self.events.push(Event::with_module(
+            module.prefix().module_name(),
+            key,
+            value,
+        ));
+    }
+}
+
 /// A wrapper over [`WorkingSet`] that only allows access to the accessory
 /// state (non-JMT state).
 pub struct AccessoryWorkingSet<'a, C: Context> {
@@ -556,7 +945,7 @@ pub mod archival_state {
 
     /// Archival JMT
     pub struct ArchivalJmtWorkingSet<C: Context> {
-        delta: RevertableWriter<Delta<C::Storage>>,
+        pub(super) delta: RevertableWriter<Delta<C::Storage>>,
     }
 
     impl<C: Context> ArchivalJmtWorkingSet<C> {
@@ -767,6 +1156,26 @@ where
     }
 }
 
+impl<S: Storage> RevertableWriter<Delta<S>> {
+    /// Returns the writes which aren't committed to the storage yet, by the bytes of their
+    /// [`StorageKey`]s.
+    fn pending_writes(&self) -> BTreeMap<Vec<u8>, Option<CacheValue>> {
+        // The cache keys of versioned writers start with the version
+        let key_offset = if self.version.is_some() {
+            mem::size_of::<u64>()
+        } else {
+            0
+        };
+        self.inner
+            .cache
+            .tx_cache
+            .writes()
+            .chain(self.writes.iter())
+            .map(|(key, value)| (key.key[key_offset..].to_vec(), value.clone()))
+            .collect()
+    }
+}
+
 impl<T: StateReaderAndWriter> StateReaderAndWriter for RevertableWriter<T> {
     fn get(&mut self, key: &StorageKey) -> Option<StorageValue> {
         if let Some(value) = self.writes.get(&key.to_cache_key_version(self.version)) {
@@ -0,0 +1,181 @@
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields, Ident};
+
+use crate::common::StructFieldExtractor;
+
+/// Converts a variant name like `CreateToken` to the name of its builder, like `create_token`.
+fn to_snake_case(ident: &Ident) -> Ident {
+    let mut name = String::new();
+    for (i, c) in ident.to_string().chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    Ident::new(&name, ident.span())
+}
+
+pub(crate) fn derive_call_message_builder(
+    input: DeriveInput,
+) -> Result<proc_macro::TokenStream, syn::Error> {
+    let DeriveInput {
+        ident,
+        generics,
+        data,
+        vis,
+        ..
+    } = input;
+    let Data::Enum(data) = data else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "The CallMessageBuilder macro supports enums only.",
+        ));
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let builder_ident = format_ident!("{}Builder", ident);
+
+    let builders = data.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+        let builder = to_snake_case(variant_ident);
+        let fields = StructFieldExtractor::get_or_generate_named_fields(&variant.fields);
+        let args = fields.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+            quote! { #name: #ty }
+        });
+        let names = fields.iter().map(|field| &field.ident);
+        let call_message = match &variant.fields {
+            Fields::Named(_) => quote! { #ident::#variant_ident { #(#names),* } },
+            Fields::Unnamed(_) => quote! { #ident::#variant_ident ( #(#names),* ) },
+            Fields::Unit => quote! { #ident::#variant_ident },
+        };
+
+        let docs: Vec<_> = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("doc"))
+            .collect();
+        let default_doc = format!("Submits a `{}::{}` call message.", ident, variant_ident);
+        let docs = if docs.is_empty() {
+            quote! { #[doc = #default_doc] }
+        } else {
+            quote! { #(#docs)* }
+        };
+
+        quote! {
+            #docs
+            #[allow(clippy::too_many_arguments)]
+            fn #builder(self, #(#args),*) -> Self::Output {
+                ::sov_modules_api::CallMessageSubmitter::submit_call(self, #call_message)
+            }
+        }
+    });
+
+    let generics_with_submitter = {
+        let mut generics = generics.clone();
+        generics.params.push(syn::parse_quote! {
+            __Submitter: ::sov_modules_api::CallMessageSubmitter<CallMessage = #ident #ty_generics>
+        });
+        generics
+    };
+    let (impl_generics_with_submitter, _, _) = generics_with_submitter.split_for_impl();
+
+    let doc = format!(
+        "Builds the `{}` call messages from their fields, and submits them. \
+         It's implemented by all the submitters of these call messages.",
+        ident
+    );
+    Ok(quote! {
+        #[doc = #doc]
+        #vis trait #builder_ident #impl_generics:
+            ::sov_modules_api::CallMessageSubmitter<CallMessage = #ident #ty_generics>
+            + ::core::marker::Sized
+            #where_clause
+        {
+            #(#builders)*
+        }
+
+        impl #impl_generics_with_submitter #builder_ident #ty_generics for __Submitter #where_clause {}
+    }
+    .into())
+}
+
+pub(crate) struct ModuleClientsMacro {
+    field_extractor: StructFieldExtractor,
+}
+
+impl ModuleClientsMacro {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self {
+            field_extractor: StructFieldExtractor::new(name),
+        }
+    }
+
+    pub(crate) fn derive_module_clients(
+        &self,
+        input: DeriveInput,
+    ) -> Result<proc_macro::TokenStream, syn::Error> {
+        let DeriveInput {
+            ident,
+            generics,
+            data,
+            vis,
+            ..
+        } = input;
+        let fields = self.field_extractor.get_fields_from_struct(&data)?;
+
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let clients_ident = format_ident!("{}ModuleClients", ident);
+
+        let accessors = fields.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+            let doc = format!("Returns the client of the `{}` module.", name);
+            quote! {
+                #[doc = #doc]
+                fn #name(&mut self) -> <Self as ::sov_modules_api::RuntimeCallSubmitter<#ident #ty_generics>>::ModuleSubmitter<
+                    '_,
+                    <#ty as ::sov_modules_api::Module>::CallMessage,
+                > {
+                    ::sov_modules_api::RuntimeCallSubmitter::module_submitter(
+                        self,
+                        // Use the enum variant as a constructor
+                        <#ident #ty_generics as ::sov_modules_api::DispatchCall>::Decodable::#name,
+                    )
+                }
+            }
+        });
+
+        let generics_with_submitter = {
+            let mut generics = generics.clone();
+            generics.params.push(syn::parse_quote! {
+                __Submitter: ::sov_modules_api::RuntimeCallSubmitter<#ident #ty_generics>
+            });
+            generics
+        };
+        let (impl_generics_with_submitter, _, _) = generics_with_submitter.split_for_impl();
+
+        let doc = format!(
+            "Gives access to the clients of the modules of the `{}`. \
+             It's implemented by all the clients of the runtime.",
+            ident
+        );
+        Ok(quote! {
+            #[doc = #doc]
+            #vis trait #clients_ident #impl_generics:
+                ::sov_modules_api::RuntimeCallSubmitter<#ident #ty_generics>
+                #where_clause
+            {
+                #(#accessors)*
+            }
+
+            impl #impl_generics_with_submitter #clients_ident #ty_generics for __Submitter #where_clause {}
+        }
+        .into())
+    }
+}
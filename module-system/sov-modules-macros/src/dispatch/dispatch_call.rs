@@ -46,6 +46,30 @@ impl<'a> StructDef<'a> {
             )
         });
 
+        let match_legs_name = self.fields.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+
+            quote::quote!(
+                #enum_ident::#name(message)=>{
+                   <#ty as ::sov_modules_api::ModuleInfo>::prefix(&self.#name).module_name()
+                },
+            )
+        });
+
+        let match_legs_versioned = self.fields.iter().map(|field| {
+            let name = &field.ident;
+            let ty = &field.ty;
+            let module_id = name.to_string();
+
+            quote::quote!(
+                #module_id => {
+                    let message = <<#ty as ::sov_modules_api::Module>::CallMessage as ::borsh::BorshDeserialize>::try_from_slice(&call.payload)?;
+                    ::core::result::Result::Ok(#enum_ident:: #type_generics ::#name(message))
+                },
+            )
+        });
+
         let ident = &self.ident;
         let impl_generics = &self.impl_generics;
         let where_clause = self.where_clause;
@@ -59,8 +83,21 @@ impl<'a> StructDef<'a> {
                 type Decodable = #call_enum #ty_generics;
 
                 fn decode_call(serialized_message: &[u8]) -> ::core::result::Result<Self::Decodable, std::io::Error> {
-                    let mut data = ::std::io::Cursor::new(serialized_message);
-                    <#call_enum #ty_generics as ::borsh::BorshDeserialize>::deserialize_reader(&mut data)
+                    // Messages in an envelope are decoded by the id of their module, and the
+                    // others in the legacy encoding of the call enum.
+                    match ::sov_modules_api::VersionedCall::decode(serialized_message)? {
+                        ::core::option::Option::Some(call) => match call.module.as_str() {
+                            #(#match_legs_versioned)*
+                            module => ::core::result::Result::Err(std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("Unknown module {} of a call message", module),
+                            )),
+                        },
+                        ::core::option::Option::None => {
+                            let mut data = ::std::io::Cursor::new(serialized_message);
+                            <#call_enum #ty_generics as ::borsh::BorshDeserialize>::deserialize_reader(&mut data)
+                        }
+                    }
                 }
 
                 fn dispatch_call(
@@ -82,6 +119,12 @@ impl<'a> StructDef<'a> {
                     }
                 }
 
+                fn module_name(&self, decodable: &Self::Decodable) -> &'static str {
+                    match decodable {
+                        #(#match_legs_name)*
+                    }
+                }
+
             }
         }
     }
@@ -115,6 +158,14 @@ impl DispatchCallMacro {
 
         let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
         let fields = self.field_extractor.get_fields_from_struct(&data)?;
+        // The legacy encoding of the call messages starts with the index of the variant of
+        // the call enum, which must stay below the versions of `VersionedCall`.
+        if fields.len() > usize::from(sov_modules_core::CALL_MESSAGE_VERSION) {
+            return Err(syn::Error::new(
+                ident.span(),
+                "A runtime can't have more than 128 modules",
+            ));
+        }
 
         let struct_def = StructDef::new(
             ident,
@@ -28,15 +28,21 @@ impl GenesisMacro {
         let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
         let fields = self.field_extractor.get_fields_from_struct(&data)?;
+        Self::check_distinct_module_types(&fields)?;
         let generic_param = get_generics_type_param(&generics, Span::call_site())?;
         let genesis_config =
             Self::make_genesis_config(&fields, &impl_generics, &type_generics, where_clause);
         let genesis_fn_body = Self::make_genesis_fn_body(&fields);
+        let storage_prefixes_fn = Self::make_storage_prefixes_fn(&fields);
 
         // Implements the Genesis trait
         Ok(quote::quote! {
             #genesis_config
 
+            impl #impl_generics #ident #type_generics #where_clause {
+                #storage_prefixes_fn
+            }
+
             impl #impl_generics sov_modules_api::Genesis for #ident #type_generics #where_clause {
                 type Context = #generic_param;
                 type Config = GenesisConfig #type_generics;
@@ -68,17 +74,55 @@ impl GenesisMacro {
         });
 
         quote::quote! {
+                ::sov_modules_api::check_storage_prefixes(&self.storage_prefixes())?;
                 let modules: ::std::vec::Vec<(&dyn ::sov_modules_api::ModuleInfo<Context = <Self as sov_modules_api::Genesis>::Context>, usize)> = ::std::vec![#(#idents),*];
                 let sorted_modules = ::sov_modules_api::sort_values_by_modules_dependencies(modules)?;
                 for module in sorted_modules {
                      match module {
                          #(#matches)*
-                         _ => Err(::sov_modules_api::Error::ModuleError(::anyhow::Error::msg(format!("Module not found: {:?}", module)))),
+                         _ => Err(::sov_modules_api::Error::NotFound(::anyhow::Error::msg(format!("Module not found: {:?}", module)))),
                      }?
                 }
         }
     }
 
+    /// Two fields of the same module type would share all their storage prefixes.
+    /// The types are compared as written, so the prefixes are checked again at genesis.
+    fn check_distinct_module_types(fields: &[StructNamedField]) -> Result<(), syn::Error> {
+        let mut types = std::collections::HashMap::new();
+        for field in fields {
+            let ty = &field.ty;
+            let ty_string = quote::quote!(#ty).to_string();
+            if let Some(previous) = types.insert(ty_string, &field.ident) {
+                return Err(syn::Error::new_spanned(
+                    &field.ident,
+                    format!(
+                        "The fields `{}` and `{}` have the same module type, so their storage prefixes collide. Every module of the runtime needs a distinct type.",
+                        previous, field.ident
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn make_storage_prefixes_fn(fields: &[StructNamedField]) -> proc_macro2::TokenStream {
+        let prefixes = fields.iter().map(|field| {
+            let ident = &field.ident;
+
+            quote::quote! {
+                (stringify!(#ident), ::sov_modules_api::ModuleInfo::storage_prefixes(&self.#ident))
+            }
+        });
+
+        quote::quote! {
+            #[doc = "Returns the prefixes of the state variables owned by each module of the runtime, with the name of its field."]
+            pub fn storage_prefixes(&self) -> ::std::vec::Vec<(&'static str, ::std::vec::Vec<::sov_modules_api::ModulePrefix>)> {
+                ::std::vec![#(#prefixes),*]
+            }
+        }
+    }
+
     fn make_genesis_config(
         fields: &[StructNamedField],
         impl_generics: &ImplGenerics,
@@ -1,12 +1,11 @@
 use proc_macro2::{Span, TokenStream};
 use syn::DeriveInput;
 
-use crate::common::{get_generics_type_param, StructDef, StructFieldExtractor, CALL};
+use crate::common::{get_generics_type_param, StructDef, StructFieldExtractor};
 
 impl<'a> StructDef<'a> {
     fn create_message_codec(&self) -> TokenStream {
         let original_ident = &self.ident;
-        let call_enum = self.enum_ident(CALL);
         let ty_generics = &self.type_generics;
         let impl_generics = &self.impl_generics;
         let where_clause = &self.where_clause;
@@ -16,6 +15,7 @@ impl<'a> StructDef<'a> {
             let ty = &field.ty;
 
             let call_doc = format!("Encodes {} call message.", field.ident);
+            let module_id = variant.to_string();
 
             // Creates functions like:
             //  encode_*module_name*_call(data: ..) -> Vec<u8>
@@ -24,8 +24,8 @@ impl<'a> StructDef<'a> {
             impl #impl_generics sov_modules_api::EncodeCall<#ty> for #original_ident #ty_generics #where_clause {
                 #[doc = #call_doc]
                 fn encode_call(data: <#ty as sov_modules_api::Module>::CallMessage) -> std::vec::Vec<u8> {
-                    let call = #call_enum:: #ty_generics ::#variant(data);
-                    ::borsh::BorshSerialize::try_to_vec(&call).unwrap()
+                    let payload = ::borsh::BorshSerialize::try_to_vec(&data).unwrap();
+                    sov_modules_api::VersionedCall::new(#module_id, payload).encode()
                 }
             }
             }
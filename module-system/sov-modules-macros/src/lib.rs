@@ -11,6 +11,8 @@
 
 #[cfg(feature = "native")]
 mod cli_parser;
+#[cfg(feature = "native")]
+mod client;
 mod common;
 mod default_runtime;
 mod dispatch;
@@ -26,6 +28,8 @@ mod rpc;
 
 #[cfg(feature = "native")]
 use cli_parser::{derive_cli_wallet_arg, CliParserMacro};
+#[cfg(feature = "native")]
+use client::{derive_call_message_builder, ModuleClientsMacro};
 use default_runtime::DefaultRuntimeMacro;
 use dispatch::dispatch_call::DispatchCallMacro;
 use dispatch::genesis::GenesisMacro;
@@ -233,6 +237,21 @@ pub fn custom_enum_clap(input: TokenStream) -> TokenStream {
     handle_macro_error(derive_cli_wallet_arg(input))
 }
 
+#[cfg(feature = "native")]
+#[proc_macro_derive(CallMessageBuilder)]
+pub fn call_message_builder(input: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = parse_macro_input!(input);
+    handle_macro_error(derive_call_message_builder(input))
+}
+
+#[cfg(feature = "native")]
+#[proc_macro_derive(ModuleClients)]
+pub fn module_clients(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input);
+    let module_clients = ModuleClientsMacro::new("ModuleClients");
+    handle_macro_error(module_clients.derive_module_clients(input))
+}
+
 /// Simple convenience macro for adding some common derive macros and
 /// impls specifically for a NewType wrapping an Address.
 /// The reason for having this is that we assumes NewTypes for address as a common use case
@@ -73,6 +73,7 @@ fn impl_module_info(
     let mut impl_self_init = Vec::default();
     let mut impl_self_body = Vec::default();
     let mut modules = Vec::default();
+    let mut states = Vec::default();
 
     for field in fields.iter() {
         match &field.attr {
@@ -85,6 +86,7 @@ fn impl_module_info(
                         .unwrap_or_else(default_codec_builder),
                 )?);
                 impl_self_body.push(&field.ident);
+                states.push(&field.ident);
             }
             ModuleFieldAttribute::Module => {
                 impl_self_init.push(make_init_module(field, ModuleType::Standard)?);
@@ -116,6 +118,7 @@ fn impl_module_info(
     let fn_address = make_fn_address(&module_address.ident)?;
     let fn_dependencies = make_fn_dependencies(modules);
     let fn_prefix = make_module_prefix_fn(ident);
+    let fn_storage_prefixes = make_fn_storage_prefixes(states);
 
     Ok(quote::quote! {
         impl #impl_generics ::std::default::Default for #ident #type_generics #where_clause{
@@ -133,6 +136,8 @@ fn impl_module_info(
 
             #fn_prefix
 
+            #fn_storage_prefixes
+
             #fn_address
 
             #fn_dependencies
@@ -191,6 +196,16 @@ fn make_fn_dependencies(modules: Vec<&proc_macro2::Ident>) -> proc_macro2::Token
         }
     }
 }
+fn make_fn_storage_prefixes(states: Vec<&proc_macro2::Ident>) -> proc_macro2::TokenStream {
+    let prefix_funcs = states.into_iter().map(prefix_func_ident);
+
+    quote::quote! {
+        fn storage_prefixes(&self) -> ::std::vec::Vec<sov_modules_api::ModulePrefix> {
+            ::std::vec![#(Self::#prefix_funcs()),*]
+        }
+    }
+}
+
 fn make_init_state(
     field: &ModuleField,
     encoding_constructor: &syn::Path,
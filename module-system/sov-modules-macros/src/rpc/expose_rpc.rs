@@ -103,10 +103,10 @@ impl ExposeRpcMacro {
             let rpc_trait_impl = quote! {
                 #(#attrs)*
                 impl #impl_generics #rpc_trait_ident #field_path_args for RpcStorage #ty_generics #where_clause {
-                    /// Get a working set on top of the current storage
+                    /// Get a working set on top of the latest version of the storage
                     fn get_working_set(&self) -> ::sov_modules_api::WorkingSet<#context_type>
                     {
-                        ::sov_modules_api::WorkingSet::new(self.storage.clone())
+                        ::sov_modules_api::WorkingSet::new_pinned(self.storage.clone())
                     }
                 }
             };
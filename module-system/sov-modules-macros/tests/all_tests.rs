@@ -34,7 +34,9 @@ fn module_dispatch_tests() {
     t.pass("tests/dispatch/derive_genesis.rs");
     t.pass("tests/dispatch/derive_dispatch.rs");
     t.pass("tests/dispatch/derive_event.rs");
+    t.pass("tests/dispatch/versioned_call.rs");
     t.compile_fail("tests/dispatch/missing_serialization.rs");
+    t.compile_fail("tests/dispatch/duplicate_module_type.rs");
 }
 
 #[test]
@@ -64,6 +66,14 @@ fn cli_wallet_arg_tests() {
     t.pass("tests/cli_wallet_arg/derive_wallet.rs");
 }
 
+#[test]
+fn client_tests() {
+    set_constants_manifest();
+    let t: trybuild::TestCases = trybuild::TestCases::new();
+
+    t.pass("tests/client/derive_module_clients.rs");
+}
+
 #[test]
 fn constants_from_manifests_test() {
     set_constants_manifest();
@@ -0,0 +1,139 @@
+use sov_modules_api::default_context::DefaultContext;
+use sov_modules_api::macros::{CallMessageBuilder, ModuleClients};
+use sov_modules_api::{
+    CallMessageSubmitter, CallResponse, Context, DispatchCall, Error, MessageCodec, Module,
+    ModuleInfo, RuntimeCallSubmitter, StateValue, WorkingSet,
+};
+
+pub mod first_test_module {
+    use super::*;
+
+    #[derive(
+        CallMessageBuilder, Debug, PartialEq, borsh::BorshDeserialize, borsh::BorshSerialize,
+    )]
+    pub enum CallMessage<C: Context> {
+        /// Sets the value.
+        SetValue {
+            value: u8,
+            owner: C::Address,
+        },
+        Increment(u8),
+        Reset,
+    }
+
+    #[derive(ModuleInfo)]
+    pub struct FirstTestStruct<C: Context> {
+        #[address]
+        pub address: C::Address,
+
+        #[state]
+        pub state_in_first_struct: StateValue<u8>,
+    }
+
+    impl<C: Context> Module for FirstTestStruct<C> {
+        type Context = C;
+        type Config = ();
+        type CallMessage = CallMessage<C>;
+        type Event = ();
+
+        fn call(
+            &self,
+            _msg: Self::CallMessage,
+            _context: &Self::Context,
+            _working_set: &mut WorkingSet<C>,
+        ) -> Result<CallResponse, Error> {
+            Ok(CallResponse::default())
+        }
+    }
+}
+
+pub mod second_test_module {
+    use super::*;
+
+    #[derive(ModuleInfo)]
+    pub struct SecondTestStruct<C: Context> {
+        #[address]
+        pub address: C::Address,
+
+        #[state]
+        pub state_in_second_struct: StateValue<u8>,
+    }
+
+    impl<C: Context> Module for SecondTestStruct<C> {
+        type Context = C;
+        type Config = ();
+        type CallMessage = u8;
+        type Event = ();
+
+        fn call(
+            &self,
+            _msg: Self::CallMessage,
+            _context: &Self::Context,
+            _working_set: &mut WorkingSet<C>,
+        ) -> Result<CallResponse, Error> {
+            Ok(CallResponse::default())
+        }
+    }
+}
+
+use first_test_module::CallMessageBuilder;
+
+#[derive(DispatchCall, MessageCodec, ModuleClients)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+pub struct Runtime<C: Context> {
+    pub first: first_test_module::FirstTestStruct<C>,
+    pub second: second_test_module::SecondTestStruct<C>,
+}
+
+/// Records the runtime calls instead of submitting them.
+struct Recorder<R: DispatchCall> {
+    calls: Vec<R::Decodable>,
+}
+
+struct ModuleRecorder<'a, R: DispatchCall, M> {
+    recorder: &'a mut Recorder<R>,
+    wrap: fn(M) -> R::Decodable,
+}
+
+impl<'a, R: DispatchCall, M> CallMessageSubmitter for ModuleRecorder<'a, R, M> {
+    type CallMessage = M;
+    type Output = usize;
+
+    fn submit_call(self, call_message: M) -> usize {
+        self.recorder.calls.push((self.wrap)(call_message));
+        self.recorder.calls.len()
+    }
+}
+
+impl<R: DispatchCall> RuntimeCallSubmitter<R> for Recorder<R> {
+    type ModuleSubmitter<'a, M>
+        = ModuleRecorder<'a, R, M>
+    where
+        Self: 'a;
+
+    fn module_submitter<M>(&mut self, wrap: fn(M) -> R::Decodable) -> ModuleRecorder<'_, R, M> {
+        ModuleRecorder {
+            recorder: self,
+            wrap,
+        }
+    }
+}
+
+fn main() {
+    type RT = Runtime<DefaultContext>;
+    let owner = sov_modules_api::Address::try_from([1; 32].as_ref()).unwrap();
+    let mut recorder = Recorder::<RT> { calls: Vec::new() };
+
+    assert_eq!(1, recorder.first().set_value(3, owner.clone()));
+    assert_eq!(2, recorder.first().increment(1));
+    assert_eq!(3, recorder.first().reset());
+    assert_eq!(4, recorder.second().submit_call(7));
+
+    let expected = vec![
+        RuntimeCall::first(first_test_module::CallMessage::SetValue { value: 3, owner }),
+        RuntimeCall::first(first_test_module::CallMessage::Increment(1)),
+        RuntimeCall::first(first_test_module::CallMessage::Reset),
+        RuntimeCall::second(7),
+    ];
+    assert_eq!(expected, recorder.calls);
+}
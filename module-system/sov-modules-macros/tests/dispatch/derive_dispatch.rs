@@ -41,6 +41,7 @@ fn main() {
         let module = RT::decode_call(&serialized_message).unwrap();
 
         assert_eq!(runtime.module_address(&module), runtime.first.address());
+        assert_eq!(runtime.module_name(&module), "FirstTestStruct");
         let _ = runtime
             .dispatch_call(module, working_set, &context)
             .unwrap();
@@ -60,6 +61,7 @@ fn main() {
         let module = RT::decode_call(&serialized_message).unwrap();
 
         assert_eq!(runtime.module_address(&module), runtime.second.address());
+        assert_eq!(runtime.module_name(&module), "SecondTestStruct");
 
         let _ = runtime
             .dispatch_call(module, working_set, &context)
@@ -20,6 +20,19 @@ where
     pub third: third_test_module::ThirdTestStruct<C, T>,
 }
 
+mod aliased {
+    use super::*;
+
+    type FirstAlias<C> = first_test_module::FirstTestStruct<C>;
+
+    // The types of the fields differ as written, but they're the same module
+    #[derive(Genesis)]
+    pub struct Runtime<C: Context> {
+        pub first: first_test_module::FirstTestStruct<C>,
+        pub alias: FirstAlias<C>,
+    }
+}
+
 fn main() {
     type C = ZkDefaultContext;
     let storage = ZkStorage::new();
@@ -42,4 +55,26 @@ fn main() {
         let response = runtime.third.get_state_value(&mut working_set);
         assert_eq!(response, Some(0));
     }
+
+    {
+        let prefixes = runtime.storage_prefixes();
+        let modules: Vec<_> = prefixes.iter().map(|(module, _)| *module).collect();
+        assert_eq!(vec!["first", "second", "third"], modules);
+        assert_eq!(
+            Some("state_in_first_struct"),
+            prefixes[0].1[0].storage_name()
+        );
+    }
+
+    {
+        let runtime = aliased::Runtime::<C> {
+            first: Default::default(),
+            alias: Default::default(),
+        };
+        let config = aliased::GenesisConfig::new((), ());
+        let error = runtime.genesis(&config, working_set).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("The storage prefixes of the runtime collide"));
+    }
 }
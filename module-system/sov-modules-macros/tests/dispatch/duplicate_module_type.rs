@@ -0,0 +1,36 @@
+use sov_modules_api::{
+    CallResponse, Context, Error, Genesis, Module, ModuleInfo, StateValue, WorkingSet,
+};
+
+#[derive(ModuleInfo)]
+pub struct TestModule<C: Context> {
+    #[address]
+    pub address: C::Address,
+
+    #[state]
+    pub value: StateValue<u8>,
+}
+
+impl<C: Context> Module for TestModule<C> {
+    type Context = C;
+    type Config = ();
+    type CallMessage = u8;
+    type Event = ();
+
+    fn call(
+        &self,
+        _msg: Self::CallMessage,
+        _context: &Self::Context,
+        _working_set: &mut WorkingSet<C>,
+    ) -> Result<CallResponse, Error> {
+        Ok(CallResponse::default())
+    }
+}
+
+#[derive(Genesis)]
+struct Runtime<C: Context> {
+    pub first: TestModule<C>,
+    pub second: TestModule<C>,
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+mod modules;
+use modules::{first_test_module, second_test_module};
+use sov_modules_api::default_context::ZkDefaultContext;
+use sov_modules_api::macros::DefaultRuntime;
+use sov_modules_api::{Context, DispatchCall, EncodeCall, MessageCodec, VersionedCall};
+
+#[derive(DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct Runtime<C: Context> {
+    pub first: first_test_module::FirstTestStruct<C>,
+    pub second: second_test_module::SecondTestStruct<C>,
+}
+
+// The next version of the runtime, with its modules reordered
+#[derive(DispatchCall, MessageCodec, DefaultRuntime)]
+#[serialization(borsh::BorshDeserialize, borsh::BorshSerialize)]
+struct UpgradedRuntime<C: Context> {
+    pub second: second_test_module::SecondTestStruct<C>,
+    pub first: first_test_module::FirstTestStruct<C>,
+}
+
+fn main() {
+    type RT = Runtime<ZkDefaultContext>;
+    type Upgraded = UpgradedRuntime<ZkDefaultContext>;
+    let upgraded = Upgraded::default();
+
+    let serialized_message =
+        <RT as EncodeCall<first_test_module::FirstTestStruct<ZkDefaultContext>>>::encode_call(11);
+    let message = Upgraded::decode_call(&serialized_message).unwrap();
+    assert_eq!(upgraded.module_name(&message), "FirstTestStruct");
+
+    // Messages in the legacy encoding are still decoded
+    let legacy_message =
+        borsh::BorshSerialize::try_to_vec(&RuntimeCall::<ZkDefaultContext>::second(22)).unwrap();
+    let message = RT::decode_call(&legacy_message).unwrap();
+    assert_eq!(RT::default().module_name(&message), "SecondTestStruct");
+
+    let unknown_module = VersionedCall::new("third", vec![1]).encode();
+    assert!(Upgraded::decode_call(&unknown_module).is_err());
+
+    let mut unsupported_version = serialized_message;
+    unsupported_version[0] += 1;
+    assert!(Upgraded::decode_call(&unsupported_version).is_err());
+}
@@ -2,12 +2,16 @@
 #![doc = include_str!("../README.md")]
 
 mod runtime_rpc;
+mod simulation;
 mod wallet;
 use std::net::SocketAddr;
 
 use async_trait::async_trait;
 pub use runtime_rpc::*;
+pub use simulation::*;
+use sov_db::ledger_db::migrations::LedgerMigrations;
 use sov_db::ledger_db::LedgerDB;
+use sov_db::pruning::PruningHandle;
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{Context, DaSpec, Spec};
 use sov_modules_stf_blueprint::{GenesisParams, Runtime as RuntimeTrait, StfBlueprint};
@@ -17,7 +21,8 @@ use sov_rollup_interface::zk::ZkvmHost;
 use sov_state::storage::NativeStorage;
 use sov_state::Storage;
 use sov_stf_runner::{
-    InitVariant, ProverService, RollupConfig, RollupProverConfig, StateTransitionRunner,
+    ConfigUpdates, ConfigWatcher, InitVariant, ProverService, ReloadableConfig, RollupConfig,
+    RollupProverConfig, StateTransitionRunner,
 };
 use tokio::sync::oneshot;
 pub use wallet::*;
@@ -65,11 +70,14 @@ pub trait RollupBlueprint: Sized + Send + Sync {
     >;
 
     /// Creates RPC methods for the rollup.
+    /// The settings which can be reloaded while the rollup runs are read from `config_updates`.
     fn create_rpc_methods(
         &self,
         storage: &<Self::NativeContext as Spec>::Storage,
         ledger_db: &LedgerDB,
         da_service: &Self::DaService,
+        rollup_config: &RollupConfig<Self::DaConfig>,
+        config_updates: &ConfigUpdates,
     ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error>;
 
     /// Creates GenesisConfig from genesis files.
@@ -120,9 +128,24 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         rollup_config: &RollupConfig<Self::DaConfig>,
     ) -> Result<Self::StorageManager, anyhow::Error>;
 
+    /// Returns the handle retaining the state versions which the runner still needs
+    /// from the pruning of the storage, if the storage manager prunes its history.
+    fn pruning_handle(&self, _storage_manager: &Self::StorageManager) -> Option<PruningHandle> {
+        None
+    }
+
+    /// The migrations of the ledger tables from the schema versions of older releases,
+    /// which are applied when the LedgerDB is opened.
+    fn ledger_migrations(&self) -> LedgerMigrations {
+        LedgerMigrations::default()
+    }
+
     /// Creates instance of a LedgerDB.
     fn create_ledger_db(&self, rollup_config: &RollupConfig<Self::DaConfig>) -> LedgerDB {
-        LedgerDB::with_path(&rollup_config.storage.path).expect("Ledger DB failed to open")
+        LedgerDB::with_path_and_migrations(&rollup_config.storage.path, &self.ledger_migrations())
+            .expect("Ledger DB failed to open")
+            .with_pruning(rollup_config.storage.pruning)
+            .with_sender_index(rollup_config.storage.index_txs_by_sender)
     }
 
     /// Creates a new rollup.
@@ -156,6 +179,7 @@ pub trait RollupBlueprint: Sized + Send + Sync {
         )?;
 
         let mut storage_manager = self.create_storage_manager(&rollup_config)?;
+        let pruning_handle = self.pruning_handle(&storage_manager);
         let prover_storage = storage_manager.create_finalized_storage()?;
 
         let prev_root = ledger_db
@@ -163,10 +187,22 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             .map(|(number, _)| prover_storage.get_root_hash(number.0))
             .transpose()?;
 
+        let config_watcher = ConfigWatcher::new(ReloadableConfig::from(&rollup_config));
+        let config_updates = config_watcher.subscribe();
+
         // TODO(https://github.com/Sovereign-Labs/sovereign-sdk/issues/1218)
-        let rpc_methods = self.create_rpc_methods(&prover_storage, &ledger_db, &da_service)?;
+        // The RPC reads the finalized state without contending with the block production
+        let rpc_storage = storage_manager.create_read_only_storage()?;
+        let rpc_methods = self.create_rpc_methods(
+            &rpc_storage,
+            &ledger_db,
+            &da_service,
+            &rollup_config,
+            &config_updates,
+        )?;
 
-        let native_stf = StfBlueprint::new();
+        let native_stf =
+            StfBlueprint::new().with_parallel_execution(rollup_config.runner.parallel_execution);
 
         let init_variant = match prev_root {
             Some(root_hash) => InitVariant::Initialized(root_hash),
@@ -176,7 +212,7 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             },
         };
 
-        let runner = StateTransitionRunner::new(
+        let mut runner = StateTransitionRunner::new(
             rollup_config.runner,
             da_service,
             ledger_db,
@@ -184,11 +220,16 @@ pub trait RollupBlueprint: Sized + Send + Sync {
             storage_manager,
             init_variant,
             prover_service,
-        )?;
+        )?
+        .with_config_updates(config_updates);
+        if let Some(pruning_handle) = pruning_handle {
+            runner = runner.with_pruning_handle(pruning_handle);
+        }
 
         Ok(Rollup {
             runner,
             rpc_methods,
+            config_watcher,
         })
     }
 }
@@ -206,6 +247,9 @@ pub struct Rollup<S: RollupBlueprint> {
     >,
     /// Rpc methods for the rollup.
     pub rpc_methods: jsonrpsee::RpcModule<()>,
+    /// Publishes the settings which can be reloaded while the rollup runs.
+    /// They are only reloaded once it watches the rollup config file, see [`ConfigWatcher::watch_file`].
+    pub config_watcher: ConfigWatcher,
 }
 
 impl<S: RollupBlueprint> Rollup<S> {
@@ -220,7 +264,9 @@ impl<S: RollupBlueprint> Rollup<S> {
         channel: Option<oneshot::Sender<SocketAddr>>,
     ) -> Result<(), anyhow::Error> {
         let mut runner = self.runner;
+        tokio::spawn(self.config_watcher.run());
         runner.start_rpc_server(self.rpc_methods, channel).await;
+        runner.start_metrics_server().await?;
         runner.run_in_process().await?;
         Ok(())
     }
@@ -1,25 +1,65 @@
+use std::time::Duration;
+
 use anyhow::Context as _;
+use sov_accounts::Accounts;
+use sov_chain_state::ChainState;
 use sov_db::ledger_db::LedgerDB;
+use sov_db::mempool_db::MempoolDB;
+use sov_db::submission_queue_db::SubmissionQueueDB;
+use sov_modules_api::hooks::TxHooks;
 use sov_modules_api::{Context, Spec};
-use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, SequencerOutcome, TxEffect};
+use sov_modules_stf_blueprint::{
+    Runtime as RuntimeTrait, RuntimeTxHook, SequencerOutcome, TxEffect,
+};
 use sov_rollup_interface::services::da::DaService;
 use sov_sequencer::batch_builder::FiFoStrictBatchBuilder;
+use sov_sequencer::ordering::BatchOrdering;
+use sov_sequencer::submission::SubmissionStrategy;
+use sov_sequencer::{Sequencer, SequencerSettings};
+use sov_state::storage::NativeStorage;
+use sov_stf_runner::{
+    BatchBuilderConfig, BatchOrderingConfig, ConfigUpdates, DaCompressionConfig, DaSubmissionConfig,
+};
+
+use crate::get_simulation_rpc;
 
 /// Register rollup's default rpc methods.
+/// The calls of the runtime are simulated with `rollup_simulateCall`, see [`get_simulation_rpc`].
+/// The batches published by the sequencer are compressed if `da_compression` is set,
+/// built with the policy of `batch_builder_config` and submitted with the policy of `da_submission_config`.
+/// The mempool size and the DA fee ceiling of the sequencer follow the snapshots of `config_updates`.
+/// The mempool reads the nonces of the senders from the accounts module, and the batch builder
+/// checks the call messages with [`RuntimeTrait::check_dispatch`] like the blueprint does.
+/// If the batch builder has a `mempool_path`, the transactions persisted there by the previous run
+/// are revalidated with the transaction hooks of the runtime, and restored in the mempool.
+#[allow(clippy::too_many_arguments)]
 pub fn register_rpc<RT, C, Da>(
     storage: &<C as Spec>::Storage,
     ledger_db: &LedgerDB,
     da_service: &Da,
     sequencer: C::Address,
+    da_compression: Option<DaCompressionConfig>,
+    batch_builder_config: &BatchBuilderConfig,
+    da_submission_config: &DaSubmissionConfig,
+    config_updates: &ConfigUpdates,
 ) -> Result<jsonrpsee::RpcModule<()>, anyhow::Error>
 where
     RT: RuntimeTrait<C, <Da as DaService>::Spec> + Send + Sync + 'static,
     C: Context,
+    C::Storage: NativeStorage,
     Da: DaService + Clone,
 {
     // runtime rpc.
     let mut rpc_methods = RT::rpc_methods(storage.clone());
 
+    // simulation rpc.
+    {
+        rpc_methods.merge(get_simulation_rpc::<RT, C, <Da as DaService>::Spec>(
+            storage.clone(),
+            sequencer.clone(),
+        )?)?;
+    }
+
     // ledger rpc.
     {
         rpc_methods.merge(sov_ledger_rpc::server::rpc_module::<
@@ -31,15 +71,86 @@ where
 
     // sequencer rpc.
     {
-        let batch_builder = FiFoStrictBatchBuilder::new(
-            1024 * 100,
-            u32::MAX as usize,
+        let ordering = match batch_builder_config.ordering {
+            BatchOrderingConfig::Fifo => BatchOrdering::Fifo,
+            BatchOrderingConfig::GasPrice => BatchOrdering::GasPrice,
+        };
+        let mut batch_builder = FiFoStrictBatchBuilder::new(
+            batch_builder_config.max_batch_size_bytes,
+            batch_builder_config.mempool_max_txs_count,
             RT::default(),
             storage.clone(),
             sequencer,
-        );
+        )
+        .with_ordering(ordering)
+        .with_nonce_reader(Box::new(|address, working_set| {
+            Accounts::<C>::default().get_nonce(address, working_set)
+        }))
+        .with_dispatch_checker(Box::new(|message, working_set| {
+            RT::default().check_dispatch(message, working_set)
+        }));
+        if let Some(max_batch_gas) = batch_builder_config.max_batch_gas {
+            batch_builder = batch_builder.with_max_batch_gas(max_batch_gas);
+        }
+        if let Some(max_txs_per_sender) = batch_builder_config.max_txs_per_sender {
+            batch_builder = batch_builder.with_max_txs_per_sender(max_txs_per_sender);
+        }
+        if let Some(max_age_secs) = batch_builder_config.mempool_max_age_secs {
+            batch_builder = batch_builder.with_mempool_max_age(Duration::from_secs(max_age_secs));
+        }
+        if let Some(mempool_path) = &batch_builder_config.mempool_path {
+            let mempool_db = MempoolDB::with_path(mempool_path)
+                .context("Failed to open the mempool database")?;
+            batch_builder = batch_builder
+                .with_mempool_db(mempool_db)
+                .with_tx_validator(Box::new(|tx, working_set| {
+                    // Same gas meter as the blueprint, so the fees of the hooks can be charged
+                    let gas_price = ChainState::<C, <Da as DaService>::Spec>::default()
+                        .get_gas_price(working_set);
+                    working_set.set_gas(tx.gas_limit(), gas_price);
+                    // Same hook arguments as the blueprint, which doesn't set the sequencer yet
+                    let hook = RuntimeTxHook {
+                        height: 0,
+                        sequencer: tx.pub_key().clone(),
+                    };
+                    RT::default()
+                        .pre_dispatch_tx_hook(tx, working_set, &hook)
+                        .map(|_| ())
+                }));
+            batch_builder.restore_mempool()?;
+        }
 
-        let sequencer_rpc = sov_sequencer::get_sequencer_rpc(batch_builder, da_service.clone());
+        let submission_strategy = SubmissionStrategy {
+            max_fee_rate: da_submission_config.max_fee_rate,
+            max_delay: Duration::from_secs(da_submission_config.max_delay_secs),
+            stuck_timeout: da_submission_config
+                .stuck_timeout_secs
+                .map(Duration::from_secs),
+            fee_bump_percent: da_submission_config.fee_bump_percent,
+            max_fee_bumps: da_submission_config.max_fee_bumps,
+            max_queued_batches: da_submission_config.max_queued_batches,
+            retry_backoff: Duration::from_secs(da_submission_config.retry_backoff_secs),
+            max_retry_backoff: Duration::from_secs(da_submission_config.max_retry_backoff_secs),
+        };
+        let config_updates = config_updates.clone();
+        let mut sequencer = Sequencer::new(batch_builder, da_service.clone())
+            .with_submission_strategy(submission_strategy)
+            .with_settings(move || {
+                let config = config_updates.borrow();
+                SequencerSettings {
+                    mempool_max_txs_count: config.mempool_max_txs_count,
+                    max_fee_rate: config.max_da_fee_rate,
+                }
+            });
+        if let Some(compression) = da_compression {
+            sequencer = sequencer.with_compression(compression.level);
+        }
+        if let Some(queue_path) = &da_submission_config.queue_path {
+            let queue_db = SubmissionQueueDB::with_path(queue_path)
+                .context("Failed to open the submission queue database")?;
+            sequencer = sequencer.with_submission_queue_db(queue_db)?;
+        }
+        let sequencer_rpc = sov_sequencer::create_sequencer_rpc(sequencer);
         rpc_methods
             .merge(sequencer_rpc)
             .context("Failed to merge Txs RPC modules")?;
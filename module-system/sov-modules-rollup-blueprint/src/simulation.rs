@@ -0,0 +1,151 @@
+use jsonrpsee::RpcModule;
+use serde::{Deserialize, Serialize};
+use sov_modules_api::utils::to_jsonrpsee_error_object;
+use sov_modules_api::{Context, DaSpec, DispatchCall, Event, GasUnit, StateSnapshot};
+use sov_modules_stf_blueprint::{Runtime as RuntimeTrait, TxEffect};
+use sov_state::storage::{CacheKey, CacheValue, NativeStorage};
+use sov_state::Storage;
+
+/// A key of the state changed by a simulated call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulatedStateChange {
+    /// The hex encoded key.
+    pub key: String,
+    /// The hex encoded value written to the key, `None` if the key was deleted.
+    pub value: Option<String>,
+}
+
+/// The result of `rollup_simulateCall`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateCallResponse {
+    /// The outcome of the call, as it would be recorded in the receipt of its transaction.
+    pub effect: TxEffect,
+    /// The events emitted by the call.
+    pub events: Vec<Event>,
+    /// The changes the call would make to the state, sorted by key. Empty if it's reverted.
+    pub state_diff: Vec<SimulatedStateChange>,
+    /// The changes the call would make to the accessory state, sorted by key.
+    /// Empty if it's reverted.
+    pub accessory_state_diff: Vec<SimulatedStateChange>,
+    /// The gas used by the call, in each dimension of the gas unit.
+    pub gas_used: Vec<u64>,
+}
+
+/// Executes the borsh encoded call of the runtime as if it was sent by `sender`, on top
+/// of the state at the given version, or at the latest version.
+///
+/// The call is dispatched with its deferred calls, like the calls of the transactions, but
+/// without the transaction hooks of the runtime: no signature, nonce nor fee is checked.
+/// Its changes are discarded, so the state of the rollup isn't affected.
+pub fn simulate_call<RT, C, Da>(
+    runtime: &RT,
+    storage: &C::Storage,
+    sender: C::Address,
+    sequencer: C::Address,
+    call: &[u8],
+    version: Option<u64>,
+) -> anyhow::Result<SimulateCallResponse>
+where
+    RT: RuntimeTrait<C, Da>,
+    C: Context,
+    C::Storage: NativeStorage,
+    Da: DaSpec,
+{
+    let storage = storage.pin_latest_version();
+    if let Some(version) = version {
+        storage.check_archival_version(version)?;
+    }
+    let message = RT::decode_call(call)?;
+    let height = version.unwrap_or_else(|| storage.latest_version());
+    let context = C::new(sender, sequencer, height);
+
+    let mut working_set = StateSnapshot::<C>::new(storage, version).to_working_set();
+    let result = runtime.dispatch_call_and_deferred_checked(
+        message,
+        &mut working_set,
+        &context,
+        &|message, working_set| runtime.check_dispatch(message, working_set),
+    );
+    let events = working_set.take_events();
+    let gas_used = working_set.gas_used().to_dimensions();
+
+    let (effect, state_diff, accessory_state_diff) = match result {
+        Ok(_) => {
+            let changes = working_set
+                .into_tracked_changes()
+                .expect("The working sets of snapshots track their changes");
+            (
+                TxEffect::Successful,
+                state_changes(changes.writes, version),
+                state_changes(changes.accessory_writes, version),
+            )
+        }
+        Err(e) => (TxEffect::from_error(&e), Vec::new(), Vec::new()),
+    };
+
+    Ok(SimulateCallResponse {
+        effect,
+        events,
+        state_diff,
+        accessory_state_diff,
+        gas_used,
+    })
+}
+
+/// Sorts the changes tracked by a working set by key, removing the version prefixing the keys
+/// of versioned snapshots.
+fn state_changes(
+    writes: impl IntoIterator<Item = (CacheKey, Option<CacheValue>)>,
+    version: Option<u64>,
+) -> Vec<SimulatedStateChange> {
+    let prefix_len = match version {
+        Some(version) => version.to_be_bytes().len(),
+        None => 0,
+    };
+    let mut changes: Vec<_> = writes
+        .into_iter()
+        .map(|(key, value)| SimulatedStateChange {
+            key: hex::encode(&key.key[prefix_len..]),
+            value: value.map(|value| hex::encode(value.value.as_ref())),
+        })
+        .collect();
+    changes.sort_by(|a, b| a.key.cmp(&b.key));
+    changes
+}
+
+/// Creates the `rollup_simulateCall` RPC method, which returns the [`SimulateCallResponse`] of
+/// a call of the runtime `RT`, without submitting it.
+///
+/// Its parameters are the address of the sender, the hex encoded borsh serialization of the
+/// call, and optionally the version of the state to execute it on, see [`simulate_call`].
+pub fn get_simulation_rpc<RT, C, Da>(
+    storage: C::Storage,
+    sequencer: C::Address,
+) -> Result<RpcModule<(C::Storage, C::Address)>, jsonrpsee::core::Error>
+where
+    RT: RuntimeTrait<C, Da> + Default + Send + Sync + 'static,
+    C: Context,
+    C::Storage: NativeStorage,
+    Da: DaSpec,
+{
+    let mut rpc = RpcModule::new((storage, sequencer));
+    rpc.register_method("rollup_simulateCall", |params, (storage, sequencer)| {
+        let mut params = params.sequence();
+        let sender: C::Address = params.next()?;
+        let call: String = params.next()?;
+        let version: Option<u64> = params.optional_next()?;
+
+        let call = hex::decode(call.trim_start_matches("0x"))
+            .map_err(|e| to_jsonrpsee_error_object(e, "Invalid hex encoded call"))?;
+        simulate_call::<RT, C, Da>(
+            &RT::default(),
+            storage,
+            sender,
+            sequencer.clone(),
+            &call,
+            version,
+        )
+        .map_err(|e| to_jsonrpsee_error_object(e, "Failed to simulate the call"))
+    })?;
+    Ok(rpc)
+}
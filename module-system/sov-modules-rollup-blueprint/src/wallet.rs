@@ -3,9 +3,12 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use sov_cli::wallet_state::WalletState;
+use sov_cli::workflows::genesis::GenesisWorkflow;
 use sov_cli::workflows::keys::KeyWorkflow;
 use sov_cli::workflows::rpc::RpcWorkflows;
+use sov_cli::workflows::signing::SigningWorkflow;
 use sov_cli::workflows::transactions::TransactionWorkflow;
+use sov_cli::workflows::wallet::WalletWorkflow;
 use sov_cli::{clap, wallet_dir};
 use sov_modules_api::clap::Parser;
 use sov_modules_api::cli::{CliFrontEnd, CliTxImportArg, JsonStringArg};
@@ -22,6 +25,12 @@ enum Workflows<File: clap::Subcommand, Json: clap::Subcommand, C: Context> {
     Keys(KeyWorkflow<C>),
     #[clap(subcommand)]
     Rpc(RpcWorkflows<C>),
+    #[clap(subcommand)]
+    Tx(SigningWorkflow<C>),
+    #[clap(subcommand)]
+    Wallet(WalletWorkflow),
+    #[clap(subcommand)]
+    Genesis(GenesisWorkflow),
 }
 
 #[derive(clap::Parser)]
@@ -92,6 +101,11 @@ where
             Workflows::Rpc(inner) => {
                 inner.run(&mut wallet_state, app_dir).await?;
             }
+            Workflows::Tx(inner) => {
+                inner.run(&mut wallet_state, app_dir).await?;
+            }
+            Workflows::Wallet(inner) => inner.run(app_dir)?,
+            Workflows::Genesis(inner) => inner.run::<<Self as RollupBlueprint>::NativeContext>()?,
         }
 
         wallet_state.save(wallet_state_path)
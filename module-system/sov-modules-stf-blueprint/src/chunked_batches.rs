@@ -0,0 +1,103 @@
+//! Reassembly of the batches split across several blobs by [`sov_rollup_interface::chunking`].
+//!
+//! The parts of a chunked batch can be included in different slots, so they are buffered in the
+//! rollup state until the last one is included. Every sender has at most one pending batch:
+//! a part of another batch drops the incomplete one.
+
+use sov_modules_api::{Context, StateMap, StateMapAccessor, WorkingSet};
+use sov_rollup_interface::chunking::{decode_chunk, is_chunked, PendingBatch};
+use sov_state::Prefix;
+use tracing::{debug, warn};
+
+/// The prefix of the pending chunked batches, keyed by the DA address of their sender.
+const PENDING_CHUNKED_BATCHES_PREFIX: &[u8] = b"sov_modules_stf_blueprint/pending_chunked_batches";
+
+/// How a blob is applied, once the chunk it carries, if any, was buffered.
+pub(crate) enum ChunkedBlob {
+    /// The blob is applied with its own data. Blobs with an invalid chunk envelope, or completing
+    /// a batch which doesn't match its manifest, are applied as is too: they fail to deserialize.
+    Whole,
+    /// The blob carries a part of a batch whose other parts weren't all included yet,
+    /// so there is nothing to apply.
+    Pending,
+    /// The blob completed a chunked batch, which is applied in its place.
+    Assembled(Vec<u8>),
+}
+
+fn pending_chunked_batches() -> StateMap<Vec<u8>, PendingBatch> {
+    StateMap::new(Prefix::new(PENDING_CHUNKED_BATCHES_PREFIX.to_vec()))
+}
+
+/// Buffers the chunk carried by the blob data of `sender`, and returns how the blob is applied.
+pub(crate) fn buffer_chunk<C: Context>(
+    data: &[u8],
+    sender: &[u8],
+    working_set: &mut WorkingSet<C>,
+) -> ChunkedBlob {
+    if !is_chunked(data) {
+        return ChunkedBlob::Whole;
+    }
+    let envelope = match decode_chunk(data) {
+        Ok(envelope) => envelope,
+        Err(e) => {
+            warn!(
+                "Sender 0x{} posted an invalid chunk: {}",
+                hex::encode(sender),
+                e
+            );
+            return ChunkedBlob::Whole;
+        }
+    };
+
+    let batches = pending_chunked_batches();
+    let sender = sender.to_vec();
+    let batch_hash = envelope.batch_hash();
+    let mut pending = match batches.get(&sender, working_set) {
+        Some(pending) if pending.batch_hash() == batch_hash => pending,
+        previous => {
+            if let Some(previous) = previous {
+                warn!(
+                    "Dropping incomplete chunked batch 0x{} of sender 0x{}",
+                    hex::encode(previous.batch_hash()),
+                    hex::encode(&sender)
+                );
+            }
+            PendingBatch::new(batch_hash)
+        }
+    };
+
+    if let Err(e) = pending.insert(envelope) {
+        warn!(
+            "Sender 0x{} posted an invalid chunk of batch 0x{}: {}",
+            hex::encode(&sender),
+            hex::encode(batch_hash),
+            e
+        );
+        batches.delete(&sender, working_set);
+        return ChunkedBlob::Whole;
+    }
+    if !pending.is_complete() {
+        debug!(
+            "Chunked batch 0x{} of sender 0x{} is missing {} part(s)",
+            hex::encode(batch_hash),
+            hex::encode(&sender),
+            pending.missing()
+        );
+        batches.set(&sender, &pending, working_set);
+        return ChunkedBlob::Pending;
+    }
+
+    batches.delete(&sender, working_set);
+    match pending.assemble() {
+        Ok(batch) => ChunkedBlob::Assembled(batch),
+        Err(e) => {
+            warn!(
+                "Chunked batch 0x{} of sender 0x{} can't be reassembled: {}",
+                hex::encode(batch_hash),
+                hex::encode(&sender),
+                e
+            );
+            ChunkedBlob::Whole
+        }
+    }
+}
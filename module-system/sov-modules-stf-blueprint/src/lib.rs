@@ -2,23 +2,31 @@
 #![doc = include_str!("../README.md")]
 
 mod batch;
+mod chunked_batches;
 pub mod kernels;
+#[cfg(feature = "native")]
+mod parallel;
 mod stf_blueprint;
 mod tx_verifier;
 
 pub use batch::Batch;
+use chunked_batches::{buffer_chunk, ChunkedBlob};
 use sov_modules_api::hooks::{ApplyBlobHooks, FinalizeHook, SlotHooks, TxHooks};
+use sov_modules_api::migration::{run_migrations, set_genesis_versions, Migration};
+use sov_modules_api::profiling::track_cycles;
 use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{
-    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, KernelWorkingSet, Spec,
-    StateCheckpoint, Zkvm,
+    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, Genesis, KernelWorkingSet,
+    ModuleError, OutOfGasError, Spec, StateCheckpoint, WorkingSet, Zkvm,
 };
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 pub use sov_rollup_interface::stf::BatchReceipt;
-use sov_rollup_interface::stf::{SlotResult, StateTransitionFunction};
+use sov_rollup_interface::stf::{SlotResult, StateDiff, StateTransitionFunction};
 use sov_state::Storage;
 #[cfg(all(target_os = "zkvm", feature = "bench"))]
 use sov_zk_cycle_macros::cycle_tracker;
 pub use stf_blueprint::StfBlueprint;
+use stf_blueprint::{data_for_deserialization, verify_soft_batch_signature};
 use tracing::info;
 pub use tx_verifier::RawTx;
 
@@ -64,17 +72,58 @@ pub trait Runtime<C: Context, Da: DaSpec>:
     fn genesis_config(
         genesis_paths: &Self::GenesisPaths,
     ) -> Result<Self::GenesisConfig, anyhow::Error>;
+
+    /// The migrations of the modules of the runtime, which run at the beginning of their activation slot.
+    /// Defaults to none.
+    fn migrations(&self) -> Vec<Migration<'_, C>> {
+        Vec::new()
+    }
+
+    /// Checks a call message before it's dispatched, including the deferred calls of the modules.
+    /// The transaction is reverted if the check fails, for example while the module of the
+    /// message is paused. Defaults to accepting every message.
+    fn check_dispatch(
+        &self,
+        _message: &<Self as DispatchCall>::Decodable,
+        _working_set: &mut WorkingSet<C>,
+    ) -> Result<(), ModuleError> {
+        Ok(())
+    }
 }
 
-/// The receipts of all the transactions in a batch.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+/// The outcome of a transaction, stored in its receipt.
+///
+/// Its changes to the state are discarded unless it's [`TxEffect::Successful`], but the
+/// transaction still pays for the gas it used.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum TxEffect {
-    /// Batch was reverted.
-    Reverted,
-    /// Batch was processed successfully.
+    /// The transaction was rejected by the runtime or by the module it was dispatched to.
+    Reverted {
+        /// The error returned by the runtime or the module, with its causes.
+        reason: String,
+        /// The numeric code of the error, see [`sov_modules_api::ModuleErrorCode`].
+        code: u32,
+    },
+    /// The transaction ran out of gas.
+    OutOfGas,
+    /// The transaction was processed successfully.
     Successful,
 }
 
+impl TxEffect {
+    /// The outcome of a transaction which failed with the given error.
+    pub fn from_error(error: &ModuleError) -> Self {
+        if error.error().downcast_ref::<OutOfGasError>().is_some() {
+            TxEffect::OutOfGas
+        } else {
+            TxEffect::Reverted {
+                reason: format!("{:#}", error.error()),
+                code: error.code(),
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 /// Represents the different outcomes that can occur for a sequencer after batch processing.
 pub enum SequencerOutcome<A: BasicAddress> {
@@ -135,11 +184,21 @@ where
             &mut working_set,
         );
 
-        self.runtime.begin_slot_hook(
-            slot_header,
-            validity_condition,
-            pre_state_root,
-            &mut working_set,
+        // Migrations run before any hook of the runtime, so the modules only see the migrated state
+        let slot_height = self.kernel.true_height(&mut working_set);
+        run_migrations(&self.runtime.migrations(), slot_height, &mut working_set)
+            .expect("Module migrations must succeed");
+
+        track_cycles(
+            || "hook::begin_slot".to_string(),
+            || {
+                self.runtime.begin_slot_hook(
+                    slot_header,
+                    validity_condition,
+                    pre_state_root,
+                    &mut working_set,
+                )
+            },
         );
 
         working_set.checkpoint()
@@ -154,14 +213,28 @@ where
         <<C as Spec>::Storage as Storage>::Root,
         <<C as Spec>::Storage as Storage>::Witness,
         C::Storage,
+        StateDiff,
     ) {
         // Run end end_slot_hook
         let mut working_set = checkpoint.to_revertable();
-        self.runtime.end_slot_hook(&mut working_set);
+        track_cycles(
+            || "hook::end_slot".to_string(),
+            || self.runtime.end_slot_hook(&mut working_set),
+        );
         // Save checkpoint
         let mut checkpoint = working_set.checkpoint();
 
         let (cache_log, witness) = checkpoint.freeze();
+        let state_diff = cache_log
+            .ordered_writes
+            .iter()
+            .map(|(key, value)| {
+                (
+                    key.key.to_vec(),
+                    value.as_ref().map(|value| value.value.to_vec()),
+                )
+            })
+            .collect();
 
         let (root_hash, state_update) = storage
             .compute_state_update(cache_log, &witness)
@@ -177,7 +250,7 @@ where
 
         storage.commit(&state_update, &accessory_log);
 
-        (root_hash, witness, storage)
+        (root_hash, witness, storage, state_diff)
     }
 }
 
@@ -217,6 +290,8 @@ where
         self.runtime
             .genesis(&params.runtime, &mut working_set)
             .expect("Runtime initialization must succeed");
+        // The state created at genesis already has the layout of the latest version of the modules
+        set_genesis_versions(&self.runtime.migrations(), &mut working_set);
 
         let mut checkpoint = working_set.checkpoint();
         let (log, witness) = checkpoint.freeze();
@@ -280,8 +355,29 @@ where
         let mut batch_receipts = vec![];
 
         for (blob_idx, mut blob) in selected_blobs.into_iter().enumerate() {
+            // The parts of a chunked batch are buffered until the last one is included
+            let mut chunk_workspace = checkpoint.to_revertable();
+            let sender = blob.as_mut_ref().sender();
+            let chunked_blob = buffer_chunk(
+                data_for_deserialization(blob.as_mut_ref()),
+                sender.as_ref(),
+                &mut chunk_workspace,
+            );
+            checkpoint = chunk_workspace.checkpoint();
+            let assembled_batch = match chunked_blob {
+                ChunkedBlob::Whole => None,
+                ChunkedBlob::Pending => {
+                    info!(
+                        "blob #{} from sequencer {} carries a part of a chunked batch",
+                        blob_idx, sender
+                    );
+                    continue;
+                }
+                ChunkedBlob::Assembled(batch) => Some(batch),
+            };
+
             let (apply_blob_result, checkpoint_after_blob) =
-                self.apply_blob(checkpoint, blob.as_mut_ref());
+                self.apply_blob(checkpoint, blob.as_mut_ref(), assembled_batch.as_deref());
             checkpoint = checkpoint_after_blob;
             let batch_receipt = apply_blob_result.unwrap_or_else(Into::into);
             info!(
@@ -303,12 +399,54 @@ where
             batch_receipts.push(batch_receipt);
         }
 
-        let (state_root, witness, storage) = self.end_slot(pre_state, checkpoint);
+        let (state_root, witness, storage, state_diff) = self.end_slot(pre_state, checkpoint);
         SlotResult {
             state_root,
             change_set: storage,
             batch_receipts,
             witness,
+            state_diff,
         }
     }
+
+    fn apply_soft_batch(
+        &self,
+        pre_state_root: &Self::StateRoot,
+        pre_state: Self::PreState,
+        witness: Self::Witness,
+        slot_header: &Da::BlockHeader,
+        validity_condition: &Da::ValidityCondition,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    > {
+        let checkpoint = StateCheckpoint::with_witness(pre_state.clone(), witness);
+        let checkpoint =
+            self.begin_slot(checkpoint, slot_header, validity_condition, pre_state_root);
+
+        let (batch_receipt, checkpoint) = self.apply_soft_confirmation(checkpoint, soft_batch);
+        info!(
+            "soft confirmation batch with hash 0x{} has been applied with #{} transactions, sequencer outcome {:?}",
+            hex::encode(batch_receipt.batch_hash),
+            batch_receipt.tx_receipts.len(),
+            batch_receipt.inner
+        );
+
+        let (state_root, witness, storage, state_diff) = self.end_slot(pre_state, checkpoint);
+        SlotResult {
+            state_root,
+            change_set: storage,
+            batch_receipts: vec![batch_receipt],
+            witness,
+            state_diff,
+        }
+    }
+
+    fn verify_soft_batch(soft_batch: &SignedSoftConfirmationBatch) -> Result<(), anyhow::Error> {
+        verify_soft_batch_signature::<C>(soft_batch)
+    }
 }
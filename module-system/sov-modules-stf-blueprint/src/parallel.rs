@@ -0,0 +1,143 @@
+//! Optimistic parallel execution of the transactions of a batch, in the style of Block-STM.
+//!
+//! All the transactions of the batch are first executed in parallel on top of the state at the
+//! beginning of the batch, each in its own working set tracking the keys it accesses. Their
+//! changes are then applied in the order of the batch. A transaction which read or wrote a key
+//! written by a previous transaction of the batch saw a stale state, so it's re-executed on top
+//! of the changes of the previous transactions instead. The state, the receipts and the rewards
+//! are the same as the ones of the serial execution.
+
+use std::mem;
+
+use rayon::prelude::*;
+use sov_modules_api::runtime::capabilities::KernelSlotHooks;
+use sov_modules_api::{Context, DaSpec, DispatchCall, Event, StateSnapshot, TrackedChanges};
+use sov_modules_core::WorkingSet;
+use sov_rollup_interface::maybestd::collections::HashSet;
+use sov_rollup_interface::stf::TransactionReceipt;
+use tracing::debug;
+
+use crate::tx_verifier::TransactionAndRawHash;
+use crate::{Runtime, StfBlueprint, TxEffect};
+
+/// The outcome of a transaction executed on top of a snapshot of the state.
+struct SpeculativeTx {
+    receipt: TransactionReceipt<TxEffect>,
+    reward: u64,
+    /// The events emitted after the receipt was built, by the post dispatch hook.
+    trailing_events: Vec<Event>,
+    changes: TrackedChanges,
+}
+
+impl<C, Vm, Da, RT, K> StfBlueprint<C, Da, Vm, RT, K>
+where
+    C: Context,
+    Da: DaSpec,
+    RT: Runtime<C, Da>,
+    K: KernelSlotHooks<C, Da>,
+{
+    /// Applies the transactions like [`Self::apply_txs`], executing them optimistically
+    /// in parallel. The witness of the transactions isn't recorded, so it can't be used for
+    /// the batches which are proven.
+    pub(crate) fn apply_txs_in_parallel(
+        &self,
+        txs: Vec<TransactionAndRawHash<C>>,
+        messages: Vec<<RT as DispatchCall>::Decodable>,
+        gas_price: &C::GasUnit,
+        height: u64,
+        tx_receipts: &mut Vec<TransactionReceipt<TxEffect>>,
+        mut batch_workspace: WorkingSet<C>,
+        sequencer_reward: &mut u64,
+    ) -> WorkingSet<C> {
+        let runtime = &self.runtime;
+        let snapshot = batch_workspace.snapshot();
+        let speculative_txs: Vec<SpeculativeTx> = txs
+            .par_iter()
+            .zip(messages.into_par_iter())
+            .map(|(tx, msg)| {
+                Self::execute_speculatively(runtime, tx, msg, gas_price, height, &snapshot)
+            })
+            .collect();
+
+        let mut written = HashSet::new();
+        let mut accessory_written = HashSet::new();
+        let mut pending_events = Vec::new();
+        let mut reexecuted = 0;
+        for (tx, speculative_tx) in txs.iter().zip(speculative_txs) {
+            let speculative_tx = if speculative_tx
+                .changes
+                .depends_on(&written, &accessory_written)
+            {
+                reexecuted += 1;
+                let msg = RT::decode_call(tx.tx.runtime_msg())
+                    .expect("The messages of the batch were decoded before its execution");
+                Self::execute_speculatively(
+                    runtime,
+                    tx,
+                    msg,
+                    gas_price,
+                    height,
+                    &batch_workspace.snapshot(),
+                )
+            } else {
+                speculative_tx
+            };
+
+            let SpeculativeTx {
+                mut receipt,
+                reward,
+                trailing_events,
+                changes,
+            } = speculative_tx;
+            batch_workspace.apply_tracked_changes(&changes);
+            written.extend(changes.writes.into_keys());
+            accessory_written.extend(changes.accessory_writes.into_keys());
+
+            // Like in the serial execution, the events of the post dispatch hook of a transaction
+            // are only kept in the receipt of the next transaction if it's rejected before
+            // its dispatch.
+            let pending_events = mem::replace(&mut pending_events, trailing_events);
+            if receipt.sender.is_none() {
+                receipt.events = pending_events.into_iter().chain(receipt.events).collect();
+            }
+
+            *sequencer_reward = sequencer_reward.saturating_add(reward);
+            tx_receipts.push(receipt);
+        }
+        debug!(
+            "Executed {} transactions in parallel, {} of them re-executed after a conflict",
+            txs.len(),
+            reexecuted
+        );
+
+        batch_workspace
+    }
+
+    fn execute_speculatively(
+        runtime: &RT,
+        tx: &TransactionAndRawHash<C>,
+        msg: <RT as DispatchCall>::Decodable,
+        gas_price: &C::GasUnit,
+        height: u64,
+        snapshot: &StateSnapshot<C>,
+    ) -> SpeculativeTx {
+        let (mut working_set, receipt, reward) = Self::apply_tx(
+            runtime,
+            tx,
+            msg,
+            gas_price,
+            height,
+            snapshot.to_working_set(),
+        );
+        let trailing_events = working_set.take_events();
+        let changes = working_set
+            .into_tracked_changes()
+            .expect("The working sets of a snapshot track their changes");
+        SpeculativeTx {
+            receipt,
+            reward,
+            trailing_events,
+            changes,
+        }
+    }
+}
@@ -1,15 +1,20 @@
 use std::marker::PhantomData;
 
 use borsh::BorshDeserialize;
-use sov_modules_api::runtime::capabilities::KernelSlotHooks;
+use sov_modules_api::profiling::track_cycles;
+use sov_modules_api::runtime::capabilities::{Kernel, KernelSlotHooks};
 use sov_modules_api::{
-    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, GasUnit, StateCheckpoint,
+    BasicAddress, BlobReaderTrait, Context, DaSpec, DispatchCall, GasUnit, Signature, Spec,
+    StateCheckpoint,
 };
 use sov_modules_core::WorkingSet;
+use sov_rollup_interface::compression::decompress_blob;
+use sov_rollup_interface::digest::Digest;
+use sov_rollup_interface::soft_confirmation::SignedSoftConfirmationBatch;
 use sov_rollup_interface::stf::{BatchReceipt, TransactionReceipt};
 use tracing::{debug, error};
 
-use crate::tx_verifier::{verify_txs_stateless, TransactionAndRawHash};
+use crate::tx_verifier::{verify_txs_stateless, RawTx, TransactionAndRawHash};
 use crate::{Batch, Runtime, RuntimeTxHook, SequencerOutcome, SlashingReason, TxEffect};
 
 type ApplyBatchResult<T, A> = Result<T, ApplyBatchError<A>>;
@@ -30,6 +35,9 @@ pub struct StfBlueprint<C: Context, Da: DaSpec, Vm, RT: Runtime<C, Da>, K: Kerne
     /// The runtime includes all the modules that the rollup supports.
     pub(crate) runtime: RT,
     pub(crate) kernel: K,
+    /// Whether the transactions of the soft confirmation batches are executed in parallel.
+    #[cfg(feature = "native")]
+    parallel_execution: bool,
     phantom_context: PhantomData<C>,
     phantom_vm: PhantomData<Vm>,
     phantom_da: PhantomData<Da>,
@@ -94,17 +102,32 @@ where
         Self {
             runtime: RT::default(),
             kernel: K::default(),
+            #[cfg(feature = "native")]
+            parallel_execution: false,
             phantom_context: PhantomData,
             phantom_vm: PhantomData,
             phantom_da: PhantomData,
         }
     }
 
+    /// Executes the transactions of the soft confirmation batches optimistically in parallel
+    /// if `enabled`, and re-executes the ones conflicting with the previous transactions of
+    /// their batch. The results are the same as the ones of the serial execution, which is
+    /// still used for the batches read from the DA layer, and proven.
+    #[cfg(feature = "native")]
+    pub fn with_parallel_execution(mut self, enabled: bool) -> Self {
+        self.parallel_execution = enabled;
+        self
+    }
+
+    /// Applies the batch of the blob, or the `assembled_batch` completed by the blob
+    /// if it carried the last part of a chunked batch.
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     pub(crate) fn apply_blob(
         &self,
         checkpoint: StateCheckpoint<C>,
         blob: &mut Da::BlobTransaction,
+        assembled_batch: Option<&[u8]>,
     ) -> (ApplyBatch<Da>, StateCheckpoint<C>) {
         debug!(
             "Applying batch from sequencer: 0x{}",
@@ -114,7 +137,10 @@ where
         let mut batch_workspace = checkpoint.to_revertable();
 
         // ApplyBlobHook: begin
-        if let Err(e) = self.runtime.begin_blob_hook(blob, &mut batch_workspace) {
+        if let Err(e) = track_cycles(
+            || "hook::begin_blob".to_string(),
+            || self.runtime.begin_blob_hook(blob, &mut batch_workspace),
+        ) {
             error!(
                 "Error: The batch was rejected by the 'begin_blob_hook' hook. Skipping batch without slashing the sequencer: {}",
                 e
@@ -132,7 +158,7 @@ where
         // TODO: don't ignore these events: https://github.com/Sovereign-Labs/sovereign/issues/350
         let _ = batch_workspace.take_events();
 
-        let (txs, messages) = match self.pre_process_batch(blob) {
+        let (txs, messages) = match self.pre_process_batch(blob, assembled_batch) {
             Ok((txs, messages)) => (txs, messages),
             Err(reason) => {
                 // Explicitly revert on slashing, even though nothing has changed in pre_process.
@@ -142,10 +168,13 @@ where
                     reason,
                     sequencer_da_address: sequencer_da_address.clone(),
                 };
-                let checkpoint = match self
-                    .runtime
-                    .end_blob_hook(sequencer_outcome, &mut batch_workspace)
-                {
+                let checkpoint = match track_cycles(
+                    || "hook::end_blob".to_string(),
+                    || {
+                        self.runtime
+                            .end_blob_hook(sequencer_outcome, &mut batch_workspace)
+                    },
+                ) {
                     Ok(()) => {
                         // TODO: will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
                         batch_workspace.checkpoint()
@@ -174,8 +203,9 @@ where
             "Error in preprocessing batch, there should be same number of txs and messages"
         );
 
-        // TODO fetch gas price from chain state
-        let gas_elastic_price = [0, 0];
+        // The gas price and the height are the same for all the transactions of a batch
+        let gas_price = self.kernel.gas_price(&mut batch_workspace);
+        let height = self.kernel.visible_height(&mut batch_workspace);
         let mut sequencer_reward = 0u64;
 
         let mut tx_receipts = Vec::with_capacity(txs.len());
@@ -183,19 +213,24 @@ where
         let mut batch_workspace = self.apply_txs(
             txs,
             messages,
-            &gas_elastic_price,
+            &gas_price,
+            height,
             &mut tx_receipts,
             batch_workspace,
             &mut sequencer_reward,
         );
 
-        // TODO: calculate the amount based of gas and fees
+        // The reward is the gas used by the transactions and their tips. The runtime hooks
+        // hold the fees in escrow and distribute them in `end_blob_hook`
         let sequencer_outcome = SequencerOutcome::Rewarded(sequencer_reward);
 
-        if let Err(e) = self
-            .runtime
-            .end_blob_hook(sequencer_outcome.clone(), &mut batch_workspace)
-        {
+        if let Err(e) = track_cycles(
+            || "hook::end_blob".to_string(),
+            || {
+                self.runtime
+                    .end_blob_hook(sequencer_outcome.clone(), &mut batch_workspace)
+            },
+        ) {
             // TODO: will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
             error!("Failed on `end_blob_hook`: {}", e);
         };
@@ -210,11 +245,128 @@ where
         )
     }
 
+    /// Applies a soft confirmation batch. Unlike [`Self::apply_blob`], the blob hooks are not run,
+    /// because the batch has not been posted on DA yet, so nothing is rewarded or slashed.
+    #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
+    pub(crate) fn apply_soft_confirmation(
+        &self,
+        checkpoint: StateCheckpoint<C>,
+        soft_batch: &SignedSoftConfirmationBatch,
+    ) -> (
+        BatchReceipt<SequencerOutcome<<Da::BlobTransaction as BlobReaderTrait>::Address>, TxEffect>,
+        StateCheckpoint<C>,
+    ) {
+        debug!(
+            "Applying soft confirmation batch 0x{}",
+            hex::encode(soft_batch.hash)
+        );
+
+        let ignored = BatchReceipt {
+            batch_hash: soft_batch.hash,
+            tx_receipts: Vec::new(),
+            inner: SequencerOutcome::Ignored,
+        };
+
+        if let Err(e) = verify_soft_batch_signature::<C>(soft_batch) {
+            error!(
+                "Soft confirmation batch 0x{} has an invalid signature: {}",
+                hex::encode(soft_batch.hash),
+                e
+            );
+            return (ignored, checkpoint);
+        }
+
+        let batch = Batch {
+            txs: soft_batch
+                .txs
+                .iter()
+                .map(|data| RawTx { data: data.clone() })
+                .collect(),
+        };
+
+        let (txs, messages) = match self
+            .verify_txs_stateless(batch)
+            .and_then(|txs| Ok((self.decode_txs(&txs)?, txs)))
+        {
+            Ok((messages, txs)) => (txs, messages),
+            Err(reason) => {
+                error!(
+                    "Soft confirmation batch 0x{} was rejected: {:?}",
+                    hex::encode(soft_batch.hash),
+                    reason
+                );
+                return (ignored, checkpoint);
+            }
+        };
+
+        let mut batch_workspace = checkpoint.to_revertable();
+        let gas_price = self.kernel.gas_price(&mut batch_workspace);
+        let height = self.kernel.visible_height(&mut batch_workspace);
+        let mut sequencer_reward = 0u64;
+
+        let mut tx_receipts = Vec::with_capacity(txs.len());
+
+        let batch_workspace = self.apply_soft_confirmation_txs(
+            txs,
+            messages,
+            &gas_price,
+            height,
+            &mut tx_receipts,
+            batch_workspace,
+            &mut sequencer_reward,
+        );
+
+        (
+            BatchReceipt {
+                batch_hash: soft_batch.hash,
+                tx_receipts,
+                inner: SequencerOutcome::Rewarded(sequencer_reward),
+            },
+            batch_workspace.checkpoint(),
+        )
+    }
+
+    /// Applies the transactions of a soft confirmation batch, in parallel if it's enabled
+    /// with [`Self::with_parallel_execution`].
+    fn apply_soft_confirmation_txs(
+        &self,
+        txs: Vec<TransactionAndRawHash<C>>,
+        messages: Vec<<RT as DispatchCall>::Decodable>,
+        gas_price: &C::GasUnit,
+        height: u64,
+        tx_receipts: &mut Vec<TransactionReceipt<TxEffect>>,
+        batch_workspace: WorkingSet<C>,
+        sequencer_reward: &mut u64,
+    ) -> WorkingSet<C> {
+        #[cfg(feature = "native")]
+        if self.parallel_execution {
+            return self.apply_txs_in_parallel(
+                txs,
+                messages,
+                gas_price,
+                height,
+                tx_receipts,
+                batch_workspace,
+                sequencer_reward,
+            );
+        }
+        self.apply_txs(
+            txs,
+            messages,
+            gas_price,
+            height,
+            tx_receipts,
+            batch_workspace,
+            sequencer_reward,
+        )
+    }
+
     // Do all stateless checks and data formatting, that can be results in sequencer slashing
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
     fn pre_process_batch(
         &self,
         blob_data: &mut impl BlobReaderTrait,
+        assembled_batch: Option<&[u8]>,
     ) -> Result<
         (
             Vec<TransactionAndRawHash<C>>,
@@ -222,7 +374,7 @@ where
         ),
         SlashingReason,
     > {
-        let batch = self.deserialize_batch(blob_data)?;
+        let batch = self.deserialize_batch(blob_data, assembled_batch)?;
         debug!("Deserialized batch with {} txs", batch.txs.len());
 
         // Run the stateless verification, since it is stateless we don't commit.
@@ -234,108 +386,149 @@ where
     }
 
     #[cfg_attr(all(target_os = "zkvm", feature = "bench"), cycle_tracker)]
-    fn apply_txs(
+    pub(crate) fn apply_txs(
         &self,
         txs: Vec<TransactionAndRawHash<C>>,
         messages: Vec<<RT as DispatchCall>::Decodable>,
-        gas_elastic_price: &[u64],
+        gas_price: &C::GasUnit,
+        height: u64,
         tx_receipts: &mut Vec<TransactionReceipt<TxEffect>>,
         mut batch_workspace: WorkingSet<C>,
         sequencer_reward: &mut u64,
     ) -> WorkingSet<C> {
         // Dispatching transactions
-        for (TransactionAndRawHash { tx, raw_tx_hash }, msg) in
-            txs.into_iter().zip(messages.into_iter())
-        {
-            // Update the working set gas meter with the available funds
-            let gas_price = C::GasUnit::from_arbitrary_dimensions(gas_elastic_price);
-            let gas_limit = tx.gas_limit();
-            let gas_tip = tx.gas_tip();
-            batch_workspace.set_gas(gas_limit, gas_price);
-
-            // Pre dispatch hook
-            // TODO set the sequencer pubkey
-            let hook = RuntimeTxHook {
-                height: 1,
-                sequencer: tx.pub_key().clone(),
-            };
-            let ctx = match self
-                .runtime
-                .pre_dispatch_tx_hook(&tx, &mut batch_workspace, &hook)
-            {
-                Ok(verified_tx) => verified_tx,
-                Err(e) => {
-                    // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
-                    // For example nonce for the relevant account is incremented.
-                    error!("Stateful verification error - the sequencer included an invalid transaction: {}", e);
-                    let gas_used = batch_workspace.gas_used().to_dimensions();
-                    let receipt = TransactionReceipt {
-                        tx_hash: raw_tx_hash,
-                        body_to_save: None,
-                        events: batch_workspace.take_events(),
-                        receipt: TxEffect::Reverted,
-                        gas_used,
-                    };
-
-                    tx_receipts.push(receipt);
-                    continue;
-                }
-            };
+        for (tx, msg) in txs.iter().zip(messages.into_iter()) {
+            let (working_set, receipt, gas_reward) =
+                Self::apply_tx(&self.runtime, tx, msg, gas_price, height, batch_workspace);
+            batch_workspace = working_set;
+            *sequencer_reward = sequencer_reward.saturating_add(gas_reward);
+            tx_receipts.push(receipt);
+        }
 
-            // Commit changes after pre_dispatch_tx_hook
-            batch_workspace = batch_workspace.checkpoint().to_revertable();
+        batch_workspace
+    }
 
-            let tx_result = self.runtime.dispatch_call(msg, &mut batch_workspace, &ctx);
+    /// Applies a single transaction on top of `batch_workspace`, returning its receipt
+    /// and the reward of the sequencer for it.
+    pub(crate) fn apply_tx(
+        runtime: &RT,
+        TransactionAndRawHash { tx, raw_tx_hash }: &TransactionAndRawHash<C>,
+        msg: <RT as DispatchCall>::Decodable,
+        gas_price: &C::GasUnit,
+        height: u64,
+        mut batch_workspace: WorkingSet<C>,
+    ) -> (WorkingSet<C>, TransactionReceipt<TxEffect>, u64) {
+        let raw_tx_hash = *raw_tx_hash;
+        // Update the working set gas meter with the available funds
+        let gas_limit = tx.gas_limit();
+        let gas_tip = tx.gas_tip();
+        batch_workspace.set_gas(gas_limit, gas_price.clone());
+
+        // Pre dispatch hook
+        // TODO set the sequencer pubkey
+        let hook = RuntimeTxHook {
+            height,
+            sequencer: tx.pub_key().clone(),
+        };
+        let ctx = match track_cycles(
+            || "hook::pre_dispatch_tx".to_string(),
+            || runtime.pre_dispatch_tx_hook(tx, &mut batch_workspace, &hook),
+        ) {
+            Ok(verified_tx) => verified_tx,
+            Err(e) => {
+                // Don't revert any state changes made by the pre_dispatch_hook even if the Tx is rejected.
+                // For example nonce for the relevant account is incremented.
+                error!("Stateful verification error - the sequencer included an invalid transaction: {}", e);
+                let gas_used = batch_workspace.gas_used().to_dimensions();
+                let receipt = TransactionReceipt {
+                    tx_hash: raw_tx_hash,
+                    body_to_save: None,
+                    sender: None,
+                    events: batch_workspace.take_events(),
+                    receipt: TxEffect::from_error(&e.into()),
+                    gas_used,
+                };
 
-            let remaining_gas = batch_workspace.gas_remaining_funds();
-            let gas_reward = gas_limit
-                .saturating_add(gas_tip)
-                .saturating_sub(remaining_gas);
+                return (batch_workspace, receipt, 0);
+            }
+        };
 
-            *sequencer_reward = sequencer_reward.saturating_add(gas_reward);
-            debug!(
-                "Tx {} sequencer reward: {}",
-                hex::encode(raw_tx_hash),
-                gas_reward
-            );
+        // Commit changes after pre_dispatch_tx_hook
+        batch_workspace = batch_workspace.checkpoint().to_revertable();
 
-            let events = batch_workspace.take_events();
-            let tx_effect = match tx_result {
-                Ok(_) => TxEffect::Successful,
-                Err(e) => {
-                    error!(
-                        "Tx 0x{} was reverted error: {}",
-                        hex::encode(raw_tx_hash),
-                        e
-                    );
-                    // The transaction causing invalid state transition is reverted
-                    // but we don't slash and we continue processing remaining transactions.
-                    batch_workspace = batch_workspace.revert().to_revertable();
-                    TxEffect::Reverted
-                }
-            };
-            debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
-
-            let gas_used = batch_workspace.gas_used().to_dimensions();
-            let receipt = TransactionReceipt {
-                tx_hash: raw_tx_hash,
-                body_to_save: None,
-                events,
-                receipt: tx_effect,
-                gas_used,
-            };
+        // The call runs in its own checkpoint, so that only its changes are reverted if it fails
+        let checkpoint = batch_workspace.start_checkpoint();
+        let module_name = runtime.module_name(&msg);
+        let tx_result = track_cycles(
+            || format!("call::{}", module_name),
+            || {
+                runtime.dispatch_call_and_deferred_checked(
+                    msg,
+                    &mut batch_workspace,
+                    &ctx,
+                    &|message, working_set| runtime.check_dispatch(message, working_set),
+                )
+            },
+        );
 
-            tx_receipts.push(receipt);
-            // We commit after events have been extracted into receipt.
-            batch_workspace = batch_workspace.checkpoint().to_revertable();
+        let remaining_gas = batch_workspace.gas_remaining_funds();
+        let gas_reward = gas_limit
+            .saturating_add(gas_tip)
+            .saturating_sub(remaining_gas);
 
-            // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
-            self.runtime
-                .post_dispatch_tx_hook(&tx, &ctx, &mut batch_workspace)
-                .expect("inconsistent state: error in post_dispatch_tx_hook");
-        }
+        debug!(
+            "Tx {} sequencer reward: {}",
+            hex::encode(raw_tx_hash),
+            gas_reward
+        );
 
-        batch_workspace
+        let mut events = batch_workspace.take_events();
+        let tx_effect = match tx_result {
+            Ok(_) => {
+                batch_workspace.commit_checkpoint(checkpoint);
+                TxEffect::Successful
+            }
+            Err(e) => {
+                error!(
+                    "Tx 0x{} was reverted error: {}",
+                    hex::encode(raw_tx_hash),
+                    e
+                );
+                // The transaction causing invalid state transition is reverted
+                // but we don't slash and we continue processing remaining transactions.
+                batch_workspace.revert_checkpoint(checkpoint);
+                track_cycles(
+                    || "hook::tx_reverted".to_string(),
+                    || runtime.tx_reverted_hook(tx, &ctx, &e, &mut batch_workspace),
+                )
+                .expect("inconsistent state: error in tx_reverted_hook");
+                events.extend(batch_workspace.take_events());
+                TxEffect::from_error(&e)
+            }
+        };
+        debug!("Tx {} effect: {:?}", hex::encode(raw_tx_hash), tx_effect);
+
+        let gas_used = batch_workspace.gas_used().to_dimensions();
+        let receipt = TransactionReceipt {
+            tx_hash: raw_tx_hash,
+            body_to_save: None,
+            sender: Some(ctx.sender().as_ref().to_vec()),
+            events,
+            receipt: tx_effect,
+            gas_used,
+        };
+
+        // We commit after events have been extracted into receipt.
+        batch_workspace = batch_workspace.checkpoint().to_revertable();
+
+        // TODO: `panic` will be covered in https://github.com/Sovereign-Labs/sovereign-sdk/issues/421
+        track_cycles(
+            || "hook::post_dispatch_tx".to_string(),
+            || runtime.post_dispatch_tx_hook(tx, &ctx, &mut batch_workspace),
+        )
+        .expect("inconsistent state: error in post_dispatch_tx_hook");
+
+        (batch_workspace, receipt, gas_reward)
     }
 
     // Attempt to deserialize batch, error results in sequencer slashing.
@@ -343,8 +536,17 @@ where
     fn deserialize_batch(
         &self,
         blob_data: &mut impl BlobReaderTrait,
+        assembled_batch: Option<&[u8]>,
     ) -> Result<Batch, SlashingReason> {
-        match Batch::try_from_slice(data_for_deserialization(blob_data)) {
+        let data = match assembled_batch {
+            Some(batch) => batch,
+            None => data_for_deserialization(blob_data),
+        };
+        // Batches might be compressed before being posted on DA
+        let batch = decompress_blob(data)
+            .map_err(anyhow::Error::from)
+            .and_then(|data| Ok(Batch::try_from_slice(&data)?));
+        match batch {
             Ok(batch) => Ok(batch),
             Err(e) => {
                 assert_eq!(blob_data.verified_data().len(), blob_data.total_len(), "Batch deserialization failed and some data was not provided. The prover might be malicious");
@@ -396,12 +598,30 @@ where
     }
 }
 
+/// Checks that the soft confirmation batch hash matches its contents and that it is signed by the embedded public key.
+pub(crate) fn verify_soft_batch_signature<C: Context>(
+    soft_batch: &SignedSoftConfirmationBatch,
+) -> anyhow::Result<()> {
+    let message = borsh::to_vec(&soft_batch.unsigned())?;
+    let hash: [u8; 32] = <C as Spec>::Hasher::digest(&message).into();
+    anyhow::ensure!(
+        hash == soft_batch.hash,
+        "Batch hash 0x{} does not match its contents",
+        hex::encode(soft_batch.hash)
+    );
+
+    let pub_key = C::PublicKey::try_from_slice(&soft_batch.pub_key)?;
+    let signature = C::Signature::try_from_slice(&soft_batch.signature)?;
+    signature.verify(&pub_key, &message)?;
+    Ok(())
+}
+
 #[cfg(feature = "native")]
-fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
+pub(crate) fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
     blob.full_data()
 }
 
 #[cfg(not(feature = "native"))]
-fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
+pub(crate) fn data_for_deserialization(blob: &mut impl BlobReaderTrait) -> &[u8] {
     blob.verified_data()
 }
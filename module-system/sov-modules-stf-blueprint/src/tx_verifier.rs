@@ -36,16 +36,112 @@ impl RawTx {
     }
 }
 
+/// Deserializes the transactions of a batch and verifies their signatures, in the order of the batch.
 pub(crate) fn verify_txs_stateless<C: Context>(
     raw_txs: Vec<RawTx>,
 ) -> anyhow::Result<Vec<TransactionAndRawHash<C>>> {
-    let mut txs = Vec::with_capacity(raw_txs.len());
     debug!("Verifying {} transactions", raw_txs.len());
-    for raw_tx in raw_txs {
-        let raw_tx_hash = raw_tx.hash::<C>();
-        let tx = raw_tx.deserialize()?;
-        tx.verify()?;
-        txs.push(TransactionAndRawHash { tx, raw_tx_hash });
+    #[cfg(feature = "native")]
+    {
+        verify_txs_parallel(raw_txs)
+    }
+    #[cfg(not(feature = "native"))]
+    {
+        verify_txs_serial(raw_txs)
+    }
+}
+
+fn verify_tx<C: Context>(raw_tx: RawTx) -> anyhow::Result<TransactionAndRawHash<C>> {
+    let raw_tx_hash = raw_tx.hash::<C>();
+    let tx = raw_tx.deserialize()?;
+    tx.verify()?;
+    Ok(TransactionAndRawHash { tx, raw_tx_hash })
+}
+
+// The zkVM is single threaded, so the signatures are verified one by one there.
+#[cfg(any(not(feature = "native"), test))]
+fn verify_txs_serial<C: Context>(
+    raw_txs: Vec<RawTx>,
+) -> anyhow::Result<Vec<TransactionAndRawHash<C>>> {
+    raw_txs.into_iter().map(verify_tx).collect()
+}
+
+// Signature checks dominate the verification, and they are independent from each other.
+// The order of the transactions is preserved, and so is the error of the first invalid one:
+// rayon would report any of the errors if the results were collected in parallel.
+#[cfg(feature = "native")]
+fn verify_txs_parallel<C: Context>(
+    raw_txs: Vec<RawTx>,
+) -> anyhow::Result<Vec<TransactionAndRawHash<C>>> {
+    use rayon::prelude::*;
+
+    let verified: Vec<_> = raw_txs.into_par_iter().map(verify_tx).collect();
+    verified.into_iter().collect()
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use sov_modules_api::default_context::DefaultContext;
+    use sov_modules_api::default_signature::private_key::DefaultPrivateKey;
+    use sov_modules_api::PrivateKey;
+
+    use super::*;
+
+    fn signed_tx(private_key: &DefaultPrivateKey, nonce: u64) -> RawTx {
+        let data = Transaction::<DefaultContext>::new_signed_tx(
+            private_key,
+            vec![nonce as u8; 32],
+            0,
+            0,
+            0,
+            nonce,
+        )
+        .try_to_vec()
+        .unwrap();
+        RawTx { data }
+    }
+
+    fn raw_tx_hashes(
+        verified: anyhow::Result<Vec<TransactionAndRawHash<DefaultContext>>>,
+    ) -> Result<Vec<RawTxHash>, String> {
+        verified
+            .map(|txs| txs.iter().map(|tx| tx.raw_tx_hash).collect())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn parallel_and_serial_verification_agree() {
+        let private_key = DefaultPrivateKey::generate();
+        let valid_txs: Vec<RawTx> = (0..64)
+            .map(|nonce| signed_tx(&private_key, nonce))
+            .collect();
+
+        let parallel = raw_tx_hashes(verify_txs_parallel(valid_txs.clone()));
+        assert_eq!(
+            parallel,
+            raw_tx_hashes(verify_txs_serial(valid_txs.clone()))
+        );
+        assert_eq!(
+            parallel.unwrap(),
+            valid_txs
+                .iter()
+                .map(|tx| tx.hash::<DefaultContext>())
+                .collect::<Vec<_>>()
+        );
+
+        // A truncated transaction can't be deserialized, and a changed nonce breaks the signature
+        let mut invalid_txs = valid_txs;
+        invalid_txs[10].data.pop();
+        *invalid_txs[40].data.last_mut().unwrap() ^= 1;
+        let first_error = raw_tx_hashes(verify_tx(invalid_txs[10].clone()).map(|tx| vec![tx]));
+        assert_ne!(
+            first_error,
+            raw_tx_hashes(verify_tx(invalid_txs[40].clone()).map(|tx| vec![tx]))
+        );
+
+        let parallel = raw_tx_hashes(verify_txs_parallel(invalid_txs.clone()));
+        assert_eq!(parallel, raw_tx_hashes(verify_txs_serial(invalid_txs)));
+        assert!(parallel.is_err());
+        assert_eq!(parallel, first_error);
     }
-    Ok(txs)
 }
@@ -44,6 +44,9 @@ impl<C: Context, Da: DaSpec> Kernel<C, Da> for SoftConfirmationsKernel<C, Da> {
     fn visible_height(&self, working_set: &mut WorkingSet<C>) -> u64 {
         self.chain_state.visible_slot_height(working_set)
     }
+    fn gas_price(&self, working_set: &mut WorkingSet<C>) -> C::GasUnit {
+        self.chain_state.get_gas_price(working_set)
+    }
 
     type GenesisConfig = SoftConfirmationsKernelGenesisConfig<C, Da>;
 
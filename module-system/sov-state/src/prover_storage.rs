@@ -1,14 +1,14 @@
 use std::marker::PhantomData;
 use std::sync::Arc;
 
-use jmt::storage::{NodeBatch, TreeWriter};
+use jmt::storage::{NodeBatch, StaleNodeIndexBatch, TreeWriter};
 use jmt::{JellyfishMerkleTree, KeyHash, Version};
 use sov_db::native_db::NativeDB;
 use sov_db::schema::{QueryManager, ReadOnlyDbSnapshot};
 use sov_db::state_db::StateDB;
 use sov_modules_core::{
-    CacheKey, NativeStorage, OrderedReadsAndWrites, Storage, StorageKey, StorageProof,
-    StorageValue, Witness,
+    ArchivalVersionError, CacheKey, NativeStorage, OrderedReadsAndWrites, Storage, StorageKey,
+    StorageProof, StorageValue, Witness,
 };
 
 use crate::config::Config;
@@ -69,6 +69,7 @@ impl<S: MerkleProofSpec, Q: QueryManager> ProverStorage<S, Q> {
 
 pub struct ProverStateUpdate {
     pub(crate) node_batch: NodeBatch,
+    pub(crate) stale_node_indices: StaleNodeIndexBatch,
     pub key_preimages: Vec<(KeyHash, CacheKey)>,
 }
 
@@ -99,6 +100,36 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
             .map(Into::into)
     }
 
+    fn pin_latest_version(&self) -> Self {
+        Self {
+            db: self
+                .db
+                .pin_latest_version()
+                // It is ok to panic here, we assume the db is available and consistent.
+                .expect("Unable to read the latest version from db"),
+            native_db: self.native_db.clone(),
+            _phantom_hasher: Default::default(),
+        }
+    }
+
+    fn check_archival_version(&self, version: Version) -> Result<(), ArchivalVersionError> {
+        let latest_version = self.latest_version();
+        if version > latest_version {
+            return Err(ArchivalVersionError::NotCommitted {
+                version,
+                latest_version,
+            });
+        }
+        let oldest_retained_version = self.oldest_retained_version();
+        if version < oldest_retained_version {
+            return Err(ArchivalVersionError::Pruned {
+                version,
+                oldest_retained_version,
+            });
+        }
+        Ok(())
+    }
+
     fn compute_state_update(
         &self,
         state_accesses: OrderedReadsAndWrites,
@@ -162,6 +193,7 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
 
         let state_update = ProverStateUpdate {
             node_batch: tree_update.node_batch,
+            stale_node_indices: tree_update.stale_node_index_batch,
             key_preimages,
         };
 
@@ -189,6 +221,10 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
             )
             .expect("native db write must succeed");
 
+        self.db
+            .put_stale_node_indices(&state_update.stale_node_indices)
+            .expect("Stale node indices put must succeed");
+
         // Write the state values last, since we base our view of what has been touched
         // on state. If the node crashes between the `native_db` update and this update,
         // then the whole `commit` will be re-run later so no data can be lost.
@@ -219,18 +255,23 @@ impl<S: MerkleProofSpec, Q: QueryManager> Storage for ProverStorage<S, Q> {
 
 impl<S: MerkleProofSpec, Q: QueryManager> NativeStorage for ProverStorage<S, Q> {
     fn get_with_proof(&self, key: StorageKey) -> StorageProof<Self::Proof> {
+        self.get_with_proof_at(key, self.latest_version()).unwrap()
+    }
+
+    fn get_with_proof_at(
+        &self,
+        key: StorageKey,
+        version: Version,
+    ) -> anyhow::Result<StorageProof<Self::Proof>> {
+        self.check_archival_version(version)?;
         let merkle = JellyfishMerkleTree::<StateDB<Q>, S::Hasher>::new(&self.db);
-        let (val_opt, proof) = merkle
-            .get_with_proof(
-                KeyHash::with::<S::Hasher>(key.as_ref()),
-                self.db.get_next_version() - 1,
-            )
-            .unwrap();
-        StorageProof {
+        let (val_opt, proof) =
+            merkle.get_with_proof(KeyHash::with::<S::Hasher>(key.as_ref()), version)?;
+        Ok(StorageProof {
             key,
             value: val_opt.map(StorageValue::from),
             proof,
-        }
+        })
     }
 
     fn get_root_hash(&self, version: Version) -> anyhow::Result<jmt::RootHash> {
@@ -238,4 +279,32 @@ impl<S: MerkleProofSpec, Q: QueryManager> NativeStorage for ProverStorage<S, Q>
             JellyfishMerkleTree::new(&self.db);
         temp_merkle.get_root_hash(version)
     }
+
+    fn latest_version(&self) -> Version {
+        self.db.get_next_version() - 1
+    }
+
+    fn oldest_retained_version(&self) -> Version {
+        self.db
+            .get_oldest_retained_version()
+            .expect("Oldest retained version must be readable")
+    }
+
+    fn get_by_prefix(
+        &self,
+        prefix: &[u8],
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        limit: usize,
+        version: Option<Version>,
+    ) -> anyhow::Result<Vec<(StorageKey, StorageValue)>> {
+        let version = version.unwrap_or_else(|| self.latest_version());
+        let values = self
+            .db
+            .get_values_by_prefix(version, prefix, start, end, limit)?;
+        Ok(values
+            .into_iter()
+            .map(|(key, value)| (StorageKey::from(key), StorageValue::from(value)))
+            .collect())
+    }
 }
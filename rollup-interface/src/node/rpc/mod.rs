@@ -4,10 +4,9 @@
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::string::String;
 use crate::maybestd::vec::Vec;
-#[cfg(feature = "native")]
-use crate::stf::Event;
-use crate::stf::EventKey;
+use crate::stf::{Event, EventKey};
 
 /// A struct containing enough information to uniquely specify single batch.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -100,6 +99,84 @@ pub enum EventGroupIdentifier {
     Key(Vec<u8>),
 }
 
+/// A filter selecting the events emitted in a range of slots.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventFilter {
+    /// The number of the first slot to search for events.
+    pub from_slot: u64,
+    /// The number of the last slot to search for events (inclusive).
+    pub to_slot: u64,
+    /// If set, only the events emitted by the module with this name are returned.
+    #[serde(default)]
+    pub module: Option<String>,
+    /// If set, only the events with this key are returned.
+    #[serde(default)]
+    pub key: Option<String>,
+}
+
+/// The response to a JSON-RPC request for filtered events: an event together with
+/// the slot, batch and transaction which emitted it.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EventResponse {
+    /// The number of the slot which emitted the event.
+    pub slot_number: u64,
+    /// The number of the batch which emitted the event.
+    pub batch_number: u64,
+    /// The number of the transaction which emitted the event.
+    pub tx_number: u64,
+    /// The number of the event.
+    pub event_number: u64,
+    /// The event itself.
+    #[serde(flatten)]
+    pub event: Event,
+}
+
+/// The order in which the items of a [`PageRequest`] are returned.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Order {
+    /// From the lowest number to the highest.
+    #[default]
+    Ascending,
+    /// From the highest number to the lowest.
+    Descending,
+}
+
+/// A request for a page of slots, batches or transactions, selected by their numbers.
+///
+/// To sync incrementally, the `next_cursor` of the returned [`Page`] is passed as `from`
+/// of the next request, until it is `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// The number of the first item to return. Defaults to the first item in the ledger,
+    /// or to the last one if the `order` is [`Order::Descending`].
+    #[serde(default)]
+    pub from: Option<u64>,
+    /// The number of the last item to return (inclusive). Defaults to the end of the ledger
+    /// in the requested `order`.
+    #[serde(default)]
+    pub to: Option<u64>,
+    /// The maximum number of items to return. The node may cap it to a lower value.
+    #[serde(default)]
+    pub limit: Option<u64>,
+    /// The order in which the items are returned.
+    #[serde(default)]
+    pub order: Order,
+    /// How much information to return for each item.
+    #[serde(default)]
+    pub query_mode: QueryMode,
+}
+
+/// The response to a [`PageRequest`].
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// The requested items, in the requested order.
+    pub items: Vec<T>,
+    /// The number of the item the next page starts from, or `None` if there are no more items
+    /// in the requested range.
+    pub next_cursor: Option<u64>,
+}
+
 /// An identifier that specifies a single slot.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -226,6 +303,13 @@ pub trait LedgerRpcProvider {
         event_ids: &[EventIdentifier],
     ) -> Result<Vec<Option<Event>>, anyhow::Error>;
 
+    /// Get the events emitted in a range of slots, optionally filtered by module and key.
+    /// The events are returned in the order they were emitted.
+    fn get_filtered_events(
+        &self,
+        filter: &EventFilter,
+    ) -> Result<Vec<EventResponse>, anyhow::Error>;
+
     /// Get a single slot by hash.
     fn get_slot_by_hash<B: DeserializeOwned, T: DeserializeOwned>(
         &self,
@@ -301,6 +385,32 @@ pub trait LedgerRpcProvider {
         query_mode: QueryMode,
     ) -> Result<Vec<Option<TxResponse<T>>>, anyhow::Error>;
 
+    /// Get a page of slots, selected by their numbers.
+    fn get_slots_page<B: DeserializeOwned, T: DeserializeOwned>(
+        &self,
+        request: &PageRequest,
+    ) -> Result<Page<SlotResponse<B, T>>, anyhow::Error>;
+
+    /// Get a page of batches, selected by their numbers.
+    fn get_batches_page<B: DeserializeOwned, T: DeserializeOwned>(
+        &self,
+        request: &PageRequest,
+    ) -> Result<Page<BatchResponse<B, T>>, anyhow::Error>;
+
+    /// Get a page of transactions, selected by their numbers.
+    fn get_transactions_page<T: DeserializeOwned>(
+        &self,
+        request: &PageRequest,
+    ) -> Result<Page<TxResponse<T>>, anyhow::Error>;
+
+    /// Get a page of the transactions sent by `address`, selected by their numbers.
+    /// Fails if the ledger doesn't index transactions by sender.
+    fn get_transactions_by_address<T: DeserializeOwned>(
+        &self,
+        address: &[u8],
+        request: &PageRequest,
+    ) -> Result<Page<TxResponse<T>>, anyhow::Error>;
+
     /// Get a notification each time a slot is processed
     fn subscribe_slots(&self) -> Result<tokio::sync::broadcast::Receiver<u64>, anyhow::Error>;
 }
@@ -1,12 +1,14 @@
 //! The da module defines traits used by the full node to interact with the DA layer.
 
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::da::BlockHeaderTrait;
 #[cfg(feature = "native")]
 use crate::da::{DaSpec, DaVerifier};
 #[cfg(feature = "native")]
+use crate::maybestd::collections::VecDeque;
+#[cfg(feature = "native")]
 use crate::maybestd::vec::Vec;
 use crate::zk::ValidityCondition;
 
@@ -36,7 +38,7 @@ pub trait DaService: Send + Sync + 'static {
     >;
 
     /// A transaction ID, used to identify the transaction in the DA layer.
-    type TransactionId: PartialEq + Eq + PartialOrd + Ord + core::hash::Hash;
+    type TransactionId: PartialEq + Eq + PartialOrd + Ord + core::hash::Hash + Send + Sync;
 
     /// The error type for fallible methods.
     type Error: core::fmt::Debug + Send + Sync + core::fmt::Display;
@@ -53,6 +55,27 @@ pub trait DaService: Send + Sync + 'static {
         &self,
     ) -> Result<<Self::Spec as DaSpec>::BlockHeader, Self::Error>;
 
+    /// Fetch the [`DaSpec::BlockHeader`] of the block at the given height, if it's finalized.
+    /// Returns `None` if the block isn't finalized yet, and can still be reverted without
+    /// a consensus violation.
+    ///
+    /// This method is blanket-implemented by comparing the height with the one of
+    /// [`DaService::get_last_finalized_block_header`].
+    async fn get_finalized_at(
+        &self,
+        height: u64,
+    ) -> Result<Option<<Self::Spec as DaSpec>::BlockHeader>, Self::Error> {
+        let last_finalized = self.get_last_finalized_block_header().await?;
+        if height > last_finalized.height() {
+            return Ok(None);
+        }
+        if height == last_finalized.height() {
+            return Ok(Some(last_finalized));
+        }
+        let block = self.get_block_at(height).await?;
+        Ok(Some(block.header().clone()))
+    }
+
     /// Subscribe to finalized headers as they are finalized.
     /// Expect only to receive headers which were finalized after subscription
     /// Optimized version of `get_last_finalized_block_header`.
@@ -110,6 +133,71 @@ pub trait DaService: Send + Sync + 'static {
     /// Returns nothing if the transaction was successfully sent.
     async fn send_transaction(&self, blob: &[u8]) -> Result<Self::TransactionId, Self::Error>;
 
+    /// The maximum size of a blob accepted by [`DaService::send_transaction`], if the DA layer has one.
+    /// Larger batches have to be split with [`split_blob`](crate::chunking::split_blob) first.
+    ///
+    /// This method is blanket-implemented to return `None`, for the DA layers without any limit.
+    fn max_blob_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Describes where the blobs of the rollup are posted on the DA layer, like its namespace on
+    /// Celestia, so that external verifiers can find them in the DA blocks.
+    ///
+    /// This method is blanket-implemented to return `None`, for the DA layers without
+    /// such a location.
+    fn blob_location(&self) -> Option<String> {
+        None
+    }
+
+    /// Estimates the fee of sending a blob of `blob_size` bytes with [`DaService::send_transaction`],
+    /// at the current fee rate of the DA layer.
+    ///
+    /// This method is blanket-implemented to return a zero fee, for the DA layers
+    /// without a fee market.
+    async fn estimate_fee(&self, _blob_size: usize) -> Result<FeeEstimate, Self::Error> {
+        Ok(FeeEstimate::default())
+    }
+
+    /// Sends a transaction like [`DaService::send_transaction`], paying the given fee rate
+    /// instead of the current one. It is used to bump the fee of submissions which are stuck.
+    ///
+    /// This method is blanket-implemented to ignore the fee rate, for the DA layers
+    /// without a fee market.
+    async fn send_transaction_with_fee_rate(
+        &self,
+        blob: &[u8],
+        _fee_rate: f64,
+    ) -> Result<Self::TransactionId, Self::Error> {
+        self.send_transaction(blob).await
+    }
+
+    /// Returns whether the transaction `tx_id`, sent with [`DaService::send_transaction`],
+    /// is included in a block of the DA layer.
+    ///
+    /// This method is blanket-implemented to return `true`, for the DA layers which include
+    /// the transactions by the time they're sent.
+    async fn is_transaction_included(
+        &self,
+        _tx_id: &Self::TransactionId,
+    ) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    /// Bumps the fee of the stuck transaction `tx_id` to `fee_rate`, by replacing it or by paying
+    /// for it with a child transaction, so that its blob still lands at most once.
+    /// Returns the id to track the bumped transaction with.
+    ///
+    /// This method is blanket-implemented to leave the transaction as it is, for the DA layers
+    /// without a fee market.
+    async fn bump_fee(
+        &self,
+        tx_id: Self::TransactionId,
+        _fee_rate: f64,
+    ) -> Result<Self::TransactionId, Self::Error> {
+        Ok(tx_id)
+    }
+
     /// Sends am aggregated ZK proofs to the DA layer.
     async fn send_aggregated_zk_proof(
         &self,
@@ -118,6 +206,126 @@ pub trait DaService: Send + Sync + 'static {
 
     /// Fetches all aggregated ZK proofs at a specified block height.
     async fn get_aggregated_proofs_at(&self, height: u64) -> Result<Vec<Vec<u8>>, Self::Error>;
+
+    /// Walks back the tracked `chain` from its tip, to the last of its blocks which is still
+    /// on the best fork of the DA layer. It's called once a block which doesn't extend the chain
+    /// is received, to find where the DA layer reorganized.
+    ///
+    /// Returns `None` if none of the tracked blocks are on the best fork anymore,
+    /// meaning that the reorganization is deeper than the chain.
+    async fn find_common_ancestor<T: Send + Sync>(
+        &self,
+        chain: &HeaderChain<Self::Spec, T>,
+    ) -> Result<Option<<Self::Spec as DaSpec>::BlockHeader>, Self::Error> {
+        for (header, _) in chain.iter().rev() {
+            let block = self.get_block_at(header.height()).await?;
+            if block.header().hash() == header.hash() {
+                return Ok(Some(header.clone()));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// The most recent blocks of a DA chain, tracked by hash to detect reorganizations
+/// of the DA layer. Every block is kept with the data `T` the node derived from it,
+/// such as the state it produced, so that it can be restored when the following blocks are reverted.
+#[cfg(feature = "native")]
+pub struct HeaderChain<Da: DaSpec, T> {
+    blocks: VecDeque<(Da::BlockHeader, T)>,
+    capacity: usize,
+}
+
+#[cfg(feature = "native")]
+impl<Da: DaSpec, T> HeaderChain<Da, T> {
+    /// Creates an empty chain, which keeps up to `capacity` blocks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            blocks: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Returns the header of the last block of the chain.
+    pub fn tip(&self) -> Option<&Da::BlockHeader> {
+        self.blocks.back().map(|(header, _)| header)
+    }
+
+    /// Returns whether the block with the given header extends the chain, by referencing its tip
+    /// as the previous block. Any block extends an empty chain.
+    pub fn is_extended_by(&self, header: &Da::BlockHeader) -> bool {
+        self.tip()
+            .map_or(true, |tip| tip.hash() == header.prev_hash())
+    }
+
+    /// Appends a block to the chain, forgetting the oldest block once the capacity is reached.
+    /// Fails if the block doesn't extend the chain.
+    pub fn push(&mut self, header: Da::BlockHeader, data: T) -> anyhow::Result<()> {
+        anyhow::ensure!(
+            self.is_extended_by(&header),
+            "Block {} at height {} doesn't extend the chain",
+            header.hash(),
+            header.height()
+        );
+        if self.blocks.len() == self.capacity {
+            self.blocks.pop_front();
+        }
+        self.blocks.push_back((header, data));
+        Ok(())
+    }
+
+    /// Returns the data tracked for the block at the given height, if the chain contains it.
+    pub fn get(&self, height: u64) -> Option<&T> {
+        self.position(height).map(|index| &self.blocks[index].1)
+    }
+
+    /// Returns a mutable reference to the data tracked for the block at the given height,
+    /// if the chain contains it.
+    pub fn get_mut(&mut self, height: u64) -> Option<&mut T> {
+        self.position(height).map(|index| &mut self.blocks[index].1)
+    }
+
+    /// Removes the blocks above the given height, which were reverted by a reorganization.
+    /// Returns the removed blocks, from the lowest one.
+    pub fn revert_after(&mut self, height: u64) -> Vec<(Da::BlockHeader, T)> {
+        let retained = self
+            .blocks
+            .iter()
+            .take_while(|(header, _)| header.height() <= height)
+            .count();
+        self.blocks.split_off(retained).into()
+    }
+
+    /// Iterates over the blocks of the chain, from the oldest one.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &(Da::BlockHeader, T)> {
+        self.blocks.iter()
+    }
+
+    /// Returns the number of blocks in the chain.
+    pub fn len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns whether the chain is empty.
+    pub fn is_empty(&self) -> bool {
+        self.blocks.is_empty()
+    }
+
+    fn position(&self, height: u64) -> Option<usize> {
+        let first = self.blocks.front()?.0.height();
+        let index = usize::try_from(height.checked_sub(first)?).ok()?;
+        (index < self.blocks.len()).then_some(index)
+    }
+}
+
+/// The fee of a DA layer transaction, as estimated by [`DaService::estimate_fee`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// The fee rate of the DA layer, in its smallest currency unit per unit of
+    /// transaction size. For example, sat/vB on Bitcoin or utia/gas on Celestia.
+    pub fee_rate: f64,
+    /// The total fee of the transaction, in the smallest currency unit of the DA layer.
+    pub fee: u64,
 }
 
 /// `SlotData` is the subset of a DA layer block which is stored in the rollup's database.
@@ -0,0 +1,342 @@
+//! Defines the envelope of the batches split across several DA transactions.
+//!
+//! DA layers limit the size of the blob of a single transaction. A larger serialized batch is split
+//! by [`split_blob`] into a [`ChunkManifest`] followed by numbered [`BlobChunk`]s, each posted in its
+//! own transaction behind the [`CHUNKED_BLOB_MAGIC`] prefix. The verifier buffers the envelopes of
+//! a batch in a [`PendingBatch`] until the manifest and all of its chunks were included, and checks
+//! the hashes of the chunks and of the reassembled batch before executing it.
+//!
+//! Like the compression prefix, the chunking prefix can't be confused with the start of a plain batch.
+use std::collections::BTreeMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use sha2::{Digest, Sha256};
+
+use crate::maybestd::vec::Vec;
+
+/// The prefix of the blobs containing a [`ChunkEnvelope`].
+pub const CHUNKED_BLOB_MAGIC: [u8; 4] = [0xff, b'S', b'O', b'C'];
+
+/// The maximum number of chunks a batch can be split into.
+pub const MAX_CHUNKS: usize = 1024;
+
+/// The maximum size of a reassembled batch. Larger batches are rejected, so the chunks buffered
+/// by the verifier can't grow without bound.
+pub const MAX_CHUNKED_BATCH_SIZE: usize = 64 * 1024 * 1024;
+
+/// The size of the envelope around the data of a [`BlobChunk`]: the prefix, the enum tag,
+/// the batch hash, the index and the length of the data.
+pub const CHUNK_OVERHEAD: usize = CHUNKED_BLOB_MAGIC.len() + 1 + 32 + 4 + 4;
+
+/// An error returned when a batch can't be split into chunks, or when its chunks can't be reassembled.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ChunkingError {
+    /// The blob doesn't contain a valid chunk envelope.
+    #[error("invalid chunk envelope: {0}")]
+    InvalidEnvelope(String),
+    /// The maximum blob size is too small to hold the manifest or any data.
+    #[error("a maximum blob size of {0} bytes is too small to split a batch")]
+    BlobSizeTooSmall(usize),
+    /// The batch is larger than [`MAX_CHUNKED_BATCH_SIZE`], or needs more than [`MAX_CHUNKS`] chunks.
+    #[error("chunked batches can't exceed {MAX_CHUNKED_BATCH_SIZE} bytes or {MAX_CHUNKS} chunks")]
+    TooLarge,
+    /// The envelope belongs to another batch.
+    #[error("the envelope belongs to batch 0x{}", hex::encode(.0))]
+    OtherBatch([u8; 32]),
+    /// A second, different manifest was received for the batch.
+    #[error("the batch already has a different manifest")]
+    ConflictingManifest,
+    /// The chunk doesn't match the manifest, or another chunk with the same index.
+    #[error("chunk {0} doesn't match the manifest of the batch")]
+    InvalidChunk(u32),
+    /// The batch can't be reassembled before the manifest and all the chunks are received.
+    #[error("{0} chunk(s) of the batch are missing")]
+    Incomplete(usize),
+    /// The reassembled batch doesn't match the hash or the length in the manifest.
+    #[error("the reassembled batch doesn't match its manifest")]
+    HashMismatch,
+}
+
+/// Describes how a batch was split: the hash and length of the batch, and the hash of every chunk.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ChunkManifest {
+    /// The SHA-256 hash of the serialized batch.
+    pub batch_hash: [u8; 32],
+    /// The length of the serialized batch.
+    pub total_len: u64,
+    /// The SHA-256 hashes of the chunks, in order.
+    pub chunk_hashes: Vec<[u8; 32]>,
+}
+
+/// A part of a batch, at position `index` in the [`ChunkManifest`] of the batch.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct BlobChunk {
+    /// The SHA-256 hash of the serialized batch the chunk belongs to.
+    pub batch_hash: [u8; 32],
+    /// The sequence number of the chunk, starting at 0.
+    pub index: u32,
+    /// The data of the chunk.
+    pub data: Vec<u8>,
+}
+
+/// The content of a blob carrying a part of a chunked batch.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum ChunkEnvelope {
+    /// The manifest of the batch.
+    Manifest(ChunkManifest),
+    /// One of the chunks of the batch.
+    Chunk(BlobChunk),
+}
+
+impl ChunkEnvelope {
+    /// The hash of the batch the envelope belongs to.
+    pub fn batch_hash(&self) -> [u8; 32] {
+        match self {
+            ChunkEnvelope::Manifest(manifest) => manifest.batch_hash,
+            ChunkEnvelope::Chunk(chunk) => chunk.batch_hash,
+        }
+    }
+
+    fn to_blob(&self) -> Vec<u8> {
+        let mut blob = CHUNKED_BLOB_MAGIC.to_vec();
+        self.serialize(&mut blob)
+            .expect("Serialization to a vector never fails");
+        blob
+    }
+}
+
+/// Returns `true` if the blob contains a part of a chunked batch.
+pub fn is_chunked(blob: &[u8]) -> bool {
+    blob.starts_with(&CHUNKED_BLOB_MAGIC)
+}
+
+/// Decodes the [`ChunkEnvelope`] contained in a blob.
+pub fn decode_chunk(blob: &[u8]) -> Result<ChunkEnvelope, ChunkingError> {
+    if !is_chunked(blob) {
+        return Err(ChunkingError::InvalidEnvelope(
+            "missing chunking prefix".into(),
+        ));
+    }
+    ChunkEnvelope::try_from_slice(&blob[CHUNKED_BLOB_MAGIC.len()..])
+        .map_err(|e| ChunkingError::InvalidEnvelope(e.to_string()))
+}
+
+/// Splits a serialized batch into blobs of at most `max_blob_size` bytes: the manifest first,
+/// then the chunks in order. A batch which fits in a single blob is returned as is.
+pub fn split_blob(batch: &[u8], max_blob_size: usize) -> Result<Vec<Vec<u8>>, ChunkingError> {
+    if batch.len() <= max_blob_size {
+        return Ok(vec![batch.to_vec()]);
+    }
+    if batch.len() > MAX_CHUNKED_BATCH_SIZE {
+        return Err(ChunkingError::TooLarge);
+    }
+    if max_blob_size <= CHUNK_OVERHEAD {
+        return Err(ChunkingError::BlobSizeTooSmall(max_blob_size));
+    }
+
+    let batch_hash: [u8; 32] = Sha256::digest(batch).into();
+    let chunks: Vec<&[u8]> = batch.chunks(max_blob_size - CHUNK_OVERHEAD).collect();
+    if chunks.len() > MAX_CHUNKS {
+        return Err(ChunkingError::TooLarge);
+    }
+
+    let manifest = ChunkEnvelope::Manifest(ChunkManifest {
+        batch_hash,
+        total_len: batch.len() as u64,
+        chunk_hashes: chunks
+            .iter()
+            .map(|chunk| Sha256::digest(chunk).into())
+            .collect(),
+    })
+    .to_blob();
+    if manifest.len() > max_blob_size {
+        return Err(ChunkingError::BlobSizeTooSmall(max_blob_size));
+    }
+
+    let mut blobs = Vec::with_capacity(chunks.len() + 1);
+    blobs.push(manifest);
+    blobs.extend(chunks.into_iter().enumerate().map(|(index, data)| {
+        ChunkEnvelope::Chunk(BlobChunk {
+            batch_hash,
+            index: index as u32,
+            data: data.to_vec(),
+        })
+        .to_blob()
+    }));
+    Ok(blobs)
+}
+
+/// The parts of a chunked batch received so far. The manifest and the chunks can be received
+/// in any order, every chunk is checked against the manifest once both are known.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PendingBatch {
+    batch_hash: [u8; 32],
+    manifest: Option<ChunkManifest>,
+    chunks: BTreeMap<u32, Vec<u8>>,
+}
+
+impl PendingBatch {
+    /// Creates an empty pending batch for the batch with the given hash.
+    pub fn new(batch_hash: [u8; 32]) -> Self {
+        Self {
+            batch_hash,
+            manifest: None,
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    /// The hash of the pending batch.
+    pub fn batch_hash(&self) -> [u8; 32] {
+        self.batch_hash
+    }
+
+    /// Adds a part of the batch. Receiving the same part twice isn't an error.
+    pub fn insert(&mut self, envelope: ChunkEnvelope) -> Result<(), ChunkingError> {
+        if envelope.batch_hash() != self.batch_hash {
+            return Err(ChunkingError::OtherBatch(envelope.batch_hash()));
+        }
+        match envelope {
+            ChunkEnvelope::Manifest(manifest) => {
+                if let Some(known) = &self.manifest {
+                    return if *known == manifest {
+                        Ok(())
+                    } else {
+                        Err(ChunkingError::ConflictingManifest)
+                    };
+                }
+                if manifest.chunk_hashes.is_empty()
+                    || manifest.chunk_hashes.len() > MAX_CHUNKS
+                    || manifest.total_len > MAX_CHUNKED_BATCH_SIZE as u64
+                {
+                    return Err(ChunkingError::TooLarge);
+                }
+                for (index, data) in &self.chunks {
+                    check_chunk(&manifest, *index, data)?;
+                }
+                self.manifest = Some(manifest);
+            }
+            ChunkEnvelope::Chunk(chunk) => {
+                if let Some(manifest) = &self.manifest {
+                    check_chunk(manifest, chunk.index, &chunk.data)?;
+                } else if chunk.index as usize >= MAX_CHUNKS {
+                    return Err(ChunkingError::InvalidChunk(chunk.index));
+                }
+                if let Some(known) = self.chunks.get(&chunk.index) {
+                    return if *known == chunk.data {
+                        Ok(())
+                    } else {
+                        Err(ChunkingError::InvalidChunk(chunk.index))
+                    };
+                }
+                let buffered: usize = self.chunks.values().map(Vec::len).sum();
+                if buffered + chunk.data.len() > MAX_CHUNKED_BATCH_SIZE {
+                    return Err(ChunkingError::TooLarge);
+                }
+                self.chunks.insert(chunk.index, chunk.data);
+            }
+        }
+        Ok(())
+    }
+
+    /// The number of parts which are still missing, counting the manifest if it wasn't received.
+    pub fn missing(&self) -> usize {
+        match &self.manifest {
+            Some(manifest) => manifest.chunk_hashes.len() - self.chunks.len(),
+            None => 1,
+        }
+    }
+
+    /// Returns `true` once the manifest and all the chunks were received.
+    pub fn is_complete(&self) -> bool {
+        self.missing() == 0
+    }
+
+    /// Reassembles the serialized batch, after checking that it matches its manifest.
+    pub fn assemble(self) -> Result<Vec<u8>, ChunkingError> {
+        let missing = self.missing();
+        let Some(manifest) = self.manifest.filter(|_| missing == 0) else {
+            return Err(ChunkingError::Incomplete(missing));
+        };
+        let batch: Vec<u8> = self.chunks.into_values().flatten().collect();
+        let batch_hash: [u8; 32] = Sha256::digest(&batch).into();
+        if batch.len() as u64 != manifest.total_len || batch_hash != self.batch_hash {
+            return Err(ChunkingError::HashMismatch);
+        }
+        Ok(batch)
+    }
+}
+
+fn check_chunk(manifest: &ChunkManifest, index: u32, data: &[u8]) -> Result<(), ChunkingError> {
+    let expected_hash = manifest
+        .chunk_hashes
+        .get(index as usize)
+        .ok_or(ChunkingError::InvalidChunk(index))?;
+    let hash: [u8; 32] = Sha256::digest(data).into();
+    if hash != *expected_hash {
+        return Err(ChunkingError::InvalidChunk(index));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reassemble(blobs: impl IntoIterator<Item = Vec<u8>>) -> Result<Vec<u8>, ChunkingError> {
+        let mut pending: Option<PendingBatch> = None;
+        for blob in blobs {
+            let envelope = decode_chunk(&blob)?;
+            pending
+                .get_or_insert_with(|| PendingBatch::new(envelope.batch_hash()))
+                .insert(envelope)?;
+        }
+        pending.expect("No blob was given").assemble()
+    }
+
+    #[test]
+    fn test_chunked_blob_roundtrip() {
+        let batch: Vec<u8> = (0..1000u32).map(|i| i as u8).collect();
+        assert_eq!(vec![batch.clone()], split_blob(&batch, 1000).unwrap());
+
+        let blobs = split_blob(&batch, 300).unwrap();
+        assert!(blobs
+            .iter()
+            .all(|blob| blob.len() <= 300 && is_chunked(blob)));
+        assert_eq!(5, blobs.len());
+        assert_eq!(batch, reassemble(blobs.clone()).unwrap());
+
+        // The parts can be received in any order, and more than once
+        let mut shuffled = blobs.clone();
+        shuffled.reverse();
+        shuffled.push(blobs[2].clone());
+        assert_eq!(batch, reassemble(shuffled).unwrap());
+    }
+
+    #[test]
+    fn test_incomplete_batch_is_rejected() {
+        let batch = vec![7u8; 1000];
+        let mut blobs = split_blob(&batch, 300).unwrap();
+        blobs.remove(3);
+        assert_eq!(Err(ChunkingError::Incomplete(1)), reassemble(blobs));
+    }
+
+    #[test]
+    fn test_tampered_chunk_is_rejected() {
+        let batch = vec![7u8; 1000];
+        let blobs = split_blob(&batch, 300).unwrap();
+        let ChunkEnvelope::Chunk(mut chunk) = decode_chunk(&blobs[1]).unwrap() else {
+            panic!("Expected a chunk");
+        };
+        chunk.data[0] ^= 1;
+
+        let mut pending = PendingBatch::new(chunk.batch_hash);
+        pending
+            .insert(decode_chunk(&blobs[0]).unwrap())
+            .expect("Manifest is valid");
+        assert_eq!(
+            Err(ChunkingError::InvalidChunk(0)),
+            pending.insert(ChunkEnvelope::Chunk(chunk))
+        );
+        assert!(split_blob(&batch, CHUNK_OVERHEAD).is_err());
+    }
+}
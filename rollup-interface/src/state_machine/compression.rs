@@ -0,0 +1,93 @@
+//! Defines the envelope of the compressed batches posted on the DA layer.
+//!
+//! A compressed batch is the [`COMPRESSED_BLOB_MAGIC`] prefix followed by a zstd frame containing
+//! the serialized batch. Blobs without the prefix are read as is, so compression is transparent
+//! to the state transition function.
+//!
+//! The prefix can't be confused with the start of an uncompressed batch: read as the length
+//! of the borsh encoded list of transactions, it would claim more than a billion of them.
+use std::io::Read;
+
+use crate::maybestd::borrow::Cow;
+use crate::maybestd::vec::Vec;
+
+/// The prefix of the blobs containing a compressed batch.
+pub const COMPRESSED_BLOB_MAGIC: [u8; 4] = [0xff, b'S', b'O', b'V'];
+
+/// The maximum size of a decompressed batch. Larger batches are rejected, so a small blob
+/// can't make the verifier allocate an unbounded amount of memory.
+pub const MAX_DECOMPRESSED_BLOB_SIZE: usize = 64 * 1024 * 1024;
+
+/// An error returned when a blob can't be decompressed.
+#[derive(Debug, thiserror::Error)]
+pub enum DecompressionError {
+    /// The compressed data is not a valid zstd frame.
+    #[error("invalid compressed blob: {0}")]
+    InvalidFrame(String),
+    /// The decompressed batch is larger than [`MAX_DECOMPRESSED_BLOB_SIZE`].
+    #[error("decompressed blob exceeds the maximum size of {MAX_DECOMPRESSED_BLOB_SIZE} bytes")]
+    TooLarge,
+}
+
+/// Returns `true` if the blob contains a compressed batch.
+pub fn is_compressed(blob: &[u8]) -> bool {
+    blob.starts_with(&COMPRESSED_BLOB_MAGIC)
+}
+
+/// Returns the serialized batch contained in the blob, decompressing it if needed.
+pub fn decompress_blob(blob: &[u8]) -> Result<Cow<'_, [u8]>, DecompressionError> {
+    if !is_compressed(blob) {
+        return Ok(Cow::Borrowed(blob));
+    }
+
+    let mut frame = &blob[COMPRESSED_BLOB_MAGIC.len()..];
+    let decoder = ruzstd::StreamingDecoder::new(&mut frame)
+        .map_err(|e| DecompressionError::InvalidFrame(e.to_string()))?;
+    let mut decompressed = Vec::new();
+    decoder
+        // Reading one more byte than allowed tells oversized batches apart
+        .take(MAX_DECOMPRESSED_BLOB_SIZE as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| DecompressionError::InvalidFrame(e.to_string()))?;
+    if decompressed.len() > MAX_DECOMPRESSED_BLOB_SIZE {
+        return Err(DecompressionError::TooLarge);
+    }
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Compresses a serialized batch with the given zstd compression level,
+/// and wraps it in the compressed blob envelope.
+#[cfg(feature = "native")]
+pub fn compress_blob(batch: &[u8], level: i32) -> std::io::Result<Vec<u8>> {
+    let mut blob = COMPRESSED_BLOB_MAGIC.to_vec();
+    zstd::stream::copy_encode(batch, &mut blob, level)?;
+    Ok(blob)
+}
+
+#[cfg(all(test, feature = "native"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compressed_blob_roundtrip() {
+        let batch = borsh::to_vec(&vec![vec![7u8; 1000], vec![8u8; 1000]]).unwrap();
+        let blob = compress_blob(&batch, 3).unwrap();
+        assert!(is_compressed(&blob));
+        assert!(blob.len() < batch.len());
+        assert_eq!(batch, decompress_blob(&blob).unwrap().as_ref());
+    }
+
+    #[test]
+    fn test_uncompressed_blob_is_read_as_is() {
+        let batch = borsh::to_vec(&vec![vec![1u8, 2, 3]]).unwrap();
+        assert!(!is_compressed(&batch));
+        assert!(matches!(decompress_blob(&batch), Ok(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn test_invalid_compressed_blob() {
+        let mut blob = COMPRESSED_BLOB_MAGIC.to_vec();
+        blob.extend_from_slice(&[1, 2, 3, 4]);
+        assert!(decompress_blob(&blob).is_err());
+    }
+}
@@ -6,6 +6,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+use crate::maybestd::vec::Vec;
 use crate::zk::ValidityCondition;
 use crate::BasicAddress;
 
@@ -38,6 +39,33 @@ pub trait DaSpec: 'static + Debug + PartialEq + Eq + Clone {
     /// The parameters of the rollup which are baked into the state-transition function.
     /// For example, this could include the namespace of the rollup on Celestia.
     type ChainParams: Send + Sync;
+
+    /// Verifies a proof that a native transaction of the DA layer locks funds in the bridge
+    /// identified by `bridge_script`, and returns the deposit it makes. The block the
+    /// transaction is included in is only identified by its hash, which the caller has to check
+    /// against the chain of DA blocks processed by the rollup.
+    ///
+    /// DA layers without native deposits reject every proof.
+    fn verify_deposit(
+        bridge_script: &[u8],
+        proof: &[u8],
+    ) -> Result<DaDeposit<Self::SlotHash>, anyhow::Error> {
+        let _ = (bridge_script, proof);
+        anyhow::bail!("The DA layer doesn't support deposits")
+    }
+}
+
+/// A deposit to a rollup bridge, made with a native transaction of the DA layer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaDeposit<H> {
+    /// The hash of the DA block including the deposit transaction.
+    pub block_hash: H,
+    /// Uniquely identifies the deposit on the DA layer, for example the outpoint of a Bitcoin output.
+    pub id: [u8; 32],
+    /// The amount locked in the bridge, in the smallest unit of the DA layer's native token.
+    pub amount: u64,
+    /// The raw rollup address credited with the deposit, which the DA transaction commits to.
+    pub recipient: Vec<u8>,
 }
 
 /// A `DaVerifier` implements the logic required to create a zk proof that some data
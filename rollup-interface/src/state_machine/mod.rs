@@ -1,7 +1,12 @@
 //! Defines types, traits, and helpers that are used by the core state-machine of the rollup.
 //! Items in this module must be fully deterministic, since they are expected to be executed inside of zkVMs.
+#[cfg(feature = "std")]
+pub mod chunking;
+#[cfg(feature = "std")]
+pub mod compression;
 pub mod crypto;
 pub mod da;
+pub mod soft_confirmation;
 pub mod stf;
 pub mod zk;
 
@@ -0,0 +1,65 @@
+//! Defines the types used by sequencers to give out soft confirmations.
+//!
+//! A soft confirmation is a batch that the sequencer signs and serves to full nodes
+//! before it lands on the DA layer. Full nodes execute it right away and later reconcile it
+//! against the batches that actually appear on DA.
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::maybestd::vec::Vec;
+
+/// The contents of a soft confirmation batch, as they are signed by the sequencer.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct UnsignedSoftConfirmationBatch {
+    /// The height of the DA block the sequencer built this batch on top of
+    pub da_slot_height: u64,
+    /// The hash of the DA block the sequencer built this batch on top of
+    pub da_slot_hash: [u8; 32],
+    /// The raw transactions of the batch, in the order they are going to be executed
+    pub txs: Vec<Vec<u8>>,
+}
+
+/// A soft confirmation batch signed by the sequencer.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct SignedSoftConfirmationBatch {
+    /// The hash of the borsh encoded [`UnsignedSoftConfirmationBatch`]
+    pub hash: [u8; 32],
+    /// The height of the DA block the sequencer built this batch on top of
+    pub da_slot_height: u64,
+    /// The hash of the DA block the sequencer built this batch on top of
+    pub da_slot_hash: [u8; 32],
+    /// The raw transactions of the batch, in the order they are going to be executed
+    pub txs: Vec<Vec<u8>>,
+    /// The borsh encoded signature of the sequencer over the borsh encoded [`UnsignedSoftConfirmationBatch`]
+    pub signature: Vec<u8>,
+    /// The borsh encoded public key of the sequencer
+    pub pub_key: Vec<u8>,
+}
+
+impl SignedSoftConfirmationBatch {
+    /// Creates a signed batch from its contents, their hash and the sequencer signature.
+    pub fn new(
+        batch: UnsignedSoftConfirmationBatch,
+        hash: [u8; 32],
+        signature: Vec<u8>,
+        pub_key: Vec<u8>,
+    ) -> Self {
+        Self {
+            hash,
+            da_slot_height: batch.da_slot_height,
+            da_slot_hash: batch.da_slot_hash,
+            txs: batch.txs,
+            signature,
+            pub_key,
+        }
+    }
+
+    /// Returns the signed contents of the batch.
+    pub fn unsigned(&self) -> UnsignedSoftConfirmationBatch {
+        UnsignedSoftConfirmationBatch {
+            da_slot_height: self.da_slot_height,
+            da_slot_hash: self.da_slot_hash,
+            txs: self.txs.clone(),
+        }
+    }
+}
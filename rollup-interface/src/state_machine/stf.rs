@@ -8,7 +8,9 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::da::DaSpec;
+use crate::maybestd::string::String;
 use crate::maybestd::vec::Vec;
+use crate::soft_confirmation::SignedSoftConfirmationBatch;
 use crate::zk::{ValidityCondition, Zkvm};
 
 #[cfg(any(all(test, feature = "sha2"), feature = "fuzzing"))]
@@ -50,6 +52,9 @@ pub struct TransactionReceipt<R> {
     /// The canonically serialized body of the transaction, if it should be persisted
     /// in the database
     pub body_to_save: Option<Vec<u8>>,
+    /// The address of the account which sent the transaction, if it's known.
+    /// The ledger can index transactions by their sender.
+    pub sender: Option<Vec<u8>>,
     /// The events output by this transaction
     pub events: Vec<Event>,
     /// Any additional structured data to be saved in the database and served over RPC
@@ -74,6 +79,10 @@ pub struct BatchReceipt<BatchReceiptContents, TxReceiptContents> {
     pub inner: BatchReceiptContents,
 }
 
+/// The writes of a slot to the provable state, in the order they were made: the raw keys,
+/// with the value they were set to, or [`None`] if they were deleted.
+pub type StateDiff = Vec<(Vec<u8>, Option<Vec<u8>>)>;
+
 /// Result of applying a slot to current state
 /// Where:
 ///  - S - generic for state root
@@ -89,6 +98,8 @@ pub struct SlotResult<S, Cs, B, T, W> {
     pub batch_receipts: Vec<BatchReceipt<B, T>>,
     /// Witness after applying the whole block
     pub witness: W,
+    /// The writes of the slot to the provable state
+    pub state_diff: StateDiff,
 }
 
 // TODO(@preston-evans98): update spec with simplified API
@@ -164,6 +175,37 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
     >
     where
         I: IntoIterator<Item = &'a mut Da::BlobTransaction>;
+
+    /// Called for every soft confirmation batch received from the sequencer, before the batch
+    /// lands on the DA layer.
+    ///
+    /// The batch is executed on top of `pre_state` in the context of the DA block it
+    /// references, which is given by `slot_header`. Batches with an invalid signature are not executed
+    /// and produce no transaction receipts.
+    ///
+    /// The resulting state is speculative: full nodes replace it with the result of
+    /// [`StateTransitionFunction::apply_slot`] once the batch is confirmed on DA.
+    #[allow(clippy::type_complexity)]
+    fn apply_soft_batch(
+        &self,
+        pre_state_root: &Self::StateRoot,
+        pre_state: Self::PreState,
+        witness: Self::Witness,
+        slot_header: &Da::BlockHeader,
+        validity_condition: &Da::ValidityCondition,
+        soft_batch: &mut SignedSoftConfirmationBatch,
+    ) -> SlotResult<
+        Self::StateRoot,
+        Self::ChangeSet,
+        Self::BatchReceiptContents,
+        Self::TxReceiptContents,
+        Self::Witness,
+    >;
+
+    /// Checks that a soft confirmation batch matches its hash and is signed by the public key
+    /// it carries, without executing it. Full nodes use it to validate the batches gossiped by
+    /// their peers before relaying them.
+    fn verify_soft_batch(soft_batch: &SignedSoftConfirmationBatch) -> Result<(), anyhow::Error>;
 }
 
 /// A key-value pair representing a change to the rollup state
@@ -172,6 +214,9 @@ pub trait StateTransitionFunction<Vm: Zkvm, Da: DaSpec> {
 pub struct Event {
     key: EventKey,
     value: EventValue,
+    /// The name of the module which emitted the event, if it is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    module: Option<String>,
 }
 
 impl Event {
@@ -180,6 +225,15 @@ impl Event {
         Self {
             key: EventKey(key.as_bytes().to_vec()),
             value: EventValue(value.as_bytes().to_vec()),
+            module: None,
+        }
+    }
+
+    /// Create a new event with the given key and value, emitted by the module with the given name
+    pub fn with_module(module: &str, key: &str, value: &str) -> Self {
+        Self {
+            module: Some(String::from(module)),
+            ..Self::new(key, value)
         }
     }
 
@@ -192,6 +246,11 @@ impl Event {
     pub fn value(&self) -> &EventValue {
         &self.value
     }
+
+    /// Get the name of the module which emitted the event, if it is known
+    pub fn module(&self) -> Option<&str> {
+        self.module.as_deref()
+    }
 }
 
 /// The key of an event. This is a wrapper around a `Vec<u8>`.
@@ -128,12 +128,13 @@ impl<R: proptest::arbitrary::Arbitrary + 'static> proptest::arbitrary::Arbitrary
             (
                 any::<[u8; 32]>(),
                 tx_body_strategy,
+                any::<Option<[u8; 32]>>(),
                 proptest::collection::vec(any::<Event>(), 0..args.max_events),
                 any::<R>(),
                 proptest::collection::vec(any::<u64>(), 0..args.gas_unit_dimensions),
             )
                 .prop_map(
-                    move |(tx_hash, body_to_save, events, receipt, mut gas_used)| {
+                    move |(tx_hash, body_to_save, sender, events, receipt, mut gas_used)| {
                         let tx_hash = match (args.hasher.as_ref(), body_to_save.as_ref()) {
                             (Some(hasher), Some(body)) => hasher.hash(body),
                             _ => tx_hash,
@@ -142,6 +143,7 @@ impl<R: proptest::arbitrary::Arbitrary + 'static> proptest::arbitrary::Arbitrary
                         Self {
                             tx_hash,
                             body_to_save,
+                            sender: sender.map(|sender| sender.to_vec()),
                             events,
                             receipt,
                             gas_used,
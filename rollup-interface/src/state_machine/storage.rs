@@ -22,6 +22,11 @@ pub trait HierarchicalStorageManager<Da: DaSpec> {
     /// Won't be saved if somehow 'saved'
     fn create_finalized_storage(&mut self) -> anyhow::Result<Self::NativeStorage>;
 
+    /// Creates a storage reading the finalized state, for the queries of the RPC.
+    /// Unlike the storage created by [`Self::create_finalized_storage`], its reads never wait
+    /// for the snapshots being saved or finalized, and it must never be written to.
+    fn create_read_only_storage(&self) -> anyhow::Result<Self::NativeStorage>;
+
     /// Adds [`Self::NativeChangeSet`] to the storage.
     /// [`DaSpec::BlockHeader`] must be provided for efficient consistency checking.
     fn save_change_set(
@@ -32,4 +37,17 @@ pub trait HierarchicalStorageManager<Da: DaSpec> {
 
     /// Finalizes snapshot on given block header
     fn finalize(&mut self, block_header: &Da::BlockHeader) -> anyhow::Result<()>;
+
+    /// Returns the version of the finalized storage, which increases with every finalized snapshot.
+    fn finalized_version(&self) -> anyhow::Result<u64>;
+
+    /// Discards the finalized data written after the given version.
+    /// It's used on startup to roll back a finalization which was interrupted, for example by a crash,
+    /// and to revert finalized blocks after a reorganization of the DA layer,
+    /// so it must not be called while there are non-finalized snapshots.
+    fn rollback_finalized(&mut self, version: u64) -> anyhow::Result<()>;
+
+    /// Discards all the non-finalized snapshots, on every fork.
+    /// The storage created afterwards only sees the finalized data.
+    fn discard_non_finalized(&mut self) -> anyhow::Result<()>;
 }
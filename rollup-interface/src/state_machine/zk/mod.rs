@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use crate::da::DaSpec;
 
 /// The ZK proof generated by the [`ZkvmHost::run`] method.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub enum Proof {
     /// Only public input was generated.
     PublicInput(Vec<u8>),
@@ -78,6 +78,13 @@ pub trait Zkvm: Send + Sync {
         serialized_proof: &[u8],
         code_commitment: &Self::CodeCommitment,
     ) -> Result<StateTransition<Da, Root>, Self::Error>;
+
+    /// Same as [`verify_and_extract_output`](Zkvm::verify_and_extract_output), for the proofs
+    /// of the aggregation program, which cover a range of slots.
+    fn verify_and_extract_aggregated_output<Da: DaSpec, Root: Serialize + DeserializeOwned>(
+        serialized_proof: &[u8],
+        code_commitment: &Self::CodeCommitment,
+    ) -> Result<AggregatedStateTransition<Da, Root, Self::CodeCommitment>, Self::Error>;
 }
 
 /// A trait which is accessible from within a zkVM program.
@@ -122,6 +129,8 @@ pub struct StateTransition<Da: DaSpec, Root> {
     pub final_state_root: Root,
     /// The slot hash of the state transition
     pub slot_hash: Da::SlotHash,
+    /// The hash of the slot preceding the one of the state transition on the DA layer
+    pub prev_slot_hash: Da::SlotHash,
 
     /// An additional validity condition for the state transition which needs
     /// to be checked outside of the zkVM circuit. This typically corresponds to
@@ -129,6 +138,73 @@ pub struct StateTransition<Da: DaSpec, Root> {
     pub validity_condition: Da::ValidityCondition,
 }
 
+/// The public output of an aggregated proof, which proves the state transitions over a range
+/// of consecutive slots on the DA layer at once.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub struct AggregatedStateTransition<Da: DaSpec, Root, CodeCommitment> {
+    /// The code commitment of the program whose slot proofs were aggregated
+    pub slot_code_commitment: CodeCommitment,
+    /// The state of the rollup before the first slot of the range
+    pub initial_state_root: Root,
+    /// The state of the rollup after the last slot of the range
+    pub final_state_root: Root,
+    /// The hash of the first slot of the range
+    pub initial_slot_hash: Da::SlotHash,
+    /// The hash of the last slot of the range
+    pub final_slot_hash: Da::SlotHash,
+    /// The validity conditions of all the slots of the range, combined into one
+    pub validity_condition: Da::ValidityCondition,
+}
+
+impl<Da: DaSpec, Root: PartialEq, CodeCommitment>
+    AggregatedStateTransition<Da, Root, CodeCommitment>
+{
+    /// Aggregates the state transitions of consecutive slots, given in order, which were proven
+    /// by the program with the given code commitment.
+    ///
+    /// Returns an error if there aren't any, if a slot doesn't follow the previous one
+    /// on the DA layer, if a transition doesn't start from the state the previous one ended in,
+    /// or if their validity conditions can't be combined.
+    pub fn aggregate<H: Digest>(
+        slot_code_commitment: CodeCommitment,
+        transitions: impl IntoIterator<Item = StateTransition<Da, Root>>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut transitions = transitions.into_iter();
+        let first = transitions
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No state transitions to aggregate"))?;
+
+        let mut aggregated = Self {
+            slot_code_commitment,
+            initial_state_root: first.initial_state_root,
+            final_state_root: first.final_state_root,
+            initial_slot_hash: first.slot_hash.clone(),
+            final_slot_hash: first.slot_hash,
+            validity_condition: first.validity_condition,
+        };
+        for transition in transitions {
+            anyhow::ensure!(
+                transition.prev_slot_hash == aggregated.final_slot_hash,
+                "The slot {:?} doesn't follow the previous slot {:?}",
+                transition.slot_hash,
+                aggregated.final_slot_hash
+            );
+            anyhow::ensure!(
+                transition.initial_state_root == aggregated.final_state_root,
+                "The state transition of slot {:?} doesn't start from the state of the previous slot",
+                transition.slot_hash
+            );
+            aggregated.validity_condition = aggregated
+                .validity_condition
+                .combine::<H>(transition.validity_condition)
+                .map_err(Into::into)?;
+            aggregated.final_state_root = transition.final_state_root;
+            aggregated.final_slot_hash = transition.slot_hash;
+        }
+        Ok(aggregated)
+    }
+}
+
 /// This trait expresses that a type can check a validity condition.
 pub trait ValidityConditionChecker<Condition: ValidityCondition>:
     BorshDeserialize + BorshSerialize + Debug
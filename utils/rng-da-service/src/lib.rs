@@ -232,6 +232,8 @@ pub fn generate_create_token_payload(start_nonce: u64) -> Vec<u8> {
             initial_balance: 100000000,
             minter_address,
             authorized_minters: vec![minter_address],
+            authority: None,
+            max_supply: None,
         };
     let enc_msg =
         <Runtime<DefaultContext, RngDaSpec> as EncodeCall<Bank<DefaultContext>>>::encode_call(msg);
@@ -29,3 +29,25 @@ pub fn print_cycle_count() {
     let serialized = (risc0_zkvm::guest::env::get_cycle_count() as u64).to_le_bytes();
     risc0_zkvm::guest::env::send_recv_slice::<u8, u8>(metrics_syscall_name, &serialized);
 }
+
+/// Sends the number of cycles spent under `label` to the host, which aggregates it in its metrics.
+/// The encoding matches the one of the `cycle_tracker` macro.
+pub fn report_cycle_count(label: &str, cycles: u64) {
+    let mut serialized = Vec::with_capacity(label.len() + 1 + std::mem::size_of::<u64>());
+    serialized.extend(label.as_bytes());
+    serialized.push(0);
+    serialized.extend(cycles.to_ne_bytes());
+    risc0_zkvm::guest::env::send_recv_slice::<u8, u8>(get_syscall_name(), &serialized);
+}
+
+/// Runs `f` and reports the number of cycles it used under `label`.
+///
+/// Unlike the `cycle_tracker` macro, the label can be computed at runtime, e.g. to
+/// attribute cycles to the module being called.
+pub fn track_cycles<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let before = risc0_zkvm::guest::env::get_cycle_count();
+    let result = f();
+    let after = risc0_zkvm::guest::env::get_cycle_count();
+    report_cycle_count(label, (after - before) as u64);
+    result
+}